@@ -0,0 +1,245 @@
+// GPU Spatial Grid Binning and Collision Pair Generation
+// Mirrors DeterministicSpatialGrid (counting sort + prefix sums) so the pair list
+// matches detect_collisions_canonical once sorted by (index_a, index_b).
+//
+// Pass order:
+//   1. count_cells        - bin each cell into its grid cell (atomic counts)
+//   2. scan_blocks        - per-workgroup exclusive scan of grid counts
+//   3. scan_block_sums    - exclusive scan of per-workgroup totals (single workgroup)
+//   4. add_block_offsets  - add scanned block totals back into grid offsets
+//   5. scatter_cells      - write cell indices into their grid cell ranges
+//   6. find_pairs         - emit overlapping pairs (home cell + 13 forward neighbors)
+
+struct GridParams {
+    cell_count: u32,
+    grid_dim: u32,
+    grid_total: u32,
+    max_pairs: u32,
+    world_size: f32,
+    cell_size: f32,
+    block_count: u32,
+    _pad: u32,
+}
+
+// Collision pair (indices only - overlap and normal are recomputed on CPU)
+struct CollisionPair {
+    index_a: u32,
+    index_b: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: GridParams;
+// position.xyz = position, position.w = radius
+@group(0) @binding(1) var<storage, read> positions: array<vec4<f32>>;
+// grid_counts[grid_total] doubles as the emitted pair counter (keeps us within
+// the default limit of 8 storage buffers per stage)
+@group(0) @binding(2) var<storage, read_write> grid_counts: array<atomic<u32>>;
+@group(0) @binding(3) var<storage, read_write> grid_offsets: array<u32>;
+@group(0) @binding(4) var<storage, read_write> grid_cursors: array<atomic<u32>>;
+@group(0) @binding(5) var<storage, read_write> block_sums: array<u32>;
+@group(0) @binding(6) var<storage, read_write> cell_grid_indices: array<u32>;
+@group(0) @binding(7) var<storage, read_write> sorted_indices: array<u32>;
+@group(0) @binding(8) var<storage, read_write> pairs: array<CollisionPair>;
+
+const SCAN_WORKGROUP_SIZE: u32 = 256u;
+
+var<workgroup> scan_scratch: array<u32, 256>;
+
+// Same clamping as DeterministicSpatialGrid::world_to_grid
+fn world_to_grid(position: vec3<f32>) -> vec3<i32> {
+    let offset_pos = position + vec3<f32>(params.world_size / 2.0);
+    let grid_pos = offset_pos / params.cell_size;
+    let max_coord = i32(params.grid_dim) - 1;
+    return vec3<i32>(
+        clamp(i32(grid_pos.x), 0, max_coord),
+        clamp(i32(grid_pos.y), 0, max_coord),
+        clamp(i32(grid_pos.z), 0, max_coord)
+    );
+}
+
+// Matches the active-cell ordering (x outer, y, z inner)
+fn active_cell_index(coord: vec3<i32>) -> u32 {
+    let g = params.grid_dim;
+    return (u32(coord.x) * g + u32(coord.y)) * g + u32(coord.z);
+}
+
+fn active_cell_coord(index: u32) -> vec3<i32> {
+    let g = params.grid_dim;
+    return vec3<i32>(i32(index / (g * g)), i32((index / g) % g), i32(index % g));
+}
+
+fn is_valid_grid_coord(coord: vec3<i32>) -> bool {
+    let max_coord = i32(params.grid_dim) - 1;
+    return coord.x >= 0 && coord.x <= max_coord &&
+           coord.y >= 0 && coord.y <= max_coord &&
+           coord.z >= 0 && coord.z <= max_coord;
+}
+
+@compute @workgroup_size(64)
+fn count_cells(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let cell_idx = global_id.x;
+    if cell_idx >= params.cell_count {
+        return;
+    }
+
+    let grid_idx = active_cell_index(world_to_grid(positions[cell_idx].xyz));
+    cell_grid_indices[cell_idx] = grid_idx;
+    atomicAdd(&grid_counts[grid_idx], 1u);
+}
+
+// Inclusive Hillis-Steele scan of scan_scratch (all threads must call)
+fn workgroup_inclusive_scan(local_idx: u32) {
+    var stride = 1u;
+    loop {
+        if stride >= SCAN_WORKGROUP_SIZE {
+            break;
+        }
+        workgroupBarrier();
+        var addend = 0u;
+        if local_idx >= stride {
+            addend = scan_scratch[local_idx - stride];
+        }
+        workgroupBarrier();
+        scan_scratch[local_idx] += addend;
+        stride = stride * 2u;
+    }
+    workgroupBarrier();
+}
+
+@compute @workgroup_size(256)
+fn scan_blocks(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    let grid_idx = global_id.x;
+    let local_idx = local_id.x;
+
+    var count = 0u;
+    if grid_idx < params.grid_total {
+        count = atomicLoad(&grid_counts[grid_idx]);
+    }
+    scan_scratch[local_idx] = count;
+
+    workgroup_inclusive_scan(local_idx);
+
+    if grid_idx < params.grid_total {
+        grid_offsets[grid_idx] = scan_scratch[local_idx] - count;
+    }
+    if local_idx == SCAN_WORKGROUP_SIZE - 1u {
+        block_sums[workgroup_id.x] = scan_scratch[local_idx];
+    }
+}
+
+// Single workgroup: each thread serially scans a contiguous run of block totals,
+// then the run totals are scanned across the workgroup.
+@compute @workgroup_size(256)
+fn scan_block_sums(@builtin(local_invocation_id) local_id: vec3<u32>) {
+    let local_idx = local_id.x;
+    let per_thread = (params.block_count + SCAN_WORKGROUP_SIZE - 1u) / SCAN_WORKGROUP_SIZE;
+    let begin = min(local_idx * per_thread, params.block_count);
+    let end = min(begin + per_thread, params.block_count);
+
+    var run_total = 0u;
+    for (var i = begin; i < end; i++) {
+        let value = block_sums[i];
+        block_sums[i] = run_total;
+        run_total += value;
+    }
+    scan_scratch[local_idx] = run_total;
+
+    workgroup_inclusive_scan(local_idx);
+
+    let run_offset = scan_scratch[local_idx] - run_total;
+    for (var i = begin; i < end; i++) {
+        block_sums[i] += run_offset;
+    }
+}
+
+@compute @workgroup_size(256)
+fn add_block_offsets(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    let grid_idx = global_id.x;
+    if grid_idx >= params.grid_total {
+        return;
+    }
+    grid_offsets[grid_idx] += block_sums[workgroup_id.x];
+}
+
+@compute @workgroup_size(64)
+fn scatter_cells(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let cell_idx = global_id.x;
+    if cell_idx >= params.cell_count {
+        return;
+    }
+
+    let grid_idx = cell_grid_indices[cell_idx];
+    let slot = atomicAdd(&grid_cursors[grid_idx], 1u);
+    sorted_indices[grid_offsets[grid_idx] + slot] = cell_idx;
+}
+
+fn cells_overlap(a: u32, b: u32) -> bool {
+    let pa = positions[a];
+    let pb = positions[b];
+    return length(pb.xyz - pa.xyz) < pa.w + pb.w;
+}
+
+fn emit_pair(a: u32, b: u32) {
+    let slot = atomicAdd(&grid_counts[params.grid_total], 1u);
+    if slot < params.max_pairs {
+        pairs[slot] = CollisionPair(a, b);
+    }
+}
+
+// Forward neighbors for half-space optimization (same table as the CPU path)
+const FORWARD_NEIGHBORS: array<vec3<i32>, 13> = array<vec3<i32>, 13>(
+    vec3<i32>(1, 0, 0),
+    vec3<i32>(-1, 1, 0), vec3<i32>(0, 1, 0), vec3<i32>(1, 1, 0),
+    vec3<i32>(-1, -1, 1), vec3<i32>(0, -1, 1), vec3<i32>(1, -1, 1),
+    vec3<i32>(-1, 0, 1), vec3<i32>(0, 0, 1), vec3<i32>(1, 0, 1),
+    vec3<i32>(-1, 1, 1), vec3<i32>(0, 1, 1), vec3<i32>(1, 1, 1),
+);
+
+// Each thread owns one cell and emits (cell, other) for:
+//   - other cells in the same grid cell with a higher index
+//   - all cells in the 13 forward neighbor grid cells
+// This reproduces the CPU pair set exactly (before the same-organism filter,
+// which needs the adhesion graph and is applied on CPU after readback).
+@compute @workgroup_size(64)
+fn find_pairs(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let cell_idx = global_id.x;
+    if cell_idx >= params.cell_count {
+        return;
+    }
+
+    let home_idx = cell_grid_indices[cell_idx];
+    let home_coord = active_cell_coord(home_idx);
+
+    let home_start = grid_offsets[home_idx];
+    let home_count = atomicLoad(&grid_counts[home_idx]);
+    for (var k = 0u; k < home_count; k++) {
+        let other_idx = sorted_indices[home_start + k];
+        if other_idx > cell_idx && cells_overlap(cell_idx, other_idx) {
+            emit_pair(cell_idx, other_idx);
+        }
+    }
+
+    var neighbors = FORWARD_NEIGHBORS;
+    for (var n = 0u; n < 13u; n++) {
+        let neighbor_coord = home_coord + neighbors[n];
+        if !is_valid_grid_coord(neighbor_coord) {
+            continue;
+        }
+
+        let neighbor_idx = active_cell_index(neighbor_coord);
+        let start = grid_offsets[neighbor_idx];
+        let count = atomicLoad(&grid_counts[neighbor_idx]);
+        for (var k = 0u; k < count; k++) {
+            let other_idx = sorted_indices[start + k];
+            if cells_overlap(cell_idx, other_idx) {
+                emit_pair(cell_idx, other_idx);
+            }
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::ui::camera::MainCamera;
+use super::{AudioSettings, SoundCategory};
+
+/// Most one-shots playing at once
+pub const MAX_VOICES: usize = 16;
+
+/// Events kept between frames; at high speed many ticks run per frame and the rest would be dropped anyway
+const MAX_QUEUED_EVENTS: usize = 512;
+
+/// A voice still alive after this long is despawned
+///
+/// The longest one-shot is under half a second. Without an output device bevy never
+/// starts (and so never despawns) the players, so this also keeps their count bounded.
+const VOICE_LIFETIME: f32 = 1.0;
+
+/// Camera distance at which a sound plays at half volume
+const HALF_VOLUME_DISTANCE: f32 = 60.0;
+
+/// Cell count at which the ambient loop reaches its full volume
+const AMBIENT_FULL_COUNT: f32 = 20_000.0;
+
+/// A simulation event that may become a sound
+#[derive(Clone, Copy, Debug)]
+pub struct SoundEvent {
+    pub category: SoundCategory,
+    pub position: Vec3,
+    /// Category-specific size of the event: parent mass, bond break speed
+    pub magnitude: f32,
+}
+
+/// Events collected from the simulation ticks since the last frame
+#[derive(Resource, Default)]
+pub struct SoundEventQueue {
+    pub events: Vec<SoundEvent>,
+}
+
+impl SoundEventQueue {
+    fn push(&mut self, event: SoundEvent) {
+        if self.events.len() < MAX_QUEUED_EVENTS {
+            self.events.push(event);
+        }
+    }
+}
+
+/// Voice count for the audio debug overlay
+#[derive(Resource, Default)]
+pub struct AudioStats {
+    /// One-shots currently playing
+    pub voices: usize,
+    /// Events dropped by rate limiting or the voice cap since startup
+    pub suppressed: u64,
+}
+
+/// A playing one-shot
+#[derive(Component)]
+pub struct SoundVoice {
+    /// Camera distance to the event when it started; farther voices are stolen first
+    distance: f32,
+    started: f32,
+}
+
+/// The ambient loop's player
+#[derive(Component)]
+pub(super) struct AmbientLoop;
+
+#[derive(Resource)]
+pub(super) struct SoundAssets {
+    pop: Handle<AudioSource>,
+    snap: Handle<AudioSource>,
+    pulse: Handle<AudioSource>,
+}
+
+impl SoundAssets {
+    fn handle(&self, category: SoundCategory) -> Option<&Handle<AudioSource>> {
+        match category {
+            SoundCategory::Division => Some(&self.pop),
+            SoundCategory::AdhesionBreak => Some(&self.snap),
+            SoundCategory::Death => Some(&self.pulse),
+            SoundCategory::Ambient => None,
+        }
+    }
+}
+
+/// Token bucket: `rate` sounds per second with bursts of up to `capacity`
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TokenBucket {
+    tokens: f32,
+    capacity: f32,
+    rate: f32,
+}
+
+impl TokenBucket {
+    fn new(rate: f32, capacity: f32) -> Self {
+        Self { tokens: capacity, capacity, rate }
+    }
+
+    fn refill(&mut self, dt: f32) {
+        self.tokens = (self.tokens + self.rate * dt).min(self.capacity);
+    }
+
+    fn try_take(&mut self) -> bool {
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Per-category rate limits and the variation counter
+#[derive(Resource)]
+pub struct VoiceLimiter {
+    buckets: [TokenBucket; 3],
+    jitter_counter: u32,
+}
+
+impl Default for VoiceLimiter {
+    fn default() -> Self {
+        Self {
+            buckets: [
+                TokenBucket::new(12.0, 6.0), // Division
+                TokenBucket::new(10.0, 4.0), // Adhesion break
+                TokenBucket::new(6.0, 3.0),  // Death
+            ],
+            jitter_counter: 0,
+        }
+    }
+}
+
+impl VoiceLimiter {
+    fn bucket(&mut self, category: SoundCategory) -> Option<&mut TokenBucket> {
+        self.buckets.get_mut(category as usize)
+    }
+}
+
+/// Which candidates play and which playing voices they replace
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct VoicePlan {
+    /// Candidate indices that get a voice
+    pub play: Vec<usize>,
+    /// Playing voice indices that are stopped to make room
+    pub steal: Vec<usize>,
+}
+
+/// Give voices to the candidates, nearest first, within `max_voices`
+///
+/// `candidates` must be sorted by distance, nearest first. Once every voice is taken a
+/// candidate replaces the farthest playing voice if it is nearer than it.
+pub(crate) fn plan_voices(playing: &[f32], candidates: &[f32], max_voices: usize) -> VoicePlan {
+    let mut plan = VoicePlan::default();
+    let mut free = max_voices.saturating_sub(playing.len());
+    let mut farthest_first: Vec<usize> = (0..playing.len()).collect();
+    farthest_first.sort_by(|&a, &b| playing[b].total_cmp(&playing[a]));
+    let mut stealable = farthest_first.into_iter();
+
+    for (index, &distance) in candidates.iter().enumerate() {
+        if free > 0 {
+            free -= 1;
+            plan.play.push(index);
+            continue;
+        }
+        // Candidates only get farther and victims only get nearer, so the first miss ends it
+        match stealable.next() {
+            Some(victim) if playing[victim] > distance => {
+                plan.steal.push(victim);
+                plan.play.push(index);
+            }
+            _ => break,
+        }
+    }
+    plan
+}
+
+/// Volume and playback speed of a one-shot from its event magnitude, before distance and settings
+///
+/// `jitter` spreads repeated sounds by a few percent so bursts don't sound mechanical.
+pub(crate) fn voice_params(category: SoundCategory, magnitude: f32, jitter: u32) -> (f32, f32) {
+    let (volume, speed) = match category {
+        // Heavier parents give a deeper, fuller pop
+        SoundCategory::Division => {
+            let size = (1.0 + magnitude.max(0.0)).ln();
+            ((0.5 + 0.25 * size).min(1.0), (1.3 - 0.25 * size).clamp(0.6, 1.4))
+        }
+        // Faster recoil gives a louder, sharper snap
+        SoundCategory::AdhesionBreak => {
+            let speed = magnitude.max(0.0);
+            ((0.35 + speed / 15.0).min(1.0), (0.9 + speed / 30.0).clamp(0.8, 1.6))
+        }
+        SoundCategory::Death | SoundCategory::Ambient => (0.8, 1.0),
+    };
+    let hash = jitter.wrapping_mul(0x9E37_79B9).rotate_left(13).wrapping_mul(0x85EB_CA6B);
+    let unit = |bits: u32| (bits & 0xFFFF) as f32 / 32767.5 - 1.0;
+    (volume * (1.0 + 0.1 * unit(hash)), speed * (1.0 + 0.06 * unit(hash >> 16)))
+}
+
+/// Gain for a sound `distance` away from the camera
+pub(crate) fn distance_gain(distance: f32) -> f32 {
+    1.0 / (1.0 + distance.max(0.0) / HALF_VOLUME_DISTANCE)
+}
+
+/// Ambient loop level for a population, rising logarithmically to 1
+pub(crate) fn ambient_intensity(cell_count: usize) -> f32 {
+    ((1.0 + cell_count as f32).ln() / (1.0 + AMBIENT_FULL_COUNT).ln()).min(1.0)
+}
+
+/// Load the embedded sounds and start the (silent) ambient loop
+pub(super) fn setup_audio_feedback(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SoundAssets {
+        pop: asset_server.load("audio/pop.wav"),
+        snap: asset_server.load("audio/snap.wav"),
+        pulse: asset_server.load("audio/pulse.wav"),
+    });
+    commands.spawn((
+        AudioPlayer::new(asset_server.load("audio/ambient.wav")),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+        AmbientLoop,
+    ));
+}
+
+/// Turn the divisions, deaths and broken bonds of the tick that just ran into sound events
+pub(super) fn collect_sound_events(
+    main_state: Res<MainSimState>,
+    settings: Res<AudioSettings>,
+    mut queue: ResMut<SoundEventQueue>,
+    mut last_time: Local<Option<f32>>,
+) {
+    // The event buffers only change when a tick ran (no cells, paused)
+    let time = main_state.simulation_time;
+    if last_time.replace(time) == Some(time) || settings.muted {
+        return;
+    }
+    let state = &main_state.canonical_state;
+
+    for division in &state.division_events_buffer {
+        let (a, b) = (division.child_a_idx, division.child_b_idx);
+        if a < state.cell_count && b < state.cell_count {
+            queue.push(SoundEvent {
+                category: SoundCategory::Division,
+                position: (state.positions[a] + state.positions[b]) * 0.5,
+                magnitude: state.masses[a] + state.masses[b],
+            });
+        }
+    }
+    for &position in &state.removed_cell_positions_buffer {
+        queue.push(SoundEvent { category: SoundCategory::Death, position, magnitude: 1.0 });
+    }
+    for (&(removed_id, _), &speed) in state.broken_adhesions_buffer.iter().zip(&state.broken_adhesion_speeds_buffer) {
+        let Some(slot) = state.removed_cell_ids_buffer.iter().position(|&id| id == removed_id) else {
+            continue;
+        };
+        queue.push(SoundEvent {
+            category: SoundCategory::AdhesionBreak,
+            position: state.removed_cell_positions_buffer[slot],
+            magnitude: speed,
+        });
+    }
+}
+
+/// Play the queued events within the rate limits and the voice cap
+#[allow(clippy::too_many_arguments)]
+pub(super) fn play_sound_events(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<AudioSettings>,
+    sounds: Option<Res<SoundAssets>>,
+    mut queue: ResMut<SoundEventQueue>,
+    mut limiter: ResMut<VoiceLimiter>,
+    mut stats: ResMut<AudioStats>,
+    voices: Query<(Entity, &SoundVoice)>,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
+) {
+    let now = time.elapsed_secs();
+    for bucket in &mut limiter.buckets {
+        bucket.refill(time.delta_secs());
+    }
+
+    let mut playing = Vec::with_capacity(MAX_VOICES);
+    for (entity, voice) in &voices {
+        if now - voice.started > VOICE_LIFETIME {
+            commands.entity(entity).try_despawn();
+        } else {
+            playing.push((entity, voice.distance));
+        }
+    }
+    stats.voices = playing.len();
+
+    let mut events = std::mem::take(&mut queue.events);
+    let (Some(sounds), Ok(camera)) = (sounds, camera_query.single()) else {
+        return;
+    };
+    if events.is_empty() || settings.muted {
+        return;
+    }
+
+    // Nearest events claim the rate limit tokens and voices first
+    let camera_position = camera.translation();
+    events.sort_by(|a, b| {
+        a.position.distance_squared(camera_position).total_cmp(&b.position.distance_squared(camera_position))
+    });
+    let mut candidates = Vec::new();
+    for event in events {
+        let allowed = settings.category_volume(event.category) > 0.0
+            && limiter.bucket(event.category).is_some_and(TokenBucket::try_take);
+        if allowed {
+            candidates.push((event, event.position.distance(camera_position)));
+        } else {
+            stats.suppressed += 1;
+        }
+    }
+
+    let playing_distances: Vec<f32> = playing.iter().map(|&(_, distance)| distance).collect();
+    let candidate_distances: Vec<f32> = candidates.iter().map(|&(_, distance)| distance).collect();
+    let plan = plan_voices(&playing_distances, &candidate_distances, MAX_VOICES);
+    stats.suppressed += (candidates.len() - plan.play.len()) as u64;
+
+    for &victim in &plan.steal {
+        commands.entity(playing[victim].0).try_despawn();
+    }
+    for &index in &plan.play {
+        let (event, distance) = candidates[index];
+        let Some(handle) = sounds.handle(event.category) else {
+            continue;
+        };
+        limiter.jitter_counter = limiter.jitter_counter.wrapping_add(1);
+        let (volume, speed) = voice_params(event.category, event.magnitude, limiter.jitter_counter);
+        let volume = volume * distance_gain(distance) * settings.category_volume(event.category);
+        commands.spawn((
+            AudioPlayer::new(handle.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)).with_speed(speed),
+            SoundVoice { distance, started: now },
+        ));
+    }
+    stats.voices = playing.len() - plan.steal.len() + plan.play.len();
+}
+
+/// Fade the ambient loop toward the level of the current population
+pub(super) fn update_ambient_loop(
+    time: Res<Time>,
+    settings: Res<AudioSettings>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut sinks: Query<&mut AudioSink, With<AmbientLoop>>,
+    mut level: Local<f32>,
+) {
+    // No sink until the loop has started, and never without an output device
+    let Ok(mut sink) = sinks.single_mut() else {
+        return;
+    };
+    let cell_count = crate::input::pin_tool::active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None)
+        .map_or(0, |(state, _)| state.cell_count);
+    let target = ambient_intensity(cell_count) * settings.category_volume(SoundCategory::Ambient);
+
+    let blend = 1.0 - (-2.0 * time.delta_secs()).exp();
+    *level += (target - *level) * blend;
+    sink.set_volume(Volume::Linear(*level));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_limits_bursts_and_refills() {
+        let mut bucket = TokenBucket::new(10.0, 3.0);
+        let taken = (0..10).filter(|_| bucket.try_take()).count();
+        assert_eq!(taken, 3);
+
+        bucket.refill(0.25);
+        let taken = (0..10).filter(|_| bucket.try_take()).count();
+        assert_eq!(taken, 2);
+
+        // Refilling never exceeds the burst size
+        bucket.refill(100.0);
+        let taken = (0..10).filter(|_| bucket.try_take()).count();
+        assert_eq!(taken, 3);
+    }
+
+    #[test]
+    fn test_plan_voices_fills_free_slots_then_steals_farthest() {
+        let plan = plan_voices(&[10.0, 50.0], &[5.0, 20.0], 4);
+        assert_eq!(plan, VoicePlan { play: vec![0, 1], steal: vec![] });
+
+        // Full: the nearest candidate replaces the farthest voice, the next one the next farthest
+        let plan = plan_voices(&[10.0, 50.0, 30.0], &[5.0, 20.0, 40.0], 3);
+        assert_eq!(plan, VoicePlan { play: vec![0, 1], steal: vec![1, 2] });
+
+        // Farther than everything playing: dropped
+        let plan = plan_voices(&[10.0, 12.0], &[15.0], 2);
+        assert_eq!(plan, VoicePlan::default());
+    }
+
+    #[test]
+    fn test_voice_params_scale_with_magnitude() {
+        let (soft, slow) = voice_params(SoundCategory::AdhesionBreak, 0.5, 0);
+        let (loud, fast) = voice_params(SoundCategory::AdhesionBreak, 8.0, 0);
+        assert!(loud > soft && fast > slow);
+
+        let (_, small) = voice_params(SoundCategory::Division, 1.0, 0);
+        let (_, large) = voice_params(SoundCategory::Division, 20.0, 0);
+        assert!(large < small, "heavier parents pop lower");
+
+        for jitter in 0..100 {
+            let (volume, speed) = voice_params(SoundCategory::Death, 1.0, jitter);
+            assert!((volume - 0.8).abs() <= 0.08 + 1e-6 && (speed - 1.0).abs() <= 0.06 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_ambient_intensity_rises_to_one() {
+        assert_eq!(ambient_intensity(0), 0.0);
+        assert!(ambient_intensity(100) < ambient_intensity(1000));
+        assert_eq!(ambient_intensity(1_000_000), 1.0);
+        assert!(distance_gain(0.0) > distance_gain(HALF_VOLUME_DISTANCE));
+        assert!((distance_gain(HALF_VOLUME_DISTANCE) - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub mod feedback;
+
+pub use feedback::{AudioStats, SoundEvent, SoundEventQueue};
+
+/// Plugin for the event-driven sound effects and the ambient loop
+///
+/// Without bevy's `AudioPlugin` (headless runs, tests) only the settings and the
+/// statistics exist; nothing is collected or played.
+pub struct AudioFeedbackPlugin;
+
+impl Plugin for AudioFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>()
+            .init_resource::<AudioStats>()
+            .init_resource::<SoundEventQueue>();
+
+        if !app.is_plugin_added::<bevy::audio::AudioPlugin>() {
+            return;
+        }
+        app.init_resource::<feedback::VoiceLimiter>()
+            .add_systems(Startup, feedback::setup_audio_feedback)
+            .add_systems(
+                FixedUpdate,
+                feedback::collect_sound_events
+                    .after(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(crate::simulation::cpu_sim::CpuSceneState::Active)),
+            )
+            .add_systems(Update, (
+                feedback::play_sound_events,
+                feedback::update_ambient_loop,
+            ));
+    }
+}
+
+/// Kind of sound, each with its own volume slider
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundCategory {
+    Division,
+    AdhesionBreak,
+    Death,
+    Ambient,
+}
+
+impl SoundCategory {
+    pub const ALL: [SoundCategory; 4] = [
+        SoundCategory::Division,
+        SoundCategory::AdhesionBreak,
+        SoundCategory::Death,
+        SoundCategory::Ambient,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundCategory::Division => "Division",
+            SoundCategory::AdhesionBreak => "Adhesion break",
+            SoundCategory::Death => "Cell death",
+            SoundCategory::Ambient => "Ambient",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Volume settings of the audio feedback (persisted in the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AudioSettings {
+    pub muted: bool,
+    pub master_volume: f32,
+    /// Per-category volume, indexed like `SoundCategory::ALL`
+    pub volumes: [f32; 4],
+    /// Show the voice count in the viewport
+    #[serde(default)]
+    pub debug: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            master_volume: 0.6,
+            volumes: [0.7, 0.6, 0.7, 0.4],
+            debug: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Final linear volume of a category, zero while muted
+    pub fn category_volume(&self, category: SoundCategory) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        self.master_volume * self.volumes[category.index()]
+    }
+
+    pub fn volume_mut(&mut self, category: SoundCategory) -> &mut f32 {
+        &mut self.volumes[category.index()]
+    }
+}
@@ -16,7 +16,7 @@ pub const MAX_ADHESIONS_PER_CELL: usize = 20;
 pub const MAX_ADHESION_CONNECTIONS: usize = 5120;
 
 /// Adhesion settings for a genome mode
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AdhesionSettings {
     pub can_break: bool,
     pub break_force: f32,
@@ -49,6 +49,24 @@ impl Default for AdhesionSettings {
     }
 }
 
+impl From<&crate::genome::AdhesionSettings> for AdhesionSettings {
+    fn from(settings: &crate::genome::AdhesionSettings) -> Self {
+        Self {
+            can_break: settings.can_break,
+            break_force: settings.break_force,
+            rest_length: settings.rest_length,
+            linear_spring_stiffness: settings.linear_spring_stiffness,
+            linear_spring_damping: settings.linear_spring_damping,
+            orientation_spring_stiffness: settings.orientation_spring_stiffness,
+            orientation_spring_damping: settings.orientation_spring_damping,
+            max_angular_deviation: settings.max_angular_deviation,
+            twist_constraint_stiffness: settings.twist_constraint_stiffness,
+            twist_constraint_damping: settings.twist_constraint_damping,
+            enable_twist_constraint: settings.enable_twist_constraint,
+        }
+    }
+}
+
 /// Adhesion connection between two cells (Structure-of-Arrays layout)
 #[derive(Clone)]
 pub struct AdhesionConnections {
@@ -75,6 +93,18 @@ pub struct AdhesionConnections {
     /// Twist reference quaternion for cell B
     pub twist_reference_b: Vec<Quat>,
     
+    /// Force model used for the connection this tick
+    pub quality_tier: Vec<AdhesionTier>,
+    /// Consecutive ticks the connection has stayed calm at full quality
+    pub calm_ticks: Vec<u16>,
+    
+    /// Connections whose forces hit the limit in the last force pass: (connection, unclamped magnitude)
+    /// The magnitude is non-finite when the forces themselves were
+    pub force_limit_hits: Vec<(usize, f32)>,
+    /// Force magnitude on (cell A, cell B) of each connection in the last force pass,
+    /// read by the force heatmap
+    pub force_magnitudes: Vec<[f32; 2]>,
+    
     /// Number of active connections
     pub active_count: usize,
 }
@@ -92,9 +122,41 @@ impl AdhesionConnections {
             anchor_direction_b: vec![-Vec3::X; capacity],
             twist_reference_a: vec![Quat::IDENTITY; capacity],
             twist_reference_b: vec![Quat::IDENTITY; capacity],
+            quality_tier: vec![AdhesionTier::Full; capacity],
+            calm_ticks: vec![0; capacity],
+            force_limit_hits: Vec::new(),
+            force_magnitudes: Vec::new(),
             active_count: 0,
         }
     }
+    
+    /// Number of active connections at (full, cheap) quality
+    pub fn tier_counts(&self) -> (usize, usize) {
+        let mut counts = (0, 0);
+        for i in 0..self.active_count {
+            if self.is_active[i] == 0 {
+                continue;
+            }
+            match self.quality_tier[i] {
+                AdhesionTier::Full => counts.0 += 1,
+                AdhesionTier::Cheap => counts.1 += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// Force model used for an adhesion connection
+///
+/// Bonds that have stayed still for a while drop to the cheap model and are promoted
+/// back as soon as they strain, bend or spin (see `AdhesionTierThresholds`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AdhesionTier {
+    /// Linear spring, orientation springs, twist constraint and tangential forces
+    #[default]
+    Full,
+    /// Linear spring and damping only
+    Cheap,
 }
 
 /// Adhesion indices for each cell (20 slots, -1 for empty)
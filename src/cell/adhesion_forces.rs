@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use super::adhesion::{AdhesionConnections, AdhesionSettings};
+use super::adhesion::{AdhesionConnections, AdhesionSettings, AdhesionTier};
+use crate::simulation::PhysicsMath;
 
 /// Numerical precision constants (matching GPU/C++)
 #[allow(dead_code)]
@@ -8,64 +9,232 @@ const ANGLE_EPSILON: f32 = 0.001;
 const QUATERNION_EPSILON: f32 = 0.0001;
 const TWIST_CLAMP_LIMIT: f32 = 1.57; // ±90 degrees
 
+/// When adhesion connections switch between the full and cheap force models,
+/// and the largest force any connection may apply
+///
+/// Every value is compared against simulated state only, so the tier of each
+/// connection is a pure function of the simulation history and replays match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdhesionTierThresholds {
+    /// Keep every connection at full quality (verification runs)
+    pub strict: bool,
+    /// Ticks a connection must stay calm before it drops to the cheap model
+    pub calm_ticks: u16,
+    /// Largest anchor or twist deviation (radians) that still counts as calm
+    pub calm_angle: f32,
+    /// Largest |extension| / rest length that still counts as calm
+    pub calm_strain: f32,
+    /// Cheap connections go back to full quality above this |extension| / rest length
+    pub wake_strain: f32,
+    /// ... or above this anchor deviation (radians)
+    pub wake_angle: f32,
+    /// ... or above this relative angular speed of the two cells (rad/s)
+    pub wake_angular_velocity: f32,
+    /// Largest force magnitude a connection applies to either cell; larger forces are
+    /// scaled down to it and non-finite ones zeroed, and both are reported
+    pub max_force: f32,
+    /// Angle and twist math (see `PhysicsConfig::strict_reproducibility`)
+    pub math: PhysicsMath,
+}
+
+impl AdhesionTierThresholds {
+    /// Every connection at full quality
+    pub const STRICT: Self = Self {
+        strict: true,
+        calm_ticks: 0,
+        calm_angle: 0.0,
+        calm_strain: 0.0,
+        wake_strain: 0.0,
+        wake_angle: 0.0,
+        wake_angular_velocity: 0.0,
+        max_force: f32::INFINITY,
+        math: PhysicsMath::Native,
+    };
+}
+
+/// Forces and next tier of one connection
+struct ConnectionStep {
+    force_a: Vec3,
+    torque_a: Vec3,
+    force_b: Vec3,
+    torque_b: Vec3,
+    tier: AdhesionTier,
+    calm_ticks: u16,
+    /// Unclamped force magnitude when the connection hit the force limit
+    limited: Option<f32>,
+}
+
+impl ConnectionStep {
+    /// Hold both forces to `max_force`; returns the unclamped magnitude when either exceeded it
+    ///
+    /// Non-finite forces or torques can't be scaled back, so the connection applies nothing this tick.
+    #[inline]
+    fn limit_forces(&mut self, max_force: f32) -> Option<f32> {
+        let magnitude = self.force_a.length().max(self.force_b.length());
+        if !(self.force_a.is_finite() && self.force_b.is_finite() && self.torque_a.is_finite() && self.torque_b.is_finite()) {
+            self.force_a = Vec3::ZERO;
+            self.force_b = Vec3::ZERO;
+            self.torque_a = Vec3::ZERO;
+            self.torque_b = Vec3::ZERO;
+            return Some(if magnitude.is_finite() { f32::NAN } else { magnitude });
+        }
+        if magnitude <= max_force {
+            return None;
+        }
+        self.force_a = self.force_a.clamp_length_max(max_force);
+        self.force_b = self.force_b.clamp_length_max(max_force);
+        Some(magnitude)
+    }
+}
+
+/// Compute one connection's forces with its current tier and decide its tier for the next tick
+///
+/// Cheap connections that cross a wake threshold are promoted before their forces are
+/// computed, so a disturbed bond never spends a tick without its angular constraints.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+fn step_connection(
+    connections: &AdhesionConnections,
+    i: usize,
+    positions: &[Vec3],
+    velocities: &[Vec3],
+    rotations: &[Quat],
+    angular_velocities: &[Vec3],
+    masses: &[f32],
+    settings: &AdhesionSettings,
+    thresholds: &AdhesionTierThresholds,
+    wake_cos: f32,
+) -> ConnectionStep {
+    let a = connections.cell_a_index[i];
+    let b = connections.cell_b_index[i];
+    let anchor_dir_a = connections.anchor_direction_a[i];
+    let anchor_dir_b = connections.anchor_direction_b[i];
+    
+    let delta_pos = positions[b] - positions[a];
+    let dist = delta_pos.length();
+    let strain = if settings.rest_length > QUATERNION_EPSILON {
+        (dist - settings.rest_length).abs() / settings.rest_length
+    } else {
+        f32::INFINITY
+    };
+    
+    if !thresholds.strict && connections.quality_tier[i] == AdhesionTier::Cheap && dist >= QUATERNION_EPSILON {
+        let adhesion_dir = delta_pos / dist;
+        let (anchor_a, anchor_b) = world_anchors(anchor_dir_a, anchor_dir_b, rotations[a], rotations[b]);
+        let relative_spin = (angular_velocities[a] - angular_velocities[b]).length();
+        let calm = strain <= thresholds.wake_strain
+            && relative_spin <= thresholds.wake_angular_velocity
+            && anchor_a.dot(adhesion_dir) >= wake_cos
+            && anchor_b.dot(-adhesion_dir) >= wake_cos;
+        if calm {
+            let linear = linear_spring_force(adhesion_dir, dist, velocities[b] - velocities[a], settings);
+            let mut step = ConnectionStep {
+                force_a: linear,
+                torque_a: Vec3::ZERO,
+                force_b: -linear,
+                torque_b: Vec3::ZERO,
+                tier: AdhesionTier::Cheap,
+                calm_ticks: 0,
+                limited: None,
+            };
+            step.limited = step.limit_forces(thresholds.max_force);
+            return step;
+        }
+    }
+    
+    let (force_a, torque_a, force_b, torque_b, deviation) = compute_adhesion_force_pair(
+        positions[a],
+        velocities[a],
+        rotations[a],
+        angular_velocities[a],
+        masses[a],
+        positions[b],
+        velocities[b],
+        rotations[b],
+        angular_velocities[b],
+        masses[b],
+        anchor_dir_a,
+        anchor_dir_b,
+        connections.twist_reference_a[i],
+        connections.twist_reference_b[i],
+        settings,
+        thresholds.math,
+    );
+    
+    let (tier, calm_ticks) = if thresholds.strict {
+        (AdhesionTier::Full, 0)
+    } else if strain <= thresholds.calm_strain && deviation <= thresholds.calm_angle {
+        let calm_ticks = connections.calm_ticks[i].saturating_add(1);
+        if calm_ticks >= thresholds.calm_ticks {
+            (AdhesionTier::Cheap, 0)
+        } else {
+            (AdhesionTier::Full, calm_ticks)
+        }
+    } else {
+        (AdhesionTier::Full, 0)
+    };
+    
+    let mut step = ConnectionStep { force_a, torque_a, force_b, torque_b, tier, calm_ticks, limited: None };
+    step.limited = step.limit_forces(thresholds.max_force);
+    step
+}
+
+/// Whether connection `i` can be evaluated (active, valid cells and mode)
+#[inline]
+fn connection_is_valid(connections: &AdhesionConnections, i: usize, cell_count: usize, mode_count: usize) -> bool {
+    connections.is_active[i] != 0
+        && connections.cell_a_index[i] < cell_count
+        && connections.cell_b_index[i] < cell_count
+        && connections.mode_index[i] < mode_count
+}
+
 /// Compute adhesion forces for all active connections
 /// Direct port of C++ CPUAdhesionForceCalculator::computeAdhesionForces
+///
+/// Also advances each connection's quality tier and records connections that hit
+/// the force limit in `force_limit_hits` (see `AdhesionTierThresholds`).
+#[allow(clippy::too_many_arguments)]
 pub fn compute_adhesion_forces(
-    connections: &AdhesionConnections,
+    connections: &mut AdhesionConnections,
     positions: &[Vec3],
     velocities: &[Vec3],
     rotations: &[Quat],
     angular_velocities: &[Vec3],
     masses: &[f32],
     mode_settings: &[AdhesionSettings],
+    thresholds: &AdhesionTierThresholds,
     forces: &mut [Vec3],
     torques: &mut [Vec3],
 ) {
+    let wake_cos = thresholds.math.cos(thresholds.wake_angle);
+    connections.force_limit_hits.clear();
+    connections.force_magnitudes.clear();
+    connections.force_magnitudes.resize(connections.active_count, [0.0; 2]);
+    
     // Process each active adhesion connection
     for i in 0..connections.active_count {
-        if connections.is_active[i] == 0 {
-            continue;
-        }
-        
-        let cell_a_idx = connections.cell_a_index[i];
-        let cell_b_idx = connections.cell_b_index[i];
-        let mode_idx = connections.mode_index[i];
-        
-        // Validate indices
-        if cell_a_idx >= positions.len() || cell_b_idx >= positions.len() {
+        if !connection_is_valid(connections, i, positions.len(), mode_settings.len()) {
             continue;
         }
         
-        if mode_idx >= mode_settings.len() {
-            continue;
-        }
-        
-        let settings = &mode_settings[mode_idx];
-        
-        // Calculate forces and torques
-        let (force_a, torque_a, force_b, torque_b) = compute_adhesion_force_pair(
-            positions[cell_a_idx],
-            velocities[cell_a_idx],
-            rotations[cell_a_idx],
-            angular_velocities[cell_a_idx],
-            masses[cell_a_idx],
-            positions[cell_b_idx],
-            velocities[cell_b_idx],
-            rotations[cell_b_idx],
-            angular_velocities[cell_b_idx],
-            masses[cell_b_idx],
-            connections.anchor_direction_a[i],
-            connections.anchor_direction_b[i],
-            connections.twist_reference_a[i],
-            connections.twist_reference_b[i],
-            settings,
+        let settings = &mode_settings[connections.mode_index[i]];
+        let step = step_connection(
+            connections, i, positions, velocities, rotations, angular_velocities, masses,
+            settings, thresholds, wake_cos,
         );
         
         // Apply forces
-        forces[cell_a_idx] += force_a;
-        forces[cell_b_idx] += force_b;
-        torques[cell_a_idx] += torque_a;
-        torques[cell_b_idx] += torque_b;
+        let (cell_a_idx, cell_b_idx) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+        forces[cell_a_idx] += step.force_a;
+        forces[cell_b_idx] += step.force_b;
+        torques[cell_a_idx] += step.torque_a;
+        torques[cell_b_idx] += step.torque_b;
+        connections.quality_tier[i] = step.tier;
+        connections.calm_ticks[i] = step.calm_ticks;
+        connections.force_magnitudes[i] = [step.force_a.length(), step.force_b.length()];
+        if let Some(magnitude) = step.limited {
+            connections.force_limit_hits.push((i, magnitude));
+        }
     }
 }
 
@@ -73,77 +242,61 @@ pub fn compute_adhesion_forces(
 /// 
 /// Uses parallel iteration with deterministic accumulation for improved performance.
 /// Results are identical to single-threaded version due to sorted accumulation.
+#[allow(clippy::too_many_arguments)]
 pub fn compute_adhesion_forces_parallel(
-    connections: &AdhesionConnections,
+    connections: &mut AdhesionConnections,
     positions: &[Vec3],
     velocities: &[Vec3],
     rotations: &[Quat],
     angular_velocities: &[Vec3],
     masses: &[f32],
     mode_settings: &[AdhesionSettings],
+    thresholds: &AdhesionTierThresholds,
     forces: &mut [Vec3],
     torques: &mut [Vec3],
 ) {
     use rayon::prelude::*;
     
-    // Compute force contributions in parallel
-    // Store as (cell_index, force, torque) for deterministic accumulation
-    let contributions: Vec<(usize, Vec3, Vec3)> = (0..connections.active_count)
+    let wake_cos = thresholds.math.cos(thresholds.wake_angle);
+    connections.force_limit_hits.clear();
+    connections.force_magnitudes.clear();
+    connections.force_magnitudes.resize(connections.active_count, [0.0; 2]);
+    
+    // Compute each connection's step in parallel; collect keeps connection order
+    let shared: &AdhesionConnections = connections;
+    let steps: Vec<(usize, ConnectionStep)> = (0..shared.active_count)
         .into_par_iter()
-        .filter(|&i| connections.is_active[i] != 0)
-        .flat_map(|i| {
-            let cell_a_idx = connections.cell_a_index[i];
-            let cell_b_idx = connections.cell_b_index[i];
-            let mode_idx = connections.mode_index[i];
-            
-            // Validate indices
-            if cell_a_idx >= positions.len() || cell_b_idx >= positions.len() {
-                return vec![];
-            }
-            
-            if mode_idx >= mode_settings.len() {
-                return vec![];
-            }
-            
-            let settings = &mode_settings[mode_idx];
-            
-            // Calculate forces and torques
-            let (force_a, torque_a, force_b, torque_b) = compute_adhesion_force_pair(
-                positions[cell_a_idx],
-                velocities[cell_a_idx],
-                rotations[cell_a_idx],
-                angular_velocities[cell_a_idx],
-                masses[cell_a_idx],
-                positions[cell_b_idx],
-                velocities[cell_b_idx],
-                rotations[cell_b_idx],
-                angular_velocities[cell_b_idx],
-                masses[cell_b_idx],
-                connections.anchor_direction_a[i],
-                connections.anchor_direction_b[i],
-                connections.twist_reference_a[i],
-                connections.twist_reference_b[i],
-                settings,
-            );
-            
-            // Return contributions for both cells
-            vec![
-                (cell_a_idx, force_a, torque_a),
-                (cell_b_idx, force_b, torque_b),
-            ]
+        .filter(|&i| connection_is_valid(shared, i, positions.len(), mode_settings.len()))
+        .map(|i| {
+            let settings = &mode_settings[shared.mode_index[i]];
+            (i, step_connection(
+                shared, i, positions, velocities, rotations, angular_velocities, masses,
+                settings, thresholds, wake_cos,
+            ))
         })
         .collect();
     
     // Accumulate forces and torques sequentially for determinism
-    for (idx, force, torque) in contributions {
-        forces[idx] += force;
-        torques[idx] += torque;
+    for (i, step) in steps {
+        let (cell_a_idx, cell_b_idx) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+        forces[cell_a_idx] += step.force_a;
+        torques[cell_a_idx] += step.torque_a;
+        forces[cell_b_idx] += step.force_b;
+        torques[cell_b_idx] += step.torque_b;
+        connections.quality_tier[i] = step.tier;
+        connections.calm_ticks[i] = step.calm_ticks;
+        connections.force_magnitudes[i] = [step.force_a.length(), step.force_b.length()];
+        if let Some(magnitude) = step.limited {
+            connections.force_limit_hits.push((i, magnitude));
+        }
     }
 }
 
 /// Compute adhesion forces for a single connection pair
 /// Direct port of C++ computeAdhesionForces (cell pair version)
 /// Optimized with inline hint for better performance
+///
+/// Also returns the largest angular deviation (either anchor or the twist), in radians.
 #[inline]
 #[allow(clippy::too_many_arguments)]
 fn compute_adhesion_force_pair(
@@ -162,7 +315,8 @@ fn compute_adhesion_force_pair(
     twist_ref_a: Quat,
     twist_ref_b: Quat,
     settings: &AdhesionSettings,
-) -> (Vec3, Vec3, Vec3, Vec3) {
+    math: PhysicsMath,
+) -> (Vec3, Vec3, Vec3, Vec3, f32) {
     let mut force_a = Vec3::ZERO;
     let mut torque_a = Vec3::ZERO;
     let mut force_b = Vec3::ZERO;
@@ -172,43 +326,25 @@ fn compute_adhesion_force_pair(
     let delta_pos = pos_b - pos_a;
     let dist = delta_pos.length();
     if dist < QUATERNION_EPSILON {
-        return (force_a, torque_a, force_b, torque_b);
+        return (force_a, torque_a, force_b, torque_b, 0.0);
     }
     
     let adhesion_dir = delta_pos / dist;
-    let rest_length = settings.rest_length;
     
-    // Linear spring force
-    let force_mag = settings.linear_spring_stiffness * (dist - rest_length);
-    let spring_force = adhesion_dir * force_mag;
-    
-    // Damping - oppose relative motion
-    let rel_vel = vel_b - vel_a;
-    let damp_mag = 1.0 - settings.linear_spring_damping * rel_vel.dot(adhesion_dir);
-    let damping_force = -adhesion_dir * damp_mag;
-    
-    force_a += spring_force + damping_force;
-    force_b -= spring_force + damping_force;
+    // Linear spring force and damping
+    let linear_force = linear_spring_force(adhesion_dir, dist, vel_b - vel_a, settings);
+    force_a += linear_force;
+    force_b -= linear_force;
     
     // Transform anchor directions to world space using PHYSICS rotations
     // Anchors are stored in local space and rotate with the cell
-    let anchor_a = if anchor_dir_a.length() < ANGLE_EPSILON && anchor_dir_b.length() < ANGLE_EPSILON {
-        Vec3::X
-    } else {
-        rotate_vector_by_quaternion(anchor_dir_a, rot_a)
-    };
-    
-    let anchor_b = if anchor_dir_a.length() < ANGLE_EPSILON && anchor_dir_b.length() < ANGLE_EPSILON {
-        -Vec3::X
-    } else {
-        rotate_vector_by_quaternion(anchor_dir_b, rot_b)
-    };
+    let (anchor_a, anchor_b) = world_anchors(anchor_dir_a, anchor_dir_b, rot_a, rot_b);
     
     // Apply orientation spring and damping
     let axis_a = anchor_a.cross(adhesion_dir);
     let sin_a = axis_a.length();
     let cos_a = anchor_a.dot(adhesion_dir);
-    let angle_a = sin_a.atan2(cos_a);
+    let angle_a = math.atan2(sin_a, cos_a);
     
     if sin_a > QUATERNION_EPSILON {
         let axis_a_norm = axis_a.normalize();
@@ -220,7 +356,7 @@ fn compute_adhesion_force_pair(
     let axis_b = anchor_b.cross(-adhesion_dir);
     let sin_b = axis_b.length();
     let cos_b = anchor_b.dot(-adhesion_dir);
-    let angle_b = sin_b.atan2(cos_b);
+    let angle_b = math.atan2(sin_b, cos_b);
     
     if sin_b > QUATERNION_EPSILON {
         let axis_b_norm = axis_b.normalize();
@@ -229,48 +365,24 @@ fn compute_adhesion_force_pair(
         torque_b += spring_torque_b + damping_torque_b;
     }
     
+    let mut deviation = angle_a.abs().max(angle_b.abs());
+    
     // Apply twist constraints if enabled
     if settings.enable_twist_constraint && 
-       twist_ref_a.length() > ANGLE_EPSILON && 
-       twist_ref_b.length() > ANGLE_EPSILON {
+       math.quat_length(twist_ref_a) > ANGLE_EPSILON && 
+       math.quat_length(twist_ref_b) > ANGLE_EPSILON {
         
         let adhesion_axis = delta_pos.normalize();
         
-        // Get current anchor directions in world space
-        let current_anchor_a = rotate_vector_by_quaternion(anchor_dir_a, rot_a);
-        let current_anchor_b = rotate_vector_by_quaternion(anchor_dir_b, rot_b);
-        
-        // Calculate target anchor directions
-        let target_anchor_a = adhesion_axis;
-        let target_anchor_b = -adhesion_axis;
-        
-        // Find rotation needed to align current to target
-        let alignment_rot_a = quat_from_two_vectors(current_anchor_a, target_anchor_a);
-        let alignment_rot_b = quat_from_two_vectors(current_anchor_b, target_anchor_b);
-        
-        // Apply alignment rotation to reference orientations
-        let target_orientation_a = (alignment_rot_a * twist_ref_a).normalize();
-        let target_orientation_b = (alignment_rot_b * twist_ref_b).normalize();
-        
-        // Calculate correction rotation
-        let correction_rot_a = (target_orientation_a * rot_a.conjugate()).normalize();
-        let correction_rot_b = (target_orientation_b * rot_b.conjugate()).normalize();
-        
-        // Convert to axis-angle
-        let axis_angle_a = quat_to_axis_angle(correction_rot_a);
-        let axis_angle_b = quat_to_axis_angle(correction_rot_b);
-        
-        // Project correction onto adhesion axis (twist component only)
-        let twist_correction_a = axis_angle_a.w * axis_angle_a.xyz().dot(adhesion_axis);
-        let twist_correction_b = axis_angle_b.w * axis_angle_b.xyz().dot(adhesion_axis);
+        // Measured twist of A relative to B about the bond axis only. Swing is left
+        // to the orientation springs, so bending the bond no longer drags the anchors.
+        let twist_angle = adhesion_twist_angle_with(math, rot_a, rot_b, twist_ref_a, twist_ref_b, adhesion_axis)
+            .clamp(-TWIST_CLAMP_LIMIT, TWIST_CLAMP_LIMIT);
+        deviation = deviation.max(twist_angle.abs());
         
-        // Clamp corrections
-        let twist_correction_a = twist_correction_a.clamp(-TWIST_CLAMP_LIMIT, TWIST_CLAMP_LIMIT);
-        let twist_correction_b = twist_correction_b.clamp(-TWIST_CLAMP_LIMIT, TWIST_CLAMP_LIMIT);
-        
-        // Apply twist torque (reduced strength for CPU stability)
-        let twist_torque_a = adhesion_axis * twist_correction_a * settings.twist_constraint_stiffness * 0.05;
-        let twist_torque_b = adhesion_axis * twist_correction_b * settings.twist_constraint_stiffness * 0.05;
+        // Equal and opposite twist torque about the world bond axis (reduced strength for CPU stability)
+        let twist_torque_a = -adhesion_axis * twist_angle * settings.twist_constraint_stiffness * 0.05;
+        let twist_torque_b = -twist_torque_a;
         
         // Add strong damping
         let angular_vel_a_proj = ang_vel_a.dot(adhesion_axis);
@@ -317,7 +429,31 @@ fn compute_adhesion_force_pair(
     // torque_a -= torque_b;
     // torque_b -= torque_a;
     
-    (force_a, torque_a, force_b, torque_b)
+    (force_a, torque_a, force_b, torque_b, deviation)
+}
+
+/// Linear spring and damping force on cell A (cell B receives the negation)
+#[inline(always)]
+fn linear_spring_force(adhesion_dir: Vec3, dist: f32, rel_vel: Vec3, settings: &AdhesionSettings) -> Vec3 {
+    // Linear spring force
+    let force_mag = settings.linear_spring_stiffness * (dist - settings.rest_length);
+    let spring_force = adhesion_dir * force_mag;
+    
+    // Damping - oppose relative motion
+    let damp_mag = 1.0 - settings.linear_spring_damping * rel_vel.dot(adhesion_dir);
+    let damping_force = -adhesion_dir * damp_mag;
+    
+    spring_force + damping_force
+}
+
+/// World-space anchor directions of both cells (defaults to +X/-X when neither anchor is set)
+#[inline(always)]
+pub fn world_anchors(anchor_dir_a: Vec3, anchor_dir_b: Vec3, rot_a: Quat, rot_b: Quat) -> (Vec3, Vec3) {
+    if anchor_dir_a.length() < ANGLE_EPSILON && anchor_dir_b.length() < ANGLE_EPSILON {
+        (Vec3::X, -Vec3::X)
+    } else {
+        (rotate_vector_by_quaternion(anchor_dir_a, rot_a), rotate_vector_by_quaternion(anchor_dir_b, rot_b))
+    }
 }
 
 /// Rotate vector by quaternion (GPU algorithm port)
@@ -333,73 +469,47 @@ fn rotate_vector_by_quaternion(v: Vec3, q: Quat) -> Vec3 {
     u * (2.0 * u_dot_v) + v * (s * s - u_dot_u) + u.cross(v) * (2.0 * s)
 }
 
-/// Convert quaternion to axis-angle representation
-/// Optimized with inline hint
-#[inline]
-fn quat_to_axis_angle(q: Quat) -> Vec4 {
-    let w_clamped = q.w.clamp(-1.0, 1.0);
-    let angle = 2.0 * w_clamped.acos();
-    
-    let axis = if angle < 0.001 {
-        Vec3::X
-    } else {
-        let sin_half = (angle * 0.5).sin();
-        Vec3::new(q.x, q.y, q.z) / sin_half
-    };
-    
-    Vec4::new(axis.x, axis.y, axis.z, angle)
+/// Twist of cell A relative to cell B about `bond_axis` since the bond formed, in radians (-PI..=PI)
+///
+/// `twist_ref_a`/`twist_ref_b` are the cells' orientations when the bond was created.
+/// The relative rotation is split into swing and twist about the bond axis and only
+/// the twist is returned, so rotating the whole pair or bending the bond reads as zero.
+pub fn adhesion_twist_angle(rot_a: Quat, rot_b: Quat, twist_ref_a: Quat, twist_ref_b: Quat, bond_axis: Vec3) -> f32 {
+    adhesion_twist_angle_with(PhysicsMath::Native, rot_a, rot_b, twist_ref_a, twist_ref_b, bond_axis)
 }
 
-/// Create quaternion from two vectors (deterministic)
-fn quat_from_two_vectors(from: Vec3, to: Vec3) -> Quat {
-    let v1 = from.normalize();
-    let v2 = to.normalize();
+/// [`adhesion_twist_angle`] computed with the given math implementation
+fn adhesion_twist_angle_with(math: PhysicsMath, rot_a: Quat, rot_b: Quat, twist_ref_a: Quat, twist_ref_b: Quat, bond_axis: Vec3) -> f32 {
+    // World-space rotation each cell has made since the bond formed
+    let delta_a = math.quat_mul(rot_a, math.quat_normalize(twist_ref_a).conjugate());
+    let delta_b = math.quat_mul(rot_b, math.quat_normalize(twist_ref_b).conjugate());
+    let relative = math.quat_mul(delta_a, delta_b.conjugate());
     
-    let cos_angle = v1.dot(v2);
-    
-    // Vectors already aligned
-    if cos_angle > 0.9999 {
-        return Quat::IDENTITY;
-    }
-    
-    // Vectors are opposite
-    if cos_angle < -0.9999 {
-        // Choose axis deterministically
-        let axis = if v1.x.abs() < v1.y.abs() && v1.x.abs() < v1.z.abs() {
-            Vec3::new(0.0, -v1.z, v1.y).normalize()
-        } else if v1.y.abs() < v1.z.abs() {
-            Vec3::new(-v1.z, 0.0, v1.x).normalize()
-        } else {
-            Vec3::new(-v1.y, v1.x, 0.0).normalize()
-        };
-        return Quat::from_xyzw(axis.x, axis.y, axis.z, 0.0); // 180 degree rotation
+    // Swing-twist decomposition: the twist quaternion is (axis * dot(xyz, axis), w)
+    let angle = 2.0 * math.atan2(relative.xyz().dot(bond_axis), relative.w);
+    if angle > std::f32::consts::PI {
+        angle - std::f32::consts::TAU
+    } else if angle < -std::f32::consts::PI {
+        angle + std::f32::consts::TAU
+    } else {
+        angle
     }
-    
-    // General case: half-way quaternion method
-    let halfway = (v1 + v2).normalize();
-    let axis = Vec3::new(
-        v1.y * halfway.z - v1.z * halfway.y,
-        v1.z * halfway.x - v1.x * halfway.z,
-        v1.x * halfway.y - v1.y * halfway.x,
-    );
-    let w = v1.dot(halfway);
-    
-    Quat::from_xyzw(axis.x, axis.y, axis.z, w).normalize()
 }
 
-
 /// Compute adhesion forces with improved cache locality
 /// 
 /// This version processes connections in batches to improve CPU cache utilization.
 /// By grouping connections that access nearby cells, we reduce cache misses.
+#[allow(clippy::too_many_arguments)]
 pub fn compute_adhesion_forces_batched(
-    connections: &AdhesionConnections,
+    connections: &mut AdhesionConnections,
     positions: &[Vec3],
     velocities: &[Vec3],
     rotations: &[Quat],
     angular_velocities: &[Vec3],
     masses: &[f32],
     mode_settings: &[AdhesionSettings],
+    thresholds: &AdhesionTierThresholds,
     forces: &mut [Vec3],
     torques: &mut [Vec3],
 ) {
@@ -407,6 +517,11 @@ pub fn compute_adhesion_forces_batched(
     // Each cell needs ~200 bytes of data, so batch of 32 cells fits in L1
     const BATCH_SIZE: usize = 32;
     
+    let wake_cos = thresholds.math.cos(thresholds.wake_angle);
+    connections.force_limit_hits.clear();
+    connections.force_magnitudes.clear();
+    connections.force_magnitudes.resize(connections.active_count, [0.0; 2]);
+    
     // Process connections in batches
     let mut batch_start = 0;
     while batch_start < connections.active_count {
@@ -414,51 +529,133 @@ pub fn compute_adhesion_forces_batched(
         
         // Process batch
         for i in batch_start..batch_end {
-            if connections.is_active[i] == 0 {
+            if !connection_is_valid(connections, i, positions.len(), mode_settings.len()) {
                 continue;
             }
             
-            let cell_a_idx = connections.cell_a_index[i];
-            let cell_b_idx = connections.cell_b_index[i];
-            let mode_idx = connections.mode_index[i];
-            
-            // Validate indices
-            if cell_a_idx >= positions.len() || cell_b_idx >= positions.len() {
-                continue;
-            }
-            
-            if mode_idx >= mode_settings.len() {
-                continue;
-            }
-            
-            let settings = &mode_settings[mode_idx];
+            let settings = &mode_settings[connections.mode_index[i]];
             
             // Calculate forces and torques
-            let (force_a, torque_a, force_b, torque_b) = compute_adhesion_force_pair(
-                positions[cell_a_idx],
-                velocities[cell_a_idx],
-                rotations[cell_a_idx],
-                angular_velocities[cell_a_idx],
-                masses[cell_a_idx],
-                positions[cell_b_idx],
-                velocities[cell_b_idx],
-                rotations[cell_b_idx],
-                angular_velocities[cell_b_idx],
-                masses[cell_b_idx],
-                connections.anchor_direction_a[i],
-                connections.anchor_direction_b[i],
-                connections.twist_reference_a[i],
-                connections.twist_reference_b[i],
-                settings,
+            let step = step_connection(
+                connections, i, positions, velocities, rotations, angular_velocities, masses,
+                settings, thresholds, wake_cos,
             );
             
             // Apply forces
-            forces[cell_a_idx] += force_a;
-            forces[cell_b_idx] += force_b;
-            torques[cell_a_idx] += torque_a;
-            torques[cell_b_idx] += torque_b;
+            let (cell_a_idx, cell_b_idx) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+            forces[cell_a_idx] += step.force_a;
+            forces[cell_b_idx] += step.force_b;
+            torques[cell_a_idx] += step.torque_a;
+            torques[cell_b_idx] += step.torque_b;
+            connections.quality_tier[i] = step.tier;
+            connections.calm_ticks[i] = step.calm_ticks;
+            connections.force_magnitudes[i] = [step.force_a.length(), step.force_b.length()];
+            if let Some(magnitude) = step.limited {
+                connections.force_limit_hits.push((i, magnitude));
+            }
         }
         
         batch_start = batch_end;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_twist_constraint_removes_pure_twist_without_translation_or_swing() {
+        let settings = AdhesionSettings { enable_twist_constraint: true, ..AdhesionSettings::default() };
+        let without_twist = AdhesionSettings { enable_twist_constraint: false, ..settings.clone() };
+        let (pos_a, pos_b) = (Vec3::ZERO, Vec3::X * settings.rest_length);
+        let (mut rot_a, mut rot_b) = (Quat::IDENTITY, Quat::IDENTITY);
+        // Cell A spins about the bond axis; B is at rest (unit inertia)
+        let (mut ang_vel_a, mut ang_vel_b) = (Vec3::X, Vec3::ZERO);
+        let dt = 0.01;
+
+        for _ in 0..3000 {
+            let pair = |settings: &AdhesionSettings| compute_adhesion_force_pair(
+                pos_a, Vec3::ZERO, rot_a, ang_vel_a, 1.0,
+                pos_b, Vec3::ZERO, rot_b, ang_vel_b, 1.0,
+                Vec3::X, -Vec3::X, Quat::IDENTITY, Quat::IDENTITY, settings, PhysicsMath::Native,
+            );
+            let (force_a, torque_a, force_b, torque_b, _) = pair(&settings);
+            let (free_force_a, _, free_force_b, _, _) = pair(&without_twist);
+
+            // Opposite torques about the bond axis add no tangential force
+            assert!(force_a.abs_diff_eq(free_force_a, 1e-6) && force_b.abs_diff_eq(free_force_b, 1e-6));
+            assert!(torque_a.y.abs() < 1e-6 && torque_a.z.abs() < 1e-6);
+            assert!((torque_a + torque_b).length() < 1e-6);
+
+            ang_vel_a += torque_a * dt;
+            ang_vel_b += torque_b * dt;
+            rot_a = (Quat::from_scaled_axis(ang_vel_a * dt) * rot_a).normalize();
+            rot_b = (Quat::from_scaled_axis(ang_vel_b * dt) * rot_b).normalize();
+        }
+
+        let relative_twist_velocity = (ang_vel_a - ang_vel_b).dot(Vec3::X);
+        assert!(relative_twist_velocity.abs() < 1e-2, "relative twist velocity {}", relative_twist_velocity);
+        assert!(adhesion_twist_angle(rot_a, rot_b, Quat::IDENTITY, Quat::IDENTITY, Vec3::X).abs() < 1e-2);
+        // No swing: both anchors still point along the bond
+        assert!((rot_a * Vec3::X).abs_diff_eq(Vec3::X, 1e-4));
+        assert!((rot_b * -Vec3::X).abs_diff_eq(-Vec3::X, 1e-4));
+    }
+
+    #[test]
+    fn test_overstretched_bond_is_clamped_and_reported_by_every_variant() {
+        let settings = vec![AdhesionSettings::default()];
+        let thresholds = AdhesionTierThresholds { max_force: 100.0, ..AdhesionTierThresholds::STRICT };
+        let positions = [Vec3::ZERO, Vec3::X * settings[0].rest_length * 10.0];
+        let (velocities, angular_velocities) = ([Vec3::ZERO; 2], [Vec3::ZERO; 2]);
+        let (rotations, masses) = ([Quat::IDENTITY; 2], [1.0; 2]);
+
+        type Variant = fn(&mut AdhesionConnections, &[Vec3], &[Vec3], &[Quat], &[Vec3], &[f32],
+            &[AdhesionSettings], &AdhesionTierThresholds, &mut [Vec3], &mut [Vec3]);
+        let variants: [Variant; 3] = [compute_adhesion_forces, compute_adhesion_forces_parallel, compute_adhesion_forces_batched];
+        for variant in variants {
+            let mut connections = AdhesionConnections::new(1);
+            connections.cell_b_index[0] = 1;
+            connections.is_active[0] = 1;
+            connections.active_count = 1;
+            let (mut forces, mut torques) = ([Vec3::ZERO; 2], [Vec3::ZERO; 2]);
+            variant(&mut connections, &positions, &velocities, &rotations, &angular_velocities, &masses,
+                &settings, &thresholds, &mut forces, &mut torques);
+
+            assert!((forces[0].length() - 100.0).abs() < 1e-3, "force {}", forces[0].length());
+            assert!((forces[1].length() - 100.0).abs() < 1e-3, "force {}", forces[1].length());
+            assert!(forces[0].x > 0.0 && forces[1].x < 0.0, "clamping kept the direction");
+            assert_eq!(connections.force_limit_hits.len(), 1);
+            let (connection, magnitude) = connections.force_limit_hits[0];
+            assert_eq!(connection, 0);
+            assert!(magnitude > 1000.0, "unclamped magnitude {}", magnitude);
+        }
+    }
+
+    #[test]
+    fn test_non_finite_forces_are_zeroed_and_reported() {
+        let mut step = ConnectionStep {
+            force_a: Vec3::new(f32::NAN, 0.0, 0.0),
+            torque_a: Vec3::ZERO,
+            force_b: Vec3::ZERO,
+            torque_b: Vec3::ZERO,
+            tier: AdhesionTier::Full,
+            calm_ticks: 0,
+            limited: None,
+        };
+        let magnitude = step.limit_forces(100.0).expect("reported");
+        assert!(!magnitude.is_finite());
+        assert_eq!(step.force_a, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_twist_angle_ignores_rigid_rotation_and_swing() {
+        let spin = Quat::from_axis_angle(Vec3::Y, 0.7);
+        assert!(adhesion_twist_angle(spin, spin, Quat::IDENTITY, Quat::IDENTITY, Vec3::X).abs() < 1e-5);
+
+        let bend = Quat::from_axis_angle(Vec3::Z, 0.4);
+        assert!(adhesion_twist_angle(bend, Quat::IDENTITY, Quat::IDENTITY, Quat::IDENTITY, Vec3::X).abs() < 1e-5);
+
+        let twist = Quat::from_axis_angle(Vec3::X, 0.5);
+        assert!((adhesion_twist_angle(twist, Quat::IDENTITY, Quat::IDENTITY, Quat::IDENTITY, Vec3::X) - 0.5).abs() < 1e-5);
+    }
+}
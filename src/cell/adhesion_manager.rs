@@ -155,6 +155,10 @@ impl AdhesionConnectionManager {
         connections.twist_reference_a[connection_index] = genome_orientation_a;
         connections.twist_reference_b[connection_index] = genome_orientation_b;
         
+        // New bonds start at full quality
+        connections.quality_tier[connection_index] = super::AdhesionTier::Full;
+        connections.calm_ticks[connection_index] = 0;
+        
         // Update adhesion indices in both cells
         if !self.set_adhesion_index(cell_a, slot_a, connection_index as i32) ||
            !self.set_adhesion_index(cell_b, slot_b, connection_index as i32) {
@@ -378,4 +382,304 @@ impl AdhesionConnectionManager {
         
         false
     }
+    
+    /// Get all cells in the same organism as `cell` (including `cell` itself)
+    /// Uses BFS over active adhesion connections
+    pub fn organism_members(&self, connections: &AdhesionConnections, cell: usize) -> Vec<usize> {
+        let mut members = vec![cell];
+        if cell >= self.cell_adhesion_indices.len() {
+            return members;
+        }
+        
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(cell);
+        let mut next = 0;
+        
+        while next < members.len() {
+            let current = members[next];
+            next += 1;
+            
+            for &conn_idx in &self.cell_adhesion_indices[current] {
+                if conn_idx < 0 {
+                    continue;
+                }
+                
+                let conn_idx = conn_idx as usize;
+                if conn_idx >= connections.active_count || connections.is_active[conn_idx] == 0 {
+                    continue;
+                }
+                
+                let neighbor = if connections.cell_a_index[conn_idx] == current {
+                    connections.cell_b_index[conn_idx]
+                } else {
+                    connections.cell_a_index[conn_idx]
+                };
+                
+                if visited.insert(neighbor) {
+                    members.push(neighbor);
+                }
+            }
+        }
+        
+        members
+    }
+    
+    /// Cells reachable from `cell` in at most `max_hops` adhesion bonds, with their hop count
+    /// Breadth-first, so each cell gets its shortest hop distance; `cell` itself is at 0
+    pub fn cells_within_hops(&self, connections: &AdhesionConnections, cell: usize, max_hops: u32) -> Vec<(usize, u32)> {
+        let mut reached = vec![(cell, 0)];
+        if cell >= self.cell_adhesion_indices.len() {
+            return reached;
+        }
+        
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(cell);
+        let mut next = 0;
+        
+        while next < reached.len() {
+            let (current, hops) = reached[next];
+            next += 1;
+            if hops >= max_hops {
+                continue;
+            }
+            
+            for &conn_idx in &self.cell_adhesion_indices[current] {
+                if conn_idx < 0 {
+                    continue;
+                }
+                
+                let conn_idx = conn_idx as usize;
+                if conn_idx >= connections.active_count || connections.is_active[conn_idx] == 0 {
+                    continue;
+                }
+                
+                let neighbor = if connections.cell_a_index[conn_idx] == current {
+                    connections.cell_b_index[conn_idx]
+                } else {
+                    connections.cell_a_index[conn_idx]
+                };
+                
+                if visited.insert(neighbor) {
+                    reached.push((neighbor, hops + 1));
+                }
+            }
+        }
+        
+        reached
+    }
+    
+    /// Rewrite both tables after the cells were compacted
+    ///
+    /// `old_to_new[i]` is the new index of the cell that was at index `i`, None if it was
+    /// removed. Connection endpoints are rewritten; connections touching a removed cell, a
+    /// cell outside the map or a cell at or past `new_cell_count` are deactivated. The slot
+    /// lists of every cell in the map are then rebuilt from the connection table in
+    /// connection order rather than patched, so a stale or misplaced slot can't survive a
+    /// compaction. Returns the number of connections deactivated.
+    pub fn remap_after_compaction(
+        &mut self,
+        connections: &mut AdhesionConnections,
+        old_to_new: &[Option<usize>],
+        new_cell_count: usize,
+    ) -> usize {
+        let mut deactivated = 0;
+        let remap = |cell: usize| old_to_new.get(cell).copied().flatten().filter(|&new| new < new_cell_count);
+        for idx in 0..connections.active_count.min(connections.is_active.len()) {
+            if connections.is_active[idx] == 0 {
+                continue;
+            }
+            match (remap(connections.cell_a_index[idx]), remap(connections.cell_b_index[idx])) {
+                (Some(cell_a), Some(cell_b)) if cell_a != cell_b => {
+                    connections.cell_a_index[idx] = cell_a;
+                    connections.cell_b_index[idx] = cell_b;
+                }
+                _ => {
+                    connections.is_active[idx] = 0;
+                    deactivated += 1;
+                }
+            }
+        }
+
+        let rebuilt = old_to_new.len().max(new_cell_count).min(self.cell_adhesion_indices.len());
+        for slots in &mut self.cell_adhesion_indices[..rebuilt] {
+            *slots = init_adhesion_indices();
+        }
+        for idx in 0..connections.active_count.min(connections.is_active.len()) {
+            if connections.is_active[idx] == 0 {
+                continue;
+            }
+            let (cell_a, cell_b) = (connections.cell_a_index[idx], connections.cell_b_index[idx]);
+            match (self.find_free_adhesion_slot(cell_a), self.find_free_adhesion_slot(cell_b)) {
+                (Some(slot_a), Some(slot_b)) => {
+                    self.cell_adhesion_indices[cell_a][slot_a] = idx as i32;
+                    self.cell_adhesion_indices[cell_b][slot_b] = idx as i32;
+                }
+                // More bonds than slots can only come from tables that were already broken
+                _ => {
+                    connections.is_active[idx] = 0;
+                    deactivated += 1;
+                }
+            }
+        }
+        deactivated
+    }
+
+    /// Check that the slot table and the connection table agree
+    /// 
+    /// Every active connection must join two different live cells (below `cell_count`) and
+    /// be listed in both cells' slots. No live cell may list an active connection it isn't
+    /// part of, the same connection twice, or a connection that is no longer active.
+    /// Returns a list of human-readable problems
+    /// (empty if consistent).
+    pub fn consistency_problems(&self, connections: &AdhesionConnections, cell_count: usize) -> Vec<String> {
+        let mut problems = Vec::new();
+        let is_live = |idx: usize| idx < connections.active_count && connections.is_active[idx] != 0;
+        let lists = |cell: usize, idx: usize| {
+            self.cell_adhesion_indices.get(cell).is_some_and(|slots| slots.contains(&(idx as i32)))
+        };
+        
+        if connections.active_count > connections.is_active.len() {
+            problems.push(format!(
+                "active count {} exceeds the connection capacity {}",
+                connections.active_count, connections.is_active.len()
+            ));
+            return problems;
+        }
+        
+        for idx in (0..connections.active_count).filter(|&idx| is_live(idx)) {
+            let (cell_a, cell_b) = (connections.cell_a_index[idx], connections.cell_b_index[idx]);
+            if cell_a >= cell_count || cell_b >= cell_count {
+                problems.push(format!("connection {} joins cells {} and {}, but only {} are alive", idx, cell_a, cell_b, cell_count));
+                continue;
+            }
+            if cell_a == cell_b {
+                problems.push(format!("connection {} joins cell {} to itself", idx, cell_a));
+            }
+            for cell in [cell_a, cell_b] {
+                if !lists(cell, idx) {
+                    problems.push(format!("connection {} is missing from the slots of cell {}", idx, cell));
+                }
+            }
+        }
+        
+        for (cell, slots) in self.cell_adhesion_indices.iter().enumerate().take(cell_count) {
+            for (slot, &idx) in slots.iter().enumerate() {
+                if idx < 0 {
+                    continue;
+                }
+                if !is_live(idx as usize) {
+                    problems.push(format!("cell {} slot {} lists connection {}, which is inactive", cell, slot, idx));
+                    continue;
+                }
+                let conn = idx as usize;
+                if connections.cell_a_index[conn] != cell && connections.cell_b_index[conn] != cell {
+                    problems.push(format!("cell {} slot {} lists connection {}, which it isn't part of", cell, slot, conn));
+                }
+                if slots[..slot].contains(&idx) {
+                    problems.push(format!("cell {} lists connection {} twice", cell, conn));
+                }
+            }
+        }
+        
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn bonded(cells: usize, pairs: &[(usize, usize)]) -> (AdhesionConnectionManager, AdhesionConnections) {
+        let mut manager = AdhesionConnectionManager::new(cells);
+        let mut connections = AdhesionConnections::new(cells * MAX_ADHESIONS_PER_CELL);
+        for &(a, b) in pairs {
+            manager.add_adhesion_with_directions(&mut connections, a, b, 0, Vec3::X, -Vec3::X,
+                Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY).unwrap();
+        }
+        (manager, connections)
+    }
+    
+    #[test]
+    fn test_consistent_tables_have_no_problems() {
+        let (mut manager, mut connections) = bonded(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert!(manager.consistency_problems(&connections, 4).is_empty());
+        
+        // Properly removed connections leave nothing behind
+        assert!(manager.remove_adhesion(&mut connections, 1));
+        manager.remove_all_connections_for_cell(&mut connections, 3);
+        assert!(manager.consistency_problems(&connections, 4).is_empty());
+    }
+    
+    #[test]
+    fn test_detects_each_kind_of_inconsistency() {
+        // Connection missing from one of its cells
+        let (mut manager, connections) = bonded(3, &[(0, 1)]);
+        manager.remove_adhesion_index(1, 0);
+        let problems = manager.consistency_problems(&connections, 3);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("missing from the slots of cell 1"));
+        
+        // Connection to a cell beyond the live count
+        let (manager, connections) = bonded(3, &[(0, 2)]);
+        let problems = manager.consistency_problems(&connections, 2);
+        assert_eq!(problems, vec!["connection 0 joins cells 0 and 2, but only 2 are alive".to_string()]);
+        
+        // Stale slot pointing at a connection that was reused by other cells
+        let (mut manager, mut connections) = bonded(4, &[(0, 1)]);
+        connections.is_active[0] = 0;
+        manager.remove_adhesion_index(1, 0);
+        manager.add_adhesion_with_directions(&mut connections, 2, 3, 0, Vec3::X, -Vec3::X,
+            Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY).unwrap();
+        let problems = manager.consistency_problems(&connections, 4);
+        assert_eq!(problems, vec!["cell 0 slot 0 lists connection 0, which it isn't part of".to_string()]);
+        
+        // The same connection listed twice
+        let (mut manager, connections) = bonded(2, &[(0, 1)]);
+        manager.set_adhesion_index(0, 5, 0);
+        let problems = manager.consistency_problems(&connections, 2);
+        assert_eq!(problems, vec!["cell 0 lists connection 0 twice".to_string()]);
+        
+        // A connection from a cell to itself
+        let (mut manager, mut connections) = bonded(2, &[(0, 1)]);
+        connections.cell_b_index[0] = 0;
+        manager.remove_adhesion_index(1, 0);
+        manager.set_adhesion_index(0, 1, 0);
+        let problems = manager.consistency_problems(&connections, 2);
+        assert!(problems.iter().any(|problem| problem.contains("joins cell 0 to itself")));
+        
+        // A slot left pointing at a deactivated connection
+        let (mut manager, mut connections) = bonded(3, &[(0, 1), (1, 2)]);
+        connections.is_active[1] = 0;
+        manager.remove_adhesion_index(1, 1);
+        let problems = manager.consistency_problems(&connections, 3);
+        assert_eq!(problems, vec!["cell 2 slot 0 lists connection 1, which is inactive".to_string()]);
+    }
+    
+    #[test]
+    fn test_compaction_remaps_endpoints_and_rebuilds_slots() {
+        // A ring of five; cells 1 and 3 are removed and the rest close up
+        let (mut manager, mut connections) = bonded(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 4)]);
+        // Junk in the slots that a patch would carry over
+        manager.set_adhesion_index(0, 7, 3);
+        manager.set_adhesion_index(4, 9, 42);
+        let old_to_new = [Some(0), None, Some(1), None, Some(2)];
+        
+        assert_eq!(manager.remap_after_compaction(&mut connections, &old_to_new, 3), 4);
+        assert!(manager.consistency_problems(&connections, 3).is_empty());
+        let active: Vec<(usize, usize, usize)> = (0..connections.active_count)
+            .filter(|&idx| connections.is_active[idx] != 0)
+            .map(|idx| (idx, connections.cell_a_index[idx], connections.cell_b_index[idx]))
+            .collect();
+        assert_eq!(active, [(4, 2, 0), (5, 1, 2)]);
+        assert_eq!(manager.get_connections_for_cell(&connections, 0), [4]);
+        assert_eq!(manager.get_connections_for_cell(&connections, 2), [4, 5]);
+        // Slots past the survivors are cleared
+        assert!(manager.cell_adhesion_indices[3..].iter().all(|slots| *slots == init_adhesion_indices()));
+        
+        // Removing nothing keeps every connection where it was
+        let (mut manager, mut connections) = bonded(3, &[(0, 1), (1, 2)]);
+        let before = manager.cell_adhesion_indices.clone();
+        assert_eq!(manager.remap_after_compaction(&mut connections, &[Some(0), Some(1), Some(2)], 3), 0);
+        assert_eq!(manager.cell_adhesion_indices, before);
+    }
 }
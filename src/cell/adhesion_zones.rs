@@ -5,7 +5,7 @@ use bevy::prelude::*;
 /// Zones determine which child cell inherits an adhesion connection during division:
 /// - Zone A: Adhesions pointing opposite to split direction → inherit to child B
 /// - Zone B: Adhesions pointing same as split direction → inherit to child A
-/// - Zone C: Adhesions in equatorial band (90° ± threshold) → inherit to both children
+/// - Zone C: Adhesions in equatorial band (90° ± threshold) → per the mode's equatorial bond rule (both children by default)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum AdhesionZone {
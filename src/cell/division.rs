@@ -116,8 +116,8 @@ fn check_and_divide_cells(
         let child_b_pos = position.position + split_direction * offset_distance;
         
         // Get child settings
-        let child_a_mode_idx = mode.child_a.mode_number.max(0) as usize;
-        let child_b_mode_idx = mode.child_b.mode_number.max(0) as usize;
+        let child_a_mode_idx = genome.genome.child_mode(cell.mode_index, 0, false);
+        let child_b_mode_idx = genome.genome.child_mode(cell.mode_index, 1, false);
         
         let child_a_mode = genome.genome.modes.get(child_a_mode_idx);
         let child_b_mode = genome.genome.modes.get(child_b_mode_idx);
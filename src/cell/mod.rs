@@ -8,8 +8,8 @@ pub mod division;
 pub mod types;
 pub mod type_registry;
 
-pub use adhesion::{AdhesionPlugin, AdhesionSettings, AdhesionConnections, AdhesionIndices, MAX_ADHESIONS_PER_CELL, MAX_ADHESION_CONNECTIONS};
-pub use adhesion_forces::{compute_adhesion_forces, compute_adhesion_forces_parallel, compute_adhesion_forces_batched};
+pub use adhesion::{AdhesionPlugin, AdhesionSettings, AdhesionConnections, AdhesionIndices, AdhesionTier, MAX_ADHESIONS_PER_CELL, MAX_ADHESION_CONNECTIONS};
+pub use adhesion_forces::{compute_adhesion_forces, compute_adhesion_forces_parallel, compute_adhesion_forces_batched, AdhesionTierThresholds};
 pub use adhesion_manager::AdhesionConnectionManager;
 pub use adhesion_zones::{AdhesionZone, classify_bond_direction, get_zone_color, EQUATORIAL_THRESHOLD_DEGREES};
 pub use division::{DivisionPlugin, DivisionQueue, has_pending_divisions};
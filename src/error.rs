@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+/// Crate-wide error type for file, serialization and validation failures
+///
+/// Every variant carries enough context (operation, path) to be shown to the
+/// user directly, e.g. in an error toast.
+#[derive(Debug, thiserror::Error)]
+pub enum BioSpheresError {
+    #[error("Could not {operation} {}: {source}", path.display())]
+    Io {
+        operation: FileOperation,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{} is not a valid {what} file: {source}", path.display())]
+    Parse {
+        what: &'static str,
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("{} is not a valid {what} file: {reason}", path.display())]
+    Format {
+        what: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+    #[error("Could not serialize {what}: {source}")]
+    Serialize {
+        what: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Not a valid {what}: {source}")]
+    Deserialize {
+        what: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Invalid {what}: {}", problems.join("; "))]
+    Validation {
+        what: &'static str,
+        problems: Vec<String>,
+    },
+    #[error("Could not serve on {address}: {reason}")]
+    Server {
+        address: String,
+        reason: String,
+    },
+}
+
+/// File operation that failed, used in error messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOperation {
+    Read,
+    Write,
+    Replace,
+    Create,
+    Rename,
+    Copy,
+    Delete,
+}
+
+impl std::fmt::Display for FileOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileOperation::Read => write!(f, "read"),
+            FileOperation::Write => write!(f, "write"),
+            FileOperation::Replace => write!(f, "replace"),
+            FileOperation::Create => write!(f, "create"),
+            FileOperation::Rename => write!(f, "rename"),
+            FileOperation::Copy => write!(f, "copy"),
+            FileOperation::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, BioSpheresError>;
+
+impl BioSpheresError {
+    /// Full error chain, one cause per line (for expandable details)
+    pub fn details(&self) -> String {
+        let mut details = self.to_string();
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            details.push_str("\ncaused by: ");
+            details.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        details
+    }
+}
+
+/// Read a file to a string
+pub fn read_to_string(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|source| BioSpheresError::Io {
+        operation: FileOperation::Read,
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Create a directory and any missing parents
+pub fn create_dir_all(path: &Path) -> Result<()> {
+    std::fs::create_dir_all(path).map_err(|source| BioSpheresError::Io {
+        operation: FileOperation::Create,
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Serialize a value as pretty JSON and write it atomically
+pub fn write_json<T: serde::Serialize>(path: &Path, value: &T, what: &'static str) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|source| BioSpheresError::Serialize { what, source })?;
+    write_atomically(path, json.as_bytes())
+}
+
+/// Read and parse a JSON file
+pub fn read_json<T: serde::de::DeserializeOwned>(path: &Path, what: &'static str) -> Result<T> {
+    let json = read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|source| BioSpheresError::Parse {
+        what,
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Write a file by writing a temporary sibling and renaming it over the target
+///
+/// If anything fails, an existing file at `path` is left untouched.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let write_temp = || -> std::io::Result<()> {
+        use std::io::Write;
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()
+    };
+    if let Err(source) = write_temp() {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(BioSpheresError::Io {
+            operation: FileOperation::Write,
+            path: path.to_path_buf(),
+            source,
+        });
+    }
+
+    std::fs::rename(&temp_path, path).map_err(|source| {
+        let _ = std::fs::remove_file(&temp_path);
+        BioSpheresError::Io {
+            operation: FileOperation::Replace,
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_messages_include_context() {
+        let error = BioSpheresError::Io {
+            operation: FileOperation::Write,
+            path: PathBuf::from("genomes/test.json"),
+            source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied"),
+        };
+        assert_eq!(error.to_string(), "Could not write genomes/test.json: access denied");
+        assert!(error.details().contains("caused by: access denied"));
+
+        let error = BioSpheresError::Validation {
+            what: "scene",
+            problems: vec!["Genome has no modes".to_string(), "Cell 3 is outside the world sphere".to_string()],
+        };
+        assert_eq!(error.to_string(), "Invalid scene: Genome has no modes; Cell 3 is outside the world sphere");
+
+        let Err(error) = crate::simulation::SceneFile::from_json_str("{") else { panic!("truncated scene parsed") };
+        assert!(matches!(error, BioSpheresError::Deserialize { what: "scene", .. }));
+        assert!(error.to_string().starts_with("Not a valid scene: "));
+    }
+
+    #[test]
+    fn test_failed_save_leaves_existing_file_untouched() {
+        let dir = std::env::temp_dir().join(format!("biospheres_atomic_write_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("genome.json");
+        std::fs::write(&path, "original").unwrap();
+
+        // A directory where the temporary file should go makes the write fail
+        let blocker = dir.join("genome.json.tmp");
+        std::fs::create_dir_all(&blocker).unwrap();
+        let result = write_atomically(&path, b"replacement");
+        assert!(matches!(result, Err(BioSpheresError::Io { operation: FileOperation::Write, .. })));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+
+        std::fs::remove_dir(&blocker).unwrap();
+        write_atomically(&path, b"replacement").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "replacement");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+use super::{AdhesionSettings, GenomeData, GenomeHistory};
+
+/// Relative difference above which two adhesion values count as different
+const MATERIAL_DIFFERENCE: f32 = 0.05;
+
+/// Modes the Adhesion Settings tab can copy the selected mode's adhesion settings to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdhesionCopyTarget {
+    ChildA,
+    ChildB,
+    /// Every mode a lineage starting in the source mode can enter
+    Reachable,
+}
+
+impl AdhesionCopyTarget {
+    pub const ALL: [AdhesionCopyTarget; 3] = [Self::ChildA, Self::ChildB, Self::Reachable];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ChildA => "Child A's mode",
+            Self::ChildB => "Child B's mode",
+            Self::Reachable => "all reachable modes",
+        }
+    }
+
+    /// Target modes for copying from `source`, never including `source` itself
+    pub fn modes(self, genome: &GenomeData, source: usize) -> BTreeSet<usize> {
+        let mut modes = match self {
+            Self::ChildA => BTreeSet::from([genome.child_mode(source, 0, false)]),
+            Self::ChildB => BTreeSet::from([genome.child_mode(source, 1, false)]),
+            Self::Reachable => genome.reachable_modes(source),
+        };
+        modes.remove(&source);
+        modes
+    }
+}
+
+/// Write `source`'s adhesion settings into `target`'s modes; returns how many modes changed
+///
+/// The modes' previous settings are recorded in `history` as one entry.
+pub fn copy_adhesion_settings(
+    genome: &mut GenomeData,
+    source: usize,
+    target: AdhesionCopyTarget,
+    history: &mut GenomeHistory,
+) -> usize {
+    let Some(settings) = genome.modes.get(source).map(|mode| mode.adhesion_settings.clone()) else {
+        return 0;
+    };
+    let changed: Vec<usize> = target.modes(genome, source).into_iter()
+        .filter(|&index| genome.modes[index].adhesion_settings != settings)
+        .collect();
+    if changed.is_empty() {
+        return 0;
+    }
+
+    let before = changed.iter().map(|&index| (index, genome.modes[index].clone())).collect();
+    history.record(format!("Copy adhesion settings of {} to {}", genome.modes[source].name, target.label()), before);
+    for &index in &changed {
+        genome.modes[index].adhesion_settings = settings.clone();
+    }
+    changed.len()
+}
+
+/// A child mode whose adhesion settings differ from those of the bond its parent creates
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdhesionMismatch {
+    /// 0 for child A, 1 for child B
+    pub side: usize,
+    pub mode: usize,
+    pub fields: Vec<&'static str>,
+}
+
+/// Children linked by the bond `mode` creates at division whose own modes specify
+/// materially different adhesion forces
+///
+/// The bond keeps the parent mode's settings, so the same two cells would be held
+/// differently by bonds their own modes create. Empty when `mode` makes no bond.
+pub fn adhesion_mismatches(genome: &GenomeData, mode: usize) -> Vec<AdhesionMismatch> {
+    let Some(parent) = genome.modes.get(mode) else {
+        return Vec::new();
+    };
+    if !(parent.parent_make_adhesion && parent.child_a.keep_adhesion && parent.child_b.keep_adhesion) {
+        return Vec::new();
+    }
+
+    let mut mismatches = Vec::new();
+    for side in 0..2 {
+        let children: BTreeSet<usize> = [false, true].into_iter()
+            .map(|reached_max_splits| genome.child_mode(mode, side, reached_max_splits))
+            .collect();
+        for child in children {
+            let fields = force_field_differences(&parent.adhesion_settings, &genome.modes[child].adhesion_settings);
+            if !fields.is_empty() {
+                mismatches.push(AdhesionMismatch { side, mode: child, fields });
+            }
+        }
+    }
+    mismatches
+}
+
+/// Names of the fields read by the adhesion force code that differ materially
+///
+/// `can_break`, `break_force` and `max_angular_deviation` are not read by the force
+/// code, so they never count.
+fn force_field_differences(a: &AdhesionSettings, b: &AdhesionSettings) -> Vec<&'static str> {
+    let values = [
+        ("Rest Length", a.rest_length, b.rest_length),
+        ("Linear Spring Stiffness", a.linear_spring_stiffness, b.linear_spring_stiffness),
+        ("Linear Spring Damping", a.linear_spring_damping, b.linear_spring_damping),
+        ("Orientation Spring Stiffness", a.orientation_spring_stiffness, b.orientation_spring_stiffness),
+        ("Orientation Spring Damping", a.orientation_spring_damping, b.orientation_spring_damping),
+        ("Twist Constraint Stiffness", a.twist_constraint_stiffness, b.twist_constraint_stiffness),
+        ("Twist Constraint Damping", a.twist_constraint_damping, b.twist_constraint_damping),
+    ];
+    let mut fields: Vec<&'static str> = values.into_iter()
+        .filter(|&(_, a, b)| (a - b).abs() > MATERIAL_DIFFERENCE * a.abs().max(b.abs()))
+        .map(|(name, _, _)| name)
+        .collect();
+    if a.enable_twist_constraint != b.enable_twist_constraint {
+        fields.push("Enable Twist Constraint");
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{ModeIndex, ModeSettings};
+
+    /// Mode 0 splits into 1 and 2; 2 moves on to 3 after its splits; 4 is unreachable
+    fn genome() -> GenomeData {
+        let mut modes: Vec<ModeSettings> = (0..5)
+            .map(|index| ModeSettings::new_self_splitting(index, format!("M{}", index)))
+            .collect();
+        modes[0].child_a.mode_number = ModeIndex::new(1);
+        modes[0].child_b.mode_number = ModeIndex::new(2);
+        modes[0].parent_make_adhesion = true;
+        modes[2].mode_b_after_splits = ModeIndex::new(3);
+        for (index, mode) in modes.iter_mut().enumerate() {
+            mode.adhesion_settings.linear_spring_stiffness = 100.0 + 50.0 * index as f32;
+        }
+        GenomeData { modes, initial_mode: ModeIndex::new(0), ..GenomeData::default() }
+    }
+
+    #[test]
+    fn test_copy_targets_children_or_reachable_modes_as_one_undo() {
+        let mut genome = genome();
+        genome.modes[0].adhesion_settings.rest_length = 2.5;
+        assert_eq!(genome.reachable_modes(0), BTreeSet::from([0, 1, 2, 3]));
+        assert_eq!(AdhesionCopyTarget::ChildB.modes(&genome, 0), BTreeSet::from([2]));
+        let edited = genome.clone();
+        let mut history = GenomeHistory::default();
+
+        assert_eq!(copy_adhesion_settings(&mut genome, 0, AdhesionCopyTarget::Reachable, &mut history), 3);
+        for index in 1..4 {
+            assert!(genome.modes[index].adhesion_settings == genome.modes[0].adhesion_settings);
+        }
+        assert_eq!(genome.modes[4].adhesion_settings.linear_spring_stiffness, 300.0);
+        // Nothing left to change, so nothing recorded
+        assert_eq!(copy_adhesion_settings(&mut genome, 0, AdhesionCopyTarget::ChildA, &mut history), 0);
+
+        assert!(history.undo(&mut genome).is_some());
+        assert!(genome == edited);
+        assert!(history.undo(&mut genome).is_none());
+    }
+
+    #[test]
+    fn test_mismatch_warning_tracks_alignment_of_force_fields() {
+        let mut genome = genome();
+        let mismatches = adhesion_mismatches(&genome, 0);
+        assert_eq!(mismatches.iter().map(|m| (m.side, m.mode)).collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(mismatches[0].fields, vec!["Linear Spring Stiffness"]);
+
+        let mut history = GenomeHistory::default();
+        copy_adhesion_settings(&mut genome, 0, AdhesionCopyTarget::ChildA, &mut history);
+        copy_adhesion_settings(&mut genome, 0, AdhesionCopyTarget::ChildB, &mut history);
+        assert!(adhesion_mismatches(&genome, 0).is_empty());
+
+        // Fields the force code never reads and tiny differences don't count
+        genome.modes[1].adhesion_settings.break_force = 99.0;
+        genome.modes[1].adhesion_settings.linear_spring_damping *= 1.01;
+        assert!(adhesion_mismatches(&genome, 0).is_empty());
+
+        genome.modes[2].adhesion_settings.enable_twist_constraint = false;
+        assert_eq!(adhesion_mismatches(&genome, 0), vec![AdhesionMismatch {
+            side: 1,
+            mode: 2,
+            fields: vec!["Enable Twist Constraint"],
+        }]);
+
+        // No bond, nothing to warn about
+        genome.modes[0].parent_make_adhesion = false;
+        assert!(adhesion_mismatches(&genome, 0).is_empty());
+    }
+}
@@ -0,0 +1,447 @@
+use bevy::prelude::*;
+use bevy::color::{Hsva, Srgba};
+use std::collections::{BTreeSet, VecDeque};
+use std::ops::RangeInclusive;
+use super::{CurrentGenome, GenomeData, ModeIndex, ModeRemap, ModeSettings};
+
+/// Undo entries kept by the genome history
+const MAX_HISTORY_ENTRIES: usize = 32;
+
+/// Mode fields the Batch Edit panel can write across selected modes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchField {
+    AdhesionBreakForce,
+    AdhesionRestLength,
+    LinearSpringStiffness,
+    LinearSpringDamping,
+    OrientationSpringStiffness,
+    OrientationSpringDamping,
+    MaxAngularDeviation,
+    TwistStiffness,
+    TwistDamping,
+    SplitInterval,
+    SplitMass,
+    NutrientGainRate,
+    NutrientPriority,
+    /// Hue rotation in degrees; always relative to each mode's own color
+    HueShift,
+    Opacity,
+    Emissive,
+}
+
+impl BatchField {
+    pub const ALL: [BatchField; 16] = [
+        BatchField::AdhesionBreakForce,
+        BatchField::AdhesionRestLength,
+        BatchField::LinearSpringStiffness,
+        BatchField::LinearSpringDamping,
+        BatchField::OrientationSpringStiffness,
+        BatchField::OrientationSpringDamping,
+        BatchField::MaxAngularDeviation,
+        BatchField::TwistStiffness,
+        BatchField::TwistDamping,
+        BatchField::SplitInterval,
+        BatchField::SplitMass,
+        BatchField::NutrientGainRate,
+        BatchField::NutrientPriority,
+        BatchField::HueShift,
+        BatchField::Opacity,
+        BatchField::Emissive,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BatchField::AdhesionBreakForce => "Adhesion Break Force",
+            BatchField::AdhesionRestLength => "Adhesion Rest Length",
+            BatchField::LinearSpringStiffness => "Linear Spring Stiffness",
+            BatchField::LinearSpringDamping => "Linear Spring Damping",
+            BatchField::OrientationSpringStiffness => "Orientation Spring Stiffness",
+            BatchField::OrientationSpringDamping => "Orientation Spring Damping",
+            BatchField::MaxAngularDeviation => "Max Angular Deviation",
+            BatchField::TwistStiffness => "Twist Constraint Stiffness",
+            BatchField::TwistDamping => "Twist Constraint Damping",
+            BatchField::SplitInterval => "Split Interval",
+            BatchField::SplitMass => "Split Mass",
+            BatchField::NutrientGainRate => "Nutrient Gain Rate",
+            BatchField::NutrientPriority => "Nutrient Priority",
+            BatchField::HueShift => "Color Hue Shift",
+            BatchField::Opacity => "Opacity",
+            BatchField::Emissive => "Emissive",
+        }
+    }
+
+    /// Valid values, matching the mode editor's sliders
+    pub fn range(self) -> RangeInclusive<f32> {
+        match self {
+            BatchField::AdhesionBreakForce => 0.1..=100.0,
+            BatchField::AdhesionRestLength => 0.5..=5.0,
+            BatchField::LinearSpringStiffness => 0.1..=500.0,
+            BatchField::OrientationSpringStiffness => 0.1..=100.0,
+            BatchField::LinearSpringDamping
+            | BatchField::OrientationSpringDamping
+            | BatchField::TwistDamping => 0.0..=10.0,
+            BatchField::MaxAngularDeviation => 0.0..=180.0,
+            BatchField::TwistStiffness => 0.0..=2.0,
+            BatchField::SplitInterval => 1.0..=60.0,
+            BatchField::SplitMass => 1.0..=3.0,
+            BatchField::NutrientGainRate => 0.0..=2.0,
+            BatchField::NutrientPriority => 0.1..=10.0,
+            BatchField::HueShift => -180.0..=180.0,
+            BatchField::Opacity => 0.0..=1.0,
+            BatchField::Emissive => 0.0..=10.0,
+        }
+    }
+
+    /// Hue shift only makes sense relative to each mode's color
+    pub fn is_relative_only(self) -> bool {
+        self == BatchField::HueShift
+    }
+
+    fn value_mut(self, mode: &mut ModeSettings) -> Option<&mut f32> {
+        let adhesion = &mut mode.adhesion_settings;
+        Some(match self {
+            BatchField::AdhesionBreakForce => &mut adhesion.break_force,
+            BatchField::AdhesionRestLength => &mut adhesion.rest_length,
+            BatchField::LinearSpringStiffness => &mut adhesion.linear_spring_stiffness,
+            BatchField::LinearSpringDamping => &mut adhesion.linear_spring_damping,
+            BatchField::OrientationSpringStiffness => &mut adhesion.orientation_spring_stiffness,
+            BatchField::OrientationSpringDamping => &mut adhesion.orientation_spring_damping,
+            BatchField::MaxAngularDeviation => &mut adhesion.max_angular_deviation,
+            BatchField::TwistStiffness => &mut adhesion.twist_constraint_stiffness,
+            BatchField::TwistDamping => &mut adhesion.twist_constraint_damping,
+            BatchField::SplitInterval => &mut mode.split_interval,
+            BatchField::SplitMass => &mut mode.split_mass,
+            BatchField::NutrientGainRate => &mut mode.nutrient_gain_rate,
+            BatchField::NutrientPriority => &mut mode.nutrient_priority,
+            BatchField::Opacity => &mut mode.opacity,
+            BatchField::Emissive => &mut mode.emissive,
+            BatchField::HueShift => return None,
+        })
+    }
+}
+
+/// How a batch value combines with each mode's current value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BatchOp {
+    #[default]
+    Set,
+    Add,
+    Multiply,
+}
+
+impl BatchOp {
+    pub const ALL: [BatchOp; 3] = [BatchOp::Set, BatchOp::Add, BatchOp::Multiply];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BatchOp::Set => "=",
+            BatchOp::Add => "+=",
+            BatchOp::Multiply => "*=",
+        }
+    }
+
+    pub fn apply(self, current: f32, value: f32) -> f32 {
+        match self {
+            BatchOp::Set => value,
+            BatchOp::Add => current + value,
+            BatchOp::Multiply => current * value,
+        }
+    }
+}
+
+/// One row of the Batch Edit panel
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchFieldEdit {
+    pub field: BatchField,
+    pub enabled: bool,
+    pub op: BatchOp,
+    pub value: f32,
+}
+
+impl BatchFieldEdit {
+    /// Write this edit into one mode, clamped to the field's valid range
+    pub fn apply_to(&self, mode: &mut ModeSettings) {
+        if self.field == BatchField::HueShift {
+            mode.color = shift_hue(mode.color, self.value);
+            return;
+        }
+        let range = self.field.range();
+        if let Some(slot) = self.field.value_mut(mode) {
+            *slot = self.op.apply(*slot, self.value).clamp(*range.start(), *range.end());
+        }
+    }
+}
+
+/// Every batch field, unticked, with neutral values
+pub fn default_batch_edits() -> Vec<BatchFieldEdit> {
+    BatchField::ALL.iter().map(|&field| BatchFieldEdit {
+        field,
+        enabled: false,
+        op: if field.is_relative_only() { BatchOp::Add } else { BatchOp::Set },
+        value: if field.is_relative_only() { 0.0 } else { *field.range().start() },
+    }).collect()
+}
+
+/// Rotate an RGB color's hue by `degrees`
+pub fn shift_hue(color: Vec3, degrees: f32) -> Vec3 {
+    let mut hsva = Hsva::from(Srgba::rgb(color.x, color.y, color.z));
+    hsva.hue = (hsva.hue + degrees).rem_euclid(360.0);
+    let shifted = Srgba::from(hsva);
+    Vec3::new(shifted.red, shifted.green, shifted.blue)
+}
+
+/// Apply the enabled edits to every selected mode; returns how many modes changed
+///
+/// The modes' previous settings are recorded in `history` as one entry.
+pub fn apply_batch_edit(
+    genome: &mut GenomeData,
+    selection: &BTreeSet<usize>,
+    edits: &[BatchFieldEdit],
+    history: &mut GenomeHistory,
+) -> usize {
+    let enabled: Vec<&BatchFieldEdit> = edits.iter().filter(|edit| edit.enabled).collect();
+    let targets: Vec<usize> = selection.iter().copied().filter(|&index| index < genome.modes.len()).collect();
+    if enabled.is_empty() || targets.is_empty() {
+        return 0;
+    }
+
+    let before: Vec<(usize, ModeSettings)> = targets.iter().map(|&index| (index, genome.modes[index].clone())).collect();
+    let mut changed = 0;
+    for &index in &targets {
+        let mode = &mut genome.modes[index];
+        let original = mode.clone();
+        for edit in &enabled {
+            edit.apply_to(mode);
+        }
+        if *mode != original {
+            changed += 1;
+        }
+    }
+    if changed > 0 {
+        let fields = enabled.iter().map(|edit| edit.field.name()).collect::<Vec<_>>().join(", ");
+        history.record(format!("Batch edit of {} modes ({})", targets.len(), fields), before);
+    }
+    changed
+}
+
+/// Update a multi-mode selection for a click on `clicked`
+///
+/// Ctrl toggles the mode, Shift selects the range from the primary mode, a plain
+/// click selects only the clicked mode. Returns the new primary mode.
+pub fn click_mode_selection(selection: &mut BTreeSet<usize>, primary: usize, clicked: usize, ctrl: bool, shift: bool) -> usize {
+    if shift {
+        selection.extend(primary.min(clicked)..=primary.max(clicked));
+        return clicked;
+    }
+    if ctrl {
+        if selection.is_empty() {
+            selection.insert(primary);
+        }
+        if !selection.insert(clicked) && selection.len() > 1 {
+            selection.remove(&clicked);
+            return if clicked == primary { *selection.iter().next().unwrap_or(&primary) } else { primary };
+        }
+        return clicked;
+    }
+    selection.clear();
+    clicked
+}
+
+/// A set of mode edits that can be undone together
+struct HistoryEntry {
+    label: String,
+    modes: Vec<(usize, ModeSettings)>,
+}
+
+/// Undo history of multi-mode genome edits
+///
+/// Entries refer to modes by index and only make sense for the genome they were recorded
+/// on, so the history is cleared when the genome is replaced.
+#[derive(Resource, Default)]
+pub struct GenomeHistory {
+    entries: VecDeque<HistoryEntry>,
+    /// [`CurrentGenome::generation`] the entries were recorded on
+    generation: u64,
+}
+
+impl GenomeHistory {
+    /// Remember the settings `modes` had before an edit
+    pub fn record(&mut self, label: String, modes: Vec<(usize, ModeSettings)>) {
+        if self.entries.len() == MAX_HISTORY_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(HistoryEntry { label, modes });
+    }
+
+    pub fn last_label(&self) -> Option<&str> {
+        self.entries.back().map(|entry| entry.label.as_str())
+    }
+
+    /// Restore the modes of the latest entry; returns its label
+    pub fn undo(&mut self, genome: &mut GenomeData) -> Option<String> {
+        let entry = self.entries.pop_back()?;
+        for (index, mode) in entry.modes {
+            if let Some(slot) = genome.modes.get_mut(index) {
+                *slot = mode;
+            }
+        }
+        Some(entry.label)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Move the entries to the new mode indices after a mode insert or removal
+    ///
+    /// Snapshots of a removed mode are dropped, along with entries left empty.
+    pub fn remap_modes(&mut self, remap: &ModeRemap) {
+        for entry in &mut self.entries {
+            entry.modes = std::mem::take(&mut entry.modes).into_iter()
+                .filter_map(|(old, mut settings)| {
+                    let new = remap.get(old)?;
+                    settings.remap_references(remap, ModeIndex::new(new));
+                    Some((new, settings))
+                })
+                .collect();
+        }
+        self.entries.retain(|entry| !entry.modes.is_empty());
+    }
+
+    /// Drop the entries if they were recorded on an earlier genome than `generation`
+    pub fn follow_generation(&mut self, generation: u64) {
+        if self.generation != generation {
+            self.entries.clear();
+            self.generation = generation;
+        }
+    }
+}
+
+/// Forget undo entries of a genome that was loaded over, sampled over or reset
+pub fn clear_history_on_replace(current_genome: Res<CurrentGenome>, mut history: ResMut<GenomeHistory>) {
+    history.follow_generation(current_genome.generation());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(field: BatchField, op: BatchOp, value: f32) -> BatchFieldEdit {
+        BatchFieldEdit { field, enabled: true, op, value }
+    }
+
+    #[test]
+    fn test_relative_adjustments_clamp_to_field_range() {
+        let mut genome = GenomeData::default();
+        genome.modes[0].adhesion_settings.linear_spring_damping = 4.0;
+        genome.modes[1].adhesion_settings.linear_spring_damping = 9.0;
+        genome.modes[2].split_interval = 50.0;
+        genome.modes[3].split_interval = 1.5;
+        let selection: BTreeSet<usize> = (0..4).collect();
+        let mut history = GenomeHistory::default();
+
+        let edits = [
+            edit(BatchField::LinearSpringDamping, BatchOp::Multiply, 2.0),
+            edit(BatchField::SplitInterval, BatchOp::Add, -1.0),
+            edit(BatchField::Opacity, BatchOp::Add, 5.0),
+        ];
+        assert_eq!(apply_batch_edit(&mut genome, &selection, &edits, &mut history), 4);
+
+        let damping: Vec<f32> = genome.modes[..2].iter().map(|m| m.adhesion_settings.linear_spring_damping).collect();
+        assert_eq!(damping, [8.0, 10.0]);
+        assert_eq!(genome.modes[2].split_interval, 49.0);
+        assert_eq!(genome.modes[3].split_interval, 1.0);
+        assert!(genome.modes[..4].iter().all(|m| m.opacity == 1.0));
+
+        let mut genome_b = genome.clone();
+        apply_batch_edit(&mut genome_b, &selection, &[edit(BatchField::NutrientPriority, BatchOp::Multiply, -3.0)], &mut history);
+        assert!(genome_b.modes[..4].iter().all(|m| m.nutrient_priority == 0.1));
+        assert!(genome_b.modes[4] == genome.modes[4]);
+    }
+
+    #[test]
+    fn test_batch_edit_is_one_undo_entry() {
+        let original = GenomeData::default();
+        let mut genome = original.clone();
+        let selection: BTreeSet<usize> = [1, 3, 5].into_iter().collect();
+        let mut history = GenomeHistory::default();
+        let edits = [
+            edit(BatchField::AdhesionBreakForce, BatchOp::Set, 42.0),
+            edit(BatchField::HueShift, BatchOp::Add, 90.0),
+        ];
+        assert_eq!(apply_batch_edit(&mut genome, &selection, &edits, &mut history), 3);
+        assert_eq!(genome.modes[3].adhesion_settings.break_force, 42.0);
+        assert!(genome.modes[3].color.distance(original.modes[3].color) > 0.1);
+        assert!(history.last_label().unwrap().contains("3 modes"));
+
+        assert!(history.undo(&mut genome).is_some());
+        assert!(genome == original);
+        assert!(history.undo(&mut genome).is_none());
+    }
+
+    #[test]
+    fn test_replacing_the_genome_clears_the_history() {
+        let mut current_genome = CurrentGenome::default();
+        let mut history = GenomeHistory::default();
+        let selection: BTreeSet<usize> = [0].into_iter().collect();
+        apply_batch_edit(&mut current_genome.genome, &selection, &[edit(BatchField::Opacity, BatchOp::Set, 0.5)], &mut history);
+
+        history.follow_generation(current_genome.generation());
+        assert!(history.last_label().is_some());
+
+        current_genome.replace(GenomeData::default(), 0, None);
+        history.follow_generation(current_genome.generation());
+        assert_eq!(history.last_label(), None);
+        assert!(history.undo(&mut current_genome.genome).is_none());
+        assert!(current_genome.genome == GenomeData::default());
+    }
+
+    #[test]
+    fn test_history_follows_mode_inserts_and_removals() {
+        let mut genome = GenomeData::default();
+        let original = genome.clone();
+        let mut history = GenomeHistory::default();
+        let selection: BTreeSet<usize> = [1, 2].into_iter().collect();
+        apply_batch_edit(&mut genome, &selection, &[edit(BatchField::Opacity, BatchOp::Set, 0.5)], &mut history);
+        let only_two: BTreeSet<usize> = [2].into_iter().collect();
+        apply_batch_edit(&mut genome, &only_two, &[edit(BatchField::SplitInterval, BatchOp::Set, 30.0)], &mut history);
+
+        // Inserting at 0 shifts every mode up by one
+        let remap = genome.insert_mode(0, ModeSettings::new_self_splitting(0, "New".to_string()));
+        history.remap_modes(&remap);
+        assert!(history.undo(&mut genome).is_some());
+        assert_eq!(genome.modes[3].split_interval, original.modes[2].split_interval);
+        assert_eq!(genome.modes[0].name, "New");
+
+        // Removing old mode 2 (now 3) drops its snapshot; mode 1 (now 2) still undoes
+        let (_, remap) = genome.remove_mode(3).unwrap();
+        history.remap_modes(&remap);
+        assert!(history.undo(&mut genome).is_some());
+        assert_eq!(genome.modes[2].opacity, original.modes[1].opacity);
+        assert_eq!(genome.modes[2].child_a.mode_number, ModeIndex::new(2));
+        assert!(history.undo(&mut genome).is_none());
+
+        // An entry about nothing but the removed mode disappears
+        apply_batch_edit(&mut genome, &only_two, &[edit(BatchField::Opacity, BatchOp::Set, 0.25)], &mut history);
+        let (_, remap) = genome.remove_mode(2).unwrap();
+        history.remap_modes(&remap);
+        assert_eq!(history.last_label(), None);
+    }
+
+    #[test]
+    fn test_click_selection() {
+        let mut selection = BTreeSet::new();
+        assert_eq!(click_mode_selection(&mut selection, 2, 5, false, true), 5);
+        assert_eq!(selection.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+        assert_eq!(click_mode_selection(&mut selection, 5, 3, true, false), 5);
+        assert_eq!(selection.iter().copied().collect::<Vec<_>>(), [2, 4, 5]);
+        assert_eq!(click_mode_selection(&mut selection, 5, 9, true, false), 9);
+        assert!(selection.contains(&9));
+        assert_eq!(click_mode_selection(&mut selection, 9, 1, false, false), 1);
+        assert!(selection.is_empty());
+
+        // Ctrl-clicking a second mode starts a selection with the primary one
+        click_mode_selection(&mut selection, 1, 7, true, false);
+        assert_eq!(selection.iter().copied().collect::<Vec<_>>(), [1, 7]);
+    }
+}
@@ -0,0 +1,452 @@
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+use crossbeam_channel::{Receiver, Sender};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::error::{BioSpheresError, FileOperation};
+use crate::genome::{GenomeData, ModeSettings};
+
+/// Plugin for the Open Genome browser's background directory scan
+pub struct GenomeBrowserPlugin;
+
+impl Plugin for GenomeBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GenomeBrowser>()
+            .add_systems(Update, (start_requested_scan, receive_scan_results).chain());
+    }
+}
+
+/// Folder (relative to the working directory) scanned until another one is chosen
+pub const DEFAULT_GENOMES_DIR: &str = "genomes";
+
+/// What the browser shows about a genome file, read on the scan task
+#[derive(Clone)]
+pub struct GenomeSummary {
+    pub name: String,
+    pub mode_count: usize,
+    /// The genome's notes field
+    pub notes: String,
+    /// Mode the genome starts in, drawn as the entry's thumbnail
+    pub initial_mode: Option<ModeSettings>,
+}
+
+impl GenomeSummary {
+    fn of(genome: GenomeData) -> Self {
+        let initial_mode = genome.resolve_mode(genome.initial_mode)
+            .or(genome.modes.first())
+            .cloned();
+        Self {
+            name: genome.name,
+            mode_count: genome.modes.len(),
+            notes: genome.notes,
+            initial_mode,
+        }
+    }
+}
+
+/// A `.json` file found under the genomes directory
+#[derive(Clone)]
+pub struct GenomeFileEntry {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    /// Error message for files that aren't valid genomes
+    pub summary: Result<GenomeSummary, String>,
+}
+
+impl GenomeFileEntry {
+    pub fn read(path: PathBuf) -> Self {
+        let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+        let summary = GenomeData::load_from_file(&path)
+            .map(GenomeSummary::of)
+            .map_err(|error| error.to_string());
+        Self { path, modified, summary }
+    }
+
+    /// Genome name, or the file name for unreadable files
+    pub fn display_name(&self) -> String {
+        match &self.summary {
+            Ok(summary) => summary.name.clone(),
+            Err(_) => self.file_stem(),
+        }
+    }
+
+    pub fn file_stem(&self) -> String {
+        self.path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    fn matches(&self, filter: &str) -> bool {
+        let contains = |text: &str| text.to_lowercase().contains(filter);
+        contains(&self.file_stem()) || self.summary.as_ref().is_ok_and(|summary| contains(&summary.name) || contains(&summary.notes))
+    }
+}
+
+/// Order of the browser's entries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GenomeSort {
+    #[default]
+    Name,
+    Modified,
+    ModeCount,
+}
+
+impl GenomeSort {
+    pub const ALL: [GenomeSort; 3] = [GenomeSort::Name, GenomeSort::Modified, GenomeSort::ModeCount];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GenomeSort::Name => "Name",
+            GenomeSort::Modified => "Last modified",
+            GenomeSort::ModeCount => "Mode count",
+        }
+    }
+}
+
+/// Rename, duplicate or delete waiting for the user's confirmation
+pub enum FileAction {
+    Rename { path: PathBuf, new_name: String },
+    Duplicate { path: PathBuf, new_name: String },
+    Delete { path: PathBuf },
+}
+
+/// Streamed from the scan task as the directory tree is walked
+pub enum ScanMessage {
+    Folder(PathBuf),
+    Entry(GenomeFileEntry),
+    Failed(BioSpheresError),
+    Done,
+}
+
+/// Genome files under the configured directory, filled in by a background scan
+#[derive(Resource)]
+pub struct GenomeBrowser {
+    /// Directory scanned recursively for `.json` genomes
+    pub root: PathBuf,
+    /// Folder being shown (`root` or one of its subfolders)
+    pub current_dir: PathBuf,
+    pub entries: Vec<GenomeFileEntry>,
+    /// Every folder under `root`, including empty ones
+    pub folders: BTreeSet<PathBuf>,
+    /// Case-insensitive text matched against names, file names and notes
+    pub filter: String,
+    pub sort: GenomeSort,
+    pub descending: bool,
+    pub pending_action: Option<FileAction>,
+    /// Set by the last scan when the directory couldn't be read
+    pub scan_error: Option<String>,
+    scan: Option<Receiver<ScanMessage>>,
+    scan_requested: bool,
+}
+
+impl Default for GenomeBrowser {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from(DEFAULT_GENOMES_DIR),
+            current_dir: PathBuf::from(DEFAULT_GENOMES_DIR),
+            entries: Vec::new(),
+            folders: BTreeSet::new(),
+            filter: String::new(),
+            sort: GenomeSort::default(),
+            descending: false,
+            pending_action: None,
+            scan_error: None,
+            scan: None,
+            scan_requested: false,
+        }
+    }
+}
+
+impl GenomeBrowser {
+    /// Scan the directory again on the next update
+    pub fn rescan(&mut self) {
+        self.scan_requested = true;
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some() || self.scan_requested
+    }
+
+    /// Switch to another genomes directory and scan it
+    pub fn set_root(&mut self, root: PathBuf) {
+        self.current_dir = root.clone();
+        self.root = root;
+        self.rescan();
+    }
+
+    /// Folders directly inside the current folder
+    pub fn subfolders(&self) -> Vec<&PathBuf> {
+        self.folders.iter().filter(|folder| folder.parent() == Some(self.current_dir.as_path())).collect()
+    }
+
+    /// Entries of the current folder, sorted; with a filter, matches anywhere below it
+    pub fn visible_entries(&self) -> Vec<&GenomeFileEntry> {
+        let filter = self.filter.trim().to_lowercase();
+        let mut entries: Vec<&GenomeFileEntry> = self.entries.iter()
+            .filter(|entry| if filter.is_empty() {
+                entry.path.parent() == Some(self.current_dir.as_path())
+            } else {
+                entry.path.starts_with(&self.current_dir) && entry.matches(&filter)
+            })
+            .collect();
+
+        let name_key = |entry: &GenomeFileEntry| entry.display_name().to_lowercase();
+        match self.sort {
+            GenomeSort::Name => entries.sort_by_cached_key(|entry| name_key(entry)),
+            GenomeSort::Modified => entries.sort_by_key(|entry| entry.modified),
+            GenomeSort::ModeCount => entries.sort_by_key(|entry| entry.summary.as_ref().map_or(0, |summary| summary.mode_count)),
+        }
+        if self.descending {
+            entries.reverse();
+        }
+        entries
+    }
+
+    /// Add a streamed scan result; returns false once the scan is over
+    pub fn apply_scan_message(&mut self, message: ScanMessage) -> bool {
+        match message {
+            ScanMessage::Folder(folder) => {
+                self.folders.insert(folder);
+            }
+            ScanMessage::Entry(entry) => self.entries.push(entry),
+            ScanMessage::Failed(error) => self.scan_error = Some(error.to_string()),
+            ScanMessage::Done => return false,
+        }
+        true
+    }
+
+    /// Carry out a confirmed file action and update the entries to match
+    pub fn perform(&mut self, action: FileAction) -> crate::error::Result<String> {
+        match action {
+            FileAction::Rename { path, new_name } => {
+                let target = rename_genome_file(&path, &new_name)?;
+                if let Some(entry) = self.entries.iter_mut().find(|entry| entry.path == path) {
+                    entry.path = target.clone();
+                }
+                Ok(format!("Renamed {} to {}", path.display(), target.display()))
+            }
+            FileAction::Duplicate { path, new_name } => {
+                let target = duplicate_genome_file(&path, &new_name)?;
+                self.entries.push(GenomeFileEntry::read(target.clone()));
+                Ok(format!("Duplicated {} as {}", path.display(), target.display()))
+            }
+            FileAction::Delete { path } => {
+                delete_genome_file(&path)?;
+                self.entries.retain(|entry| entry.path != path);
+                Ok(format!("Deleted {}", path.display()))
+            }
+        }
+    }
+}
+
+/// Walk `root` depth-first in name order, streaming folders and `.json` files
+///
+/// Stops early once the receiver is dropped (a newer scan replaced this one).
+pub fn scan_genome_dir(root: &Path, sender: &Sender<ScanMessage>) {
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let read = match std::fs::read_dir(&dir) {
+            Ok(read) => read,
+            Err(source) => {
+                // Unreadable subfolders are skipped; only a missing root is reported
+                if dir == root {
+                    let _ = sender.send(ScanMessage::Failed(BioSpheresError::Io {
+                        operation: FileOperation::Read,
+                        path: dir,
+                        source,
+                    }));
+                }
+                continue;
+            }
+        };
+        let mut paths: Vec<PathBuf> = read.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+        paths.sort();
+
+        let mut subfolders = Vec::new();
+        for path in paths {
+            let message = if path.is_dir() {
+                subfolders.push(path.clone());
+                ScanMessage::Folder(path)
+            } else if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+                ScanMessage::Entry(GenomeFileEntry::read(path))
+            } else {
+                continue;
+            };
+            if sender.send(message).is_err() {
+                return;
+            }
+        }
+        // Reversed so the stack visits subfolders in name order
+        stack.extend(subfolders.into_iter().rev());
+    }
+    let _ = sender.send(ScanMessage::Done);
+}
+
+/// Path of a sibling genome file named `new_name`, rejecting names that aren't plain file names
+fn sibling_path(path: &Path, new_name: &str) -> crate::error::Result<PathBuf> {
+    let new_name = new_name.trim();
+    let new_name = new_name.strip_suffix(".json").unwrap_or(new_name);
+    let invalid = |problem: String| BioSpheresError::Validation { what: "file name", problems: vec![problem] };
+    if new_name.is_empty() {
+        return Err(invalid("Name is empty".to_string()));
+    }
+    if new_name.contains(['/', '\\']) || new_name == "." || new_name == ".." {
+        return Err(invalid(format!("'{}' is not a plain file name", new_name)));
+    }
+    let target = path.with_file_name(format!("{}.json", new_name));
+    if target.exists() {
+        return Err(invalid(format!("{} already exists", target.display())));
+    }
+    Ok(target)
+}
+
+/// Rename a genome file within its folder, returning the new path
+pub fn rename_genome_file(path: &Path, new_name: &str) -> crate::error::Result<PathBuf> {
+    let target = sibling_path(path, new_name)?;
+    std::fs::rename(path, &target).map_err(|source| BioSpheresError::Io {
+        operation: FileOperation::Rename,
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(target)
+}
+
+/// Copy a genome file next to itself under `new_name`, returning the copy's path
+pub fn duplicate_genome_file(path: &Path, new_name: &str) -> crate::error::Result<PathBuf> {
+    let target = sibling_path(path, new_name)?;
+    std::fs::copy(path, &target).map_err(|source| BioSpheresError::Io {
+        operation: FileOperation::Copy,
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(target)
+}
+
+pub fn delete_genome_file(path: &Path) -> crate::error::Result<()> {
+    std::fs::remove_file(path).map_err(|source| BioSpheresError::Io {
+        operation: FileOperation::Delete,
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn start_requested_scan(mut browser: ResMut<GenomeBrowser>) {
+    if !std::mem::take(&mut browser.scan_requested) {
+        return;
+    }
+    browser.entries.clear();
+    browser.folders.clear();
+    browser.scan_error = None;
+    if !browser.current_dir.starts_with(&browser.root) {
+        browser.current_dir = browser.root.clone();
+    }
+
+    // Replacing the receiver stops a scan still in flight at its next send
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    browser.scan = Some(receiver);
+    let root = browser.root.clone();
+    IoTaskPool::get()
+        .spawn(async move { scan_genome_dir(&root, &sender) })
+        .detach();
+}
+
+fn receive_scan_results(mut browser: ResMut<GenomeBrowser>) {
+    let Some(receiver) = browser.scan.clone() else {
+        return;
+    };
+    for message in receiver.try_iter() {
+        if !browser.apply_scan_message(message) {
+            browser.scan = None;
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("biospheres_genome_browser_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn save_genome(path: &Path, name: &str, mode_count: usize) {
+        let mut genome = GenomeData::default();
+        genome.name = name.to_string();
+        genome.modes.truncate(mode_count);
+        genome.notes = format!("{} notes", name);
+        genome.save_to_file(path).unwrap();
+    }
+
+    fn scanned(root: &Path) -> GenomeBrowser {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        scan_genome_dir(root, &sender);
+        let mut browser = GenomeBrowser::default();
+        browser.set_root(root.to_path_buf());
+        for message in receiver.try_iter() {
+            browser.apply_scan_message(message);
+        }
+        browser
+    }
+
+    #[test]
+    fn test_scan_streams_nested_genomes_and_folders() {
+        let root = temp_dir("scan");
+        save_genome(&root.join("b.json"), "Beta", 3);
+        save_genome(&root.join("a.json"), "Alpha", 12);
+        std::fs::create_dir_all(root.join("rods/empty")).unwrap();
+        save_genome(&root.join("rods/rod.json"), "Rod", 2);
+        std::fs::write(root.join("broken.json"), "{").unwrap();
+        std::fs::write(root.join("readme.txt"), "not a genome").unwrap();
+
+        let mut browser = scanned(&root);
+        assert_eq!(browser.entries.len(), 4);
+        assert!(browser.folders.contains(&root.join("rods/empty")));
+        assert_eq!(browser.subfolders(), vec![&root.join("rods")]);
+
+        // Only the current folder, by name; unreadable files keep their file name
+        let names = |browser: &GenomeBrowser| browser.visible_entries().iter().map(|entry| entry.display_name()).collect::<Vec<_>>();
+        assert_eq!(names(&browser), vec!["Alpha", "Beta", "broken"]);
+        browser.sort = GenomeSort::ModeCount;
+        browser.descending = true;
+        assert_eq!(names(&browser), vec!["Alpha", "Beta", "broken"]);
+
+        // A filter searches the whole tree, notes included
+        browser.filter = "ROD NOTES".to_string();
+        assert_eq!(names(&browser), vec!["Rod"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_missing_root_reports_an_error() {
+        let browser = scanned(&std::env::temp_dir().join("biospheres_genome_browser_missing_dir"));
+        assert!(browser.entries.is_empty());
+        assert!(browser.scan_error.is_some());
+    }
+
+    #[test]
+    fn test_file_actions_update_entries() {
+        let root = temp_dir("actions");
+        save_genome(&root.join("a.json"), "Alpha", 2);
+        save_genome(&root.join("b.json"), "Beta", 2);
+        let mut browser = scanned(&root);
+
+        browser.perform(FileAction::Duplicate { path: root.join("a.json"), new_name: "a copy".to_string() }).unwrap();
+        browser.perform(FileAction::Rename { path: root.join("b.json"), new_name: "c.json".to_string() }).unwrap();
+        // Existing files and path-like names are refused without touching anything
+        assert!(browser.perform(FileAction::Rename { path: root.join("c.json"), new_name: "a".to_string() }).is_err());
+        assert!(browser.perform(FileAction::Rename { path: root.join("c.json"), new_name: "../escape".to_string() }).is_err());
+        browser.perform(FileAction::Delete { path: root.join("a.json") }).unwrap();
+
+        let mut files = browser.entries.iter().map(|entry| entry.file_stem()).collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, vec!["a copy", "c"]);
+        assert!(root.join("a copy.json").exists() && root.join("c.json").exists() && !root.join("a.json").exists());
+        assert!(browser.perform(FileAction::Delete { path: root.join("a.json") }).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,417 @@
+use bevy::prelude::*;
+use std::sync::Arc;
+use super::{ChildModeRule, GenomeData, ModeIndex, ModeSettings, UserDataInheritance};
+
+/// Physics-ready form of one mode, resolved once when the genome changes
+///
+/// Angles are converted to radians and directions, and clamps the per-tick stages used
+/// to apply on every cell are applied here instead. Floating point operations are the
+/// same ones the stages ran on [`ModeSettings`], so results are bit-identical.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledMode {
+    pub cell_type: i32,
+    /// Mass absorbed per second
+    pub nutrient_gain_rate: f32,
+    /// Mass above which nutrient gain stops (twice the split mass)
+    pub storage_cap: f32,
+    pub max_cell_size: f32,
+    /// Share of the parent's mass going to child A, clamped to 0..=1
+    pub split_ratio: f32,
+    pub nutrient_priority: f32,
+    pub prioritize_when_low: bool,
+    pub low_nutrient_threshold: f32,
+    /// Mass above which the low-nutrient boost releases (threshold times release factor)
+    pub low_nutrient_release: f32,
+    pub swim_force: f32,
+    pub taxis_channel: usize,
+    pub taxis_strength: f32,
+    /// Number of splits before the mode-after-splits applies; None for unlimited
+    pub split_limit: Option<i32>,
+    pub min_adhesions: usize,
+    pub max_adhesions: usize,
+    pub max_contact_pressure: f32,
+    /// Split direction in the cell's local frame
+    pub split_direction: Vec3,
+    /// Cosine of the largest split direction tilt; None without jitter
+    pub split_jitter_cos: Option<f32>,
+    pub spherical: bool,
+    /// Semi-axes of a cell of radius 1
+    pub unit_semi_axes: Vec3,
+    /// Orientation change of child A and child B relative to the parent
+    pub child_orientations: [Quat; 2],
+    /// Child modes by `[reached max splits][side]`
+    pub child_modes: [[(usize, ChildModeRule); 2]; 2],
+    /// Whether division bonds the two children to each other
+    pub bonds_children: bool,
+    pub user_data_inheritance: UserDataInheritance,
+}
+
+impl CompiledMode {
+    fn compile(genome: &GenomeData, index: usize, mode: &ModeSettings) -> Self {
+        let max_tilt = mode.split_direction_jitter_degrees.clamp(0.0, 180.0).to_radians();
+        let proportions = mode.shape_radii.max(Vec3::splat(super::MIN_SHAPE_PROPORTION));
+        let child_modes = |reached: bool| [0, 1].map(|side| genome.child_mode_with_rule(index, side, reached));
+        Self {
+            cell_type: mode.cell_type,
+            nutrient_gain_rate: mode.nutrient_gain_rate,
+            storage_cap: mode.split_mass * 2.0,
+            max_cell_size: mode.max_cell_size,
+            split_ratio: mode.split_ratio.clamp(0.0, 1.0),
+            nutrient_priority: mode.nutrient_priority,
+            prioritize_when_low: mode.prioritize_when_low,
+            low_nutrient_threshold: mode.low_nutrient_threshold,
+            low_nutrient_release: mode.low_nutrient_threshold * mode.low_nutrient_release_factor,
+            swim_force: mode.swim_force,
+            taxis_channel: mode.taxis_channel,
+            taxis_strength: mode.taxis_strength,
+            split_limit: (mode.max_splits >= 0).then_some(mode.max_splits),
+            min_adhesions: mode.min_adhesions as usize,
+            max_adhesions: mode.max_adhesions as usize,
+            max_contact_pressure: mode.max_contact_pressure,
+            split_direction: mode.split_direction(),
+            split_jitter_cos: if max_tilt <= 0.0 { None } else { Some(max_tilt.cos()) },
+            spherical: mode.is_spherical(),
+            unit_semi_axes: proportions / proportions.max_element(),
+            child_orientations: [mode.child_a.orientation, mode.child_b.orientation],
+            child_modes: [child_modes(false), child_modes(true)],
+            bonds_children: mode.parent_make_adhesion && mode.child_a.keep_adhesion && mode.child_b.keep_adhesion,
+            user_data_inheritance: mode.user_data_inheritance,
+        }
+    }
+
+    /// Flagellocytes with a thrust
+    pub fn swims(&self) -> bool {
+        self.cell_type == 1 && self.swim_force > 0.0
+    }
+
+    /// Radius of a cell of this mode holding `mass`
+    pub fn radius_for(&self, mass: f32) -> f32 {
+        mass.min(self.max_cell_size).clamp(0.5, 2.0)
+    }
+
+    pub fn semi_axes(&self, radius: f32) -> Vec3 {
+        self.unit_semi_axes * radius
+    }
+
+    /// Whether a cell that has split `split_count` times may not split again
+    pub fn reached_split_limit(&self, split_count: i32) -> bool {
+        self.split_limit.is_some_and(|limit| split_count >= limit)
+    }
+
+    /// Mode of child `side` (0 = A) and the rule that chose it, as [`GenomeData::child_mode_with_rule`]
+    pub fn child_mode(&self, side: usize, reached_max_splits: bool) -> (usize, ChildModeRule) {
+        self.child_modes[reached_max_splits as usize][side]
+    }
+
+    /// Split direction of one division, as [`ModeSettings::jittered_split_direction`]
+    pub fn jittered_split_direction(&self, cell_id: u32, tick: u64, seed: u64) -> Vec3 {
+        match self.split_jitter_cos {
+            Some(cos_max_tilt) => super::tilt_within_cap(self.split_direction, cos_max_tilt, cell_id, tick, seed),
+            None => self.split_direction,
+        }
+    }
+}
+
+/// Genome flattened for the physics stages, indexed by mode
+///
+/// Immutable once built and shared through an `Arc`, so the simulation threads can hold
+/// it without cloning the [`GenomeData`], which stays the editing and file model.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompiledGenome {
+    pub modes: Vec<CompiledMode>,
+    /// Adhesion settings per mode in the layout the adhesion force pass reads
+    pub adhesion: Vec<crate::cell::AdhesionSettings>,
+    /// Hash of the split timing fields each cell's split thresholds are resolved from
+    pub split_timing_hash: u64,
+}
+
+impl CompiledGenome {
+    pub fn compile(genome: &GenomeData) -> Self {
+        Self {
+            modes: genome.modes.iter()
+                .enumerate()
+                .map(|(index, mode)| CompiledMode::compile(genome, index, mode))
+                .collect(),
+            adhesion: genome.modes.iter().map(|mode| (&mode.adhesion_settings).into()).collect(),
+            split_timing_hash: Self::split_timing_hash(genome),
+        }
+    }
+
+    pub fn mode(&self, index: usize) -> Option<&CompiledMode> {
+        self.modes.get(index)
+    }
+
+    /// Hash of every genome setting [`CompiledGenome::compile`] reads
+    ///
+    /// One multiply per value, so checking it every step costs far less than comparing
+    /// or compiling the genome. A field `compile` starts reading must be added here too.
+    pub fn source_hash(genome: &GenomeData) -> u64 {
+        let mut hash = genome.modes.len() as u64;
+        let mut write = |value: u64| {
+            hash = (hash ^ value).wrapping_mul(0x0000_0100_0000_01b3);
+            hash ^= hash >> 29;
+        };
+        let index = |mode: ModeIndex| mode.raw() as u32 as u64;
+        for mode in &genome.modes {
+            let adhesion = &mode.adhesion_settings;
+            let floats = [
+                mode.nutrient_gain_rate, mode.split_mass, mode.max_cell_size, mode.split_ratio,
+                mode.split_interval, mode.split_phase_jitter,
+                mode.split_interval_min.unwrap_or(f32::NAN), mode.split_mass_min.unwrap_or(f32::NAN),
+                mode.nutrient_priority, mode.low_nutrient_threshold, mode.low_nutrient_release_factor,
+                mode.swim_force, mode.taxis_strength, mode.max_contact_pressure,
+                mode.parent_split_direction.x, mode.parent_split_direction.y,
+                mode.split_direction_jitter_degrees,
+                mode.shape_radii.x, mode.shape_radii.y, mode.shape_radii.z,
+                adhesion.break_force, adhesion.rest_length,
+                adhesion.linear_spring_stiffness, adhesion.linear_spring_damping,
+                adhesion.orientation_spring_stiffness, adhesion.orientation_spring_damping,
+                adhesion.max_angular_deviation,
+                adhesion.twist_constraint_stiffness, adhesion.twist_constraint_damping,
+            ];
+            let orientations = [mode.child_a.orientation, mode.child_b.orientation];
+            for value in floats.into_iter().chain(orientations.iter().flat_map(|q| q.to_array())) {
+                write(value.to_bits() as u64);
+            }
+            for value in [
+                mode.cell_type as u32 as u64,
+                mode.taxis_channel as u64,
+                mode.max_splits as u32 as u64,
+                mode.min_adhesions as u32 as u64,
+                mode.max_adhesions as u32 as u64,
+                index(mode.child_a.mode_number),
+                index(mode.child_b.mode_number),
+                index(mode.mode_a_after_splits),
+                index(mode.mode_b_after_splits),
+                mode.user_data_inheritance as u64,
+            ] {
+                write(value);
+            }
+            let flags = [
+                mode.prioritize_when_low,
+                mode.parent_make_adhesion,
+                mode.child_a.keep_adhesion,
+                mode.child_b.keep_adhesion,
+                adhesion.can_break,
+                adhesion.enable_twist_constraint,
+            ];
+            write(flags.iter().fold(0, |bits, &flag| bits << 1 | flag as u64));
+        }
+        hash
+    }
+
+    /// Hash of the split timing fields, so division only re-resolves thresholds on edits
+    fn split_timing_hash(genome: &GenomeData) -> u64 {
+        let mut hash = genome.modes.len() as u64;
+        for mode in &genome.modes {
+            for value in [
+                mode.split_interval.to_bits(),
+                mode.split_interval_min.map_or(u32::MAX, f32::to_bits),
+                mode.split_phase_jitter.to_bits(),
+                mode.split_mass.to_bits(),
+                mode.split_mass_min.map_or(u32::MAX, f32::to_bits),
+            ] {
+                hash = (hash ^ value as u64).wrapping_mul(0x0000_0100_0000_01b3);
+                hash ^= hash >> 29;
+            }
+        }
+        hash
+    }
+}
+
+/// Compiled form of the last genome a simulation stepped with
+///
+/// Keyed on [`CompiledGenome::source_hash`], so edits, phase overrides and staged
+/// genomes are all picked up on the next tick, while edits the physics doesn't read
+/// (names, colors, phases, run history) don't recompile.
+#[derive(Clone, Default)]
+pub struct CompiledGenomeCache {
+    source_hash: Option<u64>,
+    compiled: Arc<CompiledGenome>,
+}
+
+impl CompiledGenomeCache {
+    pub fn get(&mut self, genome: &GenomeData) -> Arc<CompiledGenome> {
+        let hash = CompiledGenome::source_hash(genome);
+        if self.source_hash != Some(hash) {
+            self.compiled = Arc::new(CompiledGenome::compile(genome));
+            self.source_hash = Some(hash);
+        }
+        Arc::clone(&self.compiled)
+    }
+
+    /// The compiled genome if it was built from `genome`
+    pub fn peek(&self, genome: &GenomeData) -> Option<&Arc<CompiledGenome>> {
+        (self.source_hash == Some(CompiledGenome::source_hash(genome))).then_some(&self.compiled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::cpu_physics::{division_step, physics_step_with_genome};
+    use crate::simulation::fingerprint::simulate_scene;
+
+    fn bundled_genomes() -> Vec<GenomeData> {
+        let mut genomes: Vec<GenomeData> = crate::simulation::scenario_presets::load_bundled_presets()
+            .into_iter()
+            .filter_map(|(_, result)| result.ok())
+            .map(|preset| preset.scene.genome)
+            .collect();
+        let mut varied = GenomeData::default();
+        for (index, mode) in varied.modes.iter_mut().enumerate() {
+            mode.split_direction_jitter_degrees = (index * 7) as f32;
+            mode.parent_split_direction = Vec2::new(index as f32 * 13.0 - 90.0, index as f32 * 29.0);
+            mode.max_splits = index as i32 % 4 - 1;
+            mode.shape_radii = Vec3::new(1.0, 0.3 + index as f32 * 0.02, 0.8);
+        }
+        genomes.push(varied);
+        genomes
+    }
+
+    #[test]
+    fn test_compiled_modes_match_the_genome() {
+        for genome in bundled_genomes() {
+            let compiled = CompiledGenome::compile(&genome);
+            assert_eq!(compiled.modes.len(), genome.modes.len());
+            for (index, (mode, flat)) in genome.modes.iter().zip(&compiled.modes).enumerate() {
+                assert_eq!(flat.split_direction, mode.split_direction());
+                for cell_id in [0, 7, 1234] {
+                    assert_eq!(
+                        flat.jittered_split_direction(cell_id, 99, 42),
+                        mode.jittered_split_direction(cell_id, 99, 42),
+                    );
+                }
+                for reached in [false, true] {
+                    for side in 0..2 {
+                        assert_eq!(flat.child_mode(side, reached), genome.child_mode_with_rule(index, side, reached));
+                    }
+                }
+                for radius in [0.5, 1.3] {
+                    assert_eq!(flat.semi_axes(radius), mode.semi_axes(radius));
+                }
+                for split_count in [0, 1, 5] {
+                    assert_eq!(flat.reached_split_limit(split_count), mode.max_splits >= 0 && split_count >= mode.max_splits);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cached_runs_match_runs_compiled_every_tick() {
+        let scenes: Vec<_> = crate::simulation::scenario_presets::load_bundled_presets()
+            .into_iter()
+            .filter(|(id, _)| id == "mitosis_basics" || id == "branching_colony")
+            .filter_map(|(_, result)| result.ok())
+            .map(|preset| preset.scene)
+            .collect();
+        assert_eq!(scenes.len(), 2);
+
+        for scene in &scenes {
+            let cached = simulate_scene(scene, 300);
+
+            // Dropping the cache before every tick rebuilds the compiled genome from scratch
+            let mut state = scene.to_initial_state(cached.capacity).to_canonical_state();
+            for tick in 0..300 {
+                let time = tick as f32 * scene.physics.fixed_timestep;
+                state.compiled_genome = CompiledGenomeCache::default();
+                physics_step_with_genome(&mut state, &scene.physics, &scene.genome, time, false);
+                state.compiled_genome = CompiledGenomeCache::default();
+                let capacity = state.capacity;
+                division_step(&mut state, &scene.genome, time, scene.physics.fixed_timestep, capacity, scene.rng_seed);
+            }
+            assert_eq!(state.fingerprint(), cached.fingerprint());
+        }
+    }
+
+    #[test]
+    fn test_cache_follows_genome_edits() {
+        let mut genome = GenomeData::default();
+        let mut cache = CompiledGenomeCache::default();
+        let first = cache.get(&genome);
+        assert!(Arc::ptr_eq(&first, &cache.get(&genome)), "an unchanged genome is not recompiled");
+
+        // Edits past the first mode used to slip past the adhesion settings hash
+        genome.modes[3].adhesion_settings.linear_spring_stiffness *= 2.0;
+        let edited = cache.get(&genome);
+        assert!(!Arc::ptr_eq(&first, &edited));
+        assert_ne!(edited.adhesion[3], first.adhesion[3]);
+        assert!(cache.peek(&genome).is_some());
+    }
+
+    #[test]
+    fn test_source_hash_covers_compiled_settings() {
+        let base = GenomeData::default();
+        let hash = CompiledGenome::source_hash(&base);
+        let compiled = CompiledGenome::compile(&base);
+        let edits: Vec<fn(&mut ModeSettings)> = vec![
+            |mode| mode.cell_type += 1,
+            |mode| mode.nutrient_gain_rate += 0.5,
+            |mode| mode.split_mass += 0.5,
+            |mode| mode.max_cell_size += 0.5,
+            |mode| mode.split_ratio = 0.3,
+            |mode| mode.nutrient_priority += 0.5,
+            |mode| mode.prioritize_when_low = !mode.prioritize_when_low,
+            |mode| mode.low_nutrient_release_factor += 0.5,
+            |mode| mode.swim_force += 0.5,
+            |mode| mode.taxis_channel += 1,
+            |mode| mode.max_splits += 2,
+            |mode| mode.max_adhesions += 1,
+            |mode| mode.max_contact_pressure = 5.0,
+            |mode| mode.parent_split_direction.y += 10.0,
+            |mode| mode.split_direction_jitter_degrees += 5.0,
+            |mode| mode.shape_radii.y = 0.5,
+            |mode| mode.child_b.orientation = Quat::from_rotation_x(0.3),
+            |mode| mode.child_a.mode_number = ModeIndex::new(2),
+            |mode| mode.parent_make_adhesion = !mode.parent_make_adhesion,
+            |mode| mode.user_data_inheritance = UserDataInheritance::Split,
+            |mode| mode.adhesion_settings.max_angular_deviation += 5.0,
+            |mode| mode.adhesion_settings.enable_twist_constraint = !mode.adhesion_settings.enable_twist_constraint,
+        ];
+        for (i, edit) in edits.into_iter().enumerate() {
+            let mut genome = base.clone();
+            edit(&mut genome.modes[1]);
+            assert_ne!(CompiledGenome::compile(&genome), compiled, "edit {} should change the compiled genome", i);
+            assert_ne!(CompiledGenome::source_hash(&genome), hash, "edit {} should change the source hash", i);
+        }
+
+        // Settings the physics doesn't read leave the cache alone
+        let mut renamed = base.clone();
+        renamed.modes[1].name.push_str(" (copy)");
+        renamed.record_run_at(crate::genome::RunKind::Preview, 1, 0);
+        assert_eq!(CompiledGenome::source_hash(&renamed), hash);
+    }
+
+    /// Per-tick cost of the genome-driven stages on the 40-mode default genome, compiling
+    /// once versus compiling every tick (what reading `GenomeData` directly amounted to)
+    /// Run with `cargo test --release -- --ignored --nocapture compiled_genome_benchmark`
+    #[test]
+    #[ignore = "timing benchmark"]
+    fn compiled_genome_benchmark() {
+        let genome = GenomeData::default();
+        let config = crate::simulation::PhysicsConfig::default();
+        let mut state = crate::simulation::cpu_physics::CanonicalState::new(4096);
+        for i in 0..4000 {
+            let position = Vec3::new((i % 16) as f32, (i / 16 % 16) as f32, (i / 256) as f32) * 1.05 - Vec3::splat(8.0);
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                0, i % 40, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+
+        let time = |state: &mut crate::simulation::cpu_physics::CanonicalState, recompile: bool| {
+            let started = std::time::Instant::now();
+            for tick in 0..100 {
+                if recompile {
+                    state.compiled_genome = CompiledGenomeCache::default();
+                }
+                physics_step_with_genome(state, &config, &genome, tick as f32 * config.fixed_timestep, true);
+            }
+            started.elapsed().as_secs_f64()
+        };
+        time(&mut state.clone(), false);
+        let recompiled = time(&mut state.clone(), true);
+        let cached = time(&mut state, false);
+        println!("4000 cells, 40 modes: compiled every tick {:.2} ms, compiled once {:.2} ms per tick",
+            recompiled * 10.0, cached * 10.0);
+        assert!(cached < recompiled);
+    }
+}
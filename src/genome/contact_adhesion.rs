@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use super::ModeIndex;
+
+/// New bonds a rule may form per tick unless edited
+pub const DEFAULT_MAX_NEW_BONDS_PER_TICK: u32 = 8;
+
+/// Probability a new chance rule starts with
+const DEFAULT_CHANCE: f32 = 0.5;
+
+/// Whether cells of `mode` bond to touching unconnected cells of `partner`
+///
+/// Rules are ordered: (A, B) lets A cells reach out to B cells. Either side of a contact
+/// may form the bond, so (A, B) alone is enough for A and B cells to stick together.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContactAdhesionRule {
+    pub mode: ModeIndex,
+    pub partner: ModeIndex,
+    /// Chance per tick that a touching pair bonds (1 = always)
+    pub probability: f32,
+    /// Bonds the rule may form per tick; later contacts wait for the next tick
+    pub max_new_bonds_per_tick: u32,
+}
+
+/// State of one matrix entry, as the editor's grid cycles through it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContactAdhesionState {
+    Off,
+    Always,
+    Chance,
+}
+
+impl ContactAdhesionState {
+    pub fn next(self) -> Self {
+        match self {
+            ContactAdhesionState::Off => ContactAdhesionState::Always,
+            ContactAdhesionState::Always => ContactAdhesionState::Chance,
+            ContactAdhesionState::Chance => ContactAdhesionState::Off,
+        }
+    }
+}
+
+/// Mode-pair matrix of adhesions formed by contact between unrelated cells
+///
+/// Stored sparsely; an empty matrix (the default) keeps adhesions to siblings at division.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContactAdhesionMatrix {
+    pub rules: Vec<ContactAdhesionRule>,
+}
+
+impl ContactAdhesionMatrix {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Position of the (mode, partner) rule in `rules`
+    pub fn rule_index(&self, mode: usize, partner: usize) -> Option<usize> {
+        self.rules.iter().position(|rule| rule.mode.index() == Some(mode) && rule.partner.index() == Some(partner))
+    }
+
+    pub fn rule(&self, mode: usize, partner: usize) -> Option<&ContactAdhesionRule> {
+        self.rule_index(mode, partner).map(|i| &self.rules[i])
+    }
+
+    pub fn rule_mut(&mut self, mode: usize, partner: usize) -> Option<&mut ContactAdhesionRule> {
+        self.rule_index(mode, partner).map(|i| &mut self.rules[i])
+    }
+
+    pub fn state(&self, mode: usize, partner: usize) -> ContactAdhesionState {
+        match self.rule(mode, partner) {
+            None => ContactAdhesionState::Off,
+            Some(rule) if rule.probability >= 1.0 => ContactAdhesionState::Always,
+            Some(_) => ContactAdhesionState::Chance,
+        }
+    }
+
+    /// Put the entry into `state`, keeping its budget while it stays on
+    pub fn set_state(&mut self, mode: usize, partner: usize, state: ContactAdhesionState) {
+        let probability = match state {
+            ContactAdhesionState::Off => {
+                self.rules.retain(|rule| !(rule.mode.index() == Some(mode) && rule.partner.index() == Some(partner)));
+                return;
+            }
+            ContactAdhesionState::Always => 1.0,
+            ContactAdhesionState::Chance => DEFAULT_CHANCE,
+        };
+        match self.rule_mut(mode, partner) {
+            Some(rule) => rule.probability = probability,
+            None => {
+                self.rules.push(ContactAdhesionRule {
+                    mode: ModeIndex::new(mode),
+                    partner: ModeIndex::new(partner),
+                    probability,
+                    max_new_bonds_per_tick: DEFAULT_MAX_NEW_BONDS_PER_TICK,
+                });
+                self.rules.sort_by_key(|rule| (rule.mode, rule.partner));
+            }
+        }
+    }
+
+    /// Advance the entry to its next state (off, always, chance)
+    pub fn cycle(&mut self, mode: usize, partner: usize) {
+        let next = self.state(mode, partner).next();
+        self.set_state(mode, partner, next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+
+    #[test]
+    fn test_entries_cycle_through_off_always_and_chance() {
+        let mut matrix = ContactAdhesionMatrix::default();
+        assert_eq!(matrix.state(2, 5), ContactAdhesionState::Off);
+
+        matrix.cycle(2, 5);
+        assert_eq!(matrix.state(2, 5), ContactAdhesionState::Always);
+        // Ordered: the reverse pair is a separate entry
+        assert_eq!(matrix.state(5, 2), ContactAdhesionState::Off);
+
+        matrix.rule_mut(2, 5).unwrap().max_new_bonds_per_tick = 3;
+        matrix.cycle(2, 5);
+        assert_eq!(matrix.state(2, 5), ContactAdhesionState::Chance);
+        assert_eq!(matrix.rule(2, 5).unwrap().max_new_bonds_per_tick, 3);
+
+        matrix.cycle(2, 5);
+        assert!(matrix.is_empty());
+    }
+
+    #[test]
+    fn test_empty_matrix_is_left_out_of_saved_genomes() {
+        let genome = GenomeData::default();
+        let json = serde_json::to_string(&genome).unwrap();
+        assert!(!json.contains("contact_adhesion"));
+        let loaded: GenomeData = serde_json::from_str(&json).unwrap();
+        assert!(loaded.contact_adhesion.is_empty());
+
+        let mut with_rule = GenomeData::default();
+        with_rule.contact_adhesion.cycle(0, 1);
+        let loaded: GenomeData = serde_json::from_str(&serde_json::to_string(&with_rule).unwrap()).unwrap();
+        assert_eq!(loaded.contact_adhesion, with_rule.contact_adhesion);
+    }
+}
@@ -0,0 +1,300 @@
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::path::{Path, PathBuf};
+use super::{AdhesionSettings, GenomeData, GenomeHistory, ModeSettings};
+
+/// Physics-relevant settings of a mode: its bonds' springs and limits and its shape
+///
+/// Never carries names, colors, child wiring or division rules, so applying it to a mode
+/// changes how the mode's cells hold together and collide and nothing else.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeMaterial {
+    pub adhesion_settings: AdhesionSettings,
+    pub max_adhesions: i32,
+    pub shape_radii: Vec3,
+}
+
+impl ModeMaterial {
+    pub fn of(mode: &ModeSettings) -> Self {
+        Self {
+            adhesion_settings: mode.adhesion_settings.clone(),
+            max_adhesions: mode.max_adhesions,
+            shape_radii: mode.shape_radii,
+        }
+    }
+
+    pub fn apply_to(&self, mode: &mut ModeSettings) {
+        mode.adhesion_settings = self.adhesion_settings.clone();
+        mode.max_adhesions = self.max_adhesions;
+        mode.shape_radii = self.shape_radii;
+    }
+}
+
+/// The preset a mode's material was applied from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialLink {
+    pub preset: String,
+    /// Follow later edits of the preset when it is re-synced (off = keep the applied values)
+    pub synced: bool,
+}
+
+/// A named material in the user's library
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterialPreset {
+    pub name: String,
+    pub material: ModeMaterial,
+}
+
+/// The user's material presets, shared by every genome (persisted as JSON next to the UI settings)
+#[derive(Resource, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterialLibrary {
+    pub presets: Vec<MaterialPreset>,
+}
+
+impl MaterialLibrary {
+    fn library_path() -> PathBuf {
+        PathBuf::from("material_presets.json")
+    }
+
+    pub fn load_from_file(path: &Path) -> crate::error::Result<Self> {
+        crate::error::read_json(path, "material presets")
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "material presets")
+    }
+
+    /// Save the library to the settings directory
+    pub fn save(&self) -> crate::error::Result<()> {
+        self.save_to_file(&Self::library_path())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MaterialPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// Store `preset`, replacing one of the same name
+    pub fn insert(&mut self, preset: MaterialPreset) {
+        match self.presets.iter_mut().find(|existing| existing.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// Store the material of `mode` as preset `name`
+    pub fn add_from_mode(&mut self, name: String, mode: &ModeSettings) {
+        self.insert(MaterialPreset { name, material: ModeMaterial::of(mode) });
+    }
+
+    /// Rename a preset; false when the name is taken or the preset doesn't exist
+    pub fn rename(&mut self, from: &str, to: String) -> bool {
+        if to.is_empty() || self.get(&to).is_some() {
+            return false;
+        }
+        match self.presets.iter_mut().find(|preset| preset.name == from) {
+            Some(preset) => {
+                preset.name = to;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<MaterialPreset> {
+        let index = self.presets.iter().position(|preset| preset.name == name)?;
+        Some(self.presets.remove(index))
+    }
+
+    /// Write one preset to a file for sharing
+    pub fn export_preset(&self, name: &str, path: &Path) -> crate::error::Result<()> {
+        match self.get(name) {
+            Some(preset) => crate::error::write_json(path, preset, "material preset"),
+            None => Ok(()),
+        }
+    }
+
+    /// Read a shared preset into the library (replacing one of the same name); returns its name
+    pub fn import_preset(&mut self, path: &Path) -> crate::error::Result<String> {
+        let preset: MaterialPreset = crate::error::read_json(path, "material preset")?;
+        let name = preset.name.clone();
+        self.insert(preset);
+        Ok(name)
+    }
+}
+
+impl GenomeData {
+    /// Apply `preset` to `modes` as one undoable edit, linking them to it; returns the modes changed
+    pub fn apply_material(&mut self, preset: &MaterialPreset, modes: impl IntoIterator<Item = usize>, history: &mut GenomeHistory) -> usize {
+        let link = MaterialLink { preset: preset.name.clone(), synced: true };
+        let mut before = Vec::new();
+        for index in modes {
+            let Some(mode) = self.modes.get_mut(index) else {
+                continue;
+            };
+            if ModeMaterial::of(mode) == preset.material && mode.material.as_ref() == Some(&link) {
+                continue;
+            }
+            before.push((index, mode.clone()));
+            preset.material.apply_to(mode);
+            mode.material = Some(link.clone());
+        }
+        let changed = before.len();
+        if changed > 0 {
+            history.record(format!("apply material '{}' to {} modes", preset.name, changed), before);
+        }
+        changed
+    }
+
+    /// Modes linked to preset `name`, with whether each follows re-syncs
+    pub fn material_users<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (usize, bool)> + 'a {
+        self.modes.iter().enumerate().filter_map(move |(index, mode)| {
+            mode.material.as_ref().filter(|link| link.preset == name).map(|link| (index, link.synced))
+        })
+    }
+
+    /// Bring the synced modes linked to `preset` up to date with it as one undoable edit;
+    /// returns the modes changed
+    pub fn resync_material(&mut self, preset: &MaterialPreset, history: &mut GenomeHistory) -> usize {
+        let synced: Vec<usize> = self.material_users(&preset.name)
+            .filter(|(_, synced)| *synced)
+            .map(|(index, _)| index)
+            .collect();
+        let mut before = Vec::new();
+        for index in synced {
+            let mode = &mut self.modes[index];
+            if ModeMaterial::of(mode) != preset.material {
+                before.push((index, mode.clone()));
+                preset.material.apply_to(mode);
+            }
+        }
+        let changed = before.len();
+        if changed > 0 {
+            history.record(format!("re-sync material '{}' ({} modes)", preset.name, changed), before);
+        }
+        changed
+    }
+
+    /// Point the links of a renamed preset at its new name
+    pub fn rename_material_links(&mut self, from: &str, to: &str) {
+        for link in self.modes.iter_mut().filter_map(|mode| mode.material.as_mut()) {
+            if link.preset == from {
+                link.preset = to.to_string();
+            }
+        }
+    }
+}
+
+/// Load the user's material presets
+pub fn load_material_library_on_startup(mut library: ResMut<MaterialLibrary>) {
+    let path = MaterialLibrary::library_path();
+    if !path.exists() {
+        return;
+    }
+    match MaterialLibrary::load_from_file(&path) {
+        Ok(loaded) => {
+            info!("Loaded {} material presets from {:?}", loaded.presets.len(), path);
+            *library = loaded;
+        }
+        Err(e) => warn!("{}, starting with an empty material library", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeIndex;
+
+    fn genome() -> GenomeData {
+        GenomeData {
+            modes: ["Stem", "Leaf", "Root"].iter().enumerate()
+                .map(|(index, name)| ModeSettings::new_self_splitting(index, name.to_string()))
+                .collect(),
+            ..GenomeData::default()
+        }
+    }
+
+    fn rubber() -> MaterialPreset {
+        let mut mode = ModeSettings::new_self_splitting(0, "Source".to_string());
+        mode.adhesion_settings.linear_spring_stiffness = 20.0;
+        mode.adhesion_settings.break_force = 80.0;
+        mode.max_adhesions = 4;
+        mode.shape_radii = Vec3::new(1.0, 0.8, 0.8);
+        let mut library = MaterialLibrary::default();
+        library.add_from_mode("Rubber".to_string(), &mode);
+        library.presets[0].clone()
+    }
+
+    #[test]
+    fn test_apply_changes_only_physics_and_undoes() {
+        let mut genome = genome();
+        genome.modes[1].color = Vec3::new(0.2, 0.9, 0.3);
+        genome.modes[1].child_a.mode_number = ModeIndex::new(2);
+        genome.modes[1].split_interval = 7.0;
+        let original = genome.modes[1].clone();
+        let mut history = GenomeHistory::default();
+        let preset = rubber();
+
+        assert_eq!(genome.apply_material(&preset, [1, 2, 9], &mut history), 2);
+        let mode = &genome.modes[1];
+        assert!(ModeMaterial::of(mode) == preset.material);
+        assert_eq!(mode.material, Some(MaterialLink { preset: "Rubber".to_string(), synced: true }));
+        assert_eq!((mode.name.as_str(), mode.color, mode.child_a.mode_number), ("Leaf", original.color, ModeIndex::new(2)));
+        assert_eq!(mode.split_interval, 7.0);
+        assert!(genome.modes[0].material.is_none());
+
+        // Applying again changes nothing and records nothing
+        assert_eq!(genome.apply_material(&preset, [1, 2], &mut history), 0);
+        assert!(history.undo(&mut genome).is_some());
+        assert!(genome.modes[1] == original);
+        assert!(history.undo(&mut genome).is_none());
+    }
+
+    #[test]
+    fn test_resync_follows_preset_edits_except_opted_out_modes() {
+        let mut genome = genome();
+        let mut history = GenomeHistory::default();
+        let mut preset = rubber();
+        genome.apply_material(&preset, [0, 1, 2], &mut history);
+        genome.modes[2].material.as_mut().unwrap().synced = false;
+        // A local tweak on a synced mode is overwritten by the next re-sync
+        genome.modes[0].adhesion_settings.rest_length = 2.0;
+
+        preset.material.adhesion_settings.linear_spring_stiffness = 300.0;
+        assert_eq!(genome.resync_material(&preset, &mut history), 2);
+        assert_eq!(genome.modes[0].adhesion_settings.linear_spring_stiffness, 300.0);
+        assert_eq!(genome.modes[0].adhesion_settings.rest_length, preset.material.adhesion_settings.rest_length);
+        assert_eq!(genome.modes[1].adhesion_settings.linear_spring_stiffness, 300.0);
+        assert_eq!(genome.modes[2].adhesion_settings.linear_spring_stiffness, 20.0);
+        assert_eq!(genome.resync_material(&preset, &mut history), 0);
+        assert_eq!(genome.material_users("Rubber").collect::<Vec<_>>(), [(0, true), (1, true), (2, false)]);
+
+        history.undo(&mut genome);
+        assert_eq!(genome.modes[1].adhesion_settings.linear_spring_stiffness, 20.0);
+
+        let mut library = MaterialLibrary { presets: vec![preset] };
+        assert!(library.rename("Rubber", "Gum".to_string()));
+        assert!(!library.rename("Missing", "Other".to_string()));
+        genome.rename_material_links("Rubber", "Gum");
+        assert_eq!(genome.material_users("Gum").count(), 3);
+    }
+
+    #[test]
+    fn test_presets_carry_no_identity_or_wiring() {
+        let preset = rubber();
+        let json = serde_json::to_value(&preset).unwrap();
+        let material = json["material"].as_object().unwrap();
+        let fields: Vec<&str> = material.keys().map(String::as_str).collect();
+        assert_eq!(fields, ["adhesion_settings", "max_adhesions", "shape_radii"]);
+        for identity in ["name", "default_name", "color", "child_a", "child_b", "mode_a_after_splits", "split_interval"] {
+            assert!(!material.contains_key(identity), "{}", identity);
+        }
+
+        let path = std::env::temp_dir().join(format!("biospheres_material_{}.json", std::process::id()));
+        let library = MaterialLibrary { presets: vec![preset.clone()] };
+        library.export_preset("Rubber", &path).unwrap();
+        let mut imported = MaterialLibrary::default();
+        assert_eq!(imported.import_preset(&path).unwrap(), "Rubber");
+        std::fs::remove_file(&path).ok();
+        assert!(imported.presets == [preset]);
+    }
+}
@@ -1,8 +1,34 @@
 use bevy::prelude::*;
 use serde::{Serialize, Deserialize};
 
+pub mod adhesion_propagation;
+pub mod batch_edit;
+pub mod browser;
+pub mod compiled;
+pub mod contact_adhesion;
+pub mod materials;
+pub mod mode_index;
+pub mod mode_remap;
 pub mod node_graph;
+pub mod palette;
+pub mod phases;
+pub mod population_forecast;
+pub mod run_history;
+pub mod templates;
+pub use adhesion_propagation::{adhesion_mismatches, copy_adhesion_settings, AdhesionCopyTarget, AdhesionMismatch};
+pub use batch_edit::{BatchField, BatchFieldEdit, BatchOp, GenomeHistory};
+pub use browser::{GenomeBrowser, GenomeBrowserPlugin};
+pub use compiled::{CompiledGenome, CompiledGenomeCache, CompiledMode};
+pub use contact_adhesion::{ContactAdhesionMatrix, ContactAdhesionRule, ContactAdhesionState};
+pub use materials::{MaterialLibrary, MaterialLink, MaterialPreset, ModeMaterial};
+pub use mode_index::ModeIndex;
+pub use mode_remap::{ModeRemap, RemovedModePolicy};
 pub use node_graph::GenomeNodeGraph;
+pub use palette::{CellPattern, ColorPalette, PatternKind};
+pub use phases::{GenomePhase, ModeOverride, PhaseCondition, PhasedGenome};
+pub use population_forecast::{forecast_populations, ForecastOutlook, GrowthCycle, PopulationForecast};
+pub use run_history::{GenomeMetadata, RunKind, RunRecord};
+pub use templates::{ModeArchetype, ModeTemplates, TemplateChoice};
 
 /// Plugin for genome management
 pub struct GenomePlugin;
@@ -11,7 +37,13 @@ impl Plugin for GenomePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GenomeLibrary>()
             .init_resource::<CurrentGenome>()
-            .init_resource::<GenomeNodeGraph>();
+            .init_resource::<GenomeNodeGraph>()
+            .init_resource::<ModeTemplates>()
+            .init_resource::<GenomeHistory>()
+            .init_resource::<MaterialLibrary>()
+            .add_systems(Startup, (templates::load_mode_templates_on_startup, materials::load_material_library_on_startup))
+            .add_systems(Update, batch_edit::clear_history_on_replace)
+            .add_plugins(GenomeBrowserPlugin);
     }
 }
 
@@ -35,6 +67,23 @@ pub struct CurrentGenome {
     pub selected_mode_index: i32,
     /// Whether to highlight cells of the selected mode with a pulsing glow
     pub show_mode_glow: bool,
+    /// Set when the genome was sampled from a cell in the world
+    pub sampled_from: Option<GenomeProvenance>,
+    /// Genome as of the last save, load or sample (for unsaved-changes checks)
+    saved_genome: GenomeData,
+    /// Most recent explicit save to a file (archived by experiment sessions)
+    last_save: Option<GenomeSave>,
+    /// Increases every time the genome is replaced (see [`GenomeHistory`])
+    generation: u64,
+}
+
+/// Genome written to a file by an explicit save
+#[derive(Clone)]
+pub struct GenomeSave {
+    /// Increases by one with every save
+    pub serial: u64,
+    pub path: std::path::PathBuf,
+    pub genome: GenomeData,
 }
 
 impl Default for CurrentGenome {
@@ -43,10 +92,83 @@ impl Default for CurrentGenome {
             genome: GenomeData::default(),
             selected_mode_index: 0,
             show_mode_glow: true,
+            sampled_from: None,
+            saved_genome: GenomeData::default(),
+            last_save: None,
+            generation: 0,
+        }
+    }
+}
+
+impl CurrentGenome {
+    /// Whether the genome was edited since it was last saved, loaded or sampled
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.genome != self.saved_genome
+    }
+
+    /// Record the current genome as saved
+    pub fn mark_saved(&mut self) {
+        self.saved_genome = self.genome.clone();
+    }
+
+    /// Record that the current genome was explicitly saved to `path`
+    pub fn record_save(&mut self, path: &std::path::Path) {
+        self.mark_saved();
+        let serial = self.last_save.as_ref().map_or(1, |save| save.serial + 1);
+        self.last_save = Some(GenomeSave {
+            serial,
+            path: path.to_path_buf(),
+            genome: self.genome.clone(),
+        });
+    }
+
+    /// Most recent explicit save, if any
+    pub fn last_save(&self) -> Option<&GenomeSave> {
+        self.last_save.as_ref()
+    }
+
+    /// Throwaway copy for running the editor panels on a derived genome
+    pub fn scratch(genome: GenomeData, selected_mode_index: i32) -> Self {
+        Self {
+            saved_genome: genome.clone(),
+            genome,
+            selected_mode_index,
+            show_mode_glow: false,
+            sampled_from: None,
+            last_save: None,
+            generation: 0,
+        }
+    }
+
+    /// Number of times the genome was replaced; undo history belongs to one generation
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Replace the genome with one loaded from a file or sampled from a cell
+    ///
+    /// Mode references pointing past the last mode are logged here, once per load; the
+    /// simulation falls back as described on [`GenomeData::child_mode`].
+    pub fn replace(&mut self, genome: GenomeData, selected_mode_index: i32, sampled_from: Option<GenomeProvenance>) {
+        for problem in genome.mode_reference_problems() {
+            warn!("Genome \"{}\": {}", genome.name, problem);
         }
+        self.genome = genome;
+        self.selected_mode_index = selected_mode_index;
+        self.sampled_from = sampled_from;
+        self.generation += 1;
+        self.mark_saved();
     }
 }
 
+/// Where a sampled genome came from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenomeProvenance {
+    pub cell_id: u32,
+    /// Simulation time of the sample
+    pub time: f32,
+}
+
 /// Adhesion configuration
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdhesionSettings {
@@ -76,7 +198,7 @@ impl Default for AdhesionSettings {
             max_angular_deviation: 0.0,
             twist_constraint_stiffness: 2.0,
             twist_constraint_damping: 0.5,
-            enable_twist_constraint: false,  // Disabled by default - can cause anchors to appear to "follow" the connection
+            enable_twist_constraint: true,  // Saved genomes store this explicitly, so only new modes pick it up
         }
     }
 }
@@ -84,7 +206,7 @@ impl Default for AdhesionSettings {
 /// Child settings for mode transitions
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChildSettings {
-    pub mode_number: i32,
+    pub mode_number: ModeIndex,
     pub orientation: Quat,
     pub keep_adhesion: bool,
     pub enable_angle_snapping: bool,
@@ -106,7 +228,7 @@ pub struct ChildSettings {
 impl Default for ChildSettings {
     fn default() -> Self {
         Self {
-            mode_number: 0,
+            mode_number: ModeIndex::new(0),
             orientation: Quat::IDENTITY,
             keep_adhesion: true,
             enable_angle_snapping: true,
@@ -129,6 +251,8 @@ pub struct ModeSettings {
     pub opacity: f32, // Cell transparency (0.0 = fully transparent, 1.0 = fully opaque)
     #[serde(default)]
     pub emissive: f32, // Emissive glow intensity (0.0 = no glow, 1.0+ = bright glow)
+    #[serde(default)]
+    pub pattern: Option<CellPattern>, // Surface pattern drawn over the color (None = plain)
 
     // Cell type
     pub cell_type: i32,
@@ -141,21 +265,53 @@ pub struct ModeSettings {
     pub split_interval: f32,
     #[serde(default)]
     pub split_interval_min: Option<f32>, // If Some, split_interval is the max and this is the min for random range
+    #[serde(default)]
+    pub split_phase_jitter: f32, // Per-cell shift of the division time, as a fraction of the interval (0 = lineages divide in step)
     pub nutrient_gain_rate: f32, // Mass gained per second (for Test cells)
     pub max_cell_size: f32, // Maximum visual size (1.0 to 2.0 units)
     pub split_ratio: f32, // Ratio of parent mass going to Child A (0.0 to 1.0, default 0.5 for 50/50 split)
     pub nutrient_priority: f32, // Priority for nutrient transport (0.1 to 10.0, default 1.0)
     pub prioritize_when_low: bool, // When enabled, priority increases when nutrients are low to prevent death
+    #[serde(default = "default_low_nutrient_threshold")]
+    pub low_nutrient_threshold: f32, // Mass below which the low-nutrient priority boost engages
+    #[serde(default = "default_low_nutrient_release_factor")]
+    pub low_nutrient_release_factor: f32, // Boost releases once mass recovers above threshold * factor
     pub parent_split_direction: Vec2, // pitch, yaw in degrees
+    #[serde(default)]
+    pub split_direction_jitter_degrees: f32, // Random tilt of each division's split direction, up to this many degrees (0 = exact)
+    #[serde(default = "default_max_contact_pressure", with = "infinite_as_none")]
+    pub max_contact_pressure: f32, // Contact inhibition: no division while summed collision overlap exceeds this (infinite = off)
     pub max_adhesions: i32,
     pub min_adhesions: i32, // Minimum number of connections required before cell can split
     pub enable_parent_angle_snapping: bool,
     pub max_splits: i32, // Maximum number of times a cell can split (1-20, or -1 for infinite). Split count resets to 0 when switching modes
-    pub mode_a_after_splits: i32, // Mode that Child A transitions to when max_splits is reached (-1 = use normal child_a mode)
-    pub mode_b_after_splits: i32, // Mode that Child B transitions to when max_splits is reached (-1 = use normal child_b mode)
+    pub mode_a_after_splits: ModeIndex, // Mode that Child A transitions to when max_splits is reached (NONE = use normal child_a mode)
+    pub mode_b_after_splits: ModeIndex, // Mode that Child B transitions to when max_splits is reached (NONE = use normal child_b mode)
     
     // Flagellocyte settings
     pub swim_force: f32, // Forward thrust force (0.0 to 1.0, for Flagellocyte cells)
+    #[serde(default)]
+    pub taxis_channel: usize, // Chemical channel whose gradient steers swimming
+    #[serde(default)]
+    pub taxis_strength: f32, // Swim bias up (+) or down (-) the taxis gradient (0 = no taxis)
+
+    // Chemistry settings (per chemical field channel)
+    #[serde(default)]
+    pub secretion_rate: [f32; 2], // Substance added to the cell's voxel per second
+    #[serde(default)]
+    pub absorption_rate: [f32; 2], // Substance removed from the cell's voxel per second
+
+    // Shape settings
+    #[serde(default = "default_shape_radii")]
+    pub shape_radii: Vec3, // Ellipsoid proportions in the cell's local frame (splat(1.0) = sphere)
+
+    // User data settings
+    #[serde(default)]
+    pub user_data_inheritance: UserDataInheritance, // How children receive the parent's user data channels
+
+    // Adhesion inheritance settings
+    #[serde(default)]
+    pub equatorial_bonds: EquatorialBondRule, // Which child keeps bonds in the equatorial band at division
 
     // Child settings
     pub child_a: ChildSettings,
@@ -163,17 +319,111 @@ pub struct ModeSettings {
 
     // Adhesion settings
     pub adhesion_settings: AdhesionSettings,
+
+    // Material preset the physics settings were applied from (None = set by hand)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<MaterialLink>,
+}
+
+/// How a dividing cell's user data channels reach its children
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UserDataInheritance {
+    /// Both children get the parent's values
+    #[default]
+    Copy,
+    /// Both children start at zero
+    Zero,
+    /// Values are divided between the children like mass (by split ratio)
+    Split,
+}
+
+impl UserDataInheritance {
+    pub const ALL: [UserDataInheritance; 3] = [
+        UserDataInheritance::Copy,
+        UserDataInheritance::Zero,
+        UserDataInheritance::Split,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            UserDataInheritance::Copy => "Copy",
+            UserDataInheritance::Zero => "Zero",
+            UserDataInheritance::Split => "Split by ratio",
+        }
+    }
+}
+
+/// Which child inherits a bond in the equatorial band (zone C) when the parent divides
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquatorialBondRule {
+    /// Only child A keeps the bond (child B if A doesn't keep adhesions)
+    PreferA,
+    /// Only child B keeps the bond (child A if B doesn't keep adhesions)
+    PreferB,
+    /// Every child that keeps adhesions gets its own copy of the bond
+    #[default]
+    Duplicate,
+    /// The bond breaks at division
+    Drop,
+}
+
+impl EquatorialBondRule {
+    pub const ALL: [EquatorialBondRule; 4] = [
+        EquatorialBondRule::PreferA,
+        EquatorialBondRule::PreferB,
+        EquatorialBondRule::Duplicate,
+        EquatorialBondRule::Drop,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EquatorialBondRule::PreferA => "Prefer A",
+            EquatorialBondRule::PreferB => "Prefer B",
+            EquatorialBondRule::Duplicate => "Duplicate",
+            EquatorialBondRule::Drop => "Drop",
+        }
+    }
+}
+
+fn default_low_nutrient_threshold() -> f32 {
+    0.6
+}
+
+fn default_low_nutrient_release_factor() -> f32 {
+    1.25
+}
+
+fn default_max_contact_pressure() -> f32 {
+    f32::INFINITY
+}
+
+fn default_shape_radii() -> Vec3 {
+    Vec3::ONE
+}
+
+/// Serialize an unbounded limit as null, since JSON has no infinity
+mod infinite_as_none {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        value.is_finite().then_some(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(Option::<f32>::deserialize(deserializer)?.unwrap_or(f32::INFINITY))
+    }
 }
 
 impl ModeSettings {
     /// Create a new mode that splits back to itself
-    pub fn new_self_splitting(mode_index: i32, name: String) -> Self {
+    pub fn new_self_splitting(mode_index: usize, name: String) -> Self {
         Self {
             default_name: name.clone(),
             name,
             color: Vec3::new(1.0, 1.0, 1.0),
             opacity: 1.0, // Default: fully opaque
             emissive: 0.0, // Default: no glow
+            pattern: None,
             cell_type: 0,
             parent_make_adhesion: false,
             split_mass: 1.5,
@@ -185,23 +435,36 @@ impl ModeSettings {
             split_ratio: 0.5, // Default: 50/50 split
             nutrient_priority: 1.0, // Default: neutral priority
             prioritize_when_low: true, // Default: protect cells from death
+            low_nutrient_threshold: default_low_nutrient_threshold(),
+            low_nutrient_release_factor: default_low_nutrient_release_factor(),
             parent_split_direction: Vec2::ZERO,
+            split_direction_jitter_degrees: 0.0, // Every division splits along the same direction
+            split_phase_jitter: 0.0, // Cells of a generation divide on the same tick
+            max_contact_pressure: default_max_contact_pressure(), // No contact inhibition by default
             max_adhesions: 20,
             min_adhesions: 0, // No minimum by default
             enable_parent_angle_snapping: true,
             max_splits: -1, // Infinite by default
-            mode_a_after_splits: -1, // Use normal child_a mode by default
-            mode_b_after_splits: -1, // Use normal child_b mode by default
+            mode_a_after_splits: ModeIndex::NONE, // Use normal child_a mode by default
+            mode_b_after_splits: ModeIndex::NONE, // Use normal child_b mode by default
             swim_force: 0.5, // Default swim force for flagellocytes
+            taxis_channel: 0,
+            taxis_strength: 0.0, // No taxis by default
+            secretion_rate: [0.0; 2],
+            absorption_rate: [0.0; 2],
+            shape_radii: default_shape_radii(), // Sphere by default
+            user_data_inheritance: UserDataInheritance::Copy, // Children carry the parent's markers
+            equatorial_bonds: EquatorialBondRule::Duplicate, // Equatorial bonds go to both children
             child_a: ChildSettings {
-                mode_number: mode_index,
+                mode_number: ModeIndex::new(mode_index),
                 ..Default::default()
             },
             child_b: ChildSettings {
-                mode_number: mode_index,
+                mode_number: ModeIndex::new(mode_index),
                 ..Default::default()
             },
             adhesion_settings: AdhesionSettings::default(),
+            material: None,
         }
     }
 
@@ -236,8 +499,80 @@ impl ModeSettings {
             None => self.split_interval,
         }
     }
+
+    /// Split interval of a cell with its phase offset applied
+    ///
+    /// `split_phase_jitter` shifts each cell's division by up to half that fraction of its
+    /// interval either way, picked from the cell's id and the seed, so colonies drift out
+    /// of step while the average interval stays the same. Never-splitting intervals are kept.
+    pub fn get_phased_split_interval(&self, cell_id: u32, tick: u64, seed: u64) -> f32 {
+        let interval = self.get_split_interval(cell_id, tick, seed);
+        let jitter = self.split_phase_jitter.clamp(0.0, 1.0);
+        if jitter <= 0.0 || interval > NEVER_SPLIT_INTERVAL {
+            return interval;
+        }
+        let phase = crate::simulation::deterministic_random(cell_id, 0, seed, 4) - 0.5;
+        interval + phase * jitter * interval
+    }
+
+    /// Split direction of this mode in the parent's local frame, without jitter
+    pub fn split_direction(&self) -> Vec3 {
+        let pitch = self.parent_split_direction.x.to_radians();
+        let yaw = self.parent_split_direction.y.to_radians();
+        Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z
+    }
+
+    /// Split direction of one division in the parent's local frame, tilted by up to
+    /// `split_direction_jitter_degrees` using deterministic randomness
+    ///
+    /// Directions are uniform over the cone around the mode's split direction.
+    ///
+    /// # Arguments
+    /// * `cell_id` - Unique ID of the dividing cell
+    /// * `tick` - Tick of the division
+    /// * `seed` - Global random seed
+    pub fn jittered_split_direction(&self, cell_id: u32, tick: u64, seed: u64) -> Vec3 {
+        let direction = self.split_direction();
+        let max_tilt = self.split_direction_jitter_degrees.clamp(0.0, 180.0).to_radians();
+        if max_tilt <= 0.0 {
+            return direction;
+        }
+        tilt_within_cap(direction, max_tilt.cos(), cell_id, tick, seed)
+    }
+
+    /// Whether cells of this mode are spheres (all shape proportions equal)
+    pub fn is_spherical(&self) -> bool {
+        self.shape_radii.x == self.shape_radii.y && self.shape_radii.y == self.shape_radii.z
+    }
+
+    /// Semi-axes of a cell of this mode with the given radius
+    ///
+    /// The longest semi-axis equals the radius, so the radius stays the cell's bounding
+    /// sphere for broad-phase collision, picking and the boundary.
+    pub fn semi_axes(&self, radius: f32) -> Vec3 {
+        let proportions = self.shape_radii.max(Vec3::splat(MIN_SHAPE_PROPORTION));
+        proportions / proportions.max_element() * radius
+    }
+}
+
+/// `direction` tilted to a deterministic random point of the spherical cap around it
+/// whose edge is at `cos_max_tilt`
+pub(crate) fn tilt_within_cap(direction: Vec3, cos_max_tilt: f32, cell_id: u32, tick: u64, seed: u64) -> Vec3 {
+    // Uniform over the spherical cap: cos(tilt) uniform in [cos(max_tilt), 1]
+    let u = crate::simulation::deterministic_random(cell_id, tick, seed, 2);
+    let around = crate::simulation::deterministic_random(cell_id, tick, seed, 3) * std::f32::consts::TAU;
+    let tilt = (1.0 - u * (1.0 - cos_max_tilt)).clamp(-1.0, 1.0).acos();
+    let perpendicular = direction.any_orthonormal_vector();
+    let axis = Quat::from_axis_angle(direction, around) * perpendicular;
+    (Quat::from_axis_angle(axis, tilt) * direction).normalize()
 }
 
+/// Split intervals above this many seconds mean the mode never divides
+pub const NEVER_SPLIT_INTERVAL: f32 = 59.0;
+
+/// Smallest ellipsoid proportion, keeping cells from collapsing into discs or needles
+pub const MIN_SHAPE_PROPORTION: f32 = 0.1;
+
 impl Default for ModeSettings {
     fn default() -> Self {
         Self {
@@ -246,6 +581,7 @@ impl Default for ModeSettings {
             color: Vec3::new(1.0, 1.0, 1.0),
             opacity: 1.0, // Default: fully opaque
             emissive: 0.0, // Default: no glow
+            pattern: None,
             cell_type: 0,
             parent_make_adhesion: false,
             split_mass: 1.5,
@@ -257,17 +593,51 @@ impl Default for ModeSettings {
             split_ratio: 0.5, // Default: 50/50 split
             nutrient_priority: 1.0, // Default: neutral priority
             prioritize_when_low: true, // Default: protect cells from death
+            low_nutrient_threshold: default_low_nutrient_threshold(),
+            low_nutrient_release_factor: default_low_nutrient_release_factor(),
             parent_split_direction: Vec2::ZERO,
+            split_direction_jitter_degrees: 0.0, // Every division splits along the same direction
+            split_phase_jitter: 0.0, // Cells of a generation divide on the same tick
+            max_contact_pressure: default_max_contact_pressure(), // No contact inhibition by default
             max_adhesions: 20,
             min_adhesions: 0, // No minimum by default
             enable_parent_angle_snapping: true,
             max_splits: -1, // Infinite by default
-            mode_a_after_splits: -1, // Use normal child_a mode by default
-            mode_b_after_splits: -1, // Use normal child_b mode by default
+            mode_a_after_splits: ModeIndex::NONE, // Use normal child_a mode by default
+            mode_b_after_splits: ModeIndex::NONE, // Use normal child_b mode by default
             swim_force: 0.5, // Default swim force for flagellocytes
+            taxis_channel: 0,
+            taxis_strength: 0.0, // No taxis by default
+            secretion_rate: [0.0; 2],
+            absorption_rate: [0.0; 2],
+            shape_radii: default_shape_radii(), // Sphere by default
+            user_data_inheritance: UserDataInheritance::Copy, // Children carry the parent's markers
+            equatorial_bonds: EquatorialBondRule::Duplicate, // Equatorial bonds go to both children
             child_a: ChildSettings::default(),
             child_b: ChildSettings::default(),
             adhesion_settings: AdhesionSettings::default(),
+            material: None,
+        }
+    }
+}
+
+/// Which rule picked a child's mode at division
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChildModeRule {
+    /// The parent mode's child A/B mode
+    Child,
+    /// The mode-after-splits, once the parent reached its max splits
+    AfterSplits,
+    /// The child reference was invalid, so the child stayed in the parent's mode
+    KeptParent,
+}
+
+impl ChildModeRule {
+    pub fn name(self) -> &'static str {
+        match self {
+            ChildModeRule::Child => "Child mode",
+            ChildModeRule::AfterSplits => "Mode after splits",
+            ChildModeRule::KeptParent => "Kept parent mode",
         }
     }
 }
@@ -276,85 +646,325 @@ impl Default for ModeSettings {
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct GenomeData {
     pub name: String,
-    pub initial_mode: i32,
+    pub initial_mode: ModeIndex,
     pub initial_orientation: Quat,
     pub modes: Vec<ModeSettings>,
+    /// Free-form description, shown in the Open Genome browser
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// Stages that override mode settings once they start (see [`phases`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<GenomePhase>,
+    /// Adhesions formed when unrelated cells touch (see [`contact_adhesion`])
+    #[serde(default, skip_serializing_if = "ContactAdhesionMatrix::is_empty")]
+    pub contact_adhesion: ContactAdhesionMatrix,
+    /// Growth cycles the user accepted, so the capacity guardrail stays quiet about them
+    /// (see [`population_forecast::GrowthCycle`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acknowledged_cycles: Vec<Vec<ModeIndex>>,
+    /// Pinned random seed and the runs started from this genome (see [`run_history`])
+    #[serde(default, skip_serializing_if = "GenomeMetadata::is_empty")]
+    pub metadata: GenomeMetadata,
 }
 
 impl GenomeData {
     /// Set all modes to split back to themselves
     pub fn set_all_modes_self_splitting(&mut self) {
         for (idx, mode) in self.modes.iter_mut().enumerate() {
-            let mode_idx = idx as i32;
-            mode.child_a.mode_number = mode_idx;
-            mode.child_b.mode_number = mode_idx;
+            mode.child_a.mode_number = ModeIndex::new(idx);
+            mode.child_b.mode_number = ModeIndex::new(idx);
         }
     }
-}
 
-impl Default for GenomeData {
-    fn default() -> Self {
+    /// Position of the referenced mode, if the genome has it
+    pub fn checked_index(&self, index: ModeIndex) -> Option<usize> {
+        index.index().filter(|&i| i < self.modes.len())
+    }
+
+    pub fn valid_index(&self, index: ModeIndex) -> bool {
+        self.checked_index(index).is_some()
+    }
+
+    pub fn resolve_mode(&self, index: ModeIndex) -> Option<&ModeSettings> {
+        self.modes.get(self.checked_index(index)?)
+    }
+
+    /// Mode the first cell starts in (the first mode when the reference is invalid)
+    pub fn initial_mode_index(&self) -> usize {
+        self.checked_index(self.initial_mode).unwrap_or(0)
+    }
+
+    /// Mode child A (`side` 0) or B (1) of a `parent` mode cell starts in
+    ///
+    /// With `reached_max_splits` a valid mode-after-splits wins over the normal child mode.
+    /// An invalid child reference keeps the child in the parent's mode; an invalid
+    /// mode-after-splits is ignored like [`ModeIndex::NONE`].
+    pub fn child_mode(&self, parent: usize, side: usize, reached_max_splits: bool) -> usize {
+        self.child_mode_with_rule(parent, side, reached_max_splits).0
+    }
+
+    /// [`child_mode`](Self::child_mode) and the rule that picked it
+    pub fn child_mode_with_rule(&self, parent: usize, side: usize, reached_max_splits: bool) -> (usize, ChildModeRule) {
+        let Some(mode) = self.modes.get(parent) else {
+            return (parent, ChildModeRule::KeptParent);
+        };
+        let (child, after_splits) = if side == 0 {
+            (mode.child_a.mode_number, mode.mode_a_after_splits)
+        } else {
+            (mode.child_b.mode_number, mode.mode_b_after_splits)
+        };
+        if let Some(index) = reached_max_splits.then(|| self.checked_index(after_splits)).flatten() {
+            return (index, ChildModeRule::AfterSplits);
+        }
+        match self.checked_index(child) {
+            Some(index) => (index, ChildModeRule::Child),
+            None => (parent, ChildModeRule::KeptParent),
+        }
+    }
+
+    /// Mode references that point at no mode
+    pub fn mode_reference_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.valid_index(self.initial_mode) {
+            problems.push(format!("Initial mode {} out of range", self.initial_mode.raw()));
+        }
+        for (i, mode) in self.modes.iter().enumerate() {
+            if !self.valid_index(mode.child_a.mode_number) {
+                problems.push(format!("Mode {} child A mode {} out of range", i, mode.child_a.mode_number.raw()));
+            }
+            if !self.valid_index(mode.child_b.mode_number) {
+                problems.push(format!("Mode {} child B mode {} out of range", i, mode.child_b.mode_number.raw()));
+            }
+            if !mode.mode_a_after_splits.is_none() && !self.valid_index(mode.mode_a_after_splits) {
+                problems.push(format!("Mode {} mode A after splits {} out of range", i, mode.mode_a_after_splits.raw()));
+            }
+            if !mode.mode_b_after_splits.is_none() && !self.valid_index(mode.mode_b_after_splits) {
+                problems.push(format!("Mode {} mode B after splits {} out of range", i, mode.mode_b_after_splits.raw()));
+            }
+        }
+        for phase in &self.phases {
+            for mode_override in phase.overrides.iter().filter(|o| !self.valid_index(o.mode)) {
+                problems.push(format!("Phase \"{}\" overrides mode {} out of range", phase.name, mode_override.mode.raw()));
+            }
+        }
+        problems
+    }
+
+    /// Modes a lineage starting in `from` can enter, including `from` itself
+    ///
+    /// Follows both children and their after-splits modes with the same fallbacks as
+    /// [`child_mode`](Self::child_mode), so broken references never leave the graph.
+    pub fn reachable_modes(&self, from: usize) -> std::collections::BTreeSet<usize> {
+        let mut reached = std::collections::BTreeSet::new();
+        if from >= self.modes.len() {
+            return reached;
+        }
+        let mut pending = vec![from];
+        reached.insert(from);
+        while let Some(mode) = pending.pop() {
+            for (side, reached_max_splits) in [(0, false), (0, true), (1, false), (1, true)] {
+                let child = self.child_mode(mode, side, reached_max_splits);
+                if reached.insert(child) {
+                    pending.push(child);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Create the default 40-mode genome colored with the given palette
+    pub fn with_palette(palette: ColorPalette) -> Self {
         let mut genome = Self {
             name: "Untitled Genome".to_string(),
-            initial_mode: 0,
+            initial_mode: ModeIndex::new(0),
             initial_orientation: Quat::IDENTITY,
             modes: Vec::new(),
+            notes: String::new(),
+            phases: Vec::new(),
+            contact_adhesion: ContactAdhesionMatrix::default(),
+            acknowledged_cycles: Vec::new(),
+            metadata: GenomeMetadata::default(),
         };
         
         // Create all 40 modes
         for i in 0..40 {
             let mode_name = format!("M {}", i + 1);  // Start mode numbering from 1
-            let mut mode = ModeSettings::new_self_splitting(i as i32, mode_name);
-
-            // Generate a color based on the mode number using HSV
-            let hue = (i as f32 / 40.0) * 360.0;
-            let (r, g, b) = hue_to_rgb(hue);
-            mode.color = Vec3::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
-            
-            genome.modes.push(mode);
+            genome.modes.push(ModeSettings::new_self_splitting(i, mode_name));
         }
+        genome.recolor_modes(palette);
         
         genome
     }
+
+    /// Replace every mode's color with the palette's color for its index
+    pub fn recolor_modes(&mut self, palette: ColorPalette) {
+        let count = self.modes.len();
+        for (i, mode) in self.modes.iter_mut().enumerate() {
+            mode.color = palette.color(i, count);
+        }
+    }
+
+    /// Whether both genomes simulate identically
+    ///
+    /// Ignores names, notes, how cells look and editor-only widget state (angle snapping
+    /// toggles and the quaternion balls' lat/lon tracking). Full `PartialEq` still sees
+    /// every field, for unsaved-changes checks and serialization round-trips.
+    pub fn functionally_equal(&self, other: &Self) -> bool {
+        self.functional_copy() == other.functional_copy()
+    }
+
+    /// Whether cells of both genomes look the same (color, opacity, glow and pattern per mode)
+    pub fn appearance_equal(&self, other: &Self) -> bool {
+        self.modes.len() == other.modes.len()
+            && self.modes.iter().zip(&other.modes).all(|(a, b)| {
+                a.color == b.color && a.opacity == b.opacity && a.emissive == b.emissive && a.pattern == b.pattern
+            })
+    }
+
+    /// Copy with every field that doesn't affect the simulation reset
+    fn functional_copy(&self) -> Self {
+        let mut copy = self.clone();
+        copy.name.clear();
+        copy.notes.clear();
+        copy.acknowledged_cycles.clear();
+        copy.metadata.runs.clear();
+        for phase in &mut copy.phases {
+            phase.name.clear();
+        }
+        for mode in &mut copy.modes {
+            mode.name.clear();
+            mode.default_name.clear();
+            mode.color = Vec3::ZERO;
+            mode.opacity = 0.0;
+            mode.emissive = 0.0;
+            mode.pattern = None;
+            mode.enable_parent_angle_snapping = false;
+            for child in [&mut mode.child_a, &mut mode.child_b] {
+                *child = ChildSettings {
+                    mode_number: child.mode_number,
+                    orientation: child.orientation,
+                    keep_adhesion: child.keep_adhesion,
+                    ..Default::default()
+                };
+            }
+        }
+        copy
+    }
+
+    /// Check the modes for inconsistencies that would break simulation
+    /// Returns a list of human-readable problems (empty if valid)
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mode_count = self.modes.len();
+        if mode_count == 0 {
+            errors.push("Genome has no modes".to_string());
+            return errors;
+        }
+
+        errors.extend(self.mode_reference_problems());
+        for (i, mode) in self.modes.iter().enumerate() {
+            if mode.split_mass <= 0.0 || mode.split_interval <= 0.0 {
+                errors.push(format!("Mode {} has non-positive split mass or interval", i));
+            }
+        }
+        errors
+    }
 }
 
-// Helper function to convert HSV hue to RGB
-fn hue_to_rgb(hue: f32) -> (u8, u8, u8) {
-    let h = hue / 60.0;
-    let c = 1.0;
-    let x = 1.0 - (h % 2.0 - 1.0).abs();
-    
-    let (r, g, b) = if h < 1.0 {
-        (c, x, 0.0)
-    } else if h < 2.0 {
-        (x, c, 0.0)
-    } else if h < 3.0 {
-        (0.0, c, x)
-    } else if h < 4.0 {
-        (0.0, x, c)
-    } else if h < 5.0 {
-        (x, 0.0, c)
-    } else {
-        (c, 0.0, x)
-    };
-    
-    // Scale to 100-255 range for better visibility
-    let scale = |v: f32| ((v * 155.0) + 100.0) as u8;
-    (scale(r), scale(g), scale(b))
+impl Default for GenomeData {
+    fn default() -> Self {
+        Self::with_palette(ColorPalette::Hsv)
+    }
 }
 
 impl GenomeData {
     /// Save genome to a JSON file
-    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(path, json)?;
-        Ok(())
+    /// An existing file is only replaced once the new contents are fully written.
+    pub fn save_to_file(&self, path: &std::path::Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "genome")
     }
 
     /// Load genome from a JSON file
-    pub fn load_from_file(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
-        let json = std::fs::read_to_string(path)?;
-        let genome = serde_json::from_str(&json)?;
+    pub fn load_from_file(path: &std::path::Path) -> crate::error::Result<Self> {
+        let genome: Self = crate::error::read_json(path, "genome")?;
+        if genome.modes.is_empty() {
+            return Err(crate::error::BioSpheresError::Validation {
+                what: "genome",
+                problems: vec![format!("{} contains no modes", path.display())],
+            });
+        }
         Ok(genome)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widget_tracking_fields_are_not_functional() {
+        let genome = GenomeData::default();
+        let mut edited = genome.clone();
+        edited.modes[3].child_a.x_axis_lat = 42.0;
+        edited.modes[3].child_b.z_axis_lon = -17.0;
+        edited.modes[3].child_b.enable_angle_snapping = false;
+
+        assert!(genome.functionally_equal(&edited));
+        // Still an unsaved change
+        assert!(genome != edited);
+    }
+
+    #[test]
+    fn test_looks_are_not_functional_but_behavior_is() {
+        let genome = GenomeData::default();
+        let mut recolored = genome.clone();
+        recolored.name = "Renamed".to_string();
+        recolored.modes[0].color = Vec3::new(0.1, 0.2, 0.3);
+        assert!(genome.functionally_equal(&recolored));
+        assert!(!genome.appearance_equal(&recolored));
+
+        let mut reoriented = genome.clone();
+        reoriented.modes[0].child_a.orientation = Quat::from_rotation_y(0.5);
+        assert!(!genome.functionally_equal(&reoriented));
+        assert!(genome.appearance_equal(&reoriented));
+    }
+
+    #[test]
+    fn test_out_of_range_references_are_reported_and_fall_back() {
+        let mut genome = GenomeData::default();
+        genome.modes[3].child_a.mode_number = ModeIndex::new(99);
+        genome.modes[3].child_b.mode_number = ModeIndex::new(5);
+        genome.modes[3].mode_b_after_splits = ModeIndex::from_raw(-4);
+        genome.initial_mode = ModeIndex::new(40);
+
+        assert_eq!(genome.mode_reference_problems(), vec![
+            "Initial mode 40 out of range".to_string(),
+            "Mode 3 child A mode 99 out of range".to_string(),
+            "Mode 3 mode B after splits -4 out of range".to_string(),
+        ]);
+        assert!(genome.resolve_mode(ModeIndex::new(99)).is_none());
+        assert!(!genome.valid_index(ModeIndex::NONE));
+
+        // The broken child stays in its parent's mode instead of dropping to mode 0
+        assert_eq!(genome.child_mode(3, 0, false), 3);
+        assert_eq!(genome.child_mode(3, 0, true), 3);
+        assert_eq!(genome.child_mode(3, 1, true), 5);
+        genome.modes[3].mode_a_after_splits = ModeIndex::new(7);
+        assert_eq!(genome.child_mode(3, 0, true), 7);
+        assert_eq!(genome.initial_mode_index(), 0);
+    }
+
+    #[test]
+    fn test_mode_references_serialize_as_plain_integers() {
+        let mut mode = ModeSettings::new_self_splitting(2, "M 3".to_string());
+        mode.mode_a_after_splits = ModeIndex::new(1);
+        let json = serde_json::to_value(&mode).unwrap();
+        assert_eq!(json["child_a"]["mode_number"], 2);
+        assert_eq!(json["mode_a_after_splits"], 1);
+        assert_eq!(json["mode_b_after_splits"], -1);
+
+        let loaded: ModeSettings = serde_json::from_value(json).unwrap();
+        assert!(loaded.mode_b_after_splits.is_none());
+        assert!(loaded == mode);
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Reference from a genome to one of its modes (child modes, transitions, the initial mode)
+///
+/// Serialized as the bare i32 genome files have always used. [`ModeIndex::NONE`] (-1)
+/// means "no mode, use the default"; any other negative value or an index past the
+/// last mode is an invalid reference, resolved through
+/// [`GenomeData::resolve_mode`](super::GenomeData::resolve_mode) and friends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ModeIndex(i32);
+
+impl ModeIndex {
+    /// "Use the default" (mode after splits: the normal child mode)
+    pub const NONE: Self = Self(-1);
+
+    pub const fn new(index: usize) -> Self {
+        Self(index as i32)
+    }
+
+    /// Value as stored in genome files
+    pub const fn from_raw(raw: i32) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> i32 {
+        self.0
+    }
+
+    pub const fn is_none(self) -> bool {
+        self.0 == Self::NONE.0
+    }
+
+    /// The index, unless negative; whether the genome has that mode is up to the genome
+    pub fn index(self) -> Option<usize> {
+        usize::try_from(self.0).ok()
+    }
+}
+
+impl From<usize> for ModeIndex {
+    fn from(index: usize) -> Self {
+        Self::new(index)
+    }
+}
+
+impl fmt::Display for ModeIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_none() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
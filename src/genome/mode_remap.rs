@@ -0,0 +1,231 @@
+//! Index remapping for inserting and removing genome modes
+//!
+//! Cells, adhesions and recorded edits refer to modes by position, so every edit that
+//! shifts the mode list produces a [`ModeRemap`] for whatever else holds mode indices.
+
+use super::{GenomeData, ModeIndex, ModeSettings};
+
+/// Where each mode of a genome went after a mode insert or removal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModeRemap {
+    /// New index of every old mode (None = removed)
+    map: Vec<Option<usize>>,
+}
+
+impl ModeRemap {
+    /// A mode inserted at `at` into a genome of `mode_count` modes
+    pub fn insert(mode_count: usize, at: usize) -> Self {
+        let at = at.min(mode_count);
+        Self {
+            map: (0..mode_count).map(|old| Some(if old < at { old } else { old + 1 })).collect(),
+        }
+    }
+
+    /// Mode `index` removed from a genome of `mode_count` modes
+    pub fn remove(mode_count: usize, index: usize) -> Self {
+        Self {
+            map: (0..mode_count)
+                .map(|old| match old.cmp(&index) {
+                    std::cmp::Ordering::Less => Some(old),
+                    std::cmp::Ordering::Equal => None,
+                    std::cmp::Ordering::Greater => Some(old - 1),
+                })
+                .collect(),
+        }
+    }
+
+    /// New index of `old`; None when the mode was removed or never existed
+    pub fn get(&self, old: usize) -> Option<usize> {
+        self.map.get(old).copied().flatten()
+    }
+
+    /// Old index of the removed mode, if the edit removed one
+    pub fn removed(&self) -> Option<usize> {
+        self.map.iter().position(Option::is_none)
+    }
+
+    /// Whether every mode kept its index (an append)
+    pub fn is_identity(&self) -> bool {
+        self.map.iter().enumerate().all(|(old, new)| *new == Some(old))
+    }
+
+    /// Remap a genome reference; NONE and invalid references pass through, a removed mode gives None
+    pub fn reference(&self, index: ModeIndex) -> Option<ModeIndex> {
+        match index.index().filter(|&old| old < self.map.len()) {
+            Some(old) => self.get(old).map(ModeIndex::new),
+            None => Some(index),
+        }
+    }
+}
+
+/// What happens to living cells of a removed mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemovedModePolicy {
+    /// Move them to this mode (index before the removal)
+    Reassign(usize),
+    /// Delete them from the simulation
+    Remove,
+}
+
+impl ModeSettings {
+    /// Remap the mode's child and after-splits references; `own` is the mode's new index,
+    /// which children that entered a removed mode fall back to
+    pub fn remap_references(&mut self, remap: &ModeRemap, own: ModeIndex) {
+        self.child_a.mode_number = remap.reference(self.child_a.mode_number).unwrap_or(own);
+        self.child_b.mode_number = remap.reference(self.child_b.mode_number).unwrap_or(own);
+        self.mode_a_after_splits = remap.reference(self.mode_a_after_splits).unwrap_or(ModeIndex::NONE);
+        self.mode_b_after_splits = remap.reference(self.mode_b_after_splits).unwrap_or(ModeIndex::NONE);
+    }
+}
+
+impl GenomeData {
+    /// Insert `mode` at `at` (clamped to the end) and remap every mode reference of the genome
+    pub fn insert_mode(&mut self, at: usize, mode: ModeSettings) -> ModeRemap {
+        let at = at.min(self.modes.len());
+        let remap = ModeRemap::insert(self.modes.len(), at);
+        self.remap_references(&remap, None);
+        self.modes.insert(at, mode);
+        remap
+    }
+
+    /// Remove mode `index` and remap every mode reference of the genome
+    ///
+    /// Children that entered the removed mode stay in their parent's mode, after-splits
+    /// references to it are cleared and phase overrides and contact rules of it are dropped.
+    /// An initial mode pointing at it moves to [`removal_fallback`](Self::removal_fallback).
+    /// The last mode cannot be removed.
+    pub fn remove_mode(&mut self, index: usize) -> Option<(ModeSettings, ModeRemap)> {
+        if self.modes.len() <= 1 || index >= self.modes.len() {
+            return None;
+        }
+        let remap = ModeRemap::remove(self.modes.len(), index);
+        let fallback = self.removal_fallback(index).and_then(|fallback| remap.get(fallback));
+        self.remap_references(&remap, fallback);
+        Some((self.modes.remove(index), remap))
+    }
+
+    /// Mode that takes over from a removed mode `index`: its child A mode, else its child B
+    /// mode, else the initial mode, else the first other mode
+    pub fn removal_fallback(&self, index: usize) -> Option<usize> {
+        let mode = self.modes.get(index)?;
+        [mode.child_a.mode_number, mode.child_b.mode_number, self.initial_mode]
+            .into_iter()
+            .filter_map(|reference| self.checked_index(reference))
+            .chain((0..self.modes.len()).filter(|&other| other != index))
+            .find(|&other| other != index)
+    }
+
+    fn remap_references(&mut self, remap: &ModeRemap, initial_fallback: Option<usize>) {
+        self.initial_mode = remap.reference(self.initial_mode)
+            .unwrap_or(ModeIndex::new(initial_fallback.unwrap_or(0)));
+        for (old, mode) in self.modes.iter_mut().enumerate() {
+            // The removed mode itself is dropped right after; its references don't matter
+            mode.remap_references(remap, remap.get(old).map_or(ModeIndex::NONE, ModeIndex::new));
+        }
+        for phase in &mut self.phases {
+            phase.overrides.retain_mut(|mode_override| match remap.reference(mode_override.mode) {
+                Some(mode) => {
+                    mode_override.mode = mode;
+                    true
+                }
+                None => false,
+            });
+        }
+        // An acknowledged cycle that lost a mode is a different cycle
+        self.acknowledged_cycles.retain_mut(|modes| {
+            let remapped: Option<Vec<ModeIndex>> = modes.iter().map(|&mode| remap.reference(mode)).collect();
+            match remapped {
+                Some(remapped) => {
+                    *modes = remapped;
+                    true
+                }
+                None => false,
+            }
+        });
+        self.contact_adhesion.rules.retain_mut(|rule| {
+            match (remap.reference(rule.mode), remap.reference(rule.partner)) {
+                (Some(mode), Some(partner)) => {
+                    rule.mode = mode;
+                    rule.partner = partner;
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome(names: &[&str]) -> GenomeData {
+        GenomeData {
+            modes: names.iter().enumerate()
+                .map(|(index, name)| ModeSettings::new_self_splitting(index, name.to_string()))
+                .collect(),
+            ..GenomeData::default()
+        }
+    }
+
+    fn names(genome: &GenomeData, reference: ModeIndex) -> Option<&str> {
+        genome.resolve_mode(reference).map(|mode| mode.name.as_str())
+    }
+
+    #[test]
+    fn test_remap_tables() {
+        let insert = ModeRemap::insert(3, 1);
+        assert_eq!((0..3).map(|old| insert.get(old)).collect::<Vec<_>>(), [Some(0), Some(2), Some(3)]);
+        assert!(ModeRemap::insert(3, 3).is_identity());
+        assert_eq!(insert.removed(), None);
+
+        let remove = ModeRemap::remove(3, 1);
+        assert_eq!((0..3).map(|old| remove.get(old)).collect::<Vec<_>>(), [Some(0), None, Some(1)]);
+        assert_eq!(remove.removed(), Some(1));
+        assert_eq!(remove.reference(ModeIndex::NONE), Some(ModeIndex::NONE));
+        assert_eq!(remove.reference(ModeIndex::new(7)), Some(ModeIndex::new(7)));
+        assert_eq!(remove.reference(ModeIndex::new(1)), None);
+    }
+
+    #[test]
+    fn test_genome_references_follow_their_modes() {
+        let mut genome = genome(&["Stem", "Leaf", "Root", "Tip"]);
+        genome.initial_mode = ModeIndex::new(2);
+        genome.modes[0].child_a.mode_number = ModeIndex::new(1);
+        genome.modes[0].child_b.mode_number = ModeIndex::new(2);
+        genome.modes[2].mode_a_after_splits = ModeIndex::new(3);
+        genome.contact_adhesion.set_state(1, 3, crate::genome::ContactAdhesionState::Always);
+
+        genome.insert_mode(1, ModeSettings::new_self_splitting(1, "New".to_string()));
+        assert_eq!(names(&genome, genome.initial_mode), Some("Root"));
+        assert_eq!(names(&genome, genome.modes[0].child_a.mode_number), Some("Leaf"));
+        assert_eq!(names(&genome, genome.modes[0].child_b.mode_number), Some("Root"));
+        assert_eq!(names(&genome, genome.modes[3].mode_a_after_splits), Some("Tip"));
+        assert_eq!(names(&genome, genome.modes[4].child_a.mode_number), Some("Tip"));
+        assert!(genome.contact_adhesion.rule(2, 4).is_some());
+
+        // Removing Leaf keeps Stem's child A in Stem and drops the contact rule
+        let (removed, remap) = genome.remove_mode(2).unwrap();
+        assert_eq!(removed.name, "Leaf");
+        assert_eq!(remap.removed(), Some(2));
+        assert_eq!(names(&genome, genome.modes[0].child_a.mode_number), Some("Stem"));
+        assert_eq!(names(&genome, genome.modes[0].child_b.mode_number), Some("Root"));
+        assert!(genome.contact_adhesion.is_empty());
+        assert!(genome.mode_reference_problems().is_empty());
+    }
+
+    #[test]
+    fn test_removing_the_initial_mode_moves_it_to_the_fallback() {
+        let mut genome = genome(&["Stem", "Leaf", "Root"]);
+        genome.initial_mode = ModeIndex::new(0);
+        genome.modes[0].child_b.mode_number = ModeIndex::new(2);
+        assert_eq!(genome.removal_fallback(0), Some(2));
+
+        genome.remove_mode(0);
+        assert_eq!(names(&genome, genome.initial_mode), Some("Root"));
+
+        let mut single = self::genome(&["Only"]);
+        assert!(single.remove_mode(0).is_none());
+        assert_eq!(single.removal_fallback(0), None);
+    }
+}
@@ -1,5 +1,19 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use crate::genome::GenomeData;
+
+const NODE_SPACING_X: f32 = 250.0;
+const NODE_SPACING_Y: f32 = 200.0;
+const START_X: f32 = 50.0;
+const START_Y: f32 = 50.0;
+const COLUMNS: usize = 4;
+
+/// Position of the `slot`th node in the automatic grid layout
+fn grid_position(slot: usize) -> (f32, f32) {
+    let col = slot % COLUMNS;
+    let row = slot / COLUMNS;
+    (START_X + col as f32 * NODE_SPACING_X, START_Y + row as f32 * NODE_SPACING_Y)
+}
 
 /// Node graph representation of genome modes
 #[derive(Resource)]
@@ -98,26 +112,58 @@ impl GenomeNodeGraph {
 
     /// Calculate automatic layout for nodes in a grid pattern
     pub fn calculate_grid_layout(&mut self) {
-        const NODE_SPACING_X: f32 = 250.0;
-        const NODE_SPACING_Y: f32 = 200.0;
-        const START_X: f32 = 50.0;
-        const START_Y: f32 = 50.0;
-        const COLUMNS: usize = 4;
-
         let mut sorted_nodes: Vec<i32> = self.node_to_mode.keys().copied().collect();
         sorted_nodes.sort_by_key(|node_id| self.node_to_mode.get(node_id).unwrap_or(&0));
 
         for (idx, node_id) in sorted_nodes.iter().enumerate() {
-            let col = idx % COLUMNS;
-            let row = idx / COLUMNS;
-            let x = START_X + (col as f32 * NODE_SPACING_X);
-            let y = START_Y + (row as f32 * NODE_SPACING_Y);
-            self.node_positions.insert(*node_id, (x, y));
+            self.node_positions.insert(*node_id, grid_position(idx));
         }
 
         self.needs_layout = false;
     }
 
+    /// Keep one node per mode, and the links between them, in step with the genome
+    ///
+    /// Nodes are rebuilt when marked or when the mode count changes. A rebuilt node keeps
+    /// the position saved under its mode's name; a new one takes the pending position
+    /// (a mode added at the pointer) or its grid slot.
+    pub fn sync_with_genome(&mut self, genome: &GenomeData) {
+        if self.needs_rebuild || self.mode_to_node.len() != genome.modes.len() {
+            let saved: HashMap<String, (f32, f32)> = self.node_to_name.iter()
+                .filter_map(|(node_id, name)| Some((name.clone(), self.get_node_position(*node_id)?)))
+                .collect();
+            let pending = self.pending_position.take();
+            self.clear();
+            for (mode_index, mode) in genome.modes.iter().enumerate() {
+                let node_id = self.create_node(mode_index);
+                let position = pending
+                    .filter(|(index, _, _)| *index == mode_index)
+                    .map(|(_, x, y)| (x, y))
+                    .or_else(|| saved.get(&mode.name).copied())
+                    .unwrap_or_else(|| grid_position(mode_index));
+                self.node_positions.insert(node_id, position);
+            }
+            self.needs_rebuild = false;
+            self.needs_layout = false;
+        }
+
+        // Renames and child edits don't change the mode count
+        self.links.clear();
+        for (mode_index, mode) in genome.modes.iter().enumerate() {
+            let Some(from) = self.get_node_for_mode(mode_index) else {
+                continue;
+            };
+            if self.node_to_name.get(&from) != Some(&mode.name) {
+                self.node_to_name.insert(from, mode.name.clone());
+            }
+            for (child, is_child_a) in [(&mode.child_a, true), (&mode.child_b, false)] {
+                if let Some(to) = child.mode_number.index().and_then(|index| self.get_node_for_mode(index)) {
+                    self.links.push((from, to, is_child_a));
+                }
+            }
+        }
+    }
+
     /// Get position for a node
     pub fn get_node_position(&self, node_id: i32) -> Option<(f32, f32)> {
         self.node_positions.get(&node_id).copied()
@@ -128,3 +174,41 @@ impl GenomeNodeGraph {
         self.node_positions.insert(node_id, (x, y));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{ModeIndex, ModeSettings};
+
+    fn genome(names: &[&str]) -> GenomeData {
+        let modes = names.iter()
+            .enumerate()
+            .map(|(i, name)| ModeSettings::new_self_splitting(i, name.to_string()))
+            .collect();
+        GenomeData { modes, ..GenomeData::default() }
+    }
+
+    #[test]
+    fn test_sync_keeps_moved_nodes_by_name_and_follows_children() {
+        let mut genome = genome(&["Stem", "Leaf"]);
+        let mut graph = GenomeNodeGraph::default();
+        graph.sync_with_genome(&genome);
+        assert_eq!(graph.links.len(), 4);
+        let leaf = graph.get_node_for_mode(1).unwrap();
+        graph.set_node_position(leaf, 600.0, 20.0);
+
+        // Inserting a mode before it shifts its index, not its position
+        genome.modes.insert(0, ModeSettings::new_self_splitting(0, "Root".to_string()));
+        graph.pending_position = Some((0, 5.0, 5.0));
+        graph.sync_with_genome(&genome);
+        let leaf = graph.get_node_for_mode(2).unwrap();
+        assert_eq!(graph.get_node_position(leaf), Some((600.0, 20.0)));
+        let root = graph.get_node_for_mode(0).unwrap();
+        assert_eq!(graph.get_node_position(root), Some((5.0, 5.0)));
+
+        // Child edits relink without a rebuild
+        genome.modes[0].child_a.mode_number = ModeIndex::new(2);
+        graph.sync_with_genome(&genome);
+        assert!(graph.links.contains(&(root, leaf, true)));
+    }
+}
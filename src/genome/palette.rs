@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+
+/// Generator for default mode colors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorPalette {
+    /// Hues evenly spaced around the color wheel (original default)
+    #[default]
+    Hsv,
+    /// Okabe-Ito colorblind-safe colors, repeated in lighter and darker shades
+    OkabeIto,
+    /// Perceptually uniform viridis ramp, spaced so neighbouring modes differ in lightness
+    Viridis,
+}
+
+/// Okabe-Ito palette without black (black cells are hard to see against the background)
+const OKABE_ITO: [(u8, u8, u8); 7] = [
+    (230, 159, 0),   // Orange
+    (86, 180, 233),  // Sky blue
+    (0, 158, 115),   // Bluish green
+    (240, 228, 66),  // Yellow
+    (0, 114, 178),   // Blue
+    (213, 94, 0),    // Vermillion
+    (204, 121, 167), // Reddish purple
+];
+
+/// Shade applied to each repeat of the Okabe-Ito colors (positive lightens, negative darkens)
+const OKABE_ITO_SHADES: [f32; 6] = [0.0, 0.4, -0.35, 0.65, -0.55, 0.2];
+
+/// Viridis sampled at 1/8 intervals
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 45, 123),
+    (59, 82, 139),
+    (44, 114, 142),
+    (33, 145, 140),
+    (40, 174, 128),
+    (94, 201, 98),
+    (173, 220, 48),
+    (253, 231, 37),
+];
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 3] = [ColorPalette::Hsv, ColorPalette::OkabeIto, ColorPalette::Viridis];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorPalette::Hsv => "HSV",
+            ColorPalette::OkabeIto => "Okabe-Ito (extended)",
+            ColorPalette::Viridis => "Viridis",
+        }
+    }
+
+    /// Color for mode `index` of a genome with `count` modes
+    pub fn color(self, index: usize, count: usize) -> Vec3 {
+        match self {
+            ColorPalette::Hsv => {
+                let hue = (index as f32 / count.max(1) as f32) * 360.0;
+                let (r, g, b) = hue_to_rgb(hue);
+                Vec3::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+            }
+            ColorPalette::OkabeIto => {
+                let base = rgb_to_vec3(OKABE_ITO[index % OKABE_ITO.len()]);
+                let shade = OKABE_ITO_SHADES[(index / OKABE_ITO.len()) % OKABE_ITO_SHADES.len()];
+                if shade >= 0.0 {
+                    base.lerp(Vec3::ONE, shade)
+                } else {
+                    base * (1.0 + shade)
+                }
+            }
+            ColorPalette::Viridis => {
+                // Golden-ratio stepping keeps the first few modes far apart on the ramp
+                let t = (index as f32 * 0.618_034).fract();
+                let scaled = t * (VIRIDIS.len() - 1) as f32;
+                let i = (scaled.floor() as usize).min(VIRIDIS.len() - 2);
+                rgb_to_vec3(VIRIDIS[i]).lerp(rgb_to_vec3(VIRIDIS[i + 1]), scaled - i as f32)
+            }
+        }
+    }
+}
+
+fn rgb_to_vec3((r, g, b): (u8, u8, u8)) -> Vec3 {
+    Vec3::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+}
+
+// Helper function to convert HSV hue to RGB
+fn hue_to_rgb(hue: f32) -> (u8, u8, u8) {
+    let h = hue / 60.0;
+    let c = 1.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+
+    let (r, g, b) = if h < 1.0 {
+        (c, x, 0.0)
+    } else if h < 2.0 {
+        (x, c, 0.0)
+    } else if h < 3.0 {
+        (0.0, c, x)
+    } else if h < 4.0 {
+        (0.0, x, c)
+    } else if h < 5.0 {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+
+    // Scale to 100-255 range for better visibility
+    let scale = |v: f32| ((v * 155.0) + 100.0) as u8;
+    (scale(r), scale(g), scale(b))
+}
+
+/// Surface pattern drawn over a mode's color so modes stay distinguishable without hue
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PatternKind {
+    Stripes,
+    Dots,
+    Rings,
+}
+
+impl PatternKind {
+    pub const ALL: [PatternKind; 3] = [PatternKind::Stripes, PatternKind::Dots, PatternKind::Rings];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PatternKind::Stripes => "Stripes",
+            PatternKind::Dots => "Dots",
+            PatternKind::Rings => "Rings",
+        }
+    }
+}
+
+/// A mode's surface pattern
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CellPattern {
+    pub kind: PatternKind,
+    /// Pattern repeats per cell (1.0 = default density)
+    pub scale: f32,
+}
+
+impl CellPattern {
+    pub fn new(kind: PatternKind) -> Self {
+        Self { kind, scale: 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hsv_palette_matches_original_default_colors() {
+        // Mode 0 of the original 40-mode genome was pure red scaled into 100-255
+        assert_eq!(ColorPalette::Hsv.color(0, 40), Vec3::new(1.0, 100.0 / 255.0, 100.0 / 255.0));
+    }
+
+    #[test]
+    fn test_palettes_give_distinct_neighbouring_modes() {
+        for palette in [ColorPalette::OkabeIto, ColorPalette::Viridis] {
+            for i in 0..39 {
+                let a = palette.color(i, 40);
+                let b = palette.color(i + 1, 40);
+                assert!(a.distance(b) > 0.05, "{:?} modes {} and {} are too similar", palette, i, i + 1);
+                assert!(a.min_element() >= 0.0 && a.max_element() <= 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use super::{GenomeData, ModeIndex, ModeSettings};
+
+/// When a genome phase starts
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PhaseCondition {
+    /// Simulated time in seconds
+    Time(f32),
+    /// Total divisions in the scene
+    Divisions(u64),
+}
+
+impl PhaseCondition {
+    pub fn is_met(self, time: f32, divisions: u64) -> bool {
+        match self {
+            PhaseCondition::Time(start) => time >= start,
+            PhaseCondition::Divisions(count) => divisions >= count,
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            PhaseCondition::Time(start) => format!("from {:.1}s", start),
+            PhaseCondition::Divisions(count) => format!("after {} divisions", count),
+        }
+    }
+}
+
+/// Numeric fields of one mode that differ while a phase is active
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModeOverride {
+    pub mode: ModeIndex,
+    /// Values by `ModeSettings` field name; nested settings (adhesion, children) are nested objects
+    pub values: Map<String, Value>,
+}
+
+/// Whole-genome phase such as "embryonic" or "adult"
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenomePhase {
+    pub name: String,
+    pub condition: PhaseCondition,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ModeOverride>,
+}
+
+impl GenomePhase {
+    pub fn new(name: String, condition: PhaseCondition) -> Self {
+        Self { name, condition, overrides: Vec::new() }
+    }
+
+    pub fn overrides_for(&self, mode: usize) -> Option<&Map<String, Value>> {
+        self.overrides.iter().find(|o| o.mode.index() == Some(mode)).map(|o| &o.values)
+    }
+
+    /// Replace the overrides of `mode` (an empty set removes them)
+    pub fn set_overrides(&mut self, mode: usize, values: Map<String, Value>) {
+        self.overrides.retain(|o| o.mode.index() != Some(mode));
+        if !values.is_empty() {
+            self.overrides.push(ModeOverride { mode: ModeIndex::new(mode), values });
+            self.overrides.sort_by_key(|o| o.mode);
+        }
+    }
+}
+
+/// Numeric fields of `edited` that differ from `base`, in the shape `apply_overrides` takes
+///
+/// Numbers and all-number arrays (vectors, quaternions, per-channel rates) count;
+/// names, flags, enums and cleared optional values don't.
+pub fn numeric_overrides(base: &ModeSettings, edited: &ModeSettings) -> Map<String, Value> {
+    match (serde_json::to_value(base), serde_json::to_value(edited)) {
+        (Ok(Value::Object(base)), Ok(Value::Object(edited))) => numeric_diff(&base, &edited),
+        _ => Map::new(),
+    }
+}
+
+fn numeric_diff(base: &Map<String, Value>, edited: &Map<String, Value>) -> Map<String, Value> {
+    let mut diff = Map::new();
+    for (key, value) in edited {
+        let before = base.get(key).unwrap_or(&Value::Null);
+        match (before, value) {
+            (Value::Object(before), Value::Object(after)) => {
+                let nested = numeric_diff(before, after);
+                if !nested.is_empty() {
+                    diff.insert(key.clone(), Value::Object(nested));
+                }
+            }
+            _ if is_numeric(value) && before != value => {
+                diff.insert(key.clone(), value.clone());
+            }
+            _ => {}
+        }
+    }
+    diff
+}
+
+fn is_numeric(value: &Value) -> bool {
+    match value {
+        Value::Number(_) => true,
+        Value::Array(items) => !items.is_empty() && items.iter().all(Value::is_number),
+        _ => false,
+    }
+}
+
+/// `mode` with the override values merged in
+///
+/// Unknown fields are ignored; values of the wrong type leave the mode unchanged.
+pub fn apply_overrides(mode: &ModeSettings, values: &Map<String, Value>) -> ModeSettings {
+    let Ok(mut merged) = serde_json::to_value(mode) else {
+        return mode.clone();
+    };
+    merge(&mut merged, values);
+    serde_json::from_value(merged).unwrap_or_else(|e| {
+        warn!("Ignoring phase overrides for mode \"{}\": {}", mode.name, e);
+        mode.clone()
+    })
+}
+
+fn merge(target: &mut Value, values: &Map<String, Value>) {
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in values {
+        match (target.get_mut(key), value) {
+            (Some(existing @ Value::Object(_)), Value::Object(nested)) => merge(existing, nested),
+            (Some(existing), _) => *existing = value.clone(),
+            (None, _) => {}
+        }
+    }
+}
+
+impl GenomeData {
+    /// Phase in effect at `time` after `divisions` divisions (None before the first one starts)
+    ///
+    /// The last phase whose condition is met wins, so phases are listed in the order
+    /// they are expected to start.
+    pub fn active_phase(&self, time: f32, divisions: u64) -> Option<usize> {
+        self.phases.iter().rposition(|phase| phase.condition.is_met(time, divisions))
+    }
+
+    /// Genome the simulation runs while phase `index` is active (overrides applied, no phases)
+    pub fn phase_genome(&self, index: usize) -> GenomeData {
+        let mut genome = self.clone();
+        genome.phases.clear();
+        if let Some(phase) = self.phases.get(index) {
+            for mode_override in &phase.overrides {
+                if let Some(mode) = self.checked_index(mode_override.mode) {
+                    genome.modes[mode] = apply_overrides(&self.modes[mode], &mode_override.values);
+                }
+            }
+        }
+        genome
+    }
+
+    pub fn phase_name(&self, index: Option<usize>) -> &str {
+        index.and_then(|i| self.phases.get(i)).map_or("Base", |phase| phase.name.as_str())
+    }
+}
+
+/// Effective genome of a running simulation
+///
+/// The phase is evaluated at every tick boundary from the canonical state (simulated
+/// time and total divisions), but the overridden genome is only rebuilt when the
+/// phase changes or the base genome is edited.
+#[derive(Default)]
+pub struct PhasedGenome {
+    phase: Option<usize>,
+    /// Genome `effective` was built from
+    base: Option<GenomeData>,
+    effective: Option<GenomeData>,
+}
+
+impl PhasedGenome {
+    /// Evaluate the phase for the tick starting at `time` after `divisions` divisions;
+    /// returns true if it changed
+    pub fn update(&mut self, genome: &GenomeData, divisions: u64, time: f32) -> bool {
+        let phase = genome.active_phase(time, divisions);
+        let changed = phase != self.phase;
+        self.phase = phase;
+
+        let stale = match phase {
+            Some(_) => changed || self.base.as_ref() != Some(genome),
+            None => self.effective.is_some(),
+        };
+        if stale {
+            self.effective = phase.map(|index| genome.phase_genome(index));
+            self.base = phase.map(|_| genome.clone());
+        }
+        changed
+    }
+
+    pub fn phase(&self) -> Option<usize> {
+        self.phase
+    }
+
+    /// Genome to simulate with: `base` with the active phase's overrides
+    pub fn genome<'a>(&'a self, base: &'a GenomeData) -> &'a GenomeData {
+        self.effective.as_ref().unwrap_or(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::PhysicsConfig;
+    use crate::simulation::cpu_physics::CanonicalState;
+
+    fn gated_genome() -> GenomeData {
+        let mut mode = ModeSettings::new_self_splitting(0, "Dividing".to_string());
+        mode.split_interval = 10.0;
+        mode.split_mass = 0.5;
+        let mut adult = mode.clone();
+        // Above 59 s means "never split"
+        adult.split_interval = 60.0;
+
+        let mut phase = GenomePhase::new("Adult".to_string(), PhaseCondition::Time(60.0));
+        phase.set_overrides(0, numeric_overrides(&mode, &adult));
+        GenomeData { modes: vec![mode], phases: vec![phase], ..GenomeData::default() }
+    }
+
+    #[test]
+    fn test_phase_stops_division_after_sixty_seconds() {
+        let genome = gated_genome();
+        let config = PhysicsConfig::default();
+        let fixed_timestep = config.fixed_timestep;
+        let mut state = CanonicalState::new(4_096);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 10.0, 0.5, config.default_stiffness, Quat::IDENTITY, 0);
+
+        let mut phased = PhasedGenome::default();
+        let mut divisions_at_phase_change = None;
+        let end = crate::simulation::clock::ticks_to_reach(90.0, fixed_timestep);
+        for tick in 0..end {
+            let time = tick as f32 * fixed_timestep;
+            if phased.update(&genome, state.division_stats.divisions, time) {
+                divisions_at_phase_change = Some(state.division_stats.divisions);
+            }
+            let effective = phased.genome(&genome);
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, effective, time, false);
+            let capacity = state.capacity;
+            crate::simulation::cpu_physics::division_step(&mut state, effective, time, fixed_timestep, capacity, 0);
+        }
+
+        let before = divisions_at_phase_change.expect("the adult phase should start at 60 s");
+        assert!(before > 0, "cells should divide before the phase change");
+        assert_eq!(phased.phase(), Some(0));
+        assert_eq!(state.division_stats.divisions, before, "no divisions after the phase change");
+    }
+
+    #[test]
+    fn test_phases_round_trip_and_stay_out_of_phase_free_files() {
+        let genome = gated_genome();
+        let json = serde_json::to_string(&genome).unwrap();
+        let loaded: GenomeData = serde_json::from_str(&json).unwrap();
+        assert!(loaded == genome);
+        assert_eq!(loaded.phase_genome(0).modes[0].split_interval, 60.0);
+        assert_eq!(loaded.phase_genome(0).modes[0].split_mass, 0.5);
+
+        let plain = GenomeData { phases: Vec::new(), ..genome };
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("phases"));
+    }
+}
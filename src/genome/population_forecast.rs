@@ -0,0 +1,568 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use super::{GenomeData, GenomeHistory, ModeIndex, ModeSettings, NEVER_SPLIT_INTERVAL};
+
+/// Branching process type: a mode and, for modes with a split limit, the cell's split count
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct CellType {
+    mode: usize,
+    splits: i32,
+}
+
+/// Long-run behavior of a genome's population, ignoring space, mass and nutrients
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForecastOutlook {
+    /// A lineage cycle keeps both children of some division: unbounded exponential growth
+    Divergent,
+    /// Lineages cycle but never branch within a cycle: the population grows linearly
+    Linear,
+    /// Every lineage ends in cells that never divide; the population stops at this size
+    DeadEnd { final_population: f64 },
+}
+
+/// Expected cells per mode over time for a genome started from one cell
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopulationForecast {
+    /// Time between samples (seconds); split intervals are rounded to whole steps
+    pub step: f32,
+    /// Expected cells per mode after the divisions at `k * step`, for k in 0..=steps
+    pub samples: Vec<Vec<f64>>,
+    pub outlook: ForecastOutlook,
+}
+
+impl PopulationForecast {
+    pub fn horizon(&self) -> f32 {
+        self.step * self.samples.len().saturating_sub(1) as f32
+    }
+
+    pub fn total(&self, sample: usize) -> f64 {
+        self.samples.get(sample).map_or(0.0, |counts| counts.iter().sum())
+    }
+
+    /// First time the expected total exceeds `population` (None if it never does within the horizon)
+    pub fn time_to_exceed(&self, population: f64) -> Option<f32> {
+        (0..self.samples.len()).find(|&sample| self.total(sample) > population).map(|sample| sample as f32 * self.step)
+    }
+
+    /// Doubling time of the total over the second half of the horizon (None without growth)
+    pub fn doubling_time(&self) -> Option<f32> {
+        let last = self.samples.len().checked_sub(1)?;
+        let mid = last / 2;
+        let growth = (self.total(last) / self.total(mid)).ln();
+        (growth > 1e-9 && last > mid).then(|| ((last - mid) as f64 * self.step as f64 * std::f64::consts::LN_2 / growth) as f32)
+    }
+}
+
+/// Mean of a mode's split interval (the midpoint when it is a range)
+fn expected_split_interval(mode: &ModeSettings) -> f32 {
+    match mode.split_interval_min {
+        Some(min) => 0.5 * (min + mode.split_interval),
+        None => mode.split_interval,
+    }
+}
+
+/// The mode graph unrolled into branching process types reachable from the initial cell
+struct TypeGraph {
+    types: Vec<CellType>,
+    /// Types of child A and B, None for cells that never divide
+    children: Vec<Option<[usize; 2]>>,
+}
+
+impl TypeGraph {
+    fn new(genome: &GenomeData) -> Self {
+        let mut graph = Self { types: Vec::new(), children: Vec::new() };
+        let mut index = HashMap::new();
+        let initial = normalize(genome, genome.initial_mode_index(), 0);
+        graph.insert(initial, &mut index);
+
+        let mut next = 0;
+        while next < graph.types.len() {
+            let cell = graph.types[next];
+            let children = divides(genome, cell).then(|| {
+                child_types(genome, cell).map(|child| graph.insert(child, &mut index))
+            });
+            graph.children[next] = children;
+            next += 1;
+        }
+        graph
+    }
+
+    fn insert(&mut self, cell: CellType, index: &mut HashMap<CellType, usize>) -> usize {
+        *index.entry(cell).or_insert_with(|| {
+            self.types.push(cell);
+            self.children.push(None);
+            self.types.len() - 1
+        })
+    }
+
+    /// Strongly connected component of every type (Kosaraju, iterative)
+    fn components(&self) -> Vec<usize> {
+        let n = self.types.len();
+        let successors = |v: usize| self.children[v].into_iter().flatten();
+        let mut predecessors = vec![Vec::new(); n];
+        for v in 0..n {
+            for w in successors(v) {
+                predecessors[w].push(v);
+            }
+        }
+
+        // Finish order of a depth-first search over the children
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((v, child)) = stack.pop() {
+                match successors(v).nth(child) {
+                    Some(w) => {
+                        stack.push((v, child + 1));
+                        if !visited[w] {
+                            visited[w] = true;
+                            stack.push((w, 0));
+                        }
+                    }
+                    None => order.push(v),
+                }
+            }
+        }
+
+        let mut component = vec![usize::MAX; n];
+        let mut count = 0;
+        for &root in order.iter().rev() {
+            if component[root] != usize::MAX {
+                continue;
+            }
+            component[root] = count;
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for &w in &predecessors[v] {
+                    if component[w] == usize::MAX {
+                        component[w] = count;
+                        stack.push(w);
+                    }
+                }
+            }
+            count += 1;
+        }
+        component
+    }
+
+    /// Component members and, per member, its children inside the component
+    fn branching_components(&self) -> Vec<Vec<(usize, Vec<usize>)>> {
+        let component = self.components();
+        let mut members: BTreeMap<usize, Vec<(usize, Vec<usize>)>> = BTreeMap::new();
+        for (v, children) in self.children.iter().enumerate() {
+            let inside: Vec<usize> = children.iter().flatten().copied().filter(|&child| component[child] == component[v]).collect();
+            members.entry(component[v]).or_default().push((v, inside));
+        }
+        // Only components in which some division keeps both children grow exponentially
+        members.into_values()
+            .filter(|members| members.iter().any(|(_, inside)| inside.len() == 2))
+            .collect()
+    }
+
+    fn outlook(&self) -> ForecastOutlook {
+        let component = self.components();
+        let mut cyclic = false;
+        for (v, children) in self.children.iter().enumerate() {
+            let Some(children) = children else {
+                continue;
+            };
+            let in_cycle = children.iter().filter(|&&child| component[child] == component[v]).count();
+            if in_cycle == 2 {
+                return ForecastOutlook::Divergent;
+            }
+            cyclic |= in_cycle == 1;
+        }
+        if cyclic {
+            return ForecastOutlook::Linear;
+        }
+
+        // Acyclic: cells at the end of each type's lineage, children before parents
+        let mut final_cells = vec![None; self.types.len()];
+        let mut stack = vec![0];
+        while let Some(&v) = stack.last() {
+            match self.children[v] {
+                None => {
+                    final_cells[v] = Some(1.0);
+                    stack.pop();
+                }
+                Some(children) => {
+                    let pending: Vec<usize> = children.into_iter().filter(|&c| final_cells[c].is_none()).collect();
+                    if pending.is_empty() {
+                        final_cells[v] = Some(children.iter().map(|&c| final_cells[c].unwrap_or(0.0)).sum());
+                        stack.pop();
+                    } else {
+                        stack.extend(pending);
+                    }
+                }
+            }
+        }
+        ForecastOutlook::DeadEnd { final_population: final_cells[0].unwrap_or(1.0) }
+    }
+}
+
+/// Modes without a split limit don't track split counts
+fn normalize(genome: &GenomeData, mode: usize, splits: i32) -> CellType {
+    let limited = genome.modes.get(mode).is_some_and(|settings| settings.max_splits >= 0);
+    CellType { mode, splits: if limited { splits } else { 0 } }
+}
+
+/// Whether cells of this type divide at all
+fn divides(genome: &GenomeData, cell: CellType) -> bool {
+    genome.modes.get(cell.mode).is_some_and(|mode| {
+        expected_split_interval(mode) <= NEVER_SPLIT_INTERVAL && (mode.max_splits < 0 || cell.splits < mode.max_splits)
+    })
+}
+
+/// Types of child A and B, following `division_step`'s mode and split count rules
+fn child_types(genome: &GenomeData, cell: CellType) -> [CellType; 2] {
+    let mode = &genome.modes[cell.mode];
+    let reached_max_splits = mode.max_splits >= 0 && cell.splits + 1 >= mode.max_splits;
+    [0, 1].map(|side| {
+        let child = genome.child_mode(cell.mode, side, reached_max_splits);
+        normalize(genome, child, if child == cell.mode { cell.splits + 1 } else { 0 })
+    })
+}
+
+/// Expected cells per mode from one cell of the initial mode over `horizon` seconds
+///
+/// Treats the mode graph as a branching process: every cell divides exactly its mode's
+/// expected split interval after its birth (rounded to whole steps of `horizon / steps`),
+/// following the child wiring, split limits and after-splits modes. Space, mass,
+/// nutrients, adhesion limits and genome phases are ignored.
+pub fn forecast_populations(genome: &GenomeData, horizon: f32, steps: usize) -> PopulationForecast {
+    let steps = steps.max(1);
+    let step = horizon.max(f32::EPSILON) / steps as f32;
+    let graph = TypeGraph::new(genome);
+    let lifetime = |cell: &CellType| {
+        let interval = expected_split_interval(&genome.modes[cell.mode]);
+        ((interval / step).round() as usize).max(1)
+    };
+    let lifetimes: Vec<usize> = graph.types.iter().map(lifetime).collect();
+
+    let mut alive = vec![0.0; genome.modes.len()];
+    // Expected cells of each type dividing at each step
+    let mut schedule: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); steps + 1];
+    let born = |alive: &mut Vec<f64>, schedule: &mut Vec<BTreeMap<usize, f64>>, v: usize, count: f64, at: usize| {
+        if let Some(slot) = alive.get_mut(graph.types[v].mode) {
+            *slot += count;
+        }
+        let divides_at = at + lifetimes[v];
+        if graph.children[v].is_some() && divides_at <= steps {
+            *schedule[divides_at].entry(v).or_default() += count;
+        }
+    };
+    if !graph.types.is_empty() {
+        born(&mut alive, &mut schedule, 0, 1.0, 0);
+    }
+
+    let mut samples = Vec::with_capacity(steps + 1);
+    for k in 0..=steps {
+        for (v, count) in std::mem::take(&mut schedule[k]) {
+            alive[graph.types[v].mode] -= count;
+            for child in graph.children[v].into_iter().flatten() {
+                born(&mut alive, &mut schedule, child, count, k);
+            }
+        }
+        samples.push(alive.clone());
+    }
+
+    PopulationForecast { step, samples, outlook: graph.outlook() }
+}
+
+/// A set of modes whose divisions feed each other so the population multiplies without bound
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowthCycle {
+    /// Modes of the cycle, ascending
+    pub modes: Vec<usize>,
+    /// Doubling time of the cycle's own population once its age mix has settled (seconds)
+    pub doubling_time: f32,
+}
+
+/// Growth rate (per second) of a branching cycle: the `r` at which the cycle's next-generation
+/// matrix discounted by `exp(-r * lifetime)` has spectral radius one (Euler-Lotka)
+fn cycle_growth_rate(members: &[(usize, Vec<usize>)], lifetimes: &[f32]) -> f32 {
+    let position: HashMap<usize, usize> = members.iter().enumerate().map(|(i, (v, _))| (*v, i)).collect();
+    let spectral_radius = |rate: f64| {
+        // Power iteration on K + I, which is primitive for an irreducible K
+        let mut x = vec![1.0f64; members.len()];
+        let mut radius = 0.0;
+        for _ in 0..200 {
+            let mut next = x.clone();
+            for (i, (v, inside)) in members.iter().enumerate() {
+                let weight = (-rate * lifetimes[*v] as f64).exp();
+                for child in inside {
+                    next[i] += weight * x[position[child]];
+                }
+            }
+            radius = next.iter().copied().fold(0.0, f64::max);
+            x = next.into_iter().map(|value| value / radius).collect();
+        }
+        radius - 1.0
+    };
+    // Two children per generation at the shortest lifetime bounds the rate from above
+    let shortest = members.iter().map(|(v, _)| lifetimes[*v] as f64).fold(f64::INFINITY, f64::min).max(1e-6);
+    let (mut low, mut high) = (0.0, std::f64::consts::LN_2 / shortest);
+    for _ in 0..60 {
+        let mid = 0.5 * (low + high);
+        if spectral_radius(mid) > 1.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (0.5 * (low + high)) as f32
+}
+
+impl GenomeData {
+    /// Cycles of the mode graph reachable from the initial mode whose divisions keep both
+    /// children inside the cycle, fastest first
+    ///
+    /// Cycles sharing a mode are one cycle (a strongly connected component of the cell
+    /// types), so nested and overlapping loops are reported together. Split limits count:
+    /// a mode whose cells stop dividing after their last allowed split breaks its loop.
+    pub fn growth_cycles(&self) -> Vec<GrowthCycle> {
+        if self.modes.is_empty() {
+            return Vec::new();
+        }
+        let graph = TypeGraph::new(self);
+        let lifetimes: Vec<f32> = graph.types.iter().map(|cell| expected_split_interval(&self.modes[cell.mode])).collect();
+        let mut cycles: Vec<GrowthCycle> = graph.branching_components().into_iter().map(|members| {
+            let modes: BTreeSet<usize> = members.iter().map(|(v, _)| graph.types[*v].mode).collect();
+            let rate = cycle_growth_rate(&members, &lifetimes);
+            GrowthCycle {
+                modes: modes.into_iter().collect(),
+                doubling_time: if rate > 0.0 { std::f32::consts::LN_2 / rate } else { f32::INFINITY },
+            }
+        }).collect();
+        cycles.sort_by(|a, b| a.doubling_time.total_cmp(&b.doubling_time));
+        cycles
+    }
+
+    /// Whether the growth of `cycle` was acknowledged for this genome
+    pub fn is_acknowledged(&self, cycle: &GrowthCycle) -> bool {
+        self.acknowledged_cycles.iter().any(|modes| {
+            modes.len() == cycle.modes.len() && modes.iter().zip(&cycle.modes).all(|(mode, &index)| mode.index() == Some(index))
+        })
+    }
+
+    /// Stop warning about `cycle` for this genome (saved with it)
+    pub fn acknowledge_cycle(&mut self, cycle: &GrowthCycle) {
+        if !self.is_acknowledged(cycle) {
+            self.acknowledged_cycles.push(cycle.modes.iter().map(|&mode| ModeIndex::new(mode)).collect());
+        }
+    }
+
+    /// Limit `mode` to `max_splits` consecutive divisions as one undoable edit
+    pub fn limit_cycle_splits(&mut self, mode: usize, max_splits: i32, history: &mut GenomeHistory) -> bool {
+        let Some(settings) = self.modes.get_mut(mode) else {
+            return false;
+        };
+        if settings.max_splits == max_splits {
+            return false;
+        }
+        history.record(format!("limit '{}' to {} splits", settings.name, max_splits), vec![(mode, settings.clone())]);
+        settings.max_splits = max_splits;
+        true
+    }
+
+    /// Multiply the split intervals of `cycle`'s modes by `factor` as one undoable edit
+    pub fn slow_cycle(&mut self, cycle: &GrowthCycle, factor: f32, history: &mut GenomeHistory) {
+        let mut before = Vec::new();
+        for &index in &cycle.modes {
+            let Some(mode) = self.modes.get_mut(index) else {
+                continue;
+            };
+            before.push((index, mode.clone()));
+            mode.split_interval *= factor;
+            if let Some(min) = mode.split_interval_min.as_mut() {
+                *min *= factor;
+            }
+        }
+        if !before.is_empty() {
+            history.record(format!("slow {} cycle modes by {:.1}x", before.len(), factor), before);
+        }
+    }
+
+    /// Growth problems found by the population forecast
+    ///
+    /// Unlike [`validate`](Self::validate) these never block loading: they flag
+    /// genomes whose idealized population explodes or dies out.
+    pub fn growth_warnings(&self) -> Vec<String> {
+        if self.modes.is_empty() {
+            return Vec::new();
+        }
+        match TypeGraph::new(self).outlook() {
+            ForecastOutlook::Divergent => vec!["Population grows exponentially without bound".to_string()],
+            ForecastOutlook::Linear => Vec::new(),
+            ForecastOutlook::DeadEnd { final_population } => vec![format!(
+                "Every lineage ends in cells that never divide: the population stops at {:.0} cells",
+                final_population
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeIndex;
+
+    fn mode(index: usize, interval: f32, child_a: usize, child_b: usize) -> ModeSettings {
+        let mut mode = ModeSettings::new_self_splitting(index, format!("M{}", index));
+        mode.split_interval = interval;
+        mode.child_a.mode_number = ModeIndex::new(child_a);
+        mode.child_b.mode_number = ModeIndex::new(child_b);
+        mode
+    }
+
+    fn genome(modes: Vec<ModeSettings>) -> GenomeData {
+        GenomeData { modes, initial_mode: ModeIndex::new(0), ..GenomeData::default() }
+    }
+
+    #[test]
+    fn test_two_mode_alternation_follows_fibonacci() {
+        // A lives 1 s and B 2 s, each splitting into an A and a B, so the divisions at
+        // second n are those at n - 1 plus those at n - 2
+        let genome = genome(vec![mode(0, 1.0, 0, 1), mode(1, 2.0, 0, 1)]);
+        let forecast = forecast_populations(&genome, 12.0, 120);
+
+        let (mut previous, mut current) = (0.0, 1.0);
+        for second in 1..=12 {
+            let counts = &forecast.samples[second * 10];
+            // A cells are the ones born this second, B cells those born this second or the last
+            assert_eq!(counts[0], current, "A at {} s", second);
+            assert_eq!(counts[1], current + previous, "B at {} s", second);
+            (previous, current) = (current, current + previous);
+        }
+        assert_eq!(forecast.outlook, ForecastOutlook::Divergent);
+        let doubling = forecast.doubling_time().unwrap();
+        // Growth rate of the golden ratio per second
+        assert!((doubling - std::f32::consts::LN_2 / 1.618_034f32.ln()).abs() < 0.05, "{}", doubling);
+    }
+
+    #[test]
+    fn test_split_limit_with_terminal_mode_is_a_dead_end() {
+        let mut splitter = mode(0, 1.0, 0, 0);
+        splitter.max_splits = 2;
+        splitter.mode_a_after_splits = ModeIndex::new(1);
+        splitter.mode_b_after_splits = ModeIndex::new(1);
+        let terminal = mode(1, 60.0, 1, 1);
+        let genome = genome(vec![splitter, terminal]);
+
+        let forecast = forecast_populations(&genome, 5.0, 50);
+        assert_eq!(forecast.outlook, ForecastOutlook::DeadEnd { final_population: 4.0 });
+        assert_eq!(forecast.samples[50], vec![0.0, 4.0]);
+        assert_eq!(forecast.doubling_time(), None);
+        assert_eq!(genome.growth_warnings().len(), 1);
+    }
+
+    #[test]
+    fn test_stem_cell_grows_linearly() {
+        let genome = genome(vec![mode(0, 1.0, 0, 1), mode(1, 60.0, 1, 1)]);
+        let forecast = forecast_populations(&genome, 10.0, 100);
+        assert_eq!(forecast.outlook, ForecastOutlook::Linear);
+        assert_eq!(forecast.samples[100], vec![1.0, 10.0]);
+        assert!(genome.growth_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_ranged_interval_uses_its_mean() {
+        let mut ranged = mode(0, 3.0, 0, 0);
+        ranged.split_interval_min = Some(1.0);
+        let forecast = forecast_populations(&genome(vec![ranged]), 4.0, 40);
+        assert_eq!(forecast.total(19), 1.0);
+        assert_eq!(forecast.total(20), 2.0);
+        assert_eq!(forecast.total(40), 4.0);
+    }
+
+    fn cycle_modes(genome: &GenomeData) -> Vec<Vec<usize>> {
+        genome.growth_cycles().into_iter().map(|cycle| cycle.modes).collect()
+    }
+
+    #[test]
+    fn test_self_doubling_and_alternating_cycles() {
+        let doubling = genome(vec![mode(0, 1.0, 0, 0)]);
+        let cycles = doubling.growth_cycles();
+        assert_eq!(cycle_modes(&doubling), [vec![0]]);
+        assert!((cycles[0].doubling_time - 1.0).abs() < 1e-3, "{}", cycles[0].doubling_time);
+
+        // The Fibonacci pair doubles every ln 2 / ln phi seconds, as its forecast does
+        let fibonacci = genome(vec![mode(0, 1.0, 0, 1), mode(1, 2.0, 0, 1)]);
+        let cycles = fibonacci.growth_cycles();
+        assert_eq!(cycles[0].modes, [0, 1]);
+        let expected = std::f32::consts::LN_2 / 1.618_034f32.ln();
+        assert!((cycles[0].doubling_time - expected).abs() < 1e-3, "{}", cycles[0].doubling_time);
+
+        // Stem cells, dead ends, split limits and unreachable modes don't count
+        let mut limited = mode(0, 1.0, 0, 0);
+        limited.max_splits = 3;
+        let unreachable = mode(2, 0.5, 2, 2);
+        assert!(genome(vec![mode(0, 1.0, 0, 1), mode(1, 60.0, 1, 1), unreachable.clone()]).growth_cycles().is_empty());
+        assert!(genome(vec![limited, mode(1, 60.0, 1, 1), unreachable]).growth_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_and_nested_cycles_are_one_cycle() {
+        // A <-> B and B <-> C share B; D is terminal
+        let overlapping = genome(vec![mode(0, 1.0, 1, 3), mode(1, 1.0, 0, 2), mode(2, 1.0, 1, 3), mode(3, 60.0, 3, 3)]);
+        assert_eq!(cycle_modes(&overlapping), [vec![0, 1, 2]]);
+
+        // A -> B -> C -> A with B also looping on itself inside the larger loop
+        let nested = genome(vec![mode(0, 1.0, 1, 3), mode(1, 1.0, 2, 1), mode(2, 1.0, 0, 3), mode(3, 60.0, 3, 3)]);
+        assert_eq!(cycle_modes(&nested), [vec![0, 1, 2]]);
+
+        // The same loop without a division keeping both children inside grows linearly
+        let linear = genome(vec![mode(0, 1.0, 1, 3), mode(1, 1.0, 2, 3), mode(2, 1.0, 0, 3), mode(3, 60.0, 3, 3)]);
+        assert!(linear.growth_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_separate_cycles_are_listed_fastest_first() {
+        // A slow loop of A and C feeds a fast self-doubling B downstream
+        let genome = genome(vec![mode(0, 4.0, 0, 2), mode(1, 0.5, 1, 1), mode(2, 4.0, 0, 1)]);
+        let cycles = genome.growth_cycles();
+        assert_eq!(cycles.iter().map(|cycle| cycle.modes.clone()).collect::<Vec<_>>(), [vec![1], vec![0, 2]]);
+        assert!((cycles[0].doubling_time - 0.5).abs() < 1e-3);
+        // A makes an A and a C, C makes an A: Fibonacci generations of 4 s
+        let expected = 4.0 * std::f32::consts::LN_2 / 1.618_034f32.ln();
+        assert!((cycles[1].doubling_time - expected).abs() < 1e-2, "{}", cycles[1].doubling_time);
+    }
+
+    #[test]
+    fn test_remedies_and_acknowledgement() {
+        let mut genome = genome(vec![mode(0, 1.0, 0, 0), mode(1, 1.0, 2, 1), mode(2, 1.0, 1, 2)]);
+        genome.modes[0].child_b.mode_number = ModeIndex::new(1);
+        let mut history = GenomeHistory::default();
+        let cycles = genome.growth_cycles();
+        assert_eq!(cycles.iter().map(|cycle| cycle.modes.clone()).collect::<Vec<_>>(), [vec![1, 2]]);
+        let forecast = forecast_populations(&genome, 20.0, 200);
+        assert!(forecast.time_to_exceed(1000.0).is_some_and(|time| time < 20.0));
+
+        genome.slow_cycle(&cycles[0], 2.0, &mut history);
+        assert!((genome.growth_cycles()[0].doubling_time - 2.0 * cycles[0].doubling_time).abs() < 1e-3);
+        history.undo(&mut genome);
+        assert_eq!(genome.modes[1].split_interval, 1.0);
+
+        // Acknowledgement follows the modes through edits of the mode list
+        genome.acknowledge_cycle(&cycles[0]);
+        genome.acknowledge_cycle(&cycles[0]);
+        assert_eq!(genome.acknowledged_cycles.len(), 1);
+        genome.insert_mode(0, mode(9, 60.0, 0, 0));
+        let shifted = genome.growth_cycles();
+        assert_eq!(shifted[0].modes, [2, 3]);
+        assert!(genome.is_acknowledged(&shifted[0]));
+        assert!(genome.functionally_equal(&GenomeData { acknowledged_cycles: Vec::new(), ..genome.clone() }));
+        genome.remove_mode(3);
+        assert!(genome.acknowledged_cycles.is_empty());
+
+        // A split limit breaks a self loop
+        let mut doubling = self::genome(vec![mode(0, 1.0, 0, 0)]);
+        assert!(doubling.limit_cycle_splits(0, 4, &mut history));
+        assert!(doubling.growth_cycles().is_empty());
+        assert_eq!(forecast_populations(&doubling, 10.0, 100).time_to_exceed(16.0), None);
+    }
+}
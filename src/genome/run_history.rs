@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use super::GenomeData;
+use crate::simulation::fingerprint::StableHasher;
+
+/// Runs kept in a genome's history (the oldest are dropped first)
+pub const MAX_RUN_HISTORY: usize = 50;
+
+/// How a recorded run was started
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunKind {
+    /// Preview founder spawned or respawned
+    Preview,
+    /// CPU scene activated from the genome's founder
+    Cpu,
+    /// Runs simulated off-screen, such as a parameter sweep
+    Headless,
+}
+
+impl RunKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            RunKind::Preview => "Preview",
+            RunKind::Cpu => "CPU",
+            RunKind::Headless => "Headless",
+        }
+    }
+}
+
+/// Seed and genome content a run started with
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub seed: u64,
+    /// Seconds since the Unix epoch
+    pub started_at: u64,
+    /// [`GenomeData::content_hash`] when the run started
+    pub genome_hash: u64,
+    pub kind: RunKind,
+}
+
+/// Seed pinning and run history, saved with the genome
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenomeMetadata {
+    /// Seed new runs of this genome start with
+    pub seed: u64,
+    /// Most recent runs, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<RunRecord>,
+}
+
+impl GenomeMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Seed of the most recent run, when it differs from the pinned one
+    pub fn last_run_seed(&self) -> Option<u64> {
+        self.runs.last().map(|run| run.seed).filter(|&seed| seed != self.seed)
+    }
+
+    fn push(&mut self, run: RunRecord) {
+        if self.runs.len() >= MAX_RUN_HISTORY {
+            self.runs.drain(..=self.runs.len() - MAX_RUN_HISTORY);
+        }
+        self.runs.push(run);
+    }
+}
+
+/// Fresh seed from the clock
+///
+/// Kept below 2^32 so it survives editing in a drag value (which goes through f64).
+pub fn draw_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let mut hasher = StableHasher::new();
+    hasher.write(&nanos.to_le_bytes());
+    hasher.0 >> 32
+}
+
+impl GenomeData {
+    /// Stable hash of the genome's contents, leaving out the seed and run history
+    ///
+    /// Hashes the serialized form with object keys sorted at every level, so it doesn't
+    /// depend on field order in the file or in the struct.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        if let Ok(Value::Object(mut fields)) = serde_json::to_value(self) {
+            fields.remove("metadata");
+            let mut canonical = String::new();
+            write_canonical(&Value::Object(fields), &mut canonical);
+            hasher.write(canonical.as_bytes());
+        }
+        hasher.0
+    }
+
+    /// Append a run starting now with `seed` to the history
+    pub fn record_run(&mut self, kind: RunKind, seed: u64) {
+        let started_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.record_run_at(kind, seed, started_at);
+    }
+
+    pub fn record_run_at(&mut self, kind: RunKind, seed: u64, started_at: u64) {
+        let genome_hash = self.content_hash();
+        self.metadata.push(RunRecord { seed, started_at, genome_hash, kind });
+    }
+}
+
+/// JSON text with object keys in sorted order
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&fields[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    fn genome() -> GenomeData {
+        GenomeData {
+            modes: vec![ModeSettings::new_self_splitting(0, "Stem".to_string())],
+            ..GenomeData::default()
+        }
+    }
+
+    /// Same JSON with the keys of every object written in reverse order
+    fn reversed(value: &Value) -> String {
+        match value {
+            Value::Object(fields) => {
+                let entries: Vec<String> = fields.iter().rev()
+                    .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), reversed(value)))
+                    .collect();
+                format!("{{{}}}", entries.join(","))
+            }
+            Value::Array(items) => format!("[{}]", items.iter().map(reversed).collect::<Vec<_>>().join(",")),
+            scalar => scalar.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_ignores_field_order() {
+        let genome = genome();
+        let value = serde_json::to_value(&genome).unwrap();
+        let shuffled: GenomeData = serde_json::from_str(&reversed(&value)).unwrap();
+        assert_eq!(shuffled.content_hash(), genome.content_hash());
+
+        let mut a = String::new();
+        let mut b = String::new();
+        write_canonical(&serde_json::from_str(r#"{"b":1,"a":{"y":[2,{"d":3,"c":4}],"x":null}}"#).unwrap(), &mut a);
+        write_canonical(&serde_json::from_str(r#"{"a":{"x":null,"y":[2,{"c":4,"d":3}]},"b":1}"#).unwrap(), &mut b);
+        assert_eq!(a, b);
+        assert_eq!(a, r#"{"a":{"x":null,"y":[2,{"c":4,"d":3}]},"b":1}"#);
+    }
+
+    #[test]
+    fn test_content_hash_tracks_content_but_not_metadata() {
+        let genome = genome();
+        let hash = genome.content_hash();
+
+        let mut run = genome.clone();
+        run.metadata.seed = 42;
+        run.record_run_at(RunKind::Preview, 42, 1_700_000_000);
+        assert_eq!(run.content_hash(), hash);
+
+        let mut edited = genome.clone();
+        edited.modes[0].split_interval += 1.0;
+        assert_ne!(edited.content_hash(), hash);
+    }
+
+    #[test]
+    fn test_run_history_is_bounded_and_round_trips() {
+        let mut genome = genome();
+        for i in 0..(MAX_RUN_HISTORY as u64 + 7) {
+            genome.record_run_at(RunKind::Cpu, i, 1_700_000_000 + i);
+        }
+        assert_eq!(genome.metadata.runs.len(), MAX_RUN_HISTORY);
+        assert_eq!(genome.metadata.runs[0].seed, 7);
+        assert_eq!(genome.metadata.last_run_seed(), Some(MAX_RUN_HISTORY as u64 + 6));
+
+        let json = serde_json::to_string(&genome).unwrap();
+        let loaded: GenomeData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.metadata, genome.metadata);
+
+        // Genomes saved before the metadata section existed load with seed 0 and no history
+        let mut value = serde_json::to_value(&genome).unwrap();
+        value.as_object_mut().unwrap().remove("metadata");
+        let old: GenomeData = serde_json::from_value(value).unwrap();
+        assert!(old.metadata.is_empty());
+    }
+}
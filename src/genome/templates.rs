@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use serde::{Serialize, Deserialize};
+use std::path::{Path, PathBuf};
+use super::{ColorPalette, GenomeData, ModeIndex, ModeRemap, ModeSettings};
+
+/// Hues tried when picking a color for a new mode
+const CANDIDATE_HUES: usize = 36;
+
+/// Built-in starting points for a new mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeArchetype {
+    Blank,
+    Structural,
+    Proliferative,
+    Swimmer,
+    Terminal,
+}
+
+impl ModeArchetype {
+    pub const ALL: [ModeArchetype; 5] = [
+        ModeArchetype::Blank,
+        ModeArchetype::Structural,
+        ModeArchetype::Proliferative,
+        ModeArchetype::Swimmer,
+        ModeArchetype::Terminal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ModeArchetype::Blank => "Blank",
+            ModeArchetype::Structural => "Structural",
+            ModeArchetype::Proliferative => "Proliferative",
+            ModeArchetype::Swimmer => "Swimmer",
+            ModeArchetype::Terminal => "Terminal",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ModeArchetype::Blank => "Self-splitting defaults",
+            ModeArchetype::Structural => "Adhesion on, stiff springs, never splits",
+            ModeArchetype::Proliferative => "Fast splits, high nutrient gain",
+            ModeArchetype::Swimmer => "Flagellocyte with a strong swim force",
+            ModeArchetype::Terminal => "No splits, protected from starving",
+        }
+    }
+
+    /// Settings of a mode of this archetype that splits into itself at `mode_index`
+    pub fn mode(self, mode_index: usize, name: String) -> ModeSettings {
+        let mut mode = ModeSettings::new_self_splitting(mode_index, name);
+        match self {
+            ModeArchetype::Blank => {}
+            ModeArchetype::Structural => {
+                mode.parent_make_adhesion = true;
+                mode.child_a.keep_adhesion = true;
+                mode.child_b.keep_adhesion = true;
+                // Split intervals above 59 s never come due
+                mode.split_interval = 60.0;
+                let springs = &mut mode.adhesion_settings;
+                springs.linear_spring_stiffness = 400.0;
+                springs.orientation_spring_stiffness = 150.0;
+                springs.break_force = 40.0;
+            }
+            ModeArchetype::Proliferative => {
+                mode.split_interval = 2.0;
+                mode.split_mass = 1.2;
+                mode.nutrient_gain_rate = 0.6;
+            }
+            ModeArchetype::Swimmer => {
+                mode.cell_type = 1;
+                mode.swim_force = 0.8;
+                mode.nutrient_priority = 2.0;
+            }
+            ModeArchetype::Terminal => {
+                // There is no lifespan; a terminal cell never divides and is fed first when nutrients run low
+                mode.max_splits = 0;
+                mode.split_interval = 60.0;
+                mode.nutrient_priority = 3.0;
+                mode.prioritize_when_low = true;
+            }
+        }
+        mode
+    }
+}
+
+/// A mode saved by the user as a template
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserModeTemplate {
+    pub name: String,
+    pub mode: ModeSettings,
+}
+
+/// Template picked in the Add Mode menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateChoice {
+    Builtin(ModeArchetype),
+    /// Index into `ModeTemplates::user`
+    User(usize),
+}
+
+/// Built-in archetypes plus the user's saved templates (persisted as JSON next to the UI settings)
+#[derive(Resource, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeTemplates {
+    pub user: Vec<UserModeTemplate>,
+}
+
+impl ModeTemplates {
+    fn templates_path() -> PathBuf {
+        PathBuf::from("mode_templates.json")
+    }
+
+    pub fn load_from_file(path: &Path) -> crate::error::Result<Self> {
+        crate::error::read_json(path, "mode templates")
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "mode templates")
+    }
+
+    /// Save the user templates to the settings directory
+    pub fn save(&self) -> crate::error::Result<()> {
+        self.save_to_file(&Self::templates_path())
+    }
+
+    /// Store `mode` as a user template, replacing one of the same name
+    ///
+    /// Child and after-splits modes point into the genome the mode came from, so the
+    /// template is made self-splitting; the new mode's own index is filled in when it is added.
+    pub fn add_user(&mut self, name: String, mode: &ModeSettings) {
+        let mut mode = mode.clone();
+        mode.child_a.mode_number = ModeIndex::new(0);
+        mode.child_b.mode_number = ModeIndex::new(0);
+        mode.mode_a_after_splits = ModeIndex::NONE;
+        mode.mode_b_after_splits = ModeIndex::NONE;
+        match self.user.iter_mut().find(|template| template.name == name) {
+            Some(template) => template.mode = mode,
+            None => self.user.push(UserModeTemplate { name, mode }),
+        }
+    }
+
+    /// Append a mode made from `choice` to `genome`; returns its index
+    pub fn add_mode(&self, choice: TemplateChoice, genome: &mut GenomeData) -> Option<usize> {
+        let index = genome.modes.len();
+        self.insert_mode(choice, genome, index).map(|_| index)
+    }
+
+    /// Insert a mode made from `choice` at `index`; returns where the existing modes went
+    ///
+    /// The mode splits into itself and gets the color that contrasts most with the existing modes.
+    pub fn insert_mode(&self, choice: TemplateChoice, genome: &mut GenomeData, index: usize) -> Option<ModeRemap> {
+        let index = index.min(genome.modes.len());
+        let mut mode = match choice {
+            TemplateChoice::Builtin(archetype) => archetype.mode(index, format!("M {}", index + 1)),
+            TemplateChoice::User(user_index) => {
+                let template = self.user.get(user_index)?;
+                let mut mode = template.mode.clone();
+                mode.name = template.name.clone();
+                mode.default_name = format!("M {}", index + 1);
+                mode.child_a.mode_number = ModeIndex::new(index);
+                mode.child_b.mode_number = ModeIndex::new(index);
+                mode
+            }
+        };
+        let existing: Vec<Vec3> = genome.modes.iter().map(|mode| mode.color).collect();
+        mode.color = contrasting_color(&existing);
+        Some(genome.insert_mode(index, mode))
+    }
+}
+
+/// The palette hue farthest (in RGB) from every existing mode color
+pub fn contrasting_color(existing: &[Vec3]) -> Vec3 {
+    (0..CANDIDATE_HUES)
+        .map(|i| ColorPalette::Hsv.color(i, CANDIDATE_HUES))
+        .max_by(|a, b| {
+            let nearest = |color: &Vec3| existing.iter().map(|other| color.distance_squared(*other)).fold(f32::INFINITY, f32::min);
+            nearest(a).total_cmp(&nearest(b))
+        })
+        .unwrap_or(Vec3::ONE)
+}
+
+/// Load the user's mode templates
+pub fn load_mode_templates_on_startup(mut templates: ResMut<ModeTemplates>) {
+    let path = ModeTemplates::templates_path();
+    if !path.exists() {
+        return;
+    }
+    match ModeTemplates::load_from_file(&path) {
+        Ok(loaded) => {
+            info!("Loaded {} mode templates from {:?}", loaded.user.len(), path);
+            *templates = loaded;
+        }
+        Err(e) => warn!("{}, using built-in templates only", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_pass_validation() {
+        let templates = ModeTemplates::default();
+        for archetype in ModeArchetype::ALL {
+            let mut genome = GenomeData::default();
+            let index = templates.add_mode(TemplateChoice::Builtin(archetype), &mut genome).unwrap();
+            assert_eq!(genome.validate(), Vec::<String>::new(), "{}", archetype.name());
+            assert_eq!(genome.modes[index].child_a.mode_number, ModeIndex::new(index));
+        }
+    }
+
+    #[test]
+    fn test_new_mode_color_contrasts_with_existing() {
+        let red = Vec3::new(1.0, 100.0 / 255.0, 100.0 / 255.0);
+        let color = contrasting_color(&[red]);
+        assert!(color.distance(red) > 0.5, "{:?}", color);
+        assert!(contrasting_color(&[color, red]).distance(color) > 0.5);
+    }
+
+    #[test]
+    fn test_user_templates_round_trip() {
+        let mut genome = GenomeData::default();
+        genome.modes[5] = ModeArchetype::Swimmer.mode(5, "Fast swimmer".to_string());
+        genome.modes[5].child_b.mode_number = ModeIndex::new(7);
+        genome.modes[5].swim_force = 0.95;
+
+        let mut templates = ModeTemplates::default();
+        templates.add_user("Fast swimmer".to_string(), &genome.modes[5]);
+        templates.add_user("Fast swimmer".to_string(), &genome.modes[5]);
+        assert_eq!(templates.user.len(), 1);
+
+        let path = std::env::temp_dir().join(format!("biospheres_mode_templates_{}.json", std::process::id()));
+        templates.save_to_file(&path).unwrap();
+        let loaded = ModeTemplates::load_from_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(loaded == templates);
+
+        let index = loaded.add_mode(TemplateChoice::User(0), &mut genome).unwrap();
+        let mode = &genome.modes[index];
+        assert_eq!(mode.name, "Fast swimmer");
+        assert_eq!(mode.swim_force, 0.95);
+        assert_eq!((mode.child_a.mode_number, mode.child_b.mode_number), (ModeIndex::new(index), ModeIndex::new(index)));
+        assert!(genome.validate().is_empty());
+        assert!(loaded.add_mode(TemplateChoice::User(3), &mut genome).is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::genome::CurrentGenome;
+use crate::simulation::anchor_editing::{anchor_under_ray, AnchorSnap};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::organism_surgery::{SurgeryOp, SurgeryRequests};
+use crate::ui::camera::MainCamera;
+use super::bindings::Modifiers;
+use super::pin_tool::active_state;
+use super::surgery_tool::{cursor_ray, pick_bond};
+use super::{SelectedTool, Tool};
+use super::arbitration::{InputArbitration, PointerOwner};
+
+/// Plugin for selecting a bond and dragging its anchor handles
+pub struct AnchorToolPlugin;
+
+impl Plugin for AnchorToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnchorEditState>()
+            .add_systems(Update, (
+                handle_anchor_tool.before(super::CellDraggingSet),
+                render_anchor_handles,
+            ));
+    }
+}
+
+/// Handle radius as a share of its cell's radius
+const HANDLE_SCALE: f32 = 0.22;
+
+/// Anchor changes smaller than this (as a dot product gap) are not sent
+const ANCHOR_EPSILON: f32 = 1e-6;
+
+/// Selected bond and the anchor handle being dragged
+///
+/// The bond is picked with the anchor tool or from the Cell Inspector's bond list; its
+/// handles can be dragged with any tool.
+#[derive(Resource, Default)]
+pub struct AnchorEditState {
+    /// Cells of the selected bond, by ID
+    pub bond: Option<(u32, u32)>,
+    /// End of the selected bond whose handle is held (0 = the first cell)
+    dragging: Option<usize>,
+    /// Snap applied to the handle last frame, for the gizmo
+    snap: Option<AnchorSnap>,
+    /// Point both anchors along the present bond axis on the next frame
+    pub align_requested: bool,
+}
+
+impl AnchorEditState {
+    pub fn select(&mut self, cell_a: u32, cell_b: u32) {
+        self.bond = Some((cell_a, cell_b));
+        self.dragging = None;
+    }
+
+    pub fn is_selected(&self, cell_a: u32, cell_b: u32) -> bool {
+        self.bond == Some((cell_a, cell_b)) || self.bond == Some((cell_b, cell_a))
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging.is_some()
+    }
+}
+
+/// Snap picked by the held modifier: Shift for the bond axis, Ctrl for the local axes,
+/// Alt for the split-plane normal
+fn snap_for(modifiers: Modifiers) -> Option<AnchorSnap> {
+    if modifiers.shift {
+        Some(AnchorSnap::BondAxis)
+    } else if modifiers.ctrl {
+        Some(AnchorSnap::LocalAxes)
+    } else if modifiers.alt {
+        Some(AnchorSnap::SplitNormal)
+    } else {
+        None
+    }
+}
+
+/// Slot of the selected bond and the indices of its first and second cell
+fn selected_bond(state: &CanonicalState, bond: (u32, u32)) -> Option<(usize, [usize; 2])> {
+    let slot = state.bond_between(bond.0, bond.1)?;
+    let first = state.index_of(bond.0)?;
+    let second = state.index_of(bond.1)?;
+    Some((slot, [first, second]))
+}
+
+/// Local anchor direction of cell `index` in bond `slot`
+fn local_anchor(state: &CanonicalState, slot: usize, index: usize) -> Vec3 {
+    let connections = &state.adhesion_connections;
+    if connections.cell_a_index[slot] == index {
+        connections.anchor_direction_a[slot]
+    } else {
+        connections.anchor_direction_b[slot]
+    }
+}
+
+/// World position of a cell's anchor handle
+fn handle_position(state: &CanonicalState, slot: usize, index: usize) -> Vec3 {
+    state.positions[index] + state.rotations[index] * local_anchor(state, slot, index) * state.radii[index]
+}
+
+/// Pick bonds with the anchor tool, drag the selected bond's handles, and auto-align on request
+fn handle_anchor_tool(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut edit: ResMut<AnchorEditState>,
+    mut drag_state: ResMut<super::DragState>,
+    mut requests: ResMut<SurgeryRequests>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    genome: Res<CurrentGenome>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let anchor_tool = selected_tool.tool == Tool::Anchor;
+    if anchor_tool && keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        // First Esc drops the selected bond, the second leaves the tool
+        if edit.bond.take().is_none() {
+            selected_tool.tool = Tool::Select;
+        }
+        edit.dragging = None;
+        return;
+    }
+
+    let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+        return;
+    };
+    // The bond broke or one of its cells died
+    let selected = edit.bond.and_then(|bond| selected_bond(state, bond).map(|found| (bond, found)));
+    if edit.bond.is_some() && selected.is_none() {
+        edit.bond = None;
+        edit.dragging = None;
+        edit.align_requested = false;
+        return;
+    }
+
+    if std::mem::take(&mut edit.align_requested) {
+        if let Some(((first, second), (slot, [first_index, _]))) = selected {
+            let (anchor_a, anchor_b) = state.aligned_anchors(slot);
+            let (anchor_first, anchor_second) = if state.adhesion_connections.cell_a_index[slot] == first_index {
+                (anchor_a, anchor_b)
+            } else {
+                (anchor_b, anchor_a)
+            };
+            requests.pending.push(SurgeryOp::SetAnchors { cell_a: first, cell_b: second, anchor_a: anchor_first, anchor_b: anchor_second });
+        }
+    }
+
+    let Some((_, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+
+    if mouse_button.just_released(MouseButton::Left) {
+        edit.dragging = None;
+        edit.snap = None;
+        return;
+    }
+
+    if mouse_button.just_pressed(MouseButton::Left) {
+        // A press on a handle grabs it, whatever the tool
+        let grabbed = selected.and_then(|(_, (slot, ends))| {
+            (0..2)
+                .filter_map(|side| {
+                    let index = ends[side];
+                    let radius = state.radii[index] * HANDLE_SCALE;
+                    super::cell_dragging::ray_sphere_intersection(ray.origin, *ray.direction, handle_position(state, slot, index), radius)
+                        .map(|distance| (side, distance))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(side, _)| side)
+        });
+        if let Some(side) = grabbed {
+            if arbitration.claim(PointerOwner::Tool) {
+                // The drag belongs to the handle, not to the cell under it
+                drag_state.skip_next_drag = true;
+                edit.dragging = Some(side);
+            }
+            return;
+        }
+
+        if anchor_tool && arbitration.claim(PointerOwner::Tool) {
+            drag_state.skip_next_drag = true;
+            edit.bond = pick_bond(ray, state);
+            edit.dragging = None;
+        }
+        return;
+    }
+
+    let (Some(side), Some(((first, second), (slot, ends)))) = (edit.dragging, selected) else {
+        return;
+    };
+    if !mouse_button.pressed(MouseButton::Left) || !arbitration.owns(PointerOwner::Tool) {
+        edit.dragging = None;
+        return;
+    }
+
+    let index = ends[side];
+    let partner = ends[1 - side];
+    let rotation = state.rotations[index];
+    let mut anchor = anchor_under_ray(ray.origin, *ray.direction, state.positions[index], state.radii[index], rotation);
+    edit.snap = snap_for(Modifiers::from_keyboard(&keyboard));
+    if let Some(snap) = edit.snap {
+        let bond_axis = (rotation.inverse() * (state.positions[partner] - state.positions[index])).normalize_or(anchor);
+        anchor = snap.apply(anchor, bond_axis, state.local_split_normal(index, &genome.genome));
+    }
+    if anchor.dot(local_anchor(state, slot, index)) > 1.0 - ANCHOR_EPSILON {
+        return;
+    }
+
+    let [anchor_first, anchor_second] = match side {
+        0 => [anchor, local_anchor(state, slot, partner)],
+        _ => [local_anchor(state, slot, partner), anchor],
+    };
+    requests.pending.push(SurgeryOp::SetAnchors { cell_a: first, cell_b: second, anchor_a: anchor_first, anchor_b: anchor_second });
+}
+
+const BOND_COLOR: Color = Color::srgb(0.55, 0.85, 1.0);
+const HANDLE_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const SNAP_COLOR: Color = Color::srgb(1.0, 0.45, 0.2);
+
+/// Draw the selected bond with a handle at each anchor point, and the bond under the cursor while picking
+fn render_anchor_handles(
+    mut gizmos: Gizmos,
+    selected_tool: Res<SelectedTool>,
+    edit: Res<AnchorEditState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+        return;
+    };
+
+    if selected_tool.tool == Tool::Anchor && edit.dragging.is_none() {
+        let hovered = cursor_ray(&window_query, &camera_query)
+            .and_then(|(_, ray)| pick_bond(ray, state))
+            .filter(|&(a, b)| !edit.is_selected(a, b))
+            .and_then(|bond| selected_bond(state, bond));
+        if let Some((_, [a, b])) = hovered {
+            gizmos.line(state.positions[a], state.positions[b], BOND_COLOR.with_alpha(0.5));
+        }
+    }
+
+    let Some((slot, ends)) = edit.bond.and_then(|bond| selected_bond(state, bond)) else {
+        return;
+    };
+    let handles = ends.map(|index| handle_position(state, slot, index));
+    gizmos.line(handles[0], handles[1], BOND_COLOR);
+    for side in 0..2 {
+        let index = ends[side];
+        let center = state.positions[index];
+        gizmos.line(center, handles[side], BOND_COLOR.with_alpha(0.4));
+        let held = edit.dragging == Some(side);
+        let color = match (held, edit.snap) {
+            (true, Some(_)) => SNAP_COLOR,
+            _ => HANDLE_COLOR,
+        };
+        let scale = if held { 1.3 } else { 1.0 };
+        gizmos.sphere(handles[side], state.radii[index] * HANDLE_SCALE * scale, color);
+        if held {
+            // The surface the handle slides over
+            gizmos.sphere(center, state.radii[index], HANDLE_COLOR.with_alpha(0.25));
+        }
+    }
+}
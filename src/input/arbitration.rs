@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+/// Consumer of pointer presses and drags
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerOwner {
+    /// egui panels and windows
+    Ui,
+    /// Camera orbit, pan and look drags
+    Camera,
+    /// Dragging cells in the viewport
+    CellDrag,
+    /// Click tools (pin, cut, graft, stamp, measure, sample)
+    Tool,
+}
+
+/// What the UI pass reported about the pointer and keyboard
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CaptureSignals {
+    /// The pointer is over UI rather than the open part of the viewport
+    /// (panels, viewport overlays, resize handles at the viewport's edge)
+    pub pointer_over_ui: bool,
+    /// A widget is being dragged or pressed
+    pub ui_using_pointer: bool,
+    /// A text field (or a focused detached panel window) takes the keys
+    pub ui_wants_keyboard: bool,
+}
+
+/// Single source of truth for who gets mouse and keyboard input
+///
+/// The UI pass reports its capture signals; early each frame the press state decides
+/// the drag owner. A press on UI belongs to the UI; a press in the viewport belongs to
+/// whichever consumer claims it first (the camera before the tools). The owner keeps
+/// the drag until every mouse button is released, wherever the cursor goes meanwhile.
+#[derive(Resource, Debug, Default)]
+pub struct InputArbitration {
+    signals: CaptureSignals,
+    drag_owner: Option<PointerOwner>,
+}
+
+impl InputArbitration {
+    /// Signals of the last UI pass
+    pub fn set_signals(&mut self, signals: CaptureSignals) {
+        self.signals = signals;
+    }
+
+    pub fn signals(&self) -> CaptureSignals {
+        self.signals
+    }
+
+    /// Start-of-frame update from the mouse buttons
+    pub fn begin_frame(&mut self, any_pressed: bool, any_just_pressed: bool) {
+        if !any_pressed && !any_just_pressed {
+            self.drag_owner = None;
+            return;
+        }
+        if any_just_pressed && self.drag_owner.is_none() && (self.signals.pointer_over_ui || self.signals.ui_using_pointer) {
+            self.drag_owner = Some(PointerOwner::Ui);
+        }
+    }
+
+    pub fn drag_owner(&self) -> Option<PointerOwner> {
+        self.drag_owner
+    }
+
+    pub fn owns(&self, owner: PointerOwner) -> bool {
+        self.drag_owner == Some(owner)
+    }
+
+    /// Take the press for `owner` if nobody holds it; true if `owner` holds it now
+    pub fn claim(&mut self, owner: PointerOwner) -> bool {
+        match self.drag_owner {
+            None => {
+                self.drag_owner = Some(owner);
+                true
+            }
+            Some(current) => current == owner,
+        }
+    }
+
+    /// Whether a viewport consumer may start on a press now (nobody holds the pointer
+    /// and it isn't over UI)
+    pub fn pointer_free(&self) -> bool {
+        self.drag_owner.is_none() && !self.signals.pointer_over_ui && !self.signals.ui_using_pointer
+    }
+
+    /// Whether the scroll wheel and pinch gestures belong to the viewport
+    pub fn scroll_free(&self) -> bool {
+        !self.signals.pointer_over_ui && !self.signals.ui_using_pointer && !self.owns(PointerOwner::Ui)
+    }
+
+    /// Whether key presses belong to the viewport (camera movement, tool shortcuts)
+    pub fn keyboard_free(&self) -> bool {
+        !self.signals.ui_wants_keyboard
+    }
+}
+
+/// Update the drag owner from this frame's mouse buttons, before any consumer reads it
+pub fn update_input_arbitration(mouse_button: Res<ButtonInput<MouseButton>>, mut arbitration: ResMut<InputArbitration>) {
+    let any_pressed = mouse_button.get_pressed().next().is_some();
+    let any_just_pressed = mouse_button.get_just_pressed().next().is_some();
+    arbitration.begin_frame(any_pressed, any_just_pressed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn over_viewport() -> CaptureSignals {
+        CaptureSignals::default()
+    }
+
+    fn over_panel() -> CaptureSignals {
+        CaptureSignals { pointer_over_ui: true, ..Default::default() }
+    }
+
+    #[test]
+    fn test_drag_started_in_viewport_keeps_its_owner_over_panels() {
+        let mut arbitration = InputArbitration::default();
+
+        // Press in the viewport: the camera claims it, the tools then can't
+        arbitration.set_signals(over_viewport());
+        arbitration.begin_frame(true, true);
+        assert!(arbitration.pointer_free());
+        assert!(arbitration.claim(PointerOwner::Camera));
+        assert!(!arbitration.claim(PointerOwner::Tool));
+        assert!(!arbitration.pointer_free());
+
+        // The cursor moves onto a panel while the button is held
+        arbitration.set_signals(over_panel());
+        arbitration.begin_frame(true, false);
+        assert!(arbitration.owns(PointerOwner::Camera));
+        assert!(!arbitration.claim(PointerOwner::Ui));
+
+        // Release frees the pointer; the next press over the panel is the UI's
+        arbitration.begin_frame(false, false);
+        assert_eq!(arbitration.drag_owner(), None);
+        arbitration.begin_frame(true, true);
+        assert!(arbitration.owns(PointerOwner::Ui));
+        assert!(!arbitration.claim(PointerOwner::Camera));
+        assert!(!arbitration.scroll_free());
+    }
+
+    #[test]
+    fn test_drag_started_on_ui_never_reaches_the_viewport() {
+        let mut arbitration = InputArbitration::default();
+        // A resize handle at the viewport's edge is being pressed
+        arbitration.set_signals(CaptureSignals { ui_using_pointer: true, ..Default::default() });
+        arbitration.begin_frame(true, true);
+        assert!(arbitration.owns(PointerOwner::Ui));
+
+        // Dragged into the open viewport, it still isn't the camera's
+        arbitration.set_signals(over_viewport());
+        arbitration.begin_frame(true, false);
+        assert!(!arbitration.claim(PointerOwner::Camera));
+        assert!(!arbitration.claim(PointerOwner::CellDrag));
+
+        arbitration.begin_frame(false, false);
+        assert!(arbitration.pointer_free());
+        assert!(arbitration.scroll_free());
+    }
+
+    #[test]
+    fn test_text_field_focus_holds_the_keyboard() {
+        let mut arbitration = InputArbitration::default();
+        assert!(arbitration.keyboard_free());
+        arbitration.set_signals(CaptureSignals { ui_wants_keyboard: true, ..Default::default() });
+        assert!(!arbitration.keyboard_free());
+        // Keyboard focus doesn't take the pointer
+        assert!(arbitration.pointer_free());
+    }
+}
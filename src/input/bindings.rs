@@ -0,0 +1,409 @@
+//! Camera and genome graph bindings, including the touchpad-friendly "laptop mode"
+//!
+//! Manual checks after changing gesture handling (no mouse attached):
+//! - Laptop mode: Ctrl+drag orbits, Shift+drag pans, two-finger drag orbits,
+//!   Ctrl+two-finger scroll and pinch (macOS) zoom; a plain click still selects
+//!   and drags cells.
+//! - Gestures over a panel scroll or zoom the panel, never the camera.
+//! - Genome Graph: Shift+drag pans, pinch or Ctrl+scroll resizes the cards.
+//! - Desktop mode: middle-drag orbits, Shift+middle-drag pans, the wheel zooms.
+//! - Bindings and sensitivities survive a restart.
+
+use bevy::input::mouse::MouseScrollUnit;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Scroll pixels treated as one wheel line
+pub const PIXELS_PER_LINE: f32 = 16.0;
+
+/// Pointer button of a drag binding
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingButton {
+    Left,
+    Middle,
+    Right,
+}
+
+impl BindingButton {
+    pub const ALL: [BindingButton; 3] = [BindingButton::Left, BindingButton::Middle, BindingButton::Right];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BindingButton::Left => "Left",
+            BindingButton::Middle => "Middle",
+            BindingButton::Right => "Right",
+        }
+    }
+
+    pub fn mouse_button(self) -> MouseButton {
+        match self {
+            BindingButton::Left => MouseButton::Left,
+            BindingButton::Middle => MouseButton::Middle,
+            BindingButton::Right => MouseButton::Right,
+        }
+    }
+}
+
+/// Modifier key a drag binding requires
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingModifier {
+    None,
+    Ctrl,
+    Shift,
+    Alt,
+}
+
+impl BindingModifier {
+    pub const ALL: [BindingModifier; 4] = [BindingModifier::None, BindingModifier::Ctrl, BindingModifier::Shift, BindingModifier::Alt];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BindingModifier::None => "-",
+            BindingModifier::Ctrl => "Ctrl",
+            BindingModifier::Shift => "Shift",
+            BindingModifier::Alt => "Alt",
+        }
+    }
+
+    pub fn held(self, modifiers: Modifiers) -> bool {
+        match self {
+            BindingModifier::None => true,
+            BindingModifier::Ctrl => modifiers.ctrl,
+            BindingModifier::Shift => modifiers.shift,
+            BindingModifier::Alt => modifiers.alt,
+        }
+    }
+}
+
+/// Modifier keys held this frame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers { ctrl: false, shift: false, alt: false };
+
+    pub fn from_keyboard(keyboard: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            // Cmd stands in for Ctrl on macOS
+            ctrl: keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::SuperLeft, KeyCode::SuperRight]),
+            shift: keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            alt: keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+        }
+    }
+}
+
+/// Button plus modifier that starts a drag
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DragBinding {
+    pub button: BindingButton,
+    pub modifier: BindingModifier,
+}
+
+impl DragBinding {
+    pub const fn new(button: BindingButton, modifier: BindingModifier) -> Self {
+        Self { button, modifier }
+    }
+
+    pub fn label(self) -> String {
+        match self.modifier {
+            BindingModifier::None => format!("{} drag", self.button.name()),
+            modifier => format!("{}+{} drag", modifier.name(), self.button.name()),
+        }
+    }
+}
+
+/// What a camera drag does
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragAction {
+    /// Rotate around the orbit center (orbit camera)
+    Orbit,
+    /// Move the orbit center in the view plane (orbit camera)
+    Pan,
+    /// Turn the free-fly camera
+    Look,
+}
+
+impl DragAction {
+    pub fn name(self) -> &'static str {
+        match self {
+            DragAction::Orbit => "Orbit",
+            DragAction::Pan => "Pan",
+            DragAction::Look => "Free-fly look",
+        }
+    }
+}
+
+/// What scrolling does in the orbit camera
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAction {
+    Zoom,
+    Orbit,
+}
+
+impl ScrollAction {
+    pub const ALL: [ScrollAction; 2] = [ScrollAction::Zoom, ScrollAction::Orbit];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ScrollAction::Zoom => "Zoom",
+            ScrollAction::Orbit => "Orbit",
+        }
+    }
+}
+
+/// Multipliers for each gesture axis (1.0 is the stock speed)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct GestureSensitivity {
+    pub orbit_drag: f32,
+    pub pan_drag: f32,
+    pub wheel_zoom: f32,
+    pub touchpad_zoom: f32,
+    pub touchpad_orbit: f32,
+    pub pinch_zoom: f32,
+    pub graph_zoom: f32,
+}
+
+impl Default for GestureSensitivity {
+    fn default() -> Self {
+        Self {
+            orbit_drag: 1.0,
+            pan_drag: 1.0,
+            wheel_zoom: 1.0,
+            touchpad_zoom: 1.0,
+            touchpad_orbit: 1.0,
+            pinch_zoom: 1.0,
+            graph_zoom: 1.0,
+        }
+    }
+}
+
+impl GestureSensitivity {
+    /// (label, value) of every axis, for sliders
+    pub fn axes_mut(&mut self) -> [(&'static str, &mut f32); 7] {
+        [
+            ("Orbit drag", &mut self.orbit_drag),
+            ("Pan drag", &mut self.pan_drag),
+            ("Wheel zoom", &mut self.wheel_zoom),
+            ("Touchpad zoom", &mut self.touchpad_zoom),
+            ("Touchpad orbit", &mut self.touchpad_orbit),
+            ("Pinch zoom", &mut self.pinch_zoom),
+            ("Graph zoom", &mut self.graph_zoom),
+        ]
+    }
+}
+
+/// Binding sets switched as a whole
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingPreset {
+    /// Middle-drag orbit and the wheel zooms
+    Desktop,
+    /// No middle button needed: modifier drags and touchpad gestures
+    Laptop,
+}
+
+/// Persisted input action map for the camera and the genome graph
+///
+/// A binding with a modifier wins over a plain binding on the same button, so
+/// Shift+middle-drag pans while middle-drag orbits.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct InputBindings {
+    pub orbit: DragBinding,
+    pub pan: DragBinding,
+    pub look: DragBinding,
+    /// Scrolling with a mouse wheel (line units)
+    pub wheel: ScrollAction,
+    /// Two-finger scrolling on a touchpad (pixel units)
+    pub touchpad_scroll: ScrollAction,
+    pub graph_pan: DragBinding,
+    pub sensitivity: GestureSensitivity,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self::preset(BindingPreset::Desktop)
+    }
+}
+
+impl InputBindings {
+    pub fn preset(preset: BindingPreset) -> Self {
+        use BindingButton::*;
+        use BindingModifier as Mod;
+        match preset {
+            BindingPreset::Desktop => Self {
+                orbit: DragBinding::new(Middle, Mod::None),
+                pan: DragBinding::new(Middle, Mod::Shift),
+                look: DragBinding::new(Right, Mod::None),
+                wheel: ScrollAction::Zoom,
+                touchpad_scroll: ScrollAction::Zoom,
+                graph_pan: DragBinding::new(Middle, Mod::None),
+                sensitivity: GestureSensitivity::default(),
+            },
+            BindingPreset::Laptop => Self {
+                orbit: DragBinding::new(Left, Mod::Ctrl),
+                pan: DragBinding::new(Left, Mod::Shift),
+                // Two-finger click is a right click on most touchpads
+                look: DragBinding::new(Right, Mod::None),
+                wheel: ScrollAction::Zoom,
+                touchpad_scroll: ScrollAction::Orbit,
+                graph_pan: DragBinding::new(Left, Mod::Shift),
+                sensitivity: GestureSensitivity::default(),
+            },
+        }
+    }
+
+    /// Switch every binding to `preset`, keeping the sensitivities
+    pub fn apply_preset(&mut self, preset: BindingPreset) {
+        let sensitivity = self.sensitivity;
+        *self = Self { sensitivity, ..Self::preset(preset) };
+    }
+
+    /// Preset the bindings currently match, if any
+    pub fn matching_preset(&self) -> Option<BindingPreset> {
+        [BindingPreset::Desktop, BindingPreset::Laptop]
+            .into_iter()
+            .find(|&preset| Self { sensitivity: self.sensitivity, ..Self::preset(preset) } == *self)
+    }
+
+    pub fn drag(&self, action: DragAction) -> DragBinding {
+        match action {
+            DragAction::Orbit => self.orbit,
+            DragAction::Pan => self.pan,
+            DragAction::Look => self.look,
+        }
+    }
+
+    pub fn drag_mut(&mut self, action: DragAction) -> &mut DragBinding {
+        match action {
+            DragAction::Orbit => &mut self.orbit,
+            DragAction::Pan => &mut self.pan,
+            DragAction::Look => &mut self.look,
+        }
+    }
+
+    /// Drag started by the buttons `pressed` reports, among `actions`
+    ///
+    /// Bindings whose modifier is held beat plain bindings on the same button.
+    pub fn resolve_drag(
+        &self,
+        actions: &[DragAction],
+        pressed: impl Fn(BindingButton) -> bool,
+        modifiers: Modifiers,
+    ) -> Option<DragAction> {
+        actions
+            .iter()
+            .copied()
+            .filter(|&action| {
+                let binding = self.drag(action);
+                pressed(binding.button) && binding.modifier.held(modifiers)
+            })
+            .max_by_key(|&action| self.drag(action).modifier != BindingModifier::None)
+    }
+
+    /// What a scroll of `unit` does; Ctrl+scroll always zooms (touchpads send pinches that way)
+    pub fn resolve_scroll(&self, unit: MouseScrollUnit, modifiers: Modifiers) -> ScrollAction {
+        if modifiers.ctrl {
+            return ScrollAction::Zoom;
+        }
+        match unit {
+            MouseScrollUnit::Line => self.wheel,
+            MouseScrollUnit::Pixel => self.touchpad_scroll,
+        }
+    }
+
+    /// Zoom amount of a scroll, in wheel lines scaled by the matching sensitivity
+    pub fn scroll_zoom(&self, delta: f32, unit: MouseScrollUnit) -> f32 {
+        match unit {
+            MouseScrollUnit::Line => delta * self.sensitivity.wheel_zoom,
+            MouseScrollUnit::Pixel => delta / PIXELS_PER_LINE * self.sensitivity.touchpad_zoom,
+        }
+    }
+
+    /// Whether the graph pan binding matches the held buttons and modifiers
+    pub fn graph_pan_active(&self, pressed: impl Fn(BindingButton) -> bool, modifiers: Modifiers) -> bool {
+        pressed(self.graph_pan.button) && self.graph_pan.modifier.held(modifiers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORBIT_ACTIONS: [DragAction; 2] = [DragAction::Orbit, DragAction::Pan];
+
+    fn only(button: BindingButton) -> impl Fn(BindingButton) -> bool {
+        move |pressed| pressed == button
+    }
+
+    #[test]
+    fn test_modifier_binding_beats_plain_binding() {
+        let bindings = InputBindings::default();
+        let shift = Modifiers { shift: true, ..Modifiers::NONE };
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Middle), Modifiers::NONE), Some(DragAction::Orbit));
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Middle), shift), Some(DragAction::Pan));
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Left), shift), None);
+        // Free-fly only resolves its own action
+        assert_eq!(bindings.resolve_drag(&[DragAction::Look], only(BindingButton::Middle), Modifiers::NONE), None);
+        assert_eq!(bindings.resolve_drag(&[DragAction::Look], only(BindingButton::Right), shift), Some(DragAction::Look));
+    }
+
+    #[test]
+    fn test_laptop_mode_needs_no_middle_button() {
+        let bindings = InputBindings::preset(BindingPreset::Laptop);
+        for action in [DragAction::Orbit, DragAction::Pan, DragAction::Look] {
+            assert_ne!(bindings.drag(action).button, BindingButton::Middle, "{:?}", action);
+        }
+        assert_ne!(bindings.graph_pan.button, BindingButton::Middle);
+
+        let ctrl = Modifiers { ctrl: true, ..Modifiers::NONE };
+        let shift = Modifiers { shift: true, ..Modifiers::NONE };
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Left), ctrl), Some(DragAction::Orbit));
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Left), shift), Some(DragAction::Pan));
+        // A plain left drag stays with the tools
+        assert_eq!(bindings.resolve_drag(&ORBIT_ACTIONS, only(BindingButton::Left), Modifiers::NONE), None);
+        assert!(bindings.graph_pan_active(only(BindingButton::Left), shift));
+        assert!(!bindings.graph_pan_active(only(BindingButton::Left), Modifiers::NONE));
+    }
+
+    #[test]
+    fn test_scroll_resolution_by_unit_and_modifier() {
+        let mut bindings = InputBindings::default();
+        bindings.apply_preset(BindingPreset::Laptop);
+        let ctrl = Modifiers { ctrl: true, ..Modifiers::NONE };
+        assert_eq!(bindings.resolve_scroll(MouseScrollUnit::Line, Modifiers::NONE), ScrollAction::Zoom);
+        assert_eq!(bindings.resolve_scroll(MouseScrollUnit::Pixel, Modifiers::NONE), ScrollAction::Orbit);
+        assert_eq!(bindings.resolve_scroll(MouseScrollUnit::Pixel, ctrl), ScrollAction::Zoom);
+
+        // One line of wheel equals PIXELS_PER_LINE touchpad pixels at equal sensitivity
+        assert_eq!(bindings.scroll_zoom(1.0, MouseScrollUnit::Line), bindings.scroll_zoom(PIXELS_PER_LINE, MouseScrollUnit::Pixel));
+        bindings.sensitivity.touchpad_zoom = 2.0;
+        assert_eq!(bindings.scroll_zoom(PIXELS_PER_LINE, MouseScrollUnit::Pixel), 2.0);
+    }
+
+    #[test]
+    fn test_presets_keep_sensitivity_and_are_recognized() {
+        let mut bindings = InputBindings::default();
+        bindings.sensitivity.pinch_zoom = 3.0;
+        assert_eq!(bindings.matching_preset(), Some(BindingPreset::Desktop));
+
+        bindings.apply_preset(BindingPreset::Laptop);
+        assert_eq!(bindings.matching_preset(), Some(BindingPreset::Laptop));
+        assert_eq!(bindings.sensitivity.pinch_zoom, 3.0);
+
+        bindings.pan.modifier = BindingModifier::Alt;
+        assert_eq!(bindings.matching_preset(), None);
+
+        let json = serde_json::to_string(&bindings).unwrap();
+        let restored: InputBindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, bindings);
+        // Settings written before a field existed still load
+        let partial: InputBindings = serde_json::from_str(r#"{"wheel":"Orbit"}"#).unwrap();
+        assert_eq!(partial.wheel, ScrollAction::Orbit);
+        assert_eq!(partial.orbit, InputBindings::default().orbit);
+    }
+}
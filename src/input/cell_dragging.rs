@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use crate::cell::{Cell, CellPosition};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::soft_drag::{SoftDrag, SoftDragRegion};
 use crate::ui::camera::MainCamera;
 
 /// Plugin for cell dragging interaction
@@ -17,6 +19,7 @@ impl Plugin for CellDraggingPlugin {
                 handle_drag_start,
                 handle_drag_update,
                 handle_drag_end,
+                render_soft_drag_region,
             ).chain().in_set(CellDraggingSet));
     }
 }
@@ -32,6 +35,62 @@ pub struct DragState {
     pub last_click_time: f32,
     pub double_click_threshold: f32,
     pub skip_next_drag: bool, // Flag to skip drag when camera snap handles double-click
+    /// Soft drag options (Tools > Drag Options)
+    pub soft: SoftDragSettings,
+    /// The current drag moves a falloff region instead of teleporting one cell
+    pub soft_active: bool,
+    /// Region of the current soft drag, for the highlight and Preview mode
+    pub soft_region: Option<SoftDrag>,
+}
+
+/// Soft drag options
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoftDragSettings {
+    pub enabled: bool,
+    /// Falloff radius in world units
+    pub radius: f32,
+    /// Pick the region by adhesion hops instead of distance
+    pub follow_adhesions: bool,
+    pub hop_limit: u32,
+    /// Share of the pull spread over the grabbed cell's whole organism (0 to 1)
+    pub cohesion: f32,
+}
+
+impl Default for SoftDragSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 5.0,
+            follow_adhesions: false,
+            hop_limit: 4,
+            cohesion: 0.0,
+        }
+    }
+}
+
+impl SoftDragSettings {
+    pub fn region(&self) -> SoftDragRegion {
+        if self.follow_adhesions {
+            SoftDragRegion::Hops(self.hop_limit)
+        } else {
+            SoftDragRegion::Radius(self.radius)
+        }
+    }
+
+    /// Drag of the cell at index `grabbed`: the soft region if enabled, else the cell alone
+    pub fn drag(&self, state: &CanonicalState, grabbed: usize) -> SoftDrag {
+        let drag = if self.enabled {
+            SoftDrag::around(state, grabbed, self.region())
+        } else {
+            SoftDrag::single(state, grabbed)
+        };
+        drag.with_cohesion(state.cell_ids[grabbed], self.cohesion)
+    }
+
+    /// CPU drags pull with forces rather than teleporting the cell
+    pub fn pulls(&self) -> bool {
+        self.enabled || self.cohesion > 0.0
+    }
 }
 
 impl Default for DragState {
@@ -44,6 +103,9 @@ impl Default for DragState {
             last_click_time: -999.0,
             double_click_threshold: 0.2, // 200ms for double-click (rapid)
             skip_next_drag: false,
+            soft: SoftDragSettings::default(),
+            soft_active: false,
+            soft_region: None,
         }
     }
 }
@@ -55,10 +117,17 @@ fn handle_drag_start(
     window_query: Query<&Window, With<PrimaryWindow>>,
     mut camera_query: Query<(&Camera, &GlobalTransform, &mut MainCamera)>,
     cell_query: Query<(Entity, &CellPosition, &Cell)>,
-    ui_capture: Res<crate::ui::camera::UiWantCapture>,
+    mut arbitration: ResMut<super::arbitration::InputArbitration>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    mut main_sim_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut picking: ResMut<super::Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
 ) {
-    // Don't process mouse input if UI wants to capture it
-    if ui_capture.want_capture_mouse {
+    // Don't start on a press the UI, the camera or a tool already owns
+    if !arbitration.pointer_free() {
         return;
     }
     
@@ -101,27 +170,19 @@ fn handle_drag_start(
         return;
     };
 
-    // Find closest cell intersected by ray
-    let mut closest_hit: Option<(Entity, f32, Vec3)> = None;
-
-    for (entity, cell_pos, cell) in cell_query.iter() {
-        // Ray-sphere intersection test
-        if let Some(hit_distance) = ray_sphere_intersection(
-            ray.origin,
-            *ray.direction,
-            cell_pos.position,
-            cell.radius,
-        ) {
-            // Keep track of closest hit
-            if closest_hit.is_none() || hit_distance < closest_hit.unwrap().1 {
-                let hit_point = ray.origin + *ray.direction * hit_distance;
-                closest_hit = Some((entity, hit_distance, hit_point));
-            }
-        }
-    }
+    // Nearest opaque enough cell under the cursor (Alt+click cycles through the ones behind it)
+    let hit = picking.pick(
+        ray,
+        cursor_pos,
+        super::picking::cycle_held(&keyboard),
+        super::picking::entity_candidates(cell_query.iter(), &current_genome.genome),
+    );
 
     // If we hit a cell, start dragging it
-    if let Some((entity, hit_distance, _hit_point)) = closest_hit {
+    if let Some(super::picking::PickHit { target: entity, distance: hit_distance, .. }) = hit {
+        if !arbitration.claim(super::arbitration::PointerOwner::CellDrag) {
+            return;
+        }
         let (_, cell_pos, _cell) = cell_query.get(entity).unwrap();
         
         // Stop following any entity when starting to drag
@@ -157,6 +218,42 @@ fn handle_drag_start(
             Vec3::ZERO
         };
         
+        // Dragging edits the preview state, so mark it on the preview timeline
+        if sim_state.mode == crate::simulation::SimulationMode::Preview {
+            if let Some(preview_state) = preview_state.as_deref() {
+                let cell_id = preview_state.index_to_entity.iter()
+                    .position(|e| *e == Some(entity))
+                    .map(|index| preview_state.canonical_state.cell_ids[index]);
+                let tick = crate::simulation::clock::ticks_to_reach(preview_state.current_time, timeline.timestep());
+                timeline.push(crate::simulation::event_timeline::TimelineEvent::new(
+                    tick,
+                    crate::simulation::event_timeline::TimelineEventKind::Drag,
+                    [cell_id, None],
+                ));
+            }
+        }
+
+        // A soft or cohesive drag records its region up front and CPU mode pulls it with forces.
+        // Preview records every drag (a plain one is a single-cell region) into its history.
+        let soft = drag_state.soft;
+        let soft_region = match sim_state.mode {
+            crate::simulation::SimulationMode::Cpu if soft.pulls() => main_sim_state.as_deref_mut().and_then(|main_state| {
+                let index = *main_state.entity_to_index.get(&entity)?;
+                let drag = soft.drag(&main_state.canonical_state, index);
+                main_state.canonical_state.soft_drag = Some(drag.clone());
+                Some(drag)
+            }),
+            crate::simulation::SimulationMode::Preview => preview_state.as_deref_mut().and_then(|preview_state| {
+                let index = preview_state.index_to_entity.iter().position(|e| *e == Some(entity))?;
+                let drag = soft.drag(&preview_state.canonical_state, index);
+                preview_state.begin_drag(&drag, timeline.timestep());
+                soft.enabled.then_some(drag)
+            }),
+            _ => None,
+        };
+
+        drag_state.soft_active = soft_region.is_some();
+        drag_state.soft_region = soft_region;
         drag_state.dragged_entity = Some(entity);
         drag_state.drag_offset = drag_offset;
         drag_state.drag_plane_normal = drag_plane_normal;
@@ -166,7 +263,7 @@ fn handle_drag_start(
 
 /// System to update dragged cell position
 fn handle_drag_update(
-    drag_state: Res<DragState>,
+    mut drag_state: ResMut<DragState>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     mut cell_query: Query<&mut CellPosition>,
@@ -217,6 +314,22 @@ fn handle_drag_update(
     // Calculate new position
     let new_position = plane_hit - drag_state.drag_offset;
 
+    // Preview drags pull through the replayed physics, which moves the cell
+    if sim_state.mode == crate::simulation::SimulationMode::Preview {
+        if let Some(region) = drag_state.soft_region.as_mut() {
+            region.target = new_position;
+        }
+        if let Some(preview_state) = preview_sim_state.as_deref_mut() {
+            preview_state.drag_to(new_position);
+        }
+        return;
+    }
+
+    if drag_state.soft_active {
+        update_soft_drag(&mut drag_state, new_position, main_sim_state);
+        return;
+    }
+
     // Update cell position in ECS
     if let Ok(mut cell_pos) = cell_query.get_mut(dragged_entity) {
         cell_pos.position = new_position;
@@ -232,27 +345,31 @@ fn handle_drag_update(
                         main_state.canonical_state.positions[cell_index] = new_position;
                         main_state.canonical_state.velocities[cell_index] = Vec3::ZERO;
                         main_state.canonical_state.prev_positions[cell_index] = new_position;
+                        main_state.canonical_state.collision_cache.invalidate();
                     }
                 }
             }
         }
-        crate::simulation::SimulationMode::Preview => {
-            if let Some(ref mut preview_state) = preview_sim_state {
-                // Find the cell index in preview canonical state by matching entity
-                for i in 0..preview_state.canonical_state.cell_count {
-                    if let Some(entity) = preview_state.index_to_entity[i] {
-                        if entity == dragged_entity {
-                            preview_state.canonical_state.positions[i] = new_position;
-                            preview_state.canonical_state.velocities[i] = Vec3::ZERO;
-                            preview_state.canonical_state.prev_positions[i] = new_position;
-                            break;
-                        }
-                    }
-                }
-            }
+        crate::simulation::SimulationMode::Preview | crate::simulation::SimulationMode::Gpu => {
+            // Preview is handled above; GPU mode not yet implemented
         }
-        crate::simulation::SimulationMode::Gpu => {
-            // GPU mode not yet implemented
+    }
+}
+
+/// Move a CPU mode soft drag's target to the cursor; the physics step pulls the region along
+fn update_soft_drag(
+    drag_state: &mut DragState,
+    new_position: Vec3,
+    main_sim_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+) {
+    let Some(region) = drag_state.soft_region.as_mut() else {
+        return;
+    };
+    region.target = new_position;
+
+    if let Some(mut main_state) = main_sim_state {
+        if let Some(drag) = main_state.canonical_state.soft_drag.as_mut() {
+            drag.target = new_position;
         }
     }
 }
@@ -261,16 +378,62 @@ fn handle_drag_update(
 fn handle_drag_end(
     mouse_button: Res<ButtonInput<MouseButton>>,
     mut drag_state: ResMut<DragState>,
+    main_sim_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    preview_sim_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
 ) {
     // End drag on left mouse button release
     if mouse_button.just_released(MouseButton::Left) {
         drag_state.dragged_entity = None;
+        // The recorded pull stops; physics carries the cells on from where it left them
+        if let Some(mut preview_state) = preview_sim_state {
+            preview_state.end_drag();
+        }
+        if drag_state.soft_active {
+            if let Some(mut main_state) = main_sim_state {
+                main_state.canonical_state.soft_drag = None;
+            }
+        }
+        drag_state.soft_active = false;
+        drag_state.soft_region = None;
+    }
+}
+
+/// Highlight the cells a soft drag moves, fading with their falloff weight
+fn render_soft_drag_region(
+    drag_state: Res<DragState>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_sim_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_sim_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(region) = drag_state.soft_region.as_ref() else {
+        return;
+    };
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_sim_state.as_deref().map(|s| &s.canonical_state),
+        crate::simulation::SimulationMode::Preview => preview_sim_state.as_deref().map(|s| &s.canonical_state),
+        crate::simulation::SimulationMode::Gpu => None,
+    };
+    let Some(state) = state else {
+        return;
+    };
+
+    let index_by_id = state.index_by_id();
+    for cell in &region.cells {
+        let Some(&i) = index_by_id.get(&cell.cell_id) else {
+            continue;
+        };
+        gizmos.sphere(
+            state.positions[i],
+            state.radii[i] * 1.1,
+            Color::srgba(1.0, 0.8, 0.2, 0.15 + 0.85 * cell.weight),
+        );
     }
 }
 
 /// Ray-sphere intersection test
 /// Returns the distance along the ray to the intersection point, or None if no hit
-fn ray_sphere_intersection(
+pub(crate) fn ray_sphere_intersection(
     ray_origin: Vec3,
     ray_direction: Vec3,
     sphere_center: Vec3,
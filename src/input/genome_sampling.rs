@@ -0,0 +1,289 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::cell::{Cell, CellPosition};
+use crate::genome::{CurrentGenome, GenomeData, GenomeLibrary, GenomeProvenance};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::ui::camera::MainCamera;
+use super::{SelectedTool, Tool};
+use super::arbitration::{InputArbitration, PointerOwner};
+
+/// How long the sampled cell is highlighted (seconds)
+const FLASH_DURATION: f32 = 0.8;
+
+/// Plugin for the genome sampling tool
+pub struct GenomeSamplingPlugin;
+
+impl Plugin for GenomeSamplingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GenomeSampling>()
+            .add_systems(Update, (
+                handle_genome_sample_clicks.before(super::CellDraggingSet),
+                render_sample_flash,
+            ))
+            .add_systems(bevy_egui::EguiPrimaryContextPass, unsaved_changes_prompt.after(crate::ui::ui_system));
+    }
+}
+
+/// A genome copied from a cell, waiting to be applied to the editor
+pub struct PendingSample {
+    pub genome: GenomeData,
+    pub mode_index: usize,
+    /// None when the scene keeps no canonical state to identify the cell by (GPU mode)
+    pub provenance: Option<GenomeProvenance>,
+}
+
+/// State of the genome sampling tool
+#[derive(Resource, Default)]
+pub struct GenomeSampling {
+    /// Sample held back until the user resolves unsaved edits
+    pub pending: Option<PendingSample>,
+    /// Sampled cell and remaining highlight time
+    pub flash: Option<(Entity, f32)>,
+}
+
+/// Copy the genome and mode of the clicked cell into the editor
+fn handle_genome_sample_clicks(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut sampling: ResMut<GenomeSampling>,
+    mut drag_state: ResMut<super::DragState>,
+    mut current_genome: ResMut<CurrentGenome>,
+    genome_library: Res<GenomeLibrary>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    cell_query: Query<(Entity, &CellPosition, &Cell)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut picking: ResMut<super::Picking>,
+) {
+    if selected_tool.tool != Tool::SampleGenome {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        selected_tool.tool = Tool::Select;
+        return;
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || !arbitration.claim(PointerOwner::Tool) {
+        return;
+    }
+
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+        return;
+    };
+
+    // The click belongs to the sampling tool, not to cell dragging
+    drag_state.skip_next_drag = true;
+
+    // Pick against the rendered cell entities so this works in every scene mode
+    let candidates = super::picking::entity_candidates(cell_query.iter(), &current_genome.genome);
+    let Some(entity) = picking.pick(ray, cursor_pos, super::picking::cycle_held(&keyboard), candidates).map(|hit| hit.target) else {
+        return;
+    };
+    let Ok((_, _, cell)) = cell_query.get(entity) else {
+        return;
+    };
+
+    // Cell ID and simulated time from the active simulation's canonical state
+    let provenance = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.as_deref().and_then(|main_state| {
+            let index = *main_state.entity_to_index.get(&entity)?;
+            provenance_at(&main_state.canonical_state, index, main_state.simulation_time)
+        }),
+        crate::simulation::SimulationMode::Preview => preview_state.as_deref().and_then(|preview_state| {
+            let index = preview_state.index_to_entity.iter().position(|e| *e == Some(entity))?;
+            provenance_at(&preview_state.canonical_state, index, preview_state.current_time)
+        }),
+        crate::simulation::SimulationMode::Gpu => None,
+    };
+
+    // Deep copy so later edits never touch the sampled organism's genome
+    let genome = genome_library.genomes.get(cell.genome_id)
+        .unwrap_or(&current_genome.genome)
+        .clone();
+    let sample = PendingSample {
+        genome,
+        mode_index: cell.mode_index,
+        provenance,
+    };
+
+    sampling.flash = Some((entity, FLASH_DURATION));
+    selected_tool.tool = Tool::Select;
+
+    if current_genome.has_unsaved_changes() {
+        sampling.pending = Some(sample);
+    } else {
+        apply_sample(&mut current_genome, sample);
+    }
+}
+
+/// Provenance of the live cell in slot `index` at simulated time `time`
+fn provenance_at(state: &CanonicalState, index: usize, time: f32) -> Option<GenomeProvenance> {
+    (index < state.cell_count).then(|| GenomeProvenance { cell_id: state.cell_ids[index], time })
+}
+
+fn apply_sample(current_genome: &mut CurrentGenome, sample: PendingSample) {
+    match sample.provenance {
+        Some(provenance) => info!("Sampled genome '{}' from cell #{}", sample.genome.name, provenance.cell_id),
+        None => info!("Sampled genome '{}'", sample.genome.name),
+    }
+    current_genome.replace(sample.genome, sample.mode_index as i32, sample.provenance);
+}
+
+/// Briefly highlight the sampled cell
+fn render_sample_flash(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    mut sampling: ResMut<GenomeSampling>,
+    cell_query: Query<(&CellPosition, &Cell)>,
+) {
+    let Some((entity, remaining)) = sampling.flash else {
+        return;
+    };
+    let remaining = remaining - time.delta_secs();
+    if remaining <= 0.0 {
+        sampling.flash = None;
+        return;
+    }
+    sampling.flash = Some((entity, remaining));
+
+    if let Ok((position, cell)) = cell_query.get(entity) {
+        let t = remaining / FLASH_DURATION;
+        let color = Color::srgba(1.0, 1.0, 1.0, t);
+        gizmos.sphere(position.position, cell.radius * (1.1 + 0.4 * (1.0 - t)), color);
+    }
+}
+
+enum PromptChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// Ask whether to save unsaved edits before replacing them with a sampled genome
+fn unsaved_changes_prompt(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut sampling: ResMut<GenomeSampling>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if sampling.pending.is_none() {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+    let ctx = egui_context.get_mut();
+
+    let mut choice = None;
+    egui::Window::new("Unsaved Genome Changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "'{}' has unsaved changes. Save them before opening the sampled genome?",
+                current_genome.genome.name
+            ));
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if ui.button("Save...").clicked() {
+                    choice = Some(PromptChoice::Save);
+                }
+                if ui.button("Discard Changes").clicked() {
+                    choice = Some(PromptChoice::Discard);
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(PromptChoice::Cancel);
+                }
+            });
+        });
+
+    match choice {
+        Some(PromptChoice::Save) => {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("JSON", &["json"])
+                .set_file_name(format!("{}.json", current_genome.genome.name))
+                .save_file()
+            else {
+                return;
+            };
+            match current_genome.genome.save_to_file(&path) {
+                Ok(()) => {
+                    current_genome.record_save(&path);
+                    notifications.success(format!("Saved genome to {}", path.display()));
+                    if let Some(sample) = sampling.pending.take() {
+                        apply_sample(&mut current_genome, sample);
+                    }
+                }
+                // Keep the prompt open so the edits are not lost
+                Err(e) => notifications.error(&e),
+            }
+        }
+        Some(PromptChoice::Discard) => {
+            if let Some(sample) = sampling.pending.take() {
+                apply_sample(&mut current_genome, sample);
+            }
+        }
+        Some(PromptChoice::Cancel) => sampling.pending = None,
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_cells(count: usize) -> CanonicalState {
+        let mut state = CanonicalState::new(8);
+        for i in 0..count {
+            state.add_cell(Vec3::X * i as f32, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 5.0, 2.0, 10.0, Quat::IDENTITY, 0).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_provenance_uses_canonical_cell_id_and_time() {
+        let mut state = state_with_cells(3);
+        // IDs stop matching slots once cells divide or die
+        state.cell_ids[1] = 42;
+
+        assert_eq!(provenance_at(&state, 1, 12.5), Some(GenomeProvenance { cell_id: 42, time: 12.5 }));
+        assert_eq!(provenance_at(&state, 0, 3.0), Some(GenomeProvenance { cell_id: state.cell_ids[0], time: 3.0 }));
+        // Stale slots past the live cells are not cells
+        assert_eq!(provenance_at(&state, 3, 12.5), None);
+    }
+
+    #[test]
+    fn test_applied_sample_records_provenance_and_mode() {
+        let mut genome = GenomeData::default();
+        genome.name = "Competitor".to_string();
+        let provenance = GenomeProvenance { cell_id: 7, time: 30.0 };
+        let mut current_genome = CurrentGenome::default();
+
+        apply_sample(&mut current_genome, PendingSample { genome: genome.clone(), mode_index: 2, provenance: Some(provenance) });
+        assert_eq!(current_genome.genome.name, "Competitor");
+        assert_eq!(current_genome.selected_mode_index, 2);
+        assert_eq!(current_genome.sampled_from, Some(provenance));
+        assert!(!current_genome.has_unsaved_changes());
+
+        // Without a canonical cell there is no provenance to show
+        apply_sample(&mut current_genome, PendingSample { genome, mode_index: 0, provenance: None });
+        assert_eq!(current_genome.sampled_from, None);
+    }
+}
@@ -0,0 +1,565 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::ui::camera::MainCamera;
+use super::arbitration::{InputArbitration, PointerOwner};
+
+/// Plugin for viewport measurement tools
+pub struct MeasurementPlugin;
+
+/// System set for measurement placement and updates
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MeasurementSet;
+
+impl Plugin for MeasurementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Measurements>()
+            .add_systems(Update, (
+                handle_measurement_clicks,
+                update_measurements,
+            ).chain().in_set(MeasurementSet).before(super::CellDraggingSet));
+    }
+}
+
+/// What a measurement reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementKind {
+    /// Distance between two anchors
+    Distance,
+    /// Angle at the middle of three anchors (degrees)
+    Angle,
+    /// Bounding-sphere radius of the organism containing the anchored cell, or of a cell group
+    StructureRadius,
+}
+
+impl MeasurementKind {
+    /// Number of clicks needed to place the measurement
+    pub fn anchor_count(self) -> usize {
+        match self {
+            MeasurementKind::Distance => 2,
+            MeasurementKind::Angle => 3,
+            MeasurementKind::StructureRadius => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MeasurementKind::Distance => "Distance",
+            MeasurementKind::Angle => "Angle",
+            MeasurementKind::StructureRadius => "Structure radius",
+        }
+    }
+
+    /// Format a value of this kind for display
+    pub fn format_value(self, value: f32) -> String {
+        match self {
+            MeasurementKind::Angle => format!("{:.1}°", value),
+            MeasurementKind::Distance | MeasurementKind::StructureRadius => format!("{:.3}", value),
+        }
+    }
+}
+
+/// One end of a measurement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeasurementAnchor {
+    /// A cell, tracked by cell_id so the anchor survives index compaction
+    Cell {
+        cell_id: u32,
+        /// Slot the cell was last seen in (lookup hint)
+        slot: usize,
+        /// Simulation time the cell was last seen
+        last_seen: f32,
+    },
+    /// A fixed point (on the world sphere)
+    Point(Vec3),
+    /// A cell group, measured as a whole (structure radius of a selection set)
+    Group(u32),
+}
+
+/// A placed measurement, updated live from the simulation
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub id: u32,
+    pub name: String,
+    pub kind: MeasurementKind,
+    pub anchors: Vec<MeasurementAnchor>,
+    pub visible: bool,
+    /// Record the value over time for CSV export
+    pub log_over_time: bool,
+    /// Latest value, None while an anchored cell cannot be found
+    pub value: Option<f32>,
+    /// Resolved anchor positions (empty while unresolved)
+    pub points: Vec<Vec3>,
+    /// Bounding sphere (center, radius) for structure radius measurements
+    pub sphere: Option<(Vec3, f32)>,
+    /// Logged (simulation time, value) samples
+    pub samples: Vec<(f32, f32)>,
+}
+
+impl Measurement {
+    /// World position for the screen-space label
+    pub fn label_position(&self) -> Option<Vec3> {
+        match self.kind {
+            MeasurementKind::Distance => Some((*self.points.first()? + *self.points.get(1)?) * 0.5),
+            MeasurementKind::Angle => self.points.get(1).copied(),
+            MeasurementKind::StructureRadius => self.sphere.map(|(center, radius)| center + Vec3::Y * radius),
+        }
+    }
+}
+
+/// All measurements plus the state of the measurement tool
+#[derive(Resource)]
+pub struct Measurements {
+    pub items: Vec<Measurement>,
+    /// Measurement tool mode; None when the tool is off
+    pub active_tool: Option<MeasurementKind>,
+    /// Anchors clicked so far for the measurement being placed
+    pub pending: Vec<MeasurementAnchor>,
+    /// Pending anchor positions (for drawing)
+    pub pending_points: Vec<Vec3>,
+    /// Simulated seconds between logged samples
+    pub sample_interval: f32,
+    next_id: u32,
+}
+
+impl Default for Measurements {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            active_tool: None,
+            pending: Vec::new(),
+            pending_points: Vec::new(),
+            sample_interval: 1.0,
+            next_id: 1,
+        }
+    }
+}
+
+impl Measurements {
+    /// Switch the tool mode, discarding a partially placed measurement
+    pub fn set_tool(&mut self, tool: Option<MeasurementKind>) {
+        self.active_tool = tool;
+        self.pending.clear();
+        self.pending_points.clear();
+    }
+
+    /// Add a clicked anchor; completes a measurement once enough anchors are placed
+    fn push_anchor(&mut self, kind: MeasurementKind, anchor: MeasurementAnchor, position: Vec3) {
+        self.pending.push(anchor);
+        self.pending_points.push(position);
+        if self.pending.len() < kind.anchor_count() {
+            return;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(Measurement {
+            id,
+            name: format!("{} {}", kind.name(), id),
+            kind,
+            anchors: std::mem::take(&mut self.pending),
+            visible: true,
+            log_over_time: false,
+            value: None,
+            points: std::mem::take(&mut self.pending_points),
+            sphere: None,
+            samples: Vec::new(),
+        });
+    }
+
+    /// Add a structure radius measurement over the members of a cell group
+    pub fn add_group_radius(&mut self, group: &crate::simulation::cell_groups::CellGroup) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(Measurement {
+            id,
+            name: format!("{} radius", group.name),
+            kind: MeasurementKind::StructureRadius,
+            anchors: vec![MeasurementAnchor::Group(group.id)],
+            visible: true,
+            log_over_time: false,
+            value: None,
+            points: Vec::new(),
+            sphere: None,
+            samples: Vec::new(),
+        });
+    }
+
+    /// Logged samples of all measurements as CSV (one row per sample)
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("measurement,kind,time,value\n");
+        for measurement in &self.items {
+            let name = measurement.name.replace('"', "\"\"");
+            for (time, value) in &measurement.samples {
+                csv.push_str(&format!("\"{}\",{},{:.4},{:.6}\n", name, measurement.kind.name(), time, value));
+            }
+        }
+        csv
+    }
+}
+
+/// Find the current slot of an anchored cell, updating the anchor
+///
+/// Cells are looked up by cell_id. When the id is gone because the cell divided,
+/// the anchor follows child A, which inherits the parent's slot.
+fn resolve_cell(anchor: &mut MeasurementAnchor, state: &CanonicalState, current_time: f32) -> Option<Vec3> {
+    let (cell_id, slot, last_seen) = match anchor {
+        MeasurementAnchor::Cell { cell_id, slot, last_seen } => (cell_id, slot, last_seen),
+        MeasurementAnchor::Point(point) => return Some(*point),
+        MeasurementAnchor::Group(_) => return None,
+    };
+    let ids = &state.cell_ids[..state.cell_count];
+
+    let found = if ids.get(*slot) == Some(cell_id) {
+        Some(*slot)
+    } else if let Some(index) = ids.iter().position(|id| id == cell_id) {
+        Some(index)
+    } else if *slot < state.cell_count
+        && state.cell_ids[*slot] > *cell_id
+        && state.birth_times[*slot] >= *last_seen
+    {
+        // Born in our old slot after we last saw the cell: the division's child A
+        *cell_id = state.cell_ids[*slot];
+        Some(*slot)
+    } else {
+        None
+    };
+
+    let index = found?;
+    *slot = index;
+    *last_seen = current_time;
+    Some(state.positions[index])
+}
+
+/// Distance between two points
+fn distance(a: Vec3, b: Vec3) -> f32 {
+    a.distance(b)
+}
+
+/// Angle at `vertex` between the legs to `a` and `c` in degrees (None for a zero-length leg)
+fn angle_at(a: Vec3, vertex: Vec3, c: Vec3) -> Option<f32> {
+    let a = a - vertex;
+    let c = c - vertex;
+    (a.length_squared() > 0.0 && c.length_squared() > 0.0).then(|| a.angle_between(c).to_degrees())
+}
+
+/// Bounding sphere (center, radius) of the given cells, including their radii
+fn bounds(state: &CanonicalState, members: &[usize]) -> Option<(Vec3, f32)> {
+    if members.is_empty() {
+        return None;
+    }
+    let center = members.iter().map(|&i| state.positions[i]).sum::<Vec3>() / members.len() as f32;
+    let radius = members.iter()
+        .map(|&i| state.positions[i].distance(center) + state.radii[i])
+        .fold(0.0, f32::max);
+    Some((center, radius))
+}
+
+/// Bounding sphere of the organism containing the given cell
+fn organism_bounds(state: &CanonicalState, index: usize) -> Option<(Vec3, f32)> {
+    bounds(state, &state.adhesion_manager.organism_members(&state.adhesion_connections, index))
+}
+
+/// Bounding sphere of a cell group's current members (None if the group is gone or empty)
+fn group_bounds(state: &CanonicalState, groups: &crate::simulation::CellGroups, group: u32) -> Option<(Vec3, f32)> {
+    let members = &groups.get(group)?.members;
+    let indices: Vec<usize> = (0..state.cell_count)
+        .filter(|&i| members.contains(&state.cell_ids[i]))
+        .collect();
+    bounds(state, &indices)
+}
+
+/// Recompute one measurement from the canonical state
+fn update_measurement(
+    measurement: &mut Measurement,
+    state: &CanonicalState,
+    groups: &crate::simulation::CellGroups,
+    current_time: f32,
+) {
+    measurement.points.clear();
+    measurement.sphere = None;
+    measurement.value = None;
+
+    if let [MeasurementAnchor::Group(group)] = measurement.anchors[..] {
+        measurement.sphere = group_bounds(state, groups, group);
+        measurement.value = measurement.sphere.map(|(_, radius)| radius);
+        return;
+    }
+
+    for anchor in measurement.anchors.iter_mut() {
+        match resolve_cell(anchor, state, current_time) {
+            Some(position) => measurement.points.push(position),
+            None => {
+                measurement.points.clear();
+                return;
+            }
+        }
+    }
+
+    measurement.value = match measurement.kind {
+        MeasurementKind::Distance => Some(distance(measurement.points[0], measurement.points[1])),
+        MeasurementKind::Angle => angle_at(measurement.points[0], measurement.points[1], measurement.points[2]),
+        MeasurementKind::StructureRadius => match measurement.anchors[0] {
+            MeasurementAnchor::Cell { slot, .. } => {
+                measurement.sphere = organism_bounds(state, slot);
+                measurement.sphere.map(|(_, radius)| radius)
+            }
+            MeasurementAnchor::Point(_) | MeasurementAnchor::Group(_) => None,
+        },
+    };
+}
+
+/// Canonical state and time of the active simulation (None in GPU mode)
+fn active_state<'a>(
+    sim_state: &crate::simulation::SimulationState,
+    main_state: Option<&'a crate::simulation::cpu_sim::MainSimState>,
+    preview_state: Option<&'a crate::simulation::preview_sim::PreviewSimState>,
+) -> Option<(&'a CanonicalState, f32)> {
+    match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.map(|s| (&s.canonical_state, s.simulation_time)),
+        crate::simulation::SimulationMode::Preview => preview_state.map(|s| (&s.canonical_state, s.current_time)),
+        crate::simulation::SimulationMode::Gpu => None,
+    }
+}
+
+/// Place measurement anchors on left click while the measurement tool is active
+fn handle_measurement_clicks(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut measurements: ResMut<Measurements>,
+    mut drag_state: ResMut<super::DragState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    physics_config: Res<crate::simulation::PhysicsConfig>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut picking: ResMut<super::Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+) {
+    let Some(kind) = measurements.active_tool else {
+        return;
+    };
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        measurements.set_tool(None);
+        return;
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || !arbitration.claim(PointerOwner::Tool) {
+        return;
+    }
+
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+        return;
+    };
+
+    // The click belongs to the measurement tool, not to cell dragging
+    drag_state.skip_next_drag = true;
+
+    let Some((state, current_time)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref()) else {
+        return;
+    };
+
+    // Nearest opaque enough cell under the cursor (Alt+click cycles through the ones behind it)
+    let candidates = (0..state.cell_count).map(|i| super::picking::PickCandidate {
+        target: i,
+        center: state.positions[i],
+        radius: state.radii[i],
+        mode: state.mode_indices[i],
+        opacity: current_genome.genome.modes.get(state.mode_indices[i]).map_or(1.0, |mode| mode.opacity),
+    });
+    let hit = picking.pick(ray, cursor_pos, super::picking::cycle_held(&keyboard), candidates);
+
+    if let Some(index) = hit.map(|hit| hit.target) {
+        let anchor = MeasurementAnchor::Cell { cell_id: state.cell_ids[index], slot: index, last_seen: current_time };
+        measurements.push_anchor(kind, anchor, state.positions[index]);
+        return;
+    }
+
+    // Structure radius needs a cell; other measurements can use points on the world sphere
+    if kind == MeasurementKind::StructureRadius {
+        return;
+    }
+    if let Some(point) = ray_world_sphere_point(ray.origin, *ray.direction, physics_config.sphere_radius) {
+        measurements.push_anchor(kind, MeasurementAnchor::Point(point), point);
+    }
+}
+
+/// First point where the ray meets the world sphere (centered at the origin)
+fn ray_world_sphere_point(origin: Vec3, direction: Vec3, radius: f32) -> Option<Vec3> {
+    let b = origin.dot(direction);
+    let c = origin.length_squared() - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt = discriminant.sqrt();
+    // Near intersection from outside the sphere, far one from inside
+    let t = if -b - sqrt > 0.0 { -b - sqrt } else { -b + sqrt };
+    (t > 0.0).then(|| origin + direction * t)
+}
+
+/// Track anchored cells, recompute values and log samples
+fn update_measurements(
+    mut measurements: ResMut<Measurements>,
+    groups: Res<crate::simulation::CellGroups>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if measurements.items.is_empty() {
+        return;
+    }
+    let Some((state, current_time)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref()) else {
+        return;
+    };
+
+    let sample_interval = measurements.sample_interval.max(0.01);
+    for measurement in measurements.items.iter_mut() {
+        update_measurement(measurement, state, &groups, current_time);
+
+        if !measurement.log_over_time {
+            continue;
+        }
+        // Scrubbing back in Preview mode discards samples from the abandoned future
+        measurement.samples.retain(|(time, _)| *time <= current_time);
+        let due = measurement.samples.last().is_none_or(|(time, _)| current_time - time >= sample_interval);
+        if let (true, Some(value)) = (due, measurement.value) {
+            measurement.samples.push((current_time, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_follows_cells_through_compaction_and_division() {
+        let mut state = CanonicalState::new(8);
+        let mut add = |position: Vec3| state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 5.0, 2.0, 10.0, Quat::IDENTITY, 0).unwrap();
+        let a = add(Vec3::ZERO);
+        let b = add(Vec3::new(3.0, 0.0, 0.0));
+        let c = add(Vec3::new(0.0, 4.0, 0.0));
+
+        let mut measurements = Measurements::default();
+        let anchor = |state: &CanonicalState, slot: usize| MeasurementAnchor::Cell { cell_id: state.cell_ids[slot], slot, last_seen: 0.0 };
+        measurements.push_anchor(MeasurementKind::Distance, anchor(&state, a), Vec3::ZERO);
+        measurements.push_anchor(MeasurementKind::Distance, anchor(&state, c), Vec3::ZERO);
+        let measurement = &mut measurements.items[0];
+        let groups = crate::simulation::CellGroups::default();
+
+        update_measurement(measurement, &state, &groups, 0.0);
+        assert_eq!(measurement.value, Some(4.0));
+
+        // Removing b moves c into b's slot; the anchor still finds it by id
+        crate::simulation::nutrient_system::remove_dead_cell(&mut state, b);
+        update_measurement(measurement, &state, &groups, 1.0);
+        assert_eq!(measurement.value, Some(4.0));
+
+        // A division replaces a's id in its slot with child A's id
+        state.cell_ids[a] = state.next_cell_id;
+        state.next_cell_id += 1;
+        state.birth_times[a] = 2.0;
+        state.positions[a] = Vec3::new(0.0, 1.0, 0.0);
+        update_measurement(measurement, &state, &groups, 2.0);
+        assert_eq!(measurement.value, Some(3.0));
+    }
+
+    #[test]
+    fn test_distance_and_angle_math() {
+        assert_eq!(distance(Vec3::ZERO, Vec3::new(3.0, 4.0, 0.0)), 5.0);
+        assert_eq!(distance(Vec3::ONE, Vec3::ONE), 0.0);
+
+        let right = angle_at(Vec3::X, Vec3::ZERO, Vec3::Y).unwrap();
+        assert!((right - 90.0).abs() < 1e-4, "right angle measured as {}", right);
+        let straight = angle_at(Vec3::new(-2.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 1.0), Vec3::new(5.0, 1.0, 1.0)).unwrap();
+        assert!((straight - 180.0).abs() < 1e-3, "straight angle measured as {}", straight);
+        let equilateral = angle_at(Vec3::ZERO, Vec3::X, Vec3::new(0.5, 3f32.sqrt() / 2.0, 0.0)).unwrap();
+        assert!((equilateral - 60.0).abs() < 1e-3, "equilateral corner measured as {}", equilateral);
+
+        // A leg of zero length has no angle
+        assert_eq!(angle_at(Vec3::ZERO, Vec3::ZERO, Vec3::Y), None);
+    }
+
+    #[test]
+    fn test_angle_and_distance_between_world_points() {
+        let state = CanonicalState::new(1);
+        let groups = crate::simulation::CellGroups::default();
+        let mut measurements = Measurements::default();
+        for point in [Vec3::new(2.0, 0.0, 0.0), Vec3::ZERO, Vec3::new(0.0, 0.0, 2.0)] {
+            measurements.push_anchor(MeasurementKind::Angle, MeasurementAnchor::Point(point), point);
+        }
+        for point in [Vec3::ZERO, Vec3::new(1.0, 2.0, 2.0)] {
+            measurements.push_anchor(MeasurementKind::Distance, MeasurementAnchor::Point(point), point);
+        }
+        assert_eq!(measurements.items.len(), 2);
+
+        for measurement in measurements.items.iter_mut() {
+            update_measurement(measurement, &state, &groups, 0.0);
+        }
+        let angle = measurements.items[0].value.unwrap();
+        assert!((angle - 90.0).abs() < 1e-4);
+        assert_eq!(measurements.items[0].label_position(), Some(Vec3::ZERO));
+        assert_eq!(measurements.items[1].value, Some(3.0));
+        assert_eq!(measurements.items[1].label_position(), Some(Vec3::new(0.5, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_structure_radius_of_a_cell_group() {
+        let mut state = CanonicalState::new(4);
+        let mut add = |position: Vec3| state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+            0, 0, 0.0, 5.0, 2.0, 10.0, Quat::IDENTITY, 0).unwrap();
+        let a = add(Vec3::new(-2.0, 0.0, 0.0));
+        let b = add(Vec3::new(2.0, 0.0, 0.0));
+        add(Vec3::new(0.0, 50.0, 0.0));
+
+        let mut groups = crate::simulation::CellGroups::default();
+        let ids = [state.cell_ids[a], state.cell_ids[b]];
+        let id = groups.add("Pair".to_string(), crate::simulation::cell_groups::GroupKind::Explicit(ids.into_iter().collect()));
+        groups.get_mut(id).unwrap().members = ids.to_vec();
+
+        let mut measurements = Measurements::default();
+        measurements.add_group_radius(groups.get(id).unwrap());
+        let measurement = &mut measurements.items[0];
+        assert_eq!(measurement.name, "Pair radius");
+
+        // Cells two units either side of the center, plus their own radius
+        update_measurement(measurement, &state, &groups, 0.0);
+        assert_eq!(measurement.sphere, Some((Vec3::ZERO, 2.5)));
+        assert_eq!(measurement.value, Some(2.5));
+
+        // An emptied group has nothing to measure
+        groups.get_mut(id).unwrap().members.clear();
+        update_measurement(measurement, &state, &groups, 1.0);
+        assert_eq!(measurement.value, None);
+    }
+
+    #[test]
+    fn test_samples_export_as_csv() {
+        let mut measurements = Measurements::default();
+        measurements.push_anchor(MeasurementKind::Angle, MeasurementAnchor::Point(Vec3::X), Vec3::X);
+        measurements.push_anchor(MeasurementKind::Angle, MeasurementAnchor::Point(Vec3::ZERO), Vec3::ZERO);
+        measurements.push_anchor(MeasurementKind::Angle, MeasurementAnchor::Point(Vec3::Y), Vec3::Y);
+        let measurement = &mut measurements.items[0];
+        measurement.name = "Arm \"left\"".to_string();
+        measurement.samples.push((1.5, 90.0));
+
+        assert_eq!(
+            measurements.to_csv(),
+            "measurement,kind,time,value\n\"Arm \"\"left\"\"\",Angle,1.5000,90.000000\n"
+        );
+    }
+}
@@ -1,15 +1,44 @@
 use bevy::prelude::*;
 
+pub mod anchor_tool;
+pub mod arbitration;
+pub mod bindings;
 pub mod cell_dragging;
+pub mod measurement;
+pub mod picking;
+pub mod genome_sampling;
+pub mod pin_tool;
+pub mod stamp_tool;
+pub mod surgery_tool;
 
+pub use anchor_tool::{AnchorEditState, AnchorToolPlugin};
+pub use arbitration::{InputArbitration, PointerOwner};
+pub use bindings::InputBindings;
 pub use cell_dragging::{CellDraggingPlugin, DragState, CellDraggingSet};
+pub use measurement::{MeasurementPlugin, Measurements};
+pub use picking::Picking;
+pub use genome_sampling::GenomeSamplingPlugin;
+pub use pin_tool::PinToolPlugin;
+pub use stamp_tool::{StampToolPlugin, StampToolState};
+pub use surgery_tool::{SurgeryToolPlugin, SurgeryToolState};
 
 /// Plugin for input handling
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(CellDraggingPlugin);
+        app.add_plugins(CellDraggingPlugin)
+            .add_plugins(MeasurementPlugin)
+            .add_plugins(GenomeSamplingPlugin)
+            .add_plugins(PinToolPlugin)
+            .add_plugins(SurgeryToolPlugin)
+            .add_plugins(StampToolPlugin)
+            .add_plugins(AnchorToolPlugin)
+            .init_resource::<SelectedTool>()
+            .init_resource::<InputBindings>()
+            .init_resource::<InputArbitration>()
+            .init_resource::<Picking>()
+            .add_systems(PreUpdate, arbitration::update_input_arbitration.after(bevy::input::InputSystems));
     }
 }
 
@@ -29,6 +58,18 @@ pub enum Tool {
     Remove,
     SampleGenome,
     EditCell,
+    Pin,
+    Cut,
+    Graft,
+    Stamp,
+    Anchor,
+}
+
+impl Tool {
+    /// Whether the tool takes the scroll wheel, which otherwise zooms the camera
+    pub fn uses_scroll(self) -> bool {
+        matches!(self, Tool::Stamp)
+    }
 }
 
 /// Currently selected cell
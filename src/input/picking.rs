@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use crate::cell::{Cell, CellPosition};
+use crate::genome::GenomeData;
+use super::bindings::Modifiers;
+
+/// Repeated Alt+clicks within this many pixels of the last pick cycle through its stack
+pub const CYCLE_PIXELS: f32 = 4.0;
+
+/// Default opacity below which clicks pass through a cell
+const DEFAULT_CLICK_THROUGH_OPACITY: f32 = 0.25;
+
+/// A cell the pick ray may hit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickCandidate<T> {
+    pub target: T,
+    pub center: Vec3,
+    pub radius: f32,
+    pub mode: usize,
+    pub opacity: f32,
+}
+
+/// A cell the pick ray enters, `distance` along the ray
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickHit<T> {
+    pub target: T,
+    pub mode: usize,
+    pub distance: f32,
+}
+
+/// Every cell the ray enters, nearest first, skipping cells fainter than `min_opacity`
+pub fn pick_stack<T>(
+    origin: Vec3,
+    direction: Vec3,
+    candidates: impl IntoIterator<Item = PickCandidate<T>>,
+    min_opacity: f32,
+) -> Vec<PickHit<T>> {
+    let mut hits: Vec<PickHit<T>> = candidates.into_iter()
+        .filter(|candidate| candidate.opacity >= min_opacity)
+        .filter_map(|candidate| {
+            let distance = super::cell_dragging::ray_sphere_intersection(origin, direction, candidate.center, candidate.radius)?;
+            Some(PickHit { target: candidate.target, mode: candidate.mode, distance })
+        })
+        .collect();
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    hits
+}
+
+/// Candidates for the rendered cell entities, with their modes' opacity from `genome`
+pub fn entity_candidates<'a>(
+    cells: impl IntoIterator<Item = (Entity, &'a CellPosition, &'a Cell)> + 'a,
+    genome: &'a GenomeData,
+) -> impl Iterator<Item = PickCandidate<Entity>> + 'a {
+    cells.into_iter().map(move |(entity, cell_pos, cell)| PickCandidate {
+        target: entity,
+        center: cell_pos.position,
+        radius: cell.radius,
+        mode: cell.mode_index,
+        opacity: genome.modes.get(cell.mode_index).map_or(1.0, |mode| mode.opacity),
+    })
+}
+
+/// Whether the held modifiers ask for cycling through the pick stack
+pub fn cycle_held(keyboard: &ButtonInput<KeyCode>) -> bool {
+    Modifiers::from_keyboard(keyboard).alt
+}
+
+/// The stack found by the last pick, kept for Alt+click cycling and its overlay
+#[derive(Clone, Debug, PartialEq)]
+pub struct PickCycle {
+    pub cursor: Vec2,
+    /// Mode and ray distance of each cell in the stack, nearest first
+    pub stack: Vec<(usize, f32)>,
+    /// Position in the stack of the cell picked
+    pub depth: usize,
+    /// The pick was an Alt+click, so the overlay lists the stack
+    pub cycling: bool,
+}
+
+/// Stack position to pick: the next one deeper than the last pick when cycling at the same
+/// spot over the same stack (wrapping around), else the nearest
+fn cycle_depth(previous: Option<&PickCycle>, cursor: Vec2, stack_len: usize, cycling: bool) -> usize {
+    match previous {
+        Some(previous) if cycling
+            && stack_len > 0
+            && previous.stack.len() == stack_len
+            && previous.cursor.distance(cursor) <= CYCLE_PIXELS => (previous.depth + 1) % stack_len,
+        _ => 0,
+    }
+}
+
+/// Cell picking shared by the viewport tools (select, drag, sample, pin, graft, measure)
+#[derive(Resource, Clone, Debug)]
+pub struct Picking {
+    /// Clicks pass through cells whose mode opacity is below this
+    pub click_through_opacity: f32,
+    pub last: Option<PickCycle>,
+}
+
+impl Default for Picking {
+    fn default() -> Self {
+        Self {
+            click_through_opacity: DEFAULT_CLICK_THROUGH_OPACITY,
+            last: None,
+        }
+    }
+}
+
+impl Picking {
+    /// Cell under the cursor: the nearest one opaque enough, or with `cycling` the next one
+    /// behind the last pick when clicked again at the same spot
+    pub fn pick<T: Copy>(
+        &mut self,
+        ray: Ray3d,
+        cursor: Vec2,
+        cycling: bool,
+        candidates: impl IntoIterator<Item = PickCandidate<T>>,
+    ) -> Option<PickHit<T>> {
+        let stack = pick_stack(ray.origin, *ray.direction, candidates, self.click_through_opacity);
+        let depth = cycle_depth(self.last.as_ref(), cursor, stack.len(), cycling);
+        self.last = (!stack.is_empty()).then(|| PickCycle {
+            cursor,
+            stack: stack.iter().map(|hit| (hit.mode, hit.distance)).collect(),
+            depth,
+            cycling,
+        });
+        stack.get(depth).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four cells along -Z, listed out of depth order, with a faint ghost among them
+    fn row() -> Vec<PickCandidate<u32>> {
+        let cell = |target, z, opacity| PickCandidate { target, center: Vec3::new(0.0, 0.0, z), radius: 1.0, mode: target as usize, opacity };
+        vec![cell(3, -9.0, 1.0), cell(1, -3.0, 1.0), cell(2, -6.0, 0.1), cell(4, -6.0 + 1.5, 0.9)]
+    }
+
+    fn ray() -> Ray3d {
+        Ray3d::new(Vec3::ZERO, Dir3::NEG_Z)
+    }
+
+    #[test]
+    fn test_stack_is_sorted_nearest_first() {
+        let stack = pick_stack(Vec3::ZERO, Vec3::NEG_Z, row(), 0.0);
+        let targets: Vec<u32> = stack.iter().map(|hit| hit.target).collect();
+        assert_eq!(targets, [1, 4, 2, 3]);
+        assert!(stack.windows(2).all(|pair| pair[0].distance <= pair[1].distance));
+        assert!((stack[0].distance - 2.0).abs() < 1e-4);
+
+        // A ray past every cell hits nothing
+        assert!(pick_stack(Vec3::new(5.0, 0.0, 0.0), Vec3::NEG_Z, row(), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_translucent_cells_are_clicked_through() {
+        let stack = pick_stack(Vec3::ZERO, Vec3::NEG_Z, row(), 0.25);
+        assert_eq!(stack.iter().map(|hit| hit.target).collect::<Vec<_>>(), [1, 4, 3]);
+        let stack = pick_stack(Vec3::ZERO, Vec3::NEG_Z, row(), 0.95);
+        assert_eq!(stack.iter().map(|hit| hit.target).collect::<Vec<_>>(), [1, 3]);
+
+        // With the front cell faded out, a plain click picks the one behind it
+        let mut cells = row();
+        cells[1].opacity = 0.0;
+        let mut picking = Picking::default();
+        assert_eq!(picking.pick(ray(), Vec2::ZERO, false, cells).map(|hit| hit.target), Some(4));
+    }
+
+    #[test]
+    fn test_alt_click_cycles_through_the_stack() {
+        let mut picking = Picking::default();
+        let cursor = Vec2::new(100.0, 80.0);
+        let pick = |picking: &mut Picking, cursor, cycling| picking.pick(ray(), cursor, cycling, row()).map(|hit| hit.target);
+
+        // Plain clicks always pick the nearest
+        assert_eq!(pick(&mut picking, cursor, false), Some(1));
+        assert_eq!(pick(&mut picking, cursor, false), Some(1));
+        // Alt+clicks at the same spot go deeper and wrap around (the ghost is clicked through)
+        assert_eq!(pick(&mut picking, cursor, true), Some(4));
+        assert_eq!(pick(&mut picking, cursor + Vec2::splat(1.0), true), Some(3));
+        assert_eq!(pick(&mut picking, cursor, true), Some(1));
+        assert_eq!(picking.last.as_ref().map(|last| (last.stack.len(), last.depth, last.cycling)), Some((3, 0, true)));
+
+        // Moving away or letting go of Alt starts over from the nearest
+        assert_eq!(pick(&mut picking, cursor, true), Some(4));
+        assert_eq!(pick(&mut picking, cursor + Vec2::new(20.0, 0.0), true), Some(1));
+        assert_eq!(pick(&mut picking, cursor + Vec2::new(20.0, 0.0), true), Some(4));
+        assert_eq!(pick(&mut picking, cursor + Vec2::new(20.0, 0.0), false), Some(1));
+
+        // Missing everything forgets the stack
+        assert_eq!(picking.pick(ray(), cursor, true, Vec::<PickCandidate<u32>>::new()), None);
+        assert!(picking.last.is_none());
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::cell::{Cell, CellPosition};
+use crate::simulation::pinning::PinRequests;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::ui::camera::MainCamera;
+use super::{SelectedTool, Tool};
+use super::arbitration::{InputArbitration, PointerOwner};
+
+/// Plugin for the pin tool and the pin gizmos drawn on pinned cells
+pub struct PinToolPlugin;
+
+impl Plugin for PinToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            handle_pin_clicks.before(super::CellDraggingSet),
+            render_pin_gizmos,
+        ));
+    }
+}
+
+/// Canonical state of the active simulation and the slot of a rendered cell entity in it
+pub(crate) fn active_state<'a>(
+    sim_state: &crate::simulation::SimulationState,
+    main_state: Option<&'a crate::simulation::cpu_sim::MainSimState>,
+    preview_state: Option<&'a crate::simulation::preview_sim::PreviewSimState>,
+    entity: Option<Entity>,
+) -> Option<(&'a CanonicalState, Option<usize>)> {
+    match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.map(|main_state| {
+            let index = entity.and_then(|entity| main_state.entity_to_index.get(&entity).copied());
+            (&main_state.canonical_state, index)
+        }),
+        crate::simulation::SimulationMode::Preview => preview_state.map(|preview_state| {
+            let index = entity.and_then(|entity| {
+                preview_state.index_to_entity.iter().position(|e| *e == Some(entity))
+            });
+            (&preview_state.canonical_state, index)
+        }),
+        crate::simulation::SimulationMode::Gpu => None,
+    }
+}
+
+/// Toggle the pin on the clicked cell
+fn handle_pin_clicks(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut drag_state: ResMut<super::DragState>,
+    mut pin_requests: ResMut<PinRequests>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    cell_query: Query<(Entity, &CellPosition, &Cell)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut picking: ResMut<super::Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+) {
+    if selected_tool.tool != Tool::Pin {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        selected_tool.tool = Tool::Select;
+        return;
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || !arbitration.claim(PointerOwner::Tool) {
+        return;
+    }
+
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+        return;
+    };
+
+    // The click belongs to the pin tool, not to cell dragging
+    drag_state.skip_next_drag = true;
+
+    let candidates = super::picking::entity_candidates(cell_query.iter(), &current_genome.genome);
+    let Some(entity) = picking.pick(ray, cursor_pos, super::picking::cycle_held(&keyboard), candidates).map(|hit| hit.target) else {
+        return;
+    };
+
+    let Some((state, Some(index))) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), Some(entity)) else {
+        return;
+    };
+    if index < state.cell_count {
+        pin_requests.set(state.cell_ids[index], !state.is_pinned(index));
+    }
+}
+
+/// Draw a pin (needle and head) sticking out of every pinned cell
+fn render_pin_gizmos(
+    mut gizmos: Gizmos,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+        return;
+    };
+
+    let needle_color = Color::srgb(0.85, 0.85, 0.9);
+    let head_color = Color::srgb(0.9, 0.2, 0.2);
+    for index in state.pinned_indices() {
+        let position = state.positions[index];
+        let radius = state.radii[index];
+        let tip = position + Vec3::Y * radius * 0.5;
+        let head = position + Vec3::Y * radius * 1.6;
+        gizmos.line(tip, head, needle_color);
+        gizmos.sphere(Isometry3d::from_translation(head), radius * 0.2, head_color);
+    }
+}
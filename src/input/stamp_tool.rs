@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use bevy::input::mouse::AccumulatedMouseScroll;
+use bevy::window::PrimaryWindow;
+use std::sync::Arc;
+use crate::simulation::tissue_stamp::{StampPlacement, StampRequests, TissueFile};
+use crate::ui::camera::MainCamera;
+use super::surgery_tool::cursor_ray;
+use super::{SelectedTool, Tool};
+use super::arbitration::{InputArbitration, PointerOwner};
+
+/// Plugin for the tissue stamp tool and its ghost preview
+pub struct StampToolPlugin;
+
+impl Plugin for StampToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StampToolState>()
+            .add_systems(Update, (
+                handle_stamp_tool.before(super::CellDraggingSet),
+                render_stamp_preview,
+            ));
+    }
+}
+
+/// Turn per scroll step
+const STAMP_ROTATION_STEP: f32 = std::f32::consts::PI / 12.0;
+
+/// Gap kept between a surface-placed stamp and the world sphere
+const SURFACE_MARGIN: f32 = 1.0;
+
+/// Where along the cursor ray a stamp goes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StampDepth {
+    /// Just inside the world sphere where the ray meets it
+    Surface,
+    /// This far from the camera
+    Distance(f32),
+}
+
+/// Loaded tissue and the stamp tool's options
+#[derive(Resource)]
+pub struct StampToolState {
+    /// Tissue the tool places
+    pub tissue: Option<Arc<TissueFile>>,
+    /// Target genome mode for every tissue mode
+    pub mode_map: Vec<usize>,
+    /// Turn applied to the tissue about its centroid
+    pub rotation: Quat,
+    pub depth: StampDepth,
+    /// Set from the Tools menu; handled with a file dialog by the tissue stamp window
+    pub export_requested: bool,
+    pub import_requested: bool,
+    /// Loaded tissue whose modes don't match the current genome, waiting for the user to choose a mapping
+    pub pending_import: Option<TissueFile>,
+}
+
+impl Default for StampToolState {
+    fn default() -> Self {
+        Self {
+            tissue: None,
+            mode_map: Vec::new(),
+            rotation: Quat::IDENTITY,
+            depth: StampDepth::Surface,
+            export_requested: false,
+            import_requested: false,
+            pending_import: None,
+        }
+    }
+}
+
+impl StampToolState {
+    /// Start stamping `tissue` with the given mode map
+    pub fn load(&mut self, tissue: TissueFile, mode_map: Vec<usize>) {
+        self.tissue = Some(Arc::new(tissue));
+        self.mode_map = mode_map;
+        self.rotation = Quat::IDENTITY;
+    }
+
+    /// Placement of the loaded tissue under the cursor ray, if the ray reaches a valid spot
+    fn placement(&self, ray: Ray3d, sphere_radius: f32) -> Option<StampPlacement> {
+        let tissue = self.tissue.as_ref()?;
+        let translation = match self.depth {
+            StampDepth::Surface => {
+                let hit = ray_sphere_exit_or_entry(ray.origin, *ray.direction, sphere_radius)?;
+                // Pull the whole tissue inside the wall
+                let inset = (tissue.extent() + SURFACE_MARGIN).min(sphere_radius);
+                hit - hit.normalize_or_zero() * inset
+            }
+            StampDepth::Distance(distance) => ray.origin + *ray.direction * distance,
+        };
+        Some(StampPlacement { translation, rotation: self.rotation })
+    }
+}
+
+/// First point where a ray meets the origin-centered sphere in front of its origin
+fn ray_sphere_exit_or_entry(origin: Vec3, direction: Vec3, radius: f32) -> Option<Vec3> {
+    let b = origin.dot(direction);
+    let c = origin.length_squared() - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    // Entry point from outside, exit point from inside
+    let t = if -b - root > 0.0 { -b - root } else { -b + root };
+    (t > 0.0).then(|| origin + direction * t)
+}
+
+/// Scroll to turn the tissue, click to stamp it where the ghost is
+fn handle_stamp_tool(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut tool_state: ResMut<StampToolState>,
+    mut drag_state: ResMut<super::DragState>,
+    mut requests: ResMut<StampRequests>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    config: Res<crate::simulation::PhysicsConfig>,
+) {
+    if selected_tool.tool != Tool::Stamp {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        selected_tool.tool = Tool::Select;
+        return;
+    }
+    let Some(tissue) = tool_state.tissue.clone() else {
+        return;
+    };
+
+    if arbitration.scroll_free() && mouse_scroll.delta.y.abs() > 0.001 {
+        let Ok((_, camera_transform)) = camera_query.single() else {
+            return;
+        };
+        // Roll about the view direction; with Shift, spin about the camera's up axis
+        let axis = if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            *camera_transform.up()
+        } else {
+            *camera_transform.forward()
+        };
+        let turn = Quat::from_axis_angle(axis, mouse_scroll.delta.y.signum() * STAMP_ROTATION_STEP);
+        tool_state.rotation = (turn * tool_state.rotation).normalize();
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || !arbitration.claim(PointerOwner::Tool) {
+        return;
+    }
+    let Some((_, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+
+    // The click belongs to the stamp tool, not to cell dragging
+    drag_state.skip_next_drag = true;
+
+    if let Some(placement) = tool_state.placement(ray, config.sphere_radius) {
+        requests.push(tissue, tool_state.mode_map.clone(), placement);
+    }
+}
+
+const STAMP_COLOR: Color = Color::srgb(0.75, 0.6, 1.0);
+
+/// Ghost of the tissue where a click would stamp it
+fn render_stamp_preview(
+    mut gizmos: Gizmos,
+    selected_tool: Res<SelectedTool>,
+    tool_state: Res<StampToolState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    config: Res<crate::simulation::PhysicsConfig>,
+) {
+    if selected_tool.tool != Tool::Stamp {
+        return;
+    }
+    let Some(tissue) = tool_state.tissue.as_ref() else {
+        return;
+    };
+    let Some((_, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+    let Some(placement) = tool_state.placement(ray, config.sphere_radius) else {
+        return;
+    };
+
+    for cell in &tissue.cells {
+        gizmos.sphere(placement.apply_to(cell.position), cell.radius, STAMP_COLOR.with_alpha(0.5));
+    }
+    for bond in &tissue.bonds {
+        let a = placement.apply_to(tissue.cells[bond.cell_a].position);
+        let b = placement.apply_to(tissue.cells[bond.cell_b].position);
+        gizmos.line(a, b, STAMP_COLOR);
+    }
+}
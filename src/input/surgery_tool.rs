@@ -0,0 +1,384 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::cell::{Cell, CellPosition};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::organism_surgery::{SurgeryOp, SurgeryRequests};
+use crate::ui::camera::MainCamera;
+use super::pin_tool::active_state;
+use super::{SelectedTool, Tool};
+use super::arbitration::{InputArbitration, PointerOwner};
+use super::picking::{cycle_held, entity_candidates, pick_stack, Picking};
+
+/// Plugin for the cut and graft tools and their previews
+pub struct SurgeryToolPlugin;
+
+impl Plugin for SurgeryToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurgeryToolState>()
+            .add_systems(Update, (
+                (handle_cut_tool, handle_graft_tool).before(super::CellDraggingSet),
+                (render_cut_preview, render_graft_preview),
+            ));
+    }
+}
+
+/// Strokes shorter than this (in pixels) are clicks, which split at the bond under the cursor
+const CUT_CLICK_PIXELS: f32 = 4.0;
+
+/// Cut stroke in progress and graft options
+#[derive(Resource)]
+pub struct SurgeryToolState {
+    /// Cursor position where the current cut stroke started
+    cut_start: Option<Vec2>,
+    /// First cell picked for a graft
+    graft_first: Option<u32>,
+    /// Move the smaller organism into contact before bonding
+    pub graft_snap: bool,
+    /// Mode whose adhesion settings the graft bond uses (None = the first cell's mode)
+    pub graft_mode: Option<usize>,
+}
+
+impl Default for SurgeryToolState {
+    fn default() -> Self {
+        Self {
+            cut_start: None,
+            graft_first: None,
+            graft_snap: true,
+            graft_mode: None,
+        }
+    }
+}
+
+/// Camera and primary window cursor, if the cursor is over the window
+pub(super) fn cursor_ray(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<(Vec2, Ray3d)> {
+    let window = window_query.single().ok()?;
+    let (camera, camera_transform) = camera_query.single().ok()?;
+    let cursor_pos = window.cursor_position()?;
+    let ray = camera.viewport_to_world(camera_transform, cursor_pos).ok()?;
+    Some((cursor_pos, ray))
+}
+
+/// Nearest opaque enough rendered cell under the ray, without touching the Alt+click stack
+fn hovered_cell(
+    ray: Ray3d,
+    cell_query: &Query<(Entity, &CellPosition, &Cell)>,
+    picking: &Picking,
+    genome: &crate::genome::GenomeData,
+) -> Option<Entity> {
+    pick_stack(ray.origin, *ray.direction, entity_candidates(cell_query.iter(), genome), picking.click_through_opacity)
+        .first()
+        .map(|hit| hit.target)
+}
+
+/// Active bond passing closest to the ray, within half the smaller end's radius
+pub(super) fn pick_bond(ray: Ray3d, state: &CanonicalState) -> Option<(u32, u32)> {
+    let connections = &state.adhesion_connections;
+    let mut closest: Option<(usize, usize, f32)> = None;
+    for slot in 0..connections.active_count {
+        if connections.is_active[slot] == 0 {
+            continue;
+        }
+        let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+        let distance = ray_segment_distance(ray.origin, *ray.direction, state.positions[a], state.positions[b]);
+        let reach = state.radii[a].min(state.radii[b]) * 0.5;
+        if distance < reach && closest.is_none_or(|(_, _, best)| distance < best) {
+            closest = Some((a, b, distance));
+        }
+    }
+    closest.map(|(a, b, _)| (state.cell_ids[a], state.cell_ids[b]))
+}
+
+/// Shortest distance between a ray and a segment
+fn ray_segment_distance(origin: Vec3, direction: Vec3, start: Vec3, end: Vec3) -> f32 {
+    let segment = end - start;
+    let offset = start - origin;
+    let (dd, ds, ss) = (direction.dot(direction), direction.dot(segment), segment.dot(segment));
+    let (d_off, s_off) = (direction.dot(offset), segment.dot(offset));
+    let denominator = dd * ss - ds * ds;
+    let mut s = if denominator.abs() > 1e-8 {
+        ((ds * d_off - dd * s_off) / denominator).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let t = ((s * ds + d_off) / dd).max(0.0);
+    // Re-solve the segment parameter for the clamped ray parameter
+    if ss > 1e-8 {
+        s = ((t * ds - s_off) / ss).clamp(0.0, 1.0);
+    }
+    (origin + direction * t).distance(start + segment * s)
+}
+
+/// The cut a stroke between two cursor positions makes: the wedge between their rays
+fn cut_from_stroke(camera: &Camera, camera_transform: &GlobalTransform, start: Vec2, end: Vec2) -> Option<SurgeryOp> {
+    let ray_a = camera.viewport_to_world(camera_transform, start).ok()?;
+    let ray_b = camera.viewport_to_world(camera_transform, end).ok()?;
+    Some(SurgeryOp::Cut {
+        apex: camera_transform.translation(),
+        edge_a: *ray_a.direction,
+        edge_b: *ray_b.direction,
+    })
+}
+
+/// Drag a stroke across organisms to cut every bond it passes through, or click a bond to split there
+fn handle_cut_tool(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut tool_state: ResMut<SurgeryToolState>,
+    mut drag_state: ResMut<super::DragState>,
+    mut requests: ResMut<SurgeryRequests>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if selected_tool.tool != Tool::Cut {
+        tool_state.cut_start = None;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        tool_state.cut_start = None;
+        selected_tool.tool = Tool::Select;
+        return;
+    }
+
+    let Some((cursor_pos, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+
+    if mouse_button.just_pressed(MouseButton::Left) && arbitration.claim(PointerOwner::Tool) {
+        // The stroke belongs to the cut tool, not to cell dragging
+        drag_state.skip_next_drag = true;
+        tool_state.cut_start = Some(cursor_pos);
+        return;
+    }
+
+    if !mouse_button.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(start) = tool_state.cut_start.take() else {
+        return;
+    };
+
+    let op = if start.distance(cursor_pos) < CUT_CLICK_PIXELS {
+        let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+            return;
+        };
+        pick_bond(ray, state).map(|(cell_a, cell_b)| SurgeryOp::SplitAtBond { cell_a, cell_b })
+    } else {
+        let Ok((camera, camera_transform)) = camera_query.single() else {
+            return;
+        };
+        cut_from_stroke(camera, camera_transform, start, cursor_pos)
+    };
+    if let Some(op) = op {
+        requests.pending.push(op);
+    }
+}
+
+/// Click two cells on different organisms to bond them
+fn handle_graft_tool(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut arbitration: ResMut<InputArbitration>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut tool_state: ResMut<SurgeryToolState>,
+    mut drag_state: ResMut<super::DragState>,
+    mut requests: ResMut<SurgeryRequests>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    cell_query: Query<(Entity, &CellPosition, &Cell)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut picking: ResMut<Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+) {
+    if selected_tool.tool != Tool::Graft {
+        tool_state.graft_first = None;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) && arbitration.keyboard_free() {
+        // First Esc drops the picked cell, the second leaves the tool
+        if tool_state.graft_first.take().is_none() {
+            selected_tool.tool = Tool::Select;
+        }
+        return;
+    }
+
+    if !mouse_button.just_pressed(MouseButton::Left) || !arbitration.claim(PointerOwner::Tool) {
+        return;
+    }
+    let Some((cursor, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+
+    // The click belongs to the graft tool, not to cell dragging
+    drag_state.skip_next_drag = true;
+
+    let candidates = entity_candidates(cell_query.iter(), &current_genome.genome);
+    let Some(entity) = picking.pick(ray, cursor, cycle_held(&keyboard), candidates).map(|hit| hit.target) else {
+        return;
+    };
+    let Some((state, Some(index))) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), Some(entity)) else {
+        return;
+    };
+    if index >= state.cell_count {
+        return;
+    }
+    let cell_id = state.cell_ids[index];
+
+    let Some(first) = tool_state.graft_first else {
+        tool_state.graft_first = Some(cell_id);
+        return;
+    };
+    if first == cell_id {
+        tool_state.graft_first = None;
+        return;
+    }
+
+    let first_mode = state.cell_ids[..state.cell_count].iter()
+        .position(|&id| id == first)
+        .map(|first_index| state.mode_indices[first_index]);
+    let Some(first_mode) = first_mode else {
+        // The first cell died in the meantime; start over from this one
+        tool_state.graft_first = Some(cell_id);
+        return;
+    };
+
+    requests.pending.push(SurgeryOp::Graft {
+        cell_a: first,
+        cell_b: cell_id,
+        mode_index: tool_state.graft_mode.unwrap_or(first_mode),
+        snap: tool_state.graft_snap,
+    });
+    tool_state.graft_first = None;
+}
+
+const CUT_COLOR: Color = Color::srgb(1.0, 0.3, 0.45);
+const GRAFT_COLOR: Color = Color::srgb(0.45, 0.9, 0.8);
+
+/// Draw the stroke being cut and mark the bonds it would cut
+fn render_cut_preview(
+    mut gizmos: Gizmos,
+    selected_tool: Res<SelectedTool>,
+    tool_state: Res<SurgeryToolState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if selected_tool.tool != Tool::Cut {
+        return;
+    }
+    let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+        return;
+    };
+    let Some((cursor_pos, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    let op = match tool_state.cut_start {
+        Some(start) if start.distance(cursor_pos) >= CUT_CLICK_PIXELS => {
+            let Some(op) = cut_from_stroke(camera, camera_transform, start, cursor_pos) else {
+                return;
+            };
+            // The cutting plane contains the view direction, so it faces the camera edge-on:
+            // draw it as the stroke, a little in front of the organisms it cuts
+            if let SurgeryOp::Cut { apex, edge_a, edge_b } = op {
+                let depth = state.positions[..state.cell_count].iter()
+                    .map(|position| (*position - apex).dot(*camera_transform.forward()))
+                    .fold(f32::INFINITY, f32::min)
+                    .max(1.0);
+                let scale = |edge: Vec3| apex + edge * (depth / edge.dot(*camera_transform.forward()).max(1e-3));
+                gizmos.line(scale(edge_a), scale(edge_b), CUT_COLOR);
+            }
+            op
+        }
+        // Hovering: a click would split at the bond under the cursor
+        _ => match pick_bond(ray, state) {
+            Some((cell_a, cell_b)) => SurgeryOp::SplitAtBond { cell_a, cell_b },
+            None => return,
+        },
+    };
+
+    let connections = &state.adhesion_connections;
+    for slot in state.bonds_cut_by(&op) {
+        let a = state.positions[connections.cell_a_index[slot]];
+        let b = state.positions[connections.cell_b_index[slot]];
+        gizmos.line(a, b, CUT_COLOR);
+        gizmos.sphere((a + b) * 0.5, 0.15, CUT_COLOR);
+    }
+}
+
+/// Highlight the first graft cell and preview the bond (and snapped organism) to the hovered cell
+fn render_graft_preview(
+    mut gizmos: Gizmos,
+    selected_tool: Res<SelectedTool>,
+    tool_state: Res<SurgeryToolState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    cell_query: Query<(Entity, &CellPosition, &Cell)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    picking: Res<Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+) {
+    if selected_tool.tool != Tool::Graft {
+        return;
+    }
+    let Some(first) = tool_state.graft_first else {
+        return;
+    };
+    let Some((state, _)) = active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None) else {
+        return;
+    };
+    let Some(first_index) = state.cell_ids[..state.cell_count].iter().position(|&id| id == first) else {
+        return;
+    };
+    gizmos.sphere(state.positions[first_index], state.radii[first_index] * 1.15, GRAFT_COLOR);
+
+    let Some((_, ray)) = cursor_ray(&window_query, &camera_query) else {
+        return;
+    };
+    let hovered = hovered_cell(ray, &cell_query, &picking, &current_genome.genome)
+        .and_then(|entity| active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), Some(entity)))
+        .and_then(|(_, index)| index);
+    let Some(hovered) = hovered.filter(|&index| index < state.cell_count && index != first_index) else {
+        return;
+    };
+
+    // Same organism: nothing to graft
+    let Some(snap) = state.graft_snap(first_index, hovered) else {
+        return;
+    };
+    if !tool_state.graft_snap {
+        gizmos.line(state.positions[first_index], state.positions[hovered], GRAFT_COLOR);
+        return;
+    }
+
+    // Ghosts of the moved organism where the snap would put it, and the bond it would form
+    for &index in &snap.moved {
+        gizmos.sphere(snap.apply_to(state.positions[index]), state.radii[index], GRAFT_COLOR.with_alpha(0.5));
+    }
+    let end_of = |index: usize| {
+        if snap.moved.contains(&index) {
+            snap.apply_to(state.positions[index])
+        } else {
+            state.positions[index]
+        }
+    };
+    gizmos.line(end_of(first_index), end_of(hovered), GRAFT_COLOR);
+}
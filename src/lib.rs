@@ -1,14 +1,19 @@
+pub mod audio;
 pub mod cell;
+pub mod error;
 pub mod genome;
 pub mod input;
 pub mod rendering;
+pub mod safe_mode;
 pub mod simulation;
 pub mod ui;
 
 // Re-export all plugins for convenient access
+pub use audio::AudioFeedbackPlugin;
 pub use cell::CellPlugin;
 pub use genome::GenomePlugin;
 pub use input::InputPlugin;
 pub use rendering::RenderingPlugin;
+pub use safe_mode::SafeModePlugin;
 pub use simulation::SimulationPlugin;
 pub use ui::UiPlugin;
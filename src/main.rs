@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy::window::{WindowMode, WindowResolution};
+use bevy::window::{ExitCondition, PrimaryWindow, WindowMode, WindowResolution};
 use bevy::render::{RenderPlugin, settings::{Backends, WgpuSettings}};
 use bevy_embedded_assets::{EmbeddedAssetPlugin, PluginMode};
 use bevy_egui::EguiPlugin;
@@ -7,9 +7,14 @@ use biospheres_bevy::*;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::panic;
+use std::path::Path;
 use std::time::SystemTime;
 use std::env;
 
+/// Counts heap allocations for the performance monitor's "Count allocations" readout
+#[global_allocator]
+static GLOBAL_ALLOCATOR: biospheres_bevy::simulation::memory::CountingAllocator = biospheres_bevy::simulation::memory::CountingAllocator;
+
 #[cfg(windows)]
 fn allocate_console() {
     use winapi::um::consoleapi::AllocConsole;
@@ -25,7 +30,7 @@ fn allocate_console() {
 }
 
 /// Startup system to apply window maximized state (always maximized)
-fn apply_window_state(mut windows: Query<&mut Window>) {
+fn apply_window_state(mut windows: Query<&mut Window, With<PrimaryWindow>>) {
     for mut window in windows.iter_mut() {
         println!("Setting window to maximized");
         window.set_maximized(true);
@@ -37,6 +42,14 @@ fn main() {
     #[cfg(windows)]
     allocate_console();
     
+    // Headless regression check for CI: no window, exit code reports the result
+    if let Some(exit_code) = biospheres_bevy::simulation::fingerprint::run_cli(env::args().skip(1)) {
+        std::process::exit(exit_code);
+    }
+    if let Some(exit_code) = biospheres_bevy::simulation::soak::run_cli(env::args().skip(1)) {
+        std::process::exit(exit_code);
+    }
+    
     // Enable verbose wgpu logging to console only
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("wgpu=debug,wgpu_core=debug,wgpu_hal=debug,bevy_render=debug"))
         .init();
@@ -46,8 +59,20 @@ fn main() {
         env::set_var("WGPU_VALIDATION", "1");
     }
     
+    // Count startups that never reach their first frame; enough of them in a row bypass
+    // the saved settings (safe mode)
+    let safe_mode = biospheres_bevy::safe_mode::SafeMode::launch(".");
+    
     // Set up panic hook to create crash log only when there's actually a crash
     panic::set_hook(Box::new(move |panic_info| {
+        // Soak scenes catch their panics and report them as failures
+        if biospheres_bevy::simulation::soak::in_soak_scene() {
+            eprintln!("Soak scene panicked: {}", panic_info);
+            return;
+        }
+        let phase = biospheres_bevy::safe_mode::current_phase();
+        biospheres_bevy::safe_mode::record_crash(Path::new("."));
+        
         let timestamp = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
@@ -58,12 +83,18 @@ fn main() {
         log_content.push_str("=== BIOSPHERES CRASH LOG ===\n");
         log_content.push_str(&format!("Timestamp: {}\n", timestamp));
         log_content.push_str(&format!("Panic: {}\n", panic_info));
+        log_content.push_str(&format!("Startup phase: {}\n", phase.name()));
         
         if let Some(location) = panic_info.location() {
             log_content.push_str(&format!("Location: {}:{}:{}\n", 
                 location.file(), location.line(), location.column()));
         }
         
+        // Feature support of the graphics adapter, once detected
+        if let Some(report) = biospheres_bevy::rendering::capabilities::crash_report() {
+            log_content.push_str(&format!("\nRender capabilities:\n{}", report));
+        }
+        
         log_content.push_str(&format!("\nBacktrace:\n{:?}\n", std::backtrace::Backtrace::force_capture()));
         
         // Write crash log file
@@ -86,8 +117,12 @@ fn main() {
         let _ = std::io::stdin().read_line(&mut input);
     }));
 
-    App::new()
-        .add_plugins(
+    // Reproduce the degraded rendering path of low-end adapters
+    let force_low_spec = env::args().any(|arg| arg == biospheres_bevy::rendering::capabilities::FORCE_LOW_SPEC_FLAG);
+
+    biospheres_bevy::safe_mode::set_phase(biospheres_bevy::safe_mode::StartupPhase::Plugins);
+    let mut app = App::new();
+    app.add_plugins(
             DefaultPlugins
                 .build()
                 // Embed assets in the binary for release builds
@@ -110,9 +145,14 @@ fn main() {
                         mode: WindowMode::Windowed,
                         ..default()
                     }),
+                    // A detached panel window must not keep the app alive on its own
+                    exit_condition: ExitCondition::OnPrimaryClosed,
                     ..default()
                 })
         )
+        .insert_resource(biospheres_bevy::rendering::ForceLowSpec(force_low_spec))
+        .insert_resource(safe_mode)
+        .add_plugins(SafeModePlugin)
         // Apply saved window state after startup (PostStartup ensures window is ready)
         .add_systems(PostStartup, apply_window_state)
         // Egui plugin (must be added before UiPlugin)
@@ -125,5 +165,14 @@ fn main() {
         .add_plugins(RenderingPlugin)
         .add_plugins(UiPlugin)  // Uses egui for UI
         .add_plugins(InputPlugin)
-        .run();
+        .add_plugins(AudioFeedbackPlugin);  // Silent without an output device
+
+    // Watch another machine's main simulation instead of running one
+    if let Some(address) = biospheres_bevy::simulation::spectator::spectate_address(env::args().skip(1)) {
+        app.insert_resource(biospheres_bevy::simulation::SpectatorClient::connect(address));
+    }
+    
+    // The window and the GPU device are created as the app starts running
+    biospheres_bevy::safe_mode::set_phase(biospheres_bevy::safe_mode::StartupPhase::Renderer);
+    app.run();
 }
@@ -1,12 +1,15 @@
 use bevy::prelude::*;
+use bevy::color::Mix;
 use crate::cell::get_zone_color;
+use crate::simulation::CanonicalState;
+use super::interpolation::{update_interpolation_alpha, TickInterpolation};
 
 /// Plugin for rendering adhesion connection lines
 pub struct AdhesionLineRenderPlugin;
 
 impl Plugin for AdhesionLineRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, render_adhesion_lines_gizmos);
+        app.add_systems(Update, render_adhesion_lines_gizmos.after(update_interpolation_alpha));
     }
 }
 
@@ -14,11 +17,68 @@ impl Plugin for AdhesionLineRenderPlugin {
 #[derive(Component)]
 pub struct AdhesionLines;
 
+/// Where adhesion lines attach to their cells
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AdhesionLineMode {
+    /// Between the anchor points on the cell surfaces, with a stalk from each center
+    #[default]
+    Anchored,
+    /// Between cell centers (cheapest)
+    CenterToCenter,
+}
+
+impl AdhesionLineMode {
+    pub const ALL: [AdhesionLineMode; 2] = [AdhesionLineMode::Anchored, AdhesionLineMode::CenterToCenter];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AdhesionLineMode::Anchored => "Anchor to anchor",
+            AdhesionLineMode::CenterToCenter => "Center to center",
+        }
+    }
+}
+
+/// Colors for adhesion strain (center distance relative to rest length)
+#[derive(Clone, Debug)]
+pub struct StrainGradient {
+    pub compressed: Color,
+    pub rest: Color,
+    pub stretched: Color,
+    /// |strain| at which the compressed/stretched color is reached
+    pub full_scale: f32,
+}
+
+impl Default for StrainGradient {
+    fn default() -> Self {
+        Self {
+            compressed: Color::srgb(0.2, 0.5, 1.0),
+            rest: Color::srgb(0.85, 0.85, 0.85),
+            stretched: Color::srgb(1.0, 0.25, 0.15),
+            full_scale: 0.25,
+        }
+    }
+}
+
+impl StrainGradient {
+    pub fn color(&self, strain: f32) -> Color {
+        let t = (strain / self.full_scale.max(1e-6)).clamp(-1.0, 1.0);
+        let end = if t < 0.0 { self.compressed } else { self.stretched };
+        let mixed = LinearRgba::from(self.rest).mix(&LinearRgba::from(end), t.abs());
+        Color::from(mixed)
+    }
+}
+
 /// Resource to control adhesion line visibility
 #[derive(Resource)]
 pub struct AdhesionLineSettings {
     pub show_lines: bool,
     pub line_width: f32,
+    pub mode: AdhesionLineMode,
+    /// Color the span by strain instead of by adhesion zone
+    pub color_by_strain: bool,
+    pub strain_gradient: StrainGradient,
+    /// Span color of bonds that recently hit the force limit (see `ProblemBonds`)
+    pub problem_color: Color,
 }
 
 impl Default for AdhesionLineSettings {
@@ -26,21 +86,65 @@ impl Default for AdhesionLineSettings {
         Self {
             show_lines: true,
             line_width: 0.05,
+            mode: AdhesionLineMode::default(),
+            color_by_strain: true,
+            strain_gradient: StrainGradient::default(),
+            problem_color: Color::srgb(1.0, 0.85, 0.0),
         }
     }
 }
 
+/// World-space anchor points of connection `i` on the surfaces of its two cells
+///
+/// Uses the rendered (tick-interpolated) positions and rotations and the current radii,
+/// so the points follow cell growth and the connection's current cell indices.
+pub fn anchor_points(state: &CanonicalState, interpolation: &TickInterpolation, i: usize) -> Option<(Vec3, Vec3)> {
+    let connections = &state.adhesion_connections;
+    let (a, b) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+    if a >= state.cell_count || b >= state.cell_count {
+        return None;
+    }
+    let (anchor_a, anchor_b) = crate::cell::adhesion_forces::world_anchors(
+        connections.anchor_direction_a[i],
+        connections.anchor_direction_b[i],
+        interpolation.rotation(state, a),
+        interpolation.rotation(state, b),
+    );
+    Some((
+        interpolation.position(state, a) + anchor_a.normalize_or_zero() * state.radii[a],
+        interpolation.position(state, b) + anchor_b.normalize_or_zero() * state.radii[b],
+    ))
+}
+
+fn zone_color(zone: u8) -> Color {
+    get_zone_color(match zone {
+        0 => crate::cell::AdhesionZone::ZoneA,
+        1 => crate::cell::AdhesionZone::ZoneB,
+        _ => crate::cell::AdhesionZone::ZoneC,
+    })
+}
+
+fn dimmed(color: Color) -> Color {
+    Color::from(LinearRgba::from(color).mix(&LinearRgba::BLACK, 0.6))
+}
+
 /// System to render adhesion lines using Bevy's Gizmos
 /// 
-/// Renders colored line segments showing adhesion connections:
-/// - Each connection is rendered as 2 line segments
-/// - Segment 1: Cell A center → midpoint (Zone A color)
-/// - Segment 2: Midpoint → Cell B center (Zone B color)
+/// Anchored mode (default):
+/// - A dim stalk from each cell center to its anchor point (zone color)
+/// - The span between the anchor points, colored by strain (or split at the
+///   midpoint into the two zone colors when strain coloring is off)
+/// 
+/// Center-to-center mode draws the span between cell centers, split into zone colors.
+/// 
+/// In the main simulation, bonds that recently hit the force limit are drawn in the
+/// problem color instead while highlighting is on (Performance Monitor).
 /// 
 /// Zone colors:
 /// - Zone A (Green): Adhesions pointing opposite to split direction
 /// - Zone B (Blue): Adhesions pointing same as split direction
 /// - Zone C (Red): Adhesions in equatorial band
+#[allow(clippy::too_many_arguments)]
 fn render_adhesion_lines_gizmos(
     mut gizmos: Gizmos,
     rendering_config: Res<crate::rendering::RenderingConfig>,
@@ -48,8 +152,12 @@ fn render_adhesion_lines_gizmos(
     main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
     preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
     sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<TickInterpolation>,
+    genome: Res<crate::genome::CurrentGenome>,
+    problem_bonds: Res<crate::simulation::ProblemBonds>,
     focal_plane: Res<crate::ui::camera::FocalPlaneSettings>,
     camera_query: Query<(&Transform, &crate::ui::camera::MainCamera)>,
+    regions: Res<crate::rendering::RegionVisibility>,
 ) {
     // Check if we should show lines (use RenderingConfig as primary control)
     if !rendering_config.show_adhesions {
@@ -63,6 +171,15 @@ fn render_adhesion_lines_gizmos(
         return;
     }
     
+    let default_settings;
+    let settings = match settings.as_deref() {
+        Some(settings) => settings,
+        None => {
+            default_settings = AdhesionLineSettings::default();
+            &default_settings
+        }
+    };
+    
     // Get the appropriate state based on simulation mode
     let (state, connections) = match sim_state.mode {
         crate::simulation::SimulationMode::Cpu => {
@@ -90,6 +207,10 @@ fn render_adhesion_lines_gizmos(
         return;
     }
     
+    let highlight_problems = problem_bonds.highlight
+        && !problem_bonds.events.is_empty()
+        && sim_state.mode == crate::simulation::SimulationMode::Cpu;
+    
     // Get focal plane info for visibility check
     let focal_plane_check = if focal_plane.enabled {
         if let Ok((camera_transform, cam)) = camera_query.single() {
@@ -123,10 +244,15 @@ fn render_adhesion_lines_gizmos(
         }
         
         // Get cell positions and radii
-        let pos_a = state.positions[cell_a_idx];
-        let pos_b = state.positions[cell_b_idx];
+        let pos_a = interpolation.position(state, cell_a_idx);
+        let pos_b = interpolation.position(state, cell_b_idx);
         let radius_a = state.radii[cell_a_idx];
         let radius_b = state.radii[cell_b_idx];
+
+        // Bonds touching a hidden region go with it
+        if regions.is_filtering() && (regions.hides(pos_a) || regions.hides(pos_b)) {
+            continue;
+        }
         
         // Check focal plane visibility - skip if both cells are hidden
         if let Some((plane_center, camera_forward)) = focal_plane_check {
@@ -145,32 +271,78 @@ fn render_adhesion_lines_gizmos(
             }
         }
         
-        // Calculate midpoint
-        let midpoint = (pos_a + pos_b) * 0.5;
-        
-        // Get zone colors
-        let zone_a = connections.zone_a[i];
-        let zone_b = connections.zone_b[i];
-        
-        let color_a = get_zone_color(match zone_a {
-            0 => crate::cell::AdhesionZone::ZoneA,
-            1 => crate::cell::AdhesionZone::ZoneB,
-            _ => crate::cell::AdhesionZone::ZoneC,
-        });
-        
-        let color_b = get_zone_color(match zone_b {
-            0 => crate::cell::AdhesionZone::ZoneA,
-            1 => crate::cell::AdhesionZone::ZoneB,
-            _ => crate::cell::AdhesionZone::ZoneC,
-        });
+        let color_a = zone_color(connections.zone_a[i]);
+        let color_b = zone_color(connections.zone_b[i]);
         
-        // Draw two line segments with zone colors
-        // Segment 1: Cell A → Midpoint (Zone A color)
-        gizmos.line(pos_a, midpoint, color_a);
+        let (end_a, end_b) = match settings.mode {
+            AdhesionLineMode::CenterToCenter => (pos_a, pos_b),
+            AdhesionLineMode::Anchored => {
+                let Some((end_a, end_b)) = anchor_points(state, &interpolation, i) else {
+                    continue;
+                };
+                gizmos.line(pos_a, end_a, dimmed(color_a));
+                gizmos.line(pos_b, end_b, dimmed(color_b));
+                (end_a, end_b)
+            }
+        };
         
-        // Segment 2: Midpoint → Cell B (Zone B color)
-        gizmos.line(midpoint, pos_b, color_b);
+        if highlight_problems
+            && problem_bonds.is_recent(i, state.cell_ids[cell_a_idx], state.cell_ids[cell_b_idx])
+        {
+            gizmos.line(end_a, end_b, settings.problem_color);
+        } else if settings.mode == AdhesionLineMode::Anchored && settings.color_by_strain {
+            let rest_length = genome.genome.modes.get(connections.mode_index[i])
+                .map(|mode| mode.adhesion_settings.rest_length)
+                .unwrap_or(1.0);
+            let strain = (pos_a.distance(pos_b) - rest_length) / rest_length.max(1e-6);
+            gizmos.line(end_a, end_b, settings.strain_gradient.color(strain));
+        } else {
+            // Two segments split at the midpoint, each in its cell's zone color
+            let midpoint = (end_a + end_b) * 0.5;
+            gizmos.line(end_a, midpoint, color_a);
+            gizmos.line(midpoint, end_b, color_b);
+        }
     }
 }
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_points_follow_rotation_and_growth() {
+        let mut state = CanonicalState::new(4);
+        for x in [0.0, 1.0] {
+            state.add_cell(Vec3::X * x, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        // Bond formed at an angle: A's anchor points up, B's points back along -X
+        let i = state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::Y, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+
+        let (end_a, end_b) = anchor_points(&state, &TickInterpolation::default(), i).unwrap();
+        assert!(end_a.abs_diff_eq(Vec3::Y * 0.5, 1e-6));
+        assert!(end_b.abs_diff_eq(Vec3::X * 0.5, 1e-6));
+
+        // Anchors are local to the cell and scale with its radius
+        state.rotations[0] = Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2);
+        state.radii[0] = 0.8;
+        let (end_a, _) = anchor_points(&state, &TickInterpolation::default(), i).unwrap();
+        assert!(end_a.abs_diff_eq(Vec3::X * 0.8, 1e-5));
+
+        // A connection that points past the live cells is skipped
+        state.adhesion_connections.cell_b_index[i] = 3;
+        assert!(anchor_points(&state, &TickInterpolation::default(), i).is_none());
+    }
+
+    #[test]
+    fn test_strain_gradient_saturates_at_full_scale() {
+        let gradient = StrainGradient::default();
+        assert_eq!(gradient.color(0.0), Color::from(LinearRgba::from(gradient.rest)));
+        assert_eq!(gradient.color(gradient.full_scale * 2.0), Color::from(LinearRgba::from(gradient.stretched)));
+        assert_eq!(gradient.color(-gradient.full_scale), Color::from(LinearRgba::from(gradient.compressed)));
+    }
+}
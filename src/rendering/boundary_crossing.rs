@@ -37,6 +37,7 @@ use bevy::{
     },
 };
 
+use bevy::window::PrimaryWindow;
 use crate::ui::camera::MainCamera;
 
 /// World boundary radius (must match the sphere spawned in preview_sim.rs)
@@ -164,7 +165,7 @@ fn detect_boundary_crossing(
 fn update_boundary_effect(
     time: Res<Time>,
     mut state: ResMut<BoundaryCrossingState>,
-    windows: Query<&Window>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     mut camera_query: Query<&mut BoundaryCrossingSettings, With<MainCamera>>,
 ) {
     let Ok(mut settings) = camera_query.single_mut() else {
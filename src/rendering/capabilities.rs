@@ -0,0 +1,274 @@
+use bevy::prelude::*;
+use bevy::render::renderer::{RenderAdapter, RenderAdapterInfo};
+use std::sync::OnceLock;
+
+/// Command line flag that turns off every optional feature, to test the degraded path on capable hardware
+pub const FORCE_LOW_SPEC_FLAG: &str = "--force-low-spec";
+
+/// Edge length of the volumetric fog density texture (3D)
+pub const FOG_DENSITY_TEXTURE_SIZE: u32 = 256;
+
+/// Storage buffers bound at once by the GPU pair detection shaders
+const GPU_COMPUTE_STORAGE_BUFFERS: u32 = 8;
+/// Largest workgroup of the GPU pair detection shaders
+const GPU_COMPUTE_WORKGROUP_SIZE: u32 = 256;
+
+/// Capability report of the session, kept for the crash log
+static CRASH_REPORT: OnceLock<String> = OnceLock::new();
+
+/// Set when the app was started with `--force-low-spec`
+#[derive(Resource, Default, Clone, Copy)]
+pub struct ForceLowSpec(pub bool);
+
+/// Whether an optional rendering feature can run on the adapter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Support {
+    Available,
+    /// Missing feature or limit, as shown to the user
+    Missing(String),
+}
+
+impl Support {
+    pub fn is_available(&self) -> bool {
+        matches!(self, Support::Available)
+    }
+
+    pub fn missing_reason(&self) -> Option<&str> {
+        match self {
+            Support::Available => None,
+            Support::Missing(reason) => Some(reason),
+        }
+    }
+}
+
+/// Optional rendering features supported by the adapter, detected at startup
+///
+/// Everything counts as available until detection ran (or without a renderer),
+/// so headless runs and tests behave as before.
+#[derive(Resource, Clone, Debug)]
+pub struct RenderCapabilities {
+    pub adapter_name: String,
+    pub backend: String,
+    pub forced_low_spec: bool,
+    pub bloom: Support,
+    pub volumetric_fog: Support,
+    /// Compute pipelines of the GPU simulation and GPU pair detection
+    pub gpu_compute: Support,
+    /// Highest cell sphere subdivision level the sphere quality setting may use
+    pub max_sphere_level: u32,
+    /// The dialog listing the disabled features was closed
+    pub report_dismissed: bool,
+}
+
+impl Default for RenderCapabilities {
+    fn default() -> Self {
+        Self {
+            adapter_name: "Unknown".to_string(),
+            backend: "Unknown".to_string(),
+            forced_low_spec: false,
+            bloom: Support::Available,
+            volumetric_fog: Support::Available,
+            gpu_compute: Support::Available,
+            max_sphere_level: super::sphere_quality::MAX_SPHERE_LEVEL,
+            report_dismissed: false,
+        }
+    }
+}
+
+impl RenderCapabilities {
+    /// Check the optional features against the adapter's limits and formats
+    pub fn evaluate(
+        limits: &wgpu::Limits,
+        downlevel: wgpu::DownlevelFlags,
+        bloom_format: wgpu::TextureFormatFeatures,
+    ) -> Self {
+        // Bloom renders its mip chain into, and filters from, Rg11b10Ufloat textures
+        let bloom_usages = wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING;
+        let bloom = if !bloom_format.allowed_usages.contains(bloom_usages) {
+            Support::Missing("Rg11b10Ufloat render targets are not supported".to_string())
+        } else if !bloom_format.flags.contains(wgpu::TextureFormatFeatureFlags::FILTERABLE) {
+            Support::Missing("Rg11b10Ufloat textures are not filterable".to_string())
+        } else {
+            Support::Available
+        };
+
+        let volumetric_fog = if limits.max_texture_dimension_3d < FOG_DENSITY_TEXTURE_SIZE {
+            Support::Missing(format!(
+                "max_texture_dimension_3d is {}, the fog density texture needs {}",
+                limits.max_texture_dimension_3d, FOG_DENSITY_TEXTURE_SIZE
+            ))
+        } else {
+            Support::Available
+        };
+
+        let gpu_compute = if !downlevel.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS) {
+            Support::Missing("compute shaders are not supported".to_string())
+        } else if limits.max_storage_buffers_per_shader_stage < GPU_COMPUTE_STORAGE_BUFFERS {
+            Support::Missing(format!(
+                "max_storage_buffers_per_shader_stage is {}, {} are needed",
+                limits.max_storage_buffers_per_shader_stage, GPU_COMPUTE_STORAGE_BUFFERS
+            ))
+        } else if limits.max_compute_invocations_per_workgroup < GPU_COMPUTE_WORKGROUP_SIZE
+            || limits.max_compute_workgroup_size_x < GPU_COMPUTE_WORKGROUP_SIZE
+        {
+            Support::Missing(format!(
+                "compute workgroups are limited to {} invocations, {} are needed",
+                limits.max_compute_invocations_per_workgroup.min(limits.max_compute_workgroup_size_x),
+                GPU_COMPUTE_WORKGROUP_SIZE
+            ))
+        } else {
+            Support::Available
+        };
+
+        Self { bloom, volumetric_fog, gpu_compute, ..default() }
+    }
+
+    /// Every optional feature turned off, as with `--force-low-spec`
+    pub fn low_spec() -> Self {
+        let forced = || Support::Missing(format!("disabled by {}", FORCE_LOW_SPEC_FLAG));
+        Self {
+            forced_low_spec: true,
+            bloom: forced(),
+            volumetric_fog: forced(),
+            gpu_compute: forced(),
+            max_sphere_level: 1,
+            ..default()
+        }
+    }
+
+    /// Every optional feature turned off by safe mode, whose banner explains it
+    pub fn safe_mode() -> Self {
+        let bypassed = || Support::Missing("disabled by safe mode".to_string());
+        Self {
+            bloom: bypassed(),
+            volumetric_fog: bypassed(),
+            gpu_compute: bypassed(),
+            max_sphere_level: 1,
+            report_dismissed: true,
+            ..default()
+        }
+    }
+
+    /// Name and support of each optional feature
+    pub fn features(&self) -> [(&'static str, &Support); 3] {
+        [
+            ("Bloom", &self.bloom),
+            ("Volumetric fog", &self.volumetric_fog),
+            ("GPU compute (GPU mode, GPU pair detection)", &self.gpu_compute),
+        ]
+    }
+
+    pub fn any_missing(&self) -> bool {
+        self.features().iter().any(|(_, support)| !support.is_available())
+    }
+
+    /// Plain text summary, as written to the log and crash logs
+    pub fn report(&self) -> String {
+        let mut report = format!("Adapter: {} ({})\n", self.adapter_name, self.backend);
+        if self.forced_low_spec {
+            report.push_str(&format!("Started with {}\n", FORCE_LOW_SPEC_FLAG));
+        }
+        for (name, support) in self.features() {
+            match support.missing_reason() {
+                None => report.push_str(&format!("{}: available\n", name)),
+                Some(reason) => report.push_str(&format!("{}: disabled ({})\n", name, reason)),
+            }
+        }
+        if self.max_sphere_level < super::sphere_quality::MAX_SPHERE_LEVEL {
+            report.push_str(&format!("Cell sphere quality: limited to level {}\n", self.max_sphere_level));
+        }
+        report
+    }
+}
+
+/// Capability report for crash logs, once detection ran
+pub fn crash_report() -> Option<&'static str> {
+    CRASH_REPORT.get().map(String::as_str)
+}
+
+/// Detect the optional rendering features of the adapter the renderer picked
+pub fn detect_render_capabilities(
+    mut capabilities: ResMut<RenderCapabilities>,
+    force_low_spec: Option<Res<ForceLowSpec>>,
+    adapter: Option<Res<RenderAdapter>>,
+    adapter_info: Option<Res<RenderAdapterInfo>>,
+) {
+    let (Some(adapter), Some(adapter_info)) = (adapter, adapter_info) else {
+        return;
+    };
+
+    let mut detected = if force_low_spec.is_some_and(|force| force.0) {
+        RenderCapabilities::low_spec()
+    } else if crate::safe_mode::is_bypassed(crate::safe_mode::Subsystem::Rendering) {
+        RenderCapabilities::safe_mode()
+    } else {
+        RenderCapabilities::evaluate(
+            &adapter.limits(),
+            adapter.get_downlevel_capabilities().flags,
+            adapter.get_texture_format_features(wgpu::TextureFormat::Rg11b10Ufloat),
+        )
+    };
+    detected.adapter_name = adapter_info.name.clone();
+    detected.backend = format!("{:?}", adapter_info.backend);
+
+    let report = detected.report();
+    if detected.any_missing() {
+        warn!("Some rendering features are not available and were turned off:\n{}", report);
+    } else {
+        info!("Rendering capabilities:\n{}", report);
+    }
+    let _ = CRASH_REPORT.set(report);
+    *capabilities = detected;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bloom_format(flags: wgpu::TextureFormatFeatureFlags) -> wgpu::TextureFormatFeatures {
+        wgpu::TextureFormatFeatures {
+            allowed_usages: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            flags,
+        }
+    }
+
+    #[test]
+    fn test_capable_adapter_supports_everything() {
+        let capabilities = RenderCapabilities::evaluate(
+            &wgpu::Limits::default(),
+            wgpu::DownlevelFlags::all(),
+            bloom_format(wgpu::TextureFormatFeatureFlags::FILTERABLE),
+        );
+        assert!(!capabilities.any_missing(), "{}", capabilities.report());
+    }
+
+    #[test]
+    fn test_downlevel_adapter_loses_compute_and_bloom() {
+        let capabilities = RenderCapabilities::evaluate(
+            &wgpu::Limits::downlevel_webgl2_defaults(),
+            wgpu::DownlevelFlags::empty(),
+            bloom_format(wgpu::TextureFormatFeatureFlags::empty()),
+        );
+        assert_eq!(capabilities.gpu_compute, Support::Missing("compute shaders are not supported".to_string()));
+        assert!(!capabilities.bloom.is_available());
+        assert!(capabilities.volumetric_fog.is_available());
+
+        let small_3d = wgpu::Limits { max_texture_dimension_3d: 128, ..wgpu::Limits::default() };
+        let capabilities = RenderCapabilities::evaluate(
+            &small_3d,
+            wgpu::DownlevelFlags::all(),
+            bloom_format(wgpu::TextureFormatFeatureFlags::FILTERABLE),
+        );
+        assert!(capabilities.volumetric_fog.missing_reason().unwrap().contains("128"));
+    }
+
+    #[test]
+    fn test_low_spec_report_names_the_flag() {
+        let capabilities = RenderCapabilities::low_spec();
+        assert!(capabilities.features().iter().all(|(_, support)| !support.is_available()));
+        assert!(capabilities.report().contains(FORCE_LOW_SPEC_FLAG));
+        // Cells render with the coarsest sphere whatever the quality setting
+        assert_eq!(capabilities.max_sphere_level, 1);
+        assert_eq!(RenderCapabilities::default().max_sphere_level, crate::rendering::sphere_quality::MAX_SPHERE_LEVEL);
+    }
+}
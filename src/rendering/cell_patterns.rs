@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy::image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::math::Affine2;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::genome::{CellPattern, PatternKind};
+
+/// Plugin providing the pattern textures used by mode surface patterns
+pub struct CellPatternPlugin;
+
+impl Plugin for CellPatternPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CellPatternTextures>();
+    }
+}
+
+const WIDTH: u32 = 256;
+const HEIGHT: u32 = 128;
+
+/// Brightness of the pattern marks (multiplied with the mode color)
+const MARK_BRIGHTNESS: f32 = 0.4;
+
+/// Tiling grayscale textures for each pattern kind
+///
+/// The texture is multiplied with the mode color, so marks darken the surface
+/// the same way for every hue. Sphere UVs wrap u around the equator and run v
+/// pole to pole, so the textures are twice as wide as they are tall.
+#[derive(Resource)]
+pub struct CellPatternTextures {
+    stripes: Handle<Image>,
+    dots: Handle<Image>,
+    rings: Handle<Image>,
+}
+
+impl FromWorld for CellPatternTextures {
+    fn from_world(world: &mut World) -> Self {
+        let mut images = world.resource_mut::<Assets<Image>>();
+        Self {
+            stripes: images.add(pattern_image(PatternKind::Stripes)),
+            dots: images.add(pattern_image(PatternKind::Dots)),
+            rings: images.add(pattern_image(PatternKind::Rings)),
+        }
+    }
+}
+
+impl CellPatternTextures {
+    pub fn texture(&self, kind: PatternKind) -> Handle<Image> {
+        match kind {
+            PatternKind::Stripes => self.stripes.clone(),
+            PatternKind::Dots => self.dots.clone(),
+            PatternKind::Rings => self.rings.clone(),
+        }
+    }
+
+    /// Set (or clear) a cell material's pattern
+    pub fn apply(&self, material: &mut StandardMaterial, pattern: Option<CellPattern>) {
+        match pattern {
+            Some(pattern) => {
+                material.base_color_texture = Some(self.texture(pattern.kind));
+                material.uv_transform = Affine2::from_scale(Vec2::splat(pattern.scale.clamp(0.25, 8.0)));
+            }
+            None => {
+                material.base_color_texture = None;
+                material.uv_transform = Affine2::IDENTITY;
+            }
+        }
+    }
+}
+
+/// Coverage of the pattern mark at texel (x, y), 0.0 = background, 1.0 = mark
+fn pattern_coverage(kind: PatternKind, x: f32, y: f32) -> f32 {
+    // Soft edges so the marks don't alias on small cells
+    let edge = |distance: f32, half_width: f32| (half_width + 0.75 - distance).clamp(0.0, 1.5) / 1.5;
+    match kind {
+        // 8 stripes around the equator
+        PatternKind::Stripes => {
+            let period = WIDTH as f32 / 8.0;
+            let d = ((x % period) - period * 0.5).abs();
+            edge(d, period * 0.22)
+        }
+        // 8 bands from pole to pole
+        PatternKind::Rings => {
+            let period = HEIGHT as f32 / 8.0;
+            let d = ((y % period) - period * 0.5).abs();
+            edge(d, period * 0.2)
+        }
+        // 16 x 8 grid of dots (square cells on the equator)
+        PatternKind::Dots => {
+            let period = HEIGHT as f32 / 8.0;
+            let dx = (x % period) - period * 0.5;
+            let dy = (y % period) - period * 0.5;
+            edge((dx * dx + dy * dy).sqrt(), period * 0.28)
+        }
+    }
+}
+
+fn pattern_image(kind: PatternKind) -> Image {
+    let mut data = Vec::with_capacity((WIDTH * HEIGHT * 4) as usize);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let coverage = pattern_coverage(kind, x as f32 + 0.5, y as f32 + 0.5);
+            let value = (255.0 * (1.0 - coverage * (1.0 - MARK_BRIGHTNESS))) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: WIDTH,
+            height: HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        default(),
+    );
+    // Repeat so the UV scale tiles the pattern
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::linear()
+    });
+    image
+}
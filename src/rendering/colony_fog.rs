@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use bevy::light::FogVolume;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{SimulationMode, SimulationState};
+use super::volumetric_fog::{SphericalDensityTexture, SphericalFogVolume, VolumetricFogSettings};
+
+/// Edge length of the colony density texture (3D)
+pub const COLONY_FOG_TEXTURE_SIZE: u32 = 64;
+
+pub const DEFAULT_COLONY_FOG_INTENSITY: f32 = 1.0;
+pub const DEFAULT_COLONY_FOG_INTERVAL: f32 = 0.5;
+
+/// Range of the colony texture relative to the ambient fog
+///
+/// The ambient fog is stored at `1 / DENSITY_HEADROOM` so colonies can add up to
+/// `DENSITY_HEADROOM - 1` times the ambient density before the 8-bit texel saturates;
+/// the fog volume's density factor is scaled up by the same amount to compensate.
+const DENSITY_HEADROOM: f32 = 4.0;
+
+/// Plugin regenerating the fog density texture from the cell distribution
+pub struct ColonyFogPlugin;
+
+impl Plugin for ColonyFogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColonyFog>()
+            .add_systems(Update, update_colony_fog.after(super::volumetric_fog::update_volumetric_fog_settings));
+    }
+}
+
+/// Colony density texture and its regeneration
+///
+/// Splatting and blurring run on the async compute pool; the main thread only copies
+/// the cell positions out and the finished texels in (a few hundred KB), which stays
+/// well under a millisecond.
+#[derive(Resource, Default)]
+pub struct ColonyFog {
+    texture: Option<Handle<Image>>,
+    task: Option<Task<Vec<u8>>>,
+    since_regeneration: f32,
+    /// The fog volumes sample the colony texture instead of the static one
+    active: bool,
+}
+
+impl ColonyFog {
+    /// Factor on the fog volumes' density for the texture they currently sample
+    pub fn density_scale(&self) -> f32 {
+        if self.active { DENSITY_HEADROOM } else { 1.0 }
+    }
+}
+
+/// Colony density texels (R8, x fastest) for cells given as (position, radius)
+///
+/// Each cell's volume is splatted trilinearly as the fraction of a voxel it fills,
+/// blurred once with a [1 2 1] kernel per axis, and added (times `intensity`) to the
+/// ambient sphere of the static fog texture. `volume_center`/`volume_size` place the
+/// unit fog volume in the world.
+pub fn colony_density_texels(cells: &[(Vec3, f32)], volume_center: Vec3, volume_size: Vec3, intensity: f32) -> Vec<u8> {
+    let size = COLONY_FOG_TEXTURE_SIZE as usize;
+    let index = |x: usize, y: usize, z: usize| x + size * (y + size * z);
+    let voxel = volume_size / size as f32;
+    let voxel_volume = voxel.x * voxel.y * voxel.z;
+
+    let mut occupancy = vec![0.0f32; size * size * size];
+    for &(position, radius) in cells {
+        // Voxel coordinates with voxel centers on integers
+        let grid = ((position - volume_center) / volume_size + 0.5) * size as f32 - 0.5;
+        let base = grid.floor();
+        let fraction = grid - base;
+        let filled = 4.0 / 3.0 * std::f32::consts::PI * radius.powi(3) / voxel_volume;
+        for corner in 0..8 {
+            let offset = IVec3::new(corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+            let voxel = base.as_ivec3() + offset;
+            if voxel.cmplt(IVec3::ZERO).any() || voxel.cmpge(IVec3::splat(size as i32)).any() {
+                continue;
+            }
+            let weight = Vec3::select(offset.cmpeq(IVec3::ONE), fraction, Vec3::ONE - fraction);
+            occupancy[index(voxel.x as usize, voxel.y as usize, voxel.z as usize)] += filled * weight.x * weight.y * weight.z;
+        }
+    }
+
+    // Separable blur, clamped at the edges
+    let mut blurred = vec![0.0f32; occupancy.len()];
+    for axis in 0..3 {
+        for z in 0..size {
+            for y in 0..size {
+                for x in 0..size {
+                    let mut at = [x, y, z];
+                    let center = at[axis];
+                    at[axis] = center.saturating_sub(1);
+                    let before = occupancy[index(at[0], at[1], at[2])];
+                    at[axis] = (center + 1).min(size - 1);
+                    let after = occupancy[index(at[0], at[1], at[2])];
+                    blurred[index(x, y, z)] = 0.25 * before + 0.5 * occupancy[index(x, y, z)] + 0.25 * after;
+                }
+            }
+        }
+        std::mem::swap(&mut occupancy, &mut blurred);
+    }
+
+    let center = size as f32 / 2.0;
+    let mut texels = Vec::with_capacity(occupancy.len());
+    for z in 0..size {
+        for y in 0..size {
+            for x in 0..size {
+                let distance = Vec3::new(x as f32 - center, y as f32 - center, z as f32 - center).length();
+                let ambient = if distance <= center { 1.0 } else { 0.0 };
+                let density = (ambient + intensity * occupancy[index(x, y, z)].min(1.0)) / DENSITY_HEADROOM;
+                texels.push((density.clamp(0.0, 1.0) * 255.0).round() as u8);
+            }
+        }
+    }
+    texels
+}
+
+/// Regenerate the colony texture every `colony_interval` seconds and point the fog volumes at it
+///
+/// Falls back to the static texture whenever the mode is off or fog isn't supported.
+fn update_colony_fog(
+    time: Res<Time>,
+    settings: Res<VolumetricFogSettings>,
+    capabilities: Res<super::RenderCapabilities>,
+    mut colony_fog: ResMut<ColonyFog>,
+    mut images: ResMut<Assets<Image>>,
+    static_texture: Option<Res<SphericalDensityTexture>>,
+    mut volumes: Query<(&mut FogVolume, &GlobalTransform), With<SphericalFogVolume>>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+) {
+    let Some(static_texture) = static_texture else {
+        return;
+    };
+    let wanted = settings.enabled && settings.colony_density && capabilities.volumetric_fog.is_available();
+
+    if !wanted {
+        // Dropping the task cancels it
+        colony_fog.task = None;
+        colony_fog.since_regeneration = f32::INFINITY;
+        colony_fog.active = false;
+    } else {
+        if let Some(task) = colony_fog.task.as_mut() {
+            if let Some(texels) = block_on(poll_once(task)) {
+                colony_fog.task = None;
+                match colony_fog.texture.as_ref().and_then(|handle| images.get_mut(handle)) {
+                    Some(image) => image.data = Some(texels),
+                    None => colony_fog.texture = Some(images.add(colony_image(texels))),
+                }
+                colony_fog.active = true;
+            }
+        }
+
+        colony_fog.since_regeneration += time.delta_secs();
+        if colony_fog.task.is_none() && colony_fog.since_regeneration >= settings.colony_interval {
+            colony_fog.since_regeneration = 0.0;
+            let state = match sim_state.mode {
+                SimulationMode::Cpu => main_state.as_deref().map(|main_state| &main_state.canonical_state),
+                SimulationMode::Preview => preview_state.as_deref().map(|preview_state| &preview_state.canonical_state),
+                SimulationMode::Gpu => None,
+            };
+            let cells: Vec<(Vec3, f32)> = state
+                .map(|state| (0..state.cell_count).map(|i| (state.positions[i], state.radii[i])).collect())
+                .unwrap_or_default();
+            let (volume_center, volume_size) = volumes.iter()
+                .next()
+                .map(|(_, transform)| (transform.translation(), transform.scale()))
+                .unwrap_or((Vec3::ZERO, Vec3::splat(200.0)));
+            let intensity = settings.colony_intensity;
+            colony_fog.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+                colony_density_texels(&cells, volume_center, volume_size, intensity)
+            }));
+        }
+    }
+
+    let texture = match (colony_fog.active, colony_fog.texture.as_ref()) {
+        (true, Some(texture)) => texture.clone(),
+        _ => static_texture.0.clone(),
+    };
+    let density_factor = if settings.enabled && capabilities.volumetric_fog.is_available() {
+        settings.density_factor * colony_fog.density_scale()
+    } else {
+        0.0
+    };
+    for (mut fog_volume, _) in volumes.iter_mut() {
+        if fog_volume.density_texture.as_ref() != Some(&texture) {
+            fog_volume.density_texture = Some(texture.clone());
+            fog_volume.density_factor = density_factor;
+        }
+    }
+}
+
+fn colony_image(texels: Vec<u8>) -> Image {
+    Image::new(
+        Extent3d {
+            width: COLONY_FOG_TEXTURE_SIZE,
+            height: COLONY_FOG_TEXTURE_SIZE,
+            depth_or_array_layers: COLONY_FOG_TEXTURE_SIZE,
+        },
+        TextureDimension::D3,
+        texels,
+        TextureFormat::R8Unorm,
+        default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOLUME_SIZE: Vec3 = Vec3::splat(200.0);
+
+    fn texel(texels: &[u8], x: usize, y: usize, z: usize) -> u8 {
+        let size = COLONY_FOG_TEXTURE_SIZE as usize;
+        texels[x + size * (y + size * z)]
+    }
+
+    #[test]
+    fn test_empty_scene_leaves_ambient_fog() {
+        let texels = colony_density_texels(&[], Vec3::ZERO, VOLUME_SIZE, 1.0);
+        let ambient = (255.0 / DENSITY_HEADROOM).round() as u8;
+        assert_eq!(texel(&texels, 32, 32, 32), ambient);
+        assert_eq!(texel(&texels, 0, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_colony_thickens_fog_where_it_is() {
+        // A packed ball of cells around (50, 0, 0)
+        let mut cells = Vec::new();
+        for x in -3..=3 {
+            for y in -3..=3 {
+                for z in -3..=3 {
+                    cells.push((Vec3::new(50.0, 0.0, 0.0) + Vec3::new(x as f32, y as f32, z as f32) * 2.0, 1.0));
+                }
+            }
+        }
+        let empty = colony_density_texels(&[], Vec3::ZERO, VOLUME_SIZE, 1.0);
+        let colony = colony_density_texels(&cells, Vec3::ZERO, VOLUME_SIZE, 1.0);
+
+        // (50, 0, 0) is voxel 48 along x
+        assert!(texel(&colony, 48, 32, 32) > texel(&empty, 48, 32, 32) + 20);
+        assert_eq!(texel(&colony, 16, 32, 32), texel(&empty, 16, 32, 32));
+
+        let off = colony_density_texels(&cells, Vec3::ZERO, VOLUME_SIZE, 0.0);
+        assert_eq!(off, empty);
+    }
+}
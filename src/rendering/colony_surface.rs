@@ -0,0 +1,59 @@
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use crate::simulation::{ColonySurface, SimulationState};
+
+/// Plugin for the translucent overlay of a reconstructed organism surface
+pub struct ColonySurfaceOverlayPlugin;
+
+impl Plugin for ColonySurfaceOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_colony_surface_overlay.after(crate::simulation::colony_surface::update_colony_surface));
+    }
+}
+
+/// Marker for the overlay entity
+#[derive(Component)]
+pub struct ColonySurfaceOverlay;
+
+/// Respawn the overlay when a new surface lands, hide it in the other mode or when turned off
+fn sync_colony_surface_overlay(
+    mut commands: Commands,
+    surface: Res<ColonySurface>,
+    sim_state: Res<SimulationState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<(u64, Entity)>>,
+) {
+    let wanted = surface.result.as_ref()
+        .filter(|result| surface.show_overlay && result.mode == sim_state.mode && !result.mesh.indices.is_empty());
+    if shown.map(|(generation, _)| generation) == wanted.map(|result| result.generation) {
+        return;
+    }
+    if let Some((_, entity)) = shown.take() {
+        commands.entity(entity).despawn();
+    }
+    let Some(result) = wanted else {
+        return;
+    };
+
+    let mut mesh = Mesh::new(bevy_mesh::PrimitiveTopology::TriangleList, bevy_asset::RenderAssetUsages::RENDER_WORLD);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, result.mesh.positions.clone());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, result.mesh.vertex_normals());
+    mesh.insert_indices(bevy_mesh::Indices::U32(result.mesh.indices.clone()));
+
+    let entity = commands.spawn((
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(0.4, 0.8, 1.0, 0.25),
+            alpha_mode: AlphaMode::Blend,
+            cull_mode: None,
+            double_sided: true,
+            perceptual_roughness: 0.6,
+            ..default()
+        })),
+        Transform::default(),
+        ColonySurfaceOverlay,
+        NotShadowCaster,
+    )).id();
+    *shown = Some((result.generation, entity));
+}
@@ -0,0 +1,422 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{SimulationMode, SimulationState};
+use crate::ui::LightingConfig;
+use super::skybox::{tinted_skybox_colors, SkyboxConfig, SkyboxOriginalColor};
+
+/// Plugin animating the main light and skybox tint over simulated time
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        // After the skybox update so a skybox config change doesn't wipe the tint
+        app.add_systems(Update, animate_day_night_lighting.after(super::skybox::update_skybox_materials));
+    }
+}
+
+/// Fewest keyframes a cycle can have
+pub const MIN_KEYFRAMES: usize = 2;
+
+/// Most keyframes a cycle can have
+pub const MAX_KEYFRAMES: usize = 6;
+
+/// Color temperature range of a keyframe, in kelvin
+pub const TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 1000.0..=12000.0;
+
+/// Main light and sky at one point of the cycle
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct LightKeyframe {
+    /// Position in the cycle (0..1)
+    pub phase: f32,
+    /// Angle of the light above the horizon in degrees (negative below it)
+    pub elevation: f32,
+    /// Compass direction the light comes from in degrees
+    pub azimuth: f32,
+    /// Color temperature in kelvin
+    pub temperature: f32,
+    pub illuminance: f32,
+    /// sRGB multiplier on the skybox
+    pub sky_tint: [f32; 3],
+}
+
+/// Keyframed sweep of the main light over a cycle of simulated time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct DayNightCycle {
+    pub enabled: bool,
+    /// Simulated seconds per cycle
+    pub cycle_length: f32,
+    /// Kept sorted by phase
+    pub keyframes: Vec<LightKeyframe>,
+}
+
+impl Default for DayNightCycle {
+    fn default() -> Self {
+        let keyframe = |phase, elevation, azimuth, temperature, illuminance, sky_tint| LightKeyframe {
+            phase, elevation, azimuth, temperature, illuminance, sky_tint,
+        };
+        Self {
+            enabled: false,
+            cycle_length: 120.0,
+            keyframes: vec![
+                keyframe(0.0, 5.0, 90.0, 2500.0, 3000.0, [1.0, 0.75, 0.6]),
+                keyframe(0.25, 70.0, 180.0, 6500.0, 12000.0, [1.0, 1.0, 1.0]),
+                keyframe(0.5, 5.0, 270.0, 2200.0, 3000.0, [1.0, 0.6, 0.5]),
+                keyframe(0.75, -30.0, 0.0, 9000.0, 300.0, [0.25, 0.3, 0.5]),
+            ],
+        }
+    }
+}
+
+/// Interpolated main light and sky tint
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightSample {
+    pub rotation: Quat,
+    pub color: LinearRgba,
+    pub illuminance: f32,
+    pub sky_tint: LinearRgba,
+}
+
+impl DayNightCycle {
+    /// Phase of the cycle at a simulated time (0..1)
+    pub fn phase_at(&self, time: f32) -> f32 {
+        if self.cycle_length <= 0.0 {
+            return 0.0;
+        }
+        (time / self.cycle_length).rem_euclid(1.0)
+    }
+
+    /// Light at a phase, interpolating between the keyframes around it and wrapping from
+    /// the last keyframe to the first
+    pub fn sample(&self, phase: f32) -> LightSample {
+        let Some(first) = self.keyframes.first() else {
+            return LightKeyframe::default_noon().sample();
+        };
+        let phase = phase.rem_euclid(1.0);
+        // The keyframe at or before the phase, and the one after it (a cycle later on wrap)
+        let (from, from_phase, to, to_phase) = match self.keyframes.iter().rposition(|k| k.phase <= phase) {
+            Some(index) => {
+                let from = &self.keyframes[index];
+                match self.keyframes.get(index + 1) {
+                    Some(to) => (from, from.phase, to, to.phase),
+                    None => (from, from.phase, first, first.phase + 1.0),
+                }
+            }
+            None => {
+                let last = &self.keyframes[self.keyframes.len() - 1];
+                (last, last.phase - 1.0, first, first.phase)
+            }
+        };
+        let span = to_phase - from_phase;
+        let t = if span > f32::EPSILON { ((phase - from_phase) / span).clamp(0.0, 1.0) } else { 0.0 };
+        from.sample().lerp(&to.sample(), t)
+    }
+
+    /// Light at a simulated time
+    pub fn sample_at(&self, time: f32) -> LightSample {
+        self.sample(self.phase_at(time))
+    }
+
+    /// Re-sort after a keyframe's phase was edited
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort_by(|a, b| a.phase.total_cmp(&b.phase));
+    }
+
+    /// Add a keyframe in the middle of the widest gap, matching the light there
+    pub fn add_keyframe(&mut self) {
+        if self.keyframes.len() >= MAX_KEYFRAMES {
+            return;
+        }
+        let phase = match self.keyframes.len() {
+            0 => 0.0,
+            count => {
+                let (start, gap) = (0..count)
+                    .map(|i| {
+                        let next = self.keyframes.get(i + 1).map_or(self.keyframes[0].phase + 1.0, |k| k.phase);
+                        (self.keyframes[i].phase, next - self.keyframes[i].phase)
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap_or((0.0, 1.0));
+                (start + gap * 0.5).rem_euclid(1.0)
+            }
+        };
+        let mut keyframe = LightKeyframe::default_noon();
+        if !self.keyframes.is_empty() {
+            let sample = self.sample(phase);
+            let srgb = |color: LinearRgba| Color::from(color).to_srgba();
+            let (sky, direction) = (srgb(sample.sky_tint), sample.rotation * Vec3::NEG_Z);
+            keyframe.elevation = (-direction.y).asin().to_degrees();
+            keyframe.azimuth = (-direction.x).atan2(-direction.z).to_degrees().rem_euclid(360.0);
+            keyframe.illuminance = sample.illuminance;
+            keyframe.sky_tint = [sky.red, sky.green, sky.blue];
+            let neighbour = self.keyframes.iter().rev().find(|k| k.phase <= phase).unwrap_or(&self.keyframes[0]);
+            keyframe.temperature = neighbour.temperature;
+        }
+        keyframe.phase = phase;
+        self.keyframes.push(keyframe);
+        self.sort_keyframes();
+    }
+
+    pub fn remove_keyframe(&mut self, index: usize) {
+        if self.keyframes.len() > MIN_KEYFRAMES && index < self.keyframes.len() {
+            self.keyframes.remove(index);
+        }
+    }
+}
+
+impl LightKeyframe {
+    fn default_noon() -> Self {
+        Self {
+            phase: 0.0,
+            elevation: 60.0,
+            azimuth: 180.0,
+            temperature: 6500.0,
+            illuminance: 10000.0,
+            sky_tint: [1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Orientation of a light shining from this keyframe's elevation and azimuth
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.azimuth.to_radians(), -self.elevation.to_radians(), 0.0)
+    }
+
+    fn sample(&self) -> LightSample {
+        let [r, g, b] = self.sky_tint;
+        LightSample {
+            rotation: self.rotation(),
+            color: temperature_color(self.temperature),
+            illuminance: self.illuminance,
+            sky_tint: Color::srgb(r, g, b).to_linear(),
+        }
+    }
+}
+
+impl LightSample {
+    /// Blend toward another sample; colors blend in linear space
+    pub fn lerp(&self, other: &LightSample, t: f32) -> LightSample {
+        let mix = |a: LinearRgba, b: LinearRgba| LinearRgba::new(
+            a.red + (b.red - a.red) * t,
+            a.green + (b.green - a.green) * t,
+            a.blue + (b.blue - a.blue) * t,
+            a.alpha + (b.alpha - a.alpha) * t,
+        );
+        LightSample {
+            rotation: self.rotation.slerp(other.rotation, t),
+            color: mix(self.color, other.color),
+            illuminance: self.illuminance + (other.illuminance - self.illuminance) * t,
+            sky_tint: mix(self.sky_tint, other.sky_tint),
+        }
+    }
+}
+
+/// Linear color of a black body at a temperature, normalized so the brightest channel is 1
+///
+/// Fit of the Planckian locus in sRGB (good to a few percent between 1000 K and 12000 K).
+pub fn temperature_color(kelvin: f32) -> LinearRgba {
+    let t = kelvin.clamp(*TEMPERATURE_RANGE.start(), *TEMPERATURE_RANGE.end()) / 100.0;
+    let red = if t <= 66.0 { 1.0 } else { 1.292_936_2 * (t - 60.0).powf(-0.133_204_76) };
+    let green = if t <= 66.0 {
+        0.390_081_58 * t.ln() - 0.631_841_4
+    } else {
+        1.129_890_9 * (t - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if t >= 66.0 {
+        1.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        0.543_206_8 * (t - 10.0).ln() - 1.196_254_1
+    };
+    Color::srgb(red.clamp(0.0, 1.0), green.clamp(0.0, 1.0), blue.clamp(0.0, 1.0)).to_linear()
+}
+
+/// Simulated time of the active scene (0 without one)
+pub(crate) fn active_time(
+    sim_state: &SimulationState,
+    main_state: Option<&MainSimState>,
+    preview_state: Option<&PreviewSimState>,
+) -> f32 {
+    match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| s.simulation_time),
+        SimulationMode::Preview => preview_state.map(|s| s.current_time),
+        SimulationMode::Gpu => None,
+    }
+    .unwrap_or(0.0)
+}
+
+/// Drive the directional lights and skybox tint from the active scene's simulated time
+///
+/// Nothing is touched while the cycle is off unless the static lighting is edited, and
+/// the static lighting is put back once when the cycle is turned off.
+fn animate_day_night_lighting(
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+    lighting: Res<LightingConfig>,
+    skybox_config: Res<SkyboxConfig>,
+    new_lights: Query<(), Added<DirectionalLight>>,
+    new_skybox: Query<(), Added<SkyboxOriginalColor>>,
+    mut lights: Query<(&mut DirectionalLight, &mut Transform)>,
+    skybox_materials: Query<&MeshMaterial3d<StandardMaterial>, With<SkyboxOriginalColor>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<f32>>,
+) {
+    let time = active_time(&sim_state, main_state.as_deref(), preview_state.as_deref());
+    let phase = lighting.shown_phase(time);
+    let respawned = !new_lights.is_empty() || !new_skybox.is_empty();
+
+    let (rotation, color, illuminance, sky_tint) = match phase {
+        Some(phase) => {
+            if *shown == Some(phase) && !lighting.is_changed() && !skybox_config.is_changed() && !respawned {
+                return;
+            }
+            let sample = lighting.day_night.sample(phase);
+            (sample.rotation, Color::from(sample.color), sample.illuminance, sample.sky_tint)
+        }
+        None => {
+            if shown.is_none() && !lighting.is_changed() {
+                return;
+            }
+            let [x, y, z] = lighting.directional_rotation.map(f32::to_radians);
+            let [r, g, b] = lighting.directional_color;
+            (Quat::from_euler(EulerRot::XYZ, x, y, z), Color::srgb(r, g, b), lighting.directional_illuminance, LinearRgba::WHITE)
+        }
+    };
+    *shown = phase;
+
+    for (mut light, mut transform) in lights.iter_mut() {
+        light.color = color;
+        light.illuminance = illuminance;
+        transform.rotation = rotation;
+    }
+    let (base_color, emissive) = tinted_skybox_colors(&skybox_config, sky_tint);
+    for handle in skybox_materials.iter() {
+        if let Some(material) = materials.get_mut(&handle.0) {
+            material.base_color = base_color;
+            material.emissive = emissive;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(phase: f32, illuminance: f32) -> LightKeyframe {
+        LightKeyframe { phase, illuminance, ..LightKeyframe::default_noon() }
+    }
+
+    fn cycle(keyframes: Vec<LightKeyframe>) -> DayNightCycle {
+        DayNightCycle { enabled: true, cycle_length: 100.0, keyframes }
+    }
+
+    #[test]
+    fn test_phase_follows_simulated_time_and_wraps() {
+        let cycle = cycle(vec![keyframe(0.0, 0.0), keyframe(0.5, 1.0)]);
+        assert!((cycle.phase_at(25.0) - 0.25).abs() < 1e-6);
+        assert!((cycle.phase_at(125.0) - 0.25).abs() < 1e-6);
+        assert!((cycle.phase_at(-25.0) - 0.75).abs() < 1e-6);
+        assert_eq!(DayNightCycle { cycle_length: 0.0, ..cycle.clone() }.phase_at(10.0), 0.0);
+    }
+
+    #[test]
+    fn test_sample_interpolates_between_keyframes() {
+        let cycle = cycle(vec![keyframe(0.2, 100.0), keyframe(0.6, 500.0)]);
+        assert!((cycle.sample(0.2).illuminance - 100.0).abs() < 1e-3);
+        assert!((cycle.sample(0.4).illuminance - 300.0).abs() < 1e-3);
+        assert!((cycle.sample(0.6).illuminance - 500.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_sample_wraps_from_last_keyframe_to_first() {
+        // 0.6 -> 1.2 spans the end of the cycle; 0.9 is halfway, as is -0.1
+        let cycle = cycle(vec![keyframe(0.2, 100.0), keyframe(0.6, 500.0)]);
+        assert!((cycle.sample(0.9).illuminance - 300.0).abs() < 1e-3);
+        assert!((cycle.sample(0.0).illuminance - 500.0 + 400.0 * 4.0 / 6.0).abs() < 1e-2);
+        assert!((cycle.sample(0.1).illuminance - 500.0 + 400.0 * 5.0 / 6.0).abs() < 1e-2);
+        assert!((cycle.sample(-0.1).illuminance - 300.0).abs() < 1e-3);
+
+        // Continuous across the wrap point
+        let before = cycle.sample(0.9999).illuminance;
+        let after = cycle.sample(0.0).illuminance;
+        assert!((before - after).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_color_lerps_in_linear_space() {
+        let red = LightSample {
+            rotation: Quat::IDENTITY,
+            color: LinearRgba::rgb(1.0, 0.0, 0.0),
+            illuminance: 0.0,
+            sky_tint: Color::srgb(1.0, 1.0, 1.0).to_linear(),
+        };
+        let blue = LightSample {
+            color: LinearRgba::rgb(0.0, 0.0, 1.0),
+            sky_tint: Color::srgb(0.0, 0.0, 0.0).to_linear(),
+            ..red
+        };
+        let mid = red.lerp(&blue, 0.5);
+        assert!((mid.color.red - 0.5).abs() < 1e-6);
+        assert!((mid.color.blue - 0.5).abs() < 1e-6);
+        // Half of white in linear light is brighter than sRGB 0.5 (linear ~0.214)
+        assert!((mid.sky_tint.red - 0.5).abs() < 1e-6);
+        assert!(Color::from(mid.sky_tint).to_srgba().red > 0.7);
+    }
+
+    #[test]
+    fn test_keyframe_sky_tint_is_converted_to_linear() {
+        let mut cycle = cycle(vec![keyframe(0.0, 0.0), keyframe(0.5, 0.0)]);
+        cycle.keyframes[0].sky_tint = [0.5, 0.5, 0.5];
+        let tint = cycle.sample(0.0).sky_tint;
+        assert!((tint.red - 0.214).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_temperature_color_warms_at_low_kelvin() {
+        let warm = temperature_color(2000.0);
+        let daylight = temperature_color(6600.0);
+        let cool = temperature_color(12000.0);
+        assert!(warm.red > warm.blue);
+        assert!((daylight.red - daylight.blue).abs() < 0.05);
+        assert!(cool.blue > cool.red);
+        // Out-of-range temperatures clamp
+        assert_eq!(temperature_color(100.0), temperature_color(1000.0));
+    }
+
+    #[test]
+    fn test_rotation_points_the_light_down_from_above_the_horizon() {
+        let mut noon = LightKeyframe::default_noon();
+        noon.elevation = 90.0;
+        let direction = noon.rotation() * Vec3::NEG_Z;
+        assert!((direction - Vec3::NEG_Y).length() < 1e-4);
+
+        noon.elevation = 30.0;
+        let direction = noon.rotation() * Vec3::NEG_Z;
+        assert!((direction.y + 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_keyframe_count_stays_within_bounds() {
+        let mut cycle = cycle(vec![keyframe(0.0, 100.0), keyframe(0.5, 500.0)]);
+        cycle.remove_keyframe(0);
+        assert_eq!(cycle.keyframes.len(), MIN_KEYFRAMES);
+        while cycle.keyframes.len() < MAX_KEYFRAMES {
+            cycle.add_keyframe();
+        }
+        cycle.add_keyframe();
+        assert_eq!(cycle.keyframes.len(), MAX_KEYFRAMES);
+        assert!(cycle.keyframes.windows(2).all(|pair| pair[0].phase <= pair[1].phase));
+    }
+
+    #[test]
+    fn test_added_keyframe_matches_the_light_it_splits() {
+        let mut cycle = cycle(vec![keyframe(0.0, 100.0), keyframe(0.5, 500.0)]);
+        let before = cycle.sample(0.25).illuminance;
+        cycle.add_keyframe();
+        assert_eq!(cycle.keyframes.len(), 3);
+        assert!((cycle.sample(0.25).illuminance - before).abs() < 1e-3);
+    }
+}
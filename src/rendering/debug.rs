@@ -8,11 +8,22 @@ pub struct DebugRenderingPlugin;
 
 impl Plugin for DebugRenderingPlugin {
     fn build(&self, app: &mut App) {
+        // Gizmos draw the same blended poses as the cell transforms
+        let interpolated = super::interpolation::update_interpolation_alpha;
         app.add_systems(Update, render_orientation_gizmos)
             .add_systems(Update, update_split_plane_gizmos)
             .add_systems(Update, update_split_plane_transforms)
+            .add_systems(Update, render_split_jitter_cones)
             .add_systems(Update, update_anchor_gizmos)
-            .add_systems(Update, update_anchor_transforms);
+            .add_systems(Update, update_anchor_transforms.after(interpolated))
+            .add_systems(Update, render_twist_gizmos.after(interpolated))
+            .add_systems(Update, render_pressure_overlay.after(interpolated))
+            .add_systems(Update, render_orientation_drift_overlay.after(interpolated))
+            .add_systems(Update, render_user_data_overlay.after(interpolated))
+            .add_systems(Update, render_force_heatmap.after(interpolated))
+            .add_systems(Update, render_inheritance_preview.after(interpolated))
+            .add_systems(Update, render_division_lookahead.after(interpolated))
+            .add_systems(Update, render_orbit_trails.after(interpolated));
     }
 }
 
@@ -152,12 +163,8 @@ fn update_split_plane_gizmos(
         }
         let mode = &genome.modes[cell.mode_index];
 
-        // Calculate split direction using the same method as cell division
-        let pitch = mode.parent_split_direction.x.to_radians();
-        let yaw = mode.parent_split_direction.y.to_radians();
-        
-        // Use Euler rotation to match the actual division code
-        let split_direction_local = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z;
+        // Calculate split direction using the same method as cell division (before jitter)
+        let split_direction_local = mode.split_direction();
 
         // Create two rings perpendicular to the split direction IN LOCAL SPACE
         let inner_radius = cell.radius * 1.2;
@@ -227,6 +234,54 @@ fn compute_genome_split_hash(genome: &crate::genome::GenomeData) -> u64 {
     hasher.finish()
 }
 
+/// Outline the cone each jittered mode's split direction is drawn from
+///
+/// A rim on each side of the split plane, with a few lines from the cell center, marks
+/// every direction a division can split along.
+fn render_split_jitter_cones(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    genome_library: Res<GenomeLibrary>,
+    current_genome: Res<CurrentGenome>,
+    cells_query: Query<(&Cell, &CellPosition, &CellOrientation)>,
+) {
+    if !config.show_split_plane_gizmos {
+        return;
+    }
+    // Same genome as the split plane rings
+    let genome = if !genome_library.genomes.is_empty() {
+        &genome_library.genomes[0]
+    } else {
+        &current_genome.genome
+    };
+    if genome.modes.iter().all(|mode| mode.split_direction_jitter_degrees <= 0.0) {
+        return;
+    }
+
+    let color = Color::srgba(1.0, 0.85, 0.2, 0.8);
+    for (cell, position, orientation) in cells_query.iter() {
+        let Some(mode) = genome.modes.get(cell.mode_index) else {
+            continue;
+        };
+        let half_angle = mode.split_direction_jitter_degrees.clamp(0.0, 90.0).to_radians();
+        if half_angle <= 0.0 {
+            continue;
+        }
+        let length = cell.radius * 1.6;
+        let split_direction = orientation.rotation * mode.split_direction();
+        for axis in [split_direction, -split_direction] {
+            let rim_center = position.position + axis * length * half_angle.cos();
+            let rim_radius = length * half_angle.sin();
+            let facing = Quat::from_rotation_arc(Vec3::Z, axis);
+            gizmos.circle(Isometry3d::new(rim_center, facing), rim_radius, color).resolution(24);
+            for step in 0..4 {
+                let around = facing * Quat::from_rotation_z(step as f32 * std::f32::consts::FRAC_PI_2) * Vec3::X;
+                gizmos.line(position.position, rim_center + around * rim_radius, color);
+            }
+        }
+    }
+}
+
 /// Update split plane ring transforms to follow their parent cells
 fn update_split_plane_transforms(
     config: Res<RenderingConfig>,
@@ -528,6 +583,7 @@ fn update_anchor_transforms(
     main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
     preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
     sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
     cells_query: Query<&Visibility, (With<Cell>, Without<AnchorGizmo>)>,
     mut anchor_query: Query<(&AnchorGizmo, &mut Transform, &mut Visibility), Without<Cell>>,
 ) {
@@ -587,16 +643,16 @@ fn update_anchor_transforms(
         // Use the is_side_a flag to determine which anchor direction to use
         if anchor.is_side_a {
             // This is cell A's anchor
-            let pos_a = state.positions[cell_a_idx];
-            let rot_a = state.rotations[cell_a_idx];  // Use physics rotation so anchors rotate with cell
+            let pos_a = interpolation.position(state, cell_a_idx);
+            let rot_a = interpolation.rotation(state, cell_a_idx);  // Use physics rotation so anchors rotate with cell
             let anchor_dir_a = connections.anchor_direction_a[i];
             let cell_radius_a = state.radii[cell_a_idx];
             let world_anchor_a = rot_a * anchor_dir_a;
             transform.translation = pos_a + world_anchor_a * cell_radius_a;
         } else {
             // This is cell B's anchor
-            let pos_b = state.positions[cell_b_idx];
-            let rot_b = state.rotations[cell_b_idx];  // Use physics rotation so anchors rotate with cell
+            let pos_b = interpolation.position(state, cell_b_idx);
+            let rot_b = interpolation.rotation(state, cell_b_idx);  // Use physics rotation so anchors rotate with cell
             let anchor_dir_b = connections.anchor_direction_b[i];
             let cell_radius_b = state.radii[cell_b_idx];
             let world_anchor_b = rot_b * anchor_dir_b;
@@ -604,3 +660,471 @@ fn update_anchor_transforms(
         }
     }
 }
+
+/// Render adhesion twist: each cell's twist reference direction and the measured twist arc
+fn render_twist_gizmos(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+) {
+    if !config.show_twist_gizmos {
+        return;
+    }
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => {
+            // GPU mode not yet implemented
+            return;
+        }
+    };
+    let connections = &state.adhesion_connections;
+
+    const ARC_SEGMENTS: usize = 16;
+
+    for i in 0..connections.active_count {
+        if connections.is_active[i] == 0 {
+            continue;
+        }
+
+        let cell_a_idx = connections.cell_a_index[i];
+        let cell_b_idx = connections.cell_b_index[i];
+        if cell_a_idx >= state.cell_count || cell_b_idx >= state.cell_count {
+            continue;
+        }
+
+        let twist_ref_a = connections.twist_reference_a[i];
+        let twist_ref_b = connections.twist_reference_b[i];
+        if twist_ref_a.length() < 0.001 || twist_ref_b.length() < 0.001 {
+            continue;
+        }
+
+        let pos_a = interpolation.position(state, cell_a_idx);
+        let pos_b = interpolation.position(state, cell_b_idx);
+        let Some(bond_axis) = (pos_b - pos_a).try_normalize() else {
+            continue;
+        };
+        let rot_a = interpolation.rotation(state, cell_a_idx);
+        let rot_b = interpolation.rotation(state, cell_b_idx);
+
+        // Carry a direction perpendicular to the bond through each cell's rotation since
+        // the bond formed; with no twist the two directions coincide
+        let perpendicular = bond_axis.any_orthonormal_vector();
+        let reference_direction = |rot: Quat, twist_ref: Quat| {
+            let direction = rot * twist_ref.normalize().conjugate() * perpendicular;
+            direction.reject_from_normalized(bond_axis).try_normalize().unwrap_or(perpendicular)
+        };
+        let direction_a = reference_direction(rot_a, twist_ref_a);
+        let direction_b = reference_direction(rot_b, twist_ref_b);
+
+        let radius_a = state.radii[cell_a_idx];
+        let radius_b = state.radii[cell_b_idx];
+        gizmos.line(pos_a, pos_a + direction_a * radius_a * 1.2, Color::srgb(1.0, 0.6, 0.0));
+        gizmos.line(pos_b, pos_b + direction_b * radius_b * 1.2, Color::srgb(0.0, 0.8, 1.0));
+
+        // Arc from B's reference direction through the measured twist, colored by magnitude
+        let twist = crate::cell::adhesion_forces::adhesion_twist_angle(rot_a, rot_b, twist_ref_a, twist_ref_b, bond_axis);
+        let midpoint = (pos_a + pos_b) * 0.5;
+        let arc_radius = radius_a.min(radius_b) * 0.6;
+        let t = (twist.abs() / std::f32::consts::FRAC_PI_2).min(1.0);
+        let color = Color::srgb(t, 1.0 - t, 0.2);
+        gizmos.linestrip(
+            (0..=ARC_SEGMENTS).map(|segment| {
+                let angle = twist * segment as f32 / ARC_SEGMENTS as f32;
+                midpoint + Quat::from_axis_angle(bond_axis, angle) * direction_b * arc_radius
+            }),
+            color,
+        );
+        gizmos.line(midpoint, midpoint + direction_b * arc_radius, color);
+        gizmos.line(midpoint, midpoint + Quat::from_axis_angle(bond_axis, twist) * direction_b * arc_radius, color);
+    }
+}
+
+/// Contact pressure shown as fully red in the pressure overlay
+const PRESSURE_OVERLAY_MAX: f32 = 2.0;
+
+/// Outline every cell colored by its contact pressure
+///
+/// Cells whose mode has contact inhibition are scaled to their own limit, so red
+/// means "division is being held back".
+fn render_pressure_overlay(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    current_genome: Res<CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+    regions: Res<super::RegionVisibility>,
+) {
+    if !config.show_pressure_overlay {
+        return;
+    }
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => {
+            // GPU mode not yet implemented
+            return;
+        }
+    };
+
+    for i in 0..state.cell_count {
+        let position = interpolation.position(state, i);
+        if regions.hides(position) {
+            continue;
+        }
+        let limit = current_genome.genome.modes.get(state.mode_indices[i])
+            .map(|mode| mode.max_contact_pressure)
+            .filter(|limit| limit.is_finite() && *limit > 0.0)
+            .unwrap_or(PRESSURE_OVERLAY_MAX);
+        let t = (state.contact_pressures[i] / limit).min(1.0);
+        gizmos.sphere(
+            Isometry3d::new(position, interpolation.rotation(state, i)),
+            state.radii[i] * 1.02,
+            Color::srgb(t, 0.2, 1.0 - t),
+        );
+    }
+}
+
+/// Outline every cell colored by its genome orientation drift, red at the warning threshold
+fn render_orientation_drift_overlay(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    current_genome: Res<CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+    regions: Res<super::RegionVisibility>,
+) {
+    if !config.show_orientation_drift_overlay {
+        return;
+    }
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => return,
+    };
+
+    for i in 0..state.cell_count {
+        let position = interpolation.position(state, i);
+        if regions.hides(position) {
+            continue;
+        }
+        let drift = state.genome_orientation_drift(&current_genome.genome, i);
+        let t = (drift / crate::simulation::orientation_drift::DRIFT_WARNING_DEGREES).min(1.0) as f32;
+        gizmos.sphere(
+            Isometry3d::new(position, interpolation.rotation(state, i)),
+            state.radii[i] * 1.03,
+            Color::srgb(t, 0.2, 1.0 - t),
+        );
+    }
+}
+
+/// Outline every cell colored by a user data channel, mapped from the overlay's min/max
+fn render_user_data_overlay(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+    regions: Res<super::RegionVisibility>,
+) {
+    if !config.show_user_data_overlay {
+        return;
+    }
+    let overlay = config.user_data_overlay;
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => return,
+    };
+    let Some(channel) = state.user_data.get(overlay.channel) else {
+        return;
+    };
+
+    for i in 0..state.cell_count {
+        let position = interpolation.position(state, i);
+        if regions.hides(position) {
+            continue;
+        }
+        let t = overlay.normalize(channel[i]);
+        gizmos.sphere(
+            Isometry3d::new(position, interpolation.rotation(state, i)),
+            state.radii[i] * 1.04,
+            Color::srgb(t, 0.2 + 0.6 * t * (1.0 - t), 1.0 - t),
+        );
+    }
+}
+
+/// Outline every cell colored by its averaged contact forces, one shell per shown channel
+///
+/// Each channel is normalized by its largest average in the scene, which is kept in
+/// `ForceHeatmap::scale` for the legend.
+fn render_force_heatmap(
+    mut gizmos: Gizmos,
+    mut heatmap: ResMut<crate::simulation::ForceHeatmap>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+    regions: Res<super::RegionVisibility>,
+) {
+    use crate::simulation::HeatChannel;
+
+    if !HeatChannel::ALL.iter().any(|&channel| heatmap.shown(channel)) {
+        return;
+    }
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => return,
+    };
+
+    for channel in HeatChannel::ALL {
+        if !heatmap.shown(channel) {
+            continue;
+        }
+        let averages = &state.force_heat.averages[channel.index()];
+        let count = state.cell_count.min(averages.len());
+        let max = averages[..count].iter().copied().fold(0.0f32, f32::max);
+        heatmap.bypass_change_detection().scale[channel.index()] = max;
+
+        let shell = match channel {
+            HeatChannel::Collision => 1.05,
+            HeatChannel::Adhesion => 1.06,
+        };
+        for i in 0..state.cell_count {
+            let position = interpolation.position(state, i);
+            if regions.hides(position) {
+                continue;
+            }
+            let t = if max > 0.0 { state.force_heat.average(channel, i) / max } else { 0.0 };
+            let [r, g, b] = channel.color(t);
+            gizmos.sphere(
+                Isometry3d::new(position, interpolation.rotation(state, i)),
+                state.radii[i] * shell,
+                Color::srgb(r, g, b),
+            );
+        }
+    }
+}
+
+/// Color of a bond in the inheritance preview
+fn inheritance_outcome_color(outcome: crate::simulation::InheritanceOutcome) -> Color {
+    match outcome {
+        crate::simulation::InheritanceOutcome::ToA => Color::srgb(0.2, 0.4, 1.0),
+        crate::simulation::InheritanceOutcome::ToB => Color::srgb(0.2, 1.0, 0.3),
+        crate::simulation::InheritanceOutcome::Duplicated => Color::srgb(1.0, 0.9, 0.2),
+        crate::simulation::InheritanceOutcome::Dropped => Color::srgb(1.0, 0.2, 0.2),
+    }
+}
+
+/// Color the inspected cell's bonds by where they would go if it divided now
+///
+/// Uses the same dry run as division, against the current genome, so it follows
+/// edits to the split direction, keep adhesion flags and equatorial bond rule live.
+fn render_inheritance_preview(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    inspector: Res<crate::ui::windows::cell_inspector::CellInspectorState>,
+    current_genome: Res<CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+) {
+    if !config.show_split_plane_gizmos {
+        return;
+    }
+    let Some(cell) = &inspector.snapshot else {
+        return;
+    };
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => return,
+    };
+    if cell.index >= state.cell_count {
+        return;
+    }
+
+    let mode_idx = state.mode_indices[cell.index];
+    let position = interpolation.position(state, cell.index);
+    let rotation = interpolation.rotation(state, cell.index);
+    for (bond, outcome) in crate::simulation::predict_bond_inheritance(state, &current_genome.genome, cell.index, mode_idx) {
+        // Start at the anchor so bonds leaving close together stay distinguishable
+        let anchor = position + rotation * bond.parent_anchor_direction * state.radii[cell.index];
+        let neighbor = interpolation.position(state, bond.neighbor_idx);
+        gizmos.line(anchor, neighbor, inheritance_outcome_color(outcome));
+        gizmos.sphere(Isometry3d::from_translation(anchor), state.radii[cell.index] * 0.12, inheritance_outcome_color(outcome));
+    }
+}
+
+/// Ghost structure the selected mode grows into over the lookahead's generations, scaled
+/// down and drawn at the inspected cell in its orientation (at the origin without one)
+fn render_division_lookahead(
+    mut gizmos: Gizmos,
+    preview: Res<crate::simulation::DivisionLookaheadPreview>,
+    inspector: Res<crate::ui::windows::cell_inspector::CellInspectorState>,
+    current_genome: Res<CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+) {
+    if !preview.enabled || preview.lookahead.ghosts.is_empty() {
+        return;
+    }
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.as_ref().map(|main| &main.canonical_state),
+        crate::simulation::SimulationMode::Preview => preview_state.as_ref().map(|preview| &preview.canonical_state),
+        crate::simulation::SimulationMode::Gpu => None,
+    };
+    let (center, rotation) = match (state, &inspector.snapshot) {
+        (Some(state), Some(cell)) if cell.index < state.cell_count => {
+            (interpolation.position(state, cell.index), interpolation.rotation(state, cell.index))
+        }
+        _ => (Vec3::ZERO, Quat::IDENTITY),
+    };
+
+    let generations = preview.lookahead.generations.max(1) as f32;
+    for ghost in &preview.lookahead.ghosts {
+        let color = current_genome.genome.modes.get(ghost.mode).map_or(Vec3::splat(0.7), |mode| mode.color);
+        // Older cells fainter, the newest generation strongest
+        let alpha = 0.35 + 0.5 * ghost.generation as f32 / generations;
+        let position = center + rotation * ghost.position * preview.scale;
+        gizmos.sphere(
+            Isometry3d::from_translation(position),
+            ghost.radius * preview.scale,
+            Color::srgba(color.x, color.y, color.z, alpha),
+        );
+    }
+}
+
+/// Points kept per orbit trail
+const ORBIT_TRAIL_LENGTH: usize = 120;
+/// Distance a cell must move before its trail gets a new point
+const ORBIT_TRAIL_SPACING: f32 = 0.5;
+/// A jump longer than this (scrubbing, a new scene) restarts the trail
+const ORBIT_TRAIL_BREAK: f32 = 10.0;
+
+/// Recent positions of free cells by cell ID
+#[derive(Default)]
+pub struct OrbitTrails {
+    trails: std::collections::HashMap<u32, std::collections::VecDeque<Vec3>>,
+}
+
+impl OrbitTrails {
+    /// Add a cell's current position; returns its trail
+    fn record(&mut self, cell_id: u32, position: Vec3) -> &std::collections::VecDeque<Vec3> {
+        let trail = self.trails.entry(cell_id).or_default();
+        match trail.back().map(|last| last.distance(position)) {
+            Some(step) if step > ORBIT_TRAIL_BREAK => trail.clear(),
+            Some(step) if step < ORBIT_TRAIL_SPACING => return trail,
+            _ => {}
+        }
+        trail.push_back(position);
+        if trail.len() > ORBIT_TRAIL_LENGTH {
+            trail.pop_front();
+        }
+        trail
+    }
+}
+
+/// Faint fading trails behind cells without adhesions, showing their orbits around the attractor
+fn render_orbit_trails(
+    mut gizmos: Gizmos,
+    config: Res<RenderingConfig>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    interpolation: Res<super::interpolation::TickInterpolation>,
+    regions: Res<super::RegionVisibility>,
+    mut trails: Local<OrbitTrails>,
+) {
+    if !config.show_orbit_trails {
+        trails.trails.clear();
+        return;
+    }
+
+    let state = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => match main_state.as_ref() {
+            Some(main) => &main.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Preview => match preview_state.as_ref() {
+            Some(preview) => &preview.canonical_state,
+            None => return,
+        },
+        crate::simulation::SimulationMode::Gpu => return,
+    };
+
+    let mut alive = std::collections::HashSet::new();
+    for i in 0..state.cell_count {
+        let free = state.adhesion_manager.cell_adhesion_indices[i].iter().all(|&slot| slot < 0);
+        if !free {
+            continue;
+        }
+        let cell_id = state.cell_ids[i];
+        alive.insert(cell_id);
+        let position = interpolation.position(state, i);
+        let trail = trails.record(cell_id, position);
+        if regions.hides(position) {
+            continue;
+        }
+        let points = trail.len();
+        for (index, segment) in trail.iter().zip(trail.iter().skip(1)).enumerate() {
+            let alpha = 0.35 * (index + 1) as f32 / points as f32;
+            gizmos.line(*segment.0, *segment.1, Color::srgba(0.7, 0.85, 1.0, alpha));
+        }
+    }
+    trails.trails.retain(|cell_id, _| alive.contains(cell_id));
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_sim::CpuSceneState;
+use crate::simulation::{CanonicalState, SimulationMode, SimulationState};
+
+/// Plugin blending rendered cell poses between the last two physics ticks
+///
+/// Purely cosmetic: the blended poses only reach transforms and gizmos, never the
+/// canonical state.
+pub struct TickInterpolationPlugin;
+
+impl Plugin for TickInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TickInterpolation>()
+            .add_systems(
+                FixedUpdate,
+                record_previous_tick
+                    .before(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(CpuSceneState::Active))
+                    .run_if(|state: Res<SimulationState>| state.mode == SimulationMode::Cpu && !state.paused),
+            )
+            .add_systems(Update, update_interpolation_alpha.before(crate::simulation::cpu_sim::sync_ecs_from_canonical));
+    }
+}
+
+/// Poses of the tick before the current one, and how far the frame is between them
+///
+/// Slots are matched by cell ID, so a slot whose occupant changed (division,
+/// removal) snaps to the current pose instead of blending between unrelated cells.
+#[derive(Resource)]
+pub struct TickInterpolation {
+    prev_positions: Vec<Vec3>,
+    prev_rotations: Vec<Quat>,
+    prev_ids: Vec<u32>,
+    /// 0.0 draws the previous tick, 1.0 the current one
+    pub alpha: f32,
+}
+
+impl Default for TickInterpolation {
+    fn default() -> Self {
+        Self {
+            prev_positions: Vec::new(),
+            prev_rotations: Vec::new(),
+            prev_ids: Vec::new(),
+            alpha: 1.0,
+        }
+    }
+}
+
+impl TickInterpolation {
+    /// Remember the poses of `state` before the next tick changes them
+    pub fn record(&mut self, state: &CanonicalState) {
+        let n = state.cell_count;
+        self.prev_positions.clear();
+        self.prev_positions.extend_from_slice(&state.positions[..n]);
+        self.prev_rotations.clear();
+        self.prev_rotations.extend_from_slice(&state.rotations[..n]);
+        self.prev_ids.clear();
+        self.prev_ids.extend_from_slice(&state.cell_ids[..n]);
+    }
+
+    pub fn clear(&mut self) {
+        self.prev_positions.clear();
+        self.prev_rotations.clear();
+        self.prev_ids.clear();
+        self.alpha = 1.0;
+    }
+
+    /// Whether slot `idx` still holds the cell it held at the previous tick
+    #[inline]
+    fn blends(&self, state: &CanonicalState, idx: usize) -> bool {
+        self.alpha < 1.0 && self.prev_ids.get(idx) == Some(&state.cell_ids[idx])
+    }
+
+    /// Rendered position of cell `idx`
+    #[inline]
+    pub fn position(&self, state: &CanonicalState, idx: usize) -> Vec3 {
+        if self.blends(state, idx) {
+            self.prev_positions[idx].lerp(state.positions[idx], self.alpha)
+        } else {
+            state.positions[idx]
+        }
+    }
+
+    /// Rendered rotation of cell `idx`
+    #[inline]
+    pub fn rotation(&self, state: &CanonicalState, idx: usize) -> Quat {
+        if self.blends(state, idx) {
+            self.prev_rotations[idx].slerp(state.rotations[idx], self.alpha)
+        } else {
+            state.rotations[idx]
+        }
+    }
+}
+
+/// Snapshot the poses at the start of each CPU tick
+fn record_previous_tick(
+    config: Res<super::RenderingConfig>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    mut interpolation: ResMut<TickInterpolation>,
+) {
+    if !config.interpolate_ticks {
+        return;
+    }
+    if let Some(main_state) = main_state {
+        interpolation.record(&main_state.canonical_state);
+    }
+}
+
+/// Take the blend factor from the fixed timestep's leftover time
+///
+/// Only running CPU simulations blend; paused, Preview (scrubbed) and disabled
+/// interpolation draw the current tick as is.
+pub fn update_interpolation_alpha(
+    config: Res<super::RenderingConfig>,
+    sim_state: Res<SimulationState>,
+    fixed_time: Res<Time<Fixed>>,
+    mut interpolation: ResMut<TickInterpolation>,
+) {
+    let blending = config.interpolate_ticks && sim_state.mode == SimulationMode::Cpu && !sim_state.paused;
+    if !blending {
+        if !interpolation.prev_ids.is_empty() || interpolation.alpha != 1.0 {
+            interpolation.clear();
+        }
+        return;
+    }
+    interpolation.alpha = fixed_time.overstep_fraction().clamp(0.0, 1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_cells(positions: &[Vec3]) -> CanonicalState {
+        let mut state = CanonicalState::new(8);
+        for &position in positions {
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 10.0, 10.0, 10.0, Quat::IDENTITY, 0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_blends_between_ticks() {
+        let mut state = state_with_cells(&[Vec3::ZERO]);
+        let mut interpolation = TickInterpolation::default();
+        interpolation.record(&state);
+        state.positions[0] = Vec3::X * 2.0;
+        state.rotations[0] = Quat::from_rotation_z(1.0);
+
+        interpolation.alpha = 0.25;
+        assert!(interpolation.position(&state, 0).abs_diff_eq(Vec3::X * 0.5, 1e-6));
+        assert!(interpolation.rotation(&state, 0).abs_diff_eq(Quat::from_rotation_z(0.25), 1e-5));
+
+        // Interpolation never touches the state
+        assert_eq!(state.positions[0], Vec3::X * 2.0);
+
+        interpolation.alpha = 1.0;
+        assert_eq!(interpolation.position(&state, 0), Vec3::X * 2.0);
+    }
+
+    #[test]
+    fn test_snaps_when_slot_occupant_changed() {
+        let mut state = state_with_cells(&[Vec3::ZERO, Vec3::Y * 5.0]);
+        let mut interpolation = TickInterpolation::default();
+        interpolation.record(&state);
+        interpolation.alpha = 0.5;
+
+        // A division gives slot 0 a new cell and appends another one
+        state.cell_ids[0] = 100;
+        state.positions[0] = Vec3::X;
+        state.add_cell(Vec3::NEG_X, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 10.0, 10.0, 10.0, Quat::IDENTITY, 0);
+        state.positions[1] = Vec3::Y * 7.0;
+
+        assert_eq!(interpolation.position(&state, 0), Vec3::X);
+        assert_eq!(interpolation.position(&state, 2), Vec3::NEG_X);
+        assert!(interpolation.position(&state, 1).abs_diff_eq(Vec3::Y * 6.0, 1e-6));
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use crate::input::measurement::{MeasurementKind, Measurements};
+
+/// Plugin for rendering measurement lines
+pub struct MeasurementRenderPlugin;
+
+impl Plugin for MeasurementRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, render_measurement_gizmos.after(crate::input::measurement::MeasurementSet));
+    }
+}
+
+const MEASUREMENT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const PENDING_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+
+/// Draw measurement lines, angle legs and bounding spheres with gizmos
+/// (the value labels are drawn in screen space by the UI)
+fn render_measurement_gizmos(
+    mut gizmos: Gizmos,
+    measurements: Res<Measurements>,
+) {
+    for measurement in measurements.items.iter().filter(|m| m.visible) {
+        match measurement.kind {
+            MeasurementKind::Distance | MeasurementKind::Angle => {
+                // Distance: a-b, angle: a-vertex-c
+                for segment in measurement.points.windows(2) {
+                    gizmos.line(segment[0], segment[1], MEASUREMENT_COLOR);
+                }
+                for point in &measurement.points {
+                    gizmos.sphere(*point, 0.1, MEASUREMENT_COLOR);
+                }
+            }
+            MeasurementKind::StructureRadius => {
+                if let Some((center, radius)) = measurement.sphere {
+                    gizmos.sphere(center, radius, MEASUREMENT_COLOR);
+                    gizmos.line(center, center + Vec3::Y * radius, MEASUREMENT_COLOR);
+                }
+            }
+        }
+    }
+
+    // Anchors of the measurement being placed
+    for point in &measurements.pending_points {
+        gizmos.sphere(*point, 0.15, PENDING_COLOR);
+    }
+    for segment in measurements.pending_points.windows(2) {
+        gizmos.line(segment[0], segment[1], PENDING_COLOR);
+    }
+}
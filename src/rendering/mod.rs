@@ -3,22 +3,42 @@ use bevy::pbr::wireframe::{Wireframe, WireframePlugin};
 use bevy::post_process::bloom::{Bloom, BloomCompositeMode as BevyBloomCompositeMode};
 
 pub mod cells;
+pub mod cell_patterns;
 pub mod debug;
 pub mod adhesion_lines;
+pub mod measurements;
+pub mod mode_legend;
 pub mod flagellocyte_mesh;
 pub mod volumetric_fog;
 pub mod boundary_crossing;
 pub mod skybox;
+pub mod capabilities;
+pub mod interpolation;
+pub mod colony_surface;
+pub mod colony_fog;
+pub mod sphere_quality;
+pub mod day_night;
+pub mod region_visibility;
 
 /// Marker component for the world sphere entity
 #[derive(Component)]
 pub struct WorldSphere;
 
 pub use cells::CellRenderingPlugin;
+pub use cell_patterns::{CellPatternPlugin, CellPatternTextures};
 pub use debug::DebugRenderingPlugin;
-pub use adhesion_lines::{AdhesionLineRenderPlugin, AdhesionLineSettings, AdhesionLines};
+pub use adhesion_lines::{AdhesionLineRenderPlugin, AdhesionLineSettings, AdhesionLineMode, AdhesionLines};
+pub use measurements::MeasurementRenderPlugin;
+pub use mode_legend::{ModeLegendPlugin, ModeLegend, ModeVisibility, LegendCorner};
 pub use volumetric_fog::{VolumetricFogPlugin, VolumetricFogSettings, SphericalFogVolume, SphericalDensityTexture};
 pub use boundary_crossing::{BoundaryCrossingPlugin, BoundaryCrossingSettings, BoundaryCrossingState};
+pub use interpolation::{TickInterpolationPlugin, TickInterpolation};
+pub use colony_surface::ColonySurfaceOverlayPlugin;
+pub use colony_fog::{ColonyFogPlugin, ColonyFog};
+pub use capabilities::{RenderCapabilities, Support, ForceLowSpec};
+pub use sphere_quality::{SphereQualityPlugin, SphereQuality, CellSphereMesh};
+pub use day_night::{DayNightPlugin, DayNightCycle, LightKeyframe};
+pub use region_visibility::{RegionVisibilityPlugin, RegionVisibility, RegionSettings, ClipPlane};
 pub use skybox::{Skybox, SkyboxConfig, SkyboxConfigured, SkyboxOriginalColor, spawn_skybox, configure_skybox_children, update_skybox_materials};
 
 /// Main rendering plugin
@@ -28,14 +48,27 @@ impl Plugin for RenderingPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(WireframePlugin::default())
             .add_plugins(CellRenderingPlugin)
+            .add_plugins(CellPatternPlugin)
             .add_plugins(DebugRenderingPlugin)
             .add_plugins(AdhesionLineRenderPlugin)
+            .add_plugins(MeasurementRenderPlugin)
+            .add_plugins(ModeLegendPlugin)
             .add_plugins(VolumetricFogPlugin)
+            .add_plugins(ColonyFogPlugin)
             .add_plugins(BoundaryCrossingPlugin)
+            .add_plugins(TickInterpolationPlugin)
+            .add_plugins(ColonySurfaceOverlayPlugin)
+            .add_plugins(SphereQualityPlugin)
+            .add_plugins(DayNightPlugin)
+            .add_plugins(RegionVisibilityPlugin)
             .init_resource::<RenderingConfig>()
             .init_resource::<AdhesionLineSettings>()
             .init_resource::<SkyboxConfig>()
-            .add_systems(Startup, crate::ui::settings::load_bloom_settings_on_startup)
+            .init_resource::<RenderCapabilities>()
+            .add_systems(Startup, (
+                crate::ui::settings::load_bloom_settings_on_startup,
+                capabilities::detect_render_capabilities,
+            ))
             .add_systems(Update, (
                 update_gizmos_for_mode,
                 update_wireframe_mode,
@@ -103,6 +136,23 @@ pub struct RenderingConfig {
     pub show_adhesions: bool,
     pub show_orientation_gizmos: bool,
     pub show_split_plane_gizmos: bool,
+    /// Draw each adhesion's twist reference directions and measured twist angle
+    pub show_twist_gizmos: bool,
+    /// Outline cells colored by contact pressure (blue = free, red = crowded)
+    pub show_pressure_overlay: bool,
+    /// Outline cells colored by how far their genome orientation drifted from their lineage
+    pub show_orientation_drift_overlay: bool,
+    /// Outline cells colored by one of their user data channels
+    pub show_user_data_overlay: bool,
+    pub user_data_overlay: UserDataOverlay,
+    /// Faint trails behind free (non-adhered) cells, for orbits around the central attractor
+    pub show_orbit_trails: bool,
+    /// Blend cell poses between the last two physics ticks (smooth slow motion)
+    pub interpolate_ticks: bool,
+    /// XYZ orientation gizmo in the viewport corner (click an axis to snap the view)
+    pub show_compass: bool,
+    /// Round-number length reference along the bottom of the viewport
+    pub show_scale_bar: bool,
     pub target_fps: f32,
     pub user_has_changed_gizmos: bool,
     // World sphere settings
@@ -117,6 +167,33 @@ pub struct RenderingConfig {
     pub bloom_composite_mode: BloomCompositeMode,
 }
 
+/// Channel and value range of the user data overlay
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UserDataOverlay {
+    pub channel: usize,
+    /// Value drawn blue
+    pub min: f32,
+    /// Value drawn red
+    pub max: f32,
+}
+
+impl Default for UserDataOverlay {
+    fn default() -> Self {
+        Self { channel: 0, min: 0.0, max: 1.0 }
+    }
+}
+
+impl UserDataOverlay {
+    /// Position of `value` in the range, clamped to 0..=1
+    pub fn normalize(&self, value: f32) -> f32 {
+        let span = self.max - self.min;
+        if span.abs() < f32::EPSILON {
+            return if value >= self.max { 1.0 } else { 0.0 };
+        }
+        ((value - self.min) / span).clamp(0.0, 1.0)
+    }
+}
+
 /// Bloom composite mode for UI selection
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub enum BloomCompositeMode {
@@ -132,6 +209,15 @@ impl Default for RenderingConfig {
             show_adhesions: true,
             show_orientation_gizmos: false,
             show_split_plane_gizmos: false,
+            show_twist_gizmos: false,
+            show_pressure_overlay: false,
+            show_orientation_drift_overlay: false,
+            show_user_data_overlay: false,
+            user_data_overlay: UserDataOverlay::default(),
+            show_orbit_trails: false,
+            interpolate_ticks: true,
+            show_compass: true,
+            show_scale_bar: true,
             target_fps: 60.0,
             user_has_changed_gizmos: false,
             world_sphere_opacity: 0.35,
@@ -154,12 +240,17 @@ impl Default for RenderingConfig {
 /// and Cell.radius to Transform.scale
 pub fn sync_transforms(
     mut cells_query: Query<(&crate::cell::CellPosition, &crate::cell::CellOrientation, &crate::cell::Cell, &mut Transform)>,
+    genome: Res<crate::genome::CurrentGenome>,
 ) {
     for (cell_position, cell_orientation, cell, mut transform) in cells_query.iter_mut() {
         transform.translation = cell_position.position;
         transform.rotation = cell_orientation.rotation;
         // OPTIMIZATION: All cells share the same unit sphere mesh, scaled by radius
-        transform.scale = Vec3::splat(cell.radius);
+        // (per axis for ellipsoid modes, oriented by the rotation above)
+        transform.scale = match genome.genome.modes.get(cell.mode_index) {
+            Some(mode) if !mode.is_spherical() => mode.semi_axes(cell.radius),
+            _ => Vec3::splat(cell.radius),
+        };
     }
 }
 
@@ -195,14 +286,16 @@ fn update_world_sphere_material(
 fn update_bloom_settings(
     mut commands: Commands,
     rendering_config: Res<RenderingConfig>,
+    capabilities: Res<RenderCapabilities>,
     cameras_with_bloom: Query<(Entity, &Bloom), With<Camera3d>>,
     cameras_without_bloom: Query<Entity, (With<Camera3d>, Without<Bloom>)>,
 ) {
-    if !rendering_config.is_changed() {
+    if !rendering_config.is_changed() && !capabilities.is_changed() {
         return;
     }
     
-    if rendering_config.bloom_enabled {
+    // The setting is kept so it comes back on hardware that supports bloom
+    if rendering_config.bloom_enabled && capabilities.bloom.is_available() {
         // Create bloom settings
         let bloom = Bloom {
             intensity: rendering_config.bloom_intensity,
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin for the viewport mode legend and its render-only mode filters
+pub struct ModeLegendPlugin;
+
+impl Plugin for ModeLegendPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModeLegend>()
+            .init_resource::<ModeVisibility>()
+            .add_systems(Update, update_mode_legend_counts);
+    }
+}
+
+/// How often the legend's cell counts are refreshed (seconds)
+const COUNT_REFRESH_SECS: f32 = 0.25;
+
+/// Opacity multiplier for cells outside the isolated modes
+pub const ISOLATE_DIM: f32 = 0.15;
+
+/// Viewport corner the legend is anchored to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LegendCorner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl LegendCorner {
+    pub const ALL: [LegendCorner; 4] = [
+        LegendCorner::TopLeft,
+        LegendCorner::TopRight,
+        LegendCorner::BottomLeft,
+        LegendCorner::BottomRight,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LegendCorner::TopLeft => "Top left",
+            LegendCorner::TopRight => "Top right",
+            LegendCorner::BottomLeft => "Bottom left",
+            LegendCorner::BottomRight => "Bottom right",
+        }
+    }
+}
+
+/// Mode legend overlay settings and the counts it lists
+#[derive(Resource)]
+pub struct ModeLegend {
+    pub show: bool,
+    pub corner: LegendCorner,
+    /// Cells per mode in the active simulation (None without an active CPU or preview simulation)
+    pub counts: Option<Vec<usize>>,
+    refresh: Timer,
+}
+
+impl Default for ModeLegend {
+    fn default() -> Self {
+        Self {
+            show: true,
+            corner: LegendCorner::default(),
+            counts: None,
+            refresh: Timer::new(Duration::from_secs_f32(COUNT_REFRESH_SECS), TimerMode::Repeating),
+        }
+    }
+}
+
+/// Render-only mode filters set from the legend (no effect on the simulation)
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct ModeVisibility {
+    /// When not empty, cells of every other mode are dimmed to `ISOLATE_DIM`
+    pub isolated: BTreeSet<usize>,
+    /// Modes whose cells are not drawn
+    pub hidden: BTreeSet<usize>,
+    /// When set, cells with other IDs are dimmed too (an isolated cell group)
+    pub isolated_cells: Option<BTreeSet<u32>>,
+}
+
+impl ModeVisibility {
+    /// Opacity multiplier for cells of `mode` (0.0 = hidden)
+    pub fn multiplier(&self, mode: usize) -> f32 {
+        if self.hidden.contains(&mode) {
+            0.0
+        } else if !self.isolated.is_empty() && !self.isolated.contains(&mode) {
+            ISOLATE_DIM
+        } else {
+            1.0
+        }
+    }
+
+    /// Opacity multiplier for the cell `cell_id` of `mode`, including the isolated group
+    pub fn cell_multiplier(&self, mode: usize, cell_id: u32) -> f32 {
+        let multiplier = self.multiplier(mode);
+        match &self.isolated_cells {
+            Some(cells) if multiplier > 0.0 && !cells.contains(&cell_id) => multiplier.min(ISOLATE_DIM),
+            _ => multiplier,
+        }
+    }
+
+    /// Whether any legend filter is set (the isolated group is managed by the Cell Groups panel)
+    pub fn is_filtering(&self) -> bool {
+        !self.isolated.is_empty() || !self.hidden.is_empty()
+    }
+
+    pub fn toggle_isolated(&mut self, mode: usize) {
+        if !self.isolated.remove(&mode) {
+            self.isolated.insert(mode);
+        }
+    }
+
+    pub fn toggle_hidden(&mut self, mode: usize) {
+        if !self.hidden.remove(&mode) {
+            self.hidden.insert(mode);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.isolated.clear();
+        self.hidden.clear();
+    }
+}
+
+fn update_mode_legend_counts(
+    time: Res<Time>,
+    mut legend: ResMut<ModeLegend>,
+    sim_state: Res<SimulationState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if !legend.show {
+        return;
+    }
+    // Counting is O(cells), so only a few times per second
+    if !legend.refresh.tick(time.delta()).just_finished() && legend.counts.is_some() {
+        return;
+    }
+    let mode_count = genome.genome.modes.len();
+    legend.counts = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| s.canonical_state.cells_per_mode(mode_count)),
+        SimulationMode::Preview => preview_state.map(|s| s.canonical_state.cells_per_mode(mode_count)),
+        SimulationMode::Gpu => None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_overrides_isolation() {
+        let mut visibility = ModeVisibility::default();
+        assert!(!visibility.is_filtering());
+        assert_eq!(visibility.multiplier(3), 1.0);
+
+        visibility.toggle_isolated(1);
+        assert_eq!(visibility.multiplier(1), 1.0);
+        assert_eq!(visibility.multiplier(2), ISOLATE_DIM);
+
+        visibility.toggle_hidden(1);
+        assert_eq!(visibility.multiplier(1), 0.0);
+
+        visibility.toggle_isolated(1);
+        visibility.toggle_hidden(1);
+        assert!(!visibility.is_filtering());
+
+        visibility.toggle_hidden(4);
+        visibility.clear();
+        assert_eq!(visibility.multiplier(4), 1.0);
+
+        // An isolated group dims the cells outside it, never hidden ones back in
+        visibility.isolated_cells = Some([7].into());
+        assert_eq!(visibility.cell_multiplier(0, 7), 1.0);
+        assert_eq!(visibility.cell_multiplier(0, 8), ISOLATE_DIM);
+        visibility.toggle_hidden(0);
+        assert_eq!(visibility.cell_multiplier(0, 7), 0.0);
+        assert!(visibility.is_filtering());
+    }
+}
@@ -0,0 +1,419 @@
+use bevy::camera::visibility::VisibilitySystems;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use crate::cell::{Cell, CellPosition};
+use crate::simulation::{DeterministicSpatialGrid, SimulationMode, SimulationState};
+
+/// Plugin for the render-only region and clip plane filters (the simulation is unaffected)
+pub struct RegionVisibilityPlugin;
+
+impl Plugin for RegionVisibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RegionVisibility>()
+            .add_systems(Update, (sync_region_grid, show_only_selected_regions, draw_clip_plane).chain())
+            // After everything that sets cell visibility in Update, before it propagates
+            .add_systems(PostUpdate, apply_region_visibility.before(VisibilitySystems::VisibilityPropagate));
+    }
+}
+
+/// Most regions along each axis
+pub const MAX_REGION_DIVISIONS: u32 = 8;
+
+/// Plane hiding every cell on the side its normal points to
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClipPlane {
+    pub enabled: bool,
+    /// Normal direction around the Y axis (degrees)
+    pub yaw: f32,
+    /// Normal elevation above the XZ plane (degrees)
+    pub pitch: f32,
+    /// Distance of the plane from the center along its normal
+    pub offset: f32,
+}
+
+impl Default for ClipPlane {
+    fn default() -> Self {
+        Self { enabled: false, yaw: 0.0, pitch: 0.0, offset: 0.0 }
+    }
+}
+
+impl ClipPlane {
+    pub fn normal(&self) -> Vec3 {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        Vec3::new(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos())
+    }
+
+    pub fn hides(&self, position: Vec3) -> bool {
+        self.enabled && position.dot(self.normal()) > self.offset
+    }
+
+    /// Hide the other side instead
+    pub fn flip(&mut self) {
+        self.yaw = if self.yaw > 0.0 { self.yaw - 180.0 } else { self.yaw + 180.0 };
+        self.pitch = -self.pitch;
+        self.offset = -self.offset;
+    }
+}
+
+/// Region filter as saved with a scene
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegionSettings {
+    pub divisions: u32,
+    /// Hidden regions by index (see `RegionVisibility::index`)
+    #[serde(default)]
+    pub hidden: BTreeSet<u32>,
+    #[serde(default)]
+    pub clip: ClipPlane,
+}
+
+/// Spatial grid layout the regions are merged from
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct GridFrame {
+    dimension: u32,
+    world_size: f32,
+    cell_size: f32,
+}
+
+impl Default for GridFrame {
+    fn default() -> Self {
+        // Layout of the 64-cell default grid (see CanonicalState::new)
+        Self { dimension: 64, world_size: 200.0, cell_size: 200.0 / 64.0 }
+    }
+}
+
+impl From<&DeterministicSpatialGrid> for GridFrame {
+    fn from(grid: &DeterministicSpatialGrid) -> Self {
+        Self { dimension: grid.grid_dimensions.x, world_size: grid.world_size, cell_size: grid.cell_size }
+    }
+}
+
+/// Render-only region and clip plane filters, set from the Regions menu
+///
+/// The world cube is split into `divisions`³ regions, each a block of spatial grid
+/// cells, so region borders follow the grid the physics uses.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct RegionVisibility {
+    pub divisions: u32,
+    pub hidden: BTreeSet<u32>,
+    pub clip: ClipPlane,
+    /// Hide every region without a selected cell
+    pub show_selection_requested: bool,
+    grid: GridFrame,
+}
+
+impl Default for RegionVisibility {
+    fn default() -> Self {
+        Self {
+            divisions: 4,
+            hidden: BTreeSet::new(),
+            clip: ClipPlane::default(),
+            show_selection_requested: false,
+            grid: GridFrame::default(),
+        }
+    }
+}
+
+impl RegionVisibility {
+    /// Whether anything is hidden (nothing is filtered otherwise)
+    pub fn is_filtering(&self) -> bool {
+        !self.hidden.is_empty() || self.clip.enabled
+    }
+
+    pub fn region_count(&self) -> u32 {
+        self.divisions.pow(3)
+    }
+
+    pub fn index(&self, region: UVec3) -> u32 {
+        region.x + region.y * self.divisions + region.z * self.divisions * self.divisions
+    }
+
+    pub fn region(&self, index: u32) -> UVec3 {
+        let d = self.divisions;
+        UVec3::new(index % d, (index / d) % d, index / (d * d))
+    }
+
+    /// Region of `position`: its spatial grid cell, merged `dimension / divisions` per axis
+    pub fn region_of(&self, position: Vec3) -> UVec3 {
+        let grid = self.grid;
+        let cell = DeterministicSpatialGrid::coord_of(position, grid.world_size, grid.cell_size, grid.dimension).as_uvec3();
+        cell * self.divisions / grid.dimension
+    }
+
+    /// Center of a region in world space
+    pub fn region_center(&self, region: UVec3) -> Vec3 {
+        let size = self.grid.world_size / self.divisions as f32;
+        (region.as_vec3() + 0.5) * size - Vec3::splat(self.grid.world_size / 2.0)
+    }
+
+    /// Whether any part of the region is within `radius` of the center
+    pub fn region_in_sphere(&self, region: UVec3, radius: f32) -> bool {
+        let half = self.grid.world_size / self.divisions as f32 / 2.0;
+        let center = self.region_center(region);
+        let nearest = center - center.clamp(Vec3::splat(-half), Vec3::splat(half));
+        nearest.length() <= radius
+    }
+
+    pub fn is_hidden(&self, region: UVec3) -> bool {
+        self.hidden.contains(&self.index(region))
+    }
+
+    pub fn toggle(&mut self, region: UVec3) {
+        let index = self.index(region);
+        if !self.hidden.remove(&index) {
+            self.hidden.insert(index);
+        }
+    }
+
+    /// Change the region count per axis (hidden regions no longer fit, so all are shown)
+    pub fn set_divisions(&mut self, divisions: u32) {
+        let divisions = divisions.clamp(1, MAX_REGION_DIVISIONS);
+        if divisions != self.divisions {
+            self.divisions = divisions;
+            self.hidden.clear();
+        }
+    }
+
+    pub fn invert(&mut self) {
+        self.hidden = (0..self.region_count()).filter(|index| !self.hidden.contains(index)).collect();
+    }
+
+    /// Hide every region without one of `positions`; false (nothing changed) without positions
+    pub fn show_only(&mut self, positions: impl IntoIterator<Item = Vec3>) -> bool {
+        let shown: BTreeSet<u32> = positions.into_iter().map(|p| self.index(self.region_of(p))).collect();
+        if shown.is_empty() {
+            return false;
+        }
+        self.hidden = (0..self.region_count()).filter(|index| !shown.contains(index)).collect();
+        true
+    }
+
+    /// Whether a cell, bond end, trail or label at `position` is hidden
+    pub fn hides(&self, position: Vec3) -> bool {
+        self.clip.hides(position)
+            || (!self.hidden.is_empty() && self.hidden.contains(&self.index(self.region_of(position))))
+    }
+
+    /// Which of `positions` are drawn (None when nothing is filtered, so callers skip the test)
+    pub fn instance_filter(&self, positions: &[Vec3]) -> Option<Vec<bool>> {
+        self.is_filtering().then(|| positions.iter().map(|&position| !self.hides(position)).collect())
+    }
+
+    /// Settings to save with a scene (None when nothing is filtered)
+    pub fn scene_settings(&self) -> Option<RegionSettings> {
+        self.is_filtering().then(|| RegionSettings {
+            divisions: self.divisions,
+            hidden: self.hidden.clone(),
+            clip: self.clip,
+        })
+    }
+
+    /// Take on the filters of a loaded scene (scenes without any show everything)
+    pub fn apply_scene(&mut self, settings: Option<RegionSettings>) {
+        let settings = settings.unwrap_or(RegionSettings {
+            divisions: self.divisions,
+            hidden: BTreeSet::new(),
+            clip: ClipPlane::default(),
+        });
+        self.divisions = settings.divisions.clamp(1, MAX_REGION_DIVISIONS);
+        let count = self.region_count();
+        self.hidden = settings.hidden.into_iter().filter(|&index| index < count).collect();
+        self.clip = settings.clip;
+    }
+}
+
+/// Cell entity hidden by the region filter (restored when its region is shown again)
+#[derive(Component)]
+pub struct RegionHidden;
+
+/// Follow the spatial grid of the active simulation
+fn sync_region_grid(
+    mut regions: ResMut<RegionVisibility>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let grid = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| GridFrame::from(&s.canonical_state.spatial_grid)),
+        SimulationMode::Preview => preview_state.map(|s| GridFrame::from(&s.canonical_state.spatial_grid)),
+        SimulationMode::Gpu => None,
+    };
+    if let Some(grid) = grid.filter(|&grid| grid != regions.grid) {
+        regions.grid = grid;
+    }
+}
+
+/// Show only the regions of the inspected cell and the highlighted group's members
+fn show_only_selected_regions(
+    mut regions: ResMut<RegionVisibility>,
+    inspector: Res<crate::ui::windows::cell_inspector::CellInspectorState>,
+    groups: Res<crate::simulation::CellGroups>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if !std::mem::take(&mut regions.bypass_change_detection().show_selection_requested) {
+        return;
+    }
+
+    let state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|s| &s.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|s| &s.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+    let mut positions: Vec<Vec3> = inspector.snapshot.iter().map(|cell| cell.position).collect();
+    if let (Some(state), Some(group)) = (state, groups.highlighted.and_then(|id| groups.get(id))) {
+        positions.extend((0..state.cell_count)
+            .filter(|&index| group.members.contains(&state.cell_ids[index]))
+            .map(|index| state.positions[index]));
+    }
+    if !regions.show_only(positions) {
+        notifications.info("Inspect a cell or highlight a cell group to show only its regions");
+    }
+}
+
+/// Outline of the clip plane within the world sphere and an arrow toward the hidden side
+fn draw_clip_plane(mut gizmos: Gizmos, regions: Res<RegionVisibility>, config: Res<crate::simulation::PhysicsConfig>) {
+    let clip = regions.clip;
+    if !clip.enabled || clip.offset.abs() >= config.sphere_radius {
+        return;
+    }
+    let normal = clip.normal();
+    let center = normal * clip.offset;
+    let radius = (config.sphere_radius.powi(2) - clip.offset.powi(2)).sqrt();
+    let color = Color::srgba(1.0, 0.55, 0.2, 0.8);
+    gizmos.circle(Isometry3d::new(center, Quat::from_rotation_arc(Vec3::Z, normal)), radius, color);
+    gizmos.arrow(center, center + normal * radius.min(10.0), color);
+}
+
+/// Hide cells in hidden regions or behind the clip plane, and restore them when shown again
+///
+/// Runs after the mode legend, focal plane and simulation systems have set visibility,
+/// so it only ever hides on top of them.
+fn apply_region_visibility(
+    mut commands: Commands,
+    regions: Res<RegionVisibility>,
+    mode_visibility: Res<super::ModeVisibility>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut cells: Query<(Entity, &CellPosition, &Cell, &mut Visibility, Has<RegionHidden>)>,
+) {
+    let filtering = regions.is_filtering();
+    // Nothing hidden and nothing to restore: no per-cell work
+    if !filtering && !regions.is_changed() {
+        return;
+    }
+
+    // Pooled CPU entities keep their last cell's components but stay hidden
+    let live = |entity: Entity| match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_ref().is_some_and(|s| s.entity_to_index.contains_key(&entity)),
+        SimulationMode::Preview => preview_state.as_ref().is_some_and(|s| s.index_to_entity.contains(&Some(entity))),
+        SimulationMode::Gpu => false,
+    };
+
+    for (entity, position, cell, mut visibility, region_hidden) in cells.iter_mut() {
+        if filtering && regions.hides(position.position) {
+            visibility.set_if_neq(Visibility::Hidden);
+            if !region_hidden {
+                commands.entity(entity).insert(RegionHidden);
+            }
+        } else if region_hidden {
+            commands.entity(entity).remove::<RegionHidden>();
+            if live(entity) {
+                let shown = mode_visibility.multiplier(cell.mode_index) > 0.0;
+                *visibility = if shown { Visibility::Inherited } else { Visibility::Hidden };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_filter_excludes_exactly_hidden_regions() {
+        let mut regions = RegionVisibility::default();
+        let positions: Vec<Vec3> = (0..400)
+            .map(|i| {
+                let t = i as f32 * 0.37;
+                Vec3::new(t.sin() * 90.0, (t * 1.3).cos() * 90.0, (t * 0.7).sin() * 90.0)
+            })
+            .collect();
+        assert!(regions.instance_filter(&positions).is_none());
+
+        for index in [0, 5, 21, 42, 63] {
+            regions.hidden.insert(index);
+        }
+        let visible = regions.instance_filter(&positions).unwrap();
+        // Regions from the spatial grid cells the physics puts the cells in
+        let grid = DeterministicSpatialGrid::new(64, 200.0, 100.0);
+        let mut hidden_count = 0;
+        for (position, visible) in positions.iter().zip(visible) {
+            let region = grid.world_to_grid(*position).as_uvec3() * regions.divisions / 64;
+            let in_hidden = regions.hidden.contains(&regions.index(region));
+            assert_eq!(visible, !in_hidden, "cell at {:?}", position);
+            hidden_count += usize::from(in_hidden);
+        }
+        assert!(hidden_count > 0 && hidden_count < positions.len());
+    }
+
+    #[test]
+    fn test_regions_index_round_trip_and_cover_the_world() {
+        let mut regions = RegionVisibility::default();
+        regions.set_divisions(3);
+        for index in 0..regions.region_count() {
+            assert_eq!(regions.index(regions.region(index)), index);
+        }
+        assert_eq!(regions.region_of(Vec3::splat(-100.0)), UVec3::ZERO);
+        assert_eq!(regions.region_of(Vec3::splat(99.9)), UVec3::splat(2));
+
+        // The corner regions of a fine split lie outside the world sphere
+        regions.set_divisions(8);
+        assert!(!regions.region_in_sphere(UVec3::ZERO, 100.0));
+        assert!(regions.region_in_sphere(UVec3::splat(4), 100.0));
+    }
+
+    #[test]
+    fn test_show_only_and_clip_plane() {
+        let mut regions = RegionVisibility::default();
+        assert!(!regions.show_only([]));
+        assert!(!regions.is_filtering());
+
+        let selected = Vec3::new(30.0, -10.0, 5.0);
+        assert!(regions.show_only([selected]));
+        assert!(!regions.hides(selected));
+        assert!(regions.hides(-selected * 2.0));
+        assert_eq!(regions.hidden.len() as u32, regions.region_count() - 1);
+
+        regions.hidden.clear();
+        regions.clip = ClipPlane { enabled: true, yaw: 90.0, pitch: 0.0, offset: 10.0 };
+        assert!(regions.hides(Vec3::new(11.0, 0.0, 0.0)));
+        assert!(!regions.hides(Vec3::new(9.0, 50.0, 0.0)));
+        regions.clip.flip();
+        assert!(!regions.hides(Vec3::new(11.0, 0.0, 0.0)));
+        assert!(regions.hides(Vec3::new(-11.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_scene_settings_round_trip() {
+        let mut regions = RegionVisibility::default();
+        assert!(regions.scene_settings().is_none());
+        regions.toggle(UVec3::new(1, 2, 3));
+        let saved = regions.scene_settings().unwrap();
+        let json = serde_json::to_string(&saved).unwrap();
+
+        let mut loaded = RegionVisibility::default();
+        loaded.apply_scene(Some(serde_json::from_str(&json).unwrap()));
+        assert!(loaded.is_hidden(UVec3::new(1, 2, 3)));
+
+        // Changing the region count shows everything; a scene without filters clears them
+        loaded.set_divisions(5);
+        assert!(!loaded.is_filtering());
+        loaded.toggle(UVec3::ONE);
+        loaded.apply_scene(None);
+        assert!(!loaded.is_filtering());
+    }
+}
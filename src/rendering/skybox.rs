@@ -174,6 +174,15 @@ fn apply_skybox_adjustments(config: &SkyboxConfig) -> (Color, LinearRgba) {
     (new_base, new_emissive)
 }
 
+/// Skybox adjustments multiplied by a linear tint (the day/night cycle's sky color)
+pub(crate) fn tinted_skybox_colors(config: &SkyboxConfig, tint: LinearRgba) -> (Color, LinearRgba) {
+    let (base, emissive) = apply_skybox_adjustments(config);
+    let base = base.to_linear();
+    let tinted_base = LinearRgba::rgb(base.red * tint.red, base.green * tint.green, base.blue * tint.blue);
+    let tinted_emissive = LinearRgba::rgb(emissive.red * tint.red, emissive.green * tint.green, emissive.blue * tint.blue);
+    (Color::from(tinted_base), tinted_emissive)
+}
+
 /// System to update skybox materials when config changes
 pub fn update_skybox_materials(
     skybox_config: Res<SkyboxConfig>,
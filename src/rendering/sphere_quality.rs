@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use serde::{Deserialize, Serialize};
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin for the runtime cell sphere quality
+pub struct SphereQualityPlugin;
+
+impl Plugin for SphereQualityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SphereQuality>()
+            .init_resource::<CellSphereMesh>()
+            .add_systems(Update, update_cell_sphere_mesh);
+    }
+}
+
+/// Subdivision level of the cell sphere mesh before anything is configured
+pub const DEFAULT_SPHERE_LEVEL: u32 = 2;
+
+/// Highest subdivision level
+pub const MAX_SPHERE_LEVEL: u32 = 4;
+
+/// Auto quality steps down a level above these cell counts (Medium above the first,
+/// Low above the second)
+const AUTO_THRESHOLDS: [usize; 2] = [2_000, 20_000];
+
+/// Auto quality steps back up only below this fraction of a threshold, so a population
+/// hovering around one doesn't regenerate the mesh over and over
+const AUTO_HYSTERESIS: f32 = 0.8;
+
+/// Tessellation of the sphere mesh shared by all cells
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SphereQuality {
+    /// Picked from the cell count
+    #[default]
+    Auto,
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl SphereQuality {
+    pub const ALL: [SphereQuality; 5] = [
+        SphereQuality::Auto,
+        SphereQuality::Low,
+        SphereQuality::Medium,
+        SphereQuality::High,
+        SphereQuality::Ultra,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SphereQuality::Auto => "Auto",
+            SphereQuality::Low => "Low",
+            SphereQuality::Medium => "Medium",
+            SphereQuality::High => "High",
+            SphereQuality::Ultra => "Ultra",
+        }
+    }
+
+    /// Subdivision level of a fixed quality (None for Auto)
+    pub fn level(self) -> Option<u32> {
+        match self {
+            SphereQuality::Auto => None,
+            SphereQuality::Low => Some(1),
+            SphereQuality::Medium => Some(2),
+            SphereQuality::High => Some(3),
+            SphereQuality::Ultra => Some(4),
+        }
+    }
+}
+
+/// Vertices of an icosphere subdivided `level` times (each edge halved per level)
+pub fn sphere_vertex_count(level: u32) -> usize {
+    10 * 4usize.pow(level) + 2
+}
+
+/// Triangles of an icosphere subdivided `level` times
+pub fn sphere_triangle_count(level: u32) -> usize {
+    20 * 4usize.pow(level)
+}
+
+/// Unit sphere mesh at a subdivision level
+///
+/// Bevy's icosphere splits each edge into `subdivisions + 1` segments, so halving the
+/// edges `level` times means `2^level - 1` subdivisions.
+pub fn cell_sphere_mesh(level: u32) -> Mesh {
+    let level = level.clamp(1, MAX_SPHERE_LEVEL);
+    Sphere::new(1.0)
+        .mesh()
+        .ico((1 << level) - 1)
+        .expect("sphere levels stay far below the icosphere vertex limit")
+}
+
+/// Level Auto quality picks for a cell count, given the level it is at now
+pub fn auto_sphere_level(current: u32, cell_count: usize) -> u32 {
+    // High up to the first threshold, then one level less past each threshold
+    let level_for = |count: f32| 3 - AUTO_THRESHOLDS.iter().filter(|&&threshold| count > threshold as f32).count() as u32;
+    let target = level_for(cell_count as f32);
+    if target >= current {
+        // Stepping up needs the count well below the threshold that lowered it
+        level_for(cell_count as f32 / AUTO_HYSTERESIS).max(current)
+    } else {
+        target
+    }
+}
+
+/// The sphere mesh all cells share, and its regeneration in the background
+///
+/// The handle never changes: a regenerated mesh replaces the asset in place, so every
+/// cell switches in the same frame without respawning.
+#[derive(Resource)]
+pub struct CellSphereMesh {
+    pub handle: Handle<Mesh>,
+    /// Level of the mesh behind the handle
+    pub level: u32,
+    /// Level being generated and its task (dropping the task cancels it)
+    pending: Option<(u32, Task<Mesh>)>,
+}
+
+impl FromWorld for CellSphereMesh {
+    fn from_world(world: &mut World) -> Self {
+        let handle = world.resource_mut::<Assets<Mesh>>().add(cell_sphere_mesh(DEFAULT_SPHERE_LEVEL));
+        Self { handle, level: DEFAULT_SPHERE_LEVEL, pending: None }
+    }
+}
+
+impl CellSphereMesh {
+    /// Level being generated, if a switch is in progress
+    pub fn pending_level(&self) -> Option<u32> {
+        self.pending.as_ref().map(|(level, _)| *level)
+    }
+}
+
+/// Pick the wanted level, regenerate the mesh off the main thread and swap it in
+fn update_cell_sphere_mesh(
+    quality: Res<SphereQuality>,
+    capabilities: Res<super::RenderCapabilities>,
+    mut sphere: ResMut<CellSphereMesh>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+) {
+    let sphere = &mut *sphere;
+    if let Some((level, task)) = sphere.pending.as_mut() {
+        if let Some(mesh) = block_on(poll_once(task)) {
+            let level = *level;
+            sphere.pending = None;
+            if let Some(current) = meshes.get_mut(&sphere.handle) {
+                *current = mesh;
+            }
+            sphere.level = level;
+        }
+    }
+
+    let wanted = match quality.level() {
+        Some(level) => level,
+        None => {
+            let cell_count = match sim_state.mode {
+                SimulationMode::Cpu => main_state.as_ref().map(|main| main.canonical_state.cell_count),
+                SimulationMode::Preview => preview_state.as_ref().map(|preview| preview.canonical_state.cell_count),
+                SimulationMode::Gpu => None,
+            };
+            let current = sphere.pending_level().unwrap_or(sphere.level);
+            cell_count.map_or(current, |count| auto_sphere_level(current, count))
+        }
+    }
+    .min(capabilities.max_sphere_level);
+
+    if wanted == sphere.level {
+        // Back to the current mesh before a switch finished
+        sphere.pending = None;
+    } else if sphere.pending_level() != Some(wanted) {
+        sphere.pending = Some((wanted, AsyncComputeTaskPool::get().spawn(async move {
+            cell_sphere_mesh(wanted)
+        })));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_mesh_counts_match_each_level() {
+        for level in 1..=MAX_SPHERE_LEVEL {
+            let mesh = cell_sphere_mesh(level);
+            let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+                panic!("level {} has no positions", level);
+            };
+            assert_eq!(positions.len(), sphere_vertex_count(level), "level {}", level);
+            let index_count = mesh.indices().map_or(0, |indices| indices.len());
+            assert_eq!(index_count, sphere_triangle_count(level) * 3, "level {}", level);
+            assert!(positions.iter().all(|p| (Vec3::from(*p).length() - 1.0).abs() < 1e-4));
+        }
+        assert_eq!(sphere_vertex_count(2), 162);
+        assert_eq!(sphere_triangle_count(4), 5120);
+    }
+
+    #[test]
+    fn test_auto_level_follows_cell_count_with_hysteresis() {
+        assert_eq!(auto_sphere_level(2, 100), 3);
+        assert_eq!(auto_sphere_level(3, 5_000), 2);
+        assert_eq!(auto_sphere_level(2, 25_000), 1);
+
+        // Just below the threshold that lowered the level isn't enough to step back up
+        assert_eq!(auto_sphere_level(1, 19_000), 1);
+        assert_eq!(auto_sphere_level(1, 15_000), 2);
+        assert_eq!(auto_sphere_level(2, 1_900), 2);
+        assert_eq!(auto_sphere_level(2, 1_500), 3);
+
+        // A count oscillating around a threshold switches once
+        let mut level = 2;
+        let mut switches = 0;
+        for count in [19_500, 20_500, 19_500, 20_500, 19_500] {
+            let next = auto_sphere_level(level, count);
+            switches += (next != level) as u32;
+            level = next;
+        }
+        assert_eq!(switches, 1);
+    }
+}
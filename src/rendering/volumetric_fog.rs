@@ -14,7 +14,10 @@ pub struct VolumetricFogPlugin;
 impl Plugin for VolumetricFogPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<VolumetricFogSettings>()
-            .add_systems(Startup, (setup_spherical_density_texture, crate::ui::settings::load_fog_settings_on_startup))
+            .add_systems(Startup, (
+                setup_spherical_density_texture.after(super::capabilities::detect_render_capabilities),
+                crate::ui::settings::load_fog_settings_on_startup,
+            ))
             .add_systems(Update, (update_volumetric_fog_settings, spawn_missing_fog_volumes));
     }
 }
@@ -29,6 +32,12 @@ pub struct VolumetricFogSettings {
     pub scattering: f32,
     pub ambient_intensity: f32,
     pub fog_color: Color,
+    /// Regenerate the density texture from the cells so colonies read as hazier (see `colony_fog`)
+    pub colony_density: bool,
+    /// Density a fully packed region adds, relative to the ambient fog
+    pub colony_intensity: f32,
+    /// Seconds between regenerations of the colony density texture
+    pub colony_interval: f32,
 }
 
 impl Default for VolumetricFogSettings {
@@ -48,6 +57,9 @@ impl VolumetricFogSettings {
             scattering: 0.3,
             ambient_intensity: 0.02,
             fog_color: Color::srgb(0.3, 0.4, 0.5),
+            colony_density: false,
+            colony_intensity: super::colony_fog::DEFAULT_COLONY_FOG_INTENSITY,
+            colony_interval: super::colony_fog::DEFAULT_COLONY_FOG_INTERVAL,
         }
     }
 }
@@ -60,9 +72,15 @@ pub struct SphericalDensityTexture(pub Handle<Image>);
 fn setup_spherical_density_texture(
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
+    capabilities: Res<super::RenderCapabilities>,
 ) {
+    // Without the texture no fog volume is spawned
+    if !capabilities.volumetric_fog.is_available() {
+        return;
+    }
+
     // Create a 3D texture with uniform spherical density (no falloff)
-    const SIZE: u32 = 256; // Resolution of the 3D texture
+    const SIZE: u32 = super::capabilities::FOG_DENSITY_TEXTURE_SIZE;
     let mut data = Vec::with_capacity((SIZE * SIZE * SIZE) as usize);
     
     let center = SIZE as f32 / 2.0;
@@ -110,28 +128,33 @@ fn setup_spherical_density_texture(
 }
 
 /// System to update volumetric fog settings on cameras and fog volumes
-fn update_volumetric_fog_settings(
+pub fn update_volumetric_fog_settings(
     settings: Res<VolumetricFogSettings>,
+    capabilities: Res<super::RenderCapabilities>,
     mut commands: Commands,
     cameras_with_fog: Query<(Entity, &BevyVolumetricFog), With<Camera3d>>,
     cameras_without_fog: Query<Entity, (With<Camera3d>, Without<BevyVolumetricFog>)>,
     mut fog_volume_components: Query<&mut FogVolume, With<SphericalFogVolume>>,
+    colony_fog: Res<super::ColonyFog>,
     mut last_enabled: Local<Option<bool>>,
 ) {
+    // The setting is kept so fog comes back on hardware that supports it
+    let enabled = settings.enabled && capabilities.volumetric_fog.is_available();
+
     // Check if this is the first run or if enabled state changed
     let is_first_run = last_enabled.is_none();
-    let enabled_changed = is_first_run || last_enabled.unwrap() != settings.enabled;
+    let enabled_changed = is_first_run || last_enabled.unwrap() != enabled;
     
     // Only update if settings changed OR it's the first run
-    if !settings.is_changed() && !is_first_run {
+    if !settings.is_changed() && !capabilities.is_changed() && !is_first_run {
         return;
     }
     
     if enabled_changed {
-        *last_enabled = Some(settings.enabled);
+        *last_enabled = Some(enabled);
         
         // WORKAROUND: Add/remove VolumetricFog component from cameras based on enabled state
-        if settings.enabled {
+        if enabled {
             // Add VolumetricFog to cameras that don't have it
             for entity in cameras_without_fog.iter() {
                 commands.entity(entity).insert(BevyVolumetricFog {
@@ -162,9 +185,9 @@ fn update_volumetric_fog_settings(
     // Update fog volume properties (only when settings change, not every frame)
     // Set density to 0 when disabled as additional safeguard
     for mut fog_volume in fog_volume_components.iter_mut() {
-        fog_volume.density_factor = if settings.enabled { settings.density_factor } else { 0.0 };
-        fog_volume.absorption = if settings.enabled { settings.absorption } else { 0.0 };
-        fog_volume.scattering = if settings.enabled { settings.scattering } else { 0.0 };
+        fog_volume.density_factor = if enabled { settings.density_factor * colony_fog.density_scale() } else { 0.0 };
+        fog_volume.absorption = if enabled { settings.absorption } else { 0.0 };
+        fog_volume.scattering = if enabled { settings.scattering } else { 0.0 };
         fog_volume.fog_color = settings.fog_color;
     }
 }
@@ -0,0 +1,412 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Consecutive startups that never reached their first frame before safe mode kicks in
+pub const SAFE_MODE_AFTER_FAILURES: u32 = 3;
+
+/// Startup sentinel, next to the settings files
+const SENTINEL_FILE: &str = "startup_sentinel.json";
+
+/// Startup phase the app is in (a `StartupPhase` as u8)
+static STARTUP_PHASE: AtomicU8 = AtomicU8::new(StartupPhase::Launch as u8);
+
+/// Subsystems whose saved settings this session ignores (bit per `Subsystem`)
+static BYPASSED: AtomicU8 = AtomicU8::new(0);
+
+/// Plugin clearing the startup sentinel once the first frame went through
+pub struct SafeModePlugin;
+
+impl Plugin for SafeModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, || set_phase(StartupPhase::Startup))
+            .add_systems(PostStartup, || set_phase(StartupPhase::FirstFrame))
+            .add_systems(Last, finish_startup);
+    }
+}
+
+/// Where startup is, updated by main's initialization for the panic hook
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum StartupPhase {
+    Launch,
+    /// Building the app's plugins
+    Plugins,
+    /// Creating the window and the GPU device
+    Renderer,
+    /// Startup systems: saved settings and the window layout are loaded
+    Startup,
+    /// Drawing the first frame
+    FirstFrame,
+    /// Past the first frame
+    Running,
+}
+
+impl StartupPhase {
+    const ALL: [StartupPhase; 6] = [
+        StartupPhase::Launch,
+        StartupPhase::Plugins,
+        StartupPhase::Renderer,
+        StartupPhase::Startup,
+        StartupPhase::FirstFrame,
+        StartupPhase::Running,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartupPhase::Launch => "launch",
+            StartupPhase::Plugins => "plugin setup",
+            StartupPhase::Renderer => "window and renderer creation",
+            StartupPhase::Startup => "loading saved settings and layout",
+            StartupPhase::FirstFrame => "the first frame",
+            StartupPhase::Running => "normal running",
+        }
+    }
+
+    /// Subsystems whose settings are the likely cause of a crash in this phase
+    pub fn likely_culprits(&self) -> &'static [Subsystem] {
+        match self {
+            StartupPhase::Launch | StartupPhase::Plugins | StartupPhase::Running => &[],
+            StartupPhase::Renderer => &[Subsystem::Rendering],
+            StartupPhase::Startup => &[Subsystem::UiSettings, Subsystem::Layout],
+            StartupPhase::FirstFrame => &[Subsystem::Layout, Subsystem::Rendering],
+        }
+    }
+}
+
+pub fn set_phase(phase: StartupPhase) {
+    STARTUP_PHASE.store(phase as u8, Ordering::Relaxed);
+}
+
+pub fn current_phase() -> StartupPhase {
+    let phase = STARTUP_PHASE.load(Ordering::Relaxed);
+    StartupPhase::ALL.into_iter().find(|p| *p as u8 == phase).unwrap_or(StartupPhase::Launch)
+}
+
+/// Part of the app whose saved settings safe mode can skip
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Subsystem {
+    /// Saved dock layouts
+    Layout,
+    /// ui_settings.json: theme, rendering, audio, input and the other panels' settings
+    UiSettings,
+    /// Optional rendering features (bloom, volumetric fog, GPU compute)
+    Rendering,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 3] = [Subsystem::Layout, Subsystem::UiSettings, Subsystem::Rendering];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Layout => "Window layout",
+            Subsystem::UiSettings => "Saved settings",
+            Subsystem::Rendering => "Optional rendering features",
+        }
+    }
+
+    /// What bypassing it means, for the safe mode banner
+    pub fn bypassed_description(&self) -> &'static str {
+        match self {
+            Subsystem::Layout => "the default panel layout is shown",
+            Subsystem::UiSettings => "all settings are at their defaults",
+            Subsystem::Rendering => "bloom, volumetric fog and GPU compute are off",
+        }
+    }
+
+    /// Files holding the subsystem's settings (relative to the settings folder)
+    pub fn files(&self) -> &'static [&'static str] {
+        match self {
+            Subsystem::Layout => &["dock_state.ron", "dock_state_preview.ron", "dock_state_cpu.ron"],
+            Subsystem::UiSettings => &["ui_settings.json"],
+            Subsystem::Rendering => &[],
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+/// Whether this session ignores the subsystem's saved settings
+///
+/// Loads fall back to defaults and saves are skipped, so the files stay as they were.
+pub fn is_bypassed(subsystem: Subsystem) -> bool {
+    BYPASSED.load(Ordering::Relaxed) & subsystem.bit() != 0
+}
+
+fn set_bypassed(subsystem: Subsystem, bypassed: bool) {
+    if bypassed {
+        BYPASSED.fetch_or(subsystem.bit(), Ordering::Relaxed);
+    } else {
+        BYPASSED.fetch_and(!subsystem.bit(), Ordering::Relaxed);
+    }
+}
+
+/// Startup bookkeeping kept on disk across launches
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupSentinel {
+    /// Set while starting; still set at the next launch means that startup failed
+    pub starting: bool,
+    /// Startups in a row that never drew their first frame
+    pub failed_startups: u32,
+    /// Phase the last failed startup panicked in
+    pub crashed_phase: Option<StartupPhase>,
+    /// Subsystems to bypass until restored or reset
+    pub bypassed: Vec<Subsystem>,
+}
+
+impl StartupSentinel {
+    /// Sentinel of a new launch, given the one the previous launch left behind
+    ///
+    /// Enough failed startups in a row bypass every subsystem.
+    pub fn launch(previous: Option<Self>) -> Self {
+        let previous = previous.unwrap_or_default();
+        let (failed_startups, crashed_phase) = if previous.starting {
+            (previous.failed_startups + 1, previous.crashed_phase)
+        } else {
+            (0, None)
+        };
+        let bypassed = if failed_startups >= SAFE_MODE_AFTER_FAILURES {
+            Subsystem::ALL.to_vec()
+        } else {
+            previous.bypassed
+        };
+        Self { starting: true, failed_startups, crashed_phase, bypassed }
+    }
+
+    /// The first frame went through
+    pub fn started(self) -> Self {
+        Self { starting: false, failed_startups: 0, crashed_phase: None, bypassed: self.bypassed }
+    }
+
+    /// A panic during startup
+    pub fn crashed(self, phase: StartupPhase) -> Self {
+        if !self.starting {
+            return self;
+        }
+        Self { crashed_phase: Some(phase), ..self }
+    }
+
+    fn read(root: &Path) -> Option<Self> {
+        let path = root.join(SENTINEL_FILE);
+        if !path.exists() {
+            return None;
+        }
+        crate::error::read_json(&path, "startup sentinel")
+            .inspect_err(|e| warn!("{}", e))
+            .ok()
+    }
+
+    fn write(&self, root: &Path) {
+        let path = root.join(SENTINEL_FILE);
+        let result = if self == &Self::default() {
+            // Nothing left to remember
+            match std::fs::remove_file(&path) {
+                Err(source) if source.kind() != std::io::ErrorKind::NotFound => Err(crate::error::BioSpheresError::Io {
+                    operation: crate::error::FileOperation::Delete,
+                    path: path.clone(),
+                    source,
+                }),
+                _ => Ok(()),
+            }
+        } else {
+            crate::error::write_json(&path, self, "startup sentinel")
+        };
+        if let Err(e) = result {
+            warn!("{}", e);
+        }
+    }
+}
+
+/// Safe mode state of the session
+///
+/// Subsystems bypassed at launch stay bypassed for the whole session. Restoring one
+/// takes effect at the next start; resetting one moves its files aside as `.bak`
+/// and lets the session save fresh defaults right away.
+#[derive(Resource, Debug)]
+pub struct SafeMode {
+    root: PathBuf,
+    sentinel: StartupSentinel,
+    /// Failed startups in a row before this launch
+    pub failed_startups: u32,
+    /// Phase the last failed startup panicked in
+    pub crashed_phase: Option<StartupPhase>,
+    /// Subsystems bypassed this session
+    pub session_bypassed: Vec<Subsystem>,
+    pub banner_dismissed: bool,
+}
+
+impl SafeMode {
+    /// Read the previous launch's sentinel in `root`, mark this launch as starting
+    /// and bypass what it says
+    pub fn launch(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let sentinel = StartupSentinel::launch(StartupSentinel::read(&root));
+        sentinel.write(&root);
+        for subsystem in Subsystem::ALL {
+            set_bypassed(subsystem, sentinel.bypassed.contains(&subsystem));
+        }
+        if !sentinel.bypassed.is_empty() {
+            warn!("Safe mode: {} failed startups in a row, bypassing {:?}", sentinel.failed_startups, sentinel.bypassed);
+        }
+        Self {
+            failed_startups: sentinel.failed_startups,
+            crashed_phase: sentinel.crashed_phase,
+            session_bypassed: sentinel.bypassed.clone(),
+            banner_dismissed: false,
+            root,
+            sentinel,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.session_bypassed.is_empty()
+    }
+
+    /// Bypassed subsystems still waiting for a restore or reset
+    pub fn pending(&self) -> &[Subsystem] {
+        &self.sentinel.bypassed
+    }
+
+    /// Load the subsystem's saved settings again from the next start on
+    pub fn restore(&mut self, subsystem: Subsystem) {
+        self.sentinel.bypassed.retain(|s| *s != subsystem);
+        self.sentinel.write(&self.root);
+    }
+
+    /// Move the subsystem's files aside (as `<name>.bak`) and use defaults from now on
+    pub fn reset(&mut self, subsystem: Subsystem) -> crate::error::Result<()> {
+        for file in subsystem.files() {
+            let path = self.root.join(file);
+            if !path.exists() {
+                continue;
+            }
+            let backup = self.root.join(format!("{}.bak", file));
+            std::fs::rename(&path, &backup).map_err(|source| crate::error::BioSpheresError::Io {
+                operation: crate::error::FileOperation::Rename,
+                path: path.clone(),
+                source,
+            })?;
+        }
+        // The session already runs on defaults, so they may be saved over the moved files
+        set_bypassed(subsystem, false);
+        self.session_bypassed.retain(|s| *s != subsystem);
+        self.restore(subsystem);
+        Ok(())
+    }
+
+    fn finish_startup(&mut self) {
+        self.sentinel = std::mem::take(&mut self.sentinel).started();
+        self.sentinel.write(&self.root);
+    }
+}
+
+/// Record the active startup phase in the sentinel (called by the panic hook)
+pub fn record_crash(root: &Path) {
+    if let Some(sentinel) = StartupSentinel::read(root) {
+        sentinel.crashed(current_phase()).write(root);
+    }
+}
+
+/// Clear the sentinel once the first frame was rendered
+///
+/// The render world draws a frame while the main world runs the next update, so the
+/// first frame is on screen by the end of the second update.
+fn finish_startup(safe_mode: Option<ResMut<SafeMode>>, mut updates: Local<u32>) {
+    *updates += 1;
+    if *updates != 2 {
+        return;
+    }
+    set_phase(StartupPhase::Running);
+    if let Some(mut safe_mode) = safe_mode {
+        safe_mode.finish_startup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("biospheres_safe_mode_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_sentinel_counts_startups_that_never_drew_a_frame() {
+        // A clean launch after a clean run
+        let sentinel = StartupSentinel::launch(None);
+        assert!(sentinel.starting);
+        assert_eq!(sentinel.failed_startups, 0);
+        assert!(sentinel.bypassed.is_empty());
+
+        // Two launches crash before their first frame
+        let sentinel = StartupSentinel::launch(Some(sentinel.crashed(StartupPhase::Renderer)));
+        assert_eq!((sentinel.failed_startups, sentinel.crashed_phase), (1, Some(StartupPhase::Renderer)));
+        let sentinel = StartupSentinel::launch(Some(sentinel));
+        assert_eq!(sentinel.failed_startups, 2);
+        assert!(sentinel.bypassed.is_empty());
+
+        // The third one in a row enters safe mode
+        let sentinel = StartupSentinel::launch(Some(sentinel.crashed(StartupPhase::Startup)));
+        assert_eq!(sentinel.failed_startups, SAFE_MODE_AFTER_FAILURES);
+        assert_eq!(sentinel.crashed_phase, Some(StartupPhase::Startup));
+        assert_eq!(sentinel.bypassed, Subsystem::ALL.to_vec());
+
+        // Safe mode starts; the count clears but the bypass stays until restored
+        let sentinel = sentinel.started();
+        assert_eq!(sentinel.failed_startups, 0);
+        assert!(!sentinel.starting);
+        let sentinel = StartupSentinel::launch(Some(sentinel));
+        assert_eq!(sentinel.failed_startups, 0);
+        assert_eq!(sentinel.bypassed, Subsystem::ALL.to_vec());
+
+        // A panic after startup isn't a failed startup
+        let running = sentinel.started();
+        assert_eq!(running.clone().crashed(StartupPhase::Running), running);
+    }
+
+    #[test]
+    fn test_safe_mode_survives_on_disk_and_resets_subsystems() {
+        let root = temp_root("reset");
+        std::fs::write(root.join("ui_settings.json"), "{ corrupt").unwrap();
+        std::fs::write(root.join("dock_state_cpu.ron"), "layout").unwrap();
+        std::fs::write(
+            root.join(SENTINEL_FILE),
+            serde_json::to_string(&StartupSentinel { starting: true, failed_startups: 2, ..Default::default() }).unwrap(),
+        ).unwrap();
+
+        let mut safe_mode = SafeMode::launch(&root);
+        assert!(safe_mode.is_active());
+        assert_eq!(safe_mode.failed_startups, 3);
+        assert!(is_bypassed(Subsystem::UiSettings) && is_bypassed(Subsystem::Layout));
+
+        // Resetting the settings moves the file aside and stops bypassing it at once
+        safe_mode.reset(Subsystem::UiSettings).unwrap();
+        assert!(!root.join("ui_settings.json").exists());
+        assert_eq!(std::fs::read_to_string(root.join("ui_settings.json.bak")).unwrap(), "{ corrupt");
+        assert!(!is_bypassed(Subsystem::UiSettings));
+        assert_eq!(safe_mode.pending(), &[Subsystem::Layout, Subsystem::Rendering]);
+
+        // Restoring the layout keeps its file and bypasses it for the rest of the session
+        safe_mode.restore(Subsystem::Layout);
+        assert!(is_bypassed(Subsystem::Layout));
+        assert_eq!(std::fs::read_to_string(root.join("dock_state_cpu.ron")).unwrap(), "layout");
+        safe_mode.finish_startup();
+
+        // The next start only bypasses what is left
+        let mut safe_mode = SafeMode::launch(&root);
+        assert_eq!(safe_mode.session_bypassed, vec![Subsystem::Rendering]);
+        assert!(!is_bypassed(Subsystem::Layout));
+        safe_mode.reset(Subsystem::Rendering).unwrap();
+        safe_mode.finish_startup();
+        assert!(!root.join(SENTINEL_FILE).exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
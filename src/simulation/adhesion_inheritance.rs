@@ -1,20 +1,173 @@
 use bevy::prelude::*;
 use crate::cell::{AdhesionZone, classify_bond_direction};
 use crate::simulation::cpu_physics::CanonicalState;
-use crate::genome::GenomeData;
+use crate::genome::{EquatorialBondRule, GenomeData, ModeSettings};
 
-/// Handle adhesion inheritance during cell division
-/// 
-/// This function processes all adhesions from the parent cell and determines
-/// which child(ren) should inherit each connection based on zone classification.
-/// 
+/// Where one of the parent's bonds ends up when the parent divides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InheritanceOutcome {
+    ToA,
+    ToB,
+    /// Both children get their own copy of the bond
+    Duplicated,
+    Dropped,
+}
+
+impl InheritanceOutcome {
+    pub fn to_a(self) -> bool {
+        matches!(self, InheritanceOutcome::ToA | InheritanceOutcome::Duplicated)
+    }
+
+    pub fn to_b(self) -> bool {
+        matches!(self, InheritanceOutcome::ToB | InheritanceOutcome::Duplicated)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InheritanceOutcome::ToA => "Goes to A",
+            InheritanceOutcome::ToB => "Goes to B",
+            InheritanceOutcome::Duplicated => "Duplicated",
+            InheritanceOutcome::Dropped => "Dropped",
+        }
+    }
+}
+
+/// Decide which children inherit a bond in `zone`
+///
 /// Zone inheritance rules:
 /// - Zone A: Inherit to child B (adhesions pointing opposite to split direction)
 /// - Zone B: Inherit to child A (adhesions pointing same as split direction)
-/// - Zone C: Inherit to both children (adhesions in equatorial band)
+/// - Zone C: Decided by the mode's equatorial bond rule (both children by default)
+///
+/// A child that doesn't keep adhesions never inherits; a bond neither child can
+/// take is dropped. When neither child keeps adhesions the division leaves the
+/// parent's bonds untouched, so they all stay on child A, which reuses its slot.
+pub fn inheritance_outcome(zone: AdhesionZone, keep_a: bool, keep_b: bool, equatorial: EquatorialBondRule) -> InheritanceOutcome {
+    if !keep_a && !keep_b {
+        return InheritanceOutcome::ToA;
+    }
+    let only = |to_a: bool, to_b: bool| match (to_a, to_b) {
+        (true, true) => InheritanceOutcome::Duplicated,
+        (true, false) => InheritanceOutcome::ToA,
+        (false, true) => InheritanceOutcome::ToB,
+        (false, false) => InheritanceOutcome::Dropped,
+    };
+    match zone {
+        AdhesionZone::ZoneA => only(false, keep_b),
+        AdhesionZone::ZoneB => only(keep_a, false),
+        AdhesionZone::ZoneC => match equatorial {
+            EquatorialBondRule::PreferA if keep_a => InheritanceOutcome::ToA,
+            EquatorialBondRule::PreferB if keep_b => InheritanceOutcome::ToB,
+            EquatorialBondRule::PreferA | EquatorialBondRule::PreferB => only(keep_a, keep_b),
+            EquatorialBondRule::Duplicate => only(keep_a, keep_b),
+            EquatorialBondRule::Drop => InheritanceOutcome::Dropped,
+        },
+    }
+}
+
+/// Split direction of a mode in the parent's local frame (without jitter)
+pub fn split_direction(mode: &ModeSettings) -> Vec3 {
+    mode.split_direction()
+}
+
+/// One of a cell's bonds, seen from that cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParentBond {
+    pub connection_idx: usize,
+    pub neighbor_idx: usize,
+    /// Whether the cell is side A of the connection
+    pub parent_is_a: bool,
+    /// Anchor direction on the cell, in its local frame
+    pub parent_anchor_direction: Vec3,
+    pub neighbor_anchor_direction: Vec3,
+}
+
+/// A cell's active bonds, in adhesion slot order
+pub fn parent_bonds(state: &CanonicalState, cell_idx: usize) -> Vec<ParentBond> {
+    let mut bonds = Vec::new();
+    let Some(slots) = state.adhesion_manager.cell_adhesion_indices.get(cell_idx) else {
+        return bonds;
+    };
+    for &connection_idx in slots.iter() {
+        if connection_idx < 0 {
+            continue;
+        }
+        let connection_idx = connection_idx as usize;
+        if connection_idx >= state.adhesion_connections.active_count {
+            continue;
+        }
+        if state.adhesion_connections.is_active[connection_idx] == 0 {
+            continue;
+        }
+
+        let cell_a_idx = state.adhesion_connections.cell_a_index[connection_idx];
+        let cell_b_idx = state.adhesion_connections.cell_b_index[connection_idx];
+        let (neighbor_idx, parent_is_a) = if cell_a_idx == cell_idx {
+            (cell_b_idx, true)
+        } else if cell_b_idx == cell_idx {
+            (cell_a_idx, false)
+        } else {
+            continue;
+        };
+
+        let (parent_anchor_direction, neighbor_anchor_direction) = if parent_is_a {
+            (
+                state.adhesion_connections.anchor_direction_a[connection_idx],
+                state.adhesion_connections.anchor_direction_b[connection_idx],
+            )
+        } else {
+            (
+                state.adhesion_connections.anchor_direction_b[connection_idx],
+                state.adhesion_connections.anchor_direction_a[connection_idx],
+            )
+        };
+        bonds.push(ParentBond {
+            connection_idx,
+            neighbor_idx,
+            parent_is_a,
+            parent_anchor_direction,
+            neighbor_anchor_direction,
+        });
+    }
+    bonds
+}
+
+/// Predict where each of a cell's bonds would go if it divided now in `mode_idx`
+///
+/// This is a dry run of [`inherit_adhesions_on_division`]: the same classification
+/// without touching the state. Neighbors dividing in the same step are not
+/// considered (their bonds are rebuilt between the children separately), and the
+/// mode's split direction is used without jitter.
+pub fn predict_bond_inheritance(
+    state: &CanonicalState,
+    genome: &GenomeData,
+    cell_idx: usize,
+    mode_idx: usize,
+) -> Vec<(ParentBond, InheritanceOutcome)> {
+    let Some(mode) = genome.modes.get(mode_idx) else {
+        return Vec::new();
+    };
+    let split = split_direction(mode);
+    parent_bonds(state, cell_idx)
+        .into_iter()
+        .map(|bond| {
+            let zone = classify_bond_direction(bond.parent_anchor_direction, split);
+            let outcome = inheritance_outcome(zone, mode.child_a.keep_adhesion, mode.child_b.keep_adhesion, mode.equatorial_bonds);
+            (bond, outcome)
+        })
+        .collect()
+}
+
+/// Handle adhesion inheritance during cell division
+/// 
+/// This function processes all adhesions from the parent cell and determines
+/// which child(ren) should inherit each connection with [`inheritance_outcome`].
+/// The parent's old connections are removed.
 /// 
 /// CRITICAL: parent_genome_orientation must be the parent's orientation BEFORE division,
 /// since child A overwrites the parent's slot and changes the genome orientation.
+/// `split_direction_local` is the direction this division actually split along, in the
+/// parent's local frame (the mode's direction with any jitter applied).
 pub fn inherit_adhesions_on_division(
     state: &mut CanonicalState,
     genome: &GenomeData,
@@ -22,21 +175,31 @@ pub fn inherit_adhesions_on_division(
     child_a_idx: usize,
     child_b_idx: usize,
     parent_genome_orientation: Quat,
+    split_direction_local: Vec3,
 ) {
+    inherit_adhesions(state, genome, parent_mode_idx, child_a_idx, child_b_idx, parent_genome_orientation, split_direction_local, |_| false);
+}
 
-    
+/// Shared body of the inheritance functions; bonds to neighbors for which
+/// `neighbor_divided` is true are dropped
+fn inherit_adhesions(
+    state: &mut CanonicalState,
+    genome: &GenomeData,
+    parent_mode_idx: usize,
+    child_a_idx: usize,
+    child_b_idx: usize,
+    parent_genome_orientation: Quat,
+    split_direction_local: Vec3,
+    neighbor_divided: impl Fn(usize) -> bool,
+) {
     // Get parent mode settings
-    //let parent_mode_idx = state.mode_indices[parent_idx];
     let parent_mode = match genome.modes.get(parent_mode_idx) {
         Some(mode) => mode,
         None => return, // Invalid mode
     };
     
     // Check if children keep adhesions
-    let child_a_keep = parent_mode.child_a.keep_adhesion;
-    let child_b_keep = parent_mode.child_b.keep_adhesion;
-    
-    if !child_a_keep && !child_b_keep {
+    if !parent_mode.child_a.keep_adhesion && !parent_mode.child_b.keep_adhesion {
         return; // No inheritance needed
     }
     
@@ -45,11 +208,6 @@ pub fn inherit_adhesions_on_division(
     // CRITICAL: parent_genome_orientation is passed as parameter, not read from state
     // because child A has already overwritten the parent's slot with its own orientation
     
-    // Calculate split direction from parent mode (in local space)
-    let pitch = parent_mode.parent_split_direction.x.to_radians();
-    let yaw = parent_mode.parent_split_direction.y.to_radians();
-    let split_direction_local = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z;
-    
     // Extract split direction and offset for geometric calculations (matching C++)
     let split_magnitude = split_direction_local.length();
     let split_dir_parent = if split_magnitude < 0.0001 {
@@ -68,13 +226,7 @@ pub fn inherit_adhesions_on_division(
     
     // CRITICAL: Collect parent's adhesion connections BEFORE initializing child indices
     // (since child A reuses parent index, initializing would clear the connections)
-    let mut parent_connections = Vec::new();
-    for slot_idx in 0..crate::cell::MAX_ADHESIONS_PER_CELL {
-        let connection_idx = state.adhesion_manager.cell_adhesion_indices[child_a_idx][slot_idx];
-        if connection_idx >= 0 {
-            parent_connections.push(connection_idx as usize);
-        }
-    }
+    let parent_connections = parent_bonds(state, child_a_idx);
     
     // Initialize adhesion indices for child cells (matches C++ Requirement 10.4)
     // This clears the parent's old adhesion indices
@@ -83,131 +235,53 @@ pub fn inherit_adhesions_on_division(
     state.adhesion_manager.init_cell_adhesion_indices(child_b_idx);
     
     // Process each parent connection (sequential for preview, parallel version available for main sim)
-    for &connection_idx in &parent_connections {
-        if connection_idx >= state.adhesion_connections.active_count {
-            continue;
-        }
-        
-        if state.adhesion_connections.is_active[connection_idx] == 0 {
-            continue;
-        }
-        
-        let cell_a_idx = state.adhesion_connections.cell_a_index[connection_idx];
-        let cell_b_idx = state.adhesion_connections.cell_b_index[connection_idx];
-        
-        let (neighbor_idx, parent_is_a) = if cell_a_idx == child_a_idx {
-            (cell_b_idx, true)
-        } else if cell_b_idx == child_a_idx {
-            (cell_a_idx, false)
-        } else {
-            continue;
-        };
-        
-        let (parent_anchor_direction, neighbor_anchor_direction) = if parent_is_a {
-            (
-                state.adhesion_connections.anchor_direction_a[connection_idx],
-                state.adhesion_connections.anchor_direction_b[connection_idx],
-            )
-        } else {
-            (
-                state.adhesion_connections.anchor_direction_b[connection_idx],
-                state.adhesion_connections.anchor_direction_a[connection_idx],
-            )
-        };
-        
-        let zone = classify_bond_direction(parent_anchor_direction, split_direction_local);
-        
-        match zone {
-            AdhesionZone::ZoneA if child_b_keep => {
-                create_inherited_adhesion(
-                    state,
-                    genome,
-                    child_b_idx,
-                    neighbor_idx,
-                    parent_mode_idx,
-                    parent_is_a,
-                    child_a_idx,
-                    parent_mode,
-                    parent_genome_orientation,
-                    parent_anchor_direction,
-                    neighbor_anchor_direction,
-                    parent_radius,
-                    state.radii[neighbor_idx],
-                    parent_mode.child_b.orientation,
-                    split_offset_magnitude,
-                    split_dir_parent,
-                    false,
-                );
-            }
-            AdhesionZone::ZoneB if child_a_keep => {
+    for bond in &parent_connections {
+        // CRITICAL: Skip inheritance if the neighbor also divided
+        // In that case, child-to-child adhesions will be created separately
+        if !neighbor_divided(bond.neighbor_idx) {
+            let zone = classify_bond_direction(bond.parent_anchor_direction, split_direction_local);
+            let outcome = inheritance_outcome(
+                zone,
+                parent_mode.child_a.keep_adhesion,
+                parent_mode.child_b.keep_adhesion,
+                parent_mode.equatorial_bonds,
+            );
+
+            // Child B first, matching the original creation order for equatorial bonds
+            for (inherits, is_child_a) in [(outcome.to_b(), false), (outcome.to_a(), true)] {
+                if !inherits {
+                    continue;
+                }
+                let (child_idx, child) = if is_child_a {
+                    (child_a_idx, &parent_mode.child_a)
+                } else {
+                    (child_b_idx, &parent_mode.child_b)
+                };
                 create_inherited_adhesion(
                     state,
                     genome,
-                    child_a_idx,
-                    neighbor_idx,
+                    child_idx,
+                    bond.neighbor_idx,
                     parent_mode_idx,
-                    parent_is_a,
+                    bond.parent_is_a,
                     child_a_idx,
                     parent_mode,
                     parent_genome_orientation,
-                    parent_anchor_direction,
-                    neighbor_anchor_direction,
+                    bond.parent_anchor_direction,
+                    bond.neighbor_anchor_direction,
                     parent_radius,
-                    state.radii[neighbor_idx],
-                    parent_mode.child_a.orientation,
+                    state.radii[bond.neighbor_idx],
+                    child.orientation,
                     split_offset_magnitude,
                     split_dir_parent,
-                    true,
+                    is_child_a,
                 );
             }
-            AdhesionZone::ZoneC => {
-                if child_b_keep {
-                    create_inherited_adhesion(
-                        state,
-                        genome,
-                        child_b_idx,
-                        neighbor_idx,
-                        parent_mode_idx,
-                        parent_is_a,
-                        child_a_idx,
-                        parent_mode,
-                        parent_genome_orientation,
-                        parent_anchor_direction,
-                        neighbor_anchor_direction,
-                        parent_radius,
-                        state.radii[neighbor_idx],
-                        parent_mode.child_b.orientation,
-                        split_offset_magnitude,
-                        split_dir_parent,
-                        false,
-                    );
-                }
-                if child_a_keep {
-                    create_inherited_adhesion(
-                        state,
-                        genome,
-                        child_a_idx,
-                        neighbor_idx,
-                        parent_mode_idx,
-                        parent_is_a,
-                        child_a_idx,
-                        parent_mode,
-                        parent_genome_orientation,
-                        parent_anchor_direction,
-                        neighbor_anchor_direction,
-                        parent_radius,
-                        state.radii[neighbor_idx],
-                        parent_mode.child_a.orientation,
-                        split_offset_magnitude,
-                        split_dir_parent,
-                        true,
-                    );
-                }
-            }
-            _ => {}
         }
         
-        state.adhesion_connections.is_active[connection_idx] = 0;
+        // Also frees the neighbor's slot, which would otherwise point at whatever
+        // connection reuses this index next
+        state.adhesion_manager.remove_adhesion(&mut state.adhesion_connections, bond.connection_idx);
     }
 }
 
@@ -350,188 +424,110 @@ pub fn inherit_adhesions_on_division_with_map(
     child_a_idx: usize,
     child_b_idx: usize,
     parent_genome_orientation: Quat,
+    split_direction_local: Vec3,
     division_map: &std::collections::HashMap<usize, (usize, usize)>,
 ) {
-    // Get parent mode settings
-    let parent_mode = match genome.modes.get(parent_mode_idx) {
-        Some(mode) => mode,
-        None => return,
-    };
-    
-    // Check if children keep adhesions
-    let child_a_keep = parent_mode.child_a.keep_adhesion;
-    let child_b_keep = parent_mode.child_b.keep_adhesion;
-    
-    if !child_a_keep && !child_b_keep {
-        return;
-    }
-    
-    // Get parent properties
-    let parent_radius = state.radii[child_a_idx];
-    
-    // Calculate split direction from parent mode (in local space)
-    let pitch = parent_mode.parent_split_direction.x.to_radians();
-    let yaw = parent_mode.parent_split_direction.y.to_radians();
-    let split_direction_local = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z;
-    
-    // Extract split direction and offset for geometric calculations
-    let split_magnitude = split_direction_local.length();
-    let split_dir_parent = if split_magnitude < 0.0001 {
-        Vec3::Z
-    } else {
-        split_direction_local / split_magnitude
-    };
-    let split_offset_magnitude = if split_magnitude < 0.0001 {
-        0.0
-    } else {
-        split_magnitude * 0.5
-    };
-    
-    // Collect parent's adhesion connections BEFORE initializing child indices
-    let mut parent_connections = Vec::new();
-    for slot_idx in 0..crate::cell::MAX_ADHESIONS_PER_CELL {
-        let connection_idx = state.adhesion_manager.cell_adhesion_indices[child_a_idx][slot_idx];
-        if connection_idx >= 0 {
-            parent_connections.push(connection_idx as usize);
-        }
-    }
-    
-    // Initialize adhesion indices for child cells
-    state.adhesion_manager.init_cell_adhesion_indices(child_a_idx);
-    state.adhesion_manager.init_cell_adhesion_indices(child_b_idx);
-    
-    // Process each parent connection
-    for &connection_idx in &parent_connections {
-        if connection_idx >= state.adhesion_connections.active_count {
-            continue;
-        }
-        
-        if state.adhesion_connections.is_active[connection_idx] == 0 {
-            continue;
-        }
-        
-        let cell_a_idx_conn = state.adhesion_connections.cell_a_index[connection_idx];
-        let cell_b_idx_conn = state.adhesion_connections.cell_b_index[connection_idx];
-        
-        let (neighbor_idx, parent_is_a) = if cell_a_idx_conn == child_a_idx {
-            (cell_b_idx_conn, true)
-        } else if cell_b_idx_conn == child_a_idx {
-            (cell_a_idx_conn, false)
-        } else {
-            continue;
+    inherit_adhesions(
+        state,
+        genome,
+        parent_mode_idx,
+        child_a_idx,
+        child_b_idx,
+        parent_genome_orientation,
+        split_direction_local,
+        |neighbor_idx| division_map.contains_key(&neighbor_idx),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    /// Parent in slot 0 bonded to neighbors in every zone, one of them with the
+    /// parent on side B; returns the state and the slot child B will take
+    fn bonded_parent() -> (CanonicalState, usize) {
+        let mut state = CanonicalState::new(16);
+        let add = |state: &mut CanonicalState, position: Vec3| {
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 5.0, 2.0, 10.0, Quat::IDENTITY, 0).unwrap()
         };
-        
-        // CRITICAL: Skip inheritance if the neighbor also divided
-        // In that case, child-to-child adhesions will be created separately
-        if division_map.contains_key(&neighbor_idx) {
-            state.adhesion_connections.is_active[connection_idx] = 0;
-            continue;
+        add(&mut state, Vec3::ZERO);
+        let directions = [
+            Vec3::Z,
+            Vec3::NEG_Z,
+            Vec3::X,
+            Vec3::Y,
+            Vec3::new(1.0, 0.0, 1.0).normalize(),
+            Vec3::new(0.0, 1.0, -1.0).normalize(),
+            // 2° off the split plane, still equatorial
+            Quat::from_rotation_y(2f32.to_radians()) * Vec3::X,
+        ];
+        for (i, direction) in directions.into_iter().enumerate() {
+            let neighbor = add(&mut state, direction * 2.5);
+            let manager = &mut state.adhesion_manager;
+            let connections = &mut state.adhesion_connections;
+            let (a, b, anchor_a, anchor_b) = if i == 2 {
+                (neighbor, 0, -direction, direction)
+            } else {
+                (0, neighbor, direction, -direction)
+            };
+            manager.add_adhesion_with_directions(connections, a, b, 0, anchor_a, anchor_b,
+                Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY).unwrap();
         }
-        
-        let (parent_anchor_direction, neighbor_anchor_direction) = if parent_is_a {
-            (
-                state.adhesion_connections.anchor_direction_a[connection_idx],
-                state.adhesion_connections.anchor_direction_b[connection_idx],
-            )
-        } else {
-            (
-                state.adhesion_connections.anchor_direction_b[connection_idx],
-                state.adhesion_connections.anchor_direction_a[connection_idx],
-            )
-        };
-        
-        let zone = classify_bond_direction(parent_anchor_direction, split_direction_local);
-        
-        match zone {
-            AdhesionZone::ZoneA if child_b_keep => {
-                create_inherited_adhesion(
-                    state,
-                    genome,
-                    child_b_idx,
-                    neighbor_idx,
-                    parent_mode_idx,
-                    parent_is_a,
-                    child_a_idx,
-                    parent_mode,
-                    parent_genome_orientation,
-                    parent_anchor_direction,
-                    neighbor_anchor_direction,
-                    parent_radius,
-                    state.radii[neighbor_idx],
-                    parent_mode.child_b.orientation,
-                    split_offset_magnitude,
-                    split_dir_parent,
-                    false,
-                );
-            }
-            AdhesionZone::ZoneB if child_a_keep => {
-                create_inherited_adhesion(
-                    state,
-                    genome,
-                    child_a_idx,
-                    neighbor_idx,
-                    parent_mode_idx,
-                    parent_is_a,
-                    child_a_idx,
-                    parent_mode,
-                    parent_genome_orientation,
-                    parent_anchor_direction,
-                    neighbor_anchor_direction,
-                    parent_radius,
-                    state.radii[neighbor_idx],
-                    parent_mode.child_a.orientation,
-                    split_offset_magnitude,
-                    split_dir_parent,
-                    true,
-                );
-            }
-            AdhesionZone::ZoneC => {
-                if child_b_keep {
-                    create_inherited_adhesion(
-                        state,
-                        genome,
-                        child_b_idx,
-                        neighbor_idx,
-                        parent_mode_idx,
-                        parent_is_a,
-                        child_a_idx,
-                        parent_mode,
-                        parent_genome_orientation,
-                        parent_anchor_direction,
-                        neighbor_anchor_direction,
-                        parent_radius,
-                        state.radii[neighbor_idx],
-                        parent_mode.child_b.orientation,
-                        split_offset_magnitude,
-                        split_dir_parent,
-                        false,
-                    );
-                }
-                if child_a_keep {
-                    create_inherited_adhesion(
-                        state,
-                        genome,
-                        child_a_idx,
-                        neighbor_idx,
-                        parent_mode_idx,
-                        parent_is_a,
-                        child_a_idx,
-                        parent_mode,
-                        parent_genome_orientation,
-                        parent_anchor_direction,
-                        neighbor_anchor_direction,
-                        parent_radius,
-                        state.radii[neighbor_idx],
-                        parent_mode.child_a.orientation,
-                        split_offset_magnitude,
-                        split_dir_parent,
-                        true,
-                    );
+        let child_b = add(&mut state, Vec3::new(0.0, 0.0, -0.5));
+        (state, child_b)
+    }
+
+    #[test]
+    fn test_dry_run_matches_division() {
+        let mut seen = Vec::new();
+        for rule in EquatorialBondRule::ALL {
+            for (keep_a, keep_b) in [(true, true), (true, false), (false, true), (false, false)] {
+                let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+                mode.child_a.keep_adhesion = keep_a;
+                mode.child_b.keep_adhesion = keep_b;
+                mode.equatorial_bonds = rule;
+                let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+
+                let (mut state, child_b) = bonded_parent();
+                let predicted = predict_bond_inheritance(&state, &genome, 0, 0);
+                assert_eq!(predicted.len(), 7);
+                inherit_adhesions_on_division(&mut state, &genome, 0, 0, child_b, Quat::IDENTITY, split_direction(&genome.modes[0]));
+
+                for (bond, outcome) in predicted {
+                    let manager = &state.adhesion_manager;
+                    let connections = &state.adhesion_connections;
+                    let label = format!("{:?} keep ({}, {}) neighbor {}", rule, keep_a, keep_b, bond.neighbor_idx);
+                    assert_eq!(manager.are_cells_connected(connections, 0, bond.neighbor_idx), outcome.to_a(), "{}", label);
+                    assert_eq!(manager.are_cells_connected(connections, child_b, bond.neighbor_idx), outcome.to_b(), "{}", label);
+                    if !seen.contains(&outcome) {
+                        seen.push(outcome);
+                    }
                 }
             }
-            _ => {}
         }
-        
-        state.adhesion_connections.is_active[connection_idx] = 0;
+        assert_eq!(seen.len(), 4, "scenario should produce every outcome");
+    }
+
+    #[test]
+    fn test_equatorial_rules() {
+        use InheritanceOutcome::*;
+        let zone = AdhesionZone::ZoneC;
+        // The default keeps today's behavior: both children that keep adhesions
+        assert_eq!(inheritance_outcome(zone, true, true, EquatorialBondRule::default()), Duplicated);
+        assert_eq!(inheritance_outcome(zone, true, true, EquatorialBondRule::PreferA), ToA);
+        assert_eq!(inheritance_outcome(zone, true, true, EquatorialBondRule::PreferB), ToB);
+        assert_eq!(inheritance_outcome(zone, true, true, EquatorialBondRule::Drop), Dropped);
+        // A preferred child that doesn't keep adhesions passes the bond to its sibling
+        assert_eq!(inheritance_outcome(zone, false, true, EquatorialBondRule::PreferA), ToB);
+        assert_eq!(inheritance_outcome(zone, true, false, EquatorialBondRule::PreferB), ToA);
+        // The rule only applies to the equatorial band
+        assert_eq!(inheritance_outcome(AdhesionZone::ZoneA, true, true, EquatorialBondRule::PreferA), ToB);
+        assert_eq!(inheritance_outcome(AdhesionZone::ZoneB, false, true, EquatorialBondRule::Duplicate), Dropped);
+        // Without any child keeping adhesions the parent's bonds stay on child A
+        for zone in [AdhesionZone::ZoneA, AdhesionZone::ZoneB, AdhesionZone::ZoneC] {
+            assert_eq!(inheritance_outcome(zone, false, false, EquatorialBondRule::Drop), ToA);
+        }
     }
 }
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin for the adhesion quality tier counters shown in the Performance Monitor
+pub struct AdhesionQualityPlugin;
+
+impl Plugin for AdhesionQualityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AdhesionQualityStats>()
+            .add_systems(Update, update_adhesion_quality_stats);
+    }
+}
+
+/// Number of active adhesions at each quality tier in the active simulation
+#[derive(Resource, Default)]
+pub struct AdhesionQualityStats {
+    /// (full, cheap) connection counts (refreshed every frame)
+    pub counts: Option<(usize, usize)>,
+}
+
+fn update_adhesion_quality_stats(
+    mut stats: ResMut<AdhesionQualityStats>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    stats.counts = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| s.canonical_state.adhesion_connections.tier_counts()),
+        SimulationMode::Preview => preview_state.map(|s| s.canonical_state.adhesion_connections.tier_counts()),
+        SimulationMode::Gpu => None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::AdhesionTier;
+    use crate::genome::GenomeData;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::{CanonicalState, PhysicsConfig};
+    use bevy::prelude::*;
+
+    const CHAIN_LENGTH: usize = 10;
+
+    /// Ten cells in a row along X at rest length, each bonded to the next
+    fn chain() -> CanonicalState {
+        let mut state = CanonicalState::new(16);
+        for i in 0..CHAIN_LENGTH {
+            add_test_cell(&mut state, Vec3::X * i as f32, Quat::IDENTITY, 1.0, 0.5, 0);
+        }
+        for i in 0..CHAIN_LENGTH - 1 {
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, i, i + 1, 0,
+                Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+            ).expect("adhesion slot");
+        }
+        state
+    }
+
+    fn run(state: &mut CanonicalState, config: &PhysicsConfig, genome: &GenomeData, ticks: std::ops::Range<u32>) {
+        for tick in ticks {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(state, config, genome, time, false);
+        }
+    }
+
+    #[test]
+    fn test_calm_bonds_downgrade_and_wake_on_disturbance() {
+        let (config, genome) = (PhysicsConfig::default(), never_split_genome(&["Chain"]));
+        let mut state = chain();
+        let bonds = CHAIN_LENGTH - 1;
+
+        // Settle, then stay calm for the required ticks
+        let tick = 400;
+        run(&mut state, &config, &genome, 0..tick);
+        assert_eq!(state.adhesion_connections.tier_counts(), (0, bonds));
+
+        // Spinning the middle cell promotes both of its bonds on the same tick
+        state.angular_velocities[4] = Vec3::Y * 2.0;
+        run(&mut state, &config, &genome, tick..tick + 1);
+        let tiers = &state.adhesion_connections.quality_tier;
+        assert_eq!((tiers[3], tiers[4]), (AdhesionTier::Full, AdhesionTier::Full));
+        assert_eq!(state.adhesion_connections.tier_counts(), (2, bonds - 2));
+
+        let strict = PhysicsConfig { adhesion_strict_quality: true, ..config };
+        run(&mut state, &strict, &genome, tick + 1..tick + 2);
+        assert_eq!(state.adhesion_connections.tier_counts(), (bonds, 0));
+    }
+
+    #[test]
+    fn test_tiers_replay_identically_from_a_copy() {
+        let (config, genome) = (PhysicsConfig::default(), never_split_genome(&["Chain"]));
+        let mut state = chain();
+        state.velocities[CHAIN_LENGTH - 1] = Vec3::Z * 3.0;
+
+        // Mid-transition: some bonds are calm, the far end is still swinging
+        run(&mut state, &config, &genome, 0..80);
+        let mut replay = state.grown(state.capacity);
+        assert_eq!(replay.adhesion_connections.calm_ticks, state.adhesion_connections.calm_ticks);
+
+        run(&mut state, &config, &genome, 80..240);
+        run(&mut replay, &config, &genome, 80..240);
+        let n = state.cell_count;
+        assert_eq!(replay.positions[..n], state.positions[..n]);
+        assert_eq!(replay.adhesion_connections.quality_tier, state.adhesion_connections.quality_tier);
+    }
+
+    #[test]
+    fn test_wobbling_chain_matches_strict_quality() {
+        let genome = never_split_genome(&["Chain"]);
+        let tiered = PhysicsConfig::default();
+        let strict = PhysicsConfig { adhesion_strict_quality: true, ..tiered.clone() };
+
+        let outcome = |config: &PhysicsConfig| {
+            let mut state = chain();
+            state.velocities[CHAIN_LENGTH - 1] = Vec3::Z * 3.0;
+            run(&mut state, config, &genome, 0..600);
+            state
+        };
+        let (a, b) = (outcome(&tiered), outcome(&strict));
+
+        assert!(a.adhesion_connections.tier_counts().1 > 0, "no bond ever settled");
+        // Within a tenth of a cell diameter
+        for i in 0..CHAIN_LENGTH {
+            let gap = a.positions[i].distance(b.positions[i]);
+            assert!(gap < 0.1, "cell {} differs by {}", i, gap);
+        }
+    }
+
+    /// Adhesion phase cost with and without tiers on 30K settled bonds
+    /// Run with `cargo test --release -- --ignored --nocapture adhesion_tier_benchmark`
+    #[test]
+    #[ignore = "timing benchmark"]
+    fn adhesion_tier_benchmark() {
+        const SIDE: usize = 32;
+        let settings = vec![crate::cell::AdhesionSettings::default()];
+        let mut state = CanonicalState::new(SIDE * SIDE * SIDE);
+        for z in 0..SIDE {
+            for y in 0..SIDE {
+                for x in 0..SIDE {
+                    add_test_cell(&mut state, Vec3::new(x as f32, y as f32, z as f32), Quat::IDENTITY, 1.0, 0.5, 0);
+                }
+            }
+        }
+        let mut bonds = 0;
+        for i in 0..state.cell_count {
+            for (step, axis) in [(1, Vec3::X), (SIDE, Vec3::Y), (SIDE * SIDE, Vec3::Z)] {
+                let j = i + step;
+                if j < state.cell_count && state.positions[i].distance(state.positions[j]) < 1.01 && bonds < 30_000 {
+                    state.adhesion_manager.add_adhesion_with_directions(
+                        &mut state.adhesion_connections, i, j, 0,
+                        axis, -axis, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+                    ).expect("adhesion slot");
+                    bonds += 1;
+                }
+            }
+        }
+
+        let time = |thresholds: &crate::cell::AdhesionTierThresholds, state: &mut CanonicalState| {
+            let n = state.cell_count;
+            let started = std::time::Instant::now();
+            for _ in 0..100 {
+                crate::cell::compute_adhesion_forces_batched(
+                    &mut state.adhesion_connections,
+                    &state.positions[..n], &state.velocities[..n], &state.rotations[..n],
+                    &state.angular_velocities[..n], &state.masses[..n], &settings, thresholds,
+                    &mut state.forces[..n], &mut state.torques[..n],
+                );
+            }
+            started.elapsed().as_secs_f64()
+        };
+        let tiered = PhysicsConfig::default().adhesion_tiers();
+        time(&tiered, &mut state);
+        assert_eq!(state.adhesion_connections.tier_counts(), (0, bonds));
+
+        let strict = time(&crate::cell::AdhesionTierThresholds::STRICT, &mut state.clone());
+        let cheap = time(&tiered, &mut state);
+        println!("{} bonds: strict {:.1} ms, tiered {:.1} ms ({:.2}x)", bonds, strict * 10.0, cheap * 10.0, strict / cheap);
+        assert!(strict / cheap >= 1.3);
+    }
+}
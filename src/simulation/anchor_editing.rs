@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use crate::cell::{classify_bond_direction, AdhesionTier};
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::organism_surgery::split_direction;
+
+/// Direction a dragged anchor handle snaps to while a modifier key is held
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnchorSnap {
+    /// Straight at the partner cell (the current bond axis)
+    BondAxis,
+    /// The nearest of the cell's six local axis directions
+    LocalAxes,
+    /// The nearer side of the cell's split-plane normal
+    SplitNormal,
+}
+
+impl AnchorSnap {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnchorSnap::BondAxis => "bond axis",
+            AnchorSnap::LocalAxes => "local axis",
+            AnchorSnap::SplitNormal => "split normal",
+        }
+    }
+
+    /// Snap a local anchor direction; `bond_axis` and `split_normal` are in the same local frame
+    pub fn apply(self, anchor: Vec3, bond_axis: Vec3, split_normal: Vec3) -> Vec3 {
+        match self {
+            AnchorSnap::BondAxis => bond_axis,
+            AnchorSnap::LocalAxes => [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z]
+                .into_iter()
+                .max_by(|a, b| a.dot(anchor).total_cmp(&b.dot(anchor)))
+                .unwrap_or(anchor),
+            AnchorSnap::SplitNormal => {
+                if anchor.dot(split_normal) >= 0.0 { split_normal } else { -split_normal }
+            }
+        }
+    }
+}
+
+/// Local anchor direction on a cell's sphere under a cursor ray
+///
+/// Where the ray enters the sphere, or the sphere point closest to the ray when it
+/// passes beside it, so a handle dragged off the cell slides along its silhouette.
+pub fn anchor_under_ray(origin: Vec3, direction: Vec3, center: Vec3, radius: f32, rotation: Quat) -> Vec3 {
+    let along = (center - origin).dot(direction).max(0.0);
+    let offset = origin + direction * along - center;
+    let miss = offset.length_squared();
+    let world = if miss < radius * radius {
+        origin + direction * (along - (radius * radius - miss).sqrt()) - center
+    } else {
+        offset
+    };
+    let world = world.try_normalize().unwrap_or(-direction);
+    (rotation.inverse() * world).normalize()
+}
+
+impl CanonicalState {
+    /// Slot of the active bond between two cells, by ID
+    pub fn bond_between(&self, cell_a: u32, cell_b: u32) -> Option<usize> {
+        let (a, b) = (self.index_of(cell_a)?, self.index_of(cell_b)?);
+        let connections = &self.adhesion_connections;
+        self.adhesion_manager.get_connections_for_cell(connections, a).into_iter().find(|&slot| {
+            let ends = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+            ends == (a, b) || ends == (b, a)
+        })
+    }
+
+    /// Local anchor directions of bond `slot` that point each cell straight at the other
+    pub fn aligned_anchors(&self, slot: usize) -> (Vec3, Vec3) {
+        let connections = &self.adhesion_connections;
+        let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+        let axis = (self.positions[b] - self.positions[a]).try_normalize().unwrap_or(Vec3::X);
+        (self.rotations[a].inverse() * axis, self.rotations[b].inverse() * -axis)
+    }
+
+    /// Normal of cell `index`'s split plane in its local frame
+    pub fn local_split_normal(&self, index: usize, genome: &GenomeData) -> Vec3 {
+        let world = self.genome_orientations[index] * split_direction(genome, self.mode_indices[index]);
+        (self.rotations[index].inverse() * world).try_normalize().unwrap_or(Vec3::Z)
+    }
+
+    /// Point bond `slot`'s anchors along local directions, `anchor_a` for the cell stored
+    /// as its A end; returns false if nothing changed
+    ///
+    /// Zones are reclassified and the bond is evaluated at full quality from the next
+    /// tick, so its orientation springs pull toward the new anchors right away.
+    pub fn set_bond_anchors(&mut self, slot: usize, anchor_a: Vec3, anchor_b: Vec3, genome: &GenomeData) -> bool {
+        let (Some(anchor_a), Some(anchor_b)) = (anchor_a.try_normalize(), anchor_b.try_normalize()) else {
+            return false;
+        };
+        let connections = &mut self.adhesion_connections;
+        if slot >= connections.active_count || connections.is_active[slot] == 0 {
+            return false;
+        }
+        if connections.anchor_direction_a[slot] == anchor_a && connections.anchor_direction_b[slot] == anchor_b {
+            return false;
+        }
+
+        let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+        connections.anchor_direction_a[slot] = anchor_a;
+        connections.anchor_direction_b[slot] = anchor_b;
+        connections.zone_a[slot] = classify_bond_direction(anchor_a, split_direction(genome, self.mode_indices[a])) as u8;
+        connections.zone_b[slot] = classify_bond_direction(anchor_b, split_direction(genome, self.mode_indices[b])) as u8;
+        connections.quality_tier[slot] = AdhesionTier::Full;
+        connections.calm_ticks[slot] = 0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::organism_surgery::SurgeryOp;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::PhysicsConfig;
+
+    /// Two cells one rest length apart along X, bonded end to end
+    fn pair() -> CanonicalState {
+        let mut state = CanonicalState::new(4);
+        for x in [0.0, 1.0] {
+            add_test_cell(&mut state, Vec3::X * x, Quat::IDENTITY, 1.0, 0.5, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+        state
+    }
+
+    /// Angle in degrees between each world anchor and the direction to the partner
+    fn anchor_misalignment(state: &CanonicalState, slot: usize) -> (f32, f32) {
+        let connections = &state.adhesion_connections;
+        let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+        let axis = (state.positions[b] - state.positions[a]).normalize();
+        let angle = |anchor: Vec3, toward: Vec3| anchor.normalize().dot(toward).clamp(-1.0, 1.0).acos().to_degrees();
+        (
+            angle(state.rotations[a] * connections.anchor_direction_a[slot], axis),
+            angle(state.rotations[b] * connections.anchor_direction_b[slot], -axis),
+        )
+    }
+
+    #[test]
+    fn test_pair_settles_with_bond_axis_through_edited_anchors() {
+        let (config, genome) = (PhysicsConfig::default(), never_split_genome(&["Pair"]));
+        let mut state = pair();
+        let slot = state.bond_between(state.cell_ids[1], state.cell_ids[0]).expect("bond");
+
+        // Move the first cell's anchor a quarter turn up; the second keeps pointing back
+        let op = SurgeryOp::SetAnchors {
+            cell_a: state.cell_ids[0],
+            cell_b: state.cell_ids[1],
+            anchor_a: Vec3::Y,
+            anchor_b: -Vec3::X,
+        };
+        assert!(state.apply_surgery(op, &genome));
+        assert!(!state.apply_surgery(op, &genome), "same anchors again change nothing");
+        assert!(anchor_misalignment(&state, slot).0 > 80.0);
+
+        for tick in 0..2000 {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+        }
+        let (misaligned_a, misaligned_b) = anchor_misalignment(&state, slot);
+        assert!(misaligned_a < 10.0, "first anchor {:.1} degrees off the bond axis", misaligned_a);
+        assert!(misaligned_b < 10.0, "second anchor {:.1} degrees off the bond axis", misaligned_b);
+        assert!((state.positions[0].distance(state.positions[1]) - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_anchors_named_by_cell_follow_the_stored_bond_order() {
+        let genome = never_split_genome(&["Pair"]);
+        let mut state = pair();
+        let slot = state.bond_between(state.cell_ids[0], state.cell_ids[1]).expect("bond");
+
+        // Naming the cells the other way round still lands each anchor on its own cell
+        let op = SurgeryOp::SetAnchors {
+            cell_a: state.cell_ids[1],
+            cell_b: state.cell_ids[0],
+            anchor_a: Vec3::Z * 2.0,
+            anchor_b: Vec3::Y,
+        };
+        assert!(state.apply_surgery(op, &genome));
+        assert_eq!(state.adhesion_connections.anchor_direction_a[slot], Vec3::Y);
+        assert_eq!(state.adhesion_connections.anchor_direction_b[slot], Vec3::Z);
+
+        // Auto-align points both straight along the present axis again
+        state.rotations[0] = Quat::from_rotation_z(0.5);
+        let (a, b) = state.aligned_anchors(slot);
+        assert!((state.rotations[0] * a).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(b.abs_diff_eq(-Vec3::X, 1e-6));
+    }
+
+    #[test]
+    fn test_dragged_anchor_stays_on_the_sphere_and_snaps() {
+        let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        // A ray straight down onto the top of the cell hits its local +Y
+        let hit = anchor_under_ray(Vec3::Y * 5.0, -Vec3::Y, Vec3::ZERO, 0.5, rotation);
+        assert!(hit.abs_diff_eq(Vec3::Y, 1e-5));
+        // A ray passing beside the cell slides the anchor to the nearest silhouette point
+        let beside = anchor_under_ray(Vec3::new(2.0, 5.0, 0.0), -Vec3::Y, Vec3::ZERO, 0.5, Quat::IDENTITY);
+        assert!(beside.abs_diff_eq(Vec3::X, 1e-5));
+
+        let anchor = Vec3::new(0.2, 0.9, 0.1).normalize();
+        assert_eq!(AnchorSnap::LocalAxes.apply(anchor, Vec3::X, Vec3::Z), Vec3::Y);
+        assert_eq!(AnchorSnap::BondAxis.apply(anchor, Vec3::X, Vec3::Z), Vec3::X);
+        assert_eq!(AnchorSnap::SplitNormal.apply(-Vec3::Z, Vec3::X, Vec3::Z), -Vec3::Z);
+    }
+}
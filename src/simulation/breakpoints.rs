@@ -0,0 +1,399 @@
+use bevy::prelude::*;
+use crate::genome::GenomeData;
+use crate::simulation::cell_groups::{CellGroup, QueryContext};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::derived_metrics::{CompiledMetric, MetricCounters};
+
+/// Plugin for simulation breakpoints
+pub struct BreakpointPlugin;
+
+impl Plugin for BreakpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Breakpoints>();
+    }
+}
+
+/// Condition checked against the state at the end of every tick
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BreakCondition {
+    /// The cell count goes from below this value to at least it, or back
+    CellCountCrosses(usize),
+    /// The mass of the cell with this ID drops below the value
+    CellMassBelow { cell_id: u32, mass: f32 },
+    /// The linear spring force of any adhesion exceeds the value
+    AdhesionForceAbove(f32),
+    /// The last cell of a mode is gone
+    ModeExtinct(usize),
+    /// Simulated time reaches the value (seconds)
+    TimeReaches(f32),
+    /// The member count of a cell group (by group ID) goes from below this value to at least it, or back
+    GroupCountCrosses { group: u32, count: usize },
+    /// A derived metric (by metric ID) goes from below this value to at least it, or back
+    MetricCrosses { metric: u32, value: f32 },
+}
+
+impl BreakCondition {
+    /// One default of every condition kind, for the "add" menu
+    pub const KINDS: [BreakCondition; 7] = [
+        BreakCondition::CellCountCrosses(100),
+        BreakCondition::CellMassBelow { cell_id: 0, mass: 0.5 },
+        BreakCondition::AdhesionForceAbove(50.0),
+        BreakCondition::ModeExtinct(0),
+        BreakCondition::TimeReaches(10.0),
+        BreakCondition::GroupCountCrosses { group: 0, count: 10 },
+        BreakCondition::MetricCrosses { metric: 0, value: 1.0 },
+    ];
+
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            BreakCondition::CellCountCrosses(_) => "Cell count crosses",
+            BreakCondition::CellMassBelow { .. } => "Cell mass below",
+            BreakCondition::AdhesionForceAbove(_) => "Adhesion force above",
+            BreakCondition::ModeExtinct(_) => "Mode dies out",
+            BreakCondition::TimeReaches(_) => "Time reaches",
+            BreakCondition::GroupCountCrosses { .. } => "Group size crosses",
+            BreakCondition::MetricCrosses { .. } => "Metric crosses",
+        }
+    }
+
+    /// Human-readable condition, naming modes from the genome and groups by ID
+    pub fn describe(&self, genome: &GenomeData) -> String {
+        match *self {
+            BreakCondition::CellCountCrosses(count) => format!("Cell count crosses {}", count),
+            BreakCondition::CellMassBelow { cell_id, mass } => format!("Cell {} mass below {:.2}", cell_id, mass),
+            BreakCondition::AdhesionForceAbove(force) => format!("Adhesion force above {:.1}", force),
+            BreakCondition::ModeExtinct(mode) => {
+                let name = genome.modes.get(mode).map_or("?", |m| m.name.as_str());
+                format!("Mode {} ({}) dies out", mode, name)
+            }
+            BreakCondition::TimeReaches(time) => format!("Time reaches {:.2}s", time),
+            BreakCondition::GroupCountCrosses { group, count } => format!("Group {} size crosses {}", group, count),
+            BreakCondition::MetricCrosses { metric, value } => format!("Metric {} crosses {}", metric, value),
+        }
+    }
+
+    /// Whether the condition holds, with the cell it points at
+    ///
+    /// `None` means it doesn't hold; `Some(cell)` that it does, with `cell`
+    /// the cell to select (if the condition is about one).
+    pub fn evaluate(&self, state: &CanonicalState, genome: &GenomeData, groups: &BreakpointGroups, time: f32) -> Option<Option<u32>> {
+        let n = state.cell_count;
+        match *self {
+            BreakCondition::CellCountCrosses(count) => (n >= count).then_some(None),
+            BreakCondition::CellMassBelow { cell_id, mass } => state.cell_ids[..n].iter()
+                .position(|&id| id == cell_id)
+                .filter(|&i| state.masses[i] < mass)
+                .map(|_| Some(cell_id)),
+            BreakCondition::AdhesionForceAbove(force) => {
+                let connections = &state.adhesion_connections;
+                (0..connections.is_active.len())
+                    .filter(|&i| connections.is_active[i] != 0)
+                    .find(|&i| {
+                        let Some(mode) = genome.modes.get(connections.mode_index[i]) else {
+                            return false;
+                        };
+                        let settings = &mode.adhesion_settings;
+                        let (a, b) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+                        let extension = state.positions[a].distance(state.positions[b]) - settings.rest_length;
+                        settings.linear_spring_stiffness * extension.abs() > force
+                    })
+                    .map(|i| Some(state.cell_ids[connections.cell_a_index[i]]))
+            }
+            BreakCondition::ModeExtinct(mode) => (!state.mode_indices[..n].contains(&mode)).then_some(None),
+            BreakCondition::TimeReaches(reached) => (time >= reached).then_some(None),
+            BreakCondition::GroupCountCrosses { group, count } => groups.member_count(group, state, time)
+                .filter(|&members| members >= count)
+                .map(|_| None),
+            // NaN never holds
+            BreakCondition::MetricCrosses { metric, value } => groups.metric_value(metric, state, time)
+                .filter(|&current| current >= value)
+                .map(|_| None),
+        }
+    }
+
+    /// Whether the change from `before` to `after` (see `evaluate`) fires the breakpoint
+    fn fires(&self, before: bool, after: bool) -> bool {
+        match self {
+            // Crossing works both ways
+            BreakCondition::CellCountCrosses(_)
+            | BreakCondition::GroupCountCrosses { .. }
+            | BreakCondition::MetricCrosses { .. } => before != after,
+            _ => !before && after,
+        }
+    }
+}
+
+/// Copy of the cell groups and derived metrics that group and metric conditions refer to,
+/// kept in step by `cell_groups` and `derived_metrics`
+#[derive(Clone, Debug, Default)]
+pub struct BreakpointGroups {
+    pub groups: Vec<CellGroup>,
+    pub sphere_radius: f32,
+    pub metrics: Vec<CompiledMetric>,
+    /// Modes of the genome the metrics were compiled against
+    pub mode_count: usize,
+}
+
+impl BreakpointGroups {
+    /// Cells of group `id` in `state`, or None if there is no such group
+    fn member_count(&self, id: u32, state: &CanonicalState, time: f32) -> Option<usize> {
+        let group = self.groups.iter().find(|group| group.id == id)?;
+        let context = QueryContext { time, sphere_radius: self.sphere_radius };
+        Some(group.resolve(state, &context).len())
+    }
+
+    /// Value of metric `id` in `state`, or None if there is no such metric
+    fn metric_value(&self, id: u32, state: &CanonicalState, time: f32) -> Option<f32> {
+        let metric = self.metrics.iter().find(|metric| metric.id == id)?;
+        Some(metric.evaluate(&MetricCounters::capture(state, self.mode_count, time)))
+    }
+}
+
+/// A condition with its enable and repeat flags
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    pub condition: BreakCondition,
+    pub enabled: bool,
+    /// Keep the breakpoint enabled after it fires
+    pub repeating: bool,
+    pub hits: u32,
+}
+
+impl Breakpoint {
+    pub fn new(condition: BreakCondition) -> Self {
+        Self { condition, enabled: true, repeating: false, hits: 0 }
+    }
+}
+
+/// A breakpoint firing at a tick
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakpointHit {
+    /// Index into `Breakpoints::list`
+    pub breakpoint: usize,
+    /// Tick after which the condition was met
+    pub tick: u32,
+    pub condition: BreakCondition,
+    /// Cell to select and focus
+    pub cell_id: Option<u32>,
+}
+
+/// Simulation breakpoints: the simulation pauses at the end of the tick one fires
+///
+/// Conditions are evaluated against the canonical state only, so they fire at the
+/// same ticks in every run and every Preview replay of the same history.
+#[derive(Resource, Default)]
+pub struct Breakpoints {
+    pub list: Vec<Breakpoint>,
+    /// Hits not yet reported (toast, selection and focus)
+    pub pending_hits: Vec<BreakpointHit>,
+    pub last_hit: Option<BreakpointHit>,
+    pub groups: BreakpointGroups,
+}
+
+impl Breakpoints {
+    /// Start watching the enabled breakpoints from `state`, or None if none are enabled
+    pub fn watch(&self, state: &CanonicalState, genome: &GenomeData, time: f32) -> Option<BreakpointWatch> {
+        BreakpointWatch::new(&self.list, &self.groups, state, genome, time)
+    }
+
+    /// Count a hit, disable the breakpoint unless it repeats and queue the hit for reporting
+    pub fn record_hit(&mut self, hit: BreakpointHit) {
+        if let Some(breakpoint) = self.list.get_mut(hit.breakpoint) {
+            breakpoint.hits += 1;
+            if !breakpoint.repeating {
+                breakpoint.enabled = false;
+            }
+        }
+        self.last_hit = Some(hit.clone());
+        self.pending_hits.push(hit);
+    }
+}
+
+/// Enabled breakpoints with whether each held after the last checked tick
+///
+/// A plain value (no resource access), so replays on background threads can carry one.
+#[derive(Clone, Debug)]
+pub struct BreakpointWatch {
+    watched: Vec<(usize, BreakCondition, bool)>,
+    groups: BreakpointGroups,
+}
+
+impl BreakpointWatch {
+    /// Watch the enabled breakpoints of `list` from `state`, or None if none are enabled
+    pub fn new(list: &[Breakpoint], groups: &BreakpointGroups, state: &CanonicalState, genome: &GenomeData, time: f32) -> Option<Self> {
+        let watched = list.iter()
+            .enumerate()
+            .filter(|(_, breakpoint)| breakpoint.enabled)
+            .map(|(index, breakpoint)| {
+                let holds = breakpoint.condition.evaluate(state, genome, groups, time).is_some();
+                (index, breakpoint.condition, holds)
+            })
+            .collect::<Vec<_>>();
+        (!watched.is_empty()).then(|| Self { watched, groups: groups.clone() })
+    }
+
+    /// Check the state at the end of `tick`; returns the first breakpoint that fires
+    ///
+    /// Every condition is re-evaluated even after one fires, so the watch stays in
+    /// step with the state if the caller keeps going.
+    pub fn check(&mut self, state: &CanonicalState, genome: &GenomeData, tick: u32, time: f32) -> Option<BreakpointHit> {
+        let mut hit = None;
+        for (index, condition, holding) in &mut self.watched {
+            let result = condition.evaluate(state, genome, &self.groups, time);
+            if hit.is_none() && condition.fires(*holding, result.is_some()) {
+                hit = Some(BreakpointHit {
+                    breakpoint: *index,
+                    tick,
+                    condition: *condition,
+                    cell_id: result.flatten(),
+                });
+            }
+            *holding = result.is_some();
+        }
+        hit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{ModeIndex, ModeSettings};
+
+    const DT: f32 = 1.0 / 64.0;
+
+    /// One splitter (mode 0) dividing every second into mode 1 children, and a bonded
+    /// pair (mode 2) pulled apart by a scripted stretch
+    fn scenario() -> (CanonicalState, GenomeData) {
+        let mut splitter = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        splitter.split_mass = 1.0;
+        splitter.split_interval = 1.0;
+        splitter.child_a.mode_number = ModeIndex::new(1);
+        splitter.child_b.mode_number = ModeIndex::new(1);
+        let mut child = ModeSettings::new_self_splitting(1, "Child".to_string());
+        child.split_interval = 1000.0;
+        let mut bonded = ModeSettings::new_self_splitting(2, "Bonded".to_string());
+        bonded.split_interval = 1000.0;
+        let genome = GenomeData { modes: vec![splitter, child, bonded], ..GenomeData::default() };
+
+        let mut state = CanonicalState::new(16);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+            0, 0, 0.0, 1.0, 1.0, 500.0, Quat::IDENTITY, 0);
+        for x in [20.0, 21.0] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                0, 2, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 1, 2, 2,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        );
+        (state, genome)
+    }
+
+    /// First tick (at most 200) at which `condition` fires, with the cell it points at
+    ///
+    /// Each tick runs the scripted stretch (cell 2 moved 0.05 further per tick from tick 60)
+    /// and mass drain (cell 1 loses 1% per tick) before the division step.
+    fn first_hit(condition: BreakCondition) -> Option<(u32, Option<u32>)> {
+        first_hit_with_groups(condition, BreakpointGroups::default())
+    }
+
+    fn first_hit_with_groups(condition: BreakCondition, groups: BreakpointGroups) -> Option<(u32, Option<u32>)> {
+        let (mut state, genome) = scenario();
+        let breakpoints = Breakpoints { list: vec![Breakpoint::new(condition)], groups, ..Default::default() };
+        let mut watch = breakpoints.watch(&state, &genome, 0.0).expect("enabled breakpoint");
+        for tick in 1..=200u32 {
+            if tick >= 60 {
+                state.positions[2].x += 0.05;
+            }
+            state.masses[1] *= 0.99;
+            let time = tick as f32 * DT;
+            let capacity = state.capacity;
+            crate::simulation::cpu_physics::division_step(&mut state, &genome, time, DT, capacity, 0);
+            if let Some(hit) = watch.check(&state, &genome, tick, time) {
+                return Some((hit.tick, hit.cell_id));
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_each_condition_fires_at_the_expected_tick() {
+        // The splitter divides once its one-second interval has passed (tick 64)
+        assert_eq!(first_hit(BreakCondition::CellCountCrosses(4)), Some((64, None)));
+        assert_eq!(first_hit(BreakCondition::ModeExtinct(0)), Some((64, None)));
+        // 0.99^t < 0.5 from t = 69
+        assert_eq!(first_hit(BreakCondition::CellMassBelow { cell_id: 1, mass: 0.5 }), Some((69, Some(1))));
+        // Extension 0.05 * (t - 59) with the default stiffness of 150: above 63 from t = 68
+        assert_eq!(scenario().1.modes[2].adhesion_settings.linear_spring_stiffness, 150.0);
+        assert_eq!(first_hit(BreakCondition::AdhesionForceAbove(63.0)), Some((68, Some(1))));
+        assert_eq!(first_hit(BreakCondition::TimeReaches(2.0)), Some((128, None)));
+    }
+
+    #[test]
+    fn test_group_size_condition_follows_the_group_query() {
+        use crate::simulation::cell_groups::{GroupKind, GroupPredicate, GroupQuery};
+        let children = CellGroup {
+            id: 7,
+            name: "Children".to_string(),
+            kind: GroupKind::Query(GroupQuery::Predicate(GroupPredicate::ModeIn(vec![1]))),
+            live: true,
+            members: Vec::new(),
+        };
+        let groups = BreakpointGroups { groups: vec![children], sphere_radius: 100.0, ..Default::default() };
+        // The split at tick 64 puts two cells in mode 1
+        let condition = BreakCondition::GroupCountCrosses { group: 7, count: 2 };
+        assert_eq!(first_hit_with_groups(condition, groups.clone()), Some((64, None)));
+        // A missing group never holds
+        assert_eq!(first_hit_with_groups(BreakCondition::GroupCountCrosses { group: 8, count: 0 }, groups), None);
+    }
+
+    #[test]
+    fn test_metric_condition_crosses_both_ways() {
+        use crate::simulation::derived_metrics::{DivisionByZero, Expr};
+        let metric = |id: u32, source: &str| CompiledMetric {
+            id,
+            name: source.to_string(),
+            expr: Expr::compile(source, 3).unwrap(),
+            division_by_zero: DivisionByZero::Nan,
+        };
+        let groups = BreakpointGroups {
+            metrics: vec![metric(1, "count(1) / count(0)"), metric(2, "mass_mean(1)")],
+            mode_count: 3,
+            ..Default::default()
+        };
+        // The split at tick 64 leaves mode 0 empty: the ratio becomes 2 / 0
+        assert_eq!(first_hit_with_groups(BreakCondition::MetricCrosses { metric: 1, value: 1.0 }, groups.clone()), None);
+        // Mode 1 has no cells (NaN, which doesn't hold) until the split gives it two
+        assert_eq!(first_hit_with_groups(BreakCondition::MetricCrosses { metric: 2, value: 0.0 }, groups.clone()), Some((64, None)));
+        assert_eq!(first_hit_with_groups(BreakCondition::MetricCrosses { metric: 3, value: 0.0 }, groups), None);
+    }
+
+    #[test]
+    fn test_conditions_holding_from_the_start_wait_for_a_new_crossing() {
+        // Mode 1 has no cells yet, which is not a mode dying out
+        assert_eq!(first_hit(BreakCondition::ModeExtinct(1)), None);
+        assert_eq!(first_hit(BreakCondition::TimeReaches(0.0)), None);
+    }
+
+    #[test]
+    fn test_one_shot_breakpoints_disable_and_repeating_ones_stay() {
+        let (state, genome) = scenario();
+        let mut breakpoints = Breakpoints {
+            list: vec![
+                Breakpoint::new(BreakCondition::TimeReaches(1.0)),
+                Breakpoint { repeating: true, ..Breakpoint::new(BreakCondition::CellCountCrosses(4)) },
+            ],
+            ..Default::default()
+        };
+        let mut watch = breakpoints.watch(&state, &genome, 0.0).unwrap();
+        let hit = watch.check(&state, &genome, 64, 1.0).unwrap();
+        assert_eq!(hit.breakpoint, 0);
+        breakpoints.record_hit(hit.clone());
+        breakpoints.record_hit(BreakpointHit { breakpoint: 1, ..hit });
+
+        assert!(!breakpoints.list[0].enabled);
+        assert!(breakpoints.list[1].enabled);
+        assert_eq!(breakpoints.pending_hits.len(), 2);
+        assert!(Breakpoints::default().watch(&state, &genome, 0.0).is_none());
+    }
+}
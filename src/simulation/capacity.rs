@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::event_timeline::{TimelineEvent, TimelineEventKind};
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Largest capacity the grow button and auto-grow will request
+pub const MAX_CELL_CAPACITY: usize = 100_000;
+
+/// Fill level at which auto-grow requests more room
+pub const AUTO_GROW_THRESHOLD: f32 = 0.9;
+
+/// Plugin for growing the active simulation's cell capacity at runtime
+pub struct CapacityGrowthPlugin;
+
+impl Plugin for CapacityGrowthPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CapacityGrowth>()
+            .add_systems(Update, grow_cell_capacity);
+    }
+}
+
+/// Runtime capacity growth requests
+///
+/// Buffers for the new capacity are allocated on a background thread; the live
+/// contents are copied over and swapped in between ticks once they are ready.
+/// Shrinking is not supported.
+#[derive(Resource, Default)]
+pub struct CapacityGrowth {
+    /// Capacity waiting to be applied
+    pub requested: Option<usize>,
+    /// Request double the capacity when the cell count reaches the auto-grow threshold
+    pub auto_grow: bool,
+    /// Cell count and capacity of the active simulation (refreshed every frame)
+    pub usage: Option<(usize, usize)>,
+    /// Capacity being allocated and the task allocating it
+    pending: Option<(usize, Task<CanonicalState>)>,
+}
+
+impl CapacityGrowth {
+    /// Ask for at least `capacity` cells (ignored if a larger request is already waiting)
+    pub fn request(&mut self, capacity: usize) {
+        let capacity = capacity.min(MAX_CELL_CAPACITY);
+        if self.requested.is_none_or(|requested| capacity > requested) {
+            self.requested = Some(capacity);
+        }
+    }
+
+    /// Capacity currently being allocated
+    pub fn growing_to(&self) -> Option<usize> {
+        self.pending.as_ref().map(|(capacity, _)| *capacity)
+    }
+
+    /// Default step for the grow button and auto-grow
+    pub fn next_capacity(current: usize) -> usize {
+        (current * 2).clamp(current + 1, MAX_CELL_CAPACITY.max(current))
+    }
+}
+
+/// Capacity growths recorded on the preview timeline as (tick, capacity)
+///
+/// Replays apply them at the same ticks so scrubbing reproduces the run.
+pub fn scheduled_capacity(schedule: &[(u32, usize)], tick: u32) -> Option<usize> {
+    schedule.iter()
+        .filter(|(growth_tick, _)| *growth_tick <= tick)
+        .map(|(_, capacity)| *capacity)
+        .max()
+}
+
+/// Grow `state` to the capacity scheduled for `tick`, if that is larger
+pub fn apply_scheduled_growth(state: &mut CanonicalState, schedule: &[(u32, usize)], tick: u32) {
+    if let Some(capacity) = scheduled_capacity(schedule, tick) {
+        if capacity > state.capacity {
+            *state = state.grown(capacity);
+        }
+    }
+}
+
+/// Start, finish and apply capacity growth for the active simulation
+fn grow_cell_capacity(
+    mut growth: ResMut<CapacityGrowth>,
+    mut sim_state: ResMut<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    config: Res<crate::simulation::PhysicsConfig>,
+) {
+    let state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|main_state| &main_state.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|preview_state| &preview_state.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+    let Some(state) = state else {
+        growth.usage = None;
+        return;
+    };
+    let (cell_count, capacity, grid_density) = (state.cell_count, state.capacity, state.spatial_grid.grid_dimensions.x);
+    let memory_profile = state.memory_profile;
+    growth.usage = Some((cell_count, capacity));
+
+    if growth.auto_grow
+        && growth.requested.is_none()
+        && growth.pending.is_none()
+        && capacity < MAX_CELL_CAPACITY
+        && cell_count as f32 >= capacity as f32 * AUTO_GROW_THRESHOLD
+    {
+        growth.request(CapacityGrowth::next_capacity(capacity));
+    }
+
+    // Allocate (and initialize) the new buffers off the main thread
+    if growth.pending.is_none() {
+        if let Some(requested) = growth.requested.take() {
+            if requested > capacity {
+                let task = AsyncComputeTaskPool::get().spawn(async move {
+                    CanonicalState::with_memory_profile(requested, grid_density, memory_profile)
+                });
+                growth.pending = Some((requested, task));
+            }
+        }
+    }
+
+    // A running preview resimulation would overwrite the grown state; wait for it to land
+    if sim_state.mode == SimulationMode::Preview && sim_state.is_resimulating {
+        return;
+    }
+
+    let Some((new_capacity, mut task)) = growth.pending.take() else {
+        return;
+    };
+    let Some(mut grown) = block_on(poll_once(&mut task)) else {
+        growth.pending = Some((new_capacity, task));
+        return;
+    };
+
+    // Swap at this tick boundary (this system runs between fixed ticks)
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            if main_state.canonical_state.capacity >= new_capacity {
+                return;
+            }
+            main_state.canonical_state.transfer_into(&mut grown);
+            main_state.canonical_state = grown;
+            main_state.index_to_entity.resize(new_capacity, None);
+            info!("Grew CPU cell capacity to {}", new_capacity);
+        }
+        SimulationMode::Preview => {
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+            if preview_state.canonical_state.capacity >= new_capacity {
+                return;
+            }
+
+            let tick = crate::simulation::clock::ticks_to_reach(preview_state.current_time, config.fixed_timestep);
+            preview_state.canonical_state.transfer_into(&mut grown);
+            preview_state.canonical_state = grown;
+            preview_state.index_to_entity.resize(new_capacity, None);
+
+            // Later checkpoints were simulated without the growth
+            let fixed_timestep = config.fixed_timestep;
+            preview_state.checkpoints.retain(|(time, _)| {
+                crate::simulation::clock::ticks_to_reach(*time, fixed_timestep) <= tick
+            });
+            preview_state.capacity_growths.push((tick, new_capacity));
+            timeline.push(TimelineEvent::new(tick, TimelineEventKind::CapacityGrowth, [None, None]));
+            sim_state.needs_respawn = true;
+            info!("Grew preview cell capacity to {} at tick {}", new_capacity, tick);
+        }
+        SimulationMode::Gpu => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+    use crate::simulation::PhysicsConfig;
+
+    /// Colony that divides at t = 2, 4, 6 (1 -> 8 cells by t = 7)
+    fn run_colony(initial_capacity: usize, schedule: &[(u32, usize)]) -> CanonicalState {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 2.0;
+        mode.nutrient_gain_rate = 1.0;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig::default();
+        let fixed_timestep = config.fixed_timestep;
+
+        let mut state = CanonicalState::new(initial_capacity);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+            0, 0, 0.0, 2.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        for tick in 0..crate::simulation::clock::ticks_to_reach(7.0, fixed_timestep) {
+            apply_scheduled_growth(&mut state, schedule, tick);
+            let current_time = tick as f32 * fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, current_time, false);
+            let next_time = (tick + 1) as f32 * fixed_timestep;
+            let max_cells = state.capacity;
+            crate::simulation::cpu_physics::division_step(&mut state, &genome, next_time, fixed_timestep, max_cells, 0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_growth_mid_run_matches_run_that_started_larger() {
+        let growth_tick = crate::simulation::clock::ticks_to_reach(5.0, PhysicsConfig::default().fixed_timestep);
+        let grown = run_colony(4, &[(growth_tick, 64)]);
+        let roomy = run_colony(64, &[]);
+        let capped = run_colony(4, &[]);
+
+        assert_eq!(capped.cell_count, 4);
+        assert_eq!(grown.capacity, 64);
+        assert_eq!(grown.cell_count, 8);
+        assert_eq!(grown.cell_ids[..8], roomy.cell_ids[..8]);
+        assert_eq!(grown.positions[..8], roomy.positions[..8]);
+    }
+}
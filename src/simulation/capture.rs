@@ -0,0 +1,668 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use image::{DynamicImage, RgbImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::error::{BioSpheresError, FileOperation};
+use crate::genome::GenomeData;
+use crate::simulation::capture_font::{self, GLYPH_HEIGHT, LINE_HEIGHT};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Key that saves a screenshot
+pub const SCREENSHOT_KEY: KeyCode = KeyCode::F12;
+
+/// Opacity of the chip behind annotation text
+const CHIP_ALPHA: f32 = 0.6;
+
+/// Plugin for screenshots with burned-in simulation annotations
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptureSettings>()
+            .init_resource::<Captures>()
+            .add_systems(Update, (
+                request_screenshot_on_key,
+                handle_capture_requests,
+                update_capture_preview,
+                poll_capture_tasks,
+            ).chain());
+    }
+}
+
+/// Corner of the capture an annotation block is drawn in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnnotationCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl AnnotationCorner {
+    pub const ALL: [AnnotationCorner; 4] = [
+        AnnotationCorner::TopLeft,
+        AnnotationCorner::TopRight,
+        AnnotationCorner::BottomLeft,
+        AnnotationCorner::BottomRight,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AnnotationCorner::TopLeft => "Top left",
+            AnnotationCorner::TopRight => "Top right",
+            AnnotationCorner::BottomLeft => "Bottom left",
+            AnnotationCorner::BottomRight => "Bottom right",
+        }
+    }
+
+    fn is_right(self) -> bool {
+        matches!(self, AnnotationCorner::TopRight | AnnotationCorner::BottomRight)
+    }
+
+    fn is_bottom(self) -> bool {
+        matches!(self, AnnotationCorner::BottomLeft | AnnotationCorner::BottomRight)
+    }
+
+    /// Top-left pixel of a `width` x `height` block in this corner of an image
+    fn place(self, image_size: (u32, u32), block: (u32, u32), margin: u32) -> (i64, i64) {
+        let x = if self.is_right() { image_size.0 as i64 - block.0 as i64 - margin as i64 } else { margin as i64 };
+        let y = if self.is_bottom() { image_size.1 as i64 - block.1 as i64 - margin as i64 } else { margin as i64 };
+        (x, y)
+    }
+}
+
+/// Simulation detail an annotation line shows
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnnotationField {
+    Genome,
+    Time,
+    CellCount,
+    Seed,
+    Speed,
+    Caption,
+}
+
+impl AnnotationField {
+    pub const ALL: [AnnotationField; 6] = [
+        AnnotationField::Genome,
+        AnnotationField::Time,
+        AnnotationField::CellCount,
+        AnnotationField::Seed,
+        AnnotationField::Speed,
+        AnnotationField::Caption,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AnnotationField::Genome => "Genome name",
+            AnnotationField::Time => "Time and tick",
+            AnnotationField::CellCount => "Cell count",
+            AnnotationField::Seed => "Seed",
+            AnnotationField::Speed => "Speed",
+            AnnotationField::Caption => "Caption",
+        }
+    }
+}
+
+/// Annotation overlay burned into screenshots and session snapshots
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct AnnotationSettings {
+    pub enabled: bool,
+    /// Show the overlay over the live viewport too (never part of the capture itself)
+    pub preview: bool,
+    /// Corner each shown field is drawn in; fields not listed are left out
+    pub corners: BTreeMap<AnnotationField, AnnotationCorner>,
+    pub caption: String,
+    /// Line height in capture pixels, rounded to a whole multiple of the font's
+    pub font_size: u32,
+    /// Draw a dark chip behind each block instead of a drop shadow
+    pub background: bool,
+    /// Image blended into a corner of every capture
+    pub watermark: Option<PathBuf>,
+    pub watermark_corner: AnnotationCorner,
+    pub watermark_opacity: f32,
+}
+
+impl Default for AnnotationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            preview: false,
+            corners: BTreeMap::from([
+                (AnnotationField::Genome, AnnotationCorner::TopLeft),
+                (AnnotationField::Time, AnnotationCorner::TopLeft),
+                (AnnotationField::CellCount, AnnotationCorner::TopLeft),
+                (AnnotationField::Caption, AnnotationCorner::BottomLeft),
+            ]),
+            caption: String::new(),
+            font_size: 18,
+            background: true,
+            watermark: None,
+            watermark_corner: AnnotationCorner::BottomRight,
+            watermark_opacity: 0.8,
+        }
+    }
+}
+
+impl AnnotationSettings {
+    /// Pixels per font pixel
+    pub fn scale(&self) -> u32 {
+        ((self.font_size as f32 / LINE_HEIGHT as f32).round() as u32).max(1)
+    }
+
+    /// Lines of each corner's block, in field order (corners without lines are left out)
+    pub fn blocks(&self, metadata: &CaptureMetadata) -> Vec<(AnnotationCorner, Vec<String>)> {
+        AnnotationCorner::ALL
+            .into_iter()
+            .filter_map(|corner| {
+                let lines: Vec<String> = self.corners.iter()
+                    .filter(|(_, &field_corner)| field_corner == corner)
+                    .filter_map(|(&field, _)| metadata.line(field))
+                    .collect();
+                (!lines.is_empty()).then_some((corner, lines))
+            })
+            .collect()
+    }
+}
+
+/// Where screenshots go and how they are annotated
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct CaptureSettings {
+    pub directory: PathBuf,
+    pub annotations: AnnotationSettings,
+    /// Pose the camera from the camera path at the capture's simulated time
+    pub use_camera_path: bool,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("screenshots"),
+            annotations: AnnotationSettings::default(),
+            use_camera_path: false,
+        }
+    }
+}
+
+/// Simulation context recorded with a capture, as overlay text and PNG text chunks
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptureMetadata {
+    pub genome: String,
+    /// Simulated seconds
+    pub time: f32,
+    pub tick: u32,
+    pub cell_count: usize,
+    pub seed: u64,
+    pub speed: f32,
+    pub caption: String,
+    /// Day/night cycle phase the frame was lit with (None for the static lighting)
+    pub lighting_phase: Option<f32>,
+}
+
+impl CaptureMetadata {
+    pub fn new(state: &CanonicalState, genome: &GenomeData, time: f32, tick: u32, seed: u64, speed: f32, caption: &str) -> Self {
+        Self {
+            genome: genome.name.clone(),
+            time,
+            tick,
+            cell_count: state.cell_count,
+            seed,
+            speed,
+            caption: caption.to_string(),
+            lighting_phase: None,
+        }
+    }
+
+    /// Record the lighting phase shown at the captured time
+    pub fn with_lighting(mut self, lighting: &crate::ui::LightingConfig) -> Self {
+        self.lighting_phase = lighting.shown_phase(self.time);
+        self
+    }
+
+    /// Overlay line of a field (None for an empty caption)
+    pub fn line(&self, field: AnnotationField) -> Option<String> {
+        match field {
+            AnnotationField::Genome => Some(format!("Genome: {}", self.genome)),
+            AnnotationField::Time => Some(format!("t = {:.2}s (tick {})", self.time, self.tick)),
+            AnnotationField::CellCount => Some(format!("{} cells", self.cell_count)),
+            AnnotationField::Seed => Some(format!("Seed {}", self.seed)),
+            AnnotationField::Speed => Some(format!("Speed {}x", self.speed)),
+            AnnotationField::Caption => (!self.caption.is_empty()).then(|| self.caption.clone()),
+        }
+    }
+
+    /// PNG tEXt entries (keyword, text); written whether or not the overlay is drawn
+    pub fn text_chunks(&self) -> Vec<(&'static str, String)> {
+        let mut chunks = vec![
+            ("Software", format!("BioSpheres {}", env!("CARGO_PKG_VERSION"))),
+            ("Genome", self.genome.clone()),
+            ("SimulatedTime", format!("{:.4}", self.time)),
+            ("Tick", self.tick.to_string()),
+            ("CellCount", self.cell_count.to_string()),
+            ("Seed", self.seed.to_string()),
+            ("SpeedMultiplier", self.speed.to_string()),
+        ];
+        if !self.caption.is_empty() {
+            chunks.push(("Caption", self.caption.clone()));
+        }
+        if let Some(phase) = self.lighting_phase {
+            chunks.push(("LightingPhase", format!("{:.4}", phase)));
+        }
+        chunks
+    }
+}
+
+/// Blend `color` over a pixel with `alpha`, skipping pixels outside the image
+fn blend(image: &mut RgbImage, x: i64, y: i64, color: [u8; 3], alpha: f32) {
+    if x < 0 || y < 0 || x >= image.width() as i64 || y >= image.height() as i64 {
+        return;
+    }
+    let pixel = image.get_pixel_mut(x as u32, y as u32);
+    for (channel, target) in pixel.0.iter_mut().zip(color) {
+        *channel = (*channel as f32 + (target as f32 - *channel as f32) * alpha).round() as u8;
+    }
+}
+
+/// Draw the annotation blocks and the watermark into a capture
+pub fn annotate(image: &mut RgbImage, settings: &AnnotationSettings, metadata: &CaptureMetadata, watermark: Option<&RgbaImage>) {
+    let size = image.dimensions();
+    let scale = settings.scale();
+    let padding = 3 * scale;
+    let margin = 4 * scale;
+
+    for (corner, lines) in settings.blocks(metadata) {
+        let text_width = lines.iter().map(|line| capture_font::text_width(line, scale)).max().unwrap_or(0);
+        let text_height = ((lines.len() as u32 - 1) * LINE_HEIGHT + GLYPH_HEIGHT) * scale;
+        let block = (text_width + 2 * padding, text_height + 2 * padding);
+        let (left, top) = corner.place(size, block, margin);
+
+        if settings.background {
+            for y in 0..block.1 as i64 {
+                for x in 0..block.0 as i64 {
+                    blend(image, left + x, top + y, [0, 0, 0], CHIP_ALPHA);
+                }
+            }
+        }
+        for (i, line) in lines.iter().enumerate() {
+            // Right-hand blocks are right-aligned
+            let indent = if corner.is_right() { text_width - capture_font::text_width(line, scale) } else { 0 };
+            let x0 = left + (padding + indent) as i64;
+            let y0 = top + (padding + i as u32 * LINE_HEIGHT * scale) as i64;
+            if !settings.background {
+                let shadow = scale as i64;
+                capture_font::for_each_pixel(line, scale, |x, y| {
+                    blend(image, x0 + x as i64 + shadow, y0 + y as i64 + shadow, [0, 0, 0], 1.0);
+                });
+            }
+            capture_font::for_each_pixel(line, scale, |x, y| {
+                blend(image, x0 + x as i64, y0 + y as i64, [255, 255, 255], 1.0);
+            });
+        }
+    }
+
+    if let Some(watermark) = watermark {
+        let (left, top) = settings.watermark_corner.place(size, watermark.dimensions(), margin);
+        for (x, y, pixel) in watermark.enumerate_pixels() {
+            let alpha = pixel.0[3] as f32 / 255.0 * settings.watermark_opacity.clamp(0.0, 1.0);
+            blend(image, left + x as i64, top + y as i64, [pixel.0[0], pixel.0[1], pixel.0[2]], alpha);
+        }
+    }
+}
+
+/// tEXt chunks only hold Latin-1; other characters are replaced
+fn latin1(text: &str) -> String {
+    text.chars().map(|c| if (c as u32) < 256 { c } else { '?' }).collect()
+}
+
+/// Encode an RGB capture as PNG with the metadata in tEXt chunks
+pub fn encode_png(image: &RgbImage, metadata: &CaptureMetadata) -> std::result::Result<Vec<u8>, png::EncodingError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    for (keyword, text) in metadata.text_chunks() {
+        encoder.add_text_chunk(keyword.to_string(), latin1(&text))?;
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(image.as_raw())?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+/// Annotate a captured frame and write it as PNG
+///
+/// With the overlay off the pixels are exactly the frame's RGB. Returns a warning when
+/// the watermark couldn't be loaded (the capture is still written without it).
+pub fn write_capture(
+    image: &DynamicImage,
+    path: &Path,
+    settings: &AnnotationSettings,
+    metadata: &CaptureMetadata,
+) -> crate::error::Result<Option<String>> {
+    let mut rgb = image.to_rgb8();
+    let mut warning = None;
+    if settings.enabled {
+        let watermark = settings.watermark.as_ref().and_then(|watermark_path| match image::open(watermark_path) {
+            Ok(watermark) => Some(watermark.to_rgba8()),
+            Err(e) => {
+                warning = Some(format!("Watermark {} not loaded: {}", watermark_path.display(), e));
+                None
+            }
+        });
+        annotate(&mut rgb, settings, metadata, watermark.as_ref());
+    }
+
+    let bytes = encode_png(&rgb, metadata).map_err(|e| BioSpheresError::Io {
+        operation: FileOperation::Write,
+        path: path.to_path_buf(),
+        source: std::io::Error::other(e.to_string()),
+    })?;
+    if let Some(parent) = path.parent() {
+        crate::error::create_dir_all(parent)?;
+    }
+    crate::error::write_atomically(path, &bytes)?;
+    Ok(warning)
+}
+
+/// Metadata of the active simulation, if there is one
+pub fn active_metadata(
+    sim_state: &SimulationState,
+    main_state: Option<&crate::simulation::cpu_sim::MainSimState>,
+    preview_state: Option<&crate::simulation::preview_sim::PreviewSimState>,
+    genome: &GenomeData,
+    physics_config: &PhysicsConfig,
+    caption: &str,
+) -> Option<CaptureMetadata> {
+    let (state, time, seed) = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| (&s.canonical_state, s.simulation_time, s.initial_state.rng_seed)),
+        SimulationMode::Preview => preview_state.map(|s| (&s.canonical_state, s.current_time, s.initial_state.rng_seed)),
+        SimulationMode::Gpu => None,
+    }?;
+    let tick = crate::simulation::clock::ticks_to_reach(time, physics_config.fixed_timestep);
+    Some(CaptureMetadata::new(state, genome, time, tick, seed, sim_state.speed_multiplier, caption))
+}
+
+/// A written capture
+struct CaptureOutcome {
+    path: PathBuf,
+    warning: Option<String>,
+}
+
+/// Screenshot requests and the writes in flight
+///
+/// UI code only queues requests; the metadata is taken from the simulation when the
+/// request is handled and the PNG is written on the IO task pool.
+#[derive(Resource, Default)]
+pub struct Captures {
+    requested: bool,
+    /// Screenshots requested whose frame hasn't been captured yet
+    awaiting_frames: usize,
+    tasks: Vec<Task<crate::error::Result<CaptureOutcome>>>,
+    /// Metadata shown by the viewport preview (None while it is off)
+    pub preview: Option<CaptureMetadata>,
+}
+
+impl Captures {
+    pub fn screenshot(&mut self) {
+        self.requested = true;
+    }
+
+    /// Whether a frame is about to be captured (the preview hides so it isn't burned in)
+    pub fn capturing(&self) -> bool {
+        self.awaiting_frames > 0
+    }
+
+    /// Capture writes still in flight
+    pub fn pending_writes(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
+fn request_screenshot_on_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    arbitration: Res<crate::input::InputArbitration>,
+    mut captures: ResMut<Captures>,
+) {
+    if keyboard.just_pressed(SCREENSHOT_KEY) && arbitration.keyboard_free() {
+        captures.screenshot();
+    }
+}
+
+pub(crate) fn handle_capture_requests(
+    mut commands: Commands,
+    mut captures: ResMut<Captures>,
+    settings: Res<CaptureSettings>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+    physics_config: Res<PhysicsConfig>,
+    lighting: Res<crate::ui::LightingConfig>,
+) {
+    if !std::mem::take(&mut captures.requested) {
+        return;
+    }
+    let metadata = active_metadata(
+        &sim_state,
+        main_state.as_deref(),
+        preview_state.as_deref(),
+        &current_genome.genome,
+        &physics_config,
+        &settings.annotations.caption,
+    )
+    .unwrap_or_else(|| CaptureMetadata {
+        genome: current_genome.genome.name.clone(),
+        time: 0.0,
+        tick: 0,
+        cell_count: 0,
+        seed: 0,
+        speed: sim_state.speed_multiplier,
+        caption: settings.annotations.caption.clone(),
+        lighting_phase: None,
+    })
+    .with_lighting(&lighting);
+
+    let path = settings.directory.join(format!("biospheres-t{:06}-{}.png", metadata.tick, unix_millis()));
+    let annotations = settings.annotations.clone();
+    captures.awaiting_frames += 1;
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |captured: On<ScreenshotCaptured>, mut captures: ResMut<Captures>| {
+            captures.awaiting_frames = captures.awaiting_frames.saturating_sub(1);
+            let (image, path, annotations, metadata) = (captured.image.clone(), path.clone(), annotations.clone(), metadata.clone());
+            captures.tasks.push(IoTaskPool::get().spawn(async move {
+                let dynamic = image.try_into_dynamic().map_err(|e| BioSpheresError::Io {
+                    operation: FileOperation::Write,
+                    path: path.clone(),
+                    source: std::io::Error::other(e.to_string()),
+                })?;
+                let warning = write_capture(&dynamic, &path, &annotations, &metadata)?;
+                Ok(CaptureOutcome { path, warning })
+            }));
+        },
+    );
+}
+
+/// Keep the preview's metadata current while the preview is on
+fn update_capture_preview(
+    mut captures: ResMut<Captures>,
+    settings: Res<CaptureSettings>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+    physics_config: Res<PhysicsConfig>,
+) {
+    let annotations = &settings.annotations;
+    captures.preview = (annotations.enabled && annotations.preview)
+        .then(|| active_metadata(
+            &sim_state,
+            main_state.as_deref(),
+            preview_state.as_deref(),
+            &current_genome.genome,
+            &physics_config,
+            &annotations.caption,
+        ))
+        .flatten();
+}
+
+fn poll_capture_tasks(
+    mut captures: ResMut<Captures>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    let mut finished = Vec::new();
+    captures.tasks.retain_mut(|task| match block_on(poll_once(task)) {
+        Some(result) => {
+            finished.push(result);
+            false
+        }
+        None => true,
+    });
+
+    for result in finished {
+        match result {
+            Ok(outcome) => {
+                if let Some(warning) = outcome.warning {
+                    notifications.warning(warning);
+                }
+                notifications.success(format!("Saved screenshot {}", outcome.path.display()));
+            }
+            Err(error) => notifications.error(&error),
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch, keeping screenshot file names unique
+fn unix_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> CaptureMetadata {
+        CaptureMetadata {
+            genome: "Branching Colony".to_string(),
+            time: 12.5,
+            tick: 750,
+            cell_count: 321,
+            seed: 42,
+            speed: 2.0,
+            caption: "Second division wave".to_string(),
+            lighting_phase: None,
+        }
+    }
+
+    /// A frame with a gradient, so any changed pixel shows
+    fn frame() -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(64, 48, |x, y| image::Rgba([x as u8 * 4, y as u8 * 5, (x + y) as u8, 255])))
+    }
+
+    fn decode(bytes: &[u8]) -> (Vec<(String, String)>, Vec<u8>) {
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let mut reader = decoder.read_info().unwrap();
+        let chunks = reader.info().uncompressed_latin1_text.iter()
+            .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+            .collect();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut pixels).unwrap();
+        pixels.truncate(frame.buffer_size());
+        (chunks, pixels)
+    }
+
+    #[test]
+    fn test_png_text_chunks_hold_the_metadata() {
+        let bytes = encode_png(&frame().to_rgb8(), &metadata()).unwrap();
+        let (chunks, _) = decode(&bytes);
+        let expected = [
+            ("Genome", "Branching Colony"),
+            ("SimulatedTime", "12.5000"),
+            ("Tick", "750"),
+            ("CellCount", "321"),
+            ("Seed", "42"),
+            ("SpeedMultiplier", "2"),
+            ("Caption", "Second division wave"),
+        ];
+        for (keyword, text) in expected {
+            assert!(chunks.contains(&(keyword.to_string(), text.to_string())), "missing {} = {}", keyword, text);
+        }
+        assert!(chunks.iter().any(|(keyword, text)| keyword == "Software" && text.starts_with("BioSpheres")));
+
+        // No caption, no Caption chunk; non-Latin-1 genome names still encode
+        let plain = CaptureMetadata { caption: String::new(), genome: "Colony →".to_string(), ..metadata() };
+        let (chunks, _) = decode(&encode_png(&frame().to_rgb8(), &plain).unwrap());
+        assert!(!chunks.iter().any(|(keyword, _)| keyword == "Caption"));
+        assert!(chunks.contains(&("Genome".to_string(), "Colony ?".to_string())));
+        assert!(!chunks.iter().any(|(keyword, _)| keyword == "LightingPhase"));
+    }
+
+    #[test]
+    fn test_lighting_phase_follows_the_captured_time() {
+        let mut lighting = crate::ui::LightingConfig::default();
+        assert_eq!(metadata().with_lighting(&lighting).lighting_phase, None);
+
+        lighting.day_night.enabled = true;
+        lighting.day_night.cycle_length = 10.0;
+        let lit = metadata().with_lighting(&lighting);
+        assert!((lit.lighting_phase.unwrap() - 0.25).abs() < 1e-6);
+        assert!(lit.text_chunks().contains(&("LightingPhase", "0.2500".to_string())));
+    }
+
+    #[test]
+    fn test_disabled_overlay_keeps_pixels_identical() {
+        let dir = std::env::temp_dir().join(format!("biospheres-capture-{}", std::process::id()));
+        let frame = frame();
+        let settings = AnnotationSettings { enabled: false, watermark: Some(dir.join("missing.png")), ..Default::default() };
+        let path = dir.join("plain.png");
+        assert_eq!(write_capture(&frame, &path, &settings, &metadata()).unwrap(), None);
+
+        // Pre-annotation path: the frame's RGB written as is
+        let (_, pixels) = decode(&std::fs::read(&path).unwrap());
+        assert_eq!(pixels, frame.to_rgb8().into_raw());
+
+        // The enabled overlay does change the frame
+        let enabled = AnnotationSettings { enabled: true, watermark: None, ..Default::default() };
+        let annotated = dir.join("annotated.png");
+        write_capture(&frame, &annotated, &enabled, &metadata()).unwrap();
+        let (_, annotated_pixels) = decode(&std::fs::read(&annotated).unwrap());
+        assert_ne!(annotated_pixels, pixels);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_blocks_group_fields_by_corner_and_scale_with_font_size() {
+        let mut settings = AnnotationSettings::default();
+        settings.corners.insert(AnnotationField::Seed, AnnotationCorner::BottomRight);
+        let blocks = settings.blocks(&metadata());
+        assert_eq!(blocks, vec![
+            (AnnotationCorner::TopLeft, vec![
+                "Genome: Branching Colony".to_string(),
+                "t = 12.50s (tick 750)".to_string(),
+                "321 cells".to_string(),
+            ]),
+            (AnnotationCorner::BottomLeft, vec!["Second division wave".to_string()]),
+            (AnnotationCorner::BottomRight, vec!["Seed 42".to_string()]),
+        ]);
+
+        // Text is drawn at capture resolution, 4 pixels per font pixel with 16 px margins and
+        // 12 px padding: the right-aligned seed line ends 29 px from the bottom right corner
+        settings.font_size = 36;
+        assert_eq!(settings.scale(), 4);
+        let mut image = RgbImage::new(800, 300);
+        annotate(&mut image, &settings, &metadata(), None);
+        let lit: Vec<(u32, u32)> = image.enumerate_pixels()
+            .filter(|(_, y, pixel)| *y > 150 && pixel.0 == [255, 255, 255])
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert_eq!(lit.iter().map(|&(x, _)| x).max(), Some(800 - 29));
+        assert_eq!(lit.iter().map(|&(_, y)| y).max(), Some(300 - 29));
+    }
+}
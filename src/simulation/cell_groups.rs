@@ -0,0 +1,566 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::organism_surgery::{SurgeryOp, SurgeryRequests};
+use crate::simulation::pinning::PinRequests;
+use crate::simulation::scene_file::SceneFile;
+use crate::simulation::user_data::UserDataRequests;
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Plugin for named cell groups: evaluation, render filters and bulk actions
+pub struct CellGroupsPlugin;
+
+impl Plugin for CellGroupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CellGroups>()
+            .add_systems(Update, (
+                (refresh_cell_groups, apply_group_actions).chain(),
+                draw_highlighted_group,
+            ));
+    }
+}
+
+/// How often live query groups are re-evaluated (seconds)
+const LIVE_REFRESH_SECS: f32 = 0.5;
+
+/// What query evaluation needs besides the canonical state
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QueryContext {
+    /// Simulated time, for cell ages
+    pub time: f32,
+    /// Radius of the world boundary sphere
+    pub sphere_radius: f32,
+}
+
+/// A test on one cell's state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GroupPredicate {
+    /// The cell's mode is one of these
+    ModeIn(Vec<usize>),
+    MassRange { min: f32, max: f32 },
+    /// The cell center lies between `min` and `max` from `point`
+    DistanceFrom { point: Vec3, min: f32, max: f32 },
+    /// Seconds since the cell was born
+    AgeRange { min: f32, max: f32 },
+    /// Active bonds of the cell
+    AdhesionCount { min: usize, max: usize },
+    /// The cell surface is within `margin` of the world boundary
+    TouchingBoundary { margin: f32 },
+}
+
+impl GroupPredicate {
+    /// One default of every predicate kind, for the builder's "add" menu
+    pub const KINDS: [GroupPredicate; 6] = [
+        GroupPredicate::ModeIn(Vec::new()),
+        GroupPredicate::MassRange { min: 0.0, max: 1.0 },
+        GroupPredicate::DistanceFrom { point: Vec3::ZERO, min: 0.0, max: 10.0 },
+        GroupPredicate::AgeRange { min: 0.0, max: 10.0 },
+        GroupPredicate::AdhesionCount { min: 0, max: 2 },
+        GroupPredicate::TouchingBoundary { margin: 1.0 },
+    ];
+
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            GroupPredicate::ModeIn(_) => "Mode in",
+            GroupPredicate::MassRange { .. } => "Mass range",
+            GroupPredicate::DistanceFrom { .. } => "Distance from point",
+            GroupPredicate::AgeRange { .. } => "Age range",
+            GroupPredicate::AdhesionCount { .. } => "Adhesion count",
+            GroupPredicate::TouchingBoundary { .. } => "Touching boundary",
+        }
+    }
+
+    /// Human-readable predicate, naming modes from the genome
+    pub fn describe(&self, genome: &GenomeData) -> String {
+        match self {
+            GroupPredicate::ModeIn(modes) => {
+                let names = modes.iter()
+                    .map(|&mode| genome.modes.get(mode).map_or_else(|| format!("#{}", mode), |m| m.name.clone()))
+                    .collect::<Vec<_>>();
+                format!("mode in [{}]", names.join(", "))
+            }
+            GroupPredicate::MassRange { min, max } => format!("mass {:.2}..{:.2}", min, max),
+            GroupPredicate::DistanceFrom { point, min, max } => format!(
+                "{:.1}..{:.1} from ({:.1}, {:.1}, {:.1})", min, max, point.x, point.y, point.z
+            ),
+            GroupPredicate::AgeRange { min, max } => format!("age {:.1}..{:.1}s", min, max),
+            GroupPredicate::AdhesionCount { min, max } => format!("{}..{} bonds", min, max),
+            GroupPredicate::TouchingBoundary { margin } => format!("within {:.1} of the boundary", margin),
+        }
+    }
+
+    /// Whether the cell in slot `index` passes; ranges include both ends
+    pub fn matches(&self, state: &CanonicalState, index: usize, context: &QueryContext) -> bool {
+        match *self {
+            GroupPredicate::ModeIn(ref modes) => modes.contains(&state.mode_indices[index]),
+            GroupPredicate::MassRange { min, max } => (min..=max).contains(&state.masses[index]),
+            GroupPredicate::DistanceFrom { point, min, max } => {
+                (min..=max).contains(&state.positions[index].distance(point))
+            }
+            GroupPredicate::AgeRange { min, max } => {
+                (min..=max).contains(&(context.time - state.birth_times[index]))
+            }
+            GroupPredicate::AdhesionCount { min, max } => {
+                (min..=max).contains(&state.adhesion_manager.count_active_adhesions(index))
+            }
+            GroupPredicate::TouchingBoundary { margin } => {
+                state.positions[index].length() + state.radii[index] >= context.sphere_radius - margin
+            }
+        }
+    }
+}
+
+/// A saved predicate over cell state, composed with AND/OR
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GroupQuery {
+    Predicate(GroupPredicate),
+    /// Every sub-query holds (an empty list matches every cell)
+    All(Vec<GroupQuery>),
+    /// At least one sub-query holds (an empty list matches no cell)
+    Any(Vec<GroupQuery>),
+}
+
+impl GroupQuery {
+    pub fn matches(&self, state: &CanonicalState, index: usize, context: &QueryContext) -> bool {
+        match self {
+            GroupQuery::Predicate(predicate) => predicate.matches(state, index, context),
+            GroupQuery::All(queries) => queries.iter().all(|query| query.matches(state, index, context)),
+            GroupQuery::Any(queries) => queries.iter().any(|query| query.matches(state, index, context)),
+        }
+    }
+
+    /// IDs of the matching cells, in slot order
+    pub fn evaluate(&self, state: &CanonicalState, context: &QueryContext) -> Vec<u32> {
+        (0..state.cell_count)
+            .filter(|&index| self.matches(state, index, context))
+            .map(|index| state.cell_ids[index])
+            .collect()
+    }
+
+    pub fn describe(&self, genome: &GenomeData) -> String {
+        let join = |queries: &[GroupQuery], operator: &str| {
+            let parts = queries.iter().map(|query| query.describe(genome)).collect::<Vec<_>>();
+            format!("({})", parts.join(operator))
+        };
+        match self {
+            GroupQuery::Predicate(predicate) => predicate.describe(genome),
+            GroupQuery::All(queries) if queries.is_empty() => "all cells".to_string(),
+            GroupQuery::Any(queries) if queries.is_empty() => "no cells".to_string(),
+            GroupQuery::All(queries) => join(queries, " AND "),
+            GroupQuery::Any(queries) => join(queries, " OR "),
+        }
+    }
+}
+
+/// How a group decides its members
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GroupKind {
+    /// A saved set of cell IDs; cells that died drop out
+    Explicit(BTreeSet<u32>),
+    /// Whichever cells a query matches
+    Query(GroupQuery),
+}
+
+/// A named set of cells
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CellGroup {
+    pub id: u32,
+    pub name: String,
+    pub kind: GroupKind,
+    /// Re-evaluate at a low rate instead of only on demand
+    #[serde(default)]
+    pub live: bool,
+    /// Member IDs as of the last evaluation
+    #[serde(skip)]
+    pub members: Vec<u32>,
+}
+
+impl CellGroup {
+    /// IDs of the group's cells in `state`, in slot order
+    pub fn resolve(&self, state: &CanonicalState, context: &QueryContext) -> Vec<u32> {
+        match &self.kind {
+            GroupKind::Explicit(cells) => state.cell_ids[..state.cell_count].iter()
+                .copied()
+                .filter(|id| cells.contains(id))
+                .collect(),
+            GroupKind::Query(query) => query.evaluate(state, context),
+        }
+    }
+
+    pub fn describe(&self, genome: &GenomeData) -> String {
+        match &self.kind {
+            GroupKind::Explicit(cells) => format!("{} saved cells", cells.len()),
+            GroupKind::Query(query) => query.describe(genome),
+        }
+    }
+}
+
+/// An action applied to every member of a group
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupAction {
+    /// Remove the members from the simulation
+    Delete(u32),
+    /// Pin (or unpin) the members in place
+    Freeze { group: u32, pinned: bool },
+    SetUserData { group: u32, channel: usize, value: f32 },
+    /// Save the members as a scene file of their own
+    Export { group: u32, path: PathBuf },
+}
+
+/// Named cell groups, shown in the Cell Groups panel and saved with scenes
+///
+/// Bulk actions go through the pin, user data and surgery queues, so they are
+/// recorded on the preview timeline like the same edits made by hand.
+#[derive(Resource)]
+pub struct CellGroups {
+    pub groups: Vec<CellGroup>,
+    next_id: u32,
+    /// Group whose cells are outlined in the viewport
+    pub highlighted: Option<u32>,
+    /// Group whose cells stay at full opacity while every other cell is dimmed
+    pub isolated: Option<u32>,
+    /// Evaluate every group on the next frame (after an edit or "Refresh")
+    pub refresh_requested: bool,
+    pub actions: Vec<GroupAction>,
+    refresh: Timer,
+}
+
+impl Default for CellGroups {
+    fn default() -> Self {
+        Self {
+            groups: Vec::new(),
+            next_id: 0,
+            highlighted: None,
+            isolated: None,
+            refresh_requested: false,
+            actions: Vec::new(),
+            refresh: Timer::new(Duration::from_secs_f32(LIVE_REFRESH_SECS), TimerMode::Repeating),
+        }
+    }
+}
+
+impl CellGroups {
+    /// Add a group and return its ID
+    pub fn add(&mut self, name: String, kind: GroupKind) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.groups.push(CellGroup { id, name, kind, live: false, members: Vec::new() });
+        self.refresh_requested = true;
+        id
+    }
+
+    pub fn get(&self, id: u32) -> Option<&CellGroup> {
+        self.groups.iter().find(|group| group.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut CellGroup> {
+        self.groups.iter_mut().find(|group| group.id == id)
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.groups.retain(|group| group.id != id);
+        if self.highlighted == Some(id) {
+            self.highlighted = None;
+        }
+        if self.isolated == Some(id) {
+            self.isolated = None;
+        }
+    }
+
+    /// Replace every group with those of a loaded scene
+    pub fn replace(&mut self, groups: Vec<CellGroup>) {
+        self.next_id = groups.iter().map(|group| group.id + 1).max().unwrap_or(0);
+        self.groups = groups;
+        self.highlighted = None;
+        self.isolated = None;
+        self.refresh_requested = true;
+    }
+
+    /// Groups as stored in a scene saved from `state` (see `SceneFile::from_state`)
+    ///
+    /// Loading a scene gives initial cell `i` the ID `i`, so explicit members are
+    /// stored by slot; members that are no longer alive are dropped.
+    pub fn scene_groups(&self, state: &CanonicalState) -> Vec<CellGroup> {
+        self.groups.iter()
+            .map(|group| {
+                let kind = match &group.kind {
+                    GroupKind::Explicit(cells) => GroupKind::Explicit((0..state.cell_count)
+                        .filter(|&index| cells.contains(&state.cell_ids[index]))
+                        .map(|index| index as u32)
+                        .collect()),
+                    GroupKind::Query(query) => GroupKind::Query(query.clone()),
+                };
+                CellGroup { kind, members: Vec::new(), ..group.clone() }
+            })
+            .collect()
+    }
+}
+
+/// Scene holding only the cells with these IDs, as if they were the whole simulation
+pub fn group_scene(
+    state: &CanonicalState,
+    members: &[u32],
+    genome: &GenomeData,
+    physics: &PhysicsConfig,
+    rng_seed: u64,
+    description: String,
+) -> SceneFile {
+    let mut scene = SceneFile::from_state(state, genome, physics, None, rng_seed, description);
+    let mut index = 0;
+    scene.initial_cells.retain(|_| {
+        let keep = members.contains(&state.cell_ids[index]);
+        index += 1;
+        keep
+    });
+    scene
+}
+
+/// Active simulation's state, time and seed
+fn active<'a>(
+    sim_state: &SimulationState,
+    main_state: Option<&'a crate::simulation::cpu_sim::MainSimState>,
+    preview_state: Option<&'a crate::simulation::preview_sim::PreviewSimState>,
+) -> Option<(&'a CanonicalState, f32, u64)> {
+    match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| (&s.canonical_state, s.simulation_time, s.initial_state.rng_seed)),
+        SimulationMode::Preview => preview_state.map(|s| (&s.canonical_state, s.current_time, s.initial_state.rng_seed)),
+        SimulationMode::Gpu => None,
+    }
+}
+
+/// Re-evaluate groups on request (and live ones at a low rate), and keep the
+/// renderer's isolation and the breakpoints' copy of the groups in step
+fn refresh_cell_groups(
+    time: Res<Time>,
+    mut groups: ResMut<CellGroups>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    config: Res<PhysicsConfig>,
+    mut visibility: ResMut<crate::rendering::ModeVisibility>,
+    mut breakpoints: ResMut<crate::simulation::Breakpoints>,
+) {
+    // Ticking the timer is not an edit the breakpoints need to hear about
+    let edited = groups.is_changed();
+    let live_due = groups.bypass_change_detection().refresh.tick(time.delta()).just_finished();
+    if edited || config.is_changed() {
+        breakpoints.groups.groups = groups.groups.clone();
+        breakpoints.groups.sphere_radius = config.sphere_radius;
+    }
+
+    let Some((state, current_time, _)) = active(&sim_state, main_state.as_deref(), preview_state.as_deref()) else {
+        return;
+    };
+    let context = QueryContext { time: current_time, sphere_radius: config.sphere_radius };
+    if groups.refresh_requested || live_due {
+        let everything = std::mem::take(&mut groups.refresh_requested);
+        for group in groups.groups.iter_mut().filter(|group| everything || group.live) {
+            group.members = group.resolve(state, &context);
+        }
+    }
+
+    let isolated = groups.isolated
+        .and_then(|id| groups.get(id))
+        .map(|group| group.members.iter().copied().collect::<BTreeSet<_>>());
+    // CPU cells re-material on every change, so only write a different set
+    if visibility.isolated_cells != isolated {
+        visibility.isolated_cells = isolated;
+    }
+}
+
+/// Apply queued bulk actions to the members of their groups
+fn apply_group_actions(
+    mut groups: ResMut<CellGroups>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    genome: Res<CurrentGenome>,
+    config: Res<PhysicsConfig>,
+    mut pin_requests: ResMut<PinRequests>,
+    mut user_data_requests: ResMut<UserDataRequests>,
+    mut surgery_requests: ResMut<SurgeryRequests>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if groups.actions.is_empty() {
+        return;
+    }
+    let Some((state, current_time, rng_seed)) = active(&sim_state, main_state.as_deref(), preview_state.as_deref()) else {
+        notifications.warning("No active simulation for the group action");
+        groups.actions.clear();
+        return;
+    };
+    let context = QueryContext { time: current_time, sphere_radius: config.sphere_radius };
+
+    for action in std::mem::take(&mut groups.actions) {
+        let id = match &action {
+            GroupAction::Delete(group) | GroupAction::Freeze { group, .. }
+            | GroupAction::SetUserData { group, .. } | GroupAction::Export { group, .. } => *group,
+        };
+        let Some(group) = groups.get(id) else {
+            continue;
+        };
+        // Act on the members as they are now, not as of the last refresh
+        let members = group.resolve(state, &context);
+        let name = group.name.clone();
+        match action {
+            GroupAction::Delete(_) => {
+                surgery_requests.pending.extend(members.iter().map(|&cell_id| SurgeryOp::Remove { cell_id }));
+                notifications.info(format!("Deleted {} cells of '{}'", members.len(), name));
+            }
+            GroupAction::Freeze { pinned, .. } => {
+                for &cell_id in &members {
+                    pin_requests.set(cell_id, pinned);
+                }
+                let verb = if pinned { "Froze" } else { "Unfroze" };
+                notifications.info(format!("{} {} cells of '{}'", verb, members.len(), name));
+            }
+            GroupAction::SetUserData { channel, value, .. } => {
+                for &cell_id in &members {
+                    user_data_requests.set(cell_id, channel, value);
+                }
+            }
+            GroupAction::Export { path, .. } => {
+                let description = format!("{} cells of group '{}' at t = {:.2}s", members.len(), name, current_time);
+                let scene = group_scene(state, &members, &genome.genome, &config, rng_seed, description);
+                match scene.save_to_file(&path) {
+                    Ok(()) => notifications.success(format!("Exported '{}' to {}", name, path.display())),
+                    Err(e) => notifications.error(&e),
+                }
+            }
+        }
+        groups.refresh_requested = true;
+    }
+}
+
+const HIGHLIGHT_COLOR: Color = Color::srgb(0.3, 1.0, 0.6);
+
+/// Outline the cells of the highlighted group
+fn draw_highlighted_group(
+    mut gizmos: Gizmos,
+    groups: Res<CellGroups>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let Some(group) = groups.highlighted.and_then(|id| groups.get(id)) else {
+        return;
+    };
+    let Some((state, _, _)) = active(&sim_state, main_state.as_deref(), preview_state.as_deref()) else {
+        return;
+    };
+    let members = group.members.iter().copied().collect::<BTreeSet<_>>();
+    for index in 0..state.cell_count {
+        if members.contains(&state.cell_ids[index]) {
+            gizmos.sphere(Isometry3d::from_translation(state.positions[index]), state.radii[index] * 1.1, HIGHLIGHT_COLOR);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTEXT: QueryContext = QueryContext { time: 10.0, sphere_radius: 20.0 };
+
+    /// Five cells along X: modes 0,1,2,0,1, masses 1..5, born at t = 0,2,4,6,8,
+    /// cells 0-1-2 bonded in a chain and cell 4 touching the boundary
+    fn state() -> CanonicalState {
+        let mut state = CanonicalState::new(8);
+        for (i, x) in [0.0, 1.0, 2.0, 10.0, 19.2].into_iter().enumerate() {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, (i + 1) as f32, 0.5,
+                0, i % 3, (2 * i) as f32, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        for (a, b) in [(0, 1), (1, 2)] {
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, a, b, 0,
+                Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+            ).expect("adhesion slot");
+        }
+        state
+    }
+
+    fn matching(predicate: GroupPredicate) -> Vec<u32> {
+        GroupQuery::Predicate(predicate).evaluate(&state(), &CONTEXT)
+    }
+
+    #[test]
+    fn test_mode_predicate() {
+        assert_eq!(matching(GroupPredicate::ModeIn(vec![0])), vec![0, 3]);
+        assert_eq!(matching(GroupPredicate::ModeIn(vec![1, 2])), vec![1, 2, 4]);
+        assert!(matching(GroupPredicate::ModeIn(Vec::new())).is_empty());
+    }
+
+    #[test]
+    fn test_mass_predicate_includes_both_ends() {
+        assert_eq!(matching(GroupPredicate::MassRange { min: 2.0, max: 4.0 }), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_distance_predicate() {
+        let point = Vec3::new(10.0, 3.0, 0.0);
+        assert_eq!(matching(GroupPredicate::DistanceFrom { point, min: 0.0, max: 5.0 }), vec![3]);
+        assert_eq!(matching(GroupPredicate::DistanceFrom { point: Vec3::ZERO, min: 1.5, max: 12.0 }), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_age_predicate_uses_the_context_time() {
+        // Ages at t = 10 are 10, 8, 6, 4, 2
+        assert_eq!(matching(GroupPredicate::AgeRange { min: 3.0, max: 8.0 }), vec![1, 2, 3]);
+        let later = QueryContext { time: 12.0, ..CONTEXT };
+        let query = GroupQuery::Predicate(GroupPredicate::AgeRange { min: 3.0, max: 8.0 });
+        assert_eq!(query.evaluate(&state(), &later), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_adhesion_count_predicate() {
+        assert_eq!(matching(GroupPredicate::AdhesionCount { min: 0, max: 0 }), vec![3, 4]);
+        assert_eq!(matching(GroupPredicate::AdhesionCount { min: 1, max: 1 }), vec![0, 2]);
+        assert_eq!(matching(GroupPredicate::AdhesionCount { min: 2, max: 9 }), vec![1]);
+    }
+
+    #[test]
+    fn test_boundary_predicate() {
+        // Cell 4 reaches 19.7 of the 20.0 boundary
+        assert_eq!(matching(GroupPredicate::TouchingBoundary { margin: 0.5 }), vec![4]);
+        assert!(matching(GroupPredicate::TouchingBoundary { margin: 0.1 }).is_empty());
+    }
+
+    #[test]
+    fn test_all_and_any_compose() {
+        let state = state();
+        let mode_0 = GroupQuery::Predicate(GroupPredicate::ModeIn(vec![0]));
+        let heavy = GroupQuery::Predicate(GroupPredicate::MassRange { min: 3.0, max: 100.0 });
+        let bonded = GroupQuery::Predicate(GroupPredicate::AdhesionCount { min: 1, max: 9 });
+
+        assert_eq!(GroupQuery::All(vec![mode_0.clone(), heavy.clone()]).evaluate(&state, &CONTEXT), vec![3]);
+        assert_eq!(GroupQuery::Any(vec![mode_0.clone(), heavy.clone()]).evaluate(&state, &CONTEXT), vec![0, 2, 3, 4]);
+        // (mode 0 OR heavy) AND bonded
+        let nested = GroupQuery::All(vec![GroupQuery::Any(vec![mode_0, heavy]), bonded]);
+        assert_eq!(nested.evaluate(&state, &CONTEXT), vec![0, 2]);
+
+        assert_eq!(GroupQuery::All(Vec::new()).evaluate(&state, &CONTEXT).len(), 5);
+        assert!(GroupQuery::Any(Vec::new()).evaluate(&state, &CONTEXT).is_empty());
+    }
+
+    #[test]
+    fn test_explicit_groups_drop_dead_cells_and_save_by_slot() {
+        let mut state = state();
+        let mut groups = CellGroups::default();
+        let id = groups.add("Picked".to_string(), GroupKind::Explicit([1, 3, 4].into()));
+        crate::simulation::nutrient_system::remove_dead_cell(&mut state, 1);
+
+        // Cell 4 took the removed cell's slot
+        let group = groups.get(id).unwrap();
+        assert_eq!(group.resolve(&state, &CONTEXT), vec![4, 3]);
+        let saved = groups.scene_groups(&state);
+        assert_eq!(saved[0].kind, GroupKind::Explicit([1, 3].into()));
+
+        let mut loaded = CellGroups::default();
+        loaded.replace(saved);
+        assert_eq!(loaded.add("Next".to_string(), GroupKind::Explicit(BTreeSet::new())), id + 1);
+    }
+}
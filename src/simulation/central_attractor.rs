@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::{deterministic_random, CanonicalState};
+use crate::simulation::scene_file::{SceneFile, SceneCell, SCENE_FORMAT_VERSION};
+use crate::simulation::PhysicsConfig;
+
+/// Acceleration toward the origin at `position` (Plummer-softened gravity)
+///
+/// `strength · r / (|r|² + softening²)^1.5`, which stays finite at the center and
+/// falls off as 1/|r|² far outside the softening radius.
+pub fn attraction(position: Vec3, strength: f32, softening: f32) -> Vec3 {
+    let softened = position.length_squared() + softening * softening;
+    if softened <= f32::EPSILON {
+        return Vec3::ZERO;
+    }
+    -position * strength / (softened * softened.sqrt())
+}
+
+/// Speed of a circular orbit at `radius` around the attractor
+pub fn circular_orbit_speed(radius: f32, strength: f32, softening: f32) -> f32 {
+    let softened = radius * radius + softening * softening;
+    radius * strength.max(0.0).sqrt() / softened.powf(0.75)
+}
+
+impl PhysicsConfig {
+    /// (strength, softening radius) of the central attractor, None while it is off
+    pub fn central_attractor(&self) -> Option<(f32, f32)> {
+        (self.attractor_enabled && self.attractor_strength != 0.0)
+            .then_some((self.attractor_strength, self.attractor_softening.max(0.0)))
+    }
+}
+
+impl CanonicalState {
+    /// Add the central attractor's pull to the forces (called before the velocity update of every physics step)
+    pub fn apply_central_attraction(&mut self, config: &PhysicsConfig) {
+        let Some((strength, softening)) = config.central_attractor() else {
+            return;
+        };
+        for i in 0..self.cell_count {
+            self.forces[i] += attraction(self.positions[i], strength, softening) * self.masses[i];
+        }
+    }
+}
+
+/// Parameters of the Scene Manager's orbital spawn preset
+///
+/// The layout is a pure function of these and the physics config, so a scene
+/// spawned from them replays identically.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrbitalSpawn {
+    pub cell_count: usize,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    /// Height of the disc the cells start in
+    pub thickness: f32,
+    pub seed: u64,
+}
+
+impl Default for OrbitalSpawn {
+    fn default() -> Self {
+        Self {
+            cell_count: 40,
+            inner_radius: 20.0,
+            outer_radius: 60.0,
+            thickness: 6.0,
+            seed: 0,
+        }
+    }
+}
+
+impl OrbitalSpawn {
+    /// Scene with free cells of the genome's initial mode on circular orbits around the center
+    ///
+    /// Cells start in a disc around the XZ plane, all circling the Y axis the same way.
+    /// The attractor is switched on (with the configured strength, or the default one if
+    /// that is zero) and velocity damping off, since damping would make every orbit decay.
+    pub fn scene(&self, genome: &GenomeData, physics: &PhysicsConfig) -> SceneFile {
+        let mut physics = physics.clone();
+        physics.attractor_enabled = true;
+        if physics.attractor_strength == 0.0 {
+            physics.attractor_strength = PhysicsConfig::default().attractor_strength;
+        }
+        physics.velocity_damping = 1.0;
+        let (strength, softening) = physics.central_attractor().unwrap_or_default();
+
+        // Keep the outermost orbit clear of the boundary's soft zone
+        let max_radius = (physics.sphere_radius - 10.0).max(1.0);
+        let outer = self.outer_radius.clamp(0.0, max_radius);
+        let inner = self.inner_radius.clamp(0.0, outer);
+        let mode_index = genome.initial_mode_index();
+
+        let initial_cells = (0..self.cell_count as u32)
+            .map(|i| {
+                let random = |index| deterministic_random(i, 0, self.seed, index);
+                // Uniform over the disc's area
+                let radius = (inner * inner + (outer * outer - inner * inner) * random(0)).sqrt();
+                let angle = random(1) * std::f32::consts::TAU;
+                let height = (random(2) - 0.5) * self.thickness;
+                let position = Vec3::new(radius * angle.cos(), height, radius * angle.sin());
+                // Perpendicular to the radius, so the orbit is circular about the center
+                let tangent = Vec3::Y.cross(position).normalize_or_zero();
+                SceneCell {
+                    position,
+                    velocity: tangent * circular_orbit_speed(position.length(), strength, softening),
+                    rotation: Quat::from_rotation_arc(Vec3::Z, tangent.try_normalize().unwrap_or(Vec3::Z)),
+                    mode_index,
+                    mass: None,
+                    radius: 1.0,
+                    pinned: false,
+                    user_data: Default::default(),
+                }
+            })
+            .collect();
+
+        SceneFile {
+            format_version: SCENE_FORMAT_VERSION,
+            description: format!(
+                "{} cells orbiting the center between radius {:.0} and {:.0} (seed {})",
+                self.cell_count, inner, outer, self.seed
+            ),
+            genome: genome.clone(),
+            physics,
+            physics_overrides: None,
+            initial_cells,
+            camera: None,
+            rng_seed: self.seed,
+            groups: Vec::new(),
+            regions: None,
+            interventions: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+
+    fn genome() -> GenomeData {
+        never_split_genome(&["Drifter"])
+    }
+
+    #[test]
+    fn test_computed_orbit_speed_keeps_orbit_radius() {
+        let spawn = OrbitalSpawn { cell_count: 1, inner_radius: 40.0, outer_radius: 40.0, thickness: 0.0, seed: 0 };
+        let scene = spawn.scene(&genome(), &PhysicsConfig::default());
+        assert_eq!(scene.physics.fixed_timestep, PhysicsConfig::default().fixed_timestep);
+        let mut state = scene.to_initial_state(4).to_canonical_state();
+        let radius = state.positions[0].length();
+
+        for tick in 0..10_000 {
+            crate::simulation::cpu_physics::physics_step_st_with_genome(
+                &mut state, &scene.physics, &scene.genome, tick as f32 * scene.physics.fixed_timestep,
+            );
+            let drift = (state.positions[0].length() - radius).abs() / radius;
+            assert!(drift < 0.05, "orbit radius drifted {:.1}% by tick {}", drift * 100.0, tick);
+        }
+    }
+
+    #[test]
+    fn test_attractor_is_off_by_default() {
+        let config = PhysicsConfig::default();
+        assert!(config.central_attractor().is_none());
+
+        let mut state = CanonicalState::new(4);
+        add_test_cell(&mut state, Vec3::new(30.0, 0.0, 0.0), Quat::IDENTITY, 1.0, 1.0, 0);
+        state.apply_central_attraction(&config);
+        assert_eq!(state.forces[0], Vec3::ZERO);
+
+        let enabled = PhysicsConfig { attractor_enabled: true, ..config };
+        state.apply_central_attraction(&enabled);
+        assert!(state.forces[0].x < 0.0);
+    }
+
+    #[test]
+    fn test_orbital_scene_is_deterministic_and_valid() {
+        let spawn = OrbitalSpawn { seed: 7, ..Default::default() };
+        let a = spawn.scene(&genome(), &PhysicsConfig::default());
+        let b = spawn.scene(&genome(), &PhysicsConfig::default());
+
+        assert!(a.validate().is_empty(), "{:?}", a.validate());
+        assert_eq!(a.initial_cells.len(), spawn.cell_count);
+        assert!(a.physics.attractor_enabled);
+        for (cell_a, cell_b) in a.initial_cells.iter().zip(&b.initial_cells) {
+            assert_eq!(cell_a.position, cell_b.position);
+            assert_eq!(cell_a.velocity, cell_b.velocity);
+            assert!(cell_a.velocity.dot(cell_a.position).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::PhysicsConfig;
+
+/// Number of substance channels in the environment field
+pub const FIELD_CHANNELS: usize = 2;
+
+/// Substance concentrations on a voxel grid covering the world sphere
+///
+/// Cells secrete into and absorb from the voxel containing them; flagellocytes can
+/// steer along the local gradient. Values are amounts per voxel. The grid is only
+/// allocated once something is secreted, so genomes without chemistry pay nothing.
+#[derive(Clone, Default)]
+pub struct ChemicalField {
+    /// Voxels per axis (0 = not allocated)
+    resolution: usize,
+    /// Half the edge length of the cube the grid covers (the world sphere radius)
+    half_extent: f32,
+    /// Concentrations read this tick, per channel, x-fastest
+    concentrations: [Vec<f32>; FIELD_CHANNELS],
+    /// Concentrations being written for the next tick (swapped in at the end of a step)
+    next: [Vec<f32>; FIELD_CHANNELS],
+    /// Channels that have received any substance (others are skipped entirely)
+    active: [bool; FIELD_CHANNELS],
+}
+
+impl ChemicalField {
+    pub fn is_allocated(&self) -> bool {
+        self.resolution > 0
+    }
+
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Edge length of one voxel
+    pub fn voxel_size(&self) -> f32 {
+        self.half_extent * 2.0 / self.resolution.max(1) as f32
+    }
+
+    /// Bytes held by both buffers
+    pub fn memory_bytes(&self) -> usize {
+        self.concentrations.iter().chain(&self.next)
+            .map(|values| values.capacity() * size_of::<f32>())
+            .sum()
+    }
+
+    /// Allocate (or reallocate, clearing the field) for the config's grid layout
+    fn ensure_layout(&mut self, config: &PhysicsConfig) {
+        let resolution = config.chemical_grid_resolution.clamp(4, 128) as usize;
+        if self.resolution == resolution && self.half_extent == config.sphere_radius {
+            return;
+        }
+        let voxels = resolution * resolution * resolution;
+        *self = Self {
+            resolution,
+            half_extent: config.sphere_radius,
+            concentrations: std::array::from_fn(|_| vec![0.0; voxels]),
+            next: std::array::from_fn(|_| vec![0.0; voxels]),
+            active: [false; FIELD_CHANNELS],
+        };
+    }
+
+    /// Voxel coordinates containing `position` (clamped to the grid)
+    fn voxel_coords(&self, position: Vec3) -> IVec3 {
+        let max = self.resolution as i32 - 1;
+        ((position + Vec3::splat(self.half_extent)) / self.voxel_size())
+            .floor()
+            .as_ivec3()
+            .clamp(IVec3::ZERO, IVec3::splat(max))
+    }
+
+    fn index(&self, coords: IVec3) -> usize {
+        let n = self.resolution;
+        coords.x as usize + n * (coords.y as usize + n * coords.z as usize)
+    }
+
+    /// Concentration of `channel` in the voxel containing `position`
+    pub fn concentration(&self, channel: usize, position: Vec3) -> f32 {
+        if !self.is_allocated() || channel >= FIELD_CHANNELS {
+            return 0.0;
+        }
+        self.concentrations[channel][self.index(self.voxel_coords(position))]
+    }
+
+    /// Gradient of `channel` at the voxel containing `position`, from central
+    /// differences of the neighboring voxels (one-sided at the grid edge)
+    pub fn gradient(&self, channel: usize, position: Vec3) -> Vec3 {
+        if channel >= FIELD_CHANNELS || !self.active[channel] {
+            return Vec3::ZERO;
+        }
+        let values = &self.concentrations[channel];
+        let center = self.voxel_coords(position);
+        let max = self.resolution as i32 - 1;
+        let mut gradient = Vec3::ZERO;
+        for axis in 0..3 {
+            let mut low = center;
+            let mut high = center;
+            low[axis] = (center[axis] - 1).max(0);
+            high[axis] = (center[axis] + 1).min(max);
+            let span = (high[axis] - low[axis]).max(1) as f32 * self.voxel_size();
+            gradient[axis] = (values[self.index(high)] - values[self.index(low)]) / span;
+        }
+        gradient
+    }
+
+    /// Diffuse and decay every active channel from `concentrations` into `next`
+    fn diffuse(&mut self, diffusion: f32, decay: f32, dt: f32) {
+        let n = self.resolution;
+        let h = self.voxel_size();
+        // Explicit scheme; capped at the 3D stability limit
+        let rate = (diffusion * dt / (h * h)).min(1.0 / 6.0);
+        let keep = (1.0 - decay * dt).clamp(0.0, 1.0);
+        let stride = [1, n, n * n];
+
+        for channel in 0..FIELD_CHANNELS {
+            if !self.active[channel] {
+                continue;
+            }
+            let (current, next) = (&self.concentrations[channel], &mut self.next[channel]);
+            for z in 0..n {
+                for y in 0..n {
+                    for x in 0..n {
+                        let i = x + n * (y + n * z);
+                        let value = current[i];
+                        let mut flux = 0.0;
+                        for (axis, coord) in [x, y, z].into_iter().enumerate() {
+                            // Closed boundary: a missing neighbor contributes no flux
+                            if coord > 0 {
+                                flux += current[i - stride[axis]] - value;
+                            }
+                            if coord + 1 < n {
+                                flux += current[i + stride[axis]] - value;
+                            }
+                        }
+                        next[i] = (value + rate * flux) * keep;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CanonicalState {
+    /// Advance the chemical field one tick: diffusion and decay, then secretion and
+    /// absorption by every cell in index order, then swap the buffers
+    ///
+    /// Taxis reads the field swapped in here on the following tick.
+    pub fn update_chemical_field(&mut self, genome: &crate::genome::GenomeData, config: &PhysicsConfig) {
+        let has_chemistry = genome.modes.iter()
+            .any(|mode| mode.secretion_rate.iter().chain(&mode.absorption_rate).any(|rate| *rate > 0.0));
+        if !has_chemistry && !self.chemical_field.is_allocated() {
+            return;
+        }
+
+        let dt = config.fixed_timestep;
+        let field = &mut self.chemical_field;
+        field.ensure_layout(config);
+        field.diffuse(config.chemical_diffusion, config.chemical_decay, dt);
+
+        for i in 0..self.cell_count {
+            let Some(mode) = genome.modes.get(self.mode_indices[i]) else {
+                continue;
+            };
+            let voxel = field.index(field.voxel_coords(self.positions[i]));
+            for channel in 0..FIELD_CHANNELS {
+                let secreted = mode.secretion_rate[channel] * dt;
+                if secreted > 0.0 {
+                    // An inactive channel's next buffer still holds zeros
+                    field.active[channel] = true;
+                    field.next[channel][voxel] += secreted;
+                }
+                if field.active[channel] {
+                    let value = &mut field.next[channel][voxel];
+                    *value -= (mode.absorption_rate[channel] * dt).min(*value).max(0.0);
+                }
+            }
+        }
+
+        for channel in 0..FIELD_CHANNELS {
+            if field.active[channel] {
+                std::mem::swap(&mut field.concentrations[channel], &mut field.next[channel]);
+            }
+        }
+    }
+}
+
+/// Swim direction for a flagellocyte facing `forward`, bent toward (+) or away from (-)
+/// the gradient of its taxis channel
+pub fn taxis_direction(
+    forward: Vec3,
+    position: Vec3,
+    channel: usize,
+    strength: f32,
+    field: &ChemicalField,
+) -> Vec3 {
+    if strength == 0.0 {
+        return forward;
+    }
+    let gradient = field.gradient(channel, position).normalize_or_zero();
+    (forward + gradient * strength).try_normalize().unwrap_or(forward)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+
+    fn secretor_genome(secretion: f32, absorption: f32) -> GenomeData {
+        let mut mode = ModeSettings::new_self_splitting(0, "Secretor".to_string());
+        mode.secretion_rate = [secretion, 0.0];
+        mode.absorption_rate = [absorption, 0.0];
+        GenomeData { modes: vec![mode], ..GenomeData::default() }
+    }
+
+    fn state_with_cells(positions: &[Vec3]) -> CanonicalState {
+        let mut state = CanonicalState::new(16);
+        for &position in positions {
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        state
+    }
+
+    fn total(field: &ChemicalField, channel: usize) -> f32 {
+        field.concentrations[channel].iter().sum()
+    }
+
+    #[test]
+    fn test_no_chemistry_leaves_field_unallocated() {
+        let mut state = state_with_cells(&[Vec3::ZERO]);
+        state.update_chemical_field(&secretor_genome(0.0, 0.0), &PhysicsConfig::default());
+        assert!(!state.chemical_field.is_allocated());
+        assert_eq!(state.chemical_field.memory_bytes(), 0);
+    }
+
+    #[test]
+    fn test_secretion_diffuses_and_is_conserved_without_decay() {
+        let config = PhysicsConfig { chemical_decay: 0.0, ..PhysicsConfig::default() };
+        let genome = secretor_genome(2.0, 0.0);
+        let mut state = state_with_cells(&[Vec3::ZERO]);
+        for _ in 0..64 {
+            state.update_chemical_field(&genome, &config);
+        }
+
+        let field = &state.chemical_field;
+        let secreted = 2.0 * config.fixed_timestep * 64.0;
+        assert!((total(field, 0) - secreted).abs() < 1e-3, "{} vs {}", total(field, 0), secreted);
+        assert_eq!(total(field, 1), 0.0);
+
+        // Spread to the neighbors, highest at the source, gradient pointing back at it
+        let h = field.voxel_size();
+        assert!(field.concentration(0, Vec3::X * h) > 0.0);
+        assert!(field.concentration(0, Vec3::ZERO) > field.concentration(0, Vec3::X * h));
+        assert!(field.gradient(0, Vec3::X * 3.0 * h).x < 0.0);
+    }
+
+    #[test]
+    fn test_absorption_never_drives_concentration_negative() {
+        let config = PhysicsConfig::default();
+        let mut genome = secretor_genome(1.0, 0.0);
+        let mut absorber = genome.modes[0].clone();
+        absorber.secretion_rate = [0.0; 2];
+        absorber.absorption_rate = [50.0, 0.0];
+        genome.modes.push(absorber);
+
+        let mut state = state_with_cells(&[Vec3::ZERO, Vec3::X * 30.0]);
+        state.mode_indices[1] = 1;
+        for _ in 0..200 {
+            state.update_chemical_field(&genome, &config);
+        }
+        assert!(state.chemical_field.concentrations[0].iter().all(|value| *value >= 0.0));
+        assert_eq!(state.chemical_field.concentration(0, Vec3::X * 30.0), 0.0);
+    }
+
+    /// Mean distance of the chemotaxis preset's chasers from the beacons every 500 ticks
+    fn chaser_distances(scene: &crate::simulation::SceneFile, ticks: u32) -> Vec<f32> {
+        let mut state = scene.to_initial_state(64).to_canonical_state();
+        let mut distances = Vec::new();
+        for tick in 0..=ticks {
+            if tick % 500 == 0 {
+                let chasers: Vec<f32> = (0..state.cell_count)
+                    .filter(|&i| state.mode_indices[i] == 1)
+                    .map(|i| state.positions[i].length())
+                    .collect();
+                distances.push(chasers.iter().sum::<f32>() / chasers.len() as f32);
+            }
+            let time = tick as f32 * scene.physics.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &scene.physics, &scene.genome, time, true);
+        }
+        distances
+    }
+
+    #[test]
+    fn test_chemotaxis_preset_chasers_close_in_on_beacons() {
+        let scene = crate::simulation::scenario_presets::load_bundled_presets()
+            .into_iter()
+            .find(|(id, _)| id == "chemotaxis")
+            .and_then(|(_, result)| result.ok())
+            .map(|preset| preset.scene)
+            .expect("bundled preset");
+
+        let distances = chaser_distances(&scene, 3000);
+        let (start, end) = (distances[0], distances[distances.len() - 1]);
+        assert!(end < start * 0.5, "mean chaser distance {:?}", distances);
+
+        // Without taxis the chasers swim off the way they face
+        let mut blind = scene.clone();
+        blind.genome.modes[1].taxis_strength = 0.0;
+        let blind_distances = chaser_distances(&blind, 3000);
+        assert!(blind_distances[blind_distances.len() - 1] > start, "blind chaser distance {:?}", blind_distances);
+    }
+
+    #[test]
+    fn test_taxis_bends_swimming_toward_the_gradient() {
+        let config = PhysicsConfig::default();
+        let genome = secretor_genome(5.0, 0.0);
+        let mut state = state_with_cells(&[Vec3::ZERO]);
+        for _ in 0..100 {
+            state.update_chemical_field(&genome, &config);
+        }
+
+        let position = Vec3::X * 20.0;
+        let attracted = taxis_direction(Vec3::Z, position, 0, 1.0, &state.chemical_field);
+        assert!(attracted.x < 0.0 && attracted.z > 0.0, "{:?}", attracted);
+
+        let repelled = taxis_direction(Vec3::Z, position, 0, -1.0, &state.chemical_field);
+        assert!(repelled.x > 0.0, "{:?}", repelled);
+    }
+}
@@ -151,6 +151,25 @@ impl SimulationClock {
     }
 }
 
+/// Tolerance (in ticks) for float error when converting simulated seconds to ticks
+const TICK_EPSILON: f32 = 1e-3;
+
+/// Index of the tick at the given simulated time (nearest tick)
+/// 
+/// Use this instead of multiplying by a hard-coded tick rate; the timestep is
+/// configurable and simulated time is always in seconds.
+pub fn tick_at_time(time: f32, fixed_dt: f32) -> u64 {
+    (time / fixed_dt).round().max(0.0) as u64
+}
+
+/// Number of ticks needed to reach (or pass) the given simulated time
+/// 
+/// Exact multiples of fixed_dt map to exactly that many ticks even when the
+/// division is not exact in floating point (e.g. 1.0 / 0.01).
+pub fn ticks_to_reach(time: f32, fixed_dt: f32) -> u32 {
+    (time / fixed_dt - TICK_EPSILON).ceil().max(0.0) as u32
+}
+
 impl Default for SimulationClock {
     fn default() -> Self {
         // Default to Bevy's standard fixed timestep: 64 Hz
@@ -158,3 +177,16 @@ impl Default for SimulationClock {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tick_targets_resolve_for_arbitrary_timestep() {
+        // Scrubber targets resolve to whole ticks for arbitrary dt
+        assert_eq!(ticks_to_reach(1.0, 0.01), 100);
+        assert_eq!(ticks_to_reach(1.005, 0.01), 101);
+        assert_eq!(tick_at_time(2.0, 1.0 / 60.0), 120);
+    }
+}
+
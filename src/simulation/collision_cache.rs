@@ -0,0 +1,340 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::{detect_collisions_canonical_into, CanonicalCollisionPair, CanonicalState};
+use crate::simulation::scratch::{ScratchBuffers, PAIR_CHUNKS_PER_THREAD};
+
+/// Verlet-list cache of candidate collision pairs
+///
+/// Candidates are every pair closer than their combined radius plus a skin at the
+/// last rebuild. While no cell has moved (or grown) by more than half the skin since
+/// then, no pair outside the list can have started overlapping, so each tick only
+/// re-measures the candidates. The rebuild check only reads the state, so a cached
+/// run produces the same pair list, and the same trajectory, as rebuilding every tick.
+///
+/// Full-grown cells can reach past one grid cell. The rebuild then searches as many
+/// grid cells as the largest reach needs, and re-measuring drops pairs more than one
+/// grid cell apart, since the full detector never compares those either.
+#[derive(Clone, Default)]
+pub struct CollisionPairCache {
+    /// Candidate pairs as (lower index, higher index), sorted
+    candidates: Vec<(usize, usize)>,
+    /// Per-chunk candidate lists of the parallel rebuild, reused across rebuilds
+    candidate_chunks: Vec<Vec<(usize, usize)>>,
+    /// Cell IDs, positions and radii at the last rebuild
+    reference_ids: Vec<u32>,
+    reference_positions: Vec<Vec3>,
+    reference_radii: Vec<f32>,
+    /// Skin the candidates were gathered with
+    skin: f32,
+    valid: bool,
+    /// Ticks that rebuilt the grid and candidate list
+    pub rebuilds: u64,
+    /// Ticks that reused the candidate list
+    pub reuses: u64,
+}
+
+impl CollisionPairCache {
+    /// Force a rebuild on the next tick (cells were added, removed or moved by hand)
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    pub fn candidate_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Heap bytes held by the cache
+    pub fn memory_bytes(&self) -> usize {
+        self.candidates.capacity() * std::mem::size_of::<(usize, usize)>()
+            + self.candidate_chunks.iter().map(|chunk| chunk.capacity() * std::mem::size_of::<(usize, usize)>()).sum::<usize>()
+            + self.reference_ids.capacity() * std::mem::size_of::<u32>()
+            + self.reference_positions.capacity() * std::mem::size_of::<Vec3>()
+            + self.reference_radii.capacity() * std::mem::size_of::<f32>()
+    }
+
+    /// Whether the candidates still cover every pair that can overlap
+    fn is_fresh(&self, state: &CanonicalState, skin: f32) -> bool {
+        if !self.valid || self.skin != skin || self.reference_ids.len() != state.cell_count {
+            return false;
+        }
+
+        let half_skin = skin * 0.5;
+        (0..state.cell_count).all(|i| {
+            let displacement = state.positions[i].distance(self.reference_positions[i]);
+            let growth = (state.radii[i] - self.reference_radii[i]).max(0.0);
+            state.cell_ids[i] == self.reference_ids[i] && displacement + growth <= half_skin
+        })
+    }
+
+    /// Gather the candidates from the freshly rebuilt spatial grid
+    fn rebuild(&mut self, state: &CanonicalState, skin: f32) {
+        use rayon::prelude::*;
+
+        let n = state.cell_count;
+        let grid = &state.spatial_grid;
+        let max_radius = state.radii[..n].iter().copied().fold(0.0, f32::max);
+        let range = ((2.0 * max_radius + skin) / grid.cell_size).ceil().max(1.0) as i32;
+        let offsets = forward_neighbors(range);
+        let max_chunks = rayon::current_num_threads() * PAIR_CHUNKS_PER_THREAD;
+        let chunk_len = grid.used_grid_cells.len().div_ceil(max_chunks).max(1);
+        let chunk_count = grid.used_grid_cells.len().div_ceil(chunk_len);
+        if self.candidate_chunks.len() < chunk_count {
+            self.candidate_chunks.resize_with(chunk_count, Vec::new);
+        }
+        self.candidate_chunks[..chunk_count]
+            .par_iter_mut()
+            .zip(grid.used_grid_cells.par_chunks(chunk_len))
+            .for_each(|(local, chunk)| {
+                local.clear();
+                for &grid_idx in chunk {
+                    let grid_coord = grid.active_cells[grid_idx];
+                    let cells_in_grid = grid.get_cell_contents(grid_idx);
+                    let mut consider = |a: usize, b: usize| {
+                        let reach = state.radii[a] + state.radii[b] + skin;
+                        if state.positions[a].distance_squared(state.positions[b]) < reach * reach {
+                            local.push((a.min(b), a.max(b)));
+                        }
+                    };
+
+                    for i in 0..cells_in_grid.len() {
+                        for j in (i + 1)..cells_in_grid.len() {
+                            consider(cells_in_grid[i], cells_in_grid[j]);
+                        }
+                    }
+                    for &offset in &offsets {
+                        let Some(neighbor_idx) = grid.active_cell_index(grid_coord + offset) else {
+                            continue;
+                        };
+                        for &a in cells_in_grid {
+                            for &b in grid.get_cell_contents(neighbor_idx) {
+                                consider(a, b);
+                            }
+                        }
+                    }
+                }
+            });
+
+        self.candidates.clear();
+        for local in &self.candidate_chunks[..chunk_count] {
+            self.candidates.extend_from_slice(local);
+        }
+        self.candidates.sort_unstable();
+        self.reference_ids.clear();
+        self.reference_ids.extend_from_slice(&state.cell_ids[..n]);
+        self.reference_positions.clear();
+        self.reference_positions.extend_from_slice(&state.positions[..n]);
+        self.reference_radii.clear();
+        self.reference_radii.extend_from_slice(&state.radii[..n]);
+        self.skin = skin;
+        self.valid = true;
+    }
+
+    /// Overlapping candidate pairs into `scratch.collision_pairs`, oriented and ordered
+    /// exactly as `detect_collisions_canonical` returns them
+    fn overlapping_pairs_into(&self, state: &CanonicalState, scratch: &mut ScratchBuffers) {
+        use rayon::prelude::*;
+
+        let grid = &state.spatial_grid;
+        self.candidates
+            .par_iter()
+            .map(|&(low, high)| {
+                // The full detector lists a pair from the grid cell earlier in (z, y, x) order,
+                // or lower index first within one grid cell
+                let low_coord = grid.world_to_grid(state.positions[low]);
+                let high_coord = grid.world_to_grid(state.positions[high]);
+                let (idx_a, idx_b) = if (high_coord.z, high_coord.y, high_coord.x) >= (low_coord.z, low_coord.y, low_coord.x) {
+                    (low, high)
+                } else {
+                    (high, low)
+                };
+                if (high_coord - low_coord).abs().max_element() > 1 {
+                    return None;
+                }
+
+                let delta = state.positions[idx_b] - state.positions[idx_a];
+                let distance = delta.length();
+                let combined_radius = state.radii[idx_a] + state.radii[idx_b];
+                if distance >= combined_radius
+                    || state.adhesion_manager.are_cells_in_same_organism(&state.adhesion_connections, idx_a, idx_b)
+                {
+                    return None;
+                }
+
+                let normal = if distance > 0.0001 {
+                    delta / distance
+                } else {
+                    Vec3::X
+                };
+                Some(CanonicalCollisionPair {
+                    index_a: idx_a,
+                    index_b: idx_b,
+                    overlap: combined_radius - distance,
+                    normal,
+                })
+            })
+            .collect_into_vec(&mut scratch.candidate_overlaps);
+
+        scratch.collision_pairs.clear();
+        scratch.collision_pairs.extend(scratch.candidate_overlaps.iter().flatten());
+        scratch.collision_pairs.sort_unstable_by_key(|pair| (pair.index_a, pair.index_b));
+    }
+}
+
+/// Grid offsets within `range` cells that come after the origin in (z, y, x) order
+///
+/// Half-space search like the full detector's, which is the `range` 1 case.
+fn forward_neighbors(range: i32) -> Vec<IVec3> {
+    (-range..=range)
+        .flat_map(|z| (-range..=range).flat_map(move |y| (-range..=range).map(move |x| IVec3::new(x, y, z))))
+        .filter(|offset| (offset.z, offset.y, offset.x) > (0, 0, 0))
+        .collect()
+}
+
+impl CanonicalState {
+    /// Collision pairs for this tick, reusing the cached candidate list while it is still valid
+    ///
+    /// A `skin` of 0 rebuilds the grid and runs the full detector every tick.
+    pub fn detect_collisions_cached(&mut self, skin: f32) -> Vec<CanonicalCollisionPair> {
+        let mut scratch = ScratchBuffers::default();
+        self.detect_collisions_cached_into(skin, &mut scratch);
+        scratch.collision_pairs
+    }
+
+    /// `detect_collisions_cached` into `scratch.collision_pairs`
+    pub fn detect_collisions_cached_into(&mut self, skin: f32, scratch: &mut ScratchBuffers) {
+        if skin > 0.0 && self.collision_cache.is_fresh(self, skin) {
+            self.collision_cache.reuses += 1;
+            self.collision_cache.overlapping_pairs_into(self, scratch);
+            return;
+        }
+
+        self.spatial_grid.rebuild(&self.positions, self.cell_count);
+        self.collision_cache.rebuilds += 1;
+
+        if skin <= 0.0 {
+            self.collision_cache.invalidate();
+            detect_collisions_canonical_into(self, scratch);
+            return;
+        }
+
+        let mut cache = std::mem::take(&mut self.collision_cache);
+        cache.rebuild(self, skin);
+        cache.overlapping_pairs_into(self, scratch);
+        self.collision_cache = cache;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::scene_file::SceneFile;
+    use crate::simulation::PhysicsConfig;
+
+    fn preset_scene(id: &str) -> SceneFile {
+        crate::simulation::scenario_presets::load_bundled_presets()
+            .into_iter()
+            .find(|(preset_id, _)| preset_id == id)
+            .and_then(|(_, result)| result.ok())
+            .map(|preset| preset.scene)
+            .expect("bundled preset")
+    }
+
+    /// Positions after `ticks` ticks with the given skin
+    fn run(scene: &SceneFile, skin: f32, ticks: u32) -> CanonicalState {
+        let mut physics = scene.physics.clone();
+        physics.collision_skin = skin;
+        let scene = SceneFile { physics, ..scene.clone() };
+        crate::simulation::fingerprint::simulate_scene(&scene, ticks)
+    }
+
+    #[test]
+    fn test_cached_pairs_give_bit_identical_trajectories() {
+        for id in ["mitosis_basics", "adhesion_pendulum", "twin_colonies"] {
+            let scene = preset_scene(id);
+            let always = run(&scene, 0.0, 600);
+            let cached = run(&scene, 0.3, 600);
+
+            assert_eq!(always.cell_count, cached.cell_count, "{}", id);
+            for i in 0..always.cell_count {
+                assert_eq!(always.positions[i].to_array().map(f32::to_bits), cached.positions[i].to_array().map(f32::to_bits), "{} cell {}", id, i);
+                assert_eq!(always.velocities[i].to_array().map(f32::to_bits), cached.velocities[i].to_array().map(f32::to_bits), "{} cell {}", id, i);
+            }
+            assert!(cached.collision_cache.reuses > 0, "{} never reused the pair list", id);
+        }
+    }
+
+    /// Two bonded 4 x 4 sheets of full-grown cells pressed against each other
+    fn bonded_sheets() -> (CanonicalState, crate::genome::GenomeData) {
+        use crate::simulation::test_support::{add_test_cell, never_split_genome};
+
+        let mut state = CanonicalState::new(32);
+        for sheet in 0..2 {
+            for i in 0..16 {
+                let position = Vec3::new((i % 4) as f32 * 3.4, (i / 4) as f32 * 3.4, sheet as f32 * 3.4);
+                add_test_cell(&mut state, position, Quat::IDENTITY, 1.8, 1.8, 0);
+            }
+        }
+        for i in 0..32 {
+            let neighbors = [(i % 4 < 3).then_some(i + 1), (i % 16 < 12).then_some(i + 4)];
+            for j in neighbors.into_iter().flatten() {
+                let direction = (state.positions[j] - state.positions[i]).normalize();
+                state.adhesion_manager.add_adhesion_with_directions(
+                    &mut state.adhesion_connections, i, j, state.mode_indices[i],
+                    direction, -direction, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+                ).expect("adhesion slot");
+            }
+        }
+        (state, never_split_genome(&["Sheet"]))
+    }
+
+    #[test]
+    fn test_settled_adhesion_scene_reuses_pairs_of_full_grown_cells() {
+        let (start, genome) = bonded_sheets();
+        // Reach (two radii plus skin) spans more than one grid cell
+        assert!(2.0 * 1.8 + 0.3 > start.spatial_grid.cell_size);
+
+        let run = |skin: f32| {
+            let config = PhysicsConfig { collision_skin: skin, ..PhysicsConfig::default() };
+            let mut state = start.clone();
+            let mut settled = (0, 0);
+            for tick in 0..900 {
+                if tick == 300 {
+                    settled = (state.collision_cache.rebuilds, state.collision_cache.reuses);
+                }
+                let time = tick as f32 * config.fixed_timestep;
+                crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+            }
+            let (rebuilds, reuses) = (state.collision_cache.rebuilds - settled.0, state.collision_cache.reuses - settled.1);
+            (state, rebuilds, reuses)
+        };
+        let (always, _, _) = run(0.0);
+        let (cached, rebuilds, reuses) = run(0.3);
+
+        for i in 0..always.cell_count {
+            assert_eq!(always.positions[i].to_array().map(f32::to_bits), cached.positions[i].to_array().map(f32::to_bits), "cell {}", i);
+            assert_eq!(always.velocities[i].to_array().map(f32::to_bits), cached.velocities[i].to_array().map(f32::to_bits), "cell {}", i);
+        }
+        // Once settled, at most one tick in four rebuilds
+        assert!(reuses >= 3 * rebuilds, "{} rebuilds, {} reuses", rebuilds, reuses);
+    }
+
+    #[test]
+    fn test_moving_a_cell_past_half_the_skin_forces_a_rebuild() {
+        let mut state = CanonicalState::new(8);
+        for x in [0.0, 1.5] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+
+        assert_eq!(state.detect_collisions_cached(0.4).len(), 1);
+        assert_eq!(state.detect_collisions_cached(0.4).len(), 1);
+        assert_eq!((state.collision_cache.rebuilds, state.collision_cache.reuses), (1, 1));
+
+        state.positions[1].x += 0.1;
+        state.detect_collisions_cached(0.4);
+        assert_eq!(state.collision_cache.reuses, 2);
+
+        state.positions[1].x += 1.0;
+        assert_eq!(state.detect_collisions_cached(0.4).len(), 0);
+        assert_eq!(state.collision_cache.rebuilds, 2);
+    }
+}
@@ -0,0 +1,473 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Grid cells along the longest axis of the organism's bounds
+pub const DEFAULT_SURFACE_RESOLUTION: u32 = 48;
+
+/// Resolutions offered in the Cell Inspector
+pub const SURFACE_RESOLUTION_RANGE: std::ops::RangeInclusive<u32> = 16..=128;
+
+/// Progress is reported in thousandths
+const PROGRESS_SCALE: u32 = 1000;
+
+/// Plugin for the on-demand organism surface reconstruction
+pub struct ColonySurfacePlugin;
+
+impl Plugin for ColonySurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColonySurface>()
+            .add_systems(Update, update_colony_surface);
+    }
+}
+
+/// Triangle mesh of a reconstructed surface (counter-clockwise seen from outside)
+#[derive(Clone, Debug, Default)]
+pub struct SurfaceMesh {
+    pub positions: Vec<Vec3>,
+    pub indices: Vec<u32>,
+}
+
+impl SurfaceMesh {
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    fn triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| {
+            [self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]]
+        })
+    }
+
+    /// Enclosed volume (divergence theorem over the oriented triangles)
+    pub fn volume(&self) -> f32 {
+        // Relative to the first vertex to keep the terms small far from the origin
+        let origin = self.positions.first().copied().unwrap_or(Vec3::ZERO).as_dvec3();
+        let sum: f64 = self.triangles()
+            .map(|[a, b, c]| {
+                let (a, b, c) = (a.as_dvec3() - origin, b.as_dvec3() - origin, c.as_dvec3() - origin);
+                a.dot(b.cross(c))
+            })
+            .sum();
+        (sum / 6.0) as f32
+    }
+
+    pub fn area(&self) -> f32 {
+        let sum: f64 = self.triangles()
+            .map(|[a, b, c]| 0.5 * (b - a).as_dvec3().cross((c - a).as_dvec3()).length())
+            .sum();
+        sum as f32
+    }
+
+    /// Area-weighted vertex normals for shading
+    pub fn vertex_normals(&self) -> Vec<Vec3> {
+        let mut normals = vec![Vec3::ZERO; self.positions.len()];
+        for t in self.indices.chunks_exact(3) {
+            let (a, b, c) = (self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]);
+            let normal = (b - a).cross(c - a);
+            for &i in t {
+                normals[i as usize] += normal;
+            }
+        }
+        normals.into_iter().map(|n| n.normalize_or(Vec3::Y)).collect()
+    }
+
+    /// Wavefront OBJ text (1-based indices)
+    pub fn to_obj(&self) -> String {
+        use std::fmt::Write;
+        let mut obj = String::with_capacity(self.positions.len() * 32 + self.indices.len() * 8);
+        let _ = writeln!(obj, "# BioSpheres organism surface");
+        let _ = writeln!(obj, "# {} vertices, {} triangles", self.positions.len(), self.triangle_count());
+        for p in &self.positions {
+            let _ = writeln!(obj, "v {} {} {}", p.x, p.y, p.z);
+        }
+        for t in self.indices.chunks_exact(3) {
+            let _ = writeln!(obj, "f {} {} {}", t[0] + 1, t[1] + 1, t[2] + 1);
+        }
+        obj
+    }
+}
+
+/// Size and shape of a reconstructed surface
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceMetrics {
+    pub volume: f32,
+    pub area: f32,
+    /// Area of the sphere with the same volume over the actual area (1 for a sphere)
+    pub sphericity: f32,
+}
+
+impl SurfaceMetrics {
+    pub fn of(mesh: &SurfaceMesh) -> Self {
+        let volume = mesh.volume();
+        let area = mesh.area();
+        let sphericity = if area > 0.0 {
+            std::f32::consts::PI.cbrt() * (6.0 * volume).powf(2.0 / 3.0) / area
+        } else {
+            0.0
+        };
+        Self { volume, area, sphericity }
+    }
+}
+
+/// Reconstruct the boundary of the union of `spheres` (center, radius)
+///
+/// The signed field min(|p - c| - r) is sampled on a grid with `resolution`
+/// cells along the longest axis and its zero level is extracted by marching
+/// tetrahedra (six per grid cube, so there are no ambiguous cases). `progress`
+/// goes from 0 to [`PROGRESS_SCALE`].
+pub fn reconstruct_surface(spheres: &[(Vec3, f32)], resolution: u32, progress: &AtomicU32) -> SurfaceMesh {
+    let spheres: Vec<(Vec3, f32)> = spheres.iter().copied().filter(|(_, r)| *r > 0.0).collect();
+    if spheres.is_empty() {
+        progress.store(PROGRESS_SCALE, Ordering::Relaxed);
+        return SurfaceMesh::default();
+    }
+
+    let (mut min, mut max) = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+    for &(center, radius) in &spheres {
+        min = min.min(center - radius);
+        max = max.max(center + radius);
+    }
+    let spacing = (max - min).max_element() / resolution.max(1) as f32;
+    let origin = min - spacing * 2.0;
+    let grid = ((max - min) / spacing).ceil().as_uvec3() + 5;
+    let dims = (grid.x as usize, grid.y as usize, grid.z as usize);
+    let point = |x: usize, y: usize, z: usize| origin + Vec3::new(x as f32, y as f32, z as f32) * spacing;
+    let flat = |x: usize, y: usize, z: usize| (z * dims.1 + y) * dims.0 + x;
+
+    // Signed field; far from every sphere it only needs the right sign
+    let outside = spacing * 4.0;
+    let mut field = vec![outside; dims.0 * dims.1 * dims.2];
+    for (done, &(center, radius)) in spheres.iter().enumerate() {
+        let reach = radius + spacing * 2.0;
+        let lo = ((center - reach - origin) / spacing).floor().max(Vec3::ZERO).as_uvec3();
+        let hi = ((center + reach - origin) / spacing).ceil().as_uvec3().min(grid - 1);
+        for z in lo.z as usize..=hi.z as usize {
+            for y in lo.y as usize..=hi.y as usize {
+                for x in lo.x as usize..=hi.x as usize {
+                    let value = &mut field[flat(x, y, z)];
+                    *value = value.min(point(x, y, z).distance(center) - radius);
+                }
+            }
+        }
+        progress.store((done + 1) as u32 * PROGRESS_SCALE / 2 / spheres.len() as u32, Ordering::Relaxed);
+    }
+
+    // Kuhn decomposition: each tetrahedron walks from corner 0 to corner 7 along the axes
+    const CORNERS: [(usize, usize, usize); 8] = [
+        (0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0), (0, 0, 1), (1, 0, 1), (0, 1, 1), (1, 1, 1),
+    ];
+    const TETRAHEDRA: [[usize; 4]; 6] = [
+        [0, 1, 3, 7], [0, 1, 5, 7], [0, 2, 3, 7], [0, 2, 6, 7], [0, 4, 5, 7], [0, 4, 6, 7],
+    ];
+
+    let mut mesh = SurfaceMesh::default();
+    let mut edge_vertices: HashMap<(usize, usize), u32> = HashMap::new();
+    for z in 0..dims.2 - 1 {
+        for y in 0..dims.1 - 1 {
+            for x in 0..dims.0 - 1 {
+                let corner_index = CORNERS.map(|(dx, dy, dz)| flat(x + dx, y + dy, z + dz));
+                let inside = corner_index.map(|i| field[i] < 0.0);
+                if inside.iter().all(|&i| i) || inside.iter().all(|&i| !i) {
+                    continue;
+                }
+                let corner_point = CORNERS.map(|(dx, dy, dz)| point(x + dx, y + dy, z + dz));
+                for tetrahedron in TETRAHEDRA {
+                    let (ins, outs): (Vec<usize>, Vec<usize>) = tetrahedron.iter().copied().partition(|&c| inside[c]);
+                    if ins.is_empty() || outs.is_empty() {
+                        continue;
+                    }
+                    let mut vertex = |a: usize, b: usize| -> u32 {
+                        let (ia, ib) = (corner_index[a], corner_index[b]);
+                        *edge_vertices.entry((ia.min(ib), ia.max(ib))).or_insert_with(|| {
+                            let (fa, fb) = (field[ia], field[ib]);
+                            let t = (fa / (fa - fb)).clamp(0.0, 1.0);
+                            mesh.positions.push(corner_point[a].lerp(corner_point[b], t));
+                            (mesh.positions.len() - 1) as u32
+                        })
+                    };
+                    let triangles: Vec<[u32; 3]> = match (ins.len(), outs.len()) {
+                        (1, 3) => vec![[vertex(ins[0], outs[0]), vertex(ins[0], outs[1]), vertex(ins[0], outs[2])]],
+                        (3, 1) => vec![[vertex(ins[0], outs[0]), vertex(ins[1], outs[0]), vertex(ins[2], outs[0])]],
+                        _ => {
+                            let quad = [
+                                vertex(ins[0], outs[0]), vertex(ins[0], outs[1]),
+                                vertex(ins[1], outs[1]), vertex(ins[1], outs[0]),
+                            ];
+                            vec![[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]]
+                        }
+                    };
+                    // Face away from the inside corners
+                    let inward = ins.iter().map(|&c| corner_point[c]).sum::<Vec3>() / ins.len() as f32
+                        - outs.iter().map(|&c| corner_point[c]).sum::<Vec3>() / outs.len() as f32;
+                    for [a, b, c] in triangles {
+                        let (pa, pb, pc) = (mesh.positions[a as usize], mesh.positions[b as usize], mesh.positions[c as usize]);
+                        if (pb - pa).cross(pc - pa).dot(inward) > 0.0 {
+                            mesh.indices.extend([a, c, b]);
+                        } else {
+                            mesh.indices.extend([a, b, c]);
+                        }
+                    }
+                }
+            }
+        }
+        progress.store(PROGRESS_SCALE / 2 + (z + 1) as u32 * PROGRESS_SCALE / 2 / (dims.2 - 1) as u32, Ordering::Relaxed);
+    }
+    mesh
+}
+
+/// Cell count and centroid that decide whether a cached surface still fits the organism
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrganismShape {
+    pub cell_count: usize,
+    pub centroid: Vec3,
+}
+
+/// Spheres of the organism containing cell `cell_id`
+pub fn organism_spheres(state: &CanonicalState, cell_id: u32) -> Option<Vec<(Vec3, f32)>> {
+    let index = state.cell_ids[..state.cell_count].iter().position(|&id| id == cell_id)?;
+    let members = state.adhesion_manager.organism_members(&state.adhesion_connections, index);
+    Some(members.into_iter().map(|i| (state.positions[i], state.radii[i])).collect())
+}
+
+impl OrganismShape {
+    pub fn of(spheres: &[(Vec3, f32)]) -> Self {
+        let centroid = spheres.iter().map(|(p, _)| *p).sum::<Vec3>() / spheres.len().max(1) as f32;
+        Self { cell_count: spheres.len(), centroid }
+    }
+}
+
+/// A finished reconstruction
+pub struct SurfaceResult {
+    /// Cell whose organism was reconstructed
+    pub cell_id: u32,
+    pub mode: SimulationMode,
+    pub shape: OrganismShape,
+    pub resolution: u32,
+    pub mesh: SurfaceMesh,
+    pub metrics: SurfaceMetrics,
+    /// Bumped on every new result (the overlay rebuilds its mesh on change)
+    pub generation: u64,
+}
+
+struct SurfaceJob {
+    cell_id: u32,
+    mode: SimulationMode,
+    shape: OrganismShape,
+    resolution: u32,
+    progress: Arc<AtomicU32>,
+    task: Task<SurfaceMesh>,
+}
+
+/// Organism surface analysis requested from the Cell Inspector
+///
+/// A request reuses the cached result while the organism's cell count and
+/// centroid stay within the tolerances; otherwise the surface is rebuilt on
+/// the async compute pool.
+#[derive(Resource)]
+pub struct ColonySurface {
+    pub resolution: u32,
+    /// Centroid movement after which the cached surface is outdated
+    pub centroid_tolerance: f32,
+    /// Cells gained or lost after which the cached surface is outdated
+    pub cell_count_tolerance: usize,
+    /// Draw the surface as a translucent overlay
+    pub show_overlay: bool,
+    /// Cell whose organism to reconstruct, and whether to skip the cache
+    requested: Option<(u32, bool)>,
+    job: Option<SurfaceJob>,
+    pub result: Option<SurfaceResult>,
+    /// The organism changed past the tolerances since `result`
+    pub outdated: bool,
+    pub error: Option<String>,
+    generation: u64,
+}
+
+impl Default for ColonySurface {
+    fn default() -> Self {
+        Self {
+            resolution: DEFAULT_SURFACE_RESOLUTION,
+            centroid_tolerance: 0.5,
+            cell_count_tolerance: 0,
+            show_overlay: true,
+            requested: None,
+            job: None,
+            result: None,
+            outdated: false,
+            error: None,
+            generation: 0,
+        }
+    }
+}
+
+impl ColonySurface {
+    /// Analyze the organism of `cell_id`; `force` rebuilds even a current cached surface
+    pub fn request(&mut self, cell_id: u32, force: bool) {
+        self.requested = Some((cell_id, force));
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Progress of the running reconstruction (0-1)
+    pub fn progress(&self) -> Option<f32> {
+        self.job.as_ref().map(|job| job.progress.load(Ordering::Relaxed) as f32 / PROGRESS_SCALE as f32)
+    }
+
+    /// Whether `shape` is close enough to the shape the cached surface was built from
+    pub fn fits(&self, reference: &OrganismShape, shape: &OrganismShape) -> bool {
+        reference.cell_count.abs_diff(shape.cell_count) <= self.cell_count_tolerance
+            && reference.centroid.distance(shape.centroid) <= self.centroid_tolerance
+    }
+
+    pub fn clear(&mut self) {
+        self.requested = None;
+        self.job = None;
+        self.result = None;
+        self.outdated = false;
+        self.error = None;
+    }
+
+    /// Take a request against the organism spheres of the shown scene
+    fn start(&mut self, cell_id: u32, force: bool, mode: SimulationMode, spheres: Vec<(Vec3, f32)>) {
+        let shape = OrganismShape::of(&spheres);
+        let cached = self.result.as_ref().is_some_and(|result| {
+            result.cell_id == cell_id
+                && result.mode == mode
+                && result.resolution == self.resolution
+                && self.fits(&result.shape, &shape)
+        });
+        if cached && !force {
+            self.outdated = false;
+            return;
+        }
+
+        let progress = Arc::new(AtomicU32::new(0));
+        let resolution = self.resolution;
+        let task_progress = progress.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            reconstruct_surface(&spheres, resolution, &task_progress)
+        });
+        self.error = None;
+        self.job = Some(SurfaceJob { cell_id, mode, shape, resolution, progress, task });
+    }
+
+    fn finish(&mut self, job: SurfaceJob, mesh: SurfaceMesh) {
+        self.generation += 1;
+        self.result = Some(SurfaceResult {
+            cell_id: job.cell_id,
+            mode: job.mode,
+            shape: job.shape,
+            resolution: job.resolution,
+            metrics: SurfaceMetrics::of(&mesh),
+            mesh,
+            generation: self.generation,
+        });
+        self.outdated = false;
+    }
+}
+
+/// Start requested reconstructions, collect finished ones and flag outdated surfaces
+pub fn update_colony_surface(
+    mut surface: ResMut<ColonySurface>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+) {
+    let state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|main_state| &main_state.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|preview_state| &preview_state.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+
+    if let Some((cell_id, force)) = surface.requested.take() {
+        if surface.job.is_none() {
+            match state.and_then(|state| organism_spheres(state, cell_id)) {
+                Some(spheres) => surface.start(cell_id, force, sim_state.mode, spheres),
+                None => surface.error = Some(format!("Cell {} is not in the shown scene", cell_id)),
+            }
+        }
+    }
+
+    if let Some(mut job) = surface.job.take() {
+        match block_on(poll_once(&mut job.task)) {
+            Some(mesh) => surface.finish(job, mesh),
+            None => surface.job = Some(job),
+        }
+    }
+
+    let Some(result) = surface.result.as_ref().filter(|result| result.mode == sim_state.mode) else {
+        return;
+    };
+    let outdated = match state.and_then(|state| organism_spheres(state, result.cell_id)) {
+        Some(spheres) => !surface.fits(&result.shape, &OrganismShape::of(&spheres)),
+        None => true,
+    };
+    if surface.outdated != outdated {
+        surface.outdated = outdated;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn reconstruct(spheres: &[(Vec3, f32)]) -> SurfaceMetrics {
+        let progress = AtomicU32::new(0);
+        let mesh = reconstruct_surface(spheres, DEFAULT_SURFACE_RESOLUTION, &progress);
+        assert_eq!(progress.load(Ordering::Relaxed), PROGRESS_SCALE);
+        SurfaceMetrics::of(&mesh)
+    }
+
+    fn assert_within(actual: f32, expected: f32, tolerance: f32) {
+        let error = (actual - expected).abs() / expected;
+        assert!(error <= tolerance, "{} vs {} ({:.2}% off)", actual, expected, error * 100.0);
+    }
+
+    #[test]
+    fn test_single_sphere_matches_closed_form() {
+        let radius = 1.5;
+        let metrics = reconstruct(&[(Vec3::new(10.0, -3.0, 2.0), radius)]);
+        assert_within(metrics.volume, 4.0 / 3.0 * PI * radius.powi(3), 0.02);
+        assert_within(metrics.area, 4.0 * PI * radius * radius, 0.02);
+        assert_within(metrics.sphericity, 1.0, 0.02);
+    }
+
+    #[test]
+    fn test_overlapping_spheres_match_closed_form() {
+        // Two spheres of radius r, d apart: twice a sphere minus the lens they share
+        let (radius, distance) = (1.0f32, 1.2f32);
+        let lens = PI * (4.0 * radius + distance) * (2.0 * radius - distance).powi(2) / 12.0;
+        let expected = 2.0 * 4.0 / 3.0 * PI * radius.powi(3) - lens;
+
+        let metrics = reconstruct(&[(Vec3::ZERO, radius), (Vec3::X * distance, radius)]);
+        assert_within(metrics.volume, expected, 0.02);
+        assert!(metrics.sphericity < 1.0);
+    }
+
+    #[test]
+    fn test_obj_lists_every_vertex_and_triangle() {
+        let mesh = reconstruct_surface(&[(Vec3::ZERO, 1.0)], 16, &AtomicU32::new(0));
+        let obj = mesh.to_obj();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), mesh.positions.len());
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), mesh.triangle_count());
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.positions.len()));
+    }
+
+    #[test]
+    fn test_cache_holds_until_the_organism_moves_past_the_tolerance() {
+        let surface = ColonySurface { centroid_tolerance: 0.5, cell_count_tolerance: 1, ..ColonySurface::default() };
+        let reference = OrganismShape { cell_count: 10, centroid: Vec3::ZERO };
+        assert!(surface.fits(&reference, &OrganismShape { cell_count: 11, centroid: Vec3::X * 0.4 }));
+        assert!(!surface.fits(&reference, &OrganismShape { cell_count: 12, centroid: Vec3::ZERO }));
+        assert!(!surface.fits(&reference, &OrganismShape { cell_count: 10, centroid: Vec3::Y * 0.6 }));
+    }
+}
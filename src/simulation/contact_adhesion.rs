@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::{deterministic_random, CanonicalCollisionPair, CanonicalState};
+
+/// Salt of the probability rolls, apart from the other `deterministic_random` streams
+const CONTACT_ADHESION_SALT: u32 = 0xC0_4AC7;
+
+impl CanonicalState {
+    /// Bond touching cells of different organisms whose modes the genome's contact
+    /// adhesion matrix lets stick, returning the number of new bonds
+    ///
+    /// Pairs are taken in (lower ID, higher ID) order and each is offered to the rule of
+    /// the lower-ID cell's mode first, then to the reverse rule. A rule's per-tick budget is
+    /// consumed by the bonds it forms, and its probability is rolled with
+    /// `deterministic_random`, so replays form the same bonds. Anchors point along the
+    /// contact normal. Collision pairs never join cells of one organism, so two organisms
+    /// fuse in the tick they first touch.
+    pub fn form_contact_adhesions(
+        &mut self,
+        genome: &GenomeData,
+        collision_pairs: &[CanonicalCollisionPair],
+        tick: u64,
+    ) -> usize {
+        let matrix = &genome.contact_adhesion;
+        if matrix.is_empty() || collision_pairs.is_empty() {
+            return 0;
+        }
+
+        // (lower ID, higher ID, index of lower, index of higher, normal from lower to higher)
+        let mut contacts: Vec<(u32, u32, usize, usize, Vec3)> = collision_pairs
+            .iter()
+            .map(|pair| {
+                let (a, b) = (pair.index_a, pair.index_b);
+                if self.cell_ids[a] < self.cell_ids[b] {
+                    (self.cell_ids[a], self.cell_ids[b], a, b, pair.normal)
+                } else {
+                    (self.cell_ids[b], self.cell_ids[a], b, a, -pair.normal)
+                }
+            })
+            .collect();
+        contacts.sort_unstable_by_key(|&(low, high, ..)| (low, high));
+
+        let mut formed_by_rule = vec![0u32; matrix.rules.len()];
+        let mut formed = 0;
+        for (low_id, high_id, low, high, normal) in contacts {
+            let (mode_low, mode_high) = (self.mode_indices[low], self.mode_indices[high]);
+            if !self.has_adhesion_room(genome, low) || !self.has_adhesion_room(genome, high)
+                || self.adhesion_manager.are_cells_connected(&self.adhesion_connections, low, high)
+            {
+                continue;
+            }
+
+            let offers = [(mode_low, mode_high, 0), (mode_high, mode_low, 1)];
+            let accepted = offers.iter().take(if mode_low == mode_high { 1 } else { 2 }).find_map(|&(mode, partner, side)| {
+                let rule_index = matrix.rule_index(mode, partner)?;
+                let rule = &matrix.rules[rule_index];
+                if formed_by_rule[rule_index] >= rule.max_new_bonds_per_tick {
+                    return None;
+                }
+                let roll = deterministic_random(low_id, tick, high_id as u64, CONTACT_ADHESION_SALT + side);
+                (rule.probability >= 1.0 || roll < rule.probability).then_some(rule_index)
+            });
+            let Some(rule_index) = accepted else {
+                continue;
+            };
+
+            let bonded = self.adhesion_manager.add_adhesion_with_directions(
+                &mut self.adhesion_connections,
+                low,
+                high,
+                mode_low,
+                self.rotations[low].inverse() * normal,
+                self.rotations[high].inverse() * -normal,
+                crate::simulation::organism_surgery::split_direction(genome, mode_low),
+                crate::simulation::organism_surgery::split_direction(genome, mode_high),
+                self.genome_orientations[low],
+                self.genome_orientations[high],
+            );
+            if bonded.is_some() {
+                formed_by_rule[rule_index] += 1;
+                formed += 1;
+            }
+        }
+        formed
+    }
+
+    /// Whether the cell is under its mode's max_adhesions
+    fn has_adhesion_room(&self, genome: &GenomeData, i: usize) -> bool {
+        let max = genome.modes.get(self.mode_indices[i]).map_or(0, |mode| mode.max_adhesions.max(0) as usize);
+        self.adhesion_manager.count_active_adhesions(i) < max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ContactAdhesionState;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::PhysicsConfig;
+
+    const COLUMN: usize = 4;
+
+    /// Two modes that neither grow nor divide
+    fn genome() -> GenomeData {
+        never_split_genome(&["Colony 0", "Colony 1"])
+    }
+
+    /// Two colonies, each a bonded column of cells along Y: mode 0 at -X, mode 1 at +X,
+    /// facing cells already touching
+    fn colonies() -> CanonicalState {
+        let mut state = CanonicalState::new(4 * COLUMN);
+        for (mode, x) in [(0, -0.49), (1, 0.49)] {
+            for y in 0..COLUMN {
+                add_test_cell(&mut state, Vec3::new(x, y as f32, 0.0), Quat::IDENTITY, 1.0, 0.5, mode);
+            }
+        }
+        for colony in 0..2 {
+            for y in 0..COLUMN - 1 {
+                let i = colony * COLUMN + y;
+                state.adhesion_manager.add_adhesion_with_directions(
+                    &mut state.adhesion_connections, i, i + 1, colony,
+                    Vec3::Y, -Vec3::Y, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+                ).expect("adhesion slot");
+            }
+        }
+        state
+    }
+
+    /// Bonds between the two colonies, as (mode 0 cell, mode 1 cell) indices
+    fn cross_bonds(state: &CanonicalState) -> Vec<(usize, usize)> {
+        let connections = &state.adhesion_connections;
+        let mut bonds: Vec<(usize, usize)> = (0..connections.active_count)
+            .filter(|&slot| connections.is_active[slot] != 0)
+            .map(|slot| (connections.cell_a_index[slot], connections.cell_b_index[slot]))
+            .filter(|&(a, b)| (a < COLUMN) != (b < COLUMN))
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        bonds.sort_unstable();
+        bonds
+    }
+
+    /// Hold the mode 0 colony in place and run the other in a current pressing it against
+    /// the first and sweeping it along Y; returns the final bonds between the colonies and
+    /// how far the swept one slid
+    fn run_in_current(genome: &GenomeData) -> (Vec<(usize, usize)>, f32) {
+        let config = PhysicsConfig::default();
+        let mut state = colonies();
+        for i in 0..COLUMN {
+            state.set_pinned(i, true);
+        }
+        for tick in 0..640 {
+            for i in COLUMN..2 * COLUMN {
+                state.velocities[i] += Vec3::new(-0.01, 0.02, 0.0);
+            }
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, genome, time, false);
+        }
+        let mean_y = |range: std::ops::Range<usize>| range.map(|i| state.positions[i].y).sum::<f32>() / COLUMN as f32;
+        (cross_bonds(&state), mean_y(COLUMN..2 * COLUMN) - mean_y(0..COLUMN))
+    }
+
+    #[test]
+    fn test_permitted_colonies_fuse_along_their_contact_face() {
+        let mut fusing = genome();
+        fusing.contact_adhesion.set_state(0, 1, ContactAdhesionState::Always);
+        let (bonds, slid) = run_in_current(&fusing);
+        assert_eq!(bonds, (0..COLUMN).map(|y| (y, COLUMN + y)).collect::<Vec<_>>());
+        assert!(slid.abs() < 0.5, "fused colonies slid {}", slid);
+
+        // The control has the matrix disabled and slides apart
+        let (bonds, slid) = run_in_current(&genome());
+        assert!(bonds.is_empty());
+        assert!(slid > 2.0, "control slid only {}", slid);
+    }
+
+    #[test]
+    fn test_rule_budget_and_chance_are_deterministic() {
+        let config = PhysicsConfig::default();
+        let mut budgeted = genome();
+        // The reverse rule is the one offered, as the mode 1 cells have the higher IDs
+        budgeted.contact_adhesion.set_state(1, 0, ContactAdhesionState::Always);
+        budgeted.contact_adhesion.rule_mut(1, 0).unwrap().max_new_bonds_per_tick = 2;
+
+        let mut state = colonies();
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &budgeted, 0.0, false);
+        // The budget goes to the lowest-ID pairs
+        assert_eq!(cross_bonds(&state), vec![(0, COLUMN), (1, COLUMN + 1)]);
+
+        let mut chance = genome();
+        chance.contact_adhesion.set_state(0, 1, ContactAdhesionState::Chance);
+        let formed = |genome: &GenomeData| {
+            let mut state = colonies();
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, genome, 0.0, false);
+            cross_bonds(&state)
+        };
+        assert_eq!(formed(&chance), formed(&chance));
+    }
+}
@@ -0,0 +1,581 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::cpu_sim::{CpuSceneState, MainSimState};
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Plugin for exporting who touches whom: adhesions and collision contacts
+pub struct ContactGraphPlugin;
+
+impl Plugin for ContactGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContactGraphExport>()
+            .add_systems(
+                FixedUpdate,
+                record_series
+                    .after(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(CpuSceneState::Active)),
+            )
+            .add_systems(Update, (handle_export_requests, poll_export_tasks).chain());
+    }
+}
+
+/// A cell of the contact graph
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactNode {
+    pub id: u32,
+    pub mode: usize,
+    pub position: Vec3,
+    pub mass: f32,
+}
+
+/// How two cells of the contact graph touch
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContactKind {
+    /// Active adhesion; strain is (distance - rest length) / rest length, positive when stretched
+    Adhesion { rest_length: f32, strain: f32 },
+    /// Collision contact of the latest tick (source is the lower cell id)
+    Collision { overlap: f32 },
+}
+
+impl ContactKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContactKind::Adhesion { .. } => "adhesion",
+            ContactKind::Collision { .. } => "collision",
+        }
+    }
+}
+
+/// An edge of the contact graph between two cell ids
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactEdge {
+    pub source: u32,
+    pub target: u32,
+    pub kind: ContactKind,
+}
+
+/// Cells and their contacts at one tick
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContactGraph {
+    pub tick: u32,
+    pub nodes: Vec<ContactNode>,
+    /// Adhesions in slot order, then collision contacts in detection order
+    pub edges: Vec<ContactEdge>,
+}
+
+impl ContactGraph {
+    /// Snapshot of `state` (cheap; the export is written from the copy)
+    ///
+    /// Collision contacts are the ones the last physics tick recorded. Cells of
+    /// one organism don't collide, so touching cells that adhere show up once,
+    /// as an adhesion. Contacts with cells that have since died are left out.
+    pub fn capture(state: &CanonicalState, genome: &GenomeData, tick: u32) -> Self {
+        let n = state.cell_count;
+        let nodes = (0..n)
+            .map(|i| ContactNode {
+                id: state.cell_ids[i],
+                mode: state.mode_indices[i],
+                position: state.positions[i],
+                mass: state.masses[i],
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        let connections = &state.adhesion_connections;
+        for slot in 0..connections.is_active.len() {
+            let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+            if connections.is_active[slot] == 0 || a >= n || b >= n {
+                continue;
+            }
+            let rest_length = genome.modes.get(connections.mode_index[slot])
+                .map_or(1.0, |mode| mode.adhesion_settings.rest_length);
+            let distance = state.positions[a].distance(state.positions[b]);
+            let strain = if rest_length > 0.0 { (distance - rest_length) / rest_length } else { 0.0 };
+            edges.push(ContactEdge {
+                source: state.cell_ids[a],
+                target: state.cell_ids[b],
+                kind: ContactKind::Adhesion { rest_length, strain },
+            });
+        }
+
+        let live: std::collections::HashSet<u32> = state.cell_ids[..n].iter().copied().collect();
+        edges.extend(state.scratch.last_contacts.iter()
+            .filter(|(a, b, _)| live.contains(a) && live.contains(b))
+            .map(|&(a, b, overlap)| ContactEdge {
+                source: a.min(b),
+                target: a.max(b),
+                kind: ContactKind::Collision { overlap },
+            }));
+
+        Self { tick, nodes, edges }
+    }
+
+    /// `id,mode,x,y,z,mass` rows, one per cell
+    pub fn nodes_csv(&self) -> String {
+        let mut csv = String::from("id,mode,x,y,z,mass\n");
+        for node in &self.nodes {
+            let _ = writeln!(csv, "{},{},{},{},{},{}",
+                node.id, node.mode, node.position.x, node.position.y, node.position.z, node.mass);
+        }
+        csv
+    }
+
+    /// `source,target,kind,rest_length,strain,overlap` rows; columns that don't apply are empty
+    pub fn edges_csv(&self) -> String {
+        let mut csv = String::from("source,target,kind,rest_length,strain,overlap\n");
+        for edge in &self.edges {
+            let _ = match edge.kind {
+                ContactKind::Adhesion { rest_length, strain } =>
+                    writeln!(csv, "{},{},adhesion,{},{},", edge.source, edge.target, rest_length, strain),
+                ContactKind::Collision { overlap } =>
+                    writeln!(csv, "{},{},collision,,,{}", edge.source, edge.target, overlap),
+            };
+        }
+        csv
+    }
+
+    /// Undirected GraphML document; node ids are `c<cell id>`
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"mode\" for=\"node\" attr.name=\"mode\" attr.type=\"int\"/>\n",
+            "  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"double\"/>\n",
+            "  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"double\"/>\n",
+            "  <key id=\"z\" for=\"node\" attr.name=\"z\" attr.type=\"double\"/>\n",
+            "  <key id=\"mass\" for=\"node\" attr.name=\"mass\" attr.type=\"double\"/>\n",
+            "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"rest_length\" for=\"edge\" attr.name=\"rest_length\" attr.type=\"double\"/>\n",
+            "  <key id=\"strain\" for=\"edge\" attr.name=\"strain\" attr.type=\"double\"/>\n",
+            "  <key id=\"overlap\" for=\"edge\" attr.name=\"overlap\" attr.type=\"double\"/>\n",
+        ));
+        let _ = writeln!(xml, "  <graph id=\"tick_{}\" edgedefault=\"undirected\">", self.tick);
+        for node in &self.nodes {
+            let _ = writeln!(xml, concat!(
+                "    <node id=\"c{}\"><data key=\"mode\">{}</data><data key=\"x\">{}</data>",
+                "<data key=\"y\">{}</data><data key=\"z\">{}</data><data key=\"mass\">{}</data></node>"),
+                node.id, node.mode, node.position.x, node.position.y, node.position.z, node.mass);
+        }
+        for edge in &self.edges {
+            let _ = write!(xml, "    <edge source=\"c{}\" target=\"c{}\"><data key=\"kind\">{}</data>",
+                edge.source, edge.target, edge.kind.name());
+            let _ = match edge.kind {
+                ContactKind::Adhesion { rest_length, strain } =>
+                    write!(xml, "<data key=\"rest_length\">{}</data><data key=\"strain\">{}</data>", rest_length, strain),
+                ContactKind::Collision { overlap } => write!(xml, "<data key=\"overlap\">{}</data>", overlap),
+            };
+            xml.push_str("</edge>\n");
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+/// File format of contact graph exports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    #[default]
+    GraphMl,
+    /// nodes.csv and edges.csv
+    Csv,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 2] = [GraphFormat::GraphMl, GraphFormat::Csv];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphFormat::GraphMl => "GraphML",
+            GraphFormat::Csv => "CSV (nodes + edges)",
+        }
+    }
+
+    /// Files of `graph` as (relative path, contents), under `prefix` when given
+    pub fn files(&self, graph: &ContactGraph, prefix: Option<&str>) -> Vec<(String, Vec<u8>)> {
+        let path = |name: &str| match prefix {
+            Some(prefix) => format!("{}/{}", prefix, name),
+            None => name.to_string(),
+        };
+        match self {
+            GraphFormat::GraphMl => match prefix {
+                Some(prefix) => vec![(format!("{}.graphml", prefix), graph.to_graphml().into_bytes())],
+                None => vec![("contacts.graphml".to_string(), graph.to_graphml().into_bytes())],
+            },
+            GraphFormat::Csv => vec![
+                (path("nodes.csv"), graph.nodes_csv().into_bytes()),
+                (path("edges.csv"), graph.edges_csv().into_bytes()),
+            ],
+        }
+    }
+}
+
+/// Graphs being collected for a time series export
+struct SeriesRecording {
+    path: PathBuf,
+    format: GraphFormat,
+    interval: u32,
+    start_tick: u32,
+    next_tick: u32,
+    end_tick: u32,
+    graphs: Vec<ContactGraph>,
+}
+
+enum ExportRequest {
+    /// GraphML file, or the folder receiving nodes.csv and edges.csv
+    Snapshot { path: PathBuf, format: GraphFormat },
+    Series { path: PathBuf },
+    StopSeries,
+}
+
+/// Contact graph export options, requests and writes in flight
+///
+/// Graphs are copied out of the simulation on the main thread and written on the
+/// IO task pool, so exporting doesn't hold up the simulation.
+#[derive(Resource)]
+pub struct ContactGraphExport {
+    pub format: GraphFormat,
+    /// Ticks between the graphs of a time series
+    pub series_interval: u32,
+    /// Simulated seconds a time series covers
+    pub series_duration: f32,
+    requests: Vec<ExportRequest>,
+    series: Option<SeriesRecording>,
+    tasks: Vec<Task<crate::error::Result<PathBuf>>>,
+}
+
+impl Default for ContactGraphExport {
+    fn default() -> Self {
+        Self {
+            format: GraphFormat::default(),
+            series_interval: 64,
+            series_duration: 10.0,
+            requests: Vec::new(),
+            series: None,
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl ContactGraphExport {
+    /// Export the active simulation's graph now (`path` is a folder for CSV)
+    pub fn export(&mut self, path: PathBuf) {
+        self.requests.push(ExportRequest::Snapshot { path, format: self.format });
+    }
+
+    /// Record a graph every `series_interval` ticks for `series_duration` seconds into a zip
+    pub fn start_series(&mut self, path: PathBuf) {
+        self.requests.push(ExportRequest::Series { path });
+    }
+
+    /// Finish the time series early, writing the graphs recorded so far
+    pub fn stop_series(&mut self) {
+        self.requests.push(ExportRequest::StopSeries);
+    }
+
+    /// (graphs recorded, ticks covered, ticks total) of the running time series
+    pub fn series_progress(&self) -> Option<(usize, u32, u32)> {
+        self.series.as_ref().map(|series| {
+            let covered = series.graphs.last().map_or(0, |graph| graph.tick - series.start_tick);
+            (series.graphs.len(), covered, series.end_tick - series.start_tick)
+        })
+    }
+
+    /// Exports still being written
+    pub fn pending_writes(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Hand the recorded series to the IO pool
+    fn finish_series(&mut self) {
+        let Some(series) = self.series.take() else {
+            return;
+        };
+        let SeriesRecording { path, format, graphs, .. } = series;
+        self.tasks.push(IoTaskPool::get().spawn(async move {
+            let files: Vec<(String, Vec<u8>)> = graphs.iter()
+                .flat_map(|graph| format.files(graph, Some(&format!("tick_{:06}", graph.tick))))
+                .collect();
+            crate::error::write_atomically(&path, &stored_zip(&files))?;
+            Ok(path)
+        }));
+    }
+}
+
+/// Uncompressed ("stored") zip archive of `files`
+///
+/// Enough for the export's text files; no zip64, so the archive must stay under 4 GiB.
+pub fn stored_zip(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    // 1980-01-01 00:00 in DOS date/time
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    let mut archive = Vec::new();
+    let mut directory = Vec::new();
+    for (name, contents) in files {
+        let offset = archive.len() as u32;
+        let crc = crc32(contents);
+        let size = contents.len() as u32;
+
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        for field in [20u16, 0, 0, 0, DOS_DATE] {
+            archive.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            archive.extend_from_slice(&field.to_le_bytes());
+        }
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(contents);
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        for field in [20u16, 20, 0, 0, 0, DOS_DATE] {
+            directory.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            directory.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [name.len() as u16, 0, 0, 0, 0] {
+            directory.extend_from_slice(&field.to_le_bytes());
+        }
+        directory.extend_from_slice(&0u32.to_le_bytes());
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = archive.len() as u32;
+    archive.extend_from_slice(&directory);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    for field in [0u16, 0, files.len() as u16, files.len() as u16] {
+        archive.extend_from_slice(&field.to_le_bytes());
+    }
+    archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&directory_offset.to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes());
+    archive
+}
+
+/// CRC-32 (IEEE) as used by zip
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// Graph of the active simulation and its tick
+fn active_graph(
+    sim_state: &SimulationState,
+    main_state: Option<&MainSimState>,
+    preview_state: Option<&crate::simulation::preview_sim::PreviewSimState>,
+    genome: &GenomeData,
+    fixed_timestep: f32,
+) -> Option<ContactGraph> {
+    let (state, time, genome) = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| (&s.canonical_state, s.simulation_time, s.phases.genome(genome)))?,
+        SimulationMode::Preview => preview_state.map(|s| (&s.canonical_state, s.current_time, genome))?,
+        SimulationMode::Gpu => return None,
+    };
+    let tick = crate::simulation::clock::ticks_to_reach(time, fixed_timestep);
+    Some(ContactGraph::capture(state, genome, tick))
+}
+
+fn handle_export_requests(
+    mut export: ResMut<ContactGraphExport>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    genome: Res<crate::genome::CurrentGenome>,
+    config: Res<PhysicsConfig>,
+) {
+    for request in std::mem::take(&mut export.requests) {
+        match request {
+            ExportRequest::Snapshot { path, format } => {
+                let Some(graph) = active_graph(&sim_state, main_state.as_deref(), preview_state.as_deref(), &genome.genome, config.fixed_timestep) else {
+                    notifications.warning("No active simulation to export");
+                    continue;
+                };
+                export.tasks.push(IoTaskPool::get().spawn(async move {
+                    match format {
+                        GraphFormat::GraphMl => crate::error::write_atomically(&path, graph.to_graphml().as_bytes())?,
+                        GraphFormat::Csv => {
+                            crate::error::create_dir_all(&path)?;
+                            for (name, contents) in format.files(&graph, None) {
+                                crate::error::write_atomically(&path.join(name), &contents)?;
+                            }
+                        }
+                    }
+                    Ok(path)
+                }));
+            }
+            ExportRequest::Series { path } => {
+                if export.series.is_some() {
+                    notifications.warning("A contact graph time series is already recording");
+                    continue;
+                }
+                if sim_state.mode != SimulationMode::Cpu {
+                    notifications.warning("Contact graph time series record in CPU mode");
+                    continue;
+                }
+                let Some(main_state) = main_state.as_deref() else {
+                    continue;
+                };
+                let start = crate::simulation::clock::ticks_to_reach(main_state.simulation_time, config.fixed_timestep);
+                let interval = export.series_interval.max(1);
+                let duration = crate::simulation::clock::ticks_to_reach(export.series_duration.max(0.0), config.fixed_timestep);
+                export.series = Some(SeriesRecording {
+                    path,
+                    format: export.format,
+                    interval,
+                    start_tick: start,
+                    next_tick: start,
+                    end_tick: start + duration,
+                    graphs: Vec::new(),
+                });
+            }
+            ExportRequest::StopSeries => export.finish_series(),
+        }
+    }
+}
+
+/// Take the graphs of a time series as the CPU simulation reaches their ticks
+fn record_series(
+    mut export: ResMut<ContactGraphExport>,
+    main_state: Res<MainSimState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    config: Res<PhysicsConfig>,
+) {
+    let Some(series) = export.series.as_mut() else {
+        return;
+    };
+    let tick = crate::simulation::clock::ticks_to_reach(main_state.simulation_time, config.fixed_timestep);
+    // A reset or reload ends the series with what it has
+    if series.graphs.last().is_some_and(|graph| tick < graph.tick) {
+        export.finish_series();
+        return;
+    }
+    if tick >= series.next_tick && tick <= series.end_tick {
+        let effective = main_state.phases.genome(&genome.genome);
+        series.graphs.push(ContactGraph::capture(&main_state.canonical_state, effective, tick));
+        series.next_tick = tick + series.interval;
+    }
+    if series.next_tick > series.end_tick || tick >= series.end_tick {
+        export.finish_series();
+    }
+}
+
+fn poll_export_tasks(
+    mut export: ResMut<ContactGraphExport>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    export.tasks.retain_mut(|task| match block_on(poll_once(task)) {
+        Some(Ok(path)) => {
+            notifications.success(format!("Exported contact graph to {}", path.display()));
+            false
+        }
+        Some(Err(error)) => {
+            notifications.error(&error);
+            false
+        }
+        None => true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    /// Cells 0 and 1 adhere (stretched to 1.5 over a rest length of 1), cell 2 presses into cell 1
+    fn three_cells() -> (CanonicalState, GenomeData) {
+        let genome = GenomeData {
+            modes: vec![ModeSettings::new_self_splitting(0, "A".to_string()), ModeSettings::new_self_splitting(1, "B".to_string())],
+            ..GenomeData::default()
+        };
+        let mut state = CanonicalState::new(8);
+        for (position, mass, mode) in [(Vec3::ZERO, 1.0, 0), (Vec3::X * 1.5, 2.0, 0), (Vec3::new(1.5, 1.5, 0.0), 3.0, 1)] {
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, mass, 1.0,
+                0, mode, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+        (state, genome)
+    }
+
+    #[test]
+    fn test_three_cells_export_their_nodes_and_edges() {
+        let (mut state, genome) = three_cells();
+        state.spatial_grid.rebuild(&state.positions, state.cell_count);
+        state.scratch.collision_pairs = crate::simulation::cpu_physics::detect_collisions_canonical(&state);
+        state.scratch.record_contacts(&state.cell_ids);
+        let ids = [state.cell_ids[0], state.cell_ids[1], state.cell_ids[2]];
+
+        let graph = ContactGraph::capture(&state, &genome, 7);
+        assert_eq!(graph.nodes, vec![
+            ContactNode { id: ids[0], mode: 0, position: Vec3::ZERO, mass: 1.0 },
+            ContactNode { id: ids[1], mode: 0, position: Vec3::X * 1.5, mass: 2.0 },
+            ContactNode { id: ids[2], mode: 1, position: Vec3::new(1.5, 1.5, 0.0), mass: 3.0 },
+        ]);
+        // The adhering pair overlaps too but doesn't collide; cell 2 is 1.5 from cell 1 and 2.12 from cell 0
+        assert_eq!(graph.edges, vec![
+            ContactEdge { source: ids[0], target: ids[1], kind: ContactKind::Adhesion { rest_length: 1.0, strain: 0.5 } },
+            ContactEdge { source: ids[1], target: ids[2], kind: ContactKind::Collision { overlap: 0.5 } },
+        ]);
+
+        assert_eq!(graph.nodes_csv(), format!(
+            "id,mode,x,y,z,mass\n{},0,0,0,0,1\n{},0,1.5,0,0,2\n{},1,1.5,1.5,0,3\n", ids[0], ids[1], ids[2]));
+        assert_eq!(graph.edges_csv(), format!(
+            "source,target,kind,rest_length,strain,overlap\n{},{},adhesion,1,0.5,\n{},{},collision,,,0.5\n",
+            ids[0], ids[1], ids[1], ids[2]));
+
+        let xml = graph.to_graphml();
+        assert!(xml.contains("<graph id=\"tick_7\" edgedefault=\"undirected\">"));
+        assert_eq!(xml.matches("<node ").count(), 3);
+        assert_eq!(xml.matches("<edge ").count(), 2);
+        assert!(xml.contains(&format!(
+            "<edge source=\"c{}\" target=\"c{}\"><data key=\"kind\">collision</data><data key=\"overlap\">0.5</data></edge>",
+            ids[1], ids[2])));
+    }
+
+    #[test]
+    fn test_physics_tick_records_contacts_by_id() {
+        let (mut state, genome) = three_cells();
+        let config = PhysicsConfig::default();
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, 0.0, false);
+        let contacts: Vec<(u32, u32)> = state.scratch.last_contacts.iter().map(|(a, b, _)| ((*a).min(*b), (*a).max(*b))).collect();
+        assert_eq!(contacts, vec![(state.cell_ids[1], state.cell_ids[2])]);
+
+        // Contacts with cells that are gone are dropped
+        state.scratch.last_contacts.push((999, state.cell_ids[0], 0.1));
+        let graph = ContactGraph::capture(&state, &genome, 1);
+        assert_eq!(graph.edges.len(), 2);
+    }
+
+    #[test]
+    fn test_stored_zip_layout() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let files = vec![("tick_000001/nodes.csv".to_string(), b"id\n".to_vec()), ("b.txt".to_string(), Vec::new())];
+        let zip = stored_zip(&files);
+        assert_eq!(&zip[..4], &[0x50, 0x4b, 0x03, 0x04]);
+        // End of central directory: 22 bytes, two entries, directory right after the data
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(&end[..4], &[0x50, 0x4b, 0x05, 0x06]);
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2);
+        let directory_size = u32::from_le_bytes(end[12..16].try_into().unwrap()) as usize;
+        let directory_offset = u32::from_le_bytes(end[16..20].try_into().unwrap()) as usize;
+        assert_eq!(directory_offset + directory_size, zip.len() - 22);
+        assert_eq!(&zip[directory_offset..directory_offset + 4], &[0x50, 0x4b, 0x01, 0x02]);
+        assert_eq!(directory_offset, 2 * 30 + "tick_000001/nodes.csv".len() + 3 + "b.txt".len());
+    }
+}
@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use crate::simulation::memory::{LazyColumn, MemoryProfile};
+use crate::simulation::scratch::ScratchBuffers;
+use crate::simulation::PhysicsMath;
 
 /// Canonical simulation state using Structure-of-Arrays (SoA) layout
 /// 
@@ -49,14 +52,22 @@ pub struct CanonicalState {
     // === Genome Orientation (SoA) ===
     /// Genome-derived orientations (used for adhesion calculations)
     /// This is separate from physics rotation and represents the cell's "design" orientation
-    pub genome_orientations: Vec<Quat>,
+    pub genome_orientations: LazyColumn<Quat>,
+    /// Bounded lineage record each genome orientation is checked (and optionally re-anchored) against
+    pub lineage_references: LazyColumn<crate::simulation::orientation_drift::LineageReference>,
+    /// Take child genome orientations from the lineage reference instead of composing
+    /// them from the parent's, so rounding error can't accumulate over generations
+    pub reanchor_genome_orientations: bool,
     
     // === Physics State (SoA) ===
     pub forces: Vec<Vec3>,
-    pub torques: Vec<Vec3>,
+    pub torques: LazyColumn<Vec3>,
     pub accelerations: Vec<Vec3>,
-    pub prev_accelerations: Vec<Vec3>,
+    pub prev_accelerations: LazyColumn<Vec3>,
     pub stiffnesses: Vec<f32>,
+    /// Contact pressure proxy: summed collision overlap from this tick's pair list
+    /// (read by division_step for contact inhibition)
+    pub contact_pressures: LazyColumn<f32>,
     
     // === Division Timers (SoA) ===
     pub birth_times: Vec<f32>,
@@ -65,6 +76,33 @@ pub struct CanonicalState {
     pub split_counts: Vec<i32>, // Number of times this cell has split
     pub split_ready_frame: Vec<i32>, // Frame when cell first became ready to split (-1 = not ready)
     
+    // === Nutrient State (SoA) ===
+    /// Low-nutrient priority boost ("survival mode") engaged flags, one bit per cell
+    /// Kept in canonical state so the prioritize_when_low hysteresis survives resimulation
+    pub low_nutrient_boost: Vec<u64>,
+    
+    // === Pinning (SoA) ===
+    /// Pinned (kinematic anchor) flags, one bit per cell
+    /// Pinned cells keep their position but still push and pull their neighbors
+    pub pinned: Vec<u64>,
+    
+    // === User Data (SoA) ===
+    /// Scratch scalar channels for experiments, written by interventions and passed to
+    /// children according to each mode's `user_data_inheritance`
+    pub user_data: [LazyColumn<f32>; crate::simulation::user_data::USER_DATA_CHANNELS],
+    /// Time-averaged collision and adhesion force magnitudes for the heatmap overlay
+    pub force_heat: crate::simulation::force_heatmap::ForceHeat,
+    
+    // === Interaction ===
+    /// Soft drag in progress, applied as forces in every physics step
+    pub soft_drag: Option<crate::simulation::soft_drag::SoftDrag>,
+    /// Damping ramp in progress, multiplying the configured damping tick by tick
+    pub settle: Option<crate::simulation::settle::Settle>,
+    
+    // === Environment ===
+    /// Secreted substances; double-buffered and updated once per physics step
+    pub chemical_field: crate::simulation::ChemicalField,
+    
     // === Adhesion System ===
     /// Adhesion connections between cells
     pub adhesion_connections: crate::cell::AdhesionConnections,
@@ -73,21 +111,25 @@ pub struct CanonicalState {
     
     /// Spatial partitioning for collision detection
     pub spatial_grid: DeterministicSpatialGrid,
+    /// Candidate collision pairs reused across ticks while cells barely move
+    pub collision_cache: crate::simulation::collision_cache::CollisionPairCache,
     
     /// Next cell ID to assign (monotonically increasing)
     pub next_cell_id: u32,
+    /// Serials of the tissue stamps placed into this state, so replays place each once
+    pub applied_stamps: Vec<u32>,
     
     // === Pre-allocated Scratch Buffers (avoid per-frame allocations) ===
-    /// Pre-allocated collision pairs buffer (reused each frame)
-    pub collision_pairs_buffer: Vec<CanonicalCollisionPair>,
+    /// Per-tick collision, force and division buffers
+    pub scratch: ScratchBuffers,
     /// Pre-allocated mass deltas buffer for nutrient transport
     pub mass_deltas_buffer: Vec<f32>,
     /// Pre-allocated cells to remove buffer
     pub cells_to_remove_buffer: Vec<usize>,
-    /// Cached adhesion settings from genome (rebuilt when genome changes)
-    pub cached_adhesion_settings: Vec<crate::cell::AdhesionSettings>,
-    /// Hash of genome modes to detect changes
-    pub genome_modes_hash: u64,
+    /// Genome compiled for the physics stages (rebuilt when the genome changes)
+    pub compiled_genome: crate::genome::CompiledGenomeCache,
+    /// Hash of the genome's split timing fields the per-cell thresholds were resolved from
+    pub split_thresholds_hash: u64,
     
     // === Division scratch buffers ===
     /// Pre-allocated buffer for tracking which cells have already split this tick
@@ -96,8 +138,40 @@ pub struct CanonicalState {
     pub divisions_to_process_buffer: Vec<usize>,
     /// Pre-allocated buffer for filtered divisions
     pub filtered_divisions_buffer: Vec<usize>,
+    /// Per-cell marks for choosing non-conflicting divisions (see `select_independent_divisions`)
+    pub division_marks_buffer: Vec<u8>,
     /// Pre-allocated buffer for division events
     pub division_events_buffer: Vec<DivisionEvent>,
+
+    // === Event buffers (cleared at the start of each physics step) ===
+    /// IDs of cells removed this step (starvation)
+    pub removed_cell_ids_buffer: Vec<u32>,
+    /// Last positions of the removed cells, parallel to `removed_cell_ids_buffer`
+    pub removed_cell_positions_buffer: Vec<Vec3>,
+    /// Adhesions broken by cell removal this step, as (removed cell ID, partner cell ID)
+    pub broken_adhesions_buffer: Vec<(u32, u32)>,
+    /// Relative speed of the two cells of each broken adhesion, parallel to `broken_adhesions_buffer`
+    pub broken_adhesion_speeds_buffer: Vec<f32>,
+
+    /// Division and deferral counters (shown in the Performance Monitor)
+    pub division_stats: DivisionStats,
+
+    /// Allocation strategy for the lazily grown columns
+    pub memory_profile: MemoryProfile,
+}
+
+/// Division counters of a simulation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DivisionStats {
+    /// Divisions performed since the state was created
+    pub divisions: u64,
+    /// Times a ready cell waited for an adhered neighbor to divide first
+    pub deferred: u64,
+    /// Divisions and deferrals in the most recent division step
+    pub last_step_divisions: u32,
+    pub last_step_deferred: u32,
+    /// Passes the most recent division step needed
+    pub last_step_passes: u32,
 }
 
 impl CanonicalState {
@@ -108,12 +182,14 @@ impl CanonicalState {
     
     /// Create a new canonical state with specified capacity and grid density
     pub fn with_grid_density(capacity: usize, grid_density: u32) -> Self {
+        Self::with_memory_profile(capacity, grid_density, MemoryProfile::Full)
+    }
+    
+    /// Create a new canonical state with specified capacity, grid density and memory profile
+    pub fn with_memory_profile(capacity: usize, grid_density: u32, memory_profile: MemoryProfile) -> Self {
         // Calculate adhesion connection capacity (20 connections per cell)
         let adhesion_capacity = capacity * crate::cell::MAX_ADHESIONS_PER_CELL;
         
-        // Pre-allocate collision buffer for worst case (every cell collides with ~10 others)
-        let collision_buffer_capacity = capacity * 10;
-        
         Self {
             cell_count: 0,
             capacity,
@@ -127,64 +203,267 @@ impl CanonicalState {
             mode_indices: vec![0; capacity],
             rotations: vec![Quat::IDENTITY; capacity],
             angular_velocities: vec![Vec3::ZERO; capacity],
-            genome_orientations: vec![Quat::IDENTITY; capacity],
+            genome_orientations: LazyColumn::new(memory_profile, capacity, Quat::IDENTITY),
+            lineage_references: LazyColumn::new(memory_profile, capacity, Default::default()),
+            reanchor_genome_orientations: false,
             forces: vec![Vec3::ZERO; capacity],
-            torques: vec![Vec3::ZERO; capacity],
+            torques: LazyColumn::new(memory_profile, capacity, Vec3::ZERO),
             accelerations: vec![Vec3::ZERO; capacity],
-            prev_accelerations: vec![Vec3::ZERO; capacity],
+            prev_accelerations: LazyColumn::new(memory_profile, capacity, Vec3::ZERO),
             stiffnesses: vec![10.0; capacity],
+            contact_pressures: LazyColumn::new(memory_profile, capacity, 0.0),
             birth_times: vec![0.0; capacity],
             split_intervals: vec![10.0; capacity],
             split_masses: vec![1.5; capacity],
             split_counts: vec![0; capacity],
             split_ready_frame: vec![-1; capacity],
+            low_nutrient_boost: vec![0; capacity.div_ceil(64)],
+            pinned: vec![0; capacity.div_ceil(64)],
+            user_data: std::array::from_fn(|_| LazyColumn::new(memory_profile, capacity, 0.0)),
+            force_heat: Default::default(),
+            soft_drag: None,
+            settle: None,
+            chemical_field: Default::default(),
             adhesion_connections: crate::cell::AdhesionConnections::new(adhesion_capacity),
             adhesion_manager: crate::cell::AdhesionConnectionManager::new(capacity),
-            spatial_grid: DeterministicSpatialGrid::new(grid_density, 200.0, 100.0),
+            spatial_grid: DeterministicSpatialGrid::new(grid_density, 200.0, 100.0).with_cell_capacity(capacity),
+            collision_cache: Default::default(),
             next_cell_id: 0,
+            applied_stamps: Vec::new(),
             // Pre-allocated scratch buffers
-            collision_pairs_buffer: Vec::with_capacity(collision_buffer_capacity),
+            scratch: ScratchBuffers::with_capacity(capacity),
             mass_deltas_buffer: vec![0.0; capacity],
             cells_to_remove_buffer: Vec::with_capacity(256),
-            cached_adhesion_settings: Vec::with_capacity(32), // Typical genome has <32 modes
-            genome_modes_hash: 0,
+            compiled_genome: Default::default(),
+            split_thresholds_hash: 0,
             // Division scratch buffers
             already_split_buffer: vec![false; capacity],
             divisions_to_process_buffer: Vec::with_capacity(256),
             filtered_divisions_buffer: Vec::with_capacity(256),
+            division_marks_buffer: vec![0; capacity],
             division_events_buffer: Vec::with_capacity(256),
+            removed_cell_ids_buffer: Vec::with_capacity(64),
+            removed_cell_positions_buffer: Vec::with_capacity(64),
+            broken_adhesions_buffer: Vec::with_capacity(64),
+            broken_adhesion_speeds_buffer: Vec::with_capacity(64),
+            division_stats: DivisionStats::default(),
+            memory_profile,
+        }
+    }
+
+    /// Make cell slots `0..count` addressable in the lazily grown columns
+    /// (a no-op under the Full profile, where they are allocated up front)
+    pub fn reserve_cell_slots(&mut self, count: usize) {
+        let count = count.min(self.capacity);
+        self.genome_orientations.ensure_len(count);
+        self.lineage_references.ensure_len(count);
+        self.torques.ensure_len(count);
+        self.prev_accelerations.ensure_len(count);
+        self.contact_pressures.ensure_len(count);
+        for channel in &mut self.user_data {
+            channel.ensure_len(count);
+        }
+    }
+
+    /// Copy this state's contents into `target`, a freshly allocated state with at least this capacity
+    ///
+    /// Only the live part (cells 0..cell_count, adhesion slots 0..active_count) is
+    /// copied, so allocating the larger buffers can happen on another thread and
+    /// the copy itself stays cheap. The result simulates exactly like `self`; it
+    /// can just hold more cells and adhesions.
+    pub fn transfer_into(&self, target: &mut CanonicalState) {
+        assert!(target.capacity >= self.capacity, "capacity can only grow");
+        let n = self.cell_count;
+
+        target.cell_count = n;
+        target.reserve_cell_slots(n);
+        target.cell_ids[..n].copy_from_slice(&self.cell_ids[..n]);
+        target.positions[..n].copy_from_slice(&self.positions[..n]);
+        target.prev_positions[..n].copy_from_slice(&self.prev_positions[..n]);
+        target.velocities[..n].copy_from_slice(&self.velocities[..n]);
+        target.masses[..n].copy_from_slice(&self.masses[..n]);
+        target.radii[..n].copy_from_slice(&self.radii[..n]);
+        target.genome_ids[..n].copy_from_slice(&self.genome_ids[..n]);
+        target.mode_indices[..n].copy_from_slice(&self.mode_indices[..n]);
+        target.rotations[..n].copy_from_slice(&self.rotations[..n]);
+        target.angular_velocities[..n].copy_from_slice(&self.angular_velocities[..n]);
+        target.genome_orientations[..n].copy_from_slice(&self.genome_orientations[..n]);
+        target.lineage_references[..n].copy_from_slice(&self.lineage_references[..n]);
+        target.reanchor_genome_orientations = self.reanchor_genome_orientations;
+        target.forces[..n].copy_from_slice(&self.forces[..n]);
+        target.torques[..n].copy_from_slice(&self.torques[..n]);
+        target.accelerations[..n].copy_from_slice(&self.accelerations[..n]);
+        target.prev_accelerations[..n].copy_from_slice(&self.prev_accelerations[..n]);
+        target.stiffnesses[..n].copy_from_slice(&self.stiffnesses[..n]);
+        target.contact_pressures[..n].copy_from_slice(&self.contact_pressures[..n]);
+        target.birth_times[..n].copy_from_slice(&self.birth_times[..n]);
+        target.split_intervals[..n].copy_from_slice(&self.split_intervals[..n]);
+        target.split_masses[..n].copy_from_slice(&self.split_masses[..n]);
+        target.split_counts[..n].copy_from_slice(&self.split_counts[..n]);
+        target.split_ready_frame[..n].copy_from_slice(&self.split_ready_frame[..n]);
+        target.low_nutrient_boost[..self.low_nutrient_boost.len()].copy_from_slice(&self.low_nutrient_boost);
+        target.pinned[..self.pinned.len()].copy_from_slice(&self.pinned);
+        for (target_channel, channel) in target.user_data.iter_mut().zip(&self.user_data) {
+            target_channel[..n].copy_from_slice(&channel[..n]);
         }
+        target.force_heat.clone_from(&self.force_heat);
+        target.soft_drag.clone_from(&self.soft_drag);
+        target.settle = self.settle;
+        target.chemical_field.clone_from(&self.chemical_field);
+        target.collision_cache.invalidate();
+
+        let source = &self.adhesion_connections;
+        let connections = &mut target.adhesion_connections;
+        let a = source.active_count;
+        connections.active_count = a;
+        connections.cell_a_index[..a].copy_from_slice(&source.cell_a_index[..a]);
+        connections.cell_b_index[..a].copy_from_slice(&source.cell_b_index[..a]);
+        connections.mode_index[..a].copy_from_slice(&source.mode_index[..a]);
+        connections.is_active[..a].copy_from_slice(&source.is_active[..a]);
+        connections.zone_a[..a].copy_from_slice(&source.zone_a[..a]);
+        connections.zone_b[..a].copy_from_slice(&source.zone_b[..a]);
+        connections.anchor_direction_a[..a].copy_from_slice(&source.anchor_direction_a[..a]);
+        connections.anchor_direction_b[..a].copy_from_slice(&source.anchor_direction_b[..a]);
+        connections.twist_reference_a[..a].copy_from_slice(&source.twist_reference_a[..a]);
+        connections.twist_reference_b[..a].copy_from_slice(&source.twist_reference_b[..a]);
+        connections.quality_tier[..a].copy_from_slice(&source.quality_tier[..a]);
+        connections.calm_ticks[..a].copy_from_slice(&source.calm_ticks[..a]);
+        connections.force_limit_hits.clone_from(&source.force_limit_hits);
+        let indices = &self.adhesion_manager.cell_adhesion_indices;
+        target.adhesion_manager.cell_adhesion_indices[..indices.len()].copy_from_slice(indices);
+
+        target.next_cell_id = self.next_cell_id;
+        target.applied_stamps.clone_from(&self.applied_stamps);
+        target.compiled_genome.clone_from(&self.compiled_genome);
+        target.split_thresholds_hash = self.split_thresholds_hash;
+        target.removed_cell_ids_buffer.clone_from(&self.removed_cell_ids_buffer);
+        target.removed_cell_positions_buffer.clone_from(&self.removed_cell_positions_buffer);
+        target.broken_adhesions_buffer.clone_from(&self.broken_adhesions_buffer);
+        target.broken_adhesion_speeds_buffer.clone_from(&self.broken_adhesion_speeds_buffer);
+        target.division_stats = self.division_stats;
+    }
+
+    /// Copy of this state with a larger capacity (allocates on the calling thread)
+    pub fn grown(&self, capacity: usize) -> CanonicalState {
+        let mut target = CanonicalState::with_memory_profile(capacity, self.spatial_grid.grid_dimensions.x, self.memory_profile);
+        self.transfer_into(&mut target);
+        target
     }
     
-    /// Update cached adhesion settings from genome if needed
-    /// Returns true if cache was updated
-    pub fn update_adhesion_settings_cache(&mut self, genome: &crate::genome::GenomeData) -> bool {
-        // Simple hash based on mode count and first mode's stiffness
-        // This catches most genome changes without expensive full comparison
-        let new_hash = (genome.modes.len() as u64) << 32 
-            | (genome.modes.first().map(|m| (m.adhesion_settings.linear_spring_stiffness * 1000.0) as u64).unwrap_or(0));
-        
-        if new_hash != self.genome_modes_hash || self.cached_adhesion_settings.len() != genome.modes.len() {
-            self.cached_adhesion_settings.clear();
-            for mode in &genome.modes {
-                self.cached_adhesion_settings.push(crate::cell::AdhesionSettings {
-                    can_break: mode.adhesion_settings.can_break,
-                    break_force: mode.adhesion_settings.break_force,
-                    rest_length: mode.adhesion_settings.rest_length,
-                    linear_spring_stiffness: mode.adhesion_settings.linear_spring_stiffness,
-                    linear_spring_damping: mode.adhesion_settings.linear_spring_damping,
-                    orientation_spring_stiffness: mode.adhesion_settings.orientation_spring_stiffness,
-                    orientation_spring_damping: mode.adhesion_settings.orientation_spring_damping,
-                    max_angular_deviation: mode.adhesion_settings.max_angular_deviation,
-                    twist_constraint_stiffness: mode.adhesion_settings.twist_constraint_stiffness,
-                    twist_constraint_damping: mode.adhesion_settings.twist_constraint_damping,
-                    enable_twist_constraint: mode.adhesion_settings.enable_twist_constraint,
-                });
+    /// Number of cells in each of the first `mode_count` modes
+    pub fn cells_per_mode(&self, mode_count: usize) -> Vec<usize> {
+        let mut counts = vec![0; mode_count];
+        for &mode in &self.mode_indices[..self.cell_count] {
+            if let Some(count) = counts.get_mut(mode) {
+                *count += 1;
             }
-            self.genome_modes_hash = new_hash;
-            return true;
         }
-        false
+        counts
+    }
+    
+    /// Whether cell `i` divides at `current_time`, and if not, whether it is merely not due
+    /// yet (too young, too light, or never splits) or held back by a limit
+    pub fn division_readiness(&self, genome: &crate::genome::CompiledGenome, i: usize, current_time: f32) -> DivisionReadiness {
+        let mode = genome.mode(self.mode_indices[i]);
+        
+        // Check mass threshold - cells must have enough mass to split (using per-cell split_mass)
+        let can_split_by_mass = self.masses[i] >= self.split_masses[i];
+        
+        // Check time threshold - cells must be old enough to split
+        let can_split_by_time = current_time - self.birth_times[i] >= self.split_intervals[i];
+        
+        if !can_split_by_mass || !can_split_by_time || self.split_intervals[i] > crate::genome::NEVER_SPLIT_INTERVAL {
+            return DivisionReadiness::NotDue;
+        }
+        let Some(m) = mode else {
+            return DivisionReadiness::Ready;
+        };
+        
+        // Check max_splits limit (-1 means infinite)
+        if m.reached_split_limit(self.split_counts[i]) {
+            return DivisionReadiness::Blocked(DivisionBlock::MaxSplits);
+        }
+        
+        // Check adhesion limits - prevent splitting if at/above max or below min connections
+        let adhesion_count = self.adhesion_manager.count_active_adhesions(i);
+        if adhesion_count < m.min_adhesions {
+            return DivisionReadiness::Blocked(DivisionBlock::TooFewAdhesions);
+        }
+        if adhesion_count >= m.max_adhesions {
+            return DivisionReadiness::Blocked(DivisionBlock::TooManyAdhesions);
+        }
+        
+        // Check contact inhibition - crowded cells wait until the pressure drops
+        if self.contact_pressures[i] > m.max_contact_pressure {
+            return DivisionReadiness::Blocked(DivisionBlock::ContactPressure);
+        }
+        
+        DivisionReadiness::Ready
+    }
+    
+    /// Choose which of the ready cells (`divisions_to_process_buffer`) divide this pass
+    ///
+    /// Ready cells joined by an active adhesion conflict. Candidates are visited in
+    /// cell ID order and taken unless an adhered neighbor was already taken, giving a
+    /// maximal independent set of the conflict graph: every cell left out has a
+    /// neighbor that divides now. The result goes to `filtered_divisions_buffer` in
+    /// index order.
+    pub fn select_independent_divisions(&mut self) {
+        select_independent(
+            &self.divisions_to_process_buffer,
+            &self.cell_ids,
+            &self.adhesion_manager,
+            &self.adhesion_connections,
+            &mut self.division_marks_buffer,
+            &mut self.filtered_divisions_buffer,
+        );
+    }
+    
+    /// Resolve a cell's split interval and split mass from its mode
+    /// 
+    /// Randomized ranges are sampled with the cell's own id and birth tick, so the
+    /// same cell always resolves to the same thresholds for a given seed.
+    pub fn resolve_split_thresholds(
+        &mut self,
+        idx: usize,
+        genome: &crate::genome::GenomeData,
+        fixed_timestep: f32,
+        rng_seed: u64,
+    ) {
+        let cell_id = self.cell_ids[idx];
+        let birth_tick = crate::simulation::clock::tick_at_time(self.birth_times[idx], fixed_timestep);
+        let (split_interval, split_mass) = match genome.modes.get(self.mode_indices[idx]) {
+            Some(mode) => (
+                mode.get_phased_split_interval(cell_id, birth_tick, rng_seed),
+                mode.get_split_mass(cell_id, birth_tick, rng_seed),
+            ),
+            None => (5.0, 1.5),
+        };
+        self.split_intervals[idx] = split_interval;
+        self.split_masses[idx] = split_mass;
+    }
+    
+    /// Re-resolve every living cell's split thresholds if the genome's split timing changed
+    /// Returns true if the thresholds were recomputed
+    /// 
+    /// `compiled` hashes the split timing fields once per genome change, so ticks where
+    /// nothing changed cost a single comparison.
+    pub fn update_split_thresholds(
+        &mut self,
+        genome: &crate::genome::GenomeData,
+        compiled: &crate::genome::CompiledGenome,
+        fixed_timestep: f32,
+        rng_seed: u64,
+    ) -> bool {
+        let new_hash = hash_u64(rng_seed ^ compiled.split_timing_hash);
+        if new_hash == self.split_thresholds_hash {
+            return false;
+        }
+        for idx in 0..self.cell_count {
+            self.resolve_split_thresholds(idx, genome, fixed_timestep, rng_seed);
+        }
+        self.split_thresholds_hash = new_hash;
+        true
     }
     
     /// Add a new cell to the canonical state
@@ -211,6 +490,7 @@ impl CanonicalState {
         }
         
         let idx = self.cell_count;
+        self.reserve_cell_slots(idx + 1);
         self.cell_ids[idx] = self.next_cell_id;
         self.positions[idx] = position;
         self.prev_positions[idx] = position;
@@ -222,25 +502,100 @@ impl CanonicalState {
         self.rotations[idx] = rotation;
         self.angular_velocities[idx] = angular_velocity;
         self.genome_orientations[idx] = genome_orientation;
+        self.lineage_references[idx] = crate::simulation::orientation_drift::LineageReference::founder(genome_orientation);
         self.forces[idx] = Vec3::ZERO;
         self.torques[idx] = Vec3::ZERO;
         self.accelerations[idx] = Vec3::ZERO;
         self.prev_accelerations[idx] = Vec3::ZERO;
         self.stiffnesses[idx] = stiffness;
+        self.contact_pressures[idx] = 0.0;
         self.birth_times[idx] = birth_time;
         self.split_intervals[idx] = split_interval;
         self.split_masses[idx] = split_mass;
         self.split_counts[idx] = split_count;
         self.split_ready_frame[idx] = -1; // Not ready to split yet
+        self.set_low_nutrient_boost(idx, false);
+        self.set_pinned(idx, false);
+        self.set_user_data(idx, [0.0; crate::simulation::user_data::USER_DATA_CHANNELS]);
+        self.clear_force_heat(idx);
         
         // Initialize adhesion indices for new cell
         self.adhesion_manager.init_cell_adhesion_indices(idx);
         
         self.next_cell_id += 1;
         self.cell_count += 1;
+        self.collision_cache.invalidate();
         
         Some(idx)
     }
+    
+    /// Whether the low-nutrient priority boost is engaged for a cell
+    #[inline]
+    pub fn low_nutrient_boost(&self, idx: usize) -> bool {
+        (self.low_nutrient_boost[idx / 64] >> (idx % 64)) & 1 != 0
+    }
+    
+    /// Engage or release the low-nutrient priority boost for a cell
+    #[inline]
+    pub fn set_low_nutrient_boost(&mut self, idx: usize, engaged: bool) {
+        let bit = 1u64 << (idx % 64);
+        if engaged {
+            self.low_nutrient_boost[idx / 64] |= bit;
+        } else {
+            self.low_nutrient_boost[idx / 64] &= !bit;
+        }
+    }
+    
+    /// Whether a cell is pinned in place
+    #[inline]
+    pub fn is_pinned(&self, idx: usize) -> bool {
+        (self.pinned[idx / 64] >> (idx % 64)) & 1 != 0
+    }
+    
+    /// Pin or unpin a cell; pinning stops its linear motion immediately
+    pub fn set_pinned(&mut self, idx: usize, pinned: bool) {
+        let bit = 1u64 << (idx % 64);
+        if pinned {
+            self.pinned[idx / 64] |= bit;
+            self.velocities[idx] = Vec3::ZERO;
+            self.accelerations[idx] = Vec3::ZERO;
+        } else {
+            self.pinned[idx / 64] &= !bit;
+        }
+    }
+    
+    /// Indices of the pinned living cells
+    pub fn pinned_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        let cell_count = self.cell_count;
+        self.pinned.iter().enumerate().flat_map(move |(word_index, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(word_index * 64 + bit)
+            })
+        }).take_while(move |&idx| idx < cell_count)
+    }
+    
+    /// Zero the linear motion of pinned cells so the next position update leaves them in place
+    /// (called after the velocity update of every physics step)
+    pub fn hold_pinned_cells(&mut self) {
+        for word_index in 0..self.pinned.len() {
+            let mut bits = self.pinned[word_index];
+            while bits != 0 {
+                let idx = word_index * 64 + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                if idx >= self.cell_count {
+                    return;
+                }
+                self.velocities[idx] = Vec3::ZERO;
+                self.accelerations[idx] = Vec3::ZERO;
+            }
+        }
+    }
 }
 
 /// Deterministic spatial grid using fixed-size arrays and prefix-sum algorithm
@@ -307,6 +662,15 @@ impl DeterministicSpatialGrid {
         }
     }
     
+    /// Make room for `capacity` cells in the contents buffer (it never shrinks below 10K)
+    pub fn with_cell_capacity(mut self, capacity: usize) -> Self {
+        if capacity > self.cell_contents.len() {
+            self.cell_contents.resize(capacity, 0);
+            self.used_grid_cells.reserve(capacity);
+        }
+        self
+    }
+
     /// Precompute which grid cells are within the spherical boundary
     /// 
     /// Note: We include ALL grid cells without culling at the sphere boundary.
@@ -334,12 +698,18 @@ impl DeterministicSpatialGrid {
     }
     
     /// Convert world position to grid coordinates
-    fn world_to_grid(&self, position: Vec3) -> IVec3 {
-        let offset_position = position + Vec3::splat(self.world_size / 2.0);
-        let grid_pos = offset_position / self.cell_size;
+    pub(crate) fn world_to_grid(&self, position: Vec3) -> IVec3 {
+        Self::coord_of(position, self.world_size, self.cell_size, self.grid_dimensions.x)
+    }
+
+    /// Grid coordinates of `position` in a grid of `dimension` cells of `cell_size` per axis
+    /// spanning `world_size` around the origin
+    pub(crate) fn coord_of(position: Vec3, world_size: f32, cell_size: f32, dimension: u32) -> IVec3 {
+        let offset_position = position + Vec3::splat(world_size / 2.0);
+        let grid_pos = offset_position / cell_size;
         
         // Clamp to grid dimensions
-        let max_coord = (self.grid_dimensions.x - 1) as i32;
+        let max_coord = (dimension - 1) as i32;
         IVec3::new(
             (grid_pos.x as i32).clamp(0, max_coord),
             (grid_pos.y as i32).clamp(0, max_coord),
@@ -348,7 +718,7 @@ impl DeterministicSpatialGrid {
     }
     
     /// Find the index of a grid coordinate in the active_cells array
-    fn active_cell_index(&self, grid_coord: IVec3) -> Option<usize> {
+    pub(crate) fn active_cell_index(&self, grid_coord: IVec3) -> Option<usize> {
         self.active_cell_map.get(&grid_coord).copied()
     }
     
@@ -469,108 +839,98 @@ pub struct CanonicalCollisionPair {
     pub normal: Vec3, // Points from A to B
 }
 
-/// Detect collisions using the deterministic spatial grid - Single-threaded version
-/// Returns collision pairs using cell indices (not IDs or entities)
-pub fn detect_collisions_canonical_st(
+/// Forward neighbors for half-space optimization (13 neighbors instead of 27)
+const FORWARD_NEIGHBORS: [IVec3; 13] = [
+    IVec3::new(1, 0, 0),
+    IVec3::new(-1, 1, 0), IVec3::new(0, 1, 0), IVec3::new(1, 1, 0),
+    IVec3::new(-1, -1, 1), IVec3::new(0, -1, 1), IVec3::new(1, -1, 1),
+    IVec3::new(-1, 0, 1), IVec3::new(0, 0, 1), IVec3::new(1, 0, 1),
+    IVec3::new(-1, 1, 1), IVec3::new(0, 1, 1), IVec3::new(1, 1, 1),
+];
+
+/// Append the overlapping pairs found from one occupied grid cell
+/// (pairs within it, then pairs with its forward neighbors)
+fn collect_grid_cell_pairs(
     state: &CanonicalState,
-) -> Vec<CanonicalCollisionPair> {
-    let mut collision_pairs = Vec::new();
-    
-    // Forward neighbors for half-space optimization (13 neighbors instead of 27)
-    const FORWARD_NEIGHBORS: [IVec3; 13] = [
-        IVec3::new(1, 0, 0),
-        IVec3::new(-1, 1, 0), IVec3::new(0, 1, 0), IVec3::new(1, 1, 0),
-        IVec3::new(-1, -1, 1), IVec3::new(0, -1, 1), IVec3::new(1, -1, 1),
-        IVec3::new(-1, 0, 1), IVec3::new(0, 0, 1), IVec3::new(1, 0, 1),
-        IVec3::new(-1, 1, 1), IVec3::new(0, 1, 1), IVec3::new(1, 1, 1),
-    ];
+    grid_idx: usize,
+    collision_pairs: &mut Vec<CanonicalCollisionPair>,
+) {
+    let grid_coord = state.spatial_grid.active_cells[grid_idx];
+    let cells_in_grid = state.spatial_grid.get_cell_contents(grid_idx);
     
-    // Iterate through only the grid cells that contain simulation cells
-    for &grid_idx in &state.spatial_grid.used_grid_cells {
-        let grid_coord = state.spatial_grid.active_cells[grid_idx];
-        let cells_in_grid = state.spatial_grid.get_cell_contents(grid_idx);
-        
-        // Check collisions within the same grid cell
-        for i in 0..cells_in_grid.len() {
-            let idx_a = cells_in_grid[i];
-            for j in (i + 1)..cells_in_grid.len() {
-                let idx_b = cells_in_grid[j];
-                
-                // Calculate distance between cells
-                let delta = state.positions[idx_b] - state.positions[idx_a];
-                let distance = delta.length();
-                
-                // Check for overlap
-                let combined_radius = state.radii[idx_a] + state.radii[idx_b];
-                if distance < combined_radius {
-                    // Skip collision if cells are in the same organism
-                    if are_cells_in_same_organism(state, idx_a, idx_b) {
-                        continue;
-                    }
-                    
-                    let overlap = combined_radius - distance;
-                    let normal = if distance > 0.0001 {
-                        delta / distance
-                    } else {
-                        Vec3::X
-                    };
-                    
-                    collision_pairs.push(CanonicalCollisionPair {
-                        index_a: idx_a,
-                        index_b: idx_b,
-                        overlap,
-                        normal,
-                    });
-                }
-            }
-        }
+    let mut consider = |idx_a: usize, idx_b: usize| {
+        // Calculate distance between cells
+        let delta = state.positions[idx_b] - state.positions[idx_a];
+        let distance = delta.length();
         
-        // Check forward neighbors to avoid duplicate checks
-        for &offset in &FORWARD_NEIGHBORS {
-            let neighbor_coord = grid_coord + offset;
+        // Check for overlap
+        let combined_radius = state.radii[idx_a] + state.radii[idx_b];
+        if distance < combined_radius {
+            // Skip collision if cells are in the same organism
+            if are_cells_in_same_organism(state, idx_a, idx_b) {
+                return;
+            }
             
-            // Find neighbor in active cells
-            let Some(neighbor_idx) = state.spatial_grid.active_cell_index(neighbor_coord) else {
-                continue;
+            let overlap = combined_radius - distance;
+            let normal = if distance > 0.0001 {
+                delta / distance
+            } else {
+                Vec3::X
             };
             
-            let neighbor_cells = state.spatial_grid.get_cell_contents(neighbor_idx);
-            
-            // Check all pairs between current cell and neighbor cell
-            for &idx_a in cells_in_grid {
-                for &idx_b in neighbor_cells {
-                    // Calculate distance between cells
-                    let delta = state.positions[idx_b] - state.positions[idx_a];
-                    let distance = delta.length();
-                    
-                    // Check for overlap
-                    let combined_radius = state.radii[idx_a] + state.radii[idx_b];
-                    if distance < combined_radius {
-                        // Skip collision if cells are in the same organism
-                        if are_cells_in_same_organism(state, idx_a, idx_b) {
-                            continue;
-                        }
-                        
-                        let overlap = combined_radius - distance;
-                        let normal = if distance > 0.0001 {
-                            delta / distance
-                        } else {
-                            Vec3::X
-                        };
-                        
-                        collision_pairs.push(CanonicalCollisionPair {
-                            index_a: idx_a,
-                            index_b: idx_b,
-                            overlap,
-                            normal,
-                        });
-                    }
-                }
+            collision_pairs.push(CanonicalCollisionPair {
+                index_a: idx_a,
+                index_b: idx_b,
+                overlap,
+                normal,
+            });
+        }
+    };
+    
+    // Check collisions within the same grid cell
+    for i in 0..cells_in_grid.len() {
+        for j in (i + 1)..cells_in_grid.len() {
+            consider(cells_in_grid[i], cells_in_grid[j]);
+        }
+    }
+    
+    // Check forward neighbors to avoid duplicate checks
+    for &offset in &FORWARD_NEIGHBORS {
+        // Find neighbor in active cells
+        let Some(neighbor_idx) = state.spatial_grid.active_cell_index(grid_coord + offset) else {
+            continue;
+        };
+        
+        // Check all pairs between current cell and neighbor cell
+        for &idx_a in cells_in_grid {
+            for &idx_b in state.spatial_grid.get_cell_contents(neighbor_idx) {
+                consider(idx_a, idx_b);
             }
         }
     }
+}
+
+/// Detect collisions using the deterministic spatial grid - Single-threaded version
+/// Returns collision pairs using cell indices (not IDs or entities)
+pub fn detect_collisions_canonical_st(
+    state: &CanonicalState,
+) -> Vec<CanonicalCollisionPair> {
+    let mut scratch = ScratchBuffers::default();
+    detect_collisions_canonical_st_into(state, &mut scratch);
+    scratch.collision_pairs
+}
+
+/// Single-threaded collision detection into `scratch.collision_pairs`
+pub fn detect_collisions_canonical_st_into(
+    state: &CanonicalState,
+    scratch: &mut ScratchBuffers,
+) {
+    scratch.collision_pairs.clear();
     
-    collision_pairs
+    // Iterate through only the grid cells that contain simulation cells
+    for &grid_idx in &state.spatial_grid.used_grid_cells {
+        collect_grid_cell_pairs(state, grid_idx, &mut scratch.collision_pairs);
+    }
 }
 
 /// Detect collisions using the deterministic spatial grid - Multithreaded version
@@ -581,113 +941,49 @@ pub fn detect_collisions_canonical_st(
 pub fn detect_collisions_canonical(
     state: &CanonicalState,
 ) -> Vec<CanonicalCollisionPair> {
+    let mut scratch = ScratchBuffers::default();
+    detect_collisions_canonical_into(state, &mut scratch);
+    scratch.collision_pairs
+}
+
+/// Multithreaded collision detection into `scratch.collision_pairs`
+/// 
+/// Each chunk of grid cells fills its own reused pair list; the lists are then
+/// concatenated and sorted, so the result matches `detect_collisions_canonical_st`
+/// up to ordering and allocates nothing once the buffers have grown.
+pub fn detect_collisions_canonical_into(
+    state: &CanonicalState,
+    scratch: &mut ScratchBuffers,
+) {
     use rayon::prelude::*;
     
-    // Forward neighbors for half-space optimization (13 neighbors instead of 27)
-    const FORWARD_NEIGHBORS: [IVec3; 13] = [
-        IVec3::new(1, 0, 0),
-        IVec3::new(-1, 1, 0), IVec3::new(0, 1, 0), IVec3::new(1, 1, 0),
-        IVec3::new(-1, -1, 1), IVec3::new(0, -1, 1), IVec3::new(1, -1, 1),
-        IVec3::new(-1, 0, 1), IVec3::new(0, 0, 1), IVec3::new(1, 0, 1),
-        IVec3::new(-1, 1, 1), IVec3::new(0, 1, 1), IVec3::new(1, 1, 1),
-    ];
+    let grid_cells = &state.spatial_grid.used_grid_cells;
+    let max_chunks = rayon::current_num_threads() * crate::simulation::scratch::PAIR_CHUNKS_PER_THREAD;
+    let chunk_len = grid_cells.len().div_ceil(max_chunks).max(1);
+    let chunk_count = grid_cells.len().div_ceil(chunk_len);
+    if scratch.pair_chunks.len() < chunk_count {
+        scratch.pair_chunks.resize_with(chunk_count, Vec::new);
+    }
     
-    // Process each grid cell in parallel
-    let mut collision_pairs: Vec<CanonicalCollisionPair> = state.spatial_grid.used_grid_cells
-        .par_iter()
-        .flat_map(|&grid_idx| {
-            let mut local_pairs = Vec::new();
-            let grid_coord = state.spatial_grid.active_cells[grid_idx];
-            let cells_in_grid = state.spatial_grid.get_cell_contents(grid_idx);
-            
-            // Check collisions within the same grid cell
-            for i in 0..cells_in_grid.len() {
-                let idx_a = cells_in_grid[i];
-                for j in (i + 1)..cells_in_grid.len() {
-                    let idx_b = cells_in_grid[j];
-                    
-                    // Calculate distance between cells
-                    let delta = state.positions[idx_b] - state.positions[idx_a];
-                    let distance = delta.length();
-                    
-                    // Check for overlap
-                    let combined_radius = state.radii[idx_a] + state.radii[idx_b];
-                    if distance < combined_radius {
-                        // Skip collision if cells are in the same organism
-                        if are_cells_in_same_organism(state, idx_a, idx_b) {
-                            continue;
-                        }
-                        
-                        let overlap = combined_radius - distance;
-                        let normal = if distance > 0.0001 {
-                            delta / distance
-                        } else {
-                            Vec3::X
-                        };
-                        
-                        local_pairs.push(CanonicalCollisionPair {
-                            index_a: idx_a,
-                            index_b: idx_b,
-                            overlap,
-                            normal,
-                        });
-                    }
-                }
-            }
-            
-            // Check forward neighbors to avoid duplicate checks
-            for &offset in &FORWARD_NEIGHBORS {
-                let neighbor_coord = grid_coord + offset;
-                
-                // Find neighbor in active cells
-                let Some(neighbor_idx) = state.spatial_grid.active_cell_index(neighbor_coord) else {
-                    continue;
-                };
-                
-                let neighbor_cells = state.spatial_grid.get_cell_contents(neighbor_idx);
-                
-                // Check all pairs between current cell and neighbor cell
-                for &idx_a in cells_in_grid {
-                    for &idx_b in neighbor_cells {
-                        // Calculate distance between cells
-                        let delta = state.positions[idx_b] - state.positions[idx_a];
-                        let distance = delta.length();
-                        
-                        // Check for overlap
-                        let combined_radius = state.radii[idx_a] + state.radii[idx_b];
-                        if distance < combined_radius {
-                            // Skip collision if cells are in the same organism
-                            if are_cells_in_same_organism(state, idx_a, idx_b) {
-                                continue;
-                            }
-                            
-                            let overlap = combined_radius - distance;
-                            let normal = if distance > 0.0001 {
-                                delta / distance
-                            } else {
-                                Vec3::X
-                            };
-                            
-                            local_pairs.push(CanonicalCollisionPair {
-                                index_a: idx_a,
-                                index_b: idx_b,
-                                overlap,
-                                normal,
-                            });
-                        }
-                    }
-                }
+    // Process each chunk of grid cells in parallel
+    scratch.pair_chunks[..chunk_count]
+        .par_iter_mut()
+        .zip(grid_cells.par_chunks(chunk_len))
+        .for_each(|(local_pairs, chunk)| {
+            local_pairs.clear();
+            for &grid_idx in chunk {
+                collect_grid_cell_pairs(state, grid_idx, local_pairs);
             }
-            
-            local_pairs
-        })
-        .collect();
+        });
+    
+    scratch.collision_pairs.clear();
+    for local_pairs in &scratch.pair_chunks[..chunk_count] {
+        scratch.collision_pairs.extend_from_slice(local_pairs);
+    }
     
     // Sort collision pairs by indices to maintain deterministic ordering
     // This ensures bit-identical results across runs
-    collision_pairs.sort_unstable_by_key(|pair| (pair.index_a, pair.index_b));
-    
-    collision_pairs
+    scratch.collision_pairs.sort_unstable_by_key(|pair| (pair.index_a, pair.index_b));
 }
 
 /// Check if two cells are in the same organism (connected component via adhesions)
@@ -705,23 +1001,108 @@ fn are_cells_directly_connected(state: &CanonicalState, cell_a: usize, cell_b: u
     state.adhesion_manager.are_cells_connected(&state.adhesion_connections, cell_a, cell_b)
 }
 
+/// Distance from an ellipsoid's center to its surface tangent plane along a unit world direction
+#[inline]
+pub fn ellipsoid_support(semi_axes: Vec3, rotation: Quat, direction: Vec3) -> f32 {
+    (semi_axes * (rotation.inverse() * direction)).length()
+}
+
+/// Narrow phase for ellipsoid modes: recompute each pair's overlap from both cells' support
+/// along the contact normal and drop pairs whose ellipsoids don't touch
+///
+/// Broad-phase pairs come from the bounding spheres (the cell radius), so every touching
+/// ellipsoid pair is present. Pairs of two spheres keep their overlap untouched.
+pub fn apply_ellipsoid_contacts(
+    state: &CanonicalState,
+    genome: &crate::genome::GenomeData,
+    collision_pairs: &mut Vec<CanonicalCollisionPair>,
+) {
+    if genome.modes.iter().all(|mode| mode.is_spherical()) {
+        return;
+    }
+    let ellipsoid_mode = |i: usize| genome.modes.get(state.mode_indices[i]).filter(|mode| !mode.is_spherical());
+    let support = |i: usize, direction: Vec3| match ellipsoid_mode(i) {
+        Some(mode) => ellipsoid_support(mode.semi_axes(state.radii[i]), state.rotations[i], direction),
+        None => state.radii[i],
+    };
+
+    collision_pairs.retain_mut(|pair| {
+        let (idx_a, idx_b) = (pair.index_a, pair.index_b);
+        if ellipsoid_mode(idx_a).is_none() && ellipsoid_mode(idx_b).is_none() {
+            return true;
+        }
+        let distance = (state.positions[idx_b] - state.positions[idx_a]).length();
+        pair.overlap = support(idx_a, pair.normal) + support(idx_b, -pair.normal) - distance;
+        pair.overlap > 0.0
+    });
+}
+
+/// Rescale torques on ellipsoid cells so the sphere inertia used by the angular integration
+/// gives the ellipsoid's response: I = m/5 (b² + c²) about the local x axis, and so on
+pub fn apply_ellipsoid_inertia(state: &mut CanonicalState, genome: &crate::genome::GenomeData, math: PhysicsMath) {
+    if genome.modes.iter().all(|mode| mode.is_spherical()) {
+        return;
+    }
+    for i in 0..state.cell_count {
+        let Some(mode) = genome.modes.get(state.mode_indices[i]).filter(|mode| !mode.is_spherical()) else {
+            continue;
+        };
+        let (mass, radius) = (state.masses[i], state.radii[i]);
+        if mass <= 0.0 || !mass.is_finite() || radius <= 0.0 {
+            continue;
+        }
+        let axes = mode.semi_axes(radius);
+        let axes_squared = match math {
+            PhysicsMath::Native => axes.powf(2.0),
+            // powf isn't correctly rounded on every platform; a product is
+            PhysicsMath::Reproducible => axes * axes,
+        };
+        let inertia = Vec3::new(
+            axes_squared.y + axes_squared.z,
+            axes_squared.x + axes_squared.z,
+            axes_squared.x + axes_squared.y,
+        ) * (0.2 * mass);
+        let sphere_inertia = 0.4 * mass * radius * radius;
+        let rotation = state.rotations[i];
+        state.torques[i] = match math {
+            PhysicsMath::Native => {
+                let local_torque = rotation.inverse() * state.torques[i];
+                rotation * (local_torque / inertia * sphere_inertia)
+            }
+            PhysicsMath::Reproducible => {
+                let local_torque = math.rotate(rotation.conjugate(), state.torques[i]);
+                math.rotate(rotation, local_torque / inertia * sphere_inertia)
+            }
+        };
+    }
+}
+
 /// Compute collision forces from detected collision pairs - Single-threaded version
 pub fn compute_collision_forces_canonical_st(
     state: &mut CanonicalState,
     collision_pairs: &[CanonicalCollisionPair],
     config: &crate::cell::physics::PhysicsConfig,
 ) {
-    // Clear all forces and torques
+    // Clear all forces, torques and contact pressures
     for i in 0..state.cell_count {
         state.forces[i] = Vec3::ZERO;
         state.torques[i] = Vec3::ZERO;
+        state.contact_pressures[i] = 0.0;
     }
+    state.begin_collision_heat();
+    let record_heat = state.force_heat.accumulating();
     
     // Process each collision pair
     for pair in collision_pairs {
         let idx_a = pair.index_a;
         let idx_b = pair.index_b;
         
+        // Contact pressure counts every touching neighbor, including cells of the same organism
+        if pair.overlap > 0.0 {
+            state.contact_pressures[idx_a] += pair.overlap;
+            state.contact_pressures[idx_b] += pair.overlap;
+        }
+        
         let stiffness_a = state.stiffnesses[idx_a];
         let stiffness_b = state.stiffnesses[idx_b];
         
@@ -762,6 +1143,12 @@ pub fn compute_collision_forces_canonical_st(
         // Apply equal and opposite forces
         state.forces[idx_b] += force;
         state.forces[idx_a] -= force;
+        if record_heat {
+            // Same cell order as the multithreaded merge, so both paths sum identically
+            let magnitude = force.length();
+            state.add_collision_heat(idx_b, magnitude);
+            state.add_collision_heat(idx_a, magnitude);
+        }
         
         // === Rolling Friction Torque ===
         // Apply torque based on tangential velocity at contact point
@@ -821,6 +1208,17 @@ pub fn compute_collision_forces_canonical(
     state: &mut CanonicalState,
     collision_pairs: &[CanonicalCollisionPair],
     config: &crate::cell::physics::PhysicsConfig,
+) {
+    let mut contributions = Vec::new();
+    compute_collision_forces_canonical_with(state, collision_pairs, config, &mut contributions);
+}
+
+/// Multithreaded collision forces, reusing `contributions` for the per-pair results
+pub fn compute_collision_forces_canonical_with(
+    state: &mut CanonicalState,
+    collision_pairs: &[CanonicalCollisionPair],
+    config: &crate::cell::physics::PhysicsConfig,
+    contributions: &mut Vec<Option<[(usize, Vec3, Vec3); 2]>>,
 ) {
     use rayon::prelude::*;
     
@@ -828,11 +1226,21 @@ pub fn compute_collision_forces_canonical(
     state.forces[..state.cell_count].par_iter_mut().for_each(|f| *f = Vec3::ZERO);
     state.torques[..state.cell_count].par_iter_mut().for_each(|t| *t = Vec3::ZERO);
     
+    // Contact pressure counts every touching neighbor, including cells of the same organism
+    // (accumulated sequentially, in pair order, like the forces below)
+    state.contact_pressures[..state.cell_count].fill(0.0);
+    for pair in collision_pairs {
+        if pair.overlap > 0.0 {
+            state.contact_pressures[pair.index_a] += pair.overlap;
+            state.contact_pressures[pair.index_b] += pair.overlap;
+        }
+    }
+    
     // Compute forces and torques for each collision pair in parallel
     // Store as (index, force, torque) tuples to accumulate deterministically
-    let contributions: Vec<(usize, Vec3, Vec3)> = collision_pairs
+    collision_pairs
         .par_iter()
-        .flat_map(|pair| {
+        .map(|pair| {
             let idx_a = pair.index_a;
             let idx_b = pair.index_b;
             
@@ -865,7 +1273,7 @@ pub fn compute_collision_forces_canonical(
             
             // Skip collision forces for cells in the same organism (adhesion forces handle their interaction)
             if are_cells_in_same_organism(state, idx_a, idx_b) {
-                return vec![];
+                return None;
             }
             
             // Clamp force magnitude
@@ -918,17 +1326,22 @@ pub fn compute_collision_forces_canonical(
             }
             
             // Return contributions for both cells: (index, force, torque)
-            vec![
+            Some([
                 (idx_b, force, torque_b),
                 (idx_a, -force, torque_a),
-            ]
+            ])
         })
-        .collect();
+        .collect_into_vec(contributions);
     
     // Accumulate forces and torques sequentially to maintain determinism
-    for (idx, force, torque) in contributions {
+    state.begin_collision_heat();
+    let record_heat = state.force_heat.accumulating();
+    for &(idx, force, torque) in contributions.iter().flatten().flatten() {
         state.forces[idx] += force;
         state.torques[idx] += torque;
+        if record_heat {
+            state.add_collision_heat(idx, force.length());
+        }
     }
 }
 
@@ -951,6 +1364,7 @@ pub fn physics_step_st(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
     // 3. Update spatial partitioning
@@ -975,18 +1389,28 @@ pub fn physics_step_st(
         
         // Use batched version for single-threaded (better cache locality)
         crate::cell::compute_adhesion_forces_batched(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
             &mode_settings,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
     }
     
+    // Heatmap averages of this tick's contact and bond forces
+    state.update_force_heat(config.fixed_timestep);
+    
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions
     apply_boundary_forces_soa_st(
         &mut state.positions[..state.cell_count],
@@ -1005,8 +1429,12 @@ pub fn physics_step_st(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
     // 8. Update angular velocities from torques
     integrate_angular_velocities_soa_st(
         &mut state.angular_velocities[..state.cell_count],
@@ -1015,6 +1443,7 @@ pub fn physics_step_st(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
 }
 
@@ -1024,8 +1453,10 @@ pub fn physics_step_st_with_genome(
     state: &mut CanonicalState,
     config: &crate::simulation::PhysicsConfig,
     genome: &crate::genome::GenomeData,
-    _current_time: f32,
+    current_time: f32,
 ) {
+    let compiled = state.compiled_genome.get(genome);
+    
     // 1. Verlet integration (position update)
     verlet_integrate_positions_soa_st(
         &mut state.positions[..state.cell_count],
@@ -1039,63 +1470,68 @@ pub fn physics_step_st_with_genome(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
     // 3. Update spatial partitioning
     state.spatial_grid.rebuild(&state.positions, state.cell_count);
     
     // 4. Detect collisions (skip if disabled)
-    let collisions = if config.disable_collisions {
-        Vec::new()
+    // Scratch buffers are taken out of the state for the tick and put back afterwards
+    let mut scratch = std::mem::take(&mut state.scratch);
+    if config.disable_collisions {
+        scratch.collision_pairs.clear();
     } else {
-        detect_collisions_canonical_st(state)
-    };
+        detect_collisions_canonical_st_into(state, &mut scratch);
+    }
+    apply_ellipsoid_contacts(state, genome, &mut scratch.collision_pairs);
+    scratch.record_contacts(&state.cell_ids);
     
     // 5. Compute forces and torques
-    compute_collision_forces_canonical_st(state, &collisions, config);
+    compute_collision_forces_canonical_st(state, &scratch.collision_pairs, config);
+    
+    // 5.1. Touching cells of different organisms bond where the genome lets their modes stick
+    state.form_contact_adhesions(genome, &scratch.collision_pairs, contact_tick(current_time, config));
     
     // 5.5. Compute adhesion forces with genome settings
     if state.adhesion_connections.active_count > 0 {
-        // Extract adhesion settings from genome modes
-        let mode_settings: Vec<crate::cell::AdhesionSettings> = genome.modes.iter()
-            .map(|mode| crate::cell::AdhesionSettings {
-                can_break: mode.adhesion_settings.can_break,
-                break_force: mode.adhesion_settings.break_force,
-                rest_length: mode.adhesion_settings.rest_length,
-                linear_spring_stiffness: mode.adhesion_settings.linear_spring_stiffness,
-                linear_spring_damping: mode.adhesion_settings.linear_spring_damping,
-                orientation_spring_stiffness: mode.adhesion_settings.orientation_spring_stiffness,
-                orientation_spring_damping: mode.adhesion_settings.orientation_spring_damping,
-                max_angular_deviation: mode.adhesion_settings.max_angular_deviation,
-                twist_constraint_stiffness: mode.adhesion_settings.twist_constraint_stiffness,
-                twist_constraint_damping: mode.adhesion_settings.twist_constraint_damping,
-                enable_twist_constraint: mode.adhesion_settings.enable_twist_constraint,
-            })
-            .collect();
-        
         // Use batched version for single-threaded (better cache locality)
         crate::cell::compute_adhesion_forces_batched(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
-            &mode_settings,
+            &compiled.adhesion,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
     }
+    state.scratch = scratch;
+    
+    // Heatmap averages of this tick's contact and bond forces
+    state.update_force_heat(config.fixed_timestep);
     
     // 5.6. Apply swim forces for Flagellocyte cells (disabled in preview mode)
     apply_swim_forces_st(
         &mut state.forces[..state.cell_count],
+        &state.positions[..state.cell_count],
         &state.rotations[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
+        &state.chemical_field,
         false, // Disable swim in preview mode so cells don't swim away
+        config.math(),
     );
     
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions
     apply_boundary_forces_soa_st(
         &mut state.positions[..state.cell_count],
@@ -1114,9 +1550,14 @@ pub fn physics_step_st_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
-    // 8. Update angular velocities from torques
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
+    // 8. Update angular velocities from torques (ellipsoid modes rescale theirs first)
+    apply_ellipsoid_inertia(state, genome, config.math());
     integrate_angular_velocities_soa_st(
         &mut state.angular_velocities[..state.cell_count],
         &state.torques[..state.cell_count],
@@ -1124,6 +1565,7 @@ pub fn physics_step_st_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
     
     // 9. Update nutrient growth for Test cells
@@ -1131,7 +1573,7 @@ pub fn physics_step_st_with_genome(
         &mut state.masses[..state.cell_count],
         &mut state.radii[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
         config.fixed_timestep,
     );
     
@@ -1141,7 +1583,15 @@ pub fn physics_step_st_with_genome(
     
     // 10. Synchronized nutrient transport (maintains cohort synchronization)
     // This now handles both Test cell nutrient gain AND Flagellocyte consumption
-    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, genome, config.fixed_timestep);
+    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, &compiled, config.fixed_timestep);
+    
+    // 11. Secretion, absorption and diffusion in the chemical field
+    state.update_chemical_field(genome, config);
+}
+
+/// Tick a physics step starts at, for the contact adhesion rolls
+fn contact_tick(current_time: f32, config: &crate::simulation::PhysicsConfig) -> u64 {
+    (current_time / config.fixed_timestep).round().max(0.0) as u64
 }
 
 /// Calculate which cells should have nutrient transfer blocked this frame
@@ -1152,10 +1602,11 @@ fn calculate_cells_attempting_split(
     state: &mut CanonicalState,
     genome: &crate::genome::GenomeData,
     current_time: f32,
+    fixed_timestep: f32,
 ) -> std::collections::HashSet<usize> {
-    // Calculate current frame number (fixed timestep is 1/64 seconds)
-    let current_frame = (current_time * 64.0) as i32;
-    const DEFER_FRAMES: i32 = 32; // 0.5 seconds at 64 fps = 32 frames
+    // Calculate current tick number from simulated time
+    let current_frame = crate::simulation::clock::tick_at_time(current_time, fixed_timestep) as i32;
+    let defer_frames = crate::simulation::clock::ticks_to_reach(0.5, fixed_timestep) as i32; // 0.5 seconds
     
     let mut cells_to_block = std::collections::HashSet::new();
     
@@ -1182,8 +1633,11 @@ fn calculate_cells_attempting_split(
         // Check mass threshold (using per-cell split_mass which may be randomized)
         let can_split_by_mass = state.masses[i] >= state.split_masses[i];
         
-        let is_ready_to_split = can_split_by_count && can_split_by_adhesions && can_split_by_mass 
-            && state.split_intervals[i] <= 59.0 && cell_age >= state.split_intervals[i];
+        // Check contact inhibition (crowded cells are not attempting to split)
+        let can_split_by_pressure = mode.is_none_or(|m| state.contact_pressures[i] <= m.max_contact_pressure);
+        
+        let is_ready_to_split = can_split_by_count && can_split_by_adhesions && can_split_by_mass && can_split_by_pressure
+            && state.split_intervals[i] <= crate::genome::NEVER_SPLIT_INTERVAL && cell_age >= state.split_intervals[i];
         
         if is_ready_to_split {
             // If this is the first frame the cell is ready, record it
@@ -1193,7 +1647,7 @@ fn calculate_cells_attempting_split(
             
             // Block nutrient transfer for 0.5 seconds during split attempt
             let frames_since_ready = current_frame - state.split_ready_frame[i];
-            if frames_since_ready < DEFER_FRAMES {
+            if frames_since_ready < defer_frames {
                 cells_to_block.insert(i);
             }
         } else {
@@ -1226,16 +1680,15 @@ pub fn physics_step(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
-    // 3. Update spatial partitioning
-    state.spatial_grid.rebuild(&state.positions, state.cell_count);
-    
-    // 4. Detect collisions (skip if disabled)
+    // 3-4. Update spatial partitioning and detect collisions (skip if disabled)
+    // The grid and pair list are only rebuilt once cells have moved past the skin
     let collisions = if config.disable_collisions {
         Vec::new()
     } else {
-        detect_collisions_canonical(state)
+        state.detect_collisions_cached(config.collision_skin)
     };
     
     // 5. Compute forces and torques
@@ -1250,18 +1703,28 @@ pub fn physics_step(
         
         // Use parallel version for multithreaded physics
         crate::cell::compute_adhesion_forces_parallel(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
             &mode_settings,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
     }
     
+    // Heatmap averages of this tick's contact and bond forces
+    state.update_force_heat(config.fixed_timestep);
+    
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions
     apply_boundary_forces_soa(
         &mut state.positions[..state.cell_count],
@@ -1280,8 +1743,12 @@ pub fn physics_step(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
     // 8. Update angular velocities from torques
     integrate_angular_velocities_soa(
         &mut state.angular_velocities[..state.cell_count],
@@ -1290,6 +1757,7 @@ pub fn physics_step(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
 }
 
@@ -1299,9 +1767,45 @@ pub fn physics_step_with_genome(
     state: &mut CanonicalState,
     config: &crate::simulation::PhysicsConfig,
     genome: &crate::genome::GenomeData,
-    _current_time: f32,
+    current_time: f32,
     enable_swim: bool,
 ) {
+    physics_step_with_genome_and_pairs(state, config, genome, current_time, enable_swim, |state, scratch| {
+        state.detect_collisions_cached_into(config.collision_skin, scratch);
+    });
+}
+
+/// Multithreaded physics step with a caller-supplied collision pair detector
+/// 
+/// The detector must return pairs sorted by (index_a, index_b), matching
+/// `detect_collisions_canonical`. Used by the GPU pair path, which rebuilds
+/// the grid every tick rather than using the cached pair list.
+pub fn physics_step_with_genome_and_detector(
+    state: &mut CanonicalState,
+    config: &crate::simulation::PhysicsConfig,
+    genome: &crate::genome::GenomeData,
+    current_time: f32,
+    enable_swim: bool,
+    detect_collisions: impl FnOnce(&CanonicalState) -> Vec<CanonicalCollisionPair>,
+) {
+    physics_step_with_genome_and_pairs(state, config, genome, current_time, enable_swim, |state, scratch| {
+        state.spatial_grid.rebuild(&state.positions, state.cell_count);
+        scratch.collision_pairs = detect_collisions(state);
+    });
+}
+
+/// Multithreaded physics step; `find_pairs` updates the spatial grid as needed and
+/// writes the collision pairs to `scratch.collision_pairs`
+fn physics_step_with_genome_and_pairs(
+    state: &mut CanonicalState,
+    config: &crate::simulation::PhysicsConfig,
+    genome: &crate::genome::GenomeData,
+    current_time: f32,
+    enable_swim: bool,
+    find_pairs: impl FnOnce(&mut CanonicalState, &mut ScratchBuffers),
+) {
+    let compiled = state.compiled_genome.get(genome);
+    
     // 1. Verlet integration (position update)
     verlet_integrate_positions_soa(
         &mut state.positions[..state.cell_count],
@@ -1315,49 +1819,65 @@ pub fn physics_step_with_genome(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
-    // 3. Update spatial partitioning
-    state.spatial_grid.rebuild(&state.positions, state.cell_count);
-    
-    // 4. Detect collisions (skip if disabled)
-    let collisions = if config.disable_collisions {
-        Vec::new()
+    // 3-4. Update spatial partitioning and detect collisions (skip if disabled)
+    // Scratch buffers are taken out of the state for the tick and put back afterwards
+    let mut scratch = std::mem::take(&mut state.scratch);
+    if config.disable_collisions {
+        scratch.collision_pairs.clear();
     } else {
-        detect_collisions_canonical(state)
-    };
+        find_pairs(state, &mut scratch);
+    }
+    apply_ellipsoid_contacts(state, genome, &mut scratch.collision_pairs);
+    scratch.record_contacts(&state.cell_ids);
     
     // 5. Compute forces and torques
-    compute_collision_forces_canonical(state, &collisions, config);
+    compute_collision_forces_canonical_with(state, &scratch.collision_pairs, config, &mut scratch.force_contributions);
+    
+    // 5.1. Touching cells of different organisms bond where the genome lets their modes stick
+    state.form_contact_adhesions(genome, &scratch.collision_pairs, contact_tick(current_time, config));
+    state.scratch = scratch;
     
     // 5.5. Compute adhesion forces with genome settings
     if state.adhesion_connections.active_count > 0 {
-        // Update cached adhesion settings if genome changed (avoids allocation every frame)
-        state.update_adhesion_settings_cache(genome);
-        
-        // Use parallel version for multithreaded physics with cached settings
+        // Use parallel version for multithreaded physics with the compiled settings
         crate::cell::compute_adhesion_forces_parallel(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
-            &state.cached_adhesion_settings,
+            &compiled.adhesion,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
     }
     
+    // Heatmap averages of this tick's contact and bond forces
+    state.update_force_heat(config.fixed_timestep);
+    
     // 5.6. Apply swim forces for Flagellocyte cells
     apply_swim_forces(
         &mut state.forces[..state.cell_count],
+        &state.positions[..state.cell_count],
         &state.rotations[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
+        &state.chemical_field,
         enable_swim,
+        config.math(),
     );
     
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions
     apply_boundary_forces_soa(
         &mut state.positions[..state.cell_count],
@@ -1376,9 +1896,14 @@ pub fn physics_step_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
-    // 8. Update angular velocities from torques
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
+    // 8. Update angular velocities from torques (ellipsoid modes rescale theirs first)
+    apply_ellipsoid_inertia(state, genome, config.math());
     integrate_angular_velocities_soa(
         &mut state.angular_velocities[..state.cell_count],
         &state.torques[..state.cell_count],
@@ -1386,6 +1911,7 @@ pub fn physics_step_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
     
     // 9. Update nutrient growth for Test cells
@@ -1393,7 +1919,7 @@ pub fn physics_step_with_genome(
         &mut state.masses[..state.cell_count],
         &mut state.radii[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
         config.fixed_timestep,
     );
     
@@ -1403,7 +1929,10 @@ pub fn physics_step_with_genome(
     
     // 10. Synchronized nutrient transport (maintains cohort synchronization)
     // This now handles both Test cell nutrient gain AND Flagellocyte consumption
-    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, genome, config.fixed_timestep);
+    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, &compiled, config.fixed_timestep);
+    
+    // 11. Secretion, absorption and diffusion in the chemical field
+    state.update_chemical_field(genome, config);
 }
 
 // ============================================================================
@@ -1416,6 +1945,14 @@ pub struct DivisionEvent {
     pub parent_idx: usize,
     pub child_a_idx: usize,
     pub child_b_idx: usize,
+    /// ID the parent had (child A took over its slot)
+    pub parent_id: u32,
+    pub parent_mode: usize,
+    /// IDs of child A and B (None for a child that didn't fit in the capacity)
+    pub child_ids: [Option<u32>; 2],
+    pub child_modes: [usize; 2],
+    /// Rule that picked child A's and child B's mode
+    pub child_rules: [crate::genome::ChildModeRule; 2],
 }
 
 /// Generate a pseudo-random rotation quaternion with magnitude ~0.001 radians
@@ -1452,6 +1989,217 @@ fn pseudo_random_rotation(cell_id: u32, rng_seed: u64) -> Quat {
     Quat::from_xyzw(axis_x, axis_y, axis_z, 1.0).normalize()
 }
 
+/// Division readiness of a cell (see `CanonicalState::division_readiness`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivisionReadiness {
+    /// Not old or heavy enough yet, or never splits
+    NotDue,
+    /// Due, but a limit holds it back
+    Blocked(DivisionBlock),
+    Ready,
+}
+
+/// Why a cell that is due to divide does not
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DivisionBlock {
+    /// The mode's max splits were reached
+    MaxSplits,
+    /// Fewer adhesions than the mode's minimum
+    TooFewAdhesions,
+    /// At or above the mode's maximum adhesions
+    TooManyAdhesions,
+    /// Contact inhibition (crowded past the mode's max contact pressure)
+    ContactPressure,
+    /// An adhered neighbor divides first (this cell waits a pass)
+    AdhesionConflict,
+    /// No free cell slot
+    Capacity,
+}
+
+impl DivisionBlock {
+    pub const ALL: [DivisionBlock; 6] = [
+        DivisionBlock::MaxSplits,
+        DivisionBlock::TooFewAdhesions,
+        DivisionBlock::TooManyAdhesions,
+        DivisionBlock::ContactPressure,
+        DivisionBlock::AdhesionConflict,
+        DivisionBlock::Capacity,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DivisionBlock::MaxSplits => "Max splits reached",
+            DivisionBlock::TooFewAdhesions => "Below min adhesions",
+            DivisionBlock::TooManyAdhesions => "At max adhesions",
+            DivisionBlock::ContactPressure => "Contact inhibition",
+            DivisionBlock::AdhesionConflict => "Adhesion conflict",
+            DivisionBlock::Capacity => "Capacity",
+        }
+    }
+}
+
+/// Greedy maximal independent set of `ready` (see `CanonicalState::select_independent_divisions`)
+///
+/// `marks` must be zeroed for every ready index and is zeroed again on return.
+pub fn select_independent(
+    ready: &[usize],
+    cell_ids: &[u32],
+    adhesion_manager: &crate::cell::AdhesionConnectionManager,
+    connections: &crate::cell::AdhesionConnections,
+    marks: &mut [u8],
+    selected: &mut Vec<usize>,
+) {
+    const READY: u8 = 1;
+    const SELECTED: u8 = 2;
+
+    for &i in ready {
+        marks[i] = READY;
+    }
+
+    let order = selected;
+    order.clear();
+    order.extend_from_slice(ready);
+    order.sort_unstable_by_key(|&i| cell_ids[i]);
+
+    for &cell_idx in order.iter() {
+        let conflicts = adhesion_manager.cell_adhesion_indices[cell_idx].iter()
+            .filter(|&&adhesion_idx| adhesion_idx >= 0 && connections.is_active[adhesion_idx as usize] != 0)
+            .map(|&adhesion_idx| {
+                let adhesion_idx = adhesion_idx as usize;
+                if connections.cell_a_index[adhesion_idx] == cell_idx {
+                    connections.cell_b_index[adhesion_idx]
+                } else {
+                    connections.cell_a_index[adhesion_idx]
+                }
+            })
+            .any(|other_idx| marks[other_idx] == SELECTED);
+        if !conflicts {
+            marks[cell_idx] = SELECTED;
+        }
+    }
+
+    order.clear();
+    for &i in ready {
+        if marks[i] == SELECTED {
+            order.push(i);
+        }
+        marks[i] = 0;
+    }
+}
+
+/// Parent and child properties of one division, gathered before any child is written
+#[allow(dead_code)]
+pub(crate) struct DivisionData {
+    parent_idx: usize,
+    parent_id: u32,
+    parent_mode_idx: usize,
+    child_a_slot: usize,
+    child_b_slot: usize,
+    parent_velocity: bevy::prelude::Vec3,
+    _parent_radius: f32,
+    parent_genome_id: usize,
+    parent_stiffness: f32,
+    parent_split_count: i32,
+    parent_genome_orientation: bevy::prelude::Quat,  // CRITICAL: Save parent's genome orientation before overwriting
+    split_direction_local: bevy::prelude::Vec3,  // Direction this division split along (jitter applied), parent frame
+    child_a_pos: bevy::prelude::Vec3,
+    child_b_pos: bevy::prelude::Vec3,
+    child_a_orientation: bevy::prelude::Quat,
+    child_b_orientation: bevy::prelude::Quat,
+    child_a_genome_orientation: bevy::prelude::Quat,
+    child_b_genome_orientation: bevy::prelude::Quat,
+    child_a_lineage: crate::simulation::orientation_drift::LineageReference,
+    child_b_lineage: crate::simulation::orientation_drift::LineageReference,
+    child_a_mode_idx: usize,
+    child_b_mode_idx: usize,
+    child_mode_rules: [crate::genome::ChildModeRule; 2],
+    child_a_mass: f32,           // Actual mass value (from splitting parent)
+    child_b_mass: f32,           // Actual mass value (from splitting parent)
+    child_a_radius: f32,
+    child_b_radius: f32,
+    child_a_split_count: i32,
+    child_b_split_count: i32,
+    child_a_user_data: [f32; crate::simulation::user_data::USER_DATA_CHANNELS],
+    child_b_user_data: [f32; crate::simulation::user_data::USER_DATA_CHANNELS],
+}
+
+/// Parent of a division, as [`compute_division_placement`] reads it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DividingCell {
+    pub mode: usize,
+    pub position: Vec3,
+    /// Physics rotation
+    pub rotation: Quat,
+    pub radius: f32,
+    pub mass: f32,
+    pub split_count: i32,
+}
+
+/// Children of one division where [`division_step`] puts them, before physics moves them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivisionPlacement {
+    pub positions: [Vec3; 2],
+    /// Physics rotations, before the per-child perturbation
+    pub rotations: [Quat; 2],
+    pub modes: [(usize, crate::genome::ChildModeRule); 2],
+    pub split_counts: [i32; 2],
+    pub masses: [f32; 2],
+    pub radii: [f32; 2],
+}
+
+/// Child modes, masses, sizes and placement of a division
+///
+/// `split_direction_local` is the split direction in the parent's frame, jittered or not.
+/// The children overlap 75%: their centers sit a quarter of the parent's extent along the
+/// split direction away from the parent's, child A on the positive side. None when the
+/// parent's mode isn't in the genome.
+pub fn compute_division_placement(
+    compiled: &crate::genome::CompiledGenome,
+    parent: &DividingCell,
+    split_direction_local: Vec3,
+) -> Option<DivisionPlacement> {
+    let mode = compiled.mode(parent.mode)?;
+    let split_direction = parent.rotation * split_direction_local;
+
+    // Match C++ convention: Child A at +offset, Child B at -offset
+    // Ellipsoids use their extent along the split direction instead of the radius
+    let offset_distance = if mode.spherical {
+        parent.radius * 0.25
+    } else {
+        ellipsoid_support(mode.semi_axes(parent.radius), parent.rotation, split_direction) * 0.25
+    };
+
+    // If max_splits is reached and a mode after splits is set, the children switch to it;
+    // invalid references keep the children in the parent's mode
+    let will_reach_max_splits = mode.reached_split_limit(parent.split_count + 1);
+    let modes = [0, 1].map(|side| mode.child_mode(side, will_reach_max_splits));
+    // Split counts reset to 0 when the mode changes, otherwise count on from the parent's
+    let split_counts = modes.map(|(child, _)| if child != parent.mode { 0 } else { parent.split_count + 1 });
+
+    // split_ratio is the fraction of the parent's mass going to child A
+    let masses = [parent.mass * mode.split_ratio, parent.mass * (1.0 - mode.split_ratio)];
+    let radii = [0, 1].map(|side| match compiled.mode(modes[side].0) {
+        Some(child) => child.radius_for(masses[side]),
+        None => masses[side].clamp(0.5, 2.0),
+    });
+
+    Some(DivisionPlacement {
+        positions: [
+            parent.position + split_direction * offset_distance,
+            parent.position - split_direction * offset_distance,
+        ],
+        // The parent's spin plus the genome-specified orientation change of each child
+        rotations: [parent.rotation * mode.child_orientations[0], parent.rotation * mode.child_orientations[1]],
+        modes,
+        split_counts,
+        masses,
+        radii,
+    })
+}
+
+/// Division passes per tick; ready cells left after the last one divide next tick
+pub const MAX_DIVISION_PASSES: usize = 10;
+
 /// Deterministic division step for canonical state
 ///
 /// This function handles cell division in a deterministic manner:
@@ -1460,13 +2208,16 @@ fn pseudo_random_rotation(cell_id: u32, rng_seed: u64) -> Quat {
 /// - Creates child cells with properties derived from parent and genome
 /// - Respects capacity limits
 /// - Assigns unique IDs maintaining deterministic ordering
+/// - Adhered cells never divide in the same pass; each pass divides a maximal
+///   non-conflicting set chosen by cell ID, so a ready cell waits at most one pass
+///   per adhered ready neighbor (this tick or the next)
 ///
 /// # Arguments
 /// * `state` - Mutable reference to the canonical state
 /// * `genome` - Reference to the genome data
 /// * `current_time` - Current simulation time
 /// * `max_cells` - Maximum cell capacity
-/// * `_rng_seed` - Random seed (used for pseudo-random rotation perturbations)
+/// * `rng_seed` - Random seed (rotation perturbations and randomized split thresholds)
 ///
 /// # Returns
 /// Vector of DivisionEvent describing which divisions occurred
@@ -1474,10 +2225,15 @@ pub fn division_step(
     state: &mut CanonicalState,
     genome: &crate::genome::GenomeData,
     current_time: f32,
+    fixed_timestep: f32,
     max_cells: usize,
-    _rng_seed: u64,
+    rng_seed: u64,
 ) -> Vec<DivisionEvent> {
     
+    // Pick up edits to split interval/mass so living cells follow the current genome
+    let compiled = state.compiled_genome.get(genome);
+    state.update_split_thresholds(genome, &compiled, fixed_timestep, rng_seed);
+    
     // Early exit if at capacity
     if state.cell_count >= max_cells {
         return Vec::new();
@@ -1495,10 +2251,11 @@ pub fn division_step(
         state.already_split_buffer[i] = false;
     }
     
-    // Maximum number of passes to prevent infinite loops
-    const MAX_PASSES: usize = 10;
+    state.division_stats.last_step_divisions = 0;
+    state.division_stats.last_step_deferred = 0;
+    state.division_stats.last_step_passes = 0;
     
-    for _pass in 0..MAX_PASSES {
+    for _pass in 0..MAX_DIVISION_PASSES {
         // Find cells ready to divide in this pass
         state.divisions_to_process_buffer.clear();
         for i in 0..state.cell_count {
@@ -1507,33 +2264,8 @@ pub fn division_step(
                 continue;
             }
             
-            let cell_age = current_time - state.birth_times[i];
-            let mode_index = state.mode_indices[i];
-            let mode = genome.modes.get(mode_index);
-            
-            // Check max_splits limit (-1 means infinite)
-            let can_split_by_count = if let Some(m) = mode {
-                m.max_splits < 0 || state.split_counts[i] < m.max_splits
-            } else {
-                true
-            };
-            
-            // Check adhesion limits - prevent splitting if at/above max or below min connections
-            let adhesion_count = state.adhesion_manager.count_active_adhesions(i);
-            let can_split_by_adhesions = if let Some(m) = mode {
-                adhesion_count >= m.min_adhesions as usize && adhesion_count < m.max_adhesions as usize
-            } else {
-                true
-            };
-            
-            // Check mass threshold - cells must have enough mass to split (using per-cell split_mass)
-            let can_split_by_mass = state.masses[i] >= state.split_masses[i];
-            
-            // Check time threshold - cells must be old enough to split
-            let can_split_by_time = cell_age >= state.split_intervals[i];
-            
             // Cell can split if ALL conditions are met
-            if can_split_by_count && can_split_by_adhesions && can_split_by_mass && can_split_by_time && state.split_intervals[i] <= 59.0 {
+            if state.division_readiness(&compiled, i, current_time) == DivisionReadiness::Ready {
                 state.divisions_to_process_buffer.push(i);
             }
         }
@@ -1544,42 +2276,14 @@ pub fn division_step(
         }
         
         // CRITICAL: Prevent simultaneous splits of adhered cells
-        // Use priority-based system where lower cell index has higher priority
-        // This ensures clean adhesion inheritance without race conditions
-        state.filtered_divisions_buffer.clear();
+        // Adhered ready cells take turns (a maximal independent set, greedy by cell ID);
+        // the rest wait for the next pass. This ensures clean adhesion inheritance without race conditions
+        state.select_independent_divisions();
         
-        for i in 0..state.divisions_to_process_buffer.len() {
-            let cell_idx = state.divisions_to_process_buffer[i];
-            let mut should_defer = false;
-            
-            for adhesion_idx in &state.adhesion_manager.cell_adhesion_indices[cell_idx] {
-                if *adhesion_idx < 0 {
-                    continue;
-                }
-                let adhesion_idx = *adhesion_idx as usize;
-                
-                if state.adhesion_connections.is_active[adhesion_idx] == 0 {
-                    continue;
-                }
-                
-                let other_idx = if state.adhesion_connections.cell_a_index[adhesion_idx] == cell_idx {
-                    state.adhesion_connections.cell_b_index[adhesion_idx]
-                } else {
-                    state.adhesion_connections.cell_a_index[adhesion_idx]
-                };
-                
-                // Check if other cell is ALSO in the divisions_to_process list
-                // (meaning it's fully ready to divide this pass)
-                if state.divisions_to_process_buffer.contains(&other_idx) && other_idx < cell_idx {
-                    should_defer = true;
-                    break;
-                }
-            }
-            
-            if !should_defer {
-                state.filtered_divisions_buffer.push(cell_idx);
-            }
-        }
+        let deferred = (state.divisions_to_process_buffer.len() - state.filtered_divisions_buffer.len()) as u32;
+        state.division_stats.last_step_passes += 1;
+        state.division_stats.last_step_deferred += deferred;
+        state.division_stats.deferred += deferred as u64;
         
         // If no divisions can proceed in this pass, we're done
         if state.filtered_divisions_buffer.is_empty() {
@@ -1594,40 +2298,10 @@ pub fn division_step(
         // simultaneous divisions where all parents are freed at once.
         
         // Collect division data before modifying state
-        #[allow(dead_code)]
-        struct DivisionData {
-            parent_idx: usize,
-            parent_mode_idx: usize,
-            child_a_slot: usize,
-            child_b_slot: usize,
-            parent_velocity: bevy::prelude::Vec3,
-            _parent_radius: f32,
-            parent_genome_id: usize,
-            parent_stiffness: f32,
-            parent_split_count: i32,
-            parent_genome_orientation: bevy::prelude::Quat,  // CRITICAL: Save parent's genome orientation before overwriting
-            child_a_pos: bevy::prelude::Vec3,
-            child_b_pos: bevy::prelude::Vec3,
-            child_a_orientation: bevy::prelude::Quat,
-            child_b_orientation: bevy::prelude::Quat,
-            child_a_genome_orientation: bevy::prelude::Quat,
-            child_b_genome_orientation: bevy::prelude::Quat,
-            child_a_mode_idx: usize,
-            child_b_mode_idx: usize,
-            child_a_mass: f32,           // Actual mass value (from splitting parent)
-            child_b_mass: f32,           // Actual mass value (from splitting parent)
-            child_a_radius: f32,
-            child_b_radius: f32,
-            child_a_split_interval: f32, // Split interval threshold (may be randomized)
-            child_b_split_interval: f32,
-            child_a_split_mass_threshold: f32, // Split mass threshold (may be randomized)
-            child_b_split_mass_threshold: f32,
-            child_a_split_count: i32,
-            child_b_split_count: i32,
-        }
-        
-        let mut division_data_list = Vec::new();
-        let mut pass_division_events = Vec::new();
+        // (taken out of the scratch buffers so the state can be modified while iterating)
+        let mut division_data_list = std::mem::take(&mut state.scratch.division_data);
+        division_data_list.clear();
+        let events_before_pass = state.division_events_buffer.len();
 
         // Calculate available slots for children
         // Child A reuses parent index (matches C++), Child B gets new slot
@@ -1650,7 +2324,7 @@ pub fn division_step(
             next_available_slot += 1;
             
             let mode_index = state.mode_indices[parent_idx];
-        let mode = genome.modes.get(mode_index);
+        let mode = compiled.mode(mode_index);
         
         if let Some(mode) = mode {
             // Save parent properties
@@ -1664,113 +2338,57 @@ pub fn division_step(
             let parent_stiffness = state.stiffnesses[parent_idx];
             let parent_split_count = state.split_counts[parent_idx];
             
-            // Calculate split direction using physics rotation (for positioning)
-            let pitch = mode.parent_split_direction.x.to_radians();
-            let yaw = mode.parent_split_direction.y.to_radians();
-            let split_direction = parent_rotation
-                * bevy::prelude::Quat::from_euler(bevy::prelude::EulerRot::YXZ, yaw, pitch, 0.0)
-                * bevy::prelude::Vec3::Z;
-            
-            // 75% overlap means centers are 25% of combined diameter apart
-            // Match C++ convention: Child A at +offset, Child B at -offset
-            let offset_distance = parent_radius * 0.25;
-            let child_a_pos = parent_position + split_direction * offset_distance;
-            let child_b_pos = parent_position - split_direction * offset_distance;
-            
-            // Get child mode indices
-            // Check if children will reach max_splits after this division
-            let will_reach_max_splits = mode.max_splits >= 0 && (parent_split_count + 1) >= mode.max_splits;
-            
-            // If max_splits is reached and mode_a_after_splits is set, use that mode for Child A
-            let child_a_mode_idx = if will_reach_max_splits && mode.mode_a_after_splits >= 0 {
-                mode.mode_a_after_splits.max(0) as usize
-            } else {
-                mode.child_a.mode_number.max(0) as usize
-            };
-            // If max_splits is reached and mode_b_after_splits is set, use that mode for Child B
-            let child_b_mode_idx = if will_reach_max_splits && mode.mode_b_after_splits >= 0 {
-                mode.mode_b_after_splits.max(0) as usize
-            } else {
-                mode.child_b.mode_number.max(0) as usize
-            };
-            
-            // Determine split counts: reset to 0 if mode changes, otherwise inherit parent's count + 1
-            let child_a_split_count = if child_a_mode_idx != mode_index {
-                0
-            } else {
-                parent_split_count + 1
-            };
-            let child_b_split_count = if child_b_mode_idx != mode_index {
-                0
-            } else {
-                parent_split_count + 1
-            };
-            
-            // Get child properties
-            let child_a_mode = genome.modes.get(child_a_mode_idx);
-            let child_b_mode = genome.modes.get(child_b_mode_idx);
-            
-            // Split parent's mass according to split_ratio (same for all cell types)
-            // split_ratio determines what fraction goes to Child A (0.0 to 1.0)
-            let split_ratio = mode.split_ratio.clamp(0.0, 1.0);
-            let child_a_mass = parent_mass * split_ratio;
-            let child_b_mass = parent_mass * (1.0 - split_ratio);
-            
-            // Calculate child radii based on their masses
-            let child_a_radius = if let Some(m) = child_a_mode {
-                child_a_mass.min(m.max_cell_size).clamp(0.5, 2.0)
-            } else {
-                child_a_mass.clamp(0.5, 2.0)
-            };
-            
-            let child_b_radius = if let Some(m) = child_b_mode {
-                child_b_mass.min(m.max_cell_size).clamp(0.5, 2.0)
-            } else {
-                child_b_mass.clamp(0.5, 2.0)
-            };
-            
-            // Get split intervals (potentially randomized from range)
-            // Use parent cell_id + tick for deterministic randomness
-            let parent_cell_id = state.cell_ids[parent_idx];
-            let tick = (current_time * 60.0) as u64; // Approximate tick from time
-            
-            let child_a_split_interval = if let Some(m) = child_a_mode {
-                m.get_split_interval(parent_cell_id, tick, _rng_seed)
-            } else {
-                5.0
-            };
-            
-            let child_b_split_interval = if let Some(m) = child_b_mode {
-                m.get_split_interval(parent_cell_id + 1, tick, _rng_seed)
-            } else {
-                5.0
-            };
-            
-            // Get split mass thresholds (potentially randomized from range)
-            let child_a_split_mass_threshold = if let Some(m) = child_a_mode {
-                m.get_split_mass(parent_cell_id, tick, _rng_seed)
-            } else {
-                1.5
-            };
-            
-            let child_b_split_mass_threshold = if let Some(m) = child_b_mode {
-                m.get_split_mass(parent_cell_id + 1, tick, _rng_seed)
-            } else {
-                1.5
+            // Split direction in the parent's frame, jittered per division from the parent's
+            // id and the tick so resimulation splits the same way
+            let division_tick = crate::simulation::clock::tick_at_time(current_time, fixed_timestep);
+            let split_direction_local = mode.jittered_split_direction(state.cell_ids[parent_idx], division_tick, rng_seed);
+            let Some(placement) = compute_division_placement(&compiled, &DividingCell {
+                mode: mode_index,
+                position: parent_position,
+                rotation: parent_rotation,
+                radius: parent_radius,
+                mass: parent_mass,
+                split_count: parent_split_count,
+            }, split_direction_local) else {
+                continue;
             };
+            let [child_a_pos, child_b_pos] = placement.positions;
+            let [(child_a_mode_idx, child_a_rule), (child_b_mode_idx, child_b_rule)] = placement.modes;
+            let [child_a_split_count, child_b_split_count] = placement.split_counts;
+            let [child_a_mass, child_b_mass] = placement.masses;
+            let [child_a_radius, child_b_radius] = placement.radii;
+            let split_ratio = mode.split_ratio;
             
             // CRITICAL: Use parent's GENOME orientation for child genome orientations
             // This ensures genome orientations stay fixed and don't inherit physics rotation
-            let child_a_genome_orientation = parent_genome_orientation * mode.child_a.orientation;
-            let child_b_genome_orientation = parent_genome_orientation * mode.child_b.orientation;
+            // Renormalized so rounding error can't grow the quaternions over generations
+            let parent_lineage = state.lineage_references[parent_idx];
+            let child_a_lineage = parent_lineage.child(genome, mode_index, 0);
+            let child_b_lineage = parent_lineage.child(genome, mode_index, 1);
+            let (child_a_genome_orientation, child_b_genome_orientation) = if state.reanchor_genome_orientations {
+                (
+                    child_a_lineage.orientation(genome).as_quat(),
+                    child_b_lineage.orientation(genome).as_quat(),
+                )
+            } else {
+                (
+                    (parent_genome_orientation * mode.child_orientations[0]).normalize(),
+                    (parent_genome_orientation * mode.child_orientations[1]).normalize(),
+                )
+            };
             
-            // Physics rotations inherit from parent's physics rotation + child orientation delta
-            // This preserves the parent's spin while applying the genome-specified orientation change
-            let child_a_orientation = parent_rotation * mode.child_a.orientation;
-            let child_b_orientation = parent_rotation * mode.child_b.orientation;
+            let [child_a_orientation, child_b_orientation] = placement.rotations;
+
+            // User data follows the parent mode's inheritance policy
+            let (child_a_user_data, child_b_user_data) = crate::simulation::user_data::inherit_user_data(
+                mode.user_data_inheritance,
+                state.user_data(parent_idx),
+                split_ratio,
+            );
             
             division_data_list.push(DivisionData {
                 parent_idx,
+                parent_id: state.cell_ids[parent_idx],
                 parent_mode_idx: mode_index,
                 child_a_slot: child_a_slot as usize,
                 child_b_slot: child_b_slot as usize,
@@ -1780,36 +2398,41 @@ pub fn division_step(
                 parent_stiffness,
                 parent_split_count,
                 parent_genome_orientation,  // CRITICAL: Save parent's genome orientation before overwriting
+                split_direction_local,
                 child_a_pos,
                 child_b_pos,
                 child_a_orientation,
                 child_b_orientation,
                 child_a_genome_orientation,
                 child_b_genome_orientation,
+                child_a_lineage,
+                child_b_lineage,
                 child_a_mode_idx,
                 child_b_mode_idx,
+                child_mode_rules: [child_a_rule, child_b_rule],
                 child_a_mass,
                 child_b_mass,
                 child_a_radius,
                 child_b_radius,
-                child_a_split_interval,
-                child_b_split_interval,
-                child_a_split_mass_threshold,
-                child_b_split_mass_threshold,
                 child_a_split_count,
                 child_b_split_count,
+                child_a_user_data,
+                child_b_user_data,
             });
             }
         }
         
         // Now write all the children to their allocated slots
+        state.reserve_cell_slots(state.cell_count + division_data_list.len());
         for data in &division_data_list {
             // Children are born at current_time (same birth time for cohort synchronization)
             let child_birth_time = current_time;
+            let mut child_ids = [None; 2];
 
             if data.child_a_slot < state.capacity {
             // Write child A
             let child_a_id = state.next_cell_id;
+            child_ids[0] = Some(child_a_id);
             state.cell_ids[data.child_a_slot] = child_a_id;
             state.next_cell_id += 1;
             state.positions[data.child_a_slot] = data.child_a_pos;
@@ -1821,10 +2444,11 @@ pub fn division_step(
             state.mode_indices[data.child_a_slot] = data.child_a_mode_idx;
 
             // Apply pseudo-random rotation perturbation (0.001 radians)
-            let random_rotation_a = pseudo_random_rotation(child_a_id, _rng_seed);
+            let random_rotation_a = pseudo_random_rotation(child_a_id, rng_seed);
             state.rotations[data.child_a_slot] = data.child_a_orientation * random_rotation_a;
 
             state.genome_orientations[data.child_a_slot] = data.child_a_genome_orientation;
+            state.lineage_references[data.child_a_slot] = data.child_a_lineage;
             state.angular_velocities[data.child_a_slot] = bevy::prelude::Vec3::ZERO;
             state.forces[data.child_a_slot] = bevy::prelude::Vec3::ZERO;
             state.torques[data.child_a_slot] = bevy::prelude::Vec3::ZERO;
@@ -1832,10 +2456,14 @@ pub fn division_step(
             state.prev_accelerations[data.child_a_slot] = bevy::prelude::Vec3::ZERO;
             state.stiffnesses[data.child_a_slot] = data.parent_stiffness;
             state.birth_times[data.child_a_slot] = child_birth_time;
-            state.split_intervals[data.child_a_slot] = data.child_a_split_interval;
-            state.split_masses[data.child_a_slot] = data.child_a_split_mass_threshold;
+            // Split thresholds are resolved from the child's own id and birth tick
+            state.resolve_split_thresholds(data.child_a_slot, genome, fixed_timestep, rng_seed);
             // Split count: reset to 0 if mode changed, otherwise inherit parent's count + 1
             state.split_counts[data.child_a_slot] = data.child_a_split_count;
+            state.set_low_nutrient_boost(data.child_a_slot, false);
+            // Children start unpinned; child A reuses the (possibly pinned) parent slot
+            state.set_pinned(data.child_a_slot, false);
+            state.set_user_data(data.child_a_slot, data.child_a_user_data);
 
                 // Adhesion indices will be initialized in inheritance function (matches C++)
             }
@@ -1843,6 +2471,7 @@ pub fn division_step(
             if data.child_b_slot < state.capacity {
                 // Write child B
                 let child_b_id = state.next_cell_id;
+                child_ids[1] = Some(child_b_id);
                 state.cell_ids[data.child_b_slot] = child_b_id;
                 state.next_cell_id += 1;
                 state.positions[data.child_b_slot] = data.child_b_pos;
@@ -1854,10 +2483,11 @@ pub fn division_step(
                 state.mode_indices[data.child_b_slot] = data.child_b_mode_idx;
 
                 // Apply pseudo-random rotation perturbation (0.001 radians)
-                let random_rotation_b = pseudo_random_rotation(child_b_id, _rng_seed);
+                let random_rotation_b = pseudo_random_rotation(child_b_id, rng_seed);
                 state.rotations[data.child_b_slot] = data.child_b_orientation * random_rotation_b;
 
                 state.genome_orientations[data.child_b_slot] = data.child_b_genome_orientation;
+                state.lineage_references[data.child_b_slot] = data.child_b_lineage;
                 state.angular_velocities[data.child_b_slot] = bevy::prelude::Vec3::ZERO;
                 state.forces[data.child_b_slot] = bevy::prelude::Vec3::ZERO;
                 state.torques[data.child_b_slot] = bevy::prelude::Vec3::ZERO;
@@ -1865,20 +2495,29 @@ pub fn division_step(
                 state.prev_accelerations[data.child_b_slot] = bevy::prelude::Vec3::ZERO;
                 state.stiffnesses[data.child_b_slot] = data.parent_stiffness;
                 state.birth_times[data.child_b_slot] = child_birth_time;
-                state.split_intervals[data.child_b_slot] = data.child_b_split_interval;
-                state.split_masses[data.child_b_slot] = data.child_b_split_mass_threshold;
+                state.resolve_split_thresholds(data.child_b_slot, genome, fixed_timestep, rng_seed);
                 // Split count: reset to 0 if mode changed, otherwise inherit parent's count + 1
                 state.split_counts[data.child_b_slot] = data.child_b_split_count;
+                state.set_low_nutrient_boost(data.child_b_slot, false);
+                state.set_pinned(data.child_b_slot, false);
+                state.set_user_data(data.child_b_slot, data.child_b_user_data);
+                // Both children start from the parent's averaged load
+                state.copy_force_heat(data.child_a_slot, data.child_b_slot);
 
                 // Initialize adhesion indices for child B
                 state.adhesion_manager.init_cell_adhesion_indices(data.child_b_slot);
             }
             
             // Record the division event
-            pass_division_events.push(DivisionEvent {
+            state.division_events_buffer.push(DivisionEvent {
                 parent_idx: data.parent_idx,
                 child_a_idx: data.child_a_slot,
                 child_b_idx: data.child_b_slot,
+                parent_id: data.parent_id,
+                parent_mode: data.parent_mode_idx,
+                child_ids,
+                child_modes: [data.child_a_mode_idx, data.child_b_mode_idx],
+                child_rules: data.child_mode_rules,
             });
         }
         
@@ -1887,7 +2526,7 @@ pub fn division_step(
         for data in &division_data_list {
             // Create child-to-child adhesion if parent mode allows it
             let mode_index = data.parent_mode_idx;
-            let mode = genome.modes.get(mode_index);
+            let mode = compiled.mode(mode_index);
 
             // Inherit adhesions from parent to children based on zone classification
             // CRITICAL: Pass parent's saved genome orientation, not from state (child A has overwritten it)
@@ -1898,17 +2537,17 @@ pub fn division_step(
                 data.child_a_slot,
                 data.child_b_slot,
                 data.parent_genome_orientation,
+                data.split_direction_local,
             );
 
 
             if let Some(mode) = mode {
-            if mode.parent_make_adhesion && mode.child_a.keep_adhesion && mode.child_b.keep_adhesion {
+            if mode.bonds_children {
                 // CRITICAL: Use split direction from parent's GENOME orientation (not world positions!)
                 // This ensures anchors stay aligned with the genome's intended split direction
-                // even if physics has moved the cells slightly
-                let pitch = mode.parent_split_direction.x.to_radians();
-                let yaw = mode.parent_split_direction.y.to_radians();
-                let split_dir_local = Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z;
+                // even if physics has moved the cells slightly; jitter is included so the
+                // anchors match where the children were actually placed
+                let split_dir_local = data.split_direction_local;
                 
                 // CRITICAL: Match C++ implementation exactly
                 // Direction vectors in parent's local frame:
@@ -1919,32 +2558,16 @@ pub fn division_step(
                 let direction_a_to_b_parent_local = -split_dir_local;
                 let direction_b_to_a_parent_local = split_dir_local;
                 
-                let anchor_direction_a = (mode.child_a.orientation.inverse() * direction_a_to_b_parent_local).normalize();
-                let anchor_direction_b = (mode.child_b.orientation.inverse() * direction_b_to_a_parent_local).normalize();
+                let anchor_direction_a = (mode.child_orientations[0].inverse() * direction_a_to_b_parent_local).normalize();
+                let anchor_direction_b = (mode.child_orientations[1].inverse() * direction_b_to_a_parent_local).normalize();
                 
                 // Get genome orientations for twist references
                 let child_a_genome_orientation = state.genome_orientations[data.child_a_slot];
                 let child_b_genome_orientation = state.genome_orientations[data.child_b_slot];
                 
                 // Get child mode split directions for zone classification
-                let child_a_mode = genome.modes.get(data.child_a_mode_idx);
-                let child_b_mode = genome.modes.get(data.child_b_mode_idx);
-                
-                let child_a_split_dir = if let Some(m) = child_a_mode {
-                    let pitch = m.parent_split_direction.x.to_radians();
-                    let yaw = m.parent_split_direction.y.to_radians();
-                    Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z
-                } else {
-                    Vec3::Z
-                };
-                
-                let child_b_split_dir = if let Some(m) = child_b_mode {
-                    let pitch = m.parent_split_direction.x.to_radians();
-                    let yaw = m.parent_split_direction.y.to_radians();
-                    Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z
-                } else {
-                    Vec3::Z
-                };
+                let child_a_split_dir = compiled.mode(data.child_a_mode_idx).map_or(Vec3::Z, |m| m.split_direction);
+                let child_b_split_dir = compiled.mode(data.child_b_mode_idx).map_or(Vec3::Z, |m| m.split_direction);
                 
                 // Create child-to-child connection with parent's mode index
                 let result = state.adhesion_manager.add_adhesion_with_directions(
@@ -1971,14 +2594,18 @@ pub fn division_step(
         // Simply update cell count (child B cells are already written)
         let new_cell_count = state.cell_count + division_data_list.len();
         state.cell_count = new_cell_count;
+        state.collision_cache.invalidate();
+        state.scratch.division_data = division_data_list;
         
         // No index remapping needed for adhesion connections since:
         // - Child A reuses parent index (adhesions already point to correct cell)
         // - Child B is at a new index (adhesions were created with correct index)
         // - Non-dividing cells keep their indices
         
-        // Add this pass's events to the pre-allocated buffer
-        state.division_events_buffer.extend(pass_division_events);
+        // This pass's events went straight to the pre-allocated buffer
+        let pass_divisions = state.division_events_buffer.len() - events_before_pass;
+        state.division_stats.last_step_divisions += pass_divisions as u32;
+        state.division_stats.divisions += pass_divisions as u64;
         
         // Check if we're at capacity - if so, stop processing passes
         if state.cell_count >= max_cells {
@@ -2089,8 +2716,9 @@ pub fn verlet_integrate_velocities_soa_st(
     masses: &[f32],
     dt: f32,
     velocity_damping: f32,
+    math: PhysicsMath,
 ) {
-    let velocity_damping_factor = velocity_damping.powf(dt * 100.0);
+    let velocity_damping_factor = math.powf(velocity_damping, dt * 100.0);
     
     for i in 0..velocities.len() {
         // Skip invalid masses
@@ -2122,10 +2750,11 @@ pub fn verlet_integrate_velocities_soa(
     masses: &[f32],
     dt: f32,
     velocity_damping: f32,
+    math: PhysicsMath,
 ) {
     use rayon::prelude::*;
     
-    let velocity_damping_factor = velocity_damping.powf(dt * 100.0);
+    let velocity_damping_factor = math.powf(velocity_damping, dt * 100.0);
     
     // Use parallel iteration for better performance with many cells
     velocities.par_iter_mut()
@@ -2155,10 +2784,13 @@ pub fn verlet_integrate_velocities_soa(
 /// Flagellocytes apply a forward thrust force in their orientation direction
 pub fn apply_swim_forces_st(
     forces: &mut [Vec3],
+    positions: &[Vec3],
     rotations: &[Quat],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
+    field: &crate::simulation::ChemicalField,
     enable_swim: bool,
+    math: PhysicsMath,
 ) {
     // Skip if swim is disabled (e.g., in Preview mode)
     if !enable_swim {
@@ -2167,15 +2799,18 @@ pub fn apply_swim_forces_st(
     
     for i in 0..forces.len() {
         let mode_index = mode_indices[i];
-        if let Some(mode) = genome.modes.get(mode_index) {
+        if let Some(mode) = genome.mode(mode_index) {
             // Only apply swim force to Flagellocyte cells (cell_type == 1)
-            if mode.cell_type == 1 && mode.swim_force > 0.0 {
-                // Get forward direction from cell's rotation (local +Z axis)
-                let forward = rotations[i] * Vec3::Z;
+            if mode.swims() {
+                // Get forward direction from cell's rotation (local +Z axis), bent by taxis
+                let forward = math.rotate(rotations[i], Vec3::Z);
+                let direction = crate::simulation::chemical_field::taxis_direction(
+                    forward, positions[i], mode.taxis_channel, mode.taxis_strength, field,
+                );
                 
-                // Apply thrust force in forward direction
+                // Apply thrust force in swim direction
                 // Scale by 120.0 (12x multiplier from base 10.0) to make the force meaningful in the physics simulation
-                let thrust_force = forward * mode.swim_force * 120.0;
+                let thrust_force = direction * mode.swim_force * 120.0;
                 forces[i] += thrust_force;
             }
         }
@@ -2186,10 +2821,13 @@ pub fn apply_swim_forces_st(
 /// Flagellocytes apply a forward thrust force in their orientation direction
 pub fn apply_swim_forces(
     forces: &mut [Vec3],
+    positions: &[Vec3],
     rotations: &[Quat],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
+    field: &crate::simulation::ChemicalField,
     enable_swim: bool,
+    math: PhysicsMath,
 ) {
     // Skip if swim is disabled (e.g., in Preview mode)
     if !enable_swim {
@@ -2199,18 +2837,22 @@ pub fn apply_swim_forces(
     use rayon::prelude::*;
     
     forces.par_iter_mut()
+        .zip(positions.par_iter())
         .zip(rotations.par_iter())
         .zip(mode_indices.par_iter())
-        .for_each(|((force, rotation), mode_index)| {
-            if let Some(mode) = genome.modes.get(*mode_index) {
+        .for_each(|(((force, position), rotation), mode_index)| {
+            if let Some(mode) = genome.mode(*mode_index) {
                 // Only apply swim force to Flagellocyte cells (cell_type == 1)
-                if mode.cell_type == 1 && mode.swim_force > 0.0 {
-                    // Get forward direction from cell's rotation (local +Z axis)
-                    let forward = *rotation * Vec3::Z;
+                if mode.swims() {
+                    // Get forward direction from cell's rotation (local +Z axis), bent by taxis
+                    let forward = math.rotate(*rotation, Vec3::Z);
+                    let direction = crate::simulation::chemical_field::taxis_direction(
+                        forward, *position, mode.taxis_channel, mode.taxis_strength, field,
+                    );
                     
-                    // Apply thrust force in forward direction
+                    // Apply thrust force in swim direction
                     // Scale by 120.0 (12x multiplier from base 10.0) to make the force meaningful in the physics simulation
-                    let thrust_force = forward * mode.swim_force * 120.0;
+                    let thrust_force = direction * mode.swim_force * 120.0;
                     *force += thrust_force;
                 }
             }
@@ -2234,6 +2876,7 @@ pub fn apply_boundary_forces_soa_st(
     let soft_zone_thickness = 5.0; // Start applying force 5 units before boundary
     let soft_zone_start = boundary_radius - soft_zone_thickness;
     let max_boundary_force = 500.0; // Maximum inward force at the boundary
+    let math = config.math();
     
     for i in 0..positions.len() {
         let distance_from_origin = positions[i].length();
@@ -2260,7 +2903,7 @@ pub fn apply_boundary_forces_soa_st(
             
             // Apply torque to rotate cell to face inward
             // Get the cell's forward direction (local +Z axis)
-            let forward = rotations[i] * Vec3::Z;
+            let forward = math.rotate(rotations[i], Vec3::Z);
             
             // Calculate desired direction (toward center)
             let desired_direction = -r_hat;
@@ -2275,7 +2918,7 @@ pub fn apply_boundary_forces_soa_st(
                 
                 // Calculate angle between current and desired direction
                 let dot_product = forward.dot(desired_direction).clamp(-1.0, 1.0);
-                let angle = dot_product.acos();
+                let angle = math.acos(dot_product);
                 
                 // Torque magnitude increases with penetration and angle
                 let torque_strength = 50.0 * penetration_clamped * angle;
@@ -2317,6 +2960,7 @@ pub fn apply_boundary_forces_soa(
     let soft_zone_thickness = 5.0; // Start applying force 5 units before boundary
     let soft_zone_start = boundary_radius - soft_zone_thickness;
     let max_boundary_force = 500.0; // Maximum inward force at the boundary
+    let math = config.math();
     
     // Use parallel iteration for better performance with many cells
     positions.par_iter_mut()
@@ -2348,7 +2992,7 @@ pub fn apply_boundary_forces_soa(
                 
                 // Apply torque to rotate cell to face inward
                 // Get the cell's forward direction (local +Z axis)
-                let forward = *rot * Vec3::Z;
+                let forward = math.rotate(*rot, Vec3::Z);
                 
                 // Calculate desired direction (toward center)
                 let desired_direction = -r_hat;
@@ -2363,7 +3007,7 @@ pub fn apply_boundary_forces_soa(
                     
                     // Calculate angle between current and desired direction
                     let dot_product = forward.dot(desired_direction).clamp(-1.0, 1.0);
-                    let angle = dot_product.acos();
+                    let angle = math.acos(dot_product);
                     
                     // Torque magnitude increases with penetration and angle
                     let torque_strength = 50.0 * penetration_clamped * angle;
@@ -2394,8 +3038,9 @@ pub fn integrate_angular_velocities_soa_st(
     masses: &[f32],
     dt: f32,
     angular_damping: f32,
+    math: PhysicsMath,
 ) {
-    let angular_damping_factor = angular_damping.powf(dt * 100.0);
+    let angular_damping_factor = math.powf(angular_damping, dt * 100.0);
     
     for i in 0..angular_velocities.len() {
         if masses[i] <= 0.0 || !masses[i].is_finite() || radii[i] <= 0.0 {
@@ -2420,10 +3065,11 @@ pub fn integrate_angular_velocities_soa(
     masses: &[f32],
     dt: f32,
     angular_damping: f32,
+    math: PhysicsMath,
 ) {
     use rayon::prelude::*;
     
-    let angular_damping_factor = angular_damping.powf(dt * 100.0);
+    let angular_damping_factor = math.powf(angular_damping, dt * 100.0);
     
     angular_velocities.par_iter_mut()
         .zip(torques.par_iter())
@@ -2449,14 +3095,15 @@ pub fn integrate_rotations_soa_st(
     rotations: &mut [Quat],
     angular_velocities: &[Vec3],
     dt: f32,
+    math: PhysicsMath,
 ) {
     for i in 0..rotations.len() {
         let ang_vel = angular_velocities[i];
         if ang_vel.length_squared() > 0.0001 {
             let angle = ang_vel.length() * dt;
             let axis = ang_vel.normalize();
-            let delta_rotation = Quat::from_axis_angle(axis, angle);
-            rotations[i] = (delta_rotation * rotations[i]).normalize();
+            let delta_rotation = math.quat_from_axis_angle(axis, angle);
+            rotations[i] = math.quat_normalize(math.quat_mul(delta_rotation, rotations[i]));
         }
     }
 }
@@ -2466,6 +3113,7 @@ pub fn integrate_rotations_soa(
     rotations: &mut [Quat],
     angular_velocities: &[Vec3],
     dt: f32,
+    math: PhysicsMath,
 ) {
     use rayon::prelude::*;
     
@@ -2475,8 +3123,438 @@ pub fn integrate_rotations_soa(
             if ang_vel.length_squared() > 0.0001 {
                 let angle = ang_vel.length() * dt;
                 let axis = ang_vel.normalize();
-                let delta_rotation = Quat::from_axis_angle(axis, angle);
-                *rotation = (delta_rotation * *rotation).normalize();
+                let delta_rotation = math.quat_from_axis_angle(axis, angle);
+                *rotation = math.quat_normalize(math.quat_mul(delta_rotation, *rotation));
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+    use crate::simulation::PhysicsConfig;
+    
+    /// Run an interval-driven colony for `duration` simulated seconds at the given timestep
+    /// Returns (final state, number of divisions)
+    fn run_colony(fixed_timestep: f32, duration: f32) -> (CanonicalState, usize) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 2.0;
+        mode.nutrient_gain_rate = 1.0;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig {
+            fixed_timestep,
+            ..PhysicsConfig::default()
+        };
+        
+        let mut state = CanonicalState::new(64);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+            0, 0, 0.0, 2.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        
+        let mut divisions = 0;
+        let ticks = crate::simulation::clock::ticks_to_reach(duration, fixed_timestep);
+        for tick in 0..ticks {
+            let current_time = tick as f32 * fixed_timestep;
+            physics_step_with_genome(&mut state, &config, &genome, current_time, false);
+            let next_time = (tick + 1) as f32 * fixed_timestep;
+            divisions += division_step(&mut state, &genome, next_time, fixed_timestep, 64, 0).len();
+        }
+        (state, divisions)
+    }
+    
+    #[test]
+    fn test_halving_timestep_preserves_simulated_outcome() {
+        let (coarse, coarse_divisions) = run_colony(1.0 / 64.0, 7.0);
+        let (fine, fine_divisions) = run_colony(1.0 / 128.0, 7.0);
+        
+        // Divisions at t = 2, 4, 6 regardless of dt: 1 + 2 + 4
+        assert_eq!(coarse_divisions, 7);
+        assert_eq!(coarse_divisions, fine_divisions);
+        assert_eq!(coarse.cell_count, fine.cell_count);
+        
+        // Macroscopic shape is close but not bit-identical
+        let centroid = |s: &CanonicalState| s.positions[..s.cell_count].iter().copied().sum::<Vec3>() / s.cell_count as f32;
+        let spread = |s: &CanonicalState| {
+            let c = centroid(s);
+            s.positions[..s.cell_count].iter().map(|p| p.distance(c)).sum::<f32>() / s.cell_count as f32
+        };
+        let total_mass = |s: &CanonicalState| s.masses[..s.cell_count].iter().sum::<f32>();
+        
+        assert!(centroid(&coarse).distance(centroid(&fine)) < 0.5);
+        assert!((spread(&coarse) - spread(&fine)).abs() < 0.25 * spread(&coarse).max(1.0));
+        assert!((total_mass(&coarse) - total_mass(&fine)).abs() < 0.05 * total_mass(&coarse));
+    }
+
+    /// Grow a colony whose cells split every [2, 6] seconds
+    fn run_ranged_colony(genome: &GenomeData, ticks: u32, rng_seed: u64) -> CanonicalState {
+        let config = PhysicsConfig::default();
+        let fixed_timestep = config.fixed_timestep;
+        let mut state = CanonicalState::new(256);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+            0, 0, 0.0, 6.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        for tick in 0..ticks {
+            let current_time = tick as f32 * fixed_timestep;
+            physics_step_with_genome(&mut state, &config, genome, current_time, false);
+            let next_time = (tick + 1) as f32 * fixed_timestep;
+            division_step(&mut state, genome, next_time, fixed_timestep, 256, rng_seed);
+        }
+        state
+    }
+    
+    fn ranged_genome() -> GenomeData {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 6.0;
+        mode.split_interval_min = Some(2.0);
+        mode.nutrient_gain_rate = 1.0;
+        GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        }
+    }
+    
+    #[test]
+    fn test_split_interval_range_is_varied_and_deterministic() {
+        let genome = ranged_genome();
+        let ticks = crate::simulation::clock::ticks_to_reach(14.0, PhysicsConfig::default().fixed_timestep);
+        let state = run_ranged_colony(&genome, ticks, 42);
+        let intervals = &state.split_intervals[..state.cell_count];
+        
+        assert!(state.cell_count > 2);
+        assert!(intervals.iter().all(|i| (2.0..=6.0).contains(i)));
+        // Sibling children no longer share one sample
+        assert!(intervals.iter().any(|i| (i - intervals[0]).abs() > 1e-3));
+        
+        let again = run_ranged_colony(&genome, ticks, 42);
+        assert_eq!(again.cell_count, state.cell_count);
+        assert_eq!(&again.split_intervals[..again.cell_count], intervals);
+    }
+    
+    #[test]
+    fn test_editing_split_interval_updates_living_cells() {
+        let mut genome = ranged_genome();
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+        let ticks = crate::simulation::clock::ticks_to_reach(8.0, fixed_timestep);
+        let mut state = run_ranged_colony(&genome, ticks, 7);
+        assert!(state.cell_count > 1);
+        
+        // Preview edits resimulate through division_step, which re-resolves existing cells
+        genome.modes[0].split_interval = 30.0;
+        genome.modes[0].split_interval_min = None;
+        let next_time = (ticks + 1) as f32 * fixed_timestep;
+        division_step(&mut state, &genome, next_time, fixed_timestep, 256, 7);
+        
+        assert!(state.split_intervals[..state.cell_count].iter().all(|i| *i == 30.0));
+    }
+    
+    /// Divisions per tick of 1,000 founder cells born together, over their first generation
+    fn first_generation_divisions(split_phase_jitter: f32) -> Vec<usize> {
+        let mut mode = ModeSettings::new_self_splitting(0, "Founder".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 1.0;
+        mode.split_phase_jitter = split_phase_jitter;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+
+        let mut state = CanonicalState::new(2048);
+        for i in 0..1000 {
+            state.add_cell(Vec3::new((i % 10) as f32, (i / 10 % 10) as f32, (i / 100) as f32) * 3.0, Vec3::ZERO,
+                Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0, 0, 0, 0.0, 1.0, 1.0, 500.0, Quat::IDENTITY, 0);
+        }
+        // Children of the earliest divisions come due from 1.5s on
+        (1..crate::simulation::clock::ticks_to_reach(1.4, fixed_timestep))
+            .map(|tick| division_step(&mut state, &genome, tick as f32 * fixed_timestep, fixed_timestep, 2048, 11).len())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_phase_jitter_spreads_divisions_over_ticks() {
+        let in_step = first_generation_divisions(0.0);
+        let jittered = first_generation_divisions(0.5);
+        assert_eq!(in_step.iter().sum::<usize>(), 1000);
+        assert_eq!(jittered.iter().sum::<usize>(), 1000);
+
+        let peak = |counts: &[usize]| counts.iter().copied().max().unwrap_or(0);
+        assert_eq!(peak(&in_step), 1000);
+        assert!(peak(&jittered) < 100, "{} divisions in one tick", peak(&jittered));
+
+        assert_eq!(first_generation_divisions(0.0), in_step);
+        assert_eq!(first_generation_divisions(0.5), jittered);
+    }
+
+    /// Fast-growing colony held together by a boundary small enough to pull every cell toward the center
+    /// Returns the cell count at t = 10 and t = 16
+    fn run_confined_colony(max_contact_pressure: f32) -> (usize, usize) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Crowded".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 2.0;
+        mode.nutrient_gain_rate = 2.0;
+        mode.max_contact_pressure = max_contact_pressure;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig {
+            sphere_radius: 4.0,
+            ..PhysicsConfig::default()
+        };
+        let fixed_timestep = config.fixed_timestep;
+        
+        let mut state = CanonicalState::new(64);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 2.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        
+        let mut count_at_10 = 0;
+        for tick in 0..crate::simulation::clock::ticks_to_reach(16.0, fixed_timestep) {
+            if tick == crate::simulation::clock::ticks_to_reach(10.0, fixed_timestep) {
+                count_at_10 = state.cell_count;
+            }
+            let current_time = tick as f32 * fixed_timestep;
+            physics_step_with_genome(&mut state, &config, &genome, current_time, false);
+            let next_time = (tick + 1) as f32 * fixed_timestep;
+            division_step(&mut state, &genome, next_time, fixed_timestep, 64, 0);
+        }
+        (count_at_10, state.cell_count)
+    }
+    
+    #[test]
+    fn test_contact_inhibition_plateaus_confined_colony() {
+        let (_, uninhibited) = run_confined_colony(f32::INFINITY);
+        let (inhibited_at_10, inhibited) = run_confined_colony(0.1);
+        
+        // The control keeps dividing until capacity; the inhibited colony stops once cells are pressed together
+        assert_eq!(uninhibited, 64);
+        assert!(inhibited > 1);
+        assert!(inhibited < 64);
+        assert_eq!(inhibited, inhibited_at_10);
+    }
+    
+    /// Ring of `n` ready cells, each adhered to its two neighbors
+    fn ready_ring(n: usize) -> (CanonicalState, GenomeData) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Ring".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 1.0;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig::default();
+        
+        let mut state = CanonicalState::new(4 * n);
+        let radius = n as f32 / std::f32::consts::TAU * 2.0;
+        for i in 0..n {
+            let angle = i as f32 / n as f32 * std::f32::consts::TAU;
+            let position = Vec3::new(angle.cos(), 0.0, angle.sin()) * radius;
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+                0, 0, 0.0, 1.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        }
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let direction = (state.positions[j] - state.positions[i]).normalize();
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, i, j, 0,
+                direction, -direction, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+            );
+        }
+        (state, genome)
+    }
+    
+    /// Passes the former lower-index-first rule needs to divide a fully ready ring of `n`
+    /// cells: a ready cell deferred whenever an adhered ready neighbor had a lower index
+    fn lower_index_first_passes(n: usize) -> usize {
+        let mut ready = vec![true; n];
+        let mut passes = 0;
+        while ready.iter().any(|&r| r) {
+            let divide: Vec<usize> = (0..n)
+                .filter(|&i| ready[i])
+                .filter(|&i| ![(i + n - 1) % n, (i + 1) % n].iter().any(|&j| j < i && ready[j]))
+                .collect();
+            for i in divide {
+                ready[i] = false;
+            }
+            passes += 1;
+        }
+        passes
+    }
+    
+    #[test]
+    fn test_adhered_ring_divides_within_one_tick() {
+        let n = 64;
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+        let (mut state, genome) = ready_ring(n);
+        
+        let events = division_step(&mut state, &genome, 1.0, fixed_timestep, 4 * n, 0);
+        
+        // Half the ring divides in the first pass, the rest in the second
+        assert_eq!(events.len(), n);
+        assert_eq!(state.cell_count, 2 * n);
+        assert_eq!(state.division_stats.last_step_passes, 2);
+        assert_eq!(state.division_stats.last_step_deferred, n as u32 / 2);
+        assert_eq!(state.division_stats.divisions, n as u64);
+        
+        // The old rule advanced roughly one cell per pass and spilled over several ticks
+        assert!(lower_index_first_passes(n).div_ceil(MAX_DIVISION_PASSES) > 2);
+    }
+    
+    #[test]
+    fn test_adhered_ring_division_is_deterministic() {
+        let n = 64;
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+        let run = || {
+            let (mut state, genome) = ready_ring(n);
+            division_step(&mut state, &genome, 1.0, fixed_timestep, 4 * n, 0);
+            state
+        };
+        let (a, b) = (run(), run());
+        
+        assert_eq!(a.cell_count, b.cell_count);
+        assert_eq!(&a.cell_ids[..a.cell_count], &b.cell_ids[..b.cell_count]);
+        let bits = |s: &CanonicalState| s.positions[..s.cell_count].iter()
+            .flat_map(|p| p.to_array().map(f32::to_bits))
+            .collect::<Vec<_>>();
+        assert_eq!(bits(&a), bits(&b));
+    }
+    
+    /// Two rods (long along local X) overlapping along world X, either tip to tip or side by side
+    /// Returns their separation after a few ticks
+    fn rod_pair_separation(distance: f32, rotation: Quat) -> f32 {
+        let mut rod = ModeSettings::new_self_splitting(0, "Rod".to_string());
+        rod.shape_radii = Vec3::new(1.0, 0.3, 0.3);
+        let genome = GenomeData {
+            modes: vec![rod],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig::default();
+        
+        let mut state = CanonicalState::new(4);
+        for x in [0.0, distance] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, rotation, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 60.0, 10.0, config.default_stiffness, rotation, 0);
+        }
+        for tick in 0..4 {
+            physics_step_st_with_genome(&mut state, &config, &genome, tick as f32 * config.fixed_timestep);
+        }
+        state.positions[0].distance(state.positions[1])
+    }
+    
+    #[test]
+    fn test_rod_contacts_depend_on_orientation() {
+        let end_on = Quat::IDENTITY;
+        let side_on = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        
+        // Tips overlap by 0.4; the thin sides don't reach each other
+        assert!(rod_pair_separation(1.6, end_on) > 1.6);
+        assert!((rod_pair_separation(1.6, side_on) - 1.6).abs() < 1e-6);
+        
+        // Both touch, but end-on penetration (1.5) pushes far harder than side-on (0.1)
+        let end_on_push = rod_pair_separation(0.5, end_on) - 0.5;
+        let side_on_push = rod_pair_separation(0.5, side_on) - 0.5;
+        assert!(side_on_push > 0.0);
+        assert!(end_on_push > 2.0 * side_on_push);
+    }
+    
+    #[test]
+    fn test_sphere_modes_are_unaffected_by_shape_support() {
+        let mut sphere = ModeSettings::new_self_splitting(0, "Sphere".to_string());
+        sphere.split_mass = 1.0;
+        sphere.split_interval = 1.0;
+        sphere.nutrient_gain_rate = 1.0;
+        let spheres_only = GenomeData {
+            modes: vec![sphere.clone()],
+            ..GenomeData::default()
+        };
+        // An unused ellipsoid mode turns the shape passes on without touching sphere cells
+        let mut rod = sphere.clone();
+        rod.shape_radii = Vec3::new(1.0, 0.3, 0.3);
+        let with_rod_mode = GenomeData {
+            modes: vec![sphere, rod],
+            ..GenomeData::default()
+        };
+        
+        let run = |genome: &GenomeData| {
+            let config = PhysicsConfig::default();
+            let mut state = CanonicalState::new(64);
+            state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 1.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+            for tick in 0..crate::simulation::clock::ticks_to_reach(4.5, config.fixed_timestep) {
+                physics_step_with_genome(&mut state, &config, genome, tick as f32 * config.fixed_timestep, false);
+                division_step(&mut state, genome, (tick + 1) as f32 * config.fixed_timestep, config.fixed_timestep, 64, 0);
+            }
+            state
+        };
+        let (a, b) = (run(&spheres_only), run(&with_rod_mode));
+        
+        assert!(a.cell_count > 1);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+    }
+    
+    #[test]
+    fn test_out_of_range_child_mode_keeps_the_parent_mode() {
+        let base = ModeSettings::new_self_splitting(0, "Base".to_string());
+        let mut broken = ModeSettings::new_self_splitting(1, "Broken".to_string());
+        broken.split_mass = 1.0;
+        broken.split_interval = 1.0;
+        broken.child_a.mode_number = crate::genome::ModeIndex::new(50);
+        broken.child_b.mode_number = crate::genome::ModeIndex::from_raw(-3);
+        let genome = GenomeData { modes: vec![base, broken], ..GenomeData::default() };
+        assert_eq!(genome.validate().len(), 2);
+        
+        let mut state = CanonicalState::new(8);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.5, 1.0,
+            0, 1, 0.0, 1.0, 1.0, PhysicsConfig::default().default_stiffness, Quat::IDENTITY, 0);
+        division_step(&mut state, &genome, 2.0, PhysicsConfig::default().fixed_timestep, 8, 0);
+        
+        assert_eq!(state.cell_count, 2);
+        assert_eq!(&state.mode_indices[..2], &[1, 1]);
+    }
+    
+    /// Divide 1,000 lone cells of a mode with 20° split jitter, each with its own id and
+    /// division tick; returns the angle of each realized split from the mode's direction
+    fn jittered_split_deviations(rng_seed: u64) -> Vec<f32> {
+        let mut mode = ModeSettings::new_self_splitting(0, "Jittered".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 1.0;
+        mode.parent_split_direction = Vec2::new(30.0, 45.0);
+        mode.split_direction_jitter_degrees = 20.0;
+        mode.parent_make_adhesion = true;
+        let base = mode.split_direction();
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+        
+        (0..1000u32).map(|n| {
+            let mut state = CanonicalState::new(4);
+            state.next_cell_id = n * 7;
+            state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.5, 1.0,
+                0, 0, 0.0, 1.0, 1.0, PhysicsConfig::default().default_stiffness, Quat::IDENTITY, 0);
+            division_step(&mut state, &genome, 2.0 + n as f32 * fixed_timestep, fixed_timestep, 4, rng_seed);
+            assert_eq!(state.cell_count, 2);
+            
+            // The sibling bond's anchors follow the realized split, not the mode's direction
+            let split = (state.positions[0] - state.positions[1]).normalize();
+            assert!(state.adhesion_connections.anchor_direction_a[0].distance(-split) < 1e-4);
+            assert!(state.adhesion_connections.anchor_direction_b[0].distance(split) < 1e-4);
+            split.angle_between(base)
+        }).collect()
+    }
+    
+    #[test]
+    fn test_split_jitter_is_bounded_and_reproducible() {
+        let limit = 20f32.to_radians();
+        let deviations = jittered_split_deviations(9);
+        assert!(deviations.iter().all(|&d| d <= limit + 1e-3), "max {}°", deviations.iter().fold(0f32, |a, &b| a.max(b)).to_degrees());
+        // Spread over the whole cone: uniform over the cap puts about a quarter within half the limit
+        assert!(deviations.iter().any(|&d| d > limit * 0.95));
+        let inner = deviations.iter().filter(|&&d| d < limit * 0.5).count() as f32 / deviations.len() as f32;
+        assert!((0.18..0.32).contains(&inner), "{} within half the limit", inner);
+        
+        assert_eq!(jittered_split_deviations(9), deviations);
+        assert_ne!(jittered_split_deviations(10), deviations);
+    }
+}
@@ -34,8 +34,7 @@ impl Plugin for CpuSimPlugin {
 
 impl Plugin for CpuSimTimestepPlugin {
     fn build(&self, app: &mut App) {
-        // Use Bevy's default fixed timestep: 64 Hz
-        // Note: Time<Fixed> defaults to 64 Hz, so we don't need to set it explicitly
+        // Time<Fixed> is driven by PhysicsConfig::ticks_per_second (see update_fixed_timestep)
         app
             // Add physics systems to FixedUpdate schedule (runs at fixed rate)
             .add_systems(
@@ -56,8 +55,11 @@ impl Plugin for CpuSimTimestepPlugin {
             .add_systems(
                 Update,
                 (
+                    reconcile_cell_entities,
+                    apply_mode_visibility,
                     sync_ecs_from_canonical,
                     crate::cell::physics::sync_transforms,
+                    draw_pending_cells,
                 )
                     .chain()
                     .run_if(in_state(CpuSceneState::Active))
@@ -78,7 +80,7 @@ pub struct MainSimState {
     /// Initial state for potential reset
     pub initial_state: InitialState,
     
-    /// Mapping from cell ID to ECS entity
+    /// Mapping from cell ID to ECS entity (the source of truth; the index mappings are derived from it)
     pub id_to_entity: HashMap<u32, Entity>,
     
     /// Mapping from ECS entity to cell index in canonical state
@@ -88,21 +90,50 @@ pub struct MainSimState {
     /// This avoids HashMap lookups in the hot sync path
     pub index_to_entity: Vec<Option<Entity>>,
     
+    /// Cell ID whose entity `index_to_entity` holds for each slot (None while the slot has no entity)
+    pub mapped_ids: Vec<Option<u32>>,
+    
+    /// Slots below this may hold a mapping (the cell count at the last sync)
+    pub mapped_slots: usize,
+    
     /// OPTIMIZATION: Entity pool for reusing despawned entities
     /// Instead of despawning and spawning, we hide/show and update components
     pub entity_pool: Vec<Entity>,
     
+    /// Live cells still waiting for an entity (see `ENTITY_SPAWNS_PER_FRAME`)
+    pub pending_entities: usize,
+    
     /// OPTIMIZATION: Cached sphere mesh handle (reused for all cells)
     /// Creating meshes is VERY expensive - reuse the same mesh for all cells
     pub sphere_mesh: Handle<Mesh>,
     
     /// OPTIMIZATION: Material cache by color
-    /// Creating materials is expensive - cache and reuse by color, opacity, and emissive
-    /// Key is (r, g, b, a, emissive) as u8 values for fast lookup
-    pub material_cache: HashMap<(u8, u8, u8, u8, u8), Handle<StandardMaterial>>,
+    /// Creating materials is expensive - cache and reuse by color, opacity, emissive and pattern
+    pub material_cache: HashMap<MaterialKey, Handle<StandardMaterial>>,
     
     /// Simulation time (advances based on speed multiplier)
     pub simulation_time: f32,
+    
+    /// Genome with the active phase's overrides (see `genome::phases`)
+    pub phases: crate::genome::PhasedGenome,
+    
+    /// Genome the run started with at tick 0 (None for a scene continued from the preview)
+    pub start_genome: Option<crate::genome::GenomeData>,
+    
+    /// Genome edits and other interventions since the run started
+    pub interventions: crate::simulation::scene_file::RunInterventions,
+}
+
+impl MainSimState {
+    /// Scene the run started from, with its interventions, for replaying the run headlessly
+    ///
+    /// None for a scene continued from the preview, whose start isn't recorded.
+    pub fn run_scene(&self, description: String) -> Option<crate::simulation::SceneFile> {
+        let genome = self.start_genome.as_ref()?;
+        let mut scene = crate::simulation::SceneFile::from_initial_state(&self.initial_state, genome, description);
+        scene.interventions = self.interventions.clone();
+        Some(scene)
+    }
 }
 
 impl Default for MainSimState {
@@ -123,27 +154,34 @@ impl Default for MainSimState {
             id_to_entity: HashMap::new(),
             entity_to_index: HashMap::new(),
             index_to_entity: vec![None; capacity],
+            mapped_ids: vec![None; capacity],
+            mapped_slots: 0,
             entity_pool: Vec::with_capacity(capacity),
+            pending_entities: 0,
             sphere_mesh: Handle::default(), // Will be initialized in setup
             material_cache: HashMap::new(),
             simulation_time: 0.0,
+            phases: Default::default(),
+            start_genome: None,
+            interventions: Default::default(),
         }
     }
 }
 
-/// Update the fixed timestep based on simulation speed multiplier
+/// Update the fixed timestep based on tick rate and simulation speed multiplier
 fn update_fixed_timestep(
     sim_state: Res<crate::simulation::SimulationState>,
+    config: Res<PhysicsConfig>,
     mut time: ResMut<Time<Fixed>>,
 ) {
-    // Base timestep is 64 Hz (1/64 seconds)
-    let base_timestep = 1.0 / 64.0;
+    // Real time between ticks at 1x speed (1/64 seconds by default)
+    let base_timestep = 1.0 / config.ticks_per_second();
     
     // Clamp speed multiplier between 0.1x and 10x
     let speed = sim_state.speed_multiplier.clamp(0.1, 10.0);
     
     // Calculate new timestep (higher speed = SMALLER timestep = more ticks per frame)
-    // At 1x: 1/64 seconds per tick (64 Hz)
+    // At 1x and 64 ticks/s: 1/64 seconds per tick (64 Hz)
     // At 10x: 1/640 seconds per tick (640 Hz)
     let new_timestep = base_timestep / speed;
     
@@ -155,90 +193,156 @@ fn update_fixed_timestep(
 }
 
 /// Run main simulation physics step using canonical physics
-fn run_main_simulation(
+pub fn run_main_simulation(
     mut main_state: ResMut<MainSimState>,
     config: Res<PhysicsConfig>,
     genome: Res<crate::genome::CurrentGenome>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     threading_config: Res<crate::simulation::SimulationThreadingConfig>,
     mut gpu_physics: ResMut<crate::simulation::GpuPhysicsResource>,
+    mut gpu_pairs: ResMut<crate::simulation::GpuPairDetection>,
+    mut breakpoints: ResMut<crate::simulation::Breakpoints>,
+    mut sim_state: ResMut<crate::simulation::SimulationState>,
+    mut memory: ResMut<crate::simulation::SimulationMemory>,
+    mut problem_bonds: ResMut<crate::simulation::ProblemBonds>,
+    mut staging: ResMut<crate::simulation::GenomeStaging>,
+    mut notifications: ResMut<crate::ui::Notifications>,
 ) {
     // Early return if no cells (scene not initialized yet)
     if main_state.canonical_state.cell_count == 0 {
         return;
     }
+    let allocations_before = memory.count_allocations.then(crate::simulation::memory::allocation_count);
     
     // Run canonical physics step with genome-aware adhesion settings
     let current_time = main_state.simulation_time;
     
+    // Staged genome edits reach the run only at the start of a tick, recorded with that tick
+    let start_tick = crate::simulation::clock::ticks_to_reach(current_time, config.fixed_timestep);
+    let applied = staging.apply_due(&genome.genome, |running| {
+        let state = &main_state.canonical_state;
+        crate::simulation::genome_staging::is_division_lull(state, main_state.phases.genome(running), current_time)
+    });
+    if let Some(changes) = applied {
+        notifications.success(format!("Applied {} staged genome change(s) at tick {}", changes, start_tick));
+    }
+    let running_genome = staging.running_genome(&genome.genome);
+    // Staged or not, every genome edit that reaches the run is recorded for its replay
+    if genome.is_changed() || applied.is_some() {
+        let main_state = &mut *main_state;
+        if let Some(start_genome) = &main_state.start_genome {
+            main_state.interventions.record_genome(start_genome, start_tick, running_genome);
+        }
+    }
+    let mut breakpoint_watch = breakpoints.watch(&main_state.canonical_state, running_genome, current_time);
+    
+    // Phases switch at tick boundaries; taken out so the state can be stepped while it's borrowed
+    let mut phases = std::mem::take(&mut main_state.phases);
+    phases.update(running_genome, main_state.canonical_state.division_stats.divisions, current_time);
+    let effective_genome = phases.genome(running_genome);
+
+    // A running settle multiplies the configured damping for this tick only
+    let step_config = crate::simulation::settle::settled_config(&main_state.canonical_state, &config);
+    let config = step_config.as_ref();
+    
     // Choose physics implementation based on configuration
     if threading_config.gpu_physics_enabled && gpu_physics.enabled {
         crate::simulation::gpu_physics::physics_step_gpu_with_genome(
             &mut main_state.canonical_state,
             &config,
-            &genome.genome,
+            effective_genome,
             &mut gpu_physics,
             current_time,
             true, // Enable swim in main simulation mode
         );
+    } else if gpu_pairs.is_active(&config) {
+        // GPU grid binning and pair generation, CPU force computation
+        crate::simulation::cpu_physics::physics_step_with_genome_and_detector(
+            &mut main_state.canonical_state,
+            &config,
+            effective_genome,
+            current_time,
+            true, // Enable swim in main simulation mode
+            |state| crate::simulation::gpu_collision_pairs::detect_collisions_with_gpu_pairs(state, &config, &mut gpu_pairs),
+        );
     } else {
         crate::simulation::cpu_physics::physics_step_with_genome(
             &mut main_state.canonical_state,
             &config,
-            &genome.genome,
+            effective_genome,
             current_time,
             true, // Enable swim in main simulation mode
         );
     }
     
-    // Advance simulation time by the base timestep (64 Hz)
-    // The speed multiplier is already baked into the fixed timestep rate
-    // At 1x: timestep = 1/64, runs 64 times per second
-    // At 10x: timestep = 1/640, runs 640 times per second
-    // Both advance simulation time by 1/64 per tick
-    main_state.simulation_time += 1.0 / 64.0;
+    main_state.canonical_state.advance_settle();
+
+    // Advance simulation time by the integration timestep
+    // The speed multiplier and tick rate are baked into the Time<Fixed> rate,
+    // so every tick advances simulated time by exactly fixed_timestep seconds
+    main_state.simulation_time += config.fixed_timestep;
     
     // Get current simulation time before borrowing main_state mutably
     let current_sim_time = main_state.simulation_time;
+    let tick = crate::simulation::clock::ticks_to_reach(current_sim_time, config.fixed_timestep);
+    
+    // Log clamped adhesions while the connection indices still match this tick's force pass
+    problem_bonds.record(&main_state.canonical_state, tick);
     
-    // Handle divisions using simulation time
+    // Divisions see the tick's start time, as in Preview replays, so a scene
+    // continued from the preview ticks on exactly like the preview would
+    main_state.canonical_state.reanchor_genome_orientations = config.reanchor_genome_orientations;
     handle_divisions(
         &mut main_state,
-        &genome,
-        current_sim_time,
-        &mut commands,
-        &mut meshes,
-        &mut materials,
+        effective_genome,
+        current_time,
+        config.fixed_timestep,
     );
+    main_state.phases = phases;
+    
+    if let Some(before) = allocations_before {
+        memory.allocations_last_tick = Some(crate::simulation::memory::allocation_count() - before);
+    }
+    
+    // Breakpoints pause at the end of the tick their condition is met
+    if let Some(watch) = breakpoint_watch.as_mut() {
+        if let Some(hit) = watch.check(&main_state.canonical_state, running_genome, tick, current_sim_time) {
+            breakpoints.record_hit(hit);
+            sim_state.paused = true;
+        }
+    }
 }
 
-/// Convert color, opacity, and emissive to cache key
+/// Material cache key: (r, g, b, a, emissive) as u8 values plus the pattern kind and quantized scale
+pub type MaterialKey = (u8, u8, u8, u8, u8, Option<(crate::genome::PatternKind, u8)>);
+
+/// Convert color, opacity, emissive and pattern to cache key
 #[inline]
-fn material_cache_key(color: Vec3, opacity: f32, emissive: f32) -> (u8, u8, u8, u8, u8) {
+fn material_cache_key(color: Vec3, opacity: f32, emissive: f32, pattern: Option<crate::genome::CellPattern>) -> MaterialKey {
     (
         (color.x * 255.0) as u8,
         (color.y * 255.0) as u8,
         (color.z * 255.0) as u8,
         (opacity * 255.0) as u8,
         (emissive.clamp(0.0, 10.0) * 25.0) as u8, // Scale emissive to 0-250 range for 0-10 values
+        pattern.map(|p| (p.kind, (p.scale.clamp(0.0, 15.0) * 16.0) as u8)),
     )
 }
 
-/// Get or create a cached material for a given color, opacity, and emissive
+/// Get or create a cached material for a given color, opacity, emissive and pattern
 /// OPTIMIZATION: Reuses materials to enable GPU instancing
 fn get_or_create_material(
     color: Vec3,
     opacity: f32,
     emissive: f32,
-    material_cache: &mut HashMap<(u8, u8, u8, u8, u8), Handle<StandardMaterial>>,
+    pattern: Option<crate::genome::CellPattern>,
+    material_cache: &mut HashMap<MaterialKey, Handle<StandardMaterial>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
+    patterns: &crate::rendering::CellPatternTextures,
 ) -> Handle<StandardMaterial> {
-    let key = material_cache_key(color, opacity, emissive);
+    let key = material_cache_key(color, opacity, emissive, pattern);
     
     material_cache.entry(key).or_insert_with(|| {
-        materials.add(StandardMaterial {
+        let mut material = StandardMaterial {
             base_color: Color::srgba(color.x, color.y, color.z, opacity),
             emissive: LinearRgba::rgb(color.x * emissive, color.y * emissive, color.z * emissive),
             cull_mode: Some(bevy::render::render_resource::Face::Back),
@@ -250,254 +354,347 @@ fn get_or_create_material(
                 bevy::prelude::AlphaMode::Opaque
             },
             ..default()
-        })
+        };
+        patterns.apply(&mut material, pattern);
+        materials.add(material)
     }).clone()
 }
 
 /// Handle cell divisions in canonical state using the canonical division_step function
+///
+/// Only the canonical state changes here; entities for the children are created by
+/// `reconcile_cell_entities` over the following frames.
 fn handle_divisions(
     main_state: &mut MainSimState,
-    genome: &crate::genome::CurrentGenome,
+    genome: &crate::genome::GenomeData,
     current_time: f32,
-    commands: &mut Commands,
-    _meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    fixed_timestep: f32,
 ) {
     // Early exit if at capacity
-    // Use the actual max_cells from initial state (respects what was configured)
-    let max_cells = main_state.initial_state.max_cells;
+    // Use the live capacity (starts at the configured value and can grow at runtime)
+    let max_cells = main_state.canonical_state.capacity;
     if main_state.canonical_state.cell_count >= max_cells {
+        // Nothing divided this tick; don't leave the previous tick's events behind
+        main_state.canonical_state.division_events_buffer.clear();
         return;
     }
 
-    // Create a mapping from cell index to cell ID BEFORE division
-    // This allows us to look up which entities to despawn after division
-    let index_to_cell_id: Vec<u32> = main_state.canonical_state.cell_ids[..main_state.canonical_state.cell_count].to_vec();
-
-    // Run canonical division step with cell limit
-    // This will return events for cells that actually divided
     let rng_seed = main_state.initial_state.rng_seed;
-    let division_events = crate::simulation::cpu_physics::division_step(
+    crate::simulation::cpu_physics::division_step(
         &mut main_state.canonical_state,
-        &genome.genome,
+        genome,
         current_time,
+        fixed_timestep,
         max_cells,
         rng_seed,
     );
+}
 
-    // Collect parent cell IDs that ACTUALLY divided (from division events)
-    // Important: Only despawn cells that successfully divided, not cells that
-    // wanted to divide but couldn't due to capacity constraints
-    let mut parent_cell_ids = Vec::new();
-    for event in &division_events {
-        // parent_idx refers to the index BEFORE division/compaction
-        // Look up the cell_id from our saved mapping
-        if event.parent_idx < index_to_cell_id.len() {
-            parent_cell_ids.push(index_to_cell_id[event.parent_idx]);
-        }
+/// Most entity activations per frame
+///
+/// A division burst can create thousands of cells in one tick; spreading their entities
+/// over several frames keeps the frame time flat. Cells still waiting for an entity are
+/// drawn as placeholders by `draw_pending_cells`.
+pub const ENTITY_SPAWNS_PER_FRAME: usize = 200;
+
+/// Most entity releases per frame, budgeted apart from activations so a burst of deaths
+/// never holds back new cells
+pub const ENTITY_RELEASES_PER_FRAME: usize = 200;
+
+/// Entity work for one frame
+#[derive(Debug, Default, PartialEq)]
+struct EntityPlan {
+    /// Cell IDs whose entity goes back to the pool (the cell divided or died)
+    releases: Vec<u32>,
+    /// Slots of live cells that get an entity this frame
+    spawns: Vec<usize>,
+    /// Live cells left without an entity until a later frame
+    pending: usize,
+}
+
+/// Plan entity releases and activations for the live cells, each within its own budget
+///
+/// Releases are applied first: they only hide an entity, and they free pooled entities
+/// for the activations that follow.
+fn plan_entity_changes(
+    live_ids: &[u32],
+    id_to_entity: &HashMap<u32, Entity>,
+    spawn_budget: usize,
+    release_budget: usize,
+) -> EntityPlan {
+    // Steady state: every live cell already has its entity and no other cell does
+    if id_to_entity.len() == live_ids.len() && live_ids.iter().all(|cell_id| id_to_entity.contains_key(cell_id)) {
+        return EntityPlan::default();
     }
 
-    // Return parent entities to pool (instead of despawning)
-    for parent_cell_id in parent_cell_ids {
-        if let Some(parent_entity) = main_state.id_to_entity.remove(&parent_cell_id) {
-            if let Some(idx) = main_state.entity_to_index.remove(&parent_entity) {
-                main_state.index_to_entity[idx] = None;
+    let live: std::collections::HashSet<u32> = live_ids.iter().copied().collect();
+    let mut releases: Vec<u32> = id_to_entity.keys()
+        .copied()
+        .filter(|cell_id| !live.contains(cell_id))
+        .collect();
+    releases.sort_unstable();
+    releases.truncate(release_budget);
+
+    let missing: Vec<usize> = live_ids.iter()
+        .enumerate()
+        .filter(|(_, cell_id)| !id_to_entity.contains_key(cell_id))
+        .map(|(index, _)| index)
+        .collect();
+    let spawn_count = missing.len().min(spawn_budget);
+
+    EntityPlan {
+        releases,
+        pending: missing.len() - spawn_count,
+        spawns: missing[..spawn_count].to_vec(),
+    }
+}
+
+impl MainSimState {
+    /// Whether every live cell has its entity in its slot and no other cell has one
+    fn entities_in_sync(&self) -> bool {
+        let cell_count = self.canonical_state.cell_count;
+        self.pending_entities == 0
+            && self.mapped_slots == cell_count
+            && self.id_to_entity.len() == cell_count
+            && self.mapped_ids[..cell_count].iter()
+                .zip(&self.canonical_state.cell_ids[..cell_count])
+                .all(|(mapped, cell_id)| *mapped == Some(*cell_id))
+    }
+
+    /// Bring the index mappings in line with the canonical slots
+    ///
+    /// Canonical indices shift with division and removal, so slots are checked against
+    /// the cell IDs every frame, but only slots whose cell or entity changed are remapped.
+    fn sync_index_mappings(&mut self) {
+        let cell_count = self.canonical_state.cell_count;
+        let capacity = self.canonical_state.capacity.max(self.mapped_slots);
+        if self.index_to_entity.len() < capacity {
+            self.index_to_entity.resize(capacity, None);
+            self.mapped_ids.resize(capacity, None);
+        }
+
+        for index in 0..cell_count.max(self.mapped_slots) {
+            let cell_id = (index < cell_count).then(|| self.canonical_state.cell_ids[index]);
+            let entity = cell_id.and_then(|cell_id| self.id_to_entity.get(&cell_id).copied());
+            if self.index_to_entity[index] == entity && (entity.is_none() || self.mapped_ids[index] == cell_id) {
+                continue;
+            }
+            // The old entity may already have been mapped to the slot its cell moved to
+            if let Some(old) = self.index_to_entity[index] {
+                if self.entity_to_index.get(&old) == Some(&index) {
+                    self.entity_to_index.remove(&old);
+                }
+            }
+            self.index_to_entity[index] = entity;
+            self.mapped_ids[index] = entity.and(cell_id);
+            if let Some(entity) = entity {
+                self.entity_to_index.insert(entity, index);
             }
-            // Hide entity and return to pool
-            commands.entity(parent_entity).insert(Visibility::Hidden);
-            main_state.entity_pool.push(parent_entity);
         }
+        self.mapped_slots = cell_count;
     }
-    
-    // Batch spawn child entities for better performance
-    // Pre-allocate materials to avoid repeated lookups
-    let mut materials_needed: std::collections::HashMap<(u8, u8, u8, u8, u8), Handle<StandardMaterial>> = std::collections::HashMap::new();
-    
-    for event in &division_events {
-        let child_a_idx = event.child_a_idx;
-        let child_b_idx = event.child_b_idx;
-        
-        let child_a_mode_idx = main_state.canonical_state.mode_indices[child_a_idx];
-        let child_b_mode_idx = main_state.canonical_state.mode_indices[child_b_idx];
-        
-        let child_a_mode = genome.genome.modes.get(child_a_mode_idx);
-        let child_b_mode = genome.genome.modes.get(child_b_mode_idx);
-        
-        let child_a_color = child_a_mode.map(|m| m.color).unwrap_or(Vec3::ONE);
-        let child_a_opacity = child_a_mode.map(|m| m.opacity).unwrap_or(1.0);
-        let child_a_emissive = child_a_mode.map(|m| m.emissive).unwrap_or(0.0);
-        let child_b_color = child_b_mode.map(|m| m.color).unwrap_or(Vec3::ONE);
-        let child_b_opacity = child_b_mode.map(|m| m.opacity).unwrap_or(1.0);
-        let child_b_emissive = child_b_mode.map(|m| m.emissive).unwrap_or(0.0);
-        
-        // Pre-fetch materials
-        let key_a = material_cache_key(child_a_color, child_a_opacity, child_a_emissive);
-        if !materials_needed.contains_key(&key_a) {
-            let mat = get_or_create_material(child_a_color, child_a_opacity, child_a_emissive, &mut main_state.material_cache, materials);
-            materials_needed.insert(key_a, mat);
-        }
-        let key_b = material_cache_key(child_b_color, child_b_opacity, child_b_emissive);
-        if !materials_needed.contains_key(&key_b) {
-            let mat = get_or_create_material(child_b_color, child_b_opacity, child_b_emissive, &mut main_state.material_cache, materials);
-            materials_needed.insert(key_b, mat);
+
+    /// Forget every entity (their scene was despawned)
+    fn clear_entities(&mut self) {
+        self.id_to_entity.clear();
+        self.entity_to_index.clear();
+        self.entity_pool.clear();
+        self.pending_entities = 0;
+        let capacity = self.canonical_state.capacity;
+        self.index_to_entity = vec![None; capacity];
+        self.mapped_ids = vec![None; capacity];
+        self.mapped_slots = 0;
+    }
+}
+
+/// Keep one entity per live cell, keyed by cell ID, spawning and releasing within the frame budgets
+fn reconcile_cell_entities(
+    mut main_state: ResMut<MainSimState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    visibility: Res<crate::rendering::ModeVisibility>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    patterns: Res<crate::rendering::CellPatternTextures>,
+) {
+    let main_state = &mut *main_state;
+    if main_state.entities_in_sync() {
+        return;
+    }
+
+    let cell_count = main_state.canonical_state.cell_count;
+    let plan = plan_entity_changes(
+        &main_state.canonical_state.cell_ids[..cell_count],
+        &main_state.id_to_entity,
+        ENTITY_SPAWNS_PER_FRAME,
+        ENTITY_RELEASES_PER_FRAME,
+    );
+
+    // Return entities of divided and dead cells to the pool (instead of despawning)
+    for cell_id in &plan.releases {
+        if let Some(entity) = main_state.id_to_entity.remove(cell_id) {
+            commands.entity(entity).insert(Visibility::Hidden);
+            main_state.entity_pool.push(entity);
         }
     }
-    
-    // Batch spawn all children
-    let sphere_mesh = main_state.sphere_mesh.clone();
-    
-    for event in division_events {
-        let child_a_idx = event.child_a_idx;
-        let child_b_idx = event.child_b_idx;
-        
-        // Batch read child A properties
-        let (child_a_pos, child_a_vel, child_a_rotation, child_a_mass, child_a_radius, 
-             child_a_mode_idx, child_a_split_interval, child_a_birth_time, cell_id_a) = (
-            main_state.canonical_state.positions[child_a_idx],
-            main_state.canonical_state.velocities[child_a_idx],
-            main_state.canonical_state.rotations[child_a_idx],
-            main_state.canonical_state.masses[child_a_idx],
-            main_state.canonical_state.radii[child_a_idx],
-            main_state.canonical_state.mode_indices[child_a_idx],
-            main_state.canonical_state.split_intervals[child_a_idx],
-            main_state.canonical_state.birth_times[child_a_idx],
-            main_state.canonical_state.cell_ids[child_a_idx],
+
+    for &index in &plan.spawns {
+        let entity = activate_cell_entity(main_state, index, &genome, &visibility, &mut commands, &mut meshes, &mut materials, &patterns);
+        main_state.id_to_entity.insert(main_state.canonical_state.cell_ids[index], entity);
+    }
+    main_state.pending_entities = plan.pending;
+
+    main_state.sync_index_mappings();
+}
+
+/// Show the cell in slot `index`, reusing a pooled entity when one is available
+fn activate_cell_entity(
+    main_state: &mut MainSimState,
+    index: usize,
+    genome: &crate::genome::CurrentGenome,
+    visibility: &crate::rendering::ModeVisibility,
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    patterns: &crate::rendering::CellPatternTextures,
+) -> Entity {
+    let state = &main_state.canonical_state;
+    let (cell_id, position, velocity, rotation, mass, radius, mode_index, genome_id, split_interval, birth_time) = (
+        state.cell_ids[index],
+        state.positions[index],
+        state.velocities[index],
+        state.rotations[index],
+        state.masses[index],
+        state.radii[index],
+        state.mode_indices[index],
+        state.genome_ids[index],
+        state.split_intervals[index],
+        state.birth_times[index],
+    );
+
+    let mode = genome.genome.modes.get(mode_index);
+    let color = mode.map(|m| m.color).unwrap_or(Vec3::ONE);
+    let opacity = mode.map(|m| m.opacity).unwrap_or(1.0);
+    let emissive = mode.map(|m| m.emissive).unwrap_or(0.0);
+    let pattern = mode.and_then(|m| m.pattern);
+    // Hidden modes keep their normal material so unhiding only toggles visibility
+    let multiplier = visibility.cell_multiplier(mode_index, cell_id);
+    let opacity = if multiplier > 0.0 { opacity * multiplier } else { opacity };
+    let material = get_or_create_material(color, opacity, emissive, pattern, &mut main_state.material_cache, materials, patterns);
+
+    // Flagellocytes get their own mesh; everything else shares the sphere
+    let cell_type = mode.map(|m| m.cell_type).unwrap_or(0);
+    let mesh = if cell_type == 1 {
+        let swim_force = mode.map(|m| m.swim_force).unwrap_or(0.0);
+        meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(1.0, swim_force, 5))
+    } else {
+        main_state.sphere_mesh.clone()
+    };
+
+    let components = (
+        Cell { mass, radius, genome_id, mode_index, cell_type },
+        CellPosition { position, velocity },
+        CellOrientation { rotation, angular_velocity: Vec3::ZERO },
+        CellSignaling::default(),
+        crate::cell::division::DivisionTimer { birth_time, split_interval },
+        crate::cell::physics::CellForces::default(),
+        crate::cell::physics::Cytoskeleton::default(),
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        Transform::from_translation(position).with_rotation(rotation).with_scale(Vec3::splat(radius)),
+        if multiplier > 0.0 { Visibility::Visible } else { Visibility::Hidden },
+    );
+
+    if let Some(pooled_entity) = main_state.entity_pool.pop() {
+        // Reuse pooled entity - just update components
+        commands.entity(pooled_entity).insert(components);
+        pooled_entity
+    } else {
+        commands.spawn((components, CpuSceneEntity)).id()
+    }
+}
+
+/// Re-material live cells when the legend's isolate/hide filters or the isolated group change
+///
+/// Materials are shared through the cache, so dimmed cells switch to a cached
+/// material with the reduced opacity rather than editing the shared one.
+fn apply_mode_visibility(
+    mut main_state: ResMut<MainSimState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    visibility: Res<crate::rendering::ModeVisibility>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    patterns: Res<crate::rendering::CellPatternTextures>,
+) {
+    if !visibility.is_changed() {
+        return;
+    }
+
+    let main_state = &mut *main_state;
+    for index in 0..main_state.canonical_state.cell_count {
+        let Some(entity) = main_state.index_to_entity[index] else {
+            continue;
+        };
+        let mode_index = main_state.canonical_state.mode_indices[index];
+        let mode = genome.genome.modes.get(mode_index);
+        let multiplier = visibility.cell_multiplier(mode_index, main_state.canonical_state.cell_ids[index]);
+        if multiplier == 0.0 {
+            commands.entity(entity).insert(Visibility::Hidden);
+            continue;
+        }
+        let material = get_or_create_material(
+            mode.map(|m| m.color).unwrap_or(Vec3::ONE),
+            mode.map(|m| m.opacity).unwrap_or(1.0) * multiplier,
+            mode.map(|m| m.emissive).unwrap_or(0.0),
+            mode.and_then(|m| m.pattern),
+            &mut main_state.material_cache,
+            &mut materials,
+            &patterns,
         );
-        
-        // Batch read child B properties
-        let (child_b_pos, child_b_vel, child_b_rotation, child_b_mass, child_b_radius,
-             child_b_mode_idx, child_b_split_interval, child_b_birth_time, cell_id_b) = (
-            main_state.canonical_state.positions[child_b_idx],
-            main_state.canonical_state.velocities[child_b_idx],
-            main_state.canonical_state.rotations[child_b_idx],
-            main_state.canonical_state.masses[child_b_idx],
-            main_state.canonical_state.radii[child_b_idx],
-            main_state.canonical_state.mode_indices[child_b_idx],
-            main_state.canonical_state.split_intervals[child_b_idx],
-            main_state.canonical_state.birth_times[child_b_idx],
-            main_state.canonical_state.cell_ids[child_b_idx],
+        commands.entity(entity).insert((MeshMaterial3d(material), Visibility::Visible));
+    }
+}
+
+/// Draw cells that are still waiting for an entity so bursts never pop in late
+fn draw_pending_cells(
+    main_state: Res<MainSimState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    visibility: Res<crate::rendering::ModeVisibility>,
+    mut gizmos: Gizmos,
+) {
+    if main_state.pending_entities == 0 {
+        return;
+    }
+
+    let state = &main_state.canonical_state;
+    for index in 0..state.cell_count {
+        if main_state.index_to_entity[index].is_some() {
+            continue;
+        }
+        if visibility.cell_multiplier(state.mode_indices[index], state.cell_ids[index]) == 0.0 {
+            continue;
+        }
+        let color = genome.genome.modes.get(state.mode_indices[index])
+            .map(|m| m.color)
+            .unwrap_or(Vec3::ONE);
+        gizmos.sphere(
+            Isometry3d::new(state.positions[index], state.rotations[index]),
+            state.radii[index],
+            Color::srgb(color.x, color.y, color.z),
         );
-        
-        let child_a_mode = genome.genome.modes.get(child_a_mode_idx);
-        let child_b_mode = genome.genome.modes.get(child_b_mode_idx);
-        
-        let child_a_color = child_a_mode.map(|m| m.color).unwrap_or(Vec3::ONE);
-        let child_a_opacity = child_a_mode.map(|m| m.opacity).unwrap_or(1.0);
-        let child_a_emissive = child_a_mode.map(|m| m.emissive).unwrap_or(0.0);
-        let child_b_color = child_b_mode.map(|m| m.color).unwrap_or(Vec3::ONE);
-        let child_b_opacity = child_b_mode.map(|m| m.opacity).unwrap_or(1.0);
-        let child_b_emissive = child_b_mode.map(|m| m.emissive).unwrap_or(0.0);
-        
-        let material_a = materials_needed[&material_cache_key(child_a_color, child_a_opacity, child_a_emissive)].clone();
-        let material_b = materials_needed[&material_cache_key(child_b_color, child_b_opacity, child_b_emissive)].clone();
-        
-        // Check if cells are flagellocytes and create appropriate meshes
-        let is_flagellocyte_a = child_a_mode.map(|m| m.cell_type == 1).unwrap_or(false);
-        let swim_force_a = child_a_mode.map(|m| m.swim_force).unwrap_or(0.0);
-        let mesh_a = if is_flagellocyte_a {
-            _meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(1.0, swim_force_a, 5))
-        } else {
-            sphere_mesh.clone()
-        };
-        
-        // Get or spawn child A entity (reuse from pool if available)
-        let entity_a = if let Some(pooled_entity) = main_state.entity_pool.pop() {
-            // Reuse pooled entity - just update components
-            commands.entity(pooled_entity).insert((
-            Cell { mass: child_a_mass, radius: child_a_radius, genome_id: 0, mode_index: child_a_mode_idx, cell_type: child_a_mode.map(|m| m.cell_type).unwrap_or(0) },
-            CellPosition { position: child_a_pos, velocity: child_a_vel },
-            CellOrientation { rotation: child_a_rotation, angular_velocity: Vec3::ZERO },
-            CellSignaling::default(),
-            crate::cell::division::DivisionTimer { birth_time: child_a_birth_time, split_interval: child_a_split_interval },
-            crate::cell::physics::CellForces::default(),
-            crate::cell::physics::Cytoskeleton::default(),
-            Mesh3d(mesh_a.clone()),
-            MeshMaterial3d(material_a),
-            Transform::from_translation(child_a_pos).with_rotation(child_a_rotation).with_scale(Vec3::splat(child_a_radius)),
-            Visibility::Visible,
-            ));
-            pooled_entity
-        } else {
-            // No pooled entity available - spawn new one
-            commands.spawn((
-                Cell { mass: child_a_mass, radius: child_a_radius, genome_id: 0, mode_index: child_a_mode_idx, cell_type: child_a_mode.map(|m| m.cell_type).unwrap_or(0) },
-                CellPosition { position: child_a_pos, velocity: child_a_vel },
-                CellOrientation { rotation: child_a_rotation, angular_velocity: Vec3::ZERO },
-                CellSignaling::default(),
-                crate::cell::division::DivisionTimer { birth_time: child_a_birth_time, split_interval: child_a_split_interval },
-                crate::cell::physics::CellForces::default(),
-                crate::cell::physics::Cytoskeleton::default(),
-                Mesh3d(mesh_a.clone()),
-                MeshMaterial3d(material_a.clone()),
-                Transform::from_translation(child_a_pos).with_rotation(child_a_rotation).with_scale(Vec3::splat(child_a_radius)),
-                Visibility::Visible,
-                CpuSceneEntity,
-            )).id()
-        };
-        
-        main_state.id_to_entity.insert(cell_id_a, entity_a);
-        main_state.entity_to_index.insert(entity_a, child_a_idx);
-        main_state.index_to_entity[child_a_idx] = Some(entity_a);
-        
-        // Check if child B is flagellocyte
-        let is_flagellocyte_b = child_b_mode.map(|m| m.cell_type == 1).unwrap_or(false);
-        let swim_force_b = child_b_mode.map(|m| m.swim_force).unwrap_or(0.0);
-        let mesh_b = if is_flagellocyte_b {
-            _meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(1.0, swim_force_b, 5))
-        } else {
-            sphere_mesh.clone()
-        };
-        
-        // Get or spawn child B entity (reuse from pool if available)
-        let entity_b = if let Some(pooled_entity) = main_state.entity_pool.pop() {
-            // Reuse pooled entity - just update components
-            commands.entity(pooled_entity).insert((
-            Cell { mass: child_b_mass, radius: child_b_radius, genome_id: 0, mode_index: child_b_mode_idx, cell_type: child_b_mode.map(|m| m.cell_type).unwrap_or(0) },
-            CellPosition { position: child_b_pos, velocity: child_b_vel },
-            CellOrientation { rotation: child_b_rotation, angular_velocity: Vec3::ZERO },
-            CellSignaling::default(),
-            crate::cell::division::DivisionTimer { birth_time: child_b_birth_time, split_interval: child_b_split_interval },
-            crate::cell::physics::CellForces::default(),
-            crate::cell::physics::Cytoskeleton::default(),
-            Mesh3d(mesh_b.clone()),
-            MeshMaterial3d(material_b),
-            Transform::from_translation(child_b_pos).with_rotation(child_b_rotation).with_scale(Vec3::splat(child_b_radius)),
-            Visibility::Visible,
-            ));
-            pooled_entity
-        } else {
-            // No pooled entity available - spawn new one
-            commands.spawn((
-                Cell { mass: child_b_mass, radius: child_b_radius, genome_id: 0, mode_index: child_b_mode_idx, cell_type: child_b_mode.map(|m| m.cell_type).unwrap_or(0) },
-                CellPosition { position: child_b_pos, velocity: child_b_vel },
-                CellOrientation { rotation: child_b_rotation, angular_velocity: Vec3::ZERO },
-                CellSignaling::default(),
-                crate::cell::division::DivisionTimer { birth_time: child_b_birth_time, split_interval: child_b_split_interval },
-                crate::cell::physics::CellForces::default(),
-                crate::cell::physics::Cytoskeleton::default(),
-                Mesh3d(mesh_b.clone()),
-                MeshMaterial3d(material_b.clone()),
-                Transform::from_translation(child_b_pos).with_rotation(child_b_rotation).with_scale(Vec3::splat(child_b_radius)),
-                Visibility::Visible,
-                CpuSceneEntity,
-            )).id()
-        };
-        
-        main_state.id_to_entity.insert(cell_id_b, entity_b);
-        main_state.entity_to_index.insert(entity_b, child_b_idx);
-        main_state.index_to_entity[child_b_idx] = Some(entity_b);
     }
-    
-    // No need to rebuild mappings since we removed compaction
-    // Child A reuses parent index, child B gets new index
-    // All indices remain stable
 }
 
 /// Sync ECS components from canonical state
 /// OPTIMIZED: Uses direct array indexing instead of HashMap lookups
-fn sync_ecs_from_canonical(
+///
+/// Positions and rotations are blended between the last two ticks (see
+/// `TickInterpolation`), so sync_transforms and everything reading these
+/// components draw the same pose; the canonical state is never written.
+pub fn sync_ecs_from_canonical(
     main_state: Res<MainSimState>,
     drag_state: Res<crate::input::cell_dragging::DragState>,
+    interpolation: Res<crate::rendering::TickInterpolation>,
     mut cells_query: Query<(Entity, &mut CellPosition, &mut CellOrientation, &mut Cell)>,
 ) {
     // Early return if no cells (scene not initialized yet)
@@ -509,16 +706,16 @@ fn sync_ecs_from_canonical(
     // This is O(1) instead of O(log N) and has much better cache locality
     for i in 0..main_state.canonical_state.cell_count {
         if let Some(entity) = main_state.index_to_entity[i] {
-            // Skip syncing if this cell is currently being dragged
-            if drag_state.dragged_entity == Some(entity) {
+            // Skip syncing if this cell is currently being dragged (a soft drag is moved by physics)
+            if !drag_state.soft_active && drag_state.dragged_entity == Some(entity) {
                 continue;
             }
             
             if let Ok((_, mut pos, mut orientation, mut cell)) = cells_query.get_mut(entity) {
                 // Batch read from canonical state (better cache locality)
-                pos.position = main_state.canonical_state.positions[i];
+                pos.position = interpolation.position(&main_state.canonical_state, i);
                 pos.velocity = main_state.canonical_state.velocities[i];
-                orientation.rotation = main_state.canonical_state.rotations[i];
+                orientation.rotation = interpolation.rotation(&main_state.canonical_state, i);
                 orientation.angular_velocity = main_state.canonical_state.angular_velocities[i];
                 cell.mass = main_state.canonical_state.masses[i];
                 cell.radius = main_state.canonical_state.radii[i];
@@ -547,12 +744,15 @@ fn setup_cpu_scene(
     mut meshes: ResMut<Assets<Mesh>>,
     _fog_settings: Res<crate::rendering::VolumetricFogSettings>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    genome: Res<crate::genome::CurrentGenome>,
+    mut genome: ResMut<crate::genome::CurrentGenome>,
     config: Res<PhysicsConfig>,
     mut main_state: ResMut<MainSimState>,
     cpu_cell_capacity: Res<crate::ui::scene_manager::CpuCellCapacity>,
+    memory: Res<crate::simulation::SimulationMemory>,
     lighting_config: Res<crate::ui::lighting_settings::LightingConfig>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
     mut camera_query: Query<&mut MainCamera>,
+    sphere: Res<crate::rendering::CellSphereMesh>,
 ) {
     // Reset camera to default position (reuse existing camera from Preview scene)
     for mut camera in camera_query.iter_mut() {
@@ -565,109 +765,65 @@ fn setup_cpu_scene(
         camera.followed_entity = None;
     }
 
-    // Get initial mode settings from genome (same as preview scene)
-    let initial_mode_index = genome.genome.initial_mode.max(0) as usize;
-    let mode = genome.genome.modes.get(initial_mode_index)
-        .or_else(|| genome.genome.modes.first());
-    
-    let (color, opacity, emissive, split_mass, split_interval) = if let Some(mode) = mode {
-        // Use get_split_mass/get_split_interval for potentially randomized values
-        let sm = mode.get_split_mass(0, 0, 0);
-        let si = mode.get_split_interval(0, 0, 0);
-        (mode.color, mode.opacity, mode.emissive, sm, si)
+    // Resume the scene left for Preview mode, or start over with the genome's initial cell
+    if let Some(scene) = dormant.cpu.take() {
+        info!("Restoring CPU scene ({} cells, t={:.2}s)", scene.canonical_state.cell_count, scene.simulation_time);
+        scene.restore(&mut main_state);
     } else {
-        (Vec3::new(1.0, 1.0, 1.0), 1.0, 0.0, 1.0, 5.0)
-    };
-    
-    let cell_radius = 1.0;
-    
-    // Create initial state with capacity from settings
-    let mut initial_state = InitialState::new(config.clone(), cpu_cell_capacity.capacity, 0);
-    initial_state.add_cell(InitialCell {
-        id: 0,
-        position: Vec3::ZERO,
-        velocity: Vec3::ZERO,
-        rotation: genome.genome.initial_orientation,
-        angular_velocity: Vec3::ZERO,
-        mass: split_mass,
-        radius: cell_radius,
-        genome_id: 0,
-        mode_index: initial_mode_index,
-        birth_time: 0.0,
-        split_interval,
-        split_mass,
-        stiffness: 500.0,  // Match preview scene to prevent pass-through
-    });
-    
-    // Initialize canonical state from initial state
-    main_state.canonical_state = initial_state.to_canonical_state();
-    main_state.initial_state = initial_state;
-    main_state.id_to_entity.clear();
-    main_state.entity_to_index.clear();
-    // Resize index_to_entity to match new capacity
-    main_state.index_to_entity = vec![None; cpu_cell_capacity.capacity];
-    main_state.simulation_time = 0.0;
-    
-    // OPTIMIZATION: Create shared sphere mesh once (reused for all cells)
-    // This is a MASSIVE performance improvement - mesh generation is very expensive
-    let sphere_mesh = meshes.add(Sphere::new(1.0).mesh().ico(5).unwrap());
-    main_state.sphere_mesh = sphere_mesh.clone();
+        // Get initial mode settings from genome (same as preview scene)
+        let initial_mode_index = genome.genome.initial_mode_index();
+        let mode = genome.genome.modes.get(initial_mode_index)
+            .or_else(|| genome.genome.modes.first());
     
-    // OPTIMIZATION: Clear material cache on scene reset
-    main_state.material_cache.clear();
-    
-    // Get or create cached material for initial cell
-    let initial_material = get_or_create_material(color, opacity, emissive, &mut main_state.material_cache, &mut materials);
+        let seed = genome.genome.metadata.seed;
+        let (split_mass, split_interval) = if let Some(mode) = mode {
+            // Use get_split_mass/get_split_interval for potentially randomized values
+            (mode.get_split_mass(0, 0, seed), mode.get_split_interval(0, 0, seed))
+        } else {
+            (1.0, 5.0)
+        };
     
-    // Check if initial cell is flagellocyte
-    let initial_mode = genome.genome.modes.get(initial_mode_index);
-    let is_flagellocyte = initial_mode.map(|m| m.cell_type == 1).unwrap_or(false);
-    let swim_force = initial_mode.map(|m| m.swim_force).unwrap_or(0.0);
-    let initial_mesh = if is_flagellocyte {
-        meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(cell_radius, swim_force, 5))
-    } else {
-        sphere_mesh.clone()
-    };
+        let cell_radius = 1.0;
     
-    // Spawn ECS entity for the initial cell
-    let entity = commands.spawn((
-        // Cell data components
-        Cell {
-            mass: split_mass,
-            radius: cell_radius,
-            genome_id: 0,
-            mode_index: initial_mode_index,
-            cell_type: initial_mode.map(|m| m.cell_type).unwrap_or(0),
-        },
-        CellPosition {
+        // Create initial state with capacity from settings
+        let mut initial_state = InitialState::new(config.clone(), cpu_cell_capacity.capacity, seed);
+        initial_state.memory_profile = memory.profile;
+        initial_state.add_cell(InitialCell {
+            id: 0,
             position: Vec3::ZERO,
             velocity: Vec3::ZERO,
-        },
-        CellOrientation {
             rotation: genome.genome.initial_orientation,
             angular_velocity: Vec3::ZERO,
-        },
-        CellSignaling::default(),
-        crate::cell::division::DivisionTimer {
+            mass: split_mass,
+            radius: cell_radius,
+            genome_id: 0,
+            mode_index: initial_mode_index,
             birth_time: 0.0,
             split_interval,
-        },
-        crate::cell::physics::CellForces::default(),
-        crate::cell::physics::Cytoskeleton::default(),
-        // Visual representation
-        Mesh3d(initial_mesh),
-        MeshMaterial3d(initial_material),
-        Transform::from_translation(Vec3::ZERO)
-            .with_rotation(genome.genome.initial_orientation)
-            .with_scale(Vec3::splat(cell_radius)),
-        Visibility::default(),
-        CpuSceneEntity,
-    )).id();
+            split_mass,
+            stiffness: 500.0,  // Match preview scene to prevent pass-through
+            pinned: false,
+            user_data: Default::default(),
+        });
+        
+        // Initialize canonical state from initial state
+        main_state.canonical_state = initial_state.to_canonical_state();
+        main_state.initial_state = initial_state;
+        main_state.simulation_time = 0.0;
+        genome.genome.record_run(crate::genome::RunKind::Cpu, seed);
+        main_state.start_genome = Some(genome.genome.clone());
+        main_state.interventions = Default::default();
+    }
+    // Entities (pooled ones included) were despawned with the previous scene
+    main_state.clear_entities();
+    
+    // OPTIMIZATION: All cells share one sphere mesh (its quality is switched in place)
+    main_state.sphere_mesh = sphere.handle.clone();
     
-    // Map cell ID to entity
-    main_state.id_to_entity.insert(0, entity);
-    main_state.entity_to_index.insert(entity, 0);
-    main_state.index_to_entity[0] = Some(entity);
+    // OPTIMIZATION: Clear material cache on scene reset
+    main_state.material_cache.clear();
+    
+    // Cell entities are created by reconcile_cell_entities over the next frames
 
     // Add basic lighting (using saved settings)
     let light_rotation = Quat::from_euler(
@@ -757,3 +913,128 @@ fn cleanup_cpu_scene(
 
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_division_burst_is_spread_over_frames_within_budget() {
+        let mut world = World::new();
+        let budget = 200;
+
+        // 1000 cells with entities, all of which divide in the same tick
+        let mut id_to_entity: HashMap<u32, Entity> = (0..1000)
+            .map(|cell_id| (cell_id, world.spawn_empty().id()))
+            .collect();
+        let live_ids: Vec<u32> = (1000..3000).collect();
+
+        let mut frames = 0;
+        loop {
+            let plan = plan_entity_changes(&live_ids, &id_to_entity, budget, budget);
+            if plan.releases.is_empty() && plan.spawns.is_empty() {
+                assert_eq!(plan.pending, 0);
+                break;
+            }
+            assert!(plan.releases.len() <= budget && plan.spawns.len() <= budget);
+            // Pending releases never hold back activations
+            if plan.pending > 0 {
+                assert_eq!(plan.spawns.len(), budget);
+            }
+            for cell_id in &plan.releases {
+                id_to_entity.remove(cell_id);
+            }
+            for &index in &plan.spawns {
+                id_to_entity.insert(live_ids[index], world.spawn_empty().id());
+            }
+            frames += 1;
+        }
+
+        // 2000 activations at 200 per frame; the 1000 releases finish alongside them
+        assert_eq!(frames, 10);
+        assert_eq!(id_to_entity.len(), live_ids.len());
+        assert!(live_ids.iter().all(|cell_id| id_to_entity.contains_key(cell_id)));
+    }
+
+    /// Mappings rebuilt from scratch, for comparison with the incremental sync
+    fn rebuilt_mappings(main_state: &MainSimState) -> (Vec<Option<Entity>>, HashMap<Entity, usize>) {
+        let state = &main_state.canonical_state;
+        let mut index_to_entity = vec![None; state.capacity];
+        let mut entity_to_index = HashMap::new();
+        for index in 0..state.cell_count {
+            if let Some(&entity) = main_state.id_to_entity.get(&state.cell_ids[index]) {
+                index_to_entity[index] = Some(entity);
+                entity_to_index.insert(entity, index);
+            }
+        }
+        (index_to_entity, entity_to_index)
+    }
+
+    #[test]
+    fn test_index_mappings_follow_division_and_removal() {
+        let mut world = World::new();
+        let mut main_state = MainSimState { canonical_state: CanonicalState::new(16), ..MainSimState::default() };
+        main_state.clear_entities();
+        for i in 0..6 {
+            main_state.canonical_state.add_cell(Vec3::X * i as f32, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 5.0, 2.0, 10.0, Quat::IDENTITY, 0).unwrap();
+        }
+        let mut give_entities = |main_state: &mut MainSimState| {
+            let state = &main_state.canonical_state;
+            for &cell_id in &state.cell_ids[..state.cell_count] {
+                main_state.id_to_entity.entry(cell_id).or_insert_with(|| world.spawn_empty().id());
+            }
+            main_state.sync_index_mappings();
+        };
+
+        give_entities(&mut main_state);
+        assert!(main_state.entities_in_sync());
+        assert_eq!((main_state.index_to_entity.clone(), main_state.entity_to_index.clone()), rebuilt_mappings(&main_state));
+
+        // Removing a cell moves another into its slot
+        let removed = main_state.canonical_state.cell_ids[1];
+        crate::simulation::nutrient_system::remove_dead_cell(&mut main_state.canonical_state, 1);
+        main_state.id_to_entity.remove(&removed);
+        assert!(!main_state.entities_in_sync());
+        main_state.sync_index_mappings();
+        assert_eq!((main_state.index_to_entity.clone(), main_state.entity_to_index.clone()), rebuilt_mappings(&main_state));
+
+        // A division gives slot 0 a new cell ID, which has no entity until it is spawned
+        let parent = main_state.canonical_state.cell_ids[0];
+        main_state.canonical_state.cell_ids[0] = main_state.canonical_state.next_cell_id;
+        main_state.canonical_state.next_cell_id += 1;
+        main_state.id_to_entity.remove(&parent);
+        main_state.sync_index_mappings();
+        assert_eq!(main_state.index_to_entity[0], None);
+        assert_eq!((main_state.index_to_entity.clone(), main_state.entity_to_index.clone()), rebuilt_mappings(&main_state));
+
+        give_entities(&mut main_state);
+        assert!(main_state.entities_in_sync());
+        assert_eq!((main_state.index_to_entity.clone(), main_state.entity_to_index.clone()), rebuilt_mappings(&main_state));
+    }
+
+    #[test]
+    fn test_tick_rate_independent_of_timestep() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<crate::simulation::SimulationState>();
+        world.init_resource::<Time<Fixed>>();
+        // Halving dt keeps real-time playback: twice the ticks per real second
+        let config = PhysicsConfig { fixed_timestep: 1.0 / 128.0, ..PhysicsConfig::default() };
+        assert_eq!(config.ticks_per_second(), 128.0);
+        world.insert_resource(config.clone());
+        world.run_system_once(update_fixed_timestep).unwrap();
+        assert!((world.resource::<Time<Fixed>>().timestep().as_secs_f32() - 1.0 / 128.0).abs() < 1e-6);
+
+        // Overriding the rate keeps dt: 64 ticks per real second cover half a simulated second
+        world.insert_resource(PhysicsConfig { ticks_per_second_override: Some(64.0), ..config });
+        world.run_system_once(update_fixed_timestep).unwrap();
+        assert!((world.resource::<Time<Fixed>>().timestep().as_secs_f32() - 1.0 / 64.0).abs() < 1e-6);
+
+        // Speed scales the tick rate, never dt
+        world.resource_mut::<crate::simulation::SimulationState>().speed_multiplier = 2.0;
+        world.run_system_once(update_fixed_timestep).unwrap();
+        assert!((world.resource::<Time<Fixed>>().timestep().as_secs_f32() - 1.0 / 128.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,625 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::genome::CurrentGenome;
+use crate::simulation::CanonicalState;
+
+/// Deepest nesting of parentheses and unary minus a metric expression may use
+const MAX_DEPTH: usize = 64;
+
+/// Plugin for user-defined metrics derived from the statistics counters
+///
+/// Definitions are persisted with the UI settings; the valid ones are compiled here and
+/// evaluated by the statistics history, the breakpoints and the watchdog.
+pub struct DerivedMetricsPlugin;
+
+impl Plugin for DerivedMetricsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DerivedMetricSettings>()
+            .init_resource::<DerivedMetrics>()
+            .add_systems(Update, compile_derived_metrics);
+    }
+}
+
+/// What a division by zero gives, including the mean mass of a mode without cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DivisionByZero {
+    /// Not a number; the sample is left out of plots and aggregates
+    #[default]
+    Nan,
+    Zero,
+}
+
+impl DivisionByZero {
+    pub const ALL: [DivisionByZero; 2] = [DivisionByZero::Nan, DivisionByZero::Zero];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DivisionByZero::Nan => "NaN",
+            DivisionByZero::Zero => "0",
+        }
+    }
+}
+
+/// A metric as the user wrote it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MetricDefinition {
+    pub id: u32,
+    pub name: String,
+    pub expression: String,
+    #[serde(default)]
+    pub division_by_zero: DivisionByZero,
+}
+
+/// User-defined metrics (edited in the Performance Monitor, persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct DerivedMetricSettings {
+    pub metrics: Vec<MetricDefinition>,
+}
+
+impl DerivedMetricSettings {
+    /// Add a metric with an unused ID and a placeholder expression
+    pub fn add(&mut self) {
+        let id = self.metrics.iter().map(|metric| metric.id + 1).max().unwrap_or(0);
+        self.metrics.push(MetricDefinition {
+            id,
+            name: format!("Metric {}", id + 1),
+            expression: "cells".to_string(),
+            division_by_zero: DivisionByZero::default(),
+        });
+    }
+}
+
+/// Statistics counter an expression reads
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variable {
+    /// Cells of a mode
+    Count(usize),
+    /// Total mass of a mode's cells
+    MassSum(usize),
+    /// Mean mass of a mode's cells
+    MassMean(usize),
+    Cells,
+    MassTotal,
+    AdhesionsTotal,
+    /// Simulated seconds
+    Time,
+}
+
+impl Variable {
+    /// Names of the variables and functions, for the editor's help text
+    pub const NAMES: [&'static str; 7] = [
+        "count(mode)", "mass_sum(mode)", "mass_mean(mode)", "cells", "mass_total", "adhesions_total", "time",
+    ];
+
+    fn function(name: &str, mode: usize) -> Option<Self> {
+        match name {
+            "count" => Some(Variable::Count(mode)),
+            "mass_sum" => Some(Variable::MassSum(mode)),
+            "mass_mean" => Some(Variable::MassMean(mode)),
+            _ => None,
+        }
+    }
+
+    fn constant(name: &str) -> Option<Self> {
+        match name {
+            "cells" => Some(Variable::Cells),
+            "mass_total" => Some(Variable::MassTotal),
+            "adhesions_total" => Some(Variable::AdhesionsTotal),
+            "time" => Some(Variable::Time),
+            _ => None,
+        }
+    }
+
+    fn mode(self) -> Option<usize> {
+        match self {
+            Variable::Count(mode) | Variable::MassSum(mode) | Variable::MassMean(mode) => Some(mode),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// Parsed metric expression
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+/// Problem with a metric expression at a character position (0-based)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExprError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}: {}", self.position + 1, self.message)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Open,
+    Close,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Number(value) => format!("number {}", value),
+            Token::Identifier(name) => format!("'{}'", name),
+            Token::Plus => "'+'".to_string(),
+            Token::Minus => "'-'".to_string(),
+            Token::Star => "'*'".to_string(),
+            Token::Slash => "'/'".to_string(),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+            Token::End => "end of expression".to_string(),
+        }
+    }
+}
+
+/// Split an expression into tokens with their character positions
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExprError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let token = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ if c.is_ascii_digit() || c == '.' => {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // Exponent, only when digits follow
+                if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                    let sign = usize::from(i + 1 < chars.len() && matches!(chars[i + 1], '+' | '-'));
+                    if chars.get(i + 1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                        i += 1 + sign;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text.parse::<f64>().map_err(|_| ExprError {
+                    position: start,
+                    message: format!("'{}' is not a number", text),
+                })?;
+                tokens.push((Token::Number(value), start));
+                continue;
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push((Token::Identifier(chars[start..i].iter().collect()), start));
+                continue;
+            }
+            _ => return Err(ExprError { position: start, message: format!("unexpected character '{}'", c) }),
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+    tokens.push((Token::End, chars.len()));
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the tokens
+///
+/// ```text
+/// expr    = term (("+" | "-") term)*
+/// term    = unary (("*" | "/") unary)*
+/// unary   = "-" unary | primary
+/// primary = number | variable | function "(" integer ")" | "(" expr ")"
+/// ```
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].0
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.next].1
+    }
+
+    fn advance(&mut self) -> (Token, usize) {
+        let token = self.tokens[self.next].clone();
+        if self.next + 1 < self.tokens.len() {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ExprError> {
+        Err(ExprError { position: self.position(), message: message.into() })
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExprError> {
+        if *self.peek() == expected {
+            self.advance();
+            Ok(())
+        } else {
+            self.error(format!("expected {}, found {}", expected.describe(), self.peek().describe()))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, ExprError> {
+        let mut left = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Token::Plus => BinaryOp::Add,
+                Token::Minus => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.advance();
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, ExprError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Token::Star => BinaryOp::Multiply,
+                Token::Slash => BinaryOp::Divide,
+                _ => return Ok(left),
+            };
+            self.advance();
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        if *self.peek() != Token::Minus {
+            return self.primary();
+        }
+        self.advance();
+        self.nested(|parser| Ok(Expr::Negate(Box::new(parser.unary()?))))
+    }
+
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Expr, ExprError>) -> Result<Expr, ExprError> {
+        if self.depth >= MAX_DEPTH {
+            return self.error("expression is nested too deeply");
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExprError> {
+        let position = self.position();
+        match self.advance().0 {
+            Token::Number(value) => Ok(Expr::Number(value)),
+            Token::Open => {
+                let inner = self.nested(Self::expr)?;
+                self.expect(Token::Close)?;
+                Ok(inner)
+            }
+            Token::Identifier(name) => {
+                if let Some(variable) = Variable::constant(&name) {
+                    return Ok(Expr::Variable(variable));
+                }
+                if Variable::function(&name, 0).is_none() {
+                    return Err(ExprError { position, message: format!("unknown identifier '{}'", name) });
+                }
+                self.expect(Token::Open)?;
+                let mode = match self.peek() {
+                    Token::Number(value) if value.fract() == 0.0 && *value >= 0.0 => *value as usize,
+                    _ => return self.error(format!("'{}' takes a mode index, like {}(0)", name, name)),
+                };
+                self.advance();
+                self.expect(Token::Close)?;
+                Ok(Expr::Variable(Variable::function(&name, mode).expect("known function")))
+            }
+            Token::End => Err(ExprError { position, message: "expression ends too early".to_string() }),
+            token => Err(ExprError { position, message: format!("unexpected {}", token.describe()) }),
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let mut parser = Parser { tokens: tokenize(source)?, next: 0, depth: 0 };
+        if *parser.peek() == Token::End {
+            return parser.error("empty expression");
+        }
+        let expr = parser.expr()?;
+        if *parser.peek() != Token::End {
+            return parser.error(format!("unexpected {}", parser.peek().describe()));
+        }
+        Ok(expr)
+    }
+
+    /// Parse and check the mode indices against a genome with `mode_count` modes
+    pub fn compile(source: &str, mode_count: usize) -> Result<Self, ExprError> {
+        let expr = Self::parse(source)?;
+        if let Some(mode) = expr.modes().into_iter().find(|&mode| mode >= mode_count) {
+            let position = source.find(&format!("({})", mode)).unwrap_or(0);
+            return Err(ExprError {
+                position,
+                message: format!("mode {} out of range (the genome has {} modes)", mode, mode_count),
+            });
+        }
+        Ok(expr)
+    }
+
+    /// Mode indices the expression reads
+    fn modes(&self) -> Vec<usize> {
+        match self {
+            Expr::Number(_) => Vec::new(),
+            Expr::Variable(variable) => variable.mode().into_iter().collect(),
+            Expr::Negate(inner) => inner.modes(),
+            Expr::Binary(_, left, right) => {
+                let mut modes = left.modes();
+                modes.extend(right.modes());
+                modes
+            }
+        }
+    }
+
+    /// Value against `counters`
+    ///
+    /// NaN operands give NaN whatever the policy; the policy only decides what a
+    /// division of a number by zero gives (IEEE would give an infinity).
+    pub fn evaluate(&self, counters: &MetricCounters, division_by_zero: DivisionByZero) -> f64 {
+        let divide = |numerator: f64, denominator: f64| {
+            if numerator.is_nan() || denominator.is_nan() {
+                f64::NAN
+            } else if denominator == 0.0 {
+                match division_by_zero {
+                    DivisionByZero::Nan => f64::NAN,
+                    DivisionByZero::Zero => 0.0,
+                }
+            } else {
+                numerator / denominator
+            }
+        };
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Variable(variable) => match *variable {
+                Variable::Count(mode) => counters.cells_per_mode.get(mode).copied().unwrap_or(0) as f64,
+                Variable::MassSum(mode) => counters.mass_per_mode.get(mode).copied().unwrap_or(0.0),
+                Variable::MassMean(mode) => divide(
+                    counters.mass_per_mode.get(mode).copied().unwrap_or(0.0),
+                    counters.cells_per_mode.get(mode).copied().unwrap_or(0) as f64,
+                ),
+                Variable::Cells => counters.cells as f64,
+                Variable::MassTotal => counters.mass_per_mode.iter().sum(),
+                Variable::AdhesionsTotal => counters.adhesions as f64,
+                Variable::Time => counters.time as f64,
+            },
+            Expr::Negate(inner) => -inner.evaluate(counters, division_by_zero),
+            Expr::Binary(op, left, right) => {
+                let (a, b) = (left.evaluate(counters, division_by_zero), right.evaluate(counters, division_by_zero));
+                match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Subtract => a - b,
+                    BinaryOp::Multiply => a * b,
+                    BinaryOp::Divide => divide(a, b),
+                }
+            }
+        }
+    }
+}
+
+/// Counters of a state that metric expressions read
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricCounters {
+    pub time: f32,
+    pub cells: usize,
+    pub cells_per_mode: Vec<usize>,
+    pub mass_per_mode: Vec<f64>,
+    pub adhesions: usize,
+}
+
+impl MetricCounters {
+    pub fn capture(state: &CanonicalState, mode_count: usize, time: f32) -> Self {
+        let mut mass_per_mode = vec![0.0; mode_count];
+        for i in 0..state.cell_count {
+            if let Some(mass) = mass_per_mode.get_mut(state.mode_indices[i]) {
+                *mass += state.masses[i] as f64;
+            }
+        }
+        Self {
+            time,
+            cells: state.cell_count,
+            cells_per_mode: state.cells_per_mode(mode_count),
+            mass_per_mode,
+            adhesions: state.adhesion_manager.get_active_connection_count(&state.adhesion_connections),
+        }
+    }
+}
+
+/// A metric whose expression compiled
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledMetric {
+    pub id: u32,
+    pub name: String,
+    pub expr: Expr,
+    pub division_by_zero: DivisionByZero,
+}
+
+impl CompiledMetric {
+    pub fn evaluate(&self, counters: &MetricCounters) -> f32 {
+        self.expr.evaluate(counters, self.division_by_zero) as f32
+    }
+}
+
+/// Metrics of the settings that compiled against the current genome, in settings order
+#[derive(Resource, Default)]
+pub struct DerivedMetrics {
+    pub metrics: Vec<CompiledMetric>,
+    /// Modes of the genome the metrics were compiled against
+    pub mode_count: usize,
+}
+
+impl DerivedMetrics {
+    pub fn get(&self, id: u32) -> Option<&CompiledMetric> {
+        self.metrics.iter().find(|metric| metric.id == id)
+    }
+
+    /// Value of every metric at `state`, in order (empty without metrics)
+    pub fn evaluate(&self, state: &CanonicalState, time: f32) -> Vec<f32> {
+        if self.metrics.is_empty() {
+            return Vec::new();
+        }
+        let counters = MetricCounters::capture(state, self.mode_count, time);
+        self.metrics.iter().map(|metric| metric.evaluate(&counters)).collect()
+    }
+}
+
+/// Recompile the metrics when their definitions or the genome change, and hand them to the breakpoints
+fn compile_derived_metrics(
+    settings: Res<DerivedMetricSettings>,
+    genome: Res<CurrentGenome>,
+    mut derived: ResMut<DerivedMetrics>,
+    mut breakpoints: ResMut<crate::simulation::Breakpoints>,
+) {
+    if !settings.is_changed() && !genome.is_changed() {
+        return;
+    }
+    let mode_count = genome.genome.modes.len();
+    let metrics: Vec<CompiledMetric> = settings.metrics.iter()
+        .filter_map(|definition| Some(CompiledMetric {
+            id: definition.id,
+            name: definition.name.clone(),
+            expr: Expr::compile(&definition.expression, mode_count).ok()?,
+            division_by_zero: definition.division_by_zero,
+        }))
+        .collect();
+    if derived.metrics != metrics || derived.mode_count != mode_count {
+        derived.metrics = metrics.clone();
+        derived.mode_count = mode_count;
+        breakpoints.groups.metrics = metrics;
+        breakpoints.groups.mode_count = mode_count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Modes 0 and 1 with 2 and 0 cells, mode 2 with 4 cells of total mass 10
+    fn counters() -> MetricCounters {
+        MetricCounters {
+            time: 2.5,
+            cells: 6,
+            cells_per_mode: vec![2, 0, 4],
+            mass_per_mode: vec![3.0, 0.0, 10.0],
+            adhesions: 7,
+        }
+    }
+
+    fn eval(source: &str) -> f64 {
+        Expr::parse(source).unwrap().evaluate(&counters(), DivisionByZero::Nan)
+    }
+
+    fn error(source: &str) -> ExprError {
+        Expr::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval("24 / 4 / 2"), 3.0);
+        assert_eq!(eval("-2 * 3"), -6.0);
+        assert_eq!(eval("2 - -3"), 5.0);
+        assert_eq!(eval("--4"), 4.0);
+        assert_eq!(eval("1.5e1 + .5"), 15.5);
+    }
+
+    #[test]
+    fn test_variables_and_functions() {
+        assert_eq!(eval("count(2)"), 4.0);
+        assert_eq!(eval("mass_sum(2)"), 10.0);
+        assert_eq!(eval("mass_mean(2)"), 2.5);
+        assert_eq!(eval("mass_total"), 13.0);
+        assert_eq!(eval("adhesions_total / cells"), 7.0 / 6.0);
+        assert_eq!(eval("time * 2"), 5.0);
+        assert_eq!(eval("mass_mean(2) / count(0)"), 1.25);
+    }
+
+    #[test]
+    fn test_malformed_input_is_located() {
+        assert_eq!(error("").message, "empty expression");
+        assert_eq!(error("   ").position, 3);
+        assert_eq!(error("1 +").message, "expression ends too early");
+        assert_eq!(error("foo + 1"), ExprError { position: 0, message: "unknown identifier 'foo'".to_string() });
+        assert_eq!(error("2 * (cells").message, "expected ')', found end of expression");
+        assert_eq!(error("cells 2").position, 6);
+        assert_eq!(error("count(x)").message, "'count' takes a mode index, like count(0)");
+        assert_eq!(error("count(1.5)").position, 6);
+        assert_eq!(error("count").message, "expected '(', found end of expression");
+        assert_eq!(error("1 # 2"), ExprError { position: 2, message: "unexpected character '#'".to_string() });
+        assert_eq!(error("1..2").message, "'1..2' is not a number");
+        assert_eq!(error(")").message, "unexpected ')'");
+        assert_eq!(error(&"(".repeat(100)).message, "expression is nested too deeply");
+        assert_eq!(error(&"-".repeat(100)).message, "expression is nested too deeply");
+    }
+
+    #[test]
+    fn test_modes_are_checked_against_the_genome() {
+        assert!(Expr::compile("count(2)", 3).is_ok());
+        let error = Expr::compile("cells + count(3)", 3).unwrap_err();
+        assert_eq!(error.message, "mode 3 out of range (the genome has 3 modes)");
+        assert_eq!(error.position, 13);
+    }
+
+    #[test]
+    fn test_division_by_zero_policy_and_nan_propagation() {
+        let nan = |source: &str| Expr::parse(source).unwrap().evaluate(&counters(), DivisionByZero::Nan);
+        let zero = |source: &str| Expr::parse(source).unwrap().evaluate(&counters(), DivisionByZero::Zero);
+
+        assert!(nan("1 / 0").is_nan());
+        assert_eq!(zero("1 / 0"), 0.0);
+        // The mean mass of a mode without cells divides by zero too
+        assert!(nan("mass_mean(1)").is_nan());
+        assert_eq!(zero("mass_mean(1) + 1"), 1.0);
+        // NaN propagates through every operator, and a zero policy doesn't turn it back into a number
+        assert!(nan("mass_mean(1) * 0 + 5").is_nan());
+        assert!(nan("-(0 / 0)").is_nan());
+        assert!(zero("(1 / 0 - 1 / 0) / 0").is_finite());
+        assert!(Expr::Binary(BinaryOp::Divide, Box::new(Expr::Number(f64::NAN)), Box::new(Expr::Number(0.0)))
+            .evaluate(&counters(), DivisionByZero::Zero)
+            .is_nan());
+    }
+}
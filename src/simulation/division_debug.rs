@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::{select_independent, CanonicalState, DivisionBlock, DivisionReadiness};
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Plugin for the Division Debug window's dry run
+pub struct DivisionDebugPlugin;
+
+impl Plugin for DivisionDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DivisionDebug>()
+            .add_systems(Update, update_division_debug);
+    }
+}
+
+/// How often the dry run is repeated while the window is open (seconds)
+const REFRESH_SECS: f32 = 0.25;
+
+/// Most rows kept for the table (blocked cells first)
+pub const MAX_RECORDS: usize = 500;
+
+/// What the next division step does with a cell that is due to divide
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivisionStatus {
+    Divides,
+    Blocked(DivisionBlock),
+}
+
+/// Dry-run result for one cell that is due to divide
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivisionRecord {
+    pub cell_id: u32,
+    pub index: usize,
+    pub mode_index: usize,
+    pub status: DivisionStatus,
+    /// Age at which the cell became due: its resolved split interval, phase offset included
+    pub split_interval: f32,
+    /// Cell age at the dry run
+    pub age: f32,
+}
+
+/// Blocked cells per (mode, reason) at one dry run
+pub struct BlockSample {
+    /// App time of the dry run (seconds)
+    pub time: f64,
+    pub counts: BTreeMap<(usize, DivisionBlock), u32>,
+}
+
+/// Division eligibility of the active simulation, computed only while the Division Debug window is open
+#[derive(Resource)]
+pub struct DivisionDebug {
+    /// Set by the window every frame it is drawn; the dry run is skipped when it isn't
+    pub window_open: bool,
+    /// Cells due to divide at the next tick, blocked cells first (at most `MAX_RECORDS`)
+    pub records: Vec<DivisionRecord>,
+    /// Records dropped by the `MAX_RECORDS` limit
+    pub truncated: usize,
+    /// Dry runs within the last `history_secs`
+    pub history: VecDeque<BlockSample>,
+    pub history_secs: f32,
+    /// Cell ID the window asked to select and focus
+    pub focus_request: Option<u32>,
+    refresh: Timer,
+}
+
+impl Default for DivisionDebug {
+    fn default() -> Self {
+        Self {
+            window_open: false,
+            records: Vec::new(),
+            truncated: 0,
+            history: VecDeque::new(),
+            history_secs: 10.0,
+            focus_request: None,
+            refresh: Timer::new(Duration::from_secs_f32(REFRESH_SECS), TimerMode::Repeating),
+        }
+    }
+}
+
+impl DivisionDebug {
+    /// Blocked cell counts per mode and reason summed over the history, with the number of dry runs
+    pub fn summary(&self) -> (BTreeMap<usize, BTreeMap<DivisionBlock, u32>>, usize) {
+        let mut summary: BTreeMap<usize, BTreeMap<DivisionBlock, u32>> = BTreeMap::new();
+        for sample in &self.history {
+            for (&(mode, block), &count) in &sample.counts {
+                *summary.entry(mode).or_default().entry(block).or_default() += count;
+            }
+        }
+        (summary, self.history.len())
+    }
+
+    fn record_sample(&mut self, records: Vec<DivisionRecord>, now: f64) {
+        let mut counts = BTreeMap::new();
+        for record in &records {
+            if let DivisionStatus::Blocked(block) = record.status {
+                *counts.entry((record.mode_index, block)).or_default() += 1;
+            }
+        }
+        self.history.push_back(BlockSample { time: now, counts });
+        let oldest = now - self.history_secs as f64;
+        while self.history.front().is_some_and(|sample| sample.time < oldest) {
+            self.history.pop_front();
+        }
+
+        self.truncated = records.len().saturating_sub(MAX_RECORDS);
+        self.records = records;
+        self.records.truncate(MAX_RECORDS);
+    }
+}
+
+/// What the first pass of `division_step` at `current_time` would do, without touching the state
+///
+/// Lists every cell that is due to divide: blocked ones with the reason (limits, an adhered
+/// neighbor dividing first, or no free slot) and the ones that divide. Later passes of the
+/// same tick may still divide cells deferred for an adhesion conflict.
+pub fn division_dry_run(state: &CanonicalState, genome: &GenomeData, current_time: f32, max_cells: usize) -> Vec<DivisionRecord> {
+    let record = |index: usize, status| DivisionRecord {
+        cell_id: state.cell_ids[index],
+        index,
+        mode_index: state.mode_indices[index],
+        status,
+        split_interval: state.split_intervals[index],
+        age: current_time - state.birth_times[index],
+    };
+
+    let compiled = state.compiled_genome.peek(genome).cloned()
+        .unwrap_or_else(|| std::sync::Arc::new(crate::genome::CompiledGenome::compile(genome)));
+    let mut records = Vec::new();
+    let mut ready = Vec::new();
+    for i in 0..state.cell_count {
+        match state.division_readiness(&compiled, i, current_time) {
+            DivisionReadiness::NotDue => {}
+            DivisionReadiness::Blocked(block) => records.push(record(i, DivisionStatus::Blocked(block))),
+            DivisionReadiness::Ready => ready.push(i),
+        }
+    }
+
+    if state.cell_count >= max_cells {
+        records.extend(ready.iter().map(|&i| record(i, DivisionStatus::Blocked(DivisionBlock::Capacity))));
+    } else {
+        let mut marks = vec![0; state.cell_count];
+        let mut selected = Vec::new();
+        select_independent(&ready, &state.cell_ids, &state.adhesion_manager, &state.adhesion_connections, &mut marks, &mut selected);
+
+        // Each division needs one free slot, taken in index order
+        let free_slots = state.capacity - state.cell_count;
+        let mut selected_rank = selected.iter().enumerate().map(|(rank, &i)| (i, rank)).collect::<std::collections::HashMap<_, _>>();
+        for &i in &ready {
+            let status = match selected_rank.remove(&i) {
+                None => DivisionStatus::Blocked(DivisionBlock::AdhesionConflict),
+                Some(rank) if rank >= free_slots => DivisionStatus::Blocked(DivisionBlock::Capacity),
+                Some(_) => DivisionStatus::Divides,
+            };
+            records.push(record(i, status));
+        }
+    }
+
+    records.sort_by_key(|record| (record.status == DivisionStatus::Divides, record.index));
+    records
+}
+
+fn update_division_debug(
+    time: Res<Time>,
+    mut debug: ResMut<DivisionDebug>,
+    sim_state: Res<SimulationState>,
+    config: Res<PhysicsConfig>,
+    genome: Res<crate::genome::CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    // Normal runs pay nothing while the window is closed
+    if !std::mem::take(&mut debug.window_open) {
+        return;
+    }
+    if !debug.refresh.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    // Divisions are evaluated at the end of the next tick
+    let active = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|s| (&s.canonical_state, s.simulation_time)),
+        SimulationMode::Preview => preview_state.as_deref().map(|s| (&s.canonical_state, s.current_time)),
+        SimulationMode::Gpu => None,
+    };
+    let records = match active {
+        Some((state, current_time)) => division_dry_run(state, &genome.genome, current_time + config.fixed_timestep, state.capacity),
+        None => Vec::new(),
+    };
+    debug.record_sample(records, time.elapsed_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    /// Three due cells: an adhered pair and a cell whose mode ran out of splits
+    fn due_cells(capacity: usize) -> (CanonicalState, GenomeData) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 1.0;
+        let mut capped = mode.clone();
+        capped.max_splits = 1;
+        let genome = GenomeData {
+            modes: vec![mode, capped],
+            ..GenomeData::default()
+        };
+
+        let mut state = CanonicalState::new(capacity);
+        for (x, mode_index, split_count) in [(0.0, 0, 0), (2.0, 0, 0), (10.0, 1, 1)] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+                0, mode_index, 0.0, 1.0, 1.0, 10.0, Quat::IDENTITY, split_count);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        );
+        (state, genome)
+    }
+
+    #[test]
+    fn test_dry_run_reports_reasons_without_mutating() {
+        let (state, genome) = due_cells(16);
+        let fingerprint = state.fingerprint();
+
+        let records = division_dry_run(&state, &genome, 1.0, state.capacity);
+        let statuses = records.iter().map(|r| (r.index, r.status)).collect::<Vec<_>>();
+        assert_eq!(statuses, vec![
+            (1, DivisionStatus::Blocked(DivisionBlock::AdhesionConflict)),
+            (2, DivisionStatus::Blocked(DivisionBlock::MaxSplits)),
+            (0, DivisionStatus::Divides),
+        ]);
+        assert_eq!(state.fingerprint(), fingerprint);
+
+        // Too young to be due yet
+        assert!(division_dry_run(&state, &genome, 0.5, state.capacity).is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_matches_first_division_pass() {
+        let (mut state, genome) = due_cells(4);
+        let records = division_dry_run(&state, &genome, 1.0, state.capacity);
+        let dividing = records.iter()
+            .filter(|r| r.status == DivisionStatus::Divides)
+            .map(|r| r.cell_id)
+            .collect::<Vec<_>>();
+
+        // One free slot: cell 0 divides; cell 1 waits for it and then finds no slot
+        let events = crate::simulation::cpu_physics::division_step(&mut state, &genome, 1.0, 1.0 / 64.0, 4, 0);
+        assert_eq!(events.len(), dividing.len());
+        assert_eq!(state.cell_count, 4);
+    }
+
+    #[test]
+    fn test_summary_drops_samples_older_than_history() {
+        let (state, genome) = due_cells(16);
+        let mut debug = DivisionDebug { history_secs: 1.0, ..Default::default() };
+        for now in [0.0, 0.5, 1.0, 1.5] {
+            debug.record_sample(division_dry_run(&state, &genome, 1.0, state.capacity), now);
+        }
+
+        let (summary, samples) = debug.summary();
+        assert_eq!(samples, 3);
+        assert_eq!(summary[&0][&DivisionBlock::AdhesionConflict], 3);
+        assert_eq!(summary[&1][&DivisionBlock::MaxSplits], 3);
+    }
+}
@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+use crate::genome::{CompiledGenome, CurrentGenome, GenomeData, NEVER_SPLIT_INTERVAL};
+use super::cpu_physics::{compute_division_placement, DividingCell};
+use super::fingerprint::StableHasher;
+
+/// Ghosts drawn at most; deeper lookaheads stop at the last generation that fits
+pub const MAX_GHOSTS: usize = 512;
+
+/// Seconds the genome must stay unchanged before the lookahead is recomputed
+const RECOMPUTE_DEBOUNCE: f32 = 0.15;
+
+/// Plugin keeping the division lookahead of the selected mode up to date
+pub struct DivisionLookaheadPlugin;
+
+impl Plugin for DivisionLookaheadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DivisionLookaheadPreview>()
+            .add_systems(Update, update_division_lookahead);
+    }
+}
+
+/// A cell of the structure a mode grows into, relative to the founder
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GhostCell {
+    /// Position in the founder's frame, founder at the origin
+    pub position: Vec3,
+    pub radius: f32,
+    pub mode: usize,
+    /// Generation the cell was born in (0 for a founder that never divides)
+    pub generation: usize,
+}
+
+/// Structure after a number of generations of pure geometric division
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DivisionLookahead {
+    pub ghosts: Vec<GhostCell>,
+    /// Generations actually simulated
+    pub generations: usize,
+    /// The next generation would pass the ghost cap, so fewer than asked were simulated
+    pub truncated: bool,
+}
+
+/// Whether cells of `mode` divide at all
+fn divides(genome: &GenomeData, mode: usize) -> bool {
+    genome.modes.get(mode).is_some_and(|mode| mode.split_interval <= NEVER_SPLIT_INTERVAL)
+}
+
+/// Grow one founder of `mode` for `generations` divisions, ignoring physics, nutrients and
+/// timing: every cell that may divide does so once per generation at its mode's split
+/// mass, along its mode's split direction without jitter, where [`compute_division_placement`]
+/// puts the children
+///
+/// The founder sits at the origin with the identity rotation; placement turns with the
+/// parent, so the structure of a rotated cell is this one rotated. Cells that never divide
+/// or reached their split limit stay as they are.
+pub fn division_lookahead(genome: &GenomeData, mode: usize, generations: usize, max_ghosts: usize) -> DivisionLookahead {
+    let compiled = CompiledGenome::compile(genome);
+    let Some(founder) = compiled.mode(mode) else {
+        return DivisionLookahead::default();
+    };
+    let founder_mass = genome.modes[mode].split_mass;
+    let mut cells = vec![(
+        DividingCell {
+            mode,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            radius: founder.radius_for(founder_mass),
+            mass: founder_mass,
+            split_count: 0,
+        },
+        0,
+    )];
+
+    let can_divide = |cell: &DividingCell| {
+        divides(genome, cell.mode) && compiled.mode(cell.mode).is_some_and(|mode| !mode.reached_split_limit(cell.split_count))
+    };
+
+    let mut lookahead = DivisionLookahead::default();
+    for generation in 1..=generations {
+        let dividing = cells.iter().filter(|(cell, _)| can_divide(cell)).count();
+        if dividing == 0 {
+            break;
+        }
+        if cells.len() + dividing > max_ghosts {
+            lookahead.truncated = true;
+            break;
+        }
+
+        let mut next = Vec::with_capacity(cells.len() + dividing);
+        for (cell, born) in cells {
+            let Some(mode) = compiled.mode(cell.mode).filter(|_| can_divide(&cell)) else {
+                next.push((cell, born));
+                continue;
+            };
+            // Children divide again once grown to their own mode's split mass
+            let mass = genome.modes[cell.mode].split_mass;
+            let parent = DividingCell { radius: mode.radius_for(mass), mass, ..cell };
+            let Some(placement) = compute_division_placement(&compiled, &parent, mode.split_direction) else {
+                next.push((cell, born));
+                continue;
+            };
+            for side in 0..2 {
+                next.push((
+                    DividingCell {
+                        mode: placement.modes[side].0,
+                        position: placement.positions[side],
+                        rotation: placement.rotations[side],
+                        radius: placement.radii[side],
+                        mass: placement.masses[side],
+                        split_count: placement.split_counts[side],
+                    },
+                    generation,
+                ));
+            }
+        }
+        cells = next;
+        lookahead.generations = generation;
+    }
+
+    lookahead.ghosts = cells.into_iter()
+        .map(|(cell, generation)| GhostCell { position: cell.position, radius: cell.radius, mode: cell.mode, generation })
+        .collect();
+    lookahead
+}
+
+/// Hash of the genome settings the lookahead depends on
+///
+/// Names, colors, nutrients and adhesion springs are left out, so edits of those don't
+/// recompute it.
+pub fn lookahead_genome_hash(genome: &GenomeData) -> u64 {
+    let compiled = CompiledGenome::compile(genome);
+    let mut hasher = StableHasher::new();
+    hasher.write_u64(genome.modes.len() as u64);
+    for (settings, mode) in genome.modes.iter().zip(&compiled.modes) {
+        let floats = [
+            settings.split_mass,
+            (settings.split_interval <= NEVER_SPLIT_INTERVAL) as u8 as f32,
+            mode.max_cell_size,
+            mode.split_ratio,
+            mode.split_direction.x, mode.split_direction.y, mode.split_direction.z,
+            mode.unit_semi_axes.x, mode.unit_semi_axes.y, mode.unit_semi_axes.z,
+        ];
+        for value in floats.into_iter().chain(mode.child_orientations.iter().flat_map(|q| q.to_array())) {
+            hasher.write(&value.to_bits().to_le_bytes());
+        }
+        hasher.write_u64(mode.spherical as u64);
+        hasher.write_u64(mode.split_limit.map_or(u64::MAX, |limit| limit as u64));
+        for (child, _) in mode.child_modes.iter().flatten() {
+            hasher.write_u64(*child as u64);
+        }
+    }
+    hasher.0
+}
+
+/// Ghost structure of the selected mode drawn at the inspected cell (Gizmos menu)
+#[derive(Resource, Clone, Debug)]
+pub struct DivisionLookaheadPreview {
+    pub enabled: bool,
+    /// Generations to look ahead, 2..=6
+    pub generations: usize,
+    /// Size of the ghost structure relative to the cell it is drawn at
+    pub scale: f32,
+    /// Mode, generations and genome hash the lookahead was computed for
+    key: Option<(usize, usize, u64)>,
+    /// Key waiting out the debounce and when it was first seen
+    pending: Option<((usize, usize, u64), f32)>,
+    pub lookahead: DivisionLookahead,
+}
+
+impl Default for DivisionLookaheadPreview {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            generations: 3,
+            scale: 0.5,
+            key: None,
+            pending: None,
+            lookahead: DivisionLookahead::default(),
+        }
+    }
+}
+
+impl DivisionLookaheadPreview {
+    /// Recompute when the mode, generations or relevant genome settings changed and have
+    /// stayed so for the debounce; returns whether it recomputed
+    pub fn refresh(&mut self, genome: &GenomeData, mode: usize, now: f32) -> bool {
+        let key = (mode, self.generations, lookahead_genome_hash(genome));
+        if self.key == Some(key) {
+            self.pending = None;
+            return false;
+        }
+        let since = match self.pending {
+            Some((pending, since)) if pending == key => since,
+            _ => now,
+        };
+        // The first lookahead doesn't wait
+        if self.key.is_some() && now - since < RECOMPUTE_DEBOUNCE {
+            self.pending = Some((key, since));
+            return false;
+        }
+        self.lookahead = division_lookahead(genome, mode, self.generations, MAX_GHOSTS);
+        self.key = Some(key);
+        self.pending = None;
+        true
+    }
+}
+
+fn update_division_lookahead(
+    mut preview: ResMut<DivisionLookaheadPreview>,
+    current_genome: Res<CurrentGenome>,
+    time: Res<Time>,
+) {
+    if !preview.enabled {
+        return;
+    }
+    let mode = current_genome.selected_mode_index.max(0) as usize;
+    preview.refresh(&current_genome.genome, mode, time.elapsed_secs());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{ModeIndex, ModeSettings};
+    use crate::simulation::cpu_physics::{division_step, CanonicalState};
+    use crate::simulation::PhysicsConfig;
+
+    /// Stem divides into Stem and Leaf along a tilted direction; Leaf never divides
+    fn genome() -> GenomeData {
+        let mut stem = ModeSettings::new_self_splitting(0, "Stem".to_string());
+        stem.split_mass = 1.6;
+        stem.split_interval = 1.0;
+        stem.split_ratio = 0.6;
+        stem.parent_split_direction = Vec2::new(30.0, 45.0);
+        stem.child_b.mode_number = ModeIndex::new(1);
+        stem.child_a.orientation = Quat::from_rotation_z(0.7);
+        stem.child_b.orientation = Quat::from_rotation_x(-0.4);
+        let mut leaf = ModeSettings::new_self_splitting(1, "Leaf".to_string());
+        leaf.split_interval = NEVER_SPLIT_INTERVAL + 1.0;
+        leaf.shape_radii = Vec3::new(1.0, 0.5, 0.7);
+        GenomeData { modes: vec![stem, leaf], ..GenomeData::default() }
+    }
+
+    #[test]
+    fn test_one_generation_matches_division_step() {
+        let genome = self::genome();
+        let compiled = CompiledGenome::compile(&genome);
+        let lookahead = division_lookahead(&genome, 0, 1, MAX_GHOSTS);
+        assert_eq!(lookahead.ghosts.len(), 2);
+        let mass = genome.modes[0].split_mass;
+        let radius = compiled.modes[0].radius_for(mass);
+        let fixed_timestep = PhysicsConfig::default().fixed_timestep;
+
+        let origin = Vec3::new(3.0, -1.0, 2.0);
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_y(1.2),
+            Quat::from_euler(EulerRot::XYZ, 0.4, -2.1, 0.9),
+            Quat::from_axis_angle(Vec3::new(1.0, 1.0, -1.0).normalize(), 2.8),
+        ] {
+            let mut state = CanonicalState::new(4);
+            state.add_cell(origin, Vec3::ZERO, rotation, Vec3::ZERO, mass, radius,
+                0, 0, 0.0, 1.0, mass, PhysicsConfig::default().default_stiffness, Quat::IDENTITY, 0);
+            division_step(&mut state, &genome, 2.0, fixed_timestep, 4, 0);
+            assert_eq!(state.cell_count, 2);
+
+            for (index, ghost) in lookahead.ghosts.iter().enumerate() {
+                let expected = origin + rotation * ghost.position;
+                assert!(state.positions[index].distance(expected) < 1e-4, "{:?} vs {:?}", state.positions[index], expected);
+                assert!((state.radii[index] - ghost.radius).abs() < 1e-5);
+                assert_eq!(state.mode_indices[index], ghost.mode);
+                assert_eq!(ghost.generation, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_dividing_cells_persist_and_the_cap_truncates() {
+        let genome = self::genome();
+        // Each generation the stem line adds one leaf; leaves stay put
+        let lookahead = division_lookahead(&genome, 0, 4, MAX_GHOSTS);
+        assert_eq!((lookahead.ghosts.len(), lookahead.generations, lookahead.truncated), (5, 4, false));
+        assert_eq!(lookahead.ghosts.iter().filter(|ghost| ghost.mode == 1).count(), 4);
+        assert_eq!(division_lookahead(&genome, 1, 4, MAX_GHOSTS).ghosts.len(), 1);
+
+        // A self-splitting mode doubles each generation, stopping before it passes the cap
+        let mut doubling = genome.clone();
+        doubling.modes[0].child_b.mode_number = ModeIndex::new(0);
+        let lookahead = division_lookahead(&doubling, 0, 6, 20);
+        assert_eq!((lookahead.ghosts.len(), lookahead.generations, lookahead.truncated), (16, 4, true));
+
+        // A split limit stops the line
+        doubling.modes[0].max_splits = 2;
+        let lookahead = division_lookahead(&doubling, 0, 6, MAX_GHOSTS);
+        assert_eq!((lookahead.ghosts.len(), lookahead.truncated), (4, false));
+    }
+
+    #[test]
+    fn test_recomputes_only_on_relevant_edits_after_the_debounce() {
+        let mut genome = self::genome();
+        let mut preview = DivisionLookaheadPreview::default();
+        assert!(preview.refresh(&genome, 0, 10.0));
+        assert!(!preview.refresh(&genome, 0, 10.5));
+
+        let hash = lookahead_genome_hash(&genome);
+        genome.modes[1].color = Vec3::new(0.1, 0.8, 0.2);
+        genome.modes[0].name = "Trunk".to_string();
+        assert_eq!(lookahead_genome_hash(&genome), hash);
+        assert!(!preview.refresh(&genome, 0, 11.0));
+
+        genome.modes[0].split_ratio = 0.5;
+        assert_ne!(lookahead_genome_hash(&genome), hash);
+        assert!(!preview.refresh(&genome, 0, 12.0));
+        assert!(!preview.refresh(&genome, 0, 12.1));
+        assert!(preview.refresh(&genome, 0, 12.2));
+        assert_eq!(preview.lookahead, division_lookahead(&genome, 0, preview.generations, MAX_GHOSTS));
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::DivisionStats;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin for the division counters shown in the Performance Monitor
+pub struct DivisionStatsPlugin;
+
+impl Plugin for DivisionStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DivisionStatistics>()
+            .add_systems(Update, update_division_statistics);
+    }
+}
+
+/// Division counters of the active simulation
+#[derive(Resource, Default)]
+pub struct DivisionStatistics {
+    /// Refreshed every frame (None without an active CPU or preview simulation)
+    pub stats: Option<DivisionStats>,
+    /// Name of the genome phase in effect (None when the genome has no phases)
+    pub active_phase: Option<String>,
+}
+
+fn update_division_statistics(
+    mut statistics: ResMut<DivisionStatistics>,
+    sim_state: Res<SimulationState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let (stats, time) = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| (s.canonical_state.division_stats, s.simulation_time)).unzip(),
+        SimulationMode::Preview => preview_state.map(|s| (s.canonical_state.division_stats, s.current_time)).unzip(),
+        SimulationMode::Gpu => (None, None),
+    };
+    statistics.stats = stats;
+
+    let genome = &genome.genome;
+    statistics.active_phase = match (stats, time) {
+        (Some(stats), Some(time)) if !genome.phases.is_empty() => {
+            Some(genome.phase_name(genome.active_phase(time, stats.divisions)).to_string())
+        }
+        _ => None,
+    };
+}
@@ -0,0 +1,401 @@
+use bevy::prelude::*;
+use crate::genome::GenomeData;
+use crate::simulation::capacity::MAX_CELL_CAPACITY;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::memory::MemoryUsage;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::scene_file::RunInterventions;
+use crate::simulation::{CanonicalState, EventTimeline, InitialState, SceneFile, SimulationMode, SimulationState};
+
+/// Plugin keeping the scene of the mode that isn't shown
+pub struct DormantScenesPlugin;
+
+impl Plugin for DormantScenesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DormantScenes>();
+    }
+}
+
+/// CPU scene put to sleep while Preview mode is shown
+pub struct DormantCpuScene {
+    pub canonical_state: CanonicalState,
+    pub initial_state: InitialState,
+    pub simulation_time: f32,
+    pub paused: bool,
+    /// Genome the run started with (None when continued from the preview)
+    pub start_genome: Option<GenomeData>,
+    pub interventions: RunInterventions,
+}
+
+impl DormantCpuScene {
+    /// Move the CPU scene out of `main`, leaving an empty placeholder state
+    pub fn take(main: &mut MainSimState, paused: bool) -> Self {
+        let placeholder = CanonicalState::with_grid_density(1, 1);
+        Self {
+            canonical_state: std::mem::replace(&mut main.canonical_state, placeholder),
+            initial_state: main.initial_state.clone(),
+            simulation_time: std::mem::take(&mut main.simulation_time),
+            paused,
+            start_genome: main.start_genome.take(),
+            interventions: std::mem::take(&mut main.interventions),
+        }
+    }
+
+    /// CPU scene continuing the preview from the shown time
+    ///
+    /// The state is cloned with its cell ids, adhesions and the replay's seed, so
+    /// the CPU run ticks on exactly like further preview ticks (except flagellocytes,
+    /// which only swim in CPU mode). A drag held in the preview is let go. The
+    /// capacity grows to `cpu_capacity` if that is larger; a smaller setting keeps
+    /// the preview's capacity.
+    pub fn from_preview(preview: &PreviewSimState, resimulating: bool, cpu_capacity: usize) -> Result<Self, ContinueInCpuError> {
+        if resimulating {
+            return Err(ContinueInCpuError::Resimulating);
+        }
+        let state = &preview.canonical_state;
+        if state.cell_count == 0 {
+            return Err(ContinueInCpuError::Empty);
+        }
+        if state.capacity > MAX_CELL_CAPACITY {
+            return Err(ContinueInCpuError::OverCapacity { capacity: state.capacity, max: MAX_CELL_CAPACITY });
+        }
+
+        let capacity = cpu_capacity.min(MAX_CELL_CAPACITY);
+        let mut canonical_state = if capacity > state.capacity { state.grown(capacity) } else { state.clone() };
+        canonical_state.soft_drag = None;
+        Ok(Self {
+            canonical_state,
+            initial_state: preview.initial_state.clone(),
+            simulation_time: preview.current_time,
+            paused: false,
+            start_genome: None,
+            interventions: RunInterventions::default(),
+        })
+    }
+
+    /// Put the scene back; entities are recreated by `reconcile_cell_entities`
+    pub fn restore(self, main: &mut MainSimState) {
+        main.canonical_state = self.canonical_state;
+        main.initial_state = self.initial_state;
+        main.simulation_time = self.simulation_time;
+        main.start_genome = self.start_genome;
+        main.interventions = self.interventions;
+    }
+}
+
+/// Why the preview can't be continued in CPU mode
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ContinueInCpuError {
+    #[error("The preview is still simulating up to the shown time")]
+    Resimulating,
+    #[error("The preview has no cells to continue with")]
+    Empty,
+    #[error("The preview holds {capacity} cell slots, more than CPU mode allows ({max})")]
+    OverCapacity { capacity: usize, max: usize },
+}
+
+/// Preview scene put to sleep while CPU mode is shown
+pub struct DormantPreviewScene {
+    /// Replay state, checkpoints and scheduled edits
+    pub preview: PreviewSimState,
+    pub timeline: EventTimeline,
+    /// Scrub target that was still being simulated
+    pub target_time: Option<f32>,
+    pub paused: bool,
+}
+
+impl DormantPreviewScene {
+    /// Move the preview scene out; an in-flight replay is dropped and resumed from `target_time`
+    pub fn take(preview: &mut PreviewSimState, timeline: &mut EventTimeline, sim_state: &SimulationState) -> Self {
+        let (visible, limits) = (timeline.visible, timeline.limits());
+        let scene = Self {
+            preview: std::mem::take(preview),
+            timeline: std::mem::take(timeline),
+            target_time: sim_state.target_time,
+            paused: sim_state.paused,
+        };
+        timeline.visible = visible;
+        timeline.set_limits(limits);
+        scene
+    }
+
+    /// Put the scene back and respawn its cells
+    pub fn restore(self, preview: &mut PreviewSimState, timeline: &mut EventTimeline, sim_state: &mut SimulationState) {
+        *preview = self.preview;
+        *timeline = self.timeline;
+        let capacity = preview.canonical_state.capacity;
+        preview.index_to_entity.clear();
+        preview.index_to_entity.resize(capacity, None);
+        sim_state.target_time = self.target_time;
+        sim_state.needs_respawn = true;
+    }
+}
+
+/// Cell count, simulated time and estimated memory of a dormant scene
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DormantSummary {
+    pub cell_count: usize,
+    pub time: f32,
+    pub bytes: usize,
+}
+
+/// Load that waits for the user to agree to discarding the dormant scenes
+pub enum DeferredLoad {
+    Genome(GenomeData),
+    Scene { title: String, scene: SceneFile },
+}
+
+/// Scenes of the modes that aren't shown, restored when switching back
+///
+/// Mode switches store the scene being left here; the scene setup of the mode
+/// being entered takes its dormant scene back instead of starting over.
+#[derive(Resource, Default)]
+pub struct DormantScenes {
+    pub cpu: Option<DormantCpuScene>,
+    pub preview: Option<DormantPreviewScene>,
+    /// Genome or scene load waiting for confirmation (it would discard the dormant scenes)
+    pub deferred_load: Option<DeferredLoad>,
+}
+
+impl DormantScenes {
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.preview.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.cpu = None;
+        self.preview = None;
+    }
+
+    /// Forget the dormant scene of `mode` so the next switch starts it fresh
+    pub fn discard(&mut self, mode: SimulationMode) {
+        match mode {
+            SimulationMode::Cpu => self.cpu = None,
+            SimulationMode::Preview => self.preview = None,
+            SimulationMode::Gpu => {}
+        }
+    }
+
+    /// Pause state to resume `mode` with (fresh scenes run)
+    pub fn paused(&self, mode: SimulationMode) -> bool {
+        match mode {
+            SimulationMode::Cpu => self.cpu.as_ref().is_some_and(|scene| scene.paused),
+            SimulationMode::Preview => self.preview.as_ref().is_some_and(|scene| scene.paused),
+            SimulationMode::Gpu => false,
+        }
+    }
+
+    pub fn summary(&self, mode: SimulationMode) -> Option<DormantSummary> {
+        match mode {
+            SimulationMode::Cpu => self.cpu.as_ref().map(|scene| DormantSummary {
+                cell_count: scene.canonical_state.cell_count,
+                time: scene.simulation_time,
+                bytes: MemoryUsage::of(&scene.canonical_state).total(),
+            }),
+            SimulationMode::Preview => self.preview.as_ref().map(|scene| DormantSummary {
+                cell_count: scene.preview.canonical_state.cell_count,
+                time: scene.preview.current_time,
+                bytes: MemoryUsage::of(&scene.preview.canonical_state).total()
+                    + scene.preview.checkpoints.iter()
+                        .map(|(_, checkpoint)| MemoryUsage::of(checkpoint).total())
+                        .sum::<usize>(),
+            }),
+            SimulationMode::Gpu => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::PhysicsConfig;
+
+    /// One tick the way `run_main_simulation` runs it
+    fn step(main: &mut MainSimState, genome: &GenomeData, config: &PhysicsConfig) {
+        let time = main.simulation_time;
+        let mut phases = std::mem::take(&mut main.phases);
+        phases.update(genome, main.canonical_state.division_stats.divisions, time);
+        let genome = phases.genome(genome);
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut main.canonical_state, config, genome, time, true);
+        main.simulation_time += config.fixed_timestep;
+        let (capacity, rng_seed) = (main.canonical_state.capacity, main.initial_state.rng_seed);
+        crate::simulation::cpu_physics::division_step(
+            &mut main.canonical_state, genome, time, config.fixed_timestep, capacity, rng_seed,
+        );
+        main.phases = phases;
+    }
+
+    /// One tick the way `run_preview_resimulation` replays it (no scheduled edits)
+    fn preview_step(preview: &mut PreviewSimState, phases: &mut crate::genome::PhasedGenome, genome: &GenomeData, config: &PhysicsConfig) {
+        let tick = crate::simulation::clock::ticks_to_reach(preview.current_time, config.fixed_timestep);
+        let time = tick as f32 * config.fixed_timestep;
+        phases.update(genome, preview.canonical_state.division_stats.divisions, time);
+        let genome = phases.genome(genome);
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut preview.canonical_state, config, genome, time, false);
+        let capacity = preview.canonical_state.capacity;
+        crate::simulation::cpu_physics::division_step(
+            &mut preview.canonical_state, genome, time, config.fixed_timestep, capacity, preview.initial_state.rng_seed,
+        );
+        preview.current_time = (tick + 1) as f32 * config.fixed_timestep;
+    }
+
+    #[test]
+    fn test_cpu_scene_survives_a_trip_through_preview() {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.0;
+        mode.parent_make_adhesion = true;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        let mut main = MainSimState { canonical_state: CanonicalState::new(64), ..MainSimState::default() };
+        main.canonical_state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 1.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        for _ in 0..300 {
+            step(&mut main, &genome, &config);
+        }
+        assert!(main.canonical_state.cell_count > 1);
+        let (fingerprint, time) = (main.canonical_state.fingerprint(), main.simulation_time);
+
+        let mut preview = PreviewSimState::default();
+        let mut timeline = EventTimeline::default();
+        let mut sim_state = SimulationState { paused: true, ..SimulationState::default() };
+        let mut dormant = DormantScenes::default();
+
+        // CPU -> Preview
+        dormant.cpu = Some(DormantCpuScene::take(&mut main, sim_state.paused));
+        assert_eq!(main.canonical_state.cell_count, 0);
+        assert!(dormant.preview.is_none());
+        sim_state.paused = dormant.paused(SimulationMode::Preview);
+        assert!(!sim_state.paused);
+        let summary = dormant.summary(SimulationMode::Cpu).unwrap();
+        assert_eq!((summary.time, summary.cell_count), (time, dormant.cpu.as_ref().unwrap().canonical_state.cell_count));
+        assert!(summary.bytes > 0);
+
+        // Preview -> CPU
+        dormant.preview = Some(DormantPreviewScene::take(&mut preview, &mut timeline, &sim_state));
+        sim_state.paused = dormant.paused(SimulationMode::Cpu);
+        dormant.cpu.take().unwrap().restore(&mut main);
+
+        assert!(sim_state.paused);
+        assert_eq!(main.simulation_time, time);
+        assert_eq!(main.canonical_state.fingerprint(), fingerprint);
+
+        // The restored scene carries on exactly like one that never left
+        let mut reference = MainSimState {
+            canonical_state: main.canonical_state.clone(),
+            simulation_time: time,
+            ..MainSimState::default()
+        };
+        for _ in 0..100 {
+            step(&mut main, &genome, &config);
+            step(&mut reference, &genome, &config);
+        }
+        assert_eq!(main.canonical_state.fingerprint(), reference.canonical_state.fingerprint());
+    }
+
+    #[test]
+    fn test_cpu_mode_continues_the_preview_bit_for_bit() {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_interval = 1.0;
+        mode.split_interval_min = Some(0.6);
+        mode.split_mass = 1.0;
+        mode.split_mass_min = Some(0.8);
+        mode.parent_make_adhesion = true;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        // Scrub a seeded preview to an organism with adhesions
+        let mut initial_state = InitialState::new(config.clone(), 128, 7);
+        initial_state.add_cell(crate::simulation::InitialCell {
+            id: 0,
+            position: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            angular_velocity: Vec3::ZERO,
+            mass: 1.0,
+            radius: 1.0,
+            genome_id: 0,
+            mode_index: 0,
+            birth_time: 0.0,
+            split_interval: 1.0,
+            split_mass: 1.0,
+            stiffness: config.default_stiffness,
+            pinned: false,
+            user_data: Default::default(),
+        });
+        let mut preview = PreviewSimState {
+            canonical_state: initial_state.to_canonical_state(),
+            initial_state,
+            ..PreviewSimState::default()
+        };
+        let mut phases = crate::genome::PhasedGenome::default();
+        for _ in 0..200 {
+            preview_step(&mut preview, &mut phases, &genome, &config);
+        }
+        assert!(preview.canonical_state.cell_count > 4);
+        assert!(preview.canonical_state.adhesion_connections.active_count > 0);
+
+        assert_eq!(DormantCpuScene::from_preview(&preview, true, 256).err(), Some(ContinueInCpuError::Resimulating));
+        let scene = DormantCpuScene::from_preview(&preview, false, 256).unwrap();
+        assert_eq!(scene.canonical_state.capacity, 256);
+        assert_eq!(scene.simulation_time, preview.current_time);
+        assert_eq!(scene.initial_state.rng_seed, 7);
+        assert_eq!(scene.canonical_state.next_cell_id, preview.canonical_state.next_cell_id);
+        let count = preview.canonical_state.cell_count;
+        assert_eq!(scene.canonical_state.cell_ids[..count], preview.canonical_state.cell_ids[..count]);
+        assert_eq!(scene.canonical_state.fingerprint(), preview.canonical_state.fingerprint());
+
+        let mut main = MainSimState::default();
+        scene.restore(&mut main);
+        for tick in 0..100 {
+            step(&mut main, &genome, &config);
+            preview_step(&mut preview, &mut phases, &genome, &config);
+            assert_eq!(main.canonical_state.fingerprint(), preview.canonical_state.fingerprint(), "diverged at tick {}", tick);
+        }
+        assert_eq!(main.simulation_time, preview.current_time);
+        let (cpu, preview) = (&main.canonical_state, &preview.canonical_state);
+        assert!(preview.cell_count > count && preview.cell_count < preview.capacity);
+        let count = preview.cell_count;
+        assert_eq!(cpu.cell_count, count);
+        assert_eq!(cpu.next_cell_id, preview.next_cell_id);
+        assert_eq!(cpu.cell_ids[..count], preview.cell_ids[..count]);
+        assert_eq!(cpu.positions[..count], preview.positions[..count]);
+        assert_eq!(cpu.velocities[..count], preview.velocities[..count]);
+        assert_eq!(cpu.rotations[..count], preview.rotations[..count]);
+        assert_eq!(cpu.masses[..count], preview.masses[..count]);
+    }
+
+    #[test]
+    fn test_continuing_in_cpu_lets_go_of_drags() {
+        let mut preview = PreviewSimState::default();
+        assert_eq!(DormantCpuScene::from_preview(&preview, false, 256).err(), Some(ContinueInCpuError::Empty));
+
+        preview.canonical_state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 1.0, 1.0, 500.0, Quat::IDENTITY, 0);
+        preview.canonical_state.soft_drag = Some(crate::simulation::soft_drag::SoftDrag::single(&preview.canonical_state, 0));
+        let scene = DormantCpuScene::from_preview(&preview, false, 16).unwrap();
+        assert!(scene.canonical_state.soft_drag.is_none());
+        // A smaller CPU setting keeps the preview's capacity
+        assert_eq!(scene.canonical_state.capacity, 256);
+        assert!(!scene.paused);
+    }
+
+    #[test]
+    fn test_preview_scene_keeps_its_scrub_position() {
+        let mut preview = PreviewSimState { current_time: 12.5, ..PreviewSimState::default() };
+        let mut timeline = EventTimeline::default();
+        let mut sim_state = SimulationState { target_time: Some(20.0), ..SimulationState::default() };
+
+        let scene = DormantPreviewScene::take(&mut preview, &mut timeline, &sim_state);
+        assert_eq!(preview.current_time, 0.0);
+        sim_state.target_time = None;
+
+        scene.restore(&mut preview, &mut timeline, &mut sim_state);
+        assert_eq!(preview.current_time, 12.5);
+        assert_eq!(sim_state.target_time, Some(20.0));
+        assert!(sim_state.needs_respawn);
+        assert!(preview.index_to_entity.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,623 @@
+use std::collections::{HashMap, VecDeque};
+use bevy::prelude::*;
+use crate::genome::ChildModeRule;
+use crate::simulation::cpu_physics::{CanonicalState, DivisionEvent};
+use crate::simulation::history::{HistoryBucket, HistoryLimits, HistoryRange, HistoryRecord, TieredHistory};
+
+/// Replay-surviving markers kept (oldest dropped first)
+const MAX_PERSISTENT_EVENTS: usize = 500;
+
+/// Births kept in the lineage log (oldest dropped first)
+const MAX_LINEAGE_RECORDS: usize = 65_536;
+
+/// Kind of event shown on the timeline strip
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimelineEventKind {
+    Division,
+    Death,
+    AdhesionBreak,
+    /// A cell was dragged by the user
+    Drag,
+    /// The genome was edited while the scrubber was at this time
+    GenomeEdit,
+    /// The cell count reached the capacity limit (further divisions are blocked)
+    CapacityWarning,
+    /// The user grew the cell capacity
+    CapacityGrowth,
+    /// The user cut an organism apart
+    OrganismSplit,
+    /// The user bonded two organisms together
+    Graft,
+    /// A simulation breakpoint fired
+    Breakpoint,
+    /// The user stamped a tissue file into the scene
+    TissueStamp,
+    /// A genome phase started (see `genome::phases`)
+    PhaseChange,
+    /// A watchdog condition fired (see `simulation::watchdog`)
+    Watchdog,
+    /// The user moved a bond's anchor points
+    AnchorEdit,
+}
+
+impl TimelineEventKind {
+    pub const ALL: [TimelineEventKind; 14] = [
+        TimelineEventKind::Division,
+        TimelineEventKind::Death,
+        TimelineEventKind::AdhesionBreak,
+        TimelineEventKind::Drag,
+        TimelineEventKind::GenomeEdit,
+        TimelineEventKind::CapacityWarning,
+        TimelineEventKind::CapacityGrowth,
+        TimelineEventKind::OrganismSplit,
+        TimelineEventKind::Graft,
+        TimelineEventKind::Breakpoint,
+        TimelineEventKind::TissueStamp,
+        TimelineEventKind::PhaseChange,
+        TimelineEventKind::Watchdog,
+        TimelineEventKind::AnchorEdit,
+    ];
+    pub const COUNT: usize = Self::ALL.len();
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TimelineEventKind::Division => "Division",
+            TimelineEventKind::Death => "Death",
+            TimelineEventKind::AdhesionBreak => "Adhesion break",
+            TimelineEventKind::Drag => "Cell drag",
+            TimelineEventKind::GenomeEdit => "Genome edit",
+            TimelineEventKind::CapacityWarning => "Capacity reached",
+            TimelineEventKind::CapacityGrowth => "Capacity grown",
+            TimelineEventKind::OrganismSplit => "Organism split",
+            TimelineEventKind::Graft => "Graft",
+            TimelineEventKind::Breakpoint => "Breakpoint",
+            TimelineEventKind::TissueStamp => "Tissue stamp",
+            TimelineEventKind::PhaseChange => "Genome phase",
+            TimelineEventKind::Watchdog => "Watchdog",
+            TimelineEventKind::AnchorEdit => "Anchor edit",
+        }
+    }
+
+    pub fn category(self) -> TimelineCategory {
+        match self {
+            TimelineEventKind::Division => TimelineCategory::Divisions,
+            TimelineEventKind::Death => TimelineCategory::Deaths,
+            TimelineEventKind::AdhesionBreak => TimelineCategory::AdhesionBreaks,
+            TimelineEventKind::Drag
+            | TimelineEventKind::GenomeEdit
+            | TimelineEventKind::OrganismSplit
+            | TimelineEventKind::Graft
+            | TimelineEventKind::Breakpoint
+            | TimelineEventKind::TissueStamp
+            | TimelineEventKind::Watchdog
+            | TimelineEventKind::AnchorEdit => TimelineCategory::Interventions,
+            TimelineEventKind::CapacityWarning | TimelineEventKind::CapacityGrowth => TimelineCategory::Capacity,
+            TimelineEventKind::PhaseChange => TimelineCategory::Phases,
+        }
+    }
+
+    /// Interventions that apply to every replay instead of being re-simulated
+    pub fn survives_replay(self) -> bool {
+        matches!(
+            self,
+            TimelineEventKind::GenomeEdit
+                | TimelineEventKind::CapacityGrowth
+                | TimelineEventKind::OrganismSplit
+                | TimelineEventKind::Graft
+                | TimelineEventKind::TissueStamp
+                | TimelineEventKind::AnchorEdit
+        )
+    }
+}
+
+/// Group of event kinds that can be shown or hidden together
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimelineCategory {
+    Divisions,
+    Deaths,
+    AdhesionBreaks,
+    Interventions,
+    Capacity,
+    Phases,
+}
+
+impl TimelineCategory {
+    pub const ALL: [TimelineCategory; 6] = [
+        TimelineCategory::Divisions,
+        TimelineCategory::Deaths,
+        TimelineCategory::AdhesionBreaks,
+        TimelineCategory::Interventions,
+        TimelineCategory::Capacity,
+        TimelineCategory::Phases,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TimelineCategory::Divisions => "Divisions",
+            TimelineCategory::Deaths => "Deaths",
+            TimelineCategory::AdhesionBreaks => "Adhesion breaks",
+            TimelineCategory::Interventions => "Interventions",
+            TimelineCategory::Capacity => "Capacity",
+            TimelineCategory::Phases => "Phases",
+        }
+    }
+}
+
+/// A single event at a simulation tick
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimelineEvent {
+    /// Tick after which the event is reflected in the state
+    pub tick: u32,
+    pub kind: TimelineEventKind,
+    /// Cell IDs involved (children for divisions, both ends for adhesion breaks)
+    ///
+    /// Phase changes keep the index of the phase that started in the first slot
+    /// (None when the genome returned to its base settings).
+    pub cells: [Option<u32>; 2],
+}
+
+impl HistoryRecord for TimelineEvent {
+    /// Event count per kind
+    type Summary = [u32; TimelineEventKind::COUNT];
+
+    fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    fn summarize(summary: &mut Self::Summary, record: &Self) {
+        summary[record.kind.index()] += 1;
+    }
+
+    fn merge(summary: &mut Self::Summary, other: &Self::Summary) {
+        for (count, other) in summary.iter_mut().zip(other) {
+            *count += other;
+        }
+    }
+}
+
+impl TimelineEvent {
+    pub fn new(tick: u32, kind: TimelineEventKind, cells: [Option<u32>; 2]) -> Self {
+        Self { tick, kind, cells }
+    }
+
+    pub fn phase_change(tick: u32, phase: Option<usize>) -> Self {
+        Self::new(tick, TimelineEventKind::PhaseChange, [phase.map(|index| index as u32), None])
+    }
+
+    /// Phase that started, for phase change events
+    pub fn phase(&self) -> Option<usize> {
+        (self.kind == TimelineEventKind::PhaseChange).then_some(self.cells[0]).flatten().map(|index| index as usize)
+    }
+}
+
+/// How one cell was born: its parent, its side of the division and the rule that picked its mode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineageRecord {
+    pub cell_id: u32,
+    /// Tick of the division (as in its timeline event)
+    pub tick: u32,
+    pub parent_id: u32,
+    pub parent_mode: u16,
+    pub mode: u16,
+    /// 0 for child A, 1 for child B
+    pub side: u8,
+    pub rule: ChildModeRule,
+    /// Divisions since the oldest ancestor still in the log
+    pub generation: u16,
+}
+
+impl LineageRecord {
+    pub fn side_name(&self) -> &'static str {
+        if self.side == 0 { "A" } else { "B" }
+    }
+}
+
+/// Bounded record of which division produced each cell, keyed by child cell ID
+///
+/// Records arrive in tick order; the oldest are dropped once the log is full, so
+/// traces of long-lived lineages start at the oldest ancestor still known.
+#[derive(Default)]
+pub struct LineageLog {
+    records: HashMap<u32, LineageRecord>,
+    /// Child IDs in insertion (tick) order
+    order: VecDeque<u32>,
+}
+
+impl LineageLog {
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.order.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn get(&self, cell_id: u32) -> Option<&LineageRecord> {
+        self.records.get(&cell_id)
+    }
+
+    /// Add a birth, numbering its generation from the parent's record
+    pub fn push(&mut self, mut record: LineageRecord) {
+        record.generation = self.records.get(&record.parent_id).map_or(1, |parent| parent.generation.saturating_add(1));
+        if self.order.len() >= MAX_LINEAGE_RECORDS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.records.remove(&oldest);
+            }
+        }
+        if self.records.insert(record.cell_id, record).is_none() {
+            self.order.push_back(record.cell_id);
+        }
+    }
+
+    /// Drop births after `tick`
+    pub fn truncate_after(&mut self, tick: u32) {
+        while let Some(&last) = self.order.back() {
+            if self.records.get(&last).is_some_and(|record| record.tick <= tick) {
+                break;
+            }
+            self.order.pop_back();
+            self.records.remove(&last);
+        }
+    }
+
+    /// Births leading to `cell_id`, oldest ancestor's first
+    pub fn trace(&self, cell_id: u32) -> Vec<LineageRecord> {
+        let mut trace = Vec::new();
+        let mut current = cell_id;
+        // Parents are always older than their children, but stay bounded by the log anyway
+        while let Some(record) = self.records.get(&current) {
+            if trace.len() >= self.order.len() {
+                break;
+            }
+            trace.push(*record);
+            current = record.parent_id;
+        }
+        trace.reverse();
+        trace
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        self.records.capacity() * (std::mem::size_of::<u32>() + std::mem::size_of::<LineageRecord>())
+            + self.order.capacity() * std::mem::size_of::<u32>()
+    }
+}
+
+/// Time-indexed store of the preview timeline's events
+///
+/// Simulation events are owned by the timeline they were simulated in: when a
+/// resimulation replays from a tick, everything after that tick is dropped and
+/// rebuilt from the replay's own events. Genome edits and capacity growths are
+/// kept across replays: edits mark where the user was when the (whole) timeline
+/// changed, and replays re-apply growths at their recorded ticks.
+///
+/// Simulation events age from raw events into per-second and per-minute counts
+/// (see `TieredHistory`) within the limits of the history settings.
+#[derive(Resource)]
+pub struct EventTimeline {
+    /// Simulation events, in ticks of the timeline's timestep
+    history: TieredHistory<TimelineEvent>,
+    /// Events that survive replays, sorted by insertion
+    persistent_events: Vec<TimelineEvent>,
+    /// Which division produced each cell, trimmed with the simulation events on replays
+    lineage: LineageLog,
+    /// Category visibility on the timeline strip
+    pub visible: [bool; TimelineCategory::ALL.len()],
+}
+
+impl Default for EventTimeline {
+    fn default() -> Self {
+        Self {
+            history: TieredHistory::new(
+                crate::simulation::PhysicsConfig::default().fixed_timestep,
+                crate::simulation::HistorySettings::default().limits(crate::simulation::history::HistoryStore::Timeline),
+            ),
+            persistent_events: Vec::new(),
+            lineage: LineageLog::default(),
+            visible: [true; TimelineCategory::ALL.len()],
+        }
+    }
+}
+
+impl EventTimeline {
+    /// Forget all events (scene reset)
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.persistent_events.clear();
+        self.lineage.clear();
+    }
+
+    pub fn timestep(&self) -> f32 {
+        self.history.timestep()
+    }
+
+    pub fn tick_time(&self, tick: u32) -> f32 {
+        self.history.tick_time(tick)
+    }
+
+    pub fn limits(&self) -> HistoryLimits {
+        self.history.limits()
+    }
+
+    pub fn set_limits(&mut self, limits: HistoryLimits) {
+        self.history.set_limits(limits);
+    }
+
+    /// Prepare for a replay that starts after `tick`
+    ///
+    /// Events after `tick` belong to the timeline being replaced. A timestep
+    /// change invalidates every tick, so the store starts over (keeping nothing
+    /// but the visibility settings).
+    pub fn begin_replay(&mut self, tick: u32, timestep: f32) {
+        if self.timestep() != timestep {
+            self.history.set_timestep(timestep);
+            self.clear();
+            return;
+        }
+        self.truncate_after(tick);
+    }
+
+    /// Drop simulation events after `tick`
+    ///
+    /// Folded seconds and minutes that reach past `tick` are dropped whole; the
+    /// replay re-adds their later events as raw events.
+    pub fn truncate_after(&mut self, tick: u32) {
+        self.history.truncate_after(tick);
+        self.lineage.truncate_after(tick);
+    }
+
+    /// Add an event, keeping ticks sorted and the history within its limits
+    pub fn push(&mut self, event: TimelineEvent) {
+        if event.kind.survives_replay() {
+            // Slider drags edit the genome every frame; one marker per tick is enough
+            if self.persistent_events.last().is_some_and(|last| last.tick == event.tick && last.kind == event.kind) {
+                return;
+            }
+            if self.persistent_events.len() >= MAX_PERSISTENT_EVENTS {
+                self.persistent_events.remove(0);
+            }
+            self.persistent_events.push(event);
+            return;
+        }
+
+        self.history.push(event);
+    }
+
+    pub fn extend(&mut self, events: impl IntoIterator<Item = TimelineEvent>) {
+        for event in events {
+            self.push(event);
+        }
+    }
+
+    /// Raw events sorted by tick (replay-surviving events excluded)
+    pub fn events(&self) -> &std::collections::VecDeque<TimelineEvent> {
+        self.history.raw()
+    }
+
+    /// Per-second and per-minute counts of aggregated history, oldest first
+    pub fn buckets(&self) -> impl Iterator<Item = &HistoryBucket<[u32; TimelineEventKind::COUNT]>> {
+        self.history.buckets()
+    }
+
+    /// Time ranges held at each resolution
+    pub fn coverage(&self) -> Vec<HistoryRange> {
+        self.history.coverage()
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        self.history.memory_bytes() + self.lineage.memory_bytes()
+    }
+
+    pub fn persistent_events(&self) -> &[TimelineEvent] {
+        &self.persistent_events
+    }
+
+    /// Add births in tick order
+    pub fn extend_lineage(&mut self, records: impl IntoIterator<Item = LineageRecord>) {
+        for record in records {
+            self.lineage.push(record);
+        }
+    }
+
+    pub fn lineage(&self) -> &LineageLog {
+        &self.lineage
+    }
+}
+
+/// Collects timeline events tick by tick during a simulation run
+pub struct TimelineRecorder {
+    pub events: Vec<TimelineEvent>,
+    /// Births of the recorded divisions (generations are numbered by the log)
+    pub lineage: Vec<LineageRecord>,
+    at_capacity: bool,
+}
+
+impl TimelineRecorder {
+    pub fn new(state: &CanonicalState) -> Self {
+        Self {
+            events: Vec::new(),
+            lineage: Vec::new(),
+            // A run that starts at capacity was already reported by the run that got there
+            at_capacity: state.cell_count >= state.capacity,
+        }
+    }
+
+    /// Record what happened in the tick that was just simulated
+    ///
+    /// Call after both the physics step (removals) and the division step.
+    pub fn record_tick(&mut self, tick: u32, state: &CanonicalState, divisions: &[DivisionEvent]) {
+        for &cell_id in &state.removed_cell_ids_buffer {
+            self.events.push(TimelineEvent::new(tick, TimelineEventKind::Death, [Some(cell_id), None]));
+        }
+        for &(cell_id, partner_id) in &state.broken_adhesions_buffer {
+            self.events.push(TimelineEvent::new(tick, TimelineEventKind::AdhesionBreak, [Some(cell_id), Some(partner_id)]));
+        }
+        for division in divisions {
+            let child_id = |index: usize| (index < state.cell_count).then(|| state.cell_ids[index]);
+            self.events.push(TimelineEvent::new(
+                tick,
+                TimelineEventKind::Division,
+                [child_id(division.child_a_idx), child_id(division.child_b_idx)],
+            ));
+            for side in 0..2 {
+                let Some(cell_id) = division.child_ids[side] else {
+                    continue;
+                };
+                self.lineage.push(LineageRecord {
+                    cell_id,
+                    tick,
+                    parent_id: division.parent_id,
+                    parent_mode: division.parent_mode as u16,
+                    mode: division.child_modes[side] as u16,
+                    side: side as u8,
+                    rule: division.child_rules[side],
+                    generation: 0,
+                });
+            }
+        }
+
+        let at_capacity = state.cell_count >= state.capacity;
+        if at_capacity && !self.at_capacity {
+            self.events.push(TimelineEvent::new(tick, TimelineEventKind::CapacityWarning, [None, None]));
+        }
+        self.at_capacity = at_capacity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn division(tick: u32) -> TimelineEvent {
+        TimelineEvent::new(tick, TimelineEventKind::Division, [Some(tick), None])
+    }
+
+    /// Timeline of half-second ticks keeping one second of raw events
+    fn timeline() -> EventTimeline {
+        let limits = HistoryLimits { raw_window: 1.0, ..EventTimeline::default().limits() };
+        EventTimeline { history: TieredHistory::new(0.5, limits), ..Default::default() }
+    }
+
+    #[test]
+    fn test_raw_events_fold_into_per_second_buckets() {
+        let mut timeline = timeline();
+        for tick in 0..6 {
+            timeline.push(division(tick));
+        }
+
+        // Ticks 0-1 (second 0) were folded once they left the raw window
+        assert_eq!(timeline.events().len(), 4);
+        assert_eq!(timeline.events()[0].tick, 2);
+        let first = timeline.buckets().next().unwrap();
+        assert_eq!((first.start, first.summary[TimelineEventKind::Division.index()]), (0, 2));
+    }
+
+    #[test]
+    fn test_replay_truncates_events_but_keeps_genome_edits() {
+        let mut timeline = timeline();
+        for tick in 0..8 {
+            timeline.push(division(tick));
+        }
+        timeline.push(TimelineEvent::new(7, TimelineEventKind::GenomeEdit, [None, None]));
+        timeline.push(TimelineEvent::new(7, TimelineEventKind::GenomeEdit, [None, None]));
+        assert_eq!(timeline.buckets().count(), 2);
+
+        // Replaying from tick 2 drops every later event and the folded second holding tick 3
+        timeline.begin_replay(2, 0.5);
+        assert!(timeline.events().is_empty());
+        assert_eq!(timeline.buckets().map(|bucket| bucket.start).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(timeline.persistent_events().len(), 1);
+
+        // The replay's events take their place in order
+        timeline.extend([division(4), division(3)]);
+        assert_eq!(timeline.events().iter().map(|e| e.tick).collect::<Vec<_>>(), vec![3, 4]);
+
+        // A different timestep invalidates everything
+        timeline.begin_replay(0, 0.25);
+        assert!(timeline.events().is_empty() && timeline.buckets().next().is_none() && timeline.persistent_events().is_empty());
+    }
+
+    fn birth(cell_id: u32, parent_id: u32, tick: u32) -> LineageRecord {
+        LineageRecord { cell_id, tick, parent_id, parent_mode: 0, mode: 0, side: 0, rule: ChildModeRule::Child, generation: 0 }
+    }
+
+    #[test]
+    fn test_lineage_log_numbers_generations_and_trims_on_replay() {
+        let mut timeline = timeline();
+        // Founder 0 -> 1 -> 3 -> 5, with 2 and 4 as siblings
+        timeline.extend_lineage([birth(1, 0, 1), birth(2, 0, 1), birth(3, 1, 2), birth(4, 1, 2), birth(5, 3, 3)]);
+
+        let trace = timeline.lineage().trace(5);
+        assert_eq!(trace.iter().map(|r| (r.cell_id, r.generation)).collect::<Vec<_>>(), vec![(1, 1), (3, 2), (5, 3)]);
+        assert!(timeline.lineage().trace(0).is_empty());
+
+        // Replaying from tick 2 forgets the later birth
+        timeline.begin_replay(2, 0.5);
+        assert_eq!(timeline.lineage().len(), 4);
+        assert!(timeline.lineage().get(5).is_none());
+        assert_eq!(timeline.lineage().trace(4).len(), 2);
+    }
+
+    #[test]
+    fn test_lineage_trace_reports_the_after_splits_rule_at_its_generation() {
+        use crate::genome::{GenomeData, ModeIndex, ModeSettings};
+        use crate::simulation::PhysicsConfig;
+
+        // Stem cells divide into themselves until their second split, whose children become tissue
+        let growing = |index: usize, name: &str| {
+            let mut mode = ModeSettings::new_self_splitting(index, name.to_string());
+            mode.split_mass = 1.0;
+            mode.split_interval = 0.5;
+            mode.nutrient_gain_rate = 5.0;
+            mode
+        };
+        let mut stem = growing(0, "Stem");
+        stem.max_splits = 2;
+        stem.mode_a_after_splits = ModeIndex::new(1);
+        stem.mode_b_after_splits = ModeIndex::new(1);
+        let genome = GenomeData { modes: vec![stem, growing(1, "Tissue")], ..GenomeData::default() };
+
+        let config = PhysicsConfig::default();
+        let dt = config.fixed_timestep;
+        let mut state = crate::simulation::parameter_sweep::initial_state(&genome, &config, 64, 0);
+        let mut recorder = TimelineRecorder::new(&state);
+        for tick in 0..crate::simulation::clock::ticks_to_reach(10.0, dt) {
+            let time = tick as f32 * dt;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+            let capacity = state.capacity;
+            let divisions = crate::simulation::cpu_physics::division_step(&mut state, &genome, time, dt, capacity, 0);
+            recorder.record_tick(tick + 1, &state, &divisions);
+        }
+        let mut timeline = EventTimeline::default();
+        timeline.extend_lineage(recorder.lineage);
+
+        let deepest = state.cell_ids[..state.cell_count]
+            .iter()
+            .copied()
+            .max_by_key(|&id| timeline.lineage().get(id).map_or(0, |record| record.generation))
+            .unwrap();
+        let trace = timeline.lineage().trace(deepest);
+        assert!(trace.len() >= 3, "only {} generations", trace.len());
+        for (i, record) in trace.iter().enumerate() {
+            assert_eq!(record.generation as usize, i + 1);
+            let (rule, parent_mode, mode) = match record.generation {
+                1 => (ChildModeRule::Child, 0, 0),
+                2 => (ChildModeRule::AfterSplits, 0, 1),
+                _ => (ChildModeRule::Child, 1, 1),
+            };
+            assert_eq!((record.rule, record.parent_mode, record.mode), (rule, parent_mode, mode), "generation {}", record.generation);
+        }
+        for pair in trace.windows(2) {
+            assert_eq!(pair[1].parent_id, pair[0].cell_id);
+        }
+        assert_eq!(trace.last().unwrap().mode as usize, state.mode_indices[state.cell_ids[..state.cell_count].iter().position(|&id| id == deepest).unwrap()]);
+    }
+}
@@ -0,0 +1,631 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::{block_on, poll_once, IoTaskPool, Task};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::error::{BioSpheresError, FileOperation};
+use crate::simulation::capture::{write_capture, AnnotationSettings, CaptureMetadata};
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::scene_file::{CameraPose, SceneFile};
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+use crate::ui::camera::MainCamera;
+
+/// Plugin for named experiment sessions that archive genomes, snapshots and a final report
+pub struct ExperimentSessionPlugin;
+
+impl Plugin for ExperimentSessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExperimentSession>()
+            .add_systems(Update, (
+                handle_session_requests,
+                archive_genome_saves,
+                poll_archive_tasks,
+            ).chain());
+    }
+}
+
+/// Folder (relative to the working directory) holding one subfolder per session
+pub const SESSIONS_DIR: &str = "sessions";
+
+/// Longest edge of snapshot thumbnails, in pixels
+const THUMBNAIL_SIZE: u32 = 160;
+
+/// Statistics summary stored with each snapshot (stats.json)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotStats {
+    pub tick: u32,
+    /// Simulated seconds
+    pub time: f32,
+    pub cell_count: usize,
+    pub adhesion_count: usize,
+    pub total_mass: f32,
+    pub mean_radius: f32,
+    /// (mode name, cell count) for every mode that has cells
+    pub cells_per_mode: Vec<(String, usize)>,
+    /// Simulation fingerprint as `<hex>@<tick>`
+    pub fingerprint: String,
+}
+
+impl SnapshotStats {
+    pub fn of(state: &CanonicalState, genome: &GenomeData, time: f32, tick: u32) -> Self {
+        let n = state.cell_count;
+        let connections = &state.adhesion_connections;
+        let adhesion_count = connections.is_active[..connections.active_count]
+            .iter()
+            .filter(|active| **active != 0)
+            .count();
+
+        let cells_per_mode = state.cells_per_mode(genome.modes.len()).into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(mode, count)| (genome.modes[mode].name.clone(), count))
+            .collect();
+
+        Self {
+            tick,
+            time,
+            cell_count: n,
+            adhesion_count,
+            total_mass: state.masses[..n].iter().sum(),
+            mean_radius: if n == 0 { 0.0 } else { state.radii[..n].iter().sum::<f32>() / n as f32 },
+            cells_per_mode,
+            fingerprint: format!("{:016x}@{}", state.fingerprint(), tick),
+        }
+    }
+}
+
+/// RGBA8 thumbnail of a snapshot screenshot
+pub struct Thumbnail {
+    pub size: [usize; 2],
+    pub rgba: Vec<u8>,
+}
+
+/// A snapshot archived in the current session
+pub struct SessionSnapshot {
+    /// 1-based, in the order taken
+    pub number: u32,
+    pub dir: PathBuf,
+    pub stats: SnapshotStats,
+    /// Filled in once the screenshot has been written
+    pub thumbnail: Option<Arc<Thumbnail>>,
+}
+
+impl SessionSnapshot {
+    /// The snapshot's scene file: the chunked layout if it was saved in one, else JSON
+    pub fn scene_path(&self) -> PathBuf {
+        let chunked = self.dir.join(crate::simulation::chunked_scene::CHUNKED_SCENE_FILE);
+        if chunked.exists() { chunked } else { self.dir.join("scene.json") }
+    }
+}
+
+/// A started (or ended) experiment session
+pub struct SessionRecord {
+    pub name: String,
+    pub dir: PathBuf,
+    /// UTC start time, `YYYY-MM-DD HH:MM`
+    pub started: String,
+    pub snapshots: Vec<SessionSnapshot>,
+    /// Archived genome copies, in save order
+    pub genome_saves: Vec<PathBuf>,
+    pub ended: bool,
+}
+
+impl SessionRecord {
+    pub fn is_active(&self) -> bool {
+        !self.ended
+    }
+}
+
+enum SessionRequest {
+    Start(String),
+    Snapshot,
+    End,
+    Load(usize),
+}
+
+/// Finished background archive operation
+enum ArchiveOutcome {
+    Started { name: String, dir: PathBuf, started: String },
+    Written,
+    Thumbnail { dir: PathBuf, thumbnail: Thumbnail },
+    Report(PathBuf),
+    Loaded { title: String, scene: SceneFile },
+}
+
+/// Experiment session state and its pending archive writes
+///
+/// UI code only queues requests; the session's systems build the archived data
+/// from clones of the live state and write it on the IO task pool.
+#[derive(Resource)]
+pub struct ExperimentSession {
+    pub root: PathBuf,
+    /// Current session, kept after it ends so its snapshots stay browsable
+    pub record: Option<SessionRecord>,
+    /// Name typed into the Start Session prompt (None = prompt closed)
+    pub name_prompt: Option<String>,
+    requests: Vec<SessionRequest>,
+    tasks: Vec<Task<crate::error::Result<ArchiveOutcome>>>,
+    starting: bool,
+    /// Serial of the last genome save that was seen (saves before the session are skipped)
+    genome_save_serial: u64,
+}
+
+impl Default for ExperimentSession {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from(SESSIONS_DIR),
+            record: None,
+            name_prompt: None,
+            requests: Vec::new(),
+            tasks: Vec::new(),
+            starting: false,
+            genome_save_serial: 0,
+        }
+    }
+}
+
+impl ExperimentSession {
+    pub fn is_active(&self) -> bool {
+        self.record.as_ref().is_some_and(SessionRecord::is_active)
+    }
+
+    /// Waiting for the session folder to be created
+    pub fn is_starting(&self) -> bool {
+        self.starting
+    }
+
+    /// Archive writes still in flight
+    pub fn pending_writes(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn start(&mut self, name: String) {
+        self.requests.push(SessionRequest::Start(name));
+    }
+
+    pub fn snapshot(&mut self) {
+        self.requests.push(SessionRequest::Snapshot);
+    }
+
+    pub fn end(&mut self) {
+        self.requests.push(SessionRequest::End);
+    }
+
+    /// Load snapshot `index` (into `record.snapshots`) back into the preview simulation
+    pub fn load_snapshot(&mut self, index: usize) {
+        self.requests.push(SessionRequest::Load(index));
+    }
+
+    fn spawn(&mut self, work: impl FnOnce() -> crate::error::Result<ArchiveOutcome> + Send + 'static) {
+        self.tasks.push(IoTaskPool::get().spawn(async move { work() }));
+    }
+}
+
+fn handle_session_requests(
+    mut commands: Commands,
+    mut session: ResMut<ExperimentSession>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    current_genome: Res<CurrentGenome>,
+    physics_config: Res<PhysicsConfig>,
+    physics_layers: Res<crate::simulation::PhysicsLayers>,
+    capture_settings: Res<crate::simulation::CaptureSettings>,
+    camera_query: Query<&MainCamera>,
+    mut scene_loader: ResMut<crate::simulation::SceneLoader>,
+    cpu_cell_capacity: Res<crate::ui::CpuCellCapacity>,
+    groups: Res<crate::simulation::CellGroups>,
+    lighting: Res<crate::ui::LightingConfig>,
+    regions: Res<crate::rendering::RegionVisibility>,
+) {
+    use crate::simulation::chunked_scene::{CHUNKED_SCENE_FILE, CHUNKED_SCENE_MIN_CELLS};
+
+    for request in std::mem::take(&mut session.requests) {
+        match request {
+            SessionRequest::Start(name) => {
+                if session.is_active() || session.starting {
+                    notifications.warning("End the current session before starting another");
+                    continue;
+                }
+                let name = sanitize_name(&name);
+                let started = format_utc(unix_now());
+                let folder = format!("{}-{}", name, &started[..10]);
+                let root = session.root.clone();
+                let physics = physics_config.clone();
+                session.starting = true;
+                session.genome_save_serial = current_genome.last_save().map_or(0, |save| save.serial);
+                session.spawn(move || {
+                    let dir = unique_dir(&root, &folder);
+                    crate::error::create_dir_all(&dir)?;
+                    crate::error::write_json(&dir.join("physics.json"), &physics, "physics config")?;
+                    crate::error::write_json(&dir.join("settings.json"), &crate::ui::settings::UiSettings::load(), "settings")?;
+                    Ok(ArchiveOutcome::Started { name, dir, started })
+                });
+            }
+            SessionRequest::Snapshot => {
+                let Some(record) = session.record.as_ref().filter(|record| record.is_active()) else {
+                    continue;
+                };
+                let active = match sim_state.mode {
+                    SimulationMode::Cpu => main_state.as_deref()
+                        .map(|s| (&s.canonical_state, s.simulation_time, s.initial_state.rng_seed)),
+                    SimulationMode::Preview => preview_state.as_deref()
+                        .map(|s| (&s.canonical_state, s.current_time, s.initial_state.rng_seed)),
+                    SimulationMode::Gpu => None,
+                };
+                let Some((state, time, rng_seed)) = active else {
+                    notifications.warning("No active simulation to snapshot");
+                    continue;
+                };
+
+                let tick = crate::simulation::clock::ticks_to_reach(time, physics_config.fixed_timestep);
+                let number = record.snapshots.len() as u32 + 1;
+                let dir = record.dir.join("snapshots").join(format!("{:03}-t{}", number, tick));
+                let camera = camera_query.iter().next().map(|camera| CameraPose {
+                    center: camera.center,
+                    distance: camera.distance,
+                    rotation: camera.rotation,
+                });
+                let genome = &current_genome.genome;
+                let mut scene = SceneFile::from_state(
+                    state,
+                    genome,
+                    &physics_config,
+                    camera,
+                    rng_seed,
+                    format!("Snapshot {} of session {} at t = {:.2}s", number, record.name, time),
+                );
+                scene.physics_overrides = Some(physics_layers.scene.clone());
+                scene.groups = groups.scene_groups(state);
+                scene.regions = regions.scene_settings();
+                let stats = SnapshotStats::of(state, genome, time, tick);
+                // A CPU run also saves its start and interventions, which replay to the snapshot
+                let run_scene = match sim_state.mode {
+                    SimulationMode::Cpu => main_state.as_deref().and_then(|s| {
+                        s.run_scene(format!("Run up to snapshot {} of session {}", number, record.name))
+                    }),
+                    _ => None,
+                };
+                let annotations = capture_settings.annotations.clone();
+                let metadata = CaptureMetadata::new(state, genome, time, tick, rng_seed, sim_state.speed_multiplier, &annotations.caption)
+                    .with_lighting(&lighting);
+
+                // Scene and stats go out right away; the screenshot arrives a frame or two later
+                let (scene_dir, scene_stats) = (dir.clone(), stats.clone());
+                session.spawn(move || {
+                    crate::error::create_dir_all(&scene_dir)?;
+                    // Large scenes go in the chunked layout, which loads without parsing every cell
+                    if scene.initial_cells.len() >= CHUNKED_SCENE_MIN_CELLS {
+                        scene.save_chunked(&scene_dir.join(CHUNKED_SCENE_FILE))?;
+                    } else {
+                        scene.save_to_file(&scene_dir.join("scene.json"))?;
+                    }
+                    crate::error::write_json(&scene_dir.join("stats.json"), &scene_stats, "snapshot statistics")?;
+                    if let Some(run_scene) = run_scene {
+                        run_scene.save_to_file(&scene_dir.join("run.json"))?;
+                    }
+                    Ok(ArchiveOutcome::Written)
+                });
+                let screenshot_dir = dir.clone();
+                commands.spawn(Screenshot::primary_window()).observe(
+                    move |captured: On<ScreenshotCaptured>, mut session: ResMut<ExperimentSession>| {
+                        let (image, dir) = (captured.image.clone(), screenshot_dir.clone());
+                        let (annotations, metadata) = (annotations.clone(), metadata.clone());
+                        session.spawn(move || save_screenshot(image, dir, &annotations, &metadata));
+                    },
+                );
+
+                if let Some(record) = session.record.as_mut() {
+                    record.snapshots.push(SessionSnapshot { number, dir, stats, thumbnail: None });
+                }
+            }
+            SessionRequest::End => {
+                let Some(record) = session.record.as_mut().filter(|record| record.is_active()) else {
+                    continue;
+                };
+                record.ended = true;
+                let report = session_report(record, &format_utc(unix_now()));
+                let path = record.dir.join("report.md");
+                session.spawn(move || {
+                    crate::error::write_atomically(&path, report.as_bytes())?;
+                    Ok(ArchiveOutcome::Report(path))
+                });
+            }
+            SessionRequest::Load(index) => {
+                let Some(snapshot) = session.record.as_ref().and_then(|record| record.snapshots.get(index)) else {
+                    continue;
+                };
+                let title = format!("Snapshot {}", snapshot.number);
+                let path = snapshot.scene_path();
+                if path.ends_with(CHUNKED_SCENE_FILE) {
+                    scene_loader.load(path, title, cpu_cell_capacity.capacity);
+                    continue;
+                }
+                session.spawn(move || {
+                    let scene = SceneFile::load_from_file(&path)?;
+                    Ok(ArchiveOutcome::Loaded { title, scene })
+                });
+            }
+        }
+    }
+}
+
+/// Archive a copy of the genome on every explicit save while a session is active
+fn archive_genome_saves(
+    mut session: ResMut<ExperimentSession>,
+    current_genome: Res<CurrentGenome>,
+) {
+    let Some(save) = current_genome.last_save() else {
+        return;
+    };
+    if save.serial <= session.genome_save_serial || !session.is_active() {
+        return;
+    }
+    session.genome_save_serial = save.serial;
+
+    let Some(record) = session.record.as_mut() else {
+        return;
+    };
+    let file_stem = save.path.file_stem().map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| save.genome.name.clone());
+    let path = record.dir.join("genomes")
+        .join(format!("{:03}-{}.json", record.genome_saves.len() + 1, sanitize_name(&file_stem)));
+    record.genome_saves.push(path.clone());
+
+    let genome = save.genome.clone();
+    session.spawn(move || {
+        if let Some(parent) = path.parent() {
+            crate::error::create_dir_all(parent)?;
+        }
+        genome.save_to_file(&path)?;
+        Ok(ArchiveOutcome::Written)
+    });
+}
+
+fn poll_archive_tasks(
+    mut session: ResMut<ExperimentSession>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut scene_request: ResMut<crate::ui::windows::scene_manager::SceneModeRequest>,
+) {
+    let mut finished = Vec::new();
+    session.tasks.retain_mut(|task| match block_on(poll_once(task)) {
+        Some(result) => {
+            finished.push(result);
+            false
+        }
+        None => true,
+    });
+
+    for result in finished {
+        match result {
+            Ok(ArchiveOutcome::Started { name, dir, started }) => {
+                session.starting = false;
+                notifications.success(format!("Started session '{}' in {}", name, dir.display()));
+                session.record = Some(SessionRecord {
+                    name,
+                    dir,
+                    started,
+                    snapshots: Vec::new(),
+                    genome_saves: Vec::new(),
+                    ended: false,
+                });
+            }
+            Ok(ArchiveOutcome::Written) => {}
+            Ok(ArchiveOutcome::Thumbnail { dir, thumbnail }) => {
+                let snapshot = session.record.as_mut()
+                    .and_then(|record| record.snapshots.iter_mut().find(|snapshot| snapshot.dir == dir));
+                if let Some(snapshot) = snapshot {
+                    snapshot.thumbnail = Some(Arc::new(thumbnail));
+                }
+            }
+            Ok(ArchiveOutcome::Report(path)) => {
+                notifications.success(format!("Wrote session report {}", path.display()));
+            }
+            Ok(ArchiveOutcome::Loaded { title, scene }) => {
+                scene_request.requested_scene = Some((title, scene));
+            }
+            Err(error) => {
+                session.starting = false;
+                notifications.error(&error);
+            }
+        }
+    }
+}
+
+/// Write the screenshot next to the snapshot's scene and make its thumbnail
+fn save_screenshot(
+    image: Image,
+    dir: PathBuf,
+    annotations: &AnnotationSettings,
+    metadata: &CaptureMetadata,
+) -> crate::error::Result<ArchiveOutcome> {
+    let path = dir.join("screenshot.png");
+    let encode_error = |message: String| BioSpheresError::Io {
+        operation: FileOperation::Write,
+        path: path.clone(),
+        source: std::io::Error::other(message),
+    };
+    let dynamic = image.try_into_dynamic().map_err(|e| encode_error(e.to_string()))?;
+    if let Some(warning) = write_capture(&dynamic, &path, annotations, metadata)? {
+        warn!("{}", warning);
+    }
+
+    let thumbnail = dynamic.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8();
+    Ok(ArchiveOutcome::Thumbnail {
+        dir,
+        thumbnail: Thumbnail {
+            size: [thumbnail.width() as usize, thumbnail.height() as usize],
+            rgba: thumbnail.into_raw(),
+        },
+    })
+}
+
+/// Markdown summary of a session: snapshot metrics and the genomes archived
+pub fn session_report(record: &SessionRecord, ended: &str) -> String {
+    let mut report = format!("# Session: {}\n\n", record.name);
+    report.push_str(&format!("- Started: {} UTC\n- Ended: {} UTC\n", record.started, ended));
+    report.push_str(&format!("- Snapshots: {}\n- Genome saves: {}\n\n", record.snapshots.len(), record.genome_saves.len()));
+
+    if !record.snapshots.is_empty() {
+        report.push_str("## Snapshots\n\n");
+        report.push_str("| # | Time (s) | Tick | Cells | Adhesions | Total mass | Mean radius | Fingerprint |\n");
+        report.push_str("|---|---|---|---|---|---|---|---|\n");
+        for snapshot in &record.snapshots {
+            let stats = &snapshot.stats;
+            report.push_str(&format!(
+                "| {} | {:.2} | {} | {} | {} | {:.2} | {:.3} | `{}` |\n",
+                snapshot.number, stats.time, stats.tick, stats.cell_count, stats.adhesion_count,
+                stats.total_mass, stats.mean_radius, stats.fingerprint,
+            ));
+        }
+
+        for snapshot in &record.snapshots {
+            let relative = snapshot.dir.strip_prefix(&record.dir).unwrap_or(&snapshot.dir);
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            report.push_str(&format!("\n### Snapshot {}\n\n", snapshot.number));
+            report.push_str(&format!("![Snapshot {}]({}/screenshot.png)\n\n", snapshot.number, relative));
+            report.push_str("| Mode | Cells |\n|---|---|\n");
+            for (mode, count) in &snapshot.stats.cells_per_mode {
+                report.push_str(&format!("| {} | {} |\n", mode, count));
+            }
+        }
+    }
+
+    if !record.genome_saves.is_empty() {
+        report.push_str("\n## Genome saves\n\n");
+        for path in &record.genome_saves {
+            let relative = path.strip_prefix(&record.dir).unwrap_or(path);
+            report.push_str(&format!("- {}\n", relative.to_string_lossy().replace('\\', "/")));
+        }
+    }
+
+    report
+}
+
+/// Session or file name with anything but letters, digits, '-' and '_' replaced by '_'
+fn sanitize_name(name: &str) -> String {
+    let sanitized: String = name.trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() { "session".to_string() } else { sanitized }
+}
+
+/// `root/folder`, or `root/folder-2`, `-3`, ... if it already exists
+fn unique_dir(root: &Path, folder: &str) -> PathBuf {
+    let mut dir = root.join(folder);
+    let mut suffix = 2;
+    while dir.exists() {
+        dir = root.join(format!("{}-{}", folder, suffix));
+        suffix += 1;
+    }
+    dir
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// `YYYY-MM-DD HH:MM` (UTC) for seconds since the Unix epoch
+pub fn format_utc(unix_seconds: u64) -> String {
+    // Civil-from-days (Howard Hinnant), valid for any date after 1970
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year, month, day, seconds_of_day / 3600, seconds_of_day % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_utc_dates() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00");
+        assert_eq!(format_utc(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_utc(1_767_225_599), "2025-12-31 23:59");
+    }
+
+    #[test]
+    fn test_sanitize_name_keeps_folder_safe_characters() {
+        assert_eq!(sanitize_name("  stiff bonds / v2 "), "stiff_bonds___v2");
+        assert_eq!(sanitize_name("run-3_b"), "run-3_b");
+        assert_eq!(sanitize_name("   "), "session");
+    }
+
+    #[test]
+    fn test_snapshot_scene_round_trips_cells_and_stats() {
+        let genome = GenomeData::default();
+        let physics = PhysicsConfig::default();
+        let mut state = CanonicalState::new(8);
+        for (x, mass) in [(0.0, 1.0), (2.0, 1.5)] {
+            state.add_cell(Vec3::X * x, Vec3::Y, Quat::IDENTITY, Vec3::ZERO, mass, 1.0,
+                0, 0, 0.0, 5.0, 2.0, physics.default_stiffness, Quat::IDENTITY, 0);
+        }
+
+        let scene = SceneFile::from_state(&state, &genome, &physics, None, 7, String::new());
+        assert!(scene.validate().is_empty(), "{:?}", scene.validate());
+        let initial = scene.to_initial_state(8);
+        assert_eq!(initial.initial_cells.len(), 2);
+        assert_eq!(initial.initial_cells[1].position, Vec3::X * 2.0);
+        assert_eq!(initial.initial_cells[1].mass, 1.5);
+        assert_eq!(initial.initial_cells[1].velocity, Vec3::Y);
+
+        let stats = SnapshotStats::of(&state, &genome, 1.0, 64);
+        assert_eq!((stats.cell_count, stats.adhesion_count), (2, 0));
+        assert_eq!(stats.total_mass, 2.5);
+        assert_eq!(stats.cells_per_mode, vec![(genome.modes[0].name.clone(), 2)]);
+        assert!(stats.fingerprint.ends_with("@64"));
+    }
+
+    #[test]
+    fn test_report_lists_snapshots_and_genome_saves() {
+        let dir = PathBuf::from("sessions/run-2026-01-01");
+        let stats = SnapshotStats {
+            tick: 640,
+            time: 10.0,
+            cell_count: 12,
+            adhesion_count: 11,
+            total_mass: 18.0,
+            mean_radius: 1.1,
+            cells_per_mode: vec![("Stem".to_string(), 12)],
+            fingerprint: "00000000deadbeef@640".to_string(),
+        };
+        let record = SessionRecord {
+            name: "run".to_string(),
+            dir: dir.clone(),
+            started: "2026-01-01 09:00".to_string(),
+            snapshots: vec![SessionSnapshot {
+                number: 1,
+                dir: dir.join("snapshots").join("001-t640"),
+                stats,
+                thumbnail: None,
+            }],
+            genome_saves: vec![dir.join("genomes").join("001-stem.json")],
+            ended: true,
+        };
+
+        let report = session_report(&record, "2026-01-01 10:00");
+        assert!(report.starts_with("# Session: run\n"));
+        assert!(report.contains("| 1 | 10.00 | 640 | 12 | 11 | 18.00 | 1.100 | `00000000deadbeef@640` |"));
+        assert!(report.contains("![Snapshot 1](snapshots/001-t640/screenshot.png)"));
+        assert!(report.contains("| Stem | 12 |"));
+        assert!(report.contains("- genomes/001-stem.json"));
+    }
+}
@@ -0,0 +1,425 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::scene_file::SceneFile;
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Plugin for the live simulation fingerprint and the fingerprint trace
+pub struct FingerprintPlugin;
+
+impl Plugin for FingerprintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationFingerprint>()
+            .add_systems(Update, update_fingerprint);
+    }
+}
+
+/// Floats are rounded to this many steps per unit before hashing
+const QUANTA_PER_UNIT: f64 = 1e4;
+
+/// Cell capacity for headless runs (the Preview mode limit)
+pub const HEADLESS_CAPACITY: usize = 256;
+
+/// 64-bit FNV-1a
+///
+/// Unlike DefaultHasher its output is fixed across platforms and Rust versions,
+/// so fingerprints can be compared between machines and stored in tests.
+pub(crate) struct StableHasher(pub(crate) u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_quantized(&mut self, value: f32) {
+        // NaN saturates to 0 rather than poisoning the hash
+        self.write(&((value as f64 * QUANTA_PER_UNIT).round() as i64).to_le_bytes());
+    }
+}
+
+impl CanonicalState {
+    /// Deterministic hash of the simulation outcome
+    ///
+    /// Covers the cell count, quantized positions, rotations and masses, mode indices
+    /// (in cell index order), the active adhesion table and any nonzero user data. Two runs with the same
+    /// fingerprint at the same tick produced the same organism to within 1e-4.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(self.cell_count as u64);
+
+        for i in 0..self.cell_count {
+            let position = self.positions[i];
+            let rotation = self.rotations[i];
+            for value in [position.x, position.y, position.z, rotation.x, rotation.y, rotation.z, rotation.w, self.masses[i]] {
+                hasher.write_quantized(value);
+            }
+            hasher.write_u64(self.mode_indices[i] as u64);
+        }
+
+        let adhesions = &self.adhesion_connections;
+        hasher.write_u64(adhesions.active_count as u64);
+        for slot in 0..adhesions.is_active.len() {
+            if adhesions.is_active[slot] == 0 {
+                continue;
+            }
+            hasher.write_u64(slot as u64);
+            hasher.write_u64(adhesions.cell_a_index[slot] as u64);
+            hasher.write_u64(adhesions.cell_b_index[slot] as u64);
+            hasher.write_u64(adhesions.mode_index[slot] as u64);
+        }
+
+        // Untouched user data leaves the hash as it was before the channels existed,
+        // so fingerprints recorded by older builds still match
+        let live_user_data = || self.user_data.iter().flat_map(|channel| &channel[..self.cell_count]);
+        if live_user_data().any(|&value| value != 0.0) {
+            for &value in live_user_data() {
+                hasher.write_quantized(value);
+            }
+        }
+
+        hasher.0
+    }
+}
+
+impl PhysicsConfig {
+    /// Deterministic hash of the settings that affect simulation outcomes
+    ///
+    /// Leaves out the collision pair backend, its validation and the playback tick rate,
+    /// which change how fast a run goes but not where it ends up.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        if let Ok(serde_json::Value::Object(mut fields)) = serde_json::to_value(self) {
+            for speed_only in ["gpu_pair_detection", "gpu_pair_validation", "ticks_per_second_override"] {
+                fields.remove(speed_only);
+            }
+            // serde_json maps are ordered by key, so the text is stable
+            hasher.write(serde_json::Value::Object(fields).to_string().as_bytes());
+        }
+        hasher.0
+    }
+}
+
+/// Fingerprint of a run: the simulation outcome combined with the physics config that produced it
+///
+/// Runs under different configs never compare equal, even where their states happen to match.
+pub fn run_fingerprint(state: &CanonicalState, config: &PhysicsConfig) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_u64(state.fingerprint());
+    hasher.write_u64(config.fingerprint());
+    hasher.0
+}
+
+/// Run a scene for `ticks` ticks the way Preview mode does, without rendering
+pub fn simulate_scene(scene: &SceneFile, ticks: u32) -> CanonicalState {
+    let mut state = scene.to_initial_state(HEADLESS_CAPACITY).to_canonical_state();
+    let fixed_timestep = scene.physics.fixed_timestep;
+    let mut phases = crate::genome::PhasedGenome::default();
+    for tick in 0..ticks {
+        let current_time = tick as f32 * fixed_timestep;
+        // Genome edits recorded with the run reach it at the start of their tick
+        let genome = scene.interventions.genome_at(&scene.genome, tick);
+        phases.update(genome, state.division_stats.divisions, current_time);
+        let genome = phases.genome(genome);
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &scene.physics, genome, current_time, false);
+        let max_cells = state.capacity;
+        crate::simulation::cpu_physics::division_step(
+            &mut state,
+            genome,
+            current_time,
+            fixed_timestep,
+            max_cells,
+            scene.rng_seed,
+        );
+    }
+    state
+}
+
+/// Expected fingerprint at a tick, written `<hex>@<tick>` on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FingerprintExpectation {
+    pub fingerprint: u64,
+    pub tick: u32,
+}
+
+impl std::str::FromStr for FingerprintExpectation {
+    type Err = crate::error::BioSpheresError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |problem: String| crate::error::BioSpheresError::Validation {
+            what: "fingerprint expectation",
+            problems: vec![problem],
+        };
+        let (hex, tick) = s.split_once('@')
+            .ok_or_else(|| invalid(format!("expected <hex>@<tick>, got '{}'", s)))?;
+        let hex = hex.trim_start_matches("0x");
+        let fingerprint = u64::from_str_radix(hex, 16)
+            .map_err(|e| invalid(format!("'{}' is not a hex fingerprint: {}", hex, e)))?;
+        let tick = tick.parse()
+            .map_err(|e| invalid(format!("'{}' is not a tick: {}", tick, e)))?;
+        Ok(Self { fingerprint, tick })
+    }
+}
+
+/// Scene for a headless run: a bundled preset ID, a scene file path, or the default genome
+fn load_cli_scene(scene: Option<&str>) -> Result<SceneFile, String> {
+    let Some(scene) = scene else {
+        return Ok(SceneFile {
+            format_version: crate::simulation::scene_file::SCENE_FORMAT_VERSION,
+            description: String::new(),
+            genome: crate::genome::GenomeData::default(),
+            physics: PhysicsConfig::default(),
+            physics_overrides: None,
+            initial_cells: Vec::new(),
+            camera: None,
+            rng_seed: 0,
+            groups: Vec::new(),
+            regions: None,
+            interventions: Default::default(),
+        });
+    };
+
+    for (id, result) in crate::simulation::scenario_presets::load_bundled_presets() {
+        if id == scene {
+            return result.map(|preset| preset.scene);
+        }
+    }
+    SceneFile::load_from_file(std::path::Path::new(scene)).map_err(|e| e.to_string())
+}
+
+/// Headless physics regression gate
+///
+/// `--expect-fingerprint <hex>@<tick> [--scene <preset id | scene file>] [--strict-math]`
+/// simulates the scene (the default genome if omitted) to the tick and compares
+/// fingerprints; `--strict-math` turns on strict reproducibility for the run. Returns
+/// the process exit code (0 match, 1 mismatch, 2 bad arguments), or None when the flag
+/// is absent and the app should start normally.
+pub fn run_cli(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let mut expectation = None;
+    let mut scene = None;
+    let mut strict_math = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--expect-fingerprint" => expectation = Some(args.next().unwrap_or_default()),
+            "--scene" => scene = args.next(),
+            "--strict-math" => strict_math = true,
+            _ => {}
+        }
+    }
+    let expectation = expectation?;
+
+    let expectation: FingerprintExpectation = match expectation.parse() {
+        Ok(expectation) => expectation,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Some(2);
+        }
+    };
+    let mut scene = match load_cli_scene(scene.as_deref()) {
+        Ok(scene) => scene,
+        Err(e) => {
+            eprintln!("Could not load scene: {}", e);
+            return Some(2);
+        }
+    };
+    scene.physics.strict_reproducibility |= strict_math;
+
+    // Recorded for reproduction; the scene file itself is never rewritten by the gate
+    println!("Seed {}, genome {:016x}", scene.rng_seed, scene.genome.content_hash());
+    let state = simulate_scene(&scene, expectation.tick);
+    let actual = run_fingerprint(&state, &scene.physics);
+    if actual == expectation.fingerprint {
+        println!("Fingerprint {:016x}@{} matches ({} cells)", actual, expectation.tick, state.cell_count);
+        Some(0)
+    } else {
+        eprintln!(
+            "Fingerprint mismatch at tick {}: expected {:016x}, got {:016x} ({} cells)",
+            expectation.tick, expectation.fingerprint, actual, state.cell_count,
+        );
+        Some(1)
+    }
+}
+
+/// Fingerprint of the active simulation and the fingerprint trace setting
+#[derive(Resource)]
+pub struct SimulationFingerprint {
+    /// (tick, run fingerprint) of the active simulation (refreshed every frame)
+    pub current: Option<(u32, u64)>,
+    /// Log the fingerprint every `trace_interval` ticks
+    pub trace_enabled: bool,
+    pub trace_interval: u32,
+    last_traced_tick: Option<u32>,
+}
+
+impl Default for SimulationFingerprint {
+    fn default() -> Self {
+        Self {
+            current: None,
+            trace_enabled: false,
+            trace_interval: 100,
+            last_traced_tick: None,
+        }
+    }
+}
+
+fn update_fingerprint(
+    mut fingerprint: ResMut<SimulationFingerprint>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    config: Res<PhysicsConfig>,
+) {
+    let active = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|s| (&s.canonical_state, s.simulation_time)),
+        SimulationMode::Preview => preview_state.as_deref().map(|s| (&s.canonical_state, s.current_time)),
+        SimulationMode::Gpu => None,
+    };
+    let Some((state, time)) = active else {
+        fingerprint.current = None;
+        return;
+    };
+
+    let tick = crate::simulation::clock::ticks_to_reach(time, config.fixed_timestep);
+    let value = run_fingerprint(state, &config);
+    fingerprint.current = Some((tick, value));
+
+    // Several ticks can run per frame, so log once each time the tick crosses a multiple of the interval
+    let interval = fingerprint.trace_interval.max(1);
+    if fingerprint.trace_enabled
+        && fingerprint.last_traced_tick.is_none_or(|last| tick / interval != last / interval)
+    {
+        info!("Fingerprint {:016x}@{} ({} cells)", value, tick, state.cell_count);
+        fingerprint.last_traced_tick = Some(tick);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset_scene(id: &str) -> SceneFile {
+        crate::simulation::scenario_presets::load_bundled_presets()
+            .into_iter()
+            .find(|(preset_id, _)| preset_id == id)
+            .and_then(|(_, result)| result.ok())
+            .map(|preset| preset.scene)
+            .expect("bundled preset")
+    }
+
+    /// Known run fingerprints at tick 1000
+    ///
+    /// Update these on purpose when a physics change is meant to alter outcomes:
+    /// `cargo run -- --expect-fingerprint 0@1000 --scene <preset>` prints the new value.
+    const FINGERPRINT_FIXTURES: &[(&str, u32, u64)] = &[
+        ("mitosis_basics", 1000, 0x03bf3007f6e3eb54),
+        ("branching_colony", 1000, 0x5dab4712d6807d64),
+    ];
+
+    #[test]
+    fn test_bundled_preset_fingerprints_match_fixtures() {
+        for &(id, tick, expected) in FINGERPRINT_FIXTURES {
+            let scene = preset_scene(id);
+            let actual = run_fingerprint(&simulate_scene(&scene, tick), &scene.physics);
+            assert_eq!(actual, expected, "{} at tick {}: got {:016x}", id, tick, actual);
+        }
+    }
+
+    /// Known strict-reproducibility fingerprints at tick 1000
+    ///
+    /// These must hold on every platform and build: CI runs this test with default flags and
+    /// again with `RUSTFLAGS="-C target-feature=+fma,+avx2"` (both with `--include-ignored`).
+    /// `cargo run -- --expect-fingerprint 0@1000 --scene <preset> --strict-math` prints the new value.
+    const STRICT_FINGERPRINT_FIXTURES: &[(&str, u32, u64)] = &[
+        ("mitosis_basics", 1000, 0),
+        ("branching_colony", 1000, 0),
+    ];
+
+    #[test]
+    #[ignore = "strict fixture fingerprints have not been recorded yet"]
+    fn test_strict_fingerprints_match_fixtures() {
+        for &(id, tick, expected) in STRICT_FINGERPRINT_FIXTURES {
+            let mut scene = preset_scene(id);
+            scene.physics.strict_reproducibility = true;
+            let actual = run_fingerprint(&simulate_scene(&scene, tick), &scene.physics);
+            assert_eq!(actual, expected, "{} at tick {} (strict): got {:016x}", id, tick, actual);
+        }
+    }
+
+    #[test]
+    fn test_strict_math_is_reproducible_and_close_to_native() {
+        let scene = preset_scene("branching_colony");
+        let mut strict = scene.clone();
+        strict.physics.strict_reproducibility = true;
+
+        let native_state = simulate_scene(&scene, 100);
+        let strict_state = simulate_scene(&strict, 100);
+        assert_eq!(strict_state.fingerprint(), simulate_scene(&strict, 100).fingerprint());
+        assert_ne!(run_fingerprint(&strict_state, &strict.physics), run_fingerprint(&strict_state, &scene.physics));
+
+        // Software math differs from libm by a few ulps, not by outcome
+        assert_eq!(strict_state.cell_count, native_state.cell_count);
+        for i in 0..native_state.cell_count {
+            let drift = (strict_state.positions[i] - native_state.positions[i]).length();
+            assert!(drift < 1e-2, "cell {} drifted {} from the native run", i, drift);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_is_reproducible_and_tracks_outcomes() {
+        for &(id, _, _) in FINGERPRINT_FIXTURES {
+            let scene = preset_scene(id);
+            let first = simulate_scene(&scene, 200);
+            let second = simulate_scene(&scene, 200);
+            assert_eq!(first.fingerprint(), second.fingerprint(), "{} is not reproducible", id);
+
+            // Noise below the quantum is ignored, a real displacement is not
+            let mut nudged = first.clone();
+            nudged.positions[0].x = 0.5;
+            let base = nudged.fingerprint();
+            nudged.positions[0].x += 1e-6;
+            assert_eq!(nudged.fingerprint(), base);
+            nudged.positions[0].x += 1e-3;
+            assert_ne!(nudged.fingerprint(), base);
+
+            // User data counts once written
+            let moved = nudged.fingerprint();
+            nudged.user_data[2][0] = 0.25;
+            assert_ne!(nudged.fingerprint(), moved);
+        }
+    }
+
+    #[test]
+    fn test_run_fingerprint_includes_the_physics_config() {
+        let scene = preset_scene("mitosis_basics");
+        let state = simulate_scene(&scene, 50);
+        let base = run_fingerprint(&state, &scene.physics);
+
+        let mut faster = scene.physics.clone();
+        faster.gpu_pair_detection = !faster.gpu_pair_detection;
+        faster.ticks_per_second_override = Some(10.0);
+        assert_eq!(run_fingerprint(&state, &faster), base, "speed-only settings don't count");
+
+        let mut damped = scene.physics.clone();
+        damped.velocity_damping *= 0.5;
+        assert_ne!(run_fingerprint(&state, &damped), base);
+    }
+
+    #[test]
+    fn test_expectation_parses_hex_at_tick() {
+        let expectation: FingerprintExpectation = "0x00ff10@1000".parse().unwrap();
+        assert_eq!(expectation, FingerprintExpectation { fingerprint: 0xff10, tick: 1000 });
+        assert!("ff10".parse::<FingerprintExpectation>().is_err());
+        assert!("xyz@10".parse::<FingerprintExpectation>().is_err());
+        assert_eq!(run_cli(["--scene".to_string(), "mitosis_basics".to_string()]), None);
+    }
+}
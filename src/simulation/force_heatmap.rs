@@ -0,0 +1,406 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::preview_sim::PreviewSimState;
+
+/// Plugin passing the force heatmap settings to the simulations
+pub struct ForceHeatmapPlugin;
+
+impl Plugin for ForceHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ForceHeatmap>()
+            .add_systems(Update, sync_force_heatmap);
+    }
+}
+
+/// Force accumulated by the heatmap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeatChannel {
+    /// Pushes from touching cells of other organisms
+    Collision,
+    /// Pulls and twists from adhesion bonds
+    Adhesion,
+}
+
+impl HeatChannel {
+    pub const ALL: [HeatChannel; 2] = [HeatChannel::Collision, HeatChannel::Adhesion];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HeatChannel::Collision => "Collision",
+            HeatChannel::Adhesion => "Adhesion",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Overlay and legend color of a normalized average, sRGB in 0..1
+    ///
+    /// Collision runs dark purple -> red-orange -> yellow, adhesion dark teal -> teal -> mint,
+    /// so the two stay apart when both are drawn.
+    pub fn color(self, t: f32) -> [f32; 3] {
+        let stops: [[f32; 3]; 3] = match self {
+            HeatChannel::Collision => [[0.2, 0.05, 0.3], [0.95, 0.3, 0.1], [1.0, 0.95, 0.3]],
+            HeatChannel::Adhesion => [[0.02, 0.2, 0.25], [0.1, 0.65, 0.65], [0.7, 1.0, 0.8]],
+        };
+        let t = t.clamp(0.0, 1.0) * 2.0;
+        let (from, to, t) = if t < 1.0 { (stops[0], stops[1], t) } else { (stops[1], stops[2], t - 1.0) };
+        std::array::from_fn(|i| from[i] + (to[i] - from[i]) * t)
+    }
+}
+
+/// How the physics step accumulates the heatmap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeatmapParams {
+    pub enabled: bool,
+    /// Simulated seconds for the average to cover ~63% of a step change
+    pub time_constant: f32,
+    /// Keep the averages as they are while the simulation goes on
+    pub frozen: bool,
+}
+
+impl Default for HeatmapParams {
+    fn default() -> Self {
+        Self { enabled: false, time_constant: 2.0, frozen: false }
+    }
+}
+
+/// Per-cell moving averages of force magnitudes, kept in the canonical state
+///
+/// The force passes add each contact's and bond's force magnitude to `totals`, in
+/// the same order in the single and multithreaded paths (the parallel paths add them
+/// in their sequential merge), and `update_force_heat` folds the totals into the
+/// averages once per tick. Columns stay empty until accumulation is first enabled.
+#[derive(Clone, Default)]
+pub struct ForceHeat {
+    pub params: HeatmapParams,
+    /// Exponential moving averages per channel, by cell slot
+    pub averages: [Vec<f32>; 2],
+    /// This tick's summed force magnitudes per channel, by cell slot
+    pub(crate) totals: [Vec<f32>; 2],
+}
+
+impl ForceHeat {
+    /// Whether the force passes record magnitudes this tick
+    #[inline]
+    pub fn accumulating(&self) -> bool {
+        self.params.enabled && !self.params.frozen
+    }
+
+    /// Averaged magnitude of a cell (0 before anything was accumulated)
+    pub fn average(&self, channel: HeatChannel, idx: usize) -> f32 {
+        self.averages[channel.index()].get(idx).copied().unwrap_or(0.0)
+    }
+
+    /// Zero every average
+    pub fn reset(&mut self) {
+        for column in &mut self.averages {
+            column.fill(0.0);
+        }
+    }
+}
+
+/// Weight of the newest value in a moving average with time constant `tau`, updated every `dt`
+///
+/// Matches the continuous low-pass filter exactly at the sample times, so a constant
+/// input reaches 1 - 1/e of its value after `tau` seconds whatever the timestep.
+pub fn ema_weight(dt: f32, tau: f32) -> f32 {
+    if tau <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-dt / tau).exp()
+}
+
+impl CanonicalState {
+    /// Clear the collision totals before a collision force pass
+    pub(crate) fn begin_collision_heat(&mut self) {
+        if self.force_heat.accumulating() {
+            let totals = &mut self.force_heat.totals[HeatChannel::Collision.index()];
+            totals.clear();
+            totals.resize(self.cell_count, 0.0);
+        }
+    }
+
+    /// Add a contact's force magnitude to a cell's collision total
+    #[inline]
+    pub(crate) fn add_collision_heat(&mut self, idx: usize, magnitude: f32) {
+        if let Some(total) = self.force_heat.totals[HeatChannel::Collision.index()].get_mut(idx) {
+            *total += magnitude;
+        }
+    }
+
+    /// Fold this tick's force magnitudes into the averages; call after the force passes
+    ///
+    /// Adhesion magnitudes are summed here from the per-connection values left by the
+    /// adhesion pass, in connection order, outside any parallel region.
+    pub fn update_force_heat(&mut self, dt: f32) {
+        if !self.force_heat.accumulating() {
+            return;
+        }
+        let count = self.cell_count;
+        let connections = &self.adhesion_connections;
+        let heat = &mut self.force_heat;
+
+        let adhesion = &mut heat.totals[HeatChannel::Adhesion.index()];
+        adhesion.clear();
+        adhesion.resize(count, 0.0);
+        for (i, magnitudes) in connections.force_magnitudes.iter().enumerate().take(connections.active_count) {
+            if connections.is_active[i] == 0 {
+                continue;
+            }
+            let (a, b) = (connections.cell_a_index[i], connections.cell_b_index[i]);
+            if a < count && b < count {
+                adhesion[a] += magnitudes[0];
+                adhesion[b] += magnitudes[1];
+            }
+        }
+
+        let weight = ema_weight(dt, heat.params.time_constant);
+        for (averages, totals) in heat.averages.iter_mut().zip(&mut heat.totals) {
+            // A tick without a collision pass (collisions off) adds nothing
+            totals.resize(count, 0.0);
+            averages.resize(count.max(averages.len()), 0.0);
+            for (average, &total) in averages[..count].iter_mut().zip(totals.iter()) {
+                *average += weight * (total - *average);
+            }
+            totals.clear();
+        }
+    }
+
+    /// Give slot `to` the averages of slot `from` (cell removal and division)
+    pub(crate) fn copy_force_heat(&mut self, from: usize, to: usize) {
+        for column in &mut self.force_heat.averages {
+            if column.is_empty() {
+                continue;
+            }
+            if column.len() <= to {
+                column.resize(to + 1, 0.0);
+            }
+            column[to] = column.get(from).copied().unwrap_or(0.0);
+        }
+    }
+
+    /// Zero the averages of a freshly used slot
+    pub(crate) fn clear_force_heat(&mut self, idx: usize) {
+        for column in &mut self.force_heat.averages {
+            if let Some(value) = column.get_mut(idx) {
+                *value = 0.0;
+            }
+        }
+    }
+}
+
+/// Force heatmap overlay settings and the freeze and reset controls
+#[derive(Resource)]
+pub struct ForceHeatmap {
+    /// Channels drawn; accumulation runs while either is on
+    pub show: [bool; 2],
+    pub time_constant: f32,
+    pub frozen: bool,
+    /// Zero the averages of every scene on the next frame
+    pub reset_requested: bool,
+    /// Largest average per channel in the active scene, the top of the legend
+    /// (written by the overlay)
+    pub scale: [f32; 2],
+}
+
+impl Default for ForceHeatmap {
+    fn default() -> Self {
+        Self {
+            show: [false; 2],
+            time_constant: HeatmapParams::default().time_constant,
+            frozen: false,
+            reset_requested: false,
+            scale: [0.0; 2],
+        }
+    }
+}
+
+impl ForceHeatmap {
+    pub fn shown(&self, channel: HeatChannel) -> bool {
+        self.show[channel.index()]
+    }
+
+    pub fn params(&self) -> HeatmapParams {
+        HeatmapParams {
+            enabled: self.show.iter().any(|&shown| shown),
+            time_constant: self.time_constant,
+            frozen: self.frozen,
+        }
+    }
+}
+
+/// Copy the settings into the Main and Preview states and apply a reset
+fn sync_force_heatmap(
+    mut heatmap: ResMut<ForceHeatmap>,
+    main_state: Option<ResMut<MainSimState>>,
+    preview_state: Option<ResMut<PreviewSimState>>,
+) {
+    let params = heatmap.params();
+    let reset = heatmap.reset_requested;
+    if reset {
+        heatmap.reset_requested = false;
+    }
+    let apply = |state: &mut CanonicalState| {
+        state.force_heat.params = params;
+        if reset {
+            state.force_heat.reset();
+        }
+    };
+    // Compared through a shared borrow so an unchanged state isn't flagged as changed
+    if let Some(mut main) = main_state {
+        if main.canonical_state.force_heat.params != params || reset {
+            apply(&mut main.canonical_state);
+        }
+    }
+    if let Some(mut preview) = preview_state {
+        if preview.canonical_state.force_heat.params != params || reset {
+            apply(&mut preview.canonical_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::cpu_physics::{
+        compute_collision_forces_canonical, compute_collision_forces_canonical_st, physics_step_st_with_genome,
+        CanonicalCollisionPair,
+    };
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::PhysicsConfig;
+
+    /// Two overlapping pinned cells: the overlap, and so the collision force, never changes
+    fn pinned_pair(time_constant: f32) -> CanonicalState {
+        let mut state = CanonicalState::new(4);
+        for x in [0.0, 1.5] {
+            add_test_cell(&mut state, Vec3::new(x, 0.0, 0.0), Quat::IDENTITY, 1.0, 1.0, 0);
+        }
+        state.set_pinned(0, true);
+        state.set_pinned(1, true);
+        state.force_heat.params = HeatmapParams { enabled: true, time_constant, frozen: false };
+        state
+    }
+
+    #[test]
+    fn test_average_converges_to_a_constant_force_within_the_time_constant() {
+        let config = PhysicsConfig::default();
+        // Growing cells would push harder every tick
+        let genome = never_split_genome(&["Pinned"]);
+        let tau = 0.5;
+        let mut state = pinned_pair(tau);
+        let ticks = |seconds: f32| (seconds / config.fixed_timestep).round() as usize;
+        let step = |state: &mut CanonicalState, tick: usize| {
+            physics_step_st_with_genome(state, &config, &genome, tick as f32 * config.fixed_timestep);
+        };
+
+        // The first tick takes one weight of the steady state, the constant per-tick magnitude
+        step(&mut state, 0);
+        let force = state.force_heat.average(HeatChannel::Collision, 0) / ema_weight(config.fixed_timestep, tau);
+        assert!(force > 0.0);
+        assert_eq!(state.force_heat.average(HeatChannel::Collision, 1), state.force_heat.average(HeatChannel::Collision, 0));
+
+        for tick in 1..ticks(tau) {
+            step(&mut state, tick);
+        }
+        // One time constant: 1 - 1/e of the way there
+        let at_tau = state.force_heat.average(HeatChannel::Collision, 0) / force;
+        assert!((at_tau - (1.0 - (-1.0f32).exp())).abs() < 0.01, "{} after one time constant", at_tau);
+
+        for tick in ticks(tau)..ticks(5.0 * tau) {
+            step(&mut state, tick);
+        }
+        let at_five = state.force_heat.average(HeatChannel::Collision, 1) / force;
+        assert!((at_five - 1.0).abs() < 0.01, "{} after five time constants", at_five);
+        assert_eq!(state.force_heat.average(HeatChannel::Adhesion, 0), 0.0);
+    }
+
+    #[test]
+    fn test_single_and_multithreaded_passes_accumulate_identically() {
+        let config = PhysicsConfig::default();
+        let build = || {
+            let mut state = CanonicalState::new(16);
+            for i in 0..8 {
+                let position = Vec3::new((i % 4) as f32 * 1.6, (i / 4) as f32 * 1.7, (i % 3) as f32 * 0.3);
+                state.add_cell(position, Vec3::new(0.1 * i as f32, 0.0, -0.05), Quat::IDENTITY, Vec3::ZERO,
+                    1.0, 1.0, 0, 0, 0.0, 60.0, 10.0, config.default_stiffness, Quat::IDENTITY, 0);
+            }
+            state.force_heat.params = HeatmapParams { enabled: true, time_constant: 0.25, frozen: false };
+            state
+        };
+        // Every touching pair, with a cell sharing several contacts
+        let probe = build();
+        let mut pairs = Vec::new();
+        for a in 0..probe.cell_count {
+            for b in a + 1..probe.cell_count {
+                let delta = probe.positions[b] - probe.positions[a];
+                let overlap = probe.radii[a] + probe.radii[b] - delta.length();
+                if overlap > 0.0 {
+                    pairs.push(CanonicalCollisionPair { index_a: a, index_b: b, overlap, normal: delta.normalize() });
+                }
+            }
+        }
+        assert!(pairs.len() > 2);
+
+        let (mut single, mut multi) = (build(), build());
+        for _ in 0..5 {
+            compute_collision_forces_canonical_st(&mut single, &pairs, &config);
+            single.update_force_heat(config.fixed_timestep);
+            compute_collision_forces_canonical(&mut multi, &pairs, &config);
+            multi.update_force_heat(config.fixed_timestep);
+        }
+
+        let bits = |state: &CanonicalState| state.force_heat.averages.iter()
+            .flat_map(|column| column[..state.cell_count].iter().map(|value| value.to_bits()))
+            .collect::<Vec<_>>();
+        assert!(single.force_heat.averages[0].iter().any(|&value| value > 0.0));
+        assert_eq!(bits(&single), bits(&multi));
+    }
+
+    #[test]
+    fn test_frozen_averages_hold_and_reset_zeroes_them() {
+        let config = PhysicsConfig::default();
+        let genome = GenomeData::default();
+        let mut state = pinned_pair(0.5);
+        for tick in 0..10 {
+            physics_step_st_with_genome(&mut state, &config, &genome, tick as f32 * config.fixed_timestep);
+        }
+        let held = state.force_heat.average(HeatChannel::Collision, 0);
+        assert!(held > 0.0);
+
+        state.force_heat.params.frozen = true;
+        for tick in 10..20 {
+            physics_step_st_with_genome(&mut state, &config, &genome, tick as f32 * config.fixed_timestep);
+        }
+        assert_eq!(state.force_heat.average(HeatChannel::Collision, 0), held);
+
+        state.force_heat.reset();
+        assert_eq!(state.force_heat.average(HeatChannel::Collision, 0), 0.0);
+    }
+
+    #[test]
+    fn test_palettes_span_their_stops_and_differ() {
+        for channel in HeatChannel::ALL {
+            assert_eq!(channel.color(-1.0), channel.color(0.0));
+            assert_eq!(channel.color(2.0), channel.color(1.0));
+        }
+        assert_eq!(HeatChannel::Collision.color(1.0), [1.0, 0.95, 0.3]);
+        assert_ne!(HeatChannel::Collision.color(0.5), HeatChannel::Adhesion.color(0.5));
+    }
+
+    #[test]
+    fn test_averages_follow_cells_through_removal() {
+        let mut state = pinned_pair(0.5);
+        state.force_heat.averages = [vec![1.0, 2.0], vec![3.0, 4.0]];
+        state.copy_force_heat(1, 0);
+        assert_eq!(state.force_heat.average(HeatChannel::Collision, 0), 2.0);
+        assert_eq!(state.force_heat.average(HeatChannel::Adhesion, 0), 4.0);
+        state.copy_force_heat(0, 3);
+        assert_eq!(state.force_heat.average(HeatChannel::Adhesion, 3), 4.0);
+        state.clear_force_heat(3);
+        assert_eq!(state.force_heat.average(HeatChannel::Adhesion, 3), 0.0);
+    }
+}
@@ -0,0 +1,403 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::cpu_physics::{CanonicalState, DivisionReadiness};
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin that holds genome edits back from long CPU runs until they are applied
+pub struct GenomeStagingPlugin;
+
+impl Plugin for GenomeStagingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GenomeStaging>()
+            .add_systems(Update, update_genome_staging);
+    }
+}
+
+/// Simulated time after which CPU-mode genome edits are staged by default (seconds)
+pub const DEFAULT_PROTECT_AFTER: f32 = 600.0;
+
+/// Genome edits applied to a CPU run, recorded so a replay applies them at the same tick
+///
+/// Recorded in the run's `RunInterventions` by `run_main_simulation`.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct StagedApplication {
+    /// The genome is in effect from the start of this tick
+    pub tick: u32,
+    pub genome: GenomeData,
+}
+
+/// One difference between the genome a run uses and the edited genome
+#[derive(Clone, Debug, PartialEq)]
+pub struct StagedChange {
+    /// Mode the change is in (None for genome-wide settings)
+    pub mode: Option<usize>,
+    pub field: String,
+}
+
+/// Genome edits waiting to reach a long CPU run
+///
+/// Once a CPU run is past `protect_after` simulated seconds the run keeps simulating the
+/// genome it had, while the editor goes on editing `CurrentGenome`. The difference between
+/// the two is the staged edit set, applied or discarded from the banner. Preview mode
+/// always uses the edited genome.
+#[derive(Resource)]
+pub struct GenomeStaging {
+    /// Stage edits to CPU runs past `protect_after`
+    pub enabled: bool,
+    /// Simulated seconds after which edits are staged
+    pub protect_after: f32,
+    /// Genome the CPU run simulates while edits are staged
+    running: Option<GenomeData>,
+    apply_requested: bool,
+    /// Apply at the first tick with no pending divisions
+    apply_at_lull: bool,
+    last_run_time: Option<f32>,
+}
+
+impl Default for GenomeStaging {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            protect_after: DEFAULT_PROTECT_AFTER,
+            running: None,
+            apply_requested: false,
+            apply_at_lull: false,
+            last_run_time: None,
+        }
+    }
+}
+
+impl GenomeStaging {
+    /// Whether edits are currently being staged
+    pub fn is_engaged(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Start staging: the run keeps `genome` until edits are applied
+    pub fn engage(&mut self, genome: &GenomeData) {
+        if self.running.is_none() {
+            self.running = Some(genome.clone());
+        }
+    }
+
+    /// Stop staging (a new or reloaded scene)
+    pub fn reset(&mut self) {
+        self.running = None;
+        self.apply_requested = false;
+        self.apply_at_lull = false;
+    }
+
+    /// Genome the CPU run simulates: the running copy while staging, otherwise the edited one
+    pub fn running_genome<'a>(&'a self, edited: &'a GenomeData) -> &'a GenomeData {
+        self.running.as_ref().unwrap_or(edited)
+    }
+
+    /// The running copy while staging, for mode inserts and removals that must reach it at once
+    ///
+    /// Cells are remapped when modes shift, so the running genome has to shift with them.
+    pub fn running_mut(&mut self) -> Option<&mut GenomeData> {
+        self.running.as_mut()
+    }
+
+    pub fn has_staged(&self, edited: &GenomeData) -> bool {
+        self.running.as_ref().is_some_and(|running| running != edited)
+    }
+
+    /// Staged edits, by mode and field
+    pub fn changes(&self, edited: &GenomeData) -> Vec<StagedChange> {
+        self.running.as_ref().map(|running| genome_changes(running, edited)).unwrap_or_default()
+    }
+
+    /// Apply the staged edits at the start of the next tick
+    pub fn request_apply(&mut self) {
+        self.apply_requested = true;
+    }
+
+    /// Apply the staged edits at the first tick with no pending divisions
+    pub fn request_apply_at_lull(&mut self) {
+        self.apply_at_lull = true;
+    }
+
+    pub fn cancel_apply_at_lull(&mut self) {
+        self.apply_at_lull = false;
+    }
+
+    pub fn waiting_for_lull(&self) -> bool {
+        self.apply_at_lull
+    }
+
+    /// Throw the staged edits away: the editor goes back to the running genome
+    pub fn discard(&mut self, edited: &mut GenomeData) {
+        if let Some(running) = &self.running {
+            *edited = running.clone();
+        }
+        self.apply_requested = false;
+        self.apply_at_lull = false;
+    }
+
+    /// Apply the staged edits if requested, at the start of a tick (before its physics step)
+    ///
+    /// `is_lull` is only asked when waiting for a division lull. Returns the number of
+    /// changes applied, if the edits were applied; the caller records the application.
+    pub fn apply_due(&mut self, edited: &GenomeData, is_lull: impl FnOnce(&GenomeData) -> bool) -> Option<usize> {
+        let running = self.running.as_ref()?;
+        if running == edited {
+            self.apply_requested = false;
+            self.apply_at_lull = false;
+            return None;
+        }
+        if !self.apply_requested && !(self.apply_at_lull && is_lull(running)) {
+            return None;
+        }
+
+        let changes = genome_changes(running, edited).len();
+        self.running = Some(edited.clone());
+        self.apply_requested = false;
+        self.apply_at_lull = false;
+        Some(changes)
+    }
+}
+
+/// Genome in effect at `tick` of a run that started with `base` and had `applications`
+pub fn replay_genome<'a>(base: &'a GenomeData, applications: &'a [StagedApplication], tick: u32) -> &'a GenomeData {
+    applications.iter()
+        .rev()
+        .find(|application| application.tick <= tick)
+        .map_or(base, |application| &application.genome)
+}
+
+/// Whether no cell would divide in the tick starting at `current_time`
+pub fn is_division_lull(state: &CanonicalState, genome: &GenomeData, current_time: f32) -> bool {
+    let compiled = state.compiled_genome.peek(genome).cloned()
+        .unwrap_or_else(|| std::sync::Arc::new(crate::genome::CompiledGenome::compile(genome)));
+    (0..state.cell_count).all(|i| state.division_readiness(&compiled, i, current_time) != DivisionReadiness::Ready)
+}
+
+/// Differences between two genomes, one entry per changed numeric field of a mode
+///
+/// Numeric fields are compared with the genome phase diff (`phases::numeric_overrides`);
+/// other mode changes (names, flags, enums) count once per mode.
+pub fn genome_changes(before: &GenomeData, after: &GenomeData) -> Vec<StagedChange> {
+    let mut changes = Vec::new();
+    let genome_wide = |field: &str| StagedChange { mode: None, field: field.to_string() };
+    if before.name != after.name {
+        changes.push(genome_wide("name"));
+    }
+    if before.initial_mode != after.initial_mode {
+        changes.push(genome_wide("initial_mode"));
+    }
+    if before.initial_orientation != after.initial_orientation {
+        changes.push(genome_wide("initial_orientation"));
+    }
+    if before.notes != after.notes {
+        changes.push(genome_wide("notes"));
+    }
+    if before.phases != after.phases {
+        changes.push(genome_wide("phases"));
+    }
+    if before.contact_adhesion != after.contact_adhesion {
+        changes.push(genome_wide("contact_adhesion"));
+    }
+
+    for index in 0..before.modes.len().max(after.modes.len()) {
+        let change = |field: String| StagedChange { mode: Some(index), field };
+        match (before.modes.get(index), after.modes.get(index)) {
+            (Some(old), Some(new)) if old != new => {
+                let numeric = crate::genome::phases::numeric_overrides(old, new);
+                if numeric.is_empty() {
+                    changes.push(change("settings".to_string()));
+                }
+                flatten_fields(&numeric, "", &mut |field| changes.push(change(field)));
+            }
+            (Some(_), None) => changes.push(change("removed".to_string())),
+            (None, Some(_)) => changes.push(change("added".to_string())),
+            _ => {}
+        }
+    }
+    changes
+}
+
+fn flatten_fields(values: &serde_json::Map<String, serde_json::Value>, prefix: &str, push: &mut impl FnMut(String)) {
+    for (key, value) in values {
+        let field = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            serde_json::Value::Object(nested) => flatten_fields(nested, &field, push),
+            _ => push(field),
+        }
+    }
+}
+
+/// Engage staging once a CPU run passes the protection time, and reset it for new scenes
+fn update_genome_staging(
+    mut staging: ResMut<GenomeStaging>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    genome: Res<CurrentGenome>,
+) {
+    let Some(main_state) = main_state else {
+        return;
+    };
+    // Time going backwards means a fresh or reloaded scene
+    let time = main_state.simulation_time;
+    if staging.last_run_time.replace(time).is_some_and(|last| time < last) {
+        staging.reset();
+    }
+
+    if !staging.enabled {
+        // Edits made while staging still reach the run as a recorded application
+        if staging.has_staged(&genome.genome) {
+            staging.request_apply();
+        } else if staging.is_engaged() {
+            staging.reset();
+        }
+        return;
+    }
+    if sim_state.mode == SimulationMode::Cpu && time >= staging.protect_after {
+        staging.engage(&genome.genome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::PhysicsConfig;
+    use crate::simulation::SceneFile;
+    use crate::simulation::scene_file::RunInterventions;
+
+    fn splitter() -> GenomeData {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 2.0;
+        GenomeData { modes: vec![mode], ..GenomeData::default() }
+    }
+
+    /// Run the way `run_main_simulation` does, staging from tick 0 and editing at tick 10
+    ///
+    /// `request` is called at every tick from the edit on. Returns the final state, the
+    /// staging resource and the recorded interventions.
+    fn run_staged(ticks: u32, request: impl Fn(u32, &mut GenomeStaging)) -> (CanonicalState, GenomeStaging, RunInterventions) {
+        let config = PhysicsConfig::default();
+        let dt = config.fixed_timestep;
+        let base = splitter();
+        let mut edited = base.clone();
+        let mut state = crate::simulation::parameter_sweep::initial_state(&base, &config, 256, 0);
+        let mut staging = GenomeStaging { protect_after: 0.0, ..GenomeStaging::default() };
+        staging.engage(&base);
+        let mut interventions = RunInterventions::default();
+
+        for tick in 0..ticks {
+            let time = tick as f32 * dt;
+            if tick == 10 {
+                edited.modes[0].split_interval = 0.5;
+                edited.modes[0].split_mass = 0.5;
+            }
+            if tick >= 10 {
+                request(tick, &mut staging);
+            }
+            staging.apply_due(&edited, |running| is_division_lull(&state, running, time));
+            let genome = staging.running_genome(&edited);
+            interventions.record_genome(&base, tick, genome);
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, genome, time, false);
+            crate::simulation::cpu_physics::division_step(&mut state, genome, time, dt, 256, 0);
+        }
+        (state, staging, interventions)
+    }
+
+    /// Replay the run from its saved scene, which carries the recorded interventions
+    fn replay(ticks: u32, interventions: &RunInterventions) -> CanonicalState {
+        let config = PhysicsConfig::default();
+        let base = splitter();
+        let start = crate::simulation::parameter_sweep::initial_state(&base, &config, 256, 0);
+        let mut scene = SceneFile::from_state(&start, &base, &config, None, 0, String::new());
+        scene.interventions = interventions.clone();
+        let saved = serde_json::to_string(&scene).unwrap();
+        let scene: SceneFile = serde_json::from_str(&saved).unwrap();
+        crate::simulation::fingerprint::simulate_scene(&scene, ticks)
+    }
+
+    #[test]
+    fn test_staged_edits_do_not_affect_physics_until_applied() {
+        let (held, staging, interventions) = run_staged(600, |_, _| {});
+        assert!(interventions.is_empty());
+        assert_eq!(held.fingerprint(), replay(600, &interventions).fingerprint());
+
+        let mut edited = splitter();
+        edited.modes[0].split_interval = 0.5;
+        edited.modes[0].split_mass = 0.5;
+        let fields: Vec<_> = staging.changes(&edited).into_iter().map(|change| change.field).collect();
+        assert_eq!(fields, ["split_interval", "split_mass"]);
+
+        let (applied, _, interventions) = run_staged(600, |tick, staging| if tick == 300 { staging.request_apply() });
+        assert_eq!(interventions.genome_applications.len(), 1);
+        assert_eq!(interventions.genome_applications[0].tick, 300);
+        assert!(applied.cell_count > held.cell_count, "{} vs {}", applied.cell_count, held.cell_count);
+        assert_eq!(replay(600, &interventions).fingerprint(), applied.fingerprint());
+    }
+
+    #[test]
+    fn test_lull_application_tick_is_deterministic_and_replays() {
+        let request = |tick: u32, staging: &mut GenomeStaging| if tick == 10 { staging.request_apply_at_lull() };
+        let (first, _, first_interventions) = run_staged(600, request);
+        let (second, _, second_interventions) = run_staged(600, request);
+
+        let tick = first_interventions.genome_applications[0].tick;
+        assert!(tick >= 10);
+        assert_eq!(second_interventions.genome_applications[0].tick, tick);
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(replay(600, &first_interventions).fingerprint(), first.fingerprint());
+    }
+
+    #[test]
+    fn test_discard_restores_the_running_genome() {
+        let base = splitter();
+        let mut staging = GenomeStaging::default();
+        staging.engage(&base);
+        let mut edited = base.clone();
+        edited.modes[0].name = "Renamed".to_string();
+        edited.modes.push(base.modes[0].clone());
+        assert_eq!(staging.changes(&edited).len(), 2);
+
+        staging.request_apply_at_lull();
+        staging.discard(&mut edited);
+        assert!(!staging.has_staged(&edited) && !staging.waiting_for_lull());
+        assert_eq!(staging.apply_due(&edited, |_| true), None);
+    }
+
+    #[test]
+    fn test_recorded_genome_edits_stay_bounded() {
+        use crate::simulation::scene_file::{GENOME_MERGE_TICKS, MAX_GENOME_APPLICATIONS};
+        let base = splitter();
+        let mut interventions = RunInterventions::default();
+
+        let mut renamed = base.clone();
+        renamed.modes[0].name = "Renamed".to_string();
+        interventions.record_genome(&base, 5, &renamed);
+        assert!(interventions.is_empty());
+
+        // A slider drag, one edit per tick, records one application per merge window
+        let mut dragged = base.clone();
+        for tick in 100..140 {
+            dragged.modes[0].split_interval = tick as f32 / 10.0;
+            interventions.record_genome(&base, tick, &dragged);
+        }
+        assert_eq!(interventions.genome_applications.len(), 2);
+        assert_eq!(interventions.genome_applications[0].tick, 100);
+        assert_eq!(interventions.genome_at(&base, 200).modes[0].split_interval, 13.9);
+
+        // Dragging back to the value in effect leaves nothing new behind
+        let mut undone = dragged.clone();
+        undone.modes[0].split_interval = 1.0;
+        interventions.record_genome(&base, 300, &undone);
+        interventions.record_genome(&base, 301, &dragged);
+        assert_eq!(interventions.genome_applications.len(), 2);
+
+        for i in 0..(MAX_GENOME_APPLICATIONS as u32 * 2) {
+            dragged.modes[0].split_mass = 1.0 + i as f32;
+            interventions.record_genome(&base, 1000 + i * (GENOME_MERGE_TICKS + 1), &dragged);
+        }
+        assert_eq!(interventions.genome_applications.len(), MAX_GENOME_APPLICATIONS);
+        assert!(interventions.genome_at(&base, u32::MAX) == &dragged);
+    }
+}
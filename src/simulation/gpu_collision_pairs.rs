@@ -0,0 +1,826 @@
+//! GPU spatial grid binning and collision pair generation
+//!
+//! Bins cells into the same grid layout as `DeterministicSpatialGrid` (counting sort with
+//! prefix sums, active-cell indexing), emits overlapping pairs into a GPU buffer using an
+//! atomic counter, and reads the pair indices back. On the CPU the pairs are filtered for
+//! same-organism contacts, sorted by (index_a, index_b) and handed to
+//! `compute_collision_forces_canonical` unchanged, so forces stay deterministic.
+//!
+//! The path is gated by `PhysicsConfig::gpu_pair_detection`. The CPU detector is timed
+//! periodically as a reference and the GPU path is abandoned automatically if it turns
+//! out slower (readback latency) or the adapter limits are too small.
+
+use bevy::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::simulation::cpu_physics::{detect_collisions_canonical, CanonicalCollisionPair};
+use crate::simulation::gpu_physics::{GpuPhysicsError, GPU_MAX_CELLS};
+use crate::simulation::{CanonicalState, PhysicsConfig, SpatialGridConfig};
+
+/// Maximum number of collision pairs the GPU can emit per tick
+pub const GPU_MAX_PAIRS: usize = GPU_MAX_CELLS * 16;
+
+/// Largest grid supported (SpatialGridConfig::MAX_DENSITY cubed)
+const GPU_MAX_GRID_TOTAL: usize = (SpatialGridConfig::MAX_DENSITY as usize).pow(3);
+
+/// Workgroup sizes (must match WGSL)
+const CELL_WORKGROUP_SIZE: u32 = 64;
+const SCAN_WORKGROUP_SIZE: u32 = 256;
+
+/// Storage buffers bound by the pair shader
+const REQUIRED_STORAGE_BUFFERS: u32 = 8;
+
+/// Time the CPU detector every N ticks as a reference for the speedup figure
+const CPU_REFERENCE_INTERVAL: u64 = 64;
+
+/// Samples of each path required before the fallback decision is made
+const MIN_TIMING_SAMPLES: u32 = 8;
+
+/// Exponential smoothing factor for timing averages
+const TIMING_SMOOTHING: f32 = 0.1;
+
+/// Pairs whose overlap is below this are ignored by validation (float ulp disagreements)
+const VALIDATION_OVERLAP_EPSILON: f32 = 1e-4;
+
+/// Grid parameters uniform (must match WGSL layout)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GpuGridParams {
+    pub cell_count: u32,
+    pub grid_dim: u32,
+    pub grid_total: u32,
+    pub max_pairs: u32,
+    pub world_size: f32,
+    pub cell_size: f32,
+    pub block_count: u32,
+    pub _pad: u32,
+}
+
+/// Collision pair emitted by the GPU (must match WGSL layout)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GpuCollisionPair {
+    pub index_a: u32,
+    pub index_b: u32,
+}
+
+/// GPU grid/pair compute pipelines and resources
+pub struct GpuPairContext {
+    // Compute pipelines (one per pass)
+    pub count_pipeline: wgpu::ComputePipeline,
+    pub scan_blocks_pipeline: wgpu::ComputePipeline,
+    pub scan_block_sums_pipeline: wgpu::ComputePipeline,
+    pub add_block_offsets_pipeline: wgpu::ComputePipeline,
+    pub scatter_pipeline: wgpu::ComputePipeline,
+    pub find_pairs_pipeline: wgpu::ComputePipeline,
+
+    // Buffers
+    pub params_buffer: wgpu::Buffer,
+    pub position_buffer: wgpu::Buffer,
+    /// Grid counts plus one trailing u32 used as the pair counter
+    pub grid_counts_buffer: wgpu::Buffer,
+    pub grid_offsets_buffer: wgpu::Buffer,
+    pub grid_cursors_buffer: wgpu::Buffer,
+    pub block_sums_buffer: wgpu::Buffer,
+    pub cell_grid_indices_buffer: wgpu::Buffer,
+    pub sorted_indices_buffer: wgpu::Buffer,
+    pub pairs_buffer: wgpu::Buffer,
+    pub counter_staging_buffer: wgpu::Buffer,
+    pub pairs_staging_buffer: wgpu::Buffer,
+
+    pub bind_group: wgpu::BindGroup,
+
+    // CPU-side upload buffer
+    pub cpu_positions: Vec<[f32; 4]>,
+
+    /// Number of pairs copied back speculatively with the counter (grows with the scene)
+    pub readback_estimate: usize,
+}
+
+impl GpuPairContext {
+    /// Create the pair pipeline on Bevy's render device, checking adapter limits first
+    pub fn new_from_bevy(
+        device: &bevy::render::renderer::RenderDevice,
+    ) -> Result<Self, GpuPhysicsError> {
+        Self::check_limits(&device.limits())?;
+
+        let wgpu_device = device.wgpu_device();
+
+        let shader_source = include_str!("../../assets/shaders/gpu_grid_pairs.wgsl");
+        let shader_module = wgpu_device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("GPU Grid Pairs Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        let bind_group_layout = wgpu_device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("GPU Grid Pairs Bind Group Layout"),
+            entries: &[
+                // params: uniform buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // positions: storage buffer (read)
+                storage_layout_entry(1, true),
+                // grid_counts, grid_offsets, grid_cursors, block_sums: storage buffers (read_write)
+                storage_layout_entry(2, false),
+                storage_layout_entry(3, false),
+                storage_layout_entry(4, false),
+                storage_layout_entry(5, false),
+                // cell_grid_indices, sorted_indices, pairs: storage buffers (read_write)
+                storage_layout_entry(6, false),
+                storage_layout_entry(7, false),
+                storage_layout_entry(8, false),
+            ],
+        });
+
+        let pipeline_layout = wgpu_device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("GPU Grid Pairs Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_pipeline = |entry_point: &str| {
+            wgpu_device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &shader_module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        let count_pipeline = create_pipeline("count_cells");
+        let scan_blocks_pipeline = create_pipeline("scan_blocks");
+        let scan_block_sums_pipeline = create_pipeline("scan_block_sums");
+        let add_block_offsets_pipeline = create_pipeline("add_block_offsets");
+        let scatter_pipeline = create_pipeline("scatter_cells");
+        let find_pairs_pipeline = create_pipeline("find_pairs");
+
+        // Calculate buffer sizes
+        let u32_size = std::mem::size_of::<u32>() as u64;
+        let grid_buffer_size = GPU_MAX_GRID_TOTAL as u64 * u32_size;
+        let block_count = GPU_MAX_GRID_TOTAL.div_ceil(SCAN_WORKGROUP_SIZE as usize) as u64;
+        let cell_index_buffer_size = GPU_MAX_CELLS as u64 * u32_size;
+        let pairs_buffer_size = (GPU_MAX_PAIRS * std::mem::size_of::<GpuCollisionPair>()) as u64;
+
+        let create_buffer = |label: &str, size: u64, usage: wgpu::BufferUsages| {
+            wgpu_device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+
+        let storage = wgpu::BufferUsages::STORAGE;
+        let params_buffer = create_buffer(
+            "Grid Params Buffer",
+            std::mem::size_of::<GpuGridParams>() as u64,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        let position_buffer = create_buffer(
+            "Grid Position Buffer",
+            GPU_MAX_CELLS as u64 * std::mem::size_of::<[f32; 4]>() as u64,
+            storage | wgpu::BufferUsages::COPY_DST,
+        );
+        let grid_counts_buffer = create_buffer(
+            "Grid Counts Buffer",
+            grid_buffer_size + u32_size,
+            storage | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        );
+        let grid_offsets_buffer = create_buffer("Grid Offsets Buffer", grid_buffer_size, storage);
+        let grid_cursors_buffer = create_buffer(
+            "Grid Cursors Buffer",
+            grid_buffer_size,
+            storage | wgpu::BufferUsages::COPY_DST,
+        );
+        let block_sums_buffer = create_buffer("Grid Block Sums Buffer", block_count * u32_size, storage);
+        let cell_grid_indices_buffer = create_buffer("Cell Grid Indices Buffer", cell_index_buffer_size, storage);
+        let sorted_indices_buffer = create_buffer("Sorted Cell Indices Buffer", cell_index_buffer_size, storage);
+        let pairs_buffer = create_buffer(
+            "Collision Pairs Buffer",
+            pairs_buffer_size,
+            storage | wgpu::BufferUsages::COPY_SRC,
+        );
+        let counter_staging_buffer = create_buffer(
+            "Pair Counter Staging Buffer",
+            u32_size,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+        let pairs_staging_buffer = create_buffer(
+            "Collision Pairs Staging Buffer",
+            pairs_buffer_size,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+
+        let buffers = [
+            &params_buffer,
+            &position_buffer,
+            &grid_counts_buffer,
+            &grid_offsets_buffer,
+            &grid_cursors_buffer,
+            &block_sums_buffer,
+            &cell_grid_indices_buffer,
+            &sorted_indices_buffer,
+            &pairs_buffer,
+        ];
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+
+        let bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("GPU Grid Pairs Bind Group"),
+            layout: &bind_group_layout,
+            entries: &entries,
+        });
+
+        Ok(Self {
+            count_pipeline,
+            scan_blocks_pipeline,
+            scan_block_sums_pipeline,
+            add_block_offsets_pipeline,
+            scatter_pipeline,
+            find_pairs_pipeline,
+            params_buffer,
+            position_buffer,
+            grid_counts_buffer,
+            grid_offsets_buffer,
+            grid_cursors_buffer,
+            block_sums_buffer,
+            cell_grid_indices_buffer,
+            sorted_indices_buffer,
+            pairs_buffer,
+            counter_staging_buffer,
+            pairs_staging_buffer,
+            bind_group,
+            cpu_positions: vec![[0.0; 4]; GPU_MAX_CELLS],
+            readback_estimate: 1024,
+        })
+    }
+
+    /// Reject adapters that cannot hold the largest grid or pair buffer
+    fn check_limits(limits: &wgpu::Limits) -> Result<(), GpuPhysicsError> {
+        if limits.max_storage_buffers_per_shader_stage < REQUIRED_STORAGE_BUFFERS {
+            return Err(GpuPhysicsError::UnsupportedLimits(format!(
+                "{} storage buffers per stage (need {})",
+                limits.max_storage_buffers_per_shader_stage, REQUIRED_STORAGE_BUFFERS
+            )));
+        }
+
+        let largest_binding = ((GPU_MAX_GRID_TOTAL + 1) * std::mem::size_of::<u32>())
+            .max(GPU_MAX_PAIRS * std::mem::size_of::<GpuCollisionPair>()) as u64;
+        if (limits.max_storage_buffer_binding_size as u64) < largest_binding {
+            return Err(GpuPhysicsError::UnsupportedLimits(format!(
+                "storage binding size {} bytes (need {})",
+                limits.max_storage_buffer_binding_size, largest_binding
+            )));
+        }
+
+        let max_workgroups = (GPU_MAX_GRID_TOTAL as u32).div_ceil(SCAN_WORKGROUP_SIZE)
+            .max((GPU_MAX_CELLS as u32).div_ceil(CELL_WORKGROUP_SIZE));
+        if limits.max_compute_workgroups_per_dimension < max_workgroups {
+            return Err(GpuPhysicsError::UnsupportedLimits(format!(
+                "{} workgroups per dimension (need {})",
+                limits.max_compute_workgroups_per_dimension, max_workgroups
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Bin cells and generate collision pairs on the GPU
+    ///
+    /// Returns pairs sorted by (index_a, index_b) with overlap and normal recomputed on the
+    /// CPU exactly as `detect_collisions_canonical` does, or `None` if the scene does not fit
+    /// the GPU buffers this tick (caller should use the CPU detector instead).
+    pub fn detect_pairs(
+        &mut self,
+        state: &CanonicalState,
+        device: &bevy::render::renderer::RenderDevice,
+        queue: &bevy::render::renderer::RenderQueue,
+    ) -> Option<Vec<CanonicalCollisionPair>> {
+        let cell_count = state.cell_count;
+        if cell_count == 0 {
+            return Some(Vec::new());
+        }
+        if cell_count > GPU_MAX_CELLS {
+            return None;
+        }
+
+        let grid = &state.spatial_grid;
+        let grid_dim = grid.grid_dimensions.x;
+        let grid_total = grid.active_cells.len();
+        if grid_total > GPU_MAX_GRID_TOTAL {
+            return None;
+        }
+        let block_count = (grid_total as u32).div_ceil(SCAN_WORKGROUP_SIZE);
+
+        // Upload positions (xyz) and radii (w)
+        for i in 0..cell_count {
+            let p = state.positions[i];
+            self.cpu_positions[i] = [p.x, p.y, p.z, state.radii[i]];
+        }
+        queue.write_buffer(&self.position_buffer, 0, bytemuck::cast_slice(&self.cpu_positions[..cell_count]));
+
+        let params = GpuGridParams {
+            cell_count: cell_count as u32,
+            grid_dim,
+            grid_total: grid_total as u32,
+            max_pairs: GPU_MAX_PAIRS as u32,
+            world_size: grid.world_size,
+            cell_size: grid.cell_size,
+            block_count,
+            _pad: 0,
+        };
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let wgpu_device = device.wgpu_device();
+        let mut encoder = wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("GPU Grid Pairs Encoder"),
+        });
+
+        // Reset counts (including the trailing pair counter) and insertion cursors
+        let u32_size = std::mem::size_of::<u32>() as u64;
+        let counter_offset = grid_total as u64 * u32_size;
+        encoder.clear_buffer(&self.grid_counts_buffer, 0, Some(counter_offset + u32_size));
+        encoder.clear_buffer(&self.grid_cursors_buffer, 0, Some(counter_offset));
+
+        let cell_workgroups = (cell_count as u32).div_ceil(CELL_WORKGROUP_SIZE);
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Grid Pairs Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_bind_group(0, &self.bind_group, &[]);
+
+            pass.set_pipeline(&self.count_pipeline);
+            pass.dispatch_workgroups(cell_workgroups, 1, 1);
+
+            pass.set_pipeline(&self.scan_blocks_pipeline);
+            pass.dispatch_workgroups(block_count, 1, 1);
+
+            pass.set_pipeline(&self.scan_block_sums_pipeline);
+            pass.dispatch_workgroups(1, 1, 1);
+
+            pass.set_pipeline(&self.add_block_offsets_pipeline);
+            pass.dispatch_workgroups(block_count, 1, 1);
+
+            pass.set_pipeline(&self.scatter_pipeline);
+            pass.dispatch_workgroups(cell_workgroups, 1, 1);
+
+            pass.set_pipeline(&self.find_pairs_pipeline);
+            pass.dispatch_workgroups(cell_workgroups, 1, 1);
+        }
+
+        // Read back the counter together with a speculative chunk of pairs so the common
+        // case needs a single round trip
+        let pair_size = std::mem::size_of::<GpuCollisionPair>() as u64;
+        let speculative = self.readback_estimate.min(GPU_MAX_PAIRS) as u64;
+        encoder.copy_buffer_to_buffer(&self.grid_counts_buffer, counter_offset, &self.counter_staging_buffer, 0, u32_size);
+        encoder.copy_buffer_to_buffer(&self.pairs_buffer, 0, &self.pairs_staging_buffer, 0, speculative * pair_size);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let counter_slice = self.counter_staging_buffer.slice(..);
+        if !map_and_wait(&counter_slice, wgpu_device) {
+            return None;
+        }
+        let pair_count = {
+            let data = counter_slice.get_mapped_range();
+            bytemuck::cast_slice::<u8, u32>(&data)[0] as usize
+        };
+        self.counter_staging_buffer.unmap();
+
+        if pair_count > GPU_MAX_PAIRS {
+            // Overflowed the pair buffer - the list is incomplete
+            self.readback_estimate = GPU_MAX_PAIRS;
+            return None;
+        }
+
+        // Second round trip only when the speculative copy was too small
+        if pair_count as u64 > speculative {
+            let mut encoder = wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("GPU Pair Readback Encoder"),
+            });
+            encoder.copy_buffer_to_buffer(&self.pairs_buffer, 0, &self.pairs_staging_buffer, 0, pair_count as u64 * pair_size);
+            queue.submit(std::iter::once(encoder.finish()));
+        }
+        self.readback_estimate = (pair_count + pair_count / 4 + 1024).min(GPU_MAX_PAIRS);
+
+        let mut collision_pairs = Vec::with_capacity(pair_count);
+        if pair_count > 0 {
+            let pairs_slice = self.pairs_staging_buffer.slice(..pair_count as u64 * pair_size);
+            if !map_and_wait(&pairs_slice, wgpu_device) {
+                return None;
+            }
+            {
+                let data = pairs_slice.get_mapped_range();
+                let gpu_pairs: &[GpuCollisionPair] = bytemuck::cast_slice(&data);
+                for pair in gpu_pairs {
+                    if let Some(canonical) = canonical_pair(state, pair.index_a as usize, pair.index_b as usize) {
+                        collision_pairs.push(canonical);
+                    }
+                }
+            }
+            self.pairs_staging_buffer.unmap();
+        }
+
+        // Same ordering as detect_collisions_canonical
+        collision_pairs.sort_unstable_by_key(|pair| (pair.index_a, pair.index_b));
+
+        Some(collision_pairs)
+    }
+}
+
+fn storage_layout_entry(binding: u32, read_only: bool) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Map a staging slice for reading and block until the GPU is done
+fn map_and_wait(slice: &wgpu::BufferSlice, device: &wgpu::Device) -> bool {
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait);
+    matches!(rx.recv(), Ok(Ok(())))
+}
+
+/// Recompute overlap and normal on the CPU (bit-identical to the CPU detector) and apply
+/// the same-organism filter, which needs the adhesion graph
+fn canonical_pair(state: &CanonicalState, index_a: usize, index_b: usize) -> Option<CanonicalCollisionPair> {
+    if index_a >= state.cell_count || index_b >= state.cell_count {
+        return None;
+    }
+
+    let delta = state.positions[index_b] - state.positions[index_a];
+    let distance = delta.length();
+    let combined_radius = state.radii[index_a] + state.radii[index_b];
+    if distance >= combined_radius {
+        return None;
+    }
+
+    if state.adhesion_manager.are_cells_in_same_organism(&state.adhesion_connections, index_a, index_b) {
+        return None;
+    }
+
+    let overlap = combined_radius - distance;
+    let normal = if distance > 0.0001 {
+        delta / distance
+    } else {
+        Vec3::X
+    };
+
+    Some(CanonicalCollisionPair {
+        index_a,
+        index_b,
+        overlap,
+        normal,
+    })
+}
+
+/// Count pairs present in only one of the two lists
+///
+/// Pairs are compared unordered ((a, b) == (b, a)) because cells sharing a grid cell may be
+/// listed in either order by the parallel CPU grid rebuild. Pairs with an overlap below
+/// `VALIDATION_OVERLAP_EPSILON` are ignored since GPU and CPU `length()` can disagree by an ulp.
+pub fn count_pair_mismatches(gpu_pairs: &[CanonicalCollisionPair], cpu_pairs: &[CanonicalCollisionPair]) -> usize {
+    let normalize = |pairs: &[CanonicalCollisionPair]| {
+        let mut keys: Vec<(usize, usize)> = pairs
+            .iter()
+            .filter(|pair| pair.overlap >= VALIDATION_OVERLAP_EPSILON)
+            .map(|pair| (pair.index_a.min(pair.index_b), pair.index_a.max(pair.index_b)))
+            .collect();
+        keys.sort_unstable();
+        keys
+    };
+
+    let gpu_keys = normalize(gpu_pairs);
+    let cpu_keys = normalize(cpu_pairs);
+
+    // Symmetric difference of two sorted lists
+    let (mut i, mut j, mut mismatches) = (0, 0, 0);
+    while i < gpu_keys.len() && j < cpu_keys.len() {
+        match gpu_keys[i].cmp(&cpu_keys[j]) {
+            std::cmp::Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+            std::cmp::Ordering::Less => {
+                mismatches += 1;
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                mismatches += 1;
+                j += 1;
+            }
+        }
+    }
+    mismatches + (gpu_keys.len() - i) + (cpu_keys.len() - j)
+}
+
+/// Fixture scenes used by validation mode (dense clusters, boundary straddling, grid seams)
+pub fn validation_fixture_scenes() -> Vec<(&'static str, CanonicalState)> {
+    let add = |state: &mut CanonicalState, position: Vec3, radius: f32| {
+        state.add_cell(
+            position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO,
+            1.0, radius, 0, 0, 0.0, 10.0, 1.5, 500.0, Quat::IDENTITY, 0,
+        );
+    };
+
+    let mut scenes = Vec::new();
+
+    // Tightly packed cubic lattice around the origin (spacing < diameter)
+    let mut lattice = CanonicalState::new(1_000);
+    for x in -5..5 {
+        for y in -5..5 {
+            for z in -5..5 {
+                add(&mut lattice, Vec3::new(x as f32, y as f32, z as f32) * 1.8, 1.0);
+            }
+        }
+    }
+    scenes.push(("dense lattice", lattice));
+
+    // Cells straddling grid cell seams so pairs cross every forward neighbor direction
+    let mut seams = CanonicalState::new(1_000);
+    let cell_size = seams.spatial_grid.cell_size;
+    for i in 0..512 {
+        let grid_coord = Vec3::new((i % 8) as f32 - 4.0, ((i / 8) % 8) as f32 - 4.0, (i / 64) as f32 - 4.0);
+        let jitter = Vec3::new(
+            if i % 2 == 0 { 0.4 } else { -0.4 },
+            if i % 3 == 0 { 0.4 } else { -0.4 },
+            if i % 5 == 0 { 0.4 } else { -0.4 },
+        );
+        add(&mut seams, grid_coord * cell_size + jitter, 0.5 + (i % 4) as f32 * 0.25);
+    }
+    scenes.push(("grid seams", seams));
+
+    // Shell of cells pressed against the spherical boundary
+    let mut shell = CanonicalState::new(1_000);
+    for i in 0..600 {
+        let golden = std::f32::consts::PI * (3.0 - 5.0f32.sqrt());
+        let y = 1.0 - (i as f32 / 599.0) * 2.0;
+        let r = (1.0 - y * y).sqrt();
+        let theta = golden * i as f32;
+        add(&mut shell, Vec3::new(theta.cos() * r, y, theta.sin() * r) * 99.0, 1.5);
+    }
+    scenes.push(("boundary shell", shell));
+
+    for (_, state) in scenes.iter_mut() {
+        state.spatial_grid.rebuild(&state.positions, state.cell_count);
+    }
+
+    scenes
+}
+
+/// Timing and validation statistics for the GPU pair path (shown in the performance monitor)
+#[derive(Default, Clone, Debug)]
+pub struct GpuPairStats {
+    /// Smoothed GPU detection time including readback (ms)
+    pub gpu_ms: f32,
+    /// Smoothed CPU reference detection time (ms)
+    pub cpu_ms: f32,
+    pub gpu_samples: u32,
+    pub cpu_samples: u32,
+    /// Pair count from the most recent GPU tick
+    pub last_pair_count: usize,
+    /// Ticks that fell back to the CPU because the GPU buffers overflowed
+    pub overflow_ticks: u64,
+    /// Total mismatched pairs seen in validation mode
+    pub validation_mismatches: u64,
+    /// Per-fixture mismatch counts from the last fixture validation run
+    pub fixture_results: Vec<(String, usize)>,
+    /// Why the GPU path was disabled, if it was
+    pub fallback_reason: Option<String>,
+    /// Ticks processed by the GPU path
+    pub ticks: u64,
+}
+
+impl GpuPairStats {
+    /// Measured CPU/GPU speedup, once both paths have been timed
+    pub fn speedup(&self) -> Option<f32> {
+        if self.gpu_samples == 0 || self.cpu_samples == 0 || self.gpu_ms <= 0.0 {
+            return None;
+        }
+        Some(self.cpu_ms / self.gpu_ms)
+    }
+
+    fn record(average: &mut f32, samples: &mut u32, elapsed_ms: f32) {
+        *average = if *samples == 0 {
+            elapsed_ms
+        } else {
+            *average + (elapsed_ms - *average) * TIMING_SMOOTHING
+        };
+        *samples += 1;
+    }
+}
+
+/// Bevy resource for the GPU pair path
+#[derive(Resource, Default)]
+pub struct GpuPairDetection {
+    pub context: Option<Arc<Mutex<GpuPairContext>>>,
+    pub device: Option<bevy::render::renderer::RenderDevice>,
+    pub queue: Option<bevy::render::renderer::RenderQueue>,
+    pub initialization_attempted: bool,
+    pub stats: GpuPairStats,
+}
+
+impl GpuPairDetection {
+    /// Whether ticks should currently go through the GPU pair path
+    pub fn is_active(&self, config: &PhysicsConfig) -> bool {
+        config.gpu_pair_detection && self.context.is_some() && self.stats.fallback_reason.is_none()
+    }
+
+    /// Clear the automatic fallback and timing history so the GPU path is re-evaluated
+    pub fn retry(&mut self) {
+        let fixture_results = std::mem::take(&mut self.stats.fixture_results);
+        self.stats = GpuPairStats {
+            fixture_results,
+            ..default()
+        };
+    }
+
+    /// Run every fixture scene through both detectors and record mismatches
+    pub fn validate_fixtures(&mut self) {
+        let (Some(context), Some(device), Some(queue)) = (&self.context, &self.device, &self.queue) else {
+            return;
+        };
+        let Ok(mut ctx) = context.lock() else {
+            return;
+        };
+
+        self.stats.fixture_results.clear();
+        for (name, state) in validation_fixture_scenes() {
+            let cpu_pairs = detect_collisions_canonical(&state);
+            let mismatches = match ctx.detect_pairs(&state, device, queue) {
+                Some(gpu_pairs) => count_pair_mismatches(&gpu_pairs, &cpu_pairs),
+                None => cpu_pairs.len(),
+            };
+            if mismatches > 0 {
+                warn!("GPU pair validation: fixture '{}' has {} mismatched pairs", name, mismatches);
+            } else {
+                info!("GPU pair validation: fixture '{}' matches CPU ({} pairs)", name, cpu_pairs.len());
+            }
+            self.stats.fixture_results.push((name.to_string(), mismatches));
+        }
+    }
+}
+
+/// Detect collisions with the GPU pair path, falling back to the CPU detector
+///
+/// The CPU detector is also run every `CPU_REFERENCE_INTERVAL` ticks (every tick in
+/// validation mode) to keep the speedup figure current. Once both paths have enough samples,
+/// the GPU path is disabled if it is slower than the CPU.
+pub fn detect_collisions_with_gpu_pairs(
+    state: &CanonicalState,
+    config: &PhysicsConfig,
+    gpu_pairs: &mut GpuPairDetection,
+) -> Vec<CanonicalCollisionPair> {
+    if !gpu_pairs.is_active(config) {
+        return detect_collisions_canonical(state);
+    }
+
+    let (Some(context), Some(device), Some(queue)) = (&gpu_pairs.context, &gpu_pairs.device, &gpu_pairs.queue) else {
+        return detect_collisions_canonical(state);
+    };
+
+    let start = Instant::now();
+    let gpu_result = match context.lock() {
+        Ok(mut ctx) => ctx.detect_pairs(state, device, queue),
+        Err(_) => None,
+    };
+    let gpu_elapsed_ms = start.elapsed().as_secs_f32() * 1000.0;
+
+    let stats = &mut gpu_pairs.stats;
+    let Some(pairs) = gpu_result else {
+        stats.overflow_ticks += 1;
+        return detect_collisions_canonical(state);
+    };
+
+    stats.ticks += 1;
+    stats.last_pair_count = pairs.len();
+    GpuPairStats::record(&mut stats.gpu_ms, &mut stats.gpu_samples, gpu_elapsed_ms);
+
+    if config.gpu_pair_validation || stats.ticks % CPU_REFERENCE_INTERVAL == 1 {
+        let start = Instant::now();
+        let cpu_pairs = detect_collisions_canonical(state);
+        let cpu_elapsed_ms = start.elapsed().as_secs_f32() * 1000.0;
+        GpuPairStats::record(&mut stats.cpu_ms, &mut stats.cpu_samples, cpu_elapsed_ms);
+
+        if config.gpu_pair_validation {
+            let mismatches = count_pair_mismatches(&pairs, &cpu_pairs);
+            if mismatches > 0 {
+                stats.validation_mismatches += mismatches as u64;
+                warn!("GPU pair validation: {} mismatched pairs at tick {}", mismatches, stats.ticks);
+            }
+        }
+    }
+
+    if stats.gpu_samples >= MIN_TIMING_SAMPLES
+        && stats.cpu_samples >= MIN_TIMING_SAMPLES
+        && stats.gpu_ms > stats.cpu_ms
+    {
+        let reason = format!(
+            "GPU pair detection slower than CPU ({:.2} ms vs {:.2} ms)",
+            stats.gpu_ms, stats.cpu_ms
+        );
+        info!("{}. Falling back to CPU.", reason);
+        stats.fallback_reason = Some(reason);
+    }
+
+    pairs
+}
+
+/// Plugin for GPU grid binning and pair generation
+pub struct GpuPairPlugin;
+
+impl Plugin for GpuPairPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GpuPairDetection>()
+            // Only initialize once the feature is enabled
+            .add_systems(Update, lazy_initialize_gpu_pairs.run_if(
+                |gpu: Res<GpuPairDetection>, config: Res<PhysicsConfig>| {
+                    config.gpu_pair_detection && !gpu.initialization_attempted
+                }
+            ));
+    }
+}
+
+fn lazy_initialize_gpu_pairs(
+    mut gpu_pairs: ResMut<GpuPairDetection>,
+    config: Res<PhysicsConfig>,
+    render_device: Option<Res<bevy::render::renderer::RenderDevice>>,
+    render_queue: Option<Res<bevy::render::renderer::RenderQueue>>,
+    capabilities: Option<Res<crate::rendering::RenderCapabilities>>,
+) {
+    gpu_pairs.initialization_attempted = true;
+
+    if let Some(reason) = capabilities.as_ref().and_then(|capabilities| capabilities.gpu_compute.missing_reason()) {
+        gpu_pairs.stats.fallback_reason = Some(format!("GPU compute unavailable: {}", reason));
+        return;
+    }
+
+    let (Some(device), Some(queue)) = (render_device, render_queue) else {
+        gpu_pairs.stats.fallback_reason = Some("Render device not available".to_string());
+        warn!("Render device not available for GPU pair detection");
+        return;
+    };
+
+    match GpuPairContext::new_from_bevy(&device) {
+        Ok(context) => {
+            info!("GPU pair detection initialized");
+            gpu_pairs.context = Some(Arc::new(Mutex::new(context)));
+            gpu_pairs.device = Some(device.clone());
+            gpu_pairs.queue = Some(queue.clone());
+            if config.gpu_pair_validation {
+                gpu_pairs.validate_fixtures();
+            }
+        }
+        Err(e) => {
+            warn!("Failed to initialize GPU pair detection: {}. Falling back to CPU.", e);
+            gpu_pairs.stats.fallback_reason = Some(e.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(index_a: usize, index_b: usize, overlap: f32) -> CanonicalCollisionPair {
+        CanonicalCollisionPair { index_a, index_b, overlap, normal: Vec3::X }
+    }
+
+    #[test]
+    fn test_pair_mismatches_ignore_order_and_grazing_contacts() {
+        let gpu = [pair(0, 1, 0.5), pair(3, 2, 0.2), pair(4, 5, 1e-6)];
+        let cpu = [pair(2, 3, 0.2), pair(0, 1, 0.5)];
+        assert_eq!(count_pair_mismatches(&gpu, &cpu), 0);
+
+        let cpu_missing = [pair(0, 1, 0.5)];
+        assert_eq!(count_pair_mismatches(&gpu, &cpu_missing), 1);
+    }
+}
@@ -504,6 +504,8 @@ pub enum GpuPhysicsError {
     DeviceCreation(String),
     #[error("Shader compilation error: {0}")]
     ShaderCompilation(String),
+    #[error("GPU adapter limits too small: {0}")]
+    UnsupportedLimits(String),
 }
 
 /// Bevy resource for GPU physics
@@ -588,7 +590,16 @@ fn lazy_initialize_gpu_physics(
     mut gpu_physics: ResMut<GpuPhysicsResource>,
     render_device: Option<Res<bevy::render::renderer::RenderDevice>>,
     render_queue: Option<Res<bevy::render::renderer::RenderQueue>>,
+    capabilities: Option<Res<crate::rendering::RenderCapabilities>>,
 ) {
+    // Creating compute pipelines on an adapter without compute support fails validation
+    if let Some(reason) = capabilities.as_ref().and_then(|capabilities| capabilities.gpu_compute.missing_reason()) {
+        gpu_physics.initialization_attempted = true;
+        gpu_physics.enabled = false;
+        info!("GPU physics disabled: {}", reason);
+        return;
+    }
+
     if let (Some(device), Some(queue)) = (render_device, render_queue) {
         initialize_gpu_physics_from_bevy(&mut gpu_physics, &device, &queue);
     } else {
@@ -629,6 +640,7 @@ pub fn physics_step_gpu(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
     // 3. Update spatial partitioning (needed for adhesion lookups even with GPU collision)
@@ -645,24 +657,36 @@ pub fn physics_step_gpu(
         }
     }
     
+    // The GPU pass doesn't report overlaps, so contact inhibition is inactive here
+    // and the contact graph has no collision contacts
+    state.contact_pressures[..state.cell_count].fill(0.0);
+    state.scratch.last_contacts.clear();
+    
     // 5.5. Compute adhesion forces (if any connections exist) - CPU
     if state.adhesion_connections.active_count > 0 {
         let default_settings = crate::cell::AdhesionSettings::default();
         let mode_settings = vec![default_settings; 10];
         
         crate::cell::compute_adhesion_forces_batched(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
             &mode_settings,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
     }
     
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions - CPU
     apply_boundary_forces_soa_st(
         &mut state.positions[..state.cell_count],
@@ -681,8 +705,12 @@ pub fn physics_step_gpu(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
     // 8. Update angular velocities from torques - CPU
     integrate_angular_velocities_soa_st(
         &mut state.angular_velocities[..state.cell_count],
@@ -691,6 +719,7 @@ pub fn physics_step_gpu(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
 }
 
@@ -712,6 +741,8 @@ pub fn physics_step_gpu_with_genome(
         apply_swim_forces_st,
     };
     
+    let compiled = state.compiled_genome.get(genome);
+    
     // 1. Verlet integration (position update) - CPU
     verlet_integrate_positions_soa_st(
         &mut state.positions[..state.cell_count],
@@ -725,6 +756,7 @@ pub fn physics_step_gpu_with_genome(
         &mut state.rotations[..state.cell_count],
         &state.angular_velocities[..state.cell_count],
         config.fixed_timestep,
+        config.math(),
     );
     
     // 3. Update spatial partitioning
@@ -740,32 +772,22 @@ pub fn physics_step_gpu_with_genome(
         }
     }
     
+    // The GPU pass doesn't report overlaps, so contact inhibition is inactive here
+    // and the contact graph has no collision contacts
+    state.contact_pressures[..state.cell_count].fill(0.0);
+    state.scratch.last_contacts.clear();
+    
     // 5.5. Compute adhesion forces with genome settings - CPU
     if state.adhesion_connections.active_count > 0 {
-        let mode_settings: Vec<crate::cell::AdhesionSettings> = genome.modes.iter()
-            .map(|mode| crate::cell::AdhesionSettings {
-                can_break: mode.adhesion_settings.can_break,
-                break_force: mode.adhesion_settings.break_force,
-                rest_length: mode.adhesion_settings.rest_length,
-                linear_spring_stiffness: mode.adhesion_settings.linear_spring_stiffness,
-                linear_spring_damping: mode.adhesion_settings.linear_spring_damping,
-                orientation_spring_stiffness: mode.adhesion_settings.orientation_spring_stiffness,
-                orientation_spring_damping: mode.adhesion_settings.orientation_spring_damping,
-                max_angular_deviation: mode.adhesion_settings.max_angular_deviation,
-                twist_constraint_stiffness: mode.adhesion_settings.twist_constraint_stiffness,
-                twist_constraint_damping: mode.adhesion_settings.twist_constraint_damping,
-                enable_twist_constraint: mode.adhesion_settings.enable_twist_constraint,
-            })
-            .collect();
-        
         crate::cell::compute_adhesion_forces_batched(
-            &state.adhesion_connections,
+            &mut state.adhesion_connections,
             &state.positions[..state.cell_count],
             &state.velocities[..state.cell_count],
             &state.rotations[..state.cell_count],
             &state.angular_velocities[..state.cell_count],
             &state.masses[..state.cell_count],
-            &mode_settings,
+            &compiled.adhesion,
+            &config.adhesion_tiers(),
             &mut state.forces[..state.cell_count],
             &mut state.torques[..state.cell_count],
         );
@@ -774,12 +796,21 @@ pub fn physics_step_gpu_with_genome(
     // 5.6. Apply swim forces for Flagellocyte cells
     apply_swim_forces_st(
         &mut state.forces[..state.cell_count],
+        &state.positions[..state.cell_count],
         &state.rotations[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
+        &state.chemical_field,
         enable_swim,
+        config.math(),
     );
     
+    // 5.7. Soft drag pulls the grabbed region toward the cursor
+    state.apply_soft_drag_forces();
+    
+    // 5.8. Central attractor pulls every cell toward the origin
+    state.apply_central_attraction(config);
+    
     // 6. Apply boundary conditions - CPU
     apply_boundary_forces_soa_st(
         &mut state.positions[..state.cell_count],
@@ -798,8 +829,12 @@ pub fn physics_step_gpu_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.velocity_damping,
+        config.math(),
     );
     
+    // 7.5. Pinned cells keep their position
+    state.hold_pinned_cells();
+    
     // 8. Update angular velocities from torques - CPU
     integrate_angular_velocities_soa_st(
         &mut state.angular_velocities[..state.cell_count],
@@ -808,6 +843,7 @@ pub fn physics_step_gpu_with_genome(
         &state.masses[..state.cell_count],
         config.fixed_timestep,
         config.angular_damping,
+        config.math(),
     );
     
     // 9. Update nutrient growth for cells - CPU
@@ -815,10 +851,13 @@ pub fn physics_step_gpu_with_genome(
         &mut state.masses[..state.cell_count],
         &mut state.radii[..state.cell_count],
         &state.mode_indices[..state.cell_count],
-        genome,
+        &compiled,
         config.fixed_timestep,
     );
     
     // 10. Synchronized nutrient transport - CPU
-    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, genome, config.fixed_timestep);
+    crate::simulation::synchronized_nutrients::transport_nutrients_synchronized(state, &compiled, config.fixed_timestep);
+    
+    // 11. Secretion, absorption and diffusion in the chemical field - CPU
+    state.update_chemical_field(genome, config);
 }
@@ -0,0 +1,923 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use crate::simulation::cpu_sim::{CpuSceneState, MainSimState};
+use crate::simulation::derived_metrics::{CompiledMetric, DerivedMetrics};
+use crate::simulation::live_stats::LiveEventLog;
+use crate::simulation::{CanonicalState, EventTimeline, PhysicsConfig};
+
+/// Plugin keeping the statistics history and the limits of every tiered history store
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HistorySettings>()
+            .init_resource::<StatisticsHistory>()
+            .add_systems(
+                FixedUpdate,
+                record_statistics
+                    .after(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(CpuSceneState::Active)),
+            )
+            .add_systems(Update, apply_history_settings);
+    }
+}
+
+/// Time resolution of a stretch of history
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// Every record as it happened
+    Raw,
+    Second,
+    Minute,
+}
+
+impl Resolution {
+    pub fn name(self) -> &'static str {
+        match self {
+            Resolution::Raw => "raw",
+            Resolution::Second => "per-second",
+            Resolution::Minute => "per-minute",
+        }
+    }
+
+    /// Bucket width in seconds (None for raw records)
+    pub fn bucket_seconds(self) -> Option<u32> {
+        match self {
+            Resolution::Raw => None,
+            Resolution::Second => Some(1),
+            Resolution::Minute => Some(60),
+        }
+    }
+}
+
+/// Something a `TieredHistory` stores, and how its aggregates roll up
+pub trait HistoryRecord: Clone {
+    /// Aggregate of any number of records (the default is the empty aggregate)
+    type Summary: Clone + Default + std::fmt::Debug + PartialEq;
+
+    /// Tick the record belongs to
+    fn tick(&self) -> u32;
+
+    fn summarize(summary: &mut Self::Summary, record: &Self);
+
+    fn merge(summary: &mut Self::Summary, other: &Self::Summary);
+}
+
+/// Aggregate of the records in `[start, start + width)` seconds
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryBucket<S> {
+    /// First second covered
+    pub start: u32,
+    pub resolution: Resolution,
+    pub summary: S,
+}
+
+impl<S> HistoryBucket<S> {
+    /// First second after the bucket
+    pub fn end(&self) -> u32 {
+        self.start + self.resolution.bucket_seconds().unwrap_or(0)
+    }
+}
+
+/// Stretch of history kept at one resolution, in seconds of simulated time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistoryRange {
+    pub start: f32,
+    pub end: f32,
+    pub resolution: Resolution,
+}
+
+/// Retention of each tier and the store's memory budget
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistoryLimits {
+    /// Seconds of raw records kept behind the newest one
+    pub raw_window: f32,
+    /// Seconds of per-second buckets kept behind the newest record
+    pub second_retention: f32,
+    /// Seconds of per-minute buckets kept behind the newest record
+    pub minute_retention: f32,
+    /// Bytes the records and buckets may take (see `TieredHistory::memory_bytes`)
+    pub budget_bytes: usize,
+}
+
+impl Default for HistoryLimits {
+    fn default() -> Self {
+        HistorySettings::default().limits(HistoryStore::Statistics)
+    }
+}
+
+/// Time-indexed records that age from raw into per-second, then per-minute aggregates
+///
+/// Raw records are kept for `raw_window` seconds behind the newest one, then folded
+/// into per-second buckets, which fold into per-minute buckets after
+/// `second_retention`; minutes older than `minute_retention` are dropped. Over the
+/// memory budget, the oldest aggregates go first, then the oldest raw seconds are
+/// folded early. Ticks are measured in `timestep`, seconds in simulated time.
+#[derive(Clone, Debug)]
+pub struct TieredHistory<R: HistoryRecord> {
+    timestep: f32,
+    limits: HistoryLimits,
+    /// Sorted by tick
+    raw: VecDeque<R>,
+    seconds: VecDeque<HistoryBucket<R::Summary>>,
+    minutes: VecDeque<HistoryBucket<R::Summary>>,
+}
+
+impl<R: HistoryRecord> Default for TieredHistory<R> {
+    fn default() -> Self {
+        Self::new(PhysicsConfig::default().fixed_timestep, HistoryLimits::default())
+    }
+}
+
+fn second_of(tick: u32, timestep: f32) -> u32 {
+    (tick as f32 * timestep).floor() as u32
+}
+
+impl<R: HistoryRecord> TieredHistory<R> {
+    pub fn new(timestep: f32, limits: HistoryLimits) -> Self {
+        Self { timestep, limits, raw: VecDeque::new(), seconds: VecDeque::new(), minutes: VecDeque::new() }
+    }
+
+    pub fn timestep(&self) -> f32 {
+        self.timestep
+    }
+
+    /// Measure ticks in `timestep`; a different timestep invalidates every record
+    pub fn set_timestep(&mut self, timestep: f32) {
+        if self.timestep != timestep {
+            self.timestep = timestep;
+            self.clear();
+        }
+    }
+
+    pub fn tick_time(&self, tick: u32) -> f32 {
+        tick as f32 * self.timestep
+    }
+
+    pub fn limits(&self) -> HistoryLimits {
+        self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: HistoryLimits) {
+        self.limits = limits;
+        self.enforce_limits();
+    }
+
+    pub fn clear(&mut self) {
+        self.raw.clear();
+        self.seconds.clear();
+        self.minutes.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty() && self.seconds.is_empty() && self.minutes.is_empty()
+    }
+
+    /// Add a record; one for an already aggregated second joins its bucket
+    pub fn push(&mut self, record: R) {
+        let second = second_of(record.tick(), self.timestep);
+        let aggregated_until = self.seconds.back().or(self.minutes.back()).map(|bucket| bucket.end());
+        if aggregated_until.is_some_and(|end| second < end) {
+            self.summarize_late(second, &record);
+            self.enforce_limits();
+            return;
+        }
+
+        if self.raw.back().is_none_or(|last| last.tick() <= record.tick()) {
+            self.raw.push_back(record);
+        } else {
+            let index = self.raw.partition_point(|existing| existing.tick() <= record.tick());
+            self.raw.insert(index, record);
+        }
+        self.enforce_limits();
+    }
+
+    pub fn extend(&mut self, records: impl IntoIterator<Item = R>) {
+        for record in records {
+            self.push(record);
+        }
+    }
+
+    fn summarize_late(&mut self, second: u32, record: &R) {
+        let minutes_end = self.minutes.back().map_or(0, |bucket| bucket.end());
+        if second >= minutes_end {
+            summarize_into::<R>(&mut self.seconds, second, Resolution::Second, record);
+        } else if self.minutes.front().is_some_and(|first| second >= first.start) {
+            summarize_into::<R>(&mut self.minutes, second / 60 * 60, Resolution::Minute, record);
+        }
+        // Older than everything kept: already dropped
+    }
+
+    /// Drop records after `tick`
+    ///
+    /// Buckets that reach past `tick` are dropped whole; a replay re-adds their later
+    /// records as raw records.
+    pub fn truncate_after(&mut self, tick: u32) {
+        let keep = self.raw.partition_point(|record| record.tick() <= tick);
+        self.raw.truncate(keep);
+        let first_dropped_second = second_of(tick + 1, self.timestep);
+        for tier in [&mut self.seconds, &mut self.minutes] {
+            while tier.back().is_some_and(|bucket| bucket.end() > first_dropped_second) {
+                tier.pop_back();
+            }
+        }
+    }
+
+    /// Raw records sorted by tick
+    pub fn raw(&self) -> &VecDeque<R> {
+        &self.raw
+    }
+
+    /// Aggregated history, oldest first (per-minute buckets, then per-second)
+    pub fn buckets(&self) -> impl Iterator<Item = &HistoryBucket<R::Summary>> {
+        self.minutes.iter().chain(self.seconds.iter())
+    }
+
+    /// Approximate size of the stored records and buckets (element sizes, without
+    /// allocator slack)
+    pub fn memory_bytes(&self) -> usize {
+        self.raw.len() * std::mem::size_of::<R>()
+            + (self.seconds.len() + self.minutes.len()) * std::mem::size_of::<HistoryBucket<R::Summary>>()
+    }
+
+    /// Time ranges held at each resolution, oldest first
+    pub fn coverage(&self) -> Vec<HistoryRange> {
+        let mut ranges = Vec::new();
+        for tier in [&self.minutes, &self.seconds] {
+            if let (Some(first), Some(last)) = (tier.front(), tier.back()) {
+                ranges.push(HistoryRange { start: first.start as f32, end: last.end() as f32, resolution: first.resolution });
+            }
+        }
+        if let (Some(first), Some(last)) = (self.raw.front(), self.raw.back()) {
+            ranges.push(HistoryRange {
+                start: self.tick_time(first.tick()),
+                end: self.tick_time(last.tick()),
+                resolution: Resolution::Raw,
+            });
+        }
+        ranges
+    }
+
+    /// Seconds of simulated time at the newest record
+    fn newest_time(&self) -> Option<f32> {
+        match self.raw.back() {
+            Some(last) => Some(self.tick_time(last.tick())),
+            None => self.seconds.back().or(self.minutes.back()).map(|bucket| bucket.end() as f32),
+        }
+    }
+
+    /// Age records into coarser tiers, then trim to the memory budget
+    fn enforce_limits(&mut self) {
+        let Some(newest) = self.newest_time() else {
+            return;
+        };
+        let timestep = self.timestep;
+
+        while self.raw.front()
+            .is_some_and(|first| (second_of(first.tick(), timestep) + 1) as f32 <= newest - self.limits.raw_window)
+        {
+            self.fold_oldest_raw_second();
+        }
+        while self.seconds.front()
+            .is_some_and(|first| (first.start / 60 * 60 + 60) as f32 <= newest - self.limits.second_retention)
+        {
+            self.fold_oldest_minute();
+        }
+        while self.minutes.front().is_some_and(|first| first.end() as f32 <= newest - self.limits.minute_retention) {
+            self.minutes.pop_front();
+        }
+
+        while self.memory_bytes() > self.limits.budget_bytes {
+            if self.minutes.pop_front().is_some() || self.seconds.pop_front().is_some() {
+                continue;
+            }
+            if self.raw.is_empty() {
+                break;
+            }
+            self.fold_oldest_raw_second();
+        }
+    }
+
+    /// Move the raw records of the oldest second into its per-second bucket
+    fn fold_oldest_raw_second(&mut self) {
+        let Some(first) = self.raw.front() else {
+            return;
+        };
+        let timestep = self.timestep;
+        let second = second_of(first.tick(), timestep);
+        let end = self.raw.partition_point(|record| second_of(record.tick(), timestep) <= second);
+        let mut summary = R::Summary::default();
+        for record in self.raw.drain(..end) {
+            R::summarize(&mut summary, &record);
+        }
+        push_bucket::<R>(&mut self.seconds, HistoryBucket { start: second, resolution: Resolution::Second, summary });
+    }
+
+    /// Merge the per-second buckets of the oldest minute into its per-minute bucket
+    fn fold_oldest_minute(&mut self) {
+        let Some(first) = self.seconds.front() else {
+            return;
+        };
+        let minute = first.start / 60 * 60;
+        let mut summary = R::Summary::default();
+        while self.seconds.front().is_some_and(|bucket| bucket.start < minute + 60) {
+            if let Some(bucket) = self.seconds.pop_front() {
+                R::merge(&mut summary, &bucket.summary);
+            }
+        }
+        push_bucket::<R>(&mut self.minutes, HistoryBucket { start: minute, resolution: Resolution::Minute, summary });
+    }
+}
+
+fn push_bucket<R: HistoryRecord>(tier: &mut VecDeque<HistoryBucket<R::Summary>>, bucket: HistoryBucket<R::Summary>) {
+    match tier.back_mut() {
+        Some(last) if last.start == bucket.start => R::merge(&mut last.summary, &bucket.summary),
+        _ => tier.push_back(bucket),
+    }
+}
+
+fn summarize_into<R: HistoryRecord>(
+    tier: &mut VecDeque<HistoryBucket<R::Summary>>,
+    start: u32,
+    resolution: Resolution,
+    record: &R,
+) {
+    let index = tier.partition_point(|bucket| bucket.start < start);
+    match tier.get_mut(index) {
+        Some(bucket) if bucket.start == start => R::summarize(&mut bucket.summary, record),
+        _ => {
+            let mut summary = R::Summary::default();
+            R::summarize(&mut summary, record);
+            tier.insert(index, HistoryBucket { start, resolution, summary });
+        }
+    }
+}
+
+/// Tiered history stores, each given a share of the memory budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryStore {
+    /// Preview event timeline (the strip under the time slider)
+    Timeline,
+    /// Main simulation events served at `/events`
+    LiveEvents,
+    /// Population statistics plot
+    Statistics,
+}
+
+impl HistoryStore {
+    pub const ALL: [HistoryStore; 3] = [HistoryStore::Timeline, HistoryStore::LiveEvents, HistoryStore::Statistics];
+
+    /// Fraction of `HistorySettings::memory_budget_mb` the store may use
+    pub fn budget_share(self) -> f32 {
+        match self {
+            HistoryStore::Timeline => 0.5,
+            HistoryStore::LiveEvents => 0.25,
+            HistoryStore::Statistics => 0.25,
+        }
+    }
+}
+
+/// How long each history tier is kept and the memory all stores share (persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct HistorySettings {
+    pub raw_window_minutes: f32,
+    pub second_retention_minutes: f32,
+    pub minute_retention_hours: f32,
+    pub memory_budget_mb: f32,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            raw_window_minutes: 5.0,
+            second_retention_minutes: 120.0,
+            minute_retention_hours: 48.0,
+            memory_budget_mb: 64.0,
+        }
+    }
+}
+
+impl HistorySettings {
+    pub fn limits(&self, store: HistoryStore) -> HistoryLimits {
+        HistoryLimits {
+            raw_window: self.raw_window_minutes.max(0.0) * 60.0,
+            second_retention: self.second_retention_minutes.max(0.0) * 60.0,
+            minute_retention: self.minute_retention_hours.max(0.0) * 3600.0,
+            budget_bytes: (self.memory_budget_mb.max(0.0) * store.budget_share() * 1_048_576.0) as usize,
+        }
+    }
+}
+
+/// Population statistic recorded every main-simulation tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Statistic {
+    Cells,
+    Adhesions,
+}
+
+impl Statistic {
+    pub const ALL: [Statistic; 2] = [Statistic::Cells, Statistic::Adhesions];
+    pub const COUNT: usize = Self::ALL.len();
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Statistic::Cells => "Cells",
+            Statistic::Adhesions => "Adhesions",
+        }
+    }
+
+    /// CSV column prefix
+    pub fn key(self) -> &'static str {
+        match self {
+            Statistic::Cells => "cells",
+            Statistic::Adhesions => "adhesions",
+        }
+    }
+
+    fn value(self, state: &CanonicalState) -> f32 {
+        match self {
+            Statistic::Cells => state.cell_count as f32,
+            Statistic::Adhesions => state.adhesion_manager.get_active_connection_count(&state.adhesion_connections) as f32,
+        }
+    }
+}
+
+/// Values of every statistic at a tick
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatSample {
+    pub tick: u32,
+    pub values: [f32; Statistic::COUNT],
+}
+
+impl StatSample {
+    pub fn capture(tick: u32, state: &CanonicalState) -> Self {
+        Self { tick, values: Statistic::ALL.map(|statistic| statistic.value(state)) }
+    }
+}
+
+/// Count, sum and extremes of a statistic's samples
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatSummary {
+    pub count: u32,
+    pub sum: f64,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for StatSummary {
+    fn default() -> Self {
+        Self { count: 0, sum: 0.0, min: f32::INFINITY, max: f32::NEG_INFINITY }
+    }
+}
+
+impl StatSummary {
+    pub fn add(&mut self, value: f32) {
+        self.count += 1;
+        self.sum += value as f64;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn merge(&mut self, other: &StatSummary) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn mean(&self) -> Option<f32> {
+        (self.count > 0).then(|| (self.sum / self.count as f64) as f32)
+    }
+}
+
+impl HistoryRecord for StatSample {
+    type Summary = [StatSummary; Statistic::COUNT];
+
+    fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    fn summarize(summary: &mut Self::Summary, record: &Self) {
+        for (stat, value) in summary.iter_mut().zip(record.values) {
+            stat.add(value);
+        }
+    }
+
+    fn merge(summary: &mut Self::Summary, other: &Self::Summary) {
+        for (stat, other) in summary.iter_mut().zip(other) {
+            stat.merge(other);
+        }
+    }
+}
+
+/// Values of the derived metrics at a tick, NaN where a metric has no value
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricSample {
+    pub tick: u32,
+    pub values: Vec<f32>,
+}
+
+impl HistoryRecord for MetricSample {
+    type Summary = Vec<StatSummary>;
+
+    fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    fn summarize(summary: &mut Self::Summary, record: &Self) {
+        if summary.len() < record.values.len() {
+            summary.resize(record.values.len(), StatSummary::default());
+        }
+        // NaN samples are left out of the aggregates
+        for (stat, &value) in summary.iter_mut().zip(&record.values).filter(|(_, value)| !value.is_nan()) {
+            stat.add(value);
+        }
+    }
+
+    fn merge(summary: &mut Self::Summary, other: &Self::Summary) {
+        if summary.len() < other.len() {
+            summary.resize(other.len(), StatSummary::default());
+        }
+        for (stat, other) in summary.iter_mut().zip(other) {
+            stat.merge(other);
+        }
+    }
+}
+
+/// Population statistics of the main simulation over the whole run
+#[derive(Resource, Default)]
+pub struct StatisticsHistory {
+    pub history: TieredHistory<StatSample>,
+    /// Derived metrics, recorded from the tick their definitions last changed
+    pub derived: TieredHistory<MetricSample>,
+    /// Metrics whose values `derived` holds, in value order
+    pub derived_metrics: Vec<CompiledMetric>,
+    last_tick: Option<u32>,
+}
+
+impl StatisticsHistory {
+    /// Record the tick that just ran
+    pub fn record_tick(&mut self, tick: u32, state: &CanonicalState, metrics: &DerivedMetrics) {
+        // Time going backwards means a fresh or reloaded scene
+        if self.last_tick.is_some_and(|last| tick <= last) {
+            self.clear();
+        }
+        self.last_tick = Some(tick);
+        self.history.push(StatSample::capture(tick, state));
+
+        // Values of edited metrics don't mix with the old ones
+        if self.derived_metrics != metrics.metrics {
+            self.derived.clear();
+            self.derived_metrics = metrics.metrics.clone();
+        }
+        if !self.derived_metrics.is_empty() {
+            let values = metrics.evaluate(state, self.history.tick_time(tick));
+            self.derived.push(MetricSample { tick, values });
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.derived.clear();
+        self.last_tick = None;
+    }
+
+    pub fn set_timestep(&mut self, timestep: f32) {
+        self.history.set_timestep(timestep);
+        self.derived.set_timestep(timestep);
+    }
+
+    pub fn set_limits(&mut self, limits: HistoryLimits) {
+        self.history.set_limits(limits);
+        self.derived.set_limits(limits);
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        let values = self.derived.raw().len() * self.derived_metrics.len() * std::mem::size_of::<f32>();
+        self.history.memory_bytes() + self.derived.memory_bytes() + values
+    }
+
+    /// `statistic` summarized into `columns` equal slices of `[start, end)` seconds (see `plot_columns`)
+    pub fn plot_columns(&self, statistic: Statistic, start: f32, end: f32, columns: usize) -> Vec<StatSummary> {
+        let index = statistic as usize;
+        plot_columns(&self.history, start, end, columns, |summary| summary.get(index), |sample| sample.values.get(index).copied())
+    }
+
+    /// Derived metric `index` (into `derived_metrics`) like `plot_columns`; NaN samples are left out
+    pub fn plot_metric_columns(&self, index: usize, start: f32, end: f32, columns: usize) -> Vec<StatSummary> {
+        plot_columns(&self.derived, start, end, columns, |summary| summary.get(index), |sample| {
+            sample.values.get(index).copied().filter(|value| !value.is_nan())
+        })
+    }
+
+    /// Whole history as CSV, one row per bucket or raw sample, derived metrics after the built-ins
+    ///
+    /// Leading comment lines state the resolution of each time range. Derived metric
+    /// fields are empty where a metric has no value (NaN, or not recorded yet).
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for range in self.history.coverage() {
+            csv.push_str(&format!("# {:.3}s to {:.3}s: {}\n", range.start, range.end, range.resolution.name()));
+        }
+        csv.push_str("start,end,resolution,samples");
+        for statistic in Statistic::ALL {
+            let key = statistic.key();
+            csv.push_str(&format!(",{key}_mean,{key}_min,{key}_max"));
+        }
+        for metric in &self.derived_metrics {
+            let key = metric_key(&metric.name);
+            csv.push_str(&format!(",{key}_mean,{key}_min,{key}_max"));
+        }
+        csv.push('\n');
+
+        // Derived metrics are recorded alongside, so their buckets and samples line up
+        let derived_buckets: HashMap<(u32, &str), &Vec<StatSummary>> = self.derived.buckets()
+            .map(|bucket| ((bucket.start, bucket.resolution.name()), &bucket.summary))
+            .collect();
+        let derived_samples: HashMap<u32, &Vec<f32>> = self.derived.raw().iter()
+            .map(|sample| (sample.tick, &sample.values))
+            .collect();
+        let write_derived = |csv: &mut String, summaries: Option<&Vec<StatSummary>>| {
+            for index in 0..self.derived_metrics.len() {
+                match summaries.and_then(|summaries| summaries.get(index)).filter(|stat| stat.count > 0) {
+                    Some(stat) => csv.push_str(&format!(",{:.4},{},{}", stat.mean().unwrap_or(0.0), stat.min, stat.max)),
+                    None => csv.push_str(",,,"),
+                }
+            }
+        };
+
+        for bucket in self.history.buckets() {
+            csv.push_str(&format!("{},{},{},{}", bucket.start, bucket.end(), bucket.resolution.name(), bucket.summary[0].count));
+            for stat in &bucket.summary {
+                csv.push_str(&format!(",{:.4},{},{}", stat.mean().unwrap_or(0.0), stat.min, stat.max));
+            }
+            write_derived(&mut csv, derived_buckets.get(&(bucket.start, bucket.resolution.name())).copied());
+            csv.push('\n');
+        }
+        for sample in self.history.raw() {
+            let time = self.history.tick_time(sample.tick);
+            csv.push_str(&format!("{:.4},{:.4},{},1", time, time, Resolution::Raw.name()));
+            for value in sample.values {
+                csv.push_str(&format!(",{:.4},{},{}", value, value, value));
+            }
+            for index in 0..self.derived_metrics.len() {
+                match derived_samples.get(&sample.tick).and_then(|values| values.get(index)).filter(|value| !value.is_nan()) {
+                    Some(value) => csv.push_str(&format!(",{:.4},{},{}", value, value, value)),
+                    None => csv.push_str(",,,"),
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// CSV column prefix of a derived metric: its name in lower case with `_` for anything
+/// but letters and digits
+fn metric_key(name: &str) -> String {
+    let key: String = name.trim().chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    format!("metric_{}", key)
+}
+
+/// A history's values summarized into `columns` equal slices of `[start, end)` seconds
+///
+/// Stitches the tiers: an aggregate joins every column its time span overlaps (so
+/// minute buckets still fill a fine plot), a raw sample the column of its time.
+fn plot_columns<R: HistoryRecord>(
+    history: &TieredHistory<R>,
+    start: f32,
+    end: f32,
+    columns: usize,
+    bucket_summary: impl Fn(&R::Summary) -> Option<&StatSummary>,
+    raw_value: impl Fn(&R) -> Option<f32>,
+) -> Vec<StatSummary> {
+    let mut result = vec![StatSummary::default(); columns];
+    let span = end - start;
+    if columns == 0 || span <= 0.0 {
+        return result;
+    }
+    let column_at = |time: f32| ((time - start) / span * columns as f32).clamp(0.0, columns as f32);
+
+    for bucket in history.buckets() {
+        let Some(summary) = bucket_summary(&bucket.summary) else {
+            continue;
+        };
+        let first = column_at(bucket.start as f32).floor() as usize;
+        let last = (column_at(bucket.end() as f32).ceil() as usize).max(first + 1).min(columns);
+        for column in &mut result[first.min(columns)..last] {
+            column.merge(summary);
+        }
+    }
+    for sample in history.raw() {
+        let time = history.tick_time(sample.tick());
+        if let Some(value) = raw_value(sample).filter(|_| (start..=end).contains(&time)) {
+            result[(column_at(time) as usize).min(columns - 1)].add(value);
+        }
+    }
+    result
+}
+
+fn record_statistics(
+    main_state: Res<MainSimState>,
+    config: Res<PhysicsConfig>,
+    metrics: Res<DerivedMetrics>,
+    mut statistics: ResMut<StatisticsHistory>,
+    mut last_time: Local<Option<f32>>,
+) {
+    // Only record ticks that ran (no cells, paused)
+    let time = main_state.simulation_time;
+    if last_time.replace(time) == Some(time) {
+        return;
+    }
+    statistics.set_timestep(config.fixed_timestep);
+    let tick = crate::simulation::clock::ticks_to_reach(time, config.fixed_timestep);
+    statistics.record_tick(tick, &main_state.canonical_state, &metrics);
+}
+
+/// Hand the configured limits to every store
+fn apply_history_settings(
+    settings: Res<HistorySettings>,
+    mut timeline: ResMut<EventTimeline>,
+    mut live_events: ResMut<LiveEventLog>,
+    mut statistics: ResMut<StatisticsHistory>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    timeline.set_limits(settings.limits(HistoryStore::Timeline));
+    live_events.set_limits(settings.limits(HistoryStore::LiveEvents));
+    statistics.set_limits(settings.limits(HistoryStore::Statistics));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::event_timeline::{TimelineEvent, TimelineEventKind};
+
+    const TIMESTEP: f32 = 1.0 / 64.0;
+
+    fn limits(raw_window: f32, second_retention: f32, minute_retention: f32, budget_bytes: usize) -> HistoryLimits {
+        HistoryLimits { raw_window, second_retention, minute_retention, budget_bytes }
+    }
+
+    fn sample(tick: u32) -> StatSample {
+        // A sawtooth, so minimums and maximums land inside buckets
+        let value = (tick % 97) as f32;
+        StatSample { tick, values: [value, value * 2.0 + 1.0] }
+    }
+
+    fn total<'a>(summaries: impl Iterator<Item = &'a StatSummary>) -> StatSummary {
+        let mut total = StatSummary::default();
+        summaries.for_each(|summary| total.merge(summary));
+        total
+    }
+
+    #[test]
+    fn test_roll_up_preserves_counts_sums_and_extremes() {
+        let mut history = TieredHistory::new(TIMESTEP, limits(10.0, 90.0, 3600.0, usize::MAX));
+        let ticks = 64 * 300;
+        let mut expected = [StatSummary::default(); Statistic::COUNT];
+        for tick in 0..ticks {
+            let record = sample(tick);
+            StatSample::summarize(&mut expected, &record);
+            history.push(record);
+        }
+
+        // Every tier is in use and they follow each other in time
+        let coverage = history.coverage();
+        let resolutions: Vec<Resolution> = coverage.iter().map(|range| range.resolution).collect();
+        assert_eq!(resolutions, vec![Resolution::Minute, Resolution::Second, Resolution::Raw]);
+        assert!(coverage.windows(2).all(|pair| pair[0].end <= pair[1].start + TIMESTEP));
+        assert!(history.raw().len() <= 64 * 11);
+
+        for stat in 0..Statistic::COUNT {
+            let mut kept = total(history.buckets().map(|bucket| &bucket.summary[stat]));
+            for record in history.raw() {
+                kept.add(record.values[stat]);
+            }
+            assert_eq!(kept.count, expected[stat].count);
+            assert!((kept.sum - expected[stat].sum).abs() < 1e-6);
+            assert_eq!(kept.min, expected[stat].min);
+            assert_eq!(kept.max, expected[stat].max);
+        }
+
+        // Each minute bucket holds exactly the samples of its minute
+        let first_minute = history.buckets().next().unwrap();
+        assert_eq!((first_minute.start, first_minute.resolution), (0, Resolution::Minute));
+        let mut direct = StatSummary::default();
+        (0..64 * 60).for_each(|tick| direct.add(sample(tick).values[0]));
+        assert_eq!(first_minute.summary[0].count, direct.count);
+        assert_eq!(first_minute.summary[0].mean(), direct.mean());
+        assert_eq!(first_minute.summary[0].max, direct.max);
+    }
+
+    #[test]
+    fn test_event_counts_roll_up_and_late_events_join_their_bucket() {
+        let mut history: TieredHistory<TimelineEvent> = TieredHistory::new(0.5, limits(2.0, 120.0, 3600.0, usize::MAX));
+        for tick in 0..20 {
+            history.push(TimelineEvent::new(tick, TimelineEventKind::Division, [None, None]));
+        }
+        // Raw records only cover the seconds within two of the newest event
+        assert_eq!(history.raw().front().map(|event| event.tick), Some(14));
+        assert_eq!(history.buckets().map(|bucket| bucket.summary[TimelineEventKind::Division.index()]).sum::<u32>(), 14);
+
+        history.push(TimelineEvent::new(3, TimelineEventKind::Death, [None, None]));
+        let bucket = history.buckets().find(|bucket| bucket.start == 1).unwrap();
+        assert_eq!(bucket.summary[TimelineEventKind::Death.index()], 1);
+        assert_eq!(bucket.summary[TimelineEventKind::Division.index()], 2);
+    }
+
+    #[test]
+    fn test_plot_columns_stitch_aggregates_and_raw_samples() {
+        let mut statistics = StatisticsHistory::default();
+        statistics.history = TieredHistory::new(TIMESTEP, limits(30.0, 120.0, 3600.0, usize::MAX));
+        for tick in 0..64 * 240 {
+            statistics.history.push(StatSample { tick, values: [1.0, tick as f32] });
+        }
+        // The first minute is one bucket but still fills its six columns
+        let columns = statistics.plot_columns(Statistic::Cells, 0.0, 240.0, 24);
+        assert!(columns.iter().all(|column| column.count > 0 && column.mean() == Some(1.0)));
+        let ramp = statistics.plot_columns(Statistic::Adhesions, 0.0, 240.0, 24);
+        assert!(ramp.windows(2).all(|pair| pair[0].max <= pair[1].max));
+        assert_eq!(ramp[23].max, (64 * 240 - 1) as f32);
+
+        let csv = statistics.to_csv();
+        assert!(csv.starts_with("# 0.000s to 60.000s: per-minute\n"));
+        assert!(csv.contains(": per-second\n") && csv.contains(": raw\n"));
+        assert!(csv.contains("\n0,60,per-minute,3840,1.0000,1,1,"));
+    }
+
+    #[test]
+    fn test_derived_metrics_leave_nan_out_and_export_alongside() {
+        use crate::simulation::derived_metrics::{DivisionByZero, Expr};
+        let metric = |id: u32, name: &str, source: &str| CompiledMetric {
+            id,
+            name: name.to_string(),
+            expr: Expr::compile(source, 1).unwrap(),
+            division_by_zero: DivisionByZero::Nan,
+        };
+        let metrics = DerivedMetrics { metrics: vec![metric(0, "Half time", "time / 2"), metric(1, "Per cell", "1 / cells")], mode_count: 1 };
+        let mut statistics = StatisticsHistory::default();
+        statistics.set_timestep(0.5);
+        // No cells, so "Per cell" divides by zero at every tick
+        let state = CanonicalState::new(4);
+        for tick in 0..4 {
+            statistics.record_tick(tick, &state, &metrics);
+        }
+
+        let half_time = statistics.plot_metric_columns(0, 0.0, 2.0, 4);
+        assert_eq!(half_time.iter().map(|column| column.max).collect::<Vec<_>>(), vec![0.0, 0.25, 0.5, 0.75]);
+        assert!(statistics.plot_metric_columns(1, 0.0, 2.0, 4).iter().all(|column| column.count == 0));
+
+        let csv = statistics.to_csv();
+        assert!(csv.contains(",metric_half_time_mean,metric_half_time_min,metric_half_time_max,metric_per_cell_mean,"));
+        assert!(csv.contains("\n1.5000,1.5000,raw,1,0.0000,0,0,0.0000,0,0,0.7500,0.75,0.75,,,\n"));
+
+        // Editing the metrics starts their history over
+        let edited = DerivedMetrics { metrics: vec![metric(0, "Half time", "time / 4")], mode_count: 1 };
+        statistics.record_tick(4, &state, &edited);
+        assert_eq!(statistics.derived.raw().len(), 1);
+        assert_eq!(statistics.history.raw().len(), 5);
+    }
+
+    #[test]
+    fn test_truncate_drops_buckets_reaching_past_the_tick() {
+        let mut history = TieredHistory::new(0.5, limits(1.0, 120.0, 3600.0, usize::MAX));
+        for tick in 0..12 {
+            history.push(sample(tick));
+        }
+        history.truncate_after(4);
+        assert!(history.raw().is_empty());
+        // Second 2 holds tick 5, so it goes whole
+        assert_eq!(history.buckets().map(|bucket| bucket.start).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_day_of_events_stays_within_the_memory_budget() {
+        let budget = 256 * 1024;
+        let mut history: TieredHistory<TimelineEvent> =
+            TieredHistory::new(TIMESTEP, HistorySettings::default().limits(HistoryStore::Timeline));
+        history.set_limits(HistoryLimits { budget_bytes: budget, ..history.limits() });
+
+        // A division or death every few ticks for 24 simulated hours
+        let ticks = 24 * 3600 * 64;
+        let mut peak = 0;
+        for tick in (0..ticks).step_by(7) {
+            let kind = if tick % 3 == 0 { TimelineEventKind::Death } else { TimelineEventKind::Division };
+            history.push(TimelineEvent::new(tick, kind, [Some(tick), None]));
+            peak = peak.max(history.memory_bytes());
+        }
+
+        assert!(peak <= budget, "peak {} bytes over a {} byte budget", peak, budget);
+        // The newest history survives at full resolution; the oldest aggregates were dropped
+        let coverage = history.coverage();
+        let raw = coverage.last().unwrap();
+        assert_eq!(raw.resolution, Resolution::Raw);
+        assert!(raw.end > 24.0 * 3600.0 - 1.0);
+        assert!(coverage[0].start > 0.0);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::simulation::PhysicsConfig;
 use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::memory::MemoryProfile;
 
 /// Immutable initial state for deterministic replay
 /// 
@@ -29,6 +30,9 @@ pub struct InitialState {
     
     /// Spatial grid density (NxNxN cells). Valid range: 16-128
     pub grid_density: u32,
+    
+    /// Allocation strategy for the canonical state built from this initial state
+    pub memory_profile: MemoryProfile,
 }
 
 /// Initial state for a single cell
@@ -75,6 +79,12 @@ pub struct InitialCell {
     
     /// Cytoskeleton stiffness (affects collision response)
     pub stiffness: f32,
+    
+    /// Held in place (kinematic anchor)
+    pub pinned: bool,
+    
+    /// User data channels
+    pub user_data: [f32; crate::simulation::user_data::USER_DATA_CHANNELS],
 }
 
 impl InitialState {
@@ -95,6 +105,7 @@ impl InitialState {
             rng_seed,
             created_at: 0.0, // Will be set when actually created
             grid_density: 64, // Default grid density
+            memory_profile: MemoryProfile::Full,
         }
     }
     
@@ -107,6 +118,7 @@ impl InitialState {
             rng_seed,
             created_at: 0.0,
             grid_density,
+            memory_profile: MemoryProfile::Full,
         }
     }
     
@@ -126,11 +138,11 @@ impl InitialState {
     /// # Returns
     /// A new CanonicalState initialized from this initial state
     pub fn to_canonical_state(&self) -> CanonicalState {
-        let mut state = CanonicalState::with_grid_density(self.max_cells, self.grid_density);
+        let mut state = CanonicalState::with_memory_profile(self.max_cells, self.grid_density, self.memory_profile);
         
         // Add all initial cells to the canonical state
         for cell in &self.initial_cells {
-            state.add_cell(
+            let idx = state.add_cell(
                 cell.position,
                 cell.velocity,
                 cell.rotation,
@@ -146,6 +158,10 @@ impl InitialState {
                 cell.rotation, // genome_orientation = rotation initially
                 0, // Initial cells start with split_count = 0
             );
+            if let Some(idx) = idx {
+                state.set_pinned(idx, cell.pinned);
+                state.set_user_data(idx, cell.user_data);
+            }
         }
         
         state
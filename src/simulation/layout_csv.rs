@@ -0,0 +1,430 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::scene_file::{SceneCell, SCENE_FORMAT_VERSION};
+use crate::simulation::{InitialCell, PhysicsConfig, SceneFile, SimulationMode, SimulationState};
+use crate::ui::windows::scene_manager::SceneModeRequest;
+
+/// Header written by the exporter; a first line starting with `x` is skipped on import
+pub const LAYOUT_CSV_HEADER: &str = "x,y,z,mode,mass,qx,qy,qz,qw";
+
+/// Mass of imported cells without a mass column
+const DEFAULT_MASS: f32 = 1.0;
+
+/// Quaternions further than this from unit length are normalized on import
+const NORMALIZE_TOLERANCE: f32 = 1e-4;
+
+const GHOST_COLOR: Color = Color::srgba(0.5, 0.85, 1.0, 0.6);
+const GHOST_ERROR_COLOR: Color = Color::srgba(1.0, 0.3, 0.3, 0.8);
+
+/// Plugin that imports initial layouts from CSV files and exports them back
+pub struct LayoutCsvPlugin;
+
+impl Plugin for LayoutCsvPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (process_layout_requests, draw_layout_ghosts).chain());
+    }
+}
+
+/// One cell row of a layout file; columns left out or empty are `None`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutRow {
+    /// Line in the file (1-based)
+    pub line: usize,
+    pub position: Vec3,
+    pub mode: Option<usize>,
+    pub mass: Option<f32>,
+    pub rotation: Option<Quat>,
+}
+
+/// Problem with a layout file; line and column are 1-based and `None` for the file as a whole
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl LayoutError {
+    fn at(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        Self { line: Some(line), column: Some(column), reason: reason.into() }
+    }
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: {}", line, column, self.reason),
+            (Some(line), None) => write!(f, "line {}: {}", line, self.reason),
+            _ => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+/// Parse `x,y,z[,mode][,mass][,qx,qy,qz,qw]` rows
+///
+/// A byte order mark, CRLF line endings, whitespace around fields, blank lines,
+/// `#` comments and a header line are accepted. Every malformed row is reported.
+pub fn parse_layout_csv(text: &str) -> Result<Vec<LayoutRow>, Vec<LayoutError>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut first = true;
+
+    for (index, line) in text.lines().enumerate() {
+        let content = line.trim();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = content.split(',').map(str::trim).collect();
+        if std::mem::take(&mut first) && fields[0].eq_ignore_ascii_case("x") {
+            continue;
+        }
+        match parse_row(index + 1, &fields) {
+            Ok(row) => rows.push(row),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() { Ok(rows) } else { Err(errors) }
+}
+
+fn parse_row(line: usize, fields: &[&str]) -> Result<LayoutRow, LayoutError> {
+    if !matches!(fields.len(), 3 | 4 | 5 | 9) {
+        return Err(LayoutError {
+            line: Some(line),
+            column: None,
+            reason: format!("expected 3, 4, 5 or 9 columns, found {}", fields.len()),
+        });
+    }
+    let field = |column: usize| fields.get(column - 1).copied().filter(|field| !field.is_empty());
+    let number = |column: usize| -> Result<Option<f32>, LayoutError> {
+        field(column).map(|text| match text.parse::<f32>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            _ => Err(LayoutError::at(line, column, format!("'{}' is not a finite number", text))),
+        }).transpose()
+    };
+    let required = |column: usize| number(column)?.ok_or_else(|| LayoutError::at(line, column, "missing value"));
+
+    let position = Vec3::new(required(1)?, required(2)?, required(3)?);
+    let mode = field(4).map(|text| text.parse::<usize>()
+        .map_err(|_| LayoutError::at(line, 4, format!("'{}' is not a mode index", text))))
+        .transpose()?;
+    let mass = number(5)?;
+    if mass.is_some_and(|mass| mass <= 0.0) {
+        return Err(LayoutError::at(line, 5, "mass must be positive"));
+    }
+    let rotation = if fields.len() == 9 {
+        let quat = Quat::from_xyzw(required(6)?, required(7)?, required(8)?, required(9)?);
+        let length = quat.length();
+        if length < NORMALIZE_TOLERANCE {
+            return Err(LayoutError::at(line, 6, "orientation quaternion has zero length"));
+        }
+        // Exported quaternions are kept bit for bit
+        Some(if (length - 1.0).abs() > NORMALIZE_TOLERANCE { quat / length } else { quat })
+    } else {
+        None
+    };
+
+    Ok(LayoutRow { line, position, mode, mass, rotation })
+}
+
+/// Check parsed rows against the genome, the world sphere and the free cell slots
+pub fn validate_layout(rows: &[LayoutRow], mode_count: usize, sphere_radius: f32, free_slots: usize) -> Vec<LayoutError> {
+    let mut errors = Vec::new();
+    if rows.is_empty() {
+        errors.push(LayoutError { line: None, column: None, reason: "the file has no cell rows".to_string() });
+    }
+    if rows.len() > free_slots {
+        errors.push(LayoutError {
+            line: None,
+            column: None,
+            reason: format!("{} cells don't fit in the {} free cell slots", rows.len(), free_slots),
+        });
+    }
+    for row in rows {
+        if let Some(mode) = row.mode.filter(|&mode| mode >= mode_count) {
+            errors.push(LayoutError::at(row.line, 4, format!("mode {} out of range (the genome has {} modes)", mode, mode_count)));
+        }
+        if row.position.length() > sphere_radius {
+            errors.push(LayoutError::at(row.line, 1, format!("position is outside the world sphere (radius {})", sphere_radius)));
+        }
+    }
+    errors
+}
+
+/// Scene cells of the rows in file order, defaulting to the initial mode, unit mass
+/// and identity orientation
+pub fn scene_cells(rows: &[LayoutRow], genome: &GenomeData) -> Vec<SceneCell> {
+    rows.iter()
+        .map(|row| SceneCell {
+            position: row.position,
+            velocity: Vec3::ZERO,
+            rotation: row.rotation.unwrap_or(Quat::IDENTITY),
+            mode_index: row.mode.unwrap_or_else(|| genome.initial_mode_index()),
+            mass: Some(row.mass.unwrap_or(DEFAULT_MASS)),
+            radius: 1.0,
+            pinned: false,
+            user_data: Default::default(),
+        })
+        .collect()
+}
+
+/// Initial cells in the CSV format read by `parse_layout_csv`
+pub fn export_layout_csv(cells: &[InitialCell]) -> String {
+    let mut csv = format!("{}\n", LAYOUT_CSV_HEADER);
+    for cell in cells {
+        let (p, q) = (cell.position, cell.rotation);
+        // Display of f32 is the shortest text that parses back to the same value
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            p.x, p.y, p.z, cell.mode_index, cell.mass, q.x, q.y, q.z, q.w
+        ));
+    }
+    csv
+}
+
+/// Layout import edited in the Scene Manager
+#[derive(Clone, Default)]
+pub struct LayoutImport {
+    /// File the rows were read from
+    pub path: Option<PathBuf>,
+    /// Rows that parsed
+    pub rows: Vec<LayoutRow>,
+    /// Parse errors and problems found by the last validation
+    pub errors: Vec<LayoutError>,
+    /// Append to the preview's initial layout instead of replacing it
+    pub merge: bool,
+    /// Draw the rows as ghost spheres before importing
+    pub dry_run: bool,
+    pub pick_requested: bool,
+    pub import_requested: bool,
+    pub export_requested: bool,
+    /// Parse errors of the picked file, kept apart from the revalidated problems
+    parse_errors: Vec<LayoutError>,
+}
+
+impl LayoutImport {
+    /// Replace the rows with those of a file's text
+    pub fn load(&mut self, path: PathBuf, text: &str) {
+        self.path = Some(path);
+        (self.rows, self.parse_errors) = match parse_layout_csv(text) {
+            Ok(rows) => (rows, Vec::new()),
+            Err(errors) => (Vec::new(), errors),
+        };
+    }
+
+    /// Whether a line of the file has a problem
+    pub fn has_error(&self, line: usize) -> bool {
+        self.errors.iter().any(|error| error.line == Some(line))
+    }
+
+    pub fn can_import(&self) -> bool {
+        !self.rows.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Pick, validate, import and export layout files for the Scene Manager
+///
+/// Imports reach the preview as a scene through `SceneModeRequest::requested_scene`,
+/// so they load like any other scene.
+#[allow(clippy::too_many_arguments)]
+fn process_layout_requests(
+    mut scene_request: ResMut<SceneModeRequest>,
+    sim_state: Res<SimulationState>,
+    preview_state: Res<crate::simulation::preview_sim::PreviewSimState>,
+    main_state: Res<crate::simulation::cpu_sim::MainSimState>,
+    genome: Res<CurrentGenome>,
+    physics: Res<PhysicsConfig>,
+    physics_layers: Res<crate::simulation::PhysicsLayers>,
+    groups: Res<crate::simulation::CellGroups>,
+    regions: Res<crate::rendering::RegionVisibility>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    let request = &mut scene_request.layout_import;
+
+    if std::mem::take(&mut request.pick_requested) {
+        if let Some(path) = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]).pick_file() {
+            match crate::error::read_to_string(&path) {
+                Ok(text) => request.load(path, &text),
+                Err(e) => notifications.error(&e),
+            }
+        }
+    }
+
+    if std::mem::take(&mut request.export_requested) {
+        let initial_state = match sim_state.mode {
+            SimulationMode::Cpu => &main_state.initial_state,
+            _ => &preview_state.initial_state,
+        };
+        if let Some(path) = rfd::FileDialog::new().add_filter("CSV", &["csv"]).set_file_name("layout.csv").save_file() {
+            let csv = export_layout_csv(&initial_state.initial_cells);
+            match crate::error::write_atomically(&path, csv.as_bytes()) {
+                Ok(()) => notifications.success(format!(
+                    "Exported {} cells to {}", initial_state.initial_cells.len(), path.display()
+                )),
+                Err(e) => notifications.error(&e),
+            }
+        }
+    }
+
+    if request.path.is_none() {
+        return;
+    }
+    let existing = &preview_state.initial_state;
+    let free_slots = if request.merge {
+        existing.max_cells.saturating_sub(existing.initial_cells.len())
+    } else {
+        existing.max_cells
+    };
+    let mut errors = request.parse_errors.clone();
+    if errors.is_empty() {
+        errors = validate_layout(&request.rows, genome.genome.modes.len(), physics.sphere_radius, free_slots);
+    }
+    if request.errors != errors {
+        request.errors = errors;
+    }
+
+    if !std::mem::take(&mut request.import_requested) {
+        return;
+    }
+    if !request.can_import() {
+        notifications.warning(format!("Can't import the layout: {} problems", request.errors.len()));
+        return;
+    }
+
+    let mut initial_cells: Vec<SceneCell> = Vec::new();
+    let mut scene_groups = Vec::new();
+    if request.merge {
+        initial_cells.extend(existing.initial_cells.iter().map(|cell| SceneCell {
+            position: cell.position,
+            velocity: cell.velocity,
+            rotation: cell.rotation,
+            mode_index: cell.mode_index,
+            mass: Some(cell.mass),
+            radius: cell.radius,
+            pinned: cell.pinned,
+            user_data: cell.user_data,
+        }));
+        scene_groups = groups.scene_groups(&existing.to_canonical_state());
+    }
+    initial_cells.extend(scene_cells(&request.rows, &genome.genome));
+
+    let name = request.path.as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let imported = request.rows.len();
+    let scene = SceneFile {
+        format_version: SCENE_FORMAT_VERSION,
+        description: format!("{} cells imported from {}", imported, name),
+        genome: genome.genome.clone(),
+        physics: physics.clone(),
+        // Loaded into the current physics, so the scene layer stays as it is
+        physics_overrides: Some(physics_layers.scene_layer_for(&physics)),
+        initial_cells,
+        camera: None,
+        rng_seed: existing.rng_seed,
+        groups: scene_groups,
+        regions: regions.scene_settings(),
+        interventions: Default::default(),
+    };
+    info!("Importing {} layout cells from {} ({})", imported, name, if request.merge { "merged" } else { "replacing" });
+    notifications.success(format!("Imported {} cells from {}", imported, name));
+    request.dry_run = false;
+    scene_request.requested_scene = Some((name, scene));
+}
+
+/// Ghost spheres of a dry-run import; rows with a problem are drawn red
+fn draw_layout_ghosts(mut gizmos: Gizmos, scene_request: Res<SceneModeRequest>) {
+    let layout = &scene_request.layout_import;
+    if !layout.dry_run {
+        return;
+    }
+    for row in &layout.rows {
+        let color = if layout.has_error(row.line) { GHOST_ERROR_COLOR } else { GHOST_COLOR };
+        let rotation = row.rotation.unwrap_or(Quat::IDENTITY);
+        gizmos.sphere(Isometry3d::new(row.position, rotation), 1.0, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(id: u32, position: Vec3, mode_index: usize, mass: f32, rotation: Quat) -> InitialCell {
+        InitialCell {
+            id,
+            position,
+            velocity: Vec3::ZERO,
+            rotation,
+            angular_velocity: Vec3::ZERO,
+            mass,
+            radius: 1.0,
+            genome_id: 0,
+            mode_index,
+            birth_time: 0.0,
+            split_interval: 5.0,
+            split_mass: 1.5,
+            stiffness: 10.0,
+            pinned: false,
+            user_data: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_bom_crlf_and_whitespace() {
+        let text = "\u{feff}x, y, z, mode\r\n 1.5 ,-2, 3 ,1\r\n\r\n# comment\r\n0,0,0\r\n";
+        let rows = parse_layout_csv(text).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].position, Vec3::new(1.5, -2.0, 3.0));
+        assert_eq!(rows[0].mode, Some(1));
+        assert_eq!(rows[1].line, 5);
+        assert_eq!((rows[1].mode, rows[1].mass, rows[1].rotation), (None, None, None));
+    }
+
+    #[test]
+    fn test_errors_are_located() {
+        let errors = parse_layout_csv("1,2\n1,two,3\n1,2,3,,-1\n0,0,0,0,1,0,0,0,0\n").unwrap_err();
+        let located: Vec<_> = errors.iter().map(|error| (error.line, error.column)).collect();
+        assert_eq!(located, vec![(Some(1), None), (Some(2), Some(2)), (Some(3), Some(5)), (Some(4), Some(6))]);
+    }
+
+    #[test]
+    fn test_out_of_range_mode_and_sphere() {
+        let rows = parse_layout_csv("0,0,0,2\n0,0,0,1\n200,0,0\n").unwrap();
+        let errors = validate_layout(&rows, 2, 100.0, 10);
+        assert_eq!(errors, vec![
+            LayoutError::at(1, 4, "mode 2 out of range (the genome has 2 modes)"),
+            LayoutError::at(3, 1, "position is outside the world sphere (radius 100)"),
+        ]);
+        assert_eq!(validate_layout(&rows, 3, 300.0, 2).len(), 1);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let rows = parse_layout_csv("1,2,3\n").unwrap();
+        let genome = GenomeData::default();
+        let cells = scene_cells(&rows, &genome);
+        assert_eq!(cells[0].mode_index, genome.initial_mode_index());
+        assert_eq!(cells[0].mass, Some(DEFAULT_MASS));
+        assert_eq!(cells[0].rotation, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cells = vec![
+            cell(0, Vec3::new(0.1, -3.333_333_3, 1e-7), 0, 1.234_567_9, Quat::from_euler(EulerRot::XYZ, 0.3, 1.1, -2.0)),
+            cell(1, Vec3::new(-42.5, 17.0, 0.0), 2, 0.5, Quat::IDENTITY),
+        ];
+        let rows = parse_layout_csv(&export_layout_csv(&cells)).unwrap();
+        assert_eq!(rows.len(), cells.len());
+        for (row, cell) in rows.iter().zip(&cells) {
+            assert_eq!(row.position, cell.position);
+            assert_eq!(row.mode, Some(cell.mode_index));
+            assert_eq!(row.mass, Some(cell.mass));
+            assert_eq!(row.rotation, Some(cell.rotation));
+        }
+    }
+}
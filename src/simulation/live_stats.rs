@@ -0,0 +1,701 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::genome::GenomeData;
+use crate::simulation::cpu_sim::{CpuSceneState, MainSimState};
+use crate::simulation::event_timeline::{TimelineEvent, TimelineEventKind, TimelineRecorder};
+use crate::simulation::history::{HistoryLimits, HistoryRange, TieredHistory};
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{CanonicalState, EventTimeline, PhysicsConfig, SimulationMode, SimulationState};
+
+/// Events served by `/events` before the oldest are dropped
+pub const LIVE_EVENT_CAPACITY: usize = 4096;
+
+/// Seconds between snapshot refreshes
+pub const LIVE_STATS_PUBLISH_INTERVAL: f32 = 0.25;
+
+/// Whether this build contains the HTTP server (`--features http-export`)
+pub const LIVE_STATS_AVAILABLE: bool = cfg!(feature = "http-export");
+
+/// Plugin for the read-only JSON endpoint external dashboards poll
+///
+/// The server thread only ever reads the latest published snapshot; the
+/// simulation never waits on it.
+pub struct LiveStatsPlugin;
+
+impl Plugin for LiveStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LiveStatsSettings>()
+            .init_resource::<LiveStats>()
+            .init_resource::<LiveEventLog>()
+            .add_systems(
+                FixedUpdate,
+                collect_live_events
+                    .after(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(CpuSceneState::Active)),
+            )
+            .add_systems(Update, (
+                sync_live_stats_server,
+                publish_live_stats.after(sync_live_stats_server),
+                stop_live_stats_server_on_exit,
+            ));
+    }
+}
+
+/// Where the live stats server listens (persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct LiveStatsSettings {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+}
+
+impl Default for LiveStatsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // Loopback only until the user opts into serving other machines
+            bind_address: "127.0.0.1".to_string(),
+            port: 8765,
+        }
+    }
+}
+
+impl LiveStatsSettings {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.bind_address, self.port)
+    }
+}
+
+/// Cells of one genome mode
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ModePopulation {
+    pub mode: usize,
+    pub name: String,
+    pub count: usize,
+}
+
+/// Body of `/stats`
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct LiveStatsReport {
+    /// "cpu", "preview" or "gpu"
+    pub mode: &'static str,
+    pub tick: u32,
+    pub time: f32,
+    pub cell_count: usize,
+    pub adhesion_count: usize,
+    pub fps: f32,
+    pub populations: Vec<ModePopulation>,
+}
+
+/// Body of `/fingerprint`
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct FingerprintReport {
+    pub tick: u32,
+    /// Hex digest of the state (see `CanonicalState::fingerprint`); null without a simulation
+    pub fingerprint: Option<String>,
+}
+
+/// Division, death or adhesion break served by `/events`
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct LiveEvent {
+    pub tick: u32,
+    /// "division", "death" or "adhesion_break"
+    pub kind: &'static str,
+    /// Cell IDs involved (children for divisions, both ends for adhesion breaks)
+    pub cells: [Option<u32>; 2],
+}
+
+/// JSON key of the event kinds the endpoint serves
+pub fn event_kind_key(kind: TimelineEventKind) -> Option<&'static str> {
+    match kind {
+        TimelineEventKind::Division => Some("division"),
+        TimelineEventKind::Death => Some("death"),
+        TimelineEventKind::AdhesionBreak => Some("adhesion_break"),
+        _ => None,
+    }
+}
+
+/// Time range of the event history and the resolution it is kept at
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct EventRange {
+    /// Seconds of simulated time
+    pub start: f32,
+    pub end: f32,
+    /// "raw", "per-second" or "per-minute" (only raw events are listed)
+    pub resolution: &'static str,
+}
+
+impl From<HistoryRange> for EventRange {
+    fn from(range: HistoryRange) -> Self {
+        Self { start: range.start, end: range.end, resolution: range.resolution.name() }
+    }
+}
+
+/// Everything the endpoints serve, captured a few times per second
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiveSnapshot {
+    pub stats: LiveStatsReport,
+    pub fingerprint: FingerprintReport,
+    /// Oldest first
+    pub events: Vec<LiveEvent>,
+    /// Resolution of the event history by time range, oldest first
+    pub event_ranges: Vec<EventRange>,
+}
+
+impl LiveSnapshot {
+    /// Capture a simulation state at `tick`
+    pub fn capture<'a>(
+        mode: SimulationMode,
+        state: &CanonicalState,
+        genome: &GenomeData,
+        tick: u32,
+        time: f32,
+        fps: f32,
+        events: impl IntoIterator<Item = &'a TimelineEvent>,
+    ) -> Self {
+        let mut counts = vec![0usize; genome.modes.len()];
+        for &mode_index in &state.mode_indices[..state.cell_count] {
+            if mode_index >= counts.len() {
+                counts.resize(mode_index + 1, 0);
+            }
+            counts[mode_index] += 1;
+        }
+        let populations = counts
+            .into_iter()
+            .enumerate()
+            .map(|(mode, count)| ModePopulation {
+                mode,
+                name: genome.modes.get(mode).map(|m| m.name.clone()).unwrap_or_default(),
+                count,
+            })
+            .collect();
+
+        let mut events: Vec<LiveEvent> = events
+            .into_iter()
+            .filter(|event| event.tick <= tick)
+            .filter_map(|event| event_kind_key(event.kind).map(|kind| LiveEvent { tick: event.tick, kind, cells: event.cells }))
+            .collect();
+        let excess = events.len().saturating_sub(LIVE_EVENT_CAPACITY);
+        events.drain(..excess);
+
+        Self {
+            stats: LiveStatsReport {
+                mode: mode_key(mode),
+                tick,
+                time,
+                cell_count: state.cell_count,
+                adhesion_count: state.adhesion_connections.active_count,
+                fps,
+                populations,
+            },
+            fingerprint: FingerprintReport {
+                tick,
+                fingerprint: Some(format!("{:016x}", state.fingerprint())),
+            },
+            events,
+            event_ranges: Vec::new(),
+        }
+    }
+
+    /// Snapshot of a mode without a CPU-side state
+    pub fn idle(mode: SimulationMode, fps: f32) -> Self {
+        Self {
+            stats: LiveStatsReport { mode: mode_key(mode), fps, ..LiveStatsReport::default() },
+            ..Self::default()
+        }
+    }
+}
+
+fn mode_key(mode: SimulationMode) -> &'static str {
+    match mode {
+        SimulationMode::Cpu => "cpu",
+        SimulationMode::Preview => "preview",
+        SimulationMode::Gpu => "gpu",
+    }
+}
+
+/// Answer a request for `url` (path and query) with a status code and JSON body
+pub fn respond(url: &str, snapshot: &LiveSnapshot) -> (u16, String) {
+    #[derive(Serialize)]
+    struct EventsBody<'a> {
+        since: Option<u32>,
+        latest_tick: u32,
+        ranges: &'a [EventRange],
+        events: &'a [LiveEvent],
+    }
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let body = match path.trim_end_matches('/') {
+        "/stats" => serde_json::to_string(&snapshot.stats),
+        "/fingerprint" => serde_json::to_string(&snapshot.fingerprint),
+        "/events" => {
+            let since = query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == "since")
+                .map(|(_, value)| value.parse::<u32>());
+            let since = match since {
+                Some(Ok(since)) => Some(since),
+                Some(Err(_)) => return error_response(400, "since must be a tick number"),
+                None => None,
+            };
+            let first = since.map_or(0, |since| snapshot.events.partition_point(|event| event.tick <= since));
+            serde_json::to_string(&EventsBody {
+                since,
+                latest_tick: snapshot.stats.tick,
+                ranges: &snapshot.event_ranges,
+                events: &snapshot.events[first..],
+            })
+        }
+        _ => return error_response(404, "unknown endpoint (try /stats, /events?since=<tick> or /fingerprint)"),
+    };
+    match body {
+        Ok(body) => (200, body),
+        Err(e) => error_response(500, &e.to_string()),
+    }
+}
+
+fn error_response(status: u16, message: &str) -> (u16, String) {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+/// Latest snapshot, swapped whole so readers never hold the lock while serializing
+pub type SharedSnapshot = Arc<Mutex<Arc<LiveSnapshot>>>;
+
+/// Clone the latest snapshot out of `shared`
+pub fn latest_snapshot(shared: &SharedSnapshot) -> Arc<LiveSnapshot> {
+    shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Running server and its counters
+#[derive(Resource, Default)]
+pub struct LiveStats {
+    pub shared: SharedSnapshot,
+    /// Requests answered since the server started
+    pub requests: Arc<AtomicU64>,
+    server: Option<server::LiveStatsServer>,
+    /// Why the server could not start
+    pub error: Option<String>,
+}
+
+impl LiveStats {
+    /// Start serving on `address`, replacing a running server
+    pub fn start(&mut self, address: &str) -> crate::error::Result<()> {
+        self.stop();
+        self.requests.store(0, Ordering::Relaxed);
+        self.server = Some(server::LiveStatsServer::start(address, self.shared.clone(), self.requests.clone())?);
+        Ok(())
+    }
+
+    /// Shut the server down and wait for its thread
+    pub fn stop(&mut self) {
+        self.server = None;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.server.is_some()
+    }
+
+    /// Base URL of the running server
+    pub fn url(&self) -> Option<String> {
+        self.server.as_ref().map(|server| format!("http://{}", server.address))
+    }
+
+    pub fn request_count(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn publish(&self, snapshot: LiveSnapshot) {
+        *self.shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(snapshot);
+    }
+}
+
+#[cfg(feature = "http-export")]
+mod server {
+    use super::{latest_snapshot, respond, SharedSnapshot};
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread::JoinHandle;
+
+    /// tiny_http server answering on its own thread until dropped
+    pub struct LiveStatsServer {
+        pub address: SocketAddr,
+        server: Arc<tiny_http::Server>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl LiveStatsServer {
+        pub fn start(address: &str, shared: SharedSnapshot, requests: Arc<AtomicU64>) -> crate::error::Result<Self> {
+            let unavailable = |reason: String| crate::error::BioSpheresError::Server { address: address.to_string(), reason };
+            let server = Arc::new(tiny_http::Server::http(address).map_err(|e| unavailable(e.to_string()))?);
+            let bound = server.server_addr().to_ip().ok_or_else(|| unavailable("not an IP address".to_string()))?;
+
+            let thread = {
+                let server = server.clone();
+                std::thread::Builder::new()
+                    .name("live-stats-http".to_string())
+                    .spawn(move || {
+                        // Ends once `unblock` is called
+                        for request in server.incoming_requests() {
+                            let (status, body) = if *request.method() == tiny_http::Method::Get {
+                                respond(request.url(), &latest_snapshot(&shared))
+                            } else {
+                                (405, serde_json::json!({ "error": "read-only endpoint" }).to_string())
+                            };
+                            requests.fetch_add(1, Ordering::Relaxed);
+                            let response = tiny_http::Response::from_string(body)
+                                .with_status_code(status)
+                                .with_header(header("Content-Type", "application/json"))
+                                .with_header(header("Access-Control-Allow-Origin", "*"));
+                            if let Err(e) = request.respond(response) {
+                                bevy::log::warn!("Live stats response failed: {}", e);
+                            }
+                        }
+                    })
+                    .map_err(|e| unavailable(e.to_string()))?
+            };
+            bevy::log::info!("Live stats server listening on http://{}", bound);
+            Ok(Self { address: bound, server, thread: Some(thread) })
+        }
+    }
+
+    fn header(name: &str, value: &str) -> tiny_http::Header {
+        tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+    }
+
+    impl Drop for LiveStatsServer {
+        fn drop(&mut self) {
+            self.server.unblock();
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+            bevy::log::info!("Live stats server on http://{} stopped", self.address);
+        }
+    }
+}
+
+#[cfg(not(feature = "http-export"))]
+mod server {
+    use super::SharedSnapshot;
+    use std::net::SocketAddr;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::Arc;
+
+    /// Stand-in for builds without the `http-export` feature; never starts
+    #[allow(dead_code)]
+    pub struct LiveStatsServer {
+        pub address: SocketAddr,
+    }
+
+    impl LiveStatsServer {
+        pub fn start(address: &str, _shared: SharedSnapshot, _requests: Arc<AtomicU64>) -> crate::error::Result<Self> {
+            Err(crate::error::BioSpheresError::Server {
+                address: address.to_string(),
+                reason: "this build has no HTTP server (rebuild with --features http-export)".to_string(),
+            })
+        }
+    }
+}
+
+/// Divisions, deaths and adhesion breaks of the main simulation
+///
+/// Only collected while the server is enabled. Preview mode serves the
+/// event timeline instead.
+///
+/// Raw events age into per-second and per-minute counts within the history
+/// settings' limits; `/events` serves the raw ones.
+#[derive(Resource, Default)]
+pub struct LiveEventLog {
+    events: TieredHistory<TimelineEvent>,
+    recorder: Option<TimelineRecorder>,
+    last_tick: Option<u32>,
+}
+
+impl LiveEventLog {
+    /// Take the events of the tick that just ran (after physics and divisions)
+    pub fn record_tick(&mut self, tick: u32, timestep: f32, state: &CanonicalState) {
+        // Time going backwards means a fresh or reloaded scene
+        if self.last_tick.is_some_and(|last| tick <= last) {
+            self.clear();
+        }
+        self.last_tick = Some(tick);
+        self.events.set_timestep(timestep);
+
+        let recorder = self.recorder.get_or_insert_with(|| TimelineRecorder::new(state));
+        recorder.record_tick(tick, state, &state.division_events_buffer);
+        // Lineage traces are served by the preview timeline only
+        recorder.lineage.clear();
+        for event in recorder.events.drain(..) {
+            if event_kind_key(event.kind).is_some() {
+                self.events.push(event);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.recorder = None;
+        self.last_tick = None;
+    }
+
+    /// Raw events, oldest first
+    pub fn events(&self) -> impl Iterator<Item = &TimelineEvent> {
+        self.events.raw().iter()
+    }
+
+    /// Aggregated and raw history
+    pub fn history(&self) -> &TieredHistory<TimelineEvent> {
+        &self.events
+    }
+
+    pub fn set_limits(&mut self, limits: HistoryLimits) {
+        self.events.set_limits(limits);
+    }
+}
+
+fn collect_live_events(
+    main_state: Res<MainSimState>,
+    settings: Res<LiveStatsSettings>,
+    config: Res<PhysicsConfig>,
+    mut log: ResMut<LiveEventLog>,
+    mut last_time: Local<Option<f32>>,
+) {
+    // The event buffers only change when a tick ran (no cells, paused)
+    let time = main_state.simulation_time;
+    if last_time.replace(time) == Some(time) {
+        return;
+    }
+    if !settings.enabled {
+        log.clear();
+        return;
+    }
+    let tick = crate::simulation::clock::ticks_to_reach(time, config.fixed_timestep);
+    log.record_tick(tick, config.fixed_timestep, &main_state.canonical_state);
+}
+
+/// Start or stop the server when the settings change
+fn sync_live_stats_server(settings: Res<LiveStatsSettings>, mut live: ResMut<LiveStats>) {
+    if !settings.is_changed() {
+        return;
+    }
+    live.error = None;
+    if !settings.enabled {
+        live.stop();
+        return;
+    }
+    if let Err(e) = live.start(&settings.address()) {
+        warn!("{}", e);
+        live.error = Some(e.to_string());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn publish_live_stats(
+    live: Res<LiveStats>,
+    time: Res<Time<Real>>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+    genome: Res<crate::genome::CurrentGenome>,
+    config: Res<PhysicsConfig>,
+    timeline: Res<EventTimeline>,
+    log: Res<LiveEventLog>,
+    mut frames: Local<u32>,
+    mut last_publish: Local<f32>,
+) {
+    if !live.is_running() {
+        return;
+    }
+    *frames += 1;
+    let now = time.elapsed_secs();
+    let elapsed = now - *last_publish;
+    if elapsed < LIVE_STATS_PUBLISH_INTERVAL {
+        return;
+    }
+    let fps = *frames as f32 / elapsed;
+    *frames = 0;
+    *last_publish = now;
+
+    let tick_of = |time: f32| crate::simulation::clock::ticks_to_reach(time, config.fixed_timestep);
+    let snapshot = match (sim_state.mode, main_state.as_deref(), preview_state.as_deref()) {
+        (SimulationMode::Cpu, Some(main), _) => LiveSnapshot {
+            event_ranges: log.history().coverage().into_iter().map(EventRange::from).collect(),
+            ..LiveSnapshot::capture(
+                SimulationMode::Cpu, &main.canonical_state, &genome.genome,
+                tick_of(main.simulation_time), main.simulation_time, fps, log.events(),
+            )
+        },
+        (SimulationMode::Preview, _, Some(preview)) => LiveSnapshot {
+            event_ranges: timeline.coverage().into_iter().map(EventRange::from).collect(),
+            ..LiveSnapshot::capture(
+                SimulationMode::Preview, &preview.canonical_state, &genome.genome,
+                tick_of(preview.current_time), preview.current_time, fps, timeline.events(),
+            )
+        },
+        (mode, _, _) => LiveSnapshot::idle(mode, fps),
+    };
+    live.publish(snapshot);
+}
+
+fn stop_live_stats_server_on_exit(
+    mut exit_events: MessageReader<bevy::app::AppExit>,
+    mut live: ResMut<LiveStats>,
+) {
+    if exit_events.read().next().is_some() {
+        live.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    /// Splitting colony run headlessly for `ticks`, with its main-simulation event log
+    fn headless_run(ticks: u32) -> (CanonicalState, GenomeData, LiveEventLog) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.0;
+        mode.parent_make_adhesion = true;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        let mut state = CanonicalState::new(64);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 1.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        let mut log = LiveEventLog::default();
+        for tick in 0..ticks {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, true);
+            let capacity = state.capacity;
+            crate::simulation::cpu_physics::division_step(
+                &mut state, &genome, time + config.fixed_timestep, config.fixed_timestep, capacity, 0,
+            );
+            log.record_tick(tick + 1, config.fixed_timestep, &state);
+        }
+        (state, genome, log)
+    }
+
+    fn get(url: &str, snapshot: &LiveSnapshot) -> (u16, serde_json::Value) {
+        let (status, body) = respond(url, snapshot);
+        (status, serde_json::from_str(&body).expect("JSON body"))
+    }
+
+    fn assert_event_schema(event: &serde_json::Value) {
+        assert!(event["tick"].is_u64());
+        assert!(["division", "death", "adhesion_break"].contains(&event["kind"].as_str().unwrap()));
+        let cells = event["cells"].as_array().unwrap();
+        assert_eq!(cells.len(), 2);
+        assert!(cells.iter().all(|cell| cell.is_u64() || cell.is_null()));
+    }
+
+    #[test]
+    fn test_endpoints_serve_a_headless_run() {
+        let ticks = 300;
+        let (state, genome, log) = headless_run(ticks);
+        assert!(state.cell_count > 1);
+        let time = ticks as f32 * PhysicsConfig::default().fixed_timestep;
+        let snapshot = LiveSnapshot::capture(SimulationMode::Cpu, &state, &genome, ticks, time, 60.0, log.events());
+
+        let (status, stats) = get("/stats", &snapshot);
+        assert_eq!(status, 200);
+        assert_eq!(stats["mode"], "cpu");
+        assert_eq!(stats["tick"], ticks);
+        assert!(stats["time"].is_f64());
+        assert_eq!(stats["cell_count"], state.cell_count);
+        assert_eq!(stats["adhesion_count"], state.adhesion_connections.active_count);
+        assert!(stats["fps"].is_f64());
+        let populations = stats["populations"].as_array().unwrap();
+        assert_eq!(populations.len(), 1);
+        assert_eq!(populations[0]["mode"], 0);
+        assert_eq!(populations[0]["name"], "Splitter");
+        assert_eq!(populations[0]["count"], state.cell_count);
+
+        let (status, fingerprint) = get("/fingerprint", &snapshot);
+        assert_eq!(status, 200);
+        assert_eq!(fingerprint["tick"], ticks);
+        assert_eq!(fingerprint["fingerprint"], format!("{:016x}", state.fingerprint()));
+
+        let (status, all) = get("/events", &snapshot);
+        assert_eq!(status, 200);
+        assert!(all["since"].is_null());
+        assert_eq!(all["latest_tick"], ticks);
+        assert!(all["ranges"].is_array());
+        let all = all["events"].as_array().unwrap();
+        assert!(all.iter().any(|event| event["kind"] == "division"));
+        all.iter().for_each(assert_event_schema);
+
+        // Only events after `since`, still oldest first
+        let since = all[all.len() / 2]["tick"].as_u64().unwrap();
+        let (status, recent) = get(&format!("/events?since={}", since), &snapshot);
+        assert_eq!(status, 200);
+        assert_eq!(recent["since"], since);
+        let recent = recent["events"].as_array().unwrap();
+        assert!(recent.len() < all.len());
+        assert!(recent.iter().all(|event| event["tick"].as_u64().unwrap() > since));
+        assert!(recent.windows(2).all(|pair| pair[0]["tick"].as_u64() <= pair[1]["tick"].as_u64()));
+    }
+
+    #[test]
+    fn test_bad_requests_get_json_errors() {
+        let snapshot = LiveSnapshot::idle(SimulationMode::Gpu, 30.0);
+        let (status, body) = get("/events?since=soon", &snapshot);
+        assert_eq!(status, 400);
+        assert!(body["error"].is_string());
+        let (status, body) = get("/cells", &snapshot);
+        assert_eq!(status, 404);
+        assert!(body["error"].is_string());
+
+        let (status, fingerprint) = get("/fingerprint", &snapshot);
+        assert_eq!(status, 200);
+        assert!(fingerprint["fingerprint"].is_null());
+    }
+
+    #[test]
+    fn test_event_log_restarts_with_the_scene() {
+        let (state, _, mut log) = headless_run(200);
+        assert!(log.events().count() > 0);
+        log.record_tick(1, PhysicsConfig::default().fixed_timestep, &state);
+        assert!(log.events().all(|event| event.tick == 1));
+    }
+
+    #[cfg(feature = "http-export")]
+    #[test]
+    fn test_server_answers_over_http_and_shuts_down() {
+        use std::io::{Read, Write};
+
+        let (state, genome, log) = headless_run(200);
+        let mut live = LiveStats::default();
+        live.start("127.0.0.1:0").expect("server starts");
+        live.publish(LiveSnapshot::capture(SimulationMode::Cpu, &state, &genome, 200, 2.0, 60.0, log.events()));
+        let address = live.url().unwrap().trim_start_matches("http://").to_string();
+
+        let fetch = |path: &str| {
+            let mut stream = std::net::TcpStream::connect(&address).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, address).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            (head.lines().next().unwrap().to_string(), serde_json::from_str::<serde_json::Value>(body).unwrap())
+        };
+
+        let (status, stats) = fetch("/stats");
+        assert!(status.contains("200"), "{}", status);
+        assert_eq!(stats["cell_count"], state.cell_count);
+        let (status, events) = fetch("/events?since=0");
+        assert!(status.contains("200"), "{}", status);
+        events["events"].as_array().unwrap().iter().for_each(assert_event_schema);
+        let (status, _) = fetch("/nowhere");
+        assert!(status.contains("404"), "{}", status);
+        assert_eq!(live.request_count(), 3);
+
+        // Joins the server thread
+        live.stop();
+        assert!(!live.is_running());
+        assert_eq!(live.url(), None);
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Drift samples kept for the Performance Monitor graph
+const DRIFT_SAMPLES: usize = 600;
+
+/// Drift logged the first time it is reached, then again at every doubling
+const DRIFT_LOG_THRESHOLD: f64 = 1e-4;
+
+/// Plugin for the total mass conservation audit shown in the Performance Monitor
+pub struct MassAuditPlugin;
+
+impl Plugin for MassAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MassAudit>()
+            .add_systems(Update, update_mass_audit);
+    }
+}
+
+impl CanonicalState {
+    /// Sum of every cell's mass, accumulated in f64
+    pub fn total_mass(&self) -> f64 {
+        self.masses[..self.cell_count].iter().map(|&mass| mass as f64).sum()
+    }
+}
+
+/// Drift of the active simulation's total mass since the audit started
+///
+/// With nutrient gain and consumption off, and no divisions or deaths, transport and growth
+/// must keep the total constant; any drift points at a conservation bug.
+#[derive(Resource, Default)]
+pub struct MassAudit {
+    pub enabled: bool,
+    /// Total mass when the audit (re)started
+    baseline: Option<f64>,
+    /// Total mass at the last sample
+    total: f64,
+    /// Simulation time of the last sample
+    last_time: Option<f32>,
+    /// Drift from the baseline, one sample per simulated frame (oldest first)
+    drift: VecDeque<f32>,
+    /// Largest drift logged so far
+    logged_drift: f64,
+}
+
+impl MassAudit {
+    /// Start over from the current total at the next sample
+    pub fn reset(&mut self) {
+        self.baseline = None;
+        self.last_time = None;
+        self.drift.clear();
+        self.logged_drift = 0.0;
+    }
+
+    pub fn baseline(&self) -> Option<f64> {
+        self.baseline
+    }
+
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    pub fn drift(&self) -> &VecDeque<f32> {
+        &self.drift
+    }
+
+    /// Current drift from the baseline
+    pub fn current_drift(&self) -> Option<f64> {
+        self.baseline.map(|baseline| self.total - baseline)
+    }
+
+    /// Largest absolute drift in the kept samples
+    pub fn worst_drift(&self) -> f32 {
+        self.drift.iter().fold(0.0, |worst, drift| worst.max(drift.abs()))
+    }
+
+    /// Record the total mass at a simulation time; restarts when time runs backwards
+    pub fn sample(&mut self, time: f32, total: f64) {
+        match self.last_time {
+            Some(last) if time == last => return,
+            Some(last) if time < last => self.reset(),
+            _ => {}
+        }
+        self.last_time = Some(time);
+        self.total = total;
+        let baseline = *self.baseline.get_or_insert(total);
+        let drift = total - baseline;
+        if self.drift.len() == DRIFT_SAMPLES {
+            self.drift.pop_front();
+        }
+        self.drift.push_back(drift as f32);
+
+        if drift.abs() >= DRIFT_LOG_THRESHOLD.max(self.logged_drift * 2.0) {
+            self.logged_drift = drift.abs();
+            warn!("Mass audit: total mass drifted by {:+.6} ({:.6} -> {:.6}) at t = {:.2}s",
+                drift, baseline, total, time);
+        }
+    }
+}
+
+fn update_mass_audit(
+    mut audit: ResMut<MassAudit>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if !audit.enabled {
+        return;
+    }
+    let sample = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|s| (s.simulation_time, s.canonical_state.total_mass())),
+        SimulationMode::Preview => preview_state.map(|s| (s.current_time, s.canonical_state.total_mass())),
+        SimulationMode::Gpu => None,
+    };
+    if let Some((time, total)) = sample {
+        audit.sample(time, total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::test_support::{add_test_cell, never_split_mode};
+    use crate::simulation::PhysicsConfig;
+
+    const CELLS: usize = 50;
+
+    #[test]
+    fn test_bonded_cluster_conserves_mass_without_gain() {
+        // Unequal masses and priorities keep nutrients flowing for a long while
+        let modes = (0..2)
+            .map(|i| {
+                let mut mode = never_split_mode(i, &format!("Cluster {}", i));
+                mode.nutrient_priority = 1.0 + i as f32;
+                mode
+            })
+            .collect();
+        let genome = GenomeData { modes, ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        // A 5 x 10 sheet bonded along both axes
+        let mut state = CanonicalState::new(CELLS);
+        for i in 0..CELLS {
+            let (x, y) = ((i % 5) as f32, (i / 5) as f32);
+            let mass = 1.0 + 0.5 * ((i * 7) % 11) as f32 / 10.0;
+            add_test_cell(&mut state, Vec3::new(x, y, 0.0), Quat::IDENTITY, mass, 0.5, i % 2);
+        }
+        for i in 0..CELLS {
+            let neighbors = [(i % 5 < 4).then_some(i + 1), (i + 5 < CELLS).then_some(i + 5)];
+            for j in neighbors.into_iter().flatten() {
+                let direction = (state.positions[j] - state.positions[i]).normalize();
+                state.adhesion_manager.add_adhesion_with_directions(
+                    &mut state.adhesion_connections, i, j, state.mode_indices[i],
+                    direction, -direction, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+                ).expect("adhesion slot");
+            }
+        }
+
+        let start = state.total_mass();
+        for tick in 0..10_000 {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+        }
+        assert_eq!(state.cell_count, CELLS);
+        let drift = state.total_mass() - start;
+        assert!(drift.abs() < 1e-4, "total mass drifted by {}", drift);
+    }
+
+    #[test]
+    fn test_audit_restarts_when_time_runs_backwards() {
+        let mut audit = MassAudit::default();
+        audit.sample(0.0, 10.0);
+        audit.sample(1.0, 10.5);
+        // Paused: the same time adds no sample
+        audit.sample(1.0, 10.5);
+        assert_eq!(audit.drift().len(), 2);
+        assert_eq!(audit.current_drift(), Some(0.5));
+
+        // Reset or rewind: the total at that time is the new baseline
+        audit.sample(0.0, 12.0);
+        assert_eq!(audit.baseline(), Some(12.0));
+        assert_eq!(audit.current_drift(), Some(0.0));
+        assert_eq!(audit.drift().len(), 1);
+    }
+}
@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin for the memory profile setting and memory usage readout
+pub struct SimulationMemoryPlugin;
+
+impl Plugin for SimulationMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationMemory>()
+            .add_systems(Update, update_memory_usage);
+    }
+}
+
+/// Allocation strategy for simulation state
+///
+/// Only changes how memory is allocated; Full and Lean states simulate identically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MemoryProfile {
+    /// Every SoA column is allocated for the full capacity up front
+    #[default]
+    Full,
+    /// Rarely-used SoA columns grow with the live cell count instead of the capacity
+    Lean,
+}
+
+impl MemoryProfile {
+    pub const ALL: [MemoryProfile; 2] = [MemoryProfile::Full, MemoryProfile::Lean];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MemoryProfile::Full => "Full",
+            MemoryProfile::Lean => "Lean",
+        }
+    }
+}
+
+/// SoA column that can defer its allocation until cells occupy it
+///
+/// Derefs to a slice, so reads and writes look like a plain `Vec`. Under the Lean
+/// profile the column starts empty and `ensure_len` grows it as cell slots come
+/// into use; slots are always written before they are read, so the fill value
+/// never leaks into results.
+#[derive(Clone)]
+pub struct LazyColumn<T: Copy> {
+    values: Vec<T>,
+    fill: T,
+}
+
+impl<T: Copy> LazyColumn<T> {
+    pub fn new(profile: MemoryProfile, capacity: usize, fill: T) -> Self {
+        let values = match profile {
+            MemoryProfile::Full => vec![fill; capacity],
+            MemoryProfile::Lean => Vec::new(),
+        };
+        Self { values, fill }
+    }
+
+    /// Make slots `0..len` addressable
+    pub fn ensure_len(&mut self, len: usize) {
+        if self.values.len() < len {
+            self.values.resize(len, self.fill);
+        }
+    }
+
+    /// Bytes currently allocated for this column
+    pub fn allocated_bytes(&self) -> usize {
+        vec_bytes(&self.values)
+    }
+}
+
+impl<T: Copy> std::ops::Deref for LazyColumn<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Copy> std::ops::DerefMut for LazyColumn<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+}
+
+fn vec_bytes<T>(values: &Vec<T>) -> usize {
+    values.capacity() * size_of::<T>()
+}
+
+/// Estimated bytes held by a simulation, per subsystem
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Per-cell SoA columns
+    pub cells: usize,
+    /// Adhesion connection tables and per-cell adhesion slots
+    pub adhesions: usize,
+    /// Spatial grid
+    pub spatial_grid: usize,
+    /// Chemical field buffers (zero until something is secreted)
+    pub chemical_field: usize,
+    /// Reused scratch and event buffers
+    pub scratch: usize,
+    /// Preview checkpoints (whole states kept for scrubbing)
+    pub checkpoints: usize,
+}
+
+impl MemoryUsage {
+    /// Estimate the memory held by `state` (from allocated capacities, not live counts)
+    pub fn of(state: &CanonicalState) -> Self {
+        let cells = vec_bytes(&state.cell_ids)
+            + vec_bytes(&state.positions)
+            + vec_bytes(&state.prev_positions)
+            + vec_bytes(&state.velocities)
+            + vec_bytes(&state.masses)
+            + vec_bytes(&state.radii)
+            + vec_bytes(&state.genome_ids)
+            + vec_bytes(&state.mode_indices)
+            + vec_bytes(&state.rotations)
+            + vec_bytes(&state.angular_velocities)
+            + state.genome_orientations.allocated_bytes()
+            + state.lineage_references.allocated_bytes()
+            + vec_bytes(&state.forces)
+            + state.torques.allocated_bytes()
+            + vec_bytes(&state.accelerations)
+            + state.prev_accelerations.allocated_bytes()
+            + vec_bytes(&state.stiffnesses)
+            + state.contact_pressures.allocated_bytes()
+            + state.user_data.iter().map(LazyColumn::allocated_bytes).sum::<usize>()
+            + state.force_heat.averages.iter().chain(&state.force_heat.totals).map(vec_bytes).sum::<usize>()
+            + vec_bytes(&state.birth_times)
+            + vec_bytes(&state.split_intervals)
+            + vec_bytes(&state.split_masses)
+            + vec_bytes(&state.split_counts)
+            + vec_bytes(&state.split_ready_frame)
+            + vec_bytes(&state.low_nutrient_boost)
+            + vec_bytes(&state.pinned);
+
+        let connections = &state.adhesion_connections;
+        let adhesions = vec_bytes(&connections.cell_a_index)
+            + vec_bytes(&connections.cell_b_index)
+            + vec_bytes(&connections.mode_index)
+            + vec_bytes(&connections.is_active)
+            + vec_bytes(&connections.zone_a)
+            + vec_bytes(&connections.zone_b)
+            + vec_bytes(&connections.anchor_direction_a)
+            + vec_bytes(&connections.anchor_direction_b)
+            + vec_bytes(&connections.twist_reference_a)
+            + vec_bytes(&connections.twist_reference_b)
+            + vec_bytes(&connections.quality_tier)
+            + vec_bytes(&connections.calm_ticks)
+            + vec_bytes(&connections.force_limit_hits)
+            + vec_bytes(&state.adhesion_manager.cell_adhesion_indices);
+
+        let grid = &state.spatial_grid;
+        // HashMap storage approximated as one key/value pair per entry
+        let spatial_grid = vec_bytes(&grid.active_cells)
+            + grid.active_cell_map.capacity() * size_of::<(IVec3, usize)>()
+            + vec_bytes(&grid.cell_contents)
+            + vec_bytes(&grid.cell_offsets)
+            + vec_bytes(&grid.cell_counts)
+            + vec_bytes(&grid.used_grid_cells);
+
+        let scratch = state.scratch.memory_bytes()
+            + vec_bytes(&state.mass_deltas_buffer)
+            + vec_bytes(&state.cells_to_remove_buffer)
+            + vec_bytes(&state.already_split_buffer)
+            + vec_bytes(&state.divisions_to_process_buffer)
+            + vec_bytes(&state.filtered_divisions_buffer)
+            + vec_bytes(&state.division_marks_buffer)
+            + vec_bytes(&state.division_events_buffer)
+            + vec_bytes(&state.removed_cell_ids_buffer)
+            + vec_bytes(&state.removed_cell_positions_buffer)
+            + vec_bytes(&state.broken_adhesions_buffer)
+            + vec_bytes(&state.broken_adhesion_speeds_buffer)
+            + state.collision_cache.memory_bytes();
+
+        let chemical_field = state.chemical_field.memory_bytes();
+
+        Self { cells, adhesions, spatial_grid, chemical_field, scratch, checkpoints: 0 }
+    }
+
+    pub fn total(&self) -> usize {
+        self.cells + self.adhesions + self.spatial_grid + self.chemical_field + self.scratch + self.checkpoints
+    }
+}
+
+/// Memory profile setting and the active simulation's memory estimate
+#[derive(Resource, Default)]
+pub struct SimulationMemory {
+    /// Profile used when a simulation state is created (applies on the next scene reset)
+    pub profile: MemoryProfile,
+    /// Estimated memory of the active simulation (refreshed every frame)
+    pub usage: Option<MemoryUsage>,
+    /// Debug flag: count heap allocations during main simulation ticks
+    pub count_allocations: bool,
+    /// Heap allocations during the most recent main simulation tick (while counting)
+    pub allocations_last_tick: Option<u64>,
+}
+
+/// System allocator that counts allocations while counting is switched on
+///
+/// Switched off, the overhead is one relaxed atomic load per allocation. The library
+/// does not install it; the app binary opts in with `#[global_allocator]`, and without
+/// it `allocation_count` stays at zero.
+pub struct CountingAllocator;
+
+static COUNT_ALLOCATIONS: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+#[inline]
+fn count_allocation() {
+    if COUNT_ALLOCATIONS.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// Switch allocation counting on or off
+pub fn set_allocation_counting(enabled: bool) {
+    COUNT_ALLOCATIONS.store(enabled, Ordering::Relaxed);
+}
+
+/// Allocations and reallocations counted so far, on every thread
+pub fn allocation_count() -> u64 {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+fn update_memory_usage(
+    mut memory: ResMut<SimulationMemory>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    set_allocation_counting(memory.count_allocations);
+    if !memory.count_allocations || sim_state.mode != SimulationMode::Cpu {
+        memory.allocations_last_tick = None;
+    }
+
+    memory.usage = match sim_state.mode {
+        SimulationMode::Cpu => main_state.map(|main_state| MemoryUsage::of(&main_state.canonical_state)),
+        SimulationMode::Preview => preview_state.map(|preview_state| MemoryUsage {
+            checkpoints: preview_state.checkpoints.iter()
+                .map(|(_, checkpoint)| MemoryUsage::of(checkpoint).total())
+                .sum(),
+            ..MemoryUsage::of(&preview_state.canonical_state)
+        }),
+        SimulationMode::Gpu => None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+    use crate::simulation::PhysicsConfig;
+
+    /// Colony that divides at t = 2, 4, 6 with adhesions kept between siblings
+    fn run_colony(profile: MemoryProfile) -> CanonicalState {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_mass = 1.0;
+        mode.split_interval = 2.0;
+        mode.nutrient_gain_rate = 1.0;
+        mode.parent_make_adhesion = true;
+        mode.child_a.keep_adhesion = true;
+        mode.child_b.keep_adhesion = true;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig::default();
+        let fixed_timestep = config.fixed_timestep;
+
+        let mut state = CanonicalState::with_memory_profile(64, 64, profile);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 2.0, 1.0,
+            0, 0, 0.0, 2.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        for tick in 0..crate::simulation::clock::ticks_to_reach(7.0, fixed_timestep) {
+            let current_time = tick as f32 * fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, current_time, false);
+            let next_time = (tick + 1) as f32 * fixed_timestep;
+            crate::simulation::cpu_physics::division_step(&mut state, &genome, next_time, fixed_timestep, 64, 0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_lean_profile_simulates_identically_to_full() {
+        let full = run_colony(MemoryProfile::Full);
+        let lean = run_colony(MemoryProfile::Lean);
+
+        let n = full.cell_count;
+        assert!(n > 1);
+        assert_eq!(lean.cell_count, n);
+        assert_eq!(lean.cell_ids[..n], full.cell_ids[..n]);
+        assert_eq!(lean.positions[..n], full.positions[..n]);
+        assert_eq!(lean.rotations[..n], full.rotations[..n]);
+        assert_eq!(lean.torques[..n], full.torques[..n]);
+        assert_eq!(lean.genome_orientations[..n], full.genome_orientations[..n]);
+        assert_eq!(lean.adhesion_connections.active_count, full.adhesion_connections.active_count);
+
+        // Lean columns only cover the slots that have held cells
+        assert_eq!(lean.prev_accelerations.len(), n);
+        assert!(MemoryUsage::of(&lean).cells < MemoryUsage::of(&full).cells);
+    }
+}
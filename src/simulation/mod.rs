@@ -1,30 +1,120 @@
 use bevy::prelude::*;
 
 pub mod cpu_physics;
+pub mod adhesion_quality;
+pub mod anchor_editing;
+pub mod breakpoints;
+pub mod capacity;
+pub mod capture;
+pub mod capture_font;
+pub mod cell_groups;
+pub mod central_attractor;
+pub mod chunked_scene;
 pub mod cell_allocation;
+pub mod chemical_field;
 pub mod clock;
+pub mod collision_cache;
+pub mod colony_surface;
+pub mod contact_adhesion;
+pub mod contact_graph;
 pub mod cpu_sim;
+pub mod division_debug;
+pub mod division_lookahead;
+pub mod division_stats;
+pub mod dormant_scenes;
+pub mod derived_metrics;
 pub mod double_buffer;
+pub mod event_timeline;
+pub mod experiment_session;
+pub mod fingerprint;
+pub mod force_heatmap;
+pub mod genome_staging;
 pub mod gpu_physics;
+pub mod gpu_collision_pairs;
+pub mod history;
 pub mod initial_state;
+pub mod layout_csv;
+pub mod live_stats;
+pub mod mass_audit;
+pub mod memory;
+pub mod mode_edits;
 pub mod physics_config;
+pub mod physics_layers;
+pub mod population_stats;
+pub mod parameter_sweep;
+pub mod pinning;
+pub mod preview_drag;
+pub mod preview_respawn;
 pub mod preview_sim;
+pub mod problem_bonds;
+pub mod reproducible_math;
+pub mod scenario_presets;
+pub mod scene_file;
+pub mod scene_inspector;
+pub mod scratch;
+pub mod settle;
+pub mod soak;
+pub mod soft_drag;
+pub mod spectator;
 pub mod adhesion_inheritance;
 pub mod nutrient_system;
+pub mod organism_surgery;
+pub mod orientation_drift;
 pub mod synchronized_nutrients;
+pub mod tissue_stamp;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod time_scrubber_bridge;
+pub mod user_data;
+pub mod watchdog;
 
 pub use cpu_physics::{CanonicalState, DeterministicSpatialGrid, physics_step, deterministic_random};
 pub use physics_config::{PhysicsConfig, SpatialGridConfig};
+pub use physics_layers::{PhysicsLayers, PhysicsOverrides};
+pub use reproducible_math::PhysicsMath;
 pub use cell_allocation::{Cell, Adhesion};
+pub use breakpoints::Breakpoints;
+pub use capture::{CapturePlugin, CaptureSettings, Captures};
+pub use cell_groups::CellGroups;
+pub use central_attractor::OrbitalSpawn;
+pub use chunked_scene::SceneLoader;
+pub use chemical_field::{ChemicalField, FIELD_CHANNELS};
+pub use colony_surface::ColonySurface;
+pub use contact_graph::ContactGraphExport;
 pub use clock::SimulationClock;
 pub use cpu_sim::{CpuSimPlugin, CpuSimTimestepPlugin, CpuSceneState, CpuSceneEntity};
+pub use division_debug::DivisionDebug;
+pub use division_lookahead::DivisionLookaheadPreview;
+pub use division_stats::DivisionStatistics;
+pub use derived_metrics::{DerivedMetricSettings, DerivedMetrics};
+pub use dormant_scenes::DormantScenes;
 pub use double_buffer::DoubleBufferedState;
+pub use event_timeline::EventTimeline;
+pub use experiment_session::{ExperimentSession, ExperimentSessionPlugin};
+pub use fingerprint::SimulationFingerprint;
+pub use force_heatmap::{ForceHeatmap, HeatChannel};
+pub use genome_staging::GenomeStaging;
+pub use history::{HistorySettings, StatisticsHistory};
 pub use initial_state::{InitialState, InitialCell};
+pub use layout_csv::{LayoutCsvPlugin, LayoutImport};
+pub use live_stats::{LiveStats, LiveStatsSettings};
+pub use mass_audit::MassAudit;
+pub use population_stats::{PopulationStats, PopulationStatsSettings};
+pub use memory::{MemoryProfile, SimulationMemory};
+pub use mode_edits::{ModeEdit, ModeEditPlugin, ModeEdits};
+pub use adhesion_quality::AdhesionQualityStats;
+pub use preview_respawn::{PreviewRespawn, PreviewStart};
 pub use preview_sim::{PreviewSimPlugin, PreviewSceneState, PreviewSceneEntity};
-pub use adhesion_inheritance::{inherit_adhesions_on_division, inherit_adhesions_on_division_with_map};
+pub use problem_bonds::ProblemBonds;
+pub use scene_file::SceneFile;
+pub use scene_inspector::SceneInspector;
+pub use settle::{SettleRequests, SettleSettings};
+pub use spectator::{SpectatorClient, SpectatorHost, SpectatorSettings};
+pub use watchdog::{Watchdog, WatchdogSettings};
+pub use adhesion_inheritance::{inherit_adhesions_on_division, inherit_adhesions_on_division_with_map, predict_bond_inheritance, InheritanceOutcome};
 pub use nutrient_system::{update_nutrient_growth, update_nutrient_growth_st, transport_nutrients, transport_nutrients_st};
 pub use gpu_physics::{GpuPhysicsPlugin, GpuPhysicsResource, compute_collision_forces_gpu, physics_step_gpu, physics_step_gpu_with_genome};
+pub use gpu_collision_pairs::{GpuPairPlugin, GpuPairDetection, GpuPairStats};
 
 /// Configuration for simulation threading
 #[derive(Resource, Clone, Copy, Debug)]
@@ -65,7 +155,47 @@ impl Plugin for SimulationPlugin {
             .add_plugins(PreviewSimPlugin)
             // Add GPU physics plugin
             .add_plugins(GpuPhysicsPlugin)
+            .add_plugins(GpuPairPlugin)
+            .add_plugins(scenario_presets::ScenarioPresetPlugin)
+            .add_plugins(parameter_sweep::ParameterSweepPlugin)
+            .add_plugins(soak::SoakPlugin)
+            .add_plugins(capacity::CapacityGrowthPlugin)
+            .add_plugins(memory::SimulationMemoryPlugin)
+            .add_plugins(adhesion_quality::AdhesionQualityPlugin)
+            .add_plugins(problem_bonds::ProblemBondsPlugin)
+            .add_plugins(division_stats::DivisionStatsPlugin)
+            .add_plugins(mass_audit::MassAuditPlugin)
+            .add_plugins(population_stats::PopulationStatsPlugin)
+            .add_plugins(division_debug::DivisionDebugPlugin)
+            .add_plugins(breakpoints::BreakpointPlugin)
+            .add_plugins(dormant_scenes::DormantScenesPlugin)
+            .add_plugins(live_stats::LiveStatsPlugin)
+            .add_plugins(spectator::SpectatorPlugin)
+            .add_plugins(watchdog::WatchdogPlugin)
+            .add_plugins(chunked_scene::ChunkedScenePlugin)
+            .add_plugins(history::HistoryPlugin)
+            .add_plugins(derived_metrics::DerivedMetricsPlugin)
+            .add_plugins(contact_graph::ContactGraphPlugin)
+            .add_plugins(colony_surface::ColonySurfacePlugin)
+            .add_plugins(pinning::PinningPlugin)
+            .add_plugins(user_data::UserDataPlugin)
+            .add_plugins(force_heatmap::ForceHeatmapPlugin)
+            .add_plugins(cell_groups::CellGroupsPlugin)
+            .add_plugins(organism_surgery::OrganismSurgeryPlugin)
+            .add_plugins(orientation_drift::OrientationDriftPlugin)
+            .add_plugins(tissue_stamp::TissueStampPlugin)
+            .add_plugins(fingerprint::FingerprintPlugin)
+            .add_plugins(genome_staging::GenomeStagingPlugin)
+            .add_plugins(experiment_session::ExperimentSessionPlugin)
+            .add_plugins(capture::CapturePlugin)
+            .add_plugins(physics_layers::PhysicsLayersPlugin)
+            .add_plugins(layout_csv::LayoutCsvPlugin)
+            .add_plugins(mode_edits::ModeEditPlugin)
+            .add_plugins(settle::SettlePlugin)
+            .add_plugins(scene_inspector::SceneInspectorPlugin)
+            .add_plugins(division_lookahead::DivisionLookaheadPlugin)
             .init_resource::<PhysicsConfig>()
+            .init_resource::<EventTimeline>()
             .init_resource::<SpatialGridConfig>()
             .init_resource::<SimulationState>()
             .init_resource::<SimulationConfig>()
@@ -0,0 +1,450 @@
+use bevy::prelude::*;
+use crate::genome::{CurrentGenome, ModeIndex, ModeRemap, ModeTemplates, RemovedModePolicy, TemplateChoice};
+use crate::simulation::cell_groups::{GroupKind, GroupPredicate, GroupQuery};
+use crate::simulation::breakpoints::BreakCondition;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{InitialState, SimulationMode, SimulationState};
+
+/// Plugin applying mode inserts and removals to the genome and the living cells together
+///
+/// Cells keep their mode by index, so an edit that shifts the mode list has to move
+/// every stored index in the same frame, or cells silently change mode.
+pub struct ModeEditPlugin;
+
+impl Plugin for ModeEditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModeEdits>()
+            .add_systems(Update, apply_mode_edits);
+    }
+}
+
+/// Change to the mode list requested by the editor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModeEdit {
+    /// Insert a mode made from `choice` at `index`
+    Insert { index: usize, choice: TemplateChoice },
+    /// Remove mode `index`; without a policy, living cells of the mode open a confirmation first
+    Remove { index: usize, policy: Option<RemovedModePolicy> },
+}
+
+/// Removal waiting for the user to decide what happens to the mode's living cells
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RemovalPrompt {
+    pub index: usize,
+    /// Living cells of the mode in the active simulation
+    pub cells: usize,
+    pub policy: RemovedModePolicy,
+}
+
+/// Pending mode list edit and the removal confirmation it may need
+#[derive(Resource, Default)]
+pub struct ModeEdits {
+    pub pending: Option<ModeEdit>,
+    pub prompt: Option<RemovalPrompt>,
+}
+
+impl ModeRemap {
+    /// New index the removed mode's cells move to under `policy` (None = they are removed)
+    pub fn fallback(&self, policy: RemovedModePolicy) -> Option<usize> {
+        match policy {
+            RemovedModePolicy::Reassign(mode) => self.get(mode),
+            RemovedModePolicy::Remove => None,
+        }
+    }
+
+    /// New index of a cell mode; out-of-range modes pass through, a removed mode gives `fallback`
+    fn cell_mode(&self, old: usize, fallback: Option<usize>) -> Option<usize> {
+        match self.reference(ModeIndex::new(old)) {
+            Some(mode) => mode.index(),
+            None => fallback,
+        }
+    }
+}
+
+impl CanonicalState {
+    /// Living cells in mode `index`
+    pub fn mode_population(&self, index: usize) -> usize {
+        self.mode_indices[..self.cell_count].iter().filter(|&&mode| mode == index).count()
+    }
+
+    /// Move every cell and bond to the new index of its mode
+    ///
+    /// Cells of a removed mode move to `fallback` (a new index), or are removed without
+    /// one; returns how many were removed. Bonds of a removed mode use the fallback too,
+    /// else the settings of their first cell's mode.
+    pub fn remap_modes(&mut self, remap: &ModeRemap, fallback: Option<usize>) -> usize {
+        let mut removed = 0;
+        // Back to front, so the cell swapped into a freed slot is already remapped
+        for index in (0..self.cell_count).rev() {
+            match remap.cell_mode(self.mode_indices[index], fallback) {
+                Some(mode) => self.mode_indices[index] = mode,
+                None => {
+                    crate::simulation::nutrient_system::remove_dead_cell(self, index);
+                    removed += 1;
+                }
+            }
+        }
+
+        let connections = &mut self.adhesion_connections;
+        for slot in 0..connections.is_active.len() {
+            if connections.is_active[slot] == 0 {
+                continue;
+            }
+            let cell_a = connections.cell_a_index[slot];
+            connections.mode_index[slot] = remap.cell_mode(connections.mode_index[slot], fallback)
+                .unwrap_or_else(|| self.mode_indices.get(cell_a).copied().unwrap_or(0));
+        }
+        removed
+    }
+}
+
+impl InitialState {
+    /// [`CanonicalState::remap_modes`] for the cells a run starts from
+    pub fn remap_modes(&mut self, remap: &ModeRemap, fallback: Option<usize>) -> usize {
+        let before = self.initial_cells.len();
+        self.initial_cells.retain_mut(|cell| match remap.cell_mode(cell.mode_index, fallback) {
+            Some(mode) => {
+                cell.mode_index = mode;
+                true
+            }
+            None => false,
+        });
+        before - self.initial_cells.len()
+    }
+}
+
+impl crate::simulation::preview_sim::PreviewSimState {
+    /// Remap the timeline: its state, the founders and every recorded intervention
+    ///
+    /// Checkpoints are dropped, so the next replay runs from the remapped founders.
+    /// Interventions that need a removed mode without a fallback are dropped.
+    fn remap_modes(&mut self, remap: &ModeRemap, fallback: Option<usize>) -> usize {
+        self.clear_checkpoints();
+        self.initial_state.remap_modes(remap, fallback);
+        self.stamps.retain_mut(|(_, request)| {
+            let used = request.tissue.used_modes();
+            let mut mode_map = request.mode_map.clone();
+            for &tissue_mode in &used {
+                match mode_map.get(tissue_mode).and_then(|&mode| remap.cell_mode(mode, fallback)) {
+                    Some(mode) => mode_map[tissue_mode] = mode,
+                    None => return false,
+                }
+            }
+            request.mode_map = mode_map;
+            true
+        });
+        self.user_data_changes.retain_mut(|(_, change)| match change {
+            crate::simulation::user_data::UserDataChange::SetMode { mode_index, .. } => {
+                match remap.cell_mode(*mode_index, fallback) {
+                    Some(mode) => {
+                        *mode_index = mode;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => true,
+        });
+        for (_, op) in &mut self.surgery {
+            if let crate::simulation::organism_surgery::SurgeryOp::Graft { mode_index, .. } = op {
+                // Out of range: the graft bonds with its first cell's settings
+                *mode_index = remap.cell_mode(*mode_index, fallback).unwrap_or(usize::MAX);
+            }
+        }
+        self.canonical_state.remap_modes(remap, fallback)
+    }
+}
+
+impl GroupQuery {
+    /// Follow the modes of "mode in" predicates; removed modes drop out
+    pub fn remap_modes(&mut self, remap: &ModeRemap) {
+        match self {
+            GroupQuery::Predicate(GroupPredicate::ModeIn(modes)) => {
+                *modes = modes.iter().filter_map(|&mode| remap.cell_mode(mode, None)).collect();
+            }
+            GroupQuery::Predicate(_) => {}
+            GroupQuery::All(queries) | GroupQuery::Any(queries) => {
+                queries.iter_mut().for_each(|query| query.remap_modes(remap));
+            }
+        }
+    }
+}
+
+/// Things outside the cells that name modes by index
+fn remap_mode_references(
+    remap: &ModeRemap,
+    breakpoints: &mut crate::simulation::Breakpoints,
+    groups: &mut crate::simulation::CellGroups,
+    visibility: &mut crate::rendering::ModeVisibility,
+) {
+    breakpoints.list.retain_mut(|breakpoint| match &mut breakpoint.condition {
+        BreakCondition::ModeExtinct(mode) => match remap.cell_mode(*mode, None) {
+            Some(new) => {
+                *mode = new;
+                true
+            }
+            None => false,
+        },
+        _ => true,
+    });
+    for group in &mut groups.groups {
+        if let GroupKind::Query(query) = &mut group.kind {
+            query.remap_modes(remap);
+        }
+    }
+    groups.refresh_requested = true;
+    for set in [&mut visibility.isolated, &mut visibility.hidden] {
+        *set = set.iter().filter_map(|&mode| remap.cell_mode(mode, None)).collect();
+    }
+}
+
+/// Apply a requested mode edit to the genome and, in the same frame, to every stored mode index
+fn apply_mode_edits(
+    mut edits: ResMut<ModeEdits>,
+    mut genome: ResMut<CurrentGenome>,
+    templates: Res<ModeTemplates>,
+    mut staging: ResMut<crate::simulation::GenomeStaging>,
+    sim_state: Res<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    mut breakpoints: ResMut<crate::simulation::Breakpoints>,
+    mut groups: ResMut<crate::simulation::CellGroups>,
+    mut visibility: ResMut<crate::rendering::ModeVisibility>,
+    mut history: ResMut<crate::genome::GenomeHistory>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if edits.pending.is_none() {
+        return;
+    }
+    // A running resimulation would overwrite the remapped preview; wait for it to land
+    if sim_state.mode == SimulationMode::Preview && sim_state.is_resimulating {
+        return;
+    }
+    let Some(edit) = edits.pending.take() else {
+        return;
+    };
+
+    let active_state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|main_state| &main_state.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|preview_state| &preview_state.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+
+    let (remap, policy) = match edit {
+        ModeEdit::Insert { index, choice } => {
+            let Some(remap) = templates.insert_mode(choice, &mut genome.genome, index) else {
+                return;
+            };
+            let index = index.min(genome.genome.modes.len() - 1);
+            let inserted = genome.genome.modes[index].clone();
+            if let Some(running) = staging.running_mut() {
+                running.insert_mode(index, inserted);
+            }
+            genome.selected_mode_index = index as i32;
+            info!("Inserted mode {} ({:?})", index, choice);
+            (remap, RemovedModePolicy::Remove)
+        }
+        ModeEdit::Remove { index, policy } => {
+            let Some(fallback) = genome.genome.removal_fallback(index) else {
+                notifications.warning("The last mode cannot be removed");
+                return;
+            };
+            let policy = match policy {
+                Some(policy) => policy,
+                None => {
+                    let cells = active_state.map_or(0, |state| state.mode_population(index));
+                    if cells > 0 {
+                        edits.prompt = Some(RemovalPrompt { index, cells, policy: RemovedModePolicy::Reassign(fallback) });
+                        return;
+                    }
+                    RemovedModePolicy::Reassign(fallback)
+                }
+            };
+            let Some((removed, remap)) = genome.genome.remove_mode(index) else {
+                return;
+            };
+            if let Some(running) = staging.running_mut() {
+                running.remove_mode(index);
+            }
+            let selected = genome.selected_mode_index.max(0) as usize;
+            genome.selected_mode_index = remap.cell_mode(selected, Some(index.saturating_sub(1)))
+                .unwrap_or(0)
+                .min(genome.genome.modes.len() - 1) as i32;
+            info!("Removed mode {} ({})", index, removed.name);
+            (remap, policy)
+        }
+    };
+
+    let fallback = remap.fallback(policy);
+    let mut removed_cells = 0;
+    // Both states follow the genome: the inactive one is picked up again on a mode switch
+    if let Some(main_state) = main_state.as_deref_mut() {
+        let cells = main_state.canonical_state.remap_modes(&remap, fallback);
+        main_state.initial_state.remap_modes(&remap, fallback);
+        if sim_state.mode == SimulationMode::Cpu {
+            removed_cells = cells;
+        }
+    }
+    if let Some(preview_state) = preview_state.as_deref_mut() {
+        let cells = preview_state.remap_modes(&remap, fallback);
+        if sim_state.mode == SimulationMode::Preview {
+            removed_cells = cells;
+        }
+    }
+    remap_mode_references(&remap, &mut breakpoints, &mut groups, &mut visibility);
+    history.remap_modes(&remap);
+
+    if removed_cells > 0 {
+        notifications.info(format!("Removed {} cell(s) of the deleted mode", removed_cells));
+    }
+    if sim_state.mode == SimulationMode::Gpu && !remap.is_identity() {
+        notifications.warning("GPU cells keep their mode indices; restart the GPU scene after removing or inserting modes");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+    use crate::simulation::cpu_physics::division_step;
+    use crate::simulation::{InitialCell, PhysicsConfig};
+
+    /// Every living cell's mode, by name
+    fn mode_names(state: &CanonicalState, genome: &GenomeData) -> Vec<(u32, String)> {
+        let mut names = (0..state.cell_count)
+            .map(|index| (state.cell_ids[index], genome.modes[state.mode_indices[index]].name.clone()))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Stem divides into a Leaf and a Root, which keep dividing into themselves
+    fn genome() -> GenomeData {
+        let modes = ["Stem", "Leaf", "Root", "Spare"].iter().enumerate().map(|(index, name)| {
+            let mut mode = ModeSettings::new_self_splitting(index, name.to_string());
+            mode.split_interval = 0.5;
+            mode.split_mass = 0.0;
+            mode
+        }).collect::<Vec<_>>();
+        let mut genome = GenomeData { modes, ..GenomeData::default() };
+        genome.modes[0].child_a.mode_number = ModeIndex::new(1);
+        genome.modes[0].child_b.mode_number = ModeIndex::new(2);
+        genome.initial_mode = ModeIndex::new(0);
+        genome
+    }
+
+    /// One division per simulated second, for `generations` seconds from second `first`
+    fn run(state: &mut CanonicalState, genome: &GenomeData, first: u32, generations: u32) {
+        let config = PhysicsConfig::default();
+        for generation in first..first + generations {
+            let capacity = state.capacity;
+            division_step(state, genome, generation as f32, config.fixed_timestep, capacity, 0);
+        }
+    }
+
+    /// Two founders in the initial mode, far apart
+    fn founders(genome: &GenomeData) -> CanonicalState {
+        let config = PhysicsConfig::default();
+        let mut state = CanonicalState::new(512);
+        for x in [-20.0, 20.0] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, genome.initial_mode_index(), 0.0, 0.5, 0.0, config.default_stiffness, Quat::IDENTITY, 0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_inserting_a_mode_keeps_every_cell_in_its_mode() {
+        let mut genome = genome();
+        let mut state = founders(&genome);
+        run(&mut state, &genome, 1, 3);
+        assert!(state.cell_count > 2);
+        let before = mode_names(&state, &genome);
+
+        let remap = ModeTemplates::default()
+            .insert_mode(TemplateChoice::Builtin(crate::genome::ModeArchetype::Blank), &mut genome, 1)
+            .unwrap();
+        assert_eq!(state.remap_modes(&remap, None), 0);
+        assert_eq!(mode_names(&state, &genome), before);
+
+        // The lineages keep dividing into the same modes afterwards
+        let reference = self::genome();
+        let mut reference_state = founders(&reference);
+        run(&mut reference_state, &reference, 1, 5);
+        run(&mut state, &genome, 4, 2);
+        assert_eq!(mode_names(&state, &genome), mode_names(&reference_state, &reference));
+    }
+
+    #[test]
+    fn test_removing_a_mode_reassigns_or_removes_its_cells() {
+        let genome = genome();
+        let mut state = founders(&genome);
+        run(&mut state, &genome, 1, 3);
+        let before = mode_names(&state, &genome);
+        let roots = state.mode_population(2);
+        assert!(roots > 0);
+
+        // Removing the unused Spare mode shifts nothing in use
+        let mut spare = genome.clone();
+        let mut spare_state = state.clone();
+        let (_, remap) = spare.remove_mode(3).unwrap();
+        assert_eq!(spare_state.remap_modes(&remap, remap.fallback(RemovedModePolicy::Remove)), 0);
+        assert_eq!(mode_names(&spare_state, &spare), before);
+
+        // Removing Leaf shifts Root and Spare down; Leaf cells move to Root
+        let mut reassigned = genome.clone();
+        let mut reassigned_state = state.clone();
+        let (_, remap) = reassigned.remove_mode(1).unwrap();
+        let fallback = remap.fallback(RemovedModePolicy::Reassign(2));
+        assert_eq!(reassigned_state.remap_modes(&remap, fallback), 0);
+        let expected = before.iter()
+            .map(|(id, name)| (*id, if name == "Leaf" { "Root".to_string() } else { name.clone() }))
+            .collect::<Vec<_>>();
+        assert_eq!(mode_names(&reassigned_state, &reassigned), expected);
+
+        // Or they are removed, and the others keep their mode and go on dividing
+        let mut pruned = genome.clone();
+        let mut pruned_state = state.clone();
+        let (_, remap) = pruned.remove_mode(1).unwrap();
+        let leaves = state.mode_population(1);
+        assert_eq!(pruned_state.remap_modes(&remap, None), leaves);
+        let expected = before.iter().filter(|(_, name)| name != "Leaf").cloned().collect::<Vec<_>>();
+        assert_eq!(mode_names(&pruned_state, &pruned), expected);
+        run(&mut pruned_state, &pruned, 4, 1);
+        assert_eq!(pruned_state.mode_population(1), roots * 2);
+    }
+
+    #[test]
+    fn test_founders_and_group_queries_follow_their_modes() {
+        let mut initial = InitialState::new(PhysicsConfig::default(), 8, 0);
+        for (id, mode_index) in [(0, 2), (1, 1)] {
+            initial.add_cell(InitialCell {
+                id,
+                position: Vec3::new(id as f32 * 4.0, 0.0, 0.0),
+                velocity: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+                angular_velocity: Vec3::ZERO,
+                mass: 1.0,
+                radius: 1.0,
+                genome_id: 0,
+                mode_index,
+                birth_time: 0.0,
+                split_interval: 5.0,
+                split_mass: 1.5,
+                stiffness: 10.0,
+                pinned: false,
+                user_data: Default::default(),
+            });
+        }
+        let remap = ModeRemap::remove(3, 1);
+        let mut kept = initial.clone();
+        assert_eq!(kept.remap_modes(&remap, Some(0)), 0);
+        assert_eq!(kept.initial_cells.iter().map(|cell| cell.mode_index).collect::<Vec<_>>(), [1, 0]);
+        assert_eq!(initial.remap_modes(&remap, None), 1);
+        assert_eq!(initial.initial_cells[0].mode_index, 1);
+
+        let mut query = GroupQuery::Any(vec![GroupQuery::Predicate(GroupPredicate::ModeIn(vec![0, 1, 2]))]);
+        query.remap_modes(&remap);
+        assert_eq!(query, GroupQuery::Any(vec![GroupQuery::Predicate(GroupPredicate::ModeIn(vec![0, 1]))]));
+    }
+}
@@ -1,27 +1,32 @@
 use bevy::prelude::*;
 use super::cpu_physics::CanonicalState;
 
+/// Cells below this mass die
+const MIN_CELL_MASS: f32 = 0.5;
+
+/// Part of `gain` a cell of `mass` absorbs below `storage_cap`
+fn absorbed_gain(mass: f32, gain: f32, storage_cap: f32) -> f32 {
+    gain.min(storage_cap - mass).max(0.0)
+}
+
 /// Update cell mass and radius based on nutrient gain (for Test cells) - Single-threaded
 /// Test cells (cell_type == 0) automatically gain mass over time and grow in size
 pub fn update_nutrient_growth_st(
     masses: &mut [f32],
     radii: &mut [f32],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) {
     for i in 0..masses.len() {
         let mode_index = mode_indices[i];
-        if let Some(mode) = genome.modes.get(mode_index) {
+        if let Some(mode) = genome.mode(mode_index) {
             // Apply nutrient growth to all cell types
             // Nutrient storage cap: 2x split_mass (allows storage for division plus buffer)
-            let storage_cap = mode.split_mass * 2.0;
+            let storage_cap = mode.storage_cap;
             
-            // Only gain mass if below storage cap
-            if masses[i] < storage_cap {
-                let mass_gain = mode.nutrient_gain_rate * dt;
-                masses[i] = (masses[i] + mass_gain).min(storage_cap);
-            }
+            // Absorb only what fits under the cap (never clamps mass the cell already has)
+            masses[i] += absorbed_gain(masses[i], mode.nutrient_gain_rate * dt, storage_cap);
             
             // Calculate target radius based on mass (linear relationship)
             // Clamp to max_cell_size
@@ -37,7 +42,7 @@ pub fn update_nutrient_growth(
     masses: &mut [f32],
     radii: &mut [f32],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) {
     use rayon::prelude::*;
@@ -46,17 +51,14 @@ pub fn update_nutrient_growth(
         .zip(radii.par_iter_mut())
         .zip(mode_indices.par_iter())
         .for_each(|((mass, radius), mode_index)| {
-            if let Some(mode) = genome.modes.get(*mode_index) {
+            if let Some(mode) = genome.mode(*mode_index) {
                 // Only apply nutrient growth to Test cells (cell_type == 0)
                 if mode.cell_type == 0 {
                     // Nutrient storage cap: 2x split_mass (allows storage for division plus buffer)
-                    let storage_cap = mode.split_mass * 2.0;
+                    let storage_cap = mode.storage_cap;
                     
-                    // Only gain mass if below storage cap
-                    if *mass < storage_cap {
-                        let mass_gain = mode.nutrient_gain_rate * dt;
-                        *mass = (*mass + mass_gain).min(storage_cap);
-                    }
+                    // Absorb only what fits under the cap
+                    *mass += absorbed_gain(*mass, mode.nutrient_gain_rate * dt, storage_cap);
                     
                     // Calculate target radius based on mass (linear relationship)
                     // Clamp to max_cell_size
@@ -74,17 +76,16 @@ pub fn consume_swim_nutrients_st(
     masses: &mut [f32],
     radii: &mut [f32],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) -> Vec<usize> {
-    const MIN_CELL_MASS: f32 = 0.5;
     let mut cells_to_remove = Vec::new();
     
     for i in 0..masses.len() {
         let mode_index = mode_indices[i];
-        if let Some(mode) = genome.modes.get(mode_index) {
+        if let Some(mode) = genome.mode(mode_index) {
             // Only apply nutrient consumption to Flagellocyte cells (cell_type == 1)
-            if mode.cell_type == 1 && mode.swim_force > 0.0 {
+            if mode.swims() {
                 // Consume mass proportional to swim force
                 // Consumption rate: 0.2 mass per second at full swim force (1.0)
                 let consumption_rate = 0.2;
@@ -115,13 +116,12 @@ pub fn consume_swim_nutrients(
     masses: &mut [f32],
     radii: &mut [f32],
     mode_indices: &[usize],
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) -> Vec<usize> {
     use rayon::prelude::*;
     use std::sync::Mutex;
     
-    const MIN_CELL_MASS: f32 = 0.5;
     let cells_to_remove = Mutex::new(Vec::new());
     
     masses.par_iter_mut()
@@ -129,9 +129,9 @@ pub fn consume_swim_nutrients(
         .zip(mode_indices.par_iter())
         .enumerate()
         .for_each(|(i, ((mass, radius), mode_index))| {
-            if let Some(mode) = genome.modes.get(*mode_index) {
+            if let Some(mode) = genome.mode(*mode_index) {
                 // Only apply nutrient consumption to Flagellocyte cells (cell_type == 1)
-                if mode.cell_type == 1 && mode.swim_force > 0.0 {
+                if mode.swims() {
                     // Consume mass proportional to swim force
                     // Consumption rate: 0.2 mass per second at full swim force (1.0)
                     let consumption_rate = 0.2;
@@ -155,13 +155,131 @@ pub fn consume_swim_nutrients(
     cells_to_remove.into_inner().unwrap()
 }
 
+/// Priority multiplier applied while a cell's low-nutrient boost is engaged
+const LOW_NUTRIENT_PRIORITY_BOOST: f32 = 10.0;
+
+/// Engage or release the low-nutrient priority boost for every cell
+/// The boost engages when mass drops below the mode's low_nutrient_threshold and only
+/// releases once mass recovers above threshold * low_nutrient_release_factor, so cells
+/// hovering around the threshold don't flip their priority every few ticks.
+pub fn update_low_nutrient_boost(
+    state: &mut CanonicalState,
+    genome: &crate::genome::CompiledGenome,
+) {
+    for i in 0..state.cell_count {
+        let engaged = match genome.mode(state.mode_indices[i]) {
+            Some(mode) if mode.prioritize_when_low => {
+                let mass = state.masses[i];
+                if state.low_nutrient_boost(i) {
+                    mass <= mode.low_nutrient_release
+                } else {
+                    mass < mode.low_nutrient_threshold
+                }
+            }
+            _ => false,
+        };
+        state.set_low_nutrient_boost(i, engaged);
+    }
+}
+
+/// Move nutrients over one adhesion from the higher-pressure cell to the other
+///
+/// The flow is computed from the masses at the start of the tick and limited by what the
+/// donor can give above its minimum mass, so A's delta is exactly minus B's. It only lands
+/// in `mass_deltas_buffer`; the caller applies every cell's net change afterwards, which
+/// keeps the result independent of adhesion order.
+fn transfer_over_adhesion(
+    state: &mut CanonicalState,
+    genome: &crate::genome::CompiledGenome,
+    cell_a_idx: usize,
+    cell_b_idx: usize,
+    dt: f32,
+) {
+    // Get mode settings for both cells; skip if either mode is invalid
+    let (Some(mode_a), Some(mode_b)) = (
+        genome.mode(state.mode_indices[cell_a_idx]),
+        genome.mode(state.mode_indices[cell_b_idx]),
+    ) else {
+        return;
+    };
+    
+    let mass_a = state.masses[cell_a_idx];
+    let mass_b = state.masses[cell_b_idx];
+    
+    // Apply temporary priority boost while cells are in survival mode
+    // (engaged/released with hysteresis in update_low_nutrient_boost)
+    let priority_a = if state.low_nutrient_boost(cell_a_idx) {
+        mode_a.nutrient_priority * LOW_NUTRIENT_PRIORITY_BOOST
+    } else {
+        mode_a.nutrient_priority
+    };
+    let priority_b = if state.low_nutrient_boost(cell_b_idx) {
+        mode_b.nutrient_priority * LOW_NUTRIENT_PRIORITY_BOOST
+    } else {
+        mode_b.nutrient_priority
+    };
+    
+    // Calculate equilibrium-based nutrient flow
+    // At equilibrium: mass_a / mass_b = priority_a / priority_b
+    // This means: mass_a * priority_b = mass_b * priority_a
+    // 
+    // We calculate the "pressure" difference based on mass/priority ratio
+    // Flow goes from high pressure (low priority/mass ratio) to low pressure (high priority/mass ratio)
+    let pressure_a = mass_a / priority_a;
+    let pressure_b = mass_b / priority_b;
+    
+    // Transport rate constant (tune this for desired equilibration speed)
+    // Higher values = faster equilibration
+    let transport_rate = 0.5;
+    
+    // Calculate mass transfer (positive = A loses, B gains)
+    let mass_transfer = (pressure_a - pressure_b) * transport_rate * dt;
+    
+    // Limit by what the donor holds above its minimum (0.1 with prioritize_when_low)
+    let transferable = |mass: f32, prioritize: bool| (mass - if prioritize { 0.1 } else { 0.0 }).max(0.0);
+    let (donor, recipient, wanted) = if mass_transfer > 0.0 {
+        (cell_a_idx, cell_b_idx, mass_transfer.min(transferable(mass_a, mode_a.prioritize_when_low)))
+    } else {
+        (cell_b_idx, cell_a_idx, (-mass_transfer).min(transferable(mass_b, mode_b.prioritize_when_low)))
+    };
+    
+    let moved = conserving_amount(state.masses[donor], state.masses[recipient], wanted);
+    state.mass_deltas_buffer[donor] -= moved;
+    state.mass_deltas_buffer[recipient] += moved;
+}
+
+/// Amount close to `amount` that leaves `donor` and reaches `recipient` without rounding
+///
+/// Subtracting and adding the same f32 rounds differently on masses of different size;
+/// summed over every adhesion and tick that drifts the total. The amount is snapped to the
+/// spacing of whichever mass rounded it until both operations are exact.
+///
+/// Each snap moves to a coarser power-of-two spacing, so this settles within a step or two;
+/// only a sum that keeps crossing an exponent boundary runs out of tries. Returning 0 then
+/// just skips the bond for this tick: nothing is lost, and the pressure difference that
+/// drove the flow is still there next tick.
+fn conserving_amount(donor: f32, recipient: f32, amount: f32) -> f32 {
+    let exact = |mass: f32, delta: f32| (mass + delta) as f64 == mass as f64 + delta as f64;
+    let mut amount = amount;
+    for _ in 0..4 {
+        if !exact(donor, -amount) {
+            amount = (donor as f64 - (donor - amount) as f64) as f32;
+        } else if !exact(recipient, amount) {
+            amount = ((recipient + amount) as f64 - recipient as f64) as f32;
+        } else {
+            return amount;
+        }
+    }
+    0.0
+}
+
 /// Transport nutrients between adhesion-connected cells - Single-threaded
 /// Nutrients flow to establish equilibrium where mass ratios match priority ratios.
 /// At equilibrium: mass_a / mass_b = priority_a / priority_b
 /// Flow is driven by "pressure" (mass/priority ratio) differences between cells.
 pub fn transport_nutrients_st(
     state: &mut CanonicalState,
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) {
     // Use pre-allocated buffer and clear only the portion we need
@@ -170,6 +288,8 @@ pub fn transport_nutrients_st(
         state.mass_deltas_buffer[i] = 0.0;
     }
     
+    update_low_nutrient_boost(state, genome);
+    
     // Process each active adhesion connection
     let adhesion_capacity = state.adhesion_connections.is_active.len();
     for adhesion_idx in 0..adhesion_capacity {
@@ -185,93 +305,16 @@ pub fn transport_nutrients_st(
             continue;
         }
         
-        // Get mode settings for both cells
-        let mode_a = genome.modes.get(state.mode_indices[cell_a_idx]);
-        let mode_b = genome.modes.get(state.mode_indices[cell_b_idx]);
-        
-        // Skip if either mode is invalid
-        if mode_a.is_none() || mode_b.is_none() {
-            continue;
-        }
-        
-        // Get base priorities (default to 1.0 if mode not found)
-        let base_priority_a = mode_a.map(|m| m.nutrient_priority).unwrap_or(1.0);
-        let base_priority_b = mode_b.map(|m| m.nutrient_priority).unwrap_or(1.0);
-        
-        // Get prioritize_when_low flags
-        let prioritize_a = mode_a.map(|m| m.prioritize_when_low).unwrap_or(true);
-        let prioritize_b = mode_b.map(|m| m.prioritize_when_low).unwrap_or(true);
-        
-        // Get masses
-        let mass_a = state.masses[cell_a_idx];
-        let mass_b = state.masses[cell_b_idx];
-        
-        // Apply temporary priority boost when cells are dangerously low on nutrients
-        // Boost activates when mass drops below 0.6 (danger threshold)
-        // Boost automatically deactivates when mass rises above 0.6
-        // This makes the boost temporary - it only applies during critical low-nutrient periods
-        let danger_threshold = 0.6;
-        let priority_boost = 10.0;
-        
-        // For cell A: boost only when below danger threshold
-        let priority_a = if prioritize_a && mass_a < danger_threshold {
-            base_priority_a * priority_boost
-        } else {
-            base_priority_a
-        };
-        
-        // For cell B: boost only when below danger threshold
-        let priority_b = if prioritize_b && mass_b < danger_threshold {
-            base_priority_b * priority_boost
-        } else {
-            base_priority_b
-        };
-        
-        // Calculate equilibrium-based nutrient flow
-        // At equilibrium: mass_a / mass_b = priority_a / priority_b
-        // This means: mass_a * priority_b = mass_b * priority_a
-        // 
-        // We calculate the "pressure" difference based on mass/priority ratio
-        // Flow goes from high pressure (low priority/mass ratio) to low pressure (high priority/mass ratio)
-        let pressure_a = mass_a / priority_a;
-        let pressure_b = mass_b / priority_b;
-        
-        // Flow is proportional to pressure difference
-        // Positive flow means A -> B, negative means B -> A
-        let pressure_diff = pressure_a - pressure_b;
-        
-        // Transport rate constant (tune this for desired equilibration speed)
-        // Higher values = faster equilibration
-        let transport_rate = 0.5;
-        
-        // Calculate mass transfer (positive = A loses, B gains)
-        let mass_transfer = pressure_diff * transport_rate * dt;
-        
-        // Apply transfer with different minimum thresholds based on prioritize_when_low
-        let min_mass_a = if prioritize_a { 0.1 } else { 0.0 };
-        let min_mass_b = if prioritize_b { 0.1 } else { 0.0 };
-        
-        let actual_transfer = if mass_transfer > 0.0 {
-            // A -> B: limit by A's mass (respect minimum threshold)
-            mass_transfer.min(mass_a - min_mass_a)
-        } else {
-            // B -> A: limit by B's mass (respect minimum threshold)
-            mass_transfer.max(-(mass_b - min_mass_b))
-        };
-        
-        // Accumulate deltas
-        state.mass_deltas_buffer[cell_a_idx] -= actual_transfer;
-        state.mass_deltas_buffer[cell_b_idx] += actual_transfer;
+        transfer_over_adhesion(state, genome, cell_a_idx, cell_b_idx, dt);
     }
     
     // Apply mass changes and update radii
     // Track cells that die (mass < 0.5 minimum threshold)
-    const MIN_CELL_MASS: f32 = 0.5;
     // Use pre-allocated buffer for cells to remove
     state.cells_to_remove_buffer.clear();
     
     for i in 0..state.cell_count {
-        if state.mass_deltas_buffer[i].abs() > 0.0001 {
+        if state.mass_deltas_buffer[i] != 0.0 {
             state.masses[i] += state.mass_deltas_buffer[i];
             
             // Check if cell has died (below minimum mass threshold)
@@ -281,7 +324,7 @@ pub fn transport_nutrients_st(
             }
             
             // Update radius based on new mass
-            if let Some(mode) = genome.modes.get(state.mode_indices[i]) {
+            if let Some(mode) = genome.mode(state.mode_indices[i]) {
                 let target_radius = state.masses[i].min(mode.max_cell_size);
                 if mode.cell_type == 0 {
                     // Test cells: radius 0.5 to 2.0
@@ -309,11 +352,38 @@ pub fn remove_dead_cell(state: &mut CanonicalState, cell_idx: usize) {
         return;
     }
     
-    // Remove all adhesion connections for this cell
-    state.adhesion_manager.remove_all_connections_for_cell(&mut state.adhesion_connections, cell_idx);
-    
+    // Record the removal and the adhesions it breaks for event consumers
+    let cell_id = state.cell_ids[cell_idx];
+    state.removed_cell_ids_buffer.push(cell_id);
+    state.removed_cell_positions_buffer.push(state.positions[cell_idx]);
+    if let Some(indices) = state.adhesion_manager.cell_adhesion_indices.get(cell_idx) {
+        for &connection_idx in indices {
+            if connection_idx < 0 || state.adhesion_connections.is_active[connection_idx as usize] == 0 {
+                continue;
+            }
+            let connection_idx = connection_idx as usize;
+            let partner_idx = if state.adhesion_connections.cell_a_index[connection_idx] == cell_idx {
+                state.adhesion_connections.cell_b_index[connection_idx]
+            } else {
+                state.adhesion_connections.cell_a_index[connection_idx]
+            };
+            if partner_idx < state.cell_count {
+                state.broken_adhesions_buffer.push((cell_id, state.cell_ids[partner_idx]));
+                state.broken_adhesion_speeds_buffer
+                    .push((state.velocities[partner_idx] - state.velocities[cell_idx]).length());
+            }
+        }
+    }
+
     let last_idx = state.cell_count - 1;
     
+    // Old to new index of every cell: the last cell moves into the removed one's slot
+    let mut compaction_map = std::mem::take(&mut state.scratch.compaction_map);
+    compaction_map.clear();
+    compaction_map.extend((0..state.cell_count).map(Some));
+    compaction_map[cell_idx] = None;
+    compaction_map[last_idx] = (cell_idx != last_idx).then_some(cell_idx);
+    
     if cell_idx != last_idx {
         // Swap with last cell
         state.cell_ids[cell_idx] = state.cell_ids[last_idx];
@@ -327,56 +397,55 @@ pub fn remove_dead_cell(state: &mut CanonicalState, cell_idx: usize) {
         state.rotations[cell_idx] = state.rotations[last_idx];
         state.angular_velocities[cell_idx] = state.angular_velocities[last_idx];
         state.genome_orientations[cell_idx] = state.genome_orientations[last_idx];
+        state.lineage_references[cell_idx] = state.lineage_references[last_idx];
         state.forces[cell_idx] = state.forces[last_idx];
         state.torques[cell_idx] = state.torques[last_idx];
+        state.contact_pressures[cell_idx] = state.contact_pressures[last_idx];
         state.accelerations[cell_idx] = state.accelerations[last_idx];
         state.prev_accelerations[cell_idx] = state.prev_accelerations[last_idx];
         state.stiffnesses[cell_idx] = state.stiffnesses[last_idx];
         state.birth_times[cell_idx] = state.birth_times[last_idx];
         state.split_intervals[cell_idx] = state.split_intervals[last_idx];
+        state.split_masses[cell_idx] = state.split_masses[last_idx];
         state.split_counts[cell_idx] = state.split_counts[last_idx];
         state.split_ready_frame[cell_idx] = state.split_ready_frame[last_idx];
-        
-        // Update adhesion indices: all references to last_idx should now point to cell_idx
-        if last_idx < state.adhesion_manager.cell_adhesion_indices.len() {
-            state.adhesion_manager.cell_adhesion_indices[cell_idx] = 
-                state.adhesion_manager.cell_adhesion_indices[last_idx];
-            
-            // Update all adhesion connections that reference last_idx to now reference cell_idx
-            for adhesion_idx in 0..state.adhesion_connections.is_active.len() {
-                if state.adhesion_connections.is_active[adhesion_idx] == 0 {
-                    continue;
-                }
-                
-                if state.adhesion_connections.cell_a_index[adhesion_idx] == last_idx {
-                    state.adhesion_connections.cell_a_index[adhesion_idx] = cell_idx;
-                }
-                if state.adhesion_connections.cell_b_index[adhesion_idx] == last_idx {
-                    state.adhesion_connections.cell_b_index[adhesion_idx] = cell_idx;
-                }
-            }
-        }
+        let last_boost = state.low_nutrient_boost(last_idx);
+        state.set_low_nutrient_boost(cell_idx, last_boost);
+        let last_pinned = state.is_pinned(last_idx);
+        state.set_pinned(cell_idx, last_pinned);
+        let last_user_data = state.user_data(last_idx);
+        state.set_user_data(cell_idx, last_user_data);
+        state.copy_force_heat(last_idx, cell_idx);
     }
+    state.set_pinned(last_idx, false);
     
-    // Clear the adhesion indices for the removed cell slot
-    if cell_idx < state.adhesion_manager.cell_adhesion_indices.len() {
-        state.adhesion_manager.init_cell_adhesion_indices(last_idx);
+    // Bonds of the removed cell go, the moved cell's follow it, and every slot list is
+    // rebuilt from the connection table
+    state.adhesion_manager.remap_after_compaction(&mut state.adhesion_connections, &compaction_map, last_idx);
+    state.scratch.compaction_map = compaction_map;
+    if cfg!(debug_assertions) {
+        let problems = state.adhesion_manager.consistency_problems(&state.adhesion_connections, last_idx);
+        assert!(problems.is_empty(), "adhesion tables out of sync after removing cell {}: {:?}", cell_id, problems);
     }
     
     // Decrement cell count
     state.cell_count -= 1;
+    state.collision_cache.invalidate();
 }
 
 /// Transport nutrients between adhesion-connected cells - Single-threaded with blocked cells
 /// Skips nutrient transfer for cells attempting to split this frame (prevents nutrient loss during division)
 pub fn transport_nutrients_with_deferred_st(
     state: &mut CanonicalState,
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
     cells_attempting_split: &std::collections::HashSet<usize>,
 ) {
-    // Calculate mass changes for each cell (accumulate transfers)
-    let mut mass_deltas = vec![0.0f32; state.cell_count];
+    for i in 0..state.cell_count {
+        state.mass_deltas_buffer[i] = 0.0;
+    }
+    
+    update_low_nutrient_boost(state, genome);
     
     // Process each active adhesion connection
     let adhesion_capacity = state.adhesion_connections.is_active.len();
@@ -399,72 +468,15 @@ pub fn transport_nutrients_with_deferred_st(
             continue;
         }
         
-        // Get mode settings for both cells
-        let mode_a = genome.modes.get(state.mode_indices[cell_a_idx]);
-        let mode_b = genome.modes.get(state.mode_indices[cell_b_idx]);
-        
-        // Skip if either mode is invalid
-        if mode_a.is_none() || mode_b.is_none() {
-            continue;
-        }
-        
-        // Get base priorities (default to 1.0 if mode not found)
-        let base_priority_a = mode_a.map(|m| m.nutrient_priority).unwrap_or(1.0);
-        let base_priority_b = mode_b.map(|m| m.nutrient_priority).unwrap_or(1.0);
-        
-        // Get prioritize_when_low flags
-        let prioritize_a = mode_a.map(|m| m.prioritize_when_low).unwrap_or(true);
-        let prioritize_b = mode_b.map(|m| m.prioritize_when_low).unwrap_or(true);
-        
-        // Get masses
-        let mass_a = state.masses[cell_a_idx];
-        let mass_b = state.masses[cell_b_idx];
-        
-        // Apply temporary priority boost when cells are dangerously low on nutrients
-        let danger_threshold = 0.6;
-        let priority_boost = 10.0;
-        
-        let priority_a = if prioritize_a && mass_a < danger_threshold {
-            base_priority_a * priority_boost
-        } else {
-            base_priority_a
-        };
-        
-        let priority_b = if prioritize_b && mass_b < danger_threshold {
-            base_priority_b * priority_boost
-        } else {
-            base_priority_b
-        };
-        
-        // Calculate equilibrium-based nutrient flow
-        let pressure_a = mass_a / priority_a;
-        let pressure_b = mass_b / priority_b;
-        let pressure_diff = pressure_a - pressure_b;
-        let transport_rate = 0.5;
-        let mass_transfer = pressure_diff * transport_rate * dt;
-        
-        // Apply transfer with minimum thresholds
-        let min_mass_a = if prioritize_a { 0.1 } else { 0.0 };
-        let min_mass_b = if prioritize_b { 0.1 } else { 0.0 };
-        
-        let actual_transfer = if mass_transfer > 0.0 {
-            mass_transfer.min(mass_a - min_mass_a)
-        } else {
-            mass_transfer.max(-(mass_b - min_mass_b))
-        };
-        
-        // Accumulate deltas
-        mass_deltas[cell_a_idx] -= actual_transfer;
-        mass_deltas[cell_b_idx] += actual_transfer;
+        transfer_over_adhesion(state, genome, cell_a_idx, cell_b_idx, dt);
     }
     
     // Apply mass changes and update radii
-    const MIN_CELL_MASS: f32 = 0.5;
     let mut cells_to_remove = Vec::new();
     
     for i in 0..state.cell_count {
-        if mass_deltas[i].abs() > 0.0001 {
-            state.masses[i] += mass_deltas[i];
+        if state.mass_deltas_buffer[i] != 0.0 {
+            state.masses[i] += state.mass_deltas_buffer[i];
             
             // Check if cell died
             if state.masses[i] < MIN_CELL_MASS {
@@ -474,7 +486,7 @@ pub fn transport_nutrients_with_deferred_st(
             
             // Update radius based on new mass
             let mode_index = state.mode_indices[i];
-            if let Some(mode) = genome.modes.get(mode_index) {
+            if let Some(mode) = genome.mode(mode_index) {
                 let target_radius = state.masses[i].min(mode.max_cell_size);
                 state.radii[i] = target_radius.clamp(0.5, 2.0);
             }
@@ -491,7 +503,7 @@ pub fn transport_nutrients_with_deferred_st(
 /// This is a simplified parallel version that processes adhesions in parallel
 pub fn transport_nutrients(
     state: &mut CanonicalState,
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) {
     // For thread safety, we use the single-threaded version
@@ -502,10 +514,199 @@ pub fn transport_nutrients(
 /// Transport nutrients between adhesion-connected cells - Multithreaded with blocked cells
 pub fn transport_nutrients_with_deferred(
     state: &mut CanonicalState,
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
     cells_attempting_split: &std::collections::HashSet<usize>,
 ) {
     // For thread safety, we use the single-threaded version
     transport_nutrients_with_deferred_st(state, genome, dt, cells_attempting_split);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{CompiledGenome, GenomeData, ModeSettings};
+    
+    #[test]
+    fn test_low_nutrient_boost_settles_without_flapping() {
+        // Donor (mode 0) is a well-fed test cell; recipient (mode 1) is a low-priority
+        // flagellocyte burning mass faster than it can hold on to without the boost
+        let mut donor = ModeSettings::new_self_splitting(0, "Donor".to_string());
+        donor.split_mass = 0.45; // Storage cap 0.9
+        donor.nutrient_gain_rate = 1.0;
+        let mut recipient = ModeSettings::new_self_splitting(1, "Recipient".to_string());
+        recipient.cell_type = 1;
+        recipient.swim_force = 0.5;
+        recipient.nutrient_gain_rate = 0.0;
+        recipient.nutrient_priority = 0.1;
+        let compiled = CompiledGenome::compile(&GenomeData {
+            modes: vec![donor, recipient],
+            ..GenomeData::default()
+        });
+        
+        let mut state = CanonicalState::new(8);
+        for (mode_index, mass, x) in [(0, 0.9, -1.0), (1, 1.0, 1.0)] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, mass, 1.0,
+                0, mode_index, 0.0, 100.0, 100.0, 10.0, Quat::IDENTITY, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+        
+        let dt = 1.0 / 64.0;
+        let mut toggles = 0;
+        let mut engaged = state.low_nutrient_boost(1);
+        let mut last_mass = 0.0;
+        for _ in 0..(64 * 20) {
+            update_nutrient_growth_st(&mut state.masses[..2], &mut state.radii[..2], &state.mode_indices[..2], &compiled, dt);
+            let dead = consume_swim_nutrients_st(&mut state.masses[..2], &mut state.radii[..2], &state.mode_indices[..2], &compiled, dt);
+            assert!(dead.is_empty(), "recipient starved");
+            last_mass = state.masses[1];
+            transport_nutrients_st(&mut state, &compiled, dt);
+            assert_eq!(state.cell_count, 2, "recipient starved");
+            
+            if state.low_nutrient_boost(1) != engaged {
+                engaged = !engaged;
+                toggles += 1;
+            }
+        }
+        
+        assert!(engaged, "recipient should stay in survival mode");
+        assert_eq!(toggles, 1);
+        // Transfer balances the swim consumption (0.2 * swim_force)
+        let transfer_rate = (state.masses[1] - last_mass) / dt;
+        assert!((transfer_rate - 0.1).abs() < 0.01, "transfer rate {}", transfer_rate);
+    }
+    
+    /// Bond between two cells by id, with what the force pass reads from it
+    #[derive(Clone, Copy, Debug)]
+    struct Bond {
+        ids: (u32, u32),
+        anchors: (Vec3, Vec3),
+        twist_references: (Quat, Quat),
+    }
+    
+    fn bonds_of(state: &CanonicalState) -> Vec<Bond> {
+        let connections = &state.adhesion_connections;
+        (0..connections.active_count)
+            .filter(|&idx| connections.is_active[idx] != 0)
+            .map(|idx| Bond {
+                ids: (state.cell_ids[connections.cell_a_index[idx]], state.cell_ids[connections.cell_b_index[idx]]),
+                anchors: (connections.anchor_direction_a[idx], connections.anchor_direction_b[idx]),
+                twist_references: (connections.twist_reference_a[idx], connections.twist_reference_b[idx]),
+            })
+            .collect()
+    }
+    
+    /// `count` cells with random poses and motion, bonded at random up to a few bonds each
+    fn random_bonded_population(seed: u64, count: usize) -> CanonicalState {
+        let random = |cell: u32, index: u32| crate::simulation::deterministic_random(cell, 0, seed, index) * 2.0 - 1.0;
+        let unit = |cell: u32, index: u32| {
+            Vec3::new(random(cell, index), random(cell, index + 1), random(cell, index + 2)).try_normalize().unwrap_or(Vec3::X)
+        };
+        let mut state = CanonicalState::new(count);
+        for cell in 0..count as u32 {
+            let position = Vec3::new(random(cell, 0), random(cell, 1), random(cell, 2)) * 4.0;
+            let rotation = Quat::from_axis_angle(unit(cell, 3), random(cell, 6) * 3.0);
+            state.add_cell(position, unit(cell, 7) * 0.5, rotation, unit(cell, 10) * 0.3, 1.0, 1.0,
+                0, 0, 0.0, 1000.0, 2.0, 10.0, rotation, 0);
+        }
+        for attempt in 0..count as u32 * 3 {
+            let a = (crate::simulation::deterministic_random(attempt, 1, seed, 0) * count as f32) as usize % count;
+            let b = (crate::simulation::deterministic_random(attempt, 1, seed, 1) * count as f32) as usize % count;
+            if a == b || state.adhesion_manager.are_cells_connected(&state.adhesion_connections, a, b) {
+                continue;
+            }
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, a, b, 0,
+                unit(attempt, 20), unit(attempt, 23), Vec3::Z, Vec3::Z,
+                Quat::from_rotation_z(random(attempt, 26)), Quat::from_rotation_x(random(attempt, 27)),
+            );
+        }
+        state
+    }
+    
+    /// Adhesion force and torque on each cell, by cell id
+    fn adhesion_forces_by_id(state: &mut CanonicalState) -> std::collections::BTreeMap<u32, (Vec3, Vec3)> {
+        let count = state.cell_count;
+        let mut forces = vec![Vec3::ZERO; count];
+        let mut torques = vec![Vec3::ZERO; count];
+        crate::cell::compute_adhesion_forces(
+            &mut state.adhesion_connections,
+            &state.positions[..count],
+            &state.velocities[..count],
+            &state.rotations[..count],
+            &state.angular_velocities[..count],
+            &state.masses[..count],
+            &[crate::cell::AdhesionSettings::default()],
+            &crate::simulation::PhysicsConfig::default().adhesion_tiers(),
+            &mut forces,
+            &mut torques,
+        );
+        (0..count).map(|index| (state.cell_ids[index], (forces[index], torques[index]))).collect()
+    }
+    
+    #[test]
+    fn test_random_removals_keep_adhesion_tables_consistent() {
+        for seed in 0..24u64 {
+            let count = 12 + (seed as usize % 5) * 6;
+            let mut state = random_bonded_population(seed, count);
+            let original = bonds_of(&state);
+            assert!(original.len() >= count, "seed {} bonded too few cells", seed);
+            
+            // Remove a random third of the cells one at a time, in random order
+            let mut doomed: Vec<u32> = (0..count as u32)
+                .filter(|&id| crate::simulation::deterministic_random(id, 2, seed, 0) < 0.35)
+                .collect();
+            doomed.sort_by(|a, b| crate::simulation::deterministic_random(*a, 3, seed, 0)
+                .total_cmp(&crate::simulation::deterministic_random(*b, 3, seed, 0)));
+            for &id in &doomed {
+                let index = state.cell_ids[..state.cell_count].iter().position(|&cell| cell == id).unwrap();
+                remove_dead_cell(&mut state, index);
+                let problems = state.adhesion_manager.consistency_problems(&state.adhesion_connections, state.cell_count);
+                assert!(problems.is_empty(), "seed {} after removing cell {}: {:?}", seed, id, problems);
+            }
+            assert_eq!(state.cell_count, count - doomed.len());
+            
+            // Reference: the survivors and the bonds between them, built from scratch
+            let survivors: Vec<u32> = (0..count as u32).filter(|id| !doomed.contains(id)).collect();
+            let survivor_bonds: Vec<Bond> = original.iter()
+                .filter(|bond| survivors.contains(&bond.ids.0) && survivors.contains(&bond.ids.1))
+                .copied()
+                .collect();
+            let mut reference = CanonicalState::new(count);
+            for &id in &survivors {
+                let index = state.cell_ids[..state.cell_count].iter().position(|&cell| cell == id).unwrap();
+                let added = reference.add_cell(state.positions[index], state.velocities[index], state.rotations[index],
+                    state.angular_velocities[index], state.masses[index], state.radii[index],
+                    0, 0, 0.0, 1000.0, 2.0, 10.0, state.genome_orientations[index], 0).unwrap();
+                reference.cell_ids[added] = id;
+            }
+            let reference_index = |id: u32| survivors.iter().position(|&survivor| survivor == id).unwrap();
+            for bond in &survivor_bonds {
+                reference.adhesion_manager.add_adhesion_with_directions(
+                    &mut reference.adhesion_connections, reference_index(bond.ids.0), reference_index(bond.ids.1), 0,
+                    bond.anchors.0, bond.anchors.1, Vec3::Z, Vec3::Z, bond.twist_references.0, bond.twist_references.1,
+                ).unwrap();
+            }
+            
+            // The same bonds survive, so the same forces act on the same cells
+            let sorted = |bonds: Vec<Bond>| {
+                let mut ids: Vec<(u32, u32)> = bonds.into_iter().map(|bond| bond.ids).collect();
+                ids.sort();
+                ids
+            };
+            assert_eq!(sorted(bonds_of(&state)), sorted(survivor_bonds));
+            let remapped = adhesion_forces_by_id(&mut state);
+            let expected = adhesion_forces_by_id(&mut reference);
+            assert_eq!(remapped.keys().collect::<Vec<_>>(), expected.keys().collect::<Vec<_>>());
+            for (id, (force, torque)) in &remapped {
+                let (expected_force, expected_torque) = expected[id];
+                let tolerance = 1e-4 * (1.0 + expected_force.length() + expected_torque.length());
+                assert!(force.distance(expected_force) < tolerance, "seed {} cell {}: force {:?} vs {:?}", seed, id, force, expected_force);
+                assert!(torque.distance(expected_torque) < tolerance, "seed {} cell {}: torque {:?} vs {:?}", seed, id, torque, expected_torque);
+            }
+        }
+    }
+}
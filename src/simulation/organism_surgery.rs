@@ -0,0 +1,479 @@
+use bevy::prelude::*;
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::event_timeline::{EventTimeline, TimelineEvent, TimelineEventKind};
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin applying organism splits, grafts, anchor edits and cell removals from the viewport tools and cell groups
+pub struct OrganismSurgeryPlugin;
+
+impl Plugin for OrganismSurgeryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurgeryRequests>()
+            .add_message::<OrganismTopologyChanged>()
+            .add_systems(Update, apply_surgery_requests);
+    }
+}
+
+/// An editor operation on organism topology
+///
+/// Cells are named by ID so a recorded operation survives index shuffling from
+/// division and removal, and replays at its tick reproduce it exactly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SurgeryOp {
+    /// Deactivate every bond crossing the wedge swept between two rays from `apex`
+    /// (a cutting stroke drawn across the screen)
+    Cut { apex: Vec3, edge_a: Vec3, edge_b: Vec3 },
+    /// Split the organism at a bond: deactivate every bond of that organism crossing
+    /// the plane through the bond's midpoint, perpendicular to it
+    SplitAtBond { cell_a: u32, cell_b: u32 },
+    /// Bond two cells of different organisms, using the adhesion settings of `mode_index`
+    ///
+    /// With `snap`, the smaller organism is first moved rigidly so the two cells touch,
+    /// turned so it faces away from the other organism.
+    Graft { cell_a: u32, cell_b: u32, mode_index: usize, snap: bool },
+    /// Point the bond between two cells along new local anchor directions, `anchor_a`
+    /// on `cell_a` and `anchor_b` on `cell_b` (see `simulation::anchor_editing`)
+    SetAnchors { cell_a: u32, cell_b: u32, anchor_a: Vec3, anchor_b: Vec3 },
+    /// Remove a cell and its bonds (bulk delete from a cell group)
+    Remove { cell_id: u32 },
+}
+
+impl SurgeryOp {
+    pub fn timeline_kind(&self) -> TimelineEventKind {
+        match self {
+            SurgeryOp::Cut { .. } | SurgeryOp::SplitAtBond { .. } => TimelineEventKind::OrganismSplit,
+            SurgeryOp::Graft { .. } => TimelineEventKind::Graft,
+            SurgeryOp::SetAnchors { .. } => TimelineEventKind::AnchorEdit,
+            SurgeryOp::Remove { .. } => TimelineEventKind::Death,
+        }
+    }
+
+    /// Whether the operation can change which cells form an organism
+    pub fn changes_topology(&self) -> bool {
+        !matches!(self, SurgeryOp::SetAnchors { .. })
+    }
+
+    /// Whether this operation supersedes `earlier` when both land on the same tick
+    /// (successive frames of one anchor drag)
+    pub fn supersedes(&self, earlier: &SurgeryOp) -> bool {
+        match (*self, *earlier) {
+            (SurgeryOp::SetAnchors { cell_a, cell_b, .. }, SurgeryOp::SetAnchors { cell_a: a, cell_b: b, .. }) => {
+                (cell_a, cell_b) == (a, b) || (cell_a, cell_b) == (b, a)
+            }
+            _ => false,
+        }
+    }
+
+    /// Cells named by the operation, for the timeline marker
+    pub fn cells(&self) -> [Option<u32>; 2] {
+        match *self {
+            SurgeryOp::Cut { .. } => [None, None],
+            SurgeryOp::Remove { cell_id } => [Some(cell_id), None],
+            SurgeryOp::SplitAtBond { cell_a, cell_b }
+            | SurgeryOp::Graft { cell_a, cell_b, .. }
+            | SurgeryOp::SetAnchors { cell_a, cell_b, .. } => [Some(cell_a), Some(cell_b)],
+        }
+    }
+}
+
+/// Sent whenever an operation changed which cells form an organism
+#[derive(Message, Clone, Copy, Debug)]
+pub struct OrganismTopologyChanged {
+    pub tick: u32,
+    pub op: SurgeryOp,
+}
+
+/// Where a graft would move the smaller organism
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraftSnap {
+    /// Indices of the moved cells
+    pub moved: Vec<usize>,
+    pub rotation: Quat,
+    /// Position the rotation turns about (the grafted cell before the move)
+    pub pivot: Vec3,
+    /// Where the grafted cell ends up
+    pub destination: Vec3,
+}
+
+impl GraftSnap {
+    pub fn apply_to(&self, position: Vec3) -> Vec3 {
+        self.destination + self.rotation * (position - self.pivot)
+    }
+}
+
+/// Direction a mode's children split along, in the cell's genome frame
+pub(crate) fn split_direction(genome: &GenomeData, mode_index: usize) -> Vec3 {
+    genome.modes.get(mode_index).map_or(Vec3::Z, |mode| {
+        let pitch = mode.parent_split_direction.x.to_radians();
+        let yaw = mode.parent_split_direction.y.to_radians();
+        Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0) * Vec3::Z
+    })
+}
+
+impl CanonicalState {
+    pub(crate) fn index_of(&self, cell_id: u32) -> Option<usize> {
+        self.cell_ids[..self.cell_count].iter().position(|&id| id == cell_id)
+    }
+
+    /// Active bond slots `crosses` accepts, in slot order
+    fn bond_slots_where(&self, mut crosses: impl FnMut(usize, usize) -> bool) -> Vec<usize> {
+        let connections = &self.adhesion_connections;
+        (0..connections.active_count)
+            .filter(|&slot| {
+                connections.is_active[slot] != 0
+                    && crosses(connections.cell_a_index[slot], connections.cell_b_index[slot])
+            })
+            .collect()
+    }
+
+    /// Bond slots a cut or split would deactivate (empty for grafts and anchor edits)
+    pub fn bonds_cut_by(&self, op: &SurgeryOp) -> Vec<usize> {
+        match *op {
+            SurgeryOp::Cut { apex, edge_a, edge_b } => {
+                let normal = edge_a.cross(edge_b);
+                if normal.length_squared() < 1e-12 {
+                    return Vec::new();
+                }
+                self.bond_slots_where(|a, b| {
+                    let (pa, pb) = (self.positions[a], self.positions[b]);
+                    let (side_a, side_b) = (normal.dot(pa - apex), normal.dot(pb - apex));
+                    if side_a * side_b >= 0.0 {
+                        return false;
+                    }
+                    // Only where the bond pierces the stroke, not the whole plane
+                    let offset = pa + (pb - pa) * (side_a / (side_a - side_b)) - apex;
+                    normal.dot(edge_a.cross(offset)) >= 0.0 && normal.dot(offset.cross(edge_b)) >= 0.0
+                })
+            }
+            SurgeryOp::SplitAtBond { cell_a, cell_b } => {
+                let (Some(a), Some(b)) = (self.index_of(cell_a), self.index_of(cell_b)) else {
+                    return Vec::new();
+                };
+                let point = (self.positions[a] + self.positions[b]) * 0.5;
+                let normal = self.positions[b] - self.positions[a];
+                let mut members = vec![false; self.cell_count];
+                for i in self.adhesion_manager.organism_members(&self.adhesion_connections, a) {
+                    members[i] = true;
+                }
+                self.bond_slots_where(|i, j| {
+                    members[i] && members[j]
+                        && normal.dot(self.positions[i] - point) * normal.dot(self.positions[j] - point) < 0.0
+                })
+            }
+            SurgeryOp::Graft { .. } | SurgeryOp::SetAnchors { .. } | SurgeryOp::Remove { .. } => Vec::new(),
+        }
+    }
+
+    /// Rigid move that puts `moving_cell` in contact with `fixed_cell`, carrying its whole organism
+    ///
+    /// The moved organism is the smaller of the two (ties move `moving_cell`'s). Returns
+    /// None if the cells are missing or already in the same organism.
+    pub fn graft_snap(&self, fixed_cell: usize, moving_cell: usize) -> Option<GraftSnap> {
+        if fixed_cell >= self.cell_count || moving_cell >= self.cell_count
+            || self.adhesion_manager.are_cells_in_same_organism(&self.adhesion_connections, fixed_cell, moving_cell)
+        {
+            return None;
+        }
+
+        let members_fixed = self.adhesion_manager.organism_members(&self.adhesion_connections, fixed_cell);
+        let members_moving = self.adhesion_manager.organism_members(&self.adhesion_connections, moving_cell);
+        let (fixed, moving, mut moved) = if members_fixed.len() < members_moving.len() {
+            (moving_cell, fixed_cell, members_fixed)
+        } else {
+            (fixed_cell, moving_cell, members_moving)
+        };
+        moved.sort_unstable();
+
+        let pivot = self.positions[moving];
+        let direction = (pivot - self.positions[fixed]).try_normalize().unwrap_or(Vec3::X);
+        let destination = self.positions[fixed] + direction * (self.radii[fixed] + self.radii[moving]);
+
+        // Turn the organism so its body trails away from the contact instead of overlapping it
+        let centroid = moved.iter().map(|&i| self.positions[i]).sum::<Vec3>() / moved.len() as f32;
+        let rotation = (centroid - pivot).try_normalize()
+            .map_or(Quat::IDENTITY, |outward| Quat::from_rotation_arc(outward, direction));
+
+        Some(GraftSnap { moved, rotation, pivot, destination })
+    }
+
+    /// Apply a surgery operation; returns false if it changed nothing
+    ///
+    /// Operations are idempotent, so replaying one at its tick more than once is harmless.
+    pub fn apply_surgery(&mut self, op: SurgeryOp, genome: &GenomeData) -> bool {
+        let changed = match op {
+            SurgeryOp::Cut { .. } | SurgeryOp::SplitAtBond { .. } => {
+                let slots = self.bonds_cut_by(&op);
+                for &slot in &slots {
+                    self.adhesion_manager.remove_adhesion(&mut self.adhesion_connections, slot);
+                }
+                !slots.is_empty()
+            }
+            SurgeryOp::Graft { cell_a, cell_b, mode_index, snap } => {
+                let (Some(a), Some(b)) = (self.index_of(cell_a), self.index_of(cell_b)) else {
+                    return false;
+                };
+                let Some(graft) = self.graft_snap(a, b) else {
+                    return false;
+                };
+
+                if snap {
+                    for &i in &graft.moved {
+                        self.positions[i] = graft.apply_to(self.positions[i]);
+                        self.prev_positions[i] = self.positions[i];
+                        self.velocities[i] = graft.rotation * self.velocities[i];
+                        self.angular_velocities[i] = graft.rotation * self.angular_velocities[i];
+                        self.rotations[i] = (graft.rotation * self.rotations[i]).normalize();
+                        self.genome_orientations[i] = (graft.rotation * self.genome_orientations[i]).normalize();
+                        let lineage = &mut self.lineage_references[i];
+                        lineage.anchor = (graft.rotation * lineage.anchor).normalize();
+                    }
+                }
+
+                // Bond settings are looked up by mode, so an out-of-range mode falls back to the first cell's
+                let mode_index = if mode_index < genome.modes.len() { mode_index } else { self.mode_indices[a] };
+                let direction = (self.positions[b] - self.positions[a]).try_normalize().unwrap_or(Vec3::X);
+                self.adhesion_manager.add_adhesion_with_directions(
+                    &mut self.adhesion_connections,
+                    a,
+                    b,
+                    mode_index,
+                    self.rotations[a].inverse() * direction,
+                    self.rotations[b].inverse() * -direction,
+                    split_direction(genome, self.mode_indices[a]),
+                    split_direction(genome, self.mode_indices[b]),
+                    self.genome_orientations[a],
+                    self.genome_orientations[b],
+                ).is_some()
+            }
+            SurgeryOp::SetAnchors { cell_a, cell_b, anchor_a, anchor_b } => {
+                let Some(slot) = self.bond_between(cell_a, cell_b) else {
+                    return false;
+                };
+                // The op names anchors by cell; the bond may store the pair the other way round
+                let anchors = if self.index_of(cell_a) == Some(self.adhesion_connections.cell_a_index[slot]) {
+                    (anchor_a, anchor_b)
+                } else {
+                    (anchor_b, anchor_a)
+                };
+                return self.set_bond_anchors(slot, anchors.0, anchors.1, genome);
+            }
+            SurgeryOp::Remove { cell_id } => {
+                let Some(index) = self.index_of(cell_id) else {
+                    return false;
+                };
+                crate::simulation::nutrient_system::remove_dead_cell(self, index);
+                true
+            }
+        };
+
+        if changed {
+            self.collision_cache.invalidate();
+        }
+        changed
+    }
+}
+
+/// Surgery operations waiting to be applied to the active simulation
+#[derive(Resource, Default)]
+pub struct SurgeryRequests {
+    pub pending: Vec<SurgeryOp>,
+}
+
+/// Apply the surgery recorded on the preview timeline for `tick`
+pub fn apply_scheduled_surgery(state: &mut CanonicalState, schedule: &[(u32, SurgeryOp)], tick: u32, genome: &GenomeData) {
+    for (op_tick, op) in schedule {
+        if *op_tick == tick {
+            state.apply_surgery(*op, genome);
+        }
+    }
+}
+
+/// Apply pending operations between ticks, recording them for replay in Preview mode
+fn apply_surgery_requests(
+    mut requests: ResMut<SurgeryRequests>,
+    sim_state: Res<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    genome: Res<CurrentGenome>,
+    staging: Res<crate::simulation::GenomeStaging>,
+    config: Res<crate::simulation::PhysicsConfig>,
+    mut timeline: ResMut<EventTimeline>,
+    mut changes: MessageWriter<OrganismTopologyChanged>,
+) {
+    if requests.pending.is_empty() {
+        return;
+    }
+
+    let fixed_timestep = config.fixed_timestep;
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            let tick = crate::simulation::clock::ticks_to_reach(main_state.simulation_time, fixed_timestep);
+            let running_genome = staging.running_genome(&genome.genome);
+            for op in requests.pending.drain(..) {
+                if main_state.canonical_state.apply_surgery(op, running_genome) && op.changes_topology() {
+                    changes.write(OrganismTopologyChanged { tick, op });
+                }
+            }
+        }
+        SimulationMode::Preview => {
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+            preview_state.intervene(sim_state.is_resimulating, fixed_timestep, |preview_state, tick| {
+                for op in std::mem::take(&mut requests.pending) {
+                    if !preview_state.canonical_state.apply_surgery(op, &genome.genome) {
+                        continue;
+                    }
+                    // A drag records one edit per tick: later frames replace the earlier ones
+                    let superseded = preview_state.surgery.last_mut()
+                        .filter(|(op_tick, earlier)| *op_tick == tick && op.supersedes(earlier));
+                    match superseded {
+                        Some(earlier) => earlier.1 = op,
+                        None => {
+                            preview_state.surgery.push((tick, op));
+                            timeline.push(TimelineEvent::new(tick, op.timeline_kind(), op.cells()));
+                        }
+                    }
+                    if op.changes_topology() {
+                        changes.write(OrganismTopologyChanged { tick, op });
+                    }
+                }
+            });
+        }
+        SimulationMode::Gpu => requests.pending.clear(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIDE: usize = 4;
+
+    /// 4x4 lattice in the XY plane with unit spacing, each cell bonded to its +X and +Y neighbors
+    fn lattice() -> CanonicalState {
+        let mut state = CanonicalState::new(32);
+        for y in 0..SIDE {
+            for x in 0..SIDE {
+                state.add_cell(Vec3::new(x as f32, y as f32, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                    0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+            }
+        }
+        for y in 0..SIDE {
+            for x in 0..SIDE {
+                let i = y * SIDE + x;
+                if x + 1 < SIDE {
+                    bond(&mut state, i, i + 1);
+                }
+                if y + 1 < SIDE {
+                    bond(&mut state, i, i + SIDE);
+                }
+            }
+        }
+        state
+    }
+
+    fn bond(state: &mut CanonicalState, a: usize, b: usize) {
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, a, b, 0,
+            Vec3::X, -Vec3::X, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+    }
+
+    /// Active bonds as sorted (lower, higher) index pairs
+    fn bond_set(state: &CanonicalState) -> Vec<(usize, usize)> {
+        let connections = &state.adhesion_connections;
+        let mut bonds: Vec<(usize, usize)> = (0..connections.active_count)
+            .filter(|&slot| connections.is_active[slot] != 0)
+            .map(|slot| {
+                let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        bonds.sort_unstable();
+        bonds
+    }
+
+    #[test]
+    fn test_split_at_bond_cuts_exactly_the_bonds_crossing_its_plane() {
+        let genome = GenomeData::default();
+        let mut state = lattice();
+        let before = bond_set(&state);
+        assert_eq!(before.len(), 24);
+
+        // The bond between x = 1 and x = 2 on the bottom row: the plane is x = 1.5
+        let op = SurgeryOp::SplitAtBond { cell_a: state.cell_ids[1], cell_b: state.cell_ids[2] };
+        assert!(state.apply_surgery(op, &genome));
+
+        let crossing: Vec<(usize, usize)> = (0..SIDE).map(|y| (y * SIDE + 1, y * SIDE + 2)).collect();
+        let expected: Vec<(usize, usize)> = before.into_iter().filter(|bond| !crossing.contains(bond)).collect();
+        assert_eq!(bond_set(&state), expected);
+
+        let mut left = state.adhesion_manager.organism_members(&state.adhesion_connections, 0);
+        left.sort_unstable();
+        assert_eq!(left, vec![0, 1, 4, 5, 8, 9, 12, 13]);
+
+        // Replaying the same operation changes nothing
+        assert!(!state.apply_surgery(op, &genome));
+        assert_eq!(bond_set(&state), expected);
+    }
+
+    #[test]
+    fn test_cut_stroke_only_cuts_bonds_it_passes_through() {
+        let genome = GenomeData::default();
+        let mut state = lattice();
+
+        // Camera above the plane x = 1.5; the stroke covers the bottom two rows only
+        let apex = Vec3::new(1.5, 1.5, 5.0);
+        let op = SurgeryOp::Cut {
+            apex,
+            edge_a: Vec3::new(1.5, -10.0, 0.0) - apex,
+            edge_b: Vec3::new(1.5, 1.5, -5.0) - apex,
+        };
+        assert!(state.apply_surgery(op, &genome));
+
+        let bonds = bond_set(&state);
+        assert_eq!(bonds.len(), 22);
+        assert!(!bonds.contains(&(1, 2)) && !bonds.contains(&(5, 6)));
+        assert!(bonds.contains(&(9, 10)) && bonds.contains(&(13, 14)));
+    }
+
+    #[test]
+    fn test_graft_snap_moves_the_smaller_organism_into_contact() {
+        let genome = GenomeData::default();
+        let mut state = CanonicalState::new(8);
+        // Three-cell chain along X, and a two-cell organism off to the side
+        for x in 0..3 {
+            state.add_cell(Vec3::new(x as f32, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        bond(&mut state, 0, 1);
+        bond(&mut state, 1, 2);
+        for position in [Vec3::new(5.0, 4.0, 0.0), Vec3::new(5.0, 6.0, 1.0)] {
+            state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.75,
+                0, 0, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        bond(&mut state, 3, 4);
+        let internal = state.positions[3].distance(state.positions[4]);
+        let chain_before: Vec<Vec3> = state.positions[..3].to_vec();
+
+        let op = SurgeryOp::Graft { cell_a: state.cell_ids[2], cell_b: state.cell_ids[3], mode_index: 0, snap: true };
+        assert!(state.apply_surgery(op, &genome));
+
+        // The grafted cells touch, the small organism moved rigidly, the chain stayed put
+        assert!((state.positions[2].distance(state.positions[3]) - 1.25).abs() < 1e-5);
+        assert!((state.positions[3].distance(state.positions[4]) - internal).abs() < 1e-5);
+        assert_eq!(&state.positions[..3], chain_before.as_slice());
+        // Turned to trail away from the chain
+        assert!(state.positions[4].distance(state.positions[2]) > state.positions[3].distance(state.positions[2]) + internal - 1e-4);
+
+        assert!(state.adhesion_manager.are_cells_connected(&state.adhesion_connections, 2, 3));
+        let snapped: Vec<Vec3> = state.positions[..5].to_vec();
+        assert!(!state.apply_surgery(op, &genome));
+        assert_eq!(&state.positions[..5], snapped.as_slice());
+    }
+}
@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use bevy::math::DQuat;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_physics::CanonicalState;
+
+/// Divisions recorded before a lineage reference folds them into its anchor
+pub const LINEAGE_PATH_STEPS: usize = 15;
+
+/// Drift (degrees) above which the user is told to turn on re-anchoring
+pub const DRIFT_WARNING_DEGREES: f64 = 0.1;
+
+/// Seconds between drift checks of the main simulation
+const DRIFT_CHECK_INTERVAL: f32 = 2.0;
+
+/// Plugin warning when genome orientations of the main simulation drift
+pub struct OrientationDriftPlugin;
+
+impl Plugin for OrientationDriftPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, warn_about_orientation_drift);
+    }
+}
+
+/// Bounded record of how a cell's genome orientation came about
+///
+/// The reference orientation is the anchor times the child orientations of the
+/// divisions since, composed in double precision. Every `LINEAGE_PATH_STEPS`
+/// divisions the result becomes the new anchor, so a recomputation never
+/// composes more rotations than that. Steps are looked up in the current genome.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineageReference {
+    pub anchor: Quat,
+    /// Parent mode * 2 + child side (0 = A, 1 = B) of each division since the anchor
+    steps: [u8; LINEAGE_PATH_STEPS],
+    len: u8,
+}
+
+impl Default for LineageReference {
+    fn default() -> Self {
+        Self::founder(Quat::IDENTITY)
+    }
+}
+
+impl LineageReference {
+    pub fn founder(orientation: Quat) -> Self {
+        Self { anchor: orientation, steps: [0; LINEAGE_PATH_STEPS], len: 0 }
+    }
+
+    /// Child orientation a division applies
+    fn step_rotation(genome: &GenomeData, parent_mode: usize, side: usize) -> DQuat {
+        genome.modes.get(parent_mode)
+            .map(|mode| if side == 0 { mode.child_a.orientation } else { mode.child_b.orientation })
+            .unwrap_or(Quat::IDENTITY)
+            .as_dquat()
+    }
+
+    /// Genome orientation this lineage should have, recomputed from the anchor
+    pub fn orientation(&self, genome: &GenomeData) -> DQuat {
+        self.steps[..self.len as usize]
+            .iter()
+            .fold(self.anchor.as_dquat(), |orientation, &step| {
+                orientation * Self::step_rotation(genome, (step / 2) as usize, (step % 2) as usize)
+            })
+            .normalize()
+    }
+
+    /// Reference of child `side` (0 = A, 1 = B) of a cell in `parent_mode`
+    pub fn child(&self, genome: &GenomeData, parent_mode: usize, side: usize) -> Self {
+        // Modes above 127 don't fit a step; fold them straight into the anchor
+        if parent_mode > 127 {
+            let orientation = self.orientation(genome) * Self::step_rotation(genome, parent_mode, side);
+            return Self::founder(orientation.normalize().as_quat());
+        }
+
+        let mut child = *self;
+        child.steps[child.len as usize] = (parent_mode * 2 + side) as u8;
+        child.len += 1;
+        if child.len as usize == LINEAGE_PATH_STEPS {
+            child = Self::founder(child.orientation(genome).as_quat());
+        }
+        child
+    }
+}
+
+/// Angle in degrees between two rotations, accurate for tiny angles
+fn angle_degrees(a: DQuat, b: DQuat) -> f64 {
+    let delta = a.normalize().inverse() * b.normalize();
+    (2.0 * delta.xyz().length().atan2(delta.w.abs())).to_degrees()
+}
+
+impl CanonicalState {
+    /// Degrees between cell `idx`'s stored genome orientation and its lineage reference
+    pub fn genome_orientation_drift(&self, genome: &GenomeData, idx: usize) -> f64 {
+        angle_degrees(
+            self.genome_orientations[idx].as_dquat(),
+            self.lineage_references[idx].orientation(genome),
+        )
+    }
+
+    /// Largest genome orientation drift of any cell
+    pub fn max_genome_orientation_drift(&self, genome: &GenomeData) -> f64 {
+        (0..self.cell_count)
+            .map(|idx| self.genome_orientation_drift(genome, idx))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Check the main simulation's drift now and then; warns once per scene
+fn warn_about_orientation_drift(
+    time: Res<Time>,
+    config: Res<crate::simulation::PhysicsConfig>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    genome: Res<crate::genome::CurrentGenome>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut since_check: Local<f32>,
+    mut warned: Local<bool>,
+) {
+    *since_check += time.delta_secs();
+    if *since_check < DRIFT_CHECK_INTERVAL {
+        return;
+    }
+    *since_check = 0.0;
+
+    let Some(main_state) = main_state else {
+        *warned = false;
+        return;
+    };
+    if config.reanchor_genome_orientations || *warned {
+        return;
+    }
+    let drift = main_state.canonical_state.max_genome_orientation_drift(&genome.genome);
+    if drift > DRIFT_WARNING_DEGREES {
+        notifications.warning(format!(
+            "Genome orientations have drifted up to {:.3}° from their lineage; enable re-anchoring in Physics Settings",
+            drift
+        ));
+        *warned = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::cpu_physics::division_step;
+    use crate::simulation::nutrient_system::remove_dead_cell;
+    use crate::simulation::PhysicsConfig;
+
+    /// Follow one lineage of a self-splitter for `generations` divisions; returns the
+    /// largest deviation (degrees) from the exact orientation seen along the way
+    fn lineage_drift(generations: u32, reanchor: bool) -> f64 {
+        let mut mode = ModeSettings::new_self_splitting(0, "Drifter".to_string());
+        mode.split_interval = 0.5;
+        mode.split_mass = 0.0;
+        mode.child_a.orientation = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0).normalize(), 37f32.to_radians());
+        mode.child_b.orientation = Quat::from_axis_angle(Vec3::new(-2.0, 0.5, 1.0).normalize(), 113f32.to_radians());
+        let genome = GenomeData { modes: vec![mode.clone()], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        // Room for the parent and one child; the other child is removed after every division
+        let mut state = CanonicalState::new(2);
+        state.reanchor_genome_orientations = reanchor;
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 0.5, 0.0, config.default_stiffness, Quat::IDENTITY, 0);
+
+        let mut exact = DQuat::IDENTITY;
+        let mut max_drift: f64 = 0.0;
+        for generation in 1..=generations {
+            let divisions = division_step(&mut state, &genome, generation as f32, config.fixed_timestep, 2, 0);
+            assert_eq!(divisions.len(), 1, "generation {}", generation);
+            let (kept, child) = if generation % 3 == 0 {
+                (divisions[0].child_b_idx, mode.child_b.orientation)
+            } else {
+                (divisions[0].child_a_idx, mode.child_a.orientation)
+            };
+            let removed = if kept == divisions[0].child_a_idx { divisions[0].child_b_idx } else { divisions[0].child_a_idx };
+            remove_dead_cell(&mut state, removed);
+            assert_eq!(state.cell_count, 1);
+
+            exact = (exact * child.as_dquat()).normalize();
+            max_drift = max_drift.max(angle_degrees(state.genome_orientations[0].as_dquat(), exact));
+            // The lineage reference itself stays on the exact orientation
+            assert!(angle_degrees(state.lineage_references[0].orientation(&genome), exact) < 1e-3);
+        }
+        max_drift
+    }
+
+    #[test]
+    fn test_reanchoring_bounds_drift_over_500_generations() {
+        let with_reanchoring = lineage_drift(500, true);
+        let without = lineage_drift(500, false);
+        assert!(with_reanchoring < 0.1, "drift with re-anchoring {}°", with_reanchoring);
+        assert!(without > 2.0 * with_reanchoring, "drift without {}° vs with {}°", without, with_reanchoring);
+    }
+
+    #[test]
+    fn test_reference_folds_into_anchor() {
+        let mut mode = ModeSettings::new_self_splitting(0, "Turner".to_string());
+        mode.child_a.orientation = Quat::from_rotation_y(0.1);
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+
+        let mut reference = LineageReference::founder(Quat::from_rotation_x(0.5));
+        for _ in 0..LINEAGE_PATH_STEPS - 1 {
+            reference = reference.child(&genome, 0, 0);
+        }
+        assert_eq!(reference.len as usize, LINEAGE_PATH_STEPS - 1);
+        let expected = Quat::from_rotation_x(0.5) * Quat::from_rotation_y(0.1 * LINEAGE_PATH_STEPS as f32);
+        let folded = reference.child(&genome, 0, 0);
+        assert_eq!(folded.len, 0);
+        assert!(folded.anchor.abs_diff_eq(expected, 1e-5));
+    }
+}
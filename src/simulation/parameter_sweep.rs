@@ -0,0 +1,600 @@
+use bevy::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::genome::{GenomeData, GenomeHistory, ModeSettings};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{InitialCell, InitialState, PhysicsConfig};
+
+/// Plugin for background parameter sweeps
+pub struct ParameterSweepPlugin;
+
+impl Plugin for ParameterSweepPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParameterSweep>()
+            .add_systems(Update, poll_parameter_sweep);
+    }
+}
+
+/// A numeric genome or physics parameter that can be swept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepParameter {
+    SplitInterval { mode: usize },
+    SplitMass { mode: usize },
+    SplitRatio { mode: usize },
+    AdhesionStiffness { mode: usize },
+    CellStiffness,
+    Damping,
+}
+
+impl SweepParameter {
+    /// Whether the parameter belongs to a genome mode
+    pub fn mode(self) -> Option<usize> {
+        match self {
+            SweepParameter::SplitInterval { mode }
+            | SweepParameter::SplitMass { mode }
+            | SweepParameter::SplitRatio { mode }
+            | SweepParameter::AdhesionStiffness { mode } => Some(mode),
+            SweepParameter::CellStiffness | SweepParameter::Damping => None,
+        }
+    }
+
+    /// Same parameter kind, retargeted to another mode
+    pub fn with_mode(self, new_mode: usize) -> Self {
+        match self {
+            SweepParameter::SplitInterval { .. } => SweepParameter::SplitInterval { mode: new_mode },
+            SweepParameter::SplitMass { .. } => SweepParameter::SplitMass { mode: new_mode },
+            SweepParameter::SplitRatio { .. } => SweepParameter::SplitRatio { mode: new_mode },
+            SweepParameter::AdhesionStiffness { .. } => SweepParameter::AdhesionStiffness { mode: new_mode },
+            other => other,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SweepParameter::SplitInterval { .. } => "Split interval",
+            SweepParameter::SplitMass { .. } => "Split mass",
+            SweepParameter::SplitRatio { .. } => "Split ratio",
+            SweepParameter::AdhesionStiffness { .. } => "Adhesion stiffness",
+            SweepParameter::CellStiffness => "Cell stiffness",
+            SweepParameter::Damping => "Damping",
+        }
+    }
+
+    /// Name including the mode name, e.g. "Split interval (Mode 2)"
+    pub fn label(self, genome: &GenomeData) -> String {
+        match self.mode() {
+            Some(mode) => {
+                let mode_name = genome.modes.get(mode).map(|m| m.name.as_str()).unwrap_or("?");
+                format!("{} ({})", self.name(), mode_name)
+            }
+            None => self.name().to_string(),
+        }
+    }
+
+    /// Suggested sweep range
+    pub fn default_range(self) -> (f32, f32) {
+        match self {
+            SweepParameter::SplitInterval { .. } => (2.0, 10.0),
+            SweepParameter::SplitMass { .. } => (1.0, 3.0),
+            SweepParameter::SplitRatio { .. } => (0.3, 0.7),
+            SweepParameter::AdhesionStiffness { .. } => (50.0, 300.0),
+            SweepParameter::CellStiffness => (5.0, 50.0),
+            SweepParameter::Damping => (0.1, 0.9),
+        }
+    }
+
+    /// Write the value into the genome or physics configuration
+    pub fn apply(self, genome: &mut GenomeData, config: &mut PhysicsConfig, value: f32) {
+        let mode = self.mode().and_then(|mode| genome.modes.get_mut(mode));
+        match (self, mode) {
+            (SweepParameter::SplitInterval { .. }, Some(mode)) => {
+                mode.split_interval = value;
+                mode.split_interval_min = None;
+            }
+            (SweepParameter::SplitMass { .. }, Some(mode)) => {
+                mode.split_mass = value;
+                mode.split_mass_min = None;
+            }
+            (SweepParameter::SplitRatio { .. }, Some(mode)) => mode.split_ratio = value,
+            (SweepParameter::AdhesionStiffness { .. }, Some(mode)) => {
+                mode.adhesion_settings.linear_spring_stiffness = value;
+            }
+            (SweepParameter::CellStiffness, _) => config.default_stiffness = value,
+            (SweepParameter::Damping, _) => config.damping = value,
+            _ => {}
+        }
+    }
+
+    /// Parameters available for a genome
+    pub fn all(genome: &GenomeData) -> Vec<Self> {
+        let mut parameters = Vec::new();
+        for mode in 0..genome.modes.len() {
+            parameters.push(SweepParameter::SplitInterval { mode });
+            parameters.push(SweepParameter::SplitMass { mode });
+            parameters.push(SweepParameter::SplitRatio { mode });
+            parameters.push(SweepParameter::AdhesionStiffness { mode });
+        }
+        parameters.push(SweepParameter::CellStiffness);
+        parameters.push(SweepParameter::Damping);
+        parameters
+    }
+}
+
+/// One swept parameter and its evenly spaced values
+#[derive(Debug, Clone, Copy)]
+pub struct SweepAxis {
+    pub parameter: SweepParameter,
+    pub min: f32,
+    pub max: f32,
+    pub steps: usize,
+}
+
+impl SweepAxis {
+    pub fn new(parameter: SweepParameter) -> Self {
+        let (min, max) = parameter.default_range();
+        Self { parameter, min, max, steps: 5 }
+    }
+
+    pub fn value(&self, index: usize) -> f32 {
+        if self.steps <= 1 {
+            return self.min;
+        }
+        self.min + (self.max - self.min) * index as f32 / (self.steps - 1) as f32
+    }
+}
+
+/// Metrics extracted from the final state of a sweep run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepMetric {
+    CellCount,
+    OrganismCount,
+    LargestOrganismRadius,
+}
+
+impl SweepMetric {
+    pub const ALL: [SweepMetric; 3] = [SweepMetric::CellCount, SweepMetric::OrganismCount, SweepMetric::LargestOrganismRadius];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SweepMetric::CellCount => "Cells",
+            SweepMetric::OrganismCount => "Organisms",
+            SweepMetric::LargestOrganismRadius => "Largest organism radius",
+        }
+    }
+}
+
+/// Metrics of one finished run (the full state is discarded)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepResult {
+    /// Index into the sweep grid (a_index + b_index * a_steps)
+    pub run_index: usize,
+    pub value_a: f32,
+    pub value_b: Option<f32>,
+    pub cell_count: usize,
+    pub organism_count: usize,
+    /// Mean distance of the largest organism's cells from its centroid
+    pub largest_organism_radius: f32,
+}
+
+impl SweepResult {
+    pub fn metric(&self, metric: SweepMetric) -> f32 {
+        match metric {
+            SweepMetric::CellCount => self.cell_count as f32,
+            SweepMetric::OrganismCount => self.organism_count as f32,
+            SweepMetric::LargestOrganismRadius => self.largest_organism_radius,
+        }
+    }
+}
+
+/// Everything needed to run a sweep, captured when it starts
+#[derive(Clone)]
+pub struct SweepSetup {
+    pub axis_a: SweepAxis,
+    pub axis_b: Option<SweepAxis>,
+    /// Physics ticks per run
+    pub ticks: u32,
+    pub max_cells: usize,
+    /// RNG seed shared by every run
+    pub rng_seed: u64,
+    /// Number of runs simulated at the same time
+    pub max_parallel: usize,
+    /// Metrics shown in the results table
+    pub metrics: Vec<SweepMetric>,
+}
+
+impl Default for SweepSetup {
+    fn default() -> Self {
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(2);
+        Self {
+            axis_a: SweepAxis::new(SweepParameter::SplitInterval { mode: 0 }),
+            axis_b: None,
+            ticks: 64 * 30,
+            max_cells: 256,
+            rng_seed: 0,
+            max_parallel: (threads / 2).max(1),
+            metrics: SweepMetric::ALL.to_vec(),
+        }
+    }
+}
+
+impl SweepSetup {
+    pub fn run_count(&self) -> usize {
+        self.axis_a.steps * self.axis_b.map_or(1, |axis| axis.steps)
+    }
+
+    /// Parameter values of one grid point
+    pub fn values(&self, run_index: usize) -> (f32, Option<f32>) {
+        let a_steps = self.axis_a.steps.max(1);
+        let value_a = self.axis_a.value(run_index % a_steps);
+        let value_b = self.axis_b.map(|axis| axis.value(run_index / a_steps));
+        (value_a, value_b)
+    }
+
+    /// Write a grid point's values into a genome and configuration
+    pub fn apply(&self, genome: &mut GenomeData, config: &mut PhysicsConfig, value_a: f32, value_b: Option<f32>) {
+        self.axis_a.parameter.apply(genome, config, value_a);
+        if let (Some(axis), Some(value)) = (self.axis_b, value_b) {
+            axis.parameter.apply(genome, config, value);
+        }
+    }
+
+    /// Apply a result's values to the edited genome as one undoable edit
+    ///
+    /// Physics configuration parameters are not part of the genome and are not recorded.
+    pub fn apply_result(
+        &self,
+        genome: &mut GenomeData,
+        config: &mut PhysicsConfig,
+        value_a: f32,
+        value_b: Option<f32>,
+        history: &mut GenomeHistory,
+    ) {
+        let mut modes: Vec<usize> = [Some(self.axis_a), self.axis_b].into_iter()
+            .flatten()
+            .filter_map(|axis| axis.parameter.mode())
+            .filter(|&mode| mode < genome.modes.len())
+            .collect();
+        modes.dedup();
+        let before: Vec<(usize, ModeSettings)> = modes.iter().map(|&mode| (mode, genome.modes[mode].clone())).collect();
+
+        self.apply(genome, config, value_a, value_b);
+
+        let before: Vec<_> = before.into_iter().filter(|(mode, settings)| genome.modes[*mode] != *settings).collect();
+        if !before.is_empty() {
+            history.record(format!("apply sweep result ({})", self.axis_a.parameter.label(genome)), before);
+        }
+    }
+}
+
+/// Single cell at the origin in the genome's initial mode (as in Preview mode)
+pub(crate) fn initial_state(genome: &GenomeData, config: &PhysicsConfig, max_cells: usize, rng_seed: u64) -> CanonicalState {
+    let initial_mode_index = genome.initial_mode_index();
+    let mode = genome.modes.get(initial_mode_index).or_else(|| genome.modes.first());
+    let (split_mass, split_interval) = mode
+        .map(|mode| (mode.get_split_mass(0, 0, rng_seed), mode.get_split_interval(0, 0, rng_seed)))
+        .unwrap_or((1.0, 5.0));
+
+    let mut initial_state = InitialState::new(config.clone(), max_cells, rng_seed);
+    initial_state.add_cell(InitialCell {
+        id: 0,
+        position: Vec3::ZERO,
+        velocity: Vec3::ZERO,
+        rotation: genome.initial_orientation,
+        angular_velocity: Vec3::ZERO,
+        mass: split_mass,
+        radius: 1.0,
+        genome_id: 0,
+        mode_index: initial_mode_index,
+        birth_time: 0.0,
+        split_interval,
+        split_mass,
+        stiffness: config.default_stiffness,
+        pinned: false,
+        user_data: Default::default(),
+    });
+    initial_state.to_canonical_state()
+}
+
+/// Organism count and mean radius of the largest organism
+fn organism_metrics(state: &CanonicalState) -> (usize, f32) {
+    let mut visited = vec![false; state.cell_count];
+    let mut organism_count = 0;
+    let mut largest: Vec<usize> = Vec::new();
+
+    for cell in 0..state.cell_count {
+        if visited[cell] {
+            continue;
+        }
+        let members = state.adhesion_manager.organism_members(&state.adhesion_connections, cell);
+        for &member in &members {
+            visited[member] = true;
+        }
+        organism_count += 1;
+        if members.len() > largest.len() {
+            largest = members;
+        }
+    }
+
+    if largest.is_empty() {
+        return (0, 0.0);
+    }
+    let centroid = largest.iter().map(|&i| state.positions[i]).sum::<Vec3>() / largest.len() as f32;
+    let mean_radius = largest.iter().map(|&i| state.positions[i].distance(centroid)).sum::<f32>() / largest.len() as f32;
+    (organism_count, mean_radius)
+}
+
+/// Simulate one grid point headlessly and extract its metrics
+///
+/// Uses the same stepping as Preview mode. Returns None if cancelled.
+pub fn run_sweep_point(
+    setup: &SweepSetup,
+    base_genome: &GenomeData,
+    base_config: &PhysicsConfig,
+    run_index: usize,
+    cancel: &AtomicBool,
+) -> Option<SweepResult> {
+    let (value_a, value_b) = setup.values(run_index);
+    let mut genome = base_genome.clone();
+    let mut config = base_config.clone();
+    setup.apply(&mut genome, &mut config, value_a, value_b);
+
+    let mut state = initial_state(&genome, &config, setup.max_cells, setup.rng_seed);
+    let fixed_timestep = config.fixed_timestep;
+    let mut phases = crate::genome::PhasedGenome::default();
+    for tick in 0..setup.ticks {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let current_time = tick as f32 * fixed_timestep;
+        phases.update(&genome, state.division_stats.divisions, current_time);
+        let genome = phases.genome(&genome);
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, genome, current_time, false);
+        crate::simulation::cpu_physics::division_step(
+            &mut state,
+            genome,
+            current_time,
+            fixed_timestep,
+            setup.max_cells,
+            setup.rng_seed,
+        );
+    }
+
+    let (organism_count, largest_organism_radius) = organism_metrics(&state);
+    Some(SweepResult {
+        run_index,
+        value_a,
+        value_b,
+        cell_count: state.cell_count,
+        organism_count,
+        largest_organism_radius,
+    })
+}
+
+/// A sweep running on background threads
+pub struct SweepJob {
+    pub setup: SweepSetup,
+    pub total: usize,
+    completed: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    finished: Arc<Mutex<Vec<SweepResult>>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SweepJob {
+    pub fn completed(&self) -> usize {
+        self.completed.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+/// Results table column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepColumn {
+    ValueA,
+    ValueB,
+    Metric(SweepMetric),
+}
+
+/// Parameter sweep setup, the running job and its results
+#[derive(Resource)]
+pub struct ParameterSweep {
+    pub setup: SweepSetup,
+    pub job: Option<SweepJob>,
+    /// Setup the current results were produced with
+    pub results_setup: Option<SweepSetup>,
+    pub results: Vec<SweepResult>,
+    /// Results table sort column and direction
+    pub sort: (SweepColumn, bool),
+    /// Metric shown in the heatmap
+    pub heatmap_metric: SweepMetric,
+}
+
+impl Default for ParameterSweep {
+    fn default() -> Self {
+        Self {
+            setup: SweepSetup::default(),
+            job: None,
+            results_setup: None,
+            results: Vec::new(),
+            sort: (SweepColumn::ValueA, true),
+            heatmap_metric: SweepMetric::CellCount,
+        }
+    }
+}
+
+impl SweepResult {
+    fn column(&self, column: SweepColumn) -> f32 {
+        match column {
+            SweepColumn::ValueA => self.value_a,
+            SweepColumn::ValueB => self.value_b.unwrap_or(0.0),
+            SweepColumn::Metric(metric) => self.metric(metric),
+        }
+    }
+}
+
+impl ParameterSweep {
+    /// Start running the configured grid against a snapshot of the genome and configuration
+    ///
+    /// Runs never touch the live simulation state.
+    pub fn start(&mut self, genome: &GenomeData, config: &PhysicsConfig) {
+        if let Some(job) = &self.job {
+            job.cancel();
+        }
+
+        let setup = self.setup.clone();
+        let total = setup.run_count();
+        let completed = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(Mutex::new(Vec::with_capacity(total)));
+
+        let handle = {
+            let setup = setup.clone();
+            let genome = genome.clone();
+            let config = config.clone();
+            let completed = completed.clone();
+            let cancel = cancel.clone();
+            let finished = finished.clone();
+            std::thread::spawn(move || {
+                let pool = match rayon::ThreadPoolBuilder::new().num_threads(setup.max_parallel.max(1)).build() {
+                    Ok(pool) => pool,
+                    Err(e) => {
+                        error!("Failed to create sweep thread pool: {}", e);
+                        return;
+                    }
+                };
+                pool.install(|| {
+                    use rayon::prelude::*;
+                    (0..total).into_par_iter().for_each(|run_index| {
+                        if let Some(result) = run_sweep_point(&setup, &genome, &config, run_index, &cancel) {
+                            finished.lock().unwrap().push(result);
+                            completed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+                });
+            })
+        };
+
+        self.results.clear();
+        self.results_setup = Some(setup.clone());
+        info!("Starting parameter sweep: {} runs of {} ticks", total, setup.ticks);
+        self.job = Some(SweepJob { setup, total, completed, cancel, finished, handle: Some(handle) });
+    }
+}
+
+impl ParameterSweep {
+    /// Result indices in table order
+    pub fn sorted_indices(&self) -> Vec<usize> {
+        let (column, ascending) = self.sort;
+        let mut indices: Vec<usize> = (0..self.results.len()).collect();
+        indices.sort_by(|&a, &b| {
+            let ordering = self.results[a].column(column).total_cmp(&self.results[b].column(column));
+            if ascending { ordering } else { ordering.reverse() }
+        });
+        indices
+    }
+}
+
+/// Collect finished runs and clean up completed or cancelled jobs
+fn poll_parameter_sweep(mut sweep: ResMut<ParameterSweep>) {
+    let sweep = &mut *sweep;
+    let Some(job) = &mut sweep.job else {
+        return;
+    };
+
+    sweep.results.extend(job.finished.lock().unwrap().drain(..));
+
+    if job.handle.as_ref().is_some_and(|handle| handle.is_finished()) {
+        if let Some(handle) = job.handle.take() {
+            let _ = handle.join();
+        }
+        sweep.results.extend(job.finished.lock().unwrap().drain(..));
+        sweep.results.sort_by_key(|result| result.run_index);
+        info!("Parameter sweep finished: {}/{} runs", sweep.results.len(), job.total);
+        sweep.job = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+
+    fn test_genome() -> GenomeData {
+        let mut mode = ModeSettings::new_self_splitting(0, "Mode 0".to_string());
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.0;
+        mode.nutrient_gain_rate = 1.0;
+        GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        }
+    }
+
+    #[test]
+    fn test_sweep_grid_values() {
+        let setup = SweepSetup {
+            axis_a: SweepAxis { parameter: SweepParameter::SplitInterval { mode: 0 }, min: 2.0, max: 10.0, steps: 5 },
+            axis_b: Some(SweepAxis { parameter: SweepParameter::AdhesionStiffness { mode: 0 }, min: 50.0, max: 300.0, steps: 4 }),
+            ..SweepSetup::default()
+        };
+        assert_eq!(setup.run_count(), 20);
+        assert_eq!(setup.values(0), (2.0, Some(50.0)));
+        let (a, b) = setup.values(6);
+        assert_eq!(a, 4.0);
+        assert!((b.unwrap() - 133.333).abs() < 1e-3);
+        assert_eq!(setup.values(19), (10.0, Some(300.0)));
+    }
+
+    #[test]
+    fn test_applied_result_can_be_undone() {
+        let mut genome = test_genome();
+        let mut config = PhysicsConfig::default();
+        let mut history = GenomeHistory::default();
+        let setup = SweepSetup {
+            axis_a: SweepAxis { parameter: SweepParameter::SplitInterval { mode: 0 }, min: 2.0, max: 10.0, steps: 5 },
+            axis_b: Some(SweepAxis { parameter: SweepParameter::SplitMass { mode: 0 }, min: 1.0, max: 3.0, steps: 3 }),
+            ..SweepSetup::default()
+        };
+
+        setup.apply_result(&mut genome, &mut config, 6.0, Some(2.0), &mut history);
+        assert_eq!((genome.modes[0].split_interval, genome.modes[0].split_mass), (6.0, 2.0));
+        assert!(history.undo(&mut genome).is_some());
+        assert!(genome == test_genome());
+
+        // Physics-only sweeps leave nothing to undo in the genome
+        let setup = SweepSetup { axis_a: SweepAxis::new(SweepParameter::Damping), axis_b: None, ..SweepSetup::default() };
+        setup.apply_result(&mut genome, &mut config, 0.7, None, &mut history);
+        assert_eq!(config.damping, 0.7);
+        assert_eq!(history.last_label(), None);
+    }
+
+    #[test]
+    fn test_sweep_runs_are_deterministic_and_independent() {
+        let genome = test_genome();
+        let config = PhysicsConfig::default();
+        let setup = SweepSetup {
+            axis_a: SweepAxis { parameter: SweepParameter::SplitInterval { mode: 0 }, min: 1.0, max: 4.0, steps: 2 },
+            ticks: 64 * 5,
+            max_cells: 64,
+            ..SweepSetup::default()
+        };
+        let cancel = AtomicBool::new(false);
+
+        let fast = run_sweep_point(&setup, &genome, &config, 0, &cancel).unwrap();
+        let slow = run_sweep_point(&setup, &genome, &config, 1, &cancel).unwrap();
+        assert_eq!(fast, run_sweep_point(&setup, &genome, &config, 0, &cancel).unwrap());
+        assert!(fast.cell_count > slow.cell_count);
+        // The base genome is only ever copied
+        assert_eq!(genome.modes[0].split_interval, 1.0);
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(run_sweep_point(&setup, &genome, &config, 0, &cancel).is_none());
+    }
+}
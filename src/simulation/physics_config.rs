@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Spatial grid configuration for collision detection
 /// 
@@ -33,7 +34,9 @@ impl SpatialGridConfig {
 /// 
 /// This configuration is shared by both CPU and GPU physics implementations.
 /// All values are deterministic and produce identical results across runs.
-#[derive(Resource, Clone, Debug)]
+/// Serialized as part of scene files; missing fields fall back to defaults.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicsConfig {
     /// World bounds (cubic volume)
     pub world_bounds: Vec3,
@@ -48,8 +51,14 @@ pub struct PhysicsConfig {
     pub damping: f32,
     
     /// Fixed timestep for physics integration (64 Hz ≈ 15.6ms)
+    /// This is the simulated time covered by one tick (the integrator dt)
     pub fixed_timestep: f32,
     
+    /// Ticks run per real second at 1x speed. None = 1 / fixed_timestep (real-time)
+    /// Lowering fixed_timestep alone keeps simulated time in step with wall-clock time;
+    /// set this to trade real-time playback for cost.
+    pub ticks_per_second_override: Option<f32>,
+    
     /// Velocity damping coefficient (matches desktop: 0.98)
     /// Applied as pow(velocity_damping, dt * 100.0)
     pub velocity_damping: f32,
@@ -62,6 +71,64 @@ pub struct PhysicsConfig {
     
     /// Disable collision detection (for performance testing or specific scenarios)
     pub disable_collisions: bool,
+    
+    /// Extra search distance for the cached collision pair list (0 = rebuild every tick)
+    /// The grid and pair list are only rebuilt once some cell has moved more than half of it
+    pub collision_skin: f32,
+    
+    /// Bin cells and generate collision pairs on the GPU (forces still computed on CPU)
+    /// Falls back to the CPU detector automatically if it turns out slower
+    pub gpu_pair_detection: bool,
+    
+    /// Cross-check every GPU pair list against the CPU detector (slow, for debugging)
+    pub gpu_pair_validation: bool,
+    
+    /// Evaluate every adhesion with the full orientation/twist model (verification runs)
+    /// Otherwise bonds that stay still drop to a linear-spring-only model until disturbed
+    pub adhesion_strict_quality: bool,
+    
+    /// Ticks an adhesion must stay calm before it drops to the cheap model
+    pub adhesion_calm_ticks: u16,
+    
+    /// Anchor/twist deviation (radians) and |extension| / rest length below which an adhesion is calm
+    pub adhesion_calm_angle: f32,
+    pub adhesion_calm_strain: f32,
+    
+    /// A cheap adhesion returns to the full model above any of these:
+    /// |extension| / rest length, anchor deviation (radians), relative angular speed (rad/s)
+    pub adhesion_wake_strain: f32,
+    pub adhesion_wake_angle: f32,
+    pub adhesion_wake_angular_velocity: f32,
+    
+    /// Largest force one adhesion may apply to either of its cells
+    /// Stronger (or non-finite) forces are clamped and listed as problem bonds
+    pub adhesion_max_force: f32,
+    
+    /// Chemical field voxels per axis (the grid spans the world sphere's bounding cube)
+    pub chemical_grid_resolution: u32,
+    
+    /// Chemical diffusion coefficient (world units² per second)
+    pub chemical_diffusion: f32,
+    
+    /// Fraction of every chemical removed per second
+    pub chemical_decay: f32,
+    
+    /// Recompute child genome orientations from a bounded lineage record instead of the
+    /// parent's orientation, so rounding error can't skew structures over many generations
+    pub reanchor_genome_orientations: bool,
+    
+    /// Run the CPU physics step with software transcendental and quaternion math that gives
+    /// identical results on every platform (slower; see `reproducible_math`)
+    pub strict_reproducibility: bool,
+    
+    /// Pull every cell toward the center of the world sphere (off by default)
+    pub attractor_enabled: bool,
+    
+    /// Central attraction per unit mass: strength · r / (r² + softening²)^1.5
+    pub attractor_strength: f32,
+    
+    /// Distance over which the attraction is smoothed out near the center
+    pub attractor_softening: f32,
 }
 
 impl Default for PhysicsConfig {
@@ -72,10 +139,64 @@ impl Default for PhysicsConfig {
             default_stiffness: 500.0,  // Increased from 10.0 to prevent pass-through
             damping: 0.0, // Increased from 0.0 to add velocity-based resistance
             fixed_timestep: 1.0 / 64.0, // Match Bevy's default fixed timestep (64 Hz)
+            ticks_per_second_override: None,
             velocity_damping: 0.98,
             friction_coefficient: 0.3,
             angular_damping: 0.95,
             disable_collisions: false,
+            collision_skin: 0.3,
+            gpu_pair_detection: false,
+            gpu_pair_validation: false,
+            adhesion_strict_quality: false,
+            adhesion_calm_ticks: 64,
+            adhesion_calm_angle: 0.02,
+            adhesion_calm_strain: 0.01,
+            adhesion_wake_strain: 0.02,
+            adhesion_wake_angle: 0.03,
+            adhesion_wake_angular_velocity: 0.25,
+            adhesion_max_force: 10_000.0,
+            chemical_grid_resolution: 32,
+            chemical_diffusion: 20.0,
+            chemical_decay: 0.05,
+            reanchor_genome_orientations: false,
+            strict_reproducibility: false,
+            attractor_enabled: false,
+            attractor_strength: 2000.0,
+            attractor_softening: 5.0,
+        }
+    }
+}
+
+impl PhysicsConfig {
+    /// Physics ticks per real second at 1x speed
+    /// Derived from fixed_timestep unless overridden
+    pub fn ticks_per_second(&self) -> f32 {
+        self.ticks_per_second_override
+            .filter(|tps| *tps > 0.0)
+            .unwrap_or(1.0 / self.fixed_timestep)
+    }
+    
+    /// Math implementation of the CPU physics step
+    pub fn math(&self) -> crate::simulation::PhysicsMath {
+        if self.strict_reproducibility {
+            crate::simulation::PhysicsMath::Reproducible
+        } else {
+            crate::simulation::PhysicsMath::Native
+        }
+    }
+    
+    /// Adhesion quality tier thresholds for the force routines
+    pub fn adhesion_tiers(&self) -> crate::cell::AdhesionTierThresholds {
+        crate::cell::AdhesionTierThresholds {
+            strict: self.adhesion_strict_quality,
+            calm_ticks: self.adhesion_calm_ticks,
+            calm_angle: self.adhesion_calm_angle,
+            calm_strain: self.adhesion_calm_strain,
+            wake_strain: self.adhesion_wake_strain,
+            wake_angle: self.adhesion_wake_angle,
+            wake_angular_velocity: self.adhesion_wake_angular_velocity,
+            max_force: self.adhesion_max_force,
+            math: self.math(),
         }
     }
 }
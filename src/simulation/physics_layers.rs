@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::simulation::PhysicsConfig;
+
+/// Plugin keeping `PhysicsConfig` resolved from the default, global and scene layers
+pub struct PhysicsLayersPlugin;
+
+impl Plugin for PhysicsLayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsLayers>()
+            .add_systems(Update, sync_physics_layers);
+    }
+}
+
+/// Layer a physics value comes from, lowest precedence first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// Built-in `PhysicsConfig::default()`
+    Default,
+    /// User-wide override saved with the UI settings
+    Global,
+    /// Override stored with the loaded scene
+    Scene,
+}
+
+impl ConfigLayer {
+    /// One-letter badge for the physics settings panel
+    pub fn badge(self) -> &'static str {
+        match self {
+            ConfigLayer::Default => "D",
+            ConfigLayer::Global => "G",
+            ConfigLayer::Scene => "S",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ConfigLayer::Default => "Built-in default",
+            ConfigLayer::Global => "Global override (all scenes)",
+            ConfigLayer::Scene => "Scene override (this scene only)",
+        }
+    }
+}
+
+/// Sparse set of `PhysicsConfig` values by field name, as serialized
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PhysicsOverrides(pub Map<String, Value>);
+
+impl PhysicsOverrides {
+    /// Fields of `config` that differ from `base`
+    pub fn between(base: &PhysicsConfig, config: &PhysicsConfig) -> Self {
+        let (Some(base), Some(config)) = (config_fields(base), config_fields(config)) else {
+            return Self::default();
+        };
+        Self(config.into_iter().filter(|(field, value)| base.get(field) != Some(value)).collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.0.contains_key(field)
+    }
+
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.0.get(field)
+    }
+
+    pub fn insert(&mut self, field: &str, value: Value) {
+        self.0.insert(field.to_string(), value);
+    }
+
+    pub fn remove(&mut self, field: &str) -> Option<Value> {
+        self.0.remove(field)
+    }
+
+    /// Overridden fields whose value differs from `config`
+    pub fn differing_from(&self, config: &PhysicsConfig) -> Vec<String> {
+        let fields = config_fields(config).unwrap_or_default();
+        self.0.iter()
+            .filter(|(field, value)| fields.get(*field) != Some(*value))
+            .map(|(field, _)| field.clone())
+            .collect()
+    }
+}
+
+fn config_fields(config: &PhysicsConfig) -> Option<Map<String, Value>> {
+    match serde_json::to_value(config) {
+        Ok(Value::Object(fields)) => Some(fields),
+        _ => None,
+    }
+}
+
+/// Effective config: the built-in defaults, then `global`, then `scene`
+///
+/// Unknown fields and values that don't fit the field's type are skipped, so a
+/// stale settings file or scene can't take the other layers down with it.
+pub fn resolve(global: &PhysicsOverrides, scene: &PhysicsOverrides) -> PhysicsConfig {
+    let mut config = PhysicsConfig::default();
+    let Some(mut fields) = config_fields(&config) else {
+        return config;
+    };
+    for (field, value) in global.0.iter().chain(scene.0.iter()) {
+        let Some(previous) = fields.get(field).cloned() else {
+            continue;
+        };
+        fields.insert(field.clone(), value.clone());
+        match serde_json::from_value(Value::Object(fields.clone())) {
+            Ok(resolved) => config = resolved,
+            Err(_) => {
+                fields.insert(field.clone(), previous);
+            }
+        }
+    }
+    config
+}
+
+/// Layer of `field` given the global and scene overrides
+pub fn layer_of(field: &str, global: &PhysicsOverrides, scene: &PhysicsOverrides) -> ConfigLayer {
+    if scene.contains(field) {
+        ConfigLayer::Scene
+    } else if global.contains(field) {
+        ConfigLayer::Global
+    } else {
+        ConfigLayer::Default
+    }
+}
+
+/// Global and scene physics overrides
+///
+/// Edits to `PhysicsConfig` (settings panel, sweeps) land in the scene layer; the
+/// panel can promote a value to the global layer or clear it back to the default.
+#[derive(Resource, Default)]
+pub struct PhysicsLayers {
+    pub global: PhysicsOverrides,
+    pub scene: PhysicsOverrides,
+}
+
+impl PhysicsLayers {
+    pub fn resolve(&self) -> PhysicsConfig {
+        resolve(&self.global, &self.scene)
+    }
+
+    pub fn layer_of(&self, field: &str) -> ConfigLayer {
+        layer_of(field, &self.global, &self.scene)
+    }
+
+    /// Scene layer that makes `config` the effective config on top of the global layer
+    pub fn scene_layer_for(&self, config: &PhysicsConfig) -> PhysicsOverrides {
+        PhysicsOverrides::between(&resolve(&self.global, &PhysicsOverrides::default()), config)
+    }
+
+    /// Move the scene value of `field` to the global layer
+    pub fn promote(&mut self, field: &str) {
+        if let Some(value) = self.scene.remove(field) {
+            self.global.insert(field, value);
+        }
+    }
+
+    /// Drop `field` from both layers
+    pub fn clear(&mut self, field: &str) {
+        self.scene.remove(field);
+        self.global.remove(field);
+    }
+}
+
+/// Re-resolve the config when a layer changes, or fold direct config edits into the scene layer
+///
+/// Compares by value: the UI hands both resources out mutably every frame, so change
+/// detection alone can't tell which side was edited.
+fn sync_physics_layers(
+    mut config: ResMut<PhysicsConfig>,
+    mut layers: ResMut<PhysicsLayers>,
+    mut last_seen: Local<Option<(PhysicsOverrides, PhysicsOverrides, PhysicsConfig)>>,
+) {
+    let layers_edited = last_seen.as_ref()
+        .is_none_or(|(global, scene, _)| *global != layers.global || *scene != layers.scene);
+    if layers_edited {
+        let resolved = layers.resolve();
+        if *config != resolved {
+            *config = resolved;
+        }
+    } else if last_seen.as_ref().is_some_and(|(_, _, last_config)| *last_config != *config) {
+        let scene = layers.scene_layer_for(&config);
+        if layers.scene != scene {
+            layers.scene = scene;
+        }
+    }
+    *last_seen = Some((layers.global.clone(), layers.scene.clone(), config.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn overrides(values: Value) -> PhysicsOverrides {
+        serde_json::from_value(values).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_applies_layers_in_order() {
+        let global = overrides(json!({ "damping": 0.5, "friction_coefficient": 0.1 }));
+        let scene = overrides(json!({ "damping": 0.8 }));
+        let config = resolve(&global, &scene);
+        assert_eq!(config.damping, 0.8);
+        assert_eq!(config.friction_coefficient, 0.1);
+        assert_eq!(config.velocity_damping, PhysicsConfig::default().velocity_damping);
+
+        assert_eq!(layer_of("damping", &global, &scene), ConfigLayer::Scene);
+        assert_eq!(layer_of("friction_coefficient", &global, &scene), ConfigLayer::Global);
+        assert_eq!(layer_of("velocity_damping", &global, &scene), ConfigLayer::Default);
+        assert!(resolve(&PhysicsOverrides::default(), &PhysicsOverrides::default()) == PhysicsConfig::default());
+    }
+
+    #[test]
+    fn test_resolve_skips_bad_values() {
+        let global = overrides(json!({ "damping": "soft", "no_such_field": 1.0, "world_bounds": [10.0, 20.0, 30.0] }));
+        let scene = overrides(json!({ "attractor_enabled": true }));
+        let config = resolve(&global, &scene);
+        assert_eq!(config.damping, PhysicsConfig::default().damping);
+        assert_eq!(config.world_bounds, Vec3::new(10.0, 20.0, 30.0));
+        assert!(config.attractor_enabled);
+    }
+
+    #[test]
+    fn test_overrides_between_round_trips_through_resolve() {
+        let mut edited = PhysicsConfig::default();
+        edited.chemical_decay = 0.2;
+        edited.ticks_per_second_override = Some(30.0);
+        let scene = PhysicsOverrides::between(&PhysicsConfig::default(), &edited);
+        assert_eq!(scene.len(), 2);
+        assert!(resolve(&PhysicsOverrides::default(), &scene) == edited);
+
+        assert_eq!(scene.differing_from(&edited), Vec::<String>::new());
+        assert_eq!(scene.differing_from(&PhysicsConfig::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_promote_and_clear() {
+        let mut layers = PhysicsLayers {
+            global: PhysicsOverrides::default(),
+            scene: overrides(json!({ "damping": 0.3 })),
+        };
+        layers.promote("damping");
+        assert_eq!(layers.layer_of("damping"), ConfigLayer::Global);
+        assert_eq!(layers.resolve().damping, 0.3);
+
+        layers.clear("damping");
+        assert_eq!(layers.layer_of("damping"), ConfigLayer::Default);
+        assert!(layers.resolve() == PhysicsConfig::default());
+    }
+}
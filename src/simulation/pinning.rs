@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Plugin applying pin/unpin requests from the inspector and the pin tool
+pub struct PinningPlugin;
+
+impl Plugin for PinningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PinRequests>()
+            .add_systems(Update, apply_pin_requests);
+    }
+}
+
+/// A change to which cells are pinned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PinChange {
+    /// Pin or unpin the cell with this ID
+    Set { cell_id: u32, pinned: bool },
+    /// Unpin every cell
+    UnpinAll,
+}
+
+impl CanonicalState {
+    /// Apply a pin change; returns false if the cell no longer exists
+    pub fn apply_pin_change(&mut self, change: PinChange) -> bool {
+        match change {
+            PinChange::Set { cell_id, pinned } => {
+                // Keyed by ID so the change survives index shuffling from division and removal
+                let Some(idx) = self.cell_ids[..self.cell_count].iter().position(|&id| id == cell_id) else {
+                    return false;
+                };
+                self.set_pinned(idx, pinned);
+                true
+            }
+            PinChange::UnpinAll => {
+                self.pinned.fill(0);
+                true
+            }
+        }
+    }
+}
+
+/// Pin changes waiting to be applied to the active simulation
+#[derive(Resource, Default)]
+pub struct PinRequests {
+    pub pending: Vec<PinChange>,
+}
+
+impl PinRequests {
+    pub fn set(&mut self, cell_id: u32, pinned: bool) {
+        self.pending.push(PinChange::Set { cell_id, pinned });
+    }
+
+    pub fn unpin_all(&mut self) {
+        self.pending.push(PinChange::UnpinAll);
+    }
+}
+
+/// Apply the pin changes recorded on the preview timeline for `tick`
+///
+/// Changes are recorded as (tick, change) and replayed at the same ticks so
+/// scrubbing and resimulation reproduce them. Applying a tick twice is harmless.
+pub fn apply_scheduled_pins(state: &mut CanonicalState, schedule: &[(u32, PinChange)], tick: u32) {
+    for (change_tick, change) in schedule {
+        if *change_tick == tick {
+            state.apply_pin_change(*change);
+        }
+    }
+}
+
+/// Apply pending pin changes to the active simulation between ticks
+fn apply_pin_requests(
+    mut requests: ResMut<PinRequests>,
+    sim_state: Res<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    config: Res<crate::simulation::PhysicsConfig>,
+) {
+    if requests.pending.is_empty() {
+        return;
+    }
+
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            for change in requests.pending.drain(..) {
+                main_state.canonical_state.apply_pin_change(change);
+            }
+        }
+        SimulationMode::Preview => {
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+            preview_state.intervene(sim_state.is_resimulating, config.fixed_timestep, |preview_state, tick| {
+                for change in std::mem::take(&mut requests.pending) {
+                    if preview_state.canonical_state.apply_pin_change(change) {
+                        preview_state.pin_changes.push((tick, change));
+                    }
+                }
+            });
+        }
+        SimulationMode::Gpu => requests.pending.clear(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::test_support::{add_test_cell, never_split_mode};
+    use crate::simulation::PhysicsConfig;
+
+    #[test]
+    fn test_pinned_cell_holds_while_partner_swings_around_it() {
+        let mut mode = never_split_mode(0, "Anchor");
+        mode.adhesion_settings.linear_spring_stiffness = 500.0;
+        let genome = GenomeData {
+            modes: vec![mode],
+            ..GenomeData::default()
+        };
+        let config = PhysicsConfig::default();
+
+        // Partner starts stretched well past the rest length and moving sideways
+        let mut state = CanonicalState::new(8);
+        let anchor = Vec3::new(1.0, 2.0, 3.0);
+        add_test_cell(&mut state, anchor, Quat::IDENTITY, 1.0, 1.0, 0);
+        let partner = add_test_cell(&mut state, anchor + Vec3::X * 3.0, Quat::IDENTITY, 1.0, 1.0, 0);
+        state.velocities[partner] = Vec3::Y * 10.0;
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+        let partner_id = state.cell_ids[1];
+        assert!(state.apply_pin_change(PinChange::Set { cell_id: state.cell_ids[0], pinned: true }));
+
+        let start = state.positions[1] - anchor;
+        let mut max_angle: f32 = 0.0;
+        for tick in 0..200 {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+            assert_eq!(state.positions[0], anchor, "pinned cell moved at tick {}", tick);
+            assert_eq!(state.velocities[0], Vec3::ZERO);
+            max_angle = max_angle.max((state.positions[1] - anchor).angle_between(start));
+        }
+
+        assert_eq!(state.cell_ids[1], partner_id);
+        assert!(max_angle > 0.5, "partner should swing around the anchor (max angle {})", max_angle);
+        // The pull still reached the partner: it was drawn in toward the rest length
+        assert!((state.positions[1] - anchor).length() < 2.5);
+    }
+}
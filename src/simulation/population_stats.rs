@@ -0,0 +1,493 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::genome::GenomeData;
+use crate::simulation::cpu_sim::{CpuSceneState, MainSimState};
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::{CanonicalState, SimulationMode, SimulationState};
+
+/// z-score of the 95% confidence intervals the estimates state
+pub const CONFIDENCE_Z: f64 = 1.96;
+
+/// Bins of the mass and speed distributions
+pub const HISTOGRAM_BINS: usize = 32;
+
+/// Plugin for the per-mode population means and distributions in the Performance Monitor
+///
+/// Small populations are summarized exactly. Above the settings' threshold a fixed-size
+/// random sample is drawn and split by mode (post-stratification), so the cost stays flat
+/// as the population grows; totals come from counters, never from the sample.
+pub struct PopulationStatsPlugin;
+
+impl Plugin for PopulationStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PopulationStatsSettings>()
+            .init_resource::<PopulationStats>()
+            .init_resource::<ModePopulationHistory>()
+            .add_systems(
+                FixedUpdate,
+                count_deaths
+                    .after(crate::simulation::cpu_sim::run_main_simulation)
+                    .run_if(in_state(CpuSceneState::Active)),
+            )
+            .add_systems(Update, (sample_population_stats, record_mode_populations));
+    }
+}
+
+/// When the population statistics switch to sampling, and how much they sample
+/// (persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct PopulationStatsSettings {
+    /// Populations up to this size are summarized exactly
+    pub exact_threshold: usize,
+    /// Cells drawn per sample above the threshold
+    pub sample_size: usize,
+    /// Samples per second of real time
+    pub samples_per_second: f32,
+}
+
+impl Default for PopulationStatsSettings {
+    fn default() -> Self {
+        Self {
+            exact_threshold: 20_000,
+            sample_size: 4_000,
+            samples_per_second: 10.0,
+        }
+    }
+}
+
+/// Simulated seconds between recorded mode populations
+pub const MODE_POPULATION_INTERVAL: f32 = 0.5;
+
+/// Mode populations kept per run (later samples are dropped)
+const MAX_MODE_POPULATION_SAMPLES: usize = 8_192;
+
+/// Exact cells per mode of the active simulation over simulated time (Population Forecast overlay)
+///
+/// Samples are keyed by simulated time, so scrubbing a preview back and forth fills in the
+/// same samples. Switching simulation modes or simulating a functionally different genome
+/// starts over, as does CPU time going backwards (a new or reloaded scene).
+#[derive(Resource, Default)]
+pub struct ModePopulationHistory {
+    samples: BTreeMap<u32, Vec<usize>>,
+    source: Option<(SimulationMode, GenomeData)>,
+    last_time: Option<f32>,
+}
+
+impl ModePopulationHistory {
+    /// Record the populations of `state` at `time`, unless that sample is already known
+    pub fn record(&mut self, mode: SimulationMode, genome: &GenomeData, time: f32, state: &CanonicalState) {
+        let same_source = self.source.as_ref()
+            .is_some_and(|(source_mode, source_genome)| *source_mode == mode && source_genome.functionally_equal(genome));
+        let restarted = mode == SimulationMode::Cpu && self.last_time.is_some_and(|last| time < last);
+        if !same_source || restarted {
+            self.samples.clear();
+            self.source = Some((mode, genome.clone()));
+        }
+        self.last_time = Some(time);
+
+        let key = (time / MODE_POPULATION_INTERVAL).floor().max(0.0) as u32;
+        if !self.samples.contains_key(&key) && self.samples.len() < MAX_MODE_POPULATION_SAMPLES {
+            self.samples.insert(key, state.cells_per_mode(genome.modes.len()));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.source = None;
+        self.last_time = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// (simulated time, cells per mode), oldest first
+    pub fn samples(&self) -> impl Iterator<Item = (f32, &[usize])> {
+        self.samples.iter().map(|(&key, counts)| (key as f32 * MODE_POPULATION_INTERVAL, counts.as_slice()))
+    }
+}
+
+/// Mean of a quantity and the half-width of its 95% confidence interval
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Estimate {
+    pub mean: f32,
+    /// 0 when the mean is exact
+    pub margin: f32,
+}
+
+impl Estimate {
+    pub fn contains(&self, value: f32) -> bool {
+        (value - self.mean).abs() <= self.margin
+    }
+}
+
+/// Running sums of one quantity over the cells drawn
+#[derive(Clone, Copy, Debug, Default)]
+struct Moments {
+    count: u32,
+    sum: f64,
+    sum_squares: f64,
+}
+
+impl Moments {
+    fn add(&mut self, value: f32) {
+        self.count += 1;
+        self.sum += value as f64;
+        self.sum_squares += value as f64 * value as f64;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.sum / self.count as f64 }
+    }
+
+    /// Variance of the mean of the drawn cells (0 with fewer than two)
+    fn variance_of_mean(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let n = self.count as f64;
+        ((self.sum_squares - n * self.mean() * self.mean()) / (n - 1.0)).max(0.0) / n
+    }
+}
+
+/// Population and means of one genome mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModeStats {
+    pub mode: usize,
+    /// Cells of the mode (estimated while sampling)
+    pub count: Estimate,
+    /// Cells of the mode in the sample
+    pub sampled: usize,
+    pub mass: Estimate,
+    pub speed: Estimate,
+}
+
+/// Distribution of a quantity over the population, in equal bins from 0 to `max`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub max: f32,
+    /// Cells per bin (estimated while sampling)
+    pub bins: Vec<f32>,
+}
+
+impl Histogram {
+    fn from_values(values: &[f32], weight: f32) -> Self {
+        let max = values.iter().copied().fold(0.0f32, f32::max).max(f32::EPSILON);
+        let mut bins = vec![0.0; HISTOGRAM_BINS];
+        for &value in values {
+            let bin = ((value / max * HISTOGRAM_BINS as f32) as usize).min(HISTOGRAM_BINS - 1);
+            bins[bin] += weight;
+        }
+        Self { max, bins }
+    }
+}
+
+/// Population statistics at one moment
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PopulationSample {
+    /// Whether every cell was summarized
+    pub exact: bool,
+    /// Cells summarized
+    pub sampled: usize,
+    pub population: usize,
+    pub mass: Estimate,
+    pub speed: Estimate,
+    /// Total translational and rotational kinetic energy (scaled up from the sample)
+    pub kinetic_energy: f32,
+    /// Every mode with cells in the sample, by mode index
+    pub modes: Vec<ModeStats>,
+    /// Only computed while their plot is visible
+    pub mass_histogram: Option<Histogram>,
+    pub speed_histogram: Option<Histogram>,
+}
+
+impl PopulationSample {
+    /// Summarize `state`, drawing a sample seeded by `seed` above the exact threshold
+    pub fn capture(state: &CanonicalState, settings: &PopulationStatsSettings, distributions: bool, seed: u64) -> Self {
+        let population = state.cell_count;
+        if population == 0 {
+            return Self { exact: true, ..Self::default() };
+        }
+        let exact = population <= settings.exact_threshold.max(settings.sample_size);
+        let sampled = if exact { population } else { settings.sample_size.max(1) };
+
+        let mut mass = Vec::new();
+        let mut speed = Vec::new();
+        let mut overall = (Moments::default(), Moments::default());
+        let mut moments: Vec<(Moments, Moments)> = Vec::new();
+        let mut kinetic_energy = 0.0f64;
+        for draw in 0..sampled {
+            let i = if exact { draw } else { sample_index(seed, draw as u64, population) };
+            let mode = state.mode_indices[i];
+            if mode >= moments.len() {
+                moments.resize(mode + 1, Default::default());
+            }
+            let (cell_mass, cell_speed) = (state.masses[i], state.velocities[i].length());
+            moments[mode].0.add(cell_mass);
+            moments[mode].1.add(cell_speed);
+            overall.0.add(cell_mass);
+            overall.1.add(cell_speed);
+            kinetic_energy += state.cell_kinetic_energy(i) as f64;
+            if distributions {
+                mass.push(cell_mass);
+                speed.push(cell_speed);
+            }
+        }
+
+        // Each mode is a stratum: its share of the sample estimates its share of the population
+        let total = sampled as f64;
+        let estimate = |moments: &Moments| Estimate {
+            mean: moments.mean() as f32,
+            margin: if exact { 0.0 } else { (CONFIDENCE_Z * moments.variance_of_mean().sqrt()) as f32 },
+        };
+        let modes = moments
+            .iter()
+            .enumerate()
+            .filter(|(_, (mass, _))| mass.count > 0)
+            .map(|(mode, (mass, speed))| {
+                let share = mass.count as f64 / total;
+                ModeStats {
+                    mode,
+                    count: Estimate {
+                        mean: (share * population as f64) as f32,
+                        margin: if exact {
+                            0.0
+                        } else {
+                            (CONFIDENCE_Z * (share * (1.0 - share) / total).sqrt() * population as f64) as f32
+                        },
+                    },
+                    sampled: mass.count as usize,
+                    mass: estimate(mass),
+                    speed: estimate(speed),
+                }
+            })
+            .collect();
+        let weight = population as f32 / sampled as f32;
+        Self {
+            exact,
+            sampled,
+            population,
+            // The strata shares are themselves estimated, so the whole draw gives the overall means
+            mass: estimate(&overall.0),
+            speed: estimate(&overall.1),
+            kinetic_energy: kinetic_energy as f32 * weight,
+            modes,
+            mass_histogram: distributions.then(|| Histogram::from_values(&mass, weight)),
+            speed_histogram: distributions.then(|| Histogram::from_values(&speed, weight)),
+        }
+    }
+}
+
+/// Index of the `draw`th cell of a sample (with replacement) from `population` cells
+fn sample_index(seed: u64, draw: u64, population: usize) -> usize {
+    // splitmix64
+    let mut x = seed ^ draw.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    ((x as u128 * population as u128) >> 64) as usize
+}
+
+/// Latest population statistics of the active simulation and its event counters
+#[derive(Resource, Default)]
+pub struct PopulationStats {
+    pub latest: Option<PopulationSample>,
+    /// Divisions since the scene started (from the division counter)
+    pub divisions: u64,
+    /// Cells removed from the main simulation since it started (None in other modes)
+    pub deaths: Option<u64>,
+    /// Set by the UI while a distribution plot is on screen
+    pub distributions_visible: bool,
+    samples: u64,
+    last_sample: Option<f32>,
+}
+
+/// Add the main simulation's removals of the tick that just ran
+fn count_deaths(
+    main_state: Res<MainSimState>,
+    mut stats: ResMut<PopulationStats>,
+    mut last_time: Local<Option<f32>>,
+) {
+    // The removal buffer only changes when a tick ran (no cells, paused)
+    let time = main_state.simulation_time;
+    let previous = last_time.replace(time);
+    if previous == Some(time) {
+        return;
+    }
+    // Time going backwards means a fresh or reloaded scene
+    if previous.is_some_and(|previous| time < previous) {
+        stats.deaths = Some(0);
+    }
+    let removed = main_state.canonical_state.removed_cell_ids_buffer.len() as u64;
+    stats.deaths = Some(stats.deaths.unwrap_or(0) + removed);
+}
+
+fn sample_population_stats(
+    mut stats: ResMut<PopulationStats>,
+    settings: Res<PopulationStatsSettings>,
+    time: Res<Time<Real>>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+) {
+    let now = time.elapsed_secs();
+    if stats.last_sample.is_some_and(|last| now - last < 1.0 / settings.samples_per_second.max(0.1)) {
+        return;
+    }
+    stats.last_sample = Some(now);
+
+    let state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|s| &s.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|s| &s.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+    let Some(state) = state else {
+        stats.latest = None;
+        return;
+    };
+    if sim_state.mode != SimulationMode::Cpu {
+        stats.deaths = None;
+    }
+    stats.samples += 1;
+    let distributions = std::mem::take(&mut stats.distributions_visible);
+    stats.latest = Some(PopulationSample::capture(state, &settings, distributions, stats.samples));
+    stats.divisions = state.division_stats.divisions;
+}
+
+fn record_mode_populations(
+    mut history: ResMut<ModePopulationHistory>,
+    sim_state: Res<SimulationState>,
+    genome: Res<crate::genome::CurrentGenome>,
+    staging: Res<crate::simulation::GenomeStaging>,
+    main_state: Option<Res<MainSimState>>,
+    preview_state: Option<Res<PreviewSimState>>,
+) {
+    // CPU runs may still be simulating the genome from before staged edits
+    let active = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref()
+            .map(|s| (&s.canonical_state, s.simulation_time, staging.running_genome(&genome.genome))),
+        SimulationMode::Preview => preview_state.as_deref()
+            .map(|s| (&s.canonical_state, s.current_time, &genome.genome)),
+        SimulationMode::Gpu => None,
+    };
+    if let Some((state, time, genome)) = active.filter(|(state, _, _)| state.cell_count > 0) {
+        history.record(sim_state.mode, genome, time, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synthetic population: mode 1 is a heavier, faster tenth of the cells, interleaved
+    /// every tenth index as divisions tend to lay siblings out
+    fn population(cells: usize) -> CanonicalState {
+        let mut state = CanonicalState::new(cells);
+        for i in 0..cells {
+            let mode = usize::from(i % 10 == 0);
+            let jitter = (i * 7919 % 1000) as f32 / 1000.0;
+            let mass = if mode == 1 { 2.0 + jitter } else { 1.0 + 0.5 * jitter };
+            state.add_cell(Vec3::ZERO, Vec3::X * (mode as f32 + jitter), Quat::IDENTITY, Vec3::ZERO, mass, 0.5,
+                0, mode, 0.0, 1000.0, 1000.0, 500.0, Quat::IDENTITY, 0);
+        }
+        state
+    }
+
+    #[test]
+    fn test_small_population_is_summarized_exactly() {
+        let state = population(1000);
+        let sample = PopulationSample::capture(&state, &PopulationStatsSettings::default(), true, 1);
+        assert!(sample.exact);
+        assert_eq!(sample.sampled, 1000);
+
+        let exact_mean = state.masses[..1000].iter().map(|&m| m as f64).sum::<f64>() / 1000.0;
+        assert!((sample.mass.mean as f64 - exact_mean).abs() < 1e-5);
+        assert_eq!(sample.mass.margin, 0.0);
+        assert_eq!(sample.modes.len(), 2);
+        assert_eq!(sample.modes[1].count, Estimate { mean: 100.0, margin: 0.0 });
+        let histogram = sample.mass_histogram.expect("distributions requested");
+        assert_eq!(histogram.bins.iter().sum::<f32>(), 1000.0);
+    }
+
+    #[test]
+    fn test_sampled_estimates_fall_within_their_confidence_bounds() {
+        const CELLS: usize = 100_000;
+        let state = population(CELLS);
+        let exact = PopulationSample::capture(&state, &PopulationStatsSettings { exact_threshold: CELLS, ..Default::default() }, false, 0);
+        let settings = PopulationStatsSettings { exact_threshold: 10_000, sample_size: 2_000, ..Default::default() };
+
+        // 95% intervals: across many draws nearly all of them hold the exact value
+        const DRAWS: u64 = 40;
+        let mut within = [0; 5];
+        for seed in 1..=DRAWS {
+            let sample = PopulationSample::capture(&state, &settings, false, seed);
+            assert!(!sample.exact);
+            assert_eq!(sample.population, CELLS);
+            let checks = [
+                sample.mass.contains(exact.mass.mean),
+                sample.speed.contains(exact.speed.mean),
+                sample.modes[0].mass.contains(exact.modes[0].mass.mean),
+                sample.modes[1].mass.contains(exact.modes[1].mass.mean),
+                sample.modes[1].count.contains(exact.modes[1].count.mean),
+            ];
+            for (count, check) in within.iter_mut().zip(checks) {
+                *count += check as u64;
+            }
+            assert!(sample.mass.margin < 0.05, "interval too wide: {}", sample.mass.margin);
+        }
+        for count in within {
+            assert!(count >= DRAWS * 8 / 10, "only {} of {} intervals held the exact value", count, DRAWS);
+        }
+    }
+
+    #[test]
+    fn test_mode_population_history_keeps_scrubbed_samples_and_restarts() {
+        let state = population(100);
+        let genome = GenomeData::default();
+        let mut history = ModePopulationHistory::default();
+        for time in [0.0, 1.0, 2.0, 0.5, 1.0] {
+            history.record(SimulationMode::Preview, &genome, time, &state);
+        }
+        let times: Vec<f32> = history.samples().map(|(time, _)| time).collect();
+        assert_eq!(times, [0.0, 0.5, 1.0, 2.0]);
+        assert_eq!(history.samples().next().unwrap().1[..2], [90, 10]);
+
+        // A renamed mode simulates the same; a changed interval doesn't
+        let mut renamed = genome.clone();
+        renamed.modes[0].name = "Renamed".to_string();
+        history.record(SimulationMode::Preview, &renamed, 3.0, &state);
+        assert_eq!(history.samples().count(), 5);
+        let mut edited = genome.clone();
+        edited.modes[0].split_interval = 1.0;
+        history.record(SimulationMode::Preview, &edited, 3.0, &state);
+        assert_eq!(history.samples().count(), 1);
+
+        // CPU time going backwards is a new scene
+        history.record(SimulationMode::Cpu, &edited, 4.0, &state);
+        history.record(SimulationMode::Cpu, &edited, 1.0, &state);
+        assert_eq!(history.samples().map(|(time, _)| time).collect::<Vec<_>>(), [1.0]);
+    }
+
+    /// Cost of one sample at 100K cells
+    /// Run with `cargo test --release -- --ignored --nocapture population_stats_benchmark`
+    #[test]
+    #[ignore = "timing benchmark"]
+    fn population_stats_benchmark() {
+        const CELLS: usize = 100_000;
+        let state = population(CELLS);
+        let settings = PopulationStatsSettings::default();
+        let time = |distributions: bool| {
+            let started = std::time::Instant::now();
+            for seed in 0..100 {
+                std::hint::black_box(PopulationSample::capture(&state, &settings, distributions, seed));
+            }
+            started.elapsed().as_secs_f64() * 10.0
+        };
+        let (plain, with_distributions) = (time(false), time(true));
+        println!("{} cells: {:.3} ms per sample, {:.3} ms with distributions", CELLS, plain, with_distributions);
+        assert!(plain < 0.2);
+    }
+}
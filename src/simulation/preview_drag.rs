@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::preview_sim::PreviewSimState;
+use crate::simulation::soft_drag::{SoftDrag, SoftDragCell};
+use crate::simulation::{PhysicsConfig, SimulationState};
+
+/// Ticks a held drag may advance the preview per frame (keeps each replay short)
+pub const MAX_DRAG_TICKS_PER_FRAME: u32 = 8;
+
+/// A drag recorded into the Preview history
+///
+/// The cells are pulled toward `target + offset` by the soft drag spring scaled by
+/// their weight (1 for the grabbed cell, so a plain drag is a single full-strength
+/// cell). Replays apply the same pull on the same ticks, so scrubbing back before
+/// the drag and forward through it reproduces it exactly, including the organism-wide
+/// share of a cohesive drag (its weights follow from the replayed bonds).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreviewDrag {
+    pub cells: Vec<SoftDragCell>,
+    /// Share of the pull spread over the grabbed cell's organism, 0 without cohesion
+    pub cohesion: f32,
+    /// First tick the drag pulls on
+    pub start_tick: u32,
+    /// Target of the grabbed cell on each tick from `start_tick`
+    pub targets: Vec<Vec3>,
+}
+
+impl PreviewDrag {
+    pub fn new(region: &SoftDrag, start_tick: u32) -> Self {
+        Self { cells: region.cells.clone(), cohesion: region.cohesion_fraction(), start_tick, targets: Vec::new() }
+    }
+
+    /// Pull of this drag toward `target`
+    pub fn soft_drag(&self, target: Vec3) -> SoftDrag {
+        let drag = SoftDrag { cells: self.cells.clone(), target, cohesion: None };
+        match self.cells.first() {
+            Some(grabbed) => drag.with_cohesion(grabbed.cell_id, self.cohesion),
+            None => drag,
+        }
+    }
+
+    /// First tick after the drag
+    pub fn end_tick(&self) -> u32 {
+        self.start_tick + self.targets.len() as u32
+    }
+
+    pub fn target_at(&self, tick: u32) -> Option<Vec3> {
+        self.targets.get(tick.checked_sub(self.start_tick)? as usize).copied()
+    }
+}
+
+/// Drag being held in Preview mode (always the last recorded drag)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LiveDrag {
+    /// Where the cursor wants the grabbed cell
+    pub target: Vec3,
+    /// Real time not yet simulated
+    pub pending_time: f32,
+}
+
+/// Set up the drag pull for `tick` (before its physics step)
+///
+/// Later drags win where recorded drags overlap; ticks without a drag clear the pull.
+pub fn apply_scheduled_drags(state: &mut CanonicalState, drags: &[PreviewDrag], tick: u32) {
+    let Some((drag, target)) = drags.iter().rev().find_map(|drag| Some((drag, drag.target_at(tick)?))) else {
+        state.soft_drag = None;
+        return;
+    };
+    match state.soft_drag.as_mut() {
+        // Keeping the active drag keeps its cached cohesion weights
+        Some(active) if active.cells == drag.cells && active.cohesion_fraction() == drag.cohesion => active.target = target,
+        _ => state.soft_drag = Some(drag.soft_drag(target)),
+    }
+}
+
+impl PreviewSimState {
+    /// Start recording a drag of `region` at the shown time
+    pub fn begin_drag(&mut self, region: &SoftDrag, fixed_timestep: f32) {
+        let tick = crate::simulation::clock::ticks_to_reach(self.current_time, fixed_timestep);
+        self.drags.push(PreviewDrag::new(region, tick));
+        self.live_drag = Some(LiveDrag { target: region.target, pending_time: 0.0 });
+        self.drop_checkpoints_after(tick, fixed_timestep);
+    }
+
+    /// Later checkpoints were simulated without the drag
+    fn drop_checkpoints_after(&mut self, tick: u32, fixed_timestep: f32) {
+        self.checkpoints.retain(|(time, _)| {
+            crate::simulation::clock::ticks_to_reach(*time, fixed_timestep) <= tick
+        });
+    }
+
+    pub fn drag_to(&mut self, target: Vec3) {
+        if let Some(live) = self.live_drag.as_mut() {
+            live.target = target;
+        }
+    }
+
+    /// Stop recording; the history after the drag carries on from where physics left the cells
+    pub fn end_drag(&mut self) {
+        if self.live_drag.take().is_some() && self.drags.last().is_some_and(|drag| drag.targets.is_empty()) {
+            self.drags.pop();
+        }
+    }
+
+    /// Record the held drag for the next `ticks` ticks; returns the time to simulate to
+    ///
+    /// Continues the last drag if the shown tick is where it ends, otherwise (the history
+    /// moved under the drag) starts a new drag of the same cells at the shown tick.
+    pub fn record_drag_ticks(&mut self, ticks: u32, fixed_timestep: f32) -> Option<f32> {
+        let live = self.live_drag?;
+        let tick = crate::simulation::clock::ticks_to_reach(self.current_time, fixed_timestep);
+        let last = self.drags.last_mut()?;
+        if last.end_tick() != tick {
+            let (cells, cohesion) = (last.cells.clone(), last.cohesion);
+            if last.targets.is_empty() {
+                self.drags.pop();
+            }
+            self.drags.push(PreviewDrag { cells, cohesion, start_tick: tick, targets: Vec::new() });
+            self.drop_checkpoints_after(tick, fixed_timestep);
+        }
+        let drag = self.drags.last_mut()?;
+        drag.targets.extend(std::iter::repeat_n(live.target, ticks as usize));
+        Some(drag.end_tick() as f32 * fixed_timestep)
+    }
+}
+
+/// Advance the preview in real time while a drag is held, recording the pull for each tick
+pub fn advance_dragged_preview(
+    time: Res<Time>,
+    config: Res<PhysicsConfig>,
+    mut preview_state: ResMut<PreviewSimState>,
+    mut sim_state: ResMut<SimulationState>,
+) {
+    let Some(live) = preview_state.live_drag.as_mut() else {
+        return;
+    };
+    let fixed_timestep = config.fixed_timestep;
+    // Frames that fall behind drop time instead of queueing ever longer replays
+    live.pending_time = (live.pending_time + time.delta_secs()).min(fixed_timestep * MAX_DRAG_TICKS_PER_FRAME as f32);
+    if sim_state.is_resimulating || sim_state.target_time.is_some() {
+        return;
+    }
+
+    let ticks = (live.pending_time / fixed_timestep).floor() as u32;
+    if ticks == 0 {
+        return;
+    }
+    live.pending_time -= ticks as f32 * fixed_timestep;
+    if let Some(target_time) = preview_state.record_drag_ticks(ticks, fixed_timestep) {
+        sim_state.target_time = Some(target_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+
+    fn founders() -> CanonicalState {
+        let mut state = CanonicalState::new(8);
+        for x in [-1.0, 1.0] {
+            add_test_cell(&mut state, Vec3::X * x, Quat::IDENTITY, 1.0, 1.0, 0);
+        }
+        state
+    }
+
+    /// One replayed tick, as in the preview resimulation
+    fn step(state: &mut CanonicalState, drags: &[PreviewDrag], tick: u32, genome: &GenomeData, config: &PhysicsConfig) {
+        apply_scheduled_drags(state, drags, tick);
+        let time = tick as f32 * config.fixed_timestep;
+        crate::simulation::cpu_physics::physics_step_with_genome(state, config, genome, time, false);
+        let capacity = state.capacity;
+        crate::simulation::cpu_physics::division_step(state, genome, time, config.fixed_timestep, capacity, 0);
+    }
+
+    #[test]
+    fn test_scrubbing_through_a_drag_replays_it_exactly() {
+        let genome = never_split_genome(&["Pair"]);
+        let config = PhysicsConfig::default();
+        let tick_at = |seconds: f32| crate::simulation::clock::ticks_to_reach(seconds, config.fixed_timestep);
+
+        // Live session: play to 5 s, then hold a drag until 7 s
+        let mut preview = PreviewSimState { canonical_state: founders(), ..PreviewSimState::default() };
+        let mut checkpoint = None;
+        for tick in 0..tick_at(5.0) {
+            if tick == tick_at(2.0) {
+                checkpoint = Some(preview.canonical_state.clone());
+            }
+            step(&mut preview.canonical_state, &preview.drags, tick, &genome, &config);
+        }
+        preview.current_time = tick_at(5.0) as f32 * config.fixed_timestep;
+        let region = SoftDrag::around(&preview.canonical_state, 0, crate::simulation::soft_drag::SoftDragRegion::Radius(0.1));
+        preview.begin_drag(&region, config.fixed_timestep);
+        while preview.current_time < 7.0 {
+            preview.drag_to(Vec3::new(-1.0, preview.current_time - 5.0, 0.0));
+            let start = tick_at(preview.current_time);
+            let target_time = preview.record_drag_ticks(3, config.fixed_timestep).unwrap();
+            for tick in start..tick_at(target_time) {
+                step(&mut preview.canonical_state, &preview.drags, tick, &genome, &config);
+            }
+            preview.current_time = target_time;
+        }
+        preview.end_drag();
+        assert_eq!(preview.drags.len(), 1);
+        let dragged_to = preview.canonical_state.positions[0];
+        assert!(dragged_to.y > 1.0, "the drag should have pulled the cell ({:?})", dragged_to);
+
+        // Scrub back to 3 s (from the 2 s checkpoint), then forward to 10 s
+        let mut scrubbed = checkpoint.unwrap();
+        for tick in tick_at(2.0)..tick_at(3.0) {
+            step(&mut scrubbed, &preview.drags, tick, &genome, &config);
+        }
+        for tick in tick_at(3.0)..tick_at(10.0) {
+            step(&mut scrubbed, &preview.drags, tick, &genome, &config);
+        }
+
+        // Continuous run with the recorded drag
+        let mut continuous = founders();
+        for tick in 0..tick_at(10.0) {
+            step(&mut continuous, &preview.drags, tick, &genome, &config);
+        }
+        assert_eq!(scrubbed.fingerprint(), continuous.fingerprint());
+        assert!(scrubbed.soft_drag.is_none());
+
+        // Without the drag the history differs
+        let mut undisturbed = founders();
+        for tick in 0..tick_at(10.0) {
+            step(&mut undisturbed, &[], tick, &genome, &config);
+        }
+        assert_ne!(undisturbed.fingerprint(), continuous.fingerprint());
+    }
+
+    #[test]
+    fn test_drag_restarts_when_history_moves_under_it() {
+        let config = PhysicsConfig::default();
+        let state = founders();
+        let mut preview = PreviewSimState::default();
+        preview.begin_drag(&SoftDrag::around(&state, 1, crate::simulation::soft_drag::SoftDragRegion::Radius(0.1)), config.fixed_timestep);
+        preview.record_drag_ticks(4, config.fixed_timestep);
+        assert_eq!(preview.drags[0].end_tick(), 4);
+
+        // A scrub moved the shown time elsewhere while the drag was held
+        preview.current_time = 10.0 * config.fixed_timestep;
+        preview.record_drag_ticks(2, config.fixed_timestep);
+        assert_eq!(preview.drags.len(), 2);
+        assert_eq!((preview.drags[1].start_tick, preview.drags[1].end_tick()), (10, 12));
+
+        preview.end_drag();
+        assert!(preview.live_drag.is_none());
+        assert_eq!(preview.drags.len(), 2);
+    }
+}
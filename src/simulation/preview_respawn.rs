@@ -0,0 +1,424 @@
+use bevy::prelude::*;
+use crate::cell::{Cell, CellOrientation, CellPosition, CellSignaling};
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::initial_state::{InitialCell, InitialState};
+use crate::simulation::preview_sim::{PreviewRequest, PreviewSimState};
+use crate::simulation::{EventTimeline, PhysicsConfig, SimulationState};
+use crate::ui::camera::MainCamera;
+
+/// Farthest the founder may start from the center (the world sphere has radius 100)
+pub const MAX_START_OFFSET: f32 = 90.0;
+
+/// Seconds old cells take to fade out and the new founder to scale in
+pub const RESPAWN_TRANSITION_SECS: f32 = 0.35;
+
+/// Where and how the Preview founder cell starts, edited in the Scene Manager
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PreviewStart {
+    /// Founder position, kept within [`MAX_START_OFFSET`] of the center
+    pub position: Vec3,
+    /// Founder orientation as XYZ euler angles in degrees (None uses the genome's initial orientation)
+    pub orientation: Option<Vec3>,
+    /// Founder mass (None uses the initial mode's split mass)
+    pub mass: Option<f32>,
+    /// Fade the old cells out and scale the founder in on respawn
+    pub animate: bool,
+    /// Move the camera with the founder so it stays framed the same way
+    pub keep_framing: bool,
+}
+
+impl Default for PreviewStart {
+    fn default() -> Self {
+        Self {
+            position: Vec3::ZERO,
+            orientation: None,
+            mass: None,
+            animate: true,
+            keep_framing: true,
+        }
+    }
+}
+
+impl PreviewStart {
+    /// Position pulled back inside the world sphere
+    pub fn clamped_position(&self) -> Vec3 {
+        self.position.clamp_length_max(MAX_START_OFFSET)
+    }
+
+    /// Founder orientation for `genome`
+    pub fn rotation(&self, genome: &GenomeData) -> Quat {
+        match self.orientation {
+            Some(degrees) => Quat::from_euler(
+                EulerRot::XYZ,
+                degrees.x.to_radians(),
+                degrees.y.to_radians(),
+                degrees.z.to_radians(),
+            ),
+            None => genome.initial_orientation,
+        }
+    }
+
+    /// Founder cell of a fresh preview of `genome`
+    pub fn founder(&self, genome: &GenomeData) -> InitialCell {
+        let mode_index = genome.initial_mode_index();
+        let mode = genome.modes.get(mode_index).or_else(|| genome.modes.first());
+        // Use get_split_mass/get_split_interval for potentially randomized values
+        let seed = genome.metadata.seed;
+        let (split_mass, split_interval) = mode
+            .map(|mode| (mode.get_split_mass(0, 0, seed), mode.get_split_interval(0, 0, seed)))
+            .unwrap_or((1.0, 5.0));
+
+        InitialCell {
+            id: 0,
+            position: self.clamped_position(),
+            velocity: Vec3::ZERO,
+            rotation: self.rotation(genome),
+            angular_velocity: Vec3::ZERO,
+            mass: self.mass.unwrap_or(split_mass),
+            radius: 1.0,
+            genome_id: 0,
+            mode_index,
+            birth_time: 0.0,
+            split_interval,
+            split_mass,
+            stiffness: 500.0, // High enough to prevent pass-through
+            pinned: false,
+            user_data: Default::default(),
+        }
+    }
+
+    /// Carry a genome edit into the founder of a running timeline (its placement stays)
+    pub fn update_founder(&self, founder: &mut InitialCell, genome: &GenomeData) {
+        let mode_index = genome.initial_mode_index();
+        if let Some(mode) = genome.modes.get(mode_index).or_else(|| genome.modes.first()) {
+            founder.split_interval = mode.get_split_interval(0, 0, genome.metadata.seed);
+            founder.split_mass = mode.get_split_mass(0, 0, genome.metadata.seed);
+            founder.mode_index = mode_index;
+            founder.rotation = self.rotation(genome);
+        }
+    }
+
+    /// Initial state holding just the founder, with the preview capacity of 256 cells and
+    /// the genome's pinned seed
+    pub fn initial_state(&self, genome: &GenomeData, config: &PhysicsConfig, memory_profile: crate::simulation::MemoryProfile) -> InitialState {
+        let mut initial_state = InitialState::new(config.clone(), 256, genome.metadata.seed);
+        initial_state.memory_profile = memory_profile;
+        initial_state.add_cell(self.founder(genome));
+        initial_state
+    }
+}
+
+/// One step of [`PreviewTimeline::reset`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResetStep {
+    /// Drop the replay in flight, which would otherwise land old-timeline results later
+    CancelReplay,
+    /// Time back to 0 with no pending scrub target
+    ResetClock,
+    /// Checkpoints of the old timeline
+    DropSnapshots,
+    /// Capacity growths, pins, user data, surgery, stamps and drags
+    ClearInterventions,
+    /// Events and lineage shown on the time slider
+    ClearEvents,
+    /// The new initial state, respawned next frame
+    InstallFounder,
+}
+
+/// Order the reset runs in: nothing may be left to write into the new timeline once
+/// the founder is installed
+pub const RESET_SEQUENCE: [ResetStep; 6] = [
+    ResetStep::CancelReplay,
+    ResetStep::ResetClock,
+    ResetStep::DropSnapshots,
+    ResetStep::ClearInterventions,
+    ResetStep::ClearEvents,
+    ResetStep::InstallFounder,
+];
+
+/// Everything that makes up the Preview timeline
+pub struct PreviewTimeline<'a> {
+    pub preview: &'a mut PreviewSimState,
+    pub request: &'a mut PreviewRequest,
+    pub sim_state: &'a mut SimulationState,
+    pub timeline: &'a mut EventTimeline,
+}
+
+impl PreviewTimeline<'_> {
+    /// Start the preview over from `initial_state`, dropping everything recorded on the old timeline
+    ///
+    /// The caller owns the old cell entities; `index_to_entity` is emptied.
+    pub fn reset(&mut self, initial_state: InitialState) {
+        let mut initial_state = Some(initial_state);
+        for step in RESET_SEQUENCE {
+            self.apply(step, &mut initial_state);
+        }
+    }
+
+    fn apply(&mut self, step: ResetStep, initial_state: &mut Option<InitialState>) {
+        match step {
+            ResetStep::CancelReplay => {
+                self.request.background_task = None;
+                self.sim_state.is_resimulating = false;
+            }
+            ResetStep::ResetClock => {
+                self.preview.current_time = 0.0;
+                self.sim_state.target_time = None;
+            }
+            ResetStep::DropSnapshots => self.preview.clear_checkpoints(),
+            ResetStep::ClearInterventions => {
+                let preview = &mut *self.preview;
+                preview.capacity_growths.clear();
+                preview.pin_changes.clear();
+                preview.user_data_changes.clear();
+                preview.surgery.clear();
+                preview.stamps.clear();
+                preview.drags.clear();
+                preview.settles.clear();
+                preview.live_drag = None;
+            }
+            ResetStep::ClearEvents => self.timeline.clear(),
+            ResetStep::InstallFounder => {
+                let Some(initial_state) = initial_state.take() else {
+                    return;
+                };
+                let max_cells = initial_state.max_cells;
+                self.preview.canonical_state = initial_state.to_canonical_state();
+                self.preview.initial_state = initial_state;
+                self.preview.index_to_entity.clear();
+                self.preview.index_to_entity.resize(max_cells, None);
+                self.sim_state.needs_respawn = true;
+            }
+        }
+    }
+}
+
+/// Respawn requests and the running transition of the Preview founder
+#[derive(Resource, Default)]
+pub struct PreviewRespawn {
+    /// Placement of the next respawn, mirrored from the Scene Manager
+    pub start: PreviewStart,
+    /// Start the preview over from a new founder
+    pub requested: bool,
+    /// App time the current founder started scaling in
+    scale_in_started: Option<f32>,
+}
+
+/// Old cell fading out after a respawn
+#[derive(Component)]
+pub struct PreviewFadeOut {
+    started: f32,
+    scale: Vec3,
+    alpha: f32,
+}
+
+/// Start the preview over from a founder placed by [`PreviewRespawn::start`]
+#[allow(clippy::too_many_arguments)]
+pub fn respawn_preview_founder(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut respawn: ResMut<PreviewRespawn>,
+    mut preview_state: ResMut<PreviewSimState>,
+    mut preview_request: ResMut<PreviewRequest>,
+    mut sim_state: ResMut<SimulationState>,
+    mut timeline: ResMut<EventTimeline>,
+    mut genome: ResMut<CurrentGenome>,
+    config: Res<PhysicsConfig>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cells: Query<(&Transform, &MeshMaterial3d<StandardMaterial>)>,
+    mut cameras: Query<&mut MainCamera>,
+) {
+    if !std::mem::take(&mut respawn.requested) {
+        return;
+    }
+    let start = respawn.start;
+    let now = time.elapsed_secs();
+    let old_entities: Vec<Entity> = preview_state.index_to_entity.iter().flatten().copied().collect();
+    let old_position = preview_state.start.clamped_position();
+
+    let initial_state = start.initial_state(&genome.genome, &config, preview_state.initial_state.memory_profile);
+    PreviewTimeline {
+        preview: &mut preview_state,
+        request: &mut preview_request,
+        sim_state: &mut sim_state,
+        timeline: &mut timeline,
+    }
+    .reset(initial_state);
+    preview_state.start = start;
+    let seed = genome.genome.metadata.seed;
+    genome.genome.record_run(crate::genome::RunKind::Preview, seed);
+    preview_state.simulated_genome = Some(genome.genome.clone());
+
+    for mut camera in cameras.iter_mut() {
+        if camera.followed_entity.is_some_and(|entity| old_entities.contains(&entity)) {
+            camera.followed_entity = None;
+        }
+        if start.keep_framing {
+            camera.center += start.clamped_position() - old_position;
+        }
+    }
+
+    for entity in old_entities {
+        let fade = start.animate.then(|| cells.get(entity).ok()).flatten();
+        let Some((transform, material)) = fade else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        // Own blended material so the shared per-mode material stays opaque
+        let mut faded = materials.get(&material.0).cloned().unwrap_or_default();
+        let alpha = faded.base_color.alpha();
+        faded.alpha_mode = AlphaMode::Blend;
+        // Without cell components the fading entity is invisible to picking and the cell systems
+        commands.entity(entity)
+            .remove::<(Cell, CellPosition, CellOrientation, CellSignaling)>()
+            .insert((
+                MeshMaterial3d(materials.add(faded)),
+                PreviewFadeOut { started: now, scale: transform.scale, alpha },
+            ));
+    }
+    respawn.scale_in_started = start.animate.then_some(now);
+}
+
+/// Progress of a respawn transition started at `started`, eased from 0 to 1
+fn transition_progress(started: f32, now: f32) -> f32 {
+    let t = ((now - started) / RESPAWN_TRANSITION_SECS).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Scale the new founder in and fade the old cells out (after the transforms are synced)
+pub fn animate_preview_respawn(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut respawn: ResMut<PreviewRespawn>,
+    preview_state: Res<PreviewSimState>,
+    mut transforms: Query<&mut Transform, Without<PreviewFadeOut>>,
+    mut fading: Query<(Entity, &PreviewFadeOut, &mut Transform, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let now = time.elapsed_secs();
+
+    for (entity, fade, mut transform, material) in fading.iter_mut() {
+        let progress = transition_progress(fade.started, now);
+        if progress >= 1.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.scale = fade.scale * (1.0 - 0.5 * progress);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color.set_alpha(fade.alpha * (1.0 - progress));
+        }
+    }
+
+    let Some(started) = respawn.scale_in_started else {
+        return;
+    };
+    let progress = transition_progress(started, now);
+    for entity in preview_state.index_to_entity.iter().flatten() {
+        if let Ok(mut transform) = transforms.get_mut(*entity) {
+            transform.scale *= progress.max(0.01);
+        }
+    }
+    if progress >= 1.0 {
+        respawn.scale_in_started = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::event_timeline::{TimelineEvent, TimelineEventKind};
+
+    fn position(step: ResetStep) -> usize {
+        RESET_SEQUENCE.iter().position(|&s| s == step).unwrap()
+    }
+
+    #[test]
+    fn reset_cancels_first_and_installs_last() {
+        assert_eq!(RESET_SEQUENCE[0], ResetStep::CancelReplay);
+        assert_eq!(RESET_SEQUENCE[RESET_SEQUENCE.len() - 1], ResetStep::InstallFounder);
+        // The clock is back at 0 before anything that could be scrubbed to is dropped
+        assert!(position(ResetStep::ResetClock) < position(ResetStep::DropSnapshots));
+        assert!(position(ResetStep::ResetClock) < position(ResetStep::ClearEvents));
+        for step in [ResetStep::ResetClock, ResetStep::DropSnapshots, ResetStep::ClearInterventions, ResetStep::ClearEvents] {
+            assert_eq!(RESET_SEQUENCE.iter().filter(|&&s| s == step).count(), 1);
+        }
+    }
+
+    #[test]
+    fn reset_leaves_nothing_of_the_old_timeline() {
+        let genome = GenomeData::default();
+        let mut preview = PreviewSimState::default();
+        preview.current_time = 12.5;
+        preview.checkpoints.push((5.0, preview.canonical_state.clone()));
+        preview.capacity_growths.push((40, 512));
+        preview.index_to_entity[3] = Some(Entity::PLACEHOLDER);
+        let mut request = PreviewRequest::default();
+        let mut sim_state = SimulationState {
+            target_time: Some(20.0),
+            is_resimulating: true,
+            ..SimulationState::default()
+        };
+        let mut timeline = EventTimeline::default();
+        timeline.push(TimelineEvent::new(100, TimelineEventKind::GenomeEdit, [None, None]));
+
+        let start = PreviewStart { position: Vec3::new(10.0, -4.0, 2.0), ..PreviewStart::default() };
+        let initial_state = start.initial_state(&genome, &PhysicsConfig::default(), Default::default());
+        PreviewTimeline {
+            preview: &mut preview,
+            request: &mut request,
+            sim_state: &mut sim_state,
+            timeline: &mut timeline,
+        }
+        .reset(initial_state);
+
+        assert_eq!(preview.current_time, 0.0);
+        assert_eq!(sim_state.target_time, None);
+        assert!(!sim_state.is_resimulating);
+        assert!(sim_state.needs_respawn);
+        assert!(preview.checkpoints.is_empty());
+        assert!(preview.capacity_growths.is_empty());
+        assert!(timeline.events().is_empty());
+        assert!(preview.index_to_entity.iter().all(Option::is_none));
+        assert_eq!(preview.canonical_state.cell_count, 1);
+        assert_eq!(preview.canonical_state.positions[0], start.position);
+    }
+
+    #[test]
+    fn founder_defaults_follow_the_genome() {
+        let mut genome = GenomeData::default();
+        genome.initial_orientation = Quat::from_rotation_y(0.7);
+        let founder = PreviewStart::default().founder(&genome);
+        assert_eq!(founder.position, Vec3::ZERO);
+        assert_eq!(founder.rotation, genome.initial_orientation);
+        assert_eq!(founder.mass, founder.split_mass);
+    }
+
+    #[test]
+    fn founder_overrides_and_clamps() {
+        let genome = GenomeData::default();
+        let start = PreviewStart {
+            position: Vec3::new(200.0, 0.0, 0.0),
+            orientation: Some(Vec3::new(0.0, 90.0, 0.0)),
+            mass: Some(2.5),
+            ..PreviewStart::default()
+        };
+        let founder = start.founder(&genome);
+        assert!((founder.position.length() - MAX_START_OFFSET).abs() < 1e-4);
+        assert!(founder.rotation.abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-5));
+        assert_eq!(founder.mass, 2.5);
+
+        // Genome edits keep the placement and the orientation override
+        let mut edited = founder.clone();
+        start.update_founder(&mut edited, &genome);
+        assert_eq!(edited.position, founder.position);
+        assert_eq!(edited.rotation, founder.rotation);
+    }
+
+    #[test]
+    fn transition_eases_from_zero_to_one() {
+        assert_eq!(transition_progress(1.0, 1.0), 0.0);
+        assert_eq!(transition_progress(1.0, 1.0 + RESPAWN_TRANSITION_SECS), 1.0);
+        assert!((transition_progress(0.0, RESPAWN_TRANSITION_SECS / 2.0) - 0.5).abs() < 1e-6);
+        assert_eq!(transition_progress(0.0, 10.0), 1.0);
+    }
+}
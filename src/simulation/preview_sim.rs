@@ -7,6 +7,7 @@ use crate::ui::camera::MainCamera;
 use crate::simulation::cpu_physics::CanonicalState;
 use crate::simulation::initial_state::InitialState;
 use crate::simulation::PhysicsConfig;
+use crate::simulation::preview_respawn::{PreviewRespawn, PreviewStart, PreviewTimeline};
 
 /// Preview simulation plugin for genome testing
 /// Uses deterministic replay from time 0 with canonical physics
@@ -16,11 +17,15 @@ impl Plugin for PreviewSimPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PreviewSimState>()
             .init_resource::<PreviewRequest>()
+            .init_resource::<PreviewRespawn>()
             .add_systems(OnEnter(PreviewSceneState::Active), (setup_preview_scene, spawn_preview_skybox))
             .add_systems(OnExit(PreviewSceneState::Active), cleanup_preview_scene)
             .add_systems(
                 Update,
                 (
+                    apply_pending_scenario,
+                    crate::simulation::preview_respawn::respawn_preview_founder,
+                    crate::simulation::preview_drag::advance_dragged_preview,
                     run_preview_resimulation,
                     respawn_preview_cells_after_resimulation,
                 )
@@ -36,6 +41,7 @@ impl Plugin for PreviewSimPlugin {
                     sync_preview_visuals,
                     crate::rendering::sync_transforms,
                     highlight_selected_mode_cells,
+                    crate::simulation::preview_respawn::animate_preview_respawn,
                 )
                     .chain()
                     .after(respawn_preview_cells_after_resimulation)
@@ -83,8 +89,44 @@ pub struct PreviewSimState {
     /// Checkpoint interval in seconds
     pub checkpoint_interval: f32,
     
-    /// Hash of genome to detect changes
-    pub genome_hash: u64,
+    /// Genome the current timeline was simulated with (None forces a resimulation)
+    pub simulated_genome: Option<crate::genome::GenomeData>,
+
+    /// App time of the last resimulation started by a genome edit (seconds)
+    pub last_edit_resimulation: f64,
+    
+    /// Timestep the checkpoints were simulated with
+    pub physics_timestep: f32,
+
+    /// Central attractor (strength, softening) the checkpoints were simulated with
+    pub physics_attractor: Option<(f32, f32)>,
+
+    /// Runtime capacity growths as (tick, capacity), re-applied by every replay
+    pub capacity_growths: Vec<(u32, usize)>,
+
+    /// Pin changes as (tick, change), re-applied by every replay
+    pub pin_changes: Vec<(u32, crate::simulation::pinning::PinChange)>,
+
+    /// User data writes as (tick, change), re-applied by every replay
+    pub user_data_changes: Vec<(u32, crate::simulation::user_data::UserDataChange)>,
+
+    /// Organism splits and grafts as (tick, operation), re-applied by every replay
+    pub surgery: Vec<(u32, crate::simulation::organism_surgery::SurgeryOp)>,
+
+    /// Tissue stamps as (tick, stamp), re-placed by every replay
+    pub stamps: Vec<(u32, crate::simulation::tissue_stamp::StampRequest)>,
+
+    /// Cell drags, re-applied by every replay (the held drag is the last one)
+    pub drags: Vec<crate::simulation::preview_drag::PreviewDrag>,
+
+    /// Settle starts and stops as (tick, change), re-applied by every replay
+    pub settles: Vec<(u32, crate::simulation::settle::SettleChange)>,
+
+    /// Drag currently held by the user
+    pub live_drag: Option<crate::simulation::preview_drag::LiveDrag>,
+
+    /// Placement the founder of the current timeline was built with
+    pub start: PreviewStart,
 }
 
 impl Default for PreviewSimState {
@@ -100,89 +142,26 @@ impl Default for PreviewSimState {
             index_to_entity: vec![None; 256],
             checkpoints: Vec::new(),
             checkpoint_interval: 5.0, // Checkpoint every 5 seconds
-            genome_hash: 0,
+            simulated_genome: None,
+            last_edit_resimulation: f64::NEG_INFINITY,
+            physics_timestep: PhysicsConfig::default().fixed_timestep,
+            physics_attractor: None,
+            capacity_growths: Vec::new(),
+            pin_changes: Vec::new(),
+            user_data_changes: Vec::new(),
+            surgery: Vec::new(),
+            stamps: Vec::new(),
+            drags: Vec::new(),
+            settles: Vec::new(),
+            live_drag: None,
+            start: PreviewStart::default(),
         }
     }
 }
 
 impl PreviewSimState {
-    /// Compute a comprehensive hash of the genome for change detection
-    /// This hashes ALL fields that affect simulation behavior
-    fn compute_genome_hash(genome: &crate::genome::GenomeData) -> u64 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-
-        // Hash genome-level properties
-        genome.name.hash(&mut hasher);
-        genome.initial_mode.hash(&mut hasher);
-        genome.initial_orientation.to_array().iter().for_each(|v| {
-            v.to_bits().hash(&mut hasher);
-        });
-
-        // Hash ALL mode fields that affect simulation
-        for mode in &genome.modes {
-            // Identity
-            mode.name.hash(&mut hasher);
-            mode.default_name.hash(&mut hasher);
-            mode.color.x.to_bits().hash(&mut hasher);
-            mode.color.y.to_bits().hash(&mut hasher);
-            mode.color.z.to_bits().hash(&mut hasher);
-            mode.opacity.to_bits().hash(&mut hasher);
-            mode.emissive.to_bits().hash(&mut hasher);
-
-            // Cell type
-            mode.cell_type.hash(&mut hasher);
-            mode.swim_force.to_bits().hash(&mut hasher);
-
-            // Parent settings
-            mode.split_mass.to_bits().hash(&mut hasher);
-            mode.split_interval.to_bits().hash(&mut hasher);
-            mode.nutrient_gain_rate.to_bits().hash(&mut hasher);
-            mode.max_cell_size.to_bits().hash(&mut hasher);
-            mode.split_ratio.to_bits().hash(&mut hasher);
-            mode.nutrient_priority.to_bits().hash(&mut hasher);
-            mode.parent_split_direction.x.to_bits().hash(&mut hasher);
-            mode.parent_split_direction.y.to_bits().hash(&mut hasher);
-            mode.max_adhesions.hash(&mut hasher);
-            mode.min_adhesions.hash(&mut hasher);
-            mode.max_splits.hash(&mut hasher);
-            mode.parent_make_adhesion.hash(&mut hasher);
-
-            // Child settings
-            mode.child_a.mode_number.hash(&mut hasher);
-            mode.child_a.orientation.x.to_bits().hash(&mut hasher);
-            mode.child_a.orientation.y.to_bits().hash(&mut hasher);
-            mode.child_a.orientation.z.to_bits().hash(&mut hasher);
-            mode.child_a.orientation.w.to_bits().hash(&mut hasher);
-            mode.child_a.keep_adhesion.hash(&mut hasher);
-            mode.child_b.mode_number.hash(&mut hasher);
-            mode.child_b.orientation.x.to_bits().hash(&mut hasher);
-            mode.child_b.orientation.y.to_bits().hash(&mut hasher);
-            mode.child_b.orientation.z.to_bits().hash(&mut hasher);
-            mode.child_b.orientation.w.to_bits().hash(&mut hasher);
-            mode.child_b.keep_adhesion.hash(&mut hasher);
-
-            // Adhesion settings
-            mode.adhesion_settings.can_break.hash(&mut hasher);
-            mode.adhesion_settings.break_force.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.rest_length.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.linear_spring_stiffness.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.linear_spring_damping.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.orientation_spring_stiffness.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.orientation_spring_damping.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.max_angular_deviation.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.enable_twist_constraint.hash(&mut hasher);
-            mode.adhesion_settings.twist_constraint_stiffness.to_bits().hash(&mut hasher);
-            mode.adhesion_settings.twist_constraint_damping.to_bits().hash(&mut hasher);
-        }
-
-        hasher.finish()
-    }
-    
     /// Clear checkpoints (called when genome changes)
-    fn clear_checkpoints(&mut self) {
+    pub(crate) fn clear_checkpoints(&mut self) {
         self.checkpoints.clear();
     }
     
@@ -206,6 +185,22 @@ impl PreviewSimState {
             self.checkpoints.push((time, state.clone()));
         }
     }
+    
+    /// Apply a user intervention, such as a pin change, at the current tick
+    ///
+    /// Skipped while a resimulation is running, since it would overwrite the change; the
+    /// requests stay queued until it lands. Checkpoints after the tick were simulated
+    /// without the change and are dropped.
+    pub(crate) fn intervene(&mut self, resimulating: bool, fixed_timestep: f32, apply: impl FnOnce(&mut Self, u32)) {
+        if resimulating {
+            return;
+        }
+        let tick = crate::simulation::clock::ticks_to_reach(self.current_time, fixed_timestep);
+        apply(self, tick);
+        self.checkpoints.retain(|(time, _)| {
+            crate::simulation::clock::ticks_to_reach(*time, fixed_timestep) <= tick
+        });
+    }
 }
 
 /// Result from background resimulation task
@@ -213,6 +208,12 @@ pub struct ResimulationResult {
     pub canonical_state: CanonicalState,
     pub target_time: f32,
     pub new_checkpoints: Vec<(f32, CanonicalState)>,
+    /// Events of the replayed ticks, in tick order
+    pub timeline_events: Vec<crate::simulation::event_timeline::TimelineEvent>,
+    /// Births of the replayed divisions, in tick order
+    pub lineage: Vec<crate::simulation::event_timeline::LineageRecord>,
+    /// Breakpoint that stopped the replay short of its target
+    pub breakpoint_hit: Option<crate::simulation::breakpoints::BreakpointHit>,
 }
 
 /// Preview request resource
@@ -227,17 +228,24 @@ fn setup_preview_scene(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     fog_settings: Res<crate::rendering::VolumetricFogSettings>,
+    capabilities: Res<crate::rendering::RenderCapabilities>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut preview_state: ResMut<PreviewSimState>,
-    genome: Res<CurrentGenome>,
+    mut genome: ResMut<CurrentGenome>,
     config: Res<PhysicsConfig>,
     lighting_config: Res<crate::ui::lighting_settings::LightingConfig>,
     camera_query: Query<Entity, With<MainCamera>>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    memory: Res<crate::simulation::SimulationMemory>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
+    mut sim_state: ResMut<crate::simulation::SimulationState>,
+    mut preview_request: ResMut<PreviewRequest>,
+    respawn: Res<PreviewRespawn>,
 ) {
     // Only spawn camera if it doesn't already exist (from scene switching)
     if camera_query.is_empty() {
         // Spawn camera with volumetric fog and boundary crossing effect
-        commands.spawn((
+        let mut camera = commands.spawn((
             Camera3d::default(),
             MainCamera{
                 center: Vec3::ZERO, // Orbit around world origin
@@ -248,11 +256,6 @@ fn setup_preview_scene(
                 mode: crate::ui::camera::CameraMode::Orbit,
                 followed_entity: None,
             },
-            bevy::light::VolumetricFog {
-                ambient_intensity: fog_settings.ambient_intensity,
-                step_count: fog_settings.step_count,
-                ..default()
-            },
             // Boundary crossing post-processing effect
             crate::rendering::BoundaryCrossingSettings::default(),
             // OIT (Order-Independent Transparency) - currently disabled
@@ -260,6 +263,14 @@ fn setup_preview_scene(
             Msaa::Sample4, // Enable MSAA for AlphaToCoverage transparency
             PreviewSceneEntity,
         ));
+        // Fog needs a large 3D texture that some adapters can't create
+        if capabilities.volumetric_fog.is_available() {
+            camera.insert(bevy::light::VolumetricFog {
+                ambient_intensity: fog_settings.ambient_intensity,
+                step_count: fog_settings.step_count,
+                ..default()
+            });
+        }
     } else {
         // Reset existing camera to default position
         for camera in camera_query.iter() {
@@ -327,122 +338,26 @@ fn setup_preview_scene(
     
     // Fog volume is now spawned automatically by VolumetricFogPlugin
     
-    // Initialize preview state with single cell at origin
-    let initial_mode_index = genome.genome.initial_mode.max(0) as usize;
-    let mode = genome.genome.modes.get(initial_mode_index)
-        .or_else(|| genome.genome.modes.first());
-    
-    let (split_mass, split_interval) = if let Some(mode) = mode {
-        // Use get_split_mass/get_split_interval for potentially randomized values
-        (mode.get_split_mass(0, 0, 0), mode.get_split_interval(0, 0, 0))
-    } else {
-        (1.0, 5.0)
-    };
-    
-    let cell_radius = 1.0;
-    let stiffness = 500.0;  // Increased from 10.0 to prevent pass-through
-    
-    // Create initial state with preview-specific capacity limit (256 cells)
-    // Preview simulation is optimized for low cell counts with real-time genome updates
-    let mut initial_state = InitialState::new(
-        config.clone(),
-        256, // Preview capacity limit
-        0, // RNG seed
-    );
-    
-    initial_state.add_cell(crate::simulation::InitialCell {
-        id: 0,
-        position: Vec3::ZERO,
-        velocity: Vec3::ZERO,
-        rotation: genome.genome.initial_orientation,
-        angular_velocity: Vec3::ZERO,
-        mass: split_mass,
-        radius: cell_radius,
-        genome_id: 0,
-        mode_index: initial_mode_index,
-        birth_time: 0.0,
-        split_interval,
-        split_mass,
-        stiffness,
-    });
-    
-    // Convert to canonical state
-    let canonical_state = initial_state.to_canonical_state();
-    
-    // Update preview state
-    preview_state.initial_state = initial_state;
-    preview_state.canonical_state = canonical_state;
-    preview_state.current_time = 0.0;
-    preview_state.index_to_entity.clear();
-    preview_state.index_to_entity.resize(256, None);
-    preview_state.checkpoints.clear();
-    preview_state.genome_hash = PreviewSimState::compute_genome_hash(&genome.genome);
-    
-    // Spawn ECS entity for the initial cell
-    let mode = genome.genome.modes.get(initial_mode_index)
-        .or_else(|| genome.genome.modes.first());
-    let (color, opacity, emissive) = if let Some(mode) = mode {
-        (mode.color, mode.opacity, mode.emissive)
-    } else {
-        (Vec3::ONE, 1.0, 0.0)
-    };
-    
-    // Check if this is a flagellocyte
-    let is_flagellocyte = mode.map(|m| m.cell_type == 1).unwrap_or(false);
-    let swim_force = mode.map(|m| m.swim_force).unwrap_or(0.0);
-    
-    // Choose mesh based on cell type
-    let cell_mesh = if is_flagellocyte {
-        meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(cell_radius, swim_force, 5))
-    } else {
-        meshes.add(Sphere::new(cell_radius).mesh().ico(5).unwrap())
-    };
-    
-    let entity = commands.spawn((
-        Cell {
-            mass: split_mass,
-            radius: cell_radius,
-            genome_id: 0,
-            mode_index: initial_mode_index,
-            cell_type: mode.map(|m| m.cell_type).unwrap_or(0),
-        },
-        CellPosition {
-            position: Vec3::ZERO,
-            velocity: Vec3::ZERO,
-        },
-        CellOrientation {
-            rotation: genome.genome.initial_orientation,
-            angular_velocity: Vec3::ZERO,
-        },
-        CellSignaling::default(),
-        crate::cell::division::DivisionTimer {
-            birth_time: 0.0,
-            split_interval,
-        },
-        crate::cell::physics::CellForces::default(),
-        crate::cell::physics::Cytoskeleton {
-            stiffness,
-        },
-        Mesh3d(cell_mesh),
-        MeshMaterial3d(materials.add(StandardMaterial {
-            base_color: Color::srgba(color.x, color.y, color.z, opacity),
-            emissive: LinearRgba::rgb(color.x * emissive, color.y * emissive, color.z * emissive),
-            cull_mode: Some(bevy::render::render_resource::Face::Back),
-            alpha_mode: if opacity < 0.99 {
-                bevy::prelude::AlphaMode::AlphaToCoverage
-            } else {
-                bevy::prelude::AlphaMode::Opaque
-            },
-            ..default()
-        })),
-        Transform::from_translation(Vec3::ZERO)
-            .with_rotation(genome.genome.initial_orientation),
-        Visibility::default(),
-        PreviewSceneEntity,
-    )).id();
+    // Resume the scene left for CPU mode; its cells are respawned next frame
+    if let Some(scene) = dormant.preview.take() {
+        info!("Restoring preview scene ({} cells, t={:.2}s)", scene.preview.canonical_state.cell_count, scene.preview.current_time);
+        scene.restore(&mut preview_state, &mut timeline, &mut sim_state);
+        return;
+    }
     
-    // Map cell index to entity
-    preview_state.index_to_entity[0] = Some(entity);
+    // Start with a single founder cell placed as set in the Scene Manager
+    let initial_state = respawn.start.initial_state(&genome.genome, &config, memory.profile);
+    PreviewTimeline {
+        preview: &mut preview_state,
+        request: &mut preview_request,
+        sim_state: &mut sim_state,
+        timeline: &mut timeline,
+    }
+    .reset(initial_state);
+    preview_state.start = respawn.start;
+    let seed = genome.genome.metadata.seed;
+    genome.genome.record_run(crate::genome::RunKind::Preview, seed);
+    preview_state.simulated_genome = Some(genome.genome.clone());
 }
 
 /// Cleanup Preview scene entities (but keep the camera)
@@ -455,14 +370,52 @@ fn cleanup_preview_scene(
     }
 }
 
+/// Replace the preview scene with a scenario loaded from the Scene Manager
+fn apply_pending_scenario(
+    mut commands: Commands,
+    mut pending: ResMut<crate::simulation::scenario_presets::PendingScenario>,
+    mut preview_state: ResMut<PreviewSimState>,
+    mut sim_state: ResMut<crate::simulation::SimulationState>,
+    mut preview_request: ResMut<PreviewRequest>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    genome: Res<CurrentGenome>,
+) {
+    let Some(scene) = pending.scene.take() else {
+        return;
+    };
+
+    // The scene's cells replace every entity of the previous scene
+    for entity in preview_state.index_to_entity.iter().flatten() {
+        commands.entity(*entity).despawn();
+    }
+
+    let max_cells = preview_state.initial_state.max_cells;
+    let mut initial_state = scene.to_initial_state(max_cells);
+    initial_state.memory_profile = preview_state.initial_state.memory_profile;
+    PreviewTimeline {
+        preview: &mut preview_state,
+        request: &mut preview_request,
+        sim_state: &mut sim_state,
+        timeline: &mut timeline,
+    }
+    .reset(initial_state);
+    preview_state.simulated_genome = Some(genome.genome.clone());
+}
+
+/// Shortest time between resimulations triggered by genome edits (about 5 per second)
+const EDIT_RESIMULATION_INTERVAL: f64 = 0.2;
+
 /// Run preview re-simulation using canonical physics in a background task
 /// This runs the simulation asynchronously to keep the UI responsive
 fn run_preview_resimulation(
+    time: Res<Time>,
     mut preview_state: ResMut<PreviewSimState>,
     mut sim_state: ResMut<crate::simulation::SimulationState>,
     config: Res<PhysicsConfig>,
     genome: Res<CurrentGenome>,
     mut preview_request: ResMut<PreviewRequest>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    mut breakpoints: ResMut<crate::simulation::Breakpoints>,
 ) {
     // Check if there's a completed background task
     if let Some(mut task) = preview_request.background_task.take() {
@@ -471,12 +424,26 @@ fn run_preview_resimulation(
             let _old_cell_count = preview_state.canonical_state.cell_count;
             preview_state.canonical_state = result.canonical_state;
             preview_state.current_time = result.target_time;
+            let capacity = preview_state.canonical_state.capacity;
+            if preview_state.index_to_entity.len() < capacity {
+                preview_state.index_to_entity.resize(capacity, None);
+            }
             let _new_cell_count = preview_state.canonical_state.cell_count;
             
             // Add new checkpoints created during simulation
             for (time, state) in result.new_checkpoints {
                 preview_state.maybe_add_checkpoint(time, &state);
             }
+            timeline.extend(result.timeline_events);
+            timeline.extend_lineage(result.lineage);
+            if let Some(hit) = result.breakpoint_hit {
+                timeline.push(crate::simulation::event_timeline::TimelineEvent::new(
+                    hit.tick,
+                    crate::simulation::event_timeline::TimelineEventKind::Breakpoint,
+                    [hit.cell_id, None],
+                ));
+                breakpoints.record_hit(hit);
+            }
             
             sim_state.target_time = None;
             sim_state.is_resimulating = false;
@@ -492,34 +459,63 @@ fn run_preview_resimulation(
         }
     }
 
-    // Check if genome actually changed by comparing hash
+    // Check if the genome changed in a way that affects the simulation
     // Note: We can't use genome.is_changed() because the UI system uses ResMut
     // which marks it as changed every frame even with no actual edits
-    let current_genome_hash = PreviewSimState::compute_genome_hash(&genome.genome);
-    let genome_changed = current_genome_hash != preview_state.genome_hash;
+    let functional_edit = !preview_state.simulated_genome.as_ref()
+        .is_some_and(|simulated| simulated.functionally_equal(&genome.genome));
+    // A pinned seed edit restarts the timeline under the new seed (loaded scenes keep theirs otherwise)
+    let seed_changed = preview_state.simulated_genome.as_ref()
+        .is_some_and(|simulated| simulated.metadata.seed != genome.genome.metadata.seed);
+    // Checkpoints are only valid for the timestep and attractor they were simulated with
+    let timestep_changed = preview_state.physics_timestep != config.fixed_timestep
+        || preview_state.physics_attractor != config.central_attractor();
+
+    // Continuous drags resimulate at most every EDIT_RESIMULATION_INTERVAL; the edit still
+    // differs once the drag ends, so the final value is always simulated
+    let now = time.elapsed_secs_f64();
+    let edit_due = functional_edit && now - preview_state.last_edit_resimulation >= EDIT_RESIMULATION_INTERVAL;
+    let genome_changed = edit_due || timestep_changed;
+
+    if !functional_edit {
+        // Looks-only edits (colors, names) rebuild the cells without resimulating
+        let looks_changed = preview_state.simulated_genome.as_ref()
+            .is_some_and(|simulated| !simulated.appearance_equal(&genome.genome));
+        if looks_changed {
+            sim_state.needs_respawn = true;
+            preview_state.simulated_genome = Some(genome.genome.clone());
+        }
+    }
 
     if genome_changed {
+        if edit_due {
+            preview_state.last_edit_resimulation = now;
+            let tick = crate::simulation::clock::ticks_to_reach(preview_state.current_time, config.fixed_timestep);
+            timeline.push(crate::simulation::event_timeline::TimelineEvent::new(
+                tick,
+                crate::simulation::event_timeline::TimelineEventKind::GenomeEdit,
+                [None, None],
+            ));
+        }
+
         // Genome changed - clear checkpoints and trigger resimulation from current time
         preview_state.clear_checkpoints();
-        preview_state.genome_hash = current_genome_hash;
+        preview_state.simulated_genome = Some(genome.genome.clone());
+        preview_state.physics_timestep = config.fixed_timestep;
+        preview_state.physics_attractor = config.central_attractor();
         // DON'T reset time - keep current time and resimulate from there
         // preview_state.current_time = 0.0;  // REMOVED
 
         // Trigger resimulation from current time with new genome
         sim_state.target_time = Some(preview_state.current_time);
 
-        // Update initial state with new genome values
+        // Update initial state with new genome values (the founder keeps its placement)
+        let start = preview_state.start;
+        if seed_changed {
+            preview_state.initial_state.rng_seed = genome.genome.metadata.seed;
+        }
         if let Some(initial_cell) = preview_state.initial_state.initial_cells.first_mut() {
-            let initial_mode_index = genome.genome.initial_mode.max(0) as usize;
-            let mode = genome.genome.modes.get(initial_mode_index)
-                .or_else(|| genome.genome.modes.first());
-
-            if let Some(mode) = mode {
-                initial_cell.split_interval = mode.get_split_interval(0, 0, 0);
-                initial_cell.split_mass = mode.get_split_mass(0, 0, 0);
-                initial_cell.mode_index = initial_mode_index;
-                initial_cell.rotation = genome.genome.initial_orientation;
-            }
+            start.update_founder(initial_cell, &genome.genome);
         }
 
         // DON'T reset canonical state here - keep the old state visible until resimulation completes
@@ -536,11 +532,11 @@ fn run_preview_resimulation(
     // Determine best starting point using checkpoints
     let (start_time, start_step, mut canonical_state) = if target_time > preview_state.current_time && !genome_changed {
         // Moving forward: simulate from current state
-        let start_step = (preview_state.current_time / config.fixed_timestep).ceil() as u32;
+        let start_step = crate::simulation::clock::ticks_to_reach(preview_state.current_time, config.fixed_timestep);
         (preview_state.current_time, start_step, preview_state.canonical_state.clone())
     } else if let Some((checkpoint_time, checkpoint_state)) = preview_state.find_best_checkpoint(target_time) {
         // Moving backward: use nearest checkpoint
-        let start_step = (checkpoint_time / config.fixed_timestep).ceil() as u32;
+        let start_step = crate::simulation::clock::ticks_to_reach(checkpoint_time, config.fixed_timestep);
         (checkpoint_time, start_step, checkpoint_state)
     } else {
         // No suitable checkpoint: start from initial state
@@ -548,16 +544,29 @@ fn run_preview_resimulation(
         (0.0, 0, initial_canonical)
     };
     
-    let end_step = (target_time / config.fixed_timestep).ceil() as u32;
+    let end_step = crate::simulation::clock::ticks_to_reach(target_time, config.fixed_timestep);
     let steps = end_step.saturating_sub(start_step);
 
+    // Events after the starting tick belong to the timeline being replaced
+    timeline.begin_replay(start_step, config.fixed_timestep);
+
     // Clone other data needed for background task
     let config = config.clone();
     let genome_data = genome.genome.clone();
-    let max_cells = preview_state.initial_state.max_cells;
+    let capacity_growths = preview_state.capacity_growths.clone();
+    let pin_changes = preview_state.pin_changes.clone();
+    let user_data_changes = preview_state.user_data_changes.clone();
+    let surgery = preview_state.surgery.clone();
+    let stamps = preview_state.stamps.clone();
+    let drags = preview_state.drags.clone();
+    let settles = preview_state.settles.clone();
     let rng_seed = preview_state.initial_state.rng_seed;
     let fixed_timestep = config.fixed_timestep;
     let checkpoint_interval = preview_state.checkpoint_interval;
+    let breakpoint_list = breakpoints.list.clone();
+    let breakpoint_groups = breakpoints.groups.clone();
+    // Ticks up to the shown time were already passed, so replaying them doesn't stop again
+    let breakpoints_armed_after = crate::simulation::clock::ticks_to_reach(preview_state.current_time, fixed_timestep);
 
     // Spawn background task
     let task_pool = AsyncComputeTaskPool::get();
@@ -565,10 +574,35 @@ fn run_preview_resimulation(
         // Track checkpoints to create during simulation
         let mut new_checkpoints = Vec::new();
         let mut last_checkpoint_index = (start_time / checkpoint_interval).floor() as usize;
+        crate::simulation::capacity::apply_scheduled_growth(&mut canonical_state, &capacity_growths, start_step);
+        crate::simulation::pinning::apply_scheduled_pins(&mut canonical_state, &pin_changes, start_step);
+        crate::simulation::user_data::apply_scheduled_user_data(&mut canonical_state, &user_data_changes, start_step);
+        crate::simulation::organism_surgery::apply_scheduled_surgery(&mut canonical_state, &surgery, start_step, &genome_data);
+        crate::simulation::tissue_stamp::apply_scheduled_stamps(&mut canonical_state, &stamps, start_step, &genome_data, &config, rng_seed);
+        crate::simulation::preview_drag::apply_scheduled_drags(&mut canonical_state, &drags, start_step);
+        crate::simulation::settle::apply_scheduled_settles(&mut canonical_state, &settles, start_step, fixed_timestep);
+        let mut recorder = crate::simulation::event_timeline::TimelineRecorder::new(&canonical_state);
+        // Phase the replay starts in (a change at the very first tick was recorded by the run before)
+        let mut phases = crate::genome::PhasedGenome::default();
+        phases.update(&genome_data, canonical_state.division_stats.divisions, start_step as f32 * fixed_timestep);
+        let mut breakpoint_watch = crate::simulation::breakpoints::BreakpointWatch::new(&breakpoint_list, &breakpoint_groups, &canonical_state, &genome_data, start_time);
+        let mut breakpoint_hit = None;
+        let mut end_step = end_step;
         
         // Run physics steps in background thread with multithreading
         for step in 0..steps {
             let current_time = (start_step + step) as f32 * fixed_timestep;
+            crate::simulation::capacity::apply_scheduled_growth(&mut canonical_state, &capacity_growths, start_step + step);
+            crate::simulation::pinning::apply_scheduled_pins(&mut canonical_state, &pin_changes, start_step + step);
+            crate::simulation::user_data::apply_scheduled_user_data(&mut canonical_state, &user_data_changes, start_step + step);
+            crate::simulation::organism_surgery::apply_scheduled_surgery(&mut canonical_state, &surgery, start_step + step, &genome_data);
+            crate::simulation::tissue_stamp::apply_scheduled_stamps(&mut canonical_state, &stamps, start_step + step, &genome_data, &config, rng_seed);
+            crate::simulation::preview_drag::apply_scheduled_drags(&mut canonical_state, &drags, start_step + step);
+            crate::simulation::settle::apply_scheduled_settles(&mut canonical_state, &settles, start_step + step, fixed_timestep);
+            if phases.update(&genome_data, canonical_state.division_stats.divisions, current_time) {
+                recorder.events.push(crate::simulation::event_timeline::TimelineEvent::phase_change(start_step + step, phases.phase()));
+            }
+            let effective_genome = phases.genome(&genome_data);
 
             // Run CPU physics step (multithreaded via Rayon, swim disabled for preview)
             // Preview mode disables swim to keep flagellocytes from swimming away
@@ -581,22 +615,28 @@ fn run_preview_resimulation(
             // For GPU acceleration in preview, we would need to:
             // - Run resimulation synchronously on the main thread, OR
             // - Implement a command queue system to schedule GPU work from background threads
+            let step_config = crate::simulation::settle::settled_config(&canonical_state, &config);
             crate::simulation::cpu_physics::physics_step_with_genome(
                 &mut canonical_state,
-                &config,
-                &genome_data,
+                &step_config,
+                effective_genome,
                 current_time,
                 false, // Disable swim in preview mode
             );
+            canonical_state.advance_settle();
 
             // Run division step
-            crate::simulation::cpu_physics::division_step(
+            canonical_state.reanchor_genome_orientations = config.reanchor_genome_orientations;
+            let max_cells = canonical_state.capacity;
+            let divisions = crate::simulation::cpu_physics::division_step(
                 &mut canonical_state,
-                &genome_data,
+                effective_genome,
                 current_time,
+                fixed_timestep,
                 max_cells,
                 rng_seed,
             );
+            recorder.record_tick(start_step + step + 1, &canonical_state, &divisions);
             
             // Check if we should create a checkpoint
             let current_checkpoint_index = (current_time / checkpoint_interval).floor() as usize;
@@ -605,12 +645,34 @@ fn run_preview_resimulation(
                 new_checkpoints.push((current_time, canonical_state.clone()));
                 last_checkpoint_index = current_checkpoint_index;
             }
+
+            // A breakpoint ends the replay at the tick it fires
+            let tick = start_step + step + 1;
+            let hit = breakpoint_watch.as_mut()
+                .and_then(|watch| watch.check(&canonical_state, &genome_data, tick, tick as f32 * fixed_timestep))
+                .filter(|_| tick > breakpoints_armed_after);
+            if hit.is_some() {
+                breakpoint_hit = hit;
+                end_step = tick;
+                break;
+            }
         }
 
+        crate::simulation::capacity::apply_scheduled_growth(&mut canonical_state, &capacity_growths, end_step);
+        crate::simulation::pinning::apply_scheduled_pins(&mut canonical_state, &pin_changes, end_step);
+        crate::simulation::user_data::apply_scheduled_user_data(&mut canonical_state, &user_data_changes, end_step);
+        crate::simulation::organism_surgery::apply_scheduled_surgery(&mut canonical_state, &surgery, end_step, &genome_data);
+        crate::simulation::tissue_stamp::apply_scheduled_stamps(&mut canonical_state, &stamps, end_step, &genome_data, &config, rng_seed);
+        crate::simulation::preview_drag::apply_scheduled_drags(&mut canonical_state, &drags, end_step);
+        crate::simulation::settle::apply_scheduled_settles(&mut canonical_state, &settles, end_step, fixed_timestep);
+
         ResimulationResult {
             canonical_state,
-            target_time,
+            target_time: if breakpoint_hit.is_some() { end_step as f32 * fixed_timestep } else { target_time },
             new_checkpoints,
+            timeline_events: recorder.events,
+            lineage: recorder.lineage,
+            breakpoint_hit,
         }
     });
 
@@ -632,13 +694,10 @@ fn respawn_preview_cells_after_resimulation(
     mut sim_state: ResMut<crate::simulation::SimulationState>,
     genome: Res<CurrentGenome>,
     mut cells_query: Query<(Entity, &mut Cell, &mut CellPosition, &mut CellOrientation, &MeshMaterial3d<StandardMaterial>, &mut Mesh3d), With<PreviewSceneEntity>>,
-    drag_state: Res<crate::input::DragState>,
+    patterns: Res<crate::rendering::CellPatternTextures>,
+    sphere: Res<crate::rendering::CellSphereMesh>,
 ) {
-    // Don't respawn if currently dragging
-    if drag_state.dragged_entity.is_some() {
-        return;
-    }
-    
+    // Dragged cells are moved by physics (see preview_drag), so they respawn like any other
     // Only respawn if explicitly flagged
     if !sim_state.needs_respawn {
         return;
@@ -693,6 +752,7 @@ fn respawn_preview_cells_after_resimulation(
                         if let Some(material) = materials.get_mut(&material_handle.0) {
                             material.base_color = Color::srgba(color.x, color.y, color.z, opacity);
                             material.emissive = LinearRgba::rgb(color.x * emissive, color.y * emissive, color.z * emissive);
+                            patterns.apply(material, new_mode.and_then(|m| m.pattern));
                         }
                         
                         // Check if cell type changed (need to update mesh)
@@ -704,7 +764,7 @@ fn respawn_preview_cells_after_resimulation(
                             let new_mesh = if is_flagellocyte {
                                 meshes.add(crate::rendering::flagellocyte_mesh::generate_flagellocyte_mesh(1.0, swim_force, 5))
                             } else {
-                                meshes.add(Sphere::new(1.0).mesh().ico(5).unwrap())
+                                sphere.handle.clone()
                             };
                             
                             mesh_handle.0 = new_mesh;
@@ -722,7 +782,7 @@ fn respawn_preview_cells_after_resimulation(
         }
         
         // Spawn cells that don't have entities yet
-        let sphere_mesh = meshes.add(Sphere::new(1.0).mesh().ico(5).unwrap());
+        let sphere_mesh = sphere.handle.clone();
         let max_modes = genome.genome.modes.len();
         let mut material_cache: Vec<Option<Handle<StandardMaterial>>> = vec![None; max_modes];
         
@@ -760,7 +820,7 @@ fn respawn_preview_cells_after_resimulation(
                     } else {
                         (Vec3::ONE, 1.0, 0.0)
                     };
-                    let mat = materials.add(StandardMaterial {
+                    let mut material = StandardMaterial {
                         base_color: Color::srgba(color.x, color.y, color.z, opacity),
                         emissive: LinearRgba::rgb(color.x * emissive, color.y * emissive, color.z * emissive),
                         cull_mode: Some(bevy::render::render_resource::Face::Back),
@@ -770,7 +830,9 @@ fn respawn_preview_cells_after_resimulation(
                             bevy::prelude::AlphaMode::Opaque
                         },
                         ..default()
-                    });
+                    };
+                    patterns.apply(&mut material, mode.and_then(|m| m.pattern));
+                    let mat = materials.add(material);
                     material_cache[mode_index] = Some(mat.clone());
                     mat
                 }
@@ -838,14 +900,8 @@ fn respawn_preview_cells_after_resimulation(
 fn sync_preview_visuals(
     preview_state: Res<PreviewSimState>,
     mut cells_query: Query<(Entity, &mut Cell, &mut CellPosition, &mut CellOrientation), (With<Cell>, With<PreviewSceneEntity>)>,
-    drag_state: Res<crate::input::DragState>,
     sim_state: Res<crate::simulation::SimulationState>,
 ) {
-    // Skip sync entirely if currently dragging a cell
-    if drag_state.dragged_entity.is_some() {
-        return;
-    }
-    
     // Skip if we're about to respawn (cell count changed)
     if sim_state.needs_respawn {
         return;
@@ -854,11 +910,6 @@ fn sync_preview_visuals(
     // Sync all cell data from canonical state to existing entities
     for i in 0..preview_state.canonical_state.cell_count {
         if let Some(entity) = preview_state.index_to_entity[i] {
-            // Skip the dragged entity
-            if Some(entity) == drag_state.dragged_entity {
-                continue;
-            }
-            
             // Update entity components directly using index
             if let Ok((_, mut cell, mut cell_pos, mut cell_orientation)) = cells_query.get_mut(entity) {
                 // Update all cell data
@@ -895,11 +946,14 @@ fn spawn_preview_skybox(
 
 
 
-/// Highlight cells of the selected mode with a pulsing yellow emissive glow
+/// Highlight cells of the selected mode with a pulsing yellow emissive glow,
+/// and apply the legend's isolate/hide filters and the isolated cell group
 fn highlight_selected_mode_cells(
     time: Res<Time>,
     genome: Res<CurrentGenome>,
-    cells_query: Query<(&Cell, &MeshMaterial3d<StandardMaterial>), With<PreviewSceneEntity>>,
+    mode_visibility: Res<crate::rendering::ModeVisibility>,
+    preview_state: Res<PreviewSimState>,
+    mut cells_query: Query<(Entity, &Cell, &MeshMaterial3d<StandardMaterial>, &mut Visibility), With<PreviewSceneEntity>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let selected_mode = genome.selected_mode_index as usize;
@@ -917,18 +971,43 @@ fn highlight_selected_mode_cells(
         highlight_intensity * 0.1,
     );
     
-    for (cell, material_handle) in cells_query.iter() {
+    // Group isolation is by cell ID, so map entities back to their cells (only while a group is isolated)
+    let state = &preview_state.canonical_state;
+    let cell_ids: std::collections::HashMap<Entity, u32> = match mode_visibility.isolated_cells {
+        Some(_) => preview_state.index_to_entity.iter()
+            .take(state.cell_count)
+            .enumerate()
+            .filter_map(|(index, entity)| entity.map(|entity| (entity, state.cell_ids[index])))
+            .collect(),
+        None => std::collections::HashMap::new(),
+    };
+
+    for (entity, cell, material_handle, mut visibility) in cells_query.iter_mut() {
+        let multiplier = match cell_ids.get(&entity) {
+            Some(&cell_id) => mode_visibility.cell_multiplier(cell.mode_index, cell_id),
+            None => mode_visibility.multiplier(cell.mode_index),
+        };
+        visibility.set_if_neq(if multiplier > 0.0 { Visibility::Inherited } else { Visibility::Hidden });
+        
         if let Some(material) = materials.get_mut(&material_handle.0) {
+            let mode = genome.genome.modes.get(cell.mode_index);
+            let (color, base_emissive, opacity) = if let Some(mode) = mode {
+                (mode.color, mode.emissive, mode.opacity)
+            } else {
+                (Vec3::ONE, 0.0, 1.0)
+            };
+            
+            // Isolated-out modes are dimmed (hidden modes keep their normal material)
+            let opacity = if multiplier > 0.0 { opacity * multiplier } else { opacity };
+            material.base_color.set_alpha(opacity);
+            material.alpha_mode = if opacity < 0.99 {
+                bevy::prelude::AlphaMode::AlphaToCoverage
+            } else {
+                bevy::prelude::AlphaMode::Opaque
+            };
+            
             if glow_enabled && cell.mode_index == selected_mode {
-                // Apply pulsing yellow highlight
-                let mode = genome.genome.modes.get(cell.mode_index);
-                let (color, base_emissive) = if let Some(mode) = mode {
-                    (mode.color, mode.emissive)
-                } else {
-                    (Vec3::ONE, 0.0)
-                };
-                
-                // Combine base emissive with highlight
+                // Combine base emissive with the pulsing yellow highlight
                 material.emissive = LinearRgba::rgb(
                     color.x * base_emissive + highlight_color.red,
                     color.y * base_emissive + highlight_color.green,
@@ -936,16 +1015,10 @@ fn highlight_selected_mode_cells(
                 );
             } else {
                 // Reset to normal emissive for non-selected modes (or when glow disabled)
-                let mode = genome.genome.modes.get(cell.mode_index);
-                let (color, emissive) = if let Some(mode) = mode {
-                    (mode.color, mode.emissive)
-                } else {
-                    (Vec3::ONE, 0.0)
-                };
                 material.emissive = LinearRgba::rgb(
-                    color.x * emissive,
-                    color.y * emissive,
-                    color.z * emissive,
+                    color.x * base_emissive,
+                    color.y * base_emissive,
+                    color.z * base_emissive,
                 );
             }
         }
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::simulation::CanonicalState;
+
+/// Problem bonds kept before the oldest are dropped
+pub const PROBLEM_BOND_CAPACITY: usize = 256;
+
+/// Ticks a problem bond stays highlighted in the adhesion lines
+pub const PROBLEM_BOND_HIGHLIGHT_TICKS: u32 = 64;
+
+/// Plugin for the log of adhesions whose forces hit the limit in the main simulation
+pub struct ProblemBondsPlugin;
+
+impl Plugin for ProblemBondsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProblemBonds>();
+    }
+}
+
+/// An adhesion whose force was clamped (or non-finite) on one tick
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProblemBond {
+    pub tick: u32,
+    /// Connection index at that tick (indices shift as bonds break)
+    pub connection: usize,
+    pub cell_a_id: u32,
+    pub cell_b_id: u32,
+    /// Force magnitude before clamping; non-finite when the forces were
+    pub magnitude: f32,
+}
+
+/// Most recent problem bonds of the main simulation (oldest first)
+#[derive(Resource)]
+pub struct ProblemBonds {
+    pub events: VecDeque<ProblemBond>,
+    /// Problem bonds seen since the last clear, including dropped ones
+    pub total: u64,
+    /// Tick of the last recorded force pass
+    pub last_tick: u32,
+    /// Draw problem bonds in the warning color in the adhesion lines
+    pub highlight: bool,
+}
+
+impl Default for ProblemBonds {
+    fn default() -> Self {
+        Self {
+            events: VecDeque::with_capacity(PROBLEM_BOND_CAPACITY),
+            total: 0,
+            last_tick: 0,
+            highlight: true,
+        }
+    }
+}
+
+impl ProblemBonds {
+    /// Take the force limit hits of the tick that just ran
+    pub fn record(&mut self, state: &CanonicalState, tick: u32) {
+        self.last_tick = tick;
+        let connections = &state.adhesion_connections;
+        for &(connection, magnitude) in &connections.force_limit_hits {
+            let (a, b) = (connections.cell_a_index[connection], connections.cell_b_index[connection]);
+            if self.events.len() == PROBLEM_BOND_CAPACITY {
+                self.events.pop_front();
+            }
+            self.events.push_back(ProblemBond {
+                tick,
+                connection,
+                cell_a_id: state.cell_ids[a],
+                cell_b_id: state.cell_ids[b],
+                magnitude,
+            });
+            self.total += 1;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.total = 0;
+    }
+
+    /// Whether connection `i` (between cells `cell_a_id` and `cell_b_id`) hit the limit recently
+    pub fn is_recent(&self, i: usize, cell_a_id: u32, cell_b_id: u32) -> bool {
+        self.events
+            .iter()
+            .rev()
+            .take_while(|event| event.tick + PROBLEM_BOND_HIGHLIGHT_TICKS >= self.last_tick)
+            .any(|event| event.connection == i && event.cell_a_id == cell_a_id && event.cell_b_id == cell_b_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::PhysicsConfig;
+
+    #[test]
+    fn test_overstretched_bond_is_recorded_with_its_cells() {
+        let genome = never_split_genome(&["Stretched"]);
+        let rest_length = genome.modes[0].adhesion_settings.rest_length;
+        let config = PhysicsConfig { adhesion_max_force: 50.0, ..PhysicsConfig::default() };
+
+        let mut state = CanonicalState::new(4);
+        for x in [0.0, rest_length * 10.0] {
+            add_test_cell(&mut state, Vec3::X * x, Quat::IDENTITY, 1.0, 0.5, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+
+        crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, 0.0, false);
+        let mut bonds = ProblemBonds::default();
+        bonds.record(&state, 1);
+
+        assert_eq!(bonds.events.len(), 1);
+        let event = bonds.events[0];
+        assert_eq!((event.tick, event.connection), (1, 0));
+        assert_eq!((event.cell_a_id, event.cell_b_id), (state.cell_ids[0], state.cell_ids[1]));
+        assert!(event.magnitude > config.adhesion_max_force);
+        assert!(bonds.is_recent(0, event.cell_a_id, event.cell_b_id));
+
+        bonds.last_tick = 1 + PROBLEM_BOND_HIGHLIGHT_TICKS + 1;
+        assert!(!bonds.is_recent(0, event.cell_a_id, event.cell_b_id));
+    }
+
+    #[test]
+    fn test_ring_buffer_drops_oldest() {
+        let mut state = CanonicalState::new(2);
+        for _ in 0..2 {
+            state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 0.5,
+                0, 0, 0.0, 1.0, 1.0, 10.0, Quat::IDENTITY, 0);
+        }
+        state.adhesion_connections.cell_b_index[0] = 1;
+        state.adhesion_connections.force_limit_hits.push((0, f32::NAN));
+
+        let mut bonds = ProblemBonds::default();
+        for tick in 0..PROBLEM_BOND_CAPACITY as u32 + 10 {
+            bonds.record(&state, tick);
+        }
+        assert_eq!(bonds.events.len(), PROBLEM_BOND_CAPACITY);
+        assert_eq!(bonds.events[0].tick, 10);
+        assert_eq!(bonds.total, PROBLEM_BOND_CAPACITY as u64 + 10);
+    }
+}
@@ -0,0 +1,451 @@
+//! Platform-independent math for strict reproducibility
+//!
+//! Basic float operations (+, -, *, /, sqrt) are correctly rounded by IEEE 754, and
+//! Rust never fuses them into FMAs on its own, so they give the same bits on every
+//! CPU. What differs between machines is everything else the physics step leans on:
+//! the platform libm (`powf`, `acos`, `atan2`, `sin`/`cos`) and glam's SIMD
+//! quaternion paths, whose operation order depends on the target features.
+//!
+//! With `PhysicsConfig::strict_reproducibility` the CPU physics step routes those
+//! through this module instead ([`PhysicsMath::Reproducible`]):
+//!
+//! - `powf` (velocity and angular damping factors): `exp(y · ln x)` in f64
+//! - `acos` (boundary torque) and `atan2` (adhesion anchor and twist angles):
+//!   argument reduction plus a Taylor series in f64
+//! - `cos` (adhesion wake angle) and `sin`/`cos` of quaternions built from an axis
+//!   and angle (rotation integration): reduction by π/2 plus Taylor series in f64
+//! - quaternion products, normalization and vector rotation (rotation integration,
+//!   boundary and swim directions, ellipsoid inertia, adhesion twist): written out
+//!   component by component in a fixed order
+//!
+//! Every series uses only basic f64 operations in a fixed order and is rounded to
+//! f32 once at the end, so results are the same on x86_64 and ARM whatever
+//! `-C target-feature` says. Collision, adhesion spring, nutrient and chemical
+//! field math already uses nothing but scalar `Vec3` arithmetic and `sqrt`, and
+//! forces are accumulated sequentially in pair order, so they need no changes.
+//!
+//! This is noticeably slower than the native path and its results differ from it
+//! in the last bits, so runs with and without the flag have different fingerprints.
+
+use bevy::prelude::*;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// Which implementation the physics step uses for transcendental and quaternion math
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhysicsMath {
+    /// libm and glam (fastest, bit-identical only on the same platform and build)
+    #[default]
+    Native,
+    /// This module's software implementations (slower, bit-identical everywhere)
+    Reproducible,
+}
+
+impl PhysicsMath {
+    #[inline]
+    pub fn powf(self, base: f32, exponent: f32) -> f32 {
+        match self {
+            PhysicsMath::Native => base.powf(exponent),
+            PhysicsMath::Reproducible => powf(base, exponent),
+        }
+    }
+
+    #[inline]
+    pub fn cos(self, x: f32) -> f32 {
+        match self {
+            PhysicsMath::Native => x.cos(),
+            PhysicsMath::Reproducible => sin_cos(x).1,
+        }
+    }
+
+    #[inline]
+    pub fn acos(self, x: f32) -> f32 {
+        match self {
+            PhysicsMath::Native => x.acos(),
+            PhysicsMath::Reproducible => acos(x),
+        }
+    }
+
+    #[inline]
+    pub fn atan2(self, y: f32, x: f32) -> f32 {
+        match self {
+            PhysicsMath::Native => y.atan2(x),
+            PhysicsMath::Reproducible => atan2(y, x),
+        }
+    }
+
+    #[inline]
+    pub fn quat_mul(self, a: Quat, b: Quat) -> Quat {
+        match self {
+            PhysicsMath::Native => a * b,
+            PhysicsMath::Reproducible => quat_mul(a, b),
+        }
+    }
+
+    #[inline]
+    pub fn quat_length(self, q: Quat) -> f32 {
+        match self {
+            PhysicsMath::Native => q.length(),
+            PhysicsMath::Reproducible => quat_length(q),
+        }
+    }
+
+    #[inline]
+    pub fn quat_normalize(self, q: Quat) -> Quat {
+        match self {
+            PhysicsMath::Native => q.normalize(),
+            PhysicsMath::Reproducible => quat_normalize(q),
+        }
+    }
+
+    /// `q * v`
+    #[inline]
+    pub fn rotate(self, q: Quat, v: Vec3) -> Vec3 {
+        match self {
+            PhysicsMath::Native => q * v,
+            PhysicsMath::Reproducible => rotate(q, v),
+        }
+    }
+
+    #[inline]
+    pub fn quat_from_axis_angle(self, axis: Vec3, angle: f32) -> Quat {
+        match self {
+            PhysicsMath::Native => Quat::from_axis_angle(axis, angle),
+            PhysicsMath::Reproducible => quat_from_axis_angle(axis, angle),
+        }
+    }
+}
+
+/// π/2 split so `k · PIO2_HI` is exact for |k| < 2^20 (fdlibm's pio2_1 and pio2_1t)
+const PIO2_HI: f64 = 1.570_796_326_734_125_614_17e0;
+const PIO2_LO: f64 = 6.077_100_506_506_192_249_32e-11;
+
+/// ln 2 split so `k · LN2_HI` is exact for every exponent (fdlibm's ln2_hi and ln2_lo)
+const LN2_HI: f64 = 6.931_471_803_691_238_164_90e-1;
+const LN2_LO: f64 = 1.908_214_929_270_587_700_02e-10;
+
+/// tan(π/8): atan arguments above it are shifted by π/4
+const TAN_PI_8: f64 = 0.414_213_562_373_095_03;
+
+/// Taylor coefficients of sin(r)/r - 1 and cos(r) - 1 in powers of r², from r² up
+const SIN_COEFFS: [f64; 7] = [
+    -1.0 / 6.0,
+    1.0 / 120.0,
+    -1.0 / 5_040.0,
+    1.0 / 362_880.0,
+    -1.0 / 39_916_800.0,
+    1.0 / 6_227_020_800.0,
+    -1.0 / 1_307_674_368_000.0,
+];
+const COS_COEFFS: [f64; 8] = [
+    -1.0 / 2.0,
+    1.0 / 24.0,
+    -1.0 / 720.0,
+    1.0 / 40_320.0,
+    -1.0 / 3_628_800.0,
+    1.0 / 479_001_600.0,
+    -1.0 / 87_178_291_200.0,
+    1.0 / 20_922_789_888_000.0,
+];
+
+/// Terms of the atan series (|u| ≤ tan(π/8) leaves < 1e-17 after these)
+const ATAN_TERMS: u32 = 22;
+
+/// Terms of the atanh series used by ln (|s| ≤ 0.172 leaves < 1e-17 after these)
+const LN_TERMS: u32 = 12;
+
+/// Terms of the exp series (|r| ≤ ln 2 / 2 leaves < 1e-18 after these)
+const EXP_TERMS: u32 = 16;
+
+/// `c[0] + x·(c[1] + x·(c[2] + …))`, evaluated innermost first
+#[inline]
+fn horner(x: f64, coeffs: &[f64]) -> f64 {
+    coeffs.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+/// (sin r, cos r) for |r| ≤ π/4
+fn sin_cos_reduced(r: f64) -> (f64, f64) {
+    let r2 = r * r;
+    (r + r * r2 * horner(r2, &SIN_COEFFS), 1.0 + r2 * horner(r2, &COS_COEFFS))
+}
+
+/// (sin x, cos x)
+pub fn sin_cos(x: f32) -> (f32, f32) {
+    if !x.is_finite() {
+        return (f32::NAN, f32::NAN);
+    }
+    let x = x as f64;
+    let k = (x / FRAC_PI_2).round();
+    let r = (x - k * PIO2_HI) - k * PIO2_LO;
+    let (s, c) = sin_cos_reduced(r);
+    let (sin, cos) = match (k as i64).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+    (sin as f32, cos as f32)
+}
+
+/// atan(t) in f64
+fn atan_f64(t: f64) -> f64 {
+    if t.is_nan() {
+        return t;
+    }
+    let (sign, mut a) = if t.is_sign_negative() { (-1.0, -t) } else { (1.0, t) };
+    // atan(a) = π/2 - atan(1/a)
+    let mut offset = 0.0;
+    if a > 1.0 {
+        offset = FRAC_PI_2;
+        a = -1.0 / a;
+    }
+    // atan(a) = ±π/4 + atan((a ∓ 1) / (1 ± a)), bringing |a| under tan(π/8)
+    if a > TAN_PI_8 {
+        offset += FRAC_PI_4;
+        a = (a - 1.0) / (1.0 + a);
+    } else if a < -TAN_PI_8 {
+        offset -= FRAC_PI_4;
+        a = (a + 1.0) / (1.0 - a);
+    }
+    // a - a³/3 + a⁵/5 - …
+    let a2 = a * a;
+    let mut series = 0.0;
+    for n in (1..=ATAN_TERMS).rev() {
+        let term_sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+        series = series * a2 + term_sign / (2 * n + 1) as f64;
+    }
+    sign * (offset + (a + a * a2 * series))
+}
+
+/// atan2(y, x) in f64, with the quadrant and signed-zero conventions of `f64::atan2`
+fn atan2_f64(y: f64, x: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return f64::NAN;
+    }
+    if y == 0.0 {
+        return if x.is_sign_negative() { PI.copysign(y) } else { y };
+    }
+    if x == 0.0 {
+        return FRAC_PI_2.copysign(y);
+    }
+    if x.is_infinite() && y.is_infinite() {
+        let angle = if x > 0.0 { FRAC_PI_4 } else { 3.0 * FRAC_PI_4 };
+        return angle.copysign(y);
+    }
+    let base = atan_f64(y / x);
+    if x > 0.0 {
+        base
+    } else {
+        base + PI.copysign(y)
+    }
+}
+
+pub fn atan2(y: f32, x: f32) -> f32 {
+    atan2_f64(y as f64, x as f64) as f32
+}
+
+/// acos(x) (NaN outside -1..=1)
+pub fn acos(x: f32) -> f32 {
+    let x = x as f64;
+    if !(-1.0..=1.0).contains(&x) {
+        return f32::NAN;
+    }
+    atan2_f64(((1.0 - x) * (1.0 + x)).sqrt(), x) as f32
+}
+
+/// `v · 2^k` for any k, in at most two exact-or-once-rounded multiplies
+fn scale_by_pow2(v: f64, k: i64) -> f64 {
+    let pow2 = |e: i64| f64::from_bits(((e + 1023) as u64) << 52);
+    if k > 1023 {
+        v * pow2(1023) * pow2((k - 1023).min(1023))
+    } else if k < -1022 {
+        v * pow2(-1022) * pow2((k + 1022).max(-1022))
+    } else {
+        v * pow2(k)
+    }
+}
+
+/// e^x in f64
+fn exp_f64(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.8 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.0;
+    }
+    let k = (x / std::f64::consts::LN_2).round();
+    let r = (x - k * LN2_HI) - k * LN2_LO;
+    // 1 + r + r²/2! + …, innermost (highest power) first
+    let mut series = 0.0;
+    for n in (1..=EXP_TERMS).rev() {
+        series = 1.0 + series * r / n as f64;
+    }
+    scale_by_pow2(series, k as i64)
+}
+
+/// ln(x) in f64
+fn ln_f64(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if x.is_infinite() {
+        return x;
+    }
+    // x = m · 2^e with m in [√½, √2]
+    let (bits, mut exponent) = if x < f64::MIN_POSITIVE {
+        (scale_by_pow2(x, 54).to_bits(), -54)
+    } else {
+        (x.to_bits(), 0)
+    };
+    exponent += ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mut m = f64::from_bits((bits & ((1u64 << 52) - 1)) | (1023u64 << 52));
+    if m > std::f64::consts::SQRT_2 {
+        m *= 0.5;
+        exponent += 1;
+    }
+    // ln m = 2·atanh(s) = 2·(s + s³/3 + s⁵/5 + …) with s = (m - 1) / (m + 1)
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut series = 0.0;
+    for n in (0..LN_TERMS).rev() {
+        series = series * s2 + 1.0 / (2 * n + 1) as f64;
+    }
+    let e = exponent as f64;
+    e * LN2_HI + (2.0 * s * series + e * LN2_LO)
+}
+
+/// base^exponent (negative bases only for integral exponents, like `f32::powf`)
+pub fn powf(base: f32, exponent: f32) -> f32 {
+    let (b, e) = (base as f64, exponent as f64);
+    if e == 0.0 || b == 1.0 {
+        return 1.0;
+    }
+    if b.is_nan() || e.is_nan() {
+        return f32::NAN;
+    }
+    if b == 0.0 {
+        return if e > 0.0 { 0.0 } else { f32::INFINITY };
+    }
+    if b < 0.0 {
+        if e.trunc() != e {
+            return f32::NAN;
+        }
+        let magnitude = exp_f64(e * ln_f64(-b));
+        let odd = e.abs() < 9_007_199_254_740_992.0 && (e % 2.0) != 0.0;
+        return (if odd { -magnitude } else { magnitude }) as f32;
+    }
+    exp_f64(e * ln_f64(b)) as f32
+}
+
+/// Hamilton product `a * b`
+pub fn quat_mul(a: Quat, b: Quat) -> Quat {
+    Quat::from_xyzw(
+        a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+    )
+}
+
+pub fn quat_length(q: Quat) -> f32 {
+    (q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w).sqrt()
+}
+
+/// `q` scaled to unit length (unchanged if its length is zero or not finite)
+pub fn quat_normalize(q: Quat) -> Quat {
+    let length = quat_length(q);
+    if length > 0.0 && length.is_finite() {
+        Quat::from_xyzw(q.x / length, q.y / length, q.z / length, q.w / length)
+    } else {
+        q
+    }
+}
+
+/// `q * v` for a unit quaternion: `2(u·v)u + (w² - u·u)v + 2w(u × v)`
+pub fn rotate(q: Quat, v: Vec3) -> Vec3 {
+    let (ux, uy, uz, w) = (q.x, q.y, q.z, q.w);
+    let u_dot_v = ux * v.x + uy * v.y + uz * v.z;
+    let u_dot_u = ux * ux + uy * uy + uz * uz;
+    let a = 2.0 * u_dot_v;
+    let b = w * w - u_dot_u;
+    let c = 2.0 * w;
+    Vec3::new(
+        ux * a + v.x * b + (uy * v.z - uz * v.y) * c,
+        uy * a + v.y * b + (uz * v.x - ux * v.z) * c,
+        uz * a + v.z * b + (ux * v.y - uy * v.x) * c,
+    )
+}
+
+/// Rotation of `angle` radians about the unit vector `axis`
+pub fn quat_from_axis_angle(axis: Vec3, angle: f32) -> Quat {
+    let (s, c) = sin_cos(angle * 0.5);
+    Quat::from_xyzw(axis.x * s, axis.y * s, axis.z * s, c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evenly spaced samples of `range`, endpoints included
+    fn samples(range: std::ops::RangeInclusive<f32>, count: usize) -> impl Iterator<Item = f32> {
+        let (start, end) = (*range.start(), *range.end());
+        (0..count).map(move |i| start + (end - start) * i as f32 / (count - 1) as f32)
+    }
+
+    #[test]
+    fn test_transcendentals_match_libm_closely() {
+        for x in samples(-50.0..=50.0, 4001) {
+            let (sin, cos) = sin_cos(x);
+            assert!((sin - x.sin()).abs() <= 2e-6 && (cos - x.cos()).abs() <= 2e-6, "sin_cos({})", x);
+        }
+        for x in samples(-1.0..=1.0, 2001) {
+            assert!((acos(x) - x.acos()).abs() <= 2e-6, "acos({})", x);
+        }
+        for y in samples(-3.0..=3.0, 61) {
+            for x in samples(-3.0..=3.0, 61) {
+                assert!((atan2(y, x) - y.atan2(x)).abs() <= 2e-6, "atan2({}, {})", y, x);
+            }
+        }
+        for base in samples(0.01..=4.0, 400) {
+            for exponent in [-3.0, -0.5, 0.75, 1.5625, 2.0, 7.3] {
+                let expected = base.powf(exponent);
+                assert!((powf(base, exponent) - expected).abs() <= expected.abs() * 1e-6, "powf({}, {})", base, exponent);
+            }
+        }
+    }
+
+    #[test]
+    fn test_special_values_follow_std() {
+        assert_eq!(sin_cos(0.0), (0.0, 1.0));
+        assert!(sin_cos(f32::INFINITY).0.is_nan());
+        assert_eq!(acos(1.0), 0.0);
+        assert!(acos(1.5).is_nan());
+        for (y, x) in [(0.0, 0.0), (-0.0, 0.0), (0.0, -0.0), (-0.0, -0.0), (1.0, 0.0), (-1.0, -0.0),
+            (1.0, f32::INFINITY), (1.0, f32::NEG_INFINITY), (f32::INFINITY, f32::NEG_INFINITY)]
+        {
+            let (ours, std) = (atan2(y, x), y.atan2(x));
+            assert!((ours - std).abs() <= 1e-6 && ours.is_sign_negative() == std.is_sign_negative(), "atan2({}, {})", y, x);
+        }
+        assert_eq!(powf(0.98, 0.0), 1.0);
+        assert_eq!(powf(0.0, 2.0), 0.0);
+        assert_eq!(powf(-2.0, 3.0), -8.0);
+        assert!(powf(-2.0, 0.5).is_nan());
+    }
+
+    #[test]
+    fn test_quaternion_helpers_match_glam() {
+        let a = Quat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.0);
+        let b = Quat::from_euler(EulerRot::XYZ, -0.7, 0.4, 0.1);
+        let v = Vec3::new(0.3, -2.0, 1.5);
+        assert!(quat_mul(a, b).abs_diff_eq(a * b, 1e-6));
+        assert!(rotate(a, v).abs_diff_eq(a * v, 1e-5));
+        assert!(quat_normalize(a * 3.0).abs_diff_eq(a, 1e-6));
+        let axis = Vec3::new(1.0, 2.0, -0.5).normalize();
+        assert!(quat_from_axis_angle(axis, 1.3).abs_diff_eq(Quat::from_axis_angle(axis, 1.3), 1e-6));
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::genome::CurrentGenome;
+use crate::simulation::scene_file::SceneFile;
+use crate::simulation::PhysicsConfig;
+use crate::ui::camera::MainCamera;
+
+/// Preset manifest (assets/presets/manifest.json)
+const PRESET_MANIFEST: &str = include_str!("../../assets/presets/manifest.json");
+
+/// Bundled preset scene files, keyed by the file name used in the manifest
+const PRESET_FILES: &[(&str, &str)] = &[
+    ("branching_colony.json", include_str!("../../assets/presets/branching_colony.json")),
+    ("flagellocyte_school.json", include_str!("../../assets/presets/flagellocyte_school.json")),
+    ("nutrient_stratification.json", include_str!("../../assets/presets/nutrient_stratification.json")),
+    ("adhesion_pendulum.json", include_str!("../../assets/presets/adhesion_pendulum.json")),
+    ("mitosis_basics.json", include_str!("../../assets/presets/mitosis_basics.json")),
+    ("twin_colonies.json", include_str!("../../assets/presets/twin_colonies.json")),
+    ("chemotaxis.json", include_str!("../../assets/presets/chemotaxis.json")),
+];
+
+#[derive(Deserialize)]
+struct PresetManifest {
+    presets: Vec<PresetManifestEntry>,
+}
+
+#[derive(Deserialize)]
+struct PresetManifestEntry {
+    id: String,
+    title: String,
+    file: String,
+}
+
+/// A bundled example scenario
+#[derive(Clone)]
+pub struct ScenarioPreset {
+    pub id: String,
+    pub title: String,
+    pub scene: SceneFile,
+}
+
+/// All bundled presets, parsed once at startup
+#[derive(Resource, Default)]
+pub struct ScenarioPresets {
+    pub presets: Vec<ScenarioPreset>,
+}
+
+/// Scenario that was loaded most recently (shown in the Scene Manager)
+#[derive(Resource, Default)]
+pub struct LoadedScenario {
+    pub title: Option<String>,
+    /// File the scene was loaded from. Always None for bundled presets so saving
+    /// has to go through a Save As dialog instead of overwriting the bundled asset.
+    pub source_path: Option<std::path::PathBuf>,
+    /// True until the user saves the scene somewhere
+    pub unsaved: bool,
+}
+
+/// Scene waiting to be applied to the preview simulation once it is active
+#[derive(Resource, Default)]
+pub struct PendingScenario {
+    pub scene: Option<SceneFile>,
+}
+
+/// Parse the manifest and every preset it lists
+/// Returns (id, result) so callers can report which preset failed
+pub fn load_bundled_presets() -> Vec<(String, Result<ScenarioPreset, String>)> {
+    let manifest: PresetManifest = match serde_json::from_str(PRESET_MANIFEST) {
+        Ok(manifest) => manifest,
+        Err(e) => return vec![("manifest".to_string(), Err(format!("Invalid preset manifest: {}", e)))],
+    };
+
+    manifest.presets.into_iter().map(|entry| {
+        let result = PRESET_FILES.iter()
+            .find(|(file, _)| *file == entry.file)
+            .ok_or_else(|| format!("Preset file {} is not bundled", entry.file))
+            .and_then(|(_, json)| SceneFile::from_json_str(json).map_err(|e| e.to_string()))
+            .and_then(|scene| {
+                let errors = scene.validate();
+                if errors.is_empty() {
+                    Ok(scene)
+                } else {
+                    Err(errors.join("; "))
+                }
+            })
+            .map(|scene| ScenarioPreset {
+                id: entry.id.clone(),
+                title: entry.title,
+                scene,
+            });
+        (entry.id, result)
+    }).collect()
+}
+
+/// Plugin that bundles example scenarios and loads them on request
+pub struct ScenarioPresetPlugin;
+
+impl Plugin for ScenarioPresetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioPresets>()
+            .init_resource::<LoadedScenario>()
+            .init_resource::<PendingScenario>()
+            .add_systems(Startup, register_bundled_presets)
+            .add_systems(Update, load_requested_preset);
+    }
+}
+
+fn register_bundled_presets(mut presets: ResMut<ScenarioPresets>) {
+    for (id, result) in load_bundled_presets() {
+        match result {
+            Ok(preset) => presets.presets.push(preset),
+            Err(e) => warn!("Skipping bundled preset '{}': {}", id, e),
+        }
+    }
+}
+
+/// Load the preset, scene or orbital spawn requested from the Scene Manager
+///
+/// Genome, physics config and camera are applied immediately; the initial layout is
+/// handed to the preview simulation through `PendingScenario`.
+fn load_requested_preset(
+    mut scene_request: ResMut<crate::ui::windows::scene_manager::SceneModeRequest>,
+    presets: Res<ScenarioPresets>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut physics_config: ResMut<PhysicsConfig>,
+    mut physics_layers: ResMut<crate::simulation::PhysicsLayers>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut loaded: ResMut<LoadedScenario>,
+    mut pending: ResMut<PendingScenario>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
+    mut groups: ResMut<crate::simulation::CellGroups>,
+    mut regions: ResMut<crate::rendering::RegionVisibility>,
+    mut camera_query: Query<&mut MainCamera>,
+) {
+    let (title, scene) = if let Some(preset_id) = scene_request.requested_preset.take() {
+        let Some(preset) = presets.presets.iter().find(|p| p.id == preset_id) else {
+            warn!("Unknown preset '{}'", preset_id);
+            return;
+        };
+        info!("Loading preset '{}'", preset.title);
+        (preset.title.clone(), preset.scene.clone())
+    } else if let Some((title, scene)) = scene_request.requested_scene.take() {
+        info!("Loading scene '{}'", title);
+        (title, scene)
+    } else if std::mem::take(&mut scene_request.requested_orbital_spawn) {
+        let mut scene = scene_request.orbital_spawn.scene(&current_genome.genome, &physics_config);
+        // Spawned into the current physics, so only what the spawn changed joins the scene layer
+        scene.physics_overrides = Some(physics_layers.scene_layer_for(&scene.physics));
+        info!("Spawning orbital preset: {}", scene.description);
+        ("Orbital Spawn".to_string(), scene)
+    } else {
+        return;
+    };
+
+    // Kept scenes were simulated with the genome being replaced; ask first
+    if !dormant.is_empty() {
+        dormant.deferred_load = Some(crate::simulation::dormant_scenes::DeferredLoad::Scene { title, scene });
+        return;
+    }
+
+    current_genome.replace(scene.genome.clone(), scene.genome.initial_mode_index() as i32, None);
+    let scene_layer = scene.physics_layer();
+    let changed = scene_layer.differing_from(&physics_config);
+    if !changed.is_empty() {
+        notifications.warning(format!("'{}' overrides physics settings: {}", title, changed.join(", ")));
+    }
+    physics_layers.scene = scene_layer;
+    *physics_config = physics_layers.resolve();
+    groups.replace(scene.groups.clone());
+    regions.apply_scene(scene.regions.clone());
+
+    if let Some(pose) = scene.camera {
+        for mut camera in camera_query.iter_mut() {
+            camera.center = pose.center;
+            camera.distance = pose.distance;
+            camera.target_distance = pose.distance;
+            camera.rotation = pose.rotation;
+            camera.target_rotation = pose.rotation;
+            camera.followed_entity = None;
+        }
+    }
+
+    *loaded = LoadedScenario {
+        title: Some(title),
+        source_path: None,
+        unsaved: true,
+    };
+    pending.scene = Some(scene);
+    scene_request.requested_mode = Some(crate::simulation::SimulationMode::Preview);
+    // Don't keep the CPU scene this switches away from; it ran the old genome
+    scene_request.reset_requested = Some(crate::simulation::SimulationMode::Cpu);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_presets_load_without_errors() {
+        let presets = load_bundled_presets();
+        assert_eq!(presets.len(), PRESET_FILES.len());
+        for (id, result) in presets {
+            if let Err(e) = result {
+                panic!("Preset '{}' failed to load: {}", id, e);
+            }
+        }
+    }
+}
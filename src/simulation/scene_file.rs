@@ -0,0 +1,353 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::genome::GenomeData;
+use crate::simulation::cell_groups::{CellGroup, GroupKind};
+use crate::simulation::genome_staging::StagedApplication;
+use crate::simulation::{InitialCell, InitialState, PhysicsConfig, PhysicsOverrides};
+
+/// Current scene file format version
+pub const SCENE_FORMAT_VERSION: u32 = 1;
+
+/// Serialized scene: genome, initial layout, physics configuration and camera pose
+///
+/// Stored as human-readable JSON, like genome files. Everything except the genome
+/// is optional so a bare genome wrapped in `{"genome": ...}` is a valid scene.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneFile {
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+
+    /// Human-readable description shown in the Scene Manager
+    #[serde(default)]
+    pub description: String,
+
+    pub genome: GenomeData,
+
+    /// Effective physics the scene ran with (what headless runs use)
+    #[serde(default)]
+    pub physics: PhysicsConfig,
+
+    /// The scene's own physics overrides; scenes without them treat every value
+    /// differing from the built-in defaults as an override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physics_overrides: Option<PhysicsOverrides>,
+
+    /// Initial cells. Empty means a single founder cell at the origin.
+    #[serde(default)]
+    pub initial_cells: Vec<SceneCell>,
+
+    #[serde(default)]
+    pub camera: Option<CameraPose>,
+
+    /// Deterministic RNG seed
+    #[serde(default)]
+    pub rng_seed: u64,
+
+    /// Named cell groups; explicit members are indices into `initial_cells`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<CellGroup>,
+
+    /// Render-only region and clip plane filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regions: Option<crate::rendering::RegionSettings>,
+
+    /// Interventions applied at fixed ticks when the scene is simulated
+    #[serde(default, skip_serializing_if = "RunInterventions::is_empty")]
+    pub interventions: RunInterventions,
+}
+
+/// Interventions recorded during a CPU run, by tick since the run started
+///
+/// Saved with the scene the run started from (see `MainSimState::run_scene`), so a
+/// headless replay of that scene applies them at the same ticks.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunInterventions {
+    /// Genome edits that reached the run, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub genome_applications: Vec<StagedApplication>,
+}
+
+impl RunInterventions {
+    pub fn is_empty(&self) -> bool {
+        self.genome_applications.is_empty()
+    }
+
+    /// Genome in effect at `tick` of a run that started with `base`
+    pub fn genome_at<'a>(&'a self, base: &'a GenomeData, tick: u32) -> &'a GenomeData {
+        crate::simulation::genome_staging::replay_genome(base, &self.genome_applications, tick)
+    }
+
+    /// Record that the run simulates `genome` from the start of `tick`, unless it already does
+    ///
+    /// Only functional changes count (see `GenomeData::functionally_equal`). An edit within
+    /// `GENOME_MERGE_TICKS` of the last recorded one replaces it, so a slider drag records
+    /// once, at the tick it started; past `MAX_GENOME_APPLICATIONS` the two closest
+    /// applications are merged the same way.
+    pub fn record_genome(&mut self, base: &GenomeData, tick: u32, genome: &GenomeData) {
+        if self.genome_at(base, tick).functionally_equal(genome) {
+            return;
+        }
+        let applications = &mut self.genome_applications;
+        match applications.last_mut() {
+            Some(last) if tick.saturating_sub(last.tick) <= GENOME_MERGE_TICKS => {
+                last.genome = genome.clone();
+                // An edit that was undone within the window leaves nothing to record
+                let before = applications.len().checked_sub(2).map_or(base, |i| &applications[i].genome);
+                if before.functionally_equal(genome) {
+                    applications.pop();
+                }
+            }
+            _ => applications.push(StagedApplication { tick, genome: genome.clone() }),
+        }
+
+        while applications.len() > MAX_GENOME_APPLICATIONS {
+            let closest = (1..applications.len())
+                .min_by_key(|&i| applications[i].tick - applications[i - 1].tick)
+                .unwrap_or(1);
+            let later = applications.remove(closest);
+            applications[closest - 1].genome = later.genome;
+        }
+    }
+}
+
+/// Genome edits this many ticks or fewer after the last recorded one replace it
+pub const GENOME_MERGE_TICKS: u32 = 32;
+
+/// Most genome applications recorded with a run
+pub const MAX_GENOME_APPLICATIONS: usize = 64;
+
+fn default_format_version() -> u32 {
+    SCENE_FORMAT_VERSION
+}
+
+/// A single cell in a scene's initial layout
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneCell {
+    pub position: Vec3,
+    #[serde(default)]
+    pub velocity: Vec3,
+    #[serde(default = "default_rotation")]
+    pub rotation: Quat,
+    /// Mode index within the scene genome
+    pub mode_index: usize,
+    /// Starting mass (defaults to the mode's split mass)
+    #[serde(default)]
+    pub mass: Option<f32>,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    /// Held in place (kinematic anchor)
+    #[serde(default)]
+    pub pinned: bool,
+    /// User data channels
+    #[serde(default)]
+    pub user_data: [f32; crate::simulation::user_data::USER_DATA_CHANNELS],
+}
+
+fn default_rotation() -> Quat {
+    Quat::IDENTITY
+}
+
+fn default_radius() -> f32 {
+    1.0
+}
+
+/// Orbit camera pose
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CameraPose {
+    pub center: Vec3,
+    pub distance: f32,
+    pub rotation: Quat,
+}
+
+impl SceneFile {
+    /// Save scene to a JSON file
+    pub fn save_to_file(&self, path: &std::path::Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "scene")
+    }
+
+    /// Load scene from a JSON file
+    pub fn load_from_file(path: &std::path::Path) -> crate::error::Result<Self> {
+        crate::error::read_json(path, "scene")
+    }
+
+    /// Parse scene from a JSON string
+    pub fn from_json_str(json: &str) -> crate::error::Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|source| crate::error::BioSpheresError::Deserialize { what: "scene", source })
+    }
+
+    /// Scene whose initial layout is the current cells of a running simulation
+    ///
+    /// Positions, velocities, rotations, masses, radii, modes, pins and user data are kept;
+    /// adhesions and cell ages are not part of the scene format.
+    pub fn from_state(
+        state: &crate::simulation::CanonicalState,
+        genome: &GenomeData,
+        physics: &PhysicsConfig,
+        camera: Option<CameraPose>,
+        rng_seed: u64,
+        description: String,
+    ) -> Self {
+        let initial_cells = (0..state.cell_count)
+            .map(|i| SceneCell {
+                position: state.positions[i],
+                velocity: state.velocities[i],
+                rotation: state.rotations[i],
+                mode_index: state.mode_indices[i],
+                mass: Some(state.masses[i]),
+                radius: state.radii[i],
+                pinned: state.is_pinned(i),
+                user_data: state.user_data(i),
+            })
+            .collect();
+
+        Self {
+            format_version: SCENE_FORMAT_VERSION,
+            description,
+            genome: genome.clone(),
+            physics: physics.clone(),
+            physics_overrides: None,
+            initial_cells,
+            camera,
+            rng_seed,
+            groups: Vec::new(),
+            regions: None,
+            interventions: RunInterventions::default(),
+        }
+    }
+
+    /// Scene whose initial layout is the cells of `initial_state`
+    ///
+    /// The inverse of [`SceneFile::to_initial_state`] for cells whose split thresholds and
+    /// stiffness are the defaults it derives, such as a CPU run's founder.
+    pub fn from_initial_state(initial_state: &InitialState, genome: &GenomeData, description: String) -> Self {
+        let initial_cells = initial_state.initial_cells.iter()
+            .map(|cell| SceneCell {
+                position: cell.position,
+                velocity: cell.velocity,
+                rotation: cell.rotation,
+                mode_index: cell.mode_index,
+                mass: Some(cell.mass),
+                radius: cell.radius,
+                pinned: cell.pinned,
+                user_data: cell.user_data,
+            })
+            .collect();
+
+        Self {
+            format_version: SCENE_FORMAT_VERSION,
+            description,
+            genome: genome.clone(),
+            physics: initial_state.config.clone(),
+            physics_overrides: None,
+            initial_cells,
+            camera: None,
+            rng_seed: initial_state.rng_seed,
+            groups: Vec::new(),
+            regions: None,
+            interventions: RunInterventions::default(),
+        }
+    }
+
+    /// Scene layer of the physics config while this scene is loaded
+    pub fn physics_layer(&self) -> PhysicsOverrides {
+        self.physics_overrides.clone()
+            .unwrap_or_else(|| PhysicsOverrides::between(&PhysicsConfig::default(), &self.physics))
+    }
+
+    /// Check the scene for inconsistencies that would break simulation
+    /// Returns a list of human-readable problems (empty if valid)
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mode_count = self.genome.modes.len();
+
+        if self.format_version > SCENE_FORMAT_VERSION {
+            errors.push(format!(
+                "Scene format version {} is newer than supported version {}",
+                self.format_version, SCENE_FORMAT_VERSION
+            ));
+        }
+
+        errors.extend(self.genome.validate());
+        if mode_count == 0 {
+            return errors;
+        }
+
+        if !(self.physics.fixed_timestep > 0.0) {
+            errors.push("Physics fixed timestep must be positive".to_string());
+        }
+
+        for (i, cell) in self.initial_cells.iter().enumerate() {
+            if cell.mode_index >= mode_count {
+                errors.push(format!("Cell {} mode {} out of range", i, cell.mode_index));
+            }
+            if !cell.position.is_finite() || cell.position.length() > self.physics.sphere_radius {
+                errors.push(format!("Cell {} is outside the world sphere", i));
+            }
+            if cell.radius <= 0.0 || cell.mass.is_some_and(|mass| mass <= 0.0) {
+                errors.push(format!("Cell {} has non-positive radius or mass", i));
+            }
+        }
+
+        // A layout-free scene still has its one founder
+        let cell_count = self.initial_cells.len().max(1);
+        for group in &self.groups {
+            if let GroupKind::Explicit(cells) = &group.kind {
+                if cells.iter().any(|&cell| cell as usize >= cell_count) {
+                    errors.push(format!("Group '{}' names cells outside the initial layout", group.name));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Build the initial state for this scene
+    pub fn to_initial_state(&self, max_cells: usize) -> InitialState {
+        let mut initial_state = InitialState::new(self.physics.clone(), max_cells, self.rng_seed);
+
+        // Empty layout means a single founder at the origin in the initial mode
+        let founder;
+        let cells = if self.initial_cells.is_empty() {
+            founder = [SceneCell {
+                position: Vec3::ZERO,
+                velocity: Vec3::ZERO,
+                rotation: self.genome.initial_orientation,
+                mode_index: self.genome.initial_mode_index(),
+                mass: None,
+                radius: default_radius(),
+                pinned: false,
+                user_data: Default::default(),
+            }];
+            &founder[..]
+        } else {
+            &self.initial_cells[..]
+        };
+
+        for (i, cell) in cells.iter().take(max_cells).enumerate() {
+            let mode = self.genome.modes.get(cell.mode_index);
+            let split_mass = mode.map(|m| m.get_split_mass(i as u32, 0, self.rng_seed)).unwrap_or(1.5);
+            let split_interval = mode.map(|m| m.get_split_interval(i as u32, 0, self.rng_seed)).unwrap_or(5.0);
+
+            initial_state.add_cell(InitialCell {
+                id: i as u32,
+                position: cell.position,
+                velocity: cell.velocity,
+                rotation: cell.rotation,
+                angular_velocity: Vec3::ZERO,
+                mass: cell.mass.unwrap_or(split_mass),
+                radius: cell.radius,
+                genome_id: 0,
+                mode_index: cell.mode_index,
+                birth_time: 0.0,
+                split_interval,
+                split_mass,
+                stiffness: self.physics.default_stiffness,
+                pinned: cell.pinned,
+                user_data: cell.user_data,
+            });
+        }
+
+        initial_state
+    }
+}
@@ -0,0 +1,487 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use crate::error::{BioSpheresError, FileOperation};
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::capacity::MAX_CELL_CAPACITY;
+use crate::simulation::chunked_scene::{
+    decode_mode_and_position, ChunkedSceneHeader, CELL_RECORD_BYTES, HEADER_BYTES, MAGIC, RECORDS_PER_CHUNK,
+};
+use crate::simulation::experiment_session::SnapshotStats;
+use crate::simulation::{PhysicsConfig, SceneFile, SceneLoader};
+
+/// Most cell positions kept for the inspector's preview
+pub const INSPECT_SAMPLE_CELLS: usize = 2048;
+
+/// Problems listed before the rest are only counted
+const MAX_LISTED_PROBLEMS: usize = 20;
+
+/// Plugin for inspecting scene files without loading them
+pub struct SceneInspectorPlugin;
+
+impl Plugin for SceneInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SceneInspector>()
+            .add_systems(Update, (poll_scene_inspection, apply_inspector_actions).chain());
+    }
+}
+
+/// On-disk layout of an inspected scene
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneFormat {
+    Json,
+    Chunked,
+}
+
+/// What a scene file holds, read without building any simulation state
+#[derive(Clone)]
+pub struct SceneSummary {
+    pub format: SceneFormat,
+    pub format_version: u32,
+    /// Record layout version of a chunked file
+    pub layout_version: Option<u32>,
+    pub description: String,
+    /// Cells in the initial layout (0 = a single founder)
+    pub cell_count: usize,
+    /// Tick and simulated time, from the snapshot statistics saved next to the scene
+    pub tick: Option<u32>,
+    pub time: Option<f32>,
+    pub genome: GenomeData,
+    /// `PhysicsConfig::fingerprint` of the scene's physics
+    pub physics_digest: u64,
+    pub rng_seed: u64,
+    /// Cells of each genome mode, by mode index
+    pub cells_per_mode: Vec<usize>,
+    /// Position and mode of every `sample_stride`th cell
+    pub sample: Vec<(Vec3, usize)>,
+    pub sample_stride: usize,
+    /// Why a full load would fail or misbehave (empty for a sound file)
+    pub problems: Vec<String>,
+}
+
+impl SceneSummary {
+    /// Cell capacity a full CPU load allocates (see `SceneLoader::load`)
+    pub fn load_capacity(&self, cpu_capacity: usize) -> usize {
+        self.cell_count.max(cpu_capacity).min(MAX_CELL_CAPACITY)
+    }
+}
+
+/// Step between sampled cells so at most `INSPECT_SAMPLE_CELLS` are kept
+pub fn sample_stride(cell_count: usize) -> usize {
+    cell_count.div_ceil(INSPECT_SAMPLE_CELLS).max(1)
+}
+
+/// Summarize the scene file at `path`, chunked or JSON
+///
+/// A chunked file is read for its header, metadata and the mode and position columns of
+/// its cell records only. Files that can't be summarized give the reason why; summaries of
+/// files a load would reject list the problems.
+pub fn inspect_scene(path: &Path) -> crate::error::Result<SceneSummary> {
+    let io_error = |source: std::io::Error| BioSpheresError::Io { operation: FileOperation::Read, path: path.to_path_buf(), source };
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path).and_then(|mut file| file.read(&mut magic)).map_err(io_error)?;
+    if read == magic.len() && magic == MAGIC {
+        return inspect_chunked(path);
+    }
+
+    let scene: SceneFile = crate::error::read_json(path, "scene")?;
+    let stride = sample_stride(scene.initial_cells.len());
+    let mut columns = Columns::new(scene.genome.modes.len(), stride);
+    for (index, cell) in scene.initial_cells.iter().enumerate() {
+        columns.add(index, cell.mode_index, cell.position);
+    }
+    Ok(columns.summary(path, &scene, SceneFormat::Json, None, scene.initial_cells.len()))
+}
+
+fn inspect_chunked(path: &Path) -> crate::error::Result<SceneSummary> {
+    let io_error = |source: std::io::Error| BioSpheresError::Io { operation: FileOperation::Read, path: path.to_path_buf(), source };
+    let damaged = |reason: String| BioSpheresError::Format { what: "chunked scene", path: path.to_path_buf(), reason };
+
+    let file = std::fs::File::open(path).map_err(io_error)?;
+    let file_bytes = file.metadata().map_err(io_error)?.len();
+    let mut reader = BufReader::new(file);
+    let mut header = [0u8; HEADER_BYTES];
+    reader.read_exact(&mut header).map_err(|_| damaged(format!("truncated: {} bytes, shorter than the header", file_bytes)))?;
+    let header = ChunkedSceneHeader::parse(&header, file_bytes).map_err(damaged)?;
+
+    let mut metadata = vec![0u8; header.metadata_bytes];
+    reader.read_exact(&mut metadata).map_err(io_error)?;
+    let scene: SceneFile = serde_json::from_slice(&metadata)
+        .map_err(|source| BioSpheresError::Parse { what: "chunked scene", path: path.to_path_buf(), source })?;
+
+    let mut columns = Columns::new(scene.genome.modes.len(), sample_stride(header.cell_count));
+    let mut chunk = vec![0u8; RECORDS_PER_CHUNK * CELL_RECORD_BYTES];
+    let mut read = 0;
+    while read < header.cell_count {
+        let records = (header.cell_count - read).min(RECORDS_PER_CHUNK);
+        let chunk = &mut chunk[..records * CELL_RECORD_BYTES];
+        reader.read_exact(chunk).map_err(io_error)?;
+        for record in chunk.chunks_exact(CELL_RECORD_BYTES) {
+            let (mode, position) = decode_mode_and_position(record);
+            columns.add(read, mode, position);
+            read += 1;
+        }
+    }
+    Ok(columns.summary(path, &scene, SceneFormat::Chunked, Some(header.version), header.cell_count))
+}
+
+/// Per-mode counts and the decimated sample, gathered one cell at a time
+struct Columns {
+    cells_per_mode: Vec<usize>,
+    /// Cells naming a mode the genome doesn't have
+    unknown_modes: usize,
+    sample: Vec<(Vec3, usize)>,
+    stride: usize,
+}
+
+impl Columns {
+    fn new(mode_count: usize, stride: usize) -> Self {
+        Self { cells_per_mode: vec![0; mode_count], unknown_modes: 0, sample: Vec::new(), stride }
+    }
+
+    fn add(&mut self, index: usize, mode: usize, position: Vec3) {
+        match self.cells_per_mode.get_mut(mode) {
+            Some(count) => *count += 1,
+            None => self.unknown_modes += 1,
+        }
+        if index % self.stride == 0 {
+            self.sample.push((position, mode));
+        }
+    }
+
+    fn summary(self, path: &Path, scene: &SceneFile, format: SceneFormat, layout_version: Option<u32>, cell_count: usize) -> SceneSummary {
+        let mut problems = scene.validate();
+        if self.unknown_modes > 0 {
+            problems.push(format!("{} cells have a mode outside the genome's {} modes", self.unknown_modes, self.cells_per_mode.len()));
+        }
+        if problems.len() > MAX_LISTED_PROBLEMS {
+            let more = problems.len() - MAX_LISTED_PROBLEMS;
+            problems.truncate(MAX_LISTED_PROBLEMS);
+            problems.push(format!("... and {} more", more));
+        }
+        // Snapshots keep their statistics beside the scene; other scenes don't record a time
+        let stats = crate::error::read_json::<SnapshotStats>(&path.with_file_name("stats.json"), "snapshot statistics").ok();
+
+        SceneSummary {
+            format,
+            format_version: scene.format_version,
+            layout_version,
+            description: scene.description.clone(),
+            cell_count,
+            tick: stats.as_ref().map(|stats| stats.tick),
+            time: stats.as_ref().map(|stats| stats.time),
+            genome: scene.genome.clone(),
+            physics_digest: scene.physics.fingerprint(),
+            rng_seed: scene.rng_seed,
+            cells_per_mode: self.cells_per_mode,
+            sample: self.sample,
+            sample_stride: self.stride,
+            problems,
+        }
+    }
+}
+
+/// What to do with an inspected scene
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InspectAction {
+    /// Load the whole scene, replacing the current one
+    Load,
+    /// Replace only the genome
+    LoadGenome,
+    /// Load the scene's cells as the preview's initial layout under the current genome and physics
+    ExtractLayout,
+}
+
+/// The scene file open in the inspector window
+pub struct Inspection {
+    pub path: PathBuf,
+    /// The summary, or why the file couldn't be read
+    pub result: Result<SceneSummary, String>,
+    /// Orbit of the preview (yaw, pitch in radians)
+    pub view: Vec2,
+}
+
+impl Inspection {
+    /// Title for loads: the scene's description, else its file name
+    pub fn title(&self) -> String {
+        match &self.result {
+            Ok(summary) if !summary.description.is_empty() => summary.description.clone(),
+            _ => self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Scene files opened with "Inspect..." and the actions taken from the inspector
+///
+/// Inspecting never touches the live simulation; only the actions do.
+#[derive(Resource, Default)]
+pub struct SceneInspector {
+    pub open: Option<Inspection>,
+    /// Choose a file to inspect with a file dialog
+    pub pick_requested: bool,
+    /// Action chosen in the inspector window
+    pub action: Option<InspectAction>,
+    pending: Option<(PathBuf, Task<crate::error::Result<SceneSummary>>)>,
+    /// Full read of the scene for an action that needs its cells
+    loading: Option<(InspectAction, Task<crate::error::Result<SceneFile>>)>,
+}
+
+impl SceneInspector {
+    /// Start summarizing `path` in the background; the window opens when it's done
+    pub fn inspect(&mut self, path: PathBuf) {
+        let task = {
+            let path = path.clone();
+            AsyncComputeTaskPool::get().spawn(async move { inspect_scene(&path) })
+        };
+        self.pending = Some((path, task));
+    }
+
+    /// File being summarized
+    pub fn inspecting(&self) -> Option<&Path> {
+        self.pending.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    /// Action waiting for the full scene to be read
+    pub fn busy(&self) -> Option<InspectAction> {
+        self.loading.as_ref().map(|(action, _)| *action)
+    }
+}
+
+fn poll_scene_inspection(mut inspector: ResMut<SceneInspector>) {
+    if std::mem::take(&mut inspector.pick_requested) {
+        if let Some(path) = rfd::FileDialog::new().add_filter("Scene", &["json", "bscene"]).pick_file() {
+            inspector.inspect(path);
+        }
+    }
+
+    let Some((_, task)) = inspector.pending.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(poll_once(task)) else {
+        return;
+    };
+    let Some((path, _)) = inspector.pending.take() else {
+        return;
+    };
+    inspector.open = Some(Inspection { path, result: result.map_err(|e| e.to_string()), view: Vec2::new(0.6, 0.4) });
+}
+
+/// Carry out the inspector's actions: chunked loads go through the background scene
+/// loader, everything else through the Scene Manager's scene request
+fn apply_inspector_actions(
+    mut inspector: ResMut<SceneInspector>,
+    mut scene_loader: ResMut<SceneLoader>,
+    mut scene_request: ResMut<crate::ui::windows::scene_manager::SceneModeRequest>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    physics: Res<PhysicsConfig>,
+    physics_layers: Res<crate::simulation::PhysicsLayers>,
+    cpu_cell_capacity: Res<crate::ui::CpuCellCapacity>,
+) {
+    if let Some(action) = inspector.action.take() {
+        let Some(inspection) = inspector.open.as_ref() else {
+            return;
+        };
+        let Ok(summary) = &inspection.result else {
+            return;
+        };
+        let (path, title) = (inspection.path.clone(), inspection.title());
+        match action {
+            InspectAction::LoadGenome => {
+                let genome = summary.genome.clone();
+                if !dormant.is_empty() {
+                    // Kept scenes were simulated with the genome being replaced; ask first
+                    dormant.deferred_load = Some(crate::simulation::dormant_scenes::DeferredLoad::Genome(genome));
+                } else {
+                    notifications.success(format!("Loaded genome '{}'", genome.name));
+                    current_genome.replace(genome, 0, None);
+                }
+            }
+            InspectAction::Load if summary.format == SceneFormat::Chunked => {
+                scene_loader.load(path, title, cpu_cell_capacity.capacity);
+                inspector.open = None;
+            }
+            InspectAction::Load | InspectAction::ExtractLayout => {
+                let format = summary.format;
+                let task = AsyncComputeTaskPool::get().spawn(async move {
+                    match format {
+                        SceneFormat::Chunked => SceneFile::load_chunked(&path),
+                        SceneFormat::Json => SceneFile::load_from_file(&path),
+                    }
+                });
+                inspector.loading = Some((action, task));
+            }
+        }
+    }
+
+    let Some((_, task)) = inspector.loading.as_mut() else {
+        return;
+    };
+    let Some(result) = block_on(poll_once(task)) else {
+        return;
+    };
+    let Some((action, _)) = inspector.loading.take() else {
+        return;
+    };
+    let scene = match result {
+        Ok(scene) => scene,
+        Err(e) => {
+            notifications.error(&e);
+            return;
+        }
+    };
+    let title = inspector.open.take().map(|inspection| inspection.title()).unwrap_or_default();
+
+    if action == InspectAction::Load {
+        scene_request.requested_scene = Some((title, scene));
+        return;
+    }
+    let mode_count = current_genome.genome.modes.len();
+    if let Some(cell) = scene.initial_cells.iter().find(|cell| cell.mode_index >= mode_count) {
+        notifications.warning(format!(
+            "Can't extract the layout of '{}': its cells use mode {}, the current genome has {} modes",
+            title, cell.mode_index, mode_count
+        ));
+        return;
+    }
+    let extracted = scene.initial_cells.len();
+    let layout = SceneFile {
+        description: format!("{} cells extracted from {}", extracted, title),
+        genome: current_genome.genome.clone(),
+        physics: physics.clone(),
+        // Loaded into the current physics, so the scene layer stays as it is
+        physics_overrides: Some(physics_layers.scene_layer_for(&physics)),
+        camera: None,
+        regions: None,
+        // The extracted layout is a new starting point
+        interventions: Default::default(),
+        ..scene
+    };
+    notifications.success(format!("Extracted {} cells from '{}'", extracted, title));
+    scene_request.requested_scene = Some((format!("Layout of {}", title), layout));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::chunked_scene::CHUNKED_SCENE_VERSION;
+    use crate::simulation::scene_file::SceneCell;
+    use crate::simulation::CanonicalState;
+
+    fn scene(cells: usize) -> SceneFile {
+        let genome = GenomeData {
+            name: "Inspected".to_string(),
+            modes: ["Stem", "Leaf", "Root"].iter().enumerate()
+                .map(|(index, name)| ModeSettings::new_self_splitting(index, name.to_string()))
+                .collect(),
+            ..GenomeData::default()
+        };
+        let mut scene = SceneFile::from_state(&CanonicalState::new(1), &genome, &PhysicsConfig::default(), None, 11, "Fixture".to_string());
+        scene.initial_cells = (0..cells)
+            .map(|i| SceneCell {
+                position: Vec3::new((i % 50) as f32, (i / 50 % 50) as f32, (i / 2500) as f32) * 0.5 - Vec3::splat(10.0),
+                velocity: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+                mode_index: if i % 4 == 0 { 2 } else { i % 2 },
+                mass: Some(1.0),
+                radius: 0.5,
+                pinned: false,
+                user_data: Default::default(),
+            })
+            .collect();
+        scene
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("biospheres_inspect_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_chunked_summary_reads_the_header_and_columns() {
+        let scene = scene(10_000);
+        let dir = fixture_dir("chunked");
+        let path = dir.join("scene.bscene");
+        scene.save_chunked(&path).unwrap();
+        let stats = SnapshotStats::of(&CanonicalState::new(1), &scene.genome, 12.5, 800);
+        crate::error::write_json(&dir.join("stats.json"), &stats, "snapshot statistics").unwrap();
+
+        let summary = inspect_scene(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(summary.format, SceneFormat::Chunked);
+        assert_eq!(summary.layout_version, Some(CHUNKED_SCENE_VERSION));
+        assert_eq!(summary.cell_count, 10_000);
+        assert_eq!((summary.tick, summary.time), (Some(800), Some(12.5)));
+        assert_eq!(summary.genome.name, "Inspected");
+        assert_eq!(summary.genome.modes.len(), 3);
+        assert_eq!(summary.physics_digest, PhysicsConfig::default().fingerprint());
+        assert_eq!(summary.rng_seed, 11);
+        assert_eq!(summary.cells_per_mode, [2500, 5000, 2500]);
+        assert_eq!(summary.sample_stride, 5);
+        assert_eq!(summary.sample.len(), 2000);
+        assert_eq!(summary.sample[1], (scene.initial_cells[5].position, 1));
+        assert!(summary.problems.is_empty(), "{:?}", summary.problems);
+        assert_eq!(summary.load_capacity(4096), 10_000);
+    }
+
+    #[test]
+    fn test_json_summary_matches_the_chunked_one() {
+        let scene = scene(300);
+        let dir = fixture_dir("json");
+        let json = dir.join("scene.json");
+        let chunked = dir.join("scene.bscene");
+        scene.save_to_file(&json).unwrap();
+        scene.save_chunked(&chunked).unwrap();
+
+        let from_json = inspect_scene(&json).unwrap();
+        let from_chunked = inspect_scene(&chunked).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(from_json.format, SceneFormat::Json);
+        assert_eq!((from_json.tick, from_json.layout_version), (None, None));
+        assert_eq!(from_json.sample_stride, 1);
+        assert_eq!(from_json.sample.len(), 300);
+        assert_eq!(from_json.cells_per_mode, from_chunked.cells_per_mode);
+        assert_eq!(from_json.sample, from_chunked.sample);
+        assert_eq!(from_json.physics_digest, from_chunked.physics_digest);
+    }
+
+    #[test]
+    fn test_damaged_and_newer_files_give_a_diagnostic() {
+        let dir = fixture_dir("damaged");
+
+        let path = dir.join("truncated.bscene");
+        scene(100).save_chunked(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 10]).unwrap();
+        assert!(inspect_scene(&path).err().unwrap().to_string().contains("truncated"));
+
+        let path = dir.join("newer_layout.bscene");
+        let mut newer = bytes.clone();
+        newer[4..8].copy_from_slice(&(CHUNKED_SCENE_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, newer).unwrap();
+        assert!(inspect_scene(&path).err().unwrap().to_string().contains("newer than supported"));
+
+        let path = dir.join("garbage.json");
+        std::fs::write(&path, "{ not a scene").unwrap();
+        assert!(inspect_scene(&path).is_err());
+
+        // A newer scene format still summarizes, with the reason a load would refuse it
+        let path = dir.join("newer.json");
+        let mut scene = scene(10);
+        scene.format_version = crate::simulation::scene_file::SCENE_FORMAT_VERSION + 1;
+        scene.initial_cells[3].mode_index = 7;
+        scene.save_to_file(&path).unwrap();
+        let summary = inspect_scene(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(summary.cell_count, 10);
+        assert!(summary.problems.iter().any(|problem| problem.contains("newer than supported")));
+        assert!(summary.problems.iter().any(|problem| problem.contains("1 cells have a mode outside")));
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use crate::simulation::cpu_physics::{CanonicalCollisionPair, DivisionData};
+
+/// Parallel detection splits the grid cells into this many chunks per worker thread
+pub const PAIR_CHUNKS_PER_THREAD: usize = 4;
+
+/// Working vectors of a physics tick, cleared and refilled instead of reallocated
+///
+/// Capacities only grow, so once a simulation has seen its largest pair count and
+/// division burst, further ticks allocate nothing here. Contents other than the last
+/// contacts never outlive the tick that wrote them; cloning gives empty buffers, so
+/// checkpoints don't copy them.
+#[derive(Default)]
+pub struct ScratchBuffers {
+    /// Collision pairs of the current tick, sorted by (index_a, index_b)
+    pub collision_pairs: Vec<CanonicalCollisionPair>,
+    /// Per-chunk pair lists of the parallel detector, concatenated into `collision_pairs`
+    pub pair_chunks: Vec<Vec<CanonicalCollisionPair>>,
+    /// Cached candidates re-measured in parallel (None where the pair no longer overlaps)
+    pub candidate_overlaps: Vec<Option<CanonicalCollisionPair>>,
+    /// Force and torque contributions per collision pair, accumulated in pair order
+    pub force_contributions: Vec<Option<[(usize, Vec3, Vec3); 2]>>,
+    /// Divisions of the current pass
+    pub(crate) division_data: Vec<DivisionData>,
+    /// Old to new cell indices of the last cell removal, None for the removed cell
+    pub compaction_map: Vec<Option<usize>>,
+    /// Collision contacts of the last physics tick as (cell id, cell id, overlap)
+    ///
+    /// Kept past the tick for the contact graph export; ids rather than indices
+    /// because the divisions that follow move cells around.
+    pub last_contacts: Vec<(u32, u32, f32)>,
+}
+
+impl Clone for ScratchBuffers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl ScratchBuffers {
+    /// Buffers sized for a state of `capacity` cells
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            // Worst case estimate: every cell touches ~10 others
+            collision_pairs: Vec::with_capacity(capacity * 10),
+            division_data: Vec::with_capacity(256),
+            ..Default::default()
+        }
+    }
+
+    /// Keep this tick's collision pairs as contacts between the cells in `cell_ids`
+    pub fn record_contacts(&mut self, cell_ids: &[u32]) {
+        self.last_contacts.clear();
+        self.last_contacts.extend(self.collision_pairs.iter()
+            .map(|pair| (cell_ids[pair.index_a], cell_ids[pair.index_b], pair.overlap)));
+    }
+
+    /// Heap bytes held by the buffers
+    pub fn memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+        self.collision_pairs.capacity() * size_of::<CanonicalCollisionPair>()
+            + self.pair_chunks.capacity() * size_of::<Vec<CanonicalCollisionPair>>()
+            + self.pair_chunks.iter().map(|chunk| chunk.capacity() * size_of::<CanonicalCollisionPair>()).sum::<usize>()
+            + self.candidate_overlaps.capacity() * size_of::<Option<CanonicalCollisionPair>>()
+            + self.force_contributions.capacity() * size_of::<Option<[(usize, Vec3, Vec3); 2]>>()
+            + self.division_data.capacity() * size_of::<DivisionData>()
+            + self.compaction_map.capacity() * size_of::<Option<usize>>()
+            + self.last_contacts.capacity() * size_of::<(u32, u32, f32)>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::cpu_physics::{division_step, physics_step_st_with_genome, physics_step_with_genome, CanonicalState};
+    use crate::simulation::test_support::{add_test_cell, never_split_mode};
+    use crate::simulation::PhysicsConfig;
+
+    /// A packed cluster of never-dividing cell pairs that pushes itself apart
+    fn settled_cluster() -> (CanonicalState, GenomeData, PhysicsConfig) {
+        let mut mode = never_split_mode(0, "Sticky");
+        mode.parent_make_adhesion = true;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        let mut state = CanonicalState::new(256);
+        for i in 0..64 {
+            let position = Vec3::new((i % 4) as f32, ((i / 4) % 4) as f32, (i / 16) as f32) * 1.6;
+            add_test_cell(&mut state, position, Quat::IDENTITY, 1.0, 1.0, 0);
+        }
+        for i in (0..64).step_by(2) {
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, i, i + 1, 0,
+                Vec3::X, -Vec3::X, Vec3::Z, Vec3::Z, Quat::IDENTITY, Quat::IDENTITY,
+            ).expect("adhesion slot");
+        }
+        (state, genome, config)
+    }
+
+    #[test]
+    fn test_scratch_capacity_is_stable_in_steady_state() {
+        for parallel in [false, true] {
+            let (mut state, genome, config) = settled_cluster();
+            let step = |state: &mut CanonicalState, tick: u32| {
+                let time = tick as f32 * config.fixed_timestep;
+                if parallel {
+                    physics_step_with_genome(state, &config, &genome, time, false);
+                } else {
+                    physics_step_st_with_genome(state, &config, &genome, time);
+                }
+                let capacity = state.capacity;
+                division_step(state, &genome, time + config.fixed_timestep, config.fixed_timestep, capacity, 0);
+            };
+
+            // Warm up until the cluster has reached its largest pair count
+            for tick in 0..200 {
+                step(&mut state, tick);
+            }
+            let warm = state.scratch.memory_bytes();
+            assert!(warm > 0);
+
+            for tick in 200..300 {
+                step(&mut state, tick);
+                assert_eq!(state.scratch.memory_bytes(), warm, "scratch grew at tick {} (parallel: {})", tick, parallel);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clone_drops_scratch_contents() {
+        let (mut state, genome, config) = settled_cluster();
+        physics_step_with_genome(&mut state, &config, &genome, 0.0, false);
+        assert!(!state.scratch.collision_pairs.is_empty());
+
+        let copy = state.clone();
+        assert!(copy.scratch.collision_pairs.is_empty());
+        assert_eq!(copy.scratch.memory_bytes(), 0);
+    }
+}
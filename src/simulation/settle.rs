@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Consecutive ticks the energy must stay below the threshold before a settle auto-stops
+///
+/// A ringing structure passes through zero kinetic energy at every turnaround, so a
+/// single calm tick says nothing.
+pub const AUTO_STOP_CALM_TICKS: u32 = 32;
+
+/// Plugin for the Settle action: a temporary ramp of the global damping
+pub struct SettlePlugin;
+
+impl Plugin for SettlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettleSettings>()
+            .init_resource::<SettleRequests>()
+            .add_systems(Update, apply_settle_requests);
+    }
+}
+
+/// Shape of the damping ramp over a settle, from 0 at both ends to 1 at the top
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettleCurve {
+    /// Smooth rise and fall (sin²)
+    #[default]
+    Bell,
+    /// Straight up to the middle and straight down
+    Triangle,
+    /// Quick rise, long hold at the peak, quick fall
+    Plateau,
+}
+
+impl SettleCurve {
+    pub const ALL: [SettleCurve; 3] = [SettleCurve::Bell, SettleCurve::Triangle, SettleCurve::Plateau];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SettleCurve::Bell => "Bell",
+            SettleCurve::Triangle => "Triangle",
+            SettleCurve::Plateau => "Plateau",
+        }
+    }
+
+    /// Height of the ramp at `progress` (0..=1 through the settle)
+    pub fn shape(self, progress: f32) -> f32 {
+        let x = progress.clamp(0.0, 1.0);
+        match self {
+            SettleCurve::Bell => (std::f32::consts::PI * x).sin().powi(2),
+            SettleCurve::Triangle => 1.0 - (2.0 * x - 1.0).abs(),
+            SettleCurve::Plateau => {
+                let edge = (x.min(1.0 - x) / 0.2).min(1.0);
+                edge * edge * (3.0 - 2.0 * edge)
+            }
+        }
+    }
+}
+
+/// How the Settle button ramps the damping (persisted with the UI settings)
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettleSettings {
+    /// Length of the ramp in simulated seconds
+    pub duration: f32,
+    /// Damping multiplier at the top of the ramp
+    pub peak: f32,
+    pub curve: SettleCurve,
+    /// End early once the kinetic energy stays below `energy_threshold`
+    pub auto_stop: bool,
+    pub energy_threshold: f32,
+}
+
+impl Default for SettleSettings {
+    fn default() -> Self {
+        Self {
+            duration: 1.0,
+            peak: 20.0,
+            curve: SettleCurve::Bell,
+            auto_stop: true,
+            energy_threshold: 0.01,
+        }
+    }
+}
+
+/// A settle in progress, carried in the canonical state so checkpoints and replays resume it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settle {
+    pub settings: SettleSettings,
+    /// Ticks the ramp lasts
+    pub ticks: u32,
+    /// Ticks already run
+    pub elapsed: u32,
+    /// Consecutive ticks that ended below the energy threshold
+    pub calm: u32,
+}
+
+impl Settle {
+    pub fn new(settings: SettleSettings, fixed_timestep: f32) -> Self {
+        Self {
+            settings,
+            ticks: crate::simulation::clock::ticks_to_reach(settings.duration.max(0.0), fixed_timestep).max(1),
+            elapsed: 0,
+            calm: 0,
+        }
+    }
+
+    /// Damping multiplier for the next tick (1 = the configured damping)
+    pub fn multiplier(&self) -> f32 {
+        // Sampled mid-tick, so the first and last ticks already damp a little
+        let progress = (self.elapsed as f32 + 0.5) / self.ticks as f32;
+        1.0 + (self.settings.peak - 1.0).max(0.0) * self.settings.curve.shape(progress)
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.elapsed as f32 / self.ticks as f32
+    }
+}
+
+/// Start or stop a settle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettleChange {
+    Start(SettleSettings),
+    Stop,
+}
+
+/// Settle changes waiting to be applied to the active simulation
+#[derive(Resource, Default)]
+pub struct SettleRequests {
+    pub pending: Vec<SettleChange>,
+    /// Settle running in the active simulation, refreshed every frame
+    pub active: Option<Settle>,
+    /// Kinetic energy of the active simulation (None without one)
+    pub kinetic_energy: Option<f32>,
+}
+
+impl CanonicalState {
+    /// Kinetic energy of cell `index`: ½mv² plus ½Iω² with the solid-sphere inertia the integration uses
+    pub fn cell_kinetic_energy(&self, index: usize) -> f32 {
+        let mass = self.masses[index];
+        let radius = self.radii[index];
+        let inertia = 0.4 * mass * radius * radius;
+        0.5 * mass * self.velocities[index].length_squared()
+            + 0.5 * inertia * self.angular_velocities[index].length_squared()
+    }
+
+    /// Total kinetic energy of the living cells
+    pub fn kinetic_energy(&self) -> f32 {
+        (0..self.cell_count).map(|index| self.cell_kinetic_energy(index) as f64).sum::<f64>() as f32
+    }
+
+    pub fn apply_settle_change(&mut self, change: SettleChange, fixed_timestep: f32) {
+        self.settle = match change {
+            SettleChange::Start(settings) => Some(Settle::new(settings, fixed_timestep)),
+            SettleChange::Stop => None,
+        };
+    }
+
+    /// Count the tick that just ran against the settle; ends it when the ramp is over or,
+    /// with auto-stop, once the energy has stayed below the threshold
+    pub fn advance_settle(&mut self) {
+        let Some(mut settle) = self.settle else {
+            return;
+        };
+        settle.elapsed += 1;
+        if settle.settings.auto_stop && self.kinetic_energy() < settle.settings.energy_threshold {
+            settle.calm += 1;
+        } else {
+            settle.calm = 0;
+        }
+        let done = settle.elapsed >= settle.ticks || settle.calm >= AUTO_STOP_CALM_TICKS;
+        self.settle = (!done).then_some(settle);
+    }
+}
+
+/// Config for the next tick of `state`: the damping of a running settle multiplies the configured one
+///
+/// The multiplier scales the damping rate (the exponent the retention factors are raised
+/// to), so the configured values are never written and are back in effect as soon as the
+/// settle ends. A retention of 1 (no damping) stays undamped.
+pub fn settled_config<'a>(state: &CanonicalState, config: &'a PhysicsConfig) -> Cow<'a, PhysicsConfig> {
+    let Some(settle) = state.settle else {
+        return Cow::Borrowed(config);
+    };
+    let multiplier = settle.multiplier();
+    let mut settled = config.clone();
+    settled.velocity_damping = config.velocity_damping.powf(multiplier);
+    settled.angular_damping = config.angular_damping.powf(multiplier);
+    Cow::Owned(settled)
+}
+
+/// Apply the settle changes recorded on the preview timeline for `tick`
+pub fn apply_scheduled_settles(state: &mut CanonicalState, schedule: &[(u32, SettleChange)], tick: u32, fixed_timestep: f32) {
+    for (change_tick, change) in schedule {
+        if *change_tick == tick {
+            state.apply_settle_change(*change, fixed_timestep);
+        }
+    }
+}
+
+/// Apply pending settle changes to the active simulation between ticks, recording them for replay in Preview mode
+fn apply_settle_requests(
+    mut requests: ResMut<SettleRequests>,
+    sim_state: Res<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    config: Res<PhysicsConfig>,
+) {
+    let state = match sim_state.mode {
+        SimulationMode::Cpu => main_state.as_deref().map(|main_state| &main_state.canonical_state),
+        SimulationMode::Preview => preview_state.as_deref().map(|preview_state| &preview_state.canonical_state),
+        SimulationMode::Gpu => None,
+    };
+    requests.active = state.and_then(|state| state.settle);
+    requests.kinetic_energy = state.map(CanonicalState::kinetic_energy);
+    if requests.pending.is_empty() {
+        return;
+    }
+
+    let fixed_timestep = config.fixed_timestep;
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            for change in requests.pending.drain(..) {
+                main_state.canonical_state.apply_settle_change(change, fixed_timestep);
+            }
+        }
+        SimulationMode::Preview => {
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+            preview_state.intervene(sim_state.is_resimulating, fixed_timestep, |preview_state, tick| {
+                for change in std::mem::take(&mut requests.pending) {
+                    preview_state.canonical_state.apply_settle_change(change, fixed_timestep);
+                    preview_state.settles.push((tick, change));
+                }
+            });
+        }
+        SimulationMode::Gpu => requests.pending.clear(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::GenomeData;
+    use crate::simulation::test_support::{add_test_cell, never_split_mode};
+
+    /// Two bonded cells stretched well past the rest length, with no bond damping to calm them
+    fn ringing_pair() -> (CanonicalState, GenomeData) {
+        let mut mode = never_split_mode(0, "Ringing");
+        mode.adhesion_settings.linear_spring_damping = 0.0;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+
+        let mut state = CanonicalState::new(8);
+        for x in [-1.5, 1.5] {
+            add_test_cell(&mut state, Vec3::new(x, 0.0, 0.0), Quat::IDENTITY, 1.0, 1.0, 0);
+        }
+        state.adhesion_manager.add_adhesion_with_directions(
+            &mut state.adhesion_connections, 0, 1, 0,
+            Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+        ).expect("adhesion slot");
+        (state, genome)
+    }
+
+    /// Tick after the last one whose kinetic energy reached `threshold` within `ticks`
+    fn settled_after(state: &mut CanonicalState, genome: &GenomeData, config: &PhysicsConfig, threshold: f32, ticks: u32) -> u32 {
+        let mut settled = 0;
+        for tick in 0..ticks {
+            let time = tick as f32 * config.fixed_timestep;
+            let step_config = settled_config(state, config);
+            crate::simulation::cpu_physics::physics_step_with_genome(state, &step_config, genome, time, false);
+            state.advance_settle();
+            if state.kinetic_energy() >= threshold {
+                settled = tick + 1;
+            }
+        }
+        settled
+    }
+
+    #[test]
+    fn test_settle_calms_a_ringing_pair_five_times_faster() {
+        let config = PhysicsConfig::default();
+        let before = config.clone();
+        let settings = SettleSettings::default();
+        let ticks = crate::simulation::clock::ticks_to_reach(12.0, config.fixed_timestep);
+
+        let (mut free, genome) = ringing_pair();
+        let free_ticks = settled_after(&mut free, &genome, &config, settings.energy_threshold, ticks);
+
+        let (mut settled, _) = ringing_pair();
+        settled.apply_settle_change(SettleChange::Start(settings), config.fixed_timestep);
+        let settle_ticks = settled_after(&mut settled, &genome, &config, settings.energy_threshold, ticks);
+
+        assert!(free_ticks < ticks, "the free pair should calm down within the run");
+        assert!(settle_ticks > 0, "the pair should ring before it is settled");
+        assert!(settle_ticks * 5 <= free_ticks, "settled after {} ticks, free after {}", settle_ticks, free_ticks);
+        assert!(settled.settle.is_none(), "the settle ended");
+        assert_eq!(config, before);
+        assert!(matches!(settled_config(&settled, &config), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_ramp_multiplies_the_configured_damping() {
+        let config = PhysicsConfig::default();
+        let mut state = CanonicalState::new(1);
+        let settings = SettleSettings { duration: 1.0, peak: 10.0, curve: SettleCurve::Triangle, ..Default::default() };
+        state.apply_settle_change(SettleChange::Start(settings), 0.25);
+        let ramp = (0..4).map(|_| {
+            let multiplier = state.settle.unwrap().multiplier();
+            let step_config = settled_config(&state, &config);
+            assert_eq!(step_config.velocity_damping, config.velocity_damping.powf(multiplier));
+            assert_eq!(step_config.angular_damping, config.angular_damping.powf(multiplier));
+            state.advance_settle();
+            multiplier
+        }).collect::<Vec<_>>();
+        assert_eq!(ramp, [3.25, 7.75, 7.75, 3.25]);
+        assert!(state.settle.is_none());
+
+        for curve in SettleCurve::ALL {
+            assert_eq!(curve.shape(0.0), 0.0);
+            assert!((curve.shape(0.5) - 1.0).abs() < 1e-6);
+            assert!(curve.shape(1.0).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,856 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use crate::genome::{EquatorialBondRule, GenomeData, ModeIndex, ModeSettings};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::dormant_scenes::{DormantCpuScene, DormantPreviewScene};
+use crate::simulation::fingerprint::run_fingerprint;
+use crate::simulation::memory::MemoryUsage;
+use crate::simulation::soft_drag::{SoftDrag, SoftDragRegion};
+use crate::simulation::{EventTimeline, PhysicsConfig, SimulationState};
+
+/// Plugin for the soak test harness (Debug menu, hold Shift)
+pub struct SoakPlugin;
+
+impl Plugin for SoakPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoakHarness>()
+            .add_systems(Update, poll_soak_run);
+    }
+}
+
+/// Golden fingerprints read by default, relative to the working directory
+pub const DEFAULT_GOLDEN_PATH: &str = "soak_golden.json";
+
+/// Ticks between genome edits in the hot-edit scene
+const HOT_EDIT_INTERVAL: u32 = 100;
+
+/// Ticks between mode switches in the mode-switch scene
+const MODE_SWITCH_INTERVAL: u32 = 150;
+
+/// Cell slots of the preview in the mode-switch scene (the Preview mode limit)
+const PREVIEW_CAPACITY: usize = 256;
+
+/// Memory may grow to this multiple of a freshly allocated state of the same capacity
+const MEMORY_GROWTH_LIMIT: usize = 2;
+
+/// A scripted stress scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoakScene {
+    /// Fast-dividing bonded colony running into the cell capacity
+    DivisionStorm,
+    /// Cubic lattice of bonded cells (about 30K bonds at full budget)
+    BondLattice,
+    /// Splitting colony whose genome is edited every 100 ticks
+    GenomeHotEdits,
+    /// Preview and CPU scenes parked and resumed in turn, as when switching modes
+    ModeSwitches,
+    /// Region, single-cell and organism drags at fixed ticks
+    DragInterventions,
+}
+
+impl SoakScene {
+    pub const ALL: [SoakScene; 5] = [
+        SoakScene::DivisionStorm,
+        SoakScene::BondLattice,
+        SoakScene::GenomeHotEdits,
+        SoakScene::ModeSwitches,
+        SoakScene::DragInterventions,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SoakScene::DivisionStorm => "Division storm",
+            SoakScene::BondLattice => "Bond lattice",
+            SoakScene::GenomeHotEdits => "Genome hot edits",
+            SoakScene::ModeSwitches => "Mode switches",
+            SoakScene::DragInterventions => "Drag interventions",
+        }
+    }
+
+    /// Key of the scene in reports and golden files
+    pub fn id(self) -> &'static str {
+        match self {
+            SoakScene::DivisionStorm => "division_storm",
+            SoakScene::BondLattice => "bond_lattice",
+            SoakScene::GenomeHotEdits => "genome_hot_edits",
+            SoakScene::ModeSwitches => "mode_switches",
+            SoakScene::DragInterventions => "drag_interventions",
+        }
+    }
+}
+
+/// How long and how large every scene runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoakBudget {
+    pub ticks: u32,
+    /// Cell capacity of the scenes (the lattice takes what it needs)
+    pub capacity: usize,
+    /// Cells along each edge of the bond lattice
+    pub lattice_side: usize,
+    /// Ticks between invariant checks
+    pub check_interval: u32,
+}
+
+impl SoakBudget {
+    /// Release check: a 22³ lattice has 30,492 bonds
+    pub const FULL: SoakBudget = SoakBudget { ticks: 3000, capacity: 4096, lattice_side: 22, check_interval: 50 };
+    /// Smoke run for tests and quick checks
+    pub const QUICK: SoakBudget = SoakBudget { ticks: 300, capacity: 512, lattice_side: 6, check_interval: 10 };
+}
+
+/// Fingerprint a scene is expected to end with under a budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenFingerprint {
+    pub budget: SoakBudget,
+    /// Run fingerprint as hex
+    pub fingerprint: String,
+}
+
+/// Golden fingerprints by scene
+pub type GoldenFingerprints = BTreeMap<SoakScene, GoldenFingerprint>;
+
+/// Read golden fingerprints; a missing file means none are recorded
+pub fn load_golden(path: &Path) -> crate::error::Result<GoldenFingerprints> {
+    if !path.exists() {
+        return Ok(GoldenFingerprints::new());
+    }
+    crate::error::read_json(path, "soak golden fingerprint")
+}
+
+/// Outcome of one scene
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SoakResult {
+    pub scene: SoakScene,
+    pub passed: bool,
+    /// Ticks run before the scene ended or failed
+    pub ticks: u32,
+    pub seconds: f64,
+    pub cell_count: usize,
+    pub adhesion_count: usize,
+    pub peak_memory_bytes: usize,
+    /// Final run fingerprint as hex
+    pub fingerprint: String,
+    /// Whether the fingerprint matched the golden one (None if none is recorded for the budget)
+    pub golden_match: Option<bool>,
+    pub failures: Vec<String>,
+}
+
+/// Results of a soak run, as exported for CI
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SoakReport {
+    pub version: String,
+    pub budget: SoakBudget,
+    pub passed: bool,
+    pub seconds: f64,
+    pub results: Vec<SoakResult>,
+}
+
+impl SoakReport {
+    pub fn new(budget: SoakBudget, results: Vec<SoakResult>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            budget,
+            passed: results.iter().all(|result| result.passed),
+            seconds: results.iter().map(|result| result.seconds).sum(),
+            results,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Golden file entries for the fingerprints of this run
+    pub fn golden(&self) -> GoldenFingerprints {
+        self.results.iter()
+            .map(|result| (result.scene, GoldenFingerprint { budget: self.budget, fingerprint: result.fingerprint.clone() }))
+            .collect()
+    }
+}
+
+thread_local! {
+    static IN_SOAK_SCENE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Whether this thread is running a soak scene, whose panics are caught and reported
+/// rather than being crashes
+pub fn in_soak_scene() -> bool {
+    IN_SOAK_SCENE.with(|flag| flag.get())
+}
+
+/// Invariant checks accumulated over a scene
+struct Invariants {
+    interval: u32,
+    failures: Vec<String>,
+    peak_memory: usize,
+    /// Memory of a fresh state by (capacity, grid density)
+    fresh_memory: HashMap<(usize, u32), usize>,
+}
+
+impl Invariants {
+    fn new(interval: u32) -> Self {
+        Self { interval: interval.max(1), failures: Vec::new(), peak_memory: 0, fresh_memory: HashMap::new() }
+    }
+
+    fn due(&self, tick: u32) -> bool {
+        tick % self.interval == 0
+    }
+
+    fn failed(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    fn check(&mut self, state: &CanonicalState, tick: u32, label: &str) {
+        let mut fail = |problem: String| self.failures.push(format!("tick {} ({}): {}", tick, label, problem));
+
+        if state.cell_count > state.capacity {
+            fail(format!("{} cells exceed the capacity of {}", state.cell_count, state.capacity));
+            return;
+        }
+        if let Some(problem) = non_finite_value(state) {
+            fail(problem);
+        }
+        for problem in state.adhesion_manager
+            .consistency_problems(&state.adhesion_connections, state.cell_count)
+            .into_iter()
+            .take(5)
+        {
+            fail(problem);
+        }
+
+        let memory = MemoryUsage::of(state).total();
+        let grid_density = state.spatial_grid.grid_dimensions.x;
+        let fresh = *self.fresh_memory.entry((state.capacity, grid_density)).or_insert_with(|| {
+            MemoryUsage::of(&CanonicalState::with_memory_profile(state.capacity, grid_density, state.memory_profile)).total()
+        });
+        if memory > fresh * MEMORY_GROWTH_LIMIT {
+            self.failures.push(format!(
+                "tick {} ({}): memory grew to {} bytes, over {}x the {} bytes of a fresh state",
+                tick, label, memory, MEMORY_GROWTH_LIMIT, fresh,
+            ));
+        }
+        self.peak_memory = self.peak_memory.max(memory);
+    }
+}
+
+/// First NaN or infinite value in the live part of the state
+fn non_finite_value(state: &CanonicalState) -> Option<String> {
+    for i in 0..state.cell_count {
+        let vectors = [
+            ("position", state.positions[i]),
+            ("velocity", state.velocities[i]),
+            ("angular velocity", state.angular_velocities[i]),
+        ];
+        for (name, value) in vectors {
+            if !value.is_finite() {
+                return Some(format!("cell {} has a non-finite {} {:?}", i, name, value));
+            }
+        }
+        let genome_orientation = state.genome_orientations.get(i).copied().unwrap_or(Quat::IDENTITY);
+        for (name, value) in [("rotation", state.rotations[i]), ("genome orientation", genome_orientation)] {
+            if !value.is_finite() {
+                return Some(format!("cell {} has a non-finite {} {:?}", i, name, value));
+            }
+        }
+        for (name, value) in [("mass", state.masses[i]), ("radius", state.radii[i])] {
+            if !value.is_finite() {
+                return Some(format!("cell {} has a non-finite {} {}", i, name, value));
+            }
+        }
+    }
+    let connections = &state.adhesion_connections;
+    (0..connections.active_count)
+        .filter(|&idx| connections.is_active[idx] != 0)
+        .find(|&idx| !connections.anchor_direction_a[idx].is_finite() || !connections.anchor_direction_b[idx].is_finite())
+        .map(|idx| format!("connection {} has a non-finite anchor", idx))
+}
+
+/// Step a state one tick the way Preview (no swimming) or CPU mode does
+fn step(state: &mut CanonicalState, config: &PhysicsConfig, genome: &GenomeData, time: f32, swim: bool) {
+    crate::simulation::cpu_physics::physics_step_with_genome(state, config, genome, time, swim);
+    let max_cells = state.capacity;
+    crate::simulation::cpu_physics::division_step(state, genome, time, config.fixed_timestep, max_cells, 0);
+}
+
+/// One-mode genome that divides quickly and keeps its bonds
+fn splitting_genome(split_interval: f32) -> GenomeData {
+    let mut mode = ModeSettings::new_self_splitting(0, "Soak".to_string());
+    mode.split_mass = 1.0;
+    mode.split_interval = split_interval;
+    mode.nutrient_gain_rate = 5.0;
+    mode.parent_make_adhesion = true;
+    mode.child_a.keep_adhesion = true;
+    mode.child_b.keep_adhesion = true;
+    GenomeData {
+        modes: vec![mode],
+        ..GenomeData::default()
+    }
+}
+
+/// Single cell of the genome's initial mode, as scenes start in the app
+fn colony_state(genome: &GenomeData, config: &PhysicsConfig, capacity: usize) -> CanonicalState {
+    crate::simulation::parameter_sweep::initial_state(genome, config, capacity, 0)
+}
+
+/// Final state and the config it ran with
+type SceneOutcome = (CanonicalState, PhysicsConfig);
+
+fn run_division_storm(budget: &SoakBudget, checks: &mut Invariants, cancel: &AtomicBool) -> SceneOutcome {
+    let genome = splitting_genome(0.2);
+    let config = PhysicsConfig::default();
+    let mut state = colony_state(&genome, &config, budget.capacity);
+    for tick in 0..budget.ticks {
+        if cancel.load(Ordering::Relaxed) || checks.failed() {
+            break;
+        }
+        step(&mut state, &config, &genome, tick as f32 * config.fixed_timestep, false);
+        if checks.due(tick + 1) {
+            checks.check(&state, tick + 1, "colony");
+        }
+    }
+    (state, config)
+}
+
+/// Cubic lattice of `side`³ resting cells bonded to their axis neighbors
+fn lattice_state(side: usize, capacity: usize, genome: &GenomeData, config: &PhysicsConfig) -> CanonicalState {
+    let mut state = CanonicalState::new(capacity.max(side * side * side));
+    let spacing = genome.modes[0].adhesion_settings.rest_length + 2.0;
+    let offset = (side as f32 - 1.0) * spacing * 0.5;
+    let index = |x: usize, y: usize, z: usize| x + side * (y + side * z);
+    for z in 0..side {
+        for y in 0..side {
+            for x in 0..side {
+                let position = Vec3::new(x as f32, y as f32, z as f32) * spacing - Vec3::splat(offset);
+                state.add_cell(position, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                    0, 0, 0.0, f32::MAX, f32::MAX, config.default_stiffness, Quat::IDENTITY, 0);
+            }
+        }
+    }
+    for z in 0..side {
+        for y in 0..side {
+            for x in 0..side {
+                let neighbors = [(x + 1, y, z, Vec3::X), (x, y + 1, z, Vec3::Y), (x, y, z + 1, Vec3::Z)];
+                for (nx, ny, nz, axis) in neighbors {
+                    if nx < side && ny < side && nz < side {
+                        state.adhesion_manager.add_adhesion_with_directions(
+                            &mut state.adhesion_connections,
+                            index(x, y, z),
+                            index(nx, ny, nz),
+                            0,
+                            axis,
+                            -axis,
+                            Vec3::Z,
+                            Vec3::Z,
+                            Quat::IDENTITY,
+                            Quat::IDENTITY,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    state
+}
+
+fn run_bond_lattice(budget: &SoakBudget, checks: &mut Invariants, cancel: &AtomicBool) -> SceneOutcome {
+    let mut genome = splitting_genome(f32::MAX);
+    genome.modes[0].nutrient_gain_rate = 0.0;
+    let config = PhysicsConfig::default();
+    let mut state = lattice_state(budget.lattice_side, budget.capacity, &genome, &config);
+    checks.check(&state, 0, "lattice");
+    for tick in 0..budget.ticks {
+        if cancel.load(Ordering::Relaxed) || checks.failed() {
+            break;
+        }
+        step(&mut state, &config, &genome, tick as f32 * config.fixed_timestep, false);
+        if checks.due(tick + 1) {
+            checks.check(&state, tick + 1, "lattice");
+        }
+    }
+    (state, config)
+}
+
+/// The `edit`th scripted genome edit; cycles through the settings the editor changes live
+fn hot_edit(genome: &mut GenomeData, edit: u32) {
+    let mode_count = genome.modes.len();
+    let mode = &mut genome.modes[0];
+    match edit % 6 {
+        0 => {
+            mode.parent_split_direction.x = (mode.parent_split_direction.x + 37.0) % 180.0 - 90.0;
+            mode.parent_split_direction.y = (mode.parent_split_direction.y + 53.0) % 360.0;
+        }
+        1 => mode.child_b.keep_adhesion = !mode.child_b.keep_adhesion,
+        2 => mode.equatorial_bonds = EquatorialBondRule::ALL[(edit / 6) as usize % EquatorialBondRule::ALL.len()],
+        3 => {
+            // New mode that divides back into the first one
+            if mode_count < 6 {
+                let mut added = ModeSettings::new_self_splitting(mode_count, format!("Soak {}", mode_count));
+                added.split_interval = mode.split_interval * 1.5;
+                added.child_a.mode_number = ModeIndex::new(0);
+                added.child_b.mode_number = ModeIndex::new(0);
+                mode.child_b.mode_number = ModeIndex::new(mode_count);
+                genome.modes.push(added);
+            }
+        }
+        4 => mode.split_interval = if mode.split_interval > 0.3 { mode.split_interval * 0.7 } else { 1.0 },
+        _ => std::mem::swap(&mut mode.child_a.mode_number, &mut mode.child_b.mode_number),
+    }
+}
+
+fn run_genome_hot_edits(budget: &SoakBudget, checks: &mut Invariants, cancel: &AtomicBool) -> SceneOutcome {
+    let mut genome = splitting_genome(0.6);
+    let config = PhysicsConfig::default();
+    let mut state = colony_state(&genome, &config, budget.capacity);
+    for tick in 0..budget.ticks {
+        if cancel.load(Ordering::Relaxed) || checks.failed() {
+            break;
+        }
+        if tick > 0 && tick % HOT_EDIT_INTERVAL == 0 {
+            hot_edit(&mut genome, tick / HOT_EDIT_INTERVAL - 1);
+        }
+        step(&mut state, &config, &genome, tick as f32 * config.fixed_timestep, false);
+        if checks.due(tick + 1) {
+            checks.check(&state, tick + 1, "colony");
+        }
+    }
+    (state, config)
+}
+
+/// Alternates between a preview and a CPU scene through the dormant scene handoff
+///
+/// Every other switch to CPU continues the preview ("Continue in CPU") instead of
+/// resuming the parked CPU scene, so both handoffs and the capacity growth are exercised.
+fn run_mode_switches(budget: &SoakBudget, checks: &mut Invariants, cancel: &AtomicBool) -> SceneOutcome {
+    let genome = splitting_genome(0.5);
+    let config = PhysicsConfig::default();
+
+    let mut preview = crate::simulation::preview_sim::PreviewSimState::default();
+    preview.canonical_state = colony_state(&genome, &config, PREVIEW_CAPACITY);
+    let mut timeline = EventTimeline::default();
+    let mut sim_state = SimulationState::default();
+    let mut main = crate::simulation::cpu_sim::MainSimState::default();
+    main.canonical_state = colony_state(&genome, &config, budget.capacity);
+
+    let mut dormant_cpu = Some(DormantCpuScene::take(&mut main, false));
+    let mut dormant_preview = None;
+    let mut in_cpu = false;
+    let mut switches = 0;
+
+    for tick in 0..budget.ticks {
+        if cancel.load(Ordering::Relaxed) || checks.failed() {
+            break;
+        }
+        if tick > 0 && tick % MODE_SWITCH_INTERVAL == 0 {
+            switches += 1;
+            if in_cpu {
+                dormant_cpu = Some(DormantCpuScene::take(&mut main, false));
+                if let Some(scene) = dormant_preview.take() {
+                    DormantPreviewScene::restore(scene, &mut preview, &mut timeline, &mut sim_state);
+                }
+                checks.check(&preview.canonical_state, tick, "preview resumed");
+            } else {
+                let cpu = if switches % 4 == 1 {
+                    dormant_cpu.take()
+                } else {
+                    match DormantCpuScene::from_preview(&preview, false, budget.capacity) {
+                        Ok(scene) => Some(scene),
+                        Err(e) => {
+                            checks.failures.push(format!("tick {}: continue in CPU failed: {}", tick, e));
+                            None
+                        }
+                    }
+                };
+                dormant_preview = Some(DormantPreviewScene::take(&mut preview, &mut timeline, &sim_state));
+                if let Some(cpu) = cpu {
+                    cpu.restore(&mut main);
+                }
+                checks.check(&main.canonical_state, tick, "CPU resumed");
+            }
+            in_cpu = !in_cpu;
+        }
+
+        let (state, time, swim, label) = if in_cpu {
+            (&mut main.canonical_state, &mut main.simulation_time, true, "CPU")
+        } else {
+            (&mut preview.canonical_state, &mut preview.current_time, false, "preview")
+        };
+        step(state, &config, &genome, *time, swim);
+        *time += config.fixed_timestep;
+        if checks.due(tick + 1) {
+            checks.check(state, tick + 1, label);
+        }
+    }
+
+    let state = if in_cpu { main.canonical_state } else { preview.canonical_state };
+    (state, config)
+}
+
+/// Drags as (start, end) fractions of the budget and the region they grab
+const DRAG_SCRIPT: [(f32, f32, Option<SoftDragRegion>, f32); 3] = [
+    // Region drag with cohesion, circled around the start point
+    (0.1, 0.3, Some(SoftDragRegion::Radius(6.0)), 0.3),
+    // Single cell yanked far away
+    (0.4, 0.5, None, 0.0),
+    // Bond-hop region
+    (0.6, 0.8, Some(SoftDragRegion::Hops(3)), 0.0),
+];
+
+fn run_drag_interventions(budget: &SoakBudget, checks: &mut Invariants, cancel: &AtomicBool) -> SceneOutcome {
+    let genome = splitting_genome(0.5);
+    let config = PhysicsConfig::default();
+    let mut state = colony_state(&genome, &config, budget.capacity);
+    let at = |fraction: f32| (budget.ticks as f32 * fraction) as u32;
+    let mut origin = Vec3::ZERO;
+
+    for tick in 0..budget.ticks {
+        if cancel.load(Ordering::Relaxed) || checks.failed() {
+            break;
+        }
+        for (number, &(start, end, region, cohesion)) in DRAG_SCRIPT.iter().enumerate() {
+            if tick == at(start) && state.cell_count > 0 {
+                // Alternate ends of the colony so different cells get grabbed
+                let grabbed = if number % 2 == 0 { 0 } else { state.cell_count - 1 };
+                let drag = match region {
+                    Some(region) => SoftDrag::around(&state, grabbed, region),
+                    None => SoftDrag::single(&state, grabbed),
+                };
+                origin = drag.target;
+                state.soft_drag = Some(drag.with_cohesion(state.cell_ids[grabbed], cohesion));
+            }
+            if (at(start)..at(end)).contains(&tick) {
+                if let Some(drag) = state.soft_drag.as_mut() {
+                    let progress = (tick - at(start)) as f32 / (at(end) - at(start)).max(1) as f32;
+                    drag.target = match region {
+                        Some(_) => origin + Vec3::new(progress.cos(), progress.sin(), 0.0) * 8.0 * progress,
+                        None => origin + Vec3::X * 40.0,
+                    };
+                }
+            }
+            if tick == at(end) {
+                state.soft_drag = None;
+            }
+        }
+        step(&mut state, &config, &genome, tick as f32 * config.fixed_timestep, false);
+        if checks.due(tick + 1) {
+            checks.check(&state, tick + 1, "colony");
+        }
+    }
+    (state, config)
+}
+
+/// Message of a caught panic
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Run one scene, checking its invariants and comparing against its golden fingerprint
+pub fn run_scene(scene: SoakScene, budget: &SoakBudget, golden: &GoldenFingerprints, cancel: &AtomicBool) -> SoakResult {
+    let started = Instant::now();
+    let mut checks = Invariants::new(budget.check_interval);
+    IN_SOAK_SCENE.with(|flag| flag.set(true));
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let runner = match scene {
+            SoakScene::DivisionStorm => run_division_storm,
+            SoakScene::BondLattice => run_bond_lattice,
+            SoakScene::GenomeHotEdits => run_genome_hot_edits,
+            SoakScene::ModeSwitches => run_mode_switches,
+            SoakScene::DragInterventions => run_drag_interventions,
+        };
+        let outcome = runner(budget, &mut checks, cancel);
+        checks.check(&outcome.0, budget.ticks, "final");
+        outcome
+    }));
+    IN_SOAK_SCENE.with(|flag| flag.set(false));
+
+    let mut failures = checks.failures;
+    let (ticks, cell_count, adhesion_count, fingerprint) = match &outcome {
+        Ok((state, config)) => {
+            let connections = &state.adhesion_connections;
+            let adhesions = connections.is_active[..connections.active_count].iter().filter(|&&active| active != 0).count();
+            (budget.ticks, state.cell_count, adhesions, format!("{:016x}", run_fingerprint(state, config)))
+        }
+        Err(payload) => {
+            failures.push(format!("panicked: {}", panic_message(payload.as_ref())));
+            (0, 0, 0, String::new())
+        }
+    };
+    if cancel.load(Ordering::Relaxed) {
+        failures.push("cancelled".to_string());
+    }
+
+    let golden_match = golden.get(&scene)
+        .filter(|golden| golden.budget == *budget && outcome.is_ok() && failures.is_empty())
+        .map(|golden| golden.fingerprint == fingerprint);
+    if golden_match == Some(false) {
+        failures.push(format!("fingerprint {} differs from the golden {}", fingerprint, golden[&scene].fingerprint));
+    }
+
+    SoakResult {
+        scene,
+        passed: failures.is_empty(),
+        ticks,
+        seconds: started.elapsed().as_secs_f64(),
+        cell_count,
+        adhesion_count,
+        peak_memory_bytes: checks.peak_memory,
+        fingerprint,
+        golden_match,
+        failures,
+    }
+}
+
+/// A soak run on a background thread
+pub struct SoakJob {
+    pub budget: SoakBudget,
+    cancel: Arc<AtomicBool>,
+    finished: Arc<Mutex<Vec<SoakResult>>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SoakJob {
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Soak test window state, the running job and its results
+#[derive(Resource, Default)]
+pub struct SoakHarness {
+    pub window_open: bool,
+    /// Run the quick budget instead of the full one
+    pub quick: bool,
+    pub job: Option<SoakJob>,
+    /// Results so far of the running or last run
+    pub results: Vec<SoakResult>,
+    /// Budget of the running or last run
+    pub run_budget: Option<SoakBudget>,
+    /// Error reading the golden file at the start of the run
+    pub golden_error: Option<String>,
+}
+
+impl SoakHarness {
+    pub fn budget(&self) -> SoakBudget {
+        if self.quick { SoakBudget::QUICK } else { SoakBudget::FULL }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Run every scene in turn on a background thread
+    pub fn start(&mut self) {
+        if let Some(job) = &self.job {
+            job.cancel();
+        }
+        let budget = self.budget();
+        let golden = match load_golden(Path::new(DEFAULT_GOLDEN_PATH)) {
+            Ok(golden) => {
+                self.golden_error = None;
+                golden
+            }
+            Err(e) => {
+                self.golden_error = Some(e.to_string());
+                GoldenFingerprints::new()
+            }
+        };
+        let cancel = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(Mutex::new(Vec::new()));
+        let handle = {
+            let cancel = cancel.clone();
+            let finished = finished.clone();
+            std::thread::spawn(move || {
+                for scene in SoakScene::ALL {
+                    if cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let result = run_scene(scene, &budget, &golden, &cancel);
+                    finished.lock().unwrap().push(result);
+                }
+            })
+        };
+        self.results.clear();
+        self.run_budget = Some(budget);
+        info!("Starting soak run: {} scenes of {} ticks", SoakScene::ALL.len(), budget.ticks);
+        self.job = Some(SoakJob { budget, cancel, finished, handle: Some(handle) });
+    }
+
+    /// Report of the finished run
+    pub fn report(&self) -> Option<SoakReport> {
+        if self.job.is_some() || self.results.is_empty() {
+            return None;
+        }
+        Some(SoakReport::new(self.run_budget?, self.results.clone()))
+    }
+}
+
+/// Collect finished scenes and clean up the completed job
+fn poll_soak_run(mut harness: ResMut<SoakHarness>) {
+    let harness = &mut *harness;
+    let Some(job) = &mut harness.job else {
+        return;
+    };
+    harness.results.extend(job.finished.lock().unwrap().drain(..));
+
+    if job.handle.as_ref().is_some_and(|handle| handle.is_finished()) {
+        if let Some(handle) = job.handle.take() {
+            let _ = handle.join();
+        }
+        harness.results.extend(job.finished.lock().unwrap().drain(..));
+        let failed = harness.results.iter().filter(|result| !result.passed).count();
+        info!("Soak run finished: {} of {} scenes failed", failed, harness.results.len());
+        harness.job = None;
+    }
+}
+
+/// Headless soak run for CI
+///
+/// `--soak [--soak-quick] [--soak-report <file>] [--soak-golden <file>] [--soak-record-golden]`
+/// runs every scene, prints a pass/fail line per scene and writes the JSON report if
+/// asked. Fingerprints are compared with the golden file (`soak_golden.json` by default)
+/// where it has an entry for the budget; `--soak-record-golden` writes this run's
+/// fingerprints to it instead. Returns the process exit code (0 all passed, 1 a scene
+/// failed, 2 bad arguments or files), or None when `--soak` is absent.
+pub fn run_cli(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let mut soak = false;
+    let mut budget = SoakBudget::FULL;
+    let mut report_path = None;
+    let mut golden_path = PathBuf::from(DEFAULT_GOLDEN_PATH);
+    let mut record_golden = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--soak" => soak = true,
+            "--soak-quick" => budget = SoakBudget::QUICK,
+            "--soak-report" => report_path = args.next().map(PathBuf::from),
+            "--soak-golden" => golden_path = args.next().map(PathBuf::from).unwrap_or(golden_path),
+            "--soak-record-golden" => record_golden = true,
+            _ => {}
+        }
+    }
+    if !soak {
+        return None;
+    }
+
+    let golden = if record_golden {
+        GoldenFingerprints::new()
+    } else {
+        match load_golden(&golden_path) {
+            Ok(golden) => golden,
+            Err(e) => {
+                eprintln!("Could not read golden fingerprints from {}: {}", golden_path.display(), e);
+                return Some(2);
+            }
+        }
+    };
+
+    let cancel = AtomicBool::new(false);
+    let results: Vec<SoakResult> = SoakScene::ALL.iter().map(|&scene| {
+        let result = run_scene(scene, &budget, &golden, &cancel);
+        let golden = match result.golden_match {
+            Some(true) => "golden match",
+            Some(false) => "golden MISMATCH",
+            None => "no golden",
+        };
+        println!(
+            "{} {:<20} {:>6} ticks {:>8.2}s {:>6} cells {:>7} bonds {} ({})",
+            if result.passed { "PASS" } else { "FAIL" },
+            scene.name(), result.ticks, result.seconds, result.cell_count, result.adhesion_count,
+            result.fingerprint, golden,
+        );
+        for failure in &result.failures {
+            println!("     {}", failure);
+        }
+        result
+    }).collect();
+    let report = SoakReport::new(budget, results);
+
+    if let Some(path) = &report_path {
+        if let Err(e) = crate::error::write_json(path, &report, "soak report") {
+            eprintln!("Could not write the soak report: {}", e);
+            return Some(2);
+        }
+    }
+    if record_golden {
+        let mut recorded = load_golden(&golden_path).unwrap_or_default();
+        recorded.extend(report.golden());
+        if let Err(e) = crate::error::write_json(&golden_path, &recorded, "soak golden fingerprints") {
+            eprintln!("Could not write golden fingerprints: {}", e);
+            return Some(2);
+        }
+        println!("Recorded golden fingerprints in {}", golden_path.display());
+    }
+    Some(if report.passed { 0 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_soak_passes_and_reproduces_fingerprints() {
+        let cancel = AtomicBool::new(false);
+        let first: Vec<SoakResult> = SoakScene::ALL.iter()
+            .map(|&scene| run_scene(scene, &SoakBudget::QUICK, &GoldenFingerprints::new(), &cancel))
+            .collect();
+        for result in &first {
+            assert!(result.passed, "{} failed: {:?}", result.scene.name(), result.failures);
+            assert_eq!(result.golden_match, None);
+        }
+
+        // Every scene is scripted, so a second run reproduces the fingerprints and
+        // matches them as golden values
+        let report = SoakReport::new(SoakBudget::QUICK, first);
+        let golden = report.golden();
+        for scene in SoakScene::ALL {
+            let again = run_scene(scene, &SoakBudget::QUICK, &golden, &cancel);
+            assert_eq!(again.golden_match, Some(true), "{} is not reproducible", scene.name());
+        }
+    }
+
+    #[test]
+    fn test_scene_sizes_follow_the_budget() {
+        let genome = splitting_genome(f32::MAX);
+        let config = PhysicsConfig::default();
+        let state = lattice_state(22, 0, &genome, &config);
+        let bonds = state.adhesion_connections.active_count;
+        assert_eq!(bonds, 3 * 22 * 22 * 21);
+        assert!(bonds >= 30_000);
+        assert!(state.adhesion_manager.consistency_problems(&state.adhesion_connections, state.cell_count).is_empty());
+
+        let cancel = AtomicBool::new(false);
+        let storm = run_scene(SoakScene::DivisionStorm, &SoakBudget::QUICK, &GoldenFingerprints::new(), &cancel);
+        assert!(storm.cell_count > 1 && storm.cell_count <= SoakBudget::QUICK.capacity);
+    }
+
+    #[test]
+    fn test_failures_are_reported() {
+        let mut checks = Invariants::new(1);
+        let genome = splitting_genome(1.0);
+        let mut state = colony_state(&genome, &PhysicsConfig::default(), 8);
+        state.positions[0].y = f32::NAN;
+        checks.check(&state, 3, "colony");
+        assert_eq!(checks.failures.len(), 1);
+        assert!(checks.failures[0].starts_with("tick 3 (colony): cell 0 has a non-finite position"));
+
+        // A mismatched golden fingerprint fails the scene
+        let cancel = AtomicBool::new(false);
+        let mut golden = GoldenFingerprints::new();
+        golden.insert(SoakScene::DivisionStorm, GoldenFingerprint { budget: SoakBudget::QUICK, fingerprint: "0".repeat(16) });
+        let result = run_scene(SoakScene::DivisionStorm, &SoakBudget::QUICK, &golden, &cancel);
+        assert_eq!(result.golden_match, Some(false));
+        assert!(!result.passed);
+
+        // Reports serialize the scene by its id
+        let json = SoakReport::new(SoakBudget::QUICK, vec![result]).to_json();
+        assert!(json.contains("\"scene\": \"division_storm\""));
+        assert!(json.contains("\"passed\": false"));
+    }
+}
@@ -0,0 +1,360 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::simulation::cpu_physics::CanonicalState;
+
+/// Pull of a soft drag per unit mass and weight
+pub const SOFT_DRAG_STIFFNESS: f32 = 400.0;
+/// Critical damping for `SOFT_DRAG_STIFFNESS`, so the region follows without ringing
+pub const SOFT_DRAG_DAMPING: f32 = 40.0;
+/// Cohesion weight a cell keeps however many bonds it is from the grabbed cell
+///
+/// The rest halves with every hop, so the organism moves as a whole while the grab
+/// point still leads.
+pub const COHESION_FAR_WEIGHT: f32 = 0.5;
+
+/// How the cells moved by a soft drag are chosen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SoftDragRegion {
+    /// Cells within this distance of the grabbed cell, weighted by distance
+    Radius(f32),
+    /// Cells within this many adhesion bonds of the grabbed cell, weighted by hops
+    Hops(u32),
+}
+
+/// A cell pulled by a soft drag
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoftDragCell {
+    pub cell_id: u32,
+    /// Falloff weight, 1 for the grabbed cell
+    pub weight: f32,
+    /// Offset from the grabbed cell when the drag started
+    pub offset: Vec3,
+}
+
+/// Drag of a falloff-weighted region of cells toward a moving target
+///
+/// Every cell is pulled toward `target + offset` (so the region keeps its shape) by a
+/// spring scaled by its weight. Keyed by cell ID, so the recorded (cell set, weights,
+/// target) stays valid through division and removal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoftDrag {
+    /// Pulled cells, the grabbed cell first
+    pub cells: Vec<SoftDragCell>,
+    /// Where the grabbed cell is pulled to
+    pub target: Vec3,
+    /// Share of the pull spread over the grabbed cell's whole organism
+    pub cohesion: Option<DragCohesion>,
+}
+
+/// Far-field part of a drag: `fraction` of the grab's pull spread over the organism
+///
+/// Each cell connected to the grabbed one by adhesions is pulled by the grabbed cell's
+/// spring error, scaled by a weight that decays with its bond distance. The weights are
+/// normalized to a mass-weighted mean of 1, so the organism as a whole gets `fraction`
+/// of the pull it would get if every cell were grabbed. They are rebuilt only when the
+/// cell or adhesion set changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragCohesion {
+    pub fraction: f32,
+    /// ID of the cell the drag's target belongs to
+    pub grabbed: u32,
+    /// (cell ID, normalized weight) for the organism, breadth-first from the grabbed cell
+    weights: Vec<(u32, f32)>,
+    /// `topology_signature` the weights were built for
+    topology: Option<u64>,
+}
+
+impl DragCohesion {
+    pub fn new(grabbed: u32, fraction: f32) -> Self {
+        Self { fraction: fraction.clamp(0.0, 1.0), grabbed, weights: Vec::new(), topology: None }
+    }
+
+    /// Organism weights, rebuilding them if cells or adhesions changed since the last call
+    pub fn weights(&mut self, state: &CanonicalState, index_by_id: &HashMap<u32, usize>) -> &[(u32, f32)] {
+        let topology = topology_signature(state);
+        if self.topology != Some(topology) {
+            self.topology = Some(topology);
+            self.weights = match index_by_id.get(&self.grabbed) {
+                Some(&grabbed) => organism_weights(state, grabbed),
+                None => Vec::new(),
+            };
+        }
+        &self.weights
+    }
+}
+
+/// Hash of the living cells and active adhesions, which fixes the organism's bond graph
+pub fn topology_signature(state: &CanonicalState) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    state.cell_ids[..state.cell_count].hash(&mut hasher);
+    let connections = &state.adhesion_connections;
+    for slot in 0..connections.is_active.len() {
+        if connections.is_active[slot] != 0 {
+            (slot, connections.cell_a_index[slot], connections.cell_b_index[slot]).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Cohesion weight of every cell in the organism of the cell at index `grabbed`
+///
+/// Falls from 1 at the grabbed cell toward `COHESION_FAR_WEIGHT` by bond distance,
+/// then is scaled so the mass-weighted mean over the organism is 1.
+pub fn organism_weights(state: &CanonicalState, grabbed: usize) -> Vec<(u32, f32)> {
+    let reached = state.adhesion_manager.cells_within_hops(&state.adhesion_connections, grabbed, u32::MAX);
+    let raw: Vec<(usize, f32)> = reached.into_iter()
+        .map(|(i, hops)| (i, COHESION_FAR_WEIGHT + (1.0 - COHESION_FAR_WEIGHT) * 0.5f32.powi(hops.min(64) as i32)))
+        .collect();
+    let mass: f32 = raw.iter().map(|&(i, _)| state.masses[i]).sum();
+    let weighted_mass: f32 = raw.iter().map(|&(i, weight)| state.masses[i] * weight).sum();
+    let scale = if weighted_mass > 0.0 { mass / weighted_mass } else { 1.0 };
+    raw.into_iter().map(|(i, weight)| (state.cell_ids[i], weight * scale)).collect()
+}
+
+/// Smooth falloff from 1 at `t = 0` to 0 at `t = 1`
+pub fn falloff(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    (1.0 - t * t).powi(2)
+}
+
+impl SoftDrag {
+    /// Region around the cell at index `grabbed`, starting with the target on that cell
+    pub fn around(state: &CanonicalState, grabbed: usize, region: SoftDragRegion) -> Self {
+        let origin = state.positions[grabbed];
+        let weighted: Vec<(usize, f32)> = match region {
+            SoftDragRegion::Radius(radius) => {
+                let radius = radius.max(f32::EPSILON);
+                (0..state.cell_count)
+                    .map(|i| (i, falloff(state.positions[i].distance(origin) / radius)))
+                    .collect()
+            }
+            SoftDragRegion::Hops(max_hops) => state.adhesion_manager
+                .cells_within_hops(&state.adhesion_connections, grabbed, max_hops)
+                .into_iter()
+                .map(|(i, hops)| (i, falloff(hops as f32 / (max_hops + 1) as f32)))
+                .collect(),
+        };
+
+        let mut cells: Vec<SoftDragCell> = weighted.into_iter()
+            .filter(|&(i, weight)| weight > 0.0 || i == grabbed)
+            .map(|(i, weight)| SoftDragCell {
+                cell_id: state.cell_ids[i],
+                weight: if i == grabbed { 1.0 } else { weight },
+                offset: state.positions[i] - origin,
+            })
+            .collect();
+        if let Some(first) = cells.iter().position(|cell| cell.cell_id == state.cell_ids[grabbed]) {
+            cells[..=first].rotate_right(1);
+        }
+        Self { cells, target: origin, cohesion: None }
+    }
+
+    /// Drag of just the cell at index `grabbed`, at full strength
+    pub fn single(state: &CanonicalState, grabbed: usize) -> Self {
+        Self {
+            cells: vec![SoftDragCell { cell_id: state.cell_ids[grabbed], weight: 1.0, offset: Vec3::ZERO }],
+            target: state.positions[grabbed],
+            cohesion: None,
+        }
+    }
+
+    /// Spread `fraction` of the pull over the grabbed cell's organism (0 turns cohesion off)
+    pub fn with_cohesion(mut self, grabbed_id: u32, fraction: f32) -> Self {
+        self.cohesion = (fraction > 0.0).then(|| DragCohesion::new(grabbed_id, fraction));
+        self
+    }
+
+    /// Cohesion fraction, 0 without cohesion
+    pub fn cohesion_fraction(&self) -> f32 {
+        self.cohesion.as_ref().map_or(0.0, |cohesion| cohesion.fraction)
+    }
+
+    /// Where a cell of the region is pulled to
+    pub fn goal(&self, cell: &SoftDragCell) -> Vec3 {
+        self.target + cell.offset
+    }
+}
+
+impl CanonicalState {
+    /// Slot of every living cell by ID
+    pub fn index_by_id(&self) -> HashMap<u32, usize> {
+        self.cell_ids[..self.cell_count].iter().enumerate().map(|(idx, &id)| (id, idx)).collect()
+    }
+
+    /// Add the soft drag's pull to the forces (called before the velocity update of every physics step)
+    ///
+    /// With cohesion, the region keeps `1 - fraction` of its pull and the organism shares
+    /// the rest, driven by the grabbed cell's distance to the target.
+    pub fn apply_soft_drag_forces(&mut self) {
+        let Some(mut drag) = self.soft_drag.take() else {
+            return;
+        };
+
+        let index_by_id = self.index_by_id();
+        let local = 1.0 - drag.cohesion_fraction();
+        for cell in &drag.cells {
+            let Some(&idx) = index_by_id.get(&cell.cell_id) else {
+                continue;
+            };
+            let pull = SOFT_DRAG_STIFFNESS * (drag.goal(cell) - self.positions[idx])
+                - SOFT_DRAG_DAMPING * self.velocities[idx];
+            self.forces[idx] += pull * self.masses[idx] * cell.weight * local;
+        }
+
+        if let Some(cohesion) = drag.cohesion.as_mut() {
+            if let Some(&grabbed) = index_by_id.get(&cohesion.grabbed) {
+                let error = drag.target - self.positions[grabbed];
+                let fraction = cohesion.fraction;
+                for &(cell_id, weight) in cohesion.weights(self, &index_by_id) {
+                    let Some(&idx) = index_by_id.get(&cell_id) else {
+                        continue;
+                    };
+                    let pull = SOFT_DRAG_STIFFNESS * error - SOFT_DRAG_DAMPING * self.velocities[idx];
+                    self.forces[idx] += pull * self.masses[idx] * weight * fraction;
+                }
+            }
+        }
+        self.soft_drag = Some(drag);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+    use crate::simulation::PhysicsConfig;
+
+    const CHAIN_LENGTH: usize = 10;
+
+    /// `length` touching cells in a row along X, each bonded to the next
+    fn chain(length: usize) -> CanonicalState {
+        let mut state = CanonicalState::new(length + 6);
+        for i in 0..length {
+            add_test_cell(&mut state, Vec3::X * i as f32, Quat::IDENTITY, 1.0, 0.5, 0);
+        }
+        for i in 0..length - 1 {
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, i, i + 1, 0,
+                Vec3::X, -Vec3::X, Vec3::Y, Vec3::Y, Quat::IDENTITY, Quat::IDENTITY,
+            ).expect("adhesion slot");
+        }
+        state
+    }
+
+    /// Largest spring force seen on any bond while cell 0 is dragged sideways
+    fn peak_bond_force(soft: Option<SoftDragRegion>) -> f32 {
+        let genome = never_split_genome(&["Chain"]);
+        let settings = genome.modes[0].adhesion_settings.clone();
+        let config = PhysicsConfig::default();
+        let mut state = chain(CHAIN_LENGTH);
+
+        let speed = 8.0;
+        if let Some(region) = soft {
+            state.soft_drag = Some(SoftDrag::around(&state, 0, region));
+        }
+
+        let mut peak: f32 = 0.0;
+        for tick in 0..64 {
+            let time = tick as f32 * config.fixed_timestep;
+            let target = Vec3::Z * speed * (tick + 1) as f32 * config.fixed_timestep;
+            match state.soft_drag.as_mut() {
+                Some(drag) => drag.target = target,
+                // A hard drag puts the grabbed cell on the cursor every tick
+                None => {
+                    state.positions[0] = target;
+                    state.prev_positions[0] = target;
+                    state.velocities[0] = Vec3::ZERO;
+                }
+            }
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+
+            for i in 0..CHAIN_LENGTH - 1 {
+                let stretch = state.positions[i].distance(state.positions[i + 1]) - settings.rest_length;
+                peak = peak.max(settings.linear_spring_stiffness * stretch);
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn test_soft_drag_keeps_chain_bonds_that_a_hard_drag_overloads() {
+        let break_force = ModeSettings::new_self_splitting(0, String::new()).adhesion_settings.break_force;
+
+        let hard = peak_bond_force(None);
+        let soft = peak_bond_force(Some(SoftDragRegion::Radius(30.0)));
+        let soft_by_hops = peak_bond_force(Some(SoftDragRegion::Hops(CHAIN_LENGTH as u32)));
+
+        assert!(hard > break_force, "hard drag should overload a bond (peak {})", hard);
+        assert!(soft < break_force, "soft drag overloaded a bond (peak {})", soft);
+        assert!(soft_by_hops < break_force, "hop-limited soft drag overloaded a bond (peak {})", soft_by_hops);
+    }
+
+    #[test]
+    fn test_region_weights_fall_off_from_the_grabbed_cell() {
+        let state = chain(CHAIN_LENGTH);
+
+        let drag = SoftDrag::around(&state, 0, SoftDragRegion::Hops(3));
+        let weights: Vec<f32> = drag.cells.iter().map(|cell| cell.weight).collect();
+        assert_eq!(drag.cells.len(), 4);
+        assert_eq!(weights[0], 1.0);
+        assert!(weights.windows(2).all(|pair| pair[1] < pair[0]));
+
+        let drag = SoftDrag::around(&state, 0, SoftDragRegion::Radius(2.5));
+        assert_eq!(drag.cells.len(), 3);
+    }
+
+    /// Sideways distance moved by (grabbed end, far end) of a 50-cell chain after 100 ticks
+    fn chain_ends_moved(cohesion: f32) -> (f32, f32) {
+        let genome = never_split_genome(&["Chain"]);
+        let config = PhysicsConfig::default();
+        let length = 50;
+        let mut state = chain(length);
+        let (grabbed_id, far_id) = (state.cell_ids[0], state.cell_ids[length - 1]);
+        let mut drag = SoftDrag::single(&state, 0).with_cohesion(grabbed_id, cohesion);
+        drag.target += Vec3::Z * 10.0;
+        state.soft_drag = Some(drag);
+
+        for tick in 0..100 {
+            let time = tick as f32 * config.fixed_timestep;
+            crate::simulation::cpu_physics::physics_step_with_genome(&mut state, &config, &genome, time, false);
+        }
+        let index_by_id = state.index_by_id();
+        let moved = |cell_id: u32| state.positions[index_by_id[&cell_id]].z;
+        (moved(grabbed_id), moved(far_id))
+    }
+
+    #[test]
+    fn test_cohesion_moves_the_whole_organism() {
+        let (grabbed, far) = chain_ends_moved(0.8);
+        assert!(far >= 0.5 * grabbed, "far end moved {} while the grabbed end moved {}", far, grabbed);
+
+        let (grabbed_alone, far_alone) = chain_ends_moved(0.0);
+        assert!(grabbed_alone > 0.0);
+        assert!(far_alone < 0.25 * grabbed_alone, "far end moved {} without cohesion", far_alone);
+        assert!(far_alone < 0.25 * far);
+    }
+
+    #[test]
+    fn test_organism_weights_decay_and_refresh_with_topology() {
+        let mut state = chain(CHAIN_LENGTH);
+        let weights = organism_weights(&state, 0);
+        assert_eq!(weights.len(), CHAIN_LENGTH);
+        assert!(weights.windows(2).all(|pair| pair[1].1 <= pair[0].1));
+        let mean: f32 = weights.iter().map(|&(_, weight)| weight).sum::<f32>() / CHAIN_LENGTH as f32;
+        assert!((mean - 1.0).abs() < 1e-5);
+
+        let mut cohesion = DragCohesion::new(state.cell_ids[0], 0.5);
+        let index_by_id = state.index_by_id();
+        assert_eq!(cohesion.weights(&state, &index_by_id).len(), CHAIN_LENGTH);
+
+        // Cutting the chain in the middle leaves the grabbed cell's half
+        let bond = state.adhesion_manager.get_connections_for_cell(&state.adhesion_connections, 4)
+            .into_iter()
+            .find(|&slot| state.adhesion_connections.cell_b_index[slot] == 5)
+            .expect("bond 4-5");
+        assert!(state.adhesion_manager.remove_adhesion(&mut state.adhesion_connections, bond));
+        assert_eq!(cohesion.weights(&state, &index_by_id).len(), 5);
+    }
+}
@@ -0,0 +1,1080 @@
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::genome::{CurrentGenome, GenomeData};
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::fingerprint::StableHasher;
+use crate::simulation::{CanonicalState, PhysicsConfig, SimulationMode, SimulationState};
+
+/// Port spectators connect to unless `--spectate` names another
+pub const DEFAULT_SPECTATOR_PORT: u16 = 8766;
+
+/// Command line flag that starts the app as a read-only spectator of a host
+pub const SPECTATE_FLAG: &str = "--spectate";
+
+/// Positions are sent in steps of this many world units
+const POSITION_QUANTUM: f32 = 1.0 / 256.0;
+
+/// Radii are sent in steps of this many world units
+const RADIUS_QUANTUM: f32 = 1.0 / 1024.0;
+
+/// Frames longer than this are taken for a corrupt stream
+const MAX_FRAME_BYTES: usize = 64 << 20;
+
+/// A spectator that can't take a frame within this long is dropped (it reconnects for a keyframe)
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the host thread looks for new spectators while no update is due
+const ACCEPT_POLL: Duration = Duration::from_millis(50);
+
+/// How often the client thread checks whether it should stop while the host is quiet
+const READ_POLL: Duration = Duration::from_millis(100);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+const FRAME_GENOME: u8 = 0;
+const FRAME_KEYFRAME: u8 = 1;
+const FRAME_DELTA: u8 = 2;
+
+/// Delta flags of a changed cell
+const CELL_NEW: u8 = 1;
+const CELL_MOVED_SHORT: u8 = 2;
+const CELL_MOVED_FULL: u8 = 4;
+const CELL_RESIZED: u8 = 8;
+const CELL_REMODED: u8 = 16;
+
+/// Plugin for streaming the main simulation to read-only spectators and for
+/// spectating another machine (`--spectate <host>`)
+///
+/// The host only copies the state a few times per second; quantizing, diffing and
+/// sending happen on the host thread, which drops updates it can't keep up with.
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpectatorSettings>()
+            .init_resource::<SpectatorHost>()
+            .add_systems(Startup, enter_spectated_scene)
+            .add_systems(PreUpdate, apply_spectated_scene.run_if(resource_exists::<SpectatorClient>))
+            .add_systems(Update, (
+                sync_spectator_host,
+                publish_spectator_snapshot.after(sync_spectator_host),
+                stop_spectator_host_on_exit,
+            ));
+    }
+}
+
+/// Where the spectator host listens and how often it sends (persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct SpectatorSettings {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    /// Scene updates sent per real second
+    pub updates_per_second: f32,
+}
+
+impl Default for SpectatorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // Spectators watch from other machines, so every interface once enabled
+            bind_address: "0.0.0.0".to_string(),
+            port: DEFAULT_SPECTATOR_PORT,
+            updates_per_second: 15.0,
+        }
+    }
+}
+
+impl SpectatorSettings {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.bind_address, self.port)
+    }
+}
+
+/// Host address given after `--spectate`, with the default port added when it has none
+pub fn spectate_address(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    args.by_ref().find(|arg| arg == SPECTATE_FLAG)?;
+    let host = args.next()?;
+    let has_port = host.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    Some(if has_port { host } else { format!("{}:{}", host, DEFAULT_SPECTATOR_PORT) })
+}
+
+/// Cell as spectators see it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpectatorCell {
+    /// In steps of `POSITION_QUANTUM`
+    pub position: [i32; 3],
+    /// In steps of `RADIUS_QUANTUM`
+    pub radius: u16,
+    pub mode: u16,
+}
+
+impl SpectatorCell {
+    fn quantize(position: Vec3, radius: f32, mode: usize) -> Self {
+        let step = |value: f32| (value / POSITION_QUANTUM).round() as i32;
+        Self {
+            position: [step(position.x), step(position.y), step(position.z)],
+            radius: (radius / RADIUS_QUANTUM).round() as u16,
+            mode: mode.min(u16::MAX as usize) as u16,
+        }
+    }
+
+    pub fn position(&self) -> Vec3 {
+        Vec3::new(self.position[0] as f32, self.position[1] as f32, self.position[2] as f32) * POSITION_QUANTUM
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius as f32 * RADIUS_QUANTUM
+    }
+}
+
+/// Quantized scene of one tick, as the host sends it and spectators rebuild it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpectatorScene {
+    pub tick: u32,
+    pub time: f32,
+    pub cells: BTreeMap<u32, SpectatorCell>,
+    /// Bonded cell IDs, lower ID first
+    pub bonds: BTreeSet<(u32, u32)>,
+}
+
+impl SpectatorScene {
+    /// Quantize `state` at `tick`
+    pub fn capture(state: &CanonicalState, tick: u32, time: f32) -> Self {
+        SpectatorSnapshot::capture(state, tick, time, None).scene()
+    }
+
+    /// Stable hash of everything a spectator reconstructs
+    ///
+    /// Sent with every keyframe and delta; a spectator whose scene hashes differently
+    /// has missed something and reconnects for a keyframe.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(self.tick as u64);
+        hasher.write_u64(self.cells.len() as u64);
+        for (&id, cell) in &self.cells {
+            hasher.write_u64(id as u64);
+            for value in cell.position {
+                hasher.write(&value.to_le_bytes());
+            }
+            hasher.write(&cell.radius.to_le_bytes());
+            hasher.write(&cell.mode.to_le_bytes());
+        }
+        hasher.write_u64(self.bonds.len() as u64);
+        for &(a, b) in &self.bonds {
+            hasher.write_u64(((a as u64) << 32) | b as u64);
+        }
+        hasher.0
+    }
+}
+
+/// Copy of the cells and bonds taken on the main thread; quantized on the host thread
+pub struct SpectatorSnapshot {
+    pub tick: u32,
+    pub time: f32,
+    ids: Vec<u32>,
+    positions: Vec<Vec3>,
+    radii: Vec<f32>,
+    modes: Vec<usize>,
+    bonds: Vec<(u32, u32)>,
+    /// Set when the genome changed since the last snapshot the host thread took
+    genome: Option<GenomeData>,
+}
+
+impl SpectatorSnapshot {
+    pub fn capture(state: &CanonicalState, tick: u32, time: f32, genome: Option<GenomeData>) -> Self {
+        let count = state.cell_count;
+        let connections = &state.adhesion_connections;
+        let bonds = (0..connections.is_active.len())
+            .filter(|&i| connections.is_active[i] != 0)
+            .filter(|&i| connections.cell_a_index[i] < count && connections.cell_b_index[i] < count)
+            .map(|i| {
+                let (a, b) = (state.cell_ids[connections.cell_a_index[i]], state.cell_ids[connections.cell_b_index[i]]);
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        Self {
+            tick,
+            time,
+            ids: state.cell_ids[..count].to_vec(),
+            positions: state.positions[..count].to_vec(),
+            radii: state.radii[..count].to_vec(),
+            modes: state.mode_indices[..count].to_vec(),
+            bonds,
+            genome,
+        }
+    }
+
+    pub fn scene(&self) -> SpectatorScene {
+        SpectatorScene {
+            tick: self.tick,
+            time: self.time,
+            cells: (0..self.ids.len())
+                .map(|i| (self.ids[i], SpectatorCell::quantize(self.positions[i], self.radii[i], self.modes[i])))
+                .collect(),
+            bonds: self.bonds.iter().copied().collect(),
+        }
+    }
+}
+
+/// Little-endian frame: payload length, kind, payload
+struct FrameWriter(Vec<u8>);
+
+impl FrameWriter {
+    fn new(kind: u8) -> Self {
+        let mut bytes = vec![0; 4];
+        bytes.push(kind);
+        Self(bytes)
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn header(&mut self, scene: &SpectatorScene) {
+        self.put(&scene.tick.to_le_bytes());
+        self.put(&scene.time.to_le_bytes());
+        self.put(&scene.fingerprint().to_le_bytes());
+    }
+
+    fn bonds<'a>(&mut self, bonds: impl ExactSizeIterator<Item = &'a (u32, u32)>) {
+        self.put(&(bonds.len() as u32).to_le_bytes());
+        for &(a, b) in bonds {
+            self.put(&a.to_le_bytes());
+            self.put(&b.to_le_bytes());
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let length = (self.0.len() - 4) as u32;
+        self.0[..4].copy_from_slice(&length.to_le_bytes());
+        self.0
+    }
+}
+
+fn encode_genome(genome: &GenomeData) -> Vec<u8> {
+    let mut frame = FrameWriter::new(FRAME_GENOME);
+    // A genome always serializes (the same value is saved to files)
+    frame.put(&serde_json::to_vec(genome).unwrap_or_default());
+    frame.finish()
+}
+
+fn encode_keyframe(scene: &SpectatorScene) -> Vec<u8> {
+    let mut frame = FrameWriter::new(FRAME_KEYFRAME);
+    frame.header(scene);
+    frame.put(&(scene.cells.len() as u32).to_le_bytes());
+    for (&id, cell) in &scene.cells {
+        frame.put(&id.to_le_bytes());
+        for value in cell.position {
+            frame.put(&value.to_le_bytes());
+        }
+        frame.put(&cell.radius.to_le_bytes());
+        frame.put(&cell.mode.to_le_bytes());
+    }
+    frame.bonds(scene.bonds.iter());
+    frame.finish()
+}
+
+/// Changes from `previous` to `scene`
+///
+/// Small moves go as 16-bit offsets from the previous position; unchanged cells
+/// and bonds cost nothing.
+fn encode_delta(previous: &SpectatorScene, scene: &SpectatorScene) -> Vec<u8> {
+    let mut frame = FrameWriter::new(FRAME_DELTA);
+    frame.header(scene);
+
+    let removed: Vec<u32> = previous.cells.keys().copied().filter(|id| !scene.cells.contains_key(id)).collect();
+    frame.put(&(removed.len() as u32).to_le_bytes());
+    for id in removed {
+        frame.put(&id.to_le_bytes());
+    }
+
+    let mut changed = FrameWriter(Vec::new());
+    let mut changed_count = 0u32;
+    for (&id, cell) in &scene.cells {
+        let old = previous.cells.get(&id);
+        let offsets = old.map(|old| [0, 1, 2].map(|axis| cell.position[axis] as i64 - old.position[axis] as i64));
+        let mut flags = 0;
+        match (old, offsets) {
+            (Some(old), Some(offsets)) => {
+                if offsets != [0; 3] {
+                    let short = offsets.iter().all(|&offset| i16::try_from(offset).is_ok());
+                    flags |= if short { CELL_MOVED_SHORT } else { CELL_MOVED_FULL };
+                }
+                if old.radius != cell.radius {
+                    flags |= CELL_RESIZED;
+                }
+                if old.mode != cell.mode {
+                    flags |= CELL_REMODED;
+                }
+            }
+            _ => flags = CELL_NEW | CELL_MOVED_FULL | CELL_RESIZED | CELL_REMODED,
+        }
+        if flags == 0 {
+            continue;
+        }
+        changed_count += 1;
+        changed.put(&id.to_le_bytes());
+        changed.put(&[flags]);
+        if flags & CELL_MOVED_SHORT != 0 {
+            for offset in offsets.unwrap_or_default() {
+                changed.put(&(offset as i16).to_le_bytes());
+            }
+        }
+        if flags & CELL_MOVED_FULL != 0 {
+            for value in cell.position {
+                changed.put(&value.to_le_bytes());
+            }
+        }
+        if flags & CELL_RESIZED != 0 {
+            changed.put(&cell.radius.to_le_bytes());
+        }
+        if flags & CELL_REMODED != 0 {
+            changed.put(&cell.mode.to_le_bytes());
+        }
+    }
+    frame.put(&changed_count.to_le_bytes());
+    frame.put(&changed.0);
+
+    let broken: Vec<&(u32, u32)> = previous.bonds.difference(&scene.bonds).collect();
+    frame.bonds(broken.into_iter());
+    let formed: Vec<&(u32, u32)> = scene.bonds.difference(&previous.bonds).collect();
+    frame.bonds(formed.into_iter());
+    frame.finish()
+}
+
+/// Reads a frame payload front to back
+struct FrameReader<'a>(&'a [u8]);
+
+impl FrameReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let (bytes, rest) = self.0.split_first_chunk::<N>().ok_or("truncated frame")?;
+        self.0 = rest;
+        Ok(*bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, String> {
+        Ok(i16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn bond(&mut self) -> Result<(u32, u32), String> {
+        Ok((self.u32()?, self.u32()?))
+    }
+}
+
+/// Split the first complete frame off `buffer`: (kind and payload, bytes used)
+fn next_frame(buffer: &[u8]) -> Result<Option<(&[u8], usize)>, String> {
+    let Some(length) = buffer.first_chunk::<4>().map(|bytes| u32::from_le_bytes(*bytes) as usize) else {
+        return Ok(None);
+    };
+    if length == 0 || length > MAX_FRAME_BYTES {
+        return Err(format!("frame of {} bytes", length));
+    }
+    Ok(buffer.get(4..4 + length).map(|frame| (frame, 4 + length)))
+}
+
+/// Apply one frame to the spectator's scene; returns the genome a genome frame carried
+///
+/// Fails when the frame is malformed or the rebuilt scene doesn't match the host's
+/// fingerprint, after which the scene must be rebuilt from a keyframe.
+fn decode_frame(scene: &mut Option<SpectatorScene>, frame: &[u8]) -> Result<Option<GenomeData>, String> {
+    let (&kind, payload) = frame.split_first().ok_or("empty frame")?;
+    if kind == FRAME_GENOME {
+        return serde_json::from_slice(payload).map(Some).map_err(|e| format!("genome: {}", e));
+    }
+
+    let mut reader = FrameReader(payload);
+    let tick = reader.u32()?;
+    let time = reader.f32()?;
+    let fingerprint = reader.u64()?;
+    match kind {
+        FRAME_KEYFRAME => {
+            let mut keyframe = SpectatorScene { tick, time, ..Default::default() };
+            for _ in 0..reader.u32()? {
+                let id = reader.u32()?;
+                let position = [reader.i32()?, reader.i32()?, reader.i32()?];
+                let cell = SpectatorCell { position, radius: reader.u16()?, mode: reader.u16()? };
+                keyframe.cells.insert(id, cell);
+            }
+            for _ in 0..reader.u32()? {
+                keyframe.bonds.insert(reader.bond()?);
+            }
+            *scene = Some(keyframe);
+        }
+        FRAME_DELTA => {
+            let scene = scene.as_mut().ok_or("delta before the first keyframe")?;
+            scene.tick = tick;
+            scene.time = time;
+            for _ in 0..reader.u32()? {
+                scene.cells.remove(&reader.u32()?);
+            }
+            for _ in 0..reader.u32()? {
+                let id = reader.u32()?;
+                let flags = reader.u8()?;
+                let cell = if flags & CELL_NEW != 0 {
+                    scene.cells.entry(id).or_insert(SpectatorCell { position: [0; 3], radius: 0, mode: 0 })
+                } else {
+                    scene.cells.get_mut(&id).ok_or_else(|| format!("change to unknown cell {}", id))?
+                };
+                if flags & CELL_MOVED_SHORT != 0 {
+                    for axis in 0..3 {
+                        cell.position[axis] = cell.position[axis].wrapping_add(reader.i16()? as i32);
+                    }
+                }
+                if flags & CELL_MOVED_FULL != 0 {
+                    cell.position = [reader.i32()?, reader.i32()?, reader.i32()?];
+                }
+                if flags & CELL_RESIZED != 0 {
+                    cell.radius = reader.u16()?;
+                }
+                if flags & CELL_REMODED != 0 {
+                    cell.mode = reader.u16()?;
+                }
+            }
+            for _ in 0..reader.u32()? {
+                scene.bonds.remove(&reader.bond()?);
+            }
+            for _ in 0..reader.u32()? {
+                scene.bonds.insert(reader.bond()?);
+            }
+        }
+        _ => return Err(format!("unknown frame kind {}", kind)),
+    }
+
+    let rebuilt = scene.as_ref().map(SpectatorScene::fingerprint);
+    if rebuilt != Some(fingerprint) {
+        return Err(format!("tick {} does not match the host's fingerprint", tick));
+    }
+    Ok(None)
+}
+
+/// Counters shared with the host thread
+#[derive(Default)]
+struct HostCounters {
+    spectators: AtomicUsize,
+    bytes_sent: AtomicU64,
+}
+
+/// Host thread sending scene updates until dropped
+struct HostThread {
+    address: SocketAddr,
+    sender: Option<Sender<SpectatorSnapshot>>,
+    thread: Option<JoinHandle<()>>,
+    counters: Arc<HostCounters>,
+}
+
+impl HostThread {
+    fn start(address: &str) -> crate::error::Result<Self> {
+        let unavailable = |reason: String| crate::error::BioSpheresError::Server { address: address.to_string(), reason };
+        let listener = TcpListener::bind(address).map_err(|e| unavailable(e.to_string()))?;
+        listener.set_nonblocking(true).map_err(|e| unavailable(e.to_string()))?;
+        let bound = listener.local_addr().map_err(|e| unavailable(e.to_string()))?;
+
+        // One snapshot in flight: the main thread drops updates while the host thread is busy
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        let counters = Arc::new(HostCounters::default());
+        let thread = {
+            let counters = counters.clone();
+            std::thread::Builder::new()
+                .name("spectator-host".to_string())
+                .spawn(move || run_host(listener, receiver, &counters))
+                .map_err(|e| unavailable(e.to_string()))?
+        };
+        info!("Spectator host listening on {}", bound);
+        Ok(Self { address: bound, sender: Some(sender), thread: Some(thread), counters })
+    }
+}
+
+impl Drop for HostThread {
+    fn drop(&mut self) {
+        // Disconnecting the channel ends the thread
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        info!("Spectator host on {} stopped", self.address);
+    }
+}
+
+struct Spectator {
+    stream: TcpStream,
+    peer: SocketAddr,
+    /// Whether the spectator has the previous scene (otherwise it gets a keyframe)
+    synced: bool,
+}
+
+fn run_host(listener: TcpListener, receiver: Receiver<SpectatorSnapshot>, counters: &HostCounters) {
+    let mut spectators: Vec<Spectator> = Vec::new();
+    let mut genome_frame: Option<Vec<u8>> = None;
+    let mut previous: Option<SpectatorScene> = None;
+    loop {
+        while let Ok((stream, peer)) = listener.accept() {
+            let configured = stream.set_nonblocking(false)
+                .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+                .and_then(|_| stream.set_nodelay(true));
+            match configured {
+                Ok(()) => {
+                    info!("Spectator {} connected", peer);
+                    spectators.push(Spectator { stream, peer, synced: false });
+                }
+                Err(e) => warn!("Spectator {} refused: {}", peer, e),
+            }
+        }
+        counters.spectators.store(spectators.len(), Ordering::Relaxed);
+
+        let snapshot = match receiver.recv_timeout(ACCEPT_POLL) {
+            Ok(snapshot) => snapshot,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        let scene = snapshot.scene();
+        let new_genome = snapshot.genome.as_ref().map(encode_genome);
+        if let Some(frame) = &new_genome {
+            genome_frame = Some(frame.clone());
+        }
+        let delta = previous.as_ref().map(|previous| encode_delta(previous, &scene));
+        let keyframe = spectators.iter().any(|spectator| !spectator.synced).then(|| encode_keyframe(&scene));
+
+        spectators.retain_mut(|spectator| {
+            let frames: Vec<&Vec<u8>> = if spectator.synced {
+                new_genome.iter().chain(delta.iter()).collect()
+            } else {
+                genome_frame.iter().chain(keyframe.iter()).collect()
+            };
+            match frames.iter().try_for_each(|frame| spectator.stream.write_all(frame)) {
+                Ok(()) => {
+                    let bytes: usize = frames.iter().map(|frame| frame.len()).sum();
+                    counters.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+                    spectator.synced = true;
+                    true
+                }
+                Err(e) => {
+                    info!("Spectator {} disconnected: {}", spectator.peer, e);
+                    false
+                }
+            }
+        });
+        counters.spectators.store(spectators.len(), Ordering::Relaxed);
+        previous = Some(scene);
+    }
+}
+
+/// Running spectator host and what it last sent
+#[derive(Resource, Default)]
+pub struct SpectatorHost {
+    thread: Option<HostThread>,
+    /// Genome last handed to the host thread
+    sent_genome: Option<GenomeData>,
+    /// Why the host could not start
+    pub error: Option<String>,
+}
+
+impl SpectatorHost {
+    /// Start listening on `address`, replacing a running host
+    pub fn start(&mut self, address: &str) -> crate::error::Result<()> {
+        self.stop();
+        self.thread = Some(HostThread::start(address)?);
+        Ok(())
+    }
+
+    /// Disconnect every spectator and wait for the host thread
+    pub fn stop(&mut self) {
+        self.thread = None;
+        self.sent_genome = None;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.thread.is_some()
+    }
+
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.thread.as_ref().map(|thread| thread.address)
+    }
+
+    pub fn spectator_count(&self) -> usize {
+        self.thread.as_ref().map_or(0, |thread| thread.counters.spectators.load(Ordering::Relaxed))
+    }
+
+    /// Bytes sent to spectators since the host started
+    pub fn bytes_sent(&self) -> u64 {
+        self.thread.as_ref().map_or(0, |thread| thread.counters.bytes_sent.load(Ordering::Relaxed))
+    }
+
+    /// Hand `state` to the host thread; false when it was still busy and the update was dropped
+    pub fn publish(&mut self, state: &CanonicalState, tick: u32, time: f32, genome: &GenomeData) -> bool {
+        let Some(sender) = self.thread.as_ref().and_then(|thread| thread.sender.as_ref()) else {
+            return false;
+        };
+        let genome_changed = self.sent_genome.as_ref() != Some(genome);
+        let snapshot = SpectatorSnapshot::capture(state, tick, time, genome_changed.then(|| genome.clone()));
+        match sender.try_send(snapshot) {
+            Ok(()) => {
+                if genome_changed {
+                    self.sent_genome = Some(genome.clone());
+                }
+                true
+            }
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Connection state of a spectator
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SpectatorStatus {
+    #[default]
+    Connecting,
+    Connected,
+    /// Retrying after the reason given
+    Disconnected(String),
+}
+
+/// Latest complete state received, swapped whole by the client thread
+#[derive(Default)]
+struct ReceivedState {
+    status: SpectatorStatus,
+    genome: Option<GenomeData>,
+    scene: Option<Arc<SpectatorScene>>,
+}
+
+/// Connection to a host when the app runs with `--spectate`
+///
+/// Frames are read and checked on a client thread; the main thread draws the latest
+/// complete scene, blended from the one before it.
+#[derive(Resource)]
+pub struct SpectatorClient {
+    pub address: String,
+    received: Arc<Mutex<ReceivedState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    previous: Option<Arc<SpectatorScene>>,
+    /// Latest scene and the real time it arrived
+    latest: Option<(Arc<SpectatorScene>, f32)>,
+    /// Smoothed real time between scenes
+    interval: f32,
+}
+
+impl SpectatorClient {
+    /// Start connecting to `address` (host:port) in the background, retrying until dropped
+    pub fn connect(address: String) -> Self {
+        let received = Arc::new(Mutex::new(ReceivedState::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let (address, received, stop) = (address.clone(), received.clone(), stop.clone());
+            std::thread::Builder::new()
+                .name("spectator-client".to_string())
+                .spawn(move || run_client(&address, &received, &stop))
+        };
+        let thread = thread.map_err(|e| error!("Could not start the spectator connection: {}", e)).ok();
+        Self { address, received, stop, thread, previous: None, latest: None, interval: 0.1 }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReceivedState> {
+        self.received.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn status(&self) -> SpectatorStatus {
+        self.lock().status.clone()
+    }
+
+    /// Genome received since the last call
+    pub fn take_genome(&self) -> Option<GenomeData> {
+        self.lock().genome.take()
+    }
+
+    /// Latest scene received since the last call
+    pub fn take_scene(&self) -> Option<Arc<SpectatorScene>> {
+        self.lock().scene.take()
+    }
+
+    /// Tick of the scene being drawn
+    pub fn tick(&self) -> Option<u32> {
+        self.latest.as_ref().map(|(scene, _)| scene.tick)
+    }
+
+    /// Take a newly received scene, arriving at real time `now`
+    fn receive(&mut self, now: f32) {
+        let Some(scene) = self.take_scene() else {
+            return;
+        };
+        if let Some((latest, arrived)) = self.latest.take() {
+            self.interval = self.interval * 0.8 + (now - arrived).clamp(0.005, 2.0) * 0.2;
+            self.previous = Some(latest);
+        }
+        self.latest = Some((scene, now));
+    }
+}
+
+impl Drop for SpectatorClient {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_client(address: &str, received: &Mutex<ReceivedState>, stop: &AtomicBool) {
+    let set_status = |status: SpectatorStatus| {
+        received.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).status = status;
+    };
+    while !stop.load(Ordering::Relaxed) {
+        let reason = match stream_from_host(address, received, stop) {
+            Ok(()) => "the host closed the connection".to_string(),
+            Err(reason) => reason,
+        };
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        warn!("Spectating {}: {}; reconnecting", address, reason);
+        set_status(SpectatorStatus::Disconnected(reason));
+        std::thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+/// Read frames from the host until the connection fails or `stop` is set
+fn stream_from_host(address: &str, received: &Mutex<ReceivedState>, stop: &AtomicBool) -> Result<(), String> {
+    let host = address.to_socket_addrs().map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} has no address", address))?;
+    let mut stream = TcpStream::connect_timeout(&host, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(READ_POLL)).map_err(|e| e.to_string())?;
+    received.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).status = SpectatorStatus::Connected;
+
+    let mut buffer = Vec::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    let mut scene: Option<SpectatorScene> = None;
+    while !stop.load(Ordering::Relaxed) {
+        match stream.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted) => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+
+        let mut used = 0;
+        let mut genome = None;
+        let mut updated = false;
+        while let Some((frame, length)) = next_frame(&buffer[used..])? {
+            match decode_frame(&mut scene, frame)? {
+                Some(host_genome) => genome = Some(host_genome),
+                None => updated = true,
+            }
+            used += length;
+        }
+        buffer.drain(..used);
+
+        // Only the latest complete scene is drawn
+        if genome.is_some() || updated {
+            let mut received = received.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if genome.is_some() {
+                received.genome = genome;
+            }
+            if updated {
+                received.scene = scene.clone().map(Arc::new);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write `latest`, blended from `previous` by `alpha`, into the main simulation state
+///
+/// Cells only present in `latest` are drawn where they are; bonds follow `latest`.
+pub fn write_spectated_scene(previous: Option<&SpectatorScene>, latest: &SpectatorScene, alpha: f32, main_state: &mut MainSimState) {
+    let count = latest.cells.len();
+    let state = &mut main_state.canonical_state;
+    if state.capacity < count {
+        *state = CanonicalState::new(count.next_power_of_two());
+    }
+    let mut index_of = HashMap::with_capacity(count);
+    for (index, (&id, cell)) in latest.cells.iter().enumerate() {
+        let target = cell.position();
+        let position = previous
+            .and_then(|previous| previous.cells.get(&id))
+            .map_or(target, |old| old.position().lerp(target, alpha));
+        state.cell_ids[index] = id;
+        state.positions[index] = position;
+        state.prev_positions[index] = position;
+        state.velocities[index] = Vec3::ZERO;
+        state.rotations[index] = Quat::IDENTITY;
+        state.radii[index] = cell.radius();
+        state.mode_indices[index] = cell.mode as usize;
+        index_of.insert(id, index);
+    }
+    state.cell_count = count;
+
+    let connections = &mut state.adhesion_connections;
+    if connections.is_active.len() < latest.bonds.len() {
+        *connections = crate::cell::AdhesionConnections::new(latest.bonds.len().next_power_of_two());
+    }
+    let mut active = 0;
+    for (a, b) in &latest.bonds {
+        let (Some(&a), Some(&b)) = (index_of.get(a), index_of.get(b)) else {
+            continue;
+        };
+        connections.cell_a_index[active] = a;
+        connections.cell_b_index[active] = b;
+        connections.mode_index[active] = state.mode_indices[a];
+        connections.is_active[active] = 1;
+        active += 1;
+    }
+    connections.is_active[active..].fill(0);
+    connections.active_count = active;
+
+    let start = previous.map_or(latest.time, |previous| previous.time);
+    main_state.simulation_time = start + (latest.time - start) * alpha;
+}
+
+/// Switch a spectator straight to the main simulation view
+fn enter_spectated_scene(
+    client: Option<Res<SpectatorClient>>,
+    scene_request: Option<ResMut<crate::ui::windows::scene_manager::SceneModeRequest>>,
+) {
+    if let (Some(client), Some(mut scene_request)) = (client, scene_request) {
+        info!("Spectating {}", client.address);
+        scene_request.requested_mode = Some(SimulationMode::Cpu);
+    }
+}
+
+/// Mirror the host's genome and latest scene; spectators never run the simulation
+fn apply_spectated_scene(
+    mut client: ResMut<SpectatorClient>,
+    time: Res<Time<Real>>,
+    mut sim_state: ResMut<SimulationState>,
+    mut main_state: ResMut<MainSimState>,
+    mut genome: ResMut<CurrentGenome>,
+) {
+    if !sim_state.paused {
+        sim_state.paused = true;
+    }
+    if let Some(host_genome) = client.take_genome() {
+        genome.replace(host_genome, 0, None);
+    }
+    let now = time.elapsed_secs();
+    client.receive(now);
+    if sim_state.mode != SimulationMode::Cpu {
+        return;
+    }
+    if let Some((latest, arrived)) = &client.latest {
+        let alpha = ((now - arrived) / client.interval).clamp(0.0, 1.0);
+        write_spectated_scene(client.previous.as_deref(), latest, alpha, &mut main_state);
+    }
+}
+
+/// Start or stop the host when its address or switch changes
+fn sync_spectator_host(
+    settings: Res<SpectatorSettings>,
+    mut host: ResMut<SpectatorHost>,
+    mut applied: Local<Option<(bool, String)>>,
+) {
+    let wanted = (settings.enabled, settings.address());
+    if applied.as_ref() == Some(&wanted) {
+        return;
+    }
+    *applied = Some(wanted);
+    host.error = None;
+    if !settings.enabled {
+        host.stop();
+        return;
+    }
+    if let Err(e) = host.start(&settings.address()) {
+        warn!("{}", e);
+        host.error = Some(e.to_string());
+    }
+}
+
+/// Hand the main simulation to the host thread at the configured rate
+///
+/// Paused scenes keep being sent (as empty deltas) so spectators that join get a keyframe.
+#[allow(clippy::too_many_arguments)]
+fn publish_spectator_snapshot(
+    settings: Res<SpectatorSettings>,
+    mut host: ResMut<SpectatorHost>,
+    time: Res<Time<Real>>,
+    sim_state: Res<SimulationState>,
+    main_state: Res<MainSimState>,
+    genome: Res<CurrentGenome>,
+    config: Res<PhysicsConfig>,
+    mut last_publish: Local<f32>,
+) {
+    if !host.is_running() || sim_state.mode != SimulationMode::Cpu {
+        return;
+    }
+    let now = time.elapsed_secs();
+    if now - *last_publish < 1.0 / settings.updates_per_second.max(0.1) {
+        return;
+    }
+    *last_publish = now;
+    let tick = crate::simulation::clock::ticks_to_reach(main_state.simulation_time, config.fixed_timestep);
+    host.publish(&main_state.canonical_state, tick, main_state.simulation_time, &genome.genome);
+}
+
+fn stop_spectator_host_on_exit(
+    mut exit_events: MessageReader<bevy::app::AppExit>,
+    mut host: ResMut<SpectatorHost>,
+) {
+    if exit_events.read().next().is_some() {
+        host.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use std::time::Instant;
+
+    /// Splitting, bonding colony and its physics
+    fn colony() -> (CanonicalState, GenomeData, PhysicsConfig) {
+        let mut mode = ModeSettings::new_self_splitting(0, "Splitter".to_string());
+        mode.split_interval = 1.0;
+        mode.split_mass = 1.0;
+        mode.parent_make_adhesion = true;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+        let mut state = CanonicalState::new(64);
+        state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+            0, 0, 0.0, 1.0, 1.0, config.default_stiffness, Quat::IDENTITY, 0);
+        (state, genome, config)
+    }
+
+    fn step(state: &mut CanonicalState, genome: &GenomeData, config: &PhysicsConfig, tick: u32) {
+        let time = tick as f32 * config.fixed_timestep;
+        crate::simulation::cpu_physics::physics_step_with_genome(state, config, genome, time, true);
+        let capacity = state.capacity;
+        crate::simulation::cpu_physics::division_step(state, genome, time + config.fixed_timestep, config.fixed_timestep, capacity, 0);
+    }
+
+    /// Wait until `client` has received the scene of `tick`
+    fn wait_for_tick(client: &SpectatorClient, tick: u32) -> Arc<SpectatorScene> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(scene) = client.take_scene().filter(|scene| scene.tick == tick) {
+                return scene;
+            }
+            assert!(Instant::now() < deadline, "no scene for tick {} ({:?})", tick, client.status());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_deltas_rebuild_the_host_scene_exactly() {
+        let (mut state, genome, config) = colony();
+        let mut previous = SpectatorScene::capture(&state, 0, 0.0);
+        let mut spectator = None;
+        decode_frame(&mut spectator, &encode_keyframe(&previous)[4..]).unwrap();
+
+        for tick in 1..=200 {
+            step(&mut state, &genome, &config, tick - 1);
+            if tick == 150 {
+                // A jump too large for a 16-bit offset
+                state.positions[0] += Vec3::X * 300.0;
+            }
+            let scene = SpectatorScene::capture(&state, tick, tick as f32 * config.fixed_timestep);
+            decode_frame(&mut spectator, &encode_delta(&previous, &scene)[4..]).unwrap();
+            assert_eq!(spectator.as_ref(), Some(&scene));
+            previous = scene;
+        }
+        assert!(previous.cells.len() > 1 && !previous.bonds.is_empty());
+
+        // Unchanged scenes cost only the header and empty lists
+        assert_eq!(encode_delta(&previous, &previous).len(), 4 + 1 + 16 + 4 * 4);
+
+        // A delta against a scene the spectator doesn't have is caught
+        let mut stale = Some(SpectatorScene::default());
+        assert!(decode_frame(&mut stale, &encode_delta(&previous, &previous)[4..]).is_err());
+    }
+
+    #[test]
+    fn test_spectate_flag_adds_the_default_port() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(spectate_address(args(&["--spectate", "teacher.local"])), Some(format!("teacher.local:{}", DEFAULT_SPECTATOR_PORT)));
+        assert_eq!(spectate_address(args(&["--spectate", "10.0.0.2:9000"])), Some("10.0.0.2:9000".to_string()));
+        assert_eq!(spectate_address(args(&["--spectate"])), None);
+        assert_eq!(spectate_address(args(&["--strict-math"])), None);
+    }
+
+    #[test]
+    fn test_loopback_spectators_match_the_host_at_checkpoints() {
+        let (mut state, genome, config) = colony();
+        let mut host = SpectatorHost::default();
+        host.start("127.0.0.1:0").unwrap();
+        let address = host.address().unwrap().to_string();
+        let early = SpectatorClient::connect(address.clone());
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while host.spectator_count() == 0 {
+            assert!(Instant::now() < deadline, "spectator never connected");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let mut late = None;
+        for tick in 1..=240 {
+            step(&mut state, &genome, &config, tick - 1);
+            let time = tick as f32 * config.fixed_timestep;
+            // Keep offering until the host thread takes it, as the next frame would
+            while !host.publish(&state, tick, time, &genome) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+
+            if tick % 60 == 0 {
+                let expected = SpectatorScene::capture(&state, tick, time);
+                assert_eq!(wait_for_tick(&early, tick).fingerprint(), expected.fingerprint());
+                if let Some(late) = &late {
+                    assert_eq!(*wait_for_tick(late, tick), expected);
+                }
+            }
+            // Joins mid-session and starts from a keyframe
+            if tick == 90 {
+                late = Some(SpectatorClient::connect(address.clone()));
+                let deadline = Instant::now() + Duration::from_secs(10);
+                while host.spectator_count() < 2 {
+                    assert!(Instant::now() < deadline, "late spectator never connected");
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }
+        }
+        assert!(early.take_genome().is_some_and(|received| received == genome));
+        assert!(host.bytes_sent() > 0);
+
+        drop(early);
+        host.stop();
+        assert!(!host.is_running());
+    }
+}
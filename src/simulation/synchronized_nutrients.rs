@@ -12,7 +12,7 @@ use super::cpu_physics::CanonicalState;
 /// Nutrients then flow between adhesion-connected cells based on priority ratios.
 pub fn transport_nutrients_synchronized(
     state: &mut CanonicalState,
-    genome: &crate::genome::GenomeData,
+    genome: &crate::genome::CompiledGenome,
     dt: f32,
 ) {
     // Step 1: Individual nutrient gain for Test cells
@@ -34,6 +34,11 @@ pub fn transport_nutrients_synchronized(
     );
     
     // Remove dead cells (in reverse order to maintain indices)
+    // The event buffers only ever describe the current step's removals
+    state.removed_cell_ids_buffer.clear();
+    state.removed_cell_positions_buffer.clear();
+    state.broken_adhesions_buffer.clear();
+    state.broken_adhesion_speeds_buffer.clear();
     for &cell_idx in dead_cells.iter().rev() {
         crate::simulation::nutrient_system::remove_dead_cell(state, cell_idx);
     }
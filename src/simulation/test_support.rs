@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use crate::genome::{GenomeData, ModeSettings};
+use crate::simulation::{CanonicalState, PhysicsConfig};
+
+/// Mode whose cells neither grow nor divide during a test
+pub fn never_split_mode(mode_index: usize, name: &str) -> ModeSettings {
+    let mut mode = ModeSettings::new_self_splitting(mode_index, name.to_string());
+    mode.split_interval = 1000.0;
+    mode.split_mass = 1000.0;
+    mode.nutrient_gain_rate = 0.0;
+    mode
+}
+
+/// Genome with one never-splitting mode per name
+pub fn never_split_genome(names: &[&str]) -> GenomeData {
+    let modes = names.iter()
+        .enumerate()
+        .map(|(i, name)| never_split_mode(i, name))
+        .collect();
+    GenomeData { modes, ..GenomeData::default() }
+}
+
+/// Add a resting founder cell that never reaches its split thresholds, returning its index
+pub fn add_test_cell(
+    state: &mut CanonicalState,
+    position: Vec3,
+    rotation: Quat,
+    mass: f32,
+    radius: f32,
+    mode_index: usize,
+) -> usize {
+    let stiffness = PhysicsConfig::default().default_stiffness;
+    state.add_cell(position, Vec3::ZERO, rotation, Vec3::ZERO, mass, radius,
+        0, mode_index, 0.0, 1000.0, 1000.0, stiffness, rotation, 0)
+        .expect("test state capacity")
+}
@@ -8,6 +8,7 @@ use crate::ui::ui_system::GenomeEditorState;
 pub fn sync_time_slider_to_simulation(
     genome_editor_state: Res<GenomeEditorState>,
     mut sim_state: ResMut<SimulationState>,
+    preview_state: Res<PreviewSimState>,
     mut last_time_value: Local<f32>,
 ) {
     // Only run in Preview mode
@@ -17,6 +18,11 @@ pub fn sync_time_slider_to_simulation(
 
     // Check if time_value actually changed (not just any field in GenomeEditorState)
     let current_time_value = genome_editor_state.time_value;
+    // A held drag advances the preview itself; the slider just follows it
+    if preview_state.live_drag.is_some() {
+        *last_time_value = current_time_value;
+        return;
+    }
     if (current_time_value - *last_time_value).abs() > 0.01 {
         // Convert slider value (0-100) to simulation time (seconds)
         let target_sim_time = (current_time_value / 100.0)
@@ -0,0 +1,580 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::genome::{CurrentGenome, GenomeData, ModeIndex};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::event_timeline::{EventTimeline, TimelineEvent, TimelineEventKind};
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Current tissue file format version
+pub const TISSUE_FORMAT_VERSION: u32 = 1;
+
+/// Plugin placing tissue stamps from the stamp tool
+pub struct TissueStampPlugin;
+
+impl Plugin for TissueStampPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StampRequests>()
+            .add_systems(Update, apply_stamp_requests);
+    }
+}
+
+/// An exported organism: cells relative to their centroid, internal bonds and the genome they use
+///
+/// Positions, rotations and twist references are in the world frame the organism was
+/// exported in, shifted so the centroid is at the origin. Anchor directions stay in
+/// each cell's local frame, so a rigid placement leaves them untouched.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TissueFile {
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+    pub name: String,
+    /// Genome the cells' mode indices refer to
+    pub genome: GenomeData,
+    /// Cells in ascending cell_id order, which is also the order stamps assign new IDs in
+    pub cells: Vec<TissueCell>,
+    pub bonds: Vec<TissueBond>,
+}
+
+fn default_format_version() -> u32 {
+    TISSUE_FORMAT_VERSION
+}
+
+/// One cell of a tissue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TissueCell {
+    /// Offset from the tissue's centroid
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub genome_orientation: Quat,
+    pub mode_index: usize,
+    pub mass: f32,
+    pub radius: f32,
+}
+
+/// A bond between two cells of a tissue, by their index in `TissueFile::cells`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TissueBond {
+    pub cell_a: usize,
+    pub cell_b: usize,
+    /// Mode whose adhesion settings the bond uses
+    pub mode_index: usize,
+    pub zone_a: u8,
+    pub zone_b: u8,
+    pub anchor_direction_a: Vec3,
+    pub anchor_direction_b: Vec3,
+    pub twist_reference_a: Quat,
+    pub twist_reference_b: Quat,
+}
+
+/// Where a stamp goes: the tissue is rotated about its centroid, then moved to `translation`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StampPlacement {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+impl StampPlacement {
+    pub fn apply_to(&self, offset: Vec3) -> Vec3 {
+        self.translation + self.rotation * offset
+    }
+}
+
+impl TissueFile {
+    /// Save tissue to a JSON file
+    pub fn save_to_file(&self, path: &std::path::Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "tissue")
+    }
+
+    /// Load tissue from a JSON file, rejecting files whose indices don't line up
+    pub fn load_from_file(path: &std::path::Path) -> crate::error::Result<Self> {
+        let tissue: Self = crate::error::read_json(path, "tissue")?;
+        let problems = tissue.validate();
+        if !problems.is_empty() {
+            return Err(crate::error::BioSpheresError::Validation { what: "tissue", problems });
+        }
+        Ok(tissue)
+    }
+
+    /// Check cell and bond indices against each other and the genome
+    /// Returns a list of human-readable problems (empty if valid)
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.format_version > TISSUE_FORMAT_VERSION {
+            errors.push(format!(
+                "Tissue format version {} is newer than supported version {}",
+                self.format_version, TISSUE_FORMAT_VERSION
+            ));
+        }
+        if self.cells.is_empty() {
+            errors.push("Tissue has no cells".to_string());
+        }
+
+        let mode_count = self.genome.modes.len();
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell.mode_index >= mode_count {
+                errors.push(format!("Cell {} mode {} out of range", i, cell.mode_index));
+            }
+        }
+        for (i, bond) in self.bonds.iter().enumerate() {
+            if bond.cell_a >= self.cells.len() || bond.cell_b >= self.cells.len() || bond.cell_a == bond.cell_b {
+                errors.push(format!("Bond {} joins invalid cells {} and {}", i, bond.cell_a, bond.cell_b));
+            }
+            if bond.mode_index >= mode_count {
+                errors.push(format!("Bond {} mode {} out of range", i, bond.mode_index));
+            }
+        }
+        errors
+    }
+
+    /// Distance from the centroid to the far side of the outermost cell
+    pub fn extent(&self) -> f32 {
+        self.cells.iter()
+            .map(|cell| cell.position.length() + cell.radius)
+            .fold(0.0, f32::max)
+    }
+
+    /// Modes the cells and bonds use directly, ascending
+    pub fn used_modes(&self) -> Vec<usize> {
+        let mut modes: Vec<usize> = self.cells.iter().map(|cell| cell.mode_index)
+            .chain(self.bonds.iter().map(|bond| bond.mode_index))
+            .collect();
+        modes.sort_unstable();
+        modes.dedup();
+        modes
+    }
+
+    /// Used modes plus every mode their descendants can switch to, ascending
+    pub fn needed_modes(&self) -> Vec<usize> {
+        let mode_count = self.genome.modes.len();
+        let mut needed = vec![false; mode_count];
+        let mut stack = self.used_modes();
+        while let Some(index) = stack.pop() {
+            if index >= mode_count || needed[index] {
+                continue;
+            }
+            needed[index] = true;
+            let mode = &self.genome.modes[index];
+            for next in [mode.child_a.mode_number, mode.child_b.mode_number, mode.mode_a_after_splits, mode.mode_b_after_splits] {
+                if let Some(next) = self.genome.checked_index(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        (0..mode_count).filter(|&index| needed[index]).collect()
+    }
+
+    /// Identity mode map, if the target genome simulates the same as the tissue's
+    pub fn same_genome_map(&self, genome: &GenomeData) -> Option<Vec<usize>> {
+        self.genome.functionally_equal(genome).then(|| (0..self.genome.modes.len()).collect())
+    }
+
+    /// Map every used mode to the target mode of the same name
+    ///
+    /// Returns the names the target genome lacks if any are missing. Modes the
+    /// tissue doesn't use map to 0.
+    pub fn map_modes_by_name(&self, genome: &GenomeData) -> Result<Vec<usize>, Vec<String>> {
+        let mut map = vec![0; self.genome.modes.len()];
+        let mut missing = Vec::new();
+        for index in self.used_modes() {
+            let name = &self.genome.modes[index].name;
+            match genome.modes.iter().position(|mode| &mode.name == name) {
+                Some(target) => map[index] = target,
+                None => missing.push(name.clone()),
+            }
+        }
+        if missing.is_empty() { Ok(map) } else { Err(missing) }
+    }
+
+    /// Append the needed modes to the target genome and return the mode map into it
+    ///
+    /// Child and after-splits references of the appended modes are remapped to the
+    /// appended copies, so the stamped cells keep dividing the way they did.
+    pub fn import_modes_into(&self, genome: &mut GenomeData) -> Vec<usize> {
+        let needed = self.needed_modes();
+        let mut map = vec![0; self.genome.modes.len()];
+        for (offset, &index) in needed.iter().enumerate() {
+            map[index] = genome.modes.len() + offset;
+        }
+
+        // Invalid references get the fallbacks the simulation would have used
+        let remap = |mode: ModeIndex| self.genome.checked_index(mode).map(|index| ModeIndex::new(map[index]));
+        for &index in &needed {
+            let mut mode = self.genome.modes[index].clone();
+            let own = ModeIndex::new(map[index]);
+            mode.child_a.mode_number = remap(mode.child_a.mode_number).unwrap_or(own);
+            mode.child_b.mode_number = remap(mode.child_b.mode_number).unwrap_or(own);
+            mode.mode_a_after_splits = remap(mode.mode_a_after_splits).unwrap_or(ModeIndex::NONE);
+            mode.mode_b_after_splits = remap(mode.mode_b_after_splits).unwrap_or(ModeIndex::NONE);
+            genome.modes.push(mode);
+        }
+        map
+    }
+}
+
+impl CanonicalState {
+    /// Export the organism containing `index` as a tissue
+    pub fn export_tissue(&self, index: usize, genome: &GenomeData, name: String) -> TissueFile {
+        let mut members = self.adhesion_manager.organism_members(&self.adhesion_connections, index);
+        members.sort_unstable_by_key(|&member| self.cell_ids[member]);
+        let file_index: HashMap<usize, usize> = members.iter().enumerate().map(|(i, &member)| (member, i)).collect();
+
+        let centroid = members.iter().map(|&member| self.positions[member]).sum::<Vec3>() / members.len() as f32;
+        let cells = members.iter()
+            .map(|&member| TissueCell {
+                position: self.positions[member] - centroid,
+                rotation: self.rotations[member],
+                genome_orientation: self.genome_orientations[member],
+                mode_index: self.mode_indices[member],
+                mass: self.masses[member],
+                radius: self.radii[member],
+            })
+            .collect();
+
+        let connections = &self.adhesion_connections;
+        let mut bonds: Vec<TissueBond> = self.adhesion_manager.iter_active_connections(connections)
+            .filter_map(|slot| {
+                let cell_a = *file_index.get(&connections.cell_a_index[slot])?;
+                let cell_b = *file_index.get(&connections.cell_b_index[slot])?;
+                Some(TissueBond {
+                    cell_a,
+                    cell_b,
+                    mode_index: connections.mode_index[slot],
+                    zone_a: connections.zone_a[slot],
+                    zone_b: connections.zone_b[slot],
+                    anchor_direction_a: connections.anchor_direction_a[slot],
+                    anchor_direction_b: connections.anchor_direction_b[slot],
+                    twist_reference_a: connections.twist_reference_a[slot],
+                    twist_reference_b: connections.twist_reference_b[slot],
+                })
+            })
+            .collect();
+        bonds.sort_by_key(|bond| (bond.cell_a.min(bond.cell_b), bond.cell_a.max(bond.cell_b)));
+
+        TissueFile {
+            format_version: TISSUE_FORMAT_VERSION,
+            name,
+            genome: genome.clone(),
+            cells,
+            bonds,
+        }
+    }
+
+    /// Add a tissue's cells and bonds at `placement`; returns the new cell indices in file order
+    ///
+    /// Cells get fresh IDs in file order and start at rest, born at `time`. Nothing is
+    /// added (None) unless every cell and bond fits the remaining capacity.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stamp_tissue(
+        &mut self,
+        tissue: &TissueFile,
+        mode_map: &[usize],
+        placement: StampPlacement,
+        time: f32,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        rng_seed: u64,
+    ) -> Option<Vec<usize>> {
+        let free_bonds = self.adhesion_connections.cell_a_index.len()
+            - self.adhesion_manager.get_active_connection_count(&self.adhesion_connections);
+        if self.cell_count + tissue.cells.len() > self.capacity || tissue.bonds.len() > free_bonds {
+            return None;
+        }
+
+        let mode_of = |mode: usize| mode_map.get(mode).copied().filter(|&target| target < genome.modes.len()).unwrap_or(0);
+        let rotate = |rotation: Quat| (placement.rotation * rotation).normalize();
+
+        let mut indices = Vec::with_capacity(tissue.cells.len());
+        for cell in &tissue.cells {
+            let index = self.add_cell(
+                placement.apply_to(cell.position),
+                Vec3::ZERO,
+                rotate(cell.rotation),
+                Vec3::ZERO,
+                cell.mass,
+                cell.radius,
+                0,
+                mode_of(cell.mode_index),
+                time,
+                5.0,
+                1.5,
+                config.default_stiffness,
+                rotate(cell.genome_orientation),
+                0,
+            )?;
+            self.resolve_split_thresholds(index, genome, config.fixed_timestep, rng_seed);
+            indices.push(index);
+        }
+
+        for bond in &tissue.bonds {
+            let slot = self.adhesion_manager.add_adhesion_with_directions(
+                &mut self.adhesion_connections,
+                indices[bond.cell_a],
+                indices[bond.cell_b],
+                mode_of(bond.mode_index),
+                bond.anchor_direction_a,
+                bond.anchor_direction_b,
+                Vec3::Z,
+                Vec3::Z,
+                rotate(bond.twist_reference_a),
+                rotate(bond.twist_reference_b),
+            );
+            // Zones were classified when the bond first formed; keep them (and the exact anchors)
+            // rather than re-deriving them
+            if let Some(slot) = slot {
+                let connections = &mut self.adhesion_connections;
+                connections.zone_a[slot] = bond.zone_a;
+                connections.zone_b[slot] = bond.zone_b;
+                connections.anchor_direction_a[slot] = bond.anchor_direction_a;
+                connections.anchor_direction_b[slot] = bond.anchor_direction_b;
+            }
+        }
+
+        Some(indices)
+    }
+
+    /// Place a requested stamp unless this state already holds it
+    pub fn apply_stamp(
+        &mut self,
+        request: &StampRequest,
+        time: f32,
+        genome: &GenomeData,
+        config: &PhysicsConfig,
+        rng_seed: u64,
+    ) -> Option<Vec<usize>> {
+        if self.applied_stamps.contains(&request.serial) {
+            return None;
+        }
+        let indices = self.stamp_tissue(&request.tissue, &request.mode_map, request.placement, time, genome, config, rng_seed)?;
+        self.applied_stamps.push(request.serial);
+        Some(indices)
+    }
+}
+
+/// A tissue to stamp into the active simulation
+#[derive(Clone)]
+pub struct StampRequest {
+    /// Identifies the stamp across replays, so each is placed once
+    pub serial: u32,
+    pub tissue: Arc<TissueFile>,
+    /// Target genome mode for every tissue mode
+    pub mode_map: Vec<usize>,
+    pub placement: StampPlacement,
+}
+
+/// Stamps waiting to be placed into the active simulation
+#[derive(Resource, Default)]
+pub struct StampRequests {
+    pub pending: Vec<StampRequest>,
+    next_serial: u32,
+}
+
+impl StampRequests {
+    pub fn push(&mut self, tissue: Arc<TissueFile>, mode_map: Vec<usize>, placement: StampPlacement) {
+        let serial = self.next_serial;
+        self.next_serial += 1;
+        self.pending.push(StampRequest { serial, tissue, mode_map, placement });
+    }
+}
+
+/// Place the stamps recorded on the preview timeline for `tick`
+pub fn apply_scheduled_stamps(
+    state: &mut CanonicalState,
+    schedule: &[(u32, StampRequest)],
+    tick: u32,
+    genome: &GenomeData,
+    config: &PhysicsConfig,
+    rng_seed: u64,
+) {
+    for (stamp_tick, request) in schedule {
+        if *stamp_tick == tick {
+            state.apply_stamp(request, tick as f32 * config.fixed_timestep, genome, config, rng_seed);
+        }
+    }
+}
+
+/// Place pending stamps between ticks, recording them for replay in Preview mode
+fn apply_stamp_requests(
+    mut requests: ResMut<StampRequests>,
+    mut sim_state: ResMut<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    genome: Res<CurrentGenome>,
+    config: Res<PhysicsConfig>,
+    mut timeline: ResMut<EventTimeline>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if requests.pending.is_empty() {
+        return;
+    }
+
+    let fixed_timestep = config.fixed_timestep;
+    let mut rejected = 0;
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            let time = main_state.simulation_time;
+            let rng_seed = main_state.initial_state.rng_seed;
+            for request in requests.pending.drain(..) {
+                if main_state.canonical_state.apply_stamp(&request, time, &genome.genome, &config, rng_seed).is_none() {
+                    rejected += 1;
+                }
+            }
+        }
+        SimulationMode::Preview => {
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+            preview_state.intervene(sim_state.is_resimulating, fixed_timestep, |preview_state, tick| {
+                let time = tick as f32 * fixed_timestep;
+                let rng_seed = preview_state.initial_state.rng_seed;
+                for request in std::mem::take(&mut requests.pending) {
+                    match preview_state.canonical_state.apply_stamp(&request, time, &genome.genome, &config, rng_seed) {
+                        Some(indices) => {
+                            let first_cell = indices.first().map(|&index| preview_state.canonical_state.cell_ids[index]);
+                            timeline.push(TimelineEvent::new(tick, TimelineEventKind::TissueStamp, [first_cell, None]));
+                            preview_state.stamps.push((tick, request));
+                            sim_state.needs_respawn = true;
+                        }
+                        None => rejected += 1,
+                    }
+                }
+            });
+        }
+        SimulationMode::Gpu => requests.pending.clear(),
+    }
+
+    if rejected > 0 {
+        notifications.warning(format!("{} tissue stamp(s) did not fit the remaining cell or bond capacity", rejected));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeSettings;
+    use crate::simulation::test_support::{add_test_cell, never_split_genome};
+
+    fn genome() -> GenomeData {
+        never_split_genome(&["Tissue", "Filler"])
+    }
+
+    /// A bent chain of five bonded cells with distinct rotations, plus a loose cell
+    fn organism_state() -> CanonicalState {
+        let mut state = CanonicalState::new(64);
+        let positions = [
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(11.0, 0.0, 0.0),
+            Vec3::new(12.0, 0.5, 0.0),
+            Vec3::new(12.5, 1.5, 0.3),
+            Vec3::new(12.5, 2.5, 0.9),
+        ];
+        for (i, position) in positions.iter().enumerate() {
+            let rotation = Quat::from_rotation_y(i as f32 * 0.4);
+            add_test_cell(&mut state, *position, rotation, 1.0 + i as f32 * 0.1, 0.5, i % 2);
+        }
+        add_test_cell(&mut state, Vec3::new(-20.0, 0.0, 0.0), Quat::IDENTITY, 1.0, 0.5, 1);
+
+        for i in 0..4 {
+            let direction = (state.positions[i + 1] - state.positions[i]).normalize();
+            state.adhesion_manager.add_adhesion_with_directions(
+                &mut state.adhesion_connections, i, i + 1, i % 2,
+                state.rotations[i].inverse() * direction, state.rotations[i + 1].inverse() * -direction,
+                Vec3::Z, Vec3::Z, state.genome_orientations[i], state.genome_orientations[i + 1],
+            ).expect("adhesion slot");
+        }
+        state
+    }
+
+    #[test]
+    fn test_export_then_import_reproduces_geometry_and_bonds() {
+        let genome = genome();
+        let config = PhysicsConfig::default();
+        let source = organism_state();
+        let tissue = source.export_tissue(2, &genome, "Chain".to_string());
+        assert_eq!(tissue.cells.len(), 5);
+        assert_eq!(tissue.bonds.len(), 4);
+        assert!(tissue.validate().is_empty());
+
+        // Through the file format and back
+        let json = serde_json::to_string(&tissue).unwrap();
+        let tissue: TissueFile = serde_json::from_str(&json).unwrap();
+
+        let mut target = CanonicalState::new(64);
+        let map = tissue.same_genome_map(&genome).expect("same genome");
+        let placement = StampPlacement { translation: Vec3::new(-5.0, 3.0, 7.0), rotation: Quat::IDENTITY };
+        let indices = target.stamp_tissue(&tissue, &map, placement, 0.0, &genome, &config, 0).expect("fits");
+        assert_eq!(indices, (0..5).collect::<Vec<_>>());
+        assert_eq!(target.cell_ids[..5], [0, 1, 2, 3, 4]);
+
+        let again = target.export_tissue(indices[0], &genome, "Chain".to_string());
+        let topology = |tissue: &TissueFile| tissue.bonds.iter()
+            .map(|bond| (bond.cell_a, bond.cell_b, bond.mode_index, bond.zone_a, bond.zone_b, bond.anchor_direction_a, bond.anchor_direction_b))
+            .collect::<Vec<_>>();
+        assert_eq!(topology(&again), topology(&tissue));
+        for (copy, original) in again.bonds.iter().zip(&tissue.bonds) {
+            assert!(copy.twist_reference_a.abs_diff_eq(original.twist_reference_a, 1e-6));
+            assert!(copy.twist_reference_b.abs_diff_eq(original.twist_reference_b, 1e-6));
+        }
+        for (copy, original) in again.cells.iter().zip(&tissue.cells) {
+            assert!(copy.position.distance(original.position) < 1e-5);
+            assert!(copy.rotation.abs_diff_eq(original.rotation, 1e-6));
+            assert!(copy.genome_orientation.abs_diff_eq(original.genome_orientation, 1e-6));
+            assert_eq!((copy.mode_index, copy.mass, copy.radius), (original.mode_index, original.mass, original.radius));
+        }
+        for (a, b) in [(0, 1), (1, 3), (0, 4)] {
+            let exported = source.positions[a].distance(source.positions[b]);
+            let stamped = target.positions[indices[a]].distance(target.positions[indices[b]]);
+            assert!((exported - stamped).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_stamping_twice_yields_independent_organisms() {
+        let genome = genome();
+        let config = PhysicsConfig::default();
+        let tissue = Arc::new(organism_state().export_tissue(0, &genome, "Chain".to_string()));
+        let mut requests = StampRequests::default();
+        for x in [0.0, 20.0] {
+            let placement = StampPlacement { translation: Vec3::X * x, rotation: Quat::from_rotation_z(x) };
+            requests.push(tissue.clone(), vec![0, 1], placement);
+        }
+
+        let mut state = CanonicalState::new(64);
+        let first = state.apply_stamp(&requests.pending[0], 0.0, &genome, &config, 0).unwrap();
+        let second = state.apply_stamp(&requests.pending[1], 0.0, &genome, &config, 0).unwrap();
+        // Replays of an already placed stamp add nothing
+        assert!(state.apply_stamp(&requests.pending[0], 0.0, &genome, &config, 0).is_none());
+        assert_eq!(state.cell_count, 10);
+
+        let mut members_a = state.adhesion_manager.organism_members(&state.adhesion_connections, first[0]);
+        let mut members_b = state.adhesion_manager.organism_members(&state.adhesion_connections, second[0]);
+        members_a.sort_unstable();
+        members_b.sort_unstable();
+        assert_eq!(members_a, first);
+        assert_eq!(members_b, second);
+        assert_eq!(state.adhesion_manager.get_active_connection_count(&state.adhesion_connections), 8);
+    }
+
+    #[test]
+    fn test_mode_mapping_by_name_and_import() {
+        let source = genome();
+        let tissue = organism_state().export_tissue(0, &source, "Chain".to_string());
+
+        let mut renamed = genome();
+        renamed.modes.swap(0, 1);
+        assert_eq!(tissue.map_modes_by_name(&renamed), Ok(vec![1, 0]));
+        renamed.modes[0].name = "Other".to_string();
+        assert_eq!(tissue.map_modes_by_name(&renamed), Err(vec!["Filler".to_string()]));
+
+        let mut target = GenomeData { modes: vec![ModeSettings::new_self_splitting(0, "Base".to_string())], ..GenomeData::default() };
+        let map = tissue.import_modes_into(&mut target);
+        assert_eq!(map, vec![1, 2]);
+        assert_eq!(target.modes[1].name, "Tissue");
+        assert_eq!(target.modes[1].child_a.mode_number, ModeIndex::new(1));
+        assert_eq!(target.modes[2].child_b.mode_number, ModeIndex::new(2));
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use crate::genome::UserDataInheritance;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Scratch scalar channels per cell
+pub const USER_DATA_CHANNELS: usize = 4;
+
+/// Plugin applying user data writes from the inspector and bulk edits
+pub struct UserDataPlugin;
+
+impl Plugin for UserDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UserDataRequests>()
+            .add_systems(Update, apply_user_data_requests);
+    }
+}
+
+/// A write to the user data channels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UserDataChange {
+    /// Set one channel of the cell with this ID
+    Set { cell_id: u32, channel: usize, value: f32 },
+    /// Set one channel of every cell in a genome mode
+    SetMode { mode_index: usize, channel: usize, value: f32 },
+    /// Set one channel of every cell
+    SetAll { channel: usize, value: f32 },
+}
+
+impl CanonicalState {
+    /// All user data channels of a cell
+    #[inline]
+    pub fn user_data(&self, idx: usize) -> [f32; USER_DATA_CHANNELS] {
+        std::array::from_fn(|channel| self.user_data[channel][idx])
+    }
+
+    #[inline]
+    pub fn set_user_data(&mut self, idx: usize, values: [f32; USER_DATA_CHANNELS]) {
+        for (column, value) in self.user_data.iter_mut().zip(values) {
+            column[idx] = value;
+        }
+    }
+
+    /// Apply a user data write; returns false if it reached no cell
+    pub fn apply_user_data_change(&mut self, change: UserDataChange) -> bool {
+        let count = self.cell_count;
+        match change {
+            UserDataChange::Set { cell_id, channel, value } => {
+                // Keyed by ID so the write survives index shuffling from division and removal
+                let Some(idx) = self.cell_ids[..count].iter().position(|&id| id == cell_id) else {
+                    return false;
+                };
+                let Some(column) = self.user_data.get_mut(channel) else {
+                    return false;
+                };
+                column[idx] = value;
+                true
+            }
+            UserDataChange::SetMode { mode_index, channel, value } => {
+                let Some(column) = self.user_data.get_mut(channel) else {
+                    return false;
+                };
+                let mut any = false;
+                for (slot, &mode) in column[..count].iter_mut().zip(&self.mode_indices[..count]) {
+                    if mode == mode_index {
+                        *slot = value;
+                        any = true;
+                    }
+                }
+                any
+            }
+            UserDataChange::SetAll { channel, value } => {
+                let Some(column) = self.user_data.get_mut(channel) else {
+                    return false;
+                };
+                column[..count].fill(value);
+                count > 0
+            }
+        }
+    }
+}
+
+/// User data of the two children of a parent with `parent` values
+pub fn inherit_user_data(
+    policy: UserDataInheritance,
+    parent: [f32; USER_DATA_CHANNELS],
+    split_ratio: f32,
+) -> ([f32; USER_DATA_CHANNELS], [f32; USER_DATA_CHANNELS]) {
+    match policy {
+        UserDataInheritance::Copy => (parent, parent),
+        UserDataInheritance::Zero => ([0.0; USER_DATA_CHANNELS], [0.0; USER_DATA_CHANNELS]),
+        UserDataInheritance::Split => {
+            let ratio = split_ratio.clamp(0.0, 1.0);
+            (parent.map(|value| value * ratio), parent.map(|value| value * (1.0 - ratio)))
+        }
+    }
+}
+
+/// User data writes waiting to be applied to the active simulation
+#[derive(Resource, Default)]
+pub struct UserDataRequests {
+    pub pending: Vec<UserDataChange>,
+}
+
+impl UserDataRequests {
+    pub fn set(&mut self, cell_id: u32, channel: usize, value: f32) {
+        self.pending.push(UserDataChange::Set { cell_id, channel, value });
+    }
+
+    pub fn set_mode(&mut self, mode_index: usize, channel: usize, value: f32) {
+        self.pending.push(UserDataChange::SetMode { mode_index, channel, value });
+    }
+
+    pub fn set_all(&mut self, channel: usize, value: f32) {
+        self.pending.push(UserDataChange::SetAll { channel, value });
+    }
+}
+
+/// Apply the user data writes recorded on the preview timeline for `tick`
+///
+/// Writes are recorded as (tick, change) and replayed at the same ticks so
+/// scrubbing and resimulation reproduce them. Applying a tick twice is harmless.
+pub fn apply_scheduled_user_data(state: &mut CanonicalState, schedule: &[(u32, UserDataChange)], tick: u32) {
+    for (change_tick, change) in schedule {
+        if *change_tick == tick {
+            state.apply_user_data_change(*change);
+        }
+    }
+}
+
+/// Apply pending user data writes to the active simulation between ticks
+fn apply_user_data_requests(
+    mut requests: ResMut<UserDataRequests>,
+    sim_state: Res<SimulationState>,
+    mut main_state: Option<ResMut<crate::simulation::cpu_sim::MainSimState>>,
+    mut preview_state: Option<ResMut<crate::simulation::preview_sim::PreviewSimState>>,
+    config: Res<crate::simulation::PhysicsConfig>,
+) {
+    if requests.pending.is_empty() {
+        return;
+    }
+
+    match sim_state.mode {
+        SimulationMode::Cpu => {
+            let Some(main_state) = main_state.as_deref_mut() else {
+                return;
+            };
+            for change in requests.pending.drain(..) {
+                main_state.canonical_state.apply_user_data_change(change);
+            }
+        }
+        SimulationMode::Preview => {
+            // A running resimulation would overwrite the write; wait for it to land
+            if sim_state.is_resimulating {
+                return;
+            }
+            let Some(preview_state) = preview_state.as_deref_mut() else {
+                return;
+            };
+
+            let fixed_timestep = config.fixed_timestep;
+            let tick = crate::simulation::clock::ticks_to_reach(preview_state.current_time, fixed_timestep);
+            for change in std::mem::take(&mut requests.pending) {
+                if preview_state.canonical_state.apply_user_data_change(change) {
+                    preview_state.user_data_changes.push((tick, change));
+                }
+            }
+
+            // Later checkpoints were simulated without the write
+            preview_state.checkpoints.retain(|(time, _)| {
+                crate::simulation::clock::ticks_to_reach(*time, fixed_timestep) <= tick
+            });
+        }
+        SimulationMode::Gpu => requests.pending.clear(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{GenomeData, ModeSettings};
+    use crate::simulation::cpu_physics::division_step;
+    use crate::simulation::PhysicsConfig;
+
+    #[test]
+    fn test_marker_follows_founder_lineage_through_six_generations() {
+        // Divides once per simulated second regardless of mass
+        let mut mode = ModeSettings::new_self_splitting(0, "Marker".to_string());
+        mode.split_interval = 0.5;
+        mode.split_mass = 0.0;
+        mode.user_data_inheritance = UserDataInheritance::Copy;
+        let genome = GenomeData { modes: vec![mode], ..GenomeData::default() };
+        let config = PhysicsConfig::default();
+
+        // Two founders far apart; only the first is marked
+        let mut state = CanonicalState::new(256);
+        for x in [-20.0, 20.0] {
+            state.add_cell(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, 0, 0.0, 0.5, 0.0, config.default_stiffness, Quat::IDENTITY, 0);
+        }
+        assert!(state.apply_user_data_change(UserDataChange::Set { cell_id: state.cell_ids[0], channel: 0, value: 1.0 }));
+
+        // Founder of the cell in each slot: child A keeps the parent's slot, child B gets a new one
+        let mut founder_by_slot = vec![0, 1];
+        for generation in 1..=6 {
+            let capacity = state.capacity;
+            let divisions = division_step(&mut state, &genome, generation as f32, config.fixed_timestep, capacity, 0);
+            assert_eq!(divisions.len(), founder_by_slot.len(), "every cell divides in generation {}", generation);
+            founder_by_slot.resize(state.cell_count, usize::MAX);
+            for division in &divisions {
+                founder_by_slot[division.child_b_idx] = founder_by_slot[division.parent_idx];
+            }
+        }
+        assert_eq!(state.cell_count, 2 << 6);
+
+        for (idx, &founder) in founder_by_slot.iter().enumerate() {
+            let expected = if founder == 0 { 1.0 } else { 0.0 };
+            assert_eq!(state.user_data(idx), [expected, 0.0, 0.0, 0.0], "cell {} of founder {}", idx, founder);
+        }
+        assert_eq!(founder_by_slot.iter().filter(|&&founder| founder == 0).count(), 64);
+    }
+
+    #[test]
+    fn test_inheritance_policies() {
+        let parent = [1.0, 2.0, -4.0, 0.0];
+        assert_eq!(inherit_user_data(UserDataInheritance::Copy, parent, 0.25), (parent, parent));
+        assert_eq!(inherit_user_data(UserDataInheritance::Zero, parent, 0.25), ([0.0; 4], [0.0; 4]));
+        let (a, b) = inherit_user_data(UserDataInheritance::Split, parent, 0.25);
+        assert_eq!(a, [0.25, 0.5, -1.0, 0.0]);
+        assert_eq!(b, [0.75, 1.5, -3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_bulk_writes_by_mode() {
+        let mut state = CanonicalState::new(8);
+        for mode_index in [0, 1, 1] {
+            state.add_cell(Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY, Vec3::ZERO, 1.0, 1.0,
+                0, mode_index, 0.0, 10.0, 10.0, 10.0, Quat::IDENTITY, 0);
+        }
+        assert!(state.apply_user_data_change(UserDataChange::SetMode { mode_index: 1, channel: 2, value: 3.0 }));
+        assert_eq!([state.user_data(0)[2], state.user_data(1)[2], state.user_data(2)[2]], [0.0, 3.0, 3.0]);
+        assert!(!state.apply_user_data_change(UserDataChange::SetMode { mode_index: 5, channel: 2, value: 3.0 }));
+        assert!(!state.apply_user_data_change(UserDataChange::SetAll { channel: USER_DATA_CHANNELS, value: 1.0 }));
+
+        state.apply_user_data_change(UserDataChange::SetAll { channel: 2, value: -1.0 });
+        assert!((0..3).all(|idx| state.user_data(idx)[2] == -1.0));
+    }
+}
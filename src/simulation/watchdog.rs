@@ -0,0 +1,420 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::derived_metrics::{CompiledMetric, DerivedMetrics, MetricCounters};
+use crate::simulation::event_timeline::{EventTimeline, TimelineEvent, TimelineEventKind};
+use crate::simulation::{PhysicsConfig, SimulationMode, SimulationState};
+
+/// Simulated seconds between watchdog checks
+const CHECK_INTERVAL: f32 = 1.0;
+
+/// Fraction of the cell capacity at which the population counts as exploded
+const CAPACITY_FRACTION: f32 = 0.95;
+
+/// Real seconds the window title flashes after a trigger (unless the window is focused)
+const FLASH_SECS: f32 = 10.0;
+
+/// Real seconds between title flashes
+const FLASH_PERIOD: f32 = 0.5;
+
+/// Plugin for the watchdog that pauses long main simulation runs that collapse or explode
+pub struct WatchdogPlugin;
+
+impl Plugin for WatchdogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WatchdogSettings>()
+            .init_resource::<Watchdog>()
+            .add_systems(Update, (run_watchdog, flash_window_title).chain());
+    }
+}
+
+/// Condition the watchdog checks once per simulated second
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchdogCondition {
+    /// The population fell below the floor
+    Collapse,
+    /// The population rose above the ceiling or to within 5% of the cell capacity
+    Explosion,
+    /// No cell divided for the configured time
+    Stall,
+    /// The total mass left the configured band
+    MassOutOfBand,
+    /// A derived metric left the configured band
+    MetricOutOfBand,
+}
+
+impl WatchdogCondition {
+    pub const ALL: [WatchdogCondition; 5] = [
+        WatchdogCondition::Collapse,
+        WatchdogCondition::Explosion,
+        WatchdogCondition::Stall,
+        WatchdogCondition::MassOutOfBand,
+        WatchdogCondition::MetricOutOfBand,
+    ];
+    pub const COUNT: usize = Self::ALL.len();
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WatchdogCondition::Collapse => "Population collapse",
+            WatchdogCondition::Explosion => "Population explosion",
+            WatchdogCondition::Stall => "Division stall",
+            WatchdogCondition::MassOutOfBand => "Total mass out of band",
+            WatchdogCondition::MetricOutOfBand => "Metric out of band",
+        }
+    }
+}
+
+/// Enabled watchdog conditions, their thresholds and what a trigger does
+/// (edited in the Scene Manager, persisted with the UI settings)
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct WatchdogSettings {
+    pub collapse_enabled: bool,
+    /// Fewer cells than this is a collapse
+    pub population_floor: usize,
+    pub explosion_enabled: bool,
+    /// More cells than this is an explosion
+    pub population_ceiling: usize,
+    pub stall_enabled: bool,
+    /// Simulated seconds without a division that count as a stall
+    pub stall_seconds: f32,
+    pub mass_enabled: bool,
+    pub mass_min: f32,
+    pub mass_max: f32,
+    pub metric_enabled: bool,
+    /// ID of the derived metric to watch
+    pub metric: u32,
+    pub metric_min: f32,
+    pub metric_max: f32,
+    /// Pause the simulation when a condition fires
+    pub pause: bool,
+    /// Flash the window title when a condition fires while the window is in the background
+    pub flash_window: bool,
+}
+
+impl Default for WatchdogSettings {
+    fn default() -> Self {
+        Self {
+            // Off until configured; the thresholds are starting points
+            collapse_enabled: false,
+            population_floor: 1,
+            explosion_enabled: false,
+            population_ceiling: 50_000,
+            stall_enabled: false,
+            stall_seconds: 60.0,
+            mass_enabled: false,
+            mass_min: 1.0,
+            mass_max: 100_000.0,
+            metric_enabled: false,
+            metric: 0,
+            metric_min: 0.0,
+            metric_max: 1.0,
+            pause: true,
+            flash_window: true,
+        }
+    }
+}
+
+impl WatchdogSettings {
+    pub fn is_enabled(&self, condition: WatchdogCondition) -> bool {
+        match condition {
+            WatchdogCondition::Collapse => self.collapse_enabled,
+            WatchdogCondition::Explosion => self.explosion_enabled,
+            WatchdogCondition::Stall => self.stall_enabled,
+            WatchdogCondition::MassOutOfBand => self.mass_enabled,
+            WatchdogCondition::MetricOutOfBand => self.metric_enabled,
+        }
+    }
+}
+
+/// Statistics counters the watchdog reads at a check
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WatchdogSample {
+    pub time: f32,
+    pub cells: usize,
+    pub capacity: usize,
+    /// Divisions since the state was created
+    pub divisions: u64,
+    pub total_mass: f64,
+    /// Value of the watched derived metric, if it exists
+    pub metric: Option<f32>,
+}
+
+impl WatchdogSample {
+    pub fn capture(state: &CanonicalState, time: f32, metric: Option<&CompiledMetric>, mode_count: usize) -> Self {
+        Self {
+            time,
+            cells: state.cell_count,
+            capacity: state.capacity,
+            divisions: state.division_stats.divisions,
+            total_mass: state.total_mass(),
+            metric: metric.map(|metric| metric.evaluate(&MetricCounters::capture(state, mode_count, time))),
+        }
+    }
+}
+
+/// A condition firing at a check
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchdogTrigger {
+    pub condition: WatchdogCondition,
+    pub time: f32,
+    /// The reading that fired it
+    pub reading: String,
+}
+
+/// Watchdog state: which conditions fired, and the division counter it watches for stalls
+///
+/// A condition that fired stays quiet until it is re-armed in the Scene Manager or a new
+/// scene starts, so a paused run that is resumed doesn't stop again at the next check.
+#[derive(Resource, Default)]
+pub struct Watchdog {
+    tripped: [bool; WatchdogCondition::COUNT],
+    /// Simulation time of the last check
+    last_check: Option<f32>,
+    /// Division count at the last check and when it last changed
+    last_division: Option<(u64, f32)>,
+    pub last_trigger: Option<WatchdogTrigger>,
+    /// Condition flashing in the window title and the real time the flash started
+    flash: Option<(String, Option<f32>)>,
+}
+
+impl Watchdog {
+    pub fn is_tripped(&self, condition: WatchdogCondition) -> bool {
+        self.tripped[condition as usize]
+    }
+
+    /// Let `condition` fire again
+    pub fn rearm(&mut self, condition: WatchdogCondition) {
+        self.tripped[condition as usize] = false;
+    }
+
+    /// Whether a check is due at simulation `time`
+    pub fn is_due(&self, time: f32) -> bool {
+        self.last_check.is_none_or(|last| time < last || time - last >= CHECK_INTERVAL)
+    }
+
+    /// Check the enabled conditions against `sample` if a check is due; returns those that fire
+    ///
+    /// Time running backwards means a new or reloaded scene, which re-arms every condition.
+    pub fn check(&mut self, settings: &WatchdogSettings, sample: &WatchdogSample) -> Vec<WatchdogTrigger> {
+        if !self.is_due(sample.time) {
+            return Vec::new();
+        }
+        if self.last_check.is_some_and(|last| sample.time < last) {
+            self.tripped = [false; WatchdogCondition::COUNT];
+            self.last_division = None;
+        }
+        self.last_check = Some(sample.time);
+        let since_division = match self.last_division {
+            Some((divisions, at)) if divisions == sample.divisions => at,
+            _ => sample.time,
+        };
+        self.last_division = Some((sample.divisions, since_division));
+
+        let mut triggers = Vec::new();
+        for condition in WatchdogCondition::ALL {
+            if !settings.is_enabled(condition) || self.is_tripped(condition) {
+                continue;
+            }
+            let reading = match condition {
+                WatchdogCondition::Collapse => (sample.cells < settings.population_floor)
+                    .then(|| format!("{} cells, below the floor of {}", sample.cells, settings.population_floor)),
+                WatchdogCondition::Explosion => {
+                    let near_capacity = sample.cells as f32 >= sample.capacity as f32 * CAPACITY_FRACTION;
+                    if sample.cells > settings.population_ceiling {
+                        Some(format!("{} cells, above the ceiling of {}", sample.cells, settings.population_ceiling))
+                    } else if near_capacity && sample.capacity > 0 {
+                        Some(format!("{} cells, within {:.0}% of the capacity of {}",
+                            sample.cells, (1.0 - CAPACITY_FRACTION) * 100.0, sample.capacity))
+                    } else {
+                        None
+                    }
+                }
+                WatchdogCondition::Stall => {
+                    let stalled = sample.time - since_division;
+                    (sample.cells > 0 && stalled >= settings.stall_seconds)
+                        .then(|| format!("no divisions for {:.0} s", stalled))
+                }
+                WatchdogCondition::MassOutOfBand => {
+                    let (min, max) = (settings.mass_min as f64, settings.mass_max as f64);
+                    (sample.total_mass < min || sample.total_mass > max)
+                        .then(|| format!("total mass {:.1}, outside {:.1} to {:.1}", sample.total_mass, min, max))
+                }
+                // NaN (no value) never fires
+                WatchdogCondition::MetricOutOfBand => sample.metric
+                    .filter(|&value| value < settings.metric_min || value > settings.metric_max)
+                    .map(|value| format!("metric {} at {}, outside {} to {}", settings.metric, value, settings.metric_min, settings.metric_max)),
+            };
+            if let Some(reading) = reading {
+                self.tripped[condition as usize] = true;
+                triggers.push(WatchdogTrigger { condition, time: sample.time, reading });
+            }
+        }
+        triggers
+    }
+}
+
+/// Check the main simulation once per simulated second and act on the conditions that fire
+#[allow(clippy::too_many_arguments)]
+fn run_watchdog(
+    settings: Res<WatchdogSettings>,
+    mut watchdog: ResMut<Watchdog>,
+    mut sim_state: ResMut<SimulationState>,
+    main_state: Option<Res<MainSimState>>,
+    config: Res<PhysicsConfig>,
+    metrics: Res<DerivedMetrics>,
+    mut timeline: ResMut<EventTimeline>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    let Some(main_state) = main_state.filter(|_| sim_state.mode == SimulationMode::Cpu) else {
+        return;
+    };
+    // A scene that hasn't ticked yet may not be populated
+    let time = main_state.simulation_time;
+    if time <= 0.0 || !watchdog.is_due(time) {
+        return;
+    }
+    let metric = metrics.get(settings.metric).filter(|_| settings.metric_enabled);
+    let sample = WatchdogSample::capture(&main_state.canonical_state, time, metric, metrics.mode_count);
+    let triggers = watchdog.check(&settings, &sample);
+    let Some(last) = triggers.last().cloned() else {
+        return;
+    };
+
+    let tick = crate::simulation::clock::ticks_to_reach(time, config.fixed_timestep);
+    for trigger in &triggers {
+        timeline.push(TimelineEvent::new(tick, TimelineEventKind::Watchdog, [None, None]));
+        notifications.push(
+            crate::ui::notifications::NotificationLevel::Warning,
+            format!("Watchdog: {} at {:.0} s", trigger.condition.name(), trigger.time),
+            Some(format!("{}{}", trigger.reading, if settings.pause { "; the simulation was paused" } else { "" })),
+        );
+    }
+    if settings.pause {
+        sim_state.paused = true;
+    }
+    if settings.flash_window {
+        watchdog.flash = Some((last.condition.name().to_string(), None));
+    }
+    watchdog.last_trigger = Some(last);
+}
+
+/// Alternate the window title with the trigger until the window is focused or the flash ends
+///
+/// Bevy has no portable taskbar attention request, so the title is what changes.
+fn flash_window_title(
+    time: Res<Time<Real>>,
+    mut watchdog: ResMut<Watchdog>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut saved_title: Local<Option<String>>,
+) {
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let now = time.elapsed_secs();
+    let flashing = match &mut watchdog.flash {
+        Some((message, started)) => {
+            let started = *started.get_or_insert(now);
+            let elapsed = now - started;
+            (!window.focused && elapsed < FLASH_SECS).then(|| (message.clone(), (elapsed / FLASH_PERIOD) as u32 % 2 == 0))
+        }
+        None => None,
+    };
+
+    match flashing {
+        Some((message, lit)) => {
+            let title = saved_title.get_or_insert_with(|| window.title.clone()).clone();
+            let shown = if lit { format!("(!) Watchdog: {} - {}", message, title) } else { title };
+            if window.title != shown {
+                window.title = shown;
+            }
+        }
+        None => {
+            watchdog.flash = None;
+            if let Some(title) = saved_title.take() {
+                window.title = title;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time: f32, cells: usize, divisions: u64, total_mass: f64) -> WatchdogSample {
+        WatchdogSample { time, cells, capacity: 10_000, divisions, total_mass, metric: None }
+    }
+
+    fn fired(watchdog: &mut Watchdog, settings: &WatchdogSettings, sample: WatchdogSample) -> Vec<WatchdogCondition> {
+        watchdog.check(settings, &sample).into_iter().map(|trigger| trigger.condition).collect()
+    }
+
+    #[test]
+    fn test_each_condition_fires_once_until_rearmed() {
+        let settings = WatchdogSettings {
+            collapse_enabled: true,
+            population_floor: 5,
+            explosion_enabled: true,
+            population_ceiling: 5000,
+            mass_enabled: true,
+            mass_min: 10.0,
+            mass_max: 1000.0,
+            metric_enabled: true,
+            metric_min: 0.5,
+            metric_max: 2.0,
+            ..WatchdogSettings::default()
+        };
+        let cases = [
+            (WatchdogCondition::Collapse, sample(0.0, 3, 0, 50.0)),
+            (WatchdogCondition::Explosion, sample(0.0, 6000, 0, 500.0)),
+            // Below the ceiling, but within 5% of the capacity
+            (WatchdogCondition::Explosion, WatchdogSample { capacity: 1000, ..sample(0.0, 960, 0, 500.0) }),
+            (WatchdogCondition::MassOutOfBand, sample(0.0, 100, 0, 5.0)),
+            (WatchdogCondition::MassOutOfBand, sample(0.0, 100, 0, 2000.0)),
+            (WatchdogCondition::MetricOutOfBand, WatchdogSample { metric: Some(2.5), ..sample(0.0, 100, 0, 100.0) }),
+        ];
+        for (condition, first) in cases {
+            let mut watchdog = Watchdog::default();
+            assert_eq!(fired(&mut watchdog, &settings, first), vec![condition]);
+            // Still holding a second later, but already fired
+            assert!(fired(&mut watchdog, &settings, WatchdogSample { time: 1.0, ..first }).is_empty());
+            assert!(watchdog.is_tripped(condition));
+
+            watchdog.rearm(condition);
+            assert_eq!(fired(&mut watchdog, &settings, WatchdogSample { time: 2.0, ..first }), vec![condition]);
+        }
+
+        // A healthy colony fires nothing; disabled conditions never fire
+        let mut watchdog = Watchdog::default();
+        assert!(fired(&mut watchdog, &settings, sample(0.0, 100, 0, 100.0)).is_empty());
+        let mut watchdog = Watchdog::default();
+        assert!(fired(&mut watchdog, &settings, WatchdogSample { metric: Some(f32::NAN), ..sample(0.0, 100, 0, 100.0) }).is_empty());
+        let mut watchdog = Watchdog::default();
+        assert!(fired(&mut watchdog, &WatchdogSettings::default(), sample(0.0, 0, 0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_stall_counts_simulated_seconds_since_the_last_division() {
+        let settings = WatchdogSettings { stall_enabled: true, stall_seconds: 10.0, ..WatchdogSettings::default() };
+        let mut watchdog = Watchdog::default();
+        let mut triggers = Vec::new();
+        // Divisions until 5 s, then none; checked every frame, evaluated once per second
+        for frame in 0..=1200 {
+            let time = frame as f32 / 60.0;
+            let divisions = (time.min(5.0) * 4.0) as u64;
+            triggers.extend(watchdog.check(&settings, &sample(time, 20, divisions, 100.0)));
+        }
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].condition, WatchdogCondition::Stall);
+        assert!((15.0..16.5).contains(&triggers[0].time), "fired at {}", triggers[0].time);
+
+        // A new scene (time running backwards) re-arms and restarts the stall clock
+        assert!(watchdog.check(&settings, &sample(0.0, 20, 0, 100.0)).is_empty());
+        assert!(!watchdog.is_tripped(WatchdogCondition::Stall));
+        assert!(watchdog.check(&settings, &sample(9.0, 20, 0, 100.0)).is_empty());
+        assert_eq!(fired(&mut watchdog, &settings, sample(10.0, 20, 0, 100.0)), vec![WatchdogCondition::Stall]);
+    }
+}
@@ -0,0 +1,28 @@
+use bevy_egui::egui;
+use crate::audio::{AudioSettings, AudioStats};
+
+/// Draw the audio voice count in the bottom left corner of the viewport while audio debug is on
+pub fn render_audio_overlay(ctx: &egui::Context, viewport: egui::Rect, settings: &AudioSettings, stats: &AudioStats) {
+    if !settings.debug {
+        return;
+    }
+
+    let margin = 8.0;
+    egui::Area::new(egui::Id::new("audio_overlay"))
+        .pivot(egui::Align2::LEFT_BOTTOM)
+        .fixed_pos(viewport.left_bottom() + egui::vec2(margin, -margin))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .fill(egui::Color32::from_black_alpha(170))
+                .show(ui, |ui| {
+                    let muted = if settings.muted { " (muted)" } else { "" };
+                    ui.label(egui::RichText::new(format!(
+                        "Audio voices: {}/{}{}",
+                        stats.voices, crate::audio::feedback::MAX_VOICES, muted
+                    )).small());
+                    ui.label(egui::RichText::new(format!("Suppressed events: {}", stats.suppressed)).small().weak());
+                });
+        });
+}
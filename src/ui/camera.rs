@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use bevy::{input::mouse::AccumulatedMouseMotion, input::mouse::AccumulatedMouseScroll};
+use bevy::input::gestures::PinchGesture;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
+use crate::input::arbitration::{InputArbitration, PointerOwner};
+use crate::input::bindings::{DragAction, InputBindings, Modifiers, ScrollAction};
 
 /// Plugin for camera control - Space Engineers style 6DOF camera
 pub struct CameraPlugin;
@@ -9,12 +12,17 @@ impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CameraConfig>()
             .init_resource::<CameraState>()
-            .init_resource::<UiWantCapture>()
             .init_resource::<FocalPlaneSettings>()
             .init_resource::<ModeNotification>()
+            .init_resource::<crate::ui::camera_framing::CameraFraming>()
+            .init_resource::<crate::ui::camera_path::CameraPathPlayer>()
+            // Before Update so tools see the pointer claimed on the frame a drag starts
+            .add_systems(PreUpdate, camera_mouse_grab.after(crate::input::arbitration::update_input_arbitration))
             .add_systems(Update, (
                 detect_double_click_and_snap,
-                camera_mouse_grab,
+                crate::ui::camera_framing::camera_framing_system,
+                // After capture requests so a screenshot taken on the path is posed the same frame
+                crate::ui::camera_path::camera_path_system.after(crate::simulation::capture::handle_capture_requests),
                 camera_update,
                 focal_plane_input,
                 update_focal_plane_visibility,
@@ -81,6 +89,16 @@ pub enum CameraMode {
     FreeFly,
 }
 
+impl CameraMode {
+    /// Drags the mode responds to
+    pub fn drag_actions(self) -> &'static [DragAction] {
+        match self {
+            CameraMode::Orbit => &[DragAction::Orbit, DragAction::Pan],
+            CameraMode::FreeFly => &[DragAction::Look],
+        }
+    }
+}
+
 /// Component marking our orbital camera
 #[derive(Component)]
 pub struct MainCamera {
@@ -156,59 +174,62 @@ impl Default for CameraConfig {
 #[derive(Resource, Default)]
 pub struct CameraState {
     pub is_dragging: bool,
+    /// Drag in progress, kept until its button is released
+    pub drag: Option<DragAction>,
 }
 
-/// Resource to track whether ImGui wants to capture input
-/// Resource to track if UI wants to capture input (prevents camera/game input when over UI)
-#[derive(Resource, Default)]
-pub struct UiWantCapture {
-    pub want_capture_mouse: bool,
-    pub want_capture_keyboard: bool,
-}
-
-/// System to handle mouse grab for camera rotation (middle-click for orbit, right-click for free fly)
+/// System to start and end camera drags (see `InputBindings`), locking the cursor while dragging
+///
+/// Drags only start on presses nobody else holds (see `InputArbitration`); once started,
+/// a drag keeps its action until its button is released, even if the modifier is let go.
 fn camera_mouse_grab(
     mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
     mut camera_state: ResMut<CameraState>,
+    mut arbitration: ResMut<InputArbitration>,
     mut cursor_options: Single<&mut CursorOptions, With<PrimaryWindow>>,
     camera_query: Query<&MainCamera>,
 ) {
-    if let Ok(cam) = camera_query.single() {
-        let control_button = match cam.mode {
-            CameraMode::Orbit => MouseButton::Middle,
-            CameraMode::FreeFly => MouseButton::Right,
-        };
-        
-        if mouse_button.just_pressed(control_button) {
-            camera_state.is_dragging = true;
-            cursor_options.grab_mode = CursorGrabMode::Locked;
-            cursor_options.visible = false;
-        }
+    let Ok(cam) = camera_query.single() else {
+        return;
+    };
+    let actions = cam.mode.drag_actions();
 
-        if mouse_button.just_released(control_button) {
-            camera_state.is_dragging = false;
-            cursor_options.grab_mode = CursorGrabMode::None;
-            cursor_options.visible = true;
+    // End the drag when its button is released or the mode no longer has the action
+    if let Some(action) = camera_state.drag {
+        if !actions.contains(&action) || !mouse_button.pressed(bindings.drag(action).button.mouse_button()) {
+            camera_state.drag = None;
         }
-        
-        // Safety: if we're marked as dragging but the control button isn't pressed,
-        // reset the state. This handles mode switching while dragging.
-        if camera_state.is_dragging && !mouse_button.pressed(control_button) {
-            camera_state.is_dragging = false;
-            cursor_options.grab_mode = CursorGrabMode::None;
-            cursor_options.visible = true;
+    }
+    if camera_state.drag.is_none() && arbitration.pointer_free() {
+        let modifiers = Modifiers::from_keyboard(&keyboard);
+        camera_state.drag = bindings.resolve_drag(actions, |button| mouse_button.just_pressed(button.mouse_button()), modifiers);
+        if camera_state.drag.is_some() {
+            arbitration.claim(PointerOwner::Camera);
         }
     }
+
+    let dragging = camera_state.drag.is_some();
+    if dragging != camera_state.is_dragging {
+        camera_state.is_dragging = dragging;
+        cursor_options.grab_mode = if dragging { CursorGrabMode::Locked } else { CursorGrabMode::None };
+        cursor_options.visible = !dragging;
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn camera_update(
     time: Res<Time>,
     mouse_motion: Res<AccumulatedMouseMotion>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
     mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut pinch_events: MessageReader<PinchGesture>,
     keyboard: Res<ButtonInput<KeyCode>>,
     config: Res<CameraConfig>,
-    ui_capture: Res<UiWantCapture>,
+    bindings: Res<InputBindings>,
+    camera_state: Res<CameraState>,
+    arbitration: Res<InputArbitration>,
+    selected_tool: Res<crate::input::SelectedTool>,
     mut query: Query<(&mut Transform, &mut MainCamera)>,
     mut notification: ResMut<ModeNotification>,
 ) {
@@ -222,7 +243,7 @@ pub fn camera_update(
     // -------------------------------
     // 0. MODE SWITCHING (Tab key)
     // -------------------------------
-    // Allow Tab to work even when ImGui wants keyboard (camera mode is critical)
+    // Allow Tab to work even when a text field has the keyboard (camera mode is critical)
     if keyboard.just_pressed(KeyCode::Tab) {
         match cam.mode {
             CameraMode::Orbit => {
@@ -250,11 +271,29 @@ pub fn camera_update(
     }
 
     // -------------------------------
-    // 1. ZOOM (scroll) - Only in Orbit mode
+    // 1. ZOOM (scroll, pinch) - Only in Orbit mode
     // -------------------------------
-    if cam.mode == CameraMode::Orbit && !ui_capture.want_capture_mouse && mouse_scroll.delta.y.abs() > 0.001 {
-        // Additive zoom - constant speed regardless of distance (doubled multiplier)
-        cam.target_distance -= mouse_scroll.delta.y * config.zoom_speed * 30.0;
+    // Gestures over UI panels belong to egui
+    let scroll_free = arbitration.scroll_free() && !selected_tool.tool.uses_scroll();
+    let pinch: f32 = pinch_events.read().map(|pinch| pinch.0).sum();
+    if cam.mode == CameraMode::Orbit && scroll_free {
+        let modifiers = Modifiers::from_keyboard(&keyboard);
+        match bindings.resolve_scroll(mouse_scroll.unit, modifiers) {
+            ScrollAction::Zoom if mouse_scroll.delta.y.abs() > 0.001 => {
+                // Additive zoom - constant speed regardless of distance (doubled multiplier)
+                cam.target_distance -= bindings.scroll_zoom(mouse_scroll.delta.y, mouse_scroll.unit) * config.zoom_speed * 30.0;
+            }
+            ScrollAction::Orbit if mouse_scroll.delta.length_squared() > 0.0 => {
+                let delta = mouse_scroll.delta * config.mouse_sensitivity * bindings.sensitivity.touchpad_orbit;
+                orbit(&mut cam, delta);
+            }
+            _ => {}
+        }
+        // Pinch is a relative magnification; spreading the fingers zooms in
+        if pinch != 0.0 {
+            let factor = (1.0 - pinch * bindings.sensitivity.pinch_zoom).clamp(0.5, 2.0);
+            cam.target_distance *= factor;
+        }
         cam.target_distance = cam.target_distance.max(0.1); // Don't allow too close to origin
     }
     
@@ -276,29 +315,19 @@ pub fn camera_update(
     }
 
     // -------------------------------
-    // 2. ROTATION (mouse)
+    // 2. ROTATION AND PAN (mouse drags, see InputBindings)
     // -------------------------------
-    let control_button = match cam.mode {
-        CameraMode::Orbit => MouseButton::Middle,
-        CameraMode::FreeFly => MouseButton::Right,
-    };
-    
-    if mouse_buttons.pressed(control_button) {
-        let delta = mouse_motion.delta * config.mouse_sensitivity;
-
-        if cam.mode == CameraMode::Orbit {
-            // Orbit mode: rotate around world origin
-            // Horizontal rotation (yaw) around world Y axis
-            let yaw = Quat::from_axis_angle(Vec3::Y, -delta.x);
-            
-            // Vertical rotation (pitch) around camera's local right axis
-            let right = cam.target_rotation * Vec3::X;
-            let pitch = Quat::from_axis_angle(right, -delta.y);
-            
-            // Apply rotations to target
-            cam.target_rotation = yaw * pitch * cam.target_rotation;
-            cam.target_rotation = cam.target_rotation.normalize();
-        } else {
+    let delta = mouse_motion.delta * config.mouse_sensitivity;
+    match camera_state.drag {
+        Some(DragAction::Orbit) => orbit(&mut cam, delta * bindings.sensitivity.orbit_drag),
+        Some(DragAction::Pan) => {
+            // Move the orbit center with the cursor, faster when zoomed out
+            let delta = delta * bindings.sensitivity.pan_drag * cam.distance.max(1.0);
+            let (right, up) = (cam.rotation * Vec3::X, cam.rotation * Vec3::Y);
+            cam.center += -right * delta.x + up * delta.y;
+            cam.followed_entity = None; // Panning away stops following
+        }
+        Some(DragAction::Look) => {
             // FreeFly mode: free rotation with target for smoothing
             let pitch = Quat::from_axis_angle(cam.target_rotation * Vec3::X, -delta.y);
             let local_up = cam.target_rotation * Vec3::Y;
@@ -307,6 +336,7 @@ pub fn camera_update(
             cam.target_rotation = (free_yaw * pitch) * cam.target_rotation;
             cam.target_rotation = cam.target_rotation.normalize();
         }
+        None => {}
     }
     
     // Apply spring interpolation to rotation in free fly mode (same as orbit)
@@ -321,54 +351,22 @@ pub fn camera_update(
     }
 
     // -------------------------------
-    // 3. ROLL (Q/E) - Only in FreeFly mode
+    // 3-4. ROLL (Q/E) AND MOVEMENT (WASD + Space + C) - Only in FreeFly mode
     // -------------------------------
-    if cam.mode == CameraMode::FreeFly && !ui_capture.want_capture_keyboard {
-        let mut roll_amount = 0.0;
-        if keyboard.pressed(KeyCode::KeyQ) {
-            roll_amount += 1.0;
-        }
-        if keyboard.pressed(KeyCode::KeyE) {
-            roll_amount -= 1.0;
-        }
+    if cam.mode == CameraMode::FreeFly {
+        let (move_local, roll_amount) = free_fly_keys(&keyboard, &arbitration);
 
         if roll_amount != 0.0 {
             let roll_axis = cam.target_rotation * Vec3::Z; // viewing direction local Z
             let roll = Quat::from_axis_angle(roll_axis, roll_amount * config.roll_speed * dt);
             cam.target_rotation = (roll * cam.target_rotation).normalize();
         }
-    }
 
-    // -------------------------------
-    // 4. MOVEMENT (WASD + Space + C) - Only in FreeFly mode
-    // -------------------------------
-    if cam.mode == CameraMode::FreeFly && !ui_capture.want_capture_keyboard {
         let mut speed = config.move_speed * dt;
         if keyboard.pressed(KeyCode::ShiftLeft) {
             speed *= config.sprint_multiplier;
         }
-
-        let mut move_vec = Vec3::ZERO;
-
-        if keyboard.pressed(KeyCode::KeyW) {
-            move_vec += cam.rotation * Vec3::Z * -1.0; // forward
-        }
-        if keyboard.pressed(KeyCode::KeyS) {
-            move_vec += cam.rotation * Vec3::Z; // backward
-        }
-        if keyboard.pressed(KeyCode::KeyA) {
-            move_vec += cam.rotation * Vec3::X * -1.0; // left
-        }
-        if keyboard.pressed(KeyCode::KeyD) {
-            move_vec += cam.rotation * Vec3::X; // right
-        }
-        if keyboard.pressed(KeyCode::Space) {
-            move_vec += cam.rotation * Vec3::Y; // up
-        }
-        if keyboard.pressed(KeyCode::KeyC) {
-            move_vec += cam.rotation * Vec3::Y * -1.0; // down
-        }
-
+        let move_vec = cam.rotation * move_local;
         if move_vec.length_squared() > 0.0 {
             cam.center += move_vec.normalize() * speed;
         }
@@ -384,6 +382,51 @@ pub fn camera_update(
 
 
 
+/// Free-fly movement direction in camera space and roll direction from the held keys
+///
+/// Nothing while a text field has the keyboard, so typing never moves the camera.
+fn free_fly_keys(keyboard: &ButtonInput<KeyCode>, arbitration: &InputArbitration) -> (Vec3, f32) {
+    if !arbitration.keyboard_free() {
+        return (Vec3::ZERO, 0.0);
+    }
+    let mut roll = 0.0;
+    if keyboard.pressed(KeyCode::KeyQ) {
+        roll += 1.0;
+    }
+    if keyboard.pressed(KeyCode::KeyE) {
+        roll -= 1.0;
+    }
+
+    let mut direction = Vec3::ZERO;
+    for (key, axis) in [
+        (KeyCode::KeyW, -Vec3::Z), // forward
+        (KeyCode::KeyS, Vec3::Z),  // backward
+        (KeyCode::KeyA, -Vec3::X), // left
+        (KeyCode::KeyD, Vec3::X),  // right
+        (KeyCode::Space, Vec3::Y), // up
+        (KeyCode::KeyC, -Vec3::Y), // down
+    ] {
+        if keyboard.pressed(key) {
+            direction += axis;
+        }
+    }
+    (direction, roll)
+}
+
+/// Rotate the orbit camera around its center by a screen-space delta
+fn orbit(cam: &mut MainCamera, delta: Vec2) {
+    // Horizontal rotation (yaw) around world Y axis
+    let yaw = Quat::from_axis_angle(Vec3::Y, -delta.x);
+    
+    // Vertical rotation (pitch) around camera's local right axis
+    let right = cam.target_rotation * Vec3::X;
+    let pitch = Quat::from_axis_angle(right, -delta.y);
+    
+    // Apply rotations to target
+    cam.target_rotation = yaw * pitch * cam.target_rotation;
+    cam.target_rotation = cam.target_rotation.normalize();
+}
+
 /// System to detect double-click and snap to cell
 fn detect_double_click_and_snap(
     time: Res<Time>,
@@ -392,10 +435,13 @@ fn detect_double_click_and_snap(
     mut camera_query: Query<(&Camera, &GlobalTransform, &mut MainCamera)>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     cell_query: Query<(Entity, &crate::cell::CellPosition, &crate::cell::Cell)>,
-    ui_capture: Res<UiWantCapture>,
+    arbitration: Res<InputArbitration>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut picking: ResMut<crate::input::Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
 ) {
-    // Don't process if UI wants to capture mouse
-    if ui_capture.want_capture_mouse {
+    // Don't process presses on the UI or held by a drag (e.g. a Ctrl/Shift+left camera drag)
+    if !arbitration.pointer_free() {
         return;
     }
     
@@ -441,26 +487,16 @@ fn detect_double_click_and_snap(
         return;
     };
     
-    // Find closest cell intersected by ray
-    let mut closest_hit: Option<(Entity, f32)> = None;
-    
-    for (entity, cell_pos, cell) in cell_query.iter() {
-        // Ray-sphere intersection test
-        if let Some(hit_distance) = ray_sphere_intersection(
-            ray.origin,
-            *ray.direction,
-            cell_pos.position,
-            cell.radius,
-        ) {
-            // Keep track of closest hit
-            if closest_hit.is_none() || hit_distance < closest_hit.unwrap().1 {
-                closest_hit = Some((entity, hit_distance));
-            }
-        }
-    }
+    // Nearest opaque enough cell under the cursor (Alt cycles through the ones behind it)
+    let hit = picking.pick(
+        ray,
+        cursor_pos,
+        crate::input::picking::cycle_held(&keyboard),
+        crate::input::picking::entity_candidates(cell_query.iter(), &current_genome.genome),
+    );
     
     // If we hit a cell, snap to it
-    if let Some((entity, _)) = closest_hit {
+    if let Some(entity) = hit.map(|hit| hit.target) {
         // Set flag to prevent drag system from starting
         drag_state.skip_next_drag = true;
         
@@ -506,31 +542,6 @@ fn follow_entity_system(
     }
 }
 
-/// Ray-sphere intersection test (borrowed from cell_dragging.rs)
-fn ray_sphere_intersection(
-    ray_origin: Vec3,
-    ray_direction: Vec3,
-    sphere_center: Vec3,
-    sphere_radius: f32,
-) -> Option<f32> {
-    let oc = ray_origin - sphere_center;
-    let a = ray_direction.dot(ray_direction);
-    let b = 2.0 * oc.dot(ray_direction);
-    let c = oc.dot(oc) - sphere_radius * sphere_radius;
-    let discriminant = b * b - 4.0 * a * c;
-
-    if discriminant < 0.0 {
-        None
-    } else {
-        let t = (-b - discriminant.sqrt()) / (2.0 * a);
-        if t > 0.0 {
-            Some(t)
-        } else {
-            None
-        }
-    }
-}
-
 /// System to update camera FOV when config changes
 fn update_camera_fov(
     config: Res<CameraConfig>,
@@ -553,7 +564,8 @@ fn focal_plane_input(
     mouse_scroll: Res<AccumulatedMouseScroll>,
     mut focal_plane: ResMut<FocalPlaneSettings>,
     camera_query: Query<&MainCamera>,
-    ui_capture: Res<UiWantCapture>,
+    arbitration: Res<InputArbitration>,
+    selected_tool: Res<crate::input::SelectedTool>,
     mut notification: ResMut<ModeNotification>,
 ) {
     let Ok(cam) = camera_query.single() else {
@@ -570,7 +582,7 @@ fn focal_plane_input(
     }
     
     // Toggle focal plane with F key (allow even when UI wants keyboard for this critical feature)
-    if keyboard.just_pressed(KeyCode::KeyF) && !ui_capture.want_capture_keyboard {
+    if keyboard.just_pressed(KeyCode::KeyF) && arbitration.keyboard_free() {
         focal_plane.enabled = !focal_plane.enabled;
         if focal_plane.enabled {
             notification.show("Focal Slice Enabled", 1.5);
@@ -580,7 +592,7 @@ fn focal_plane_input(
     }
     
     // Adjust distance with scroll wheel when focal plane is enabled
-    if focal_plane.enabled && !ui_capture.want_capture_mouse && mouse_scroll.delta.y.abs() > 0.001 {
+    if focal_plane.enabled && arbitration.scroll_free() && !selected_tool.tool.uses_scroll() && mouse_scroll.delta.y.abs() > 0.001 {
         focal_plane.distance += mouse_scroll.delta.y * focal_plane.scroll_speed;
         focal_plane.distance = focal_plane.distance.clamp(focal_plane.min_distance, focal_plane.max_distance);
     }
@@ -646,3 +658,27 @@ fn render_mode_notification(
     // Function temporarily disabled during egui migration
     // Will be re-implemented using egui overlays
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::arbitration::CaptureSignals;
+
+    #[test]
+    fn test_typing_in_a_text_field_does_not_fly_the_camera() {
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        for key in [KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD, KeyCode::KeyQ, KeyCode::KeyE, KeyCode::Space, KeyCode::KeyC] {
+            keyboard.press(key);
+        }
+        // The genome name field has focus
+        let mut arbitration = InputArbitration::default();
+        arbitration.set_signals(CaptureSignals { ui_wants_keyboard: true, ..Default::default() });
+        assert_eq!(free_fly_keys(&keyboard, &arbitration), (Vec3::ZERO, 0.0));
+
+        // Focus leaves the field: the same keys move the camera again
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        keyboard.press(KeyCode::KeyW);
+        arbitration.set_signals(CaptureSignals::default());
+        assert_eq!(free_fly_keys(&keyboard, &arbitration), (-Vec3::Z, 0.0));
+    }
+}
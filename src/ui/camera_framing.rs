@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use crate::input::arbitration::InputArbitration;
+use crate::ui::camera::{CameraMode, MainCamera};
+
+/// Minimum time between bounding-sphere evaluations (seconds)
+const EVALUATE_INTERVAL: f64 = 0.25;
+
+/// Camera auto-framing settings and "Frame All" requests
+#[derive(Resource)]
+pub struct CameraFraming {
+    /// Re-frame automatically when too many cells leave the view
+    pub auto_frame: bool,
+    /// Fraction of cells outside the view frustum that triggers auto-framing
+    pub out_of_view_fraction: f32,
+    /// Extra space around the bounding sphere (1.0 = tight fit)
+    pub margin: f32,
+    /// Auto-framing is suspended for this long after manual camera input (seconds)
+    pub manual_suspend_secs: f32,
+    /// Set by the UI (or the Home key) to frame all cells once
+    pub frame_all_requested: bool,
+}
+
+impl Default for CameraFraming {
+    fn default() -> Self {
+        Self {
+            auto_frame: false,
+            out_of_view_fraction: 0.2,
+            margin: 1.2,
+            manual_suspend_secs: 3.0,
+            frame_all_requested: false,
+        }
+    }
+}
+
+/// Per-system state for framing (scratch buffer and animation target)
+#[derive(Default)]
+pub struct FramingRuntime {
+    /// Cell positions (xyz) and radii (w) copied from the active simulation
+    scratch: Vec<Vec4>,
+    last_manual_input: f64,
+    last_evaluated: f64,
+    /// Center/distance the camera is easing towards
+    target: Option<(Vec3, f32)>,
+}
+
+/// Bounding sphere of all cells plus how many are outside the view frustum
+struct FramingBounds {
+    center: Vec3,
+    radius: f32,
+    out_of_view: usize,
+    count: usize,
+}
+
+/// Compute bounds and frustum coverage in a single pass over the cells
+fn compute_bounds(
+    cells: &[Vec4],
+    camera_position: Vec3,
+    camera_rotation: Quat,
+    tan_half_fov: Vec2,
+) -> Option<FramingBounds> {
+    if cells.is_empty() {
+        return None;
+    }
+
+    let inverse_rotation = camera_rotation.inverse();
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    let mut out_of_view = 0;
+
+    for cell in cells {
+        let position = cell.truncate();
+        min = min.min(position - cell.w);
+        max = max.max(position + cell.w);
+
+        // Camera looks down -Z in its local space
+        let local = inverse_rotation * (position - camera_position);
+        let depth = -local.z;
+        let visible = depth > 0.0
+            && local.x.abs() <= depth * tan_half_fov.x
+            && local.y.abs() <= depth * tan_half_fov.y;
+        if !visible {
+            out_of_view += 1;
+        }
+    }
+
+    Some(FramingBounds {
+        center: (min + max) * 0.5,
+        radius: ((max - min) * 0.5).length(),
+        out_of_view,
+        count: cells.len(),
+    })
+}
+
+/// Whether the user is orbiting, zooming, flying or switching the camera mode this frame
+pub(crate) fn manual_camera_input(
+    keyboard: &ButtonInput<KeyCode>,
+    camera_state: &crate::ui::CameraState,
+    mouse_motion: &AccumulatedMouseMotion,
+    mouse_scroll: &AccumulatedMouseScroll,
+    arbitration: &InputArbitration,
+    mode: CameraMode,
+) -> bool {
+    let orbiting = camera_state.drag.is_some() && mouse_motion.delta.length_squared() > 0.0;
+    let zooming = arbitration.scroll_free() && mouse_scroll.delta.y.abs() > 0.001;
+    let moving = mode == CameraMode::FreeFly && arbitration.keyboard_free()
+        && keyboard.any_pressed([KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD, KeyCode::Space, KeyCode::KeyC]);
+    orbiting || zooming || moving || keyboard.just_pressed(KeyCode::Tab)
+}
+
+/// Frame all cells on request and auto-frame when the colony drifts out of view
+pub fn camera_framing_system(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    camera_state: Res<crate::ui::CameraState>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    arbitration: Res<InputArbitration>,
+    mut framing: ResMut<CameraFraming>,
+    mut runtime: Local<FramingRuntime>,
+    mut camera_query: Query<(&mut MainCamera, &Transform, &Projection)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    cell_query: Query<(&crate::cell::CellPosition, &crate::cell::Cell)>,
+) {
+    let Ok((mut cam, transform, projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let now = time.elapsed_secs_f64();
+
+    if keyboard.just_pressed(KeyCode::Home) && arbitration.keyboard_free() {
+        framing.frame_all_requested = true;
+    }
+
+    // Any manual camera input cancels the current animation and suspends auto-framing
+    if manual_camera_input(&keyboard, &camera_state, &mouse_motion, &mouse_scroll, &arbitration, cam.mode) {
+        runtime.last_manual_input = now;
+        runtime.target = None;
+    }
+
+    let frame_all = std::mem::take(&mut framing.frame_all_requested);
+    let auto_frame_due = framing.auto_frame
+        && cam.mode == CameraMode::Orbit
+        && runtime.target.is_none()
+        && now - runtime.last_manual_input > framing.manual_suspend_secs as f64
+        && now - runtime.last_evaluated > EVALUATE_INTERVAL;
+
+    if frame_all || auto_frame_due {
+        runtime.last_evaluated = now;
+
+        // Copy positions from whichever source is active into the scratch buffer
+        let scratch = &mut runtime.scratch;
+        scratch.clear();
+        let canonical = match sim_state.mode {
+            crate::simulation::SimulationMode::Cpu => main_state.as_deref().map(|s| &s.canonical_state),
+            crate::simulation::SimulationMode::Preview => preview_state.as_deref().map(|s| &s.canonical_state),
+            crate::simulation::SimulationMode::Gpu => None,
+        };
+        match canonical {
+            Some(state) => scratch.extend(
+                state.positions[..state.cell_count].iter()
+                    .zip(&state.radii[..state.cell_count])
+                    .map(|(position, radius)| position.extend(*radius)),
+            ),
+            None => scratch.extend(
+                cell_query.iter().map(|(position, cell)| position.position.extend(cell.radius)),
+            ),
+        }
+
+        let (vertical_fov, aspect) = match projection {
+            Projection::Perspective(perspective) => (perspective.fov, perspective.aspect_ratio),
+            _ => (std::f32::consts::FRAC_PI_4, 1.0),
+        };
+        let tan_half_fov = Vec2::new((vertical_fov * 0.5).tan() * aspect, (vertical_fov * 0.5).tan());
+
+        if let Some(bounds) = compute_bounds(&runtime.scratch, transform.translation, transform.rotation, tan_half_fov) {
+            let out_fraction = bounds.out_of_view as f32 / bounds.count as f32;
+            if frame_all || out_fraction > framing.out_of_view_fraction {
+                // Distance at which the sphere fits the narrower field of view
+                let half_fov = tan_half_fov.x.min(tan_half_fov.y).atan();
+                let distance = (bounds.radius * framing.margin / half_fov.sin()).max(5.0);
+                runtime.target = Some((bounds.center, distance));
+                cam.followed_entity = None;
+            }
+        }
+    }
+
+    // Ease towards the target
+    let Some((target_center, target_distance)) = runtime.target else {
+        return;
+    };
+    let t = (time.delta_secs() * 4.0).min(1.0);
+    match cam.mode {
+        CameraMode::Orbit => {
+            cam.center = cam.center.lerp(target_center, t);
+            cam.target_distance = target_distance;
+            if cam.center.distance(target_center) < 0.01 {
+                cam.center = target_center;
+                runtime.target = None;
+            }
+        }
+        CameraMode::FreeFly => {
+            // Free-fly orbits around its own position, so move the camera itself back
+            let goal = target_center + cam.rotation * Vec3::new(0.0, 0.0, target_distance);
+            cam.center = cam.center.lerp(goal, t);
+            if cam.center.distance(goal) < 0.01 {
+                cam.center = goal;
+                runtime.target = None;
+            }
+        }
+    }
+}
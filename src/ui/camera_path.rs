@@ -0,0 +1,341 @@
+use std::path::Path;
+use bevy::prelude::*;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use serde::{Deserialize, Serialize};
+use crate::input::arbitration::InputArbitration;
+use crate::ui::camera::{CameraConfig, CameraMode, MainCamera};
+
+/// Key that records the current camera pose as a keyframe
+pub const ADD_KEYFRAME_KEY: KeyCode = KeyCode::Insert;
+
+/// Distance in front of a free-fly camera used as the keyframe target
+const FREE_FLY_TARGET_DISTANCE: f32 = 10.0;
+
+/// Keyframes closer together than this (simulated seconds) replace each other
+const SAME_TIME_EPSILON: f32 = 1e-4;
+
+/// Camera pose at a simulated time
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CameraKeyframe {
+    pub position: Vec3,
+    pub target: Vec3,
+    /// Vertical field of view (degrees)
+    pub fov: f32,
+    /// Simulated seconds
+    pub time: f32,
+}
+
+impl CameraKeyframe {
+    fn pose(&self) -> CameraPose {
+        CameraPose { position: self.position, target: self.target, fov: self.fov }
+    }
+}
+
+/// Interpolated camera placement
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraPose {
+    pub position: Vec3,
+    pub target: Vec3,
+    /// Vertical field of view (degrees)
+    pub fov: f32,
+}
+
+/// Keyframed camera path, sorted by simulated time
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+impl CameraPath {
+    pub fn load_from_file(path: &Path) -> crate::error::Result<Self> {
+        let mut camera_path: Self = crate::error::read_json(path, "camera path")?;
+        camera_path.sort();
+        Ok(camera_path)
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> crate::error::Result<()> {
+        crate::error::write_json(path, self, "camera path")
+    }
+
+    /// Insert a keyframe in time order, replacing one at the same time; returns its index
+    pub fn insert(&mut self, keyframe: CameraKeyframe) -> usize {
+        if let Some(index) = self.keyframes.iter().position(|k| (k.time - keyframe.time).abs() < SAME_TIME_EPSILON) {
+            self.keyframes[index] = keyframe;
+            return index;
+        }
+        let index = self.keyframes.partition_point(|k| k.time < keyframe.time);
+        self.keyframes.insert(index, keyframe);
+        index
+    }
+
+    /// Restore time order after keyframe times were edited
+    pub fn sort(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Move the pose at `from` to `to`, keeping the time stamps where they are
+    pub fn move_keyframe(&mut self, from: usize, to: usize) {
+        if from >= self.keyframes.len() || to >= self.keyframes.len() || from == to {
+            return;
+        }
+        let times: Vec<f32> = self.keyframes.iter().map(|k| k.time).collect();
+        let keyframe = self.keyframes.remove(from);
+        self.keyframes.insert(to, keyframe);
+        for (keyframe, time) in self.keyframes.iter_mut().zip(times) {
+            keyframe.time = time;
+        }
+    }
+
+    /// Simulated time span covered by the keyframes
+    pub fn time_range(&self) -> Option<(f32, f32)> {
+        Some((self.keyframes.first()?.time, self.keyframes.last()?.time))
+    }
+
+    /// Pose at a simulated time, held at the first and last keyframe outside the path
+    ///
+    /// Position and target follow a Catmull-Rom spline whose tangents are scaled by the
+    /// keyframe spacing, so uneven gaps don't overshoot; the field of view eases per segment.
+    pub fn pose_at(&self, time: f32) -> Option<CameraPose> {
+        let keys = &self.keyframes;
+        let (first, last) = (keys.first()?, keys.last()?);
+        if keys.len() == 1 || time <= first.time {
+            return Some(first.pose());
+        }
+        if time >= last.time {
+            return Some(last.pose());
+        }
+
+        let i = keys.partition_point(|k| k.time <= time).saturating_sub(1).min(keys.len() - 2);
+        let (a, b) = (&keys[i], &keys[i + 1]);
+        let span = b.time - a.time;
+        if span <= f32::EPSILON {
+            return Some(b.pose());
+        }
+        let s = (time - a.time) / span;
+
+        let tangent = |index: usize, value: fn(&CameraKeyframe) -> Vec3| {
+            let prev = index.saturating_sub(1);
+            let next = (index + 1).min(keys.len() - 1);
+            let dt = keys[next].time - keys[prev].time;
+            if dt <= f32::EPSILON {
+                Vec3::ZERO
+            } else {
+                (value(&keys[next]) - value(&keys[prev])) / dt
+            }
+        };
+        let position = |k: &CameraKeyframe| k.position;
+        let target = |k: &CameraKeyframe| k.target;
+        let eased = s * s * (3.0 - 2.0 * s);
+
+        Some(CameraPose {
+            position: hermite(a.position, tangent(i, position), b.position, tangent(i + 1, position), span, s),
+            target: hermite(a.target, tangent(i, target), b.target, tangent(i + 1, target), span, s),
+            fov: a.fov + (b.fov - a.fov) * eased,
+        })
+    }
+}
+
+/// Cubic Hermite interpolation with tangents in units per simulated second
+fn hermite(p0: Vec3, m0: Vec3, p1: Vec3, m1: Vec3, span: f32, s: f32) -> Vec3 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+    p0 * (2.0 * s3 - 3.0 * s2 + 1.0)
+        + m0 * span * (s3 - 2.0 * s2 + s)
+        + p1 * (3.0 * s2 - 2.0 * s3)
+        + m1 * span * (s3 - s2)
+}
+
+/// The presentation camera path and its playback (Camera Path panel)
+#[derive(Resource, Default)]
+pub struct CameraPathPlayer {
+    pub path: CameraPath,
+    /// Follow the path as simulated time advances
+    pub playing: bool,
+    /// Playback stopped because the camera was moved by hand
+    pub paused_by_input: bool,
+    /// Path-local time shown instead of the simulation's while scrubbing
+    pub preview_time: Option<f32>,
+    /// Set by the UI (or the Insert key) to record the current camera pose
+    pub add_requested: bool,
+    /// The path positioned the camera last frame (the configured FOV is restored when it stops)
+    driving: bool,
+}
+
+impl CameraPathPlayer {
+    pub fn driving(&self) -> bool {
+        self.driving
+    }
+}
+
+/// Record keyframes and place the camera along the path while playing, scrubbing or capturing
+pub fn camera_path_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    camera_state: Res<crate::ui::CameraState>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    arbitration: Res<InputArbitration>,
+    config: Res<CameraConfig>,
+    mut player: ResMut<CameraPathPlayer>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    capture_settings: Res<crate::simulation::CaptureSettings>,
+    captures: Res<crate::simulation::Captures>,
+    mut camera_query: Query<(&mut MainCamera, &Transform, &mut Projection)>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    let Ok((mut cam, transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let now = crate::rendering::day_night::active_time(&sim_state, main_state.as_deref(), preview_state.as_deref());
+
+    if keyboard.just_pressed(ADD_KEYFRAME_KEY) && arbitration.keyboard_free() {
+        player.add_requested = true;
+    }
+    if std::mem::take(&mut player.add_requested) {
+        let forward = transform.forward();
+        let target = match cam.mode {
+            CameraMode::Orbit if cam.distance > 0.01 => cam.center,
+            _ => transform.translation + forward * FREE_FLY_TARGET_DISTANCE,
+        };
+        let fov = match *projection {
+            Projection::Perspective(ref perspective) => perspective.fov.to_degrees(),
+            _ => config.fov,
+        };
+        let index = player.path.insert(CameraKeyframe { position: transform.translation, target, fov, time: now });
+        notifications.info(format!("Camera keyframe {} at t = {:.2}s", index + 1, now));
+    }
+
+    // Moving the camera by hand hands control back to the user
+    if player.driving
+        && crate::ui::camera_framing::manual_camera_input(&keyboard, &camera_state, &mouse_motion, &mouse_scroll, &arbitration, cam.mode)
+    {
+        if player.playing {
+            player.playing = false;
+            player.paused_by_input = true;
+        }
+        player.preview_time = None;
+    }
+
+    let time = player.preview_time.or_else(|| {
+        (player.playing || (capture_settings.use_camera_path && captures.capturing())).then_some(now)
+    });
+    match time.and_then(|time| player.path.pose_at(time)) {
+        Some(pose) => {
+            apply_pose(&mut cam, &mut projection, &pose);
+            player.driving = true;
+        }
+        None if player.driving => {
+            if let Projection::Perspective(ref mut perspective) = *projection {
+                perspective.fov = config.fov.to_radians();
+            }
+            player.driving = false;
+        }
+        None => {}
+    }
+}
+
+/// Place the camera at a pose without easing (camera_update derives the transform)
+fn apply_pose(cam: &mut MainCamera, projection: &mut Projection, pose: &CameraPose) {
+    if pose.position.distance_squared(pose.target) > 1e-6 {
+        let rotation = Transform::from_translation(pose.position).looking_at(pose.target, Vec3::Y).rotation;
+        cam.rotation = rotation;
+        cam.target_rotation = rotation;
+    }
+    match cam.mode {
+        CameraMode::Orbit => {
+            cam.center = pose.target;
+            cam.distance = pose.position.distance(pose.target);
+        }
+        CameraMode::FreeFly => {
+            cam.center = pose.position;
+            cam.distance = 0.0;
+        }
+    }
+    cam.target_distance = cam.distance;
+    cam.followed_entity = None;
+    if let Projection::Perspective(perspective) = projection {
+        perspective.fov = pose.fov.to_radians();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time: f32, position: Vec3, fov: f32) -> CameraKeyframe {
+        CameraKeyframe { position, target: Vec3::ZERO, fov, time }
+    }
+
+    #[test]
+    fn test_pose_holds_at_endpoints_and_hits_keyframes() {
+        let path = CameraPath {
+            keyframes: vec![
+                keyframe(1.0, Vec3::new(0.0, 0.0, 10.0), 45.0),
+                keyframe(2.0, Vec3::new(10.0, 5.0, 0.0), 60.0),
+                keyframe(4.0, Vec3::new(0.0, 0.0, -10.0), 30.0),
+            ],
+        };
+        assert_eq!(path.pose_at(0.0).unwrap().position, path.keyframes[0].position);
+        assert_eq!(path.pose_at(9.0).unwrap().position, path.keyframes[2].position);
+        for key in &path.keyframes {
+            let pose = path.pose_at(key.time).unwrap();
+            assert!(pose.position.distance(key.position) < 1e-4, "{:?} vs {:?}", pose.position, key.position);
+            assert!((pose.fov - key.fov).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_uneven_spacing_reproduces_linear_motion() {
+        // Constant velocity over unevenly spaced keyframes must not overshoot or wobble
+        let velocity = Vec3::new(2.0, -1.0, 0.5);
+        let times = [0.0, 0.3, 2.0, 2.2, 7.0];
+        let path = CameraPath {
+            keyframes: times.iter().map(|&t| keyframe(t, velocity * t, 50.0)).collect(),
+        };
+        let mut t = 0.0;
+        while t <= 7.0 {
+            let pose = path.pose_at(t).unwrap();
+            assert!(pose.position.distance(velocity * t) < 1e-3, "t = {t}: {:?}", pose.position);
+            t += 0.05;
+        }
+    }
+
+    #[test]
+    fn test_degenerate_paths() {
+        assert!(CameraPath::default().pose_at(1.0).is_none());
+
+        let single = CameraPath { keyframes: vec![keyframe(3.0, Vec3::X, 40.0)] };
+        for t in [0.0, 3.0, 10.0] {
+            assert_eq!(single.pose_at(t).unwrap(), single.keyframes[0].pose());
+        }
+    }
+
+    #[test]
+    fn test_fov_eases_between_keyframes() {
+        let path = CameraPath {
+            keyframes: vec![keyframe(0.0, Vec3::ZERO, 30.0), keyframe(1.0, Vec3::ZERO, 90.0)],
+        };
+        assert!((path.pose_at(0.5).unwrap().fov - 60.0).abs() < 1e-4);
+        // Slower than linear near the ends
+        assert!(path.pose_at(0.1).unwrap().fov < 36.0);
+        assert!(path.pose_at(0.9).unwrap().fov > 84.0);
+    }
+
+    #[test]
+    fn test_insert_and_reorder_keep_times_sorted() {
+        let mut path = CameraPath::default();
+        path.insert(keyframe(2.0, Vec3::X, 40.0));
+        path.insert(keyframe(1.0, Vec3::Y, 40.0));
+        assert_eq!(path.insert(keyframe(2.0, Vec3::Z, 40.0)), 1);
+        assert_eq!(path.keyframes.len(), 2);
+        assert_eq!(path.keyframes[1].position, Vec3::Z);
+
+        path.move_keyframe(1, 0);
+        assert_eq!(path.keyframes[0].position, Vec3::Z);
+        assert_eq!(path.keyframes[0].time, 1.0);
+        assert_eq!(path.keyframes[1].position, Vec3::Y);
+        assert_eq!(path.keyframes[1].time, 2.0);
+    }
+}
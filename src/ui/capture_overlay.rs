@@ -0,0 +1,151 @@
+use bevy_egui::egui;
+use crate::simulation::capture::{AnnotationCorner, AnnotationField, AnnotationSettings, CaptureSettings, Captures};
+
+/// Capture menu: take a screenshot and configure the annotation overlay
+pub fn render_capture_menu(ui: &mut egui::Ui, settings: &mut CaptureSettings, captures: &mut Captures) {
+    ui.horizontal(|ui| {
+        if ui.button("Take Screenshot").clicked() {
+            captures.screenshot();
+            ui.close();
+        }
+        ui.weak("F12");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Folder:");
+        if ui.button(settings.directory.display().to_string())
+            .on_hover_text("Choose where screenshots are saved")
+            .clicked()
+        {
+            if let Some(folder) = rfd::FileDialog::new().set_directory(&settings.directory).pick_folder() {
+                settings.directory = folder;
+            }
+        }
+    });
+    if captures.pending_writes() > 0 {
+        ui.weak(format!("Writing {} screenshot(s)...", captures.pending_writes()));
+    }
+
+    ui.checkbox(&mut settings.use_camera_path, "Use camera path")
+        .on_hover_text("Place the camera on the Camera Path at the capture's simulated time");
+
+    ui.separator();
+    ui.menu_button("Annotations", |ui| render_annotation_settings(ui, &mut settings.annotations));
+}
+
+fn render_annotation_settings(ui: &mut egui::Ui, annotations: &mut AnnotationSettings) {
+    ui.checkbox(&mut annotations.enabled, "Burn in annotations")
+        .on_hover_text("Draw the simulation details into screenshots and session snapshots");
+    ui.add_enabled_ui(annotations.enabled, |ui| {
+        ui.checkbox(&mut annotations.preview, "Preview in viewport")
+            .on_hover_text("Show the overlay over the window as it will appear in captures");
+
+        ui.separator();
+        egui::Grid::new("annotation_fields").num_columns(2).show(ui, |ui| {
+            for field in AnnotationField::ALL {
+                ui.label(field.name());
+                let mut corner = annotations.corners.get(&field).copied();
+                egui::ComboBox::from_id_salt(("annotation_corner", field))
+                    .selected_text(corner.map_or("Hidden", AnnotationCorner::name))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut corner, None, "Hidden");
+                        for option in AnnotationCorner::ALL {
+                            ui.selectable_value(&mut corner, Some(option), option.name());
+                        }
+                    });
+                match corner {
+                    Some(corner) => annotations.corners.insert(field, corner),
+                    None => annotations.corners.remove(&field),
+                };
+                ui.end_row();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Caption:");
+            ui.text_edit_singleline(&mut annotations.caption);
+        });
+
+        ui.separator();
+        ui.add(egui::Slider::new(&mut annotations.font_size, 9..=72).text("Font size (px)"));
+        ui.checkbox(&mut annotations.background, "Background chip")
+            .on_hover_text("Dark chip behind the text; off draws a drop shadow instead");
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let label = annotations.watermark.as_ref()
+                .and_then(|path| path.file_name())
+                .map_or("None".to_string(), |name| name.to_string_lossy().into_owned());
+            ui.label("Watermark:");
+            if ui.button(label).clicked() {
+                if let Some(path) = rfd::FileDialog::new().add_filter("Image", &["png", "jpg", "jpeg"]).pick_file() {
+                    annotations.watermark = Some(path);
+                }
+            }
+            if annotations.watermark.is_some() && ui.small_button("✖").on_hover_text("Remove the watermark").clicked() {
+                annotations.watermark = None;
+            }
+        });
+        ui.add_enabled_ui(annotations.watermark.is_some(), |ui| {
+            egui::ComboBox::from_label("Watermark corner")
+                .selected_text(annotations.watermark_corner.name())
+                .show_ui(ui, |ui| {
+                    for corner in AnnotationCorner::ALL {
+                        ui.selectable_value(&mut annotations.watermark_corner, corner, corner.name());
+                    }
+                });
+            ui.add(egui::Slider::new(&mut annotations.watermark_opacity, 0.0..=1.0).text("Watermark opacity"));
+        });
+    });
+}
+
+/// Draw the annotation blocks over the window as captures will show them
+///
+/// Captures are of the whole window, so the blocks sit in the window's corners. The
+/// preview is hidden while a frame is being captured so it isn't burned in twice.
+pub fn render_capture_preview(ctx: &egui::Context, settings: &CaptureSettings, captures: &Captures) {
+    let Some(metadata) = captures.preview.as_ref().filter(|_| !captures.capturing()) else {
+        return;
+    };
+    let annotations = &settings.annotations;
+    let pixels_per_point = ctx.pixels_per_point();
+    let scale = annotations.scale() as f32 / pixels_per_point;
+    let line_height = crate::simulation::capture_font::LINE_HEIGHT as f32 * scale;
+    let margin = 4.0 * scale;
+    let padding = 3.0 * scale;
+    let screen = ctx.content_rect();
+
+    for (corner, lines) in annotations.blocks(metadata) {
+        let align = match corner {
+            AnnotationCorner::TopLeft => egui::Align2::LEFT_TOP,
+            AnnotationCorner::TopRight => egui::Align2::RIGHT_TOP,
+            AnnotationCorner::BottomLeft => egui::Align2::LEFT_BOTTOM,
+            AnnotationCorner::BottomRight => egui::Align2::RIGHT_BOTTOM,
+        };
+        let anchor = align.pos_in_rect(&screen.shrink(margin));
+        egui::Area::new(egui::Id::new(("capture_preview", corner)))
+            .pivot(align)
+            .fixed_pos(anchor)
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                let fill = if annotations.background { egui::Color32::from_black_alpha(153) } else { egui::Color32::TRANSPARENT };
+                egui::Frame::NONE
+                    .fill(fill)
+                    .inner_margin(padding)
+                    .show(ui, |ui| {
+                        ui.spacing_mut().item_spacing.y = 0.0;
+                        let layout = if matches!(corner, AnnotationCorner::TopRight | AnnotationCorner::BottomRight) {
+                            egui::Layout::top_down(egui::Align::Max)
+                        } else {
+                            egui::Layout::top_down(egui::Align::Min)
+                        };
+                        ui.with_layout(layout, |ui| {
+                            for line in &lines {
+                                ui.label(egui::RichText::new(line)
+                                    .font(egui::FontId::monospace(line_height * 0.8))
+                                    .color(egui::Color32::WHITE));
+                            }
+                        });
+                    });
+            });
+    }
+}
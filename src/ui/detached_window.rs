@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use bevy::camera::RenderTarget;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::window::{WindowPosition, WindowRef, WindowResolution};
+use bevy_egui::{egui, EguiContext, EguiMultipassSchedule, PrimaryEguiContext};
+use crate::genome::CurrentGenome;
+use crate::ui::dock::{close_panel, reattach_panel, DockResource, Panel};
+use crate::ui::settings::{DetachedWindowGeometry, UiSettings};
+use crate::ui::GlobalUiState;
+
+/// Plugin for popping dock panels out into their own OS windows
+pub struct DetachedWindowPlugin;
+
+impl Plugin for DetachedWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DetachedPanels>()
+            .add_systems(Update, (
+                open_detached_windows,
+                track_detached_windows,
+                reattach_on_exit.before(crate::ui::save_on_exit),
+            ).chain())
+            .add_systems(DetachedPanelPass(Panel::GenomeGraph), render_detached_genome_graph);
+    }
+}
+
+/// Egui pass of the window a panel was detached into
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DetachedPanelPass(pub Panel);
+
+/// Marks the camera rendering a detached panel's window
+#[derive(Component)]
+pub struct DetachedPanelCamera(pub Panel);
+
+struct DetachedWindow {
+    panel: Panel,
+    window: Entity,
+    camera: Entity,
+    /// Main-surface leaf the panel was docked in before detaching
+    home: Option<egui_dock::NodeIndex>,
+    geometry: DetachedWindowGeometry,
+}
+
+/// Panels currently shown in their own OS windows
+#[derive(Resource, Default)]
+pub struct DetachedPanels {
+    requests: Vec<Panel>,
+    windows: Vec<DetachedWindow>,
+    /// The cursor is over a detached window, so viewport input must ignore it
+    pub wants_pointer: bool,
+    /// A detached window has keyboard focus
+    pub wants_keyboard: bool,
+}
+
+impl DetachedPanels {
+    /// Panels that can be popped out of the dock
+    pub fn can_detach(panel: &Panel) -> bool {
+        matches!(panel, Panel::GenomeGraph)
+    }
+
+    /// Pop the panel out into its own window next frame
+    pub fn request_detach(&mut self, panel: Panel) {
+        if Self::can_detach(&panel) && !self.is_detached(&panel) && !self.requests.contains(&panel) {
+            self.requests.push(panel);
+        }
+    }
+
+    pub fn is_detached(&self, panel: &Panel) -> bool {
+        self.windows.iter().any(|window| window.panel == *panel)
+    }
+}
+
+fn geometry_for(settings: &UiSettings, panel: &Panel) -> DetachedWindowGeometry {
+    match panel {
+        Panel::GenomeGraph => settings.detached_windows.genome_graph,
+        _ => DetachedWindowGeometry::default(),
+    }
+}
+
+/// Remember where the user left a detached window
+fn save_geometry(panel: &Panel, geometry: DetachedWindowGeometry, notifications: &mut crate::ui::Notifications) {
+    let mut settings = UiSettings::load();
+    match panel {
+        Panel::GenomeGraph => settings.detached_windows.genome_graph = geometry,
+        _ => return,
+    }
+    if let Err(e) = settings.save() {
+        notifications.error(&e);
+    }
+}
+
+/// Take requested panels out of the dock and give each its own window and egui context
+fn open_detached_windows(
+    mut commands: Commands,
+    mut detached: ResMut<DetachedPanels>,
+    mut dock_resource: ResMut<DockResource>,
+) {
+    if detached.requests.is_empty() {
+        return;
+    }
+
+    let settings = UiSettings::load();
+    for panel in std::mem::take(&mut detached.requests) {
+        let home = dock_resource.tree.find_tab(&panel)
+            .filter(|(surface, _, _)| surface.is_main())
+            .map(|(_, node, _)| node);
+        // The dock reflows around the gap on its next layout pass
+        close_panel(&mut dock_resource.tree, &panel);
+        dock_resource.store_current_tree();
+
+        let geometry = geometry_for(&settings, &panel);
+        let window = commands.spawn(Window {
+            title: format!("BioSpheres - {}", panel),
+            resolution: WindowResolution::new(geometry.size[0], geometry.size[1]),
+            position: geometry.position
+                .map(|[x, y]| WindowPosition::At(IVec2::new(x, y)))
+                .unwrap_or(WindowPosition::Automatic),
+            ..default()
+        }).id();
+        let camera = commands.spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Window(WindowRef::Entity(window)),
+                ..default()
+            },
+            EguiMultipassSchedule::new(DetachedPanelPass(panel.clone())),
+            DetachedPanelCamera(panel.clone()),
+        )).id();
+
+        info!("Detached {} into its own window", panel);
+        detached.windows.push(DetachedWindow { panel, window, camera, home, geometry });
+    }
+}
+
+/// Follow detached windows as they move and resize, and dock their panels again once closed
+fn track_detached_windows(
+    mut commands: Commands,
+    mut detached: ResMut<DetachedPanels>,
+    mut dock_resource: ResMut<DockResource>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    windows: Query<&Window>,
+) {
+    let mut wants_pointer = false;
+    let mut wants_keyboard = false;
+    let mut closed = Vec::new();
+
+    for (index, detached_window) in detached.windows.iter_mut().enumerate() {
+        // Closing a window despawns its entity
+        let Ok(window) = windows.get(detached_window.window) else {
+            closed.push(index);
+            continue;
+        };
+
+        detached_window.geometry.size = [window.resolution.physical_width(), window.resolution.physical_height()];
+        if let WindowPosition::At(position) = window.position {
+            detached_window.geometry.position = Some([position.x, position.y]);
+        }
+        wants_pointer |= window.cursor_position().is_some();
+        wants_keyboard |= window.focused;
+    }
+
+    for index in closed.into_iter().rev() {
+        let detached_window = detached.windows.remove(index);
+        commands.entity(detached_window.camera).despawn();
+        save_geometry(&detached_window.panel, detached_window.geometry, &mut notifications);
+        reattach_panel(&mut dock_resource.tree, &detached_window.panel, detached_window.home);
+        dock_resource.store_current_tree();
+        info!("Re-attached {} to the dock", detached_window.panel);
+    }
+
+    detached.wants_pointer = wants_pointer;
+    detached.wants_keyboard = wants_keyboard;
+}
+
+/// Dock detached panels again before the layout is saved on exit, so they come back next session
+fn reattach_on_exit(
+    mut exit_events: MessageReader<bevy::app::AppExit>,
+    mut detached: ResMut<DetachedPanels>,
+    mut dock_resource: ResMut<DockResource>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    for detached_window in std::mem::take(&mut detached.windows) {
+        save_geometry(&detached_window.panel, detached_window.geometry, &mut notifications);
+        reattach_panel(&mut dock_resource.tree, &detached_window.panel, detached_window.home);
+    }
+    dock_resource.store_current_tree();
+}
+
+fn render_detached_genome_graph(
+    mut contexts: Query<(&mut EguiContext, &DetachedPanelCamera), Without<PrimaryEguiContext>>,
+    mut current_genome: ResMut<CurrentGenome>,
+    global_ui_state: Res<GlobalUiState>,
+    mode_templates: Res<crate::genome::ModeTemplates>,
+    input_bindings: Res<crate::input::InputBindings>,
+    mut genome_editor_state: ResMut<crate::ui::GenomeEditorState>,
+    mut node_graph: ResMut<crate::genome::GenomeNodeGraph>,
+    // Textures belong to the context that loaded them, so the detached window keeps its own
+    mut thumbnails: Local<crate::ui::genome_editor::ModeThumbnails>,
+) {
+    for (mut egui_context, camera) in contexts.iter_mut() {
+        if camera.0 != Panel::GenomeGraph {
+            continue;
+        }
+        let ctx = egui_context.get_mut();
+        ctx.set_zoom_factor(global_ui_state.ui_scale);
+        #[allow(deprecated)]
+        egui::CentralPanel::default().show(ctx, |ui| {
+            crate::ui::genome_editor::render_genome_graph(ui, &mut current_genome, &mut node_graph, &mut thumbnails, &mut genome_editor_state.selected_modes, &mode_templates, &input_bindings);
+        });
+    }
+}
@@ -4,6 +4,7 @@ use egui_dock::DockState;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use crate::safe_mode::Subsystem;
 use std::time::Duration;
 
 const DOCK_STATE_FILE: &str = "dock_state.ron";
@@ -30,6 +31,15 @@ pub enum Panel {
     ThemeEditor,
     CameraSettings,
     LightingSettings,
+    PhysicsSettings,
+    Log,
+    Measurements,
+    ParameterSweep,
+    GenomeGraph,
+    SessionBrowser,
+    DivisionDebug,
+    Breakpoints,
+    CellGroups,
     
     // Legacy names for compatibility
     Inspector,
@@ -43,6 +53,11 @@ pub enum Panel {
     AdhesionSettings,
     ParentSettings,
     TimeSlider,
+    GenomePhases,
+    ContactAdhesion,
+    PopulationForecast,
+    MaterialLibrary,
+    CameraPath,
 }
 
 impl Panel {
@@ -68,6 +83,15 @@ impl std::fmt::Display for Panel {
             Panel::ThemeEditor => write!(f, "Theme Editor"),
             Panel::CameraSettings => write!(f, "Camera Settings"),
             Panel::LightingSettings => write!(f, "Lighting Settings"),
+            Panel::PhysicsSettings => write!(f, "Physics Settings"),
+            Panel::Log => write!(f, "Log"),
+            Panel::SessionBrowser => write!(f, "Session Browser"),
+            Panel::DivisionDebug => write!(f, "Division Debug"),
+            Panel::Breakpoints => write!(f, "Breakpoints"),
+            Panel::CellGroups => write!(f, "Cell Groups"),
+            Panel::Measurements => write!(f, "Measurements"),
+            Panel::ParameterSweep => write!(f, "Parameter Sweep"),
+            Panel::GenomeGraph => write!(f, "Genome Graph"),
             // Legacy names
             Panel::Inspector => write!(f, "Inspector"),
             Panel::Console => write!(f, "Console"),
@@ -80,6 +104,11 @@ impl std::fmt::Display for Panel {
             Panel::AdhesionSettings => write!(f, "Adhesion Settings"),
             Panel::ParentSettings => write!(f, "Parent Settings"),
             Panel::TimeSlider => write!(f, "Time Slider"),
+            Panel::GenomePhases => write!(f, "Genome Phases"),
+            Panel::ContactAdhesion => write!(f, "Contact Adhesion"),
+            Panel::PopulationForecast => write!(f, "Population Forecast"),
+            Panel::MaterialLibrary => write!(f, "Material Library"),
+            Panel::CameraPath => write!(f, "Camera Path"),
         }
     }
 }
@@ -94,15 +123,21 @@ pub struct DockResource {
     pub current_mode: crate::simulation::SimulationMode,
 }
 
-pub fn load_dock_state() -> Option<DockState<Panel>> {
-    if Path::new(DOCK_STATE_FILE).exists() {
-        let data = fs::read_to_string(DOCK_STATE_FILE).ok()?;
-        ron::from_str(&data).ok()
-    } else {
-        None
+impl DockResource {
+    /// Copy the live tree into the stored layout for the current mode
+    pub fn store_current_tree(&mut self) {
+        match self.current_mode {
+            crate::simulation::SimulationMode::Preview => self.preview_tree = self.tree.clone(),
+            crate::simulation::SimulationMode::Cpu => self.cpu_tree = self.tree.clone(),
+            crate::simulation::SimulationMode::Gpu => {}
+        }
     }
 }
 
+pub fn load_dock_state() -> Option<DockState<Panel>> {
+    load_dock_state_from_file(DOCK_STATE_FILE)
+}
+
 pub fn load_dock_state_for_mode(mode: &str) -> Option<DockState<Panel>> {
     let filename = match mode {
         "preview" => PREVIEW_DOCK_STATE_FILE,
@@ -110,18 +145,11 @@ pub fn load_dock_state_for_mode(mode: &str) -> Option<DockState<Panel>> {
         _ => return None,
     };
     
-    if Path::new(filename).exists() {
-        let data = fs::read_to_string(filename).ok()?;
-        ron::from_str(&data).ok()
-    } else {
-        None
-    }
+    load_dock_state_from_file(filename)
 }
 
 pub fn save_dock_state(tree: &DockState<Panel>) {
-    if let Ok(serialized) = ron::ser::to_string_pretty(tree, Default::default()) {
-        let _ = fs::write(DOCK_STATE_FILE, serialized);
-    }
+    save_dock_state_to_file(tree, DOCK_STATE_FILE);
 }
 
 pub fn save_dock_state_for_mode(tree: &DockState<Panel>, mode: &str) {
@@ -131,9 +159,7 @@ pub fn save_dock_state_for_mode(tree: &DockState<Panel>, mode: &str) {
         _ => return,
     };
     
-    if let Ok(serialized) = ron::ser::to_string_pretty(tree, Default::default()) {
-        let _ = fs::write(filename, serialized);
-    }
+    save_dock_state_to_file(tree, filename);
 }
 
 pub fn create_default_layout() -> DockState<Panel> {
@@ -160,7 +186,18 @@ pub fn create_default_cpu_layout() -> DockState<Panel> {
     create_hardcoded_cpu_layout()
 }
 
+/// Whether safe mode bypasses saved layouts
+///
+/// Layout loads and autosaves go through the two helpers below, so in safe mode saved
+/// layouts are neither read nor overwritten and the hardcoded defaults are used.
+fn layouts_bypassed() -> bool {
+    crate::safe_mode::is_bypassed(Subsystem::Layout)
+}
+
 fn load_dock_state_from_file(filename: &str) -> Option<DockState<Panel>> {
+    if layouts_bypassed() {
+        return None;
+    }
     if Path::new(filename).exists() {
         let data = fs::read_to_string(filename).ok()?;
         ron::from_str(&data).ok()
@@ -169,6 +206,15 @@ fn load_dock_state_from_file(filename: &str) -> Option<DockState<Panel>> {
     }
 }
 
+fn save_dock_state_to_file(tree: &DockState<Panel>, filename: &str) {
+    if layouts_bypassed() {
+        return;
+    }
+    if let Ok(serialized) = ron::ser::to_string_pretty(tree, Default::default()) {
+        let _ = fs::write(filename, serialized);
+    }
+}
+
 fn create_hardcoded_preview_layout() -> DockState<Panel> {
     // Create the initial layout matching the exact saved Preview layout structure
     let mut tree = DockState::new(vec![Panel::LeftPanel]);
@@ -337,6 +383,22 @@ pub fn open_panel(tree: &mut DockState<Panel>, panel: &Panel, screen_rect: Optio
     }
 }
 
+/// Put a panel back into the dock, into the main-surface leaf it was taken from if that still exists
+pub fn reattach_panel(tree: &mut DockState<Panel>, panel: &Panel, home: Option<egui_dock::NodeIndex>) {
+    if is_panel_open(tree, panel) {
+        return;
+    }
+
+    let main_surface = tree.main_surface_mut();
+    match home.filter(|node| node.0 < main_surface.len() && main_surface[*node].is_leaf()) {
+        Some(node) => {
+            main_surface.set_focused_node(node);
+            main_surface.push_to_focused_leaf(panel.clone());
+        }
+        None => open_panel(tree, panel, None),
+    }
+}
+
 #[derive(Resource)]
 pub struct SaveTimer {
     timer: Timer,
@@ -380,7 +442,7 @@ pub fn save_on_exit(
     }
 }
 
-pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockResource, global_ui_state: &mut crate::ui::GlobalUiState) {
+pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockResource, global_ui_state: &mut crate::ui::GlobalUiState, hud: &mut crate::ui::HudSettings) {
     // Get the viewport rect for centering windows
     let screen_rect = ui.ctx().input(|i| i.viewport_rect());
     
@@ -391,6 +453,10 @@ pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockR
         .suffix("x"));
     
     ui.separator();
+
+    crate::ui::hud::show_hud_menu(ui, hud);
+
+    ui.separator();
     
     // Get the appropriate locked windows set based on current scene
     let locked_windows = match dock_resource.current_mode {
@@ -408,6 +474,11 @@ pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockR
         Panel::CircleSliders,
         Panel::QuaternionBall,
         Panel::TimeSlider,
+        Panel::GenomeGraph,
+        Panel::GenomePhases,
+        Panel::ContactAdhesion,
+        Panel::PopulationForecast,
+        Panel::MaterialLibrary,
     ];
 
     // Only show genome editor windows in Preview mode
@@ -438,6 +509,8 @@ pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockR
                     }
                 }
                 
+                crate::ui::hud::panel_opacity_control(ui, hud, &panel_name);
+
                 // Lock/Unlock button
                 let lock_icon = if is_locked { "🔒" } else { "🔓" };
                 if ui.small_button(lock_icon).clicked() {
@@ -488,6 +561,10 @@ pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockR
                 }
             }
             
+            if *panel != Panel::Viewport {
+                crate::ui::hud::panel_opacity_control(ui, hud, &panel_name);
+            }
+
             // Lock/Unlock button
             let lock_icon = if is_locked { "🔒" } else { "🔓" };
             if ui.small_button(lock_icon).clicked() {
@@ -505,39 +582,57 @@ pub fn show_windows_menu(ui: &mut bevy_egui::egui::Ui, dock_resource: &mut DockR
     // Other windows
     ui.label("Other Windows:");
 
-    // Scene Manager
-    let scene_manager_open = is_panel_open(&dock_resource.tree, &Panel::SceneManager);
-    let scene_manager_name = Panel::SceneManager.to_string();
-    let scene_manager_locked = locked_windows.contains(&scene_manager_name);
-    
-    ui.horizontal(|ui| {
-        if ui.selectable_label(scene_manager_open, "  Scene Manager").clicked() {
-            if scene_manager_open {
-                close_panel(&mut dock_resource.tree, &Panel::SceneManager);
-            } else {
-                open_panel(&mut dock_resource.tree, &Panel::SceneManager, Some(screen_rect));
-            }
-            // Sync changes to the stored tree for current mode
-            match dock_resource.current_mode {
-                crate::simulation::SimulationMode::Preview => {
-                    dock_resource.preview_tree = dock_resource.tree.clone();
+    let other_panels = [
+        Panel::SceneManager,
+        Panel::CellInspector,
+        Panel::PhysicsSettings,
+        Panel::PerformanceMonitor,
+        Panel::Measurements,
+        Panel::ParameterSweep,
+        Panel::SessionBrowser,
+        Panel::DivisionDebug,
+        Panel::Breakpoints,
+        Panel::CellGroups,
+        Panel::CameraPath,
+        Panel::Log,
+    ];
+
+    for panel in &other_panels {
+        let is_open = is_panel_open(&dock_resource.tree, panel);
+        let panel_name = panel.to_string();
+        let is_locked = locked_windows.contains(&panel_name);
+
+        ui.horizontal(|ui| {
+            if ui.selectable_label(is_open, format!("  {}", panel)).clicked() {
+                if is_open {
+                    close_panel(&mut dock_resource.tree, panel);
+                } else {
+                    open_panel(&mut dock_resource.tree, panel, Some(screen_rect));
                 }
-                crate::simulation::SimulationMode::Cpu => {
-                    dock_resource.cpu_tree = dock_resource.tree.clone();
+                // Sync changes to the stored tree for current mode
+                match dock_resource.current_mode {
+                    crate::simulation::SimulationMode::Preview => {
+                        dock_resource.preview_tree = dock_resource.tree.clone();
+                    }
+                    crate::simulation::SimulationMode::Cpu => {
+                        dock_resource.cpu_tree = dock_resource.tree.clone();
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
-        }
-        
-        let lock_icon = if scene_manager_locked { "🔒" } else { "🔓" };
-        if ui.small_button(lock_icon).clicked() {
-            if scene_manager_locked {
-                locked_windows.remove(&scene_manager_name);
-            } else {
-                locked_windows.insert(scene_manager_name);
+
+            crate::ui::hud::panel_opacity_control(ui, hud, &panel_name);
+            
+            let lock_icon = if is_locked { "🔒" } else { "🔓" };
+            if ui.small_button(lock_icon).clicked() {
+                if is_locked {
+                    locked_windows.remove(&panel_name);
+                } else {
+                    locked_windows.insert(panel_name);
+                }
             }
-        }
-    });
+        });
+    }
 
     ui.separator();
 
@@ -636,3 +731,27 @@ pub fn switch_dock_on_scene_change(
         dock_resource.current_mode = sim_state.mode;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui_dock::{NodeIndex, SurfaceIndex};
+
+    #[test]
+    fn test_reattach_returns_panel_to_its_leaf() {
+        let mut tree = DockState::new(vec![Panel::Viewport]);
+        let [_, side] = tree.main_surface_mut().split_right(NodeIndex::root(), 0.7, vec![Panel::Modes, Panel::GenomeGraph]);
+
+        close_panel(&mut tree, &Panel::GenomeGraph);
+        assert!(!is_panel_open(&tree, &Panel::GenomeGraph));
+
+        reattach_panel(&mut tree, &Panel::GenomeGraph, Some(side));
+        let (surface, node, _) = tree.find_tab(&Panel::GenomeGraph).expect("panel docked again");
+        assert_eq!(surface, SurfaceIndex::main());
+        assert_eq!(node, side);
+
+        // Already docked: nothing is duplicated
+        reattach_panel(&mut tree, &Panel::GenomeGraph, Some(side));
+        assert_eq!(tree.iter_all_tabs().filter(|(_, tab)| **tab == Panel::GenomeGraph).count(), 1);
+    }
+}
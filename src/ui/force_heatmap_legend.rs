@@ -0,0 +1,54 @@
+use bevy_egui::egui;
+use crate::simulation::{ForceHeatmap, HeatChannel};
+
+/// Gradient steps drawn per legend bar
+const LEGEND_STEPS: usize = 24;
+
+/// Draw the shown force heatmap channels' palettes and scales at the bottom of the viewport
+pub fn render_force_heatmap_legend(ctx: &egui::Context, viewport: egui::Rect, heatmap: &ForceHeatmap) {
+    if !HeatChannel::ALL.iter().any(|&channel| heatmap.shown(channel)) {
+        return;
+    }
+
+    let margin = 8.0;
+    egui::Area::new(egui::Id::new("force_heatmap_legend"))
+        .pivot(egui::Align2::CENTER_BOTTOM)
+        .fixed_pos(viewport.center_bottom() + egui::vec2(0.0, -margin))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .fill(egui::Color32::from_black_alpha(170))
+                .show(ui, |ui| {
+                    let state = if heatmap.frozen { " (frozen)" } else { "" };
+                    ui.label(egui::RichText::new(format!(
+                        "Force heatmap, τ = {:.2} s{}", heatmap.time_constant, state
+                    )).small());
+                    for channel in HeatChannel::ALL {
+                        if heatmap.shown(channel) {
+                            legend_bar(ui, channel, heatmap.scale[channel.index()]);
+                        }
+                    }
+                });
+        });
+}
+
+/// One channel's gradient from zero to the largest average in the scene
+fn legend_bar(ui: &mut egui::Ui, channel: HeatChannel, max: f32) {
+    ui.horizontal(|ui| {
+        ui.add_sized([64.0, 14.0], egui::Label::new(egui::RichText::new(channel.name()).small()));
+        ui.label(egui::RichText::new("0").small().weak());
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(140.0, 10.0), egui::Sense::hover());
+        let step = rect.width() / LEGEND_STEPS as f32;
+        for i in 0..LEGEND_STEPS {
+            let [r, g, b] = channel.color((i as f32 + 0.5) / LEGEND_STEPS as f32);
+            let x = rect.left() + i as f32 * step;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_max(egui::pos2(x, rect.top()), egui::pos2(x + step + 0.5, rect.bottom())),
+                0.0,
+                egui::Color32::from_rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8),
+            );
+        }
+        ui.label(egui::RichText::new(format!("{:.1}", max)).small().weak());
+    });
+}
@@ -1,15 +1,238 @@
 use bevy_egui::egui;
-use crate::genome::CurrentGenome;
+use crate::genome::{CurrentGenome, GenomeNodeGraph, ModeTemplates};
+use crate::input::bindings::{BindingButton, InputBindings, Modifiers};
+use super::mode_thumbnails::{draw_mode_chip, ModeThumbnails};
 
-/// Placeholder for genome graph node editor
-/// TODO: Implement using egui_node_graph once dependency is resolved
-pub fn render_genome_graph(ui: &mut egui::Ui, _current_genome: &mut CurrentGenome) {
-    egui::ScrollArea::vertical()
+/// Node width at zoom 1; nodes grow taller to fit their chip, anchored at their saved top-left
+const NODE_WIDTH: f32 = 150.0;
+
+/// Genome graph: one node per mode, linked to its child A and child B modes
+///
+/// Node headers carry the mode's color and pattern swatch and node bodies its thumbnail
+/// chip. Clicking a node selects its mode and dragging moves it. Shift-clicking the graph
+/// opens the Add Mode templates at the pointer. The graph pans with the `graph_pan`
+/// binding and zooms with pinch or Ctrl+scroll, so no middle button is needed.
+pub fn render_genome_graph(
+    ui: &mut egui::Ui,
+    current_genome: &mut CurrentGenome,
+    node_graph: &mut GenomeNodeGraph,
+    thumbnails: &mut ModeThumbnails,
+    selected_modes: &mut std::collections::BTreeSet<usize>,
+    templates: &ModeTemplates,
+    bindings: &InputBindings,
+) {
+    thumbnails.evict_removed(current_genome.genome.modes.len());
+    node_graph.sync_with_genome(&current_genome.genome);
+
+    // Zoom lives in egui memory next to the popup position
+    let zoom_id = ui.id().with("graph_zoom");
+    let mut zoom = ui.memory(|memory| memory.data.get_temp::<f32>(zoom_id)).unwrap_or(1.0);
+    let hovered = ui.rect_contains_pointer(ui.max_rect());
+    let zoom_delta = ui.input(|i| i.zoom_delta());
+    if hovered && zoom_delta != 1.0 {
+        zoom = (zoom * zoom_delta.powf(bindings.sensitivity.graph_zoom)).clamp(0.5, 3.0);
+        ui.memory_mut(|memory| memory.data.insert_temp(zoom_id, zoom));
+    }
+    let panning = hovered && ui.input(|i| {
+        bindings.graph_pan_active(|button| i.pointer.button_down(pointer_button(button)), modifiers(i.modifiers))
+    });
+    // With a left-button pan binding a left drag pans the graph instead of moving a node
+    let nodes_movable = bindings.graph_pan.button != BindingButton::Left;
+
+    // Registered before the nodes so clicks on a node still select its mode
+    let popup_id = ui.id().with("add_mode_popup");
+    let background = ui.interact(ui.max_rect(), ui.id().with("graph_background"), egui::Sense::click());
+    if background.clicked() && ui.input(|i| i.modifiers.shift) {
+        if let Some(pos) = background.interact_pointer_pos() {
+            ui.memory_mut(|memory| memory.data.insert_temp(popup_id, pos));
+        }
+    }
+
+    let mut canvas_origin = ui.max_rect().min;
+    egui::ScrollArea::both()
         .auto_shrink([false, false])
+        // A left-button pan binding replaces egui's own drag scrolling instead of doubling it
+        .scroll_source(egui::scroll_area::ScrollSource { drag: nodes_movable, ..Default::default() })
         .show(ui, |ui| {
-        ui.separator();
-        ui.heading("Genome Graph");
-        ui.label("Node-based genome editor");
-        ui.label("(Implementation pending - requires egui_node_graph)");
+        if panning {
+            ui.scroll_with_delta(ui.input(|i| i.pointer.delta()));
+        }
+        ui.label(egui::RichText::new("Shift-click to add a mode").small().weak());
+        ui.label(egui::RichText::new(format!("{} to pan, pinch or Ctrl+scroll to zoom", bindings.graph_pan.label())).small().weak());
+
+        let origin = ui.cursor().min;
+        canvas_origin = origin;
+        // Links go behind the nodes but need their final rects
+        let links_shape = ui.painter().add(egui::Shape::Noop);
+        let mut node_rects = vec![egui::Rect::NOTHING; current_genome.genome.modes.len()];
+
+        for (mode_index, mode) in current_genome.genome.modes.iter().enumerate() {
+            let Some(node_id) = node_graph.get_node_for_mode(mode_index) else {
+                continue;
+            };
+            let (x, y) = node_graph.get_node_position(node_id).unwrap_or_default();
+            let min = origin + egui::vec2(x, y) * zoom;
+            let mut node_ui = ui.new_child(
+                egui::UiBuilder::new()
+                    .id_salt(("graph_node", node_id))
+                    .max_rect(egui::Rect::from_min_size(min, egui::vec2(NODE_WIDTH * zoom, f32::INFINITY))),
+            );
+
+            let selected = current_genome.selected_mode_index == mode_index as i32;
+            let multi_selected = selected_modes.len() > 1 && selected_modes.contains(&mode_index);
+            let frame = egui::Frame::window(node_ui.style())
+                .shadow(egui::Shadow::NONE)
+                .stroke(if selected {
+                    egui::Stroke::new(2.0, node_ui.visuals().selection.stroke.color)
+                } else if multi_selected {
+                    egui::Stroke::new(1.5, node_ui.visuals().selection.stroke.color.gamma_multiply(0.6))
+                } else {
+                    node_ui.visuals().widgets.noninteractive.bg_stroke
+                })
+                .show(&mut node_ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        let color = egui::Color32::from_rgb(
+                            (mode.color.x * 255.0) as u8,
+                            (mode.color.y * 255.0) as u8,
+                            (mode.color.z * 255.0) as u8,
+                        );
+                        crate::ui::widgets::mode_chip(ui, color, mode.pattern, 16.0);
+                        ui.label(egui::RichText::new(&mode.name).strong());
+                    });
+                    ui.separator();
+                    draw_mode_chip(ui, thumbnails, mode_index, mode);
+                });
+            let rect = frame.response.rect;
+            node_rects[mode_index] = rect;
+
+            let sense = if nodes_movable { egui::Sense::click_and_drag() } else { egui::Sense::click() };
+            let response = node_ui.interact(rect, node_ui.id().with("node"), sense);
+            if response.dragged_by(egui::PointerButton::Primary) {
+                let delta = response.drag_delta() / zoom;
+                node_graph.set_node_position(node_id, x + delta.x, y + delta.y);
+            }
+            if response.clicked() {
+                let modifiers = ui.input(|i| i.modifiers);
+                let primary = current_genome.selected_mode_index.max(0) as usize;
+                current_genome.selected_mode_index = crate::genome::batch_edit::click_mode_selection(
+                    selected_modes,
+                    primary,
+                    mode_index,
+                    modifiers.command,
+                    modifiers.shift,
+                ) as i32;
+            }
+        }
+
+        let mut links = Vec::new();
+        for &(from, to, is_child_a) in &node_graph.links {
+            let rect_of = |node_id| node_graph.get_mode_for_node(node_id)
+                .and_then(|index| node_rects.get(index).copied())
+                .filter(|rect| rect.is_positive());
+            let (Some(from), Some(to)) = (rect_of(from), rect_of(to)) else {
+                continue;
+            };
+            link_shapes(&mut links, from, to, is_child_a, zoom);
+        }
+        ui.painter().set(links_shape, egui::Shape::Vec(links));
+
+        // Scroll far enough to reach every node
+        let content = node_rects.iter().fold(egui::Rect::from_min_size(origin, egui::Vec2::ZERO), |all, rect| {
+            if rect.is_positive() { all.union(*rect) } else { all }
+        });
+        ui.allocate_rect(content.expand(8.0), egui::Sense::hover());
     });
+
+    render_add_mode_popup(ui, popup_id, current_genome, node_graph, canvas_origin, zoom, templates);
+}
+
+/// Curve from a parent node's right edge to a child node's left edge, or a loop over the
+/// node for a mode that divides into itself; child A links sit above child B links
+fn link_shapes(shapes: &mut Vec<egui::Shape>, from: egui::Rect, to: egui::Rect, is_child_a: bool, zoom: f32) {
+    let color = if is_child_a {
+        egui::Color32::from_rgb(110, 170, 255)
+    } else {
+        egui::Color32::from_rgb(255, 160, 90)
+    };
+    let stroke = egui::Stroke::new(1.5, color);
+    let lane = if is_child_a { 0.35 } else { 0.65 };
+
+    let (start, end, bend) = if from == to {
+        let start = egui::pos2(from.right(), from.top() + from.height() * lane);
+        let end = egui::pos2(from.left(), start.y);
+        (start, end, egui::vec2(0.0, -(from.height() * lane + 30.0 * zoom)))
+    } else {
+        let start = egui::pos2(from.right(), from.top() + from.height() * lane);
+        let end = egui::pos2(to.left(), to.top() + to.height() * lane);
+        (start, end, egui::Vec2::ZERO)
+    };
+    let reach = egui::vec2(60.0 * zoom, 0.0);
+    shapes.push(egui::Shape::CubicBezier(egui::epaint::CubicBezierShape::from_points_stroke(
+        [start, start + reach + bend, end - reach + bend, end],
+        false,
+        egui::Color32::TRANSPARENT,
+        stroke,
+    )));
+    // Arrowhead into the child
+    let tip = 6.0 * zoom;
+    shapes.push(egui::Shape::convex_polygon(
+        vec![end, end - egui::vec2(tip, tip * 0.6), end - egui::vec2(tip, -tip * 0.6)],
+        color,
+        egui::Stroke::NONE,
+    ));
+}
+
+fn pointer_button(button: BindingButton) -> egui::PointerButton {
+    match button {
+        BindingButton::Left => egui::PointerButton::Primary,
+        BindingButton::Middle => egui::PointerButton::Middle,
+        BindingButton::Right => egui::PointerButton::Secondary,
+    }
+}
+
+fn modifiers(modifiers: egui::Modifiers) -> Modifiers {
+    Modifiers { ctrl: modifiers.command || modifiers.ctrl, shift: modifiers.shift, alt: modifiers.alt }
+}
+
+/// Add Mode popup opened by shift-clicking the graph; its position lives in egui memory
+///
+/// The new mode's node is placed where the graph was clicked.
+fn render_add_mode_popup(
+    ui: &egui::Ui,
+    popup_id: egui::Id,
+    current_genome: &mut CurrentGenome,
+    node_graph: &mut GenomeNodeGraph,
+    canvas_origin: egui::Pos2,
+    zoom: f32,
+    templates: &ModeTemplates,
+) {
+    let Some(pos) = ui.memory(|memory| memory.data.get_temp::<egui::Pos2>(popup_id)) else {
+        return;
+    };
+
+    let area = egui::Area::new(popup_id)
+        .order(egui::Order::Foreground)
+        .fixed_pos(pos)
+        .show(ui.ctx(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new("Add Mode").strong());
+                ui.separator();
+                crate::ui::widgets::mode_template_menu(ui, templates)
+            }).inner
+        });
+
+    let clicked_elsewhere = ui.input(|i| i.pointer.any_pressed()) && !area.response.contains_pointer();
+    let escape = ui.input(|i| i.key_pressed(egui::Key::Escape));
+    if let Some(choice) = area.inner {
+        let mode_count = current_genome.genome.modes.len();
+        crate::ui::windows::modes::add_mode_from_template(current_genome, templates, choice);
+        if current_genome.genome.modes.len() > mode_count {
+            let at = (pos - canvas_origin) / zoom;
+            node_graph.pending_position = Some((current_genome.selected_mode_index as usize, at.x, at.y));
+        }
+    }
+    if area.inner.is_some() || escape || (clicked_elsewhere && !ui.input(|i| i.modifiers.shift)) {
+        ui.memory_mut(|memory| memory.data.remove::<egui::Pos2>(popup_id));
+    }
 }
@@ -2,15 +2,18 @@
 
 pub mod settings_panels;
 pub mod genome_graph;
+pub mod mode_thumbnails;
 
 // Re-export panel rendering functions from windows module
 pub use crate::ui::windows::modes::render_modes_panel;
 pub use settings_panels::{
     render_name_type_editor,
     render_adhesion_settings,
+    render_adhesion_propagation,
     render_parent_settings,
     render_circle_sliders,
     render_quaternion_ball,
     render_time_slider,
 };
 pub use genome_graph::render_genome_graph;
+pub use mode_thumbnails::ModeThumbnails;
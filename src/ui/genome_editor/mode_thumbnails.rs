@@ -0,0 +1,188 @@
+use bevy::prelude::Vec3;
+use bevy_egui::egui;
+use std::hash::{Hash, Hasher};
+use crate::genome::ModeSettings;
+
+/// Side length of a mode thumbnail sprite (pixels)
+pub const THUMBNAIL_SIZE: usize = 32;
+
+/// Direction the thumbnail sphere is lit from (view space, +z toward the viewer)
+const LIGHT_DIRECTION: Vec3 = Vec3::new(-0.45, 0.55, 0.7);
+const AMBIENT: f32 = 0.25;
+
+/// Per-mode sphere sprites, regenerated when a mode's color, opacity or emissive changes
+#[derive(Default)]
+pub struct ModeThumbnails {
+    /// (visual hash, texture) by mode index
+    entries: Vec<Option<(u64, egui::TextureHandle)>>,
+}
+
+impl ModeThumbnails {
+    /// Texture for `mode`, rendered on first use and after its visual fields change
+    pub fn texture(&mut self, ctx: &egui::Context, mode_index: usize, mode: &ModeSettings) -> egui::TextureId {
+        if self.entries.len() <= mode_index {
+            self.entries.resize(mode_index + 1, None);
+        }
+        let hash = visual_hash(mode);
+        let entry = &mut self.entries[mode_index];
+        match entry {
+            Some((cached, texture)) if *cached == hash => texture.id(),
+            _ => {
+                let image = render_sprite(mode.color, mode.opacity, mode.emissive);
+                let texture = ctx.load_texture(format!("mode_thumbnail_{}", mode_index), image, egui::TextureOptions::LINEAR);
+                let id = texture.id();
+                *entry = Some((hash, texture));
+                id
+            }
+        }
+    }
+
+    /// Drop the textures of modes past `mode_count` (freed once their handles are gone)
+    pub fn evict_removed(&mut self, mode_count: usize) {
+        self.entries.truncate(mode_count);
+    }
+
+    /// Number of modes with a cached texture
+    pub fn cached_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.is_some()).count()
+    }
+}
+
+/// Hash of the fields a thumbnail depends on
+pub fn visual_hash(mode: &ModeSettings) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for value in [mode.color.x, mode.color.y, mode.color.z, mode.opacity, mode.emissive] {
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Shaded sphere impression: Lambert plus a specular highlight, faded by opacity,
+/// with emissive brightening the body and adding a soft halo
+pub fn render_sprite(color: Vec3, opacity: f32, emissive: f32) -> egui::ColorImage {
+    let light = LIGHT_DIRECTION.normalize();
+    let half_vector = (light + Vec3::Z).normalize();
+    let radius = THUMBNAIL_SIZE as f32 * 0.5 - 3.0;
+    let center = THUMBNAIL_SIZE as f32 * 0.5;
+    let glow = emissive.clamp(0.0, 4.0);
+
+    let mut rgba = Vec::with_capacity(THUMBNAIL_SIZE * THUMBNAIL_SIZE * 4);
+    for y in 0..THUMBNAIL_SIZE {
+        for x in 0..THUMBNAIL_SIZE {
+            let dx = (x as f32 + 0.5 - center) / radius;
+            let dy = (center - (y as f32 + 0.5)) / radius;
+            let distance = (dx * dx + dy * dy).sqrt();
+            // One pixel of antialiasing at the silhouette
+            let coverage = ((1.0 - distance) * radius + 0.5).clamp(0.0, 1.0);
+
+            let (rgb, alpha) = if coverage > 0.0 {
+                let normal = Vec3::new(dx, dy, (1.0 - (dx * dx + dy * dy).min(1.0)).sqrt());
+                let diffuse = AMBIENT + (1.0 - AMBIENT) * normal.dot(light).max(0.0);
+                let specular = normal.dot(half_vector).max(0.0).powf(24.0) * 0.5;
+                let rgb = color * diffuse * (1.0 + glow * 0.5) + Vec3::splat(specular) + color * glow * 0.3;
+                (rgb, opacity.clamp(0.05, 1.0).max(specular) * coverage)
+            } else {
+                // Halo fading out over the sprite margin
+                let halo = (1.0 - (distance - 1.0) * radius / 3.0).clamp(0.0, 1.0) * (glow / 4.0).sqrt() * 0.6;
+                (color * (1.0 + glow * 0.5), halo)
+            };
+
+            let to_byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            rgba.extend_from_slice(&[to_byte(rgb.x), to_byte(rgb.y), to_byte(rgb.z), to_byte(alpha)]);
+        }
+    }
+    egui::ColorImage::from_rgba_unmultiplied([THUMBNAIL_SIZE, THUMBNAIL_SIZE], &rgba)
+}
+
+/// Whether cells of `mode` can never divide (interval at the slider's "never" end or no splits allowed)
+pub fn never_splits(mode: &ModeSettings) -> bool {
+    mode.split_interval > 59.0 || mode.max_splits == 0
+}
+
+/// Thumbnail chip with the cell type, "makes adhesion" and "never splits" icons beneath it
+pub fn draw_mode_chip(ui: &mut egui::Ui, thumbnails: &mut ModeThumbnails, mode_index: usize, mode: &ModeSettings) {
+    let texture = thumbnails.texture(ui.ctx(), mode_index, mode);
+    ui.vertical(|ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(2.0, 2.0);
+        ui.add(egui::Image::new((texture, egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32))));
+        ui.horizontal(|ui| {
+            let flagellocyte = mode.cell_type == 1;
+            icon(ui, if flagellocyte { "Flagellocyte" } else { "Test cell" }, |painter, rect, stroke| {
+                painter.circle_stroke(rect.center(), rect.width() * 0.35, stroke);
+                if flagellocyte {
+                    // Flagellum trailing off the cell
+                    let start = rect.center() + egui::vec2(rect.width() * 0.35, 0.0);
+                    painter.line_segment([start, rect.right_center() + egui::vec2(2.0, -2.0)], stroke);
+                }
+            });
+            if mode.parent_make_adhesion {
+                icon(ui, "Makes adhesion", |painter, rect, stroke| {
+                    let offset = egui::vec2(rect.width() * 0.25, 0.0);
+                    painter.circle_stroke(rect.center() - offset, rect.width() * 0.18, stroke);
+                    painter.circle_stroke(rect.center() + offset, rect.width() * 0.18, stroke);
+                    painter.line_segment([rect.center() - offset * 0.3, rect.center() + offset * 0.3], stroke);
+                });
+            }
+            if never_splits(mode) {
+                icon(ui, "Never splits", |painter, rect, stroke| {
+                    let radius = rect.width() * 0.38;
+                    painter.circle_stroke(rect.center(), radius, stroke);
+                    let diagonal = egui::vec2(radius, -radius) * std::f32::consts::FRAC_1_SQRT_2;
+                    painter.line_segment([rect.center() - diagonal, rect.center() + diagonal], stroke);
+                });
+            }
+        });
+    });
+}
+
+/// 10×10 painted icon with a hover hint
+fn icon(ui: &mut egui::Ui, hint: &str, paint: impl FnOnce(&egui::Painter, egui::Rect, egui::Stroke)) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+    let stroke = egui::Stroke::new(1.0, ui.visuals().text_color());
+    paint(ui.painter(), rect, stroke);
+    response.on_hover_text(hint);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alpha_at(image: &egui::ColorImage, x: usize, y: usize) -> u8 {
+        image.pixels[y * THUMBNAIL_SIZE + x].a()
+    }
+
+    #[test]
+    fn test_sprite_fades_with_opacity_and_glows_with_emissive() {
+        let solid = render_sprite(Vec3::new(0.2, 0.4, 1.0), 1.0, 0.0);
+        let faint = render_sprite(Vec3::new(0.2, 0.4, 1.0), 0.3, 0.0);
+        let glowing = render_sprite(Vec3::new(0.2, 0.4, 1.0), 1.0, 2.0);
+        let mid = THUMBNAIL_SIZE / 2;
+
+        assert_eq!(alpha_at(&solid, mid, mid), 255);
+        assert!(alpha_at(&faint, mid, mid) < 128);
+        assert_eq!(alpha_at(&solid, 0, 0), 0);
+        // Emissive adds a halo just outside the silhouette
+        assert_eq!(alpha_at(&solid, mid, 1), 0);
+        assert!(alpha_at(&glowing, mid, 1) > 0);
+    }
+
+    #[test]
+    fn test_thumbnails_regenerate_on_visual_change_and_evict_removed_modes() {
+        let ctx = egui::Context::default();
+        let mut thumbnails = ModeThumbnails::default();
+        let mut mode = ModeSettings::new_self_splitting(0, "Swimmer".to_string());
+
+        let first = thumbnails.texture(&ctx, 0, &mode);
+        assert_eq!(thumbnails.texture(&ctx, 0, &mode), first);
+        // Fields the sprite doesn't show keep the cached texture
+        mode.split_mass = 3.0;
+        assert_eq!(thumbnails.texture(&ctx, 0, &mode), first);
+        mode.emissive = 1.5;
+        assert_ne!(thumbnails.texture(&ctx, 0, &mode), first);
+
+        thumbnails.texture(&ctx, 2, &mode);
+        assert_eq!(thumbnails.cached_count(), 2);
+        thumbnails.evict_removed(1);
+        assert_eq!(thumbnails.cached_count(), 1);
+    }
+}
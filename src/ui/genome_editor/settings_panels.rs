@@ -1,6 +1,6 @@
-use bevy::prelude::*;
 use bevy_egui::egui;
-use crate::genome::CurrentGenome;
+use crate::genome::{adhesion_mismatches, copy_adhesion_settings, AdhesionCopyTarget, CurrentGenome, GenomeHistory, ModeIndex};
+use crate::ui::Notifications;
 use crate::ui::GenomeEditorState;
 use crate::ui::widgets;
 
@@ -21,7 +21,12 @@ fn group_container(ui: &mut egui::Ui, title: &str, color: egui::Color32, content
     ui.add_space(6.0);
 }
 
-pub fn render_name_type_editor(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
+pub fn render_name_type_editor(
+    ui: &mut egui::Ui,
+    current_genome: &mut CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    notifications: &mut Notifications,
+) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
         .show(ui, |ui| {
@@ -36,19 +41,17 @@ pub fn render_name_type_editor(ui: &mut egui::Ui, current_genome: &mut CurrentGe
                     .set_file_name(&format!("{}.json", current_genome.genome.name))
                     .save_file()
                 {
-                    info!("Would save genome to: {:?}", path);
-                    // TODO: Implement actual save
+                    match current_genome.genome.save_to_file(&path) {
+                        Ok(()) => {
+                            current_genome.record_save(&path);
+                            notifications.success(format!("Saved genome to {}", path.display()));
+                        }
+                        Err(e) => notifications.error(&e),
+                    }
                 }
             }
             if ui.button("Load Genome").clicked() {
-                // Open load dialog
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("JSON", &["json"])
-                    .pick_file()
-                {
-                    info!("Would load genome from: {:?}", path);
-                    // TODO: Implement actual load
-                }
+                genome_editor_state.genome_browser_open = true;
             }
             if ui.button("Genome Graph").clicked() {
                 // TODO: Implement genome graph
@@ -60,9 +63,19 @@ pub fn render_name_type_editor(ui: &mut egui::Ui, current_genome: &mut CurrentGe
         // Genome Name label and field on same line
         ui.horizontal(|ui| {
             ui.label("Genome Name:");
-            ui.text_edit_singleline(&mut current_genome.genome.name);
+            let response = ui.text_edit_singleline(&mut current_genome.genome.name);
+            if let Some(sample) = current_genome.sampled_from {
+                response.on_hover_text(format!("Sampled from cell #{} at t={:.2}s", sample.cell_id, sample.time));
+            }
         });
 
+        ui.add(
+            egui::TextEdit::multiline(&mut current_genome.genome.notes)
+                .desired_rows(2)
+                .desired_width(f32::INFINITY)
+                .hint_text("Notes (shown in the Open Genome browser)"),
+        );
+
         ui.add_space(4.0);
 
         // Get current mode
@@ -208,6 +221,51 @@ pub fn render_adhesion_settings(ui: &mut egui::Ui, current_genome: &mut CurrentG
     });
 }
 
+/// Copy buttons for the selected mode's adhesion settings and the mismatch warning for
+/// the bond it creates (above the Adhesion Settings tab)
+pub fn render_adhesion_propagation(
+    ui: &mut egui::Ui,
+    current_genome: &mut CurrentGenome,
+    history: &mut GenomeHistory,
+    notifications: &mut Notifications,
+) {
+    let selected_idx = current_genome.selected_mode_index as usize;
+    let genome = &mut current_genome.genome;
+    if selected_idx >= genome.modes.len() {
+        return;
+    }
+    let mismatches = adhesion_mismatches(genome, selected_idx);
+    if !mismatches.is_empty() {
+        let warning = ui.visuals().warn_fg_color;
+        group_container(ui, "Bond Mismatch", warning, |ui| {
+            ui.label("The bond this mode creates links children whose own modes bond differently:");
+            for mismatch in &mismatches {
+                let side = if mismatch.side == 0 { "A" } else { "B" };
+                ui.label(format!("Child {} ({}): {}", side, genome.modes[mismatch.mode].name, mismatch.fields.join(", ")));
+            }
+        });
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Copy adhesion settings to:");
+        for target in AdhesionCopyTarget::ALL {
+            let targets = target.modes(genome, selected_idx);
+            let names = targets.iter().map(|&index| genome.modes[index].name.as_str()).collect::<Vec<_>>().join(", ");
+            if ui.add_enabled(!targets.is_empty(), egui::Button::new(target.label()))
+                .on_hover_text(if names.is_empty() { "No other mode".to_string() } else { names })
+                .clicked()
+            {
+                let changed = copy_adhesion_settings(genome, selected_idx, target, history);
+                if changed > 0 {
+                    notifications.success(format!("Copied adhesion settings to {} modes", changed));
+                } else {
+                    notifications.info("Adhesion settings already match");
+                }
+            }
+        }
+    });
+}
+
 pub fn render_parent_settings(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -277,6 +335,43 @@ pub fn render_parent_settings(ui: &mut egui::Ui, current_genome: &mut CurrentGen
                 ui.add(egui::DragValue::new(&mut mode.split_interval).speed(0.1).range(1.0..=60.0).suffix("s"));
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Split Phase Jitter:");
+                ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+                    "Shifts each cell's division by up to half this fraction of the split interval either way, \
+                     so a colony stops dividing all on the same tick. The average interval is unchanged.",
+                );
+            });
+            ui.horizontal(|ui| {
+                let available = ui.available_width();
+                let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                ui.style_mut().spacing.slider_width = slider_width;
+                ui.add(egui::Slider::new(&mut mode.split_phase_jitter, 0.0..=1.0).show_value(false));
+                ui.add(egui::DragValue::new(&mut mode.split_phase_jitter).speed(0.01).range(0.0..=1.0));
+            });
+
+            ui.horizontal(|ui| {
+                let mut inhibited = mode.max_contact_pressure.is_finite();
+                if ui.checkbox(&mut inhibited, "Contact Inhibition").changed() {
+                    mode.max_contact_pressure = if inhibited { 1.0 } else { f32::INFINITY };
+                }
+                ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+                    "Cells skip division while crowded. Pressure is the summed overlap with every touching cell this tick; \
+                     division waits until it drops to the limit.",
+                );
+            });
+
+            if mode.max_contact_pressure.is_finite() {
+                ui.label("Max Contact Pressure:");
+                ui.horizontal(|ui| {
+                    let available = ui.available_width();
+                    let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                    ui.style_mut().spacing.slider_width = slider_width;
+                    ui.add(egui::Slider::new(&mut mode.max_contact_pressure, 0.0..=5.0).show_value(false));
+                    ui.add(egui::DragValue::new(&mut mode.max_contact_pressure).speed(0.01).range(0.0..=5.0));
+                });
+            }
+
             ui.label("Split Ratio (A/B):");
             ui.horizontal(|ui| {
                 let available = ui.available_width();
@@ -285,6 +380,28 @@ pub fn render_parent_settings(ui: &mut egui::Ui, current_genome: &mut CurrentGen
                 ui.add(egui::Slider::new(&mut mode.split_ratio, 0.0..=1.0).show_value(false));
                 ui.add(egui::DragValue::new(&mut mode.split_ratio).speed(0.01).range(0.0..=1.0));
             });
+
+            ui.horizontal(|ui| {
+                ui.label("User Data:");
+                egui::ComboBox::from_id_salt("user_data_inheritance")
+                    .selected_text(mode.user_data_inheritance.name())
+                    .show_ui(ui, |ui| {
+                        for policy in crate::genome::UserDataInheritance::ALL {
+                            ui.selectable_value(&mut mode.user_data_inheritance, policy, policy.name());
+                        }
+                    });
+            }).response.on_hover_text("How children receive the parent's user data channels");
+
+            ui.horizontal(|ui| {
+                ui.label("Equatorial Bonds:");
+                egui::ComboBox::from_id_salt("equatorial_bonds")
+                    .selected_text(mode.equatorial_bonds.name())
+                    .show_ui(ui, |ui| {
+                        for rule in crate::genome::EquatorialBondRule::ALL {
+                            ui.selectable_value(&mut mode.equatorial_bonds, rule, rule.name());
+                        }
+                    });
+            }).response.on_hover_text("Which child keeps bonds that lie on the split plane");
         });
 
         // Nutrient Settings Group (Green)
@@ -298,7 +415,7 @@ pub fn render_parent_settings(ui: &mut egui::Ui, current_genome: &mut CurrentGen
                 ui.add(egui::DragValue::new(&mut mode.nutrient_priority).speed(0.01).range(0.1..=10.0));
             });
 
-            ui.checkbox(&mut mode.prioritize_when_low, "Prioritize When Low");
+            render_low_nutrient_sliders(ui, mode);
         });
 
         // Connection Settings Group (Cyan)
@@ -330,9 +447,117 @@ pub fn render_parent_settings(ui: &mut egui::Ui, current_genome: &mut CurrentGen
                 ui.add(egui::DragValue::new(&mut mode.max_splits).speed(0.1).range(-1.0..=20.0));
             });
         });
+
+        // Chemistry (collapsible, most genomes don't use it)
+        egui::CollapsingHeader::new(egui::RichText::new("Chemistry").strong().color(egui::Color32::from_rgb(200, 130, 90)))
+            .id_salt("parent_settings_chemistry")
+            .default_open(mode.secretion_rate.iter().chain(&mode.absorption_rate).any(|rate| *rate > 0.0) || mode.taxis_strength != 0.0)
+            .show(ui, |ui| {
+                for channel in 0..crate::simulation::FIELD_CHANNELS {
+                    ui.label(format!("Channel {}", channel + 1));
+                    egui::Grid::new(("chemistry_channel", channel))
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Secretion:");
+                            ui.add(egui::DragValue::new(&mut mode.secretion_rate[channel]).speed(0.01).range(0.0..=20.0).suffix("/s"));
+                            ui.end_row();
+
+                            ui.label("Absorption:");
+                            ui.add(egui::DragValue::new(&mut mode.absorption_rate[channel]).speed(0.01).range(0.0..=20.0).suffix("/s"));
+                            ui.end_row();
+                        });
+                }
+
+                ui.add_space(4.0);
+                ui.add_enabled_ui(mode.cell_type == 1, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Taxis:");
+                        egui::ComboBox::from_id_salt("taxis_channel")
+                            .selected_text(format!("Channel {}", mode.taxis_channel + 1))
+                            .show_ui(ui, |ui| {
+                                for channel in 0..crate::simulation::FIELD_CHANNELS {
+                                    ui.selectable_value(&mut mode.taxis_channel, channel, format!("Channel {}", channel + 1));
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        let available = ui.available_width();
+                        let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                        ui.style_mut().spacing.slider_width = slider_width;
+                        ui.add(egui::Slider::new(&mut mode.taxis_strength, -5.0..=5.0).show_value(false));
+                        ui.add(egui::DragValue::new(&mut mode.taxis_strength).speed(0.01).range(-5.0..=5.0));
+                    });
+                }).response.on_hover_text("Flagellocytes only: bends swimming up (+) or down (-) the channel's gradient");
+            });
+
+        // Shape (collapsible, spheres unless changed)
+        egui::CollapsingHeader::new(egui::RichText::new("Shape").strong().color(egui::Color32::from_rgb(170, 130, 210)))
+            .id_salt("parent_settings_shape")
+            .default_open(!mode.is_spherical())
+            .show(ui, |ui| render_shape_sliders(ui, mode));
+    });
+}
+
+/// "Prioritize When Low" toggle and its hysteresis band (threshold and release factor)
+pub(crate) fn render_low_nutrient_sliders(ui: &mut egui::Ui, mode: &mut crate::genome::ModeSettings) {
+    ui.checkbox(&mut mode.prioritize_when_low, "Prioritize When Low");
+
+    ui.add_enabled_ui(mode.prioritize_when_low, |ui| {
+        ui.label("Low Threshold (mass):");
+        ui.horizontal(|ui| {
+            let available = ui.available_width();
+            let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+            ui.style_mut().spacing.slider_width = slider_width;
+            ui.add(egui::Slider::new(&mut mode.low_nutrient_threshold, 0.5..=2.0).show_value(false));
+            ui.add(egui::DragValue::new(&mut mode.low_nutrient_threshold).speed(0.01).range(0.5..=2.0));
+        });
+
+        ui.label("Release Factor:");
+        ui.horizontal(|ui| {
+            let available = ui.available_width();
+            let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+            ui.style_mut().spacing.slider_width = slider_width;
+            ui.add(egui::Slider::new(&mut mode.low_nutrient_release_factor, 1.0..=3.0).show_value(false));
+            ui.add(egui::DragValue::new(&mut mode.low_nutrient_release_factor).speed(0.01).range(1.0..=3.0));
+        }).response.on_hover_text("Boost releases once mass recovers above threshold x factor");
     });
 }
 
+/// Ellipsoid proportion sliders for the cell's local X, Y and Z axes
+///
+/// While "Round Cross-Section" is on, Y and Z move together, giving rods along X.
+fn render_shape_sliders(ui: &mut egui::Ui, mode: &mut crate::genome::ModeSettings) {
+    let link_id = ui.id().with("shape_round_cross_section");
+    let mut linked = ui.data(|data| data.get_temp::<bool>(link_id)).unwrap_or(mode.shape_radii.y == mode.shape_radii.z);
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut linked, "Round Cross-Section");
+        if ui.small_button("Sphere").clicked() {
+            mode.shape_radii = bevy::prelude::Vec3::ONE;
+        }
+        ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+            "Proportions of the cell along its local axes. The longest axis spans the cell radius, \
+             so equal values give a sphere.",
+        );
+    });
+    ui.data_mut(|data| data.insert_temp(link_id, linked));
+
+    let range = crate::genome::MIN_SHAPE_PROPORTION..=1.0;
+    for (axis, label) in ["X Proportion:", "Y Proportion:", "Z Proportion:"].into_iter().enumerate() {
+        ui.label(label);
+        let changed = ui.horizontal(|ui| {
+            let available = ui.available_width();
+            let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+            ui.style_mut().spacing.slider_width = slider_width;
+            let slider = ui.add(egui::Slider::new(&mut mode.shape_radii[axis], range.clone()).show_value(false));
+            let drag = ui.add(egui::DragValue::new(&mut mode.shape_radii[axis]).speed(0.01).range(range.clone()));
+            slider.changed() || drag.changed()
+        }).inner;
+        if changed && linked && axis > 0 {
+            mode.shape_radii[3 - axis] = mode.shape_radii[axis];
+        }
+    }
+}
+
 pub fn render_circle_sliders(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, genome_editor_state: &mut GenomeEditorState) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -379,6 +604,23 @@ pub fn render_circle_sliders(ui: &mut egui::Ui, current_genome: &mut CurrentGeno
                     );
                 });
             });
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label("Split Jitter:");
+                ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+                    "Tilts each division's split direction by a random angle up to this limit, so colonies grow \
+                     less like crystal lattices. The tilt is drawn from the cell's id and the tick, so resimulation \
+                     reproduces it. The Split Planes gizmo shows the cone.",
+                );
+            });
+            ui.horizontal(|ui| {
+                let available = ui.available_width();
+                let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                ui.style_mut().spacing.slider_width = slider_width;
+                ui.add(egui::Slider::new(&mut mode.split_direction_jitter_degrees, 0.0..=90.0).show_value(false));
+                ui.add(egui::DragValue::new(&mut mode.split_direction_jitter_degrees).speed(0.5).range(0.0..=90.0).suffix("°"));
+            });
         }
     });
 }
@@ -450,42 +692,7 @@ pub fn render_quaternion_ball(ui: &mut egui::Ui, current_genome: &mut CurrentGen
 
                     // Mode label and dropdown for ball 1
                     ui.label("Mode:");
-                    let child_a_mode_idx = mode.child_a.mode_number as usize;
-                    let mode_color = mode_display_data[child_a_mode_idx].1;
-                    let brightness = mode_color.r() as f32 * 0.299 + mode_color.g() as f32 * 0.587 + mode_color.b() as f32 * 0.114;
-                    let text_color = if brightness > 127.5 {
-                        egui::Color32::BLACK
-                    } else {
-                        egui::Color32::WHITE
-                    };
-                    egui::ComboBox::from_id_salt("qball1_mode")
-                        .selected_text(
-                            egui::RichText::new(&mode_display_data[child_a_mode_idx].0)
-                                .color(text_color)
-                                .background_color(mode_color)
-                        )
-                        .width(ball_container_width - 20.0)
-                        .show_ui(ui, |ui| {
-                            for (i, (mode_name, mode_color)) in mode_display_data.iter().enumerate() {
-                                // Calculate brightness to determine text color
-                                let brightness = mode_color.r() as f32 * 0.299 + mode_color.g() as f32 * 0.587 + mode_color.b() as f32 * 0.114;
-                                let text_color = if brightness > 127.5 {
-                                    egui::Color32::BLACK
-                                } else {
-                                    egui::Color32::WHITE
-                                };
-
-                                let mut current_mode = mode.child_a.mode_number as usize;
-                                let _response = ui.selectable_value(
-                                    &mut current_mode,
-                                    i,
-                                    egui::RichText::new(mode_name).color(text_color).background_color(*mode_color)
-                                );
-                                if current_mode != mode.child_a.mode_number as usize {
-                                    mode.child_a.mode_number = current_mode as i32;
-                                }
-                            }
-                        });
+                    child_mode_combo(ui, "qball1_mode", &mut mode.child_a.mode_number, &mode_display_data, ball_container_width - 20.0);
                 }
             );
 
@@ -523,52 +730,53 @@ pub fn render_quaternion_ball(ui: &mut egui::Ui, current_genome: &mut CurrentGen
 
                     // Mode label and dropdown for ball 2
                     ui.label("Mode:");
-                    let child_b_mode_idx = mode.child_b.mode_number as usize;
-                    let mode_color = mode_display_data[child_b_mode_idx].1;
-                    let brightness = mode_color.r() as f32 * 0.299 + mode_color.g() as f32 * 0.587 + mode_color.b() as f32 * 0.114;
-                    let text_color = if brightness > 127.5 {
-                        egui::Color32::BLACK
-                    } else {
-                        egui::Color32::WHITE
-                    };
-                    egui::ComboBox::from_id_salt("qball2_mode")
-                        .selected_text(
-                            egui::RichText::new(&mode_display_data[child_b_mode_idx].0)
-                                .color(text_color)
-                                .background_color(mode_color)
-                        )
-                        .width(ball_container_width - 20.0)
-                        .show_ui(ui, |ui| {
-                            for (i, (mode_name, mode_color)) in mode_display_data.iter().enumerate() {
-                                // Calculate brightness to determine text color
-                                let brightness = mode_color.r() as f32 * 0.299 + mode_color.g() as f32 * 0.587 + mode_color.b() as f32 * 0.114;
-                                let text_color = if brightness > 127.5 {
-                                    egui::Color32::BLACK
-                                } else {
-                                    egui::Color32::WHITE
-                                };
-
-                                let mut current_mode = mode.child_b.mode_number as usize;
-                                let _response = ui.selectable_value(
-                                    &mut current_mode,
-                                    i,
-                                    egui::RichText::new(mode_name).color(text_color).background_color(*mode_color)
-                                );
-                                if current_mode != mode.child_b.mode_number as usize {
-                                    mode.child_b.mode_number = current_mode as i32;
-                                }
-                            }
-                        });
+                    child_mode_combo(ui, "qball2_mode", &mut mode.child_b.mode_number, &mode_display_data, ball_container_width - 20.0);
                 }
             );
         });
     });
 }
 
+/// Mode dropdown of a child; an invalid reference is shown as such until a mode is picked
+fn child_mode_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    mode_number: &mut ModeIndex,
+    mode_display_data: &[(String, egui::Color32)],
+    width: f32,
+) {
+    // Black or white text, whichever reads better on the mode color
+    let text_color = |color: egui::Color32| {
+        let brightness = color.r() as f32 * 0.299 + color.g() as f32 * 0.587 + color.b() as f32 * 0.114;
+        if brightness > 127.5 { egui::Color32::BLACK } else { egui::Color32::WHITE }
+    };
+
+    let selected_text = match mode_number.index().and_then(|index| mode_display_data.get(index)) {
+        Some((name, color)) => egui::RichText::new(name).color(text_color(*color)).background_color(*color),
+        None => egui::RichText::new(format!("Invalid mode {}", mode_number.raw()))
+            .color(egui::Color32::from_rgb(230, 90, 90)),
+    };
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected_text)
+        .width(width)
+        .show_ui(ui, |ui| {
+            for (i, (mode_name, mode_color)) in mode_display_data.iter().enumerate() {
+                ui.selectable_value(
+                    mode_number,
+                    ModeIndex::new(i),
+                    egui::RichText::new(mode_name).color(text_color(*mode_color)).background_color(*mode_color),
+                );
+            }
+        });
+}
+
 pub fn render_time_slider(
     ui: &mut egui::Ui,
     genome_editor_state: &mut GenomeEditorState,
     sim_state: &crate::simulation::SimulationState,
+    timeline: &mut crate::simulation::EventTimeline,
+    genome: &crate::genome::GenomeData,
+    scene_request: &mut crate::ui::windows::scene_manager::SceneModeRequest,
 ) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -587,7 +795,7 @@ pub fn render_time_slider(
             ui.label("");
         }
 
-        ui.horizontal(|ui| {
+        let slider_rect = ui.horizontal(|ui| {
             ui.label("Time:");
 
             let available = ui.available_width();
@@ -613,6 +821,213 @@ pub fn render_time_slider(
                         format!("{:.1}s", time_sec)
                     })
             );
-        });
+
+            slider_response.rect
+        }).inner;
+
+        if is_preview_mode {
+            render_event_timeline(ui, genome_editor_state, timeline, genome, slider_rect);
+            crate::ui::windows::scene_manager::continue_in_cpu_button(ui, scene_request);
+        }
     });
 }
+
+/// Height of one category lane in the event timeline strip
+const TIMELINE_LANE_HEIGHT: f32 = 6.0;
+
+/// Lanes with more raw events than this fraction of their width in pixels are drawn as a heat strip
+const TIMELINE_DENSE_EVENTS_PER_PIXEL: f32 = 0.3;
+
+fn timeline_kind_color(kind: crate::simulation::event_timeline::TimelineEventKind) -> egui::Color32 {
+    use crate::simulation::event_timeline::TimelineEventKind;
+    match kind {
+        TimelineEventKind::Division => egui::Color32::from_rgb(90, 200, 110),
+        TimelineEventKind::Death => egui::Color32::from_rgb(230, 80, 70),
+        TimelineEventKind::AdhesionBreak => egui::Color32::from_rgb(240, 150, 50),
+        TimelineEventKind::Drag => egui::Color32::from_rgb(100, 180, 250),
+        TimelineEventKind::GenomeEdit => egui::Color32::from_rgb(190, 120, 240),
+        TimelineEventKind::CapacityWarning => egui::Color32::from_rgb(240, 220, 60),
+        TimelineEventKind::CapacityGrowth => egui::Color32::from_rgb(250, 250, 200),
+        TimelineEventKind::OrganismSplit => egui::Color32::from_rgb(250, 120, 170),
+        TimelineEventKind::Graft => egui::Color32::from_rgb(120, 230, 210),
+        TimelineEventKind::Breakpoint => egui::Color32::from_rgb(255, 90, 40),
+        TimelineEventKind::TissueStamp => egui::Color32::from_rgb(190, 150, 255),
+        TimelineEventKind::PhaseChange => egui::Color32::from_rgb(230, 200, 140),
+        TimelineEventKind::Watchdog => egui::Color32::from_rgb(255, 60, 120),
+        TimelineEventKind::AnchorEdit => egui::Color32::from_rgb(150, 210, 255),
+    }
+}
+
+fn timeline_category_color(category: crate::simulation::event_timeline::TimelineCategory) -> egui::Color32 {
+    use crate::simulation::event_timeline::{TimelineCategory, TimelineEventKind};
+    timeline_kind_color(match category {
+        TimelineCategory::Divisions => TimelineEventKind::Division,
+        TimelineCategory::Deaths => TimelineEventKind::Death,
+        TimelineCategory::AdhesionBreaks => TimelineEventKind::AdhesionBreak,
+        TimelineCategory::Interventions => TimelineEventKind::Drag,
+        TimelineCategory::Capacity => TimelineEventKind::CapacityWarning,
+        TimelineCategory::Phases => TimelineEventKind::PhaseChange,
+    })
+}
+
+/// Event strip under the time slider: one lane per visible category
+///
+/// Markers are tick-aligned with the slider. Hovering shows the event, clicking
+/// moves the scrub target to it (or to the clicked time between markers).
+fn render_event_timeline(
+    ui: &mut egui::Ui,
+    genome_editor_state: &mut GenomeEditorState,
+    timeline: &mut crate::simulation::EventTimeline,
+    genome: &crate::genome::GenomeData,
+    slider_rect: egui::Rect,
+) {
+    use crate::simulation::event_timeline::{TimelineCategory, TimelineEvent};
+
+    ui.horizontal_wrapped(|ui| {
+        for (index, category) in TimelineCategory::ALL.iter().enumerate() {
+            let text = egui::RichText::new(category.name()).small().color(timeline_category_color(*category));
+            ui.checkbox(&mut timeline.visible[index], text);
+        }
+    });
+
+    let lanes: Vec<TimelineCategory> = TimelineCategory::ALL.iter().enumerate()
+        .filter(|(index, _)| timeline.visible[*index])
+        .map(|(_, category)| *category)
+        .collect();
+    if lanes.is_empty() {
+        return;
+    }
+
+    let duration = genome_editor_state.max_preview_duration.max(f32::EPSILON);
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), TIMELINE_LANE_HEIGHT * lanes.len() as f32 + 2.0),
+        egui::Sense::click(),
+    );
+    // Align with the slider rail (egui insets the rail by the handle radius)
+    let handle_radius = slider_rect.height() / 2.5;
+    let x_range = egui::Rangef::new(slider_rect.left() + handle_radius, slider_rect.right() - handle_radius);
+    let width = x_range.span().max(1.0);
+    let time_to_x = |time: f32| x_range.min + (time / duration).clamp(0.0, 1.0) * width;
+    let x_to_time = |x: f32| ((x - x_range.min) / width).clamp(0.0, 1.0) * duration;
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(egui::Rect::from_x_y_ranges(x_range, rect.y_range()), 2.0, egui::Color32::from_gray(25));
+
+    let pointer = response.hover_pos();
+    let bins = width.ceil() as usize;
+    let bin_of = |time: f32| ((time_to_x(time) - x_range.min) as usize).min(bins - 1);
+
+    // Closest marker to the pointer (distance, tooltip, time); heat bins report their own range
+    let mut hovered: Option<(f32, String, f32)> = None;
+
+    for (lane, category) in lanes.iter().enumerate() {
+        let lane_rect = egui::Rect::from_x_y_ranges(
+            x_range,
+            egui::Rangef::new(
+                rect.top() + 1.0 + lane as f32 * TIMELINE_LANE_HEIGHT,
+                rect.top() + 1.0 + (lane + 1) as f32 * TIMELINE_LANE_HEIGHT,
+            ),
+        );
+        let color = timeline_category_color(*category);
+        let pointer_in_lane = pointer.filter(|pos| lane_rect.y_range().contains(pos.y));
+
+        let events: Vec<&TimelineEvent> = timeline.events().iter()
+            .chain(timeline.persistent_events())
+            .filter(|event| event.kind.category() == *category)
+            .collect();
+
+        // Aggregated (per-second and per-minute) history is always a heat strip; raw events join it when the lane is dense
+        let mut heat = vec![0u32; bins];
+        for bucket in timeline.buckets() {
+            let count: u32 = bucket.summary.iter().enumerate()
+                .filter(|(kind, _)| crate::simulation::event_timeline::TimelineEventKind::ALL[*kind].category() == *category)
+                .map(|(_, count)| *count)
+                .sum();
+            if count > 0 {
+                let start = bin_of(bucket.start as f32);
+                let end = bin_of(bucket.end() as f32).max(start + 1).min(bins);
+                let per_bin = (count / (end - start) as u32).max(1);
+                heat[start..end].iter_mut().for_each(|bin| *bin += per_bin);
+            }
+        }
+        let dense = events.len() as f32 > width * TIMELINE_DENSE_EVENTS_PER_PIXEL;
+        if dense {
+            for event in &events {
+                heat[bin_of(timeline.tick_time(event.tick))] += 1;
+            }
+        }
+
+        let max_heat = heat.iter().copied().max().unwrap_or(0);
+        if max_heat > 0 {
+            for (bin, count) in heat.iter().enumerate().filter(|(_, count)| **count > 0) {
+                let alpha = 0.25 + 0.75 * (*count as f32 / max_heat as f32);
+                let x = x_range.min + bin as f32;
+                painter.rect_filled(
+                    egui::Rect::from_x_y_ranges(egui::Rangef::new(x, x + 1.0), lane_rect.y_range()),
+                    0.0,
+                    color.gamma_multiply(alpha),
+                );
+            }
+            if let Some(pos) = pointer_in_lane {
+                let bin = ((pos.x - x_range.min).max(0.0) as usize).min(bins - 1);
+                if heat[bin] > 0 {
+                    let time = x_to_time(pos.x);
+                    let text = format!("{}: {} around {:.2}s", category.name(), heat[bin], time);
+                    hovered = Some((0.0, text, time));
+                }
+            }
+        }
+
+        if !dense {
+            for event in &events {
+                let time = timeline.tick_time(event.tick);
+                let x = time_to_x(time);
+                painter.line_segment(
+                    [egui::pos2(x, lane_rect.top()), egui::pos2(x, lane_rect.bottom())],
+                    egui::Stroke::new(1.5, timeline_kind_color(event.kind)),
+                );
+
+                if let Some(pos) = pointer_in_lane {
+                    let distance = (pos.x - x).abs();
+                    if distance <= 4.0 && hovered.as_ref().is_none_or(|(closest, _, _)| distance < *closest) {
+                        hovered = Some((distance, timeline_event_tooltip(event, time, genome), time));
+                    }
+                }
+            }
+        }
+    }
+
+    // Current scrub position
+    let current_x = time_to_x(genome_editor_state.time_value / 100.0 * duration);
+    painter.line_segment(
+        [egui::pos2(current_x, rect.top()), egui::pos2(current_x, rect.bottom())],
+        egui::Stroke::new(1.0, egui::Color32::WHITE),
+    );
+
+    let clicked = response.clicked();
+    let jump_time = match (&hovered, pointer) {
+        (Some((_, _, time)), _) => Some(*time),
+        (None, Some(pos)) => Some(x_to_time(pos.x)),
+        (None, None) => None,
+    };
+    if let Some((_, text, _)) = hovered {
+        response.on_hover_text(text);
+    }
+    if clicked {
+        if let Some(time) = jump_time {
+            genome_editor_state.time_value = (time / duration * 100.0).clamp(0.0, 100.0);
+        }
+    }
+}
+
+fn timeline_event_tooltip(event: &crate::simulation::event_timeline::TimelineEvent, time: f32, genome: &crate::genome::GenomeData) -> String {
+    if event.kind == crate::simulation::event_timeline::TimelineEventKind::PhaseChange {
+        return format!("{}: {}\nTick {} ({:.2}s)", event.kind.name(), genome.phase_name(event.phase()), event.tick, time);
+    }
+    let cells: Vec<String> = event.cells.iter().flatten().map(|id| format!("#{}", id)).collect();
+    if cells.is_empty() {
+        format!("{}\nTick {} ({:.2}s)", event.kind.name(), event.tick, time)
+    } else {
+        format!("{}\nCells: {}\nTick {} ({:.2}s)", event.kind.name(), cells.join(", "), event.tick, time)
+    }
+}
@@ -0,0 +1,293 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use crate::input::bindings::{BindingModifier, Modifiers};
+use crate::input::InputArbitration;
+
+/// Key that toggles HUD mode
+pub const HUD_TOGGLE_KEY: KeyCode = KeyCode::F10;
+
+/// Lowest opacity a panel can be set to, so it never disappears entirely
+pub const MIN_PANEL_OPACITY: f32 = 0.1;
+
+/// Distance from a hidden panel at which the pointer brings it back (points)
+const APPROACH_MARGIN: f32 = 40.0;
+
+/// Seconds a panel takes to fade out or back in
+const FADE_SECS: f32 = 0.25;
+
+/// Plugin for panel opacity and HUD mode
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HudSettings>()
+            .init_resource::<HudState>()
+            .add_systems(Update, toggle_hud_mode);
+    }
+}
+
+/// Panel opacity and the click-through HUD mode used for recording
+///
+/// In HUD mode every panel drops to `opacity` and lets the pointer through to the
+/// viewport, so the camera orbits and zooms over the panels. Holding the interact
+/// modifier makes the panels usable (and opaque) again.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct HudSettings {
+    pub enabled: bool,
+    /// Opacity of every panel in HUD mode
+    pub opacity: f32,
+    /// Held to use the panels in HUD mode
+    pub interact_modifier: BindingModifier,
+    /// Fade panels out in HUD mode after `auto_hide_secs` without the pointer near them
+    pub auto_hide: bool,
+    pub auto_hide_secs: f32,
+    /// Opacity by panel name; panels not listed are opaque
+    panel_opacity: BTreeMap<String, f32>,
+}
+
+impl Default for HudSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            opacity: 0.3,
+            interact_modifier: BindingModifier::Alt,
+            auto_hide: false,
+            auto_hide_secs: 3.0,
+            panel_opacity: BTreeMap::new(),
+        }
+    }
+}
+
+impl HudSettings {
+    /// Opacity set for a panel outside HUD mode
+    pub fn panel_opacity(&self, panel: &str) -> f32 {
+        self.panel_opacity.get(panel).copied().unwrap_or(1.0)
+    }
+
+    pub fn set_panel_opacity(&mut self, panel: &str, opacity: f32) {
+        let opacity = opacity.clamp(MIN_PANEL_OPACITY, 1.0);
+        if opacity >= 1.0 {
+            self.panel_opacity.remove(panel);
+        } else {
+            self.panel_opacity.insert(panel.to_string(), opacity);
+        }
+    }
+
+    /// Whether the panels take the pointer: always outside HUD mode, only with the
+    /// interact modifier held in it
+    pub fn panels_interactive(&self, modifiers: Modifiers) -> bool {
+        !self.enabled || self.interact_modifier.held(modifiers)
+    }
+
+    /// Opacity a panel is drawn with this frame
+    pub fn effective_opacity(&self, panel: &str, modifiers: Modifiers) -> f32 {
+        let own = self.panel_opacity(panel);
+        if self.panels_interactive(modifiers) {
+            own
+        } else {
+            own.min(self.opacity)
+        }
+    }
+
+    /// Whether the pointer counts as over UI for input arbitration
+    ///
+    /// `over_panel` is a docked panel, `over_other_ui` anything else drawn by egui
+    /// (menu bar, popups, floating windows), which always keeps the pointer.
+    pub fn pointer_over_ui(&self, over_panel: bool, over_other_ui: bool, modifiers: Modifiers) -> bool {
+        over_other_ui || (over_panel && self.panels_interactive(modifiers))
+    }
+}
+
+/// Per-frame HUD bookkeeping: where the panels are and when the pointer last came near
+#[derive(Resource, Default)]
+pub struct HudState {
+    /// Body rects of the docked panels drawn this frame, by panel name
+    panel_rects: Vec<(String, egui::Rect)>,
+    /// Last time (seconds) the pointer was near each panel
+    last_contact: HashMap<String, f64>,
+}
+
+impl HudState {
+    pub fn begin_frame(&mut self) {
+        self.panel_rects.clear();
+    }
+
+    pub fn add_panel_rect(&mut self, panel: &str, rect: egui::Rect) {
+        self.panel_rects.push((panel.to_string(), rect));
+    }
+
+    pub fn is_over_panel(&self, pos: egui::Pos2) -> bool {
+        self.panel_rects.iter().any(|(_, rect)| rect.contains(pos))
+    }
+
+    /// Body rect of a panel drawn this frame (None while it's closed or in a hidden tab)
+    pub fn panel_rect(&self, panel: &str) -> Option<egui::Rect> {
+        self.panel_rects.iter().find(|(name, _)| name == panel).map(|(_, rect)| *rect)
+    }
+
+    /// Whether a panel is shown under auto-hide, given whether the pointer is near it now
+    pub fn panel_shown(&mut self, panel: &str, near: bool, now: f64, settings: &HudSettings) -> bool {
+        if near || !settings.enabled || !settings.auto_hide {
+            self.last_contact.insert(panel.to_string(), now);
+            return true;
+        }
+        let last = *self.last_contact.entry(panel.to_string()).or_insert(now);
+        now - last < settings.auto_hide_secs as f64
+    }
+
+    /// Record a panel's body rect and return the opacity to draw it with, fading
+    /// it out under auto-hide and back in when the pointer approaches
+    pub fn show_panel(&mut self, ui: &egui::Ui, panel: &str, settings: &HudSettings, modifiers: Modifiers) -> f32 {
+        let rect = ui.clip_rect();
+        self.add_panel_rect(panel, rect);
+        let near = ui.ctx().pointer_latest_pos().is_some_and(|pos| rect.expand(APPROACH_MARGIN).contains(pos));
+        let now = ui.input(|i| i.time);
+        let shown = self.panel_shown(panel, near, now, settings);
+        let fade = ui.ctx().animate_bool_with_time(egui::Id::new(("hud_panel", panel)), shown, FADE_SECS);
+        settings.effective_opacity(panel, modifiers) * fade
+    }
+}
+
+/// Modifier keys egui saw this frame (Cmd counts as Ctrl, as in the bindings)
+pub fn egui_modifiers(ctx: &egui::Context) -> Modifiers {
+    ctx.input(|i| Modifiers {
+        ctrl: i.modifiers.ctrl || i.modifiers.command,
+        shift: i.modifiers.shift,
+        alt: i.modifiers.alt,
+    })
+}
+
+/// Toggle HUD mode with its key
+pub fn toggle_hud_mode(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    arbitration: Res<InputArbitration>,
+    mut settings: ResMut<HudSettings>,
+) {
+    if keyboard.just_pressed(HUD_TOGGLE_KEY) && arbitration.keyboard_free() {
+        settings.enabled = !settings.enabled;
+    }
+}
+
+/// HUD section of the Windows menu
+pub fn show_hud_menu(ui: &mut egui::Ui, settings: &mut HudSettings) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.enabled, "HUD mode")
+            .on_hover_text("Fade the panels and let clicks through to the viewport, for recording");
+        ui.weak("F10");
+    });
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        ui.add(egui::Slider::new(&mut settings.opacity, MIN_PANEL_OPACITY..=1.0).text("HUD opacity"));
+        egui::ComboBox::from_label("Hold to use panels")
+            .selected_text(settings.interact_modifier.name())
+            .show_ui(ui, |ui| {
+                for modifier in [BindingModifier::Ctrl, BindingModifier::Shift, BindingModifier::Alt] {
+                    ui.selectable_value(&mut settings.interact_modifier, modifier, modifier.name());
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.auto_hide, "Auto-hide after");
+            ui.add_enabled(settings.auto_hide, egui::DragValue::new(&mut settings.auto_hide_secs)
+                .range(0.5..=60.0)
+                .speed(0.1)
+                .suffix(" s"));
+        });
+    });
+    if settings.enabled {
+        ui.weak(format!("Panels are click-through; hold {} to use them", settings.interact_modifier.name()));
+    }
+}
+
+/// Opacity control for one panel row of the Windows menu
+pub fn panel_opacity_control(ui: &mut egui::Ui, settings: &mut HudSettings, panel: &str) {
+    let mut opacity = settings.panel_opacity(panel);
+    let response = ui.add(egui::DragValue::new(&mut opacity)
+        .range(MIN_PANEL_OPACITY..=1.0)
+        .speed(0.01)
+        .fixed_decimals(2))
+        .on_hover_text("Panel opacity");
+    if response.changed() {
+        settings.set_panel_opacity(panel, opacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::arbitration::CaptureSignals;
+    use crate::input::PointerOwner;
+
+    const ALT: Modifiers = Modifiers { ctrl: false, shift: false, alt: true };
+
+    #[test]
+    fn test_pointer_routing_matrix() {
+        // (HUD on, modifier held, pointer over panel) -> camera gets the press
+        let cases = [
+            (false, false, false, true),
+            (false, false, true, false),
+            (false, true, false, true),
+            (false, true, true, false),
+            (true, false, false, true),
+            (true, false, true, true),
+            (true, true, false, true),
+            (true, true, true, false),
+        ];
+        for (hud_on, held, over_panel, camera) in cases {
+            let settings = HudSettings { enabled: hud_on, ..Default::default() };
+            let modifiers = if held { ALT } else { Modifiers::NONE };
+            let mut arbitration = InputArbitration::default();
+            arbitration.set_signals(CaptureSignals {
+                pointer_over_ui: settings.pointer_over_ui(over_panel, false, modifiers),
+                ..Default::default()
+            });
+            arbitration.begin_frame(true, true);
+            assert_eq!(arbitration.claim(PointerOwner::Camera), camera,
+                "HUD {} modifier {} over panel {}", hud_on, held, over_panel);
+        }
+
+        // The menu bar and popups keep the pointer even in HUD mode
+        let settings = HudSettings { enabled: true, ..Default::default() };
+        assert!(settings.pointer_over_ui(false, true, Modifiers::NONE));
+    }
+
+    #[test]
+    fn test_hud_opacity_and_persistence() {
+        let mut settings = HudSettings::default();
+        settings.set_panel_opacity("Cell Inspector", 0.8);
+        settings.set_panel_opacity("Log", 0.0);
+        assert_eq!(settings.effective_opacity("Cell Inspector", Modifiers::NONE), 0.8);
+        assert_eq!(settings.panel_opacity("Log"), MIN_PANEL_OPACITY);
+        assert_eq!(settings.panel_opacity("Modes"), 1.0);
+
+        // HUD mode lowers every panel, the interact modifier restores their own opacity
+        settings.enabled = true;
+        assert_eq!(settings.effective_opacity("Modes", Modifiers::NONE), settings.opacity);
+        assert_eq!(settings.effective_opacity("Log", Modifiers::NONE), MIN_PANEL_OPACITY);
+        assert_eq!(settings.effective_opacity("Cell Inspector", ALT), 0.8);
+
+        // Back to opaque drops the entry
+        settings.set_panel_opacity("Cell Inspector", 1.0);
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: HudSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, settings);
+        assert!(!json.contains("Cell Inspector"));
+    }
+
+    #[test]
+    fn test_auto_hide_after_idle_and_reveal_on_approach() {
+        let settings = HudSettings { enabled: true, auto_hide: true, auto_hide_secs: 2.0, ..Default::default() };
+        let mut state = HudState::default();
+        assert!(state.panel_shown("Log", false, 0.0, &settings));
+        assert!(state.panel_shown("Log", false, 1.9, &settings));
+        assert!(!state.panel_shown("Log", false, 2.1, &settings));
+        // The pointer comes near: shown, and the idle timer restarts
+        assert!(state.panel_shown("Log", true, 5.0, &settings));
+        assert!(state.panel_shown("Log", false, 6.5, &settings));
+
+        // Outside HUD mode panels never hide
+        let off = HudSettings { enabled: false, ..settings };
+        assert!(state.panel_shown("Log", false, 100.0, &off));
+    }
+}
@@ -1,9 +1,9 @@
-// Temporary stub for lighting_settings - just exports the resource types
-// Full egui implementation coming soon
+// Lighting resource types; the Lighting Settings panel is in windows/lighting_settings.rs
 
 use bevy::prelude::*;
+use crate::rendering::DayNightCycle;
 
-#[derive(Resource)]
+#[derive(Resource, Clone, PartialEq)]
 pub struct LightingConfig {
     /// Directional light illuminance
     pub directional_illuminance: f32,
@@ -13,6 +13,10 @@ pub struct LightingConfig {
     pub directional_rotation: [f32; 3],
     /// Ambient light brightness
     pub ambient_brightness: f32,
+    /// Day/night animation of the directional light and skybox tint (off by default)
+    pub day_night: DayNightCycle,
+    /// Phase shown by the Lighting Settings scrubber instead of the simulated time
+    pub preview_phase: Option<f32>,
 }
 
 impl Default for LightingConfig {
@@ -22,6 +26,15 @@ impl Default for LightingConfig {
             directional_color: [1.0, 1.0, 1.0],
             directional_rotation: [45.0, 45.0, 0.0],
             ambient_brightness: 0.1,
+            day_night: DayNightCycle::default(),
+            preview_phase: None,
         }
     }
 }
+
+impl LightingConfig {
+    /// Cycle phase lighting the scene at a simulated time (None for the static lighting)
+    pub fn shown_phase(&self, time: f32) -> Option<f32> {
+        self.preview_phase.or_else(|| self.day_night.enabled.then(|| self.day_night.phase_at(time)))
+    }
+}
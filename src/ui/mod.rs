@@ -9,8 +9,20 @@ pub mod genome_editor;
 pub mod windows;
 
 // Feature modules (still using old implementations for now)
+pub mod audio_overlay;
+pub mod force_heatmap_legend;
+pub mod capture_overlay;
 pub mod camera;
+pub mod camera_framing;
+pub mod camera_path;
+pub mod detached_window;
+pub mod hud;
+pub mod mode_legend;
+pub mod notifications;
+pub mod region_menu;
 pub mod settings;
+pub mod tutorial;
+pub mod viewport_overlays;
 
 // Temporary stubs for resource types (until full egui implementation)
 #[path = "scene_manager_stub.rs"]
@@ -31,6 +43,12 @@ pub use ui_system::{ui_system, ViewportRect, GenomeEditorState};
 
 // Export camera (still using old implementation)
 pub use camera::{CameraPlugin, MainCamera, CameraConfig, CameraState, CameraMode, FocalPlaneSettings};
+pub use camera_framing::CameraFraming;
+pub use camera_path::CameraPathPlayer;
+pub use detached_window::{DetachedPanels, DetachedWindowPlugin};
+pub use hud::{HudPlugin, HudSettings, HudState};
+pub use notifications::Notifications;
+pub use tutorial::{Tutorial, TutorialPlugin};
 
 // Export settings
 pub use settings::UiSettings;
@@ -100,9 +118,16 @@ impl Plugin for UiPlugin {
             .init_resource::<ViewportRect>()
             .init_resource::<GenomeEditorState>()
             .init_resource::<CpuCellCapacity>()
+            .init_resource::<windows::growth_guardrail::GrowthGuardrailSettings>()
+            .init_resource::<windows::growth_guardrail::GrowthGuardrail>()
             .init_resource::<LightingConfig>()
             .init_resource::<windows::scene_manager::SceneModeRequest>()
+            .init_resource::<windows::cell_inspector::CellInspectorState>()
+            .init_resource::<Notifications>()
             .add_plugins(CameraPlugin)
+            .add_plugins(DetachedWindowPlugin)
+            .add_plugins(HudPlugin)
+            .add_plugins(TutorialPlugin)
             .add_systems(Startup, (
                 setup_dock,
                 load_ui_scale_on_startup,
@@ -111,15 +136,71 @@ impl Plugin for UiPlugin {
                 settings::load_skybox_settings_on_startup,
                 settings::load_simulation_settings_on_startup,
                 settings::load_lock_settings_on_startup,
+                settings::load_mode_palette_on_startup,
+                settings::load_genome_directory_on_startup,
+                settings::load_audio_settings_on_startup,
+                (
+                    settings::load_live_stats_settings_on_startup,
+                    settings::load_spectator_settings_on_startup,
+                    settings::load_watchdog_settings_on_startup,
+                    settings::load_derived_metric_settings_on_startup,
+                    settings::load_settle_settings_on_startup,
+                    settings::load_growth_guardrail_settings_on_startup,
+                ),
+                settings::load_history_settings_on_startup,
+                settings::load_population_stats_settings_on_startup,
+                settings::load_input_bindings_on_startup,
+                settings::load_viewport_overlay_settings_on_startup,
+                settings::load_physics_overrides_on_startup,
+                settings::load_hud_settings_on_startup,
+                settings::load_capture_settings_on_startup,
+                settings::load_sphere_quality_on_startup,
             ))
             // CRITICAL: ui_system must run in EguiPrimaryContextPass, not Update
-            .add_systems(bevy_egui::EguiPrimaryContextPass, ui_system)
+            .add_systems(bevy_egui::EguiPrimaryContextPass, (
+                ui_system,
+                windows::measurements::draw_measurement_labels.after(ui_system),
+                windows::genome_browser::render_genome_browser.after(ui_system),
+                windows::tissue_stamp::render_tissue_stamp_dialogs.after(ui_system),
+                windows::capability_report::render_capability_report.after(ui_system),
+                windows::safe_mode_banner::render_safe_mode_banner.after(ui_system),
+                windows::staged_genome_banner::render_staged_genome_banner.after(ui_system),
+                windows::spectator_banner::render_spectator_banner.after(ui_system),
+                windows::scene_load_progress::render_scene_load_progress.after(ui_system),
+                windows::scene_inspector::render_scene_inspector.after(ui_system),
+                windows::pick_stack::render_pick_stack.after(ui_system),
+                windows::growth_guardrail::render_growth_guardrail_banner.after(ui_system),
+                windows::soak_test::render_soak_test.after(ui_system),
+                windows::scene_manager::render_discard_prompt.after(ui_system),
+            ))
             .add_systems(Update, (
                 auto_save_dock_state,
                 save_on_exit,
                 save_ui_scale_on_change,
-                settings::save_lock_settings_on_change,
+                (
+                    settings::save_lock_settings_on_change,
+                    settings::save_mode_palette_on_change,
+                    settings::save_audio_settings_on_change,
+                    settings::save_live_stats_settings_on_change,
+                    settings::save_spectator_settings_on_change,
+                    settings::save_watchdog_settings_on_change,
+                    settings::save_history_settings_on_change,
+                    settings::save_population_stats_settings_on_change,
+                    settings::save_derived_metric_settings_on_change,
+                    settings::save_settle_settings_on_change,
+                    settings::save_growth_guardrail_settings_on_change,
+                    settings::save_input_bindings_on_change,
+                    settings::save_viewport_overlay_settings_on_change,
+                    settings::save_physics_overrides_on_change,
+                    settings::save_hud_settings_on_change,
+                    settings::save_capture_settings_on_change,
+                    settings::save_sphere_quality_on_change,
+                    settings::save_lighting_settings_on_change,
+                ),
                 process_scene_mode_requests,
+                windows::cell_inspector::update_cell_inspector,
+                windows::division_debug::focus_requested_cell.before(windows::cell_inspector::update_cell_inspector),
+                windows::breakpoints::report_breakpoint_hits.before(windows::cell_inspector::update_cell_inspector),
                 dock::switch_dock_on_scene_change,
                 // TODO: Re-enable after fixing for egui
                 // settings::save_ui_settings_on_change,
@@ -137,6 +218,7 @@ fn load_ui_scale_on_startup(mut global_ui_state: ResMut<GlobalUiState>) {
 /// Save UI scale when it changes
 fn save_ui_scale_on_change(
     global_ui_state: Res<GlobalUiState>,
+    mut notifications: ResMut<Notifications>,
     mut last_saved_scale: Local<Option<f32>>,
 ) {
     // Initialize on first run
@@ -153,7 +235,7 @@ fn save_ui_scale_on_change(
         settings.ui_scale = global_ui_state.ui_scale;
         
         if let Err(e) = settings.save() {
-            error!("Failed to save UI scale: {}", e);
+            notifications.error(&e);
         } else {
             info!("Saved UI scale: {}", global_ui_state.ui_scale);
         }
@@ -163,26 +245,78 @@ fn save_ui_scale_on_change(
 }
 
 /// Process scene mode change requests from the UI
+#[allow(clippy::too_many_arguments)]
 fn process_scene_mode_requests(
     mut scene_request: ResMut<windows::scene_manager::SceneModeRequest>,
     mut sim_state: ResMut<crate::simulation::SimulationState>,
     mut next_preview_state: ResMut<NextState<crate::simulation::PreviewSceneState>>,
     mut next_cpu_state: ResMut<NextState<crate::simulation::CpuSceneState>>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
+    mut main_state: ResMut<crate::simulation::cpu_sim::MainSimState>,
+    mut preview_state: ResMut<crate::simulation::preview_sim::PreviewSimState>,
+    mut preview_request: ResMut<crate::simulation::preview_sim::PreviewRequest>,
+    mut timeline: ResMut<crate::simulation::EventTimeline>,
+    cpu_cell_capacity: Res<scene_manager::CpuCellCapacity>,
+    mut statistics: ResMut<crate::simulation::StatisticsHistory>,
+    mut live_events: ResMut<crate::simulation::live_stats::LiveEventLog>,
+    mut notifications: ResMut<Notifications>,
+    mut respawn: ResMut<crate::simulation::PreviewRespawn>,
 ) {
+    use crate::simulation::dormant_scenes::{DormantCpuScene, DormantPreviewScene};
+
+    // The next preview founder, whether from a respawn, a reset or entering the mode
+    if respawn.start != scene_request.preview_start {
+        respawn.start = scene_request.preview_start;
+    }
+    if std::mem::take(&mut scene_request.requested_preview_respawn) && sim_state.mode == crate::simulation::SimulationMode::Preview {
+        sim_state.paused = false;
+        respawn.requested = true;
+    }
+
+    // Install the preview at the shown time as the CPU scene, then switch like the CPU button
+    if std::mem::take(&mut scene_request.continue_in_cpu) && sim_state.mode == crate::simulation::SimulationMode::Preview {
+        match DormantCpuScene::from_preview(&preview_state, sim_state.is_resimulating, cpu_cell_capacity.capacity) {
+            Ok(scene) => {
+                info!("Continuing the preview in CPU mode ({} cells, t={:.2}s)", scene.canonical_state.cell_count, scene.simulation_time);
+                if scene.canonical_state.capacity > cpu_cell_capacity.capacity {
+                    notifications.info(format!(
+                        "CPU mode keeps the preview's capacity of {} cells (setting: {})",
+                        scene.canonical_state.capacity, cpu_cell_capacity.capacity,
+                    ));
+                }
+                dormant.cpu = Some(scene);
+                main_state.phases = Default::default();
+                // The CPU run's history starts at the transfer
+                statistics.clear();
+                live_events.clear();
+                scene_request.requested_mode = Some(crate::simulation::SimulationMode::Cpu);
+            }
+            Err(error) => notifications.warning(format!("Can't continue in CPU mode: {}", error)),
+        }
+    }
+
     if let Some(requested_mode) = scene_request.requested_mode.take() {
         if sim_state.mode != requested_mode {
             match requested_mode {
                 crate::simulation::SimulationMode::Preview => {
                     info!("Switching to Preview mode");
+                    dormant.cpu = Some(DormantCpuScene::take(&mut main_state, sim_state.paused));
                     next_cpu_state.set(crate::simulation::CpuSceneState::Inactive);
                     next_preview_state.set(crate::simulation::PreviewSceneState::Active);
                     sim_state.mode = crate::simulation::SimulationMode::Preview;
+                    sim_state.paused = dormant.paused(requested_mode);
                 }
                 crate::simulation::SimulationMode::Cpu => {
                     info!("Switching to CPU mode");
+                    // A replay still running is dropped; the restored scene resumes it
+                    dormant.preview = Some(DormantPreviewScene::take(&mut preview_state, &mut timeline, &sim_state));
+                    preview_request.background_task = None;
+                    sim_state.target_time = None;
+                    sim_state.is_resimulating = false;
                     next_preview_state.set(crate::simulation::PreviewSceneState::Inactive);
                     next_cpu_state.set(crate::simulation::CpuSceneState::Active);
                     sim_state.mode = crate::simulation::SimulationMode::Cpu;
+                    sim_state.paused = dormant.paused(requested_mode);
                 }
                 crate::simulation::SimulationMode::Gpu => {
                     warn!("GPU mode not yet implemented");
@@ -191,4 +325,20 @@ fn process_scene_mode_requests(
             }
         }
     }
+
+    if let Some(mode) = scene_request.reset_requested.take() {
+        // After any switch above, so a scene load can drop the scene it switched away from
+        dormant.discard(mode);
+        // The active preview respawns its founder in place; re-entering the CPU scene state
+        // rebuilds it from scratch
+        if mode == sim_state.mode {
+            info!("Resetting {:?} scene", mode);
+            sim_state.paused = false;
+            match mode {
+                crate::simulation::SimulationMode::Preview => respawn.requested = true,
+                crate::simulation::SimulationMode::Cpu => next_cpu_state.set(crate::simulation::CpuSceneState::Active),
+                crate::simulation::SimulationMode::Gpu => {}
+            }
+        }
+    }
 }
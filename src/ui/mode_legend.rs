@@ -0,0 +1,110 @@
+use bevy_egui::egui;
+use crate::genome::CurrentGenome;
+use crate::rendering::{LegendCorner, ModeLegend, ModeVisibility};
+
+/// Draw the mode legend in a corner of the viewport
+///
+/// Lists every mode that has cells with its color, name and count. Click selects the
+/// mode in the genome editor, Ctrl+click toggles isolating it and right-click toggles
+/// hiding it. Returns the legend's rect so clicks on it don't reach the viewport.
+pub fn render_mode_legend(
+    ctx: &egui::Context,
+    viewport: egui::Rect,
+    legend: &ModeLegend,
+    visibility: &mut ModeVisibility,
+    genome: &mut CurrentGenome,
+) -> Option<egui::Rect> {
+    if !legend.show {
+        return None;
+    }
+    let counts = legend.counts.as_ref()?;
+    if counts.iter().all(|count| *count == 0) && !visibility.is_filtering() {
+        return None;
+    }
+
+    let margin = 8.0;
+    let (align, corner) = match legend.corner {
+        LegendCorner::TopLeft => (egui::Align2::LEFT_TOP, viewport.left_top() + egui::vec2(margin, margin)),
+        LegendCorner::TopRight => (egui::Align2::RIGHT_TOP, viewport.right_top() + egui::vec2(-margin, margin)),
+        LegendCorner::BottomLeft => (egui::Align2::LEFT_BOTTOM, viewport.left_bottom() + egui::vec2(margin, -margin)),
+        LegendCorner::BottomRight => (egui::Align2::RIGHT_BOTTOM, viewport.right_bottom() + egui::vec2(-margin, -margin)),
+    };
+
+    let response = egui::Area::new(egui::Id::new("mode_legend"))
+        .pivot(align)
+        .fixed_pos(corner)
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .fill(egui::Color32::from_black_alpha(170))
+                .show(ui, |ui| {
+                    ui.set_max_width(220.0);
+                    ui.spacing_mut().item_spacing.y = 2.0;
+                    for (mode_index, &count) in counts.iter().enumerate() {
+                        // Filtered modes stay listed so they can be toggled back
+                        let filtered = visibility.hidden.contains(&mode_index) || visibility.isolated.contains(&mode_index);
+                        if count == 0 && !filtered {
+                            continue;
+                        }
+                        let Some((name, color)) = genome.genome.modes.get(mode_index).map(|m| (m.name.clone(), m.color)) else {
+                            continue;
+                        };
+                        legend_entry(ui, mode_index, &name, color, count, visibility, genome);
+                    }
+
+                    if visibility.is_filtering() {
+                        ui.separator();
+                        if ui.small_button("Show all modes").clicked() {
+                            visibility.clear();
+                        }
+                    }
+                });
+        })
+        .response;
+
+    Some(response.rect)
+}
+
+fn legend_entry(
+    ui: &mut egui::Ui,
+    mode_index: usize,
+    name: &str,
+    color: bevy::prelude::Vec3,
+    count: usize,
+    visibility: &mut ModeVisibility,
+    genome: &mut CurrentGenome,
+) {
+    let multiplier = visibility.multiplier(mode_index);
+    let selected = genome.selected_mode_index == mode_index as i32;
+    let swatch = egui::Color32::from_rgb((color.x * 255.0) as u8, (color.y * 255.0) as u8, (color.z * 255.0) as u8)
+        .gamma_multiply(multiplier.max(0.3));
+
+    let mut text = egui::RichText::new(format!("{}  {}", name, count));
+    if multiplier == 0.0 {
+        text = text.strikethrough().weak();
+    } else if multiplier < 1.0 {
+        text = text.weak();
+    }
+    if visibility.isolated.contains(&mode_index) {
+        text = text.underline();
+    }
+
+    let response = ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, swatch);
+        ui.selectable_label(selected, text)
+    }).inner
+        .on_hover_text("Click: select in the genome editor\nCtrl+click: isolate (dim other modes)\nRight-click: hide");
+
+    if response.clicked() {
+        if ui.input(|i| i.modifiers.command) {
+            visibility.toggle_isolated(mode_index);
+        } else {
+            genome.selected_mode_index = mode_index as i32;
+        }
+    }
+    if response.secondary_clicked() {
+        visibility.toggle_hidden(mode_index);
+    }
+}
@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use std::collections::VecDeque;
+
+/// How long success/info toasts stay on screen (seconds)
+const SHORT_TOAST_SECS: f32 = 3.0;
+/// How long warning/error toasts stay on screen (seconds)
+const LONG_TOAST_SECS: f32 = 8.0;
+/// Maximum number of toasts stacked at once (oldest are dropped first)
+const MAX_VISIBLE_TOASTS: usize = 5;
+/// Maximum number of entries kept for the Log window
+const HISTORY_LIMIT: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Success,
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            NotificationLevel::Success => egui::Color32::from_rgb(90, 190, 110),
+            NotificationLevel::Info => egui::Color32::from_rgb(120, 170, 230),
+            NotificationLevel::Warning => egui::Color32::from_rgb(230, 180, 60),
+            NotificationLevel::Error => egui::Color32::from_rgb(225, 85, 85),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationLevel::Success => "OK",
+            NotificationLevel::Info => "Info",
+            NotificationLevel::Warning => "Warning",
+            NotificationLevel::Error => "Error",
+        }
+    }
+}
+
+/// A user-facing message, kept in the history after its toast is dismissed
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+    /// Extra detail shown when the toast or log entry is expanded
+    pub details: Option<String>,
+    /// Seconds since application start
+    pub timestamp: f32,
+}
+
+struct Toast {
+    id: u64,
+    notification: Notification,
+    remaining: f32,
+    expanded: bool,
+}
+
+/// Toast notifications and the message history shown in the Log window
+#[derive(Resource)]
+pub struct Notifications {
+    toasts: VecDeque<Toast>,
+    history: VecDeque<Notification>,
+    next_id: u64,
+    started: std::time::Instant,
+    /// Log window filter
+    pub log_errors_only: bool,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            history: VecDeque::new(),
+            next_id: 0,
+            started: std::time::Instant::now(),
+            log_errors_only: false,
+        }
+    }
+}
+
+impl Notifications {
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Success, message.into(), None);
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Info, message.into(), None);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Warning, message.into(), None);
+    }
+
+    /// Report a failed action; the error's cause chain becomes the expandable details
+    pub fn error(&mut self, error: &crate::error::BioSpheresError) {
+        let message = error.to_string();
+        let details = error.details();
+        let details = (details != message).then_some(details);
+        self.push(NotificationLevel::Error, message, details);
+    }
+
+    pub fn push(&mut self, level: NotificationLevel, message: String, details: Option<String>) {
+        match level {
+            NotificationLevel::Error => error!("{}", message),
+            NotificationLevel::Warning => warn!("{}", message),
+            _ => info!("{}", message),
+        }
+
+        let notification = Notification {
+            level,
+            message,
+            details,
+            timestamp: self.started.elapsed().as_secs_f32(),
+        };
+
+        if self.history.len() >= HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+        self.history.push_back(notification.clone());
+
+        if self.toasts.len() >= MAX_VISIBLE_TOASTS {
+            self.toasts.pop_front();
+        }
+        let remaining = match level {
+            NotificationLevel::Success | NotificationLevel::Info => SHORT_TOAST_SECS,
+            NotificationLevel::Warning | NotificationLevel::Error => LONG_TOAST_SECS,
+        };
+        self.toasts.push_back(Toast { id: self.next_id, notification, remaining, expanded: false });
+        self.next_id += 1;
+    }
+
+    /// All messages, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> + ExactSizeIterator {
+        self.history.iter()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Count down toast lifetimes; expanded toasts stay until collapsed or closed
+    fn tick(&mut self, dt: f32, hovered: Option<u64>) {
+        for toast in self.toasts.iter_mut() {
+            if !toast.expanded && hovered != Some(toast.id) {
+                toast.remaining -= dt;
+            }
+        }
+        self.toasts.retain(|toast| toast.remaining > 0.0);
+    }
+}
+
+/// Draw stacked toasts in the bottom-right corner of the screen
+pub fn render_toasts(ctx: &egui::Context, notifications: &mut Notifications) {
+    if notifications.toasts.is_empty() {
+        return;
+    }
+
+    let mut hovered = None;
+    let mut closed = None;
+
+    egui::Area::new(egui::Id::new("notification_toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            ui.set_max_width(360.0);
+            for toast in notifications.toasts.iter_mut() {
+                let level = toast.notification.level;
+                let mut response = egui::Frame::popup(ui.style())
+                    .stroke(egui::Stroke::new(1.0, level.color()))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(level.color(), egui::RichText::new(level.label()).strong());
+                            ui.label(&toast.notification.message);
+                            if ui.small_button("x").clicked() {
+                                closed = Some(toast.id);
+                            }
+                        });
+                        if toast.expanded {
+                            if let Some(details) = &toast.notification.details {
+                                ui.separator();
+                                ui.label(egui::RichText::new(details).monospace().small());
+                            }
+                        }
+                    })
+                    .response
+                    .interact(egui::Sense::click());
+                if toast.notification.details.is_some() && !toast.expanded {
+                    response = response.on_hover_text("Click for details");
+                }
+
+                if response.hovered() {
+                    hovered = Some(toast.id);
+                }
+                if response.clicked() {
+                    toast.expanded = !toast.expanded;
+                }
+                ui.add_space(4.0);
+            }
+        });
+
+    if let Some(id) = closed {
+        notifications.toasts.retain(|toast| toast.id != id);
+    }
+    let dt = ctx.input(|i| i.stable_dt).min(0.1);
+    notifications.tick(dt, hovered);
+    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_but_history_remains() {
+        let mut notifications = Notifications::default();
+        notifications.success("Saved genome");
+        notifications.warning("Something odd");
+        for i in 0..MAX_VISIBLE_TOASTS {
+            notifications.info(format!("Message {}", i));
+        }
+        assert_eq!(notifications.toasts.len(), MAX_VISIBLE_TOASTS);
+
+        // Expanded toasts are kept until collapsed
+        notifications.toasts.back_mut().unwrap().expanded = true;
+        notifications.tick(LONG_TOAST_SECS + 1.0, None);
+        assert_eq!(notifications.toasts.len(), 1);
+        assert_eq!(notifications.history().count(), MAX_VISIBLE_TOASTS + 2);
+        assert_eq!(notifications.history().next().unwrap().message, "Saved genome");
+    }
+}
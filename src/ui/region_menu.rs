@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::rendering::region_visibility::MAX_REGION_DIVISIONS;
+use crate::rendering::RegionVisibility;
+
+/// Side of the miniature sphere in points
+const MINIATURE_SIZE: f32 = 180.0;
+
+/// World sphere radius drawn by the miniature
+const WORLD_RADIUS: f32 = 100.0;
+
+/// Region filters of the Regions menu: the miniature sphere, quick actions and the clip plane
+pub fn render_region_menu(ui: &mut egui::Ui, regions: &mut RegionVisibility, camera_rotation: Quat) {
+    ui.horizontal(|ui| {
+        ui.label("Regions per axis:");
+        let mut divisions = regions.divisions;
+        if ui.add(egui::DragValue::new(&mut divisions).range(1..=MAX_REGION_DIVISIONS)).changed() {
+            regions.set_divisions(divisions);
+        }
+    });
+    render_miniature(ui, regions, camera_rotation);
+    ui.label(egui::RichText::new("Click a region to hide or show it (rendering only)").small().weak());
+
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!regions.hidden.is_empty(), egui::Button::new("Show all")).clicked() {
+            regions.hidden.clear();
+        }
+        if ui.button("Invert").clicked() {
+            regions.invert();
+        }
+    });
+    if ui.button("Show only regions containing selection")
+        .on_hover_text("Hide every region without the inspected cell or a member of the highlighted group")
+        .clicked()
+    {
+        regions.show_selection_requested = true;
+    }
+
+    ui.separator();
+    let clip = &mut regions.clip;
+    ui.checkbox(&mut clip.enabled, "Clip plane")
+        .on_hover_text("Hide every cell on the side the plane's arrow points to");
+    ui.add_enabled_ui(clip.enabled, |ui| {
+        egui::Grid::new("clip_plane_grid").num_columns(2).show(ui, |ui| {
+            ui.label("Yaw:");
+            ui.add(egui::Slider::new(&mut clip.yaw, -180.0..=180.0).suffix("°"));
+            ui.end_row();
+            ui.label("Pitch:");
+            ui.add(egui::Slider::new(&mut clip.pitch, -90.0..=90.0).suffix("°"));
+            ui.end_row();
+            ui.label("Offset:");
+            ui.add(egui::Slider::new(&mut clip.offset, -WORLD_RADIUS..=WORLD_RADIUS));
+            ui.end_row();
+        });
+        if ui.button("Flip").clicked() {
+            clip.flip();
+        }
+    });
+}
+
+/// Regions inside the world sphere as dots seen from the camera; the front-most dot
+/// under the pointer toggles on click
+fn render_miniature(ui: &mut egui::Ui, regions: &mut RegionVisibility, camera_rotation: Quat) {
+    let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(MINIATURE_SIZE), egui::Sense::click());
+    let painter = ui.painter_at(rect);
+    let scale = MINIATURE_SIZE * 0.45 / WORLD_RADIUS;
+    painter.circle_stroke(rect.center(), WORLD_RADIUS * scale, ui.visuals().widgets.noninteractive.fg_stroke);
+
+    // View space: +x right, +y up, +z toward the camera
+    let view = camera_rotation.inverse();
+    let region_size = 2.0 * WORLD_RADIUS / regions.divisions as f32;
+    let mut dots: Vec<(UVec3, egui::Pos2, f32, f32)> = (0..regions.region_count())
+        .map(|index| regions.region(index))
+        .filter(|&region| regions.region_in_sphere(region, WORLD_RADIUS))
+        .map(|region| {
+            let center = regions.region_center(region);
+            let v = view * center;
+            let position = rect.center() + egui::vec2(v.x, -v.y) * scale;
+            // Nearer regions are drawn larger
+            let depth = (v.z / WORLD_RADIUS).clamp(-1.0, 1.0);
+            let radius = region_size * scale * 0.18 * (1.0 + 0.4 * depth);
+            (region, position, v.z, radius)
+        })
+        .collect();
+    dots.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let pointer = response.hover_pos();
+    let hovered = pointer.and_then(|pointer| {
+        dots.iter().rev().find(|(_, position, _, radius)| position.distance(pointer) <= radius.max(4.0))
+    }).map(|&(region, ..)| region);
+
+    for &(region, position, z, radius) in &dots {
+        let center = regions.region_center(region);
+        let shade = (160.0 + 60.0 * (z / WORLD_RADIUS).clamp(-1.0, 1.0)) as u8;
+        let color = if regions.is_hidden(region) {
+            egui::Color32::from_gray(shade / 3)
+        } else if regions.clip.hides(center) {
+            egui::Color32::from_rgb(shade / 2, shade / 3, shade / 4)
+        } else {
+            egui::Color32::from_rgb(shade / 3, shade, shade / 2)
+        };
+        painter.circle_filled(position, radius, color);
+        if hovered == Some(region) {
+            painter.circle_stroke(position, radius + 1.5, egui::Stroke::new(1.5, egui::Color32::WHITE));
+        }
+    }
+
+    if let Some(region) = hovered {
+        let state = if regions.is_hidden(region) { "hidden" } else { "shown" };
+        response.clone().on_hover_text(format!("Region ({}, {}, {}): {}", region.x, region.y, region.z, state));
+        if response.clicked() {
+            regions.toggle(region);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
+use crate::safe_mode::Subsystem;
 
 /// Persisted UI settings that are saved to disk
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -32,6 +32,91 @@ pub struct UiSettings {
     /// Window lock settings
     #[serde(default)]
     pub lock_settings: LockSettings,
+    /// Color generator for default genomes and "Recolor all modes"
+    #[serde(default)]
+    pub mode_palette: crate::genome::ColorPalette,
+    /// Size and position of detached panel windows
+    #[serde(default)]
+    pub detached_windows: DetachedWindowSettings,
+    /// Directory the Open Genome browser scans
+    #[serde(default = "default_genome_directory")]
+    pub genome_directory: PathBuf,
+    /// Sound effect volumes and mute
+    #[serde(default)]
+    pub audio_settings: crate::audio::AudioSettings,
+    /// Bind address of the live stats endpoint and whether it runs
+    #[serde(default)]
+    pub live_stats: crate::simulation::LiveStatsSettings,
+    /// Bind address and update rate of the spectator host and whether it runs
+    #[serde(default)]
+    pub spectator: crate::simulation::SpectatorSettings,
+    /// Watchdog conditions, thresholds and trigger actions
+    #[serde(default)]
+    pub watchdog: crate::simulation::WatchdogSettings,
+    /// Retention of the event and statistics history tiers and their memory budget
+    #[serde(default)]
+    pub history: crate::simulation::HistorySettings,
+    /// When the population statistics switch from exact to sampled, and the sample size
+    #[serde(default)]
+    pub population_stats: crate::simulation::PopulationStatsSettings,
+    /// User-defined metrics over the statistics
+    #[serde(default)]
+    pub derived_metrics: crate::simulation::DerivedMetricSettings,
+    /// Duration, strength and auto-stop of the Settle action
+    #[serde(default)]
+    pub settle: crate::simulation::SettleSettings,
+    /// Runaway growth banner switch and forecast horizon
+    #[serde(default)]
+    pub growth_guardrail: crate::ui::windows::growth_guardrail::GrowthGuardrailSettings,
+    /// Camera and genome graph bindings with gesture sensitivities
+    #[serde(default)]
+    pub input_bindings: crate::input::InputBindings,
+    /// Orientation gizmo and scale bar visibility
+    #[serde(default)]
+    pub viewport_overlays: ViewportOverlaySettings,
+    /// Physics values overridden for every scene
+    #[serde(default)]
+    pub physics_overrides: crate::simulation::PhysicsOverrides,
+    /// Panel opacities and HUD mode
+    #[serde(default)]
+    pub hud: crate::ui::HudSettings,
+    /// Screenshot folder and the annotation overlay burned into captures
+    #[serde(default)]
+    pub capture: crate::simulation::CaptureSettings,
+    /// Tessellation of the cell sphere mesh
+    #[serde(default)]
+    pub sphere_quality: crate::rendering::SphereQuality,
+    /// Whether the guided tutorial was finished or turned down
+    #[serde(default)]
+    pub tutorial: crate::ui::tutorial::TutorialProgress,
+}
+
+fn default_genome_directory() -> PathBuf {
+    PathBuf::from(crate::genome::browser::DEFAULT_GENOMES_DIR)
+}
+
+/// Geometry of a panel popped out into its own OS window
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DetachedWindowGeometry {
+    pub size: [u32; 2],
+    /// Top-left corner in physical screen coordinates; None lets the OS place the window
+    pub position: Option<[i32; 2]>,
+}
+
+impl Default for DetachedWindowGeometry {
+    fn default() -> Self {
+        Self {
+            size: [800, 600],
+            position: None,
+        }
+    }
+}
+
+/// Detached window geometry, per panel
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DetachedWindowSettings {
+    #[serde(default)]
+    pub genome_graph: DetachedWindowGeometry,
 }
 
 /// Window visibility settings
@@ -87,6 +172,20 @@ pub struct FogSettings {
     pub scattering: f32,
     pub ambient_intensity: f32,
     pub fog_color: [f32; 3],
+    #[serde(default = "default_false")]
+    pub colony_density: bool,
+    #[serde(default = "default_colony_intensity")]
+    pub colony_intensity: f32,
+    #[serde(default = "default_colony_interval")]
+    pub colony_interval: f32,
+}
+
+fn default_colony_intensity() -> f32 {
+    crate::rendering::colony_fog::DEFAULT_COLONY_FOG_INTENSITY
+}
+
+fn default_colony_interval() -> f32 {
+    crate::rendering::colony_fog::DEFAULT_COLONY_FOG_INTERVAL
 }
 
 impl Default for FogSettings {
@@ -98,6 +197,25 @@ impl Default for FogSettings {
             scattering: 0.3,
             ambient_intensity: 0.02,
             fog_color: [0.3, 0.4, 0.5],
+            colony_density: false,
+            colony_intensity: default_colony_intensity(),
+            colony_interval: default_colony_interval(),
+        }
+    }
+}
+
+/// Visibility of the orientation gizmo and scale bar drawn over the viewport
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ViewportOverlaySettings {
+    pub show_compass: bool,
+    pub show_scale_bar: bool,
+}
+
+impl Default for ViewportOverlaySettings {
+    fn default() -> Self {
+        Self {
+            show_compass: true,
+            show_scale_bar: true,
         }
     }
 }
@@ -125,12 +243,26 @@ impl Default for BloomSettings {
 }
 
 /// Lighting settings
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct LightingSettings {
     pub directional_illuminance: f32,
     pub directional_color: [f32; 3],
     pub directional_rotation: [f32; 3],
     pub ambient_brightness: f32,
+    #[serde(default)]
+    pub day_night: crate::rendering::DayNightCycle,
+}
+
+impl LightingSettings {
+    fn of(config: &crate::ui::lighting_settings::LightingConfig) -> Self {
+        Self {
+            directional_illuminance: config.directional_illuminance,
+            directional_color: config.directional_color,
+            directional_rotation: config.directional_rotation,
+            ambient_brightness: config.ambient_brightness,
+            day_night: config.day_night.clone(),
+        }
+    }
 }
 
 impl Default for LightingSettings {
@@ -140,6 +272,7 @@ impl Default for LightingSettings {
             directional_color: [0.934, 0.934, 0.934],
             directional_rotation: [55.1, -103.5, 0.0],
             ambient_brightness: 1327.0,
+            day_night: crate::rendering::DayNightCycle::default(),
         }
     }
 }
@@ -268,6 +401,42 @@ impl Default for UiSettings {
             simulation_settings: SimulationSettings::default(),
             // Default lock settings
             lock_settings: LockSettings::default(),
+            // Original HSV mode colors
+            mode_palette: crate::genome::ColorPalette::default(),
+            // Detached windows open at the default size, placed by the OS
+            detached_windows: DetachedWindowSettings::default(),
+            // Scan ./genomes until another folder is chosen
+            genome_directory: default_genome_directory(),
+            // Audio on at moderate volumes
+            audio_settings: crate::audio::AudioSettings::default(),
+            // Live stats server off, loopback only
+            live_stats: crate::simulation::LiveStatsSettings::default(),
+            // Spectator host off, 15 updates a second once enabled
+            spectator: crate::simulation::SpectatorSettings::default(),
+            // Every watchdog condition off; a trigger pauses and flashes the title
+            watchdog: crate::simulation::WatchdogSettings::default(),
+            // Five minutes of raw history, two hours per second, two days per minute
+            history: crate::simulation::HistorySettings::default(),
+            // Exact up to 20K cells, then 4K-cell samples ten times a second
+            population_stats: crate::simulation::PopulationStatsSettings::default(),
+            // No derived metrics
+            derived_metrics: crate::simulation::DerivedMetricSettings::default(),
+            settle: crate::simulation::SettleSettings::default(),
+            // Warn about cycles that pass capacity within 30 seconds
+            growth_guardrail: crate::ui::windows::growth_guardrail::GrowthGuardrailSettings::default(),
+            // Mouse bindings (middle-drag orbit) until laptop mode is chosen
+            input_bindings: crate::input::InputBindings::default(),
+            // Gizmo and scale bar shown
+            viewport_overlays: ViewportOverlaySettings::default(),
+            // Built-in physics defaults
+            physics_overrides: crate::simulation::PhysicsOverrides::default(),
+            // Opaque panels, HUD mode off (Alt to use panels while it is on)
+            hud: crate::ui::HudSettings::default(),
+            // ./screenshots, no annotations
+            capture: crate::simulation::CaptureSettings::default(),
+            // Picked from the cell count
+            sphere_quality: crate::rendering::SphereQuality::default(),
+            tutorial: Default::default(),
         }
     }
 }
@@ -307,24 +476,36 @@ impl UiSettings {
     pub fn load() -> Self {
         let path = Self::settings_path();
 
-        if let Ok(contents) = fs::read_to_string(&path) {
-            if let Ok(settings) = serde_json::from_str::<UiSettings>(&contents) {
+        if crate::safe_mode::is_bypassed(Subsystem::UiSettings) {
+            return Self::default();
+        }
+
+        if !path.exists() {
+            info!("Using default UI settings (first startup)");
+            return Self::default();
+        }
+
+        match crate::error::read_json::<UiSettings>(&path, "UI settings") {
+            Ok(settings) => {
                 info!("Loaded UI settings from {:?}", path);
-                return settings;
-            } else {
-                warn!("Failed to parse UI settings, using defaults");
+                settings
+            }
+            Err(e) => {
+                warn!("{}, using defaults", e);
+                Self::default()
             }
         }
-
-        info!("Using default UI settings (first startup)");
-        Self::default()
     }
 
     /// Save settings to disk
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// Skipped in safe mode, which keeps the bypassed file as it was.
+    pub fn save(&self) -> crate::error::Result<()> {
+        if crate::safe_mode::is_bypassed(Subsystem::UiSettings) {
+            return Ok(());
+        }
         let path = Self::settings_path();
-        let contents = serde_json::to_string_pretty(self)?;
-        fs::write(&path, contents)?;
+        crate::error::write_json(&path, self, "UI settings")?;
         info!("Saved UI settings to {:?}", path);
         Ok(())
     }
@@ -401,6 +582,9 @@ pub fn save_ui_settings_on_change(
                     fog_settings.fog_color.to_srgba().green,
                     fog_settings.fog_color.to_srgba().blue,
                 ],
+                colony_density: fog_settings.colony_density,
+                colony_intensity: fog_settings.colony_intensity,
+                colony_interval: fog_settings.colony_interval,
             },
             bloom_settings: BloomSettings {
                 enabled: rendering_config.bloom_enabled,
@@ -458,7 +642,10 @@ pub fn save_ui_settings_on_change(
         || (last.fog_settings.ambient_intensity - fog_settings.ambient_intensity).abs() > 0.001
         || (last.fog_settings.fog_color[0] - fog_settings.fog_color.to_srgba().red).abs() > 0.001
         || (last.fog_settings.fog_color[1] - fog_settings.fog_color.to_srgba().green).abs() > 0.001
-        || (last.fog_settings.fog_color[2] - fog_settings.fog_color.to_srgba().blue).abs() > 0.001;
+        || (last.fog_settings.fog_color[2] - fog_settings.fog_color.to_srgba().blue).abs() > 0.001
+        || last.fog_settings.colony_density != fog_settings.colony_density
+        || (last.fog_settings.colony_intensity - fog_settings.colony_intensity).abs() > 0.001
+        || (last.fog_settings.colony_interval - fog_settings.colony_interval).abs() > 0.001;
 
     // Check if bloom settings changed
     let current_bloom_mode = match rendering_config.bloom_composite_mode {
@@ -537,6 +724,9 @@ pub fn save_ui_settings_on_change(
                 fog_settings.fog_color.to_srgba().green,
                 fog_settings.fog_color.to_srgba().blue,
             ],
+            colony_density: fog_settings.colony_density,
+            colony_intensity: fog_settings.colony_intensity,
+            colony_interval: fog_settings.colony_interval,
         };
         settings.bloom_settings = BloomSettings {
             enabled: rendering_config.bloom_enabled,
@@ -599,6 +789,9 @@ pub fn save_ui_settings_on_change(
                     fog_settings.fog_color.to_srgba().green,
                     fog_settings.fog_color.to_srgba().blue,
                 ],
+                colony_density: fog_settings.colony_density,
+                colony_intensity: fog_settings.colony_intensity,
+                colony_interval: fog_settings.colony_interval,
             },
             bloom_settings: BloomSettings {
                 enabled: rendering_config.bloom_enabled,
@@ -648,6 +841,9 @@ pub fn load_fog_settings_on_startup(
         saved_settings.fog_settings.fog_color[1],
         saved_settings.fog_settings.fog_color[2],
     );
+    fog_settings.colony_density = saved_settings.fog_settings.colony_density;
+    fog_settings.colony_intensity = saved_settings.fog_settings.colony_intensity;
+    fog_settings.colony_interval = saved_settings.fog_settings.colony_interval;
 }
 
 /// System to load bloom settings from saved UI settings on startup
@@ -675,6 +871,42 @@ pub fn load_lighting_settings_on_startup(
     lighting_config.directional_color = saved_settings.lighting_settings.directional_color;
     lighting_config.directional_rotation = saved_settings.lighting_settings.directional_rotation;
     lighting_config.ambient_brightness = saved_settings.lighting_settings.ambient_brightness;
+    lighting_config.day_night = saved_settings.lighting_settings.day_night;
+}
+
+/// Save the lighting and its day/night cycle once they stop changing (dragging a slider
+/// writes the file once)
+pub fn save_lighting_settings_on_change(
+    time: Res<Time>,
+    lighting_config: Res<crate::ui::lighting_settings::LightingConfig>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<LightingSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(LightingSettings::of(&lighting_config));
+        return;
+    };
+
+    if lighting_config.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    if !changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5) {
+        return;
+    }
+    *changed_at = None;
+    let current = LightingSettings::of(&lighting_config);
+    if *last != current {
+        let mut settings = UiSettings::load();
+        settings.lighting_settings = current.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(current);
+    }
 }
 
 /// System to load skybox settings from saved UI settings on startup
@@ -690,7 +922,6 @@ pub fn load_skybox_settings_on_startup(
 /// System to load simulation settings from saved UI settings on startup
 pub fn load_simulation_settings_on_startup(
     mut threading_config: ResMut<crate::simulation::SimulationThreadingConfig>,
-    mut physics_config: ResMut<crate::simulation::PhysicsConfig>,
     mut cpu_cell_capacity: ResMut<crate::ui::scene_manager::CpuCellCapacity>,
     mut spatial_grid_config: ResMut<crate::simulation::SpatialGridConfig>,
 ) {
@@ -699,7 +930,6 @@ pub fn load_simulation_settings_on_startup(
     threading_config.cpu_multithreaded = saved_settings.simulation_settings.cpu_multithreaded;
     cpu_cell_capacity.capacity = saved_settings.simulation_settings.cpu_cell_capacity;
     spatial_grid_config.grid_density = saved_settings.simulation_settings.grid_density;
-    physics_config.disable_collisions = saved_settings.simulation_settings.disable_collisions;
 }
 
 /// System to load lock settings from saved UI settings on startup
@@ -734,6 +964,7 @@ pub fn load_lock_settings_on_startup(
 /// System to save lock settings when they change
 pub fn save_lock_settings_on_change(
     global_ui_state: Res<crate::ui::GlobalUiState>,
+    mut notifications: ResMut<crate::ui::Notifications>,
     mut last_saved: Local<Option<LockSettings>>,
 ) {
     // Initialize on first run
@@ -783,7 +1014,7 @@ pub fn save_lock_settings_on_change(
         };
 
         if let Err(e) = settings.save() {
-            error!("Failed to save lock settings: {}", e);
+            notifications.error(&e);
         } else {
             info!("Saved lock settings");
         }
@@ -798,3 +1029,625 @@ pub fn save_lock_settings_on_change(
         });
     }
 }
+
+/// Load the mode color palette and apply it to the untouched default genome
+pub fn load_mode_palette_on_startup(
+    mut genome_editor_state: ResMut<crate::ui::GenomeEditorState>,
+    mut current_genome: ResMut<crate::genome::CurrentGenome>,
+) {
+    let palette = UiSettings::load().mode_palette;
+    genome_editor_state.mode_palette = palette;
+
+    // Existing genomes keep their explicit colors; only recolor the fresh default
+    if current_genome.genome == crate::genome::GenomeData::default() {
+        let selected_mode_index = current_genome.selected_mode_index;
+        current_genome.replace(crate::genome::GenomeData::with_palette(palette), selected_mode_index, None);
+    }
+    info!("Loaded mode palette: {}", palette.name());
+}
+
+/// Point the Open Genome browser at the saved genomes directory
+pub fn load_genome_directory_on_startup(mut browser: ResMut<crate::genome::GenomeBrowser>) {
+    let directory = UiSettings::load().genome_directory;
+    browser.current_dir = directory.clone();
+    browser.root = directory;
+}
+
+/// Save the mode color palette when it changes
+pub fn save_mode_palette_on_change(
+    genome_editor_state: Res<crate::ui::GenomeEditorState>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::genome::ColorPalette>>,
+) {
+    // Initialize on first run
+    let Some(last) = *last_saved else {
+        *last_saved = Some(genome_editor_state.mode_palette);
+        return;
+    };
+
+    if last != genome_editor_state.mode_palette {
+        let mut settings = UiSettings::load();
+        settings.mode_palette = genome_editor_state.mode_palette;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        } else {
+            info!("Saved mode palette: {}", genome_editor_state.mode_palette.name());
+        }
+
+        *last_saved = Some(genome_editor_state.mode_palette);
+    }
+}
+
+/// Load the audio volumes and mute state
+pub fn load_audio_settings_on_startup(mut audio_settings: ResMut<crate::audio::AudioSettings>) {
+    *audio_settings = UiSettings::load().audio_settings;
+}
+
+/// Save the audio volumes and mute state once they stop changing
+///
+/// Waits for a moment of quiet so dragging a volume slider writes the file once.
+pub fn save_audio_settings_on_change(
+    time: Res<Time>,
+    audio_settings: Res<crate::audio::AudioSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::audio::AudioSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(audio_settings.clone());
+        return;
+    };
+
+    if audio_settings.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *audio_settings {
+        let mut settings = UiSettings::load();
+        settings.audio_settings = audio_settings.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(audio_settings.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the live stats server address and whether it was running
+pub fn load_live_stats_settings_on_startup(mut live_stats: ResMut<crate::simulation::LiveStatsSettings>) {
+    *live_stats = UiSettings::load().live_stats;
+}
+
+/// Save the live stats settings once they stop changing (typing an address writes the file once)
+pub fn save_live_stats_settings_on_change(
+    time: Res<Time>,
+    live_stats: Res<crate::simulation::LiveStatsSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::LiveStatsSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(live_stats.clone());
+        return;
+    };
+
+    if live_stats.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *live_stats {
+        let mut settings = UiSettings::load();
+        settings.live_stats = live_stats.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(live_stats.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the spectator host address and update rate and whether it was running
+pub fn load_spectator_settings_on_startup(mut spectator: ResMut<crate::simulation::SpectatorSettings>) {
+    *spectator = UiSettings::load().spectator;
+}
+
+/// Save the spectator host settings once they stop changing
+pub fn save_spectator_settings_on_change(
+    time: Res<Time>,
+    spectator: Res<crate::simulation::SpectatorSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::SpectatorSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(spectator.clone());
+        return;
+    };
+
+    if spectator.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *spectator {
+        let mut settings = UiSettings::load();
+        settings.spectator = spectator.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(spectator.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the watchdog conditions and trigger actions
+pub fn load_watchdog_settings_on_startup(mut watchdog: ResMut<crate::simulation::WatchdogSettings>) {
+    *watchdog = UiSettings::load().watchdog;
+}
+
+/// Save the watchdog settings once they stop changing (dragging a threshold writes the file once)
+pub fn save_watchdog_settings_on_change(
+    time: Res<Time>,
+    watchdog: Res<crate::simulation::WatchdogSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::WatchdogSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(watchdog.clone());
+        return;
+    };
+
+    if watchdog.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *watchdog {
+        let mut settings = UiSettings::load();
+        settings.watchdog = watchdog.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(watchdog.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the history retention and memory budget
+pub fn load_history_settings_on_startup(mut history: ResMut<crate::simulation::HistorySettings>) {
+    *history = UiSettings::load().history;
+}
+
+/// Save the history settings once they stop changing (dragging a retention writes the file once)
+pub fn save_history_settings_on_change(
+    time: Res<Time>,
+    history: Res<crate::simulation::HistorySettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::HistorySettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(history.clone());
+        return;
+    };
+
+    if history.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *history {
+        let mut settings = UiSettings::load();
+        settings.history = history.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(history.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the population statistics sampling settings
+pub fn load_population_stats_settings_on_startup(mut population_stats: ResMut<crate::simulation::PopulationStatsSettings>) {
+    *population_stats = UiSettings::load().population_stats;
+}
+
+/// Save the population statistics settings once they stop changing
+pub fn save_population_stats_settings_on_change(
+    time: Res<Time>,
+    population_stats: Res<crate::simulation::PopulationStatsSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::PopulationStatsSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(population_stats.clone());
+        return;
+    };
+
+    if population_stats.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *population_stats {
+        let mut settings = UiSettings::load();
+        settings.population_stats = population_stats.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(population_stats.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the user-defined metrics
+pub fn load_derived_metric_settings_on_startup(mut derived_metrics: ResMut<crate::simulation::DerivedMetricSettings>) {
+    *derived_metrics = UiSettings::load().derived_metrics;
+}
+
+/// Save the user-defined metrics once they stop changing
+pub fn save_derived_metric_settings_on_change(
+    time: Res<Time>,
+    derived_metrics: Res<crate::simulation::DerivedMetricSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::DerivedMetricSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(derived_metrics.clone());
+        return;
+    };
+
+    if derived_metrics.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *derived_metrics {
+        let mut settings = UiSettings::load();
+        settings.derived_metrics = derived_metrics.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(derived_metrics.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the Settle action settings
+pub fn load_settle_settings_on_startup(mut settle: ResMut<crate::simulation::SettleSettings>) {
+    *settle = UiSettings::load().settle;
+}
+
+/// Save the Settle action settings once they stop changing
+pub fn save_settle_settings_on_change(
+    time: Res<Time>,
+    settle: Res<crate::simulation::SettleSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::SettleSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(*settle);
+        return;
+    };
+
+    if settle.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *settle {
+        let mut settings = UiSettings::load();
+        settings.settle = *settle;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(*settle);
+        *changed_at = None;
+    }
+}
+
+/// Load the runaway growth guardrail settings
+pub fn load_growth_guardrail_settings_on_startup(mut guardrail: ResMut<crate::ui::windows::growth_guardrail::GrowthGuardrailSettings>) {
+    *guardrail = UiSettings::load().growth_guardrail;
+}
+
+/// Save the runaway growth guardrail settings once they stop changing
+pub fn save_growth_guardrail_settings_on_change(
+    time: Res<Time>,
+    guardrail: Res<crate::ui::windows::growth_guardrail::GrowthGuardrailSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::ui::windows::growth_guardrail::GrowthGuardrailSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(*guardrail);
+        return;
+    };
+
+    if guardrail.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *guardrail {
+        let mut settings = UiSettings::load();
+        settings.growth_guardrail = *guardrail;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(*guardrail);
+        *changed_at = None;
+    }
+}
+
+/// Load panel opacities and HUD mode
+pub fn load_hud_settings_on_startup(mut hud: ResMut<crate::ui::HudSettings>) {
+    *hud = UiSettings::load().hud;
+}
+
+/// Save panel opacities and HUD mode once they stop changing
+pub fn save_hud_settings_on_change(
+    time: Res<Time>,
+    hud: Res<crate::ui::HudSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::ui::HudSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(hud.clone());
+        return;
+    };
+
+    if hud.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *hud {
+        let mut settings = UiSettings::load();
+        settings.hud = hud.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(hud.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the screenshot folder and annotation overlay
+pub fn load_capture_settings_on_startup(mut capture: ResMut<crate::simulation::CaptureSettings>) {
+    *capture = UiSettings::load().capture;
+}
+
+/// Save the capture settings once they stop changing (typing a caption writes the file once)
+pub fn save_capture_settings_on_change(
+    time: Res<Time>,
+    capture: Res<crate::simulation::CaptureSettings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::CaptureSettings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(capture.clone());
+        return;
+    };
+
+    if capture.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *capture {
+        let mut settings = UiSettings::load();
+        settings.capture = capture.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(capture.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the camera and graph bindings
+pub fn load_input_bindings_on_startup(mut bindings: ResMut<crate::input::InputBindings>) {
+    *bindings = UiSettings::load().input_bindings;
+}
+
+/// Save the bindings once they stop changing (dragging a sensitivity slider writes the file once)
+pub fn save_input_bindings_on_change(
+    time: Res<Time>,
+    bindings: Res<crate::input::InputBindings>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::input::InputBindings>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(bindings.clone());
+        return;
+    };
+
+    if bindings.is_changed() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != *bindings {
+        let mut settings = UiSettings::load();
+        settings.input_bindings = bindings.clone();
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(bindings.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load whether the orientation gizmo and scale bar are shown
+pub fn load_viewport_overlay_settings_on_startup(mut rendering_config: ResMut<crate::rendering::RenderingConfig>) {
+    let overlays = UiSettings::load().viewport_overlays;
+    rendering_config.show_compass = overlays.show_compass;
+    rendering_config.show_scale_bar = overlays.show_scale_bar;
+}
+
+/// Save the gizmo and scale bar visibility when toggled
+pub fn save_viewport_overlay_settings_on_change(
+    rendering_config: Res<crate::rendering::RenderingConfig>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<ViewportOverlaySettings>>,
+) {
+    let current = ViewportOverlaySettings {
+        show_compass: rendering_config.show_compass,
+        show_scale_bar: rendering_config.show_scale_bar,
+    };
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(current);
+        return;
+    };
+
+    if *last != current {
+        let mut settings = UiSettings::load();
+        settings.viewport_overlays = current;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(current);
+    }
+}
+
+/// Load the global physics overrides
+///
+/// Settings files from before the override layers kept "disable collisions" with the
+/// simulation settings; it becomes a global override.
+pub fn load_physics_overrides_on_startup(mut layers: ResMut<crate::simulation::PhysicsLayers>) {
+    let saved_settings = UiSettings::load();
+    let mut global = saved_settings.physics_overrides;
+    if saved_settings.simulation_settings.disable_collisions && !global.contains("disable_collisions") {
+        global.insert("disable_collisions", serde_json::Value::Bool(true));
+    }
+    layers.global = global;
+}
+
+/// Save the global physics overrides once they stop changing
+pub fn save_physics_overrides_on_change(
+    time: Res<Time>,
+    layers: Res<crate::simulation::PhysicsLayers>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::simulation::PhysicsOverrides>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // Initialize on first run
+    let Some(last) = last_saved.as_ref() else {
+        *last_saved = Some(layers.global.clone());
+        return;
+    };
+
+    if *last != layers.global && changed_at.is_none() {
+        *changed_at = Some(time.elapsed_secs());
+    }
+    let settled = changed_at.is_some_and(|at| time.elapsed_secs() - at > 0.5);
+    if settled && *last != layers.global {
+        let mut settings = UiSettings::load();
+        settings.physics_overrides = layers.global.clone();
+        // Now stored as a global override
+        settings.simulation_settings.disable_collisions = false;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(layers.global.clone());
+        *changed_at = None;
+    }
+}
+
+/// Load the cell sphere quality
+pub fn load_sphere_quality_on_startup(mut quality: ResMut<crate::rendering::SphereQuality>) {
+    *quality = UiSettings::load().sphere_quality;
+}
+
+/// Save the cell sphere quality when it changes
+pub fn save_sphere_quality_on_change(
+    quality: Res<crate::rendering::SphereQuality>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::rendering::SphereQuality>>,
+) {
+    // Initialize on first run
+    let Some(last) = *last_saved else {
+        *last_saved = Some(*quality);
+        return;
+    };
+
+    if last != *quality {
+        let mut settings = UiSettings::load();
+        settings.sphere_quality = *quality;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(*quality);
+    }
+}
+
+/// Load whether the tutorial was finished or turned down
+pub fn load_tutorial_progress_on_startup(mut tutorial: ResMut<crate::ui::tutorial::Tutorial>) {
+    tutorial.progress = UiSettings::load().tutorial;
+}
+
+/// Save the tutorial progress when it changes
+pub fn save_tutorial_progress_on_change(
+    tutorial: Res<crate::ui::tutorial::Tutorial>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    mut last_saved: Local<Option<crate::ui::tutorial::TutorialProgress>>,
+) {
+    // Initialize on first run
+    let Some(last) = *last_saved else {
+        *last_saved = Some(tutorial.progress);
+        return;
+    };
+
+    if last != tutorial.progress {
+        let mut settings = UiSettings::load();
+        settings.tutorial = tutorial.progress;
+
+        if let Err(e) = settings.save() {
+            notifications.error(&e);
+        }
+
+        *last_saved = Some(tutorial.progress);
+    }
+}
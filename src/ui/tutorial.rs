@@ -0,0 +1,536 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use serde::{Deserialize, Serialize};
+use crate::genome::CurrentGenome;
+use crate::ui::dock::{is_panel_open, open_panel, DockResource, Panel};
+use crate::ui::{GenomeEditorState, HudState, MainCamera, Notifications, ViewportRect};
+
+/// Degrees the camera must turn for the orbit step
+const ORBIT_ANGLE: f32 = 15.0;
+
+/// Percent of the preview duration the time slider must move for the scrub step
+const SCRUB_PERCENT: f32 = 2.0;
+
+/// Plugin for the guided tutorial and its first-run prompt
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tutorial>()
+            .add_systems(Startup, crate::ui::settings::load_tutorial_progress_on_startup)
+            .add_systems(Update, crate::ui::settings::save_tutorial_progress_on_change)
+            .add_systems(bevy_egui::EguiPrimaryContextPass, render_tutorial.after(crate::ui::ui_system));
+    }
+}
+
+/// Whether the tutorial was finished or turned down, saved with the UI settings
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct TutorialProgress {
+    pub completed: bool,
+    /// The first-run prompt was answered with "Not now" or the tutorial was exited early
+    pub prompt_dismissed: bool,
+}
+
+impl TutorialProgress {
+    /// Whether to offer the tutorial on startup
+    pub fn show_prompt(&self) -> bool {
+        !self.completed && !self.prompt_dismissed
+    }
+}
+
+/// Part of the window a step points at
+#[derive(Clone, Debug, PartialEq)]
+pub enum TutorialTarget {
+    /// Nothing in particular; the whole window is dimmed
+    None,
+    /// The 3D viewport
+    Viewport,
+    /// A dock panel, by the name it's drawn under
+    Panel(Panel),
+}
+
+/// State the step predicates look at, captured from the live resources
+#[derive(Clone, Debug, PartialEq)]
+pub struct TutorialSnapshot {
+    pub camera_rotation: Quat,
+    pub selected_mode: usize,
+    pub initial_mode: usize,
+    /// Fields of the initial mode
+    pub color: Vec3,
+    pub split_interval: f32,
+    /// Mode child A becomes (None for an invalid reference)
+    pub child_a_mode: Option<usize>,
+    pub makes_adhesion: bool,
+    /// Time slider position (percent of the preview duration)
+    pub time_value: f32,
+    /// Serial of the last explicit genome save (0 before any)
+    pub save_serial: u64,
+}
+
+impl TutorialSnapshot {
+    pub fn capture(current_genome: &CurrentGenome, editor: &GenomeEditorState, camera_rotation: Quat) -> Self {
+        let genome = &current_genome.genome;
+        let initial_mode = genome.initial_mode_index();
+        let mode = genome.modes.get(initial_mode);
+        Self {
+            camera_rotation,
+            selected_mode: current_genome.selected_mode_index.max(0) as usize,
+            initial_mode,
+            color: mode.map_or(Vec3::ZERO, |mode| mode.color),
+            split_interval: mode.map_or(0.0, |mode| mode.split_interval),
+            child_a_mode: mode.and_then(|mode| genome.checked_index(mode.child_a.mode_number)),
+            makes_adhesion: mode.is_some_and(|mode| mode.parent_make_adhesion),
+            time_value: editor.time_value,
+            save_serial: current_genome.last_save().map_or(0, |save| save.serial),
+        }
+    }
+}
+
+/// Goal of a step: true once reached, given the state when the step began and now
+pub type StepPredicate = fn(&TutorialSnapshot, &TutorialSnapshot) -> bool;
+
+/// One step of the tutorial
+pub struct TutorialStep {
+    pub title: &'static str,
+    pub text: &'static str,
+    pub target: TutorialTarget,
+    /// None waits for the Next button
+    pub done: Option<StepPredicate>,
+}
+
+/// The tutorial, in order
+pub static TUTORIAL_STEPS: [TutorialStep; 9] = [
+    TutorialStep {
+        title: "Welcome",
+        text: "BioSpheres grows organisms from a genome: a set of modes, each describing how a cell looks, \
+               when it splits and what its two children become. This tour builds a small genome in a few minutes.",
+        target: TutorialTarget::None,
+        done: None,
+    },
+    TutorialStep {
+        title: "Orbit the camera",
+        text: "Drag in the viewport to orbit around the cells, and scroll to zoom.",
+        target: TutorialTarget::Viewport,
+        done: Some(camera_orbited),
+    },
+    TutorialStep {
+        title: "Select the initial mode",
+        text: "Every organism starts as one cell in the initial mode. Click it in the Modes panel to edit it.",
+        target: TutorialTarget::Panel(Panel::Modes),
+        done: Some(initial_mode_selected),
+    },
+    TutorialStep {
+        title: "Change its color",
+        text: "Click the color swatch of the initial mode and pick a new color. The cells recolor as you drag.",
+        target: TutorialTarget::Panel(Panel::Modes),
+        done: Some(initial_color_changed),
+    },
+    TutorialStep {
+        title: "Set a split interval",
+        text: "The split interval is how many seconds a cell lives before it divides. Drag it to a new value.",
+        target: TutorialTarget::Panel(Panel::ParentSettings),
+        done: Some(split_interval_changed),
+    },
+    TutorialStep {
+        title: "Give child A a new mode",
+        text: "In the Genome Graph, drag from the initial mode's child A output to another mode, \
+               so one child of each division grows differently.",
+        target: TutorialTarget::Panel(Panel::GenomeGraph),
+        done: Some(child_a_linked),
+    },
+    TutorialStep {
+        title: "Enable adhesion",
+        text: "Tick Make Adhesion so the two children stay stuck together and the organism holds its shape.",
+        target: TutorialTarget::Panel(Panel::NameTypeEditor),
+        done: Some(adhesion_enabled),
+    },
+    TutorialStep {
+        title: "Scrub time",
+        text: "Drag the time slider to watch the organism grow from its first cell. Every edit replays instantly.",
+        target: TutorialTarget::Panel(Panel::TimeSlider),
+        done: Some(time_scrubbed),
+    },
+    TutorialStep {
+        title: "Save the genome",
+        text: "Click Save Genome to keep your organism. Load it again later, or drop it into a CPU scene.",
+        target: TutorialTarget::Panel(Panel::NameTypeEditor),
+        done: Some(genome_saved),
+    },
+];
+
+fn camera_orbited(start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    start.camera_rotation.angle_between(now.camera_rotation) >= ORBIT_ANGLE.to_radians()
+}
+
+fn initial_mode_selected(_start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.selected_mode == now.initial_mode
+}
+
+fn initial_color_changed(start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.initial_mode == start.initial_mode && now.color != start.color
+}
+
+fn split_interval_changed(start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.initial_mode == start.initial_mode && now.split_interval != start.split_interval
+}
+
+fn child_a_linked(_start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.child_a_mode.is_some_and(|mode| mode != now.initial_mode)
+}
+
+fn adhesion_enabled(_start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.makes_adhesion
+}
+
+fn time_scrubbed(start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    (now.time_value - start.time_value).abs() >= SCRUB_PERCENT
+}
+
+fn genome_saved(start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+    now.save_serial > start.save_serial
+}
+
+/// A tutorial in progress
+#[derive(Clone, Debug)]
+pub struct ActiveTutorial {
+    pub step: usize,
+    /// State when the step began, captured on its first frame
+    pub start: Option<TutorialSnapshot>,
+}
+
+impl ActiveTutorial {
+    /// Check the current step against `now`; returns true once every step is done
+    pub fn update(&mut self, now: &TutorialSnapshot) -> bool {
+        let Some(step) = TUTORIAL_STEPS.get(self.step) else {
+            return true;
+        };
+        let start = self.start.get_or_insert_with(|| now.clone());
+        if step.done.is_some_and(|done| done(start, now)) {
+            self.next(now);
+        }
+        self.step >= TUTORIAL_STEPS.len()
+    }
+
+    /// Move on to the next step, which starts from `now`
+    pub fn next(&mut self, now: &TutorialSnapshot) {
+        self.step += 1;
+        self.start = Some(now.clone());
+    }
+}
+
+/// Tutorial progress and the tutorial running, if any
+#[derive(Resource, Default)]
+pub struct Tutorial {
+    pub progress: TutorialProgress,
+    pub active: Option<ActiveTutorial>,
+}
+
+impl Tutorial {
+    /// Start from the first step (Windows menu and the first-run prompt)
+    pub fn start(&mut self) {
+        self.active = Some(ActiveTutorial { step: 0, start: None });
+    }
+
+    /// Leave the tutorial; it isn't offered on startup again
+    pub fn exit(&mut self) {
+        self.active = None;
+        self.progress.prompt_dismissed = true;
+    }
+}
+
+/// Where the step card goes: away from the highlighted region, so it never covers it
+pub fn card_anchor(screen: egui::Rect, target: Option<egui::Rect>) -> (egui::Align2, egui::Pos2) {
+    let margin = 16.0;
+    let Some(target) = target else {
+        return (egui::Align2::CENTER_CENTER, screen.center());
+    };
+    // The viewport fills most of the window: sit at its top edge
+    if target.width() > screen.width() * 0.5 && target.height() > screen.height() * 0.5 {
+        return (egui::Align2::CENTER_TOP, target.center_top() + egui::vec2(0.0, margin));
+    }
+    let right_room = screen.right() - target.right();
+    let left_room = target.left() - screen.left();
+    if right_room >= left_room {
+        (egui::Align2::LEFT_CENTER, egui::pos2(target.right() + margin * 3.0, target.center().y.clamp(screen.top() + 120.0, screen.bottom() - 120.0)))
+    } else {
+        (egui::Align2::RIGHT_CENTER, egui::pos2(target.left() - margin * 3.0, target.center().y.clamp(screen.top() + 120.0, screen.bottom() - 120.0)))
+    }
+}
+
+/// Point of `rect` closest to `pos`
+fn closest_point(rect: egui::Rect, pos: egui::Pos2) -> egui::Pos2 {
+    egui::pos2(pos.x.clamp(rect.left(), rect.right()), pos.y.clamp(rect.top(), rect.bottom()))
+}
+
+/// Dim everything but the target and outline it
+fn paint_spotlight(ctx: &egui::Context, screen: egui::Rect, target: Option<egui::Rect>) {
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("tutorial_spotlight")));
+    let dim = egui::Color32::from_black_alpha(150);
+    let Some(target) = target.map(|target| target.intersect(screen)) else {
+        painter.rect_filled(screen, 0.0, dim);
+        return;
+    };
+    let above = egui::Rect::from_min_max(screen.min, egui::pos2(screen.right(), target.top()));
+    let below = egui::Rect::from_min_max(egui::pos2(screen.left(), target.bottom()), screen.max);
+    let left = egui::Rect::from_min_max(egui::pos2(screen.left(), target.top()), egui::pos2(target.left(), target.bottom()));
+    let right = egui::Rect::from_min_max(egui::pos2(target.right(), target.top()), egui::pos2(screen.right(), target.bottom()));
+    for rect in [above, below, left, right] {
+        if rect.is_positive() {
+            painter.rect_filled(rect, 0.0, dim);
+        }
+    }
+    painter.rect_stroke(target, 4.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 60)), egui::StrokeKind::Outside);
+}
+
+/// First-run prompt, the step card, the spotlight and the arrow, and step advancement
+#[allow(clippy::too_many_arguments)]
+pub fn render_tutorial(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut tutorial: ResMut<Tutorial>,
+    current_genome: Res<CurrentGenome>,
+    editor_state: Res<GenomeEditorState>,
+    cameras: Query<&MainCamera>,
+    hud_state: Res<HudState>,
+    viewport_rect: Res<ViewportRect>,
+    mut dock_resource: ResMut<DockResource>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+    let ctx = egui_context.get_mut();
+
+    let Some(active) = tutorial.active.as_mut() else {
+        if tutorial.progress.show_prompt() {
+            render_prompt(ctx, &mut tutorial);
+        }
+        return;
+    };
+
+    let camera_rotation = cameras.iter().next().map_or(Quat::IDENTITY, |camera| camera.rotation);
+    let now = TutorialSnapshot::capture(&current_genome, &editor_state, camera_rotation);
+    if active.update(&now) {
+        tutorial.active = None;
+        tutorial.progress.completed = true;
+        notifications.success("Tutorial complete. Reopen it any time from the Windows menu.");
+        return;
+    }
+
+    let step = &TUTORIAL_STEPS[active.step];
+    let screen = ctx.content_rect();
+    let target = match &step.target {
+        TutorialTarget::None => None,
+        TutorialTarget::Viewport => viewport_rect.rect,
+        TutorialTarget::Panel(panel) => hud_state.panel_rect(&panel.to_string()),
+    };
+    paint_spotlight(ctx, screen, target);
+
+    let (pivot, position) = card_anchor(screen, target);
+    let mut next = false;
+    let mut exit = false;
+    let card = egui::Area::new(egui::Id::new("tutorial_card"))
+        .pivot(pivot)
+        .fixed_pos(position)
+        .order(egui::Order::Tooltip)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_max_width(320.0);
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("Step {} of {}", active.step + 1, TUTORIAL_STEPS.len())).small().weak());
+                    ui.add(egui::ProgressBar::new(active.step as f32 / TUTORIAL_STEPS.len() as f32).desired_height(6.0));
+                });
+                ui.label(egui::RichText::new(step.title).heading());
+                ui.label(step.text);
+
+                if let TutorialTarget::Panel(panel) = &step.target {
+                    if sim_state.mode != crate::simulation::SimulationMode::Preview {
+                        ui.label(egui::RichText::new("The genome is edited in the Preview scene; switch to it in the Scene Manager.")
+                            .small().color(ui.visuals().warn_fg_color));
+                    } else if !is_panel_open(&dock_resource.tree, panel) {
+                        if ui.button(format!("Open {}", panel)).clicked() {
+                            open_panel(&mut dock_resource.tree, panel, Some(screen));
+                            dock_resource.preview_tree = dock_resource.tree.clone();
+                        }
+                    } else if target.is_none() {
+                        ui.label(egui::RichText::new(format!("Bring the {} tab to the front.", panel)).small().weak());
+                    }
+                }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    let next_label = if step.done.is_some() { "Skip step" } else { "Next" };
+                    next = ui.button(next_label).clicked();
+                    exit = ui.button("Exit tutorial").clicked();
+                });
+            });
+        });
+
+    // Arrow from the card to the highlighted region
+    if let Some(target) = target {
+        let card_rect = card.response.rect;
+        let tip = closest_point(target.expand(6.0), card_rect.center());
+        let origin = closest_point(card_rect, tip);
+        if origin.distance(tip) > 12.0 {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Tooltip, egui::Id::new("tutorial_arrow")));
+            painter.arrow(origin, tip - origin, egui::Stroke::new(3.0, egui::Color32::from_rgb(255, 200, 60)));
+        }
+    }
+
+    if next {
+        active.next(&now);
+        if active.step >= TUTORIAL_STEPS.len() {
+            tutorial.active = None;
+            tutorial.progress.completed = true;
+        }
+    } else if exit {
+        tutorial.exit();
+    }
+}
+
+/// Offer the tutorial to a new user
+fn render_prompt(ctx: &egui::Context, tutorial: &mut Tutorial) {
+    egui::Window::new("Welcome to BioSpheres")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label("New here? A short guided tour walks through building your first genome.");
+            ui.label(egui::RichText::new("You can start it later from the Windows menu.").small().weak());
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Start tutorial").clicked() {
+                    tutorial.start();
+                }
+                if ui.button("Not now").clicked() {
+                    tutorial.progress.prompt_dismissed = true;
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::ModeIndex;
+
+    fn snapshot() -> TutorialSnapshot {
+        TutorialSnapshot::capture(&CurrentGenome::default(), &GenomeEditorState::default(), Quat::IDENTITY)
+    }
+
+    fn step_done(title: &str, start: &TutorialSnapshot, now: &TutorialSnapshot) -> bool {
+        let step = TUTORIAL_STEPS.iter().find(|step| step.title == title).unwrap();
+        (step.done.unwrap())(start, now)
+    }
+
+    #[test]
+    fn test_camera_step_needs_a_real_orbit() {
+        let start = snapshot();
+        let mut now = start.clone();
+        now.camera_rotation = Quat::from_rotation_y(5f32.to_radians());
+        assert!(!step_done("Orbit the camera", &start, &now));
+        now.camera_rotation = Quat::from_rotation_y(30f32.to_radians());
+        assert!(step_done("Orbit the camera", &start, &now));
+    }
+
+    #[test]
+    fn test_genome_steps_follow_edits_to_the_initial_mode() {
+        let mut current_genome = CurrentGenome::default();
+        let editor = GenomeEditorState::default();
+        current_genome.genome.initial_mode = ModeIndex::new(2);
+        current_genome.selected_mode_index = 0;
+        let start = TutorialSnapshot::capture(&current_genome, &editor, Quat::IDENTITY);
+        assert!(!step_done("Select the initial mode", &start, &start));
+
+        current_genome.selected_mode_index = 2;
+        // Editing another mode doesn't count
+        current_genome.genome.modes[0].color = Vec3::new(0.1, 0.2, 0.3);
+        current_genome.genome.modes[0].split_interval += 3.0;
+        let now = TutorialSnapshot::capture(&current_genome, &editor, Quat::IDENTITY);
+        assert!(step_done("Select the initial mode", &start, &now));
+        assert!(!step_done("Change its color", &start, &now));
+        assert!(!step_done("Set a split interval", &start, &now));
+
+        let mode = &mut current_genome.genome.modes[2];
+        mode.color = Vec3::new(0.9, 0.1, 0.1);
+        mode.split_interval += 1.0;
+        mode.child_a.mode_number = ModeIndex::new(2);
+        mode.parent_make_adhesion = false;
+        let now = TutorialSnapshot::capture(&current_genome, &editor, Quat::IDENTITY);
+        assert!(step_done("Change its color", &start, &now));
+        assert!(step_done("Set a split interval", &start, &now));
+        // A link back to itself isn't a new mode
+        assert!(!step_done("Give child A a new mode", &start, &now));
+        assert!(!step_done("Enable adhesion", &start, &now));
+
+        let mode = &mut current_genome.genome.modes[2];
+        mode.child_a.mode_number = ModeIndex::new(5);
+        mode.parent_make_adhesion = true;
+        let now = TutorialSnapshot::capture(&current_genome, &editor, Quat::IDENTITY);
+        assert!(step_done("Give child A a new mode", &start, &now));
+        assert!(step_done("Enable adhesion", &start, &now));
+    }
+
+    #[test]
+    fn test_scrub_and_save_steps_need_a_change_since_the_step_began() {
+        let mut start = snapshot();
+        start.save_serial = 3;
+        let mut now = start.clone();
+        now.time_value += 1.0;
+        assert!(!step_done("Scrub time", &start, &now));
+        assert!(!step_done("Save the genome", &start, &now));
+        now.time_value += 2.0;
+        now.save_serial = 4;
+        assert!(step_done("Scrub time", &start, &now));
+        assert!(step_done("Save the genome", &start, &now));
+    }
+
+    #[test]
+    fn test_tutorial_advances_step_by_step_and_finishes() {
+        let mut active = ActiveTutorial { step: 0, start: None };
+        let mut now = snapshot();
+        // The welcome step waits for Next
+        assert!(!active.update(&now));
+        assert_eq!(active.step, 0);
+        active.next(&now);
+
+        // Each step begins from the state it was entered with
+        now.camera_rotation = Quat::from_rotation_x(1.0);
+        assert!(!active.update(&now));
+        assert_eq!(active.step, 2);
+        assert_eq!(active.start.as_ref().unwrap().camera_rotation, now.camera_rotation);
+
+        active.step = TUTORIAL_STEPS.len() - 1;
+        active.start = Some(now.clone());
+        now.save_serial += 1;
+        assert!(active.update(&now));
+    }
+
+    #[test]
+    fn test_prompt_shows_until_completed_or_dismissed() {
+        let mut tutorial = Tutorial::default();
+        assert!(tutorial.progress.show_prompt());
+        tutorial.start();
+        tutorial.exit();
+        assert!(tutorial.active.is_none());
+        assert!(!tutorial.progress.show_prompt());
+        assert!(!TutorialProgress { completed: true, prompt_dismissed: false }.show_prompt());
+    }
+
+    #[test]
+    fn test_card_stays_clear_of_the_target() {
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1600.0, 900.0));
+        let left_panel = egui::Rect::from_min_size(egui::pos2(0.0, 40.0), egui::vec2(300.0, 800.0));
+        let (pivot, position) = card_anchor(screen, Some(left_panel));
+        assert_eq!(pivot, egui::Align2::LEFT_CENTER);
+        assert!(position.x > left_panel.right());
+
+        let right_panel = egui::Rect::from_min_size(egui::pos2(1300.0, 40.0), egui::vec2(300.0, 800.0));
+        let (pivot, position) = card_anchor(screen, Some(right_panel));
+        assert_eq!(pivot, egui::Align2::RIGHT_CENTER);
+        assert!(position.x < right_panel.left());
+
+        assert_eq!(card_anchor(screen, None), (egui::Align2::CENTER_CENTER, screen.center()));
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use bevy_egui::{egui, EguiContext};
+use bevy::ecs::system::SystemParam;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
 use egui_dock::{DockArea, Style};
 
 use crate::ui::dock::*;
@@ -29,6 +30,13 @@ pub struct GenomeEditorState {
     pub copy_into_dialog_open: bool,
     pub copy_into_source: usize,
     pub color_picker_state: Option<(usize, egui::ecolor::Hsva)>,
+    /// Mode being saved as a template and the name typed for it
+    pub template_name_prompt: Option<(usize, String)>,
+    pub mode_palette: crate::genome::ColorPalette,
+    /// Modes selected together with Ctrl/Shift+click (empty or one = no multi-selection)
+    pub selected_modes: std::collections::BTreeSet<usize>,
+    /// Rows of the Batch Edit panel
+    pub batch_edits: Vec<crate::genome::BatchFieldEdit>,
     // UI state for quaternion balls
     pub qball_snapping: bool,
     pub qball1_locked_axis: i32,
@@ -41,6 +49,25 @@ pub struct GenomeEditorState {
     pub time_value: f32,
     pub max_preview_duration: f32,
     pub time_slider_dragging: bool,
+    // Genome graph node thumbnails
+    pub mode_thumbnails: crate::ui::genome_editor::ModeThumbnails,
+    // Open Genome browser window
+    pub genome_browser_open: bool,
+    // Genome Phases panel
+    pub selected_phase: Option<usize>,
+    pub phase_override_mode: usize,
+    pub phase_override_panel: crate::ui::windows::genome_phases::OverridePanel,
+    // Contact Adhesion panel: (mode, partner) of the entry being edited
+    pub selected_contact_rule: Option<(usize, usize)>,
+    // Population Forecast panel
+    pub forecast_horizon: f32,
+    pub forecast_log_scale: bool,
+    pub forecast_show_measured: bool,
+    /// Forecast and the genome and horizon it was computed for
+    pub forecast: Option<(crate::genome::GenomeData, f32, crate::genome::PopulationForecast)>,
+    // Material Library panel: name for a new preset, and the preset being renamed with its new name
+    pub material_name: String,
+    pub material_rename: Option<(String, String)>,
 }
 
 impl Default for GenomeEditorState {
@@ -51,6 +78,10 @@ impl Default for GenomeEditorState {
             copy_into_dialog_open: false,
             copy_into_source: 0,
             color_picker_state: None,
+            template_name_prompt: None,
+            mode_palette: crate::genome::ColorPalette::default(),
+            selected_modes: Default::default(),
+            batch_edits: crate::genome::batch_edit::default_batch_edits(),
             qball_snapping: true,
             qball1_locked_axis: -1,
             qball1_initial_distance: 0.0,
@@ -60,22 +91,202 @@ impl Default for GenomeEditorState {
             time_value: 0.0,
             max_preview_duration: 60.0,
             time_slider_dragging: false,
+            mode_thumbnails: Default::default(),
+            genome_browser_open: false,
+            selected_phase: None,
+            phase_override_mode: 0,
+            phase_override_panel: Default::default(),
+            selected_contact_rule: None,
+            forecast_horizon: 60.0,
+            forecast_log_scale: true,
+            forecast_show_measured: true,
+            forecast: None,
+            material_name: String::new(),
+            material_rename: None,
         }
     }
 }
 
+/// Resources used by individual dock panels and the menu bar
+///
+/// Bundled so ui_system stays within Bevy's system parameter limit.
+#[derive(SystemParam)]
+pub struct PanelResources<'w> {
+    physics: PhysicsResources<'w>,
+    gpu_pairs: ResMut<'w, crate::simulation::GpuPairDetection>,
+    scenes: SceneResources<'w>,
+    cell_inspector: Res<'w, crate::ui::windows::cell_inspector::CellInspectorState>,
+    camera: CameraResources<'w>,
+    notifications: ResMut<'w, crate::ui::Notifications>,
+    analysis: AnalysisResources<'w>,
+    tools: ToolResources<'w>,
+    parameter_sweep: ResMut<'w, crate::simulation::parameter_sweep::ParameterSweep>,
+    event_timeline: ResMut<'w, crate::simulation::EventTimeline>,
+    capacity_growth: ResMut<'w, crate::simulation::capacity::CapacityGrowth>,
+    rendering: RenderingResources<'w>,
+    diagnostics: DiagnosticsResources<'w>,
+    audio: AudioResources<'w>,
+    detached_panels: ResMut<'w, crate::ui::DetachedPanels>,
+    genome_edits: GenomeEditResources<'w>,
+}
+
+/// Effective physics config and the override layers it is resolved from (Physics Settings)
+#[derive(SystemParam)]
+pub struct PhysicsResources<'w> {
+    config: ResMut<'w, crate::simulation::PhysicsConfig>,
+    layers: ResMut<'w, crate::simulation::PhysicsLayers>,
+}
+
+/// Measurement tool and the contact graph export (Measurements panel)
+#[derive(SystemParam)]
+pub struct AnalysisResources<'w> {
+    measurements: ResMut<'w, crate::input::Measurements>,
+    contact_graph: ResMut<'w, crate::simulation::ContactGraphExport>,
+}
+
+/// Camera framing, the input bindings (Camera menu, Genome Graph) and the presentation
+/// camera path (Camera Path panel)
+#[derive(SystemParam)]
+pub struct CameraResources<'w> {
+    framing: ResMut<'w, crate::ui::CameraFraming>,
+    bindings: ResMut<'w, crate::input::InputBindings>,
+    path: ResMut<'w, crate::ui::CameraPathPlayer>,
+}
+
+/// Mode templates, the edit history, the node graph (Modes panel and Genome Graph),
+/// genome edit staging for long CPU runs (Tools menu), measured mode populations
+/// (Population Forecast), mode inserts and removals and the material presets (Material Library)
+#[derive(SystemParam)]
+pub struct GenomeEditResources<'w> {
+    templates: ResMut<'w, crate::genome::ModeTemplates>,
+    history: ResMut<'w, crate::genome::GenomeHistory>,
+    node_graph: ResMut<'w, crate::genome::GenomeNodeGraph>,
+    staging: ResMut<'w, crate::simulation::GenomeStaging>,
+    measured_populations: Res<'w, crate::simulation::population_stats::ModePopulationHistory>,
+    mode_edits: ResMut<'w, crate::simulation::ModeEdits>,
+    materials: ResMut<'w, crate::genome::MaterialLibrary>,
+    growth_guardrail: ResMut<'w, crate::ui::windows::growth_guardrail::GrowthGuardrailSettings>,
+}
+
+/// Interaction tools and their options (Tools menu) and the cell groups bulk actions target
+#[derive(SystemParam)]
+pub struct ToolResources<'w> {
+    selected_tool: ResMut<'w, crate::input::SelectedTool>,
+    pin_requests: ResMut<'w, crate::simulation::pinning::PinRequests>,
+    user_data_requests: ResMut<'w, crate::simulation::user_data::UserDataRequests>,
+    drag_state: ResMut<'w, crate::input::DragState>,
+    surgery: ResMut<'w, crate::input::SurgeryToolState>,
+    stamp: ResMut<'w, crate::input::StampToolState>,
+    anchor_edit: ResMut<'w, crate::input::AnchorEditState>,
+    cell_groups: ResMut<'w, crate::simulation::CellGroups>,
+    picking: ResMut<'w, crate::input::Picking>,
+}
+
+/// Scenario presets, experiment sessions, the watchdog with the derived metrics it can
+/// watch, the Settle action and the scene file inspector (Scene Manager and Session Browser)
+#[derive(SystemParam)]
+pub struct SceneResources<'w> {
+    presets: Res<'w, crate::simulation::scenario_presets::ScenarioPresets>,
+    loaded: Res<'w, crate::simulation::scenario_presets::LoadedScenario>,
+    session: ResMut<'w, crate::simulation::ExperimentSession>,
+    dormant: Res<'w, crate::simulation::DormantScenes>,
+    watchdog_settings: ResMut<'w, crate::simulation::WatchdogSettings>,
+    watchdog: ResMut<'w, crate::simulation::Watchdog>,
+    derived_metrics: Res<'w, crate::simulation::DerivedMetrics>,
+    settle_settings: ResMut<'w, crate::simulation::SettleSettings>,
+    settle: ResMut<'w, crate::simulation::SettleRequests>,
+    inspector: ResMut<'w, crate::simulation::SceneInspector>,
+}
+
+/// Rendering toggles (Graphics, Debug, Legend and Regions menus), the viewport mode legend,
+/// the cell sphere quality and the lighting
+#[derive(SystemParam)]
+pub struct RenderingResources<'w> {
+    config: ResMut<'w, crate::rendering::RenderingConfig>,
+    adhesion_lines: ResMut<'w, crate::rendering::AdhesionLineSettings>,
+    mode_legend: ResMut<'w, crate::rendering::ModeLegend>,
+    mode_visibility: ResMut<'w, crate::rendering::ModeVisibility>,
+    fog: ResMut<'w, crate::rendering::VolumetricFogSettings>,
+    capabilities: ResMut<'w, crate::rendering::RenderCapabilities>,
+    sphere_quality: ResMut<'w, crate::rendering::SphereQuality>,
+    sphere_mesh: Res<'w, crate::rendering::CellSphereMesh>,
+    lighting: ResMut<'w, crate::ui::LightingConfig>,
+    force_heatmap: ResMut<'w, crate::simulation::ForceHeatmap>,
+    regions: ResMut<'w, crate::rendering::RegionVisibility>,
+    division_lookahead: ResMut<'w, crate::simulation::DivisionLookaheadPreview>,
+}
+
+/// Simulation diagnostics and analyses shown in the Performance Monitor (with the derived
+/// metric definitions), Division Debug, Breakpoints and Cell Inspector windows, and the
+/// soak test harness (Debug menu)
+#[derive(SystemParam)]
+pub struct DiagnosticsResources<'w> {
+    memory: ResMut<'w, crate::simulation::SimulationMemory>,
+    fingerprint: ResMut<'w, crate::simulation::SimulationFingerprint>,
+    adhesion_quality: Res<'w, crate::simulation::AdhesionQualityStats>,
+    problem_bonds: ResMut<'w, crate::simulation::ProblemBonds>,
+    division: Res<'w, crate::simulation::DivisionStatistics>,
+    mass_audit: ResMut<'w, crate::simulation::MassAudit>,
+    population: ResMut<'w, crate::simulation::PopulationStats>,
+    population_settings: ResMut<'w, crate::simulation::PopulationStatsSettings>,
+    division_debug: ResMut<'w, crate::simulation::DivisionDebug>,
+    breakpoints: ResMut<'w, crate::simulation::Breakpoints>,
+    sharing: SharingResources<'w>,
+    colony_surface: ResMut<'w, crate::simulation::ColonySurface>,
+    history_settings: ResMut<'w, crate::simulation::HistorySettings>,
+    statistics: Res<'w, crate::simulation::StatisticsHistory>,
+    metric_settings: ResMut<'w, crate::simulation::DerivedMetricSettings>,
+    soak: ResMut<'w, crate::simulation::soak::SoakHarness>,
+}
+
+/// Servers that share the main simulation with other programs and machines (Performance Monitor)
+#[derive(SystemParam)]
+pub struct SharingResources<'w> {
+    live_stats_settings: ResMut<'w, crate::simulation::LiveStatsSettings>,
+    live_stats: Res<'w, crate::simulation::LiveStats>,
+    spectator_settings: ResMut<'w, crate::simulation::SpectatorSettings>,
+    spectator_host: Res<'w, crate::simulation::SpectatorHost>,
+}
+
+/// Panel opacities, HUD mode and the panel rects it routes the pointer by
+#[derive(SystemParam)]
+pub struct HudResources<'w> {
+    settings: ResMut<'w, crate::ui::HudSettings>,
+    state: ResMut<'w, crate::ui::HudState>,
+}
+
+/// Screenshot requests and the annotation overlay (Capture menu)
+#[derive(SystemParam)]
+pub struct CaptureResources<'w> {
+    settings: ResMut<'w, crate::simulation::CaptureSettings>,
+    captures: ResMut<'w, crate::simulation::Captures>,
+}
+
+/// Sound volumes (Audio menu) and the voice count overlay
+#[derive(SystemParam)]
+pub struct AudioResources<'w> {
+    settings: ResMut<'w, crate::audio::AudioSettings>,
+    stats: Res<'w, crate::audio::AudioStats>,
+}
+
 /// Main UI system - renders all UI panels using egui_dock
 pub fn ui_system(
-    mut contexts: Query<&mut EguiContext>,
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
     mut dock_resource: ResMut<DockResource>,
     mut viewport_rect: ResMut<ViewportRect>,
     mut current_genome: ResMut<CurrentGenome>,
     mut genome_editor_state: ResMut<GenomeEditorState>,
     mut global_ui_state: ResMut<GlobalUiState>,
-    mut ui_capture: ResMut<crate::ui::camera::UiWantCapture>,
+    mut arbitration: ResMut<crate::input::InputArbitration>,
     mut last_scale: Local<LastAppliedScale>,
     sim_state: Res<crate::simulation::SimulationState>,
     mut scene_mode_request: ResMut<crate::ui::windows::scene_manager::SceneModeRequest>,
+    mut panels: PanelResources,
+    mut cameras: Query<&mut crate::ui::MainCamera>,
+    camera_config: Res<crate::ui::CameraConfig>,
+    mut hud: HudResources,
+    mut capture: CaptureResources,
+    mut tutorial: ResMut<crate::ui::Tutorial>,
 ) {
     for mut egui_context in contexts.iter_mut() {
         let ctx = egui_context.get_mut();
@@ -121,6 +332,9 @@ pub fn ui_system(
 
         // Clear viewport rect at the start of each frame
         viewport_rect.rect = None;
+        hud.state.begin_frame();
+        let modifiers = crate::ui::hud::egui_modifiers(ctx);
+        let panels_interactive = hud.settings.panels_interactive(modifiers);
 
         // Show menu bar at the top
         #[allow(deprecated)]
@@ -134,11 +348,399 @@ pub fn ui_system(
                 let config = MenuConfig::new()
                     .close_behavior(PopupCloseBehavior::IgnoreClicks);
                 
-                MenuButton::new("Windows")
+                let windows_label = if hud.settings.enabled { "Windows (HUD)" } else { "Windows" };
+                MenuButton::new(windows_label)
                     .config(config)
                     .ui(ui, |ui| {
-                        show_windows_menu(ui, &mut dock_resource, &mut global_ui_state);
+                        show_windows_menu(ui, &mut dock_resource, &mut global_ui_state, &mut hud.settings);
+                        ui.separator();
+                        if ui.button("Tutorial")
+                            .on_hover_text("Guided tour of building a first genome")
+                            .clicked()
+                        {
+                            tutorial.start();
+                            ui.close();
+                        }
+                    });
+
+                ui.menu_button("Tools", |ui| {
+                    use crate::input::{stamp_tool::StampDepth, Tool};
+                    let tool = &mut panels.tools.selected_tool.tool;
+                    if ui.selectable_label(*tool == Tool::Select, "Select / Drag").clicked() {
+                        *tool = Tool::Select;
+                        ui.close();
+                    }
+                    ui.menu_button("Drag Options", |ui| {
+                        let soft = &mut panels.tools.drag_state.soft;
+                        ui.checkbox(&mut soft.enabled, "Soft drag")
+                            .on_hover_text("Pull the cells around the grabbed one along, weighted by a smooth falloff");
+                        ui.add_enabled_ui(soft.enabled, |ui| {
+                            ui.checkbox(&mut soft.follow_adhesions, "Follow adhesions")
+                                .on_hover_text("Only pull cells bonded to the grabbed one, weighted by bond hops instead of distance");
+                            if soft.follow_adhesions {
+                                ui.add(egui::Slider::new(&mut soft.hop_limit, 1..=20).text("Hops"));
+                            } else {
+                                ui.add(egui::Slider::new(&mut soft.radius, 0.5..=30.0).text("Radius"));
+                            }
+                        });
+                        ui.add(egui::Slider::new(&mut soft.cohesion, 0.0..=1.0).text("Cohesion"))
+                            .on_hover_text("Share of the pull spread over the grabbed cell's whole organism, so it moves as one body");
+                    });
+                    ui.menu_button("Picking", |ui| {
+                        ui.add(egui::Slider::new(&mut panels.tools.picking.click_through_opacity, 0.0..=1.0).text("Click-through opacity"))
+                            .on_hover_text("Clicks pass through cells whose mode is more transparent than this and pick the cell behind");
+                        ui.label(egui::RichText::new("Alt+click again at the same spot to pick the next cell behind").small().weak());
+                    });
+                    if ui.selectable_label(*tool == Tool::SampleGenome, "Sample Genome")
+                        .on_hover_text("Click a cell to open its genome and mode in the editor (Esc to cancel)")
+                        .clicked()
+                    {
+                        *tool = Tool::SampleGenome;
+                        ui.close();
+                    }
+                    if ui.selectable_label(*tool == Tool::Pin, "Pin / Unpin")
+                        .on_hover_text("Click a cell to pin it in place or release it (Esc to cancel)")
+                        .clicked()
+                    {
+                        *tool = Tool::Pin;
+                        ui.close();
+                    }
+                    if ui.selectable_label(*tool == Tool::Cut, "Cut Organism")
+                        .on_hover_text("Drag a stroke across an organism to cut the bonds it crosses, or click a bond to split the organism there (Esc to cancel)")
+                        .clicked()
+                    {
+                        *tool = Tool::Cut;
+                        ui.close();
+                    }
+                    if ui.selectable_label(*tool == Tool::Graft, "Graft Organisms")
+                        .on_hover_text("Click a cell, then a cell of another organism, to bond them (Esc to cancel)")
+                        .clicked()
+                    {
+                        *tool = Tool::Graft;
+                        ui.close();
+                    }
+                    ui.menu_button("Graft Options", |ui| {
+                        let surgery = &mut panels.tools.surgery;
+                        ui.checkbox(&mut surgery.graft_snap, "Snap into contact")
+                            .on_hover_text("Move the smaller organism so the two cells touch before bonding");
+                        let mut use_first_mode = surgery.graft_mode.is_none();
+                        if ui.checkbox(&mut use_first_mode, "Bond with first cell's mode").changed() {
+                            surgery.graft_mode = if use_first_mode { None } else { Some(0) };
+                        }
+                        if let Some(mode) = surgery.graft_mode.as_mut() {
+                            ui.add(egui::DragValue::new(mode).range(0..=119).prefix("Bond mode: "))
+                                .on_hover_text("Mode whose adhesion settings the new bond uses");
+                        }
+                    });
+                    if ui.selectable_label(*tool == Tool::Anchor, "Edit Bond Anchors")
+                        .on_hover_text("Click an adhesion line to select its bond, then drag the handles on its cells. \
+                            Hold Shift to snap to the bond axis, Ctrl to the cell's axes, Alt to the split-plane normal (Esc to cancel)")
+                        .clicked()
+                    {
+                        *tool = Tool::Anchor;
+                        ui.close();
+                    }
+                    let anchor_edit = &mut panels.tools.anchor_edit;
+                    if ui.add_enabled(anchor_edit.bond.is_some(), egui::Button::new("Auto-align Selected Bond"))
+                        .on_hover_text("Point both anchors of the selected bond straight along its present axis")
+                        .clicked()
+                    {
+                        anchor_edit.align_requested = true;
+                        ui.close();
+                    }
+                    ui.menu_button("Tissue Stamps", |ui| {
+                        let stamp = &mut panels.tools.stamp;
+                        if ui.button("Export Organism...")
+                            .on_hover_text("Save the organism of the cell in the Cell Inspector as a tissue file")
+                            .clicked()
+                        {
+                            stamp.export_requested = true;
+                            ui.close();
+                        }
+                        if ui.button("Import Tissue Stamp...")
+                            .on_hover_text("Load a tissue file and place copies of it with the stamp tool")
+                            .clicked()
+                        {
+                            stamp.import_requested = true;
+                            ui.close();
+                        }
+                        let name = stamp.tissue.as_ref().map(|tissue| tissue.name.clone());
+                        if let Some(name) = name {
+                            if ui.selectable_label(*tool == Tool::Stamp, format!("Stamp '{}'", name))
+                                .on_hover_text("Click to place the tissue, scroll to turn it, Shift+scroll to spin it (Esc to cancel)")
+                                .clicked()
+                            {
+                                *tool = Tool::Stamp;
+                                ui.close();
+                            }
+                        }
+                        ui.separator();
+                        let mut surface = stamp.depth == StampDepth::Surface;
+                        if ui.checkbox(&mut surface, "Place at world sphere surface")
+                            .on_hover_text("Put the stamp just inside the world sphere under the cursor, instead of at a fixed distance from the camera")
+                            .changed()
+                        {
+                            stamp.depth = if surface { StampDepth::Surface } else { StampDepth::Distance(40.0) };
+                        }
+                        if let StampDepth::Distance(distance) = &mut stamp.depth {
+                            ui.add(egui::DragValue::new(distance).speed(0.5).range(1.0..=1000.0).prefix("Depth: "));
+                        }
+                    });
+                    ui.menu_button("Genome Edit Protection", |ui| {
+                        let staging = &mut panels.genome_edits.staging;
+                        ui.checkbox(&mut staging.enabled, "Stage edits in long CPU runs")
+                            .on_hover_text("Hold genome edits back from a CPU run past the set time until they are applied from the banner");
+                        ui.add_enabled(
+                            staging.enabled,
+                            egui::DragValue::new(&mut staging.protect_after).speed(10.0).range(0.0..=86_400.0).prefix("After: ").suffix(" s"),
+                        ).on_hover_text("Simulated time after which edits are staged");
+                    });
+                    ui.separator();
+                    if ui.button("Unpin All").clicked() {
+                        panels.tools.pin_requests.unpin_all();
+                        ui.close();
+                    }
+                });
+
+                ui.menu_button("Camera", |ui| {
+                    if ui.button("Frame All (Home)").clicked() {
+                        panels.camera.framing.frame_all_requested = true;
+                        ui.close();
+                    }
+                    ui.checkbox(&mut panels.camera.framing.auto_frame, "Auto-frame")
+                        .on_hover_text("Re-frame when cells leave the view (paused briefly after manual camera input)");
+                    ui.add_enabled_ui(panels.camera.framing.auto_frame, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Trigger when out of view:");
+                            ui.add(egui::DragValue::new(&mut panels.camera.framing.out_of_view_fraction)
+                                .speed(0.01)
+                                .range(0.05..=0.9)
+                                .custom_formatter(|n, _| format!("{:.0}%", n * 100.0))
+                                .custom_parser(|s| s.trim_end_matches('%').trim().parse::<f64>().ok().map(|n| n / 100.0)));
+                        });
+                    });
+                    ui.separator();
+                    ui.menu_button("Controls", |ui| {
+                        crate::ui::widgets::controls_menu(ui, &mut panels.camera.bindings);
+                    });
+                });
+
+                ui.menu_button("Graphics", |ui| {
+                    let capabilities = &mut panels.rendering.capabilities;
+                    // Unsupported features stay visible but disabled, with the reason on hover
+                    ui.add_enabled_ui(capabilities.bloom.is_available(), |ui| {
+                        ui.checkbox(&mut panels.rendering.config.bloom_enabled, "Bloom")
+                    }).inner.on_disabled_hover_text(capabilities.bloom.missing_reason().unwrap_or_default());
+                    ui.add_enabled_ui(capabilities.volumetric_fog.is_available(), |ui| {
+                        ui.checkbox(&mut panels.rendering.fog.enabled, "Volumetric Fog")
+                    }).inner.on_disabled_hover_text(capabilities.volumetric_fog.missing_reason().unwrap_or_default());
+                    let fog = &mut panels.rendering.fog;
+                    ui.add_enabled_ui(capabilities.volumetric_fog.is_available() && fog.enabled, |ui| {
+                        ui.indent("colony_fog", |ui| {
+                            ui.checkbox(&mut fog.colony_density, "Colony Density")
+                                .on_hover_text("Thicken the fog where cells are packed, regenerated from the cells periodically");
+                            ui.add_enabled_ui(fog.colony_density, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("Intensity:");
+                                    ui.add(egui::DragValue::new(&mut fog.colony_intensity).speed(0.01).range(0.0..=3.0));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Every:");
+                                    ui.add(egui::DragValue::new(&mut fog.colony_interval).speed(0.01).range(0.1..=5.0).suffix(" s"));
+                                });
+                            });
+                        });
+                    });
+                    ui.menu_button("Sphere Quality", |ui| {
+                        let max_level = capabilities.max_sphere_level;
+                        let quality = &mut panels.rendering.sphere_quality;
+                        for option in crate::rendering::SphereQuality::ALL {
+                            let supported = option.level().is_none_or(|level| level <= max_level);
+                            ui.add_enabled_ui(supported, |ui| {
+                                ui.radio_value(&mut **quality, option, option.name())
+                            }).inner.on_disabled_hover_text("Not available on this graphics adapter");
+                        }
+                        let sphere = &panels.rendering.sphere_mesh;
+                        let status = match sphere.pending_level() {
+                            Some(level) => format!("Switching to level {}...", level),
+                            None => format!(
+                                "Level {}: {} triangles per cell",
+                                sphere.level,
+                                crate::rendering::sphere_quality::sphere_triangle_count(sphere.level)
+                            ),
+                        };
+                        ui.label(egui::RichText::new(status).small().weak())
+                            .on_hover_text("Auto uses High below 2,000 cells, Medium up to 20,000 and Low above");
+                    });
+                    ui.checkbox(&mut panels.rendering.config.interpolate_ticks, "Smooth Motion")
+                        .on_hover_text("Blend cells between physics ticks so slow motion and low tick rates don't stutter (display only)");
+                    ui.checkbox(&mut panels.rendering.config.show_compass, "Orientation Gizmo")
+                        .on_hover_text("XYZ axes in the viewport corner; click an axis to view the scene from that side");
+                    ui.checkbox(&mut panels.rendering.config.show_scale_bar, "Scale Bar")
+                        .on_hover_text("Round-number length reference at the bottom of the viewport");
+                    ui.separator();
+                    if ui.add_enabled(capabilities.any_missing(), egui::Button::new("Disabled Features..."))
+                        .on_hover_text("Show which rendering features were turned off for this graphics adapter")
+                        .clicked()
+                    {
+                        capabilities.report_dismissed = false;
+                        ui.close();
+                    }
+                });
+
+                ui.menu_button("Debug", |ui| {
+                    ui.menu_button("Adhesion Lines", |ui| {
+                        let lines = &mut panels.rendering.adhesion_lines;
+                        for mode in crate::rendering::AdhesionLineMode::ALL {
+                            ui.radio_value(&mut lines.mode, mode, mode.name());
+                        }
+                        ui.add_enabled_ui(lines.mode == crate::rendering::AdhesionLineMode::Anchored, |ui| {
+                            ui.checkbox(&mut lines.color_by_strain, "Color by strain")
+                                .on_hover_text("Color each bond by its extension relative to rest length instead of by zone");
+                            ui.add_enabled_ui(lines.color_by_strain, |ui| {
+                                let gradient = &mut lines.strain_gradient;
+                                for (label, color) in [
+                                    ("Compressed", &mut gradient.compressed),
+                                    ("At rest", &mut gradient.rest),
+                                    ("Stretched", &mut gradient.stretched),
+                                ] {
+                                    ui.horizontal(|ui| {
+                                        let srgba = color.to_srgba();
+                                        let mut rgb = [srgba.red, srgba.green, srgba.blue];
+                                        if ui.color_edit_button_rgb(&mut rgb).changed() {
+                                            *color = Color::srgb(rgb[0], rgb[1], rgb[2]);
+                                        }
+                                        ui.label(label);
+                                    });
+                                }
+                                ui.horizontal(|ui| {
+                                    ui.label("Full color at:");
+                                    ui.add(egui::DragValue::new(&mut gradient.full_scale)
+                                        .speed(0.01)
+                                        .range(0.01..=2.0)
+                                        .custom_formatter(|n, _| format!("{:.0}%", n * 100.0))
+                                        .custom_parser(|s| s.trim_end_matches('%').trim().parse::<f64>().ok().map(|n| n / 100.0)));
+                                });
+                            });
+                        });
+                    });
+                    let config = &mut panels.rendering.config;
+                    if ui.checkbox(&mut config.show_split_plane_gizmos, "Split Planes")
+                        .on_hover_text("Show each cell's split plane. The inspected cell's bonds are colored by where they go at division: blue to child A, green to child B, yellow to both, red dropped")
+                        .changed()
+                    {
+                        config.user_has_changed_gizmos = true;
+                    }
+                    ui.menu_button("Division Lookahead", |ui| {
+                        let preview = &mut panels.rendering.division_lookahead;
+                        ui.checkbox(&mut preview.enabled, "Show ghosts")
+                            .on_hover_text("Ghost cells of the structure the selected mode grows into by pure geometric division, drawn at the inspected cell (or the origin), colored by mode");
+                        ui.add_enabled_ui(preview.enabled, |ui| {
+                            ui.add(egui::Slider::new(&mut preview.generations, 2..=6).text("Generations"));
+                            ui.add(egui::Slider::new(&mut preview.scale, 0.1..=1.0).text("Scale"));
+                        });
+                        let lookahead = &preview.lookahead;
+                        if preview.enabled && lookahead.truncated {
+                            ui.colored_label(egui::Color32::from_rgb(220, 160, 60), format!(
+                                "Stopped after {} generations: the next passes {} ghosts",
+                                lookahead.generations,
+                                crate::simulation::division_lookahead::MAX_GHOSTS
+                            ));
+                        }
+                    });
+                    ui.checkbox(&mut panels.rendering.config.show_twist_gizmos, "Adhesion Twist")
+                        .on_hover_text("Show each bonded cell's twist reference direction and the measured twist angle at the bond midpoint");
+                    ui.checkbox(&mut panels.rendering.config.show_pressure_overlay, "Contact Pressure")
+                        .on_hover_text("Outline cells by contact pressure: blue is free, red is at the mode's contact inhibition limit (or 2.0 without one)");
+                    ui.checkbox(&mut panels.rendering.config.show_orientation_drift_overlay, "Orientation Drift")
+                        .on_hover_text("Outline cells by the angle between their genome orientation and the one recomputed for their lineage: blue is exact, red is 0.1° or more");
+                    ui.menu_button("User Data", |ui| {
+                        let config = &mut panels.rendering.config;
+                        ui.checkbox(&mut config.show_user_data_overlay, "Show overlay")
+                            .on_hover_text("Outline cells by a user data channel: blue at the minimum, red at the maximum");
+                        ui.add_enabled_ui(config.show_user_data_overlay, |ui| {
+                            let overlay = &mut config.user_data_overlay;
+                            for channel in 0..crate::simulation::user_data::USER_DATA_CHANNELS {
+                                ui.radio_value(&mut overlay.channel, channel, format!("Channel {}", channel + 1));
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label("Min:");
+                                ui.add(egui::DragValue::new(&mut overlay.min).speed(0.01));
+                                ui.label("Max:");
+                                ui.add(egui::DragValue::new(&mut overlay.max).speed(0.01));
+                            });
+                        });
+                    });
+                    ui.menu_button("Force Heatmap", |ui| {
+                        let heatmap = &mut panels.rendering.force_heatmap;
+                        for channel in crate::simulation::HeatChannel::ALL {
+                            ui.checkbox(&mut heatmap.show[channel.index()], channel.name());
+                        }
+                        ui.label(egui::RichText::new("Averages accumulate while a channel is shown").small().weak());
+                        ui.horizontal(|ui| {
+                            ui.label("Time constant:");
+                            ui.add(egui::DragValue::new(&mut heatmap.time_constant).speed(0.05).range(0.05..=600.0).suffix(" s"))
+                                .on_hover_text("Simulated seconds for the average to follow ~63% of a change in force");
+                        });
+                        ui.checkbox(&mut heatmap.frozen, "Freeze")
+                            .on_hover_text("Keep the current averages while the simulation runs");
+                        if ui.button("Reset").clicked() {
+                            heatmap.reset_requested = true;
+                        }
+                    });
+                    ui.checkbox(&mut panels.rendering.config.show_orbit_trails, "Orbit Trails")
+                        .on_hover_text("Draw fading trails behind cells without adhesions");
+                    // Developer tool, listed only while Shift is held
+                    if ui.input(|i| i.modifiers.shift) {
+                        ui.separator();
+                        if ui.button("Soak Test...").clicked() {
+                            panels.diagnostics.soak.window_open = true;
+                            ui.close();
+                        }
+                    }
+                });
+
+                ui.menu_button("Legend", |ui| {
+                    let legend = &mut panels.rendering.mode_legend;
+                    ui.checkbox(&mut legend.show, "Show mode legend");
+                    ui.add_enabled_ui(legend.show, |ui| {
+                        for corner in crate::rendering::LegendCorner::ALL {
+                            ui.radio_value(&mut legend.corner, corner, corner.name());
+                        }
+                    });
+                    ui.separator();
+                    if ui.add_enabled(panels.rendering.mode_visibility.is_filtering(), egui::Button::new("Show All Modes"))
+                        .on_hover_text("Clear the legend's isolate and hide filters")
+                        .clicked()
+                    {
+                        panels.rendering.mode_visibility.clear();
+                        ui.close();
+                    }
+                });
+
+                ui.menu_button("Regions", |ui| {
+                    let camera_rotation = cameras.iter().next().map(|camera| camera.rotation).unwrap_or_default();
+                    crate::ui::region_menu::render_region_menu(ui, &mut panels.rendering.regions, camera_rotation);
+                });
+
+                ui.menu_button("Capture", |ui| {
+                    crate::ui::capture_overlay::render_capture_menu(ui, &mut capture.settings, &mut capture.captures);
+                });
+
+                ui.menu_button("Audio", |ui| {
+                    let audio = &mut panels.audio.settings;
+                    ui.checkbox(&mut audio.muted, "Mute");
+                    ui.add_enabled_ui(!audio.muted, |ui| {
+                        ui.add(egui::Slider::new(&mut audio.master_volume, 0.0..=1.0).text("Master"));
+                        ui.separator();
+                        for category in crate::audio::SoundCategory::ALL {
+                            ui.add(egui::Slider::new(audio.volume_mut(category), 0.0..=1.0).text(category.name()));
+                        }
                     });
+                    ui.separator();
+                    ui.checkbox(&mut audio.debug, "Audio debug")
+                        .on_hover_text("Show the number of playing sounds in the viewport");
+                });
             });
         });
 
@@ -148,8 +750,8 @@ pub fn ui_system(
             // Reduce separator minimum constraint to allow smaller panels
             style.separator.extra = 75.0;
 
-            // Apply lock settings to hide tab bar height if locked
-            if global_ui_state.lock_tab_bar {
+            // Apply lock settings to hide tab bar height if locked (HUD mode hides it too)
+            if global_ui_state.lock_tab_bar || hud.settings.enabled {
                 style.tab_bar.height = 0.0;
             }
 
@@ -161,13 +763,13 @@ pub fn ui_system(
                 .window_bounds(ctx.content_rect());  // Set window bounds for floating windows
 
             // Apply lock settings for tabs and close buttons
-            if global_ui_state.lock_tabs {
+            if global_ui_state.lock_tabs || hud.settings.enabled {
                 dock_area = dock_area
                     .show_tab_name_on_hover(false)
                     .draggable_tabs(false);  // Disable dragging when tabs are locked
             }
 
-            if global_ui_state.lock_close_buttons {
+            if global_ui_state.lock_close_buttons || hud.settings.enabled {
                 dock_area = dock_area.show_close_buttons(false);
             }
 
@@ -178,24 +780,127 @@ pub fn ui_system(
                 sim_state: &sim_state,
                 scene_mode_request: &mut scene_mode_request,
                 global_ui_state: &global_ui_state,
+                physics_config: &mut panels.physics.config,
+                physics_layers: &mut panels.physics.layers,
+                gpu_pairs: &mut panels.gpu_pairs,
+                scenario_presets: &panels.scenes.presets,
+                loaded_scenario: &panels.scenes.loaded,
+                session: &mut panels.scenes.session,
+                dormant_scenes: &panels.scenes.dormant,
+                watchdog_settings: &mut panels.scenes.watchdog_settings,
+                watchdog: &mut panels.scenes.watchdog,
+                derived_metrics: &panels.scenes.derived_metrics,
+                settle_settings: &mut panels.scenes.settle_settings,
+                settle: &mut panels.scenes.settle,
+                scene_inspector: &mut panels.scenes.inspector,
+                cell_inspector: &panels.cell_inspector,
+                notifications: &mut panels.notifications,
+                measurements: &mut panels.analysis.measurements,
+                contact_graph: &mut panels.analysis.contact_graph,
+                parameter_sweep: &mut panels.parameter_sweep,
+                event_timeline: &mut panels.event_timeline,
+                capacity_growth: &mut panels.capacity_growth,
+                memory: &mut panels.diagnostics.memory,
+                fingerprint: &mut panels.diagnostics.fingerprint,
+                adhesion_quality: &panels.diagnostics.adhesion_quality,
+                problem_bonds: &mut panels.diagnostics.problem_bonds,
+                division: &panels.diagnostics.division,
+                mass_audit: &mut panels.diagnostics.mass_audit,
+                population: &mut panels.diagnostics.population,
+                population_settings: &mut panels.diagnostics.population_settings,
+                division_debug: &mut panels.diagnostics.division_debug,
+                breakpoints: &mut panels.diagnostics.breakpoints,
+                live_stats_settings: &mut panels.diagnostics.sharing.live_stats_settings,
+                live_stats: &panels.diagnostics.sharing.live_stats,
+                spectator_settings: &mut panels.diagnostics.sharing.spectator_settings,
+                spectator_host: &panels.diagnostics.sharing.spectator_host,
+                history_settings: &mut panels.diagnostics.history_settings,
+                statistics: &panels.diagnostics.statistics,
+                metric_settings: &mut panels.diagnostics.metric_settings,
+                colony_surface: &mut panels.diagnostics.colony_surface,
+                pin_requests: &mut panels.tools.pin_requests,
+                user_data_requests: &mut panels.tools.user_data_requests,
+                anchor_edit: &mut panels.tools.anchor_edit,
+                cell_groups: &mut panels.tools.cell_groups,
+                detached_panels: &mut panels.detached_panels,
+                capabilities: &panels.rendering.capabilities,
+                lighting: panels.rendering.lighting.reborrow(),
+                mode_templates: &mut panels.genome_edits.templates,
+                genome_history: &mut panels.genome_edits.history,
+                node_graph: &mut panels.genome_edits.node_graph,
+                materials: &mut panels.genome_edits.materials,
+                growth_guardrail: &mut panels.genome_edits.growth_guardrail,
+                measured_populations: &panels.genome_edits.measured_populations,
+                mode_edits: &mut panels.genome_edits.mode_edits,
+                input_bindings: &panels.camera.bindings,
+                camera_path: &mut panels.camera.path,
+                hud_settings: &hud.settings,
+                hud_state: &mut hud.state,
+                modifiers,
+                panels_interactive,
             });
         } else {
             // When hidden, set viewport to entire available screen area
             viewport_rect.rect = Some(ctx.content_rect());
         }
 
+        // Clicks on the legend and the orientation gizmo don't reach the viewport
+        let mut legend_rect = None;
+        let mut gizmo_rect = None;
+        if let Some(viewport) = viewport_rect.rect {
+            let mut visibility = panels.rendering.mode_visibility.clone();
+            legend_rect = crate::ui::mode_legend::render_mode_legend(
+                ctx,
+                viewport,
+                &panels.rendering.mode_legend,
+                &mut visibility,
+                &mut current_genome,
+            );
+            // Only flag a change when a filter was toggled (CPU cells re-material on change)
+            panels.rendering.mode_visibility.set_if_neq(visibility);
+
+            crate::ui::audio_overlay::render_audio_overlay(ctx, viewport, &panels.audio.settings, &panels.audio.stats);
+            crate::ui::force_heatmap_legend::render_force_heatmap_legend(ctx, viewport, &panels.rendering.force_heatmap);
+
+            if let Ok(mut cam) = cameras.single_mut() {
+                gizmo_rect = crate::ui::viewport_overlays::render_viewport_overlays(
+                    ctx,
+                    viewport,
+                    &panels.rendering.config,
+                    global_ui_state.ui_scale,
+                    camera_config.fov.to_radians(),
+                    &mut cam,
+                );
+            }
+        }
+
+        crate::ui::capture_overlay::render_capture_preview(ctx, &capture.settings, &capture.captures);
+        crate::ui::notifications::render_toasts(ctx, &mut panels.notifications);
+
         // Update mouse capture state AFTER UI is rendered
         // Egui wants mouse if pointer is over any UI or if any widget is being interacted with
         // BUT exclude the viewport area - camera should work there
         let pointer_pos = ctx.pointer_hover_pos();
         let is_over_viewport = if let (Some(pos), Some(viewport)) = (pointer_pos, viewport_rect.rect) {
             viewport.contains(pos)
+                && !legend_rect.is_some_and(|rect| rect.contains(pos))
+                && !gizmo_rect.is_some_and(|rect| rect.contains(pos))
         } else {
             false
         };
         
-        ui_capture.want_capture_mouse = !is_over_viewport && (ctx.egui_wants_pointer_input() || ctx.is_pointer_over_egui());
-        ui_capture.want_capture_keyboard = ctx.egui_wants_keyboard_input();
+        // Docked panels let the pointer through in HUD mode; popups and floating windows above them don't
+        let over_panel = !is_over_viewport && pointer_pos.is_some_and(|pos| {
+            hud.state.is_over_panel(pos) && ctx.layer_id_at(pos).is_some_and(|layer| layer.order == egui::Order::Background)
+        });
+        let over_other_ui = !is_over_viewport && !over_panel && ctx.is_pointer_over_egui();
+
+        // Mouse and keyboard input is shared by all windows, so a detached panel window captures it too
+        arbitration.set_signals(crate::input::arbitration::CaptureSignals {
+            pointer_over_ui: panels.detached_panels.wants_pointer || hud.settings.pointer_over_ui(over_panel, over_other_ui, modifiers),
+            ui_using_pointer: ctx.egui_is_using_pointer(),
+            ui_wants_keyboard: panels.detached_panels.wants_keyboard || ctx.egui_wants_keyboard_input(),
+        });
     }
 }
 
@@ -207,6 +912,72 @@ struct TabViewer<'a> {
     sim_state: &'a crate::simulation::SimulationState,
     scene_mode_request: &'a mut crate::ui::windows::scene_manager::SceneModeRequest,
     global_ui_state: &'a GlobalUiState,
+    physics_config: &'a mut crate::simulation::PhysicsConfig,
+    physics_layers: &'a mut crate::simulation::PhysicsLayers,
+    gpu_pairs: &'a mut crate::simulation::GpuPairDetection,
+    scenario_presets: &'a crate::simulation::scenario_presets::ScenarioPresets,
+    loaded_scenario: &'a crate::simulation::scenario_presets::LoadedScenario,
+    session: &'a mut crate::simulation::ExperimentSession,
+    dormant_scenes: &'a crate::simulation::DormantScenes,
+    watchdog_settings: &'a mut crate::simulation::WatchdogSettings,
+    watchdog: &'a mut crate::simulation::Watchdog,
+    derived_metrics: &'a crate::simulation::DerivedMetrics,
+    settle_settings: &'a mut crate::simulation::SettleSettings,
+    settle: &'a mut crate::simulation::SettleRequests,
+    scene_inspector: &'a mut crate::simulation::SceneInspector,
+    cell_inspector: &'a crate::ui::windows::cell_inspector::CellInspectorState,
+    notifications: &'a mut crate::ui::Notifications,
+    measurements: &'a mut crate::input::Measurements,
+    contact_graph: &'a mut crate::simulation::ContactGraphExport,
+    parameter_sweep: &'a mut crate::simulation::parameter_sweep::ParameterSweep,
+    event_timeline: &'a mut crate::simulation::EventTimeline,
+    capacity_growth: &'a mut crate::simulation::capacity::CapacityGrowth,
+    memory: &'a mut crate::simulation::SimulationMemory,
+    fingerprint: &'a mut crate::simulation::SimulationFingerprint,
+    adhesion_quality: &'a crate::simulation::AdhesionQualityStats,
+    problem_bonds: &'a mut crate::simulation::ProblemBonds,
+    division: &'a crate::simulation::DivisionStatistics,
+    mass_audit: &'a mut crate::simulation::MassAudit,
+    population: &'a mut crate::simulation::PopulationStats,
+    population_settings: &'a mut crate::simulation::PopulationStatsSettings,
+    division_debug: &'a mut crate::simulation::DivisionDebug,
+    breakpoints: &'a mut crate::simulation::Breakpoints,
+    live_stats_settings: &'a mut crate::simulation::LiveStatsSettings,
+    live_stats: &'a crate::simulation::LiveStats,
+    spectator_settings: &'a mut crate::simulation::SpectatorSettings,
+    spectator_host: &'a crate::simulation::SpectatorHost,
+    history_settings: &'a mut crate::simulation::HistorySettings,
+    statistics: &'a crate::simulation::StatisticsHistory,
+    metric_settings: &'a mut crate::simulation::DerivedMetricSettings,
+    colony_surface: &'a mut crate::simulation::ColonySurface,
+    pin_requests: &'a mut crate::simulation::pinning::PinRequests,
+    user_data_requests: &'a mut crate::simulation::user_data::UserDataRequests,
+    anchor_edit: &'a mut crate::input::AnchorEditState,
+    cell_groups: &'a mut crate::simulation::CellGroups,
+    detached_panels: &'a mut crate::ui::DetachedPanels,
+    capabilities: &'a crate::rendering::RenderCapabilities,
+    /// Only flagged as changed when the panel edits it (the cycle re-lights the scene)
+    lighting: Mut<'a, crate::ui::LightingConfig>,
+    mode_templates: &'a mut crate::genome::ModeTemplates,
+    genome_history: &'a mut crate::genome::GenomeHistory,
+    node_graph: &'a mut crate::genome::GenomeNodeGraph,
+    materials: &'a mut crate::genome::MaterialLibrary,
+    growth_guardrail: &'a mut crate::ui::windows::growth_guardrail::GrowthGuardrailSettings,
+    measured_populations: &'a crate::simulation::population_stats::ModePopulationHistory,
+    mode_edits: &'a mut crate::simulation::ModeEdits,
+    input_bindings: &'a crate::input::InputBindings,
+    camera_path: &'a mut crate::ui::CameraPathPlayer,
+    hud_settings: &'a crate::ui::HudSettings,
+    hud_state: &'a mut crate::ui::HudState,
+    modifiers: crate::input::bindings::Modifiers,
+    panels_interactive: bool,
+}
+
+impl TabViewer<'_> {
+    /// Whether a panel is see-through, so it paints its own faded background
+    fn translucent(&self, tab: &Panel) -> bool {
+        self.hud_settings.enabled || self.hud_settings.panel_opacity(&tab.to_string()) < 1.0
+    }
 }
 
 impl<'a> egui_dock::TabViewer for TabViewer<'a> {
@@ -217,6 +988,18 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        if *tab != Panel::Viewport {
+            let opacity = self.hud_state.show_panel(ui, &tab.to_string(), self.hud_settings, self.modifiers);
+            ui.multiply_opacity(opacity);
+            if self.translucent(tab) {
+                ui.painter().rect_filled(ui.clip_rect(), 0.0, ui.visuals().panel_fill);
+            }
+            if !self.panels_interactive {
+                // Click-through: widgets ignore the pointer, without the disabled fade on top
+                ui.style_mut().visuals.disabled_alpha = 1.0;
+                ui.disable();
+            }
+        }
         match tab {
             Panel::Viewport => {
                 // Capture the viewport rect for mouse interaction
@@ -231,12 +1014,14 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
             }
             // Genome editor panels - using actual implementations
             Panel::Modes => {
-                crate::ui::genome_editor::render_modes_panel(ui, self.current_genome, self.genome_editor_state);
+                crate::ui::genome_editor::render_modes_panel(ui, self.current_genome, self.genome_editor_state, self.mode_templates, self.mode_edits, self.notifications);
+                crate::ui::windows::render_batch_edit(ui.ctx(), self.current_genome, self.genome_editor_state, self.genome_history, self.node_graph, self.notifications);
             }
             Panel::NameTypeEditor => {
-                crate::ui::genome_editor::render_name_type_editor(ui, self.current_genome);
+                crate::ui::genome_editor::render_name_type_editor(ui, self.current_genome, self.genome_editor_state, self.notifications);
             }
             Panel::AdhesionSettings => {
+                crate::ui::genome_editor::render_adhesion_propagation(ui, self.current_genome, self.genome_history, self.notifications);
                 crate::ui::genome_editor::render_adhesion_settings(ui, self.current_genome);
             }
             Panel::ParentSettings => {
@@ -248,11 +1033,91 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
             Panel::QuaternionBall => {
                 crate::ui::genome_editor::render_quaternion_ball(ui, self.current_genome, self.genome_editor_state);
             }
+            Panel::GenomePhases => {
+                crate::ui::windows::render_genome_phases(ui, self.current_genome, self.genome_editor_state);
+            }
+            Panel::ContactAdhesion => {
+                crate::ui::windows::render_contact_adhesion(ui, self.current_genome, self.genome_editor_state);
+            }
+            Panel::MaterialLibrary => {
+                crate::ui::windows::render_material_library(ui, self.current_genome, self.genome_editor_state, self.materials, self.genome_history, self.node_graph, self.notifications);
+            }
+            Panel::PopulationForecast => {
+                crate::ui::windows::render_population_forecast(ui, self.current_genome, self.genome_editor_state, self.measured_populations, self.growth_guardrail);
+            }
             Panel::TimeSlider => {
-                crate::ui::genome_editor::render_time_slider(ui, self.genome_editor_state, self.sim_state);
+                crate::ui::genome_editor::render_time_slider(ui, self.genome_editor_state, self.sim_state, self.event_timeline, &self.current_genome.genome, self.scene_mode_request);
             }
             Panel::SceneManager => {
-                crate::ui::windows::render_scene_manager(ui, self.sim_state.mode, self.scene_mode_request);
+                crate::ui::windows::render_scene_manager(
+                    ui,
+                    self.sim_state.mode,
+                    self.scene_mode_request,
+                    self.dormant_scenes,
+                    self.scenario_presets,
+                    self.loaded_scenario,
+                    self.capacity_growth,
+                    self.session,
+                    self.watchdog_settings,
+                    self.watchdog,
+                    self.derived_metrics,
+                    self.settle_settings,
+                    self.settle,
+                    self.scene_inspector,
+                    &self.capabilities.gpu_compute,
+                    &mut self.current_genome.genome,
+                );
+            }
+            Panel::SessionBrowser => {
+                crate::ui::windows::render_session_browser(ui, self.session, self.scene_inspector);
+            }
+            Panel::DivisionDebug => {
+                crate::ui::windows::render_division_debug(ui, self.division_debug, self.current_genome);
+            }
+            Panel::Breakpoints => {
+                let inspected_cell = self.cell_inspector.snapshot.as_ref().map(|cell| cell.cell_id);
+                crate::ui::windows::render_breakpoints(ui, self.breakpoints, self.current_genome, inspected_cell);
+            }
+            Panel::CameraPath => {
+                crate::ui::windows::render_camera_path(ui, self.camera_path, self.notifications);
+            }
+            Panel::CellGroups => {
+                let inspected_cell = self.cell_inspector.snapshot.as_ref().map(|cell| cell.cell_id);
+                crate::ui::windows::render_cell_groups(ui, self.cell_groups, self.current_genome, inspected_cell);
+            }
+            Panel::CellInspector => {
+                crate::ui::windows::render_cell_inspector(ui, self.cell_inspector, self.current_genome, self.genome_editor_state, self.sim_state.mode, self.event_timeline, self.pin_requests, self.user_data_requests, self.anchor_edit, self.colony_surface, self.notifications);
+            }
+            Panel::PhysicsSettings => {
+                crate::ui::windows::render_physics_settings(ui, self.physics_config, self.physics_layers);
+            }
+            Panel::PerformanceMonitor => {
+                crate::ui::windows::render_performance_monitor(ui, self.physics_config, self.gpu_pairs, &self.capabilities.gpu_compute, self.memory, self.fingerprint, self.adhesion_quality, self.problem_bonds, self.division, self.mass_audit, self.population, self.population_settings, &self.current_genome.genome, self.live_stats_settings, self.live_stats, self.spectator_settings, self.spectator_host, self.history_settings, self.statistics, self.metric_settings, self.event_timeline, self.notifications);
+            }
+            Panel::Measurements => {
+                crate::ui::windows::render_measurements(ui, self.measurements, self.cell_groups, self.contact_graph, self.sim_state.mode, self.notifications);
+            }
+            Panel::ParameterSweep => {
+                crate::ui::windows::render_parameter_sweep(ui, self.parameter_sweep, self.current_genome, self.genome_history, self.physics_config);
+            }
+            Panel::LightingSettings => {
+                let mut lighting = (*self.lighting).clone();
+                crate::ui::windows::render_lighting_settings(ui, &mut lighting);
+                self.lighting.set_if_neq(lighting);
+            }
+            Panel::Log => {
+                crate::ui::windows::render_log(ui, self.notifications);
+            }
+            Panel::GenomeGraph => {
+                ui.horizontal(|ui| {
+                    if ui.small_button("⇱ Detach")
+                        .on_hover_text("Open in a separate window; close that window to dock the panel again")
+                        .clicked()
+                    {
+                        self.detached_panels.request_detach(tab.clone());
+                    }
+                });
+                crate::ui::genome_editor::render_genome_graph(ui, self.current_genome, self.node_graph, &mut self.genome_editor_state.mode_thumbnails, &mut self.genome_editor_state.selected_modes, self.mode_templates, self.input_bindings);
             }
             // Unused stub panels - show placeholder message
             _ => {
@@ -267,9 +1132,16 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
         }
     }
 
+    fn context_menu(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab, _surface: egui_dock::SurfaceIndex, _node: egui_dock::NodeIndex) {
+        if crate::ui::DetachedPanels::can_detach(tab) && ui.button("Detach to Window").clicked() {
+            self.detached_panels.request_detach(tab.clone());
+            ui.close();
+        }
+    }
+
     fn clear_background(&self, tab: &Self::Tab) -> bool {
-        // Only the Viewport panel should be transparent
-        !matches!(tab, Panel::Viewport)
+        // Only the Viewport panel should be transparent; translucent panels paint their own
+        !matches!(tab, Panel::Viewport) && !self.translucent(tab)
     }
 
     fn is_closeable(&self, tab: &Self::Tab) -> bool {
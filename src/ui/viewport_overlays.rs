@@ -0,0 +1,274 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::ui::camera::{CameraMode, MainCamera};
+
+/// Radius of the orientation gizmo at 100% UI scale (points)
+const GIZMO_RADIUS: f32 = 40.0;
+/// Longest the scale bar may get at 100% UI scale (points)
+const SCALE_BAR_MAX_LENGTH: f32 = 140.0;
+
+/// World axes drawn by the gizmo, with their label and color
+const AXES: [(Vec3, &str, egui::Color32); 3] = [
+    (Vec3::X, "X", egui::Color32::from_rgb(230, 80, 80)),
+    (Vec3::Y, "Y", egui::Color32::from_rgb(100, 200, 90)),
+    (Vec3::Z, "Z", egui::Color32::from_rgb(80, 140, 240)),
+];
+
+/// World-space length covered by one point at `depth` in front of the camera
+///
+/// `view_height` is the height the vertical field of view spans, in points.
+pub fn world_units_per_point(depth: f32, fov_y: f32, view_height: f32) -> f32 {
+    2.0 * depth * (fov_y * 0.5).tan() / view_height
+}
+
+/// Longest round length (1, 2 or 5 times a power of ten) that is at most `max_length`
+pub fn round_scale_length(max_length: f32) -> Option<f32> {
+    if !(max_length.is_finite() && max_length > 0.0) {
+        return None;
+    }
+    let decade = 10.0f32.powf(max_length.log10().floor());
+    // The tolerance keeps exact powers of ten from rounding down a step
+    [5.0, 2.0, 1.0].into_iter()
+        .map(|step| step * decade)
+        .find(|&length| length <= max_length * (1.0 + 1e-5))
+}
+
+/// Scale bar for a camera looking at `depth`: (world length, bar length in points)
+pub fn scale_bar(depth: f32, fov_y: f32, view_height: f32, max_points: f32) -> Option<(f32, f32)> {
+    let units_per_point = world_units_per_point(depth, fov_y, view_height);
+    if !(units_per_point.is_finite() && units_per_point > 0.0) {
+        return None;
+    }
+    let length = round_scale_length(max_points * units_per_point)?;
+    Some((length, length / units_per_point))
+}
+
+/// Camera rotation looking at the scene from the `axis` side, as in CAD views
+///
+/// Side views keep +Y up; the top and bottom views put -Z and +Z up respectively.
+pub fn axis_view_rotation(axis: Vec3) -> Quat {
+    let back = axis.normalize();
+    let up = if back.y.abs() > 0.5 { Vec3::Z * -back.y.signum() } else { Vec3::Y };
+    let right = up.cross(back);
+    Quat::from_mat3(&Mat3::from_cols(right, up, back)).normalize()
+}
+
+/// Depth the scale bar measures at: the orbit center, or the world origin in free-fly
+fn reference_depth(cam: &MainCamera) -> f32 {
+    match cam.mode {
+        CameraMode::Orbit => cam.distance,
+        CameraMode::FreeFly => {
+            let position = cam.center + cam.rotation * Vec3::Z * cam.distance;
+            (Vec3::ZERO - position).dot(cam.rotation * Vec3::NEG_Z)
+        }
+    }
+}
+
+/// Draw the orientation gizmo and the scale bar over the viewport
+///
+/// Returns the gizmo's rect so clicks on it don't reach the camera.
+pub fn render_viewport_overlays(
+    ctx: &egui::Context,
+    viewport: egui::Rect,
+    config: &crate::rendering::RenderingConfig,
+    ui_scale: f32,
+    fov_y: f32,
+    cam: &mut MainCamera,
+) -> Option<egui::Rect> {
+    if config.show_scale_bar {
+        render_scale_bar(ctx, viewport, ui_scale, fov_y, cam);
+    }
+    if config.show_compass {
+        Some(render_orientation_gizmo(ctx, viewport, ui_scale, cam))
+    } else {
+        None
+    }
+}
+
+/// XYZ axes in the top left corner, turning with the camera; clicking an axis end snaps the view to it
+fn render_orientation_gizmo(ctx: &egui::Context, viewport: egui::Rect, ui_scale: f32, cam: &mut MainCamera) -> egui::Rect {
+    let radius = GIZMO_RADIUS * ui_scale;
+    let knob = 8.0 * ui_scale;
+    let margin = 8.0;
+    let to_camera = cam.rotation.inverse();
+
+    let response = egui::Area::new(egui::Id::new("orientation_gizmo"))
+        .fixed_pos(viewport.left_top() + egui::vec2(margin, margin))
+        .order(egui::Order::Foreground)
+        .interactable(true)
+        .show(ctx, |ui| {
+            let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(2.0 * radius), egui::Sense::click());
+            let center = rect.center();
+            let hovered = response.hover_pos().map_or(false, |pos| pos.distance(center) <= radius);
+            let painter = ui.painter_at(rect);
+            painter.circle_filled(center, radius, egui::Color32::from_black_alpha(if hovered { 150 } else { 90 }));
+
+            // Axis ends in screen space, drawn back to front
+            let mut ends: Vec<(Vec3, &str, egui::Color32, egui::Pos2, f32)> = AXES.iter()
+                .flat_map(|&(axis, label, color)| [(axis, label, color), (-axis, "", color)])
+                .map(|(axis, label, color)| {
+                    let view = to_camera * axis;
+                    let end = center + egui::vec2(view.x, -view.y) * (radius - knob - 2.0);
+                    (axis, label, color, end, view.z)
+                })
+                .collect();
+            ends.sort_by(|a, b| a.4.total_cmp(&b.4));
+
+            let pointer = response.hover_pos();
+            let hovered_end = pointer.and_then(|pos| {
+                ends.iter().rev().find(|end| end.3.distance(pos) <= knob).map(|end| end.0)
+            });
+
+            for &(axis, label, color, end, _) in &ends {
+                let highlight = hovered_end == Some(axis);
+                if label.is_empty() {
+                    painter.circle(end, knob * 0.8, egui::Color32::from_black_alpha(120), egui::Stroke::new(1.5 * ui_scale, color));
+                } else {
+                    painter.line_segment([center, end], egui::Stroke::new(2.0 * ui_scale, color));
+                    painter.circle_filled(end, knob, color);
+                    painter.text(end, egui::Align2::CENTER_CENTER, label,
+                        egui::FontId::proportional(10.0 * ui_scale), egui::Color32::BLACK);
+                }
+                if highlight {
+                    painter.circle_stroke(end, knob + 1.5, egui::Stroke::new(1.5 * ui_scale, egui::Color32::WHITE));
+                }
+            }
+
+            if let Some(axis) = hovered_end {
+                let view_name = axis_view_name(axis);
+                let response = response.on_hover_text(format!("View from {}", view_name));
+                if response.clicked() {
+                    // The camera springs toward the target like any other rotation
+                    cam.target_rotation = axis_view_rotation(axis);
+                }
+            }
+            rect
+        });
+    response.inner
+}
+
+fn axis_view_name(axis: Vec3) -> &'static str {
+    match (axis.x, axis.y, axis.z) {
+        (x, _, _) if x > 0.5 => "+X",
+        (x, _, _) if x < -0.5 => "-X",
+        (_, y, _) if y > 0.5 => "+Y (top)",
+        (_, y, _) if y < -0.5 => "-Y (bottom)",
+        (_, _, z) if z > 0.5 => "+Z",
+        _ => "-Z",
+    }
+}
+
+/// Round-number length bar along the bottom edge, measured at the orbit center's depth
+fn render_scale_bar(ctx: &egui::Context, viewport: egui::Rect, ui_scale: f32, fov_y: f32, cam: &MainCamera) {
+    let depth = reference_depth(cam);
+    if depth <= 0.0 {
+        return;
+    }
+    // The camera renders the whole window, so the field of view spans the screen height
+    let view_height = ctx.content_rect().height();
+    let Some((length, points)) = scale_bar(depth, fov_y, view_height, SCALE_BAR_MAX_LENGTH * ui_scale) else {
+        return;
+    };
+
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("scale_bar")))
+        .with_clip_rect(viewport);
+    let bottom = viewport.bottom() - 14.0 * ui_scale;
+    let left = viewport.center().x - points * 0.5;
+    let right = left + points;
+    let tick = 5.0 * ui_scale;
+    let color = egui::Color32::from_white_alpha(220);
+    let shadow = egui::Stroke::new(4.0 * ui_scale, egui::Color32::from_black_alpha(140));
+    let stroke = egui::Stroke::new(2.0 * ui_scale, color);
+
+    let bar = [
+        [egui::pos2(left, bottom), egui::pos2(right, bottom)],
+        [egui::pos2(left, bottom - tick), egui::pos2(left, bottom)],
+        [egui::pos2(right, bottom - tick), egui::pos2(right, bottom)],
+    ];
+    for segment in bar {
+        painter.line_segment(segment, shadow);
+    }
+    for segment in bar {
+        painter.line_segment(segment, stroke);
+    }
+    painter.text(
+        egui::pos2(viewport.center().x, bottom - tick),
+        egui::Align2::CENTER_BOTTOM,
+        format_length(length),
+        egui::FontId::proportional(12.0 * ui_scale),
+        color,
+    );
+}
+
+fn format_length(length: f32) -> String {
+    if length >= 1.0 {
+        format!("{:.0} units", length)
+    } else {
+        // Round lengths below 1 have one significant digit
+        let decimals = (-length.log10()).ceil() as usize;
+        format!("{:.*} units", decimals, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOV: f32 = 70.0 * std::f32::consts::PI / 180.0;
+    const HEIGHT: f32 = 800.0;
+
+    fn is_round(length: f32) -> bool {
+        let mantissa = length / 10.0f32.powf(length.log10().floor());
+        [1.0, 2.0, 5.0].iter().any(|step| (mantissa - step).abs() < 1e-3)
+    }
+
+    #[test]
+    fn test_round_scale_length_steps() {
+        assert_eq!(round_scale_length(10.0), Some(10.0));
+        assert_eq!(round_scale_length(10.5), Some(10.0));
+        assert_eq!(round_scale_length(19.9), Some(10.0));
+        assert_eq!(round_scale_length(2.0), Some(2.0));
+        assert_eq!(round_scale_length(4.9), Some(2.0));
+        assert_eq!(round_scale_length(7.0), Some(5.0));
+        assert!((round_scale_length(0.3).unwrap() - 0.2).abs() < 1e-6);
+        assert_eq!(round_scale_length(0.0), None);
+        assert_eq!(round_scale_length(f32::INFINITY), None);
+    }
+
+    #[test]
+    fn test_scale_bar_across_camera_distances() {
+        let max_points = 140.0;
+        let mut last_length = 0.0;
+        for step in 0..60 {
+            let distance = 0.5 * 1.2f32.powi(step);
+            let (length, points) = scale_bar(distance, FOV, HEIGHT, max_points).unwrap();
+            assert!(is_round(length), "{} at distance {}", length, distance);
+            // 1-2-5 steps are at most 2.5x apart, so the bar never drops below 40% of its room
+            assert!(points <= max_points + 1e-3 && points >= max_points / 2.5 - 1e-3,
+                "{} points at distance {}", points, distance);
+            assert!(length >= last_length, "zooming out should never shorten the reference length");
+            last_length = length;
+        }
+    }
+
+    #[test]
+    fn test_scale_bar_matches_projection() {
+        // 50 units away with a 70 degree FOV on 800 points: ~0.0875 units per point
+        let (length, points) = scale_bar(50.0, FOV, HEIGHT, 140.0).unwrap();
+        assert_eq!(length, 10.0);
+        assert!((points - 114.25).abs() < 0.1, "{}", points);
+        assert_eq!(scale_bar(0.0, FOV, HEIGHT, 140.0), None);
+    }
+
+    #[test]
+    fn test_axis_views_look_along_the_axis() {
+        for axis in [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z] {
+            let rotation = axis_view_rotation(axis);
+            // The camera sits on the axis side and looks back toward the center
+            assert!((rotation * Vec3::Z).distance(axis) < 1e-5, "{:?}", axis);
+            assert!((rotation * Vec3::X).dot(rotation * Vec3::Y).abs() < 1e-5);
+        }
+        assert!((axis_view_rotation(Vec3::Z) * Vec3::Y).distance(Vec3::Y) < 1e-5);
+        assert!((axis_view_rotation(Vec3::Y) * Vec3::X).distance(Vec3::X) < 1e-5);
+    }
+}
@@ -637,28 +637,122 @@ pub fn modes_buttons(
     (copy_into_clicked, reset_clicked)
 }
 
+/// Built-in archetypes and user templates offered when adding a mode
+///
+/// Returns the clicked choice; callers close their menu or popup on `Some`.
+pub fn mode_template_menu(ui: &mut Ui, templates: &crate::genome::ModeTemplates) -> Option<crate::genome::TemplateChoice> {
+    use crate::genome::{ModeArchetype, TemplateChoice};
+
+    let mut choice = None;
+    for archetype in ModeArchetype::ALL {
+        if ui.button(archetype.name()).on_hover_text(archetype.description()).clicked() {
+            choice = Some(TemplateChoice::Builtin(archetype));
+        }
+    }
+    if !templates.user.is_empty() {
+        ui.separator();
+        ui.label(egui::RichText::new("Saved templates").small().weak());
+        for (index, template) in templates.user.iter().enumerate() {
+            if ui.button(&template.name).clicked() {
+                choice = Some(TemplateChoice::User(index));
+            }
+        }
+    }
+    choice
+}
+
+/// Camera and genome graph bindings with the laptop mode switch and gesture sensitivities
+pub fn controls_menu(ui: &mut Ui, bindings: &mut crate::input::InputBindings) {
+    use crate::input::bindings::{BindingButton, BindingModifier, BindingPreset, DragAction, DragBinding, ScrollAction};
+
+    let mut laptop = bindings.matching_preset() == Some(BindingPreset::Laptop);
+    if ui.checkbox(&mut laptop, "Laptop mode")
+        .on_hover_text("Touchpad-friendly bindings: Ctrl+drag orbits, Shift+drag pans, two-finger scroll orbits, pinch or Ctrl+scroll zooms")
+        .changed()
+    {
+        bindings.apply_preset(if laptop { BindingPreset::Laptop } else { BindingPreset::Desktop });
+    }
+    ui.separator();
+
+    fn drag_row(ui: &mut Ui, label: &str, binding: &mut DragBinding) {
+        ui.label(label);
+        egui::ComboBox::from_id_salt(("binding_modifier", label))
+            .width(50.0)
+            .selected_text(binding.modifier.name())
+            .show_ui(ui, |ui| {
+                for modifier in BindingModifier::ALL {
+                    ui.selectable_value(&mut binding.modifier, modifier, modifier.name());
+                }
+            });
+        egui::ComboBox::from_id_salt(("binding_button", label))
+            .width(60.0)
+            .selected_text(binding.button.name())
+            .show_ui(ui, |ui| {
+                for button in BindingButton::ALL {
+                    ui.selectable_value(&mut binding.button, button, button.name());
+                }
+            });
+        ui.end_row();
+    }
+
+    fn scroll_row(ui: &mut Ui, label: &str, action: &mut ScrollAction) {
+        ui.label(label);
+        egui::ComboBox::from_id_salt(("scroll_action", label))
+            .width(60.0)
+            .selected_text(action.name())
+            .show_ui(ui, |ui| {
+                for option in ScrollAction::ALL {
+                    ui.selectable_value(action, option, option.name());
+                }
+            });
+        ui.end_row();
+    }
+
+    egui::Grid::new("input_bindings").num_columns(3).show(ui, |ui| {
+        for action in [DragAction::Orbit, DragAction::Pan, DragAction::Look] {
+            drag_row(ui, action.name(), bindings.drag_mut(action));
+        }
+        drag_row(ui, "Graph pan", &mut bindings.graph_pan);
+    });
+    egui::Grid::new("scroll_bindings").num_columns(2).show(ui, |ui| {
+        scroll_row(ui, "Mouse wheel", &mut bindings.wheel);
+        scroll_row(ui, "Two-finger scroll", &mut bindings.touchpad_scroll);
+    });
+    ui.label(egui::RichText::new("Ctrl+scroll always zooms").small().weak());
+    ui.separator();
+
+    ui.label("Sensitivity");
+    for (label, value) in bindings.sensitivity.axes_mut() {
+        ui.add(egui::Slider::new(value, 0.1..=5.0).logarithmic(true).text(label));
+    }
+}
+
 /// Modes list items widget - displays only the list of modes (for use in scroll area)
+/// Modes in `multi_selected` (when it holds more than one) get a solid outline.
 /// Returns (selection_changed, initial_changed, rename_index, color_change)
 pub fn modes_list_items(
     ui: &mut Ui,
-    modes: &[(String, egui::Color32)], // (name, color) pairs
+    modes: &[(String, egui::Color32, Option<crate::genome::CellPattern>)], // (name, color, pattern)
     selected_index: &mut usize,
+    multi_selected: &std::collections::BTreeSet<usize>,
     initial_mode: &mut usize,
     _width: f32,
     copy_into_mode: bool,
     color_picker_state: &mut Option<(usize, egui::ecolor::Hsva)>,
-) -> (bool, bool, Option<usize>, Option<(usize, egui::Color32)>) {
+) -> ModesListResult {
     let mut selection_changed = false;
     let mut initial_changed = false;
     let mut rename_index = None;
     let mut color_picker_index: Option<(usize, egui::Color32)> = None;
+    let mut pattern_change = None;
     
-    for (i, (name, color)) in modes.iter().enumerate() {
+    for (i, (name, color, pattern)) in modes.iter().enumerate() {
         let is_selected = i == *selected_index;
+        let is_multi_selected = multi_selected.len() > 1 && multi_selected.contains(&i);
         let is_initial = i == *initial_mode;
         
         // Determine button colors based on selection
-        let button_color = if is_selected {
+        let button_color = if is_selected || is_multi_selected {
             *color
         } else {
             egui::Color32::from_rgb(
@@ -695,6 +789,10 @@ pub fn modes_list_items(
                 radio_response.on_hover_text("Make this mode initial");
             }
             
+            // Swatch + pattern chip so modes are distinguishable without relying on hue
+            let chip_size = ui.spacing().interact_size.y;
+            mode_chip(ui, *color, *pattern, chip_size);
+            
             // Mode button with custom styling - use remaining width
             let button_width = ui.available_width();
             let button_height = ui.spacing().interact_size.y; // Match standard widget height
@@ -765,6 +863,11 @@ pub fn modes_list_items(
                             }
                         });
                     }
+                    
+                    ui.separator();
+                    if let Some(new_pattern) = pattern_picker(ui, *color, *pattern) {
+                        pattern_change = Some((i, new_pattern));
+                    }
                 });
                 
                 if should_close {
@@ -776,6 +879,12 @@ pub fn modes_list_items(
                 }
             }
             
+            // Solid outline for the other modes of a multi-selection
+            if is_multi_selected && !is_selected {
+                let stroke = Stroke::new(2.0, ui.visuals().selection.stroke.color);
+                ui.painter().rect_stroke(button_response.rect, 3.0, stroke, egui::StrokeKind::Outside);
+            }
+
             // Draw dashed outline for selected mode
             if is_selected {
                 let rect = button_response.rect;
@@ -831,5 +940,115 @@ pub fn modes_list_items(
         });
     }
 
-    (selection_changed, initial_changed, rename_index, color_picker_index)
+    (selection_changed, initial_changed, rename_index, color_picker_index, pattern_change)
+}
+
+/// (selection changed, initial changed, rename index, color change, pattern change)
+pub type ModesListResult = (
+    bool,
+    bool,
+    Option<usize>,
+    Option<(usize, egui::Color32)>,
+    Option<(usize, Option<crate::genome::CellPattern>)>,
+);
+
+/// Paint a mode's pattern marks inside `rect` (mirrors the cell surface textures)
+fn paint_pattern(painter: &egui::Painter, rect: egui::Rect, pattern: crate::genome::CellPattern, mark_color: egui::Color32) {
+    use crate::genome::PatternKind;
+    let painter = painter.with_clip_rect(rect);
+    let scale = pattern.scale.clamp(0.25, 8.0);
+    match pattern.kind {
+        PatternKind::Stripes => {
+            let period = rect.width() / (3.0 * scale);
+            let mut x = rect.left() + period * 0.5;
+            while x < rect.right() {
+                painter.line_segment([Pos2::new(x, rect.top()), Pos2::new(x, rect.bottom())], Stroke::new(period * 0.44, mark_color));
+                x += period;
+            }
+        }
+        PatternKind::Rings => {
+            let period = rect.height() / (3.0 * scale);
+            let mut y = rect.top() + period * 0.5;
+            while y < rect.bottom() {
+                painter.line_segment([Pos2::new(rect.left(), y), Pos2::new(rect.right(), y)], Stroke::new(period * 0.4, mark_color));
+                y += period;
+            }
+        }
+        PatternKind::Dots => {
+            let period = rect.height() / (2.5 * scale);
+            let mut y = rect.top() + period * 0.5;
+            while y < rect.bottom() {
+                let mut x = rect.left() + period * 0.5;
+                while x < rect.right() {
+                    painter.circle_filled(Pos2::new(x, y), period * 0.28, mark_color);
+                    x += period;
+                }
+                y += period;
+            }
+        }
+    }
+}
+
+/// Small square swatch showing a mode's color and surface pattern
+pub fn mode_chip(
+    ui: &mut Ui,
+    color: egui::Color32,
+    pattern: Option<crate::genome::CellPattern>,
+    size: f32,
+) -> Response {
+    let (rect, response) = ui.allocate_exact_size(EguiVec2::splat(size), Sense::click());
+    let rect = rect.shrink(2.0);
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, color);
+    if let Some(pattern) = pattern {
+        // Same darkening as the 3D pattern textures
+        let mark_color = egui::Color32::from_rgb(
+            (color.r() as f32 * 0.4) as u8,
+            (color.g() as f32 * 0.4) as u8,
+            (color.b() as f32 * 0.4) as u8,
+        );
+        paint_pattern(painter, rect, pattern, mark_color);
+    }
+    painter.rect_stroke(rect, 2.0, Stroke::new(1.0, egui::Color32::from_gray(20)), egui::StrokeKind::Outside);
+    response
+}
+
+/// Icon picker for a mode's surface pattern
+///
+/// Returns the new pattern if the user changed it
+pub fn pattern_picker(
+    ui: &mut Ui,
+    color: egui::Color32,
+    pattern: Option<crate::genome::CellPattern>,
+) -> Option<Option<crate::genome::CellPattern>> {
+    use crate::genome::{CellPattern, PatternKind};
+    let mut changed = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Pattern:");
+        let choices = std::iter::once(None).chain(PatternKind::ALL.into_iter().map(Some));
+        for kind in choices {
+            let is_current = pattern.map(|p| p.kind) == kind;
+            let preview = kind.map(|kind| CellPattern { kind, scale: pattern.map_or(1.0, |p| p.scale) });
+            let response = mode_chip(ui, color, preview, 22.0)
+                .on_hover_text(kind.map_or("None", |kind| kind.name()));
+            if is_current {
+                ui.painter().rect_stroke(response.rect, 2.0, Stroke::new(1.5, egui::Color32::WHITE), egui::StrokeKind::Inside);
+            }
+            if response.clicked() && !is_current {
+                changed = Some(preview);
+            }
+        }
+    });
+
+    if let Some(mut current) = pattern {
+        ui.horizontal(|ui| {
+            ui.label("Scale:");
+            if ui.add(egui::DragValue::new(&mut current.scale).speed(0.05).range(0.25..=8.0)).changed() {
+                changed = Some(Some(current));
+            }
+        });
+    }
+
+    changed
 }
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::genome::batch_edit::apply_batch_edit;
+use crate::genome::{BatchOp, CurrentGenome, GenomeHistory, GenomeNodeGraph};
+use crate::ui::GenomeEditorState;
+
+/// Batch Edit window, shown while more than one mode is selected in the Modes panel
+///
+/// Ticked fields are written to every selected mode in one frame, so the preview
+/// resimulates once and the node graph is rebuilt once for the whole batch.
+pub fn render(
+    ctx: &egui::Context,
+    current_genome: &mut CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    history: &mut GenomeHistory,
+    node_graph: &mut GenomeNodeGraph,
+    notifications: &mut crate::ui::Notifications,
+) {
+    let mode_count = current_genome.genome.modes.len();
+    genome_editor_state.selected_modes.retain(|&index| index < mode_count);
+
+    // Undo stays reachable after the selection is gone
+    let undo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+    if history.last_label().is_some()
+        && !ctx.egui_wants_keyboard_input()
+        && ctx.input_mut(|i| i.consume_shortcut(&undo_shortcut))
+    {
+        undo(current_genome, history, node_graph, notifications);
+    }
+
+    if genome_editor_state.selected_modes.len() < 2 {
+        return;
+    }
+
+    let mut apply = false;
+    let mut clear_selection = false;
+    let mut undo_clicked = false;
+    egui::Window::new("Batch Edit")
+        .collapsible(true)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(format!("{} modes selected (Ctrl+click to toggle, Shift+click for a range)", genome_editor_state.selected_modes.len()));
+            ui.separator();
+
+            egui::Grid::new("batch_edit_fields")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for edit in &mut genome_editor_state.batch_edits {
+                        ui.checkbox(&mut edit.enabled, edit.field.name());
+                        ui.add_enabled_ui(edit.enabled, |ui| {
+                            if edit.field.is_relative_only() {
+                                ui.label(BatchOp::Add.symbol());
+                            } else {
+                                egui::ComboBox::from_id_salt(("batch_op", edit.field.name()))
+                                    .width(40.0)
+                                    .selected_text(edit.op.symbol())
+                                    .show_ui(ui, |ui| {
+                                        for op in BatchOp::ALL {
+                                            ui.selectable_value(&mut edit.op, op, op.symbol());
+                                        }
+                                    });
+                            }
+                        });
+                        ui.add_enabled_ui(edit.enabled, |ui| {
+                            let drag = egui::DragValue::new(&mut edit.value).speed(0.01);
+                            if edit.op == BatchOp::Set {
+                                ui.add(drag.range(edit.field.range()));
+                            } else {
+                                ui.add(drag);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+
+            ui.label(egui::RichText::new("Results are clamped to each field's valid range.").small().weak());
+            ui.separator();
+            ui.horizontal(|ui| {
+                let any_enabled = genome_editor_state.batch_edits.iter().any(|edit| edit.enabled);
+                if ui.add_enabled(any_enabled, egui::Button::new("Apply")).clicked() {
+                    apply = true;
+                }
+                if let Some(label) = history.last_label() {
+                    if ui.button("Undo").on_hover_text(label).clicked() {
+                        undo_clicked = true;
+                    }
+                }
+                if ui.button("Clear Selection").clicked() {
+                    clear_selection = true;
+                }
+            });
+        });
+
+    if apply {
+        let changed = apply_batch_edit(
+            &mut current_genome.genome,
+            &genome_editor_state.selected_modes,
+            &genome_editor_state.batch_edits,
+            history,
+        );
+        if changed > 0 {
+            node_graph.mark_for_rebuild();
+            info!("Batch edit changed {} modes", changed);
+        }
+    }
+    if undo_clicked {
+        undo(current_genome, history, node_graph, notifications);
+    }
+    if clear_selection {
+        genome_editor_state.selected_modes.clear();
+    }
+}
+
+fn undo(
+    current_genome: &mut CurrentGenome,
+    history: &mut GenomeHistory,
+    node_graph: &mut GenomeNodeGraph,
+    notifications: &mut crate::ui::Notifications,
+) {
+    if let Some(label) = history.undo(&mut current_genome.genome) {
+        node_graph.mark_for_rebuild();
+        notifications.info(format!("Undid {}", label));
+    }
+}
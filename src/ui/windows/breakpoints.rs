@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::genome::CurrentGenome;
+use crate::simulation::breakpoints::{BreakCondition, Breakpoint, Breakpoints};
+
+/// Breakpoint list: add conditions, edit their values, enable, repeat or remove them
+pub fn render(ui: &mut egui::Ui, breakpoints: &mut Breakpoints, current_genome: &CurrentGenome, inspected_cell: Option<u32>) {
+    let groups = breakpoints.groups.groups.iter().map(|group| (group.id, group.name.clone())).collect::<Vec<_>>();
+    let metrics = breakpoints.groups.metrics.iter().map(|metric| (metric.id, metric.name.clone())).collect::<Vec<_>>();
+    ui.horizontal(|ui| {
+        ui.menu_button("Add", |ui| {
+            for condition in BreakCondition::KINDS {
+                if ui.button(condition.kind_name()).clicked() {
+                    let condition = match condition {
+                        // Start from the inspected cell when there is one
+                        BreakCondition::CellMassBelow { mass, .. } => BreakCondition::CellMassBelow {
+                            cell_id: inspected_cell.unwrap_or(0),
+                            mass,
+                        },
+                        // And from the first cell group
+                        BreakCondition::GroupCountCrosses { count, .. } => BreakCondition::GroupCountCrosses {
+                            group: groups.first().map_or(0, |(id, _)| *id),
+                            count,
+                        },
+                        // And from the first derived metric
+                        BreakCondition::MetricCrosses { value, .. } => BreakCondition::MetricCrosses {
+                            metric: metrics.first().map_or(0, |(id, _)| *id),
+                            value,
+                        },
+                        other => other,
+                    };
+                    breakpoints.list.push(Breakpoint::new(condition));
+                    ui.close();
+                }
+            }
+        });
+        if ui.add_enabled(!breakpoints.list.is_empty(), egui::Button::new("Clear")).clicked() {
+            breakpoints.list.clear();
+        }
+    });
+    if let Some(hit) = &breakpoints.last_hit {
+        ui.label(egui::RichText::new(format!(
+            "Last hit: {} at tick {}",
+            hit.condition.describe(&current_genome.genome), hit.tick
+        )).small().weak());
+    }
+    ui.separator();
+
+    if breakpoints.list.is_empty() {
+        ui.label("No breakpoints. Conditions are checked at the end of every tick.");
+        return;
+    }
+
+    let mut remove = None;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        egui::Grid::new("breakpoint_list")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, breakpoint) in breakpoints.list.iter_mut().enumerate() {
+                    ui.checkbox(&mut breakpoint.enabled, "");
+                    ui.horizontal(|ui| {
+                        ui.label(breakpoint.condition.kind_name());
+                        render_condition_values(ui, index, &mut breakpoint.condition, current_genome, &groups, &metrics, inspected_cell);
+                    });
+                    ui.checkbox(&mut breakpoint.repeating, "Repeat")
+                        .on_hover_text("Stay enabled after firing (otherwise the breakpoint fires once)");
+                    ui.label(egui::RichText::new(format!("{} hits", breakpoint.hits)).small());
+                    if ui.small_button("x").on_hover_text("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+    if let Some(index) = remove {
+        breakpoints.list.remove(index);
+    }
+}
+
+fn render_condition_values(
+    ui: &mut egui::Ui,
+    index: usize,
+    condition: &mut BreakCondition,
+    current_genome: &CurrentGenome,
+    groups: &[(u32, String)],
+    metrics: &[(u32, String)],
+    inspected_cell: Option<u32>,
+) {
+    match condition {
+        BreakCondition::CellCountCrosses(count) => {
+            ui.add(egui::DragValue::new(count).range(1..=1_000_000));
+        }
+        BreakCondition::CellMassBelow { cell_id, mass } => {
+            ui.label("cell");
+            ui.add(egui::DragValue::new(cell_id));
+            if let Some(inspected) = inspected_cell {
+                if ui.small_button("Inspected").on_hover_text("Watch the cell shown in the Cell Inspector").clicked() {
+                    *cell_id = inspected;
+                }
+            }
+            ui.label("<");
+            ui.add(egui::DragValue::new(mass).speed(0.01).range(0.0..=100.0));
+        }
+        BreakCondition::AdhesionForceAbove(force) => {
+            ui.add(egui::DragValue::new(force).speed(0.5).range(0.0..=100_000.0));
+        }
+        BreakCondition::ModeExtinct(mode) => {
+            let modes = &current_genome.genome.modes;
+            egui::ComboBox::from_id_salt(("breakpoint_mode", index))
+                .selected_text(modes.get(*mode).map_or("?", |m| m.name.as_str()))
+                .show_ui(ui, |ui| {
+                    for (mode_index, settings) in modes.iter().enumerate() {
+                        ui.selectable_value(mode, mode_index, settings.name.as_str());
+                    }
+                });
+        }
+        BreakCondition::TimeReaches(time) => {
+            ui.add(egui::DragValue::new(time).speed(0.1).range(0.0..=100_000.0).suffix("s"));
+        }
+        BreakCondition::GroupCountCrosses { group, count } => {
+            let selected = groups.iter().find(|(id, _)| id == group).map_or("(removed group)", |(_, name)| name.as_str());
+            egui::ComboBox::from_id_salt(("breakpoint_group", index))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (id, name) in groups {
+                        ui.selectable_value(group, *id, name.as_str());
+                    }
+                });
+            ui.add(egui::DragValue::new(count).range(0..=1_000_000));
+        }
+        BreakCondition::MetricCrosses { metric, value } => {
+            let selected = metrics.iter().find(|(id, _)| id == metric).map_or("(invalid metric)", |(_, name)| name.as_str());
+            egui::ComboBox::from_id_salt(("breakpoint_metric", index))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (id, name) in metrics {
+                        ui.selectable_value(metric, *id, name.as_str());
+                    }
+                });
+            ui.add(egui::DragValue::new(value).speed(0.01));
+        }
+    }
+}
+
+/// Toast every breakpoint hit and select and focus the cell it points at
+pub fn report_breakpoint_hits(
+    mut breakpoints: ResMut<Breakpoints>,
+    current_genome: Res<CurrentGenome>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut inspector: ResMut<crate::ui::windows::cell_inspector::CellInspectorState>,
+    mut camera_query: Query<&mut crate::ui::MainCamera>,
+    cell_query: Query<&crate::cell::CellPosition>,
+) {
+    // Preview entities are rebuilt after a replay; wait for them before selecting one
+    if breakpoints.pending_hits.is_empty() || sim_state.needs_respawn {
+        return;
+    }
+
+    for hit in std::mem::take(&mut breakpoints.pending_hits) {
+        let cell = hit.cell_id.map(|id| format!(" (cell {})", id)).unwrap_or_default();
+        notifications.warning(format!(
+            "Breakpoint: {} at tick {}{}",
+            hit.condition.describe(&current_genome.genome), hit.tick, cell
+        ));
+        if let Some(cell_id) = hit.cell_id {
+            crate::ui::windows::division_debug::focus_cell(
+                cell_id, &sim_state, main_state.as_deref(), preview_state.as_deref(),
+                &mut inspector, &mut camera_query, &cell_query,
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy_egui::egui;
+use crate::ui::camera_path::{CameraPath, CameraPathPlayer, ADD_KEYFRAME_KEY};
+use crate::ui::Notifications;
+
+/// Presentation camera path: keyframes on simulated time, playback, scrubbing and files
+pub fn render(ui: &mut egui::Ui, player: &mut CameraPathPlayer, notifications: &mut Notifications) {
+    ui.horizontal(|ui| {
+        if ui.button("Add Keyframe")
+            .on_hover_text(format!("Record the current camera at the current simulated time ({:?})", ADD_KEYFRAME_KEY))
+            .clicked()
+        {
+            player.add_requested = true;
+        }
+        let has_keys = !player.path.keyframes.is_empty();
+        let label = if player.playing { "Stop" } else { "Play" };
+        if ui.add_enabled(has_keys || player.playing, egui::Button::new(label))
+            .on_hover_text("Follow the path as the simulation runs")
+            .clicked()
+        {
+            player.playing = !player.playing;
+            player.paused_by_input = false;
+            player.preview_time = None;
+        }
+        if player.paused_by_input && ui.button("Resume").on_hover_text("Playback stopped when the camera was moved").clicked() {
+            player.playing = true;
+            player.paused_by_input = false;
+        }
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("Save...").clicked() {
+            save_path(&player.path, notifications);
+        }
+        if ui.button("Load...").clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Camera path", &["json"]).pick_file() {
+                match CameraPath::load_from_file(&path) {
+                    Ok(loaded) => {
+                        notifications.success(format!("Loaded {} camera keyframes", loaded.keyframes.len()));
+                        player.path = loaded;
+                        player.preview_time = None;
+                    }
+                    Err(e) => notifications.error(&e),
+                }
+            }
+        }
+        if ui.add_enabled(!player.path.keyframes.is_empty(), egui::Button::new("Clear")).clicked() {
+            player.path.keyframes.clear();
+            player.playing = false;
+            player.preview_time = None;
+        }
+    });
+
+    let Some((start, end)) = player.path.time_range() else {
+        ui.separator();
+        ui.label("No keyframes. Position the camera, then add a keyframe at each moment of the run.");
+        return;
+    };
+
+    ui.separator();
+    let mut previewing = player.preview_time.is_some();
+    ui.horizontal(|ui| {
+        if ui.checkbox(&mut previewing, "Preview").on_hover_text("Scrub the path without moving the simulation").changed() {
+            player.preview_time = previewing.then_some(start);
+        }
+        if let Some(time) = player.preview_time.as_mut() {
+            ui.add(egui::Slider::new(time, start..=end).suffix(" s"));
+        }
+    });
+
+    ui.separator();
+    let count = player.path.keyframes.len();
+    let mut moved = None;
+    let mut removed = None;
+    let mut go_to = None;
+    let mut retimed = false;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        egui::Grid::new("camera_path_keyframes").num_columns(4).striped(true).show(ui, |ui| {
+            for (index, keyframe) in player.path.keyframes.iter_mut().enumerate() {
+                ui.label(format!("{}", index + 1));
+                retimed |= ui.add(egui::DragValue::new(&mut keyframe.time).speed(0.1).range(0.0..=f32::MAX).suffix(" s"))
+                    .on_hover_text("Simulated time of this keyframe")
+                    .changed();
+                ui.label(format!("FOV {:.0}°", keyframe.fov))
+                    .on_hover_text(format!("Position {:.1}\nTarget {:.1}", keyframe.position, keyframe.target));
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).clicked() {
+                        moved = Some((index, index - 1));
+                    }
+                    if ui.add_enabled(index + 1 < count, egui::Button::new("⬇").small()).clicked() {
+                        moved = Some((index, index + 1));
+                    }
+                    if ui.small_button("Go").on_hover_text("Preview the path at this keyframe").clicked() {
+                        go_to = Some(keyframe.time);
+                    }
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(index);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    });
+
+    if go_to.is_some() {
+        player.preview_time = go_to;
+    }
+    if retimed {
+        player.path.sort();
+    }
+    if let Some((from, to)) = moved {
+        player.path.move_keyframe(from, to);
+    }
+    if let Some(index) = removed {
+        player.path.keyframes.remove(index);
+        if player.path.keyframes.is_empty() {
+            player.playing = false;
+            player.preview_time = None;
+        }
+    }
+}
+
+fn save_path(path: &CameraPath, notifications: &mut Notifications) {
+    let Some(file) = rfd::FileDialog::new()
+        .add_filter("Camera path", &["json"])
+        .set_file_name("camera_path.json")
+        .save_file()
+    else {
+        return;
+    };
+    match path.save_to_file(&file) {
+        Ok(()) => notifications.success(format!("Saved {} camera keyframes to {}", path.keyframes.len(), file.display())),
+        Err(e) => notifications.error(&e),
+    }
+}
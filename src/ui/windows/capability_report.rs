@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::rendering::RenderCapabilities;
+
+/// Dialog listing the rendering features turned off for the graphics adapter
+///
+/// Shown once after startup when anything is missing, and again from the Graphics menu.
+pub fn render_capability_report(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut capabilities: ResMut<RenderCapabilities>,
+) {
+    if capabilities.report_dismissed || !capabilities.any_missing() {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let mut dismissed = false;
+    egui::Window::new("Reduced Graphics")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!(
+                "Some features are not supported by your graphics adapter ({}, {}) and were turned off:",
+                capabilities.adapter_name, capabilities.backend
+            ));
+            ui.add_space(4.0);
+            egui::Grid::new("capability_report")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, support) in capabilities.features() {
+                        let Some(reason) = support.missing_reason() else {
+                            continue;
+                        };
+                        ui.label(egui::RichText::new(name).strong());
+                        ui.label(reason);
+                        ui.end_row();
+                    }
+                });
+            if capabilities.forced_low_spec {
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new(format!(
+                    "Started with {}; restart without it to use the adapter's full capabilities.",
+                    crate::rendering::capabilities::FORCE_LOW_SPEC_FLAG
+                )).small());
+            }
+            ui.add_space(6.0);
+            if ui.button("OK").clicked() {
+                dismissed = true;
+            }
+        });
+
+    if dismissed {
+        capabilities.report_dismissed = true;
+    }
+}
@@ -0,0 +1,300 @@
+use bevy_egui::egui;
+use std::collections::BTreeSet;
+use crate::genome::CurrentGenome;
+use crate::simulation::cell_groups::{CellGroup, CellGroups, GroupAction, GroupKind, GroupPredicate, GroupQuery};
+use crate::simulation::user_data::USER_DATA_CHANNELS;
+
+/// Explicit members listed before the rest are summarized
+const LISTED_MEMBERS: usize = 24;
+
+/// Named cell groups with their counts, query builders, render filters and bulk actions
+pub fn render(ui: &mut egui::Ui, groups: &mut CellGroups, current_genome: &CurrentGenome, inspected_cell: Option<u32>) {
+    ui.horizontal(|ui| {
+        let from_inspected = ui.add_enabled(inspected_cell.is_some(), egui::Button::new("New from inspected cell"))
+            .on_hover_text("Saved set of cells, starting with the cell shown in the Cell Inspector");
+        if from_inspected.clicked() {
+            let cells = inspected_cell.into_iter().collect();
+            let name = format!("Group {}", groups.groups.len() + 1);
+            groups.add(name, GroupKind::Explicit(cells));
+        }
+        if ui.button("New query").on_hover_text("Cells matching conditions on their state").clicked() {
+            let name = format!("Query {}", groups.groups.len() + 1);
+            groups.add(name, GroupKind::Query(GroupQuery::All(Vec::new())));
+        }
+        if ui.add_enabled(!groups.groups.is_empty(), egui::Button::new("Refresh")).clicked() {
+            groups.refresh_requested = true;
+        }
+    });
+    ui.separator();
+
+    if groups.groups.is_empty() {
+        ui.label("No groups. Groups are saved with scenes and can be used by breakpoints.");
+        return;
+    }
+
+    let mut remove = None;
+    let mut edited = false;
+    let (mut highlighted, mut isolated) = (groups.highlighted, groups.isolated);
+    let mut actions = Vec::new();
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        for group in &mut groups.groups {
+            let id = group.id;
+            let header = format!("{} ({} cells)", group.name, group.members.len());
+            egui::CollapsingHeader::new(header)
+                .id_salt(("cell_group", id))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut group.name);
+                    });
+                    ui.label(egui::RichText::new(group.describe(&current_genome.genome)).small().weak());
+
+                    ui.horizontal(|ui| {
+                        let mut shown = highlighted == Some(id);
+                        if ui.checkbox(&mut shown, "Highlight").changed() {
+                            highlighted = shown.then_some(id);
+                        }
+                        let mut alone = isolated == Some(id);
+                        if ui.checkbox(&mut alone, "Isolate").on_hover_text("Dim every cell outside the group").changed() {
+                            isolated = alone.then_some(id);
+                        }
+                        if matches!(group.kind, GroupKind::Query(_)) {
+                            ui.checkbox(&mut group.live, "Live")
+                                .on_hover_text("Re-evaluate a few times per second instead of on Refresh");
+                        }
+                    });
+
+                    edited |= render_members(ui, group, inspected_cell, current_genome);
+                    render_actions(ui, group, &mut actions);
+
+                    if ui.small_button("Remove group").clicked() {
+                        remove = Some(id);
+                    }
+                });
+        }
+    });
+
+    groups.highlighted = highlighted;
+    groups.isolated = isolated;
+    groups.actions.extend(actions);
+    if edited {
+        groups.refresh_requested = true;
+    }
+    if let Some(id) = remove {
+        groups.remove(id);
+    }
+}
+
+/// Member list of an explicit group or the builder of a query group; returns true on an edit
+fn render_members(ui: &mut egui::Ui, group: &mut CellGroup, inspected_cell: Option<u32>, current_genome: &CurrentGenome) -> bool {
+    let mut edited = false;
+    let mut freeze = None;
+    match &mut group.kind {
+        GroupKind::Explicit(cells) => {
+            ui.horizontal(|ui| {
+                if let Some(cell) = inspected_cell {
+                    if ui.add_enabled(!cells.contains(&cell), egui::Button::new("Add inspected")).clicked() {
+                        edited |= cells.insert(cell);
+                    }
+                    if ui.add_enabled(cells.contains(&cell), egui::Button::new("Remove inspected")).clicked() {
+                        edited |= cells.remove(&cell);
+                    }
+                }
+            });
+            let mut dropped = None;
+            ui.horizontal_wrapped(|ui| {
+                for &cell in cells.iter().take(LISTED_MEMBERS) {
+                    if ui.small_button(format!("{} x", cell)).on_hover_text("Remove from the group").clicked() {
+                        dropped = Some(cell);
+                    }
+                }
+                if cells.len() > LISTED_MEMBERS {
+                    ui.label(egui::RichText::new(format!("and {} more", cells.len() - LISTED_MEMBERS)).small().weak());
+                }
+            });
+            if let Some(cell) = dropped {
+                edited |= cells.remove(&cell);
+            }
+        }
+        GroupKind::Query(query) => {
+            edited |= render_query(ui, query, current_genome, &mut vec![group.id as usize]);
+            if ui.button("Freeze to saved set")
+                .on_hover_text("Turn the group into the set of cells it matches now")
+                .clicked()
+            {
+                freeze = Some(group.members.iter().copied().collect::<BTreeSet<_>>());
+            }
+        }
+    }
+    if let Some(cells) = freeze {
+        group.kind = GroupKind::Explicit(cells);
+        group.live = false;
+        edited = true;
+    }
+    edited
+}
+
+/// Builder for one query node; `path` keeps widget IDs unique; returns true on an edit
+fn render_query(ui: &mut egui::Ui, query: &mut GroupQuery, current_genome: &CurrentGenome, path: &mut Vec<usize>) -> bool {
+    let mut edited = false;
+    let operator = match query {
+        GroupQuery::All(_) => Some("Match all (AND)"),
+        GroupQuery::Any(_) => Some("Match any (OR)"),
+        GroupQuery::Predicate(_) => None,
+    };
+    if let Some(label) = operator {
+        if ui.small_button(label).on_hover_text("Switch between AND and OR, keeping the conditions").clicked() {
+            *query = match std::mem::replace(query, GroupQuery::All(Vec::new())) {
+                GroupQuery::All(children) => GroupQuery::Any(children),
+                GroupQuery::Any(children) => GroupQuery::All(children),
+                predicate => predicate,
+            };
+            edited = true;
+        }
+    }
+    match query {
+        GroupQuery::Predicate(predicate) => {
+            ui.horizontal(|ui| {
+                ui.label(predicate.kind_name());
+                edited |= render_predicate(ui, predicate, current_genome, path);
+            });
+        }
+        GroupQuery::All(children) | GroupQuery::Any(children) => {
+            let mut remove = None;
+            ui.group(|ui| {
+                for (index, child) in children.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("x").on_hover_text("Remove condition").clicked() {
+                            remove = Some(index);
+                        }
+                        ui.vertical(|ui| {
+                            path.push(index);
+                            edited |= render_query(ui, child, current_genome, path);
+                            path.pop();
+                        });
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.menu_button("+ Condition", |ui| {
+                        for predicate in GroupPredicate::KINDS {
+                            if ui.button(predicate.kind_name()).clicked() {
+                                children.push(GroupQuery::Predicate(predicate));
+                                edited = true;
+                                ui.close();
+                            }
+                        }
+                    });
+                    if ui.small_button("+ AND").clicked() {
+                        children.push(GroupQuery::All(Vec::new()));
+                        edited = true;
+                    }
+                    if ui.small_button("+ OR").clicked() {
+                        children.push(GroupQuery::Any(Vec::new()));
+                        edited = true;
+                    }
+                });
+            });
+            if let Some(index) = remove {
+                children.remove(index);
+                edited = true;
+            }
+        }
+    }
+
+    edited
+}
+
+fn render_predicate(ui: &mut egui::Ui, predicate: &mut GroupPredicate, current_genome: &CurrentGenome, path: &[usize]) -> bool {
+    let range = |ui: &mut egui::Ui, min: &mut f32, max: &mut f32, speed: f64| {
+        let changed = ui.add(egui::DragValue::new(min).speed(speed)).changed();
+        ui.label("to");
+        let changed = ui.add(egui::DragValue::new(max).speed(speed)).changed() || changed;
+        changed
+    };
+    match predicate {
+        GroupPredicate::ModeIn(modes) => {
+            let mut edited = false;
+            let label = format!("{} modes", modes.len());
+            ui.menu_button(label, |ui| {
+                for (mode_index, mode) in current_genome.genome.modes.iter().enumerate() {
+                    let mut included = modes.contains(&mode_index);
+                    if ui.checkbox(&mut included, mode.name.as_str()).changed() {
+                        if included {
+                            modes.push(mode_index);
+                        } else {
+                            modes.retain(|&m| m != mode_index);
+                        }
+                        edited = true;
+                    }
+                }
+            });
+            edited
+        }
+        GroupPredicate::MassRange { min, max } => range(ui, min, max, 0.01),
+        GroupPredicate::AgeRange { min, max } => range(ui, min, max, 0.1),
+        GroupPredicate::DistanceFrom { point, min, max } => {
+            let mut edited = false;
+            ui.push_id(("distance_point", path), |ui| {
+                for axis in [&mut point.x, &mut point.y, &mut point.z] {
+                    edited |= ui.add(egui::DragValue::new(axis).speed(0.1)).changed();
+                }
+            });
+            ui.label("within");
+            range(ui, min, max, 0.1) || edited
+        }
+        GroupPredicate::AdhesionCount { min, max } => {
+            let changed = ui.add(egui::DragValue::new(min).range(0..=20)).changed();
+            ui.label("to");
+            ui.add(egui::DragValue::new(max).range(0..=20)).changed() || changed
+        }
+        GroupPredicate::TouchingBoundary { margin } => {
+            ui.label("margin");
+            ui.add(egui::DragValue::new(margin).speed(0.1).range(0.0..=1000.0)).changed()
+        }
+    }
+}
+
+/// Bulk action buttons, queued for the simulation to apply to the current members
+fn render_actions(ui: &mut egui::Ui, group: &CellGroup, actions: &mut Vec<GroupAction>) {
+    let id = group.id;
+    ui.horizontal(|ui| {
+        if ui.button("Freeze").on_hover_text("Pin every member in place").clicked() {
+            actions.push(GroupAction::Freeze { group: id, pinned: true });
+        }
+        if ui.button("Unfreeze").clicked() {
+            actions.push(GroupAction::Freeze { group: id, pinned: false });
+        }
+        if ui.button("Delete cells").on_hover_text("Remove every member from the simulation").clicked() {
+            actions.push(GroupAction::Delete(id));
+        }
+        if ui.button("Export...").on_hover_text("Save the members as a scene of their own").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Scene", &["json"])
+                .set_file_name(format!("{}.json", group.name))
+                .save_file()
+            {
+                actions.push(GroupAction::Export { group: id, path });
+            }
+        }
+    });
+
+    let data_id = egui::Id::new(("group_user_data", id));
+    let (mut channel, mut value) = ui.data_mut(|data| *data.get_temp_mut_or_default::<(usize, f32)>(data_id));
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt(("group_user_data_channel", id))
+            .selected_text(format!("Data {}", channel + 1))
+            .show_ui(ui, |ui| {
+                for index in 0..USER_DATA_CHANNELS {
+                    ui.selectable_value(&mut channel, index, format!("Data {}", index + 1));
+                }
+            });
+        ui.label("=");
+        ui.add(egui::DragValue::new(&mut value).speed(0.01));
+        if ui.button("Set").on_hover_text("Write this user data value to every member").clicked() {
+            actions.push(GroupAction::SetUserData { group: id, channel, value });
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(data_id, (channel, value)));
+}
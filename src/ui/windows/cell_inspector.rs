@@ -0,0 +1,467 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::genome::CurrentGenome;
+use crate::genome::ChildModeRule;
+use crate::simulation::colony_surface::SURFACE_RESOLUTION_RANGE;
+use crate::simulation::cpu_physics::CanonicalState;
+use crate::simulation::{ColonySurface, EventTimeline, SimulationMode};
+use crate::ui::ui_system::GenomeEditorState;
+use crate::simulation::user_data::{UserDataRequests, USER_DATA_CHANNELS};
+
+/// Snapshot of the inspected cell's canonical state, refreshed every frame
+#[derive(Clone, Debug)]
+pub struct InspectedCell {
+    pub cell_id: u32,
+    pub index: usize,
+    pub mode_index: usize,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub mass: f32,
+    pub radius: f32,
+    pub split_mass: f32,
+    pub split_interval: f32,
+    pub age: f32,
+    pub split_count: i32,
+    pub adhesion_count: usize,
+    /// Summed collision overlap from the last tick (contact inhibition input)
+    pub contact_pressure: f32,
+    /// Low-nutrient priority boost engaged ("survival mode")
+    pub low_nutrient_boost: bool,
+    /// Held in place by a pin
+    pub pinned: bool,
+    /// User data channels
+    pub user_data: [f32; USER_DATA_CHANNELS],
+    /// Active bonds, in the cell's adhesion slot order
+    pub bonds: Vec<InspectedBond>,
+}
+
+/// One of the inspected cell's bonds
+#[derive(Clone, Debug)]
+pub struct InspectedBond {
+    pub partner_id: u32,
+    pub partner_mode: usize,
+    pub length: f32,
+    /// Largest angle (degrees) between either anchor and the direction to the other cell
+    pub misalignment: f32,
+}
+
+/// Cell currently shown in the Cell Inspector (the last cell picked in the viewport)
+#[derive(Resource, Default)]
+pub struct CellInspectorState {
+    pub inspected_entity: Option<Entity>,
+    pub snapshot: Option<InspectedCell>,
+}
+
+impl InspectedCell {
+    fn from_state(state: &CanonicalState, index: usize, current_time: f32) -> Self {
+        Self {
+            cell_id: state.cell_ids[index],
+            index,
+            mode_index: state.mode_indices[index],
+            position: state.positions[index],
+            velocity: state.velocities[index],
+            mass: state.masses[index],
+            radius: state.radii[index],
+            split_mass: state.split_masses[index],
+            split_interval: state.split_intervals[index],
+            age: current_time - state.birth_times[index],
+            split_count: state.split_counts[index],
+            adhesion_count: state.adhesion_manager.count_active_adhesions(index),
+            contact_pressure: state.contact_pressures[index],
+            low_nutrient_boost: state.low_nutrient_boost(index),
+            pinned: state.is_pinned(index),
+            user_data: state.user_data(index),
+            bonds: InspectedBond::of(state, index),
+        }
+    }
+}
+
+impl InspectedBond {
+    fn of(state: &CanonicalState, index: usize) -> Vec<Self> {
+        let connections = &state.adhesion_connections;
+        state.adhesion_manager.get_connections_for_cell(connections, index).into_iter()
+            .filter_map(|slot| {
+                let (a, b) = (connections.cell_a_index[slot], connections.cell_b_index[slot]);
+                let partner = if a == index { b } else { a };
+                if partner >= state.cell_count {
+                    return None;
+                }
+                let axis = (state.positions[b] - state.positions[a]).try_normalize()?;
+                let angle = |anchor: Vec3, rotation: Quat, toward: Vec3| {
+                    (rotation * anchor).normalize_or_zero().dot(toward).clamp(-1.0, 1.0).acos().to_degrees()
+                };
+                let misalignment = angle(connections.anchor_direction_a[slot], state.rotations[a], axis)
+                    .max(angle(connections.anchor_direction_b[slot], state.rotations[b], -axis));
+                Some(Self {
+                    partner_id: state.cell_ids[partner],
+                    partner_mode: state.mode_indices[partner],
+                    length: state.positions[a].distance(state.positions[b]),
+                    misalignment,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Track the picked cell and copy its canonical state for the inspector panel
+pub fn update_cell_inspector(
+    mut inspector: ResMut<CellInspectorState>,
+    drag_state: Res<crate::input::DragState>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if let Some(entity) = drag_state.dragged_entity {
+        inspector.inspected_entity = Some(entity);
+    }
+    let Some(entity) = inspector.inspected_entity else {
+        inspector.snapshot = None;
+        return;
+    };
+
+    inspector.snapshot = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.and_then(|main_state| {
+            let index = *main_state.entity_to_index.get(&entity)?;
+            let state = &main_state.canonical_state;
+            (index < state.cell_count)
+                .then(|| InspectedCell::from_state(state, index, main_state.simulation_time))
+        }),
+        crate::simulation::SimulationMode::Preview => preview_state.and_then(|preview_state| {
+            let state = &preview_state.canonical_state;
+            (0..state.cell_count)
+                .find(|&i| preview_state.index_to_entity.get(i).copied().flatten() == Some(entity))
+                .map(|index| InspectedCell::from_state(state, index, preview_state.current_time))
+        }),
+        crate::simulation::SimulationMode::Gpu => None,
+    };
+}
+
+pub fn render(
+    ui: &mut egui::Ui,
+    inspector: &CellInspectorState,
+    current_genome: &mut CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    sim_mode: SimulationMode,
+    event_timeline: &EventTimeline,
+    pin_requests: &mut crate::simulation::pinning::PinRequests,
+    user_data_requests: &mut UserDataRequests,
+    anchor_edit: &mut crate::input::AnchorEditState,
+    colony_surface: &mut ColonySurface,
+    notifications: &mut crate::ui::Notifications,
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        let Some(cell) = &inspector.snapshot else {
+            ui.label("Drag a cell in the viewport to inspect it.");
+            return;
+        };
+
+        let mode = current_genome.genome.modes.get(cell.mode_index);
+        let mode_name = mode.map(|mode| mode.name.as_str()).unwrap_or("?");
+        let max_contact_pressure = mode.map(|mode| mode.max_contact_pressure).unwrap_or(f32::INFINITY);
+
+        egui::Grid::new("cell_inspector_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Cell ID:");
+                ui.label(format!("{} (slot {})", cell.cell_id, cell.index));
+                ui.end_row();
+
+                ui.label("Mode:");
+                ui.label(format!("{} ({})", mode_name, cell.mode_index));
+                ui.end_row();
+
+                ui.label("Position:");
+                ui.label(format!("{:.2}, {:.2}, {:.2}", cell.position.x, cell.position.y, cell.position.z));
+                ui.end_row();
+
+                ui.label("Speed:");
+                ui.label(format!("{:.3}", cell.velocity.length()));
+                ui.end_row();
+
+                ui.label("Mass:");
+                ui.label(format!("{:.3} / {:.3}", cell.mass, cell.split_mass));
+                ui.end_row();
+
+                ui.label("Radius:");
+                ui.label(format!("{:.3}", cell.radius));
+                ui.end_row();
+
+                ui.label("Age:");
+                ui.label(format!("{:.2}s / {:.2}s", cell.age, cell.split_interval));
+                ui.end_row();
+
+                ui.label("Splits:");
+                ui.label(cell.split_count.to_string());
+                ui.end_row();
+
+                ui.label("Adhesions:");
+                ui.label(cell.adhesion_count.to_string());
+                ui.end_row();
+
+                ui.label("Pressure:");
+                if cell.contact_pressure > max_contact_pressure {
+                    ui.colored_label(egui::Color32::from_rgb(230, 160, 60), format!("{:.3} (inhibited)", cell.contact_pressure))
+                        .on_hover_text(format!("Above the mode's contact limit of {:.2}; division waits", max_contact_pressure));
+                } else {
+                    ui.label(format!("{:.3}", cell.contact_pressure));
+                }
+                ui.end_row();
+
+                ui.label("Nutrients:");
+                if cell.low_nutrient_boost {
+                    ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "Survival mode")
+                        .on_hover_text("Low-nutrient priority boost engaged");
+                } else {
+                    ui.label("Normal");
+                }
+                ui.end_row();
+
+                ui.label("Pinned:");
+                let mut pinned = cell.pinned;
+                if ui.checkbox(&mut pinned, "")
+                    .on_hover_text("Hold the cell in place; forces still act on its partners")
+                    .changed()
+                {
+                    pin_requests.set(cell.cell_id, pinned);
+                }
+                ui.end_row();
+
+                for channel in 0..USER_DATA_CHANNELS {
+                    ui.label(format!("Data {}:", channel + 1));
+                    ui.horizontal(|ui| {
+                        let mut value = cell.user_data[channel];
+                        if ui.add(egui::DragValue::new(&mut value).speed(0.01)).changed() {
+                            user_data_requests.set(cell.cell_id, channel, value);
+                        }
+                        ui.menu_button("...", |ui| {
+                            if ui.button(format!("Set {:.3} on every {} cell", value, mode_name)).clicked() {
+                                user_data_requests.set_mode(cell.mode_index, channel, value);
+                                ui.close();
+                            }
+                            if ui.button(format!("Set {:.3} on every cell", value)).clicked() {
+                                user_data_requests.set_all(channel, value);
+                                ui.close();
+                            }
+                        }).response.on_hover_text("Copy this value to other cells");
+                    });
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(8.0);
+        render_bond_list(ui, cell, current_genome, anchor_edit);
+
+        ui.add_space(8.0);
+        render_lineage_trace(ui, cell.cell_id, current_genome, genome_editor_state, sim_mode, event_timeline);
+
+        ui.add_space(8.0);
+        render_organism_surface(ui, cell.cell_id, colony_surface, notifications);
+    });
+}
+
+/// The inspected cell's bonds; picking one selects it for anchor editing in the viewport
+fn render_bond_list(
+    ui: &mut egui::Ui,
+    cell: &InspectedCell,
+    current_genome: &CurrentGenome,
+    anchor_edit: &mut crate::input::AnchorEditState,
+) {
+    egui::CollapsingHeader::new(format!("Bonds ({})", cell.bonds.len()))
+        .id_salt("cell_inspector_bonds")
+        .show(ui, |ui| {
+            if cell.bonds.is_empty() {
+                ui.label(egui::RichText::new("No bonds.").weak());
+                return;
+            }
+            for bond in &cell.bonds {
+                let partner_mode = current_genome.genome.modes.get(bond.partner_mode)
+                    .map(|mode| mode.name.as_str())
+                    .unwrap_or("?");
+                let selected = anchor_edit.is_selected(cell.cell_id, bond.partner_id);
+                let label = format!(
+                    "Cell {} ({}), length {:.2}, anchors {:.0}\u{b0} off axis",
+                    bond.partner_id, partner_mode, bond.length, bond.misalignment,
+                );
+                if ui.selectable_label(selected, label)
+                    .on_hover_text("Select the bond to drag its anchor handles in the viewport")
+                    .clicked()
+                {
+                    if selected {
+                        anchor_edit.bond = None;
+                    } else {
+                        anchor_edit.select(cell.cell_id, bond.partner_id);
+                    }
+                }
+            }
+
+            let selected_here = cell.bonds.iter().any(|bond| anchor_edit.is_selected(cell.cell_id, bond.partner_id));
+            if ui.add_enabled(selected_here, egui::Button::new("Auto-align anchors to current geometry"))
+                .on_hover_text("Point both anchors of the selected bond straight along its present axis")
+                .clicked()
+            {
+                anchor_edit.align_requested = true;
+            }
+        });
+}
+
+/// Divisions that led to the inspected cell, oldest first ("why is this cell in this mode?")
+///
+/// Each step names the parent's mode, the child side and the rule that picked the
+/// child's mode. Mode names select the mode in the genome editor; the scrub button
+/// moves the preview to the division.
+fn render_lineage_trace(
+    ui: &mut egui::Ui,
+    cell_id: u32,
+    current_genome: &mut CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    sim_mode: SimulationMode,
+    event_timeline: &EventTimeline,
+) {
+    egui::CollapsingHeader::new("Lineage")
+        .id_salt("cell_inspector_lineage")
+        .show(ui, |ui| {
+            if sim_mode != SimulationMode::Preview {
+                ui.label("Lineage traces are recorded on the Preview timeline.");
+                return;
+            }
+            let trace = event_timeline.lineage().trace(cell_id);
+            let Some(first) = trace.first() else {
+                ui.label("No recorded division produced this cell (a founder, or born before the oldest recorded division).");
+                return;
+            };
+            if first.generation > 1 {
+                ui.label(egui::RichText::new(format!(
+                    "{} earlier division{} no longer recorded",
+                    first.generation - 1,
+                    if first.generation == 2 { " is" } else { "s are" },
+                )).small().weak());
+            }
+
+            let duration = genome_editor_state.max_preview_duration;
+            egui::Grid::new("cell_inspector_lineage_grid")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    for record in &trace {
+                        ui.label(egui::RichText::new(format!("Gen {}", record.generation)).monospace());
+                        mode_link(ui, current_genome, record.parent_mode as usize);
+                        ui.label(format!("→ {} →", record.side_name()));
+                        mode_link(ui, current_genome, record.mode as usize);
+                        ui.horizontal(|ui| {
+                            let rule = egui::RichText::new(record.rule.name()).small();
+                            match record.rule {
+                                ChildModeRule::Child => ui.label(rule.weak()),
+                                ChildModeRule::AfterSplits => ui.colored_label(egui::Color32::from_rgb(110, 170, 230), rule)
+                                    .on_hover_text("The parent reached its max splits"),
+                                ChildModeRule::KeptParent => ui.colored_label(egui::Color32::from_rgb(230, 160, 60), rule)
+                                    .on_hover_text("The child mode reference was out of range"),
+                            };
+                            let time = event_timeline.tick_time(record.tick);
+                            if ui.add_enabled(time <= duration, egui::Button::new("⏵").small())
+                                .on_hover_text(format!("Scrub to this division (tick {}, {:.2}s)", record.tick, time))
+                                .on_disabled_hover_text("Past the end of the preview timeline")
+                                .clicked()
+                            {
+                                genome_editor_state.time_value = (time / duration * 100.0).clamp(0.0, 100.0);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Mode name that selects the mode in the genome editor when clicked
+fn mode_link(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, mode_index: usize) {
+    let name = current_genome.genome.modes.get(mode_index).map_or("?", |mode| mode.name.as_str());
+    if ui.link(format!("{} ({})", name, mode_index)).on_hover_text("Select in the genome editor").clicked() {
+        current_genome.selected_mode_index = mode_index as i32;
+    }
+}
+
+/// Surface reconstruction of the inspected cell's organism
+fn render_organism_surface(
+    ui: &mut egui::Ui,
+    cell_id: u32,
+    surface: &mut ColonySurface,
+    notifications: &mut crate::ui::Notifications,
+) {
+    egui::CollapsingHeader::new("Organism Surface")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Resolution:");
+                ui.add(egui::Slider::new(&mut surface.resolution, SURFACE_RESOLUTION_RANGE))
+                    .on_hover_text("Grid cells along the organism's longest axis");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Recompute after:");
+                ui.add(egui::DragValue::new(&mut surface.cell_count_tolerance).suffix(" cells"));
+                ui.add(egui::DragValue::new(&mut surface.centroid_tolerance).speed(0.05).range(0.0..=100.0).suffix(" moved"));
+            }).response.on_hover_text("A cached surface is reused until the organism gains or loses more cells, or its centroid moves further");
+
+            let computed = surface.result.as_ref().is_some_and(|result| result.cell_id == cell_id);
+            ui.horizontal(|ui| {
+                let label = if computed { "Recompute" } else { "Compute" };
+                if ui.add_enabled(!surface.is_running(), egui::Button::new(label)).clicked() {
+                    // An up-to-date surface is only rebuilt when asked again explicitly
+                    surface.request(cell_id, computed && !surface.outdated);
+                }
+                if let Some(progress) = surface.progress() {
+                    ui.add(egui::ProgressBar::new(progress).show_percentage());
+                }
+            });
+            if let Some(error) = &surface.error {
+                ui.colored_label(egui::Color32::from_rgb(230, 90, 90), error);
+            }
+
+            let Some(result) = surface.result.as_ref().filter(|result| result.cell_id == cell_id) else {
+                return;
+            };
+            egui::Grid::new("organism_surface_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Volume:");
+                    ui.label(format!("{:.3}", result.metrics.volume));
+                    ui.end_row();
+
+                    ui.label("Surface area:");
+                    ui.label(format!("{:.3}", result.metrics.area));
+                    ui.end_row();
+
+                    ui.label("Sphericity:");
+                    ui.label(format!("{:.3}", result.metrics.sphericity))
+                        .on_hover_text("1 for a sphere, lower for elongated or lumpy shapes");
+                    ui.end_row();
+
+                    ui.label("Cells:");
+                    ui.label(format!("{} ({} triangles)", result.shape.cell_count, result.mesh.triangle_count()));
+                    ui.end_row();
+                });
+            if surface.outdated {
+                ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "Outdated: the organism changed since this surface was built");
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut surface.show_overlay, "Show overlay");
+                if ui.button("Export OBJ...").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Wavefront OBJ", &["obj"])
+                        .set_file_name("organism_surface.obj")
+                        .save_file()
+                    {
+                        match crate::error::write_atomically(&path, result.mesh.to_obj().as_bytes()) {
+                            Ok(()) => notifications.success(format!("Exported surface to {}", path.display())),
+                            Err(e) => notifications.error(&e),
+                        }
+                    }
+                }
+            });
+        });
+}
@@ -0,0 +1,118 @@
+use bevy_egui::egui;
+use crate::genome::{ContactAdhesionState, CurrentGenome};
+use crate::ui::GenomeEditorState;
+
+/// Side of one matrix cell in points
+const CELL_SIZE: f32 = 12.0;
+
+/// Mode × mode grid of contact adhesion rules and the editor of the selected rule
+///
+/// Rows are the mode reaching out, columns the partner it bonds to. Click cycles an
+/// entry through off, always and chance; right-click selects it for editing.
+pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, editor: &mut GenomeEditorState) {
+    let genome = &mut current_genome.genome;
+    ui.label(egui::RichText::new(
+        "Touching cells of different organisms bond when their modes' entry allows it. \
+         Row: the cell's mode, column: the partner's. Click cycles off / always / chance, right-click edits."
+    ).small().weak());
+    ui.horizontal(|ui| {
+        ui.label(format!("{} rules", genome.contact_adhesion.rules.len()));
+        if ui.add_enabled(!genome.contact_adhesion.is_empty(), egui::Button::new("Clear")).clicked() {
+            genome.contact_adhesion.rules.clear();
+            editor.selected_contact_rule = None;
+        }
+    });
+    ui.separator();
+
+    let mode_count = genome.modes.len();
+    let colors: Vec<egui::Color32> = genome.modes.iter()
+        .map(|mode| egui::Color32::from_rgb(
+            (mode.color.x * 255.0) as u8,
+            (mode.color.y * 255.0) as u8,
+            (mode.color.z * 255.0) as u8,
+        ))
+        .collect();
+
+    egui::ScrollArea::both().max_height((ui.available_height() - 80.0).max(100.0)).show(ui, |ui| {
+        ui.spacing_mut().item_spacing = egui::vec2(1.0, 1.0);
+        // Header row of partner mode colors
+        ui.horizontal(|ui| {
+            ui.add_space(CELL_SIZE + 1.0);
+            for (partner, color) in colors.iter().enumerate() {
+                swatch(ui, *color).on_hover_text(genome.modes[partner].name.as_str());
+            }
+        });
+        for mode in 0..mode_count {
+            ui.horizontal(|ui| {
+                swatch(ui, colors[mode]).on_hover_text(genome.modes[mode].name.as_str());
+                for partner in 0..mode_count {
+                    let state = genome.contact_adhesion.state(mode, partner);
+                    let selected = editor.selected_contact_rule == Some((mode, partner));
+                    let response = matrix_cell(ui, state, selected).on_hover_text(format!(
+                        "{} → {}: {}",
+                        genome.modes[mode].name,
+                        genome.modes[partner].name,
+                        describe(genome.contact_adhesion.rule(mode, partner).map(|rule| rule.probability)),
+                    ));
+                    if response.clicked() {
+                        genome.contact_adhesion.cycle(mode, partner);
+                        editor.selected_contact_rule = Some((mode, partner));
+                    }
+                    if response.secondary_clicked() {
+                        editor.selected_contact_rule = Some((mode, partner));
+                    }
+                }
+            });
+        }
+    });
+
+    ui.separator();
+    let Some((mode, partner)) = editor.selected_contact_rule.filter(|&(m, p)| m < mode_count && p < mode_count) else {
+        ui.label("Select an entry to edit its chance and budget.");
+        return;
+    };
+    ui.label(egui::RichText::new(format!("{} → {}", genome.modes[mode].name, genome.modes[partner].name)).strong());
+    let Some(rule) = genome.contact_adhesion.rule_mut(mode, partner) else {
+        ui.label("Off");
+        return;
+    };
+    egui::Grid::new("contact_adhesion_rule").num_columns(2).show(ui, |ui| {
+        ui.label("Chance per tick");
+        ui.add(egui::Slider::new(&mut rule.probability, 0.0..=1.0).fixed_decimals(2));
+        ui.end_row();
+        ui.label("Max new bonds per tick");
+        ui.add(egui::DragValue::new(&mut rule.max_new_bonds_per_tick).range(1..=256));
+        ui.end_row();
+    });
+}
+
+fn describe(probability: Option<f32>) -> String {
+    match probability {
+        None => "off".to_string(),
+        Some(p) if p >= 1.0 => "always".to_string(),
+        Some(p) => format!("{:.0}% chance", p * 100.0),
+    }
+}
+
+fn swatch(ui: &mut egui::Ui, color: egui::Color32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(CELL_SIZE, CELL_SIZE), egui::Sense::hover());
+    ui.painter().rect_filled(rect.shrink(2.0), 2.0, color);
+    response
+}
+
+fn matrix_cell(ui: &mut egui::Ui, state: ContactAdhesionState, selected: bool) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(CELL_SIZE, CELL_SIZE), egui::Sense::click());
+    let visuals = ui.visuals();
+    let fill = match state {
+        ContactAdhesionState::Off => visuals.faint_bg_color,
+        ContactAdhesionState::Always => visuals.selection.bg_fill,
+        ContactAdhesionState::Chance => visuals.selection.bg_fill.gamma_multiply(0.45),
+    };
+    let stroke = if selected || response.hovered() {
+        visuals.selection.stroke
+    } else {
+        visuals.widgets.noninteractive.bg_stroke
+    };
+    ui.painter().rect(rect, 1.0, fill, stroke, egui::StrokeKind::Inside);
+    response
+}
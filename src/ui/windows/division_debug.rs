@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use crate::genome::CurrentGenome;
+use crate::simulation::division_debug::{DivisionDebug, DivisionRecord, DivisionStatus};
+
+/// Cells due to divide, why the blocked ones don't, and blocking reasons per mode over time
+pub fn render(ui: &mut egui::Ui, debug: &mut DivisionDebug, current_genome: &CurrentGenome) {
+    // Keeps the dry run running while this panel is drawn
+    debug.window_open = true;
+
+    let mode_name = |mode_index: usize| {
+        current_genome.genome.modes.get(mode_index).map_or("?", |mode| mode.name.as_str())
+    };
+    let dividing = debug.records.iter().filter(|r| r.status == DivisionStatus::Divides).count();
+    ui.label(format!("Next tick: {} divide, {} blocked", dividing, debug.records.len() - dividing));
+    if debug.truncated > 0 {
+        ui.label(egui::RichText::new(format!("{} more not listed", debug.truncated)).small().weak());
+    }
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        egui::CollapsingHeader::new("Due cells")
+            .default_open(true)
+            .show(ui, |ui| {
+                if debug.records.is_empty() {
+                    ui.label("No cells are due to divide.");
+                    return;
+                }
+                let mut focus = None;
+                egui::Grid::new("division_debug_records")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new("Cell").strong());
+                        ui.label(egui::RichText::new("Mode").strong());
+                        ui.label(egui::RichText::new("Next tick").strong());
+                        ui.end_row();
+
+                        for record in &debug.records {
+                            if ui.selectable_label(false, record.cell_id.to_string())
+                                .on_hover_text("Select and focus this cell")
+                                .clicked()
+                            {
+                                focus = Some(record.cell_id);
+                            }
+                            ui.label(mode_name(record.mode_index)).on_hover_text(timing_text(record, current_genome));
+                            match record.status {
+                                DivisionStatus::Divides => ui.colored_label(egui::Color32::from_rgb(90, 190, 110), "Divides"),
+                                DivisionStatus::Blocked(block) => ui.label(block.name()),
+                            };
+                            ui.end_row();
+                        }
+                    });
+                if focus.is_some() {
+                    debug.focus_request = focus;
+                }
+            });
+
+        egui::CollapsingHeader::new("Blocking reasons by mode")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Over the last");
+                    ui.add(egui::DragValue::new(&mut debug.history_secs).speed(0.5).range(1.0..=60.0).suffix("s"));
+                });
+                let (summary, samples) = debug.summary();
+                if summary.is_empty() {
+                    ui.label("Nothing blocked.");
+                    return;
+                }
+                egui::Grid::new("division_debug_summary")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (mode_index, blocks) in &summary {
+                            for (block, count) in blocks {
+                                ui.label(mode_name(*mode_index));
+                                ui.label(block.name());
+                                ui.label(format!("{:.1} cells", *count as f32 / samples.max(1) as f32))
+                                    .on_hover_text("Average number of blocked cells per check (4 per second)");
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+    });
+}
+
+/// Why a cell is due now: its age against its resolved interval and the mode's phase jitter
+fn timing_text(record: &DivisionRecord, current_genome: &CurrentGenome) -> String {
+    let mut text = format!("Age {:.2}s, split interval {:.2}s", record.age, record.split_interval);
+    if let Some(mode) = current_genome.genome.modes.get(record.mode_index) {
+        if mode.split_phase_jitter > 0.0 {
+            let base = mode.split_interval;
+            text.push_str(&format!(
+                "\nPhase jitter shifts this mode's divisions by up to ±{:.2}s of its {:.2}s interval",
+                base * mode.split_phase_jitter.clamp(0.0, 1.0) * 0.5,
+                base,
+            ));
+        }
+    }
+    text
+}
+
+/// Select and follow the cell the Division Debug window asked for
+pub fn focus_requested_cell(
+    mut debug: ResMut<DivisionDebug>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut inspector: ResMut<crate::ui::windows::cell_inspector::CellInspectorState>,
+    mut camera_query: Query<&mut crate::ui::MainCamera>,
+    cell_query: Query<&crate::cell::CellPosition>,
+) {
+    let Some(cell_id) = debug.focus_request.take() else {
+        return;
+    };
+    focus_cell(cell_id, &sim_state, main_state.as_deref(), preview_state.as_deref(), &mut inspector, &mut camera_query, &cell_query);
+}
+
+/// Select a cell of the active simulation in the inspector and make the camera follow it
+pub fn focus_cell(
+    cell_id: u32,
+    sim_state: &crate::simulation::SimulationState,
+    main_state: Option<&crate::simulation::cpu_sim::MainSimState>,
+    preview_state: Option<&crate::simulation::preview_sim::PreviewSimState>,
+    inspector: &mut crate::ui::windows::cell_inspector::CellInspectorState,
+    camera_query: &mut Query<&mut crate::ui::MainCamera>,
+    cell_query: &Query<&crate::cell::CellPosition>,
+) {
+    let entity = match sim_state.mode {
+        crate::simulation::SimulationMode::Cpu => main_state.and_then(|s| s.id_to_entity.get(&cell_id).copied()),
+        crate::simulation::SimulationMode::Preview => preview_state.and_then(|s| {
+            let state = &s.canonical_state;
+            let index = state.cell_ids[..state.cell_count].iter().position(|&id| id == cell_id)?;
+            s.index_to_entity.get(index).copied().flatten()
+        }),
+        crate::simulation::SimulationMode::Gpu => None,
+    };
+    let Some(entity) = entity else {
+        return;
+    };
+
+    inspector.inspected_entity = Some(entity);
+    if let Ok(mut camera) = camera_query.single_mut() {
+        camera.followed_entity = Some(entity);
+        if let Ok(position) = cell_query.get(entity) {
+            camera.center = position.position;
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::genome::browser::{FileAction, GenomeFileEntry, GenomeSort};
+use crate::genome::{CurrentGenome, GenomeBrowser};
+use crate::ui::genome_editor::mode_thumbnails::{render_sprite, visual_hash, THUMBNAIL_SIZE};
+use crate::ui::{GenomeEditorState, Notifications};
+
+/// Width of an entry tile in the grid
+const TILE_WIDTH: f32 = 140.0;
+
+/// Initial-mode sprites by file, regenerated when the mode's look changes
+#[derive(Default)]
+pub struct BrowserThumbnails {
+    textures: HashMap<PathBuf, (u64, egui::TextureHandle)>,
+}
+
+impl BrowserThumbnails {
+    fn texture(&mut self, ctx: &egui::Context, entry: &GenomeFileEntry) -> Option<egui::TextureId> {
+        let mode = entry.summary.as_ref().ok()?.initial_mode.as_ref()?;
+        let hash = visual_hash(mode);
+        let cached = self.textures.get(&entry.path).filter(|(cached, _)| *cached == hash);
+        if let Some((_, texture)) = cached {
+            return Some(texture.id());
+        }
+        let image = render_sprite(mode.color, mode.opacity, mode.emissive);
+        let texture = ctx.load_texture(format!("genome_browser_{}", entry.path.display()), image, egui::TextureOptions::LINEAR);
+        let id = texture.id();
+        self.textures.insert(entry.path.clone(), (hash, texture));
+        Some(id)
+    }
+}
+
+/// In-app Open Genome window: a scanned genomes folder with thumbnails, sorting and filtering
+pub fn render_genome_browser(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut editor_state: ResMut<GenomeEditorState>,
+    mut browser: ResMut<GenomeBrowser>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut notifications: ResMut<Notifications>,
+    mut dormant: ResMut<crate::simulation::DormantScenes>,
+    mut thumbnails: Local<BrowserThumbnails>,
+    mut was_open: Local<bool>,
+) {
+    let open = editor_state.genome_browser_open;
+    if open && !*was_open {
+        browser.rescan();
+    }
+    *was_open = open;
+    if !open {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+    let ctx = egui_context.get_mut();
+
+    let mut load = None;
+    let mut keep_open = true;
+    egui::Window::new("Open Genome")
+        .open(&mut keep_open)
+        .default_size([640.0, 460.0])
+        .collapsible(false)
+        .show(ctx, |ui| {
+            render_toolbar(ui, &mut browser, &mut notifications, &mut load);
+            ui.separator();
+            render_entries(ui, &mut browser, &mut thumbnails, &mut load);
+        });
+
+    if let Some(action) = render_confirmation(ctx, &mut browser) {
+        match browser.perform(action) {
+            Ok(message) => notifications.success(message),
+            Err(e) => notifications.error(&e),
+        }
+    }
+
+    if let Some(path) = load {
+        match crate::genome::GenomeData::load_from_file(&path) {
+            Ok(genome) if !dormant.is_empty() => {
+                // Kept scenes were simulated with the genome being replaced; ask first
+                dormant.deferred_load = Some(crate::simulation::dormant_scenes::DeferredLoad::Genome(genome));
+                keep_open = false;
+            }
+            Ok(genome) => {
+                notifications.success(format!("Loaded genome '{}'", genome.name));
+                current_genome.replace(genome, 0, None);
+                keep_open = false;
+            }
+            Err(e) => notifications.error(&e),
+        }
+    }
+    editor_state.genome_browser_open = keep_open;
+}
+
+fn render_toolbar(ui: &mut egui::Ui, browser: &mut GenomeBrowser, notifications: &mut Notifications, load: &mut Option<PathBuf>) {
+    ui.horizontal(|ui| {
+        ui.label("Folder:");
+        ui.label(egui::RichText::new(browser.root.display().to_string()).monospace());
+        if ui.small_button("Change...").on_hover_text("Choose the directory scanned for genomes").clicked() {
+            if let Some(root) = rfd::FileDialog::new().set_directory(&browser.root).pick_folder() {
+                browser.set_root(root.clone());
+                let mut settings = crate::ui::UiSettings::load();
+                settings.genome_directory = root;
+                if let Err(e) = settings.save() {
+                    notifications.error(&e);
+                }
+            }
+        }
+        if ui.small_button("Refresh").clicked() {
+            browser.rescan();
+        }
+        if browser.is_scanning() {
+            ui.spinner();
+        }
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Browse...").on_hover_text("Load a genome from anywhere with the system file dialog").clicked() {
+                if let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+                    *load = Some(path);
+                }
+            }
+        });
+    });
+
+    ui.horizontal(|ui| {
+        let at_root = browser.current_dir == browser.root;
+        if ui.add_enabled(!at_root, egui::Button::new("Up")).clicked() {
+            if let Some(parent) = browser.current_dir.parent() {
+                browser.current_dir = parent.to_path_buf();
+            }
+        }
+        let relative = browser.current_dir.strip_prefix(&browser.root).unwrap_or(Path::new(""));
+        ui.label(egui::RichText::new(format!("/{}", relative.to_string_lossy().replace('\\', "/"))).weak());
+
+        ui.separator();
+        ui.add(egui::TextEdit::singleline(&mut browser.filter).hint_text("Filter").desired_width(140.0));
+
+        egui::ComboBox::from_id_salt("genome_browser_sort")
+            .selected_text(browser.sort.name())
+            .show_ui(ui, |ui| {
+                for sort in GenomeSort::ALL {
+                    ui.selectable_value(&mut browser.sort, sort, sort.name());
+                }
+            });
+        let direction = if browser.descending { "Descending" } else { "Ascending" };
+        if ui.small_button(if browser.descending { "v" } else { "^" }).on_hover_text(direction).clicked() {
+            browser.descending = !browser.descending;
+        }
+    });
+}
+
+fn render_entries(ui: &mut egui::Ui, browser: &mut GenomeBrowser, thumbnails: &mut BrowserThumbnails, load: &mut Option<PathBuf>) {
+    if let Some(error) = &browser.scan_error {
+        ui.colored_label(egui::Color32::from_rgb(225, 85, 85), error.as_str());
+    }
+
+    let mut open_folder = None;
+    let mut action = None;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        // Folders hide behind a filter, which already searches below the current folder
+        if browser.filter.trim().is_empty() {
+            for folder in browser.subfolders() {
+                let name = folder.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                if ui.selectable_label(false, format!("[folder] {}", name)).clicked() {
+                    open_folder = Some(folder.clone());
+                }
+            }
+        }
+
+        let entries = browser.visible_entries();
+        if entries.is_empty() && !browser.is_scanning() {
+            ui.label("No genomes here.");
+        }
+        ui.horizontal_wrapped(|ui| {
+            for entry in entries {
+                let response = render_tile(ui, entry, thumbnails);
+                if response.double_clicked() {
+                    *load = Some(entry.path.clone());
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Rename...").clicked() {
+                        action = Some(FileAction::Rename { path: entry.path.clone(), new_name: entry.file_stem() });
+                        ui.close();
+                    }
+                    if ui.button("Duplicate...").clicked() {
+                        action = Some(FileAction::Duplicate { path: entry.path.clone(), new_name: format!("{} copy", entry.file_stem()) });
+                        ui.close();
+                    }
+                    if ui.button("Delete...").clicked() {
+                        action = Some(FileAction::Delete { path: entry.path.clone() });
+                        ui.close();
+                    }
+                });
+            }
+        });
+    });
+
+    if let Some(folder) = open_folder {
+        browser.current_dir = folder;
+    }
+    if action.is_some() {
+        browser.pending_action = action;
+    }
+}
+
+/// One entry: thumbnail, name, mode count, modification date and notes
+fn render_tile(ui: &mut egui::Ui, entry: &GenomeFileEntry, thumbnails: &mut BrowserThumbnails) -> egui::Response {
+    let frame = egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.set_width(TILE_WIDTH);
+        // Clicks go to the whole tile rather than selecting label text
+        ui.style_mut().interaction.selectable_labels = false;
+        ui.vertical_centered(|ui| {
+            let size = egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32) * 1.5;
+            match thumbnails.texture(ui.ctx(), entry) {
+                Some(texture) => {
+                    ui.add(egui::Image::new((texture, size)));
+                }
+                None => {
+                    // Placeholder for files without a readable mode
+                    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+                    let stroke = egui::Stroke::new(1.5, ui.visuals().weak_text_color());
+                    ui.painter().circle_stroke(rect.center(), size.x * 0.4, stroke);
+                    ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, "?", egui::FontId::proportional(18.0), ui.visuals().weak_text_color());
+                }
+            }
+            ui.label(egui::RichText::new(entry.display_name()).strong());
+        });
+
+        match &entry.summary {
+            Ok(summary) => {
+                let modified = entry.modified
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|elapsed| crate::simulation::experiment_session::format_utc(elapsed.as_secs()))
+                    .unwrap_or_else(|| "unknown date".to_string());
+                ui.label(egui::RichText::new(format!("{} modes", summary.mode_count)).small());
+                ui.label(egui::RichText::new(modified).small().weak());
+                if !summary.notes.is_empty() {
+                    let first_line = summary.notes.lines().next().unwrap_or_default();
+                    ui.add(egui::Label::new(egui::RichText::new(first_line).small().italics()).truncate())
+                        .on_hover_text(summary.notes.as_str());
+                }
+            }
+            Err(error) => {
+                ui.colored_label(egui::Color32::from_rgb(230, 180, 60), "Not a valid genome").on_hover_text(error.as_str());
+            }
+        }
+    });
+
+    let id = egui::Id::new(("genome_browser_tile", &entry.path));
+    ui.interact(frame.response.rect, id, egui::Sense::click())
+        .on_hover_text(format!("{}\nDouble-click to load, right-click for more", entry.path.display()))
+}
+
+/// Confirmation window for the pending rename, duplicate or delete; returns it once confirmed
+fn render_confirmation(ctx: &egui::Context, browser: &mut GenomeBrowser) -> Option<FileAction> {
+    let action = browser.pending_action.as_mut()?;
+    let title = match action {
+        FileAction::Rename { .. } => "Rename Genome File",
+        FileAction::Duplicate { .. } => "Duplicate Genome File",
+        FileAction::Delete { .. } => "Delete Genome File",
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            match action {
+                FileAction::Rename { path, new_name } | FileAction::Duplicate { path, new_name } => {
+                    ui.label(format!("New name for a file next to {}:", path.display()));
+                    let response = ui.text_edit_singleline(new_name);
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        confirmed = true;
+                    }
+                }
+                FileAction::Delete { path } => {
+                    ui.label(format!("Delete {}? This cannot be undone.", path.display()));
+                }
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let confirm = match action {
+                    FileAction::Rename { .. } => "Rename",
+                    FileAction::Duplicate { .. } => "Duplicate",
+                    FileAction::Delete { .. } => "Delete",
+                };
+                if ui.button(confirm).clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if cancelled {
+        browser.pending_action = None;
+    }
+    if confirmed {
+        browser.pending_action.take()
+    } else {
+        None
+    }
+}
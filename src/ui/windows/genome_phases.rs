@@ -0,0 +1,144 @@
+use bevy_egui::egui;
+use crate::genome::phases::{apply_overrides, numeric_overrides};
+use crate::genome::{CurrentGenome, GenomePhase, PhaseCondition};
+use crate::ui::GenomeEditorState;
+
+/// Settings panel the phase overrides are edited in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverridePanel {
+    #[default]
+    Parent,
+    Adhesion,
+}
+
+/// Phase table (name, start condition, overridden modes) and the override editor of the selected phase
+///
+/// Overrides are edited with the regular Parent/Adhesion Settings panels on a copy of
+/// the mode; whatever numeric fields end up differing from the base mode are stored.
+pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, editor: &mut GenomeEditorState) {
+    let genome = &mut current_genome.genome;
+    ui.horizontal(|ui| {
+        if ui.button("Add Phase").clicked() {
+            let start = genome.phases.iter()
+                .filter_map(|phase| match phase.condition {
+                    PhaseCondition::Time(start) => Some(start),
+                    PhaseCondition::Divisions(_) => None,
+                })
+                .fold(0.0, f32::max) + 30.0;
+            genome.phases.push(GenomePhase::new(format!("Phase {}", genome.phases.len() + 1), PhaseCondition::Time(start)));
+            editor.selected_phase = Some(genome.phases.len() - 1);
+        }
+    });
+    ui.label(egui::RichText::new(
+        "The last phase whose condition holds is in effect; before the first one the genome runs unchanged."
+    ).small().weak());
+    ui.separator();
+
+    if genome.phases.is_empty() {
+        ui.label("No phases.");
+        return;
+    }
+
+    let mut remove = None;
+    egui::Grid::new("genome_phases")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for (index, phase) in genome.phases.iter_mut().enumerate() {
+                if ui.selectable_label(editor.selected_phase == Some(index), format!("{}", index + 1))
+                    .on_hover_text("Edit this phase's overrides")
+                    .clicked()
+                {
+                    editor.selected_phase = Some(index);
+                }
+                ui.add(egui::TextEdit::singleline(&mut phase.name).desired_width(100.0));
+                ui.horizontal(|ui| render_condition(ui, index, &mut phase.condition));
+                if ui.small_button("x").on_hover_text("Remove").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(index) = remove {
+        genome.phases.remove(index);
+        editor.selected_phase = None;
+    }
+
+    let Some(phase_index) = editor.selected_phase.filter(|&i| i < genome.phases.len()) else {
+        return;
+    };
+    ui.separator();
+
+    let mode_count = genome.modes.len();
+    let mode_index = editor.phase_override_mode.min(mode_count.saturating_sub(1));
+    let phase = &genome.phases[phase_index];
+    ui.horizontal(|ui| {
+        ui.label(format!("{} overrides for", phase.name));
+        egui::ComboBox::from_id_salt("phase_override_mode")
+            .selected_text(genome.modes.get(mode_index).map_or("?", |m| m.name.as_str()))
+            .show_ui(ui, |ui| {
+                for (index, mode) in genome.modes.iter().enumerate() {
+                    let marker = if phase.overrides_for(index).is_some() { " •" } else { "" };
+                    ui.selectable_value(&mut editor.phase_override_mode, index, format!("{}{}", mode.name, marker));
+                }
+            });
+    });
+    let Some(base_mode) = genome.modes.get(mode_index) else {
+        return;
+    };
+
+    let overridden = phase.overrides_for(mode_index).cloned().unwrap_or_default();
+    ui.horizontal(|ui| {
+        let fields: Vec<&str> = overridden.keys().map(String::as_str).collect();
+        ui.label(egui::RichText::new(if fields.is_empty() {
+            "Nothing overridden".to_string()
+        } else {
+            format!("Overridden: {}", fields.join(", "))
+        }).small());
+        if !fields.is_empty() && ui.small_button("Reset").on_hover_text("Use the base settings for this mode").clicked() {
+            genome.phases[phase_index].set_overrides(mode_index, Default::default());
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut editor.phase_override_panel, OverridePanel::Parent, "Parent Settings");
+        ui.selectable_value(&mut editor.phase_override_panel, OverridePanel::Adhesion, "Adhesion Settings");
+    });
+
+    // Run the regular panels on a genome whose selected mode shows the phase's values
+    let mut edited_genome = genome.clone();
+    edited_genome.modes[mode_index] = apply_overrides(base_mode, &overridden);
+    let mut scratch = CurrentGenome::scratch(edited_genome, mode_index as i32);
+    ui.push_id(("phase_overrides", phase_index, mode_index), |ui| match editor.phase_override_panel {
+        OverridePanel::Parent => crate::ui::genome_editor::render_parent_settings(ui, &mut scratch),
+        OverridePanel::Adhesion => crate::ui::genome_editor::render_adhesion_settings(ui, &mut scratch),
+    });
+
+    let values = numeric_overrides(base_mode, &scratch.genome.modes[mode_index]);
+    if values != overridden {
+        genome.phases[phase_index].set_overrides(mode_index, values);
+    }
+}
+
+fn render_condition(ui: &mut egui::Ui, index: usize, condition: &mut PhaseCondition) {
+    let is_time = matches!(condition, PhaseCondition::Time(_));
+    egui::ComboBox::from_id_salt(("phase_condition", index))
+        .selected_text(if is_time { "Time" } else { "Divisions" })
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(is_time, "Time").clicked() && !is_time {
+                *condition = PhaseCondition::Time(60.0);
+            }
+            if ui.selectable_label(!is_time, "Divisions").clicked() && is_time {
+                *condition = PhaseCondition::Divisions(100);
+            }
+        });
+    match condition {
+        PhaseCondition::Time(start) => {
+            ui.label("from");
+            ui.add(egui::DragValue::new(start).speed(0.5).range(0.0..=100_000.0).suffix("s"));
+        }
+        PhaseCondition::Divisions(count) => {
+            ui.label("after");
+            ui.add(egui::DragValue::new(count).range(0..=u64::MAX));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use serde::{Deserialize, Serialize};
+use crate::genome::{forecast_populations, CurrentGenome, GenomeData, GenomeHistory, GenomeNodeGraph, GrowthCycle};
+use crate::simulation::{SimulationMode, SimulationState};
+
+/// Forecast samples across the guardrail horizon
+const FORECAST_STEPS: usize = 300;
+
+/// Whether and how far ahead the edited genome's population is checked against capacity
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrowthGuardrailSettings {
+    pub enabled: bool,
+    /// Seconds of forecast in which passing capacity raises the banner
+    pub horizon: f32,
+}
+
+impl Default for GrowthGuardrailSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            horizon: 30.0,
+        }
+    }
+}
+
+/// Last guardrail check and the remedy choices of its banner
+#[derive(Resource)]
+pub struct GrowthGuardrail {
+    /// Genome, capacity and horizon of the last check
+    checked: Option<(GenomeData, usize, f32)>,
+    /// When the forecast passes capacity and the cycles driving it
+    alert: Option<(f32, Vec<GrowthCycle>)>,
+    /// Cycle member the split limit goes on, and the limit
+    pub member: Option<usize>,
+    pub max_splits: i32,
+    /// Split interval multiplier of the slow-down remedy
+    pub factor: f32,
+}
+
+impl Default for GrowthGuardrail {
+    fn default() -> Self {
+        Self {
+            checked: None,
+            alert: None,
+            member: None,
+            max_splits: 4,
+            factor: 2.0,
+        }
+    }
+}
+
+impl GrowthGuardrail {
+    /// Re-run the forecast and cycle detection when the genome, capacity or horizon changed
+    fn check(&mut self, genome: &GenomeData, capacity: usize, horizon: f32) {
+        let stale = self.checked.as_ref().is_none_or(|(checked, checked_capacity, checked_horizon)| {
+            *checked_capacity != capacity || *checked_horizon != horizon || !checked.functionally_equal(genome)
+        });
+        if !stale {
+            return;
+        }
+        let forecast = forecast_populations(genome, horizon, FORECAST_STEPS);
+        self.alert = forecast.time_to_exceed(capacity as f64).map(|time| (time, genome.growth_cycles()));
+        self.checked = Some((genome.clone(), capacity, horizon));
+    }
+}
+
+/// Banner naming the division cycles that drive the edited genome past capacity within the
+/// guardrail horizon, with one-click remedies
+///
+/// Cycles the user acknowledged (saved with the genome) are left out; the banner never
+/// blocks editing or the simulation.
+pub fn render_growth_guardrail_banner(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    settings: Res<GrowthGuardrailSettings>,
+    mut guardrail: ResMut<GrowthGuardrail>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut history: ResMut<GenomeHistory>,
+    mut node_graph: ResMut<GenomeNodeGraph>,
+    cpu_cell_capacity: Res<crate::ui::CpuCellCapacity>,
+    sim_state: Res<SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    // Capacity of the simulation the edits run in
+    let capacity = match sim_state.mode {
+        SimulationMode::Preview => preview_state.as_deref().map(|state| state.canonical_state.capacity),
+        SimulationMode::Cpu => main_state.as_deref().map(|state| state.canonical_state.capacity),
+        SimulationMode::Gpu => None,
+    }
+    .unwrap_or(cpu_cell_capacity.capacity);
+    guardrail.check(&current_genome.genome, capacity, settings.horizon);
+
+    let Some((time, cycles)) = guardrail.alert.as_ref() else {
+        return;
+    };
+    let genome = &current_genome.genome;
+    let Some(cycle) = cycles.iter().find(|cycle| !genome.is_acknowledged(cycle)).cloned() else {
+        return;
+    };
+    let time = *time;
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let name = |mode: usize| genome.modes.get(mode).map_or_else(|| format!("Mode {}", mode), |mode| mode.name.clone());
+    let member = guardrail.member.filter(|member| cycle.modes.contains(member)).unwrap_or(cycle.modes[0]);
+    let mut limit = None;
+    let mut slow = false;
+    let mut acknowledge = false;
+    egui::Window::new("Runaway Growth")
+        .collapsible(true)
+        .resizable(false)
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-12.0, 40.0))
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(egui::RichText::new(format!(
+                "The forecast passes the {}-cell capacity after {:.1} s",
+                capacity, time
+            )).strong());
+            let names: Vec<String> = cycle.modes.iter().map(|&mode| name(mode)).collect();
+            ui.label(format!("Cycle: {} (doubles every {:.2} s)", names.join(", "), cycle.doubling_time));
+            ui.label(egui::RichText::new("Each division in this cycle keeps both children in it. Fine if intended; otherwise:").small().weak());
+            ui.separator();
+
+            let mut chosen = member;
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("growth_guardrail_member")
+                    .selected_text(name(chosen))
+                    .show_ui(ui, |ui| {
+                        for &mode in &cycle.modes {
+                            ui.selectable_value(&mut chosen, mode, name(mode));
+                        }
+                    });
+                ui.add(egui::DragValue::new(&mut guardrail.max_splits).range(1..=20).prefix("max splits "));
+                if ui.button("Set").on_hover_text("Stop cells of this mode dividing after that many splits in a row").clicked() {
+                    limit = Some((chosen, guardrail.max_splits));
+                }
+            });
+            let mut trial = genome.clone();
+            trial.modes[chosen].max_splits = guardrail.max_splits;
+            if trial.growth_cycles().iter().any(|cycle| cycle.modes.contains(&chosen)) {
+                ui.label(egui::RichText::new(format!(
+                    "Doesn't stop the cycle: split counts restart whenever cells re-enter {} from another mode",
+                    name(chosen)
+                )).small().weak());
+            }
+            guardrail.member = Some(chosen);
+
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut guardrail.factor).range(1.1..=10.0).speed(0.05).suffix("x"));
+                if ui.button("Slow Cycle").on_hover_text("Multiply the split intervals of every mode in the cycle").clicked() {
+                    slow = true;
+                }
+            });
+            if ui.button("Acknowledge").on_hover_text("Intended: stop warning about this cycle for this genome").clicked() {
+                acknowledge = true;
+            }
+        });
+
+    let genome = &mut current_genome.genome;
+    if let Some((mode, max_splits)) = limit {
+        if genome.limit_cycle_splits(mode, max_splits, &mut history) {
+            node_graph.mark_for_rebuild();
+            notifications.success(format!("Limited '{}' to {} splits", genome.modes[mode].name, max_splits));
+        }
+    }
+    if slow {
+        genome.slow_cycle(&cycle, guardrail.factor, &mut history);
+        node_graph.mark_for_rebuild();
+        notifications.success(format!("Slowed the cycle's divisions by {:.1}x", guardrail.factor));
+    }
+    if acknowledge {
+        genome.acknowledge_cycle(&cycle);
+        notifications.info("Runaway growth acknowledged; it is saved with the genome");
+    }
+}
+
+/// Guardrail switch and horizon (Population Forecast panel)
+pub fn render_settings(ui: &mut egui::Ui, settings: &mut GrowthGuardrailSettings) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.enabled, "Capacity guardrail")
+            .on_hover_text("Warn when division cycles would fill the simulation's capacity soon after the genome changes");
+        ui.add_enabled(
+            settings.enabled,
+            egui::Slider::new(&mut settings.horizon, 5.0..=600.0).logarithmic(true).text("within (s)"),
+        );
+    });
+}
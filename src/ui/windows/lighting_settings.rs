@@ -0,0 +1,100 @@
+use bevy_egui::egui;
+use crate::rendering::day_night::{DayNightCycle, LightKeyframe, MAX_KEYFRAMES, MIN_KEYFRAMES, TEMPERATURE_RANGE};
+use crate::ui::LightingConfig;
+
+/// Static main light and the day/night cycle that animates it over simulated time
+pub fn render(ui: &mut egui::Ui, lighting: &mut LightingConfig) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        ui.label(egui::RichText::new("Main light").strong());
+        ui.add_enabled_ui(!lighting.day_night.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Illuminance:");
+                ui.add(egui::DragValue::new(&mut lighting.directional_illuminance).speed(50.0).range(0.0..=100_000.0).suffix(" lx"));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Color:");
+                ui.color_edit_button_rgb(&mut lighting.directional_color);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Rotation:");
+                for angle in &mut lighting.directional_rotation {
+                    ui.add(egui::DragValue::new(angle).speed(0.5).range(-180.0..=180.0).suffix("°"));
+                }
+            });
+        });
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Day/night cycle").strong());
+        ui.checkbox(&mut lighting.day_night.enabled, "Animate over simulated time")
+            .on_hover_text("Sweeps the main light and skybox tint through the keyframes. \
+                            Follows the simulation clock, so pausing pauses it and captures are reproducible.");
+        render_cycle(ui, &mut lighting.day_night, &mut lighting.preview_phase);
+    });
+}
+
+fn render_cycle(ui: &mut egui::Ui, cycle: &mut DayNightCycle, preview_phase: &mut Option<f32>) {
+    ui.horizontal(|ui| {
+        ui.label("Cycle length:");
+        ui.add(egui::DragValue::new(&mut cycle.cycle_length).speed(1.0).range(1.0..=100_000.0).suffix(" s"));
+        ui.label(egui::RichText::new("simulated").small().weak());
+    });
+
+    ui.horizontal(|ui| {
+        let mut scrubbing = preview_phase.is_some();
+        if ui.checkbox(&mut scrubbing, "Preview").on_hover_text("Show a phase of the cycle instead of the simulated time").changed() {
+            *preview_phase = scrubbing.then_some(0.0);
+        }
+        if let Some(phase) = preview_phase.as_mut() {
+            ui.add(egui::Slider::new(phase, 0.0..=1.0).fixed_decimals(3));
+        }
+    });
+
+    let mut remove = None;
+    let mut reorder = false;
+    let count = cycle.keyframes.len();
+    egui::Grid::new("day_night_keyframes").striped(true).show(ui, |ui| {
+        for header in ["Phase", "Elevation", "Azimuth", "Temperature", "Illuminance", "Sky", ""] {
+            ui.label(egui::RichText::new(header).small().weak());
+        }
+        ui.end_row();
+        for (index, keyframe) in cycle.keyframes.iter_mut().enumerate() {
+            reorder |= render_keyframe(ui, keyframe);
+            if ui.add_enabled(count > MIN_KEYFRAMES, egui::Button::new("x").small())
+                .on_hover_text("Remove keyframe")
+                .clicked()
+            {
+                remove = Some(index);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(index) = remove {
+        cycle.remove_keyframe(index);
+    }
+    if reorder {
+        cycle.sort_keyframes();
+    }
+    if ui.add_enabled(count < MAX_KEYFRAMES, egui::Button::new("+ Keyframe"))
+        .on_hover_text(format!("Between {} and {} keyframes", MIN_KEYFRAMES, MAX_KEYFRAMES))
+        .clicked()
+    {
+        cycle.add_keyframe();
+    }
+}
+
+/// One keyframe's row; returns true when its phase was edited
+fn render_keyframe(ui: &mut egui::Ui, keyframe: &mut LightKeyframe) -> bool {
+    let phase_changed = ui.add(egui::DragValue::new(&mut keyframe.phase).speed(0.005).range(0.0..=0.999)).changed();
+    ui.add(egui::DragValue::new(&mut keyframe.elevation).speed(0.5).range(-90.0..=90.0).suffix("°"));
+    ui.add(egui::DragValue::new(&mut keyframe.azimuth).speed(0.5).range(0.0..=360.0).suffix("°"));
+    ui.add(egui::DragValue::new(&mut keyframe.temperature).speed(20.0).range(TEMPERATURE_RANGE).suffix(" K"));
+    ui.add(egui::DragValue::new(&mut keyframe.illuminance).speed(50.0).range(0.0..=100_000.0).suffix(" lx"));
+    ui.color_edit_button_rgb(&mut keyframe.sky_tint);
+    phase_changed
+}
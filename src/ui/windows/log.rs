@@ -0,0 +1,52 @@
+use bevy_egui::egui;
+use crate::ui::notifications::{NotificationLevel, Notifications};
+
+/// Message history (errors, warnings and confirmations), newest first
+pub fn render(ui: &mut egui::Ui, notifications: &mut Notifications) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut notifications.log_errors_only, "Errors only");
+        if ui.button("Clear").clicked() {
+            notifications.clear_history();
+        }
+    });
+    ui.separator();
+
+    let errors_only = notifications.log_errors_only;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        let mut any = false;
+        for (i, entry) in notifications.history().enumerate().rev() {
+            if errors_only && entry.level != NotificationLevel::Error {
+                continue;
+            }
+            any = true;
+
+            let minutes = (entry.timestamp / 60.0) as u32;
+            let seconds = entry.timestamp % 60.0;
+            let header = egui::RichText::new(format!(
+                "[{:02}:{:04.1}] {}: {}",
+                minutes, seconds, entry.level.label(), entry.message
+            )).color(entry.level.color());
+
+            match &entry.details {
+                Some(details) => {
+                    egui::CollapsingHeader::new(header)
+                        .id_salt(("log_entry", i))
+                        .show(ui, |ui| {
+                            ui.label(egui::RichText::new(details).monospace().small());
+                        });
+                }
+                None => {
+                    ui.label(header);
+                }
+            }
+        }
+
+        if !any {
+            ui.label("No messages.");
+        }
+    });
+}
@@ -0,0 +1,167 @@
+use bevy_egui::egui;
+use crate::genome::{CurrentGenome, GenomeHistory, GenomeNodeGraph, MaterialLibrary};
+use crate::ui::GenomeEditorState;
+
+/// User-level material presets: create from the selected mode, apply to the selected
+/// modes, re-sync linked modes, rename, delete and share as JSON
+///
+/// The library lives in the settings directory and is shared by every genome; each
+/// change to it is saved right away.
+pub fn render(
+    ui: &mut egui::Ui,
+    current_genome: &mut CurrentGenome,
+    editor: &mut GenomeEditorState,
+    library: &mut MaterialLibrary,
+    history: &mut GenomeHistory,
+    node_graph: &mut GenomeNodeGraph,
+    notifications: &mut crate::ui::Notifications,
+) {
+    let mode_count = current_genome.genome.modes.len();
+    let selected = current_genome.selected_mode_index.max(0) as usize;
+    // Multi-selected modes, else the selected one
+    let targets: Vec<usize> = if editor.selected_modes.len() > 1 {
+        editor.selected_modes.iter().copied().filter(|&index| index < mode_count).collect()
+    } else {
+        (selected < mode_count).then_some(selected).into_iter().collect()
+    };
+    let target_text = match targets.as_slice() {
+        [index] => format!("'{}'", current_genome.genome.modes[*index].name),
+        _ => format!("{} modes", targets.len()),
+    };
+
+    ui.label(egui::RichText::new(
+        "Named adhesion, bond limit and shape settings shared by every genome. \
+         Applying one links the mode to it so later edits of the preset can be re-synced."
+    ).small().weak());
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut editor.material_name).hint_text("Preset name").desired_width(140.0));
+        let name = editor.material_name.trim().to_string();
+        let replaces = library.get(&name).is_some();
+        let button = ui.add_enabled(!name.is_empty() && selected < mode_count, egui::Button::new(if replaces { "Update" } else { "Create" }))
+            .on_hover_text("Store the material of the selected mode under this name");
+        if button.clicked() {
+            library.add_from_mode(name.clone(), &current_genome.genome.modes[selected]);
+            save(library, notifications, format!("Saved material preset '{}'", name));
+            editor.material_name.clear();
+        }
+        if ui.button("Import...").clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Material preset", &["json"]).pick_file() {
+                match library.import_preset(&path) {
+                    Ok(name) => save(library, notifications, format!("Imported material preset '{}'", name)),
+                    Err(e) => notifications.error(&e),
+                }
+            }
+        }
+    });
+    ui.separator();
+
+    if library.presets.is_empty() {
+        ui.label("No presets yet. Select a mode and create one from it.");
+        return;
+    }
+
+    let mut remove = None;
+    let mut rename = None;
+    egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+        for preset in &library.presets {
+            let users: Vec<(usize, bool)> = current_genome.genome.material_users(&preset.name).collect();
+            ui.horizontal(|ui| {
+                match editor.material_rename.as_mut().filter(|(from, _)| *from == preset.name) {
+                    Some((_, to)) => {
+                        let response = ui.add(egui::TextEdit::singleline(to).desired_width(140.0));
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            rename = Some((preset.name.clone(), to.trim().to_string()));
+                        }
+                        if ui.small_button("Cancel").clicked() {
+                            rename = Some((preset.name.clone(), String::new()));
+                        }
+                    }
+                    None => {
+                        ui.label(egui::RichText::new(&preset.name).strong());
+                        ui.label(egui::RichText::new(format!(
+                            "stiffness {:.0}, damping {:.1}, break {:.0}",
+                            preset.material.adhesion_settings.linear_spring_stiffness,
+                            preset.material.adhesion_settings.linear_spring_damping,
+                            preset.material.adhesion_settings.break_force,
+                        )).small().weak());
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!targets.is_empty(), egui::Button::new("Apply"))
+                    .on_hover_text(format!("Apply to {}", target_text))
+                    .clicked()
+                {
+                    let changed = current_genome.genome.apply_material(preset, targets.iter().copied(), history);
+                    if changed > 0 {
+                        node_graph.mark_for_rebuild();
+                        notifications.success(format!("Applied '{}' to {} modes", preset.name, changed));
+                    }
+                }
+                let synced = users.iter().filter(|(_, synced)| *synced).count();
+                if ui.add_enabled(synced > 0, egui::Button::new(format!("Re-sync ({})", synced)))
+                    .on_hover_text("Bring the linked modes that follow this preset up to date with it")
+                    .clicked()
+                {
+                    let changed = current_genome.genome.resync_material(preset, history);
+                    if changed > 0 {
+                        node_graph.mark_for_rebuild();
+                    }
+                    notifications.info(format!("Re-synced '{}': {} modes changed", preset.name, changed));
+                }
+                if ui.small_button("Rename").clicked() {
+                    editor.material_rename = Some((preset.name.clone(), preset.name.clone()));
+                }
+                if ui.small_button("Export...").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Material preset", &["json"])
+                        .set_file_name(format!("{}.json", preset.name))
+                        .save_file()
+                    {
+                        match library.export_preset(&preset.name, &path) {
+                            Ok(()) => notifications.success(format!("Exported '{}' to {}", preset.name, path.display())),
+                            Err(e) => notifications.error(&e),
+                        }
+                    }
+                }
+                if ui.small_button("Delete").on_hover_text("Linked modes keep their values").clicked() {
+                    remove = Some(preset.name.clone());
+                }
+            });
+
+            // Linked modes with their opt-out of re-syncs
+            for (index, _) in users {
+                let mode = &mut current_genome.genome.modes[index];
+                let name = mode.name.clone();
+                if let Some(link) = mode.material.as_mut() {
+                    ui.checkbox(&mut link.synced, format!("  {}", name))
+                        .on_hover_text("Follow re-syncs of this preset (off keeps the mode's own values)");
+                }
+            }
+            ui.separator();
+        }
+    });
+
+    if let Some((from, to)) = rename {
+        if to.is_empty() || to == from {
+            editor.material_rename = None;
+        } else if library.rename(&from, to.clone()) {
+            current_genome.genome.rename_material_links(&from, &to);
+            editor.material_rename = None;
+            save(library, notifications, format!("Renamed material preset '{}' to '{}'", from, to));
+        } else {
+            notifications.warning(format!("A material preset named '{}' already exists", to));
+        }
+    }
+    if let Some(name) = remove {
+        library.remove(&name);
+        save(library, notifications, format!("Deleted material preset '{}'", name));
+    }
+}
+
+fn save(library: &MaterialLibrary, notifications: &mut crate::ui::Notifications, message: String) {
+    match library.save() {
+        Ok(()) => notifications.success(message),
+        Err(e) => notifications.error(&e),
+    }
+}
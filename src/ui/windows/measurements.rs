@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::input::measurement::{MeasurementKind, Measurements};
+use crate::simulation::contact_graph::{ContactGraphExport, GraphFormat};
+use crate::simulation::{CellGroups, SimulationMode};
+use crate::ui::Notifications;
+
+pub fn render(
+    ui: &mut egui::Ui,
+    measurements: &mut Measurements,
+    cell_groups: &CellGroups,
+    contact_graph: &mut ContactGraphExport,
+    mode: SimulationMode,
+    notifications: &mut Notifications,
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        // Tool mode
+        ui.label("Tool:");
+        ui.horizontal_wrapped(|ui| {
+            let tools = [
+                (None, "Off"),
+                (Some(MeasurementKind::Distance), "Distance"),
+                (Some(MeasurementKind::Angle), "Angle"),
+                (Some(MeasurementKind::StructureRadius), "Structure radius"),
+            ];
+            for (tool, label) in tools {
+                if ui.selectable_label(measurements.active_tool == tool, label).clicked() {
+                    measurements.set_tool(tool);
+                }
+            }
+        });
+        if let Some(tool) = measurements.active_tool {
+            let remaining = tool.anchor_count() - measurements.pending.len();
+            let hint = match tool {
+                MeasurementKind::StructureRadius => "Click a cell of the organism to measure".to_string(),
+                _ => format!("Click {} more cell(s) or points on the world sphere", remaining),
+            };
+            ui.label(egui::RichText::new(hint).weak());
+            ui.label(egui::RichText::new("Esc to stop measuring").weak());
+
+            if tool == MeasurementKind::StructureRadius && !cell_groups.groups.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Or a group:");
+                    for group in &cell_groups.groups {
+                        if ui.small_button(&group.name).clicked() {
+                            measurements.add_group_radius(group);
+                        }
+                    }
+                });
+            }
+        }
+
+        ui.separator();
+
+        if measurements.items.is_empty() {
+            ui.label("No measurements.");
+        }
+
+        let mut delete = None;
+        for (i, measurement) in measurements.items.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut measurement.visible, "")
+                    .on_hover_text("Show in viewport");
+                ui.add(egui::TextEdit::singleline(&mut measurement.name).desired_width(120.0));
+                match measurement.value {
+                    Some(value) => ui.label(measurement.kind.format_value(value)),
+                    None => ui.label(egui::RichText::new("lost").weak())
+                        .on_hover_text("An anchored cell or group no longer exists"),
+                };
+                ui.checkbox(&mut measurement.log_over_time, "Log")
+                    .on_hover_text(format!("Record over time for CSV export ({} samples)", measurement.samples.len()));
+                if ui.small_button("x").on_hover_text("Delete").clicked() {
+                    delete = Some(i);
+                }
+            });
+        }
+        if let Some(i) = delete {
+            measurements.items.remove(i);
+        }
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Log interval:");
+            ui.add(egui::DragValue::new(&mut measurements.sample_interval)
+                .speed(0.1)
+                .range(0.01..=600.0)
+                .suffix(" s"));
+        });
+
+        ui.horizontal(|ui| {
+            let has_samples = measurements.items.iter().any(|m| !m.samples.is_empty());
+            if ui.add_enabled(has_samples, egui::Button::new("Export CSV...")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CSV", &["csv"])
+                    .set_file_name("measurements.csv")
+                    .save_file()
+                {
+                    match crate::error::write_atomically(&path, measurements.to_csv().as_bytes()) {
+                        Ok(()) => notifications.success(format!("Exported measurements to {}", path.display())),
+                        Err(e) => notifications.error(&e),
+                    }
+                }
+            }
+            if ui.add_enabled(!measurements.items.is_empty(), egui::Button::new("Clear All")).clicked() {
+                measurements.items.clear();
+            }
+        });
+
+        ui.separator();
+        render_contact_graph(ui, contact_graph, mode);
+    });
+}
+
+/// Export of the adhesion and collision contact graph, now or as a time series
+fn render_contact_graph(ui: &mut egui::Ui, export: &mut ContactGraphExport, mode: SimulationMode) {
+    ui.label(egui::RichText::new("Contact Graph").strong());
+    egui::ComboBox::from_label("Format")
+        .selected_text(export.format.name())
+        .show_ui(ui, |ui| {
+            for format in GraphFormat::ALL {
+                ui.selectable_value(&mut export.format, format, format.name());
+            }
+        });
+
+    if ui.button("Export Contact Graph...")
+        .on_hover_text("Cells with their adhesions and the latest tick's collision contacts")
+        .clicked()
+    {
+        let path = match export.format {
+            GraphFormat::GraphMl => rfd::FileDialog::new()
+                .add_filter("GraphML", &["graphml"])
+                .set_file_name("contacts.graphml")
+                .save_file(),
+            GraphFormat::Csv => rfd::FileDialog::new()
+                .set_title("Folder for nodes.csv and edges.csv")
+                .pick_folder(),
+        };
+        if let Some(path) = path {
+            export.export(path);
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Series: every");
+        ui.add(egui::DragValue::new(&mut export.series_interval).range(1..=100_000).suffix(" ticks"));
+        ui.label("for");
+        ui.add(egui::DragValue::new(&mut export.series_duration).speed(0.5).range(0.0..=86_400.0).suffix(" s"));
+    });
+    match export.series_progress() {
+        Some((graphs, covered, total)) => {
+            ui.horizontal(|ui| {
+                ui.add(egui::ProgressBar::new(covered as f32 / total.max(1) as f32)
+                    .desired_width(140.0)
+                    .text(format!("{} graphs", graphs)));
+                if ui.button("Stop").on_hover_text("Write the graphs recorded so far").clicked() {
+                    export.stop_series();
+                }
+            });
+        }
+        None => {
+            let response = ui.add_enabled(mode == SimulationMode::Cpu, egui::Button::new("Record Time Series..."))
+                .on_disabled_hover_text("Time series record in CPU mode");
+            if response.clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Zip", &["zip"])
+                    .set_file_name("contacts.zip")
+                    .save_file()
+                {
+                    export.start_series(path);
+                }
+            }
+        }
+    }
+    if export.pending_writes() > 0 {
+        ui.label(egui::RichText::new("Writing...").weak());
+    }
+}
+
+/// Draw measurement values next to their lines in the viewport
+pub fn draw_measurement_labels(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    measurements: Res<Measurements>,
+    viewport_rect: Res<crate::ui::ViewportRect>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<crate::ui::MainCamera>>,
+    regions: Res<crate::rendering::RegionVisibility>,
+) {
+    let Some(viewport) = viewport_rect.rect else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    for mut egui_context in contexts.iter_mut() {
+        let ctx = egui_context.get_mut();
+        let painter = ctx
+            .layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("measurement_labels")))
+            .with_clip_rect(viewport);
+        let zoom = ctx.zoom_factor();
+
+        for measurement in measurements.items.iter().filter(|m| m.visible) {
+            let (Some(value), Some(world_position)) = (measurement.value, measurement.label_position()) else {
+                continue;
+            };
+            if regions.hides(world_position) {
+                continue;
+            }
+            // Logical window pixels to egui points
+            let Ok(screen) = camera.world_to_viewport(camera_transform, world_position) else {
+                continue;
+            };
+            let position = egui::pos2(screen.x / zoom, screen.y / zoom);
+
+            let text = format!("{}: {}", measurement.name, measurement.kind.format_value(value));
+            let galley = painter.layout_no_wrap(text, egui::FontId::proportional(13.0), egui::Color32::from_rgb(255, 217, 51));
+            let rect = egui::Rect::from_min_size(position + egui::vec2(6.0, -galley.size().y - 2.0), galley.size())
+                .expand(2.0);
+            painter.rect_filled(rect, 2.0, egui::Color32::from_black_alpha(160));
+            painter.galley(rect.min + egui::vec2(2.0, 2.0), galley, egui::Color32::WHITE);
+        }
+    }
+}
@@ -5,6 +5,34 @@ pub mod adhesion_settings;
 pub mod name_type_editor;
 pub mod parent_settings;
 pub mod scene_manager;
+pub mod performance_monitor;
+pub mod cell_inspector;
+pub mod physics_settings;
+pub mod log;
+pub mod measurements;
+pub mod parameter_sweep;
+pub mod session_browser;
+pub mod division_debug;
+pub mod breakpoints;
+pub mod cell_groups;
+pub mod lighting_settings;
+pub mod genome_browser;
+pub mod tissue_stamp;
+pub mod capability_report;
+pub mod safe_mode_banner;
+pub mod batch_edit;
+pub mod genome_phases;
+pub mod contact_adhesion;
+pub mod population_forecast;
+pub mod material_library;
+pub mod soak_test;
+pub mod scene_load_progress;
+pub mod scene_inspector;
+pub mod spectator_banner;
+pub mod staged_genome_banner;
+pub mod pick_stack;
+pub mod growth_guardrail;
+pub mod camera_path;
 
 // Re-export rendering functions with consistent naming
 pub use modes::render_modes_panel;
@@ -12,3 +40,20 @@ pub use adhesion_settings::render as render_adhesion_settings;
 pub use name_type_editor::render as render_name_type_editor;
 pub use parent_settings::render as render_parent_settings;
 pub use scene_manager::render as render_scene_manager;
+pub use performance_monitor::render as render_performance_monitor;
+pub use cell_inspector::render as render_cell_inspector;
+pub use physics_settings::render as render_physics_settings;
+pub use log::render as render_log;
+pub use measurements::render as render_measurements;
+pub use parameter_sweep::render as render_parameter_sweep;
+pub use session_browser::render as render_session_browser;
+pub use division_debug::render as render_division_debug;
+pub use breakpoints::render as render_breakpoints;
+pub use cell_groups::render as render_cell_groups;
+pub use lighting_settings::render as render_lighting_settings;
+pub use batch_edit::render as render_batch_edit;
+pub use genome_phases::render as render_genome_phases;
+pub use contact_adhesion::render as render_contact_adhesion;
+pub use population_forecast::render as render_population_forecast;
+pub use material_library::render as render_material_library;
+pub use camera_path::render as render_camera_path;
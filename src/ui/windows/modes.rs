@@ -1,10 +1,20 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crate::genome::{CurrentGenome, ModeSettings};
+use crate::genome::{CellPattern, ColorPalette, CurrentGenome, ModeIndex, ModeSettings, ModeTemplates, RemovedModePolicy};
+use crate::simulation::{ModeEdit, ModeEdits};
 use crate::ui::GenomeEditorState;
 use crate::ui::widgets;
 
-pub fn render_modes_panel(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, genome_editor_state: &mut GenomeEditorState) {
+pub fn render_modes_panel(
+    ui: &mut egui::Ui,
+    current_genome: &mut CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    templates: &mut ModeTemplates,
+    mode_edits: &mut ModeEdits,
+    notifications: &mut crate::ui::Notifications,
+) {
+    render_removal_prompt(ui.ctx(), current_genome, mode_edits);
+
     // Handle rename dialog (outside scroll area)
     let mut rename_confirmed = false;
     let mut rename_cancelled = false;
@@ -59,9 +69,71 @@ pub fn render_modes_panel(ui: &mut egui::Ui, current_genome: &mut CurrentGenome,
         ui,
         current_genome.genome.modes.len(),
         current_genome.selected_mode_index as usize,
-        current_genome.genome.initial_mode as usize,
+        current_genome.genome.initial_mode_index(),
     );
 
+    // New modes start from an archetype or a saved template
+    ui.horizontal(|ui| {
+        let mut choice = None;
+        ui.menu_button("➕ Add Mode", |ui| {
+            choice = widgets::mode_template_menu(ui, templates);
+            if choice.is_some() {
+                ui.close();
+            }
+        });
+        if let Some(choice) = choice {
+            add_mode_from_template(current_genome, templates, choice);
+        }
+
+        // Inserting and removing shift later modes; cells are remapped with them
+        let selected_idx = current_genome.selected_mode_index as usize;
+        let mut insert_choice = None;
+        ui.menu_button("Insert", |ui| {
+            insert_choice = widgets::mode_template_menu(ui, templates);
+            if insert_choice.is_some() {
+                ui.close();
+            }
+        }).response.on_hover_text("Insert a mode before the selected mode");
+        if let Some(choice) = insert_choice {
+            mode_edits.pending = Some(ModeEdit::Insert { index: selected_idx, choice });
+            genome_editor_state.selected_modes.clear();
+        }
+        let removable = current_genome.genome.modes.len() > 1 && selected_idx < current_genome.genome.modes.len();
+        if ui.add_enabled(removable, egui::Button::new("Remove").small())
+            .on_hover_text("Remove the selected mode (asks what happens to its living cells)")
+            .clicked()
+        {
+            mode_edits.pending = Some(ModeEdit::Remove { index: selected_idx, policy: None });
+            genome_editor_state.selected_modes.clear();
+        }
+
+        if ui.small_button("Save as Template...").on_hover_text("Keep the selected mode as a template for new modes").clicked()
+            && selected_idx < current_genome.genome.modes.len()
+        {
+            let name = current_genome.genome.modes[selected_idx].name.clone();
+            genome_editor_state.template_name_prompt = Some((selected_idx, name));
+        }
+    });
+
+    render_template_name_prompt(ui.ctx(), current_genome, genome_editor_state, templates, notifications);
+
+    // Palette used for default genomes and recoloring
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("mode_palette")
+            .selected_text(genome_editor_state.mode_palette.name())
+            .show_ui(ui, |ui| {
+                for palette in ColorPalette::ALL {
+                    ui.selectable_value(&mut genome_editor_state.mode_palette, palette, palette.name());
+                }
+            })
+            .response
+            .on_hover_text("Color palette for new genomes (saved with UI settings)");
+        if ui.small_button("Recolor all modes").on_hover_text("Replace every mode's color with the selected palette").clicked() {
+            current_genome.genome.recolor_modes(genome_editor_state.mode_palette);
+            info!("Recolored all modes with the {} palette", genome_editor_state.mode_palette.name());
+        }
+    });
+
     ui.separator();
 
     // Show instruction text if in copy into mode (also outside scroll area)
@@ -71,37 +143,53 @@ pub fn render_modes_panel(ui: &mut egui::Ui, current_genome: &mut CurrentGenome,
     }
 
     // Convert modes to display format
-    let modes_display: Vec<(String, egui::Color32)> = current_genome.genome.modes.iter()
+    let modes_display: Vec<(String, egui::Color32, Option<CellPattern>)> = current_genome.genome.modes.iter()
         .map(|m| {
             let color = m.color;
             let r = (color.x * 255.0) as u8;
             let g = (color.y * 255.0) as u8;
             let b = (color.z * 255.0) as u8;
-            (m.name.clone(), egui::Color32::from_rgb(r, g, b))
+            (m.name.clone(), egui::Color32::from_rgb(r, g, b), m.pattern)
         })
         .collect();
 
     // Now create scroll area for the list
-    let (selection_changed, initial_changed, rename_idx, color_change) = egui::ScrollArea::vertical()
+    let (selection_changed, initial_changed, rename_idx, color_change, pattern_change) = egui::ScrollArea::vertical()
         .auto_shrink([false, false])
         .show(ui, |ui| {
         let available_width = ui.available_width();
 
         let mut selected_mode = current_genome.selected_mode_index as usize;
-        let mut initial_mode = current_genome.genome.initial_mode as usize;
+        let mut initial_mode = current_genome.genome.initial_mode_index();
 
         let result = widgets::modes_list_items(
             ui,
             &modes_display,
             &mut selected_mode,
+            &genome_editor_state.selected_modes,
             &mut initial_mode,
             available_width,
             genome_editor_state.copy_into_dialog_open,
             &mut genome_editor_state.color_picker_state,
         );
 
+        // Ctrl/Shift+click build a multi-selection for batch edits
+        if result.0 && !genome_editor_state.copy_into_dialog_open {
+            let modifiers = ui.input(|i| i.modifiers);
+            let primary = current_genome.selected_mode_index.max(0) as usize;
+            selected_mode = crate::genome::batch_edit::click_mode_selection(
+                &mut genome_editor_state.selected_modes,
+                primary,
+                selected_mode,
+                modifiers.command,
+                modifiers.shift,
+            );
+        }
+
         current_genome.selected_mode_index = selected_mode as i32;
-        current_genome.genome.initial_mode = initial_mode as i32;
+        if result.1 {
+            current_genome.genome.initial_mode = ModeIndex::new(initial_mode);
+        }
 
         result
     }).inner;
@@ -149,6 +237,13 @@ pub fn render_modes_panel(ui: &mut egui::Ui, current_genome: &mut CurrentGenome,
         }
     }
 
+    // Handle pattern change from context menu pattern picker
+    if let Some((idx, pattern)) = pattern_change {
+        if let Some(mode) = current_genome.genome.modes.get_mut(idx) {
+            mode.pattern = pattern;
+        }
+    }
+
     // Handle copy into mode
     if copy_into_clicked {
         let selected_idx = current_genome.selected_mode_index as usize;
@@ -166,12 +261,133 @@ pub fn render_modes_panel(ui: &mut egui::Ui, current_genome: &mut CurrentGenome,
             // Reset to default values
             let name = current_genome.genome.modes[selected_idx].name.clone();
             let color = current_genome.genome.modes[selected_idx].color;
+            let pattern = current_genome.genome.modes[selected_idx].pattern;
             current_genome.genome.modes[selected_idx] = ModeSettings::default();
             current_genome.genome.modes[selected_idx].name = name;
             current_genome.genome.modes[selected_idx].color = color;
-            current_genome.genome.modes[selected_idx].child_a.mode_number = selected_idx as i32;
-            current_genome.genome.modes[selected_idx].child_b.mode_number = selected_idx as i32;
+            current_genome.genome.modes[selected_idx].pattern = pattern;
+            current_genome.genome.modes[selected_idx].child_a.mode_number = ModeIndex::new(selected_idx);
+            current_genome.genome.modes[selected_idx].child_b.mode_number = ModeIndex::new(selected_idx);
             info!("Reset mode {}", selected_idx);
         }
     }
 }
+
+/// Append a mode made from `choice` and select it
+pub fn add_mode_from_template(current_genome: &mut CurrentGenome, templates: &ModeTemplates, choice: crate::genome::TemplateChoice) {
+    if let Some(index) = templates.add_mode(choice, &mut current_genome.genome) {
+        current_genome.selected_mode_index = index as i32;
+        info!("Added mode {} ({:?})", index, choice);
+    }
+}
+
+/// Confirmation of a removal that affects living cells: reassign them or remove them
+fn render_removal_prompt(ctx: &egui::Context, current_genome: &CurrentGenome, mode_edits: &mut ModeEdits) {
+    let Some(prompt) = mode_edits.prompt.as_mut() else {
+        return;
+    };
+    let modes = &current_genome.genome.modes;
+    let Some(removed) = modes.get(prompt.index) else {
+        mode_edits.prompt = None;
+        return;
+    };
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Remove Mode")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(format!("{} living cell(s) are in mode \"{}\".", prompt.cells, removed.name));
+            let fallback = match prompt.policy {
+                RemovedModePolicy::Reassign(mode) => mode,
+                RemovedModePolicy::Remove => current_genome.genome.removal_fallback(prompt.index).unwrap_or(0),
+            };
+            ui.horizontal(|ui| {
+                if ui.radio(matches!(prompt.policy, RemovedModePolicy::Reassign(_)), "Move them to").clicked() {
+                    prompt.policy = RemovedModePolicy::Reassign(fallback);
+                }
+                let mut target = fallback;
+                egui::ComboBox::from_id_salt("removed_mode_fallback")
+                    .selected_text(modes.get(target).map_or("?", |mode| mode.name.as_str()))
+                    .show_ui(ui, |ui| {
+                        for (index, mode) in modes.iter().enumerate().filter(|(index, _)| *index != prompt.index) {
+                            ui.selectable_value(&mut target, index, mode.name.as_str());
+                        }
+                    });
+                if target != fallback {
+                    prompt.policy = RemovedModePolicy::Reassign(target);
+                }
+            });
+            ui.radio_value(&mut prompt.policy, RemovedModePolicy::Remove, "Remove them from the simulation");
+            ui.horizontal(|ui| {
+                if ui.button("Remove Mode").clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed {
+        mode_edits.pending = Some(ModeEdit::Remove { index: prompt.index, policy: Some(prompt.policy) });
+    }
+    if confirmed || cancelled {
+        mode_edits.prompt = None;
+    }
+}
+
+/// Name prompt of "Save as Template..."; saving writes the templates file
+fn render_template_name_prompt(
+    ctx: &egui::Context,
+    current_genome: &CurrentGenome,
+    genome_editor_state: &mut GenomeEditorState,
+    templates: &mut ModeTemplates,
+    notifications: &mut crate::ui::Notifications,
+) {
+    let Some((mode_idx, name)) = genome_editor_state.template_name_prompt.as_mut() else {
+        return;
+    };
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Save Mode Template")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label("Template Name:");
+            let response = ui.text_edit_singleline(name);
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                confirmed = true;
+            }
+            if templates.user.iter().any(|template| template.name == name.trim()) {
+                ui.colored_label(egui::Color32::YELLOW, "Replaces the saved template of this name");
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+            if !response.has_focus() {
+                response.request_focus();
+            }
+        });
+
+    let trimmed = name.trim().to_string();
+    if confirmed && !trimmed.is_empty() {
+        if let Some(mode) = current_genome.genome.modes.get(*mode_idx) {
+            templates.add_user(trimmed.clone(), mode);
+            match templates.save() {
+                Ok(()) => notifications.success(format!("Saved mode template \"{}\"", trimmed)),
+                Err(e) => notifications.error(&e),
+            }
+        }
+        genome_editor_state.template_name_prompt = None;
+    } else if cancelled {
+        genome_editor_state.template_name_prompt = None;
+    }
+}
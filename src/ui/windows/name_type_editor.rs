@@ -1,8 +1,8 @@
-use bevy::prelude::*;
 use bevy_egui::egui;
 use crate::genome::CurrentGenome;
+use crate::ui::Notifications;
 
-pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
+pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome, notifications: &mut Notifications) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
         .show(ui, |ui| {
@@ -17,8 +17,13 @@ pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
                     .set_file_name(&format!("{}.json", current_genome.genome.name))
                     .save_file()
                 {
-                    info!("Would save genome to: {:?}", path);
-                    // TODO: Implement actual save
+                    match current_genome.genome.save_to_file(&path) {
+                        Ok(()) => {
+                            current_genome.record_save(&path);
+                            notifications.success(format!("Saved genome to {}", path.display()));
+                        }
+                        Err(e) => notifications.error(&e),
+                    }
                 }
             }
             if ui.button("Load Genome").clicked() {
@@ -27,8 +32,13 @@ pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
                     .add_filter("JSON", &["json"])
                     .pick_file()
                 {
-                    info!("Would load genome from: {:?}", path);
-                    // TODO: Implement actual load
+                    match crate::genome::GenomeData::load_from_file(&path) {
+                        Ok(genome) => {
+                            notifications.success(format!("Loaded genome '{}'", genome.name));
+                            current_genome.replace(genome, 0, None);
+                        }
+                        Err(e) => notifications.error(&e),
+                    }
                 }
             }
             if ui.button("Genome Graph").clicked() {
@@ -41,7 +51,10 @@ pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
         // Genome Name label and field on same line
         ui.horizontal(|ui| {
             ui.label("Genome Name:");
-            ui.text_edit_singleline(&mut current_genome.genome.name);
+            let response = ui.text_edit_singleline(&mut current_genome.genome.name);
+            if let Some(sample) = current_genome.sampled_from {
+                response.on_hover_text(format!("Sampled from cell #{} at t={:.2}s", sample.cell_id, sample.time));
+            }
         });
 
         ui.add_space(4.0);
@@ -0,0 +1,251 @@
+use bevy_egui::egui;
+use crate::genome::{CurrentGenome, GenomeHistory};
+use crate::simulation::PhysicsConfig;
+use crate::simulation::parameter_sweep::{
+    ParameterSweep, SweepAxis, SweepColumn, SweepMetric, SweepParameter, SweepResult, SweepSetup,
+};
+
+pub fn render(
+    ui: &mut egui::Ui,
+    sweep: &mut ParameterSweep,
+    current_genome: &mut CurrentGenome,
+    genome_history: &mut GenomeHistory,
+    physics_config: &mut PhysicsConfig,
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        let running = sweep.job.is_some();
+        ui.add_enabled_ui(!running, |ui| {
+            render_setup(ui, &mut sweep.setup, &current_genome.genome);
+        });
+
+        ui.add_space(4.0);
+
+        match &sweep.job {
+            Some(job) => {
+                let completed = job.completed();
+                ui.horizontal(|ui| {
+                    ui.add(egui::ProgressBar::new(completed as f32 / job.total.max(1) as f32)
+                        .text(format!("{} / {} runs", completed, job.total))
+                        .desired_width(200.0));
+                    if job.is_cancelled() {
+                        ui.label("Cancelling...");
+                    } else if ui.button("Cancel").clicked() {
+                        job.cancel();
+                    }
+                });
+                ui.ctx().request_repaint();
+            }
+            None => {
+                let runs = sweep.setup.run_count();
+                if ui.button(format!("Run Sweep ({} runs)", runs)).clicked() {
+                    current_genome.genome.record_run(crate::genome::RunKind::Headless, sweep.setup.rng_seed);
+                    sweep.start(&current_genome.genome, physics_config);
+                }
+            }
+        }
+
+        let Some(results_setup) = sweep.results_setup.clone() else {
+            return;
+        };
+        if sweep.results.is_empty() {
+            return;
+        }
+
+        ui.separator();
+        ui.label(egui::RichText::new("Results").strong());
+        ui.label(egui::RichText::new("Click a result to load its parameters into the live genome/config.").small().weak());
+
+        let mut selected = None;
+
+        // Heatmap
+        ui.horizontal(|ui| {
+            ui.label("Heatmap:");
+            egui::ComboBox::from_id_salt("sweep_heatmap_metric")
+                .selected_text(sweep.heatmap_metric.name())
+                .show_ui(ui, |ui| {
+                    for metric in SweepMetric::ALL {
+                        ui.selectable_value(&mut sweep.heatmap_metric, metric, metric.name());
+                    }
+                });
+        });
+        if let Some(index) = render_heatmap(ui, &sweep.results, &results_setup, sweep.heatmap_metric) {
+            selected = Some(index);
+        }
+
+        ui.add_space(6.0);
+
+        // Sortable table
+        let genome = &current_genome.genome;
+        let mut columns = vec![(SweepColumn::ValueA, results_setup.axis_a.parameter.label(genome))];
+        if let Some(axis) = results_setup.axis_b {
+            columns.push((SweepColumn::ValueB, axis.parameter.label(genome)));
+        }
+        for metric in &results_setup.metrics {
+            columns.push((SweepColumn::Metric(*metric), metric.name().to_string()));
+        }
+
+        egui::Grid::new("sweep_results_grid")
+            .num_columns(columns.len())
+            .striped(true)
+            .show(ui, |ui| {
+                for (column, title) in &columns {
+                    let arrow = match sweep.sort {
+                        (sorted, true) if sorted == *column => " ^",
+                        (sorted, false) if sorted == *column => " v",
+                        _ => "",
+                    };
+                    if ui.button(format!("{}{}", title, arrow)).clicked() {
+                        sweep.sort = if sweep.sort.0 == *column {
+                            (*column, !sweep.sort.1)
+                        } else {
+                            (*column, true)
+                        };
+                    }
+                }
+                ui.end_row();
+
+                for index in sweep.sorted_indices() {
+                    let result = &sweep.results[index];
+                    for (column, _) in &columns {
+                        let text = match column {
+                            SweepColumn::ValueA => format!("{:.3}", result.value_a),
+                            SweepColumn::ValueB => format!("{:.3}", result.value_b.unwrap_or(0.0)),
+                            SweepColumn::Metric(SweepMetric::LargestOrganismRadius) => {
+                                format!("{:.2}", result.largest_organism_radius)
+                            }
+                            SweepColumn::Metric(metric) => format!("{}", result.metric(*metric)),
+                        };
+                        if ui.selectable_label(false, text).clicked() {
+                            selected = Some(index);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(index) = selected {
+            let result = sweep.results[index];
+            results_setup.apply_result(&mut current_genome.genome, physics_config, result.value_a, result.value_b, genome_history);
+        }
+    });
+}
+
+fn render_axis(ui: &mut egui::Ui, id: &str, axis: &mut SweepAxis, parameters: &[SweepParameter], genome: &crate::genome::GenomeData) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(axis.parameter.label(genome))
+            .show_ui(ui, |ui| {
+                for parameter in parameters {
+                    if ui.selectable_label(axis.parameter == *parameter, parameter.label(genome)).clicked() {
+                        *axis = SweepAxis { steps: axis.steps, ..SweepAxis::new(*parameter) };
+                    }
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label("From");
+        ui.add(egui::DragValue::new(&mut axis.min).speed(0.1));
+        ui.label("to");
+        ui.add(egui::DragValue::new(&mut axis.max).speed(0.1));
+        ui.label("in");
+        ui.add(egui::DragValue::new(&mut axis.steps).range(1..=20));
+        ui.label("steps");
+    });
+}
+
+fn render_setup(ui: &mut egui::Ui, setup: &mut SweepSetup, genome: &crate::genome::GenomeData) {
+    let parameters = SweepParameter::all(genome);
+
+    ui.label(egui::RichText::new("Parameter A").strong());
+    render_axis(ui, "sweep_axis_a", &mut setup.axis_a, &parameters, genome);
+
+    ui.add_space(4.0);
+    let mut use_b = setup.axis_b.is_some();
+    if ui.checkbox(&mut use_b, egui::RichText::new("Parameter B").strong()).changed() {
+        setup.axis_b = use_b.then(|| SweepAxis { steps: 4, ..SweepAxis::new(SweepParameter::AdhesionStiffness { mode: 0 }) });
+    }
+    if let Some(axis_b) = setup.axis_b.as_mut() {
+        render_axis(ui, "sweep_axis_b", axis_b, &parameters, genome);
+    }
+
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new("Runs").strong());
+    ui.horizontal(|ui| {
+        ui.label("Ticks per run:");
+        ui.add(egui::DragValue::new(&mut setup.ticks).speed(10.0).range(1..=100_000));
+        ui.label("Max cells:");
+        ui.add(egui::DragValue::new(&mut setup.max_cells).range(1..=10_000));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Seed:");
+        ui.add(egui::DragValue::new(&mut setup.rng_seed));
+        if ui.small_button("Genome").on_hover_text(format!("Use the genome's pinned seed ({})", genome.metadata.seed)).clicked() {
+            setup.rng_seed = genome.metadata.seed;
+        }
+        ui.label("Parallel runs:");
+        ui.add(egui::DragValue::new(&mut setup.max_parallel).range(1..=64));
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Record:");
+        for metric in SweepMetric::ALL {
+            let mut enabled = setup.metrics.contains(&metric);
+            if ui.checkbox(&mut enabled, metric.name()).changed() {
+                if enabled {
+                    setup.metrics.push(metric);
+                } else {
+                    setup.metrics.retain(|m| *m != metric);
+                }
+            }
+        }
+    });
+}
+
+/// Grid of colored cells (A across, B down); returns the clicked result index
+fn render_heatmap(ui: &mut egui::Ui, results: &[SweepResult], setup: &SweepSetup, metric: SweepMetric) -> Option<usize> {
+    let columns = setup.axis_a.steps.max(1);
+    let rows = setup.axis_b.map_or(1, |axis| axis.steps.max(1));
+    let (min, max) = results.iter()
+        .map(|result| result.metric(metric))
+        .fold((f32::MAX, f32::MIN), |(min, max), value| (min.min(value), max.max(value)));
+    let range = (max - min).max(f32::EPSILON);
+
+    let cell_size = egui::vec2(28.0, 20.0);
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(cell_size.x * columns as f32, cell_size.y * rows as f32),
+        egui::Sense::click(),
+    );
+    let painter = ui.painter_at(rect);
+
+    let mut hovered = None;
+    for (index, result) in results.iter().enumerate() {
+        let column = result.run_index % columns;
+        let row = result.run_index / columns;
+        let cell_rect = egui::Rect::from_min_size(
+            rect.min + egui::vec2(column as f32 * cell_size.x, row as f32 * cell_size.y),
+            cell_size,
+        ).shrink(1.0);
+
+        let t = (result.metric(metric) - min) / range;
+        let color = egui::Color32::from_rgb((40.0 + 215.0 * t) as u8, (60.0 + 120.0 * t) as u8, (160.0 * (1.0 - t)) as u8);
+        painter.rect_filled(cell_rect, 2.0, color);
+
+        if response.hover_pos().is_some_and(|pos| cell_rect.contains(pos)) {
+            painter.rect_stroke(cell_rect, 2.0, egui::Stroke::new(1.5, egui::Color32::WHITE), egui::StrokeKind::Inside);
+            hovered = Some((index, result));
+        }
+    }
+
+    let (index, result) = hovered?;
+    let text = match result.value_b {
+        Some(b) => format!("A = {:.3}, B = {:.3}\n{}: {:.3}", result.value_a, b, metric.name(), result.metric(metric)),
+        None => format!("A = {:.3}\n{}: {:.3}", result.value_a, metric.name(), result.metric(metric)),
+    };
+    let clicked = response.clicked();
+    response.on_hover_text(text);
+    clicked.then_some(index)
+}
@@ -53,6 +53,43 @@ pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
                 ui.add(egui::Slider::new(&mut mode.split_interval, 1.0..=60.0).show_value(false));
                 ui.add(egui::DragValue::new(&mut mode.split_interval).speed(0.1).range(1.0..=60.0).suffix("s"));
             });
+
+            ui.horizontal(|ui| {
+                ui.label("Split Phase Jitter:");
+                ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+                    "Shifts each cell's division by up to half this fraction of the split interval either way, \
+                     so a colony stops dividing all on the same tick. The average interval is unchanged.",
+                );
+            });
+            ui.horizontal(|ui| {
+                let available = ui.available_width();
+                let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                ui.style_mut().spacing.slider_width = slider_width;
+                ui.add(egui::Slider::new(&mut mode.split_phase_jitter, 0.0..=1.0).show_value(false));
+                ui.add(egui::DragValue::new(&mut mode.split_phase_jitter).speed(0.01).range(0.0..=1.0));
+            });
+
+            ui.horizontal(|ui| {
+                let mut inhibited = mode.max_contact_pressure.is_finite();
+                if ui.checkbox(&mut inhibited, "Contact Inhibition").changed() {
+                    mode.max_contact_pressure = if inhibited { 1.0 } else { f32::INFINITY };
+                }
+                ui.label(egui::RichText::new("(?)").weak()).on_hover_text(
+                    "Cells skip division while crowded. Pressure is the summed overlap with every touching cell this tick; \
+                     division waits until it drops to the limit.",
+                );
+            });
+
+            if mode.max_contact_pressure.is_finite() {
+                ui.label("Max Contact Pressure:");
+                ui.horizontal(|ui| {
+                    let available = ui.available_width();
+                    let slider_width = if available > 80.0 { available - 70.0 } else { 50.0 };
+                    ui.style_mut().spacing.slider_width = slider_width;
+                    ui.add(egui::Slider::new(&mut mode.max_contact_pressure, 0.0..=5.0).show_value(false));
+                    ui.add(egui::DragValue::new(&mut mode.max_contact_pressure).speed(0.01).range(0.0..=5.0));
+                });
+            }
         });
 
         // Nutrient Settings Group (Green)
@@ -66,7 +103,7 @@ pub fn render(ui: &mut egui::Ui, current_genome: &mut CurrentGenome) {
                 ui.add(egui::DragValue::new(&mut mode.nutrient_priority).speed(0.01).range(0.1..=10.0));
             });
 
-            ui.checkbox(&mut mode.prioritize_when_low, "Prioritize When Low");
+            crate::ui::genome_editor::settings_panels::render_low_nutrient_sliders(ui, mode);
         });
 
         // Connection Settings Group (Cyan)
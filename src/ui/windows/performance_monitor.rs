@@ -0,0 +1,763 @@
+use bevy_egui::egui;
+use crate::simulation::derived_metrics::{DerivedMetricSettings, DivisionByZero, Expr, Variable};
+use crate::simulation::history::{Resolution, StatSummary, Statistic};
+use crate::simulation::{AdhesionQualityStats, DivisionStatistics, EventTimeline, GpuPairDetection, HistorySettings, LiveStats, LiveStatsSettings, MassAudit, MemoryProfile, PopulationStats, PopulationStatsSettings, PhysicsConfig, ProblemBonds, SimulationFingerprint, SimulationMemory, SpectatorHost, SpectatorSettings, StatisticsHistory};
+
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    ui: &mut egui::Ui,
+    physics_config: &mut PhysicsConfig,
+    gpu_pairs: &mut GpuPairDetection,
+    gpu_compute: &crate::rendering::Support,
+    memory: &mut SimulationMemory,
+    fingerprint: &mut SimulationFingerprint,
+    adhesion_quality: &AdhesionQualityStats,
+    problem_bonds: &mut ProblemBonds,
+    division: &DivisionStatistics,
+    mass_audit: &mut MassAudit,
+    population: &mut PopulationStats,
+    population_settings: &mut PopulationStatsSettings,
+    genome: &crate::genome::GenomeData,
+    live_stats_settings: &mut LiveStatsSettings,
+    live_stats: &LiveStats,
+    spectator_settings: &mut SpectatorSettings,
+    spectator_host: &SpectatorHost,
+    history_settings: &mut HistorySettings,
+    statistics: &StatisticsHistory,
+    metric_settings: &mut DerivedMetricSettings,
+    timeline: &EventTimeline,
+    notifications: &mut crate::ui::Notifications,
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        render_memory(ui, memory);
+        ui.separator();
+
+        render_fingerprint(ui, fingerprint);
+        ui.separator();
+
+        render_adhesion_quality(ui, adhesion_quality);
+        ui.separator();
+
+        render_problem_bonds(ui, problem_bonds, physics_config.adhesion_max_force);
+        ui.separator();
+
+        render_divisions(ui, division);
+        ui.separator();
+
+        render_population(ui, population, population_settings, genome);
+        ui.separator();
+
+        render_mass_audit(ui, mass_audit);
+        ui.separator();
+
+        render_live_stats(ui, live_stats_settings, live_stats);
+        ui.separator();
+
+        render_spectator_host(ui, spectator_settings, spectator_host);
+        ui.separator();
+
+        render_history(ui, history_settings, statistics, timeline, notifications);
+        ui.separator();
+
+        render_derived_metrics(ui, metric_settings, genome);
+        ui.separator();
+
+        ui.label(egui::RichText::new("GPU Collision Pairs").strong());
+        ui.add_enabled_ui(gpu_compute.is_available(), |ui| {
+            ui.checkbox(&mut physics_config.gpu_pair_detection, "Enable GPU pair detection")
+        }).inner.on_disabled_hover_text(gpu_compute.missing_reason().unwrap_or_default());
+        ui.checkbox(&mut physics_config.gpu_pair_validation, "Validate against CPU");
+
+        if !physics_config.gpu_pair_detection {
+            return;
+        }
+
+        let stats = &gpu_pairs.stats;
+        egui::Grid::new("gpu_pair_stats")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("GPU detect:");
+                ui.label(format!("{:.3} ms", stats.gpu_ms));
+                ui.end_row();
+
+                ui.label("CPU detect:");
+                ui.label(format!("{:.3} ms", stats.cpu_ms));
+                ui.end_row();
+
+                ui.label("Speedup:");
+                match stats.speedup() {
+                    Some(speedup) => ui.label(format!("{:.2}x", speedup)),
+                    None => ui.label("measuring..."),
+                };
+                ui.end_row();
+
+                ui.label("Pairs:");
+                ui.label(stats.last_pair_count.to_string());
+                ui.end_row();
+
+                ui.label("Overflow ticks:");
+                ui.label(stats.overflow_ticks.to_string());
+                ui.end_row();
+
+                if physics_config.gpu_pair_validation {
+                    ui.label("Mismatched pairs:");
+                    ui.label(stats.validation_mismatches.to_string());
+                    ui.end_row();
+                }
+            });
+
+        if !stats.fixture_results.is_empty() {
+            ui.add_space(4.0);
+            ui.label("Fixture validation:");
+            for (name, mismatches) in &stats.fixture_results {
+                let (text, color) = if *mismatches == 0 {
+                    (format!("{}: match", name), egui::Color32::from_rgb(120, 200, 120))
+                } else {
+                    (format!("{}: {} mismatched", name, mismatches), egui::Color32::from_rgb(220, 100, 100))
+                };
+                ui.colored_label(color, text);
+            }
+        }
+
+        let mut retry = false;
+        if let Some(reason) = &stats.fallback_reason {
+            ui.add_space(4.0);
+            ui.colored_label(egui::Color32::from_rgb(220, 180, 80), format!("Using CPU: {}", reason));
+            retry = gpu_pairs.context.is_some() && ui.button("Retry GPU").clicked();
+        }
+        if gpu_pairs.context.is_some() && ui.button("Run fixture validation").clicked() {
+            gpu_pairs.validate_fixtures();
+        }
+        if retry {
+            gpu_pairs.retry();
+        }
+    });
+}
+
+fn render_memory(ui: &mut egui::Ui, memory: &mut SimulationMemory) {
+    ui.label(egui::RichText::new("Memory").strong());
+    ui.horizontal(|ui| {
+        ui.label("Profile:");
+        egui::ComboBox::from_id_salt("memory_profile")
+            .selected_text(memory.profile.name())
+            .show_ui(ui, |ui| {
+                for profile in MemoryProfile::ALL {
+                    ui.selectable_value(&mut memory.profile, profile, profile.name());
+                }
+            });
+    }).response.on_hover_text("Lean grows rarely-used cell columns with the cell count instead of allocating them for the full capacity. Applies when the scene is reset; results are identical.");
+
+    let Some(usage) = memory.usage else {
+        return;
+    };
+    egui::Grid::new("memory_usage")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            for (label, bytes) in [
+                ("Cells:", usage.cells),
+                ("Adhesions:", usage.adhesions),
+                ("Spatial grid:", usage.spatial_grid),
+                ("Chemical field:", usage.chemical_field),
+                ("Scratch buffers:", usage.scratch),
+                ("Checkpoints:", usage.checkpoints),
+                ("Total:", usage.total()),
+            ] {
+                ui.label(label);
+                ui.label(format_bytes(bytes));
+                ui.end_row();
+            }
+        });
+
+    ui.checkbox(&mut memory.count_allocations, "Count allocations")
+        .on_hover_text("Count heap allocations during each main simulation tick. Counts every thread, so work running alongside the tick is included.");
+    if memory.count_allocations {
+        let text = match memory.allocations_last_tick {
+            Some(count) => format!("Allocations / tick: {}", count),
+            None => "Allocations / tick: waiting for a main simulation tick".to_string(),
+        };
+        ui.label(text);
+    }
+}
+
+fn render_fingerprint(ui: &mut egui::Ui, fingerprint: &mut SimulationFingerprint) {
+    ui.label(egui::RichText::new("Fingerprint").strong());
+    match fingerprint.current {
+        Some((tick, value)) => {
+            let text = format!("{:016x}@{}", value, tick);
+            ui.horizontal(|ui| {
+                ui.monospace(&text);
+                if ui.small_button("Copy")
+                    .on_hover_text("Copy as an --expect-fingerprint argument")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(text.clone());
+                }
+            });
+        }
+        None => {
+            ui.label("No active simulation");
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut fingerprint.trace_enabled, "Trace every")
+            .on_hover_text("Log the fingerprint to the console while the simulation runs");
+        ui.add(egui::DragValue::new(&mut fingerprint.trace_interval).range(1..=100_000));
+        ui.label("ticks");
+    });
+}
+
+fn render_adhesion_quality(ui: &mut egui::Ui, stats: &AdhesionQualityStats) {
+    ui.label(egui::RichText::new("Adhesion Quality").strong());
+
+    let Some((full, cheap)) = stats.counts else {
+        ui.label("No active simulation");
+        return;
+    };
+    egui::Grid::new("adhesion_quality")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Full:");
+            ui.label(full.to_string());
+            ui.end_row();
+
+            ui.label("Cheap:");
+            ui.label(cheap.to_string());
+            ui.end_row();
+        });
+}
+
+fn render_problem_bonds(ui: &mut egui::Ui, bonds: &mut ProblemBonds, max_force: f32) {
+    ui.label(egui::RichText::new("Problem Bonds").strong())
+        .on_hover_text(format!("Adhesions whose force exceeded {} or wasn't finite (main simulation)", max_force));
+
+    ui.horizontal(|ui| {
+        ui.label(format!("{} total", bonds.total));
+        ui.checkbox(&mut bonds.highlight, "Highlight")
+            .on_hover_text("Draw recent problem bonds in the warning color");
+        if ui.add_enabled(!bonds.events.is_empty(), egui::Button::new("Clear")).clicked() {
+            bonds.clear();
+        }
+    });
+    if bonds.events.is_empty() {
+        ui.label("None");
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .id_salt("problem_bonds")
+        .max_height(120.0)
+        .show(ui, |ui| {
+            egui::Grid::new("problem_bonds_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Tick").strong());
+                    ui.label(egui::RichText::new("Bond").strong());
+                    ui.label(egui::RichText::new("Cells").strong());
+                    ui.label(egui::RichText::new("Force").strong());
+                    ui.end_row();
+
+                    for event in bonds.events.iter().rev() {
+                        ui.label(event.tick.to_string());
+                        ui.label(format!("#{}", event.connection));
+                        ui.label(format!("{} - {}", event.cell_a_id, event.cell_b_id));
+                        if event.magnitude.is_finite() {
+                            ui.label(format!("{:.1}", event.magnitude));
+                        } else {
+                            ui.colored_label(egui::Color32::from_rgb(230, 80, 60), "non-finite");
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn render_live_stats(ui: &mut egui::Ui, settings: &mut LiveStatsSettings, live: &LiveStats) {
+    ui.label(egui::RichText::new("Live Stats Server").strong())
+        .on_hover_text("Read-only JSON at /stats, /events?since=<tick> and /fingerprint for external dashboards");
+
+    ui.add_enabled_ui(crate::simulation::live_stats::LIVE_STATS_AVAILABLE, |ui| {
+        ui.checkbox(&mut settings.enabled, "Serve over HTTP")
+    }).inner.on_disabled_hover_text("Rebuild with --features http-export");
+
+    // The address only applies when the server starts
+    ui.add_enabled_ui(!settings.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Bind:");
+            ui.add(egui::TextEdit::singleline(&mut settings.bind_address).desired_width(110.0));
+            ui.label("Port:");
+            ui.add(egui::DragValue::new(&mut settings.port).range(1..=u16::MAX));
+        });
+    });
+
+    if let Some(url) = live.url() {
+        ui.horizontal(|ui| {
+            ui.hyperlink_to(url.as_str(), format!("{}/stats", url));
+            ui.label(format!("{} requests", live.request_count()));
+        });
+    } else if let Some(error) = &live.error {
+        ui.colored_label(egui::Color32::from_rgb(230, 80, 60), error);
+    } else {
+        ui.label("Stopped");
+    }
+}
+
+fn render_spectator_host(ui: &mut egui::Ui, settings: &mut SpectatorSettings, host: &SpectatorHost) {
+    ui.label(egui::RichText::new("Spectator Host").strong())
+        .on_hover_text("Stream the main simulation to read-only viewers started with --spectate <host>");
+
+    ui.checkbox(&mut settings.enabled, "Accept spectators");
+
+    // The address only applies when the host starts
+    ui.add_enabled_ui(!settings.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Bind:");
+            ui.add(egui::TextEdit::singleline(&mut settings.bind_address).desired_width(110.0));
+            ui.label("Port:");
+            ui.add(egui::DragValue::new(&mut settings.port).range(1..=u16::MAX));
+        });
+    });
+    ui.horizontal(|ui| {
+        ui.label("Updates:");
+        ui.add(egui::DragValue::new(&mut settings.updates_per_second).speed(0.5).range(1.0..=60.0).suffix(" /s"))
+            .on_hover_text("Spectators blend between updates; fewer updates use less bandwidth");
+    });
+
+    if let Some(address) = host.address() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} spectators", host.spectator_count()));
+            ui.label(format!("{} sent", format_bytes(host.bytes_sent() as usize)));
+        });
+        ui.label(egui::RichText::new(format!("Join with --spectate <this machine>:{}", address.port())).small().weak());
+    } else if let Some(error) = &host.error {
+        ui.colored_label(egui::Color32::from_rgb(230, 80, 60), error);
+    } else {
+        ui.label("Stopped");
+    }
+}
+
+/// Height of a statistics history plot
+const HISTORY_PLOT_HEIGHT: f32 = 56.0;
+
+fn render_history(
+    ui: &mut egui::Ui,
+    settings: &mut HistorySettings,
+    statistics: &StatisticsHistory,
+    timeline: &EventTimeline,
+    notifications: &mut crate::ui::Notifications,
+) {
+    ui.label(egui::RichText::new("History").strong())
+        .on_hover_text("Recent events and statistics are kept as they happened, older ones as per-second, then per-minute aggregates");
+
+    egui::Grid::new("history_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Raw window:");
+            ui.add(egui::DragValue::new(&mut settings.raw_window_minutes).speed(0.1).range(0.5..=120.0).suffix(" min"));
+            ui.end_row();
+
+            ui.label("Per-second:");
+            ui.add(egui::DragValue::new(&mut settings.second_retention_minutes).speed(1.0).range(1.0..=1440.0).suffix(" min"));
+            ui.end_row();
+
+            ui.label("Per-minute:");
+            ui.add(egui::DragValue::new(&mut settings.minute_retention_hours).speed(0.5).range(1.0..=720.0).suffix(" h"));
+            ui.end_row();
+
+            ui.label("Memory budget:");
+            ui.add(egui::DragValue::new(&mut settings.memory_budget_mb).speed(1.0).range(4.0..=4096.0).suffix(" MB"))
+                .on_hover_text("Shared by the event timeline, the live event log and the statistics; the oldest aggregates go first");
+            ui.end_row();
+
+            ui.label("In use:");
+            ui.label(format_bytes(timeline.memory_bytes() + statistics.memory_bytes()));
+            ui.end_row();
+        });
+
+    let coverage = statistics.history.coverage();
+    let (Some(first), Some(last)) = (coverage.first(), coverage.last()) else {
+        ui.label("No main simulation statistics yet");
+        return;
+    };
+    let (start, end) = (first.start, last.end.max(first.start + 1.0));
+    let columns = ui.available_width().max(1.0) as usize;
+    for statistic in Statistic::ALL {
+        let plot = statistics.plot_columns(statistic, start, end, columns);
+        render_statistic_plot(ui, statistics, statistic.name(), &plot, start, end);
+    }
+    for (index, metric) in statistics.derived_metrics.iter().enumerate() {
+        let plot = statistics.plot_metric_columns(index, start, end, columns);
+        render_statistic_plot(ui, statistics, &metric.name, &plot, start, end);
+    }
+    ui.label(egui::RichText::new(coverage.iter()
+        .map(|range| format!("{} {}-{}", range.resolution.name(), format_sim_time(range.start), format_sim_time(range.end)))
+        .collect::<Vec<_>>()
+        .join(", ")).small());
+
+    if ui.button("Export CSV...").clicked() {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("statistics.csv")
+            .save_file()
+        {
+            match crate::error::write_atomically(&path, statistics.to_csv().as_bytes()) {
+                Ok(()) => notifications.success(format!("Exported statistics to {}", path.display())),
+                Err(e) => notifications.error(&e),
+            }
+        }
+    }
+}
+
+/// Mean line over a min-max band, one column per pixel across every history tier
+fn render_statistic_plot(ui: &mut egui::Ui, statistics: &StatisticsHistory, name: &str, columns: &[StatSummary], start: f32, end: f32) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(columns.len() as f32, HISTORY_PLOT_HEIGHT), egui::Sense::hover());
+    let filled = || columns.iter().filter(|column| column.count > 0);
+    let low = filled().map(|column| column.min).fold(f32::INFINITY, f32::min);
+    let high = filled().map(|column| column.max).fold(f32::NEG_INFINITY, f32::max);
+    if !(low.is_finite() && high.is_finite()) {
+        return;
+    }
+    let high = high.max(low + 1.0);
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+    let time_to_x = |time: f32| rect.left() + (time - start) / (end - start) * rect.width();
+    // Coarser history sits on a lighter background
+    for range in statistics.history.coverage() {
+        let gray = match range.resolution {
+            Resolution::Minute => 45,
+            Resolution::Second => 35,
+            Resolution::Raw => continue,
+        };
+        let x_range = egui::Rangef::new(time_to_x(range.start), time_to_x(range.end));
+        painter.rect_filled(egui::Rect::from_x_y_ranges(x_range, rect.y_range()), 0.0, egui::Color32::from_gray(gray));
+    }
+
+    let color = egui::Color32::from_rgb(100, 180, 250);
+    let y_of = |value: f32| rect.bottom() - 2.0 - (value - low) / (high - low) * (rect.height() - 4.0);
+    let mut line = Vec::new();
+    for (index, column) in columns.iter().enumerate() {
+        let x = rect.left() + index as f32 + 0.5;
+        match column.mean() {
+            Some(mean) => {
+                painter.line_segment([egui::pos2(x, y_of(column.min)), egui::pos2(x, y_of(column.max))],
+                    egui::Stroke::new(1.0, color.gamma_multiply(0.35)));
+                line.push(egui::pos2(x, y_of(mean)));
+            }
+            None if !line.is_empty() => {
+                painter.add(egui::Shape::line(std::mem::take(&mut line), egui::Stroke::new(1.5, color)));
+            }
+            None => {}
+        }
+    }
+    if !line.is_empty() {
+        painter.add(egui::Shape::line(line, egui::Stroke::new(1.5, color)));
+    }
+    painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP,
+        format!("{} ({} to {})", name, format_value(low), format_value(high)),
+        egui::FontId::proportional(11.0), egui::Color32::from_gray(200));
+
+    if let Some(pos) = response.hover_pos() {
+        let index = ((pos.x - rect.left()).max(0.0) as usize).min(columns.len().saturating_sub(1));
+        if let Some(column) = columns.get(index).filter(|column| column.count > 0) {
+            let time = start + (index as f32 + 0.5) / columns.len() as f32 * (end - start);
+            let resolution = statistics.history.coverage().into_iter()
+                .find(|range| time <= range.end)
+                .map_or(Resolution::Raw, |range| range.resolution);
+            response.on_hover_text(format!("{}: {} (min {}, max {}) around {}, {}",
+                name, format_value(column.mean().unwrap_or(0.0)), column.min, column.max,
+                format_sim_time(time), resolution.name()));
+        }
+    }
+}
+
+/// Plot value: whole numbers as counts, small ones with three significant decimals
+fn format_value(value: f32) -> String {
+    if value.fract() == 0.0 || value.abs() >= 100.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.3}", value)
+    }
+}
+
+/// Editor of the user-defined metrics, validating each expression as it is typed
+fn render_derived_metrics(ui: &mut egui::Ui, settings: &mut DerivedMetricSettings, genome: &crate::genome::GenomeData) {
+    ui.label(egui::RichText::new("Derived Metrics").strong())
+        .on_hover_text(format!(
+            "Formulas over the statistics, recorded with every statistics sample, plotted above, \
+             exported in the CSV and usable by breakpoints and the watchdog.\n\
+             Numbers, + - * / and parentheses, with: {}",
+            Variable::NAMES.join(", ")
+        ));
+
+    let mut remove = None;
+    for (index, metric) in settings.metrics.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut metric.name).desired_width(90.0));
+            ui.add(egui::TextEdit::singleline(&mut metric.expression).desired_width(ui.available_width() - 90.0).code_editor());
+            egui::ComboBox::from_id_salt(("metric_division", metric.id))
+                .width(40.0)
+                .selected_text(format!("÷0 {}", metric.division_by_zero.name()))
+                .show_ui(ui, |ui| {
+                    for policy in DivisionByZero::ALL {
+                        ui.selectable_value(&mut metric.division_by_zero, policy, format!("Division by zero gives {}", policy.name()));
+                    }
+                });
+            if ui.small_button("x").on_hover_text("Remove").clicked() {
+                remove = Some(index);
+            }
+        });
+        if let Err(error) = Expr::compile(&metric.expression, genome.modes.len()) {
+            ui.colored_label(egui::Color32::from_rgb(230, 110, 90), format!("{}: {}", metric.name, error));
+        }
+    }
+    if let Some(index) = remove {
+        settings.metrics.remove(index);
+    }
+    if ui.button("Add metric").clicked() {
+        settings.add();
+    }
+}
+
+/// Simulated time as h:mm:ss
+fn format_sim_time(seconds: f32) -> String {
+    let total = seconds.max(0.0) as u32;
+    format!("{}:{:02}:{:02}", total / 3600, total / 60 % 60, total % 60)
+}
+
+fn render_divisions(ui: &mut egui::Ui, division: &DivisionStatistics) {
+    ui.label(egui::RichText::new("Divisions").strong());
+
+    let Some(stats) = division.stats else {
+        ui.label("No active simulation");
+        return;
+    };
+    egui::Grid::new("division_stats")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Total:");
+            ui.label(stats.divisions.to_string());
+            ui.end_row();
+
+            if let Some(phase) = &division.active_phase {
+                ui.label("Phase:");
+                ui.label(phase).on_hover_text("Genome phase in effect (set up in the Genome Phases panel)");
+                ui.end_row();
+            }
+
+            ui.label("Deferred:");
+            ui.label(stats.deferred.to_string())
+                .on_hover_text("Ready cells held back a pass because an adhered neighbor divided first");
+            ui.end_row();
+
+            ui.label("Last step:");
+            ui.label(format!("{} divided, {} deferred, {} passes",
+                stats.last_step_divisions, stats.last_step_deferred, stats.last_step_passes));
+            ui.end_row();
+        });
+}
+
+fn render_population(
+    ui: &mut egui::Ui,
+    population: &mut PopulationStats,
+    settings: &mut PopulationStatsSettings,
+    genome: &crate::genome::GenomeData,
+) {
+    ui.label(egui::RichText::new("Population").strong())
+        .on_hover_text("Per-mode means of the active simulation; large populations are estimated from a random sample");
+
+    egui::Grid::new("population_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Exact up to:");
+            ui.add(egui::DragValue::new(&mut settings.exact_threshold).speed(100.0).range(0..=1_000_000).suffix(" cells"));
+            ui.end_row();
+
+            ui.label("Sample size:");
+            ui.add(egui::DragValue::new(&mut settings.sample_size).speed(50.0).range(100..=100_000).suffix(" cells"))
+                .on_hover_text("Cells drawn per sample above the exact threshold; 4x the cells halves the uncertainty");
+            ui.end_row();
+
+            ui.label("Rate:");
+            ui.add(egui::DragValue::new(&mut settings.samples_per_second).speed(0.5).range(1.0..=60.0).suffix(" /s"));
+            ui.end_row();
+        });
+
+    let Some(sample) = &population.latest else {
+        ui.label("No active simulation");
+        return;
+    };
+    if sample.exact {
+        ui.label(format!("Exact over all {} cells", sample.population));
+    } else {
+        ui.label(format!("Estimated from {} of {} cells (± 95% confidence)", sample.sampled, sample.population))
+            .on_hover_text("Modes are summarized from their cells in the sample; rare modes have wider intervals");
+    }
+    let estimate = |estimate: crate::simulation::population_stats::Estimate, decimals: usize| {
+        if sample.exact {
+            format!("{:.*}", decimals, estimate.mean)
+        } else {
+            format!("{:.*} ± {:.*}", decimals, estimate.mean, decimals, estimate.margin)
+        }
+    };
+
+    egui::Grid::new("population_stats")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Cells:");
+            ui.label(sample.population.to_string());
+            ui.label("Divisions:");
+            ui.label(population.divisions.to_string());
+            ui.end_row();
+
+            ui.label("Deaths:");
+            ui.label(population.deaths.map_or("-".to_string(), |deaths| deaths.to_string()))
+                .on_hover_text("Cells removed from the main simulation");
+            ui.label("Kinetic energy:");
+            ui.label(format!("{:.4}", sample.kinetic_energy))
+                .on_hover_text("Translational and rotational kinetic energy of all cells");
+            ui.end_row();
+        });
+
+    egui::Grid::new("population_modes")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Mode");
+            ui.label("Cells");
+            ui.label("Mass");
+            ui.label("Speed");
+            ui.end_row();
+
+            ui.label("All");
+            ui.label(sample.population.to_string());
+            ui.label(estimate(sample.mass, 3));
+            ui.label(estimate(sample.speed, 3));
+            ui.end_row();
+
+            for mode in &sample.modes {
+                let name = genome.modes.get(mode.mode).map_or_else(|| format!("Mode {}", mode.mode), |m| m.name.clone());
+                ui.label(name).on_hover_text(format!("{} cells in the sample", mode.sampled));
+                ui.label(estimate(mode.count, 0));
+                ui.label(estimate(mode.mass, 3));
+                ui.label(estimate(mode.speed, 3));
+                ui.end_row();
+            }
+        });
+
+    // Histograms are only computed while their plots are open
+    let shown = egui::CollapsingHeader::new("Distributions")
+        .id_salt("population_distributions")
+        .show(ui, |ui| {
+            match (&sample.mass_histogram, &sample.speed_histogram) {
+                (Some(mass), Some(speed)) => {
+                    render_histogram(ui, "Mass", mass);
+                    render_histogram(ui, "Speed", speed);
+                }
+                _ => {
+                    ui.label("Sampling...");
+                }
+            }
+        })
+        .body_returned
+        .is_some();
+    population.distributions_visible |= shown;
+}
+
+/// Bars of a population distribution
+fn render_histogram(ui: &mut egui::Ui, name: &str, histogram: &crate::simulation::population_stats::Histogram) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), HISTORY_PLOT_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+    let tallest = histogram.bins.iter().copied().fold(0.0f32, f32::max).max(1.0);
+    let width = rect.width() / histogram.bins.len() as f32;
+    for (index, &cells) in histogram.bins.iter().enumerate() {
+        let height = cells / tallest * (rect.height() - 14.0);
+        let left = rect.left() + index as f32 * width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(egui::pos2(left + 0.5, rect.bottom() - height), egui::pos2(left + width - 0.5, rect.bottom())),
+            0.0,
+            egui::Color32::from_rgb(100, 180, 250),
+        );
+    }
+    painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP,
+        format!("{} (0 to {:.2})", name, histogram.max), egui::FontId::proportional(11.0), egui::Color32::from_gray(200));
+
+    if let Some(pos) = response.hover_pos() {
+        let index = (((pos.x - rect.left()) / width).max(0.0) as usize).min(histogram.bins.len() - 1);
+        let bin = histogram.max / histogram.bins.len() as f32;
+        response.on_hover_text(format!("{:.2} to {:.2}: {:.0} cells", index as f32 * bin, (index + 1) as f32 * bin, histogram.bins[index]));
+    }
+}
+
+fn render_mass_audit(ui: &mut egui::Ui, audit: &mut MassAudit) {
+    ui.label(egui::RichText::new("Mass Conservation").strong())
+        .on_hover_text("Total mass drift of the active simulation; with nutrient gain, consumption, divisions and deaths off it should stay at zero");
+
+    ui.horizontal(|ui| {
+        if ui.checkbox(&mut audit.enabled, "Audit").changed() {
+            audit.reset();
+        }
+        if ui.add_enabled(audit.enabled, egui::Button::new("Reset")).clicked() {
+            audit.reset();
+        }
+    });
+    let (Some(baseline), Some(drift)) = (audit.baseline(), audit.current_drift()) else {
+        if audit.enabled {
+            ui.label("No active simulation");
+        }
+        return;
+    };
+    egui::Grid::new("mass_audit")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Total:");
+            ui.label(format!("{:.6} (from {:.6})", audit.total(), baseline));
+            ui.end_row();
+
+            ui.label("Drift:");
+            ui.label(format!("{:+.2e}", drift));
+            ui.end_row();
+
+            ui.label("Worst:");
+            ui.label(format!("{:.2e}", audit.worst_drift()))
+                .on_hover_text("Largest drift over the graphed frames");
+            ui.end_row();
+        });
+
+    // Drift per simulated frame, zero on the center line
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), HISTORY_PLOT_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+    painter.hline(rect.x_range(), rect.center().y, egui::Stroke::new(1.0, egui::Color32::from_gray(60)));
+    let samples = audit.drift();
+    let scale = audit.worst_drift().max(f32::EPSILON);
+    let points: Vec<egui::Pos2> = samples.iter().enumerate()
+        .map(|(index, drift)| egui::pos2(
+            rect.left() + index as f32 / samples.len().max(2).saturating_sub(1) as f32 * rect.width(),
+            rect.center().y - drift / scale * (rect.height() / 2.0 - 2.0),
+        ))
+        .collect();
+    if points.len() > 1 {
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 180, 250))));
+    }
+    painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP,
+        format!("±{:.1e}", scale), egui::FontId::proportional(11.0), egui::Color32::from_gray(200));
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
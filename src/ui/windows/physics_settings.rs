@@ -0,0 +1,273 @@
+use bevy_egui::egui;
+use crate::simulation::physics_layers::ConfigLayer;
+use crate::simulation::{PhysicsConfig, PhysicsLayers};
+
+/// Physics settings; each value is badged with the layer it comes from
+///
+/// Edits go to the scene layer (see `PhysicsLayers`); the badge menu promotes a value
+/// to the global layer or clears it back to the built-in default.
+pub fn render(
+    ui: &mut egui::Ui,
+    physics_config: &mut PhysicsConfig,
+    layers: &mut PhysicsLayers,
+) {
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        render_layer_summary(ui, layers);
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Timing").strong());
+
+        // Timestep (dt) - edited in Hz since that's how people think about it
+        let mut timestep_hz = 1.0 / physics_config.fixed_timestep;
+        ui.horizontal(|ui| {
+            ui.label("Timestep:");
+            if ui.add(egui::DragValue::new(&mut timestep_hz)
+                .speed(1.0)
+                .range(16.0..=1024.0)
+                .suffix(" Hz"))
+                .changed()
+            {
+                physics_config.fixed_timestep = 1.0 / timestep_hz;
+            }
+            ui.label(format!("({:.2} ms)", physics_config.fixed_timestep * 1000.0));
+            layer_badge(ui, layers, "fixed_timestep");
+        });
+        ui.label(egui::RichText::new(
+            "Simulated time covered by one tick. Smaller steps are more stable; \
+             simulated seconds (split intervals, ages) are unaffected."
+        ).small().weak());
+
+        ui.add_space(4.0);
+
+        let mut override_rate = physics_config.ticks_per_second_override.is_some();
+        ui.horizontal(|ui| {
+            ui.label("Tick rate:");
+            if ui.checkbox(&mut override_rate, "Override").changed() {
+                physics_config.ticks_per_second_override = override_rate
+                    .then(|| 1.0 / physics_config.fixed_timestep);
+            }
+            match physics_config.ticks_per_second_override.as_mut() {
+                Some(tps) => {
+                    ui.add(egui::DragValue::new(tps)
+                        .speed(1.0)
+                        .range(1.0..=2048.0)
+                        .suffix(" ticks/s"));
+                }
+                None => {
+                    ui.label(format!("{:.0} ticks/s", physics_config.ticks_per_second()));
+                }
+            }
+            layer_badge(ui, layers, "ticks_per_second_override");
+        });
+
+        let realtime_ratio = physics_config.ticks_per_second() * physics_config.fixed_timestep;
+        ui.label(egui::RichText::new(format!(
+            "Ticks run per real second at 1x speed. Derived from the timestep by default \
+             (real-time). Currently {:.2} simulated seconds per real second.",
+            realtime_ratio
+        )).small().weak());
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Collisions").strong());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut physics_config.disable_collisions, "Disable collisions");
+            layer_badge(ui, layers, "disable_collisions");
+        });
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Reproducibility").strong());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut physics_config.strict_reproducibility, "Strict reproducibility");
+            layer_badge(ui, layers, "strict_reproducibility");
+        });
+        ui.label(egui::RichText::new(
+            "Trig, powers and rotations in the CPU step use software math, so a run gives the \
+             same result on every CPU and compiler. Slower; the GPU collision path is not covered."
+        ).small().weak());
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Adhesion Quality").strong());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut physics_config.adhesion_strict_quality, "Strict (full quality only)");
+            layer_badge(ui, layers, "adhesion_strict_quality");
+        });
+        ui.add_enabled_ui(!physics_config.adhesion_strict_quality, |ui| {
+            egui::Grid::new("adhesion_tiers")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.label("Calm for:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_calm_ticks).range(1..=10_000).suffix(" ticks"));
+                    layer_badge(ui, layers, "adhesion_calm_ticks");
+                    ui.end_row();
+
+                    ui.label("Calm angle:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_calm_angle).speed(0.001).range(0.0..=0.5).suffix(" rad"));
+                    layer_badge(ui, layers, "adhesion_calm_angle");
+                    ui.end_row();
+
+                    ui.label("Calm strain:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_calm_strain).speed(0.001).range(0.0..=0.5));
+                    layer_badge(ui, layers, "adhesion_calm_strain");
+                    ui.end_row();
+
+                    ui.label("Wake strain:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_wake_strain).speed(0.001).range(0.0..=0.5));
+                    layer_badge(ui, layers, "adhesion_wake_strain");
+                    ui.end_row();
+
+                    ui.label("Wake angle:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_wake_angle).speed(0.001).range(0.0..=1.0).suffix(" rad"));
+                    layer_badge(ui, layers, "adhesion_wake_angle");
+                    ui.end_row();
+
+                    ui.label("Wake spin:");
+                    ui.add(egui::DragValue::new(&mut physics_config.adhesion_wake_angular_velocity).speed(0.01).range(0.0..=10.0).suffix(" rad/s"));
+                    layer_badge(ui, layers, "adhesion_wake_angular_velocity");
+                    ui.end_row();
+                });
+        });
+        ui.label(egui::RichText::new(
+            "Bonds that stay within the calm limits drop to a linear-spring-only model \
+             and return to the full model as soon as they cross a wake limit."
+        ).small().weak());
+        ui.horizontal(|ui| {
+            ui.label("Max bond force:");
+            ui.add(egui::DragValue::new(&mut physics_config.adhesion_max_force).speed(10.0).range(1.0..=1_000_000.0));
+            layer_badge(ui, layers, "adhesion_max_force");
+        });
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Genome Orientation").strong());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut physics_config.reanchor_genome_orientations, "Re-anchor to lineage");
+            layer_badge(ui, layers, "reanchor_genome_orientations");
+        });
+        ui.label(egui::RichText::new(
+            "Child orientations are recomputed in double precision from the last few generations \
+             instead of composed from the parent's, so long lineages don't slowly skew."
+        ).small().weak());
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Central Attractor").strong());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut physics_config.attractor_enabled, "Pull cells toward the center");
+            layer_badge(ui, layers, "attractor_enabled");
+        });
+        ui.add_enabled_ui(physics_config.attractor_enabled, |ui| {
+            egui::Grid::new("central_attractor")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.label("Strength:");
+                    ui.add(egui::DragValue::new(&mut physics_config.attractor_strength).speed(10.0).range(0.0..=100_000.0));
+                    layer_badge(ui, layers, "attractor_strength");
+                    ui.end_row();
+
+                    ui.label("Softening:");
+                    ui.add(egui::DragValue::new(&mut physics_config.attractor_softening).speed(0.1).range(0.0..=100.0));
+                    layer_badge(ui, layers, "attractor_softening");
+                    ui.end_row();
+                });
+        });
+        ui.label(egui::RichText::new(
+            "Gravity-like pull toward the origin, smoothed out within the softening radius. \
+             Velocity damping slows orbiting cells down until they fall in."
+        ).small().weak());
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        ui.label(egui::RichText::new("Chemical Field").strong());
+        egui::Grid::new("chemical_field")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Resolution:");
+                ui.add(egui::DragValue::new(&mut physics_config.chemical_grid_resolution).range(4..=128).suffix(" voxels"));
+                layer_badge(ui, layers, "chemical_grid_resolution");
+                ui.end_row();
+
+                ui.label("Diffusion:");
+                ui.add(egui::DragValue::new(&mut physics_config.chemical_diffusion).speed(0.1).range(0.0..=500.0));
+                layer_badge(ui, layers, "chemical_diffusion");
+                ui.end_row();
+
+                ui.label("Decay:");
+                ui.add(egui::DragValue::new(&mut physics_config.chemical_decay).speed(0.001).range(0.0..=1.0).suffix("/s"));
+                layer_badge(ui, layers, "chemical_decay");
+                ui.end_row();
+            });
+        ui.label(egui::RichText::new(
+            "Secreted substances spread and fade on a voxel grid over the world sphere. \
+             Changing the resolution clears the field."
+        ).small().weak());
+    });
+}
+
+/// Override counts per layer, with bulk promote/clear of the scene layer
+fn render_layer_summary(ui: &mut egui::Ui, layers: &mut PhysicsLayers) {
+    ui.label(egui::RichText::new("Overrides").strong());
+    ui.label(format!("{} scene, {} global", layers.scene.len(), layers.global.len()))
+        .on_hover_text("Scene overrides are saved with the scene; global overrides apply to every scene");
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!layers.scene.is_empty(), egui::Button::new("Promote Scene to Global"))
+            .on_hover_text("Make this scene's physics the defaults for every scene")
+            .clicked()
+        {
+            let fields: Vec<String> = layers.scene.0.keys().cloned().collect();
+            for field in fields {
+                layers.promote(&field);
+            }
+        }
+        if ui.add_enabled(!layers.scene.is_empty(), egui::Button::new("Clear Scene"))
+            .on_hover_text("Drop this scene's overrides (global overrides and defaults apply)")
+            .clicked()
+        {
+            layers.scene = Default::default();
+        }
+    });
+}
+
+/// Badge naming the layer `field`'s value comes from; its menu promotes or clears the value
+fn layer_badge(ui: &mut egui::Ui, layers: &mut PhysicsLayers, field: &str) {
+    let layer = layers.layer_of(field);
+    let color = match layer {
+        ConfigLayer::Default => ui.visuals().weak_text_color(),
+        ConfigLayer::Global => egui::Color32::from_rgb(110, 170, 240),
+        ConfigLayer::Scene => egui::Color32::from_rgb(240, 180, 80),
+    };
+    ui.menu_button(egui::RichText::new(layer.badge()).small().strong().color(color), |ui| {
+        ui.label(layer.description());
+        ui.separator();
+        if ui.add_enabled(layer == ConfigLayer::Scene, egui::Button::new("Promote to Global"))
+            .on_hover_text("Use this value in every scene")
+            .clicked()
+        {
+            layers.promote(field);
+            ui.close();
+        }
+        if ui.add_enabled(layer != ConfigLayer::Default, egui::Button::new("Clear to Default"))
+            .on_hover_text("Remove the scene and global overrides")
+            .clicked()
+        {
+            layers.clear(field);
+            ui.close();
+        }
+    })
+    .response
+    .on_hover_text(layer.description());
+}
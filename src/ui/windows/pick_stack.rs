@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::input::picking::CYCLE_PIXELS;
+use crate::input::Picking;
+
+/// List of the cells under the last Alt+click with the picked one highlighted,
+/// shown next to the cursor until it moves away
+pub fn render_pick_stack(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut picking: ResMut<Picking>,
+    current_genome: Res<crate::genome::CurrentGenome>,
+) {
+    let Some(last) = picking.last.as_ref().filter(|last| last.cycling) else {
+        return;
+    };
+    let near = window_query.single().ok()
+        .and_then(|window| window.cursor_position())
+        .is_some_and(|cursor| cursor.distance(last.cursor) <= CYCLE_PIXELS * 4.0);
+    if !near {
+        picking.last = None;
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    egui::Area::new(egui::Id::new("pick_stack"))
+        .fixed_pos(egui::pos2(last.cursor.x + 16.0, last.cursor.y + 16.0))
+        .interactable(false)
+        .show(egui_context.get_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new(format!("{} cells under the cursor (Alt+click for the next)", last.stack.len())).small().weak());
+                for (depth, (mode, distance)) in last.stack.iter().enumerate() {
+                    let name = current_genome.genome.modes.get(*mode).map_or("?", |mode| mode.name.as_str());
+                    let text = egui::RichText::new(format!("{}. {} ({:.1})", depth + 1, name, distance));
+                    ui.label(if depth == last.depth { text.strong() } else { text.weak() });
+                }
+            });
+        });
+}
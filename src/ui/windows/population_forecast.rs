@@ -0,0 +1,176 @@
+use bevy_egui::egui;
+use crate::genome::{forecast_populations, CurrentGenome, ForecastOutlook, PopulationForecast};
+use crate::simulation::population_stats::ModePopulationHistory;
+use crate::ui::GenomeEditorState;
+
+/// Forecast samples across the horizon
+const FORECAST_STEPS: usize = 600;
+
+/// Modes drawn in the plot (the most populous at the horizon)
+const MAX_PLOTTED_MODES: usize = 8;
+
+const PLOT_HEIGHT: f32 = 180.0;
+
+/// Idealized mode populations of the edited genome over a horizon, with the measured
+/// populations of the active simulation drawn over them
+///
+/// The forecast is a branching process on the mode graph (see `genome::population_forecast`),
+/// so the gap to the measured curves is what space, mass and nutrients cost.
+pub fn render(
+    ui: &mut egui::Ui,
+    current_genome: &CurrentGenome,
+    editor: &mut GenomeEditorState,
+    measured: &ModePopulationHistory,
+    guardrail: &mut super::growth_guardrail::GrowthGuardrailSettings,
+) {
+    ui.horizontal(|ui| {
+        ui.add(egui::Slider::new(&mut editor.forecast_horizon, 5.0..=600.0).logarithmic(true).text("Horizon (s)"));
+    });
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut editor.forecast_log_scale, "Log scale");
+        ui.checkbox(&mut editor.forecast_show_measured, "Measured overlay")
+            .on_hover_text("Draw the populations recorded from the active simulation as dots");
+    });
+
+    super::growth_guardrail::render_settings(ui, guardrail);
+
+    let genome = &current_genome.genome;
+    let horizon = editor.forecast_horizon;
+    let stale = editor.forecast.as_ref()
+        .is_none_or(|(source, source_horizon, _)| *source_horizon != horizon || !source.functionally_equal(genome));
+    if stale {
+        editor.forecast = Some((genome.clone(), horizon, forecast_populations(genome, horizon, FORECAST_STEPS)));
+    }
+    let Some((_, _, forecast)) = editor.forecast.as_ref() else {
+        return;
+    };
+
+    render_outlook(ui, forecast);
+    ui.label(egui::RichText::new(
+        "One starting cell; every cell divides exactly at its mode's mean split interval. \
+         Space, mass, nutrients, adhesion limits and phases are ignored."
+    ).small().weak());
+    ui.separator();
+
+    let last = forecast.samples.len() - 1;
+    let mut modes: Vec<usize> = (0..genome.modes.len())
+        .filter(|&mode| forecast.samples.iter().any(|counts| counts[mode] > 0.0))
+        .collect();
+    modes.sort_by(|&a, &b| forecast.samples[last][b].total_cmp(&forecast.samples[last][a]));
+    modes.truncate(MAX_PLOTTED_MODES);
+
+    let measured = editor.forecast_show_measured.then_some(measured).filter(|measured| !measured.is_empty());
+    render_plot(ui, current_genome, forecast, &modes, measured, editor.forecast_log_scale);
+
+    egui::Grid::new("population_forecast_modes")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("Mode").strong());
+            ui.label(egui::RichText::new(format!("Forecast at {:.0} s", horizon)).strong());
+            ui.label(egui::RichText::new("Measured (latest)").strong());
+            ui.end_row();
+            let latest = measured.and_then(|measured| measured.samples().last());
+            for &mode in &modes {
+                ui.horizontal(|ui| {
+                    let (swatch, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+                    ui.painter().rect_filled(swatch, 2.0, mode_color(current_genome, mode));
+                    ui.label(&genome.modes[mode].name);
+                });
+                ui.label(format_count(forecast.samples[last][mode]));
+                match latest {
+                    Some((time, counts)) => ui.label(format!("{} at {:.0} s", counts.get(mode).copied().unwrap_or(0), time)),
+                    None => ui.label("-"),
+                };
+                ui.end_row();
+            }
+        });
+}
+
+fn render_outlook(ui: &mut egui::Ui, forecast: &PopulationForecast) {
+    let warning = ui.visuals().warn_fg_color;
+    match forecast.outlook {
+        ForecastOutlook::Divergent => {
+            let doubling = forecast.doubling_time()
+                .map(|doubling| format!(", doubling every {:.1} s", doubling))
+                .unwrap_or_default();
+            ui.colored_label(warning, format!("Divergent: unbounded exponential growth{}", doubling));
+        }
+        ForecastOutlook::Linear => {
+            ui.label("Linear: lineages cycle without branching, so growth stays linear");
+        }
+        ForecastOutlook::DeadEnd { final_population } => {
+            ui.colored_label(warning, format!(
+                "Dead end: every lineage ends in cells that never divide ({} cells in the end)",
+                format_count(final_population)
+            ));
+        }
+    }
+}
+
+fn render_plot(
+    ui: &mut egui::Ui,
+    current_genome: &CurrentGenome,
+    forecast: &PopulationForecast,
+    modes: &[usize],
+    measured: Option<&ModePopulationHistory>,
+    log_scale: bool,
+) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), PLOT_HEIGHT), egui::Sense::hover());
+    let horizon = forecast.horizon();
+    let scale = |count: f64| if log_scale { (1.0 + count).log10() } else { count };
+    let mut high = forecast.samples.iter()
+        .flat_map(|counts| modes.iter().map(|&mode| counts[mode]))
+        .fold(1.0, f64::max);
+    if let Some(measured) = measured {
+        for (_, counts) in measured.samples().filter(|(time, _)| *time <= horizon) {
+            high = modes.iter().filter_map(|&mode| counts.get(mode)).fold(high, |high, &count| high.max(count as f64));
+        }
+    }
+    let high = scale(high).max(f64::EPSILON);
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+    let x_of = |time: f32| rect.left() + time / horizon * rect.width();
+    let y_of = |count: f64| rect.bottom() - 2.0 - (scale(count) / high) as f32 * (rect.height() - 4.0);
+
+    for &mode in modes {
+        let color = mode_color(current_genome, mode);
+        let points: Vec<egui::Pos2> = forecast.samples.iter().enumerate()
+            .map(|(k, counts)| egui::pos2(x_of(k as f32 * forecast.step), y_of(counts[mode])))
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+
+        if let Some(measured) = measured {
+            for (time, counts) in measured.samples().filter(|(time, _)| *time <= horizon) {
+                let count = counts.get(mode).copied().unwrap_or(0) as f64;
+                painter.circle_filled(egui::pos2(x_of(time), y_of(count)), 1.5, color);
+            }
+        }
+    }
+    painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP,
+        format!("{} cells{}", format_count(forecast.total(forecast.samples.len() - 1)), if log_scale { " (log)" } else { "" }),
+        egui::FontId::proportional(11.0), egui::Color32::from_gray(200));
+
+    if let Some(pos) = response.hover_pos() {
+        let time = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0) * horizon;
+        let sample = ((time / forecast.step).round() as usize).min(forecast.samples.len() - 1);
+        response.on_hover_text(format!("{:.1} s: {} cells forecast", time, format_count(forecast.total(sample))));
+    }
+}
+
+fn mode_color(current_genome: &CurrentGenome, mode: usize) -> egui::Color32 {
+    let color = current_genome.genome.modes[mode].color;
+    egui::Color32::from_rgb((color.x * 255.0) as u8, (color.y * 255.0) as u8, (color.z * 255.0) as u8)
+}
+
+/// Expected counts as whole cells, with thousands and scientific notation for huge ones
+fn format_count(count: f64) -> String {
+    if count >= 1e9 {
+        format!("{:.2e}", count)
+    } else if count >= 1e4 {
+        format!("{:.0}k", count / 1e3)
+    } else {
+        format!("{:.0}", count)
+    }
+}
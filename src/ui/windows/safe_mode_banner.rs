@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::safe_mode::SafeMode;
+use crate::ui::Notifications;
+
+/// Banner explaining what safe mode bypassed after repeated failed startups
+///
+/// Each bypassed subsystem can be restored (its saved settings load again at the next
+/// start) or reset (its files are kept as `.bak` and defaults are used from now on).
+pub fn render_safe_mode_banner(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    safe_mode: Option<ResMut<SafeMode>>,
+    mut notifications: ResMut<Notifications>,
+) {
+    let Some(mut safe_mode) = safe_mode else {
+        return;
+    };
+    if safe_mode.banner_dismissed || !safe_mode.is_active() {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let mut restore = None;
+    let mut reset = None;
+    let mut dismissed = false;
+    egui::Window::new("Safe Mode")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!(
+                "BioSpheres failed to start {} times in a row and started in safe mode.",
+                safe_mode.failed_startups
+            ));
+            if let Some(phase) = safe_mode.crashed_phase {
+                let culprits: Vec<&str> = phase.likely_culprits().iter().map(|s| s.name()).collect();
+                let mut text = format!("The last attempt crashed during {}.", phase.name());
+                if !culprits.is_empty() {
+                    text.push_str(&format!(" Likely cause: {}.", culprits.join(" or ")));
+                }
+                ui.label(text);
+            }
+            ui.label("Your files were left untouched. Bypassed this session:");
+            ui.add_space(4.0);
+
+            egui::Grid::new("safe_mode_subsystems")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for subsystem in safe_mode.session_bypassed.clone() {
+                        ui.label(egui::RichText::new(subsystem.name()).strong())
+                            .on_hover_text(subsystem.bypassed_description());
+                        if safe_mode.pending().contains(&subsystem) {
+                            if ui.button("Restore on next start")
+                                .on_hover_text("Load the saved settings again the next time BioSpheres starts")
+                                .clicked()
+                            {
+                                restore = Some(subsystem);
+                            }
+                            // Rendering has no files, restoring it is all there is
+                            if subsystem.files().is_empty() {
+                                ui.label("");
+                            } else if ui.button("Reset to defaults")
+                                .on_hover_text("Keep the saved files as .bak and use defaults from now on")
+                                .clicked()
+                            {
+                                reset = Some(subsystem);
+                            }
+                        } else {
+                            ui.label("restored on next start");
+                            ui.label("");
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(6.0);
+            if ui.button("Dismiss").clicked() {
+                dismissed = true;
+            }
+        });
+
+    if let Some(subsystem) = restore {
+        safe_mode.restore(subsystem);
+        notifications.info(format!("{} will be loaded at the next start", subsystem.name()));
+    }
+    if let Some(subsystem) = reset {
+        match safe_mode.reset(subsystem) {
+            Ok(()) => notifications.success(format!("{} reset to defaults", subsystem.name())),
+            Err(e) => notifications.error(&e),
+        }
+    }
+    if dismissed {
+        safe_mode.banner_dismissed = true;
+    }
+}
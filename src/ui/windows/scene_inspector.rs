@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::simulation::scene_inspector::{InspectAction, SceneFormat, SceneSummary};
+use crate::simulation::SceneInspector;
+
+/// Summary window of an inspected scene file with its load actions
+pub fn render_scene_inspector(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut inspector: ResMut<SceneInspector>,
+    cpu_cell_capacity: Res<crate::ui::CpuCellCapacity>,
+) {
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+    let ctx = egui_context.get_mut();
+
+    if let Some(path) = inspector.inspecting() {
+        let name = path.display().to_string();
+        egui::Window::new("Inspecting Scene")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!("Reading {}", name));
+                });
+            });
+    }
+
+    let busy = inspector.busy();
+    let mut action = None;
+    let mut keep_open = true;
+    let Some(inspection) = inspector.open.as_mut() else {
+        return;
+    };
+    egui::Window::new("Scene Inspector")
+        .open(&mut keep_open)
+        .default_size([420.0, 560.0])
+        .show(ctx, |ui| {
+            ui.label(egui::RichText::new(inspection.path.display().to_string()).small().weak());
+            let summary = match &inspection.result {
+                Ok(summary) => summary,
+                Err(reason) => {
+                    ui.colored_label(egui::Color32::from_rgb(220, 90, 70), "This file can't be read as a scene:");
+                    ui.label(reason);
+                    return;
+                }
+            };
+
+            egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+                render_header(ui, summary, cpu_cell_capacity.capacity);
+                if !summary.problems.is_empty() {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::from_rgb(220, 160, 60), "A full load would reject or misread this file:");
+                    for problem in &summary.problems {
+                        ui.label(format!("• {}", problem));
+                    }
+                }
+                ui.separator();
+                render_modes(ui, summary);
+                ui.separator();
+                render_preview(ui, summary, &mut inspection.view);
+            });
+
+            ui.separator();
+            ui.add_enabled_ui(busy.is_none(), |ui| {
+                ui.horizontal(|ui| {
+                    let loads_fully = summary.problems.is_empty();
+                    if ui.add_enabled(loads_fully, egui::Button::new("Load"))
+                        .on_hover_text("Load the whole scene, replacing the current one")
+                        .clicked()
+                    {
+                        action = Some(InspectAction::Load);
+                    }
+                    if ui.button("Load Genome Only").clicked() {
+                        action = Some(InspectAction::LoadGenome);
+                    }
+                    if ui.add_enabled(loads_fully && summary.cell_count > 0, egui::Button::new("Extract Initial Layout"))
+                        .on_hover_text("Use the scene's cells as the preview's initial layout, keeping the current genome and physics")
+                        .clicked()
+                    {
+                        action = Some(InspectAction::ExtractLayout);
+                    }
+                });
+            });
+            if busy.is_some() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Reading the whole scene...");
+                });
+            }
+        });
+
+    if action.is_some() {
+        inspector.action = action;
+    }
+    if !keep_open && busy.is_none() {
+        inspector.open = None;
+    }
+}
+
+fn render_header(ui: &mut egui::Ui, summary: &SceneSummary, cpu_capacity: usize) {
+    if !summary.description.is_empty() {
+        ui.label(egui::RichText::new(&summary.description).strong());
+    }
+    egui::Grid::new("scene_inspector_header").num_columns(2).striped(true).show(ui, |ui| {
+        ui.label("Format:");
+        ui.label(match (summary.format, summary.layout_version) {
+            (SceneFormat::Chunked, Some(layout)) => format!("Chunked (layout {}), scene version {}", layout, summary.format_version),
+            _ => format!("JSON, scene version {}", summary.format_version),
+        });
+        ui.end_row();
+
+        ui.label("Cells:");
+        if summary.cell_count == 0 {
+            ui.label("None (a single founder)");
+        } else {
+            ui.label(summary.cell_count.to_string());
+        }
+        ui.end_row();
+
+        ui.label("Tick:");
+        match (summary.tick, summary.time) {
+            (Some(tick), Some(time)) => ui.label(format!("{} (t = {:.2}s)", tick, time)),
+            _ => ui.label(egui::RichText::new("Not recorded").weak())
+                .on_hover_text("Only session snapshots save the time they were taken"),
+        };
+        ui.end_row();
+
+        ui.label("Genome:");
+        ui.label(format!("{} ({} modes)", summary.genome.name, summary.genome.modes.len()));
+        ui.end_row();
+
+        ui.label("Physics:");
+        ui.label(egui::RichText::new(format!("{:016x}", summary.physics_digest)).monospace())
+            .on_hover_text("Digest of the settings that affect outcomes; equal digests mean equal physics");
+        ui.end_row();
+
+        ui.label("Capacity:");
+        match summary.format {
+            SceneFormat::Chunked => ui.label(format!("{} cells in CPU mode", summary.load_capacity(cpu_capacity))),
+            SceneFormat::Json => ui.label("Loads into the Genome Editor preview"),
+        };
+        ui.end_row();
+
+        ui.label("Seed:");
+        ui.label(summary.rng_seed.to_string());
+        ui.end_row();
+    });
+}
+
+fn render_modes(ui: &mut egui::Ui, summary: &SceneSummary) {
+    egui::Grid::new("scene_inspector_modes").num_columns(3).striped(true).show(ui, |ui| {
+        ui.label(egui::RichText::new("Mode").strong());
+        ui.label(egui::RichText::new("Cells").strong());
+        ui.label(egui::RichText::new("Share").strong());
+        ui.end_row();
+        let total = summary.cell_count.max(1) as f32;
+        for (mode, count) in summary.cells_per_mode.iter().enumerate() {
+            let name = summary.genome.modes.get(mode).map_or("?", |mode| mode.name.as_str());
+            ui.label(name);
+            ui.label(count.to_string());
+            ui.label(format!("{:.1}%", *count as f32 / total * 100.0));
+            ui.end_row();
+        }
+    });
+}
+
+/// Orbitable point cloud of the sampled cells, colored by mode
+fn render_preview(ui: &mut egui::Ui, summary: &SceneSummary, view: &mut Vec2) {
+    let note = if summary.sample_stride > 1 {
+        format!("Preview of {} sampled cells (every {}th); drag to orbit", summary.sample.len(), summary.sample_stride)
+    } else {
+        "Preview of every cell; drag to orbit".to_string()
+    };
+    ui.label(egui::RichText::new(note).small().weak());
+
+    let side = ui.available_width().min(320.0);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::drag());
+    if response.dragged() {
+        let delta = response.drag_delta();
+        view.x += delta.x * 0.01;
+        view.y = (view.y + delta.y * 0.01).clamp(-1.5, 1.5);
+    }
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, egui::Color32::from_rgb(20, 24, 32));
+    if summary.sample.is_empty() {
+        painter.text(rect.center(), egui::Align2::CENTER_CENTER, "No cells", egui::FontId::proportional(14.0), egui::Color32::GRAY);
+        return;
+    }
+
+    let center = summary.sample.iter().map(|(position, _)| *position).sum::<Vec3>() / summary.sample.len() as f32;
+    let extent = summary.sample.iter().map(|(position, _)| position.distance(center)).fold(1e-3, f32::max);
+    let scale = side * 0.45 / extent;
+    let rotation = Quat::from_rotation_x(view.y) * Quat::from_rotation_y(view.x);
+    let mut points: Vec<(Vec3, usize)> = summary.sample.iter()
+        .map(|(position, mode)| (rotation * (*position - center), *mode))
+        .collect();
+    // Far points first so near ones draw over them
+    points.sort_by(|a, b| a.0.z.total_cmp(&b.0.z));
+    for (point, mode) in points {
+        let color = summary.genome.modes.get(mode).map_or(Vec3::splat(0.6), |mode| mode.color);
+        let shade = 0.6 + 0.4 * (point.z / extent * 0.5 + 0.5);
+        let color = egui::Color32::from_rgb(
+            (color.x * shade * 255.0) as u8,
+            (color.y * shade * 255.0) as u8,
+            (color.z * shade * 255.0) as u8,
+        );
+        painter.circle_filled(rect.center() + egui::vec2(point.x, -point.y) * scale, 2.0, color);
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::simulation::chunked_scene::SceneLoadPhase;
+use crate::simulation::cpu_sim::MainSimState;
+use crate::simulation::SceneLoader;
+
+/// Progress bar for a chunked scene load, with a Cancel button while cells are being read
+pub fn render_scene_load_progress(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut loader: ResMut<SceneLoader>,
+    main_state: Res<MainSimState>,
+) {
+    let Some((title, phase, done, total)) = loader.progress(&main_state).map(|(title, phase, done, total)| (title.to_string(), phase, done, total)) else {
+        return;
+    };
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let cancelling = loader.is_cancelling();
+    let mut cancel = false;
+    egui::Window::new("Loading Scene")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(&title);
+            let fraction = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+            let text = match phase {
+                SceneLoadPhase::Reading => format!("Reading cells {} / {}", done, total),
+                SceneLoadPhase::Spawning => format!("Spawning cells {} / {}", done, total),
+            };
+            ui.add(egui::ProgressBar::new(fraction).text(text).desired_width(280.0));
+            match phase {
+                SceneLoadPhase::Reading => {
+                    ui.add_enabled_ui(!cancelling, |ui| {
+                        cancel = ui.button(if cancelling { "Cancelling..." } else { "Cancel" })
+                            .on_hover_text("Stop loading and keep the current scene")
+                            .clicked();
+                    });
+                }
+                SceneLoadPhase::Spawning => {
+                    ui.label(egui::RichText::new("The simulation resumes once every cell is shown.").small().weak());
+                }
+            }
+        });
+
+    if cancel {
+        loader.cancel();
+    }
+}
@@ -1,17 +1,53 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crate::simulation::SimulationMode;
+use crate::simulation::{DormantScenes, ExperimentSession, SettleRequests, SettleSettings, SimulationMode, Watchdog, WatchdogSettings};
+use crate::simulation::settle::{SettleChange, SettleCurve};
+use crate::simulation::watchdog::WatchdogCondition;
+use crate::simulation::capacity::{CapacityGrowth, AUTO_GROW_THRESHOLD, MAX_CELL_CAPACITY};
+use crate::simulation::scenario_presets::{LoadedScenario, ScenarioPreset, ScenarioPresets};
 
 /// Resource to request scene mode changes from UI
 #[derive(Resource, Default)]
 pub struct SceneModeRequest {
     pub requested_mode: Option<SimulationMode>,
+    /// Start the scene of this mode over (discards its dormant scene)
+    pub reset_requested: Option<SimulationMode>,
+    /// Id of a bundled example scenario to load
+    pub requested_preset: Option<String>,
+    /// Scene to load, with the title shown in the Scene Manager
+    pub requested_scene: Option<(String, crate::simulation::SceneFile)>,
+    /// Parameters of the orbital spawn preset, edited in the Scene Manager
+    pub orbital_spawn: crate::simulation::OrbitalSpawn,
+    /// Spawn the orbital preset with the current genome and physics settings
+    pub requested_orbital_spawn: bool,
+    /// Placement of the Preview founder, edited in the Scene Manager
+    pub preview_start: crate::simulation::PreviewStart,
+    /// Start the preview over from a founder placed by `preview_start`
+    pub requested_preview_respawn: bool,
+    /// CSV layout import and export, edited in the Scene Manager
+    pub layout_import: crate::simulation::LayoutImport,
+    /// Carry the preview on in CPU mode from the shown time (replaces the kept CPU scene)
+    pub continue_in_cpu: bool,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     ui: &mut egui::Ui,
     current_mode: SimulationMode,
     scene_request: &mut SceneModeRequest,
+    dormant: &DormantScenes,
+    presets: &ScenarioPresets,
+    loaded: &LoadedScenario,
+    capacity_growth: &mut CapacityGrowth,
+    session: &mut ExperimentSession,
+    watchdog_settings: &mut WatchdogSettings,
+    watchdog: &mut Watchdog,
+    metrics: &crate::simulation::DerivedMetrics,
+    settle_settings: &mut SettleSettings,
+    settle: &mut SettleRequests,
+    scene_inspector: &mut crate::simulation::SceneInspector,
+    gpu_compute: &crate::rendering::Support,
+    genome: &mut crate::genome::GenomeData,
 ) {
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -87,6 +123,678 @@ pub fn render(
         let gpu_response = ui.add_enabled_ui(false, |ui| {
             ui.add_sized(egui::vec2(button_width, button_height), gpu_button)
         }).inner;
-        gpu_response.on_disabled_hover_text("GPU mode is not yet implemented");
+        match gpu_compute.missing_reason() {
+            Some(reason) => gpu_response.on_disabled_hover_text(format!("GPU mode is unavailable on this graphics adapter: {}", reason)),
+            None => gpu_response.on_disabled_hover_text("GPU mode is not yet implemented"),
+        };
+
+        ui.add_space(8.0);
+        ui.separator();
+
+        render_scene_states(ui, current_mode, dormant, scene_request);
+        render_preview_start(ui, current_mode, scene_request);
+        render_seed(ui, current_mode, genome, scene_request);
+
+        ui.separator();
+
+        render_capacity(ui, capacity_growth);
+
+        ui.separator();
+
+        render_settle(ui, settle_settings, settle);
+
+        ui.separator();
+
+        render_session(ui, session);
+
+        ui.separator();
+
+        render_watchdog(ui, watchdog_settings, watchdog, metrics);
+
+        ui.separator();
+
+        // Examples section - bundled scenario presets
+        ui.label(egui::RichText::new("Examples").size(16.0).strong());
+        if let Some(title) = &loaded.title {
+            let status = if loaded.unsaved { " (unsaved copy)" } else { "" };
+            ui.label(egui::RichText::new(format!("Loaded: {}{}", title, status)).weak());
+        }
+
+        render_orbital_spawn(ui, scene_request);
+        render_layout_import(ui, scene_request);
+        if ui.button("Inspect Scene File...")
+            .on_hover_text("Summarize a saved scene without loading it")
+            .clicked()
+        {
+            scene_inspector.pick_requested = true;
+        }
+        ui.add_space(4.0);
+
+        for preset in &presets.presets {
+            let clicked = ui.horizontal(|ui| {
+                let thumbnail = draw_preset_thumbnail(ui, preset, 64.0);
+                let text = ui.vertical(|ui| {
+                    let title = ui.add(egui::Label::new(egui::RichText::new(&preset.title).strong())
+                        .sense(egui::Sense::click()));
+                    ui.add(egui::Label::new(egui::RichText::new(&preset.scene.description).small()).wrap());
+                    let load = ui.small_button("Load");
+                    title.clicked() || load.clicked()
+                }).inner;
+                thumbnail.clicked() || text
+            }).inner;
+
+            if clicked {
+                info!("Requesting preset '{}'", preset.id);
+                scene_request.requested_preset = Some(preset.id.clone());
+            }
+            ui.add_space(4.0);
+        }
+    });
+}
+
+/// What each mode resumes with when switched to, and a reset for each
+fn render_scene_states(ui: &mut egui::Ui, current_mode: SimulationMode, dormant: &DormantScenes, scene_request: &mut SceneModeRequest) {
+    ui.label(egui::RichText::new("Scenes").size(16.0).strong());
+    egui::Grid::new("scene_states")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for (mode, name) in [(SimulationMode::Preview, "Genome Editor"), (SimulationMode::Cpu, "CPU Mode")] {
+                ui.label(name);
+                let kept = dormant.summary(mode);
+                if mode == current_mode {
+                    ui.label("Active");
+                } else if let Some(summary) = kept {
+                    ui.label(format!("Kept: {} cells, t={:.1}s, {}",
+                        summary.cell_count, summary.time, super::performance_monitor::format_bytes(summary.bytes)))
+                        .on_hover_text("Restored as it was when you switch back");
+                } else {
+                    ui.label(egui::RichText::new("Starts fresh").weak());
+                }
+                let resettable = mode == current_mode || kept.is_some();
+                if ui.add_enabled(resettable, egui::Button::new("Reset scene"))
+                    .on_hover_text("Start this mode's scene over from the genome's initial cell")
+                    .clicked()
+                {
+                    scene_request.reset_requested = Some(mode);
+                }
+                ui.end_row();
+            }
+        });
+    if current_mode == SimulationMode::Preview {
+        continue_in_cpu_button(ui, scene_request);
+    }
+}
+
+/// Founder placement of the preview, applied by the next respawn or reset
+fn render_preview_start(ui: &mut egui::Ui, current_mode: SimulationMode, scene_request: &mut SceneModeRequest) {
+    use crate::simulation::preview_respawn::MAX_START_OFFSET;
+
+    egui::CollapsingHeader::new(egui::RichText::new("Preview Start").strong())
+        .id_salt("preview_start")
+        .show(ui, |ui| {
+            let start = &mut scene_request.preview_start;
+            egui::Grid::new("preview_start_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Position:");
+                    ui.horizontal(|ui| {
+                        for (axis, value) in ["x", "y", "z"].into_iter().zip(start.position.as_mut()) {
+                            ui.add(egui::DragValue::new(value).speed(0.5).prefix(format!("{}: ", axis))
+                                .range(-MAX_START_OFFSET..=MAX_START_OFFSET));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Orientation:");
+                    ui.horizontal(|ui| {
+                        let mut custom = start.orientation.is_some();
+                        if ui.checkbox(&mut custom, "Custom")
+                            .on_hover_text("Off uses the genome's initial orientation")
+                            .changed()
+                        {
+                            start.orientation = custom.then_some(Vec3::ZERO);
+                        }
+                        if let Some(degrees) = &mut start.orientation {
+                            for value in degrees.as_mut() {
+                                ui.add(egui::DragValue::new(value).speed(1.0).suffix("°").range(-180.0..=180.0));
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Mass:");
+                    ui.horizontal(|ui| {
+                        let mut custom = start.mass.is_some();
+                        if ui.checkbox(&mut custom, "Custom")
+                            .on_hover_text("Off uses the initial mode's split mass")
+                            .changed()
+                        {
+                            start.mass = custom.then_some(1.0);
+                        }
+                        if let Some(mass) = &mut start.mass {
+                            ui.add(egui::DragValue::new(mass).speed(0.05).range(0.1..=10.0));
+                        }
+                    });
+                    ui.end_row();
+                });
+            if start.position.length() > MAX_START_OFFSET {
+                ui.label(egui::RichText::new(format!("Starts {:.0} units from the center", MAX_START_OFFSET)).small().weak());
+            }
+            ui.checkbox(&mut start.animate, "Animate respawn")
+                .on_hover_text("Fade the old cells out and scale the new founder in");
+            ui.checkbox(&mut start.keep_framing, "Keep camera framing")
+                .on_hover_text("Move the camera with the founder so the view of it stays the same");
+
+            let preview = current_mode == SimulationMode::Preview;
+            if ui.add_enabled(preview, egui::Button::new("Respawn founder"))
+                .on_hover_text("Start the preview over from a founder placed here (also used by Reset scene)")
+                .on_disabled_hover_text("Available in the Genome Editor")
+                .clicked()
+            {
+                scene_request.requested_preview_respawn = true;
+            }
+        });
+}
+
+/// The genome's pinned seed and the runs started from it
+fn render_seed(ui: &mut egui::Ui, current_mode: SimulationMode, genome: &mut crate::genome::GenomeData, scene_request: &mut SceneModeRequest) {
+    let metadata = &mut genome.metadata;
+    ui.horizontal(|ui| {
+        ui.label("Seed:");
+        ui.add(egui::DragValue::new(&mut metadata.seed))
+            .on_hover_text("Random seed new runs of this genome start with (saved in the genome file)");
+        if ui.button("Randomize").clicked() {
+            metadata.seed = crate::genome::run_history::draw_seed();
+        }
+    });
+
+    // Offered for genomes shared with their history, so a collaborator's run can be reproduced
+    if let Some(seed) = metadata.last_run_seed() {
+        if ui.button(format!("Start with last used seed ({})", seed))
+            .on_hover_text("Pin the seed of the most recent run in the history and start the scene over")
+            .clicked()
+        {
+            metadata.seed = seed;
+            scene_request.reset_requested = Some(current_mode);
+        }
+    }
+
+    if metadata.runs.is_empty() {
+        return;
+    }
+    let current_hash = genome.content_hash();
+    let metadata = &mut genome.metadata;
+    egui::CollapsingHeader::new(format!("Run History ({})", metadata.runs.len()))
+        .id_salt("run_history")
+        .show(ui, |ui| {
+            let mut reuse = None;
+            egui::Grid::new("run_history_grid").num_columns(5).striped(true).show(ui, |ui| {
+                for column in ["Started (UTC)", "Run", "Seed", "Genome", ""] {
+                    ui.label(egui::RichText::new(column).small().strong());
+                }
+                ui.end_row();
+                for run in metadata.runs.iter().rev() {
+                    ui.label(egui::RichText::new(crate::simulation::experiment_session::format_utc(run.started_at)).small());
+                    ui.label(egui::RichText::new(run.kind.name()).small());
+                    ui.label(egui::RichText::new(run.seed.to_string()).small().monospace());
+                    let same = run.genome_hash == current_hash;
+                    ui.label(egui::RichText::new(format!("{:08x}", run.genome_hash >> 32)).small().monospace())
+                        .on_hover_text(if same {
+                            format!("{:016x}\nSame genome contents as now", run.genome_hash)
+                        } else {
+                            format!("{:016x}\nThe genome was edited since this run", run.genome_hash)
+                        });
+                    if ui.add_enabled(run.seed != metadata.seed, egui::Button::new("Reuse").small())
+                        .on_hover_text("Pin this seed for new runs")
+                        .clicked()
+                    {
+                        reuse = Some(run.seed);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(seed) = reuse {
+                metadata.seed = seed;
+            }
+        });
+}
+
+/// Button that carries the preview on in CPU mode from the scrubbed time
+pub fn continue_in_cpu_button(ui: &mut egui::Ui, scene_request: &mut SceneModeRequest) {
+    if ui.button("Continue in CPU mode from here")
+        .on_hover_text("Start CPU mode from the preview at the shown time, with the same cells, adhesions and seed (replaces the kept CPU scene)")
+        .clicked()
+    {
+        info!("Requesting to continue the preview in CPU mode");
+        scene_request.continue_in_cpu = true;
+    }
+}
+
+/// Orbital spawn preset: free cells on circular orbits around the central attractor
+fn render_orbital_spawn(ui: &mut egui::Ui, scene_request: &mut SceneModeRequest) {
+    egui::CollapsingHeader::new(egui::RichText::new("Orbital Spawn").strong())
+        .id_salt("orbital_spawn")
+        .show(ui, |ui| {
+            let spawn = &mut scene_request.orbital_spawn;
+            egui::Grid::new("orbital_spawn_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Cells:");
+                    ui.add(egui::DragValue::new(&mut spawn.cell_count).range(1..=2000));
+                    ui.end_row();
+
+                    ui.label("Radius:");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut spawn.inner_radius).speed(0.5).range(0.0..=spawn.outer_radius));
+                        ui.label("to");
+                        ui.add(egui::DragValue::new(&mut spawn.outer_radius).speed(0.5).range(spawn.inner_radius..=90.0));
+                    });
+                    ui.end_row();
+
+                    ui.label("Thickness:");
+                    ui.add(egui::DragValue::new(&mut spawn.thickness).speed(0.1).range(0.0..=40.0));
+                    ui.end_row();
+
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut spawn.seed));
+                    ui.end_row();
+                });
+            if ui.button("Spawn")
+                .on_hover_text("Start a scene of the current genome's initial mode orbiting the center. \
+                                Turns on the central attractor and turns off velocity damping.")
+                .clicked()
+            {
+                scene_request.requested_orbital_spawn = true;
+            }
+        });
+}
+
+/// CSV layout import with a dry-run preview, and export of the current initial layout
+fn render_layout_import(ui: &mut egui::Ui, scene_request: &mut SceneModeRequest) {
+    egui::CollapsingHeader::new(egui::RichText::new("Layout CSV").strong())
+        .id_salt("layout_csv")
+        .show(ui, |ui| {
+            let layout = &mut scene_request.layout_import;
+            ui.label(egui::RichText::new("Columns: x,y,z[,mode][,mass][,qx,qy,qz,qw]").small().weak());
+            ui.horizontal(|ui| {
+                if ui.button("Import Layout from CSV...").clicked() {
+                    layout.pick_requested = true;
+                }
+                if ui.button("Export Layout...")
+                    .on_hover_text("Save the active scene's initial cells in the same format")
+                    .clicked()
+                {
+                    layout.export_requested = true;
+                }
+            });
+
+            let Some(path) = &layout.path else {
+                return;
+            };
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            ui.label(format!("{}: {} cells", name, layout.rows.len()));
+            ui.radio_value(&mut layout.merge, false, "Replace the initial layout");
+            ui.radio_value(&mut layout.merge, true, "Merge with the initial layout")
+                .on_hover_text("Append the cells after the Genome Editor's current initial cells");
+            ui.checkbox(&mut layout.dry_run, "Dry run")
+                .on_hover_text("Draw the cells as ghost spheres without importing them (problem rows in red)");
+
+            if !layout.errors.is_empty() {
+                egui::ScrollArea::vertical()
+                    .id_salt("layout_csv_errors")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for error in &layout.errors {
+                            ui.colored_label(egui::Color32::from_rgb(230, 110, 90), error.to_string());
+                        }
+                    });
+            }
+            if ui.add_enabled(layout.can_import(), egui::Button::new("Import"))
+                .on_hover_text("Load the layout into the Genome Editor with the current genome and physics")
+                .on_disabled_hover_text("Fix the problems listed above first")
+                .clicked()
+            {
+                layout.import_requested = true;
+            }
+        });
+}
+
+/// Cell usage of the active simulation with runtime growth controls
+fn render_capacity(ui: &mut egui::Ui, growth: &mut CapacityGrowth) {
+    ui.label(egui::RichText::new("Cell Capacity").size(16.0).strong());
+    let Some((cell_count, capacity)) = growth.usage else {
+        ui.label(egui::RichText::new("No active simulation").weak());
+        return;
+    };
+
+    let fill = cell_count as f32 / capacity.max(1) as f32;
+    let color = if fill >= AUTO_GROW_THRESHOLD {
+        egui::Color32::from_rgb(220, 90, 70)
+    } else {
+        egui::Color32::from_rgb(90, 160, 220)
+    };
+    ui.add(egui::ProgressBar::new(fill)
+        .text(format!("{} / {} cells", cell_count, capacity))
+        .fill(color));
+
+    ui.horizontal(|ui| {
+        let next = CapacityGrowth::next_capacity(capacity);
+        let busy = growth.growing_to().or(growth.requested);
+        match busy {
+            Some(target) => {
+                ui.spinner();
+                ui.label(format!("Growing to {}...", target));
+            }
+            None => {
+                let button = ui.add_enabled(capacity < MAX_CELL_CAPACITY, egui::Button::new(format!("Grow to {}", next)));
+                if button.clicked() {
+                    growth.request(next);
+                }
+            }
+        }
     });
+    ui.checkbox(&mut growth.auto_grow, format!("Auto-grow at {:.0}% full", AUTO_GROW_THRESHOLD * 100.0))
+        .on_hover_text("Double the capacity whenever the cell count reaches the threshold");
+}
+
+/// Settle button with its ramp settings, the progress of a running settle and the kinetic energy
+fn render_settle(ui: &mut egui::Ui, settings: &mut SettleSettings, settle: &mut SettleRequests) {
+    ui.label(egui::RichText::new("Settle").size(16.0).strong())
+        .on_hover_text("Temporarily raise the global damping to calm a jittery structure; the physics settings are unchanged afterwards");
+    let Some(kinetic_energy) = settle.kinetic_energy else {
+        ui.label(egui::RichText::new("No active simulation").weak());
+        return;
+    };
+    ui.label(format!("Kinetic energy: {:.4}", kinetic_energy));
+
+    match settle.active {
+        Some(active) => {
+            ui.add(egui::ProgressBar::new(active.progress())
+                .text(format!("Damping x{:.1}", active.multiplier())));
+            if ui.button("Stop").clicked() {
+                settle.pending.push(SettleChange::Stop);
+            }
+        }
+        None => {
+            if ui.button("Settle").on_hover_text("Recorded on the preview timeline in the Genome Editor").clicked() {
+                settle.pending.push(SettleChange::Start(*settings));
+            }
+        }
+    }
+
+    egui::Grid::new("settle_settings").num_columns(2).show(ui, |ui| {
+        ui.label("Curve:");
+        egui::ComboBox::from_id_salt("settle_curve")
+            .selected_text(settings.curve.name())
+            .show_ui(ui, |ui| {
+                for curve in SettleCurve::ALL {
+                    ui.selectable_value(&mut settings.curve, curve, curve.name());
+                }
+            });
+        ui.end_row();
+
+        ui.label("Duration:");
+        ui.add(egui::DragValue::new(&mut settings.duration).speed(0.05).range(0.1..=30.0).suffix(" s"));
+        ui.end_row();
+
+        ui.label("Peak damping:");
+        ui.add(egui::DragValue::new(&mut settings.peak).speed(0.5).range(1.0..=200.0).prefix("x"))
+            .on_hover_text("Multiplier of the damping rate at the top of the ramp");
+        ui.end_row();
+
+        ui.checkbox(&mut settings.auto_stop, "Stop below:")
+            .on_hover_text(format!("End early once the kinetic energy stays below the threshold for {} ticks",
+                crate::simulation::settle::AUTO_STOP_CALM_TICKS));
+        ui.add_enabled(settings.auto_stop, egui::DragValue::new(&mut settings.energy_threshold).speed(0.001).range(0.0..=1000.0));
+        ui.end_row();
+    });
+}
+
+/// Watchdog conditions with their thresholds, fired state and re-arm buttons
+fn render_watchdog(ui: &mut egui::Ui, settings: &mut WatchdogSettings, watchdog: &mut Watchdog, metrics: &crate::simulation::DerivedMetrics) {
+    ui.label(egui::RichText::new("Watchdog").size(16.0).strong())
+        .on_hover_text("Checked once per simulated second of the CPU simulation");
+
+    egui::Grid::new("watchdog_conditions")
+        .num_columns(3)
+        .show(ui, |ui| {
+            for condition in WatchdogCondition::ALL {
+                match condition {
+                    WatchdogCondition::Collapse => {
+                        ui.checkbox(&mut settings.collapse_enabled, "Fewer cells than");
+                        ui.add(egui::DragValue::new(&mut settings.population_floor).range(1..=usize::MAX));
+                    }
+                    WatchdogCondition::Explosion => {
+                        ui.checkbox(&mut settings.explosion_enabled, "More cells than")
+                            .on_hover_text("Also fires within 5% of the cell capacity");
+                        ui.add(egui::DragValue::new(&mut settings.population_ceiling).speed(100.0).range(1..=usize::MAX));
+                    }
+                    WatchdogCondition::Stall => {
+                        ui.checkbox(&mut settings.stall_enabled, "No divisions for");
+                        ui.add(egui::DragValue::new(&mut settings.stall_seconds).speed(1.0).range(1.0..=86_400.0).suffix(" s"));
+                    }
+                    WatchdogCondition::MassOutOfBand => {
+                        ui.checkbox(&mut settings.mass_enabled, "Total mass outside");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut settings.mass_min).speed(1.0).range(0.0..=settings.mass_max));
+                            ui.label("to");
+                            ui.add(egui::DragValue::new(&mut settings.mass_max).speed(10.0).range(settings.mass_min..=f32::MAX));
+                        });
+                    }
+                    WatchdogCondition::MetricOutOfBand => {
+                        ui.checkbox(&mut settings.metric_enabled, "Metric outside")
+                            .on_hover_text("A derived metric from the Performance Monitor; NaN values never fire");
+                        ui.horizontal(|ui| {
+                            let selected = metrics.get(settings.metric).map_or("(invalid metric)", |metric| metric.name.as_str());
+                            egui::ComboBox::from_id_salt("watchdog_metric")
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    for metric in &metrics.metrics {
+                                        ui.selectable_value(&mut settings.metric, metric.id, metric.name.as_str());
+                                    }
+                                });
+                            ui.add(egui::DragValue::new(&mut settings.metric_min).speed(0.01).range(f32::MIN..=settings.metric_max));
+                            ui.label("to");
+                            ui.add(egui::DragValue::new(&mut settings.metric_max).speed(0.01).range(settings.metric_min..=f32::MAX));
+                        });
+                    }
+                }
+                if watchdog.is_tripped(condition) {
+                    if ui.small_button("Re-arm").on_hover_text("Fired; let it fire again").clicked() {
+                        watchdog.rearm(condition);
+                    }
+                } else {
+                    ui.label("");
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.pause, "Pause");
+        ui.checkbox(&mut settings.flash_window, "Flash window title");
+    });
+    if let Some(trigger) = &watchdog.last_trigger {
+        ui.label(egui::RichText::new(format!(
+            "Last: {} at {:.0} s ({})", trigger.condition.name(), trigger.time, trigger.reading
+        )).small().weak());
+    }
+}
+
+/// Experiment session controls: start (with a name), snapshot and end
+fn render_session(ui: &mut egui::Ui, session: &mut ExperimentSession) {
+    ui.label(egui::RichText::new("Session").size(16.0).strong());
+
+    if session.is_active() {
+        if let Some(record) = &session.record {
+            ui.label(format!("'{}' started {} UTC", record.name, record.started));
+            ui.label(egui::RichText::new(format!(
+                "{} snapshots, {} genome saves archived",
+                record.snapshots.len(), record.genome_saves.len()
+            )).weak());
+        }
+        ui.horizontal(|ui| {
+            if ui.button("📷 Snapshot")
+                .on_hover_text("Archive the scene, statistics and a screenshot of the current simulation")
+                .clicked()
+            {
+                session.snapshot();
+            }
+            if ui.button("End Session")
+                .on_hover_text("Write the session report")
+                .clicked()
+            {
+                session.end();
+            }
+        });
+    } else if session.is_starting() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Creating session folder...");
+        });
+    } else if let Some(name) = session.name_prompt.as_mut() {
+        let mut start = false;
+        let mut cancel = false;
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            let response = ui.text_edit_singleline(name);
+            start = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        });
+        ui.horizontal(|ui| {
+            start |= ui.add_enabled(!name.trim().is_empty(), egui::Button::new("Start")).clicked();
+            cancel = ui.button("Cancel").clicked();
+        });
+        if start && !name.trim().is_empty() {
+            let name = name.clone();
+            session.name_prompt = None;
+            session.start(name);
+        } else if cancel {
+            session.name_prompt = None;
+        }
+    } else {
+        if ui.button("Start Session")
+            .on_hover_text(format!(
+                "Archive genome saves, snapshots and a final report under {}/",
+                crate::simulation::experiment_session::SESSIONS_DIR
+            ))
+            .clicked()
+        {
+            session.name_prompt = Some(String::new());
+        }
+        if let Some(record) = &session.record {
+            ui.label(egui::RichText::new(format!("Last session: {} ({})", record.name, record.dir.display())).weak());
+        }
+    }
+
+    if session.pending_writes() > 0 {
+        ui.label(egui::RichText::new(format!("Writing {} archive files...", session.pending_writes())).small().weak());
+    }
+}
+
+/// Draw a small top-down thumbnail of a preset's initial layout, colored by mode
+fn draw_preset_thumbnail(ui: &mut egui::Ui, preset: &ScenarioPreset, size: f32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::click());
+    let painter = ui.painter_at(rect);
+    let center = rect.center();
+
+    painter.circle_filled(center, size * 0.5, egui::Color32::from_rgb(25, 30, 40));
+    painter.circle_stroke(center, size * 0.5 - 1.0, egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 80, 100)));
+
+    let scene = &preset.scene;
+    let mode_color = |mode_index: usize| {
+        let color = scene.genome.modes.get(mode_index).map(|m| m.color).unwrap_or(Vec3::ONE);
+        egui::Color32::from_rgb((color.x * 255.0) as u8, (color.y * 255.0) as u8, (color.z * 255.0) as u8)
+    };
+
+    if scene.initial_cells.is_empty() {
+        // Single founder: draw the genome's modes as a ring of swatches around it
+        let mode_count = scene.genome.modes.len().min(8);
+        for i in 0..mode_count {
+            let angle = i as f32 / mode_count as f32 * std::f32::consts::TAU;
+            let pos = center + egui::vec2(angle.cos(), angle.sin()) * size * 0.3;
+            painter.circle_filled(pos, size * 0.07, mode_color(i));
+        }
+        painter.circle_filled(center, size * 0.14, mode_color(scene.genome.initial_mode_index()));
+    } else {
+        // Project the layout onto the XZ plane, scaled to fit
+        let extent = scene.initial_cells.iter()
+            .map(|cell| cell.position.x.abs().max(cell.position.z.abs()) + cell.radius)
+            .fold(1.0f32, f32::max);
+        let scale = size * 0.4 / extent;
+        for cell in &scene.initial_cells {
+            let pos = center + egui::vec2(cell.position.x, cell.position.z) * scale;
+            painter.circle_filled(pos, (cell.radius * scale).max(1.5), mode_color(cell.mode_index));
+        }
+    }
+
+    response.on_hover_text("Click to load")
+}
+
+/// Confirmation for a genome or scene load that would discard the kept scenes
+pub fn render_discard_prompt(
+    mut contexts: Query<&mut bevy_egui::EguiContext, With<bevy_egui::PrimaryEguiContext>>,
+    mut dormant: ResMut<DormantScenes>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    mut current_genome: ResMut<crate::genome::CurrentGenome>,
+    mut scene_request: ResMut<SceneModeRequest>,
+    mut notifications: ResMut<crate::ui::Notifications>,
+) {
+    use crate::simulation::dormant_scenes::DeferredLoad;
+
+    let Some(load) = dormant.deferred_load.as_ref() else {
+        return;
+    };
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let (what, discards_active_cpu) = match load {
+        DeferredLoad::Genome(genome) => (format!("genome '{}'", genome.name), false),
+        DeferredLoad::Scene { title, .. } => (format!("scene '{}'", title), sim_state.mode == SimulationMode::Cpu),
+    };
+    let mut discarded = Vec::new();
+    for (mode, name) in [(SimulationMode::Preview, "Genome Editor"), (SimulationMode::Cpu, "CPU Mode")] {
+        if let Some(summary) = dormant.summary(mode) {
+            discarded.push(format!("{} scene ({} cells, t={:.1}s)", name, summary.cell_count, summary.time));
+        }
+    }
+    if discards_active_cpu {
+        discarded.push("the running CPU scene".to_string());
+    }
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    egui::Window::new("Discard Kept Scenes?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!("Loading the {} discards:", what));
+            for scene in &discarded {
+                ui.label(format!("• {}", scene));
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                confirmed = ui.button("Load").clicked();
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+
+    if cancelled {
+        dormant.deferred_load = None;
+    } else if confirmed {
+        dormant.clear();
+        match dormant.deferred_load.take() {
+            Some(DeferredLoad::Genome(genome)) => {
+                notifications.success(format!("Loaded genome '{}'", genome.name));
+                current_genome.replace(genome, 0, None);
+            }
+            Some(DeferredLoad::Scene { title, scene }) => {
+                // Loaded by load_requested_preset now that nothing is kept
+                scene_request.requested_scene = Some((title, scene));
+            }
+            None => {}
+        }
+    }
 }
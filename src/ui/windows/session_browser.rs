@@ -0,0 +1,92 @@
+use bevy_egui::egui;
+use std::sync::Arc;
+use crate::simulation::experiment_session::{ExperimentSession, SessionSnapshot, Thumbnail};
+
+/// Snapshots of the current (or last) experiment session with their metrics
+pub fn render(ui: &mut egui::Ui, session: &mut ExperimentSession, inspector: &mut crate::simulation::SceneInspector) {
+    let Some(record) = &session.record else {
+        ui.label("No session yet. Start one from the Scene Manager.");
+        return;
+    };
+
+    let state = if record.is_active() { "active" } else { "ended" };
+    ui.label(egui::RichText::new(format!("{} ({})", record.name, state)).strong());
+    ui.label(egui::RichText::new(record.dir.display().to_string()).small().weak());
+    ui.separator();
+
+    let mut load = None;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+        ui.set_width(ui.available_width());
+
+        if record.snapshots.is_empty() {
+            ui.label("No snapshots.");
+        }
+        for (index, snapshot) in record.snapshots.iter().enumerate().rev() {
+            ui.horizontal(|ui| {
+                draw_thumbnail(ui, snapshot);
+                ui.vertical(|ui| {
+                    render_metrics(ui, snapshot);
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Load")
+                            .on_hover_text("Load this snapshot into the Genome Editor preview")
+                            .clicked()
+                        {
+                            load = Some(index);
+                        }
+                        if ui.small_button("Inspect...")
+                            .on_hover_text("Summarize the snapshot's scene file without loading it")
+                            .clicked()
+                        {
+                            inspector.inspect(snapshot.scene_path());
+                        }
+                    });
+                });
+            });
+            ui.separator();
+        }
+    });
+
+    if let Some(index) = load {
+        session.load_snapshot(index);
+    }
+}
+
+fn render_metrics(ui: &mut egui::Ui, snapshot: &SessionSnapshot) {
+    let stats = &snapshot.stats;
+    ui.label(egui::RichText::new(format!("#{}  t = {:.2}s (tick {})", snapshot.number, stats.time, stats.tick)).strong());
+    ui.label(format!("{} cells, {} adhesions", stats.cell_count, stats.adhesion_count));
+    ui.label(format!("Mass {:.2}, mean radius {:.3}", stats.total_mass, stats.mean_radius));
+    let modes = stats.cells_per_mode.iter()
+        .map(|(mode, count)| format!("{} {}", mode, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    ui.label(egui::RichText::new(modes).small().weak());
+}
+
+/// Screenshot thumbnail, uploaded to egui once and cached in its temp data
+fn draw_thumbnail(ui: &mut egui::Ui, snapshot: &SessionSnapshot) {
+    let size = egui::vec2(96.0, 72.0);
+    let Some(thumbnail) = &snapshot.thumbnail else {
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        ui.painter().rect_filled(rect, 4.0, egui::Color32::from_rgb(25, 30, 40));
+        ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, "…", egui::FontId::proportional(16.0), egui::Color32::GRAY);
+        return;
+    };
+
+    let id = egui::Id::new(("session_thumbnail", &snapshot.dir));
+    let texture = ui.ctx().data_mut(|data| data.get_temp::<(Arc<Thumbnail>, egui::TextureHandle)>(id))
+        .filter(|(cached, _)| Arc::ptr_eq(cached, thumbnail))
+        .map(|(_, texture)| texture)
+        .unwrap_or_else(|| {
+            let image = egui::ColorImage::from_rgba_unmultiplied(thumbnail.size, &thumbnail.rgba);
+            let texture = ui.ctx().load_texture(format!("session_thumbnail_{}", snapshot.number), image, Default::default());
+            ui.ctx().data_mut(|data| data.insert_temp(id, (thumbnail.clone(), texture.clone())));
+            texture
+        });
+
+    let aspect = thumbnail.size[0] as f32 / thumbnail.size[1].max(1) as f32;
+    let size = egui::vec2(size.y * aspect, size.y);
+    ui.add(egui::Image::new((texture.id(), size)).corner_radius(4.0));
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::simulation::soak::{SoakHarness, SoakScene};
+use crate::ui::Notifications;
+
+/// Soak test window: runs the stress scenes and shows their pass/fail table
+///
+/// Opened from the Debug menu while Shift is held.
+pub fn render_soak_test(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut harness: ResMut<SoakHarness>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if !harness.window_open {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new("Soak Test")
+        .open(&mut open)
+        .default_width(620.0)
+        .show(egui_context.get_mut(), |ui| {
+            let harness = &mut *harness;
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!harness.is_running(), |ui| {
+                    ui.radio_value(&mut harness.quick, true, "Quick");
+                    ui.radio_value(&mut harness.quick, false, "Full");
+                });
+                let budget = harness.budget();
+                ui.label(egui::RichText::new(format!(
+                    "{} ticks, {} cells, {}³ lattice",
+                    budget.ticks, budget.capacity, budget.lattice_side
+                )).small().weak());
+            });
+            ui.horizontal(|ui| {
+                if let Some(job) = &harness.job {
+                    ui.spinner();
+                    ui.label(format!("Running scene {} of {}", harness.results.len() + 1, SoakScene::ALL.len()));
+                    if ui.button("Cancel").clicked() {
+                        job.cancel();
+                    }
+                } else if ui.button("Run").on_hover_text("Run every scene on a background thread").clicked() {
+                    harness.start();
+                }
+                let report = harness.report();
+                if ui.add_enabled(report.is_some(), egui::Button::new("Export JSON…")).clicked() {
+                    if let (Some(report), Some(path)) = (report, rfd::FileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .set_file_name("soak_report.json")
+                        .save_file())
+                    {
+                        match crate::error::write_json(&path, &report, "soak report") {
+                            Ok(()) => notifications.success(format!("Exported the soak report to {}", path.display())),
+                            Err(e) => notifications.error(&e),
+                        }
+                    }
+                }
+            });
+            if let Some(error) = &harness.golden_error {
+                ui.colored_label(egui::Color32::from_rgb(220, 170, 60), format!("Golden fingerprints not compared: {}", error));
+            }
+            ui.separator();
+
+            if harness.results.is_empty() {
+                ui.label("No results yet.");
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                egui::Grid::new("soak_results")
+                    .num_columns(7)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for heading in ["Scene", "Result", "Time", "Cells", "Bonds", "Peak memory", "Golden"] {
+                            ui.label(egui::RichText::new(heading).strong());
+                        }
+                        ui.end_row();
+
+                        for result in &harness.results {
+                            ui.label(result.scene.name());
+                            if result.passed {
+                                ui.colored_label(egui::Color32::from_rgb(90, 190, 110), "Pass");
+                            } else {
+                                ui.colored_label(egui::Color32::from_rgb(220, 80, 80), "Fail")
+                                    .on_hover_text(result.failures.join("\n"));
+                            }
+                            ui.label(format!("{:.2}s", result.seconds));
+                            ui.label(result.cell_count.to_string());
+                            ui.label(result.adhesion_count.to_string());
+                            ui.label(format!("{:.1} MB", result.peak_memory_bytes as f64 / (1024.0 * 1024.0)));
+                            ui.label(match result.golden_match {
+                                Some(true) => "Match",
+                                Some(false) => "Mismatch",
+                                None => "—",
+                            }).on_hover_text(format!("Fingerprint {}", result.fingerprint));
+                            ui.end_row();
+                        }
+                    });
+                for result in harness.results.iter().filter(|result| !result.passed) {
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new(result.scene.name()).strong());
+                    for failure in &result.failures {
+                        ui.label(egui::RichText::new(failure).small());
+                    }
+                }
+            });
+        });
+
+    if !open {
+        harness.window_open = false;
+    }
+}
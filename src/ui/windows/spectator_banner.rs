@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::simulation::spectator::SpectatorStatus;
+use crate::simulation::SpectatorClient;
+
+/// Banner showing which host a spectator is watching and whether the stream is live
+pub fn render_spectator_banner(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    client: Option<Res<SpectatorClient>>,
+) {
+    let Some(client) = client else {
+        return;
+    };
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    egui::Window::new("Spectating")
+        .collapsible(true)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+        .show(egui_context.get_mut(), |ui| {
+            match client.status() {
+                SpectatorStatus::Connecting => {
+                    ui.label(format!("Connecting to {}...", client.address));
+                }
+                SpectatorStatus::Connected => {
+                    let text = match client.tick() {
+                        Some(tick) => format!("Watching {} (tick {})", client.address, tick),
+                        None => format!("Connected to {}, waiting for the first keyframe...", client.address),
+                    };
+                    ui.label(text);
+                }
+                SpectatorStatus::Disconnected(reason) => {
+                    let warning = ui.visuals().warn_fg_color;
+                    ui.colored_label(warning, format!("Lost {}: {}. Reconnecting...", client.address, reason));
+                }
+            }
+            ui.label(egui::RichText::new(
+                "Read-only: the simulation runs on the host. Genome edits here are not sent back."
+            ).small().weak());
+        });
+}
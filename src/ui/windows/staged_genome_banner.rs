@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::genome::CurrentGenome;
+use crate::simulation::{GenomeStaging, SimulationMode, SimulationState};
+
+/// Most staged changes listed by name
+const MAX_LISTED_CHANGES: usize = 12;
+
+/// Banner offering to apply or discard genome edits staged for a long CPU run
+pub fn render_staged_genome_banner(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut staging: ResMut<GenomeStaging>,
+    mut current_genome: ResMut<CurrentGenome>,
+    sim_state: Res<SimulationState>,
+) {
+    if sim_state.mode != SimulationMode::Cpu || !staging.has_staged(&current_genome.genome) {
+        return;
+    }
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        return;
+    };
+
+    let changes = staging.changes(&current_genome.genome);
+    let mut discard = false;
+    egui::Window::new("Staged Genome Edits")
+        .collapsible(true)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(egui::RichText::new(format!("{} staged change(s)", changes.len())).strong());
+            ui.label(egui::RichText::new(format!(
+                "The CPU run is past {:.0} s, so genome edits are held back until applied.",
+                staging.protect_after
+            )).small().weak());
+
+            ui.collapsing("Changes", |ui| {
+                for change in changes.iter().take(MAX_LISTED_CHANGES) {
+                    let mode = change.mode
+                        .map(|index| current_genome.genome.modes.get(index)
+                            .map_or_else(|| format!("Mode {}", index), |mode| mode.name.clone()))
+                        .unwrap_or_else(|| "Genome".to_string());
+                    ui.label(format!("{}: {}", mode, change.field));
+                }
+                if changes.len() > MAX_LISTED_CHANGES {
+                    ui.label(egui::RichText::new(format!("and {} more", changes.len() - MAX_LISTED_CHANGES)).weak());
+                }
+            });
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Apply")
+                    .on_hover_text("Apply the edits at the start of the next tick")
+                    .clicked()
+                {
+                    staging.request_apply();
+                }
+                if ui.button("Discard")
+                    .on_hover_text("Return the editor to the genome the run is using")
+                    .clicked()
+                {
+                    discard = true;
+                }
+                if staging.waiting_for_lull() {
+                    if ui.button("Cancel lull")
+                        .on_hover_text("Stop waiting for a tick without divisions")
+                        .clicked()
+                    {
+                        staging.cancel_apply_at_lull();
+                    }
+                } else if ui.button("Apply at next division lull")
+                    .on_hover_text("Apply the edits at the first tick in which no cell divides")
+                    .clicked()
+                {
+                    staging.request_apply_at_lull();
+                }
+            });
+            if staging.waiting_for_lull() {
+                ui.label(egui::RichText::new("Waiting for a tick without divisions...").small().weak());
+            }
+        });
+
+    if discard {
+        staging.discard(&mut current_genome.genome);
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, PrimaryEguiContext};
+use crate::genome::CurrentGenome;
+use crate::input::{SelectedTool, StampToolState, Tool};
+use crate::simulation::tissue_stamp::TissueFile;
+use crate::ui::windows::cell_inspector::CellInspectorState;
+use crate::ui::Notifications;
+
+/// Export and import file dialogs of the Tools menu, and the mode mapping prompt for imports
+pub fn render_tissue_stamp_dialogs(
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+    mut tool_state: ResMut<StampToolState>,
+    mut selected_tool: ResMut<SelectedTool>,
+    mut current_genome: ResMut<CurrentGenome>,
+    mut notifications: ResMut<Notifications>,
+    inspector: Res<CellInspectorState>,
+    sim_state: Res<crate::simulation::SimulationState>,
+    main_state: Option<Res<crate::simulation::cpu_sim::MainSimState>>,
+    preview_state: Option<Res<crate::simulation::preview_sim::PreviewSimState>>,
+) {
+    if std::mem::take(&mut tool_state.export_requested) {
+        let state = crate::input::pin_tool::active_state(&sim_state, main_state.as_deref(), preview_state.as_deref(), None)
+            .map(|(state, _)| state);
+        export_inspected_organism(state, &inspector, &current_genome, &mut notifications);
+    }
+
+    if std::mem::take(&mut tool_state.import_requested) {
+        if let Some(path) = rfd::FileDialog::new().add_filter("Tissue", &["json"]).pick_file() {
+            match TissueFile::load_from_file(&path) {
+                Ok(tissue) => match tissue.same_genome_map(&current_genome.genome) {
+                    Some(map) => {
+                        notifications.success(format!("Loaded tissue '{}' ({} cells)", tissue.name, tissue.cells.len()));
+                        tool_state.load(tissue, map);
+                        selected_tool.tool = Tool::Stamp;
+                    }
+                    None => tool_state.pending_import = Some(tissue),
+                },
+                Err(e) => notifications.error(&e),
+            }
+        }
+    }
+
+    let Some(tissue) = tool_state.pending_import.take() else {
+        return;
+    };
+    let Some(mut egui_context) = contexts.iter_mut().next() else {
+        tool_state.pending_import = Some(tissue);
+        return;
+    };
+
+    let by_name = tissue.map_modes_by_name(&current_genome.genome);
+    let mut choice = None;
+    egui::Window::new("Import Tissue Stamp")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(egui_context.get_mut(), |ui| {
+            ui.label(format!(
+                "'{}' was exported with genome '{}', which differs from the current genome.",
+                tissue.name, tissue.genome.name
+            ));
+            ui.label(format!("It uses {} mode(s):", tissue.used_modes().len()));
+            for index in tissue.used_modes() {
+                ui.label(egui::RichText::new(format!("  {}", tissue.genome.modes[index].name)).small());
+            }
+            if let Err(missing) = &by_name {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 180, 60),
+                    format!("Not in the current genome: {}", missing.join(", ")),
+                );
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if ui.button("Import needed modes")
+                    .on_hover_text(format!("Append {} mode(s) and their child modes to the current genome", tissue.needed_modes().len()))
+                    .clicked()
+                {
+                    choice = Some(ImportChoice::ImportModes);
+                }
+                if ui.add_enabled(by_name.is_ok(), egui::Button::new("Map by name"))
+                    .on_hover_text("Use the current genome's modes of the same names")
+                    .clicked()
+                {
+                    choice = Some(ImportChoice::MapByName);
+                }
+                if ui.button("Cancel").clicked() {
+                    choice = Some(ImportChoice::Cancel);
+                }
+            });
+        });
+
+    let map = match choice {
+        None => {
+            tool_state.pending_import = Some(tissue);
+            return;
+        }
+        Some(ImportChoice::Cancel) => return,
+        Some(ImportChoice::ImportModes) => tissue.import_modes_into(&mut current_genome.genome),
+        Some(ImportChoice::MapByName) => match by_name {
+            Ok(map) => map,
+            Err(_) => return,
+        },
+    };
+    notifications.success(format!("Loaded tissue '{}' ({} cells)", tissue.name, tissue.cells.len()));
+    tool_state.load(tissue, map);
+    selected_tool.tool = Tool::Stamp;
+}
+
+enum ImportChoice {
+    ImportModes,
+    MapByName,
+    Cancel,
+}
+
+/// Save the organism of the cell shown in the Cell Inspector through a file dialog
+fn export_inspected_organism(
+    state: Option<&crate::simulation::CanonicalState>,
+    inspector: &CellInspectorState,
+    current_genome: &CurrentGenome,
+    notifications: &mut Notifications,
+) {
+    let index = state.zip(inspector.snapshot.as_ref()).and_then(|(state, inspected)| {
+        state.cell_ids[..state.cell_count].iter().position(|&id| id == inspected.cell_id)
+    });
+    let (Some(state), Some(index)) = (state, index) else {
+        notifications.warning("Select a cell of the organism to export in the Cell Inspector first");
+        return;
+    };
+
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Tissue", &["json"])
+        .set_file_name("tissue.json")
+        .save_file()
+    else {
+        return;
+    };
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let tissue = state.export_tissue(index, &current_genome.genome, name);
+    match tissue.save_to_file(&path) {
+        Ok(()) => notifications.success(format!(
+            "Exported {} cells and {} bonds to {}",
+            tissue.cells.len(), tissue.bonds.len(), path.display()
+        )),
+        Err(e) => notifications.error(&e),
+    }
+}